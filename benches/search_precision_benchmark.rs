@@ -75,6 +75,18 @@ fn bench_query_latency(c: &mut Criterion) {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                        rerank: None,
+                        geo: None,
+                        filters: Vec::new(),
+                        access_boost: false,
+                        highlight_windows: 0,
+                        language: None,
+                        diversify: None,
+                        group_by_parent: false,
+                        return_parents: false,
+                        explain: false,
+                        mode: memvid_core::types::SearchMode::Lexical,
+                        time_budget_ms: None,
                     })
                     .unwrap();
                 total += start.elapsed();
@@ -110,6 +122,18 @@ fn bench_precision(c: &mut Criterion) {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                        rerank: None,
+                        geo: None,
+                        filters: Vec::new(),
+                        access_boost: false,
+                        highlight_windows: 0,
+                        language: None,
+                        diversify: None,
+                        group_by_parent: false,
+                        return_parents: false,
+                        explain: false,
+                        mode: memvid_core::types::SearchMode::Lexical,
+                        time_budget_ms: None,
                     })
                     .unwrap();
 
@@ -155,6 +179,18 @@ fn bench_result_count(c: &mut Criterion) {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                        rerank: None,
+                        geo: None,
+                        filters: Vec::new(),
+                        access_boost: false,
+                        highlight_windows: 0,
+                        language: None,
+                        diversify: None,
+                        group_by_parent: false,
+                        return_parents: false,
+                        explain: false,
+                        mode: memvid_core::types::SearchMode::Lexical,
+                        time_budget_ms: None,
                     })
                     .unwrap();
                 let _count = results.hits.len();
@@ -55,6 +55,18 @@ fn main() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
     })?;
 
     println!("ACTUAL RESULTS: {} documents found", results.hits.len());
@@ -0,0 +1,97 @@
+#ifndef MEMVID_H
+#define MEMVID_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Version of the C ABI exposed by this module.
+#define MEMVID_ABI_VERSION 1
+
+// Status codes returned by every fallible `memvid_*` function.
+typedef enum MemvidStatus {
+  MEMVID_STATUS_OK = 0,
+  MEMVID_STATUS_NULL_ARGUMENT = 1,
+  MEMVID_STATUS_INVALID_UTF8 = 2,
+  MEMVID_STATUS_INVALID_ARGUMENT = 3,
+  MEMVID_STATUS_IO = 4,
+  MEMVID_STATUS_LOCKED = 5,
+  MEMVID_STATUS_CORRUPT = 6,
+  MEMVID_STATUS_NOT_FOUND = 7,
+  MEMVID_STATUS_FEATURE_DISABLED = 8,
+  MEMVID_STATUS_CAPACITY_EXCEEDED = 9,
+  MEMVID_STATUS_PANIC = 10,
+  MEMVID_STATUS_OTHER = 255,
+} MemvidStatus;
+
+// Opaque handle over an open `.mv2` file.
+typedef struct MemvidHandle MemvidHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// ABI version implemented by this library; compare against `MEMVID_ABI_VERSION` in the header.
+uint32_t memvid_abi_version(void);
+
+// Message for the last failed call on this thread, or null if it succeeded.
+//
+// The pointer stays valid until the next `memvid_*` call on the same thread.
+const char *memvid_last_error_message(void);
+
+// Create a new `.mv2` file and return a handle in `*out`.
+MemvidStatus memvid_create(const char *path, MemvidHandle **out);
+
+// Open an existing `.mv2` file for reading and writing.
+MemvidStatus memvid_open(const char *path, MemvidHandle **out);
+
+// Open an existing `.mv2` file with a shared (read-only) lock.
+MemvidStatus memvid_open_read_only(const char *path, MemvidHandle **out);
+
+// Close a handle, releasing the file lock. Null is ignored.
+void memvid_close(MemvidHandle *handle);
+
+// Enable the lexical index so `memvid_search_json` can be used.
+MemvidStatus memvid_enable_lex(MemvidHandle *handle);
+
+// Append `len` bytes at `data` as a new frame and write its WAL sequence to `*out_sequence`.
+//
+// `options_json` is an optional `PutOptions` object (may be null). Call `memvid_commit` to make
+// the frame durable and searchable.
+MemvidStatus memvid_put_bytes(MemvidHandle *handle,
+                              const uint8_t *data,
+                              size_t len,
+                              const char *options_json,
+                              uint64_t *out_sequence);
+
+// Persist pending frames.
+MemvidStatus memvid_commit(MemvidHandle *handle);
+
+// Run a `SearchRequest` (JSON) and write the `SearchResponse` JSON to `*out_json`.
+MemvidStatus memvid_search_json(MemvidHandle *handle, const char *request_json, char **out_json);
+
+// Run an `AskRequest` (JSON) and write the `AskResponse` JSON to `*out_json`.
+MemvidStatus memvid_ask_json(MemvidHandle *handle, const char *request_json, char **out_json);
+
+// Run a `TimelineQuery` (JSON, or null for the default query) and write the entries as a JSON
+// array to `*out_json`.
+MemvidStatus memvid_timeline_json(MemvidHandle *handle, const char *query_json, char **out_json);
+
+// Write the memory's `Stats` JSON to `*out_json`.
+MemvidStatus memvid_stats_json(MemvidHandle *handle, char **out_json);
+
+// Write the searchable text of `frame_id` to `*out_text` (free with `memvid_string_free`).
+MemvidStatus memvid_frame_text(MemvidHandle *handle, uint64_t frame_id, char **out_text);
+
+// Free a string returned through an `out_json`/`out_text` parameter. Null is ignored.
+void memvid_string_free(char *value);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MEMVID_H */
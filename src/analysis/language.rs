@@ -0,0 +1,289 @@
+//! Lightweight language detection for ingest-time analyzer selection.
+//!
+//! Scripts with their own writing system (CJK, Hangul, Cyrillic, Greek, Arabic) are decided by
+//! character counts; Latin-script text is scored against short stopword lists. Detection only
+//! needs to be good enough to pick a tokenizer, so short or ambiguous text yields `None` and is
+//! indexed with the default analyzer.
+
+/// Extra-metadata key holding a frame's ISO 639-1 language code.
+pub const LANGUAGE_KEY: &str = "language";
+
+/// Characters sampled from the start of a document.
+const SAMPLE_CHARS: usize = 4096;
+/// Stopword hits the best Latin-script candidate needs before it is trusted.
+const MIN_STOPWORD_HITS: usize = 3;
+
+/// ISO 639-1 codes the lexical index has a dedicated analyzer for.
+pub const SUPPORTED_LANGUAGES: &[&str] = &[
+    "ar", "da", "de", "el", "en", "es", "fi", "fr", "hu", "it", "ja", "ko", "nl", "no", "pt", "ro",
+    "ru", "sv", "ta", "tr", "zh",
+];
+
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "of", "to", "is", "in", "that", "it", "with", "for", "was", "this",
+            "are", "on", "be",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "mit", "ein", "eine", "den", "von", "zu",
+            "auf", "sich", "auch",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "des", "une", "dans", "pour", "pas", "que", "qui",
+            "sur", "avec", "du",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "es", "que", "del", "una", "por", "con", "para", "como",
+            "pero", "su", "está",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "gli", "e", "è", "che", "di", "della", "una", "per", "non", "con", "sono", "nel",
+            "anche", "questo",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "as", "e", "é", "que", "do", "da", "uma", "para", "com", "não", "em",
+            "mais", "está",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "van", "niet", "dat", "op", "met", "voor", "zijn",
+            "ook", "maar", "wordt",
+        ],
+    ),
+    (
+        "sv",
+        &[
+            "och", "är", "att", "det", "som", "en", "på", "för", "med", "inte", "av", "till",
+            "den", "har", "jag",
+        ],
+    ),
+    (
+        "da",
+        &[
+            "og", "er", "at", "det", "som", "en", "på", "for", "med", "ikke", "af", "til", "den",
+            "har", "jeg",
+        ],
+    ),
+    (
+        "no",
+        &[
+            "og", "er", "å", "det", "som", "en", "på", "for", "med", "ikke", "av", "til", "den",
+            "har", "jeg",
+        ],
+    ),
+    (
+        "fi",
+        &[
+            "ja", "on", "ei", "se", "että", "oli", "mutta", "kun", "niin", "hän", "ovat", "tämä",
+            "myös", "joka", "voi",
+        ],
+    ),
+    (
+        "tr",
+        &[
+            "ve", "bir", "bu", "da", "de", "için", "ile", "çok", "ama", "gibi", "daha", "olarak",
+            "değil", "ne", "var",
+        ],
+    ),
+    (
+        "hu",
+        &[
+            "a", "az", "és", "hogy", "nem", "egy", "is", "van", "meg", "de", "már", "csak", "mint",
+            "vagy", "volt",
+        ],
+    ),
+    (
+        "ro",
+        &[
+            "și", "în", "este", "la", "nu", "cu", "pe", "care", "un", "o", "mai", "din", "pentru",
+            "sunt", "fost",
+        ],
+    ),
+];
+
+/// Best-guess ISO 639-1 code for `text`, or `None` when it is too short or too mixed to tell.
+#[must_use]
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let sample: String = text.chars().take(SAMPLE_CHARS).collect();
+    let mut letters = 0usize;
+    let (mut han, mut kana, mut hangul, mut cyrillic, mut greek, mut arabic, mut tamil) =
+        (0usize, 0usize, 0usize, 0usize, 0usize, 0usize, 0usize);
+    for c in sample.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        match u32::from(c) {
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9D => kana += 1,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => han += 1,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => hangul += 1,
+            0x0400..=0x04FF => cyrillic += 1,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => greek += 1,
+            0x0600..=0x06FF | 0x0750..=0x077F => arabic += 1,
+            0x0B80..=0x0BFF => tamil += 1,
+            _ => {}
+        }
+    }
+    if letters == 0 {
+        return None;
+    }
+    // Japanese mixes kanji with kana; any meaningful share of kana settles it
+    if kana * 10 >= letters {
+        return Some("ja");
+    }
+    let dominant = [
+        (hangul, "ko"),
+        (han, "zh"),
+        (cyrillic, "ru"),
+        (greek, "el"),
+        (arabic, "ar"),
+        (tamil, "ta"),
+    ]
+    .into_iter()
+    .max_by_key(|(count, _)| *count)?;
+    if dominant.0 * 2 >= letters {
+        return Some(dominant.1);
+    }
+    detect_latin(&sample)
+}
+
+fn detect_latin(sample: &str) -> Option<&'static str> {
+    let words: Vec<String> = sample
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut scores: Vec<(usize, &'static str)> = STOPWORDS
+        .iter()
+        .map(|(code, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (hits, *code)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.0.cmp(&a.0));
+    let (best, code) = scores[0];
+    let runner_up = scores[1].0;
+    // Closely related languages share stopwords; require a clear lead
+    (best >= MIN_STOPWORD_HITS && best * 4 >= runner_up * 5).then_some(code)
+}
+
+/// Whether `word` (lowercase) is a stopword in any language the detector knows.
+pub(crate) fn is_common_stopword(word: &str) -> bool {
+    STOPWORDS
+        .iter()
+        .any(|(_, stopwords)| stopwords.contains(&word))
+}
+
+/// Canonical ISO 639-1 code for a language hint such as `"de"`, `"DE"`, `"de-AT"`, or
+/// `"german"`, or `None` when no analyzer covers it.
+#[must_use]
+pub fn normalize_language(hint: &str) -> Option<&'static str> {
+    let lower = hint.trim().to_ascii_lowercase();
+    let primary = lower.split(['-', '_']).next().unwrap_or_default();
+    let code = match primary {
+        "arabic" => "ar",
+        "danish" => "da",
+        "german" | "deutsch" => "de",
+        "greek" => "el",
+        "english" => "en",
+        "spanish" | "español" => "es",
+        "finnish" => "fi",
+        "french" | "français" => "fr",
+        "hungarian" => "hu",
+        "italian" => "it",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "dutch" => "nl",
+        "norwegian" | "nb" | "nn" => "no",
+        "portuguese" => "pt",
+        "romanian" => "ro",
+        "russian" => "ru",
+        "swedish" => "sv",
+        "tamil" => "ta",
+        "turkish" => "tr",
+        "chinese" => "zh",
+        other => other,
+    };
+    SUPPORTED_LANGUAGES
+        .iter()
+        .copied()
+        .find(|supported| *supported == code)
+}
+
+/// Whether `c` belongs to a script written without spaces between words.
+#[cfg(feature = "lex")]
+#[must_use]
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        u32::from(c),
+        0x3040..=0x30FF
+            | 0x31F0..=0x31FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xF900..=0xFAFF
+            | 0xFF66..=0xFF9D
+            | 0x1100..=0x11FF
+            | 0x3130..=0x318F
+            | 0xAC00..=0xD7AF
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_scripts_and_latin_languages() {
+        assert_eq!(
+            detect_language(
+                "Die Häuser in der Altstadt sind nicht mehr bewohnt, und das ist schade."
+            ),
+            Some("de")
+        );
+        assert_eq!(
+            detect_language(
+                "The houses in the old town are empty and it is a shame that this happened."
+            ),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language(
+                "Les maisons de la vieille ville sont vides et c'est dommage pour les habitants."
+            ),
+            Some("fr")
+        );
+        assert_eq!(detect_language("東京タワーへ行きました。"), Some("ja"));
+        assert_eq!(detect_language("我们明天去北京参观故宫。"), Some("zh"));
+        assert_eq!(detect_language("서울에서 친구를 만났어요."), Some("ko"));
+        assert_eq!(detect_language("Москва — столица России."), Some("ru"));
+        assert_eq!(detect_language("invoice 4711"), None);
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn normalizes_hints() {
+        assert_eq!(normalize_language("DE"), Some("de"));
+        assert_eq!(normalize_language("pt-BR"), Some("pt"));
+        assert_eq!(normalize_language("Japanese"), Some("ja"));
+        assert_eq!(normalize_language("klingon"), None);
+    }
+}
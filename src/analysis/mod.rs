@@ -1,6 +1,8 @@
 pub mod auto_tag;
+pub mod language;
 pub mod ner;
 #[cfg(feature = "temporal_track")]
 pub mod temporal;
 #[cfg(feature = "temporal_enrich")]
 pub mod temporal_enrich;
+pub mod timezone;
@@ -5,6 +5,7 @@ use regex::Regex;
 use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, Weekday};
 
 use crate::error::{MemvidError, Result};
+use crate::types::{RecurrenceFrequency, TemporalRecurrence};
 
 const DEFAULT_CONFIDENCE: u16 = 950;
 const AMBIGUOUS_CONFIDENCE: u16 = 700;
@@ -41,6 +42,11 @@ pub enum TemporalResolutionValue {
         year: i32,
         month: Month,
     },
+    /// A repeating event such as "every Monday" or "quarterly", first occurring on `start`.
+    Recurring {
+        start: Date,
+        rule: TemporalRecurrence,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -102,6 +108,9 @@ impl TemporalNormalizer {
         if let Some(resolution) = self.resolve_fixed(&lower) {
             return Ok(resolution);
         }
+        if let Some(resolution) = self.resolve_recurring(&lower) {
+            return Ok(resolution);
+        }
         if let Some(resolution) = self.resolve_relative_days(&lower) {
             return Ok(resolution);
         }
@@ -386,6 +395,87 @@ impl TemporalNormalizer {
         Some(self.year_range(year))
     }
 
+    fn resolve_recurring(&self, phrase: &str) -> Option<TemporalResolution> {
+        static EVERY_PERIOD: OnceCell<Regex> = OnceCell::new();
+
+        let (frequency, interval) = match phrase {
+            "daily" => (RecurrenceFrequency::Daily, 1),
+            "weekly" => (RecurrenceFrequency::Weekly, 1),
+            "biweekly" | "fortnightly" => (RecurrenceFrequency::Weekly, 2),
+            "monthly" => (RecurrenceFrequency::Monthly, 1),
+            "quarterly" => (RecurrenceFrequency::Monthly, 3),
+            "yearly" | "annually" => (RecurrenceFrequency::Yearly, 1),
+            _ => {
+                let rest = phrase.strip_prefix("every ")?;
+                if let Some(caps) = EVERY_PERIOD
+                    .get_or_init(|| {
+                        Regex::new(
+                            r"^(?:(?P<count>[[:word:]]+) )?(?P<unit>day|week|month|quarter|year)s?$",
+                        )
+                        .expect("valid recurrence regex")
+                    })
+                    .captures(rest)
+                {
+                    let count = match caps.name("count").map(|m| m.as_str()) {
+                        None => 1,
+                        Some("other") => 2,
+                        Some(token) => parse_number(token)?,
+                    };
+                    let (frequency, months) = match caps.name("unit")?.as_str() {
+                        "day" => (RecurrenceFrequency::Daily, 1),
+                        "week" => (RecurrenceFrequency::Weekly, 1),
+                        "month" => (RecurrenceFrequency::Monthly, 1),
+                        "quarter" => (RecurrenceFrequency::Monthly, 3),
+                        _ => (RecurrenceFrequency::Yearly, 1),
+                    };
+                    (frequency, u8::try_from(count * months).ok()?)
+                } else {
+                    return self.recurring_weekdays(rest);
+                }
+            }
+        };
+        let rule = TemporalRecurrence::new(frequency, interval);
+        Some(self.recurring_resolution(self.anchor_date(), rule))
+    }
+
+    /// "every monday and thursday", "every other friday", "every weekday".
+    fn recurring_weekdays(&self, days: &str) -> Option<TemporalResolution> {
+        let (interval, days) = match days.strip_prefix("other ") {
+            Some(rest) => (2, rest),
+            None => (1, days),
+        };
+        let mut mask = 0u8;
+        for token in days
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty() && *token != "and")
+        {
+            if matches!(token, "weekday" | "weekdays") {
+                mask |= 0b0001_1111;
+                continue;
+            }
+            let weekday =
+                parse_weekday(token).or_else(|| parse_weekday(token.strip_suffix('s')?))?;
+            mask |= 1 << weekday.number_days_from_monday();
+        }
+        if mask == 0 {
+            return None;
+        }
+        let mut start = self.anchor_date();
+        while mask & (1 << start.weekday().number_days_from_monday()) == 0 {
+            start = add_days(start, 1);
+        }
+        let rule = TemporalRecurrence::new(RecurrenceFrequency::Weekly, interval).on_weekdays(mask);
+        Some(self.recurring_resolution(start, rule))
+    }
+
+    fn recurring_resolution(&self, start: Date, rule: TemporalRecurrence) -> TemporalResolution {
+        TemporalResolution {
+            value: TemporalResolutionValue::Recurring { start, rule },
+            flags: vec![TemporalResolutionFlag::Relative],
+            confidence: RELATIVE_CONFIDENCE,
+        }
+    }
+
     fn relative_days(&self, delta: i64, confidence: u16) -> Option<TemporalResolution> {
         let date = add_days(self.anchor_date(), delta);
         let mut res = self.date_resolution(date);
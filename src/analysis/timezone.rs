@@ -0,0 +1,243 @@
+//! Time zone offsets for temporal anchoring, without a time zone database.
+//!
+//! Accepts `UTC`/`Z`, fixed offsets (`+02:00`, `-0530`, `+09`), and the IANA names of common
+//! zones. Named zones apply the current daylight-saving rules of their region (North America,
+//! the EU, south-eastern Australia, New Zealand); other zones keep their standard offset.
+
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday};
+
+use crate::error::{MemvidError, Result};
+
+/// `extra_metadata` key holding the time zone a frame's relative dates are resolved in.
+pub const TEMPORAL_TZ_KEY: &str = "temporal_tz";
+/// TOC extension holding the file's default time zone for temporal anchoring.
+pub const TEMPORAL_TZ_EXTENSION: &str = "memvid.temporal_tz";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DstRule {
+    None,
+    /// Second Sunday of March to first Sunday of November, 02:00 local.
+    NorthAmerica,
+    /// Last Sunday of March to last Sunday of October, 01:00 UTC.
+    Europe,
+    /// First Sunday of October to first Sunday of April, 02:00 standard time.
+    SouthEastAustralia,
+    /// Last Sunday of September to first Sunday of April, 02:00 standard time.
+    NewZealand,
+}
+
+/// Named zones: standard offset in minutes and daylight-saving rule.
+const ZONES: &[(&str, i32, DstRule)] = &[
+    ("UTC", 0, DstRule::None),
+    ("Etc/UTC", 0, DstRule::None),
+    ("GMT", 0, DstRule::None),
+    ("America/New_York", -300, DstRule::NorthAmerica),
+    ("America/Toronto", -300, DstRule::NorthAmerica),
+    ("America/Chicago", -360, DstRule::NorthAmerica),
+    ("America/Denver", -420, DstRule::NorthAmerica),
+    ("America/Phoenix", -420, DstRule::None),
+    ("America/Los_Angeles", -480, DstRule::NorthAmerica),
+    ("America/Vancouver", -480, DstRule::NorthAmerica),
+    ("America/Anchorage", -540, DstRule::NorthAmerica),
+    ("Pacific/Honolulu", -600, DstRule::None),
+    ("America/Mexico_City", -360, DstRule::None),
+    ("America/Bogota", -300, DstRule::None),
+    ("America/Sao_Paulo", -180, DstRule::None),
+    ("America/Argentina/Buenos_Aires", -180, DstRule::None),
+    ("Europe/London", 0, DstRule::Europe),
+    ("Europe/Dublin", 0, DstRule::Europe),
+    ("Europe/Lisbon", 0, DstRule::Europe),
+    ("Europe/Paris", 60, DstRule::Europe),
+    ("Europe/Berlin", 60, DstRule::Europe),
+    ("Europe/Madrid", 60, DstRule::Europe),
+    ("Europe/Rome", 60, DstRule::Europe),
+    ("Europe/Amsterdam", 60, DstRule::Europe),
+    ("Europe/Brussels", 60, DstRule::Europe),
+    ("Europe/Vienna", 60, DstRule::Europe),
+    ("Europe/Zurich", 60, DstRule::Europe),
+    ("Europe/Stockholm", 60, DstRule::Europe),
+    ("Europe/Oslo", 60, DstRule::Europe),
+    ("Europe/Copenhagen", 60, DstRule::Europe),
+    ("Europe/Warsaw", 60, DstRule::Europe),
+    ("Europe/Prague", 60, DstRule::Europe),
+    ("Europe/Athens", 120, DstRule::Europe),
+    ("Europe/Helsinki", 120, DstRule::Europe),
+    ("Europe/Kyiv", 120, DstRule::Europe),
+    ("Europe/Bucharest", 120, DstRule::Europe),
+    ("Europe/Istanbul", 180, DstRule::None),
+    ("Europe/Moscow", 180, DstRule::None),
+    ("Africa/Lagos", 60, DstRule::None),
+    ("Africa/Johannesburg", 120, DstRule::None),
+    ("Africa/Nairobi", 180, DstRule::None),
+    ("Asia/Dubai", 240, DstRule::None),
+    ("Asia/Karachi", 300, DstRule::None),
+    ("Asia/Kolkata", 330, DstRule::None),
+    ("Asia/Dhaka", 360, DstRule::None),
+    ("Asia/Bangkok", 420, DstRule::None),
+    ("Asia/Jakarta", 420, DstRule::None),
+    ("Asia/Singapore", 480, DstRule::None),
+    ("Asia/Hong_Kong", 480, DstRule::None),
+    ("Asia/Shanghai", 480, DstRule::None),
+    ("Asia/Taipei", 480, DstRule::None),
+    ("Asia/Seoul", 540, DstRule::None),
+    ("Asia/Tokyo", 540, DstRule::None),
+    ("Australia/Perth", 480, DstRule::None),
+    ("Australia/Brisbane", 600, DstRule::None),
+    ("Australia/Adelaide", 570, DstRule::SouthEastAustralia),
+    ("Australia/Sydney", 600, DstRule::SouthEastAustralia),
+    ("Australia/Melbourne", 600, DstRule::SouthEastAustralia),
+    ("Pacific/Auckland", 720, DstRule::NewZealand),
+];
+
+/// UTC offset of the zone `spec` at the instant `at`.
+pub fn timezone_offset(spec: &str, at: OffsetDateTime) -> Result<UtcOffset> {
+    let trimmed = spec.trim();
+    if trimmed.eq_ignore_ascii_case("utc") || trimmed.eq_ignore_ascii_case("z") {
+        return Ok(UtcOffset::UTC);
+    }
+    if let Some(offset) = parse_fixed_offset(trimmed) {
+        return offset;
+    }
+    let Some(&(_, standard, rule)) = ZONES
+        .iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(trimmed))
+    else {
+        return Err(MemvidError::InvalidQuery {
+            reason: format!("unsupported timezone specifier: {spec}"),
+        });
+    };
+    let minutes = if observes_dst(rule, standard, at.unix_timestamp()) {
+        standard + 60
+    } else {
+        standard
+    };
+    offset_from_minutes(minutes, spec)
+}
+
+/// Whether `spec` names a zone [`timezone_offset`] understands.
+#[must_use]
+pub fn is_supported_timezone(spec: &str) -> bool {
+    timezone_offset(spec, OffsetDateTime::UNIX_EPOCH).is_ok()
+}
+
+fn parse_fixed_offset(value: &str) -> Option<Result<UtcOffset>> {
+    let sign = match value.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits: String = value[1..].chars().filter(char::is_ascii_digit).collect();
+    if digits.len() != 2 && digits.len() != 4 {
+        return Some(Err(MemvidError::InvalidQuery {
+            reason: format!("invalid timezone offset: {value}"),
+        }));
+    }
+    let hours: i32 = digits[0..2].parse().ok()?;
+    let minutes: i32 = digits.get(2..4).map_or(Some(0), |m| m.parse().ok())?;
+    Some(offset_from_minutes(sign * (hours * 60 + minutes), value))
+}
+
+fn offset_from_minutes(minutes: i32, spec: &str) -> Result<UtcOffset> {
+    UtcOffset::from_whole_seconds(minutes * 60).map_err(|_| MemvidError::InvalidQuery {
+        reason: format!("invalid timezone offset: {spec}"),
+    })
+}
+
+fn observes_dst(rule: DstRule, standard_minutes: i32, timestamp: i64) -> bool {
+    let Ok(at) = OffsetDateTime::from_unix_timestamp(timestamp) else {
+        return false;
+    };
+    let year = at.year();
+    let standard = i64::from(standard_minutes) * 60;
+    // Transition instants in UTC: local wall-clock time minus the offset in force before it
+    let local = |date: Option<Date>, hour: u8, offset: i64| {
+        date.map(|date| {
+            PrimitiveDateTime::new(date, Time::from_hms(hour, 0, 0).unwrap_or(Time::MIDNIGHT))
+                .assume_utc()
+                .unix_timestamp()
+                - offset
+        })
+    };
+    let window = match rule {
+        DstRule::None => return false,
+        DstRule::NorthAmerica => (
+            local(nth_weekday(year, Month::March, 2), 2, standard),
+            local(nth_weekday(year, Month::November, 1), 2, standard + 3600),
+        ),
+        DstRule::Europe => (
+            local(last_weekday(year, Month::March), 1, 0),
+            local(last_weekday(year, Month::October), 1, 0),
+        ),
+        DstRule::SouthEastAustralia => (
+            local(nth_weekday(year, Month::October, 1), 2, standard),
+            local(nth_weekday(year, Month::April, 1), 3, standard + 3600),
+        ),
+        DstRule::NewZealand => (
+            local(last_weekday(year, Month::September), 2, standard),
+            local(nth_weekday(year, Month::April, 1), 3, standard + 3600),
+        ),
+    };
+    let (Some(start), Some(end)) = window else {
+        return false;
+    };
+    if start < end {
+        (start..end).contains(&timestamp)
+    } else {
+        // Southern hemisphere: daylight time spans the new year
+        timestamp >= start || timestamp < end
+    }
+}
+
+/// The `n`th Sunday of `month`.
+fn nth_weekday(year: i32, month: Month, n: u8) -> Option<Date> {
+    let first = Date::from_calendar_date(year, month, 1).ok()?;
+    let offset = (7 + 6 - first.weekday().number_days_from_monday()) % 7;
+    Date::from_calendar_date(year, month, 1 + offset + 7 * (n - 1)).ok()
+}
+
+/// The last Sunday of `month`.
+fn last_weekday(year: i32, month: Month) -> Option<Date> {
+    let mut date = (28..=31)
+        .rev()
+        .find_map(|day| Date::from_calendar_date(year, month, day).ok())?;
+    while date.weekday() != Weekday::Sunday {
+        date = date.previous_day()?;
+    }
+    Some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offset_minutes(spec: &str, timestamp: i64) -> i16 {
+        let at = OffsetDateTime::from_unix_timestamp(timestamp).expect("timestamp");
+        timezone_offset(spec, at).expect("offset").whole_minutes()
+    }
+
+    #[test]
+    fn named_zones_follow_daylight_saving() {
+        // 2024-01-15 and 2024-07-15, 12:00 UTC
+        let (winter, summer) = (1_705_320_000, 1_721_044_800);
+        assert_eq!(offset_minutes("Europe/Berlin", winter), 60);
+        assert_eq!(offset_minutes("Europe/Berlin", summer), 120);
+        assert_eq!(offset_minutes("America/Chicago", winter), -360);
+        assert_eq!(offset_minutes("America/Chicago", summer), -300);
+        assert_eq!(offset_minutes("Australia/Sydney", winter), 660);
+        assert_eq!(offset_minutes("Australia/Sydney", summer), 600);
+        assert_eq!(offset_minutes("Asia/Tokyo", summer), 540);
+
+        // US clocks change at 2024-03-10 08:00 UTC (02:00 CST)
+        assert_eq!(offset_minutes("America/Chicago", 1_710_057_599), -360);
+        assert_eq!(offset_minutes("America/Chicago", 1_710_057_600), -300);
+    }
+
+    #[test]
+    fn fixed_offsets_and_unknown_zones() {
+        assert_eq!(offset_minutes("+05:30", 0), 330);
+        assert_eq!(offset_minutes("-0800", 0), -480);
+        assert_eq!(offset_minutes("utc", 0), 0);
+        assert!(!is_supported_timezone("Mars/Olympus_Mons"));
+        assert!(!is_supported_timezone("+5"));
+    }
+}
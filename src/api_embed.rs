@@ -1,8 +1,13 @@
-//! API-based embedding providers (OpenAI, Anthropic, etc.)
+//! API-based embedding providers (OpenAI, Cohere, Voyage, Ollama, etc.)
 //!
 //! This module provides cloud API embedding generation, enabling semantic search
 //! using external embedding services. Requires the `api_embed` feature.
 //!
+//! [`OpenAIEmbedder`] talks to OpenAI; [`ApiEmbedder`] covers Cohere, Voyage, Ollama, and any
+//! other OpenAI-compatible `/embeddings` endpoint. Each reports its own provider name, model,
+//! and dimension, so frames it embeds carry an [`EmbeddingIdentity`] that keeps vectors from
+//! different providers apart.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -16,13 +21,21 @@
 //! let embedding = embedder.embed_text("Hello, world!")?;
 //! println!("Embedding dimension: {}", embedding.len());
 //! ```
+//!
+//! [`EmbeddingIdentity`]: crate::types::EmbeddingIdentity
 
 use crate::error::{MemvidError, Result};
-use crate::types::embedding::EmbeddingProvider;
+use crate::types::VecEmbedder;
+use crate::types::embedding::{EmbedderRequestStats, EmbeddingProvider, EmbeddingProviderKind};
+use reqwest::StatusCode;
 use reqwest::blocking::Client;
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, RETRY_AFTER};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // OpenAI Models Registry
@@ -99,10 +112,20 @@ pub struct OpenAIConfig {
     pub base_url: String,
     /// Request timeout in seconds
     pub timeout_secs: u64,
-    /// Maximum retries on rate limit (429) errors
+    /// Maximum retries on rate limit (429), server (5xx), and network errors
     pub max_retries: u32,
     /// Initial backoff in milliseconds for exponential retry
     pub initial_backoff_ms: u64,
+    /// Upper bound on a single backoff, including one asked for by `Retry-After`
+    pub max_backoff_ms: u64,
+    /// Texts per request; capped at the model's `max_batch_size`
+    pub batch_size: usize,
+    /// Requests in flight at once across every thread sharing the embedder
+    pub max_concurrent_requests: usize,
+    /// Consecutive failed calls that open the circuit breaker; 0 disables it
+    pub circuit_breaker_threshold: u32,
+    /// How long an open circuit breaker refuses calls, in seconds
+    pub circuit_breaker_cooldown_secs: u64,
 }
 
 impl Default for OpenAIConfig {
@@ -114,6 +137,11 @@ impl Default for OpenAIConfig {
             timeout_secs: 30,
             max_retries: 3,
             initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+            batch_size: 256,
+            max_concurrent_requests: 4,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 60,
         }
     }
 }
@@ -163,6 +191,368 @@ impl OpenAIConfig {
         self.timeout_secs = secs;
         self
     }
+
+    /// Set how many texts are sent per request
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set retry count and backoff bounds for rate limits and transient errors
+    #[must_use]
+    pub fn with_retries(
+        mut self,
+        max_retries: u32,
+        initial_backoff_ms: u64,
+        max_backoff_ms: u64,
+    ) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff_ms = initial_backoff_ms;
+        self.max_backoff_ms = max_backoff_ms;
+        self
+    }
+
+    /// Set how many requests may be in flight at once
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = limit;
+        self
+    }
+
+    /// Open the circuit breaker after `threshold` consecutive failed calls, refusing calls
+    /// for `cooldown_secs`; a threshold of 0 disables it
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, threshold: u32, cooldown_secs: u64) -> Self {
+        self.circuit_breaker_threshold = threshold;
+        self.circuit_breaker_cooldown_secs = cooldown_secs;
+        self
+    }
+
+    fn policy(&self) -> RequestPolicy {
+        RequestPolicy {
+            max_retries: self.max_retries,
+            initial_backoff_ms: self.initial_backoff_ms,
+            max_backoff_ms: self.max_backoff_ms,
+            max_concurrent_requests: self.max_concurrent_requests,
+            circuit_breaker_threshold: self.circuit_breaker_threshold,
+            circuit_breaker_cooldown_secs: self.circuit_breaker_cooldown_secs,
+        }
+    }
+}
+
+// ============================================================================
+// Other Providers
+// ============================================================================
+
+/// Embedding model offered by a non-OpenAI provider
+#[derive(Debug, Clone)]
+pub struct ApiModelInfo {
+    /// Model identifier (e.g., "embed-english-v3.0")
+    pub name: &'static str,
+    /// Output embedding dimension
+    pub dimension: usize,
+    /// Maximum texts per batch request
+    pub max_batch_size: usize,
+}
+
+/// Cohere embedding models (first entry is the default)
+pub static COHERE_MODELS: &[ApiModelInfo] = &[
+    ApiModelInfo {
+        name: "embed-english-v3.0",
+        dimension: 1024,
+        max_batch_size: 96,
+    },
+    ApiModelInfo {
+        name: "embed-multilingual-v3.0",
+        dimension: 1024,
+        max_batch_size: 96,
+    },
+    ApiModelInfo {
+        name: "embed-english-light-v3.0",
+        dimension: 384,
+        max_batch_size: 96,
+    },
+    ApiModelInfo {
+        name: "embed-multilingual-light-v3.0",
+        dimension: 384,
+        max_batch_size: 96,
+    },
+    ApiModelInfo {
+        name: "embed-v4.0",
+        dimension: 1536,
+        max_batch_size: 96,
+    },
+];
+
+/// Voyage AI embedding models (first entry is the default)
+pub static VOYAGE_MODELS: &[ApiModelInfo] = &[
+    ApiModelInfo {
+        name: "voyage-3.5",
+        dimension: 1024,
+        max_batch_size: 128,
+    },
+    ApiModelInfo {
+        name: "voyage-3.5-lite",
+        dimension: 1024,
+        max_batch_size: 128,
+    },
+    ApiModelInfo {
+        name: "voyage-3-large",
+        dimension: 1024,
+        max_batch_size: 128,
+    },
+    ApiModelInfo {
+        name: "voyage-code-3",
+        dimension: 1024,
+        max_batch_size: 128,
+    },
+    ApiModelInfo {
+        name: "voyage-3",
+        dimension: 1024,
+        max_batch_size: 128,
+    },
+    ApiModelInfo {
+        name: "voyage-3-lite",
+        dimension: 512,
+        max_batch_size: 128,
+    },
+];
+
+/// Request and response shape of an embedding endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiFormat {
+    /// `POST {base_url}/embeddings` with `input`, as served by OpenAI, Ollama, vLLM, etc.
+    OpenAI,
+    /// Cohere `POST {base_url}/embed` (v2) with `texts` and a search `input_type`
+    Cohere,
+    /// Voyage `POST {base_url}/embeddings`: the OpenAI shape plus a query/document `input_type`
+    Voyage,
+}
+
+impl ApiFormat {
+    fn models(self) -> &'static [ApiModelInfo] {
+        match self {
+            Self::OpenAI => &[],
+            Self::Cohere => COHERE_MODELS,
+            Self::Voyage => VOYAGE_MODELS,
+        }
+    }
+}
+
+/// Configuration for [`ApiEmbedder`]
+#[derive(Debug, Clone)]
+pub struct ApiEmbedderConfig {
+    /// Provider name recorded in each frame's embedding identity (e.g., "cohere", "ollama")
+    pub provider: String,
+    /// Wire format of the endpoint
+    pub format: ApiFormat,
+    /// Model name sent with every request
+    pub model: String,
+    /// Embedding dimension the model returns; responses of any other size are rejected
+    pub dimension: usize,
+    /// Whether the model returns unit-length vectors, if known
+    pub normalized: Option<bool>,
+    /// Base URL without the trailing endpoint path
+    pub base_url: String,
+    /// Environment variable holding the API key; `None` sends no `Authorization` header
+    pub api_key_env: Option<String>,
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+    /// Maximum retries on rate limit (429), server (5xx), and network errors
+    pub max_retries: u32,
+    /// Initial backoff in milliseconds for exponential retry
+    pub initial_backoff_ms: u64,
+    /// Upper bound on a single backoff, including one asked for by `Retry-After`
+    pub max_backoff_ms: u64,
+    /// Texts per request
+    pub batch_size: usize,
+    /// Requests in flight at once across every thread sharing the embedder
+    pub max_concurrent_requests: usize,
+    /// Consecutive failed calls that open the circuit breaker; 0 disables it
+    pub circuit_breaker_threshold: u32,
+    /// How long an open circuit breaker refuses calls, in seconds
+    pub circuit_breaker_cooldown_secs: u64,
+}
+
+impl Default for ApiEmbedderConfig {
+    fn default() -> Self {
+        Self::cohere()
+    }
+}
+
+impl ApiEmbedderConfig {
+    fn preset(provider: &str, format: ApiFormat, base_url: &str, api_key_env: &str) -> Self {
+        let model = &format.models()[0];
+        Self {
+            provider: provider.to_string(),
+            format,
+            model: model.name.to_string(),
+            dimension: model.dimension,
+            normalized: None,
+            base_url: base_url.to_string(),
+            api_key_env: Some(api_key_env.to_string()),
+            batch_size: model.max_batch_size,
+            ..Self::openai_compatible("openai", "https://api.openai.com/v1", "", 0)
+        }
+    }
+
+    /// Cohere Embed v2 (`COHERE_API_KEY`), embed-english-v3.0 by default
+    #[must_use]
+    pub fn cohere() -> Self {
+        Self::preset(
+            "cohere",
+            ApiFormat::Cohere,
+            "https://api.cohere.com/v2",
+            "COHERE_API_KEY",
+        )
+    }
+
+    /// Voyage AI (`VOYAGE_API_KEY`), voyage-3.5 by default; Voyage returns unit-length vectors
+    #[must_use]
+    pub fn voyage() -> Self {
+        Self {
+            normalized: Some(true),
+            ..Self::preset(
+                "voyage",
+                ApiFormat::Voyage,
+                "https://api.voyageai.com/v1",
+                "VOYAGE_API_KEY",
+            )
+        }
+    }
+
+    /// A model served by a local Ollama instance through its OpenAI-compatible API
+    #[must_use]
+    pub fn ollama(model: impl Into<String>, dimension: usize) -> Self {
+        Self {
+            batch_size: 64,
+            max_concurrent_requests: 1,
+            ..Self::openai_compatible("ollama", "http://localhost:11434/v1", model, dimension)
+        }
+    }
+
+    /// Any OpenAI-compatible `/embeddings` endpoint (vLLM, LM Studio, gateways, ...).
+    /// `provider` names the service in embedding identities; no API key is sent unless
+    /// [`with_api_key_env`](Self::with_api_key_env) sets one.
+    #[must_use]
+    pub fn openai_compatible(
+        provider: impl Into<String>,
+        base_url: impl Into<String>,
+        model: impl Into<String>,
+        dimension: usize,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            format: ApiFormat::OpenAI,
+            model: model.into(),
+            dimension,
+            normalized: None,
+            base_url: base_url.into(),
+            api_key_env: None,
+            timeout_secs: 30,
+            max_retries: 3,
+            initial_backoff_ms: 1000,
+            max_backoff_ms: 30_000,
+            batch_size: 256,
+            max_concurrent_requests: 4,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 60,
+        }
+    }
+
+    /// Use another model; its dimension and batch limit come from the provider's model table
+    /// when listed there (see [`with_dimension`](Self::with_dimension) otherwise)
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        if let Some(info) = self
+            .format
+            .models()
+            .iter()
+            .find(|info| info.name == self.model)
+        {
+            self.dimension = info.dimension;
+            self.batch_size = self.batch_size.min(info.max_batch_size);
+        }
+        self
+    }
+
+    /// Set the embedding dimension the model returns
+    #[must_use]
+    pub fn with_dimension(mut self, dimension: usize) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Set custom base URL (for proxies or self-hosted endpoints)
+    #[must_use]
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Set the API key environment variable name
+    #[must_use]
+    pub fn with_api_key_env(mut self, env_var: impl Into<String>) -> Self {
+        self.api_key_env = Some(env_var.into());
+        self
+    }
+
+    /// Set request timeout
+    #[must_use]
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout_secs = secs;
+        self
+    }
+
+    /// Set how many texts are sent per request
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set retry count and backoff bounds for rate limits and transient errors
+    #[must_use]
+    pub fn with_retries(
+        mut self,
+        max_retries: u32,
+        initial_backoff_ms: u64,
+        max_backoff_ms: u64,
+    ) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff_ms = initial_backoff_ms;
+        self.max_backoff_ms = max_backoff_ms;
+        self
+    }
+
+    /// Set how many requests may be in flight at once
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, limit: usize) -> Self {
+        self.max_concurrent_requests = limit;
+        self
+    }
+
+    /// Open the circuit breaker after `threshold` consecutive failed calls, refusing calls
+    /// for `cooldown_secs`; a threshold of 0 disables it
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, threshold: u32, cooldown_secs: u64) -> Self {
+        self.circuit_breaker_threshold = threshold;
+        self.circuit_breaker_cooldown_secs = cooldown_secs;
+        self
+    }
+
+    fn policy(&self) -> RequestPolicy {
+        RequestPolicy {
+            max_retries: self.max_retries,
+            initial_backoff_ms: self.initial_backoff_ms,
+            max_backoff_ms: self.max_backoff_ms,
+            max_concurrent_requests: self.max_concurrent_requests,
+            circuit_breaker_threshold: self.circuit_breaker_threshold,
+            circuit_breaker_cooldown_secs: self.circuit_breaker_cooldown_secs,
+        }
+    }
 }
 
 // ============================================================================
@@ -172,30 +562,44 @@ impl OpenAIConfig {
 #[derive(Serialize)]
 struct EmbeddingRequest<'a> {
     model: &'a str,
-    input: Vec<&'a str>,
+    input: &'a [&'a str],
     encoding_format: &'a str,
 }
 
+#[derive(Serialize)]
+struct VoyageRequest<'a> {
+    model: &'a str,
+    input: &'a [&'a str],
+    input_type: &'a str,
+}
+
+#[derive(Serialize)]
+struct CohereRequest<'a> {
+    model: &'a str,
+    texts: &'a [&'a str],
+    input_type: &'a str,
+    embedding_types: [&'a str; 1],
+}
+
 #[derive(Deserialize)]
 struct EmbeddingResponse {
     data: Vec<EmbeddingData>,
-    #[allow(dead_code)]
-    usage: Usage,
 }
 
 #[derive(Deserialize)]
 struct EmbeddingData {
     embedding: Vec<f32>,
-    #[allow(dead_code)]
     index: usize,
 }
 
 #[derive(Deserialize)]
-struct Usage {
-    #[allow(dead_code)]
-    prompt_tokens: usize,
-    #[allow(dead_code)]
-    total_tokens: usize,
+struct CohereResponse {
+    embeddings: CohereEmbeddings,
+}
+
+#[derive(Deserialize)]
+struct CohereEmbeddings {
+    float: Vec<Vec<f32>>,
 }
 
 #[derive(Deserialize)]
@@ -210,193 +614,679 @@ struct ApiErrorDetail {
     error_type: Option<String>,
 }
 
+/// Error body of providers that answer `{"message": ...}` (Cohere) or `{"detail": ...}`
+/// (Voyage) instead of OpenAI's nested `error` object.
+#[derive(Deserialize)]
+struct FlatApiError {
+    #[serde(alias = "detail")]
+    message: String,
+}
+
+/// Whether texts are embedded for storage or as a search query; asymmetric models
+/// (Cohere, Voyage) embed them differently.
+#[derive(Clone, Copy)]
+enum InputType {
+    Document,
+    Query,
+}
+
 // ============================================================================
-// OpenAI Embedder
+// Request Accounting
 // ============================================================================
 
-/// OpenAI embedding provider
-///
-/// Generates embeddings using OpenAI's embedding API. Requires the `OPENAI_API_KEY`
-/// environment variable to be set (or a custom env var via config).
-///
-/// # Example
-///
-/// ```ignore
-/// use memvid_core::api_embed::{OpenAIConfig, OpenAIEmbedder};
-/// use memvid_core::types::embedding::EmbeddingProvider;
-///
-/// let embedder = OpenAIEmbedder::new(OpenAIConfig::default())?;
-/// let embedding = embedder.embed_text("Hello, world!")?;
-/// ```
-pub struct OpenAIEmbedder {
-    config: OpenAIConfig,
-    model_info: &'static OpenAIModelInfo,
-    client: Client,
-    api_key: String,
+/// Retry, concurrency, and circuit-breaker settings of one embedder.
+#[derive(Debug, Clone, Copy)]
+struct RequestPolicy {
+    max_retries: u32,
+    initial_backoff_ms: u64,
+    max_backoff_ms: u64,
+    max_concurrent_requests: usize,
+    circuit_breaker_threshold: u32,
+    circuit_breaker_cooldown_secs: u64,
 }
 
-impl OpenAIEmbedder {
-    /// Create a new OpenAI embedder
-    ///
-    /// Reads the API key from the environment variable specified in config.
-    /// Returns an error if the API key is not set.
-    pub fn new(config: OpenAIConfig) -> Result<Self> {
-        let api_key =
-            std::env::var(&config.api_key_env).map_err(|_| MemvidError::EmbeddingFailed {
-                reason: format!(
-                    "API key not found. Set the {} environment variable.",
-                    config.api_key_env
-                )
-                .into(),
-            })?;
+/// How often async callers poll for a free request slot.
+#[cfg(feature = "async")]
+const SLOT_POLL: Duration = Duration::from_millis(5);
+
+/// State shared by every request of one embedder: counters, in-flight slots, and the
+/// circuit breaker.
+struct RequestState {
+    stats: Mutex<EmbedderRequestStats>,
+    breaker: Mutex<Breaker>,
+    in_flight: Mutex<usize>,
+    slot_freed: Condvar,
+    max_in_flight: usize,
+}
 
-        if api_key.is_empty() {
-            return Err(MemvidError::EmbeddingFailed {
-                reason: format!("{} environment variable is empty", config.api_key_env).into(),
-            });
-        }
+#[derive(Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
 
-        let model_info = get_openai_model_info(&config.model);
+/// A request slot, released on drop.
+struct Slot<'a>(&'a RequestState);
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .build()
-            .map_err(|e| MemvidError::EmbeddingFailed {
-                reason: format!("Failed to create HTTP client: {}", e).into(),
-            })?;
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        *lock(&self.0.in_flight) -= 1;
+        self.0.slot_freed.notify_one();
+    }
+}
 
-        tracing::info!(
-            model = %model_info.name,
-            dimension = model_info.dimension,
-            "OpenAI embedder initialized"
-        );
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
 
-        Ok(Self {
-            config,
-            model_info,
-            client,
-            api_key,
+impl RequestState {
+    fn new(max_in_flight: usize) -> Self {
+        Self {
+            stats: Mutex::new(EmbedderRequestStats::default()),
+            breaker: Mutex::new(Breaker::default()),
+            in_flight: Mutex::new(0),
+            slot_freed: Condvar::new(),
+            max_in_flight: max_in_flight.max(1),
+        }
+    }
+
+    /// Refuse the call while the circuit breaker is open. Once the cooldown ends calls go
+    /// through again, and the next failure reopens it.
+    fn admit(&self) -> Result<()> {
+        let breaker = lock(&self.breaker);
+        let Some(open_until) = breaker.open_until else {
+            return Ok(());
+        };
+        let remaining = open_until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        lock(&self.stats).rejected += 1;
+        Err(MemvidError::EmbedderUnavailable {
+            reason: "circuit breaker open".into(),
+            retry_after_ms: duration_ms(remaining),
         })
     }
 
-    /// Get model info
-    #[must_use]
-    pub fn model_info(&self) -> &'static OpenAIModelInfo {
-        self.model_info
+    #[cfg(any(feature = "async", test))]
+    fn try_acquire(&self) -> Option<Slot<'_>> {
+        let mut in_flight = lock(&self.in_flight);
+        if *in_flight >= self.max_in_flight {
+            return None;
+        }
+        *in_flight += 1;
+        Some(Slot(self))
     }
 
-    /// Make an embedding request with retry logic
-    fn request_embeddings(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        let url = format!("{}/embeddings", self.config.base_url);
+    fn acquire(&self) -> Slot<'_> {
+        let mut in_flight = lock(&self.in_flight);
+        while *in_flight >= self.max_in_flight {
+            in_flight = self
+                .slot_freed
+                .wait(in_flight)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *in_flight += 1;
+        Slot(self)
+    }
 
-        let request_body = EmbeddingRequest {
-            model: &self.config.model,
-            input: texts.to_vec(),
-            encoding_format: "float",
-        };
+    #[cfg(feature = "async")]
+    async fn acquire_async(&self) -> Slot<'_> {
+        loop {
+            if let Some(slot) = self.try_acquire() {
+                return slot;
+            }
+            tokio::time::sleep(SLOT_POLL).await;
+        }
+    }
 
-        let mut headers = HeaderMap::new();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).map_err(|_| {
-                MemvidError::EmbeddingFailed {
-                    reason: "Invalid API key format".into(),
-                }
-            })?,
-        );
+    fn record_request(&self, latency: Duration) {
+        let latency_ms = duration_ms(latency);
+        let mut stats = lock(&self.stats);
+        stats.requests += 1;
+        stats.total_latency_ms = stats.total_latency_ms.saturating_add(latency_ms);
+        stats.max_latency_ms = stats.max_latency_ms.max(latency_ms);
+    }
 
-        let mut backoff_ms = self.config.initial_backoff_ms;
-        let mut last_error = None;
+    fn record_retry(&self) {
+        lock(&self.stats).retries += 1;
+    }
 
-        for attempt in 0..=self.config.max_retries {
-            if attempt > 0 {
-                tracing::warn!(
-                    attempt = attempt,
-                    backoff_ms = backoff_ms,
-                    "Retrying OpenAI request after rate limit"
-                );
-                std::thread::sleep(Duration::from_millis(backoff_ms));
-                backoff_ms *= 2; // Exponential backoff
-            }
+    fn record_status(&self, status: StatusCode) {
+        let mut stats = lock(&self.stats);
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            stats.rate_limited += 1;
+        } else if status.is_server_error() {
+            stats.server_errors += 1;
+        }
+    }
 
-            let response = self
-                .client
-                .post(&url)
-                .headers(headers.clone())
-                .json(&request_body)
-                .send();
+    fn record_success(&self, texts: usize) {
+        lock(&self.stats).texts += texts as u64;
+        *lock(&self.breaker) = Breaker::default();
+    }
 
-            match response {
-                Ok(resp) => {
-                    let status = resp.status();
+    /// Count a call whose retries ran out, opening the breaker once `policy`'s threshold
+    /// of consecutive failures is reached.
+    fn record_exhausted(
+        &self,
+        policy: &RequestPolicy,
+        reason: String,
+        next_delay: Duration,
+    ) -> MemvidError {
+        let mut breaker = lock(&self.breaker);
+        let mut stats = lock(&self.stats);
+        stats.failures += 1;
+        breaker.consecutive_failures += 1;
+        let threshold = policy.circuit_breaker_threshold;
+        let retry_after = if threshold > 0 && breaker.consecutive_failures >= threshold {
+            let cooldown = Duration::from_secs(policy.circuit_breaker_cooldown_secs);
+            breaker.open_until = Some(Instant::now() + cooldown);
+            stats.circuit_opened += 1;
+            tracing::warn!(
+                failures = breaker.consecutive_failures,
+                cooldown_secs = policy.circuit_breaker_cooldown_secs,
+                "embedding API circuit breaker opened"
+            );
+            cooldown
+        } else {
+            next_delay
+        };
+        MemvidError::EmbedderUnavailable {
+            reason: reason.into(),
+            retry_after_ms: duration_ms(retry_after),
+        }
+    }
 
-                    if status.is_success() {
-                        let embedding_response: EmbeddingResponse =
-                            resp.json().map_err(|e| MemvidError::EmbeddingFailed {
-                                reason: format!("Failed to parse response: {}", e).into(),
-                            })?;
+    fn snapshot(&self) -> EmbedderRequestStats {
+        let mut stats = *lock(&self.stats);
+        stats.circuit_open = lock(&self.breaker)
+            .open_until
+            .is_some_and(|until| until > Instant::now());
+        stats
+    }
+}
 
-                        // Sort by index to ensure correct order
-                        let mut data = embedding_response.data;
-                        data.sort_by_key(|d| d.index);
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
 
-                        let embeddings: Vec<Vec<f32>> =
-                            data.into_iter().map(|d| d.embedding).collect();
+/// Whether a response with `status` is worth retrying.
+fn retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
 
-                        tracing::debug!(
-                            texts = texts.len(),
-                            dimension = embeddings.first().map(|e| e.len()).unwrap_or(0),
-                            "Generated OpenAI embeddings"
-                        );
+/// Delay the server asked for in a `Retry-After` header given in seconds.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
 
-                        return Ok(embeddings);
+/// Wait before retry number `attempt` (from 1): the server's `Retry-After` if it sent one,
+/// else exponential backoff with half of it randomized so clients that failed together
+/// spread out. Either way at most `max_backoff_ms`.
+fn backoff_delay(policy: &RequestPolicy, attempt: u32, retry_after: Option<Duration>) -> Duration {
+    let max = Duration::from_millis(policy.max_backoff_ms);
+    if let Some(requested) = retry_after {
+        return requested.min(max);
+    }
+    let exponent = attempt.saturating_sub(1).min(20);
+    let base = policy
+        .initial_backoff_ms
+        .saturating_mul(1 << exponent)
+        .min(policy.max_backoff_ms);
+    // Each `RandomState` is freshly keyed, which is random enough for jitter.
+    let random = RandomState::new().hash_one(attempt);
+    Duration::from_millis(base / 2 + random % (base / 2 + 1))
+}
+
+// ============================================================================
+// HTTP Transport
+// ============================================================================
+
+/// HTTP clients, headers, and request accounting behind one API embedder.
+struct Transport {
+    /// Provider name for logs and error messages
+    provider: String,
+    url: String,
+    headers: HeaderMap,
+    client: Client,
+    #[cfg(feature = "async")]
+    async_client: reqwest::Client,
+    policy: RequestPolicy,
+    state: RequestState,
+}
+
+/// Read an API key from `env_var`, failing if it is unset or empty.
+fn read_api_key(env_var: &str) -> Result<String> {
+    let api_key = std::env::var(env_var).map_err(|_| MemvidError::EmbeddingFailed {
+        reason: format!("API key not found. Set the {env_var} environment variable.").into(),
+    })?;
+    if api_key.is_empty() {
+        return Err(MemvidError::EmbeddingFailed {
+            reason: format!("{env_var} environment variable is empty").into(),
+        });
+    }
+    Ok(api_key)
+}
+
+impl Transport {
+    fn new(
+        provider: &str,
+        url: String,
+        api_key: Option<&str>,
+        timeout_secs: u64,
+        policy: RequestPolicy,
+    ) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        if let Some(api_key) = api_key {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {api_key}")).map_err(|_| {
+                    MemvidError::EmbeddingFailed {
+                        reason: "Invalid API key format".into(),
                     }
+                })?,
+            );
+        }
 
-                    // Handle rate limiting
-                    if status.as_u16() == 429 {
-                        last_error = Some(MemvidError::EmbeddingFailed {
-                            reason: "Rate limit exceeded".into(),
-                        });
-                        continue;
+        let client = Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .build()
+            .map_err(|e| MemvidError::EmbeddingFailed {
+                reason: format!("Failed to create HTTP client: {e}").into(),
+            })?;
+
+        #[cfg(feature = "async")]
+        let async_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .build()
+            .map_err(|e| MemvidError::EmbeddingFailed {
+                reason: format!("Failed to create HTTP client: {e}").into(),
+            })?;
+
+        Ok(Self {
+            provider: provider.to_string(),
+            url,
+            headers,
+            client,
+            #[cfg(feature = "async")]
+            async_client,
+            policy,
+            state: RequestState::new(policy.max_concurrent_requests),
+        })
+    }
+
+    /// Log and count retry number `attempt`, returning how long to wait before it.
+    fn before_retry(
+        &self,
+        attempt: u32,
+        requested_delay: Option<Duration>,
+        last_error: &str,
+    ) -> Duration {
+        let delay = backoff_delay(&self.policy, attempt, requested_delay);
+        tracing::warn!(
+            provider = %self.provider,
+            attempt = attempt,
+            backoff_ms = duration_ms(delay),
+            error = %last_error,
+            "Retrying embedding request"
+        );
+        self.state.record_retry();
+        delay
+    }
+
+    fn exhausted(&self, last_error: String, requested_delay: Option<Duration>) -> MemvidError {
+        let next_delay = backoff_delay(&self.policy, self.policy.max_retries + 1, requested_delay);
+        self.state
+            .record_exhausted(&self.policy, last_error, next_delay)
+    }
+
+    fn parse_failed(&self, err: &reqwest::Error) -> MemvidError {
+        MemvidError::EmbeddingFailed {
+            reason: format!("Failed to parse {} response: {err}", self.provider).into(),
+        }
+    }
+
+    /// POST `body` for `texts` inputs and parse the reply, with retry logic
+    ///
+    /// Rate limits, server errors, and timeouts are retried with backoff; once retries run
+    /// out the call fails with [`MemvidError::EmbedderUnavailable`], and enough such calls
+    /// in a row open the circuit breaker.
+    fn post<B: Serialize, R: DeserializeOwned>(&self, body: &B, texts: usize) -> Result<R> {
+        self.state.admit()?;
+        let _slot = self.state.acquire();
+
+        let mut requested_delay = None;
+        let mut last_error = String::new();
+
+        for attempt in 0..=self.policy.max_retries {
+            if attempt > 0 {
+                let delay = self.before_retry(attempt, requested_delay.take(), &last_error);
+                std::thread::sleep(delay);
+            }
+
+            let started = Instant::now();
+            let response = self
+                .client
+                .post(&self.url)
+                .headers(self.headers.clone())
+                .json(body)
+                .send();
+            self.state.record_request(started.elapsed());
+
+            match response {
+                Ok(resp) => {
+                    let status = resp.status();
+
+                    if status.is_success() {
+                        let parsed = resp.json().map_err(|e| self.parse_failed(&e))?;
+                        self.state.record_success(texts);
+                        return Ok(parsed);
                     }
 
-                    // Parse error response
+                    // Rate limits and server errors are transient
+                    let retryable = retryable_status(status);
+                    if retryable {
+                        self.state.record_status(status);
+                        requested_delay = retry_after(resp.headers());
+                    }
                     let error_text = resp.text().unwrap_or_default();
-                    let error_msg =
-                        if let Ok(api_error) = serde_json::from_str::<ApiError>(&error_text) {
-                            format!(
-                                "OpenAI API error ({}): {}",
-                                api_error.error.error_type.unwrap_or_default(),
-                                api_error.error.message
-                            )
-                        } else {
-                            format!("OpenAI API error ({}): {}", status, error_text)
-                        };
-
-                    return Err(MemvidError::EmbeddingFailed {
-                        reason: error_msg.into(),
-                    });
+                    last_error = api_error_message(&self.provider, status, &error_text);
+                    if !retryable {
+                        return Err(MemvidError::EmbeddingFailed {
+                            reason: last_error.into(),
+                        });
+                    }
                 }
                 Err(e) => {
                     // Network error - might be transient
-                    last_error = Some(MemvidError::EmbeddingFailed {
-                        reason: format!("Request failed: {}", e).into(),
-                    });
+                    last_error = format!("Request failed: {e}");
 
-                    if e.is_timeout() || e.is_connect() {
-                        continue; // Retry on timeout or connection errors
+                    if !(e.is_timeout() || e.is_connect()) {
+                        return Err(MemvidError::EmbeddingFailed {
+                            reason: last_error.into(),
+                        });
                     }
-
-                    return Err(last_error.unwrap());
                 }
             }
         }
 
         // All retries exhausted
-        Err(last_error.unwrap_or_else(|| MemvidError::EmbeddingFailed {
-            reason: "Max retries exceeded".into(),
-        }))
+        Err(self.exhausted(last_error, requested_delay))
+    }
+
+    /// Async counterpart of [`Transport::post`].
+    #[cfg(feature = "async")]
+    async fn post_async<B: Serialize, R: DeserializeOwned>(
+        &self,
+        body: &B,
+        texts: usize,
+    ) -> Result<R> {
+        self.state.admit()?;
+        let _slot = self.state.acquire_async().await;
+
+        let mut requested_delay = None;
+        let mut last_error = String::new();
+        for attempt in 0..=self.policy.max_retries {
+            if attempt > 0 {
+                let delay = self.before_retry(attempt, requested_delay.take(), &last_error);
+                tokio::time::sleep(delay).await;
+            }
+
+            let started = Instant::now();
+            let response = self
+                .async_client
+                .post(&self.url)
+                .headers(self.headers.clone())
+                .json(body)
+                .send()
+                .await;
+            self.state.record_request(started.elapsed());
+            match response {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if status.is_success() {
+                        let parsed = resp.json().await.map_err(|e| self.parse_failed(&e))?;
+                        self.state.record_success(texts);
+                        return Ok(parsed);
+                    }
+                    let retryable = retryable_status(status);
+                    if retryable {
+                        self.state.record_status(status);
+                        requested_delay = retry_after(resp.headers());
+                    }
+                    let error_text = resp.text().await.unwrap_or_default();
+                    last_error = api_error_message(&self.provider, status, &error_text);
+                    if !retryable {
+                        return Err(MemvidError::EmbeddingFailed {
+                            reason: last_error.into(),
+                        });
+                    }
+                }
+                Err(e) => {
+                    last_error = format!("Request failed: {e}");
+                    if !(e.is_timeout() || e.is_connect()) {
+                        return Err(MemvidError::EmbeddingFailed {
+                            reason: last_error.into(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Err(self.exhausted(last_error, requested_delay))
+    }
+
+    /// Embed `texts` in batches of `batch_size` with `request`, sending up to
+    /// `max_concurrent_requests` batches at once.
+    fn embed_in_batches<F>(
+        &self,
+        texts: &[&str],
+        batch_size: usize,
+        request: F,
+    ) -> Result<Vec<Vec<f32>>>
+    where
+        F: Fn(&[&str]) -> Result<Vec<Vec<f32>>> + Sync,
+    {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let batches: Vec<&[&str]> = texts.chunks(batch_size.max(1)).collect();
+        if batches.len() == 1 {
+            return request(texts);
+        }
+        let mut all_embeddings = Vec::with_capacity(texts.len());
+        for wave in batches.chunks(self.state.max_in_flight) {
+            let results: Vec<Result<Vec<Vec<f32>>>> = std::thread::scope(|scope| {
+                let handles: Vec<_> = wave
+                    .iter()
+                    .map(|batch| scope.spawn(|| request(batch)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle.join().unwrap_or_else(|_| {
+                            Err(MemvidError::EmbeddingFailed {
+                                reason: "embedding request thread panicked".into(),
+                            })
+                        })
+                    })
+                    .collect()
+            });
+            for result in results {
+                all_embeddings.extend(result?);
+            }
+        }
+
+        Ok(all_embeddings)
+    }
+}
+
+// ============================================================================
+// OpenAI Embedder
+// ============================================================================
+
+/// OpenAI embedding provider
+///
+/// Generates embeddings using OpenAI's embedding API. Requires the `OPENAI_API_KEY`
+/// environment variable to be set (or a custom env var via config).
+///
+/// # Example
+///
+/// ```ignore
+/// use memvid_core::api_embed::{OpenAIConfig, OpenAIEmbedder};
+/// use memvid_core::types::embedding::EmbeddingProvider;
+///
+/// let embedder = OpenAIEmbedder::new(OpenAIConfig::default())?;
+/// let embedding = embedder.embed_text("Hello, world!")?;
+/// ```
+pub struct OpenAIEmbedder {
+    config: OpenAIConfig,
+    model_info: &'static OpenAIModelInfo,
+    transport: Transport,
+}
+
+impl OpenAIEmbedder {
+    /// Create a new OpenAI embedder
+    ///
+    /// Reads the API key from the environment variable specified in config.
+    /// Returns an error if the API key is not set.
+    pub fn new(config: OpenAIConfig) -> Result<Self> {
+        let api_key = read_api_key(&config.api_key_env)?;
+        let model_info = get_openai_model_info(&config.model);
+
+        let transport = Transport::new(
+            "OpenAI",
+            format!("{}/embeddings", config.base_url),
+            Some(&api_key),
+            config.timeout_secs,
+            config.policy(),
+        )?;
+
+        tracing::info!(
+            model = %model_info.name,
+            dimension = model_info.dimension,
+            "OpenAI embedder initialized"
+        );
+
+        Ok(Self {
+            config,
+            model_info,
+            transport,
+        })
+    }
+
+    /// Get model info
+    #[must_use]
+    pub fn model_info(&self) -> &'static OpenAIModelInfo {
+        self.model_info
+    }
+
+    /// Request counters since the embedder was created.
+    #[must_use]
+    pub fn stats(&self) -> EmbedderRequestStats {
+        self.transport.state.snapshot()
+    }
+
+    /// Texts sent per request.
+    fn batch_size(&self) -> usize {
+        self.config
+            .batch_size
+            .clamp(1, self.model_info.max_batch_size)
+    }
+
+    fn request_body<'a>(&'a self, texts: &'a [&'a str]) -> EmbeddingRequest<'a> {
+        EmbeddingRequest {
+            model: &self.config.model,
+            input: texts,
+            encoding_format: "float",
+        }
+    }
+
+    /// Make an embedding request with retry logic
+    fn request_embeddings(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .transport
+            .post(&self.request_body(texts), texts.len())?;
+        let embeddings = ordered_embeddings(response);
+
+        tracing::debug!(
+            texts = texts.len(),
+            dimension = embeddings.first().map_or(0, Vec::len),
+            "Generated OpenAI embeddings"
+        );
+
+        Ok(embeddings)
+    }
+}
+
+// ============================================================================
+// Async API (feature `async`)
+// ============================================================================
+
+#[cfg(feature = "async")]
+impl OpenAIEmbedder {
+    /// Async counterpart of [`EmbeddingProvider::embed_text`] that never blocks the runtime.
+    pub async fn embed_text_async(&self, text: &str) -> Result<Vec<f32>> {
+        let embeddings = self.request_embeddings_async(&[text]).await?;
+        first_embedding(embeddings)
+    }
+
+    /// Async counterpart of [`EmbeddingProvider::embed_batch`]. Batches are sent one at a
+    /// time; run several calls concurrently to use more of `max_concurrent_requests`.
+    pub async fn embed_batch_async(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut all_embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size()) {
+            all_embeddings.extend(self.request_embeddings_async(chunk).await?);
+        }
+        Ok(all_embeddings)
+    }
+
+    async fn request_embeddings_async(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .transport
+            .post_async(&self.request_body(texts), texts.len())
+            .await?;
+        Ok(ordered_embeddings(response))
+    }
+}
+
+fn ordered_embeddings(response: EmbeddingResponse) -> Vec<Vec<f32>> {
+    // Sort by index to ensure correct order
+    let mut data = response.data;
+    data.sort_by_key(|d| d.index);
+    data.into_iter().map(|d| d.embedding).collect()
+}
+
+fn first_embedding(embeddings: Vec<Vec<f32>>) -> Result<Vec<f32>> {
+    embeddings
+        .into_iter()
+        .next()
+        .ok_or_else(|| MemvidError::EmbeddingFailed {
+            reason: "No embedding returned".into(),
+        })
+}
+
+fn api_error_message(provider: &str, status: StatusCode, error_text: &str) -> String {
+    if let Ok(api_error) = serde_json::from_str::<ApiError>(error_text) {
+        format!(
+            "{provider} API error ({}): {}",
+            api_error.error.error_type.unwrap_or_default(),
+            api_error.error.message
+        )
+    } else if let Ok(api_error) = serde_json::from_str::<FlatApiError>(error_text) {
+        format!("{provider} API error ({status}): {}", api_error.message)
+    } else {
+        format!("{provider} API error ({status}): {error_text}")
     }
 }
 
@@ -428,36 +1318,371 @@ impl EmbeddingProvider for OpenAIEmbedder {
     }
 
     fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
-        let embeddings = self.request_embeddings(&[text])?;
-        embeddings
-            .into_iter()
-            .next()
-            .ok_or_else(|| MemvidError::EmbeddingFailed {
-                reason: "No embedding returned".into(),
-            })
+        first_embedding(self.request_embeddings(&[text])?)
     }
 
     fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        if texts.is_empty() {
-            return Ok(Vec::new());
+        self.transport
+            .embed_in_batches(texts, self.batch_size(), |batch| {
+                self.request_embeddings(batch)
+            })
+    }
+
+    fn is_ready(&self) -> bool {
+        // We have an API key, so we're ready
+        self.transport.headers.contains_key(AUTHORIZATION)
+    }
+
+    fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        Some(self.stats())
+    }
+}
+
+/// Lets the embedder drive `start_enrichment_worker_with_embeddings`.
+impl VecEmbedder for OpenAIEmbedder {
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        EmbeddingProvider::embed_query(self, text)
+    }
+
+    fn embed_chunks(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.embed_batch(texts)
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.model_info.dimension
+    }
+
+    fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        Some(self.stats())
+    }
+}
+
+// ============================================================================
+// Cohere / Voyage / OpenAI-compatible Embedder
+// ============================================================================
+
+/// Request body in any of the supported wire formats.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ApiRequest<'a> {
+    OpenAI(EmbeddingRequest<'a>),
+    Voyage(VoyageRequest<'a>),
+    Cohere(CohereRequest<'a>),
+}
+
+/// Response body in any of the supported wire formats.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ApiResponse {
+    OpenAI(EmbeddingResponse),
+    Cohere(CohereResponse),
+}
+
+impl ApiResponse {
+    fn into_embeddings(self) -> Vec<Vec<f32>> {
+        match self {
+            Self::OpenAI(response) => ordered_embeddings(response),
+            Self::Cohere(response) => response.embeddings.float,
         }
+    }
+}
 
-        // Split into chunks respecting API batch size limit
-        let max_batch = self.model_info.max_batch_size;
-        let mut all_embeddings = Vec::with_capacity(texts.len());
+/// Embedding provider for Cohere, Voyage, Ollama, and other OpenAI-compatible endpoints
+///
+/// The provider name, model, and dimension in [`ApiEmbedderConfig`] are what the embedder
+/// reports through [`EmbeddingProvider`], so vectors from, say, Ollama's `nomic-embed-text`
+/// are never mistaken for the local fastembed model of the same name.
+///
+/// # Example
+///
+/// ```ignore
+/// use memvid_core::api_embed::{ApiEmbedder, ApiEmbedderConfig};
+/// use memvid_core::types::embedding::EmbeddingProvider;
+///
+/// // Requires COHERE_API_KEY environment variable
+/// let embedder = ApiEmbedder::new(ApiEmbedderConfig::cohere())?;
+/// let embedding = embedder.embed_text("Hello, world!")?;
+///
+/// // Local Ollama, no key needed
+/// let ollama = ApiEmbedder::new(ApiEmbedderConfig::ollama("nomic-embed-text", 768))?;
+/// ```
+pub struct ApiEmbedder {
+    config: ApiEmbedderConfig,
+    transport: Transport,
+}
+
+impl ApiEmbedder {
+    /// Create an embedder, reading the API key from `config.api_key_env` when set.
+    pub fn new(config: ApiEmbedderConfig) -> Result<Self> {
+        if config.dimension == 0 {
+            return Err(MemvidError::EmbeddingFailed {
+                reason: format!("Embedding dimension not set for model {}", config.model).into(),
+            });
+        }
+        let api_key = config
+            .api_key_env
+            .as_deref()
+            .map(read_api_key)
+            .transpose()?;
+        let endpoint = match config.format {
+            ApiFormat::Cohere => "embed",
+            ApiFormat::OpenAI | ApiFormat::Voyage => "embeddings",
+        };
+
+        let transport = Transport::new(
+            &config.provider,
+            format!("{}/{endpoint}", config.base_url.trim_end_matches('/')),
+            api_key.as_deref(),
+            config.timeout_secs,
+            config.policy(),
+        )?;
+
+        tracing::info!(
+            provider = %config.provider,
+            model = %config.model,
+            dimension = config.dimension,
+            "API embedder initialized"
+        );
+
+        Ok(Self { config, transport })
+    }
+
+    /// The configuration the embedder was built with.
+    #[must_use]
+    pub fn config(&self) -> &ApiEmbedderConfig {
+        &self.config
+    }
+
+    /// Request counters since the embedder was created.
+    #[must_use]
+    pub fn stats(&self) -> EmbedderRequestStats {
+        self.transport.state.snapshot()
+    }
+
+    fn request_body<'a>(&'a self, texts: &'a [&'a str], input: InputType) -> ApiRequest<'a> {
+        let model = self.config.model.as_str();
+        match self.config.format {
+            ApiFormat::OpenAI => ApiRequest::OpenAI(EmbeddingRequest {
+                model,
+                input: texts,
+                encoding_format: "float",
+            }),
+            ApiFormat::Voyage => ApiRequest::Voyage(VoyageRequest {
+                model,
+                input: texts,
+                input_type: match input {
+                    InputType::Document => "document",
+                    InputType::Query => "query",
+                },
+            }),
+            ApiFormat::Cohere => ApiRequest::Cohere(CohereRequest {
+                model,
+                texts,
+                input_type: match input {
+                    InputType::Document => "search_document",
+                    InputType::Query => "search_query",
+                },
+                embedding_types: ["float"],
+            }),
+        }
+    }
 
-        for chunk in texts.chunks(max_batch) {
-            let chunk_embeddings = self.request_embeddings(chunk)?;
-            all_embeddings.extend(chunk_embeddings);
+    /// Check that the service answered with one vector of the configured dimension per text,
+    /// so nothing of another shape is stored under this embedder's identity.
+    fn checked(&self, texts: usize, response: ApiResponse) -> Result<Vec<Vec<f32>>> {
+        let embeddings = response.into_embeddings();
+        if embeddings.len() != texts {
+            return Err(MemvidError::EmbeddingFailed {
+                reason: format!(
+                    "{} returned {} embeddings for {texts} texts",
+                    self.config.provider,
+                    embeddings.len()
+                )
+                .into(),
+            });
+        }
+        if let Some(embedding) = embeddings
+            .iter()
+            .find(|embedding| embedding.len() != self.config.dimension)
+        {
+            return Err(MemvidError::VecDimensionMismatch {
+                expected: u32::try_from(self.config.dimension).unwrap_or(u32::MAX),
+                actual: embedding.len(),
+            });
         }
+        tracing::debug!(
+            provider = %self.config.provider,
+            texts,
+            dimension = self.config.dimension,
+            "Generated API embeddings"
+        );
+        Ok(embeddings)
+    }
+
+    fn request_embeddings(&self, texts: &[&str], input: InputType) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .transport
+            .post(&self.request_body(texts, input), texts.len())?;
+        self.checked(texts.len(), response)
+    }
+}
 
+#[cfg(feature = "async")]
+impl ApiEmbedder {
+    /// Async counterpart of [`EmbeddingProvider::embed_text`] that never blocks the runtime.
+    pub async fn embed_text_async(&self, text: &str) -> Result<Vec<f32>> {
+        let embeddings = self
+            .request_embeddings_async(&[text], InputType::Document)
+            .await?;
+        first_embedding(embeddings)
+    }
+
+    /// Async counterpart of [`EmbeddingProvider::embed_query`].
+    pub async fn embed_query_async(&self, text: &str) -> Result<Vec<f32>> {
+        let embeddings = self
+            .request_embeddings_async(&[text], InputType::Query)
+            .await?;
+        first_embedding(embeddings)
+    }
+
+    /// Async counterpart of [`EmbeddingProvider::embed_batch`]. Batches are sent one at a
+    /// time; run several calls concurrently to use more of `max_concurrent_requests`.
+    pub async fn embed_batch_async(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut all_embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.config.batch_size.max(1)) {
+            all_embeddings.extend(
+                self.request_embeddings_async(chunk, InputType::Document)
+                    .await?,
+            );
+        }
         Ok(all_embeddings)
     }
 
-    fn is_ready(&self) -> bool {
-        // We have an API key, so we're ready
-        !self.api_key.is_empty()
+    async fn request_embeddings_async(
+        &self,
+        texts: &[&str],
+        input: InputType,
+    ) -> Result<Vec<Vec<f32>>> {
+        let response = self
+            .transport
+            .post_async(&self.request_body(texts, input), texts.len())
+            .await?;
+        self.checked(texts.len(), response)
+    }
+}
+
+impl std::fmt::Debug for ApiEmbedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiEmbedder")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EmbeddingProvider for ApiEmbedder {
+    fn kind(&self) -> &str {
+        &self.config.provider
+    }
+
+    fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    fn dimension(&self) -> usize {
+        self.config.dimension
+    }
+
+    fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        first_embedding(self.request_embeddings(&[text], InputType::Document)?)
+    }
+
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.transport
+            .embed_in_batches(texts, self.config.batch_size, |batch| {
+                self.request_embeddings(batch, InputType::Document)
+            })
+    }
+
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        first_embedding(self.request_embeddings(&[text], InputType::Query)?)
+    }
+
+    fn normalized(&self) -> Option<bool> {
+        self.config.normalized
+    }
+
+    fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        Some(self.stats())
+    }
+}
+
+/// Lets the embedder drive `start_enrichment_worker_with_embeddings`.
+impl VecEmbedder for ApiEmbedder {
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        EmbeddingProvider::embed_query(self, text)
     }
+
+    fn embed_chunks(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.embed_batch(texts)
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.config.dimension
+    }
+
+    fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        Some(self.stats())
+    }
+}
+
+/// Build the HTTP embedder that `kind` describes.
+///
+/// Fails for kinds that are not served over HTTP (`Local`, `Anthropic`, `Custom`), and when
+/// the provider's API key is missing.
+pub fn embedder_for_kind(kind: &EmbeddingProviderKind) -> Result<Box<dyn EmbeddingProvider>> {
+    let config = match kind {
+        EmbeddingProviderKind::OpenAI { model, api_key_env } => {
+            let config = OpenAIConfig {
+                model: model.clone(),
+                ..OpenAIConfig::default()
+            };
+            return Ok(Box::new(OpenAIEmbedder::new(
+                config.with_api_key_env(api_key_env.clone()),
+            )?));
+        }
+        EmbeddingProviderKind::Cohere { model, api_key_env } => ApiEmbedderConfig::cohere()
+            .with_model(model.clone())
+            .with_api_key_env(api_key_env.clone()),
+        EmbeddingProviderKind::Voyage { model, api_key_env } => ApiEmbedderConfig::voyage()
+            .with_model(model.clone())
+            .with_api_key_env(api_key_env.clone()),
+        EmbeddingProviderKind::OpenAICompatible {
+            provider,
+            base_url,
+            model,
+            dimension,
+            api_key_env,
+        } => {
+            let config = ApiEmbedderConfig::openai_compatible(
+                provider.clone(),
+                base_url.clone(),
+                model.clone(),
+                *dimension,
+            );
+            match api_key_env {
+                Some(env_var) => config.with_api_key_env(env_var.clone()),
+                None => config,
+            }
+        }
+        EmbeddingProviderKind::Local(_)
+        | EmbeddingProviderKind::Anthropic { .. }
+        | EmbeddingProviderKind::Custom(_) => {
+            return Err(MemvidError::EmbeddingFailed {
+                reason: format!("{kind:?} is not an HTTP embedding provider").into(),
+            });
+        }
+    };
+    Ok(Box::new(ApiEmbedder::new(config)?))
 }
 
 // ============================================================================
@@ -528,6 +1753,178 @@ mod tests {
         assert_eq!(config.timeout_secs, 60);
     }
 
+    #[test]
+    fn test_backoff_delay_is_jittered_and_capped() {
+        let config = OpenAIConfig::default().with_retries(3, 1000, 5000);
+        for _ in 0..20 {
+            let first = backoff_delay(&config.policy(), 1, None);
+            assert!((500..=1000).contains(&first.as_millis()));
+            let late = backoff_delay(&config.policy(), 6, None);
+            assert!((2500..=5000).contains(&late.as_millis()));
+        }
+        let requested = backoff_delay(&config.policy(), 1, Some(Duration::from_secs(60)));
+        assert_eq!(requested, Duration::from_millis(5000));
+    }
+
+    #[test]
+    fn test_circuit_breaker_opens_after_consecutive_failures() {
+        let config = OpenAIConfig::default().with_circuit_breaker(2, 60);
+        let state = RequestState::new(1);
+        let delay = Duration::from_millis(10);
+
+        let first = state.record_exhausted(&config.policy(), "429".to_string(), delay);
+        assert!(matches!(
+            first,
+            MemvidError::EmbedderUnavailable {
+                retry_after_ms: 10,
+                ..
+            }
+        ));
+        assert!(state.admit().is_ok());
+        let second = state.record_exhausted(&config.policy(), "429".to_string(), delay);
+        assert!(matches!(
+            second,
+            MemvidError::EmbedderUnavailable {
+                retry_after_ms: 60_000,
+                ..
+            }
+        ));
+        assert!(matches!(
+            state.admit(),
+            Err(MemvidError::EmbedderUnavailable { .. })
+        ));
+
+        let stats = state.snapshot();
+        assert!(stats.circuit_open);
+        assert_eq!(
+            (stats.failures, stats.rejected, stats.circuit_opened),
+            (2, 1, 1)
+        );
+
+        state.record_success(3);
+        assert!(state.admit().is_ok());
+        assert_eq!(state.snapshot().texts, 3);
+    }
+
+    #[test]
+    fn test_request_slots_limit_concurrency() {
+        let state = RequestState::new(1);
+        let slot = state.acquire();
+        assert!(state.try_acquire().is_none());
+        drop(slot);
+        assert!(state.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_api_config_presets() {
+        let cohere = ApiEmbedderConfig::cohere();
+        assert_eq!(cohere.provider, "cohere");
+        assert_eq!(cohere.model, "embed-english-v3.0");
+        assert_eq!(cohere.dimension, 1024);
+        assert_eq!(cohere.batch_size, 96);
+        assert_eq!(cohere.api_key_env.as_deref(), Some("COHERE_API_KEY"));
+
+        let light = cohere.with_model("embed-english-light-v3.0");
+        assert_eq!(light.dimension, 384);
+
+        let voyage = ApiEmbedderConfig::voyage().with_model("voyage-3-lite");
+        assert_eq!(
+            (voyage.provider.as_str(), voyage.dimension),
+            ("voyage", 512)
+        );
+        assert_eq!(voyage.normalized, Some(true));
+
+        let ollama = ApiEmbedderConfig::ollama("nomic-embed-text", 768);
+        assert_eq!(ollama.base_url, "http://localhost:11434/v1");
+        assert_eq!(ollama.format, ApiFormat::OpenAI);
+        assert!(ollama.api_key_env.is_none());
+    }
+
+    #[test]
+    fn test_api_request_bodies() {
+        let texts = ["hello"];
+        let cohere = ApiEmbedder::new(
+            ApiEmbedderConfig::cohere()
+                .with_base_url("http://127.0.0.1:9")
+                .with_api_key_env("PATH"),
+        )
+        .expect("embedder");
+        let body = serde_json::to_value(cohere.request_body(&texts, InputType::Query)).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "model": "embed-english-v3.0",
+                "texts": ["hello"],
+                "input_type": "search_query",
+                "embedding_types": ["float"],
+            })
+        );
+
+        let voyage = ApiEmbedder::new(ApiEmbedderConfig::voyage().with_api_key_env("PATH"))
+            .expect("embedder");
+        let body = serde_json::to_value(voyage.request_body(&texts, InputType::Document)).unwrap();
+        assert_eq!(body["input_type"], "document");
+        assert_eq!(body["input"], serde_json::json!(["hello"]));
+    }
+
+    #[test]
+    fn test_api_responses_are_checked() {
+        let embedder =
+            ApiEmbedder::new(ApiEmbedderConfig::ollama("nomic-embed-text", 2)).expect("embedder");
+        let openai: ApiResponse = serde_json::from_str(
+            r#"{"data":[{"embedding":[0.0,1.0],"index":1},{"embedding":[1.0,0.0],"index":0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            embedder.checked(2, openai).unwrap(),
+            vec![vec![1.0, 0.0], vec![0.0, 1.0]]
+        );
+
+        let cohere: ApiResponse =
+            serde_json::from_str(r#"{"id":"x","embeddings":{"float":[[0.5,0.5,0.5]]}}"#).unwrap();
+        assert!(matches!(
+            embedder.checked(1, cohere),
+            Err(MemvidError::VecDimensionMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_api_embedder_identity() {
+        let embedder = ApiEmbedder::new(ApiEmbedderConfig::ollama("nomic-embed-text", 768))
+            .expect("no key needed");
+        let identity = crate::types::EmbeddingIdentity::of_provider(&embedder);
+        assert_eq!(identity.provider.as_deref(), Some("ollama"));
+        assert_eq!(identity.model.as_deref(), Some("nomic-embed-text"));
+        assert_eq!(identity.dimension, Some(768));
+
+        let zero = ApiEmbedder::new(ApiEmbedderConfig::ollama("unknown", 0));
+        assert!(zero.is_err());
+    }
+
+    #[test]
+    fn test_embedder_for_kind() {
+        let kind = EmbeddingProviderKind::OpenAICompatible {
+            provider: "vllm".to_string(),
+            base_url: "http://127.0.0.1:8000/v1".to_string(),
+            model: "bge-m3".to_string(),
+            dimension: 1024,
+            api_key_env: None,
+        };
+        let embedder = embedder_for_kind(&kind).expect("embedder");
+        assert_eq!(embedder.kind(), kind.provider_name());
+        assert_eq!(embedder.dimension(), 1024);
+
+        let cohere = EmbeddingProviderKind::Cohere {
+            model: "embed-english-v3.0".to_string(),
+            api_key_env: "NONEXISTENT_API_KEY_12345".to_string(),
+        };
+        assert!(embedder_for_kind(&cohere).is_err());
+        assert!(embedder_for_kind(&EmbeddingProviderKind::default()).is_err());
+    }
+
     #[test]
     fn test_embedder_requires_api_key() {
         // Use a custom env var name that doesn't exist
@@ -0,0 +1,657 @@
+//! Async facade over the blocking core API (feature `async`).
+//!
+//! The core library is synchronous by design. [`AsyncMemvid`] moves every call onto Tokio's
+//! blocking pool via `spawn_blocking`, so a memory can be shared by request handlers in an
+//! axum/tonic service without stalling the async worker threads. The handle is cheap to clone;
+//! clones share one `Memvid` behind a mutex, so writes stay single-writer as the format requires.
+//!
+//! Network-bound paths do not need the blocking pool: with `api_embed` enabled,
+//! `OpenAIEmbedder::embed_text_async` / `embed_batch_async` use an async HTTP client directly.
+//! Remote memories behind an [`AsyncRangeFetcher`] (e.g. `AsyncHttpRangeFetcher` with
+//! `remote`) are opened with [`AsyncRemoteMemvid`], which awaits every range read and only uses
+//! the blocking pool for local work (building the Tantivy index, scoring). For blocking
+//! [`RangeFetcher`]s, [`BlockingRemoteAdapter`] moves the whole call onto the blocking pool.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "lex")]
+use std::time::Instant;
+
+use tokio::sync::OnceCell;
+
+use crate::constants::HEADER_SIZE;
+use crate::error::{MemvidError, Result};
+use crate::footer::FOOTER_SIZE;
+use crate::io::remote::{AsyncRangeFetcher, RangeFetcher};
+use crate::memvid::remote::{
+    canonical_sources, check_fetched_len, check_remote_len, check_remote_range,
+    decode_remote_footer, decode_remote_header, decode_remote_payload, decode_remote_toc,
+    payload_ranges, remote_frame_by_id, search_remote_vec, text_without_fetch, vec_index_range,
+};
+#[cfg(feature = "lex")]
+use crate::memvid::remote::{
+    check_remote_search, open_remote_tantivy, remote_analyzer, search_remote_index,
+    tantivy_segment_ranges,
+};
+use crate::memvid::{Memvid, RemoteMemvid};
+use crate::types::{
+    AskRequest, AskResponse, Frame, FrameId, Header, PutOptions, SearchRequest, SearchResponse,
+    Stats, TimelineEntry, TimelineQuery, Toc, VecEmbedder,
+};
+use crate::vec::{VecIndex, VecSearchHit};
+
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|err| MemvidError::Lock(format!("blocking task failed: {err}")))?
+}
+
+fn lock<T>(inner: &Mutex<T>) -> Result<MutexGuard<'_, T>> {
+    inner
+        .lock()
+        .map_err(|_| MemvidError::Lock("async memvid mutex poisoned".into()))
+}
+
+/// Cloneable async handle over a local `.mv2` file.
+#[derive(Clone)]
+pub struct AsyncMemvid {
+    inner: Arc<Mutex<Memvid>>,
+}
+
+impl AsyncMemvid {
+    /// Wrap an already-open memory.
+    #[must_use]
+    pub fn new(memvid: Memvid) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(memvid)),
+        }
+    }
+
+    pub async fn create(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        run_blocking(move || Memvid::create(path))
+            .await
+            .map(Self::new)
+    }
+
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        run_blocking(move || Memvid::open(path))
+            .await
+            .map(Self::new)
+    }
+
+    pub async fn open_read_only(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        run_blocking(move || Memvid::open_read_only(path))
+            .await
+            .map(Self::new)
+    }
+
+    /// Run an arbitrary closure against the underlying `Memvid` on the blocking pool.
+    ///
+    /// Escape hatch for APIs without a dedicated async wrapper.
+    pub async fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Memvid) -> Result<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        run_blocking(move || {
+            let mut memvid = lock(&inner)?;
+            f(&mut memvid)
+        })
+        .await
+    }
+
+    pub async fn put_bytes(&self, payload: Vec<u8>) -> Result<u64> {
+        self.with(move |mem| mem.put_bytes(&payload)).await
+    }
+
+    pub async fn put_bytes_with_options(
+        &self,
+        payload: Vec<u8>,
+        options: PutOptions,
+    ) -> Result<u64> {
+        self.with(move |mem| mem.put_bytes_with_options(&payload, options))
+            .await
+    }
+
+    pub async fn commit(&self) -> Result<()> {
+        self.with(Memvid::commit).await
+    }
+
+    pub async fn search(&self, request: SearchRequest) -> Result<SearchResponse> {
+        self.with(move |mem| mem.search(request)).await
+    }
+
+    pub async fn search_vec(&self, query: Vec<f32>, limit: usize) -> Result<Vec<VecSearchHit>> {
+        self.with(move |mem| mem.search_vec(&query, limit)).await
+    }
+
+    pub async fn ask<E>(&self, request: AskRequest, embedder: Option<Arc<E>>) -> Result<AskResponse>
+    where
+        E: VecEmbedder + Send + Sync + 'static,
+    {
+        self.with(move |mem| mem.ask(request, embedder.as_deref()))
+            .await
+    }
+
+    pub async fn timeline(&self, query: TimelineQuery) -> Result<Vec<TimelineEntry>> {
+        self.with(move |mem| mem.timeline(query)).await
+    }
+
+    pub async fn stats(&self) -> Result<Stats> {
+        self.with(|mem| mem.stats()).await
+    }
+
+    pub async fn frame_text_by_id(&self, frame_id: FrameId) -> Result<String> {
+        self.with(move |mem| mem.frame_text_by_id(frame_id)).await
+    }
+
+    /// Recover the blocking handle once no other clones remain.
+    pub fn into_inner(self) -> Result<Memvid> {
+        Arc::try_unwrap(self.inner)
+            .map_err(|_| MemvidError::Lock("async memvid handle still shared".into()))?
+            .into_inner()
+            .map_err(|_| MemvidError::Lock("async memvid mutex poisoned".into()))
+    }
+}
+
+/// Read-only handle over a remote `.mv2` object whose range reads are awaited.
+///
+/// Async counterpart of [`RemoteMemvid`]: opening touches only the header, commit footer, and
+/// TOC, and index blobs are fetched on first use and cached for the lifetime of the handle.
+/// Methods take `&self`, so concurrent calls (e.g. through an `Arc`) overlap their fetches;
+/// the first query to need an index fetches it while the others wait for the same load.
+pub struct AsyncRemoteMemvid<F> {
+    url: String,
+    fetcher: F,
+    header: Header,
+    toc: Arc<Toc>,
+    generation: u64,
+    file_len: u64,
+    vec_index: OnceCell<Option<Arc<VecIndex>>>,
+    #[cfg(feature = "lex")]
+    tantivy: OnceCell<Option<Arc<crate::search::TantivyEngine>>>,
+    bytes_fetched: AtomicU64,
+    range_requests: AtomicU64,
+}
+
+/// Bounds-checked range read that rejects short responses from the fetcher.
+async fn fetch_exact<F: AsyncRangeFetcher>(
+    fetcher: &F,
+    file_len: u64,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>> {
+    check_remote_range(file_len, offset, length)?;
+    let bytes = fetcher.fetch(offset, length).await?;
+    check_fetched_len(&bytes, length)?;
+    Ok(bytes)
+}
+
+impl<F: AsyncRangeFetcher> AsyncRemoteMemvid<F> {
+    /// Open a memory stored behind `fetcher` in read-only mode.
+    ///
+    /// `url` is only used for diagnostics; all IO goes through the fetcher.
+    pub async fn open(url: impl Into<String>, fetcher: F) -> Result<Self> {
+        let file_len = fetcher.len().await?;
+        check_remote_len(file_len)?;
+        let header =
+            decode_remote_header(&fetch_exact(&fetcher, file_len, 0, HEADER_SIZE as u64).await?)?;
+        let footer_offset = file_len - FOOTER_SIZE as u64;
+        let footer = decode_remote_footer(
+            footer_offset,
+            &fetch_exact(&fetcher, file_len, footer_offset, FOOTER_SIZE as u64).await?,
+        )?;
+        let toc_offset = footer_offset - footer.toc_len;
+        let toc = decode_remote_toc(
+            &footer,
+            &fetch_exact(&fetcher, file_len, toc_offset, footer.toc_len).await?,
+        )?;
+
+        Ok(Self {
+            url: url.into(),
+            fetcher,
+            header,
+            toc: Arc::new(toc),
+            generation: footer.generation,
+            file_len,
+            vec_index: OnceCell::new(),
+            #[cfg(feature = "lex")]
+            tantivy: OnceCell::new(),
+            bytes_fetched: AtomicU64::new(HEADER_SIZE as u64 + FOOTER_SIZE as u64 + footer.toc_len),
+            range_requests: AtomicU64::new(3),
+        })
+    }
+
+    async fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        let bytes = fetch_exact(&self.fetcher, self.file_len, offset, length).await?;
+        self.bytes_fetched.fetch_add(length, Ordering::Relaxed);
+        self.range_requests.fetch_add(1, Ordering::Relaxed);
+        Ok(bytes)
+    }
+
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    #[must_use]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    #[must_use]
+    pub fn toc(&self) -> &Toc {
+        &self.toc
+    }
+
+    /// Commit generation recorded in the remote footer.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    #[must_use]
+    pub fn frame_count(&self) -> usize {
+        self.toc.frames.len()
+    }
+
+    /// Total bytes pulled through the fetcher since the handle was opened.
+    #[must_use]
+    pub fn bytes_fetched(&self) -> u64 {
+        self.bytes_fetched.load(Ordering::Relaxed)
+    }
+
+    /// Number of range requests issued since the handle was opened.
+    #[must_use]
+    pub fn range_requests(&self) -> u64 {
+        self.range_requests.load(Ordering::Relaxed)
+    }
+
+    pub fn frame_by_id(&self, frame_id: FrameId) -> Result<Frame> {
+        remote_frame_by_id(&self.toc, frame_id).cloned()
+    }
+
+    /// Decoded canonical bytes for a frame, fetched with one range request per payload.
+    pub async fn frame_canonical_payload(&self, frame_id: FrameId) -> Result<Vec<u8>> {
+        let frame = remote_frame_by_id(&self.toc, frame_id)?;
+        self.frame_canonical_bytes(frame).await
+    }
+
+    /// Full text of a frame, preferring the indexed search text (no payload fetch).
+    pub async fn frame_text_by_id(&self, frame_id: FrameId) -> Result<String> {
+        let frame = remote_frame_by_id(&self.toc, frame_id)?;
+        if let Some(text) = text_without_fetch(frame) {
+            return Ok(text);
+        }
+        let bytes = self.frame_canonical_bytes(frame).await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn frame_canonical_bytes(&self, frame: &Frame) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        for source in canonical_sources(&self.toc, frame)? {
+            let Some(ranges) = payload_ranges(&self.toc, &self.header, source)? else {
+                continue;
+            };
+            let mut raw = Vec::new();
+            for (offset, length) in ranges {
+                raw.extend(self.fetch(offset, length).await?);
+            }
+            buffer.extend_from_slice(&decode_remote_payload(source, &raw)?);
+        }
+        Ok(buffer)
+    }
+
+    async fn vec_index(&self) -> Result<Option<Arc<VecIndex>>> {
+        self.vec_index
+            .get_or_try_init(|| async {
+                let Some((offset, length)) = vec_index_range(&self.toc) else {
+                    return Ok(None);
+                };
+                let bytes = self.fetch(offset, length).await?;
+                Ok(Some(Arc::new(VecIndex::decode(&bytes)?)))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Nearest-neighbour search over the embedded vector index. The index blob is fetched
+    /// on first use; returns an empty list when the file has no vector index.
+    pub async fn search_vec(&self, query: Vec<f32>, top_k: usize) -> Result<Vec<VecSearchHit>> {
+        let index = self.vec_index().await?;
+        let toc = Arc::clone(&self.toc);
+        run_blocking(move || Ok(search_remote_vec(&toc, index.as_deref(), &query, top_k))).await
+    }
+}
+
+#[cfg(feature = "lex")]
+impl<F: AsyncRangeFetcher> AsyncRemoteMemvid<F> {
+    async fn tantivy(&self) -> Result<Option<Arc<crate::search::TantivyEngine>>> {
+        self.tantivy
+            .get_or_try_init(|| async {
+                let ranges = tantivy_segment_ranges(&self.toc);
+                if ranges.is_empty() {
+                    return Ok(None);
+                }
+                let mut segments = Vec::with_capacity(ranges.len());
+                for (path, offset, length) in ranges {
+                    let bytes = if length > 0 {
+                        self.fetch(offset, length).await?
+                    } else {
+                        Vec::new()
+                    };
+                    segments.push((path, bytes));
+                }
+                let analyzer = remote_analyzer(&self.toc)?;
+                let engine = run_blocking(move || open_remote_tantivy(&analyzer, segments)).await?;
+                Ok(Some(Arc::new(engine)))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Lexical search over the embedded Tantivy index.
+    ///
+    /// Same semantics as [`RemoteMemvid::search`]: segments are fetched on first use,
+    /// snippets come from the indexed text, and unsupported options are rejected.
+    pub async fn search(&self, request: SearchRequest) -> Result<SearchResponse> {
+        let start_time = Instant::now();
+        check_remote_search(&request)?;
+        let engine = self.tantivy().await?;
+        let toc = Arc::clone(&self.toc);
+        run_blocking(move || search_remote_index(&toc, engine.as_deref(), &request, start_time))
+            .await
+    }
+}
+
+/// Cloneable adapter that lets async code call a blocking [`RemoteMemvid`].
+///
+/// This is not async I/O; prefer [`AsyncRemoteMemvid`] when an [`AsyncRangeFetcher`] is
+/// available. [`RangeFetcher`]s are blocking, so every call, metadata-only ones
+/// included, runs on Tokio's blocking pool and holds one of its threads (and the handle's
+/// mutex) until every range request it makes has finished; concurrent calls therefore queue on
+/// the mutex rather than overlapping their fetches. It keeps network waits off the async worker
+/// threads, nothing more.
+#[derive(Clone)]
+pub struct BlockingRemoteAdapter {
+    inner: Arc<Mutex<RemoteMemvid>>,
+}
+
+impl BlockingRemoteAdapter {
+    pub async fn open<F>(url: impl Into<String>, fetcher: F) -> Result<Self>
+    where
+        F: RangeFetcher + 'static,
+    {
+        let url = url.into();
+        let remote = run_blocking(move || Memvid::open_remote(url, fetcher)).await?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(remote)),
+        })
+    }
+
+    pub async fn with<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut RemoteMemvid) -> Result<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        run_blocking(move || {
+            let mut remote = lock(&inner)?;
+            f(&mut remote)
+        })
+        .await
+    }
+
+    #[cfg(feature = "lex")]
+    pub async fn search(&self, request: SearchRequest) -> Result<SearchResponse> {
+        self.with(move |remote| remote.search(request)).await
+    }
+
+    pub async fn search_vec(&self, query: Vec<f32>, top_k: usize) -> Result<Vec<VecSearchHit>> {
+        self.with(move |remote| remote.search_vec(&query, top_k))
+            .await
+    }
+
+    pub async fn timeline(&self, query: TimelineQuery) -> Result<Vec<TimelineEntry>> {
+        self.with(move |remote| remote.timeline(query)).await
+    }
+
+    pub async fn frame_canonical_payload(&self, frame_id: FrameId) -> Result<Vec<u8>> {
+        self.with(move |remote| remote.frame_canonical_payload(frame_id))
+            .await
+    }
+
+    pub async fn frame_text_by_id(&self, frame_id: FrameId) -> Result<String> {
+        self.with(move |remote| remote.frame_text_by_id(frame_id))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime")
+    }
+
+    #[test]
+    fn async_put_commit_and_reopen() {
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("async.mv2");
+        runtime().block_on(async {
+            let mem = AsyncMemvid::create(&path).await.expect("create");
+            let handle = mem.clone();
+            handle
+                .put_bytes(b"async payload".to_vec())
+                .await
+                .expect("put");
+            mem.commit().await.expect("commit");
+            assert_eq!(mem.stats().await.expect("stats").frame_count, 1);
+            drop(handle);
+            drop(mem.into_inner().expect("unique"));
+
+            let reopened = AsyncMemvid::open_read_only(&path).await.expect("reopen");
+            let text = reopened.frame_text_by_id(0).await.expect("text");
+            assert!(text.starts_with("async payload"));
+        });
+    }
+
+    #[test]
+    fn blocking_remote_adapter_reads_frames() {
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("remote.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.put_bytes(b"fetched by range").expect("put");
+        mem.commit().expect("commit");
+        drop(mem);
+
+        runtime().block_on(async {
+            let fetcher = crate::FileRangeFetcher::open(&path).expect("fetcher");
+            let remote = BlockingRemoteAdapter::open("file://remote.mv2", fetcher)
+                .await
+                .expect("open");
+            let text = remote.frame_text_by_id(0).await.expect("text");
+            assert!(text.starts_with("fetched by range"));
+        });
+    }
+
+    /// Async view over a local file, standing in for an object-store client.
+    struct FileAsyncFetcher(crate::FileRangeFetcher);
+
+    impl AsyncRangeFetcher for FileAsyncFetcher {
+        async fn len(&self) -> Result<u64> {
+            self.0.len()
+        }
+
+        async fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+            self.0.fetch(offset, length)
+        }
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn async_remote_memvid_searches_and_reads_payloads() {
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("async-remote.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        mem.put_bytes(b"the quick brown fox").expect("put");
+        mem.put_bytes(b"lazy dogs sleep all day").expect("put");
+        mem.commit().expect("commit");
+        drop(mem);
+
+        runtime().block_on(async {
+            let fetcher = FileAsyncFetcher(crate::FileRangeFetcher::open(&path).expect("fetcher"));
+            let remote = Arc::new(
+                AsyncRemoteMemvid::open("file://async-remote.mv2", fetcher)
+                    .await
+                    .expect("open"),
+            );
+            assert_eq!(remote.frame_count(), 2);
+            assert_eq!(remote.range_requests(), 3);
+
+            let searches: Vec<_> = ["fox", "dogs"]
+                .into_iter()
+                .map(|query| {
+                    let remote = Arc::clone(&remote);
+                    tokio::spawn(async move { remote.search(search_request(query)).await })
+                })
+                .collect();
+            for (search, expected) in searches.into_iter().zip(["fox", "dogs"]) {
+                let response = search.await.expect("task").expect("search");
+                assert_eq!(response.hits.len(), 1);
+                assert!(response.hits[0].text.contains(expected));
+            }
+            let after_search = remote.range_requests();
+            assert!(after_search > 3, "segments fetched on first search");
+
+            let payload = remote.frame_canonical_payload(1).await.expect("payload");
+            assert!(payload.starts_with(b"lazy dogs"));
+            assert!(remote.range_requests() > after_search);
+            assert!(
+                remote
+                    .search_vec(vec![0.0; 4], 3)
+                    .await
+                    .expect("vec")
+                    .is_empty()
+            );
+        });
+    }
+
+    #[cfg(feature = "lex")]
+    fn search_request(query: &str) -> SearchRequest {
+        SearchRequest {
+            query: query.into(),
+            top_k: 5,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        }
+    }
+
+    /// Serve `bytes` over HTTP, answering `HEAD` and single `Range` requests.
+    #[cfg(feature = "remote")]
+    fn serve_ranges(bytes: Vec<u8>) -> std::net::SocketAddr {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.expect("accept");
+                let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).expect("request line");
+                let mut range = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("header");
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        let (start, end) = value.trim().split_once('-').expect("range");
+                        range = Some((
+                            start.parse::<usize>().expect("start"),
+                            end.parse::<usize>().expect("end"),
+                        ));
+                    }
+                }
+                let head = request_line.starts_with("HEAD");
+                let (status, body) = match range {
+                    Some((start, end)) if !head => ("206 Partial Content", &bytes[start..=end]),
+                    _ => ("200 OK", &bytes[..]),
+                };
+                let length = body.len();
+                let body = if head { &body[..0] } else { body };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {length}\r\nConnection: close\r\n\r\n"
+                )
+                .expect("head");
+                stream.write_all(body).expect("body");
+            }
+        });
+        addr
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn async_http_range_fetcher_opens_a_served_memory() {
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("served.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.put_bytes(b"served over http").expect("put");
+        mem.commit().expect("commit");
+        drop(mem);
+        let addr = serve_ranges(std::fs::read(&path).expect("read"));
+
+        runtime().block_on(async {
+            let url = format!("http://{addr}/served.mv2");
+            let fetcher = crate::AsyncHttpRangeFetcher::new(&url).expect("fetcher");
+            assert_eq!(
+                fetcher.len().await.expect("len"),
+                std::fs::metadata(&path).expect("meta").len()
+            );
+            let remote = AsyncRemoteMemvid::open(url, fetcher).await.expect("open");
+            let text = remote.frame_text_by_id(0).await.expect("text");
+            assert!(text.starts_with("served over http"));
+            let payload = remote.frame_canonical_payload(0).await.expect("payload");
+            assert!(payload.starts_with(b"served over http"));
+        });
+    }
+}
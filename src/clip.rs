@@ -28,6 +28,7 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use crate::inference_device::InferenceDevice;
 use crate::{MemvidError, Result, types::FrameId};
 
 // ============================================================================
@@ -119,6 +120,9 @@ pub const MIN_COLOR_VARIANCE: f32 = 0.01;
 /// Model unload timeout (5 minutes idle)
 pub const MODEL_UNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
 
+/// Default number of images per inference call in batched encoding
+pub const DEFAULT_CLIP_BATCH_SIZE: usize = 16;
+
 // ============================================================================
 // Bincode Configuration
 // ============================================================================
@@ -515,6 +519,10 @@ pub struct ClipConfig {
     pub models_dir: PathBuf,
     /// Whether to run in offline mode (no downloads)
     pub offline: bool,
+    /// Device to run the encoders on; falls back to CPU if unavailable
+    pub device: InferenceDevice,
+    /// Images per inference call when embedding in batches
+    pub batch_size: usize,
 }
 
 impl Default for ClipConfig {
@@ -531,10 +539,17 @@ impl Default for ClipConfig {
 
         let offline = std::env::var("MEMVID_OFFLINE").is_ok();
 
+        let device = std::env::var("MEMVID_CLIP_DEVICE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default();
+
         Self {
             model_name,
             models_dir,
             offline,
+            device,
+            batch_size: DEFAULT_CLIP_BATCH_SIZE,
         }
     }
 }
@@ -586,9 +601,11 @@ impl From<ClipError> for MemvidError {
 #[cfg(feature = "clip")]
 mod model {
     use super::*;
+    use crate::inference_device::build_session;
     use image::{DynamicImage, GenericImageView, imageops::FilterType};
     use ndarray::{Array, Array4};
-    use ort::session::{Session, builder::GraphOptimizationLevel};
+    use ndarray::{Axis, concatenate};
+    use ort::session::Session;
     use ort::value::Tensor;
     use std::sync::Mutex;
     use std::time::Instant;
@@ -609,6 +626,8 @@ mod model {
         tokenizer: Mutex<Option<Tokenizer>>,
         /// Last time the model was used (for idle unloading)
         last_used: Mutex<Instant>,
+        /// Device the most recently loaded encoder runs on
+        active_device: Mutex<Option<InferenceDevice>>,
     }
 
     impl ClipModel {
@@ -623,6 +642,7 @@ mod model {
                 text_session: Mutex::new(None),
                 tokenizer: Mutex::new(None),
                 last_used: Mutex::new(Instant::now()),
+                active_device: Mutex::new(None),
             })
         }
 
@@ -636,6 +656,11 @@ mod model {
             self.model_info
         }
 
+        /// Get the configuration
+        pub fn config(&self) -> &ClipConfig {
+            &self.config
+        }
+
         /// Get embedding dimensions
         pub fn dims(&self) -> u32 {
             self.model_info.dims
@@ -740,27 +765,20 @@ mod model {
             // Suppress stderr during ONNX session creation (macOS emits harmless warnings)
             let _stderr_guard = stderr_suppress::StderrSuppressor::new().ok();
 
-            let session = Session::builder()
-                .map_err(|e| ClipError::InferenceError {
-                    cause: e.to_string(),
-                })?
-                .with_optimization_level(GraphOptimizationLevel::Level3)
-                .map_err(|e| ClipError::InferenceError {
-                    cause: e.to_string(),
-                })?
-                .with_intra_threads(4)
-                .map_err(|e| ClipError::InferenceError {
-                    cause: e.to_string(),
-                })?
-                .commit_from_file(&vision_path)
-                .map_err(|e| ClipError::InferenceError {
-                    cause: format!("Failed to load vision model: {}", e),
+            let (session, device) =
+                build_session(self.config.device, &vision_path, 4).map_err(|e| {
+                    ClipError::InferenceError {
+                        cause: format!("Failed to load vision model: {}", e),
+                    }
                 })?;
 
             // _stderr_guard dropped here, restoring stderr
 
             *session_guard = Some(session);
-            tracing::info!(model = %self.model_info.name, "CLIP vision model loaded");
+            if let Ok(mut active) = self.active_device.lock() {
+                *active = Some(device);
+            }
+            tracing::info!(model = %self.model_info.name, device = %device, "CLIP vision model loaded");
 
             Ok(())
         }
@@ -783,27 +801,20 @@ mod model {
             // Suppress stderr during ONNX session creation (macOS emits harmless warnings)
             let _stderr_guard = stderr_suppress::StderrSuppressor::new().ok();
 
-            let session = Session::builder()
-                .map_err(|e| ClipError::InferenceError {
-                    cause: e.to_string(),
-                })?
-                .with_optimization_level(GraphOptimizationLevel::Level3)
-                .map_err(|e| ClipError::InferenceError {
-                    cause: e.to_string(),
-                })?
-                .with_intra_threads(4)
-                .map_err(|e| ClipError::InferenceError {
-                    cause: e.to_string(),
-                })?
-                .commit_from_file(&text_path)
-                .map_err(|e| ClipError::InferenceError {
-                    cause: format!("Failed to load text model: {}", e),
+            let (session, device) =
+                build_session(self.config.device, &text_path, 4).map_err(|e| {
+                    ClipError::InferenceError {
+                        cause: format!("Failed to load text model: {}", e),
+                    }
                 })?;
 
             // _stderr_guard dropped here, restoring stderr
 
             *session_guard = Some(session);
-            tracing::info!(model = %self.model_info.name, "CLIP text model loaded");
+            if let Ok(mut active) = self.active_device.lock() {
+                *active = Some(device);
+            }
+            tracing::info!(model = %self.model_info.name, device = %device, "CLIP text model loaded");
 
             Ok(())
         }
@@ -893,11 +904,50 @@ mod model {
 
         /// Encode an image to CLIP embedding
         pub fn encode_image(&self, image: &DynamicImage) -> Result<Vec<f32>> {
+            self.encode_images(std::slice::from_ref(image))?
+                .pop()
+                .ok_or_else(|| {
+                    ClipError::InferenceError {
+                        cause: "Vision model produced no embedding".to_string(),
+                    }
+                    .into()
+                })
+        }
+
+        /// Encode several images, `config.batch_size` per inference call
+        pub fn encode_images(&self, images: &[DynamicImage]) -> Result<Vec<Vec<f32>>> {
             // Ensure vision session is loaded
             self.load_vision_session()?;
 
-            // Preprocess the image
-            let pixel_values = self.preprocess_image(image);
+            let mut embeddings = Vec::with_capacity(images.len());
+            for chunk in images.chunks(self.config.batch_size.max(1)) {
+                match self.run_vision(chunk) {
+                    Ok(batch) => embeddings.extend(batch),
+                    // Some exported models have a fixed batch dimension of 1
+                    Err(err) if chunk.len() > 1 => {
+                        tracing::debug!(error = %err, "Batched vision inference failed, encoding one by one");
+                        for image in chunk {
+                            embeddings.extend(self.run_vision(std::slice::from_ref(image))?);
+                        }
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(embeddings)
+        }
+
+        /// Run one vision inference call, returning one normalized embedding per image
+        fn run_vision(&self, images: &[DynamicImage]) -> Result<Vec<Vec<f32>>> {
+            // Preprocess the images and stack them along the batch axis
+            let arrays: Vec<Array4<f32>> = images
+                .iter()
+                .map(|image| self.preprocess_image(image))
+                .collect();
+            let views: Vec<_> = arrays.iter().map(|array| array.view()).collect();
+            let pixel_values =
+                concatenate(Axis(0), &views).map_err(|e| ClipError::InferenceError {
+                    cause: format!("Failed to stack image batch: {}", e),
+                })?;
 
             // Update last used timestamp
             if let Ok(mut last) = self.last_used.lock() {
@@ -955,19 +1005,41 @@ mod model {
                         cause: format!("Failed to extract embeddings: {}", e),
                     })?;
 
-            // Get the embedding from the raw data
-            let embedding: Vec<f32> = data.to_vec();
-            if embedding.iter().any(|v| !v.is_finite()) {
+            // One embedding row per image
+            if images.is_empty() || data.is_empty() || data.len() % images.len() != 0 {
                 return Err(ClipError::InferenceError {
-                    cause: "Vision embedding contains non-finite values".to_string(),
+                    cause: format!(
+                        "Vision model returned {} values for {} images",
+                        data.len(),
+                        images.len()
+                    ),
                 }
                 .into());
             }
-            let normalized = l2_normalize(&embedding);
+            let row_len = data.len() / images.len();
+            let mut embeddings = Vec::with_capacity(images.len());
+            for embedding in data.chunks_exact(row_len) {
+                if embedding.iter().any(|v| !v.is_finite()) {
+                    return Err(ClipError::InferenceError {
+                        cause: "Vision embedding contains non-finite values".to_string(),
+                    }
+                    .into());
+                }
+                embeddings.push(l2_normalize(embedding));
+            }
 
-            tracing::debug!(dims = normalized.len(), "Generated CLIP image embedding");
+            tracing::debug!(
+                images = images.len(),
+                dims = row_len,
+                "Generated CLIP image embeddings"
+            );
 
-            Ok(normalized)
+            Ok(embeddings)
+        }
+
+        /// Device the most recently loaded encoder runs on, or `None` before first use
+        pub fn active_device(&self) -> Option<InferenceDevice> {
+            self.active_device.lock().ok().and_then(|device| *device)
         }
 
         /// Encode image bytes to CLIP embedding
@@ -1166,6 +1238,9 @@ mod model {
             if let Ok(mut guard) = self.tokenizer.lock() {
                 *guard = None;
             }
+            if let Ok(mut guard) = self.active_device.lock() {
+                *guard = None;
+            }
             tracing::debug!(model = %self.model_info.name, "CLIP sessions unloaded");
             Ok(())
         }
@@ -1588,8 +1663,21 @@ impl ClipEmbeddingProvider for ClipModel {
 
     fn embed_image_batch(&self, paths: &[&Path]) -> Result<Vec<Vec<f32>>> {
         let mut embeddings = Vec::with_capacity(paths.len());
-        for path in paths {
-            embeddings.push(self.encode_image_file(path)?);
+        // Decode one batch at a time to bound memory
+        for chunk in paths.chunks(self.config().batch_size.max(1)) {
+            let images = chunk
+                .iter()
+                .map(|path| {
+                    image::open(path).map_err(|e| {
+                        ClipError::ImageDecodeError {
+                            path: path.to_path_buf(),
+                            cause: e.to_string(),
+                        }
+                        .into()
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            embeddings.extend(self.encode_images(&images)?);
         }
         Ok(embeddings)
     }
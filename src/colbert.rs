@@ -0,0 +1,363 @@
+//! ColBERT [`TokenEncoder`](crate::TokenEncoder) for late-interaction reranking.
+//!
+//! [`ColbertEncoder`] runs a ColBERT model exported to ONNX, loaded from the models directory
+//! like the cross-encoder reranker. Passages and queries are tagged with ColBERT's document and
+//! query marker tokens, queries are padded with `[MASK]` to a fixed length (query
+//! augmentation), and every token embedding is L2-normalized so MaxSim scores are cosines.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use memvid_core::colbert::{ColbertConfig, ColbertEncoder};
+//! use memvid_core::RerankerConfig;
+//!
+//! mem.set_token_encoder(Arc::new(ColbertEncoder::new(ColbertConfig::default())?));
+//! mem.index_token_embeddings(&["mv2://contracts/"], 16)?;
+//! request.rerank = Some(RerankerConfig::late_interaction());
+//! let response = mem.search(request)?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ndarray::Array;
+use ort::session::Session;
+use ort::value::Tensor;
+use tokenizers::{Tokenizer, TruncationDirection, TruncationParams, TruncationStrategy};
+
+use crate::error::{MemvidError, Result};
+use crate::inference_device::{InferenceDevice, build_session};
+use crate::types::token_track::{TokenEmbeddings, TokenEncoder};
+
+/// ColBERT model info for the models registry
+#[derive(Debug, Clone)]
+pub struct ColbertModelInfo {
+    /// Model identifier, also the directory name under the models directory
+    pub name: &'static str,
+    /// URL for ONNX model
+    pub model_url: &'static str,
+    /// URL for tokenizer JSON
+    pub tokenizer_url: &'static str,
+    /// Model size in MB
+    pub size_mb: f32,
+    /// Components per token embedding
+    pub dims: usize,
+    /// Maximum sequence length of a passage
+    pub max_seq_len: usize,
+    /// Whether this is the default model
+    pub is_default: bool,
+}
+
+/// Available ColBERT models
+pub static COLBERT_MODELS: &[ColbertModelInfo] = &[
+    ColbertModelInfo {
+        name: "answerai-colbert-small-v1",
+        model_url: "https://huggingface.co/answerdotai/answerai-colbert-small-v1/resolve/main/vespa_colbert.onnx",
+        tokenizer_url: "https://huggingface.co/answerdotai/answerai-colbert-small-v1/resolve/main/tokenizer.json",
+        size_mb: 130.0,
+        dims: 96,
+        max_seq_len: 512,
+        is_default: true,
+    },
+    ColbertModelInfo {
+        name: "colbertv2.0",
+        model_url: "https://huggingface.co/colbert-ir/colbertv2.0/resolve/main/model.onnx",
+        tokenizer_url: "https://huggingface.co/colbert-ir/colbertv2.0/resolve/main/tokenizer.json",
+        size_mb: 440.0,
+        dims: 128,
+        max_seq_len: 512,
+        is_default: false,
+    },
+];
+
+/// Get ColBERT model info by name
+#[must_use]
+pub fn get_colbert_model_info(name: &str) -> Option<&'static ColbertModelInfo> {
+    COLBERT_MODELS.iter().find(|m| m.name == name)
+}
+
+/// Get default ColBERT model info
+#[must_use]
+pub fn default_colbert_model_info() -> &'static ColbertModelInfo {
+    COLBERT_MODELS
+        .iter()
+        .find(|m| m.is_default)
+        .unwrap_or(&COLBERT_MODELS[0])
+}
+
+/// Get the expected path for a ColBERT model in the models directory
+#[must_use]
+pub fn colbert_model_path(models_dir: &Path, name: &str) -> PathBuf {
+    models_dir.join(name).join("model.onnx")
+}
+
+/// Get the expected path for a ColBERT tokenizer in the models directory
+#[must_use]
+pub fn colbert_tokenizer_path(models_dir: &Path, name: &str) -> PathBuf {
+    models_dir.join(name).join("tokenizer.json")
+}
+
+/// Token id ColBERT inserts after `[CLS]` in queries (`[unused0]`).
+const QUERY_MARKER_TOKEN_ID: i64 = 1;
+/// Token id ColBERT inserts after `[CLS]` in passages (`[unused1]`).
+const DOCUMENT_MARKER_TOKEN_ID: i64 = 2;
+
+/// Configuration for the local ColBERT encoder
+#[derive(Debug, Clone)]
+pub struct ColbertConfig {
+    /// Model name from [`COLBERT_MODELS`]
+    pub model_name: String,
+    /// Directory holding `<model_name>/model.onnx` and `<model_name>/tokenizer.json`
+    pub models_dir: PathBuf,
+    /// Device to run the model on; falls back to CPU if unavailable (default: auto)
+    pub device: InferenceDevice,
+    /// Passages per inference call (default: 8)
+    pub batch_size: usize,
+    /// Tokens every query is padded to with `[MASK]` (default: 32)
+    pub query_len: usize,
+}
+
+impl Default for ColbertConfig {
+    fn default() -> Self {
+        let models_dir = dirs_next::cache_dir()
+            .map(|p| p.join("memvid").join("colbert-models"))
+            .unwrap_or_else(|| PathBuf::from(".memvid-cache/colbert-models"));
+        Self {
+            model_name: default_colbert_model_info().name.to_string(),
+            models_dir,
+            device: InferenceDevice::default(),
+            batch_size: 8,
+            query_len: 32,
+        }
+    }
+}
+
+fn encoding_error(reason: String) -> MemvidError {
+    MemvidError::EmbeddingFailed {
+        reason: reason.into(),
+    }
+}
+
+/// Token encoder running a ColBERT model locally.
+pub struct ColbertEncoder {
+    model_info: &'static ColbertModelInfo,
+    kind: String,
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    mask_token_id: i64,
+    batch_size: usize,
+    query_len: usize,
+    device: InferenceDevice,
+}
+
+impl ColbertEncoder {
+    /// Load the model and tokenizer, returning download instructions if they are missing.
+    pub fn new(config: ColbertConfig) -> Result<Self> {
+        let model_info = get_colbert_model_info(&config.model_name).ok_or_else(|| {
+            encoding_error(format!(
+                "unknown ColBERT model '{}'; available: {}",
+                config.model_name,
+                COLBERT_MODELS
+                    .iter()
+                    .map(|m| m.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        let model_path = colbert_model_path(&config.models_dir, model_info.name);
+        let tokenizer_path = colbert_tokenizer_path(&config.models_dir, model_info.name);
+        for (path, url) in [
+            (&model_path, model_info.model_url),
+            (&tokenizer_path, model_info.tokenizer_url),
+        ] {
+            if !path.exists() {
+                return Err(encoding_error(format!(
+                    "ColBERT file not found at {}. Please download manually:\n\
+                     mkdir -p {} && curl -L '{}' -o '{}'",
+                    path.display(),
+                    path.parent().unwrap_or(&config.models_dir).display(),
+                    url,
+                    path.display()
+                )));
+            }
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| encoding_error(format!("Failed to load tokenizer: {e}")))?;
+        tokenizer.with_padding(None);
+        // One position is taken by the marker token.
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: model_info.max_seq_len - 1,
+                strategy: TruncationStrategy::LongestFirst,
+                stride: 0,
+                direction: TruncationDirection::Right,
+            }))
+            .map_err(|e| encoding_error(format!("Failed to apply truncation config: {e}")))?;
+        let mask_token_id = tokenizer
+            .token_to_id("[MASK]")
+            .map(i64::from)
+            .ok_or_else(|| encoding_error("tokenizer has no [MASK] token".to_string()))?;
+
+        let (session, device) = build_session(config.device, &model_path, 4)
+            .map_err(|e| encoding_error(format!("Failed to load ColBERT model: {e}")))?;
+        tracing::info!(model = %model_info.name, device = %device, "ColBERT encoder loaded");
+
+        Ok(Self {
+            model_info,
+            kind: format!("colbert/{}", model_info.name),
+            session: Mutex::new(session),
+            tokenizer,
+            mask_token_id,
+            batch_size: config.batch_size.max(1),
+            query_len: config.query_len.clamp(1, model_info.max_seq_len),
+            device,
+        })
+    }
+
+    /// Get model info
+    #[must_use]
+    pub fn model_info(&self) -> &'static ColbertModelInfo {
+        self.model_info
+    }
+
+    /// Device the session is running on
+    #[must_use]
+    pub fn device(&self) -> InferenceDevice {
+        self.device
+    }
+
+    /// Normalized embeddings of each text's attended tokens, in input order
+    fn encode_texts(&self, texts: &[&str], query: bool) -> Result<Vec<TokenEmbeddings>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| encoding_error(format!("Tokenization failed: {e}")))?;
+
+        let marker = if query {
+            QUERY_MARKER_TOKEN_ID
+        } else {
+            DOCUMENT_MARKER_TOKEN_ID
+        };
+        let mut rows: Vec<Vec<i64>> = Vec::with_capacity(encodings.len());
+        for encoding in &encodings {
+            let ids = encoding.get_ids();
+            let mut row = Vec::with_capacity(ids.len() + 1);
+            row.extend(ids.first().map(|id| i64::from(*id)));
+            row.push(marker);
+            row.extend(ids.iter().skip(1).map(|id| i64::from(*id)));
+            if query && row.len() < self.query_len {
+                row.resize(self.query_len, self.mask_token_id);
+            }
+            rows.push(row);
+        }
+
+        let batch_size = rows.len();
+        let max_length = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut input_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        let mut attention_mask: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        for row in &rows {
+            let padding = max_length - row.len();
+            input_ids.extend(row);
+            input_ids.extend(std::iter::repeat_n(0, padding));
+            // Query [MASK] padding is attended to: that is ColBERT's query augmentation.
+            attention_mask.extend(std::iter::repeat_n(1, row.len()));
+            attention_mask.extend(std::iter::repeat_n(0, padding));
+        }
+        let mask = attention_mask.clone();
+        let token_type_ids = vec![0i64; batch_size * max_length];
+
+        let tensor = |name: &str, values: Vec<i64>| {
+            Array::from_shape_vec((batch_size, max_length), values)
+                .map_err(|e| e.to_string())
+                .and_then(|array| Tensor::from_array(array).map_err(|e| e.to_string()))
+                .map_err(|e| encoding_error(format!("Failed to create {name} tensor: {e}")))
+        };
+        let input_ids = tensor("input_ids", input_ids)?;
+        let attention_mask = tensor("attention_mask", attention_mask)?;
+        let token_type_ids = tensor("token_type_ids", token_type_ids)?;
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| MemvidError::Lock("Failed to lock session".into()))?;
+        let input_names: Vec<String> = session.inputs.iter().map(|i| i.name.clone()).collect();
+        let output_name = session
+            .outputs
+            .first()
+            .map_or_else(|| "last_hidden_state".to_string(), |o| o.name.clone());
+        let outputs = if input_names.len() >= 3 {
+            session.run(ort::inputs![
+                input_names[0].clone() => input_ids,
+                input_names[1].clone() => attention_mask,
+                input_names[2].clone() => token_type_ids
+            ])
+        } else {
+            session.run(ort::inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask
+            ])
+        }
+        .map_err(|e| encoding_error(format!("ColBERT inference failed: {e}")))?;
+
+        let (shape, data) = outputs[output_name.as_str()]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| encoding_error(format!("Failed to extract token embeddings: {e}")))?;
+        // Token embeddings are [batch, tokens, dims]
+        let dims = shape
+            .get(2)
+            .and_then(|dim| usize::try_from(*dim).ok())
+            .unwrap_or(0);
+        if dims != self.model_info.dims || data.len() != batch_size * max_length * dims {
+            return Err(encoding_error(format!(
+                "unexpected token embedding shape: {shape:?}"
+            )));
+        }
+
+        let mut embeddings = Vec::with_capacity(batch_size);
+        for row in 0..batch_size {
+            let tokens = (0..max_length)
+                .filter(|token| mask[row * max_length + token] != 0)
+                .map(|token| {
+                    let start = (row * max_length + token) * dims;
+                    let mut vector = data[start..start + dims].to_vec();
+                    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+                    if norm > 0.0 {
+                        for v in &mut vector {
+                            *v /= norm;
+                        }
+                    }
+                    vector
+                });
+            embeddings.push(TokenEmbeddings::new(dims, tokens)?);
+        }
+        Ok(embeddings)
+    }
+}
+
+impl TokenEncoder for ColbertEncoder {
+    fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    fn dimension(&self) -> usize {
+        self.model_info.dims
+    }
+
+    fn encode(&self, text: &str) -> Result<TokenEmbeddings> {
+        Ok(self.encode_batch(&[text])?.pop().unwrap_or_default())
+    }
+
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<TokenEmbeddings>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            embeddings.extend(self.encode_texts(batch, false)?);
+        }
+        Ok(embeddings)
+    }
+
+    fn encode_query(&self, text: &str) -> Result<TokenEmbeddings> {
+        Ok(self.encode_texts(&[text], true)?.pop().unwrap_or_default())
+    }
+}
@@ -16,6 +16,9 @@ pub const SPEC_MAJOR: u8 = 2;
 pub const SPEC_MINOR: u8 = 1;
 /// Combined two-byte specification version encoded in headers.
 pub const SPEC_VERSION: u16 = ((SPEC_MAJOR as u16) << 8) | SPEC_MINOR as u16;
+/// Header version written once the WAL has been relocated into an extent past the data region.
+/// Readers that only know `SPEC_VERSION` reject it rather than reading the extent as data.
+pub const WAL_EXTENT_VERSION: u16 = SPEC_VERSION + 1;
 /// Binary format schema version.
 pub const FORMAT_VERSION: u16 = 1;
 
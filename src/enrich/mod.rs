@@ -4,7 +4,9 @@
 //! that process MV2 frames and extract structured memory cards.
 
 pub mod engine;
+pub mod registry;
 pub mod rules;
 
 pub use engine::{EnrichmentContext, EnrichmentEngine, EnrichmentResult};
+pub use registry::{EngineRegistry, RegisteredEngine};
 pub use rules::RulesEngine;
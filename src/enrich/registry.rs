@@ -0,0 +1,141 @@
+//! Registry of custom enrichment engines run by the enrichment worker.
+//!
+//! Engines are kept in priority order: higher priorities run first, and engines of equal
+//! priority run in registration order. An engine kind is registered at most once;
+//! registering the same kind again replaces the earlier engine.
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::engine::EnrichmentEngine;
+use crate::error::Result;
+
+/// An engine together with the priority it was registered at.
+#[derive(Clone)]
+pub struct RegisteredEngine {
+    pub engine: Arc<dyn EnrichmentEngine>,
+    pub priority: i32,
+}
+
+/// Ordered set of enrichment engines.
+#[derive(Clone, Default)]
+pub struct EngineRegistry {
+    engines: Vec<RegisteredEngine>,
+}
+
+impl EngineRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Initialize `engine` and add it at `priority`, replacing any engine of the same kind.
+    ///
+    /// # Errors
+    /// Returns the error of the engine's `init`; the registry is left unchanged.
+    pub fn register(&mut self, mut engine: Box<dyn EnrichmentEngine>, priority: i32) -> Result<()> {
+        engine.init()?;
+        self.unregister(engine.kind());
+        let position = self
+            .engines
+            .iter()
+            .position(|registered| registered.priority < priority)
+            .unwrap_or(self.engines.len());
+        self.engines.insert(
+            position,
+            RegisteredEngine {
+                engine: Arc::from(engine),
+                priority,
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove the engine of `kind`. Returns whether one was registered.
+    pub fn unregister(&mut self, kind: &str) -> bool {
+        let before = self.engines.len();
+        self.engines
+            .retain(|registered| registered.engine.kind() != kind);
+        self.engines.len() != before
+    }
+
+    /// Registered engines in run order.
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredEngine> {
+        self.engines.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.engines.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.engines.is_empty()
+    }
+}
+
+impl fmt::Debug for EngineRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.engines.iter().map(|registered| {
+                (
+                    registered.engine.kind(),
+                    registered.engine.version(),
+                    registered.priority,
+                )
+            }))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrich::{EnrichmentContext, EnrichmentResult};
+
+    struct NamedEngine(&'static str, &'static str);
+
+    impl EnrichmentEngine for NamedEngine {
+        fn kind(&self) -> &str {
+            self.0
+        }
+        fn version(&self) -> &str {
+            self.1
+        }
+        fn enrich(&self, _ctx: &EnrichmentContext) -> EnrichmentResult {
+            EnrichmentResult::empty()
+        }
+    }
+
+    fn kinds(registry: &EngineRegistry) -> Vec<&str> {
+        registry.iter().map(|r| r.engine.kind()).collect()
+    }
+
+    #[test]
+    fn test_registry_orders_by_priority_then_registration() {
+        let mut registry = EngineRegistry::new();
+        registry
+            .register(Box::new(NamedEngine("a", "1")), 0)
+            .unwrap();
+        registry
+            .register(Box::new(NamedEngine("b", "1")), 10)
+            .unwrap();
+        registry
+            .register(Box::new(NamedEngine("c", "1")), 0)
+            .unwrap();
+        assert_eq!(kinds(&registry), ["b", "a", "c"]);
+
+        // Re-registering a kind replaces it at its new priority.
+        registry
+            .register(Box::new(NamedEngine("a", "2")), 20)
+            .unwrap();
+        assert_eq!(kinds(&registry), ["a", "b", "c"]);
+        assert_eq!(registry.iter().next().unwrap().engine.version(), "2");
+
+        assert!(registry.unregister("b"));
+        assert!(!registry.unregister("b"));
+        assert_eq!(registry.len(), 2);
+    }
+}
@@ -6,12 +6,87 @@
 //! - Updates Tantivy index with enriched content
 //! - Marks frames as Enriched when complete
 
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
+use crate::enrich::{EngineRegistry, EnrichmentEngine};
 use crate::error::Result;
-use crate::types::{EnrichmentTask, FrameId, VecEmbedder};
+use crate::types::{
+    EmbedderRequestStats, EnrichmentPriority, EnrichmentTask, FrameId, VecEmbedder,
+};
+
+/// How the worker schedules frames of one kind (see `EnrichmentWorkerConfig::kind_policies`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KindPolicy {
+    /// Priority of frames of this kind; an explicit per-frame priority still wins.
+    pub priority: EnrichmentPriority,
+    /// Leave frames of this kind queued until the policy changes or they are given an
+    /// explicit priority.
+    pub deferred: bool,
+}
+
+/// Upper bound on embedder requests, or on tasks for workers without an embedder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    #[must_use]
+    pub fn per_second(max_requests: u32) -> Self {
+        Self {
+            max_requests,
+            per: Duration::from_secs(1),
+        }
+    }
+
+    #[must_use]
+    pub fn per_minute(max_requests: u32) -> Self {
+        Self {
+            max_requests,
+            per: Duration::from_secs(60),
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        self.per / self.max_requests.max(1)
+    }
+}
+
+/// Spaces requests evenly to honour a [`RateLimit`] across every thread sharing it.
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            interval: limit.interval(),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Reserve the next free slot and sleep until it opens.
+    fn wait(&self) {
+        let slot = {
+            let mut next = self
+                .next_slot
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        let delay = slot.saturating_duration_since(Instant::now());
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
 
 /// Configuration for the enrichment worker.
 #[derive(Debug, Clone)]
@@ -24,6 +99,17 @@ pub struct EnrichmentWorkerConfig {
     pub task_delay_ms: u64,
     /// Maximum time to spend on a single task before yielding.
     pub max_task_time_ms: u64,
+    /// Worker threads started by `start_enrichment_worker`; each claims its own tasks.
+    pub concurrency: usize,
+    /// Throttle for API embedders (per embedding batch) or, without an embedder, per task.
+    pub rate_limit: Option<RateLimit>,
+    /// Scheduling policy per frame kind; kinds not listed run at normal priority.
+    pub kind_policies: HashMap<String, KindPolicy>,
+    /// Run frames already returned by search or cited by ask at
+    /// [`EnrichmentPriority::High`], since users have seen them.
+    pub prioritize_accessed: bool,
+    /// Custom engines run on each enriched frame, after the built-in stages.
+    pub engines: EngineRegistry,
 }
 
 impl Default for EnrichmentWorkerConfig {
@@ -33,10 +119,29 @@ impl Default for EnrichmentWorkerConfig {
             checkpoint_interval: 100,
             task_delay_ms: 50,
             max_task_time_ms: 5000,
+            concurrency: 1,
+            rate_limit: None,
+            kind_policies: HashMap::new(),
+            prioritize_accessed: true,
+            engines: EngineRegistry::default(),
         }
     }
 }
 
+impl EnrichmentWorkerConfig {
+    /// Register a custom enrichment engine; see [`EngineRegistry::register`].
+    ///
+    /// # Errors
+    /// Returns the error of the engine's `init`.
+    pub fn register_engine(
+        &mut self,
+        engine: Box<dyn EnrichmentEngine>,
+        priority: i32,
+    ) -> Result<()> {
+        self.engines.register(engine, priority)
+    }
+}
+
 /// Statistics for the enrichment worker.
 #[derive(Debug, Clone, Default)]
 pub struct EnrichmentWorkerStats {
@@ -48,10 +153,16 @@ pub struct EnrichmentWorkerStats {
     pub re_extractions: u64,
     /// Total errors encountered.
     pub errors: u64,
+    /// Frames left queued because the embedder was unavailable, counted each time.
+    pub deferred: u64,
+    /// Request counters of an API-backed embedder, as of its last batch.
+    pub embedder: Option<EmbedderRequestStats>,
     /// Current queue depth.
     pub queue_depth: usize,
     /// Whether worker is currently running.
     pub is_running: bool,
+    /// Whether the worker is paused between tasks.
+    pub is_paused: bool,
 }
 
 /// Handle for controlling the background enrichment worker.
@@ -66,8 +177,18 @@ pub struct EnrichmentWorkerHandle {
     re_extractions: Arc<AtomicU64>,
     /// Counter for errors.
     errors: Arc<AtomicU64>,
-    /// Running state.
-    is_running: Arc<AtomicBool>,
+    /// Counter for frames deferred while the embedder was unavailable.
+    deferred: Arc<AtomicU64>,
+    /// Latest embedder request counters.
+    embedder_stats: Arc<Mutex<Option<EmbedderRequestStats>>>,
+    /// Number of worker threads running.
+    running: Arc<AtomicUsize>,
+    /// Hold the worker between tasks.
+    paused: Arc<AtomicBool>,
+    /// Stop once no schedulable task remains.
+    draining: Arc<AtomicBool>,
+    /// Shared throttle, if the configuration sets one.
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl EnrichmentWorkerHandle {
@@ -80,7 +201,12 @@ impl EnrichmentWorkerHandle {
             embeddings_generated: Arc::new(AtomicU64::new(0)),
             re_extractions: Arc::new(AtomicU64::new(0)),
             errors: Arc::new(AtomicU64::new(0)),
-            is_running: Arc::new(AtomicBool::new(false)),
+            deferred: Arc::new(AtomicU64::new(0)),
+            embedder_stats: Arc::new(Mutex::new(None)),
+            running: Arc::new(AtomicUsize::new(0)),
+            paused: Arc::new(AtomicBool::new(false)),
+            draining: Arc::new(AtomicBool::new(false)),
+            rate_limiter: None,
         }
     }
 
@@ -98,7 +224,47 @@ impl EnrichmentWorkerHandle {
     /// Check if worker is currently running.
     #[must_use]
     pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::SeqCst)
+        self.running.load(Ordering::SeqCst) > 0
+    }
+
+    /// Hold the worker between tasks until `resume`; the task in progress finishes first.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Let a paused worker pick up tasks again.
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Check if the worker is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Finish every schedulable task, then stop. Lifts a pause.
+    pub fn drain(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+        self.resume();
+    }
+
+    /// Check if the worker stops once the queue is empty.
+    #[must_use]
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Throttle the worker; must be set before the handle is cloned for worker threads.
+    pub(crate) fn set_rate_limit(&mut self, limit: Option<RateLimit>) {
+        self.rate_limiter = limit.map(|limit| Arc::new(RateLimiter::new(limit)));
+    }
+
+    /// Block until the rate limit admits another request.
+    pub(crate) fn wait_for_rate_limit(&self) {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.wait();
+        }
     }
 
     /// Get current statistics.
@@ -109,8 +275,14 @@ impl EnrichmentWorkerHandle {
             embeddings_generated: self.embeddings_generated.load(Ordering::Relaxed),
             re_extractions: self.re_extractions.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
+            deferred: self.deferred.load(Ordering::Relaxed),
+            embedder: *self
+                .embedder_stats
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
             queue_depth: 0, // Will be updated by caller
-            is_running: self.is_running.load(Ordering::Relaxed),
+            is_running: self.is_running(),
+            is_paused: self.is_paused(),
         }
     }
 
@@ -135,9 +307,30 @@ impl EnrichmentWorkerHandle {
         self.errors.fetch_add(1, Ordering::Relaxed);
     }
 
-    /// Set running state.
+    /// Count frames left queued for a later attempt.
+    pub(crate) fn inc_deferred(&self, count: u64) {
+        self.deferred.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Publish the embedder's latest request counters.
+    pub(crate) fn set_embedder_stats(&self, stats: Option<EmbedderRequestStats>) {
+        *self
+            .embedder_stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = stats;
+    }
+
+    /// Record a worker thread starting or stopping.
     pub(crate) fn set_running(&self, running: bool) {
-        self.is_running.store(running, Ordering::SeqCst);
+        if running {
+            self.running.fetch_add(1, Ordering::SeqCst);
+        } else {
+            let _ = self
+                .running
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                    count.checked_sub(1)
+                });
+        }
     }
 
     /// Clone the handle for sharing with the worker thread.
@@ -149,7 +342,12 @@ impl EnrichmentWorkerHandle {
             embeddings_generated: Arc::clone(&self.embeddings_generated),
             re_extractions: Arc::clone(&self.re_extractions),
             errors: Arc::clone(&self.errors),
-            is_running: Arc::clone(&self.is_running),
+            deferred: Arc::clone(&self.deferred),
+            embedder_stats: Arc::clone(&self.embedder_stats),
+            running: Arc::clone(&self.running),
+            paused: Arc::clone(&self.paused),
+            draining: Arc::clone(&self.draining),
+            rate_limiter: self.rate_limiter.clone(),
         }
     }
 }
@@ -252,6 +450,11 @@ impl<E: VecEmbedder> EmbeddingBatcher<E> {
         std::mem::take(&mut self.ready_embeddings)
     }
 
+    /// Request counters of the embedder, if it calls a remote API.
+    pub fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        self.embedder.request_stats()
+    }
+
     /// Get embedding dimension from the embedder.
     pub fn dimension(&self) -> usize {
         self.embedder.embedding_dimension()
@@ -347,7 +550,8 @@ impl EnrichmentProcessor {
 /// Run the enrichment worker loop.
 ///
 /// This function should be called from a background thread.
-/// It processes tasks from the enrichment queue until stopped.
+/// It processes tasks from the enrichment queue until stopped, or, once draining, until
+/// `get_next_task` runs dry. A paused handle holds the loop between tasks.
 ///
 /// # Arguments
 /// * `handle` - Worker handle for control and statistics
@@ -375,15 +579,24 @@ pub fn run_worker_loop<G, P, M, C>(
     let mut tasks_since_checkpoint = 0;
 
     while !handle.should_stop() {
+        if handle.is_paused() {
+            std::thread::sleep(Duration::from_millis(config.task_delay_ms * 10));
+            continue;
+        }
+
         // Get next task
         let task = if let Some(task) = get_next_task() {
             task
+        } else if handle.is_draining() {
+            break;
         } else {
             // Queue is empty, wait and check again
             std::thread::sleep(Duration::from_millis(config.task_delay_ms * 10));
             continue;
         };
 
+        handle.wait_for_rate_limit();
+
         // Process the task
         let result = process_task(&task);
 
@@ -545,6 +758,61 @@ mod tests {
         assert_eq!(stats.errors, 1);
     }
 
+    #[test]
+    fn test_worker_loop_drains_then_stops() {
+        let handle = EnrichmentWorkerHandle::new();
+        handle.pause();
+        assert!(handle.stats().is_paused);
+        handle.drain();
+        assert!(!handle.is_paused());
+
+        let config = EnrichmentWorkerConfig {
+            task_delay_ms: 0,
+            ..EnrichmentWorkerConfig::default()
+        };
+        let mut queue: Vec<EnrichmentTask> = (1..=3)
+            .map(|frame_id| EnrichmentTask {
+                frame_id,
+                created_at: 0,
+                chunks_done: 0,
+                chunks_total: 0,
+            })
+            .collect();
+        let mut completed = Vec::new();
+        run_worker_loop(
+            &handle,
+            &config,
+            || (!queue.is_empty()).then(|| queue.remove(0)),
+            |task| TaskResult {
+                frame_id: task.frame_id,
+                re_extracted: false,
+                embeddings_generated: 0,
+                elapsed_ms: 0,
+                error: None,
+            },
+            |frame_id| completed.push(frame_id),
+            || {},
+        );
+
+        assert_eq!(completed, vec![1, 2, 3]);
+        assert_eq!(handle.stats().frames_processed, 3);
+        assert!(!handle.is_running());
+    }
+
+    #[test]
+    fn test_rate_limit_spaces_requests() {
+        let mut handle = EnrichmentWorkerHandle::new();
+        handle.set_rate_limit(Some(RateLimit::per_second(50)));
+        let worker = handle.clone_handle();
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            worker.wait_for_rate_limit();
+        }
+        // The first request goes out at once; the other three wait 20ms each.
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
     #[test]
     fn test_processor() {
         let processor = EnrichmentProcessor::new(EnrichmentWorkerConfig::default());
@@ -84,6 +84,18 @@ pub enum MemvidError {
     #[error("Sketch track is invalid: {reason}")]
     InvalidSketchTrack { reason: Cow<'static, str> },
 
+    #[error("Sparse track is invalid: {reason}")]
+    InvalidSparseTrack { reason: Cow<'static, str> },
+
+    #[error("No sparse encoder installed; call Memvid::set_sparse_encoder")]
+    SparseEncoderMissing,
+
+    #[error("Token track is invalid: {reason}")]
+    InvalidTokenTrack { reason: Cow<'static, str> },
+
+    #[error("No token encoder installed; call Memvid::set_token_encoder")]
+    TokenEncoderMissing,
+
     #[cfg(feature = "temporal_track")]
     #[error("Temporal track is invalid: {reason}")]
     InvalidTemporalTrack { reason: Cow<'static, str> },
@@ -184,6 +196,15 @@ pub enum MemvidError {
     #[error("Frame with uri '{uri}' was not found")]
     FrameNotFoundByUri { uri: String },
 
+    #[error("Byte range {start}..{end} is outside blob of {size} bytes")]
+    BlobRangeOutOfBounds { start: u64, end: u64, size: u64 },
+
+    #[error("Frame {frame_id} failed payload checksum verification and is quarantined")]
+    FrameQuarantined { frame_id: crate::types::FrameId },
+
+    #[error("Frame {frame_id} is pinned; unpin it before deleting")]
+    FramePinned { frame_id: crate::types::FrameId },
+
     #[error("Ticket signature verification failed: {reason}")]
     TicketSignatureInvalid { reason: Box<str> },
 
@@ -202,9 +223,23 @@ pub enum MemvidError {
     #[error("Embedding failed: {reason}")]
     EmbeddingFailed { reason: Box<str> },
 
+    /// The embedding service kept failing with rate limits or transient errors; the call
+    /// may succeed after `retry_after_ms`.
+    #[error("Embedding provider unavailable ({reason}); retry in {retry_after_ms} ms")]
+    EmbedderUnavailable {
+        reason: Box<str>,
+        retry_after_ms: u64,
+    },
+
     #[error("Reranking failed: {reason}")]
     RerankFailed { reason: Box<str> },
 
+    #[error("LLM completion failed: {reason}")]
+    LlmFailed { reason: Box<str> },
+
+    #[error("Video decoding failed: {reason}")]
+    VideoDecodeFailed { reason: Box<str> },
+
     #[error("Model mismatch: Index is bound to '{expected}', but requested model was '{actual}'")]
     ModelMismatch { expected: String, actual: String },
 
@@ -219,6 +254,50 @@ pub enum MemvidError {
 
     #[error("Schema validation failed: {reason}")]
     SchemaValidation { reason: String },
+
+    #[error(
+        "Commit log no longer covers generation {requested}; oldest resumable generation is {oldest}"
+    )]
+    CommitLogTruncated { requested: u64, oldest: u64 },
+
+    #[error("Audit chain broken at entry {seq}: {reason}")]
+    AuditChainBroken { seq: u64, reason: String },
+
+    #[error("Replication failed: {reason}")]
+    Replication { reason: String },
+
+    #[error("Snapshot '{label}' was not found")]
+    SnapshotNotFound { label: String },
+
+    #[error("Snapshot '{label}' already exists")]
+    SnapshotExists { label: String },
+
+    #[error("Snapshot '{label}' is a read-only view")]
+    SnapshotReadOnly { label: String },
+
+    #[error("Memory is under a {mode} legal hold; cannot {operation}")]
+    LegalHold {
+        mode: &'static str,
+        operation: &'static str,
+    },
+
+    #[error("Collection '{name}' was not found")]
+    CollectionNotFound { name: String },
+
+    #[error("Collection '{name}' already exists")]
+    CollectionExists { name: String },
+
+    #[error("Ingest profile '{name}' was not found")]
+    IngestProfileNotFound { name: String },
+
+    #[error("Session '{session_id}' was not found")]
+    SessionNotFound { session_id: String },
+
+    #[error("Invalid tag '{tag}': {reason}")]
+    InvalidTag { tag: String, reason: &'static str },
+
+    #[error("Invalid configuration: {reason}")]
+    InvalidConfig { reason: String },
 }
 
 impl From<std::io::Error> for MemvidError {
@@ -235,3 +314,20 @@ impl From<tantivy::TantivyError> for MemvidError {
         }
     }
 }
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for MemvidError {
+    fn from(value: parquet::errors::ParquetError) -> Self {
+        std::io::Error::other(value).into()
+    }
+}
+
+#[cfg(feature = "notify")]
+impl From<notify::Error> for MemvidError {
+    fn from(value: notify::Error) -> Self {
+        match value.kind {
+            notify::ErrorKind::Io(source) => source.into(),
+            _ => std::io::Error::other(value).into(),
+        }
+    }
+}
@@ -0,0 +1,599 @@
+//! C ABI (feature `ffi`).
+//!
+//! A small, stable C surface for embedding a `.mv2` memory in Swift, Kotlin/JNI, or any other
+//! language with a C FFI. The matching header lives at `include/memvid.h` and is regenerated
+//! with `cbindgen --config cbindgen.toml --output include/memvid.h`. Build a linkable artifact
+//! with:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --lib --crate-type staticlib   # iOS / static linking
+//! cargo rustc --release --features ffi --lib --crate-type cdylib      # Android / dynamic
+//! ```
+//!
+//! Conventions:
+//! - Every fallible function returns a [`MemvidStatus`]; `MEMVID_STATUS_OK` is zero. On failure the
+//!   message is available from [`memvid_last_error_message`] on the same thread.
+//! - Handles are opaque. Free them with [`memvid_close`]. A handle is internally locked, so it
+//!   may be shared across threads.
+//! - Requests and responses cross the boundary as UTF-8 JSON using the same serde shapes as the
+//!   Rust types (`SearchRequest`, `AskRequest`, `TimelineQuery`, `PutOptions`). Strings returned
+//!   through `out_json` are owned by the caller and must be released with
+//!   [`memvid_string_free`].
+//! - Panics never unwind across the boundary; they surface as `MEMVID_STATUS_PANIC`.
+//!
+//! [`MEMVID_ABI_VERSION`] is bumped whenever a signature or status code changes meaning.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::MemvidError;
+use crate::memvid::Memvid;
+use crate::types::{AskRequest, FrameId, PutOptions, SearchRequest, TimelineQuery, VecEmbedder};
+
+/// Version of the C ABI exposed by this module.
+pub const MEMVID_ABI_VERSION: u32 = 1;
+
+/// Status codes returned by every fallible `memvid_*` function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemvidStatus {
+    Ok = 0,
+    NullArgument = 1,
+    InvalidUtf8 = 2,
+    InvalidArgument = 3,
+    Io = 4,
+    Locked = 5,
+    Corrupt = 6,
+    NotFound = 7,
+    FeatureDisabled = 8,
+    CapacityExceeded = 9,
+    Panic = 10,
+    Other = 255,
+}
+
+impl From<&MemvidError> for MemvidStatus {
+    fn from(err: &MemvidError) -> Self {
+        match err {
+            MemvidError::Io { .. } => Self::Io,
+            MemvidError::Lock(_) | MemvidError::Locked(_) => Self::Locked,
+            MemvidError::Decode(_)
+            | MemvidError::ChecksumMismatch { .. }
+            | MemvidError::InvalidHeader { .. }
+            | MemvidError::InvalidToc { .. }
+            | MemvidError::WalCorruption { .. }
+            | MemvidError::ManifestWalCorrupted { .. } => Self::Corrupt,
+            MemvidError::FrameNotFound { .. } | MemvidError::FrameNotFoundByUri { .. } => {
+                Self::NotFound
+            }
+            MemvidError::InvalidQuery { .. }
+            | MemvidError::InvalidCursor { .. }
+            | MemvidError::SchemaValidation { .. }
+            | MemvidError::VecDimensionMismatch { .. } => Self::InvalidArgument,
+            MemvidError::LexNotEnabled
+            | MemvidError::VecNotEnabled
+            | MemvidError::ClipNotEnabled
+            | MemvidError::FeatureUnavailable { .. } => Self::FeatureDisabled,
+            MemvidError::CapacityExceeded { .. } => Self::CapacityExceeded,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Opaque handle over an open `.mv2` file.
+pub struct MemvidHandle {
+    inner: Mutex<Memvid>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    // Interior NULs cannot be represented in a C string; replace them rather than dropping the
+    // message.
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+struct FfiError {
+    status: MemvidStatus,
+    message: String,
+}
+
+impl From<MemvidError> for FfiError {
+    fn from(err: MemvidError) -> Self {
+        Self {
+            status: MemvidStatus::from(&err),
+            message: err.to_string(),
+        }
+    }
+}
+
+impl FfiError {
+    fn new(status: MemvidStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+type FfiResult<T> = std::result::Result<T, FfiError>;
+
+/// Run `f`, translating errors and panics into a status code plus thread-local message.
+fn guard(f: impl FnOnce() -> FfiResult<()>) -> MemvidStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => {
+            LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+            MemvidStatus::Ok
+        }
+        Ok(Err(err)) => {
+            set_last_error(&err.message);
+            err.status
+        }
+        Err(_) => {
+            set_last_error("panic inside memvid");
+            MemvidStatus::Panic
+        }
+    }
+}
+
+/// # Safety
+/// `ptr` must be null or a valid NUL-terminated string.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if ptr.is_null() {
+        return Err(FfiError::new(
+            MemvidStatus::NullArgument,
+            format!("`{name}` is null"),
+        ));
+    }
+    // SAFETY: caller guarantees a valid NUL-terminated string.
+    unsafe { CStr::from_ptr(ptr) }.to_str().map_err(|_| {
+        FfiError::new(
+            MemvidStatus::InvalidUtf8,
+            format!("`{name}` is not valid UTF-8"),
+        )
+    })
+}
+
+/// # Safety
+/// `ptr` must be null or a valid NUL-terminated string.
+unsafe fn read_json<T: DeserializeOwned>(ptr: *const c_char, name: &str) -> FfiResult<T> {
+    // SAFETY: forwarded caller contract.
+    let text = unsafe { read_str(ptr, name) }?;
+    serde_json::from_str(text).map_err(|err| {
+        FfiError::new(
+            MemvidStatus::InvalidArgument,
+            format!("`{name}` is not a valid request: {err}"),
+        )
+    })
+}
+
+/// # Safety
+/// `handle` must be null or a pointer returned by `memvid_create`/`memvid_open*`.
+unsafe fn handle_ref<'a>(handle: *const MemvidHandle) -> FfiResult<&'a MemvidHandle> {
+    // SAFETY: caller guarantees the pointer is live and came from this module.
+    unsafe { handle.as_ref() }
+        .ok_or_else(|| FfiError::new(MemvidStatus::NullArgument, "`handle` is null"))
+}
+
+fn with_memvid<T>(
+    handle: &MemvidHandle,
+    f: impl FnOnce(&mut Memvid) -> crate::Result<T>,
+) -> FfiResult<T> {
+    let mut mem = handle
+        .inner
+        .lock()
+        .map_err(|_| FfiError::new(MemvidStatus::Locked, "memvid handle mutex poisoned"))?;
+    Ok(f(&mut mem)?)
+}
+
+/// # Safety
+/// `out` must be null or valid for a pointer write.
+unsafe fn write_out<T>(out: *mut T, value: T, name: &str) -> FfiResult<()> {
+    if out.is_null() {
+        return Err(FfiError::new(
+            MemvidStatus::NullArgument,
+            format!("`{name}` is null"),
+        ));
+    }
+    // SAFETY: checked non-null; caller guarantees validity.
+    unsafe { out.write(value) };
+    Ok(())
+}
+
+/// # Safety
+/// `out` must be null or valid for a pointer write.
+unsafe fn write_json<T: Serialize>(out: *mut *mut c_char, value: &T) -> FfiResult<()> {
+    let encoded = serde_json::to_string(value)
+        .map_err(|err| FfiError::new(MemvidStatus::Other, err.to_string()))?;
+    let encoded =
+        CString::new(encoded).map_err(|err| FfiError::new(MemvidStatus::Other, err.to_string()))?;
+    // SAFETY: forwarded caller contract.
+    unsafe { write_cstring(out, encoded, "out_json") }
+}
+
+/// # Safety
+/// `out` must be null or valid for a pointer write.
+unsafe fn write_cstring(out: *mut *mut c_char, value: CString, name: &str) -> FfiResult<()> {
+    // Check before `into_raw` so a null `out` does not leak the string.
+    if out.is_null() {
+        return Err(FfiError::new(
+            MemvidStatus::NullArgument,
+            format!("`{name}` is null"),
+        ));
+    }
+    // SAFETY: checked non-null; caller guarantees validity.
+    unsafe { out.write(value.into_raw()) };
+    Ok(())
+}
+
+/// # Safety
+/// `path` must be a valid NUL-terminated string and `out` valid for a pointer write.
+unsafe fn open_with(
+    path: *const c_char,
+    out: *mut *mut MemvidHandle,
+    open: fn(&str) -> crate::Result<Memvid>,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let path = unsafe { read_str(path, "path") }?;
+        let mem = open(path)?;
+        let handle = Box::into_raw(Box::new(MemvidHandle {
+            inner: Mutex::new(mem),
+        }));
+        // SAFETY: forwarded caller contract.
+        unsafe { write_out(out, handle, "out") }.inspect_err(|_| {
+            // SAFETY: `handle` was just created by `Box::into_raw` and never shared.
+            drop(unsafe { Box::from_raw(handle) });
+        })
+    })
+}
+
+/// ABI version implemented by this library; compare against `MEMVID_ABI_VERSION` in the header.
+#[unsafe(no_mangle)]
+pub extern "C" fn memvid_abi_version() -> u32 {
+    MEMVID_ABI_VERSION
+}
+
+/// Message for the last failed call on this thread, or null if it succeeded.
+///
+/// The pointer stays valid until the next `memvid_*` call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn memvid_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Create a new `.mv2` file and return a handle in `*out`.
+///
+/// # Safety
+/// `path` must be a valid NUL-terminated string and `out` valid for a pointer write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_create(
+    path: *const c_char,
+    out: *mut *mut MemvidHandle,
+) -> MemvidStatus {
+    // SAFETY: forwarded caller contract.
+    unsafe { open_with(path, out, |path| Memvid::create(path)) }
+}
+
+/// Open an existing `.mv2` file for reading and writing.
+///
+/// # Safety
+/// `path` must be a valid NUL-terminated string and `out` valid for a pointer write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_open(
+    path: *const c_char,
+    out: *mut *mut MemvidHandle,
+) -> MemvidStatus {
+    // SAFETY: forwarded caller contract.
+    unsafe { open_with(path, out, |path| Memvid::open(path)) }
+}
+
+/// Open an existing `.mv2` file with a shared (read-only) lock.
+///
+/// # Safety
+/// `path` must be a valid NUL-terminated string and `out` valid for a pointer write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_open_read_only(
+    path: *const c_char,
+    out: *mut *mut MemvidHandle,
+) -> MemvidStatus {
+    // SAFETY: forwarded caller contract.
+    unsafe { open_with(path, out, |path| Memvid::open_read_only(path)) }
+}
+
+/// Close a handle, releasing the file lock. Null is ignored.
+///
+/// # Safety
+/// `handle` must be null or a pointer returned by `memvid_create`/`memvid_open*` that has not
+/// already been closed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_close(handle: *mut MemvidHandle) {
+    if !handle.is_null() {
+        // SAFETY: caller guarantees unique ownership of a pointer from `Box::into_raw`.
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(unsafe { Box::from_raw(handle) })));
+    }
+}
+
+/// Enable the lexical index so `memvid_search_json` can be used.
+///
+/// # Safety
+/// `handle` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_enable_lex(handle: *mut MemvidHandle) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        with_memvid(handle, Memvid::enable_lex)
+    })
+}
+
+/// Append `len` bytes at `data` as a new frame and write its WAL sequence to `*out_sequence`.
+///
+/// `options_json` is an optional `PutOptions` object (may be null). Call `memvid_commit` to make
+/// the frame durable and searchable.
+///
+/// # Safety
+/// `handle` must be live, `data` valid for `len` reads (may be null when `len == 0`),
+/// `options_json` null or a valid string, and `out_sequence` null or valid for a write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_put_bytes(
+    handle: *mut MemvidHandle,
+    data: *const u8,
+    len: usize,
+    options_json: *const c_char,
+    out_sequence: *mut u64,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        let payload: &[u8] = if len == 0 {
+            &[]
+        } else if data.is_null() {
+            return Err(FfiError::new(MemvidStatus::NullArgument, "`data` is null"));
+        } else {
+            // SAFETY: caller guarantees `data` is valid for `len` bytes.
+            unsafe { std::slice::from_raw_parts(data, len) }
+        };
+        let options: PutOptions = if options_json.is_null() {
+            PutOptions::default()
+        } else {
+            // SAFETY: forwarded caller contract.
+            unsafe { read_json(options_json, "options_json") }?
+        };
+        let sequence = with_memvid(handle, |mem| mem.put_bytes_with_options(payload, options))?;
+        if !out_sequence.is_null() {
+            // SAFETY: checked non-null; caller guarantees validity.
+            unsafe { out_sequence.write(sequence) };
+        }
+        Ok(())
+    })
+}
+
+/// Persist pending frames.
+///
+/// # Safety
+/// `handle` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_commit(handle: *mut MemvidHandle) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        with_memvid(handle, Memvid::commit)
+    })
+}
+
+/// Run a `SearchRequest` (JSON) and write the `SearchResponse` JSON to `*out_json`.
+///
+/// # Safety
+/// `handle` must be live, `request_json` a valid string, and `out_json` valid for a write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_search_json(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        // SAFETY: forwarded caller contract.
+        let request: SearchRequest = unsafe { read_json(request_json, "request_json") }?;
+        let response = with_memvid(handle, |mem| mem.search(request))?;
+        // SAFETY: forwarded caller contract.
+        unsafe { write_json(out_json, &response) }
+    })
+}
+
+/// Run an `AskRequest` (JSON) and write the `AskResponse` JSON to `*out_json`.
+///
+/// # Safety
+/// `handle` must be live, `request_json` a valid string, and `out_json` valid for a write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_ask_json(
+    handle: *mut MemvidHandle,
+    request_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        // SAFETY: forwarded caller contract.
+        let request: AskRequest = unsafe { read_json(request_json, "request_json") }?;
+        let response = with_memvid(handle, |mem| mem.ask(request, None::<&dyn VecEmbedder>))?;
+        // SAFETY: forwarded caller contract.
+        unsafe { write_json(out_json, &response) }
+    })
+}
+
+/// Run a `TimelineQuery` (JSON, or null for the default query) and write the entries as a JSON
+/// array to `*out_json`.
+///
+/// # Safety
+/// `handle` must be live, `query_json` null or a valid string, and `out_json` valid for a write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_timeline_json(
+    handle: *mut MemvidHandle,
+    query_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        let query: TimelineQuery = if query_json.is_null() {
+            TimelineQuery::default()
+        } else {
+            // SAFETY: forwarded caller contract.
+            unsafe { read_json(query_json, "query_json") }?
+        };
+        let entries = with_memvid(handle, |mem| mem.timeline(query))?;
+        // SAFETY: forwarded caller contract.
+        unsafe { write_json(out_json, &entries) }
+    })
+}
+
+/// Write the memory's `Stats` JSON to `*out_json`.
+///
+/// # Safety
+/// `handle` must be live and `out_json` valid for a write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_stats_json(
+    handle: *mut MemvidHandle,
+    out_json: *mut *mut c_char,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        let stats = with_memvid(handle, |mem| mem.stats())?;
+        // SAFETY: forwarded caller contract.
+        unsafe { write_json(out_json, &stats) }
+    })
+}
+
+/// Write the searchable text of `frame_id` to `*out_text` (free with `memvid_string_free`).
+///
+/// # Safety
+/// `handle` must be live and `out_text` valid for a write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_frame_text(
+    handle: *mut MemvidHandle,
+    frame_id: FrameId,
+    out_text: *mut *mut c_char,
+) -> MemvidStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract.
+        let handle = unsafe { handle_ref(handle) }?;
+        let text = with_memvid(handle, |mem| {
+            // Resolve first so an unknown id maps to MEMVID_STATUS_NOT_FOUND.
+            mem.frame_by_id(frame_id)?;
+            mem.frame_text_by_id(frame_id)
+        })?;
+        let text = CString::new(text.replace('\0', " "))
+            .map_err(|err| FfiError::new(MemvidStatus::Other, err.to_string()))?;
+        // SAFETY: forwarded caller contract.
+        unsafe { write_cstring(out_text, text, "out_text") }
+    })
+}
+
+/// Free a string returned through an `out_json`/`out_text` parameter. Null is ignored.
+///
+/// # Safety
+/// `value` must be null or a string returned by this library that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn memvid_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: caller guarantees the pointer came from `CString::into_raw`.
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take_string(ptr: *mut c_char) -> String {
+        assert!(!ptr.is_null());
+        let text = unsafe { CStr::from_ptr(ptr) }
+            .to_str()
+            .expect("utf8")
+            .to_owned();
+        unsafe { memvid_string_free(ptr) };
+        text
+    }
+
+    #[test]
+    fn c_abi_round_trip() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = CString::new(dir.path().join("ffi.mv2").to_str().expect("path")).expect("c");
+        let mut handle: *mut MemvidHandle = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                memvid_create(path.as_ptr(), &raw mut handle),
+                MemvidStatus::Ok
+            );
+            assert_eq!(memvid_enable_lex(handle), MemvidStatus::Ok);
+
+            let payload = b"mobile apps embed memories";
+            let options = CString::new(r#"{"title":"mobile"}"#).expect("c");
+            let mut sequence = 0u64;
+            assert_eq!(
+                memvid_put_bytes(
+                    handle,
+                    payload.as_ptr(),
+                    payload.len(),
+                    options.as_ptr(),
+                    &raw mut sequence,
+                ),
+                MemvidStatus::Ok
+            );
+            assert!(sequence > 0);
+            assert_eq!(memvid_commit(handle), MemvidStatus::Ok);
+
+            let request =
+                CString::new(r#"{"query":"embed","top_k":5,"snippet_chars":80}"#).expect("c");
+            let mut out: *mut c_char = ptr::null_mut();
+            assert_eq!(
+                memvid_search_json(handle, request.as_ptr(), &raw mut out),
+                MemvidStatus::Ok
+            );
+            let response: serde_json::Value =
+                serde_json::from_str(&take_string(out)).expect("json");
+            assert_eq!(response["hits"].as_array().map(Vec::len), Some(1));
+
+            let mut text: *mut c_char = ptr::null_mut();
+            assert_eq!(
+                memvid_frame_text(handle, 0, &raw mut text),
+                MemvidStatus::Ok
+            );
+            assert!(take_string(text).starts_with("mobile apps"));
+
+            assert_eq!(
+                memvid_frame_text(handle, 42, &raw mut text),
+                MemvidStatus::NotFound
+            );
+            assert!(!memvid_last_error_message().is_null());
+
+            let bad = CString::new("{").expect("c");
+            assert_eq!(
+                memvid_search_json(handle, bad.as_ptr(), &raw mut out),
+                MemvidStatus::InvalidArgument
+            );
+            assert_eq!(
+                memvid_search_json(handle, ptr::null(), &raw mut out),
+                MemvidStatus::NullArgument
+            );
+
+            memvid_close(handle);
+        }
+    }
+}
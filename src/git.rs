@@ -0,0 +1,217 @@
+//! Read-only access to a git repository.
+//!
+//! A [`GitReader`] runs the `git` command-line tool against a working tree: it resolves
+//! revisions, lists a revision's files, reads blobs in batches through one `git cat-file`
+//! process, and reads the commit log with the paths each commit changed. Nothing is written
+//! to the repository. `Memvid::ingest_git_repo` builds on it.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::error::{MemvidError, Result};
+use crate::types::{GitCommit, GitTreeEntry};
+
+const RECORD_SEPARATOR: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
+const LOG_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%ae%x1f%at%x1f%s%x1f%b%x1f";
+/// Mode of symbolic links in a tree.
+const SYMLINK_MODE: &str = "120000";
+
+/// A git working tree read through the `git` tool.
+#[derive(Debug, Clone)]
+pub struct GitReader {
+    root: PathBuf,
+}
+
+impl GitReader {
+    /// Open the repository containing `path`.
+    ///
+    /// # Errors
+    /// Fails if `git` cannot be run or `path` is not inside a working tree.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let output = git_output(path, &["rev-parse", "--show-toplevel"])?;
+        let root = String::from_utf8_lossy(&output).trim().to_string();
+        if root.is_empty() {
+            return Err(MemvidError::InvalidConfig {
+                reason: format!("{} is not inside a git working tree", path.display()),
+            });
+        }
+        Ok(Self {
+            root: PathBuf::from(root),
+        })
+    }
+
+    /// Top-level directory of the working tree.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Full hash of the commit `revision` names.
+    ///
+    /// # Errors
+    /// Fails if `revision` does not name a commit.
+    pub fn resolve(&self, revision: &str) -> Result<String> {
+        let spec = format!("{revision}^{{commit}}");
+        let output = self.run(&["rev-parse", "--verify", "--quiet", &spec])?;
+        Ok(String::from_utf8_lossy(&output).trim().to_string())
+    }
+
+    /// Regular files in the tree of `revision`, in path order. Symbolic links and submodules
+    /// are left out.
+    ///
+    /// # Errors
+    /// Fails if the tree cannot be listed.
+    pub fn tree(&self, revision: &str) -> Result<Vec<GitTreeEntry>> {
+        let output = self.run(&["ls-tree", "-r", "-l", "-z", "--full-tree", revision])?;
+        let mut entries = Vec::new();
+        for record in output.split(|byte| *byte == 0) {
+            // `<mode> <type> <object> <size>\t<path>`, the size padded with spaces.
+            let record = String::from_utf8_lossy(record);
+            let Some((header, path)) = record.split_once('\t') else {
+                continue;
+            };
+            let mut fields = header.split_whitespace();
+            let (Some(mode), Some(kind), Some(blob), Some(size)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            if kind != "blob" || mode == SYMLINK_MODE {
+                continue;
+            }
+            entries.push(GitTreeEntry {
+                path: path.to_string(),
+                blob: blob.to_string(),
+                size: size.parse().unwrap_or(0),
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Contents of `blobs`, in order.
+    ///
+    /// # Errors
+    /// Fails if a blob is missing or `git cat-file` fails.
+    pub fn read_blobs(&self, blobs: &[&str]) -> Result<Vec<Vec<u8>>> {
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(["cat-file", "--batch"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let (Some(mut stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(std::io::Error::other("git cat-file pipes unavailable").into());
+        };
+        let mut input = String::new();
+        for blob in blobs {
+            input.push_str(blob);
+            input.push('\n');
+        }
+        // Feed requests from another thread so neither pipe fills up while the other waits.
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let mut stdout = BufReader::new(stdout);
+        let mut contents = Vec::with_capacity(blobs.len());
+        let mut header = String::new();
+        for blob in blobs {
+            header.clear();
+            stdout.read_line(&mut header)?;
+            // `<object> <type> <size>`, or `<object> missing`.
+            let size = header
+                .split_whitespace()
+                .nth(2)
+                .and_then(|size| size.parse::<usize>().ok())
+                .ok_or_else(|| std::io::Error::other(format!("git blob {blob} missing")))?;
+            let mut bytes = vec![0; size];
+            stdout.read_exact(&mut bytes)?;
+            let mut newline = [0u8; 1];
+            stdout.read_exact(&mut newline)?;
+            contents.push(bytes);
+        }
+        drop(stdout);
+        writer
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+        child.wait()?;
+        Ok(contents)
+    }
+
+    /// Commits reachable from `revision`, newest first, at most `limit` of them.
+    ///
+    /// # Errors
+    /// Fails if the log cannot be read.
+    pub fn commits(&self, revision: &str, limit: Option<usize>) -> Result<Vec<GitCommit>> {
+        let max_count = limit.map(|limit| format!("--max-count={limit}"));
+        let mut args = vec![
+            "-c",
+            "core.quotePath=false",
+            "log",
+            LOG_FORMAT,
+            "--name-only",
+            "--no-renames",
+        ];
+        if let Some(max_count) = max_count.as_deref() {
+            args.push(max_count);
+        }
+        args.extend([revision, "--"]);
+        let output = self.run(&args)?;
+        let log = String::from_utf8_lossy(&output);
+        Ok(log
+            .split(RECORD_SEPARATOR)
+            .filter_map(parse_commit)
+            .collect())
+    }
+
+    fn run(&self, args: &[&str]) -> Result<Vec<u8>> {
+        git_output(&self.root, args)
+    }
+}
+
+fn parse_commit(record: &str) -> Option<GitCommit> {
+    let mut fields = record.splitn(7, FIELD_SEPARATOR);
+    let hash = fields.next()?.trim();
+    if hash.is_empty() {
+        return None;
+    }
+    let author = fields.next()?;
+    let email = fields.next()?;
+    let timestamp = fields.next()?.trim().parse().unwrap_or(0);
+    let subject = fields.next()?;
+    let body = fields.next()?;
+    let files = fields
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    Some(GitCommit {
+        hash: hash.to_string(),
+        author: author.to_string(),
+        email: email.to_string(),
+        timestamp,
+        subject: subject.trim().to_string(),
+        body: body.trim().to_string(),
+        files,
+    })
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        ))
+        .into());
+    }
+    Ok(output.stdout)
+}
@@ -329,6 +329,18 @@ pub fn hybrid_search(memvid: &mut Memvid, plan: &QueryPlan) -> Result<Vec<Hybrid
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: crate::types::SearchMode::Lexical,
+                time_budget_ms: None,
             };
             let response = memvid.search(request)?;
             Ok(response
@@ -395,6 +407,18 @@ pub fn hybrid_search(memvid: &mut Memvid, plan: &QueryPlan) -> Result<Vec<Hybrid
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 };
                 let response = memvid.search(request)?;
                 return Ok(response
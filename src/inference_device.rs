@@ -0,0 +1,215 @@
+//! Device selection for local ONNX inference (text embeddings and CLIP).
+//!
+//! GPU execution providers are compiled in with the `cuda` and `metal` features; Metal is
+//! reached through ONNX Runtime's CoreML provider. Sessions try the requested device first
+//! and fall back to CPU when the provider is missing or fails to initialise, so the same
+//! configuration works on machines with and without a GPU.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Where local embedding models run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InferenceDevice {
+    /// First GPU provider compiled into this build, otherwise CPU.
+    #[default]
+    Auto,
+    Cpu,
+    /// NVIDIA GPU via the CUDA execution provider (`cuda` feature).
+    Cuda {
+        device_id: i32,
+    },
+    /// Apple GPU / Neural Engine via the CoreML execution provider (`metal` feature).
+    Metal,
+}
+
+impl InferenceDevice {
+    #[must_use]
+    pub fn is_gpu(self) -> bool {
+        matches!(self, Self::Cuda { .. } | Self::Metal)
+    }
+
+    /// Whether this build can use the device at all.
+    #[must_use]
+    pub fn is_compiled(self) -> bool {
+        match self {
+            Self::Auto | Self::Cpu => true,
+            Self::Cuda { .. } => cfg!(feature = "cuda"),
+            Self::Metal => cfg!(feature = "metal"),
+        }
+    }
+
+    /// Devices to try, in order, ending with CPU. Devices not compiled in are skipped.
+    #[must_use]
+    pub fn candidates(self) -> Vec<Self> {
+        let preferred = match self {
+            Self::Auto => vec![Self::Cuda { device_id: 0 }, Self::Metal],
+            Self::Cpu => Vec::new(),
+            device => vec![device],
+        };
+        preferred
+            .into_iter()
+            .filter(|device| device.is_compiled())
+            .chain(std::iter::once(Self::Cpu))
+            .collect()
+    }
+}
+
+impl fmt::Display for InferenceDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Cpu => f.write_str("cpu"),
+            Self::Cuda { device_id } => write!(f, "cuda:{device_id}"),
+            Self::Metal => f.write_str("metal"),
+        }
+    }
+}
+
+impl FromStr for InferenceDevice {
+    type Err = String;
+
+    /// Parses `auto`, `cpu`, `metal`, `cuda`, or `cuda:<id>`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "auto" => Ok(Self::Auto),
+            "cpu" => Ok(Self::Cpu),
+            "metal" | "coreml" => Ok(Self::Metal),
+            "cuda" | "gpu" => Ok(Self::Cuda { device_id: 0 }),
+            other => other
+                .strip_prefix("cuda:")
+                .and_then(|id| id.parse().ok())
+                .map(|device_id| Self::Cuda { device_id })
+                .ok_or_else(|| format!("unknown inference device '{other}'")),
+        }
+    }
+}
+
+#[cfg(feature = "vec")]
+pub(crate) use session::build_session;
+
+#[cfg(feature = "vec")]
+mod session {
+    use std::path::Path;
+
+    use ort::execution_providers::ExecutionProviderDispatch;
+    use ort::session::{Session, builder::GraphOptimizationLevel};
+
+    use super::InferenceDevice;
+
+    /// Build a session for `model_path` on the first usable device in `preferred`'s
+    /// candidates, returning the device actually used. Errors carry the last failure.
+    pub(crate) fn build_session(
+        preferred: InferenceDevice,
+        model_path: &Path,
+        intra_threads: usize,
+    ) -> Result<(Session, InferenceDevice), String> {
+        if preferred.is_gpu() && !preferred.is_compiled() {
+            tracing::warn!(
+                device = %preferred,
+                "inference device not compiled into this build, using CPU"
+            );
+        }
+        let mut last_error = String::new();
+        for device in preferred.candidates() {
+            match build_on(device, model_path, intra_threads) {
+                Ok(session) => {
+                    tracing::debug!(device = %device, "ONNX session created");
+                    return Ok((session, device));
+                }
+                Err(err) => {
+                    if device.is_gpu() {
+                        tracing::warn!(
+                            device = %device,
+                            error = %err,
+                            "execution provider unavailable, falling back"
+                        );
+                    }
+                    last_error = err;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    fn build_on(
+        device: InferenceDevice,
+        model_path: &Path,
+        intra_threads: usize,
+    ) -> Result<Session, String> {
+        let mut builder = Session::builder()
+            .map_err(|e| format!("Failed to create session builder: {e}"))?
+            .with_optimization_level(GraphOptimizationLevel::Level3)
+            .map_err(|e| format!("Failed to set optimization level: {e}"))?
+            .with_intra_threads(intra_threads)
+            .map_err(|e| format!("Failed to set intra threads: {e}"))?;
+        if let Some(provider) = execution_provider(device) {
+            builder = builder
+                .with_execution_providers([provider])
+                .map_err(|e| format!("Failed to register {device} execution provider: {e}"))?;
+        }
+        builder
+            .commit_from_file(model_path)
+            .map_err(|e| format!("Failed to load model: {e}"))
+    }
+
+    /// Provider for `device`, configured to fail loudly so the caller can fall back.
+    fn execution_provider(device: InferenceDevice) -> Option<ExecutionProviderDispatch> {
+        #[cfg(feature = "cuda")]
+        if let InferenceDevice::Cuda { device_id } = device {
+            return Some(
+                ort::execution_providers::CUDAExecutionProvider::default()
+                    .with_device_id(device_id)
+                    .build()
+                    .error_on_failure(),
+            );
+        }
+        #[cfg(feature = "metal")]
+        if device == InferenceDevice::Metal {
+            return Some(
+                ort::execution_providers::CoreMLExecutionProvider::default()
+                    .build()
+                    .error_on_failure(),
+            );
+        }
+        let _ = device;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_end_with_cpu_and_parse_round_trips() {
+        assert_eq!(
+            InferenceDevice::Cpu.candidates(),
+            vec![InferenceDevice::Cpu]
+        );
+        let auto = InferenceDevice::Auto.candidates();
+        assert_eq!(auto.last(), Some(&InferenceDevice::Cpu));
+        assert!(auto.iter().all(|device| device.is_compiled()));
+        assert_eq!(
+            InferenceDevice::Metal.candidates().len(),
+            if cfg!(feature = "metal") { 2 } else { 1 }
+        );
+
+        for device in [
+            InferenceDevice::Auto,
+            InferenceDevice::Cpu,
+            InferenceDevice::Cuda { device_id: 1 },
+            InferenceDevice::Metal,
+        ] {
+            assert_eq!(device.to_string().parse::<InferenceDevice>(), Ok(device));
+        }
+        assert_eq!(
+            "CUDA".parse::<InferenceDevice>(),
+            Ok(InferenceDevice::Cuda { device_id: 0 })
+        );
+        assert!("tpu".parse::<InferenceDevice>().is_err());
+    }
+}
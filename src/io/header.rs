@@ -4,9 +4,9 @@ use std::{
 };
 
 use crate::{
-    constants::{HEADER_SIZE, MAGIC, SPEC_MAJOR, SPEC_MINOR, WAL_OFFSET},
+    constants::{HEADER_SIZE, MAGIC, SPEC_MAJOR, SPEC_MINOR, WAL_EXTENT_VERSION, WAL_OFFSET},
     error::{MemvidError, Result},
-    types::Header,
+    types::{Header, ImmutableHold, ImmutableMode},
 };
 
 const VERSION_OFFSET: usize = 4;
@@ -21,6 +21,13 @@ const TOC_CHECKSUM_END: usize = 80;
 // Legacy lock metadata occupied bytes 80..140 within the header padding.
 const LEGACY_LOCK_REGION_START: usize = TOC_CHECKSUM_END;
 const LEGACY_LOCK_REGION_END: usize = LEGACY_LOCK_REGION_START + 60;
+// Legal hold: mode byte (0 none, 1 append-only, 2 frozen), then a flag byte for `until`.
+const HOLD_MODE_POS: usize = 144;
+const HOLD_HAS_UNTIL_POS: usize = 145;
+const HOLD_UNTIL_POS: usize = 152;
+// Data region start, only meaningful in `WAL_EXTENT_VERSION` headers; older headers derive it
+// from the WAL slot.
+const DATA_START_POS: usize = 160;
 const EXPECTED_VERSION: u16 = ((SPEC_MAJOR as u16) << 8) | SPEC_MINOR as u16;
 
 /// Deterministic encoder/decoder for the fixed-size header region.
@@ -57,7 +64,7 @@ impl HeaderCodec {
                 reason: "magic mismatch".into(),
             });
         }
-        if header.version != EXPECTED_VERSION {
+        if header.version != EXPECTED_VERSION && header.version != WAL_EXTENT_VERSION {
             return Err(MemvidError::InvalidHeader {
                 reason: "unsupported version".into(),
             });
@@ -72,6 +79,7 @@ impl HeaderCodec {
                 reason: "wal_size must be non-zero".into(),
             });
         }
+        check_data_start(header)?;
 
         let mut buf = [0u8; HEADER_SIZE];
         buf[..MAGIC.len()].copy_from_slice(&header.magic);
@@ -87,6 +95,20 @@ impl HeaderCodec {
         buf[WAL_SEQUENCE_POS..WAL_SEQUENCE_POS + 8]
             .copy_from_slice(&header.wal_sequence.to_le_bytes());
         buf[TOC_CHECKSUM_POS..TOC_CHECKSUM_END].copy_from_slice(&header.toc_checksum);
+        if let Some(hold) = header.immutable {
+            buf[HOLD_MODE_POS] = match hold.mode {
+                ImmutableMode::AppendOnly => 1,
+                ImmutableMode::Frozen => 2,
+            };
+            if let Some(until) = hold.until {
+                buf[HOLD_HAS_UNTIL_POS] = 1;
+                buf[HOLD_UNTIL_POS..HOLD_UNTIL_POS + 8].copy_from_slice(&until.to_le_bytes());
+            }
+        }
+        if header.version == WAL_EXTENT_VERSION {
+            buf[DATA_START_POS..DATA_START_POS + 8]
+                .copy_from_slice(&header.data_start.to_le_bytes());
+        }
         Ok(buf)
     }
 
@@ -102,7 +124,7 @@ impl HeaderCodec {
         }
 
         let version = u16::from_le_bytes(extract_array(bytes, VERSION_OFFSET)?);
-        if version != EXPECTED_VERSION {
+        if version != EXPECTED_VERSION && version != WAL_EXTENT_VERSION {
             return Err(MemvidError::InvalidHeader {
                 reason: "unsupported version".into(),
             });
@@ -130,8 +152,27 @@ impl HeaderCodec {
         let wal_checkpoint_pos = u64::from_le_bytes(extract_array(bytes, WAL_CHECKPOINT_POS)?);
         let wal_sequence = u64::from_le_bytes(extract_array(bytes, WAL_SEQUENCE_POS)?);
         let toc_checksum: [u8; 32] = extract_array(bytes, TOC_CHECKSUM_POS)?;
+        let mode = match bytes[HOLD_MODE_POS] {
+            0 => None,
+            1 => Some(ImmutableMode::AppendOnly),
+            2 => Some(ImmutableMode::Frozen),
+            _ => {
+                return Err(MemvidError::InvalidHeader {
+                    reason: "unknown legal hold mode".into(),
+                });
+            }
+        };
+        let until = (bytes[HOLD_HAS_UNTIL_POS] != 0)
+            .then(|| extract_array(bytes, HOLD_UNTIL_POS).map(i64::from_le_bytes))
+            .transpose()?;
+        let immutable = mode.map(|mode| ImmutableHold { mode, until });
+        let data_start = if version == WAL_EXTENT_VERSION {
+            u64::from_le_bytes(extract_array(bytes, DATA_START_POS)?)
+        } else {
+            wal_offset.saturating_add(wal_size)
+        };
 
-        Ok(Header {
+        let header = Header {
             magic,
             version,
             footer_offset,
@@ -140,6 +181,27 @@ impl HeaderCodec {
             wal_checkpoint_pos,
             wal_sequence,
             toc_checksum,
+            immutable,
+            data_start,
+        };
+        check_data_start(&header)?;
+        Ok(header)
+    }
+}
+
+/// The data region starts right after the WAL slot until the WAL is relocated; from then on it
+/// starts after the header and the relocated WAL lies at or past it.
+fn check_data_start(header: &Header) -> Result<()> {
+    let valid = if header.version == WAL_EXTENT_VERSION {
+        header.data_start >= WAL_OFFSET && header.wal_relocated()
+    } else {
+        header.data_start == header.wal_end()
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(MemvidError::InvalidHeader {
+            reason: "data_start does not match the wal layout".into(),
         })
     }
 }
@@ -181,6 +243,8 @@ mod tests {
             wal_checkpoint_pos: 0,
             wal_sequence: 42,
             toc_checksum: [0xAB; 32],
+            immutable: None,
+            data_start: WAL_OFFSET + 4 * 1024 * 1024,
         }
     }
 
@@ -194,6 +258,44 @@ mod tests {
         assert_eq!(decoded.footer_offset, header.footer_offset);
         assert_eq!(decoded.wal_offset, WAL_OFFSET);
         assert_eq!(decoded.toc_checksum, header.toc_checksum);
+        assert_eq!(decoded.immutable, None);
+    }
+
+    #[test]
+    fn roundtrip_legal_hold() {
+        let mut header = sample_header();
+        header.immutable = Some(ImmutableHold {
+            mode: ImmutableMode::Frozen,
+            until: Some(1_900_000_000),
+        });
+        let encoded = HeaderCodec::encode(&header).expect("encode header");
+        let decoded = HeaderCodec::decode(&encoded).expect("decode header");
+        assert_eq!(decoded.immutable, header.immutable);
+
+        let mut unknown = encoded;
+        unknown[HOLD_MODE_POS] = 9;
+        assert!(HeaderCodec::decode(&unknown).is_err());
+    }
+
+    #[test]
+    fn roundtrip_relocated_wal() {
+        let mut header = sample_header();
+        header.version = WAL_EXTENT_VERSION;
+        header.wal_offset = 8 * 1024 * 1024;
+        header.wal_size = 8 * 1024 * 1024;
+        let encoded = HeaderCodec::encode(&header).expect("encode header");
+        let decoded = HeaderCodec::decode(&encoded).expect("decode header");
+        assert_eq!(decoded.version, WAL_EXTENT_VERSION);
+        assert_eq!(decoded.data_start, header.data_start);
+        assert!(decoded.wal_relocated());
+
+        // A pre-relocation header must keep its data region right after the WAL slot.
+        header.version = EXPECTED_VERSION;
+        assert!(HeaderCodec::encode(&header).is_err());
+        let mut legacy = encoded;
+        legacy[VERSION_OFFSET..VERSION_OFFSET + 2].copy_from_slice(&EXPECTED_VERSION.to_le_bytes());
+        let decoded = HeaderCodec::decode(&legacy).expect("decode legacy header");
+        assert_eq!(decoded.data_start, decoded.wal_end());
     }
 
     #[test]
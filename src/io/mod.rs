@@ -3,6 +3,8 @@
 pub mod header;
 #[cfg(feature = "parallel_segments")]
 pub mod manifest_wal;
+pub mod remote;
+pub(crate) mod storage;
 #[cfg(feature = "temporal_track")]
 pub mod temporal_index;
 pub mod time_index;
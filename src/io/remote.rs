@@ -0,0 +1,296 @@
+//! Byte-range access to `.mv2` files that live outside the local filesystem.
+//!
+//! A [`RangeFetcher`] is the only primitive remote readers need: the total object length and
+//! the ability to read an arbitrary `[offset, offset + len)` window. Object stores (S3, GCS,
+//! Azure) and plain HTTP servers all expose this via `Range` requests, so the trait stays small
+//! and implementations can live in downstream crates next to their SDKs.
+//!
+//! [`AsyncRangeFetcher`] is the same primitive for async callers (feature `async`); it backs
+//! `AsyncRemoteMemvid`, which awaits its range reads instead of parking a thread on them.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::{MemvidError, Result};
+
+/// Random-access byte source for a single `.mv2` object.
+///
+/// Implementations must be safe to share across threads; callers may issue concurrent range
+/// reads for independent index segments.
+pub trait RangeFetcher: Send + Sync {
+    /// Total length of the object in bytes.
+    fn len(&self) -> Result<u64>;
+
+    /// Fetch exactly `length` bytes starting at `offset`.
+    fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>>;
+}
+
+/// Async counterpart of [`RangeFetcher`] (feature `async`).
+///
+/// The returned futures must be `Send` so remote handles can be used from spawned tasks.
+#[cfg(feature = "async")]
+pub trait AsyncRangeFetcher: Send + Sync {
+    /// Total length of the object in bytes.
+    fn len(&self) -> impl std::future::Future<Output = Result<u64>> + Send;
+
+    /// Fetch exactly `length` bytes starting at `offset`.
+    fn fetch(
+        &self,
+        offset: u64,
+        length: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<u8>>> + Send;
+}
+
+/// [`RangeFetcher`] backed by a local file. Useful for tests and for mounted object stores
+/// (e.g. FUSE) where only a handful of ranges should be touched.
+pub struct FileRangeFetcher {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileRangeFetcher {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path).map_err(|source| MemvidError::Io {
+            source,
+            path: Some(path.clone()),
+        })?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl RangeFetcher for FileRangeFetcher {
+    fn len(&self) -> Result<u64> {
+        let file = self
+            .file
+            .lock()
+            .map_err(|_| MemvidError::Lock("range fetcher file mutex poisoned".into()))?;
+        Ok(file.metadata()?.len())
+    }
+
+    fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| MemvidError::Lock("range fetcher file mutex poisoned".into()))?;
+        let len = usize::try_from(length).map_err(|_| MemvidError::InvalidToc {
+            reason: "range length exceeds addressable memory".into(),
+        })?;
+        let mut buf = vec![0u8; len];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+/// [`RangeFetcher`] over plain HTTP(S) using `Range` requests.
+///
+/// Works with any server or object store that honours byte ranges, including presigned
+/// S3/GCS URLs. Requires the `remote` feature.
+#[cfg(feature = "remote")]
+pub struct HttpRangeFetcher {
+    url: String,
+    client: reqwest::blocking::Client,
+    headers: Vec<(String, String)>,
+}
+
+#[cfg(feature = "remote")]
+impl HttpRangeFetcher {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .build()
+            .map_err(|err| MemvidError::Io {
+                source: std::io::Error::other(err.to_string()),
+                path: None,
+            })?;
+        Ok(Self {
+            url: url.into(),
+            client,
+            headers: Vec::new(),
+        })
+    }
+
+    /// Attach a header (e.g. `Authorization`) to every range request.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn request(&self, method: reqwest::Method) -> reqwest::blocking::RequestBuilder {
+        let mut builder = self.client.request(method, &self.url);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        builder
+    }
+}
+
+#[cfg(feature = "remote")]
+fn http_error(reason: impl std::fmt::Display) -> MemvidError {
+    MemvidError::Io {
+        source: std::io::Error::other(reason.to_string()),
+        path: None,
+    }
+}
+
+#[cfg(feature = "remote")]
+fn range_header(offset: u64, length: u64) -> String {
+    format!("bytes={}-{}", offset, offset + length - 1)
+}
+
+/// Object length from a `HEAD` response.
+#[cfg(feature = "remote")]
+fn head_content_length(
+    url: &str,
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Result<u64> {
+    if !status.is_success() {
+        return Err(http_error(format!("HEAD {url} returned {status}")));
+    }
+    headers
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .ok_or_else(|| http_error(format!("HEAD {url} returned no Content-Length")))
+}
+
+#[cfg(feature = "remote")]
+fn check_partial_content(url: &str, status: reqwest::StatusCode) -> Result<()> {
+    if status != reqwest::StatusCode::PARTIAL_CONTENT {
+        return Err(http_error(format!(
+            "GET {url} returned {status} (expected 206 Partial Content)"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "remote")]
+fn check_body_len(bytes: &[u8], length: u64) -> Result<()> {
+    if bytes.len() as u64 != length {
+        return Err(http_error(format!(
+            "range request returned {} bytes, expected {length}",
+            bytes.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "remote")]
+impl RangeFetcher for HttpRangeFetcher {
+    fn len(&self) -> Result<u64> {
+        let response = self
+            .request(reqwest::Method::HEAD)
+            .send()
+            .map_err(http_error)?;
+        head_content_length(&self.url, response.status(), response.headers())
+    }
+
+    fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .request(reqwest::Method::GET)
+            .header(reqwest::header::RANGE, range_header(offset, length))
+            .send()
+            .map_err(http_error)?;
+        check_partial_content(&self.url, response.status())?;
+        let bytes = response.bytes().map_err(http_error)?;
+        check_body_len(&bytes, length)?;
+        Ok(bytes.to_vec())
+    }
+}
+
+/// [`AsyncRangeFetcher`] over plain HTTP(S) using `Range` requests on an async client.
+///
+/// Same protocol as [`HttpRangeFetcher`]; requires the `async` and `remote` features and must
+/// be driven from a Tokio runtime.
+#[cfg(all(feature = "async", feature = "remote"))]
+pub struct AsyncHttpRangeFetcher {
+    url: String,
+    client: reqwest::Client,
+    headers: Vec<(String, String)>,
+}
+
+#[cfg(all(feature = "async", feature = "remote"))]
+impl AsyncHttpRangeFetcher {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let client = reqwest::Client::builder().build().map_err(http_error)?;
+        Ok(Self {
+            url: url.into(),
+            client,
+            headers: Vec::new(),
+        })
+    }
+
+    /// Attach a header (e.g. `Authorization`) to every range request.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn request(&self, method: reqwest::Method) -> reqwest::RequestBuilder {
+        let mut builder = self.client.request(method, &self.url);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        builder
+    }
+}
+
+#[cfg(all(feature = "async", feature = "remote"))]
+impl AsyncRangeFetcher for AsyncHttpRangeFetcher {
+    async fn len(&self) -> Result<u64> {
+        let response = self
+            .request(reqwest::Method::HEAD)
+            .send()
+            .await
+            .map_err(http_error)?;
+        head_content_length(&self.url, response.status(), response.headers())
+    }
+
+    async fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        if length == 0 {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .request(reqwest::Method::GET)
+            .header(reqwest::header::RANGE, range_header(offset, length))
+            .send()
+            .await
+            .map_err(http_error)?;
+        check_partial_content(&self.url, response.status())?;
+        let bytes = response.bytes().await.map_err(http_error)?;
+        check_body_len(&bytes, length)?;
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn file_fetcher_reads_ranges() {
+        let mut tmp = tempfile::NamedTempFile::new().expect("tmp");
+        tmp.write_all(b"0123456789").expect("write");
+        let fetcher = FileRangeFetcher::open(tmp.path()).expect("open");
+        assert_eq!(fetcher.len().expect("len"), 10);
+        assert_eq!(fetcher.fetch(3, 4).expect("fetch"), b"3456");
+        assert!(fetcher.fetch(8, 4).is_err());
+    }
+}
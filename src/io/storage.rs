@@ -0,0 +1,101 @@
+//! File handle used for every write a `Memvid` handle and its WAL make.
+//!
+//! `StorageFile` derefs to the underlying [`File`] for reads and metadata, but routes writes,
+//! truncation, and fsync through itself. With the `testing` feature those calls consult the
+//! fault plan installed by [`crate::testing::FaultyStorage`] on the current thread; without it
+//! they forward straight to the file.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "testing")]
+use crate::testing::faults;
+
+#[derive(Debug)]
+pub(crate) struct StorageFile {
+    file: File,
+}
+
+impl StorageFile {
+    pub(crate) fn new(file: File) -> Self {
+        Self { file }
+    }
+
+    /// Swap in another file, returning the previous one.
+    pub(crate) fn replace(&mut self, file: File) -> File {
+        std::mem::replace(&mut self.file, file)
+    }
+
+    pub(crate) fn set_len(&self, size: u64) -> io::Result<()> {
+        #[cfg(feature = "testing")]
+        faults::admit_metadata()?;
+        self.file.set_len(size)
+    }
+
+    pub(crate) fn sync_all(&self) -> io::Result<()> {
+        #[cfg(feature = "testing")]
+        faults::admit_metadata()?;
+        self.file.sync_all()?;
+        #[cfg(feature = "testing")]
+        faults::record_sync();
+        Ok(())
+    }
+
+    /// Write the header bytes at offset 0. Kept apart from `write` so a fault plan can cut
+    /// power right before the header switches over to a new footer.
+    pub(crate) fn write_header(&mut self, bytes: &[u8]) -> io::Result<()> {
+        #[cfg(feature = "testing")]
+        faults::admit_header()?;
+        self.seek(SeekFrom::Start(0))?;
+        self.write_all(bytes)
+    }
+}
+
+impl Deref for StorageFile {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        &self.file
+    }
+}
+
+impl DerefMut for StorageFile {
+    fn deref_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+}
+
+impl Write for StorageFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(feature = "testing")]
+        {
+            let admitted = faults::admit_write(buf.len())?;
+            if admitted < buf.len() {
+                self.file.write_all(&buf[..admitted])?;
+                return Err(faults::power_cut());
+            }
+            let written = self.file.write(buf)?;
+            faults::record_write(written);
+            Ok(written)
+        }
+        #[cfg(not(feature = "testing"))]
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Read for StorageFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl Seek for StorageFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+}
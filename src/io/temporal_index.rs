@@ -10,7 +10,7 @@ use crate::constants::{TEMPORAL_TRACK_MAGIC, TEMPORAL_TRACK_VERSION};
 use crate::error::{MemvidError, Result};
 use crate::types::{
     AnchorSource, TemporalAnchor, TemporalMention, TemporalMentionFlags, TemporalMentionKind,
-    TemporalTrack,
+    TemporalRecurrence, TemporalTrack,
 };
 
 const HEADER_SIZE: usize = 56;
@@ -29,7 +29,7 @@ struct RawMention {
     confidence: u16,
     tz_hint_minutes: i16,
     flags: u8,
-    reserved: u16,
+    recurrence: u16,
 }
 
 impl RawMention {
@@ -43,7 +43,7 @@ impl RawMention {
             confidence: mention.confidence,
             tz_hint_minutes: mention.tz_hint_minutes,
             flags: mention.flags.0,
-            reserved: 0,
+            recurrence: mention.recurrence.map_or(0, TemporalRecurrence::to_bits),
         }
     }
 
@@ -57,7 +57,7 @@ impl RawMention {
         out[25..27].copy_from_slice(&self.confidence.to_le_bytes());
         out[27..29].copy_from_slice(&self.tz_hint_minutes.to_le_bytes());
         out[29] = self.flags;
-        out[30..32].copy_from_slice(&self.reserved.to_le_bytes());
+        out[30..32].copy_from_slice(&self.recurrence.to_le_bytes());
         out
     }
 
@@ -70,7 +70,8 @@ impl RawMention {
         let confidence = u16::from_le_bytes(bytes[25..27].try_into().unwrap());
         let tz_hint_minutes = i16::from_le_bytes(bytes[27..29].try_into().unwrap());
         let flags = bytes[29];
-        let reserved = u16::from_le_bytes(bytes[30..32].try_into().unwrap());
+        let recurrence =
+            u16::from_le_bytes(bytes[30..32].try_into().expect("two-byte recurrence slice"));
         Ok(Self {
             ts_utc,
             frame_id,
@@ -80,7 +81,7 @@ impl RawMention {
             confidence,
             tz_hint_minutes,
             flags,
-            reserved,
+            recurrence,
         })
     }
 
@@ -89,7 +90,7 @@ impl RawMention {
             TemporalMentionKind::from_u8(self.kind).ok_or(MemvidError::InvalidTemporalTrack {
                 reason: "unknown mention kind".into(),
             })?;
-        Ok(TemporalMention::new(
+        let mut mention = TemporalMention::new(
             self.ts_utc,
             self.frame_id,
             self.byte_start,
@@ -98,7 +99,9 @@ impl RawMention {
             self.confidence,
             self.tz_hint_minutes,
             TemporalMentionFlags(self.flags),
-        ))
+        );
+        mention.recurrence = TemporalRecurrence::from_bits(self.recurrence);
+        Ok(mention)
     }
 }
 
@@ -454,6 +457,20 @@ mod tests {
                 0,
                 TemporalMentionFlags(TemporalMentionFlags::HAS_RANGE),
             ),
+            TemporalMention::new(
+                30,
+                2,
+                4,
+                9,
+                TemporalMentionKind::Recurring,
+                900,
+                0,
+                TemporalMentionFlags::empty(),
+            )
+            .with_recurrence(TemporalRecurrence::new(
+                crate::types::RecurrenceFrequency::Monthly,
+                3,
+            )),
         ];
         let mut anchors = vec![
             TemporalAnchor::new(1, 100, AnchorSource::FrameTimestamp),
@@ -465,7 +482,8 @@ mod tests {
             append_track(&mut file, &mut mentions, &mut anchors, 0).expect("append track");
 
         let track = read_track(&mut file, offset, length).expect("read track");
-        assert_eq!(track.mentions.len(), 3);
+        assert_eq!(track.mentions.len(), 4);
+        assert_eq!(track.mentions[3], mentions[3]);
         assert_eq!(track.anchors.len(), 2);
         assert_eq!(track.flags, 0);
 
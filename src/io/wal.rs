@@ -4,6 +4,7 @@ use std::io::{Read, Seek, SeekFrom, Write};
 use crate::{
     constants::{WAL_CHECKPOINT_PERIOD, WAL_CHECKPOINT_THRESHOLD},
     error::{MemvidError, Result},
+    io::storage::StorageFile,
     types::Header,
 };
 
@@ -26,7 +27,7 @@ pub struct WalRecord {
 
 #[derive(Debug)]
 pub struct EmbeddedWal {
-    file: File,
+    file: StorageFile,
     region_offset: u64,
     region_size: u64,
     write_head: u64,
@@ -71,7 +72,7 @@ impl EmbeddedWal {
             .map_or(checkpoint_sequence, |entry| entry.sequence);
 
         let mut wal = Self {
-            file: clone,
+            file: StorageFile::new(clone),
             region_offset,
             region_size,
             write_head: next_head % region_size,
@@ -159,7 +160,13 @@ impl EmbeddedWal {
         if self.read_only || self.region_size == 0 {
             return false;
         }
-        let occupancy = self.pending_bytes as f64 / self.region_size as f64;
+        // Pending records cannot wrap, so the log is as full as its write head is far along.
+        let used = if self.pending_bytes == 0 {
+            0
+        } else {
+            self.write_head.max(self.pending_bytes)
+        };
+        let occupancy = used as f64 / self.region_size as f64;
         occupancy >= WAL_CHECKPOINT_THRESHOLD
             || self.appends_since_checkpoint >= WAL_CHECKPOINT_PERIOD
     }
@@ -175,6 +182,77 @@ impl EmbeddedWal {
         self.maybe_write_sentinel()
     }
 
+    /// Zero the whole region, checkpointed records included, so their payloads no longer
+    /// linger on disk. Refused while records are pending; the sequence carries on.
+    pub fn scrub(&mut self, header: &mut Header) -> Result<()> {
+        self.assert_writable()?;
+        if self.pending_bytes > 0 {
+            return Err(MemvidError::CheckpointFailed {
+                reason: "cannot scrub a wal with pending records".into(),
+            });
+        }
+        let zeros = vec![0u8; usize::try_from(self.region_size.min(1 << 20)).unwrap_or(1 << 20)];
+        let mut written = 0u64;
+        while written < self.region_size {
+            let len = (self.region_size - written).min(zeros.len() as u64);
+            self.file
+                .seek(SeekFrom::Start(self.region_offset + written))?;
+            #[allow(clippy::cast_possible_truncation)]
+            self.file.write_all(&zeros[..len as usize])?;
+            written += len;
+        }
+        self.file.sync_all()?;
+        self.write_head = 0;
+        self.checkpoint_head = 0;
+        header.wal_checkpoint_pos = 0;
+        header.wal_sequence = self.checkpoint_sequence;
+        Ok(())
+    }
+
+    /// Move the WAL to a fresh `size`-byte region at `offset`, carrying the pending records over
+    /// to its start. The old region is left as is, so the header only has to point here once
+    /// this returns.
+    pub fn relocate(&mut self, header: &mut Header, offset: u64, size: u64) -> Result<()> {
+        self.assert_writable()?;
+        // With nothing pending the old region is not read at all, so it may already be reused.
+        let pending: Vec<ScannedRecord> = if self.pending_bytes == 0 {
+            Vec::new()
+        } else {
+            Self::scan_records(&mut self.file, self.region_offset, self.region_size)?
+                .0
+                .into_iter()
+                .filter(|entry| entry.sequence > self.checkpoint_sequence)
+                .collect()
+        };
+        let pending_bytes: u64 = pending.iter().map(|entry| entry.total_size).sum();
+        if pending_bytes + ENTRY_HEADER_SIZE as u64 > size {
+            return Err(MemvidError::CheckpointFailed {
+                reason: "relocated WAL region too small for pending records".into(),
+            });
+        }
+
+        self.region_offset = offset;
+        self.region_size = size;
+        self.write_head = 0;
+        self.checkpoint_head = 0;
+        let skip_sync = std::mem::replace(&mut self.skip_sync, true);
+        let copied = pending.iter().try_for_each(|entry| -> Result<()> {
+            self.write_record(self.write_head, entry.sequence, &entry.payload)?;
+            self.write_head += entry.total_size;
+            Ok(())
+        });
+        self.skip_sync = skip_sync;
+        copied?;
+        self.pending_bytes = pending_bytes;
+        self.maybe_write_sentinel()?;
+        self.file.sync_all()?;
+
+        header.wal_offset = offset;
+        header.wal_size = size;
+        header.wal_checkpoint_pos = 0;
+        Ok(())
+    }
+
     pub fn pending_records(&mut self) -> Result<Vec<WalRecord>> {
         self.records_after(self.checkpoint_sequence)
     }
@@ -204,6 +282,45 @@ impl EmbeddedWal {
             .collect())
     }
 
+    /// Walk the records at the start of a raw WAL region, stopping quietly at the first
+    /// sentinel, truncated record, or checksum mismatch. Returns each record with its offset in
+    /// `region`, and the offset where the walk stopped. Used to salvage damaged files, where
+    /// the header describing the region may itself be unreadable.
+    #[must_use]
+    pub fn salvage_records(region: &[u8]) -> (Vec<(usize, WalRecord)>, usize) {
+        let mut records = Vec::new();
+        let mut cursor = 0usize;
+        while let Some(header) = region.get(cursor..cursor + ENTRY_HEADER_SIZE) {
+            let mut sequence = [0u8; 8];
+            sequence.copy_from_slice(&header[..8]);
+            let sequence = u64::from_le_bytes(sequence);
+            let mut length = [0u8; 4];
+            length.copy_from_slice(&header[8..12]);
+            let Ok(length) = usize::try_from(u32::from_le_bytes(length)) else {
+                break;
+            };
+            if length == 0 {
+                break;
+            }
+            let start = cursor + ENTRY_HEADER_SIZE;
+            let Some(payload) = region.get(start..start + length) else {
+                break;
+            };
+            if blake3::hash(payload).as_bytes() != &header[16..48] {
+                break;
+            }
+            records.push((
+                cursor,
+                WalRecord {
+                    sequence,
+                    payload: payload.to_vec(),
+                },
+            ));
+            cursor = start + length;
+        }
+        (records, cursor)
+    }
+
     #[must_use]
     pub fn stats(&self) -> WalStats {
         WalStats {
@@ -437,7 +554,7 @@ impl EmbeddedWal {
 mod tests {
     use super::*;
     use crate::constants::WAL_OFFSET;
-    use std::io::{Seek, SeekFrom, Write};
+    use std::io::{Read, Seek, SeekFrom, Write};
     use tempfile::tempfile;
 
     fn header_for(size: u64) -> Header {
@@ -450,6 +567,8 @@ mod tests {
             wal_checkpoint_pos: 0,
             wal_sequence: 0,
             toc_checksum: [0u8; 32],
+            immutable: None,
+            data_start: WAL_OFFSET + size,
         }
     }
 
@@ -494,6 +613,48 @@ mod tests {
         assert_eq!(records[0].payload, vec![0xCC; 32]);
     }
 
+    #[test]
+    fn scrub_erases_checkpointed_records() {
+        let (mut file, mut header) = prepare_wal(1024);
+        let mut wal = EmbeddedWal::open(&file, &header).expect("open wal");
+        wal.append_entry(b"private").expect("append");
+        assert!(wal.scrub(&mut header).is_err(), "pending records survive");
+        wal.record_checkpoint(&mut header).expect("checkpoint");
+        wal.scrub(&mut header).expect("scrub");
+
+        let mut region = vec![0u8; 1024];
+        file.seek(SeekFrom::Start(header.wal_offset)).expect("seek");
+        file.read_exact(&mut region).expect("read");
+        assert!(region.iter().all(|byte| *byte == 0));
+
+        let mut reopened = EmbeddedWal::open(&file, &header).expect("reopen");
+        assert_eq!(reopened.stats().sequence, 1);
+        let seq = reopened.append_entry(b"next").expect("append after scrub");
+        assert_eq!(seq, 2);
+    }
+
+    #[test]
+    fn relocate_carries_pending_records() {
+        let (file, mut header) = prepare_wal(256);
+        let mut wal = EmbeddedWal::open(&file, &header).expect("open wal");
+        wal.append_entry(b"done").expect("append done");
+        wal.record_checkpoint(&mut header).expect("checkpoint");
+        wal.append_entry(b"pending").expect("append pending");
+
+        let offset = WAL_OFFSET + 256 + 128;
+        file.set_len(offset + 1024).expect("extend");
+        wal.relocate(&mut header, offset, 1024).expect("relocate");
+        assert_eq!((header.wal_offset, header.wal_size), (offset, 1024));
+        assert_eq!(header.wal_checkpoint_pos, 0);
+
+        let mut reopened = EmbeddedWal::open(&file, &header).expect("reopen");
+        let records = reopened.pending_records().expect("pending");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].payload, b"pending");
+        assert_eq!(records[0].sequence, 2);
+        assert_eq!(reopened.append_entry(b"next").expect("append"), 3);
+    }
+
     #[test]
     fn corrupted_record_reports_offset() {
         let (mut file, header) = prepare_wal(64);
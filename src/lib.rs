@@ -82,17 +82,20 @@ pub mod error;
 pub mod extract;
 pub mod extract_budgeted;
 pub mod footer;
+pub mod git;
 pub mod io;
 pub mod lex;
 mod lock;
 pub mod lockfile;
 pub mod memvid;
+pub mod metrics;
 pub mod models;
 pub mod pii;
 pub mod reader;
 mod registry;
 mod search;
 pub mod signature;
+pub mod sql;
 pub mod structure;
 pub mod table;
 pub mod text;
@@ -100,13 +103,25 @@ mod toc;
 pub mod types;
 pub mod vec;
 pub mod vec_pq;
+pub mod vec_scalar;
 
 // SIMD-accelerated distance calculations
 pub mod simd;
 
+// Device selection for local ONNX inference
+pub mod inference_device;
+
 #[cfg(feature = "vec")]
 pub mod text_embed;
 
+// SPLADE encoder for the learned sparse track
+#[cfg(feature = "splade")]
+pub mod splade;
+
+// ColBERT token encoder for late-interaction reranking
+#[cfg(feature = "colbert")]
+pub mod colbert;
+
 // Triplet extraction module for automatic SPO extraction during ingestion
 pub mod triplet;
 
@@ -121,6 +136,9 @@ pub mod clip;
 // Model inference requires the "whisper" feature
 pub mod whisper;
 
+// Video decoding (keyframes and audio track) for video ingestion
+pub mod video;
+
 // Replay module for time-travel debugging of agent sessions
 // Types are always available for serde compatibility
 // Full functionality requires the "replay" feature
@@ -135,10 +153,46 @@ pub mod encryption;
 #[cfg(feature = "symspell_cleanup")]
 pub mod symspell_cleanup;
 
-// API-based embedding providers (OpenAI, etc.) - requires network
+// API-based embedding providers (OpenAI, Cohere, Voyage, Ollama, etc.) - requires network
 #[cfg(feature = "api_embed")]
 pub mod api_embed;
 
+// LLM backends for generative ask
+#[cfg(any(feature = "llm_openai", feature = "llm_local"))]
+pub mod llm;
+
+// Rerankers for second-stage search ranking
+#[cfg(any(feature = "vec", feature = "api_embed"))]
+pub mod rerank;
+
+// Async facade over the blocking API for Tokio-based services
+#[cfg(feature = "async")]
+pub mod async_api;
+
+// HTTP/JSON serving layer for a directory of memories
+#[cfg(feature = "server")]
+pub mod server;
+
+// MCP tool adapter exposing a memory to agents over stdio
+#[cfg(feature = "mcp")]
+pub mod mcp;
+
+// Continuous folder sync into a memory
+#[cfg(feature = "notify")]
+pub mod watch;
+
+// Python bindings (PyO3)
+#[cfg(feature = "python")]
+pub mod python;
+
+// C ABI for native embedding (header: include/memvid.h)
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+// Fault-injection storage and invariant checkers for crash-safety suites
+#[cfg(feature = "testing")]
+pub mod testing;
+
 #[cfg(test)]
 mod tests_lex_flag;
 
@@ -147,7 +201,11 @@ pub use analysis::temporal::{
     TemporalContext, TemporalNormalizer, TemporalResolution, TemporalResolutionFlag,
     TemporalResolutionValue, parse_clock_inheritance, parse_week_start,
 };
+pub use analysis::timezone::{
+    TEMPORAL_TZ_EXTENSION, TEMPORAL_TZ_KEY, is_supported_timezone, timezone_offset,
+};
 // Temporal enrichment for resolving relative time references during ingestion
+pub use analysis::language::{LANGUAGE_KEY, detect_language, normalize_language};
 #[cfg(feature = "temporal_enrich")]
 pub use analysis::temporal_enrich::{
     AnchorSource as TemporalEnrichAnchorSource, RelativePhrase, ResolvedTemporal,
@@ -155,10 +213,18 @@ pub use analysis::temporal_enrich::{
     enrich_chunk, enrich_chunks, enrich_document, resolve_relative_phrase,
 };
 pub use constants::*;
-pub use enrichment_worker::{EnrichmentWorkerConfig, EnrichmentWorkerStats};
+pub use enrichment_worker::{EnrichmentWorkerConfig, EnrichmentWorkerStats, KindPolicy, RateLimit};
 pub use error::{MemvidError, Result};
 pub use extract::{DocumentProcessor, ExtractedDocument, ProcessorConfig};
 pub use footer::{CommitFooter, find_last_valid_footer};
+pub use inference_device::InferenceDevice;
+#[cfg(all(feature = "async", feature = "remote"))]
+pub use io::remote::AsyncHttpRangeFetcher;
+#[cfg(feature = "async")]
+pub use io::remote::AsyncRangeFetcher;
+#[cfg(feature = "remote")]
+pub use io::remote::HttpRangeFetcher;
+pub use io::remote::{FileRangeFetcher, RangeFetcher};
 #[cfg(feature = "temporal_track")]
 pub use io::temporal_index::{
     append_track as temporal_track_append, calculate_checksum as temporal_track_checksum,
@@ -172,20 +238,24 @@ pub use io::wal::{EmbeddedWal, WalRecord, WalStats};
 pub use lex::{LexIndex, LexIndexArtifact, LexIndexBuilder, LexSearchHit};
 pub use lock::FileLock;
 pub use memvid::{
-    BlobReader, EnrichmentHandle, EnrichmentStats, LockSettings, Memvid, OpenReadOptions,
-    SketchCandidate, SketchSearchOptions, SketchSearchStats,
-    mutation::{CommitMode, CommitOptions},
+    BlobReader, CommitSubscription, EnrichmentHandle, EnrichmentProgress, EnrichmentStats,
+    LockSettings, Memvid, MemvidUnion, OpenReadOptions, RemoteMemvid, SketchCandidate,
+    SketchSearchOptions, SketchSearchStats,
+    mutation::{CommitMode, CommitOptions, DurabilityProfile},
     start_enrichment_worker, start_enrichment_worker_with_embeddings,
 };
 #[cfg(feature = "parallel_segments")]
 pub use memvid::{BuildOpts, ParallelInput, ParallelPayload};
+pub use metrics::{Metrics, clear_metrics, set_metrics};
 pub use models::{
     ModelManifest, ModelManifestEntry, ModelVerification, ModelVerificationStatus,
     ModelVerifyOptions, verify_model_dir, verify_models,
 };
 pub use reader::{
-    DetectedTable, DocumentFormat, DocumentReader, PassthroughReader, PdfReader, ReaderDiagnostics,
-    ReaderHint, ReaderOutput, ReaderRegistry, XlsxChunkingOptions, XlsxReader,
+    ArchiveContents, ArchiveFormat, ArchiveMember, ArchiveOptions, ArchiveReader, ArchiveSkip,
+    DetectedTable, DocumentFormat, DocumentReader, JsonlFieldMapping, JsonlLineError, JsonlParse,
+    JsonlReader, JsonlRecord, PassthroughReader, PdfReader, ReaderDiagnostics, ReaderHint,
+    ReaderOutput, ReaderRegistry, XlsxChunkingOptions, XlsxReader,
 };
 pub use signature::{
     parse_ed25519_public_key_base64, verify_model_manifest, verify_ticket_signature,
@@ -193,42 +263,114 @@ pub use signature::{
 pub use text::{NormalizedText, normalize_text, truncate_at_grapheme_boundary};
 pub use types::{
     ACL_POLICY_VERSION_KEY, ACL_READ_GROUPS_KEY, ACL_READ_PRINCIPALS_KEY, ACL_READ_ROLES_KEY,
-    ACL_RESOURCE_ID_KEY, ACL_TENANT_ID_KEY, ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode,
-    AskCitation, AskMode, AskRequest, AskResponse, AskRetriever, AskStats, AudioSegmentMetadata,
-    AuditOptions, AuditReport, CanonicalEncoding, DOCTOR_PLAN_VERSION, DocAudioMetadata,
-    DocExifMetadata, DocGpsMetadata, DocMetadata, DoctorActionDetail, DoctorActionKind,
-    DoctorActionPlan, DoctorActionReport, DoctorActionStatus, DoctorFinding, DoctorFindingCode,
-    DoctorMetrics, DoctorOptions, DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan,
-    DoctorPhaseReport, DoctorPhaseStatus, DoctorPlan, DoctorReport, DoctorSeverity, DoctorStatus,
-    EmbeddingIdentity, EmbeddingIdentityCount, EmbeddingIdentitySummary, Frame, FrameId, FrameRole,
-    FrameStatus, Header, IndexManifests, LexIndexManifest, LexSegmentDescriptor,
+    ACL_RESOURCE_ID_KEY, ACL_TENANT_ID_KEY, ACL_VISIBILITY_KEY, AUDIO_END_MS_KEY, AUDIO_FRAME_KIND,
+    AUDIO_SEGMENT_FRAME_KIND, AUDIO_START_MS_KEY, AUDIT_CHAIN_EXTENSION, AclContext,
+    AclEnforcementMode, AskCitation, AskMode, AskRequest, AskResponse, AskRetriever, AskStats,
+    AudioReceipt, AudioSegmentMetadata, AuditAction, AuditChain, AuditChainReport, AuditEntry,
+    AuditOptions, AuditReport, BackfillReport, CHAT_AUTHOR_KEY, CHAT_INDEX_KEY, CHAT_ROLE_KEY,
+    CLASSIFICATION_KEY, COMMIT_HISTORY_EXTENSION, COMMIT_LOG_EXTENSION, CONSENT_KEY,
+    CanonicalEncoding, CardContradiction, ChatMessage, ChatRole, ChatSession, ChatSessionMessage,
+    ChatSessionQuery, ChatSessionSummary, CitationHighlight, Classification, ClassificationAction,
+    ClassificationPolicy, CommitEvent, CommitHistory, CommitLog, CommitMetadata, CommitProvenance,
+    ConversationReceipt, DOCTOR_PLAN_VERSION, DOCTOR_REPORT_VERSION, DeltaBundle, DeltaRange,
+    DocAudioMetadata, DocExifMetadata, DocGpsMetadata, DocMetadata, DoctorActionDetail,
+    DoctorActionKind, DoctorActionPlan, DoctorActionReport, DoctorActionStatus, DoctorByteRange,
+    DoctorFinding, DoctorFindingCode, DoctorIndexBump, DoctorIndexKind, DoctorMetrics,
+    DoctorOptions, DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan, DoctorPhaseReport,
+    DoctorPhaseStatus, DoctorPlan, DoctorPlanDiff, DoctorReport, DoctorSeverity, DoctorStatus,
+    DuplicateCluster, DuplicateKind, EmbeddingIdentity, EmbeddingIdentityCount,
+    EmbeddingIdentitySummary, EmbeddingMigrationReport, EmbeddingMigrationState, Frame, FrameId,
+    FrameRole, FrameStatus, FrameSupersession, GEO_LAT_KEY, GEO_LON_KEY, GeoFilter, GeoIndex,
+    GeoPoint, Header, HighlightSpan, ImmutableHold, ImmutableMode, IndexManifests,
+    LexIndexManifest, LexSegmentDescriptor, LlmBackend, LlmCompletion, LlmParams,
     MEMVID_EMBEDDING_DIMENSION_KEY, MEMVID_EMBEDDING_MODEL_KEY, MEMVID_EMBEDDING_NORMALIZED_KEY,
-    MEMVID_EMBEDDING_PROVIDER_KEY, MediaManifest, MemvidHandle, Open, PutManyOpts, PutOptions,
-    PutOptionsBuilder, Sealed, SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams,
-    SearchRequest, SearchResponse, SegmentCatalog, SegmentCommon, SegmentCompression, SegmentMeta,
-    SegmentSpan, SourceSpan, Stats, TextChunkManifest, TextChunkRange, Ticket, TicketRef, Tier,
-    TimeIndexManifest, TimeSegmentDescriptor, TimelineEntry, TimelineQuery, TimelineQueryBuilder,
-    Toc, VecEmbedder, VecIndexManifest, VecSegmentDescriptor, VectorCompression, VerificationCheck,
-    VerificationReport, VerificationStatus,
+    MEMVID_EMBEDDING_PROVIDER_KEY, MESSAGE_FRAME_KIND, META_SCHEMA_EXTENSION, MediaManifest,
+    MemoryDiff, MemvidHandle, MetaField, MetaFilter, MetaIndex, MetaOp, MetaSchema, MetaType,
+    MetaValue, Open, PDF_PAGE_KEY, PDF_SPANS_KEY, PageRect, PdfTextSpan, PutManyOpts, PutOptions,
+    PutOptionsBuilder, REDACTED_TEXT, SESSION_FRAME_KIND, SESSION_ID_KEY, Sealed, SearchEngineKind,
+    SearchHit, SearchHitMetadata, SearchMode, SearchParams, SearchRequest, SearchResponse,
+    SegmentCatalog, SegmentCommon, SegmentCompression, SegmentMeta, SegmentSpan, SkippedStage,
+    Snapshot, SourceSpan, Stats, Summarizer, SummaryCard, SummaryTarget, SummaryTrack,
+    TextChunkManifest, TextChunkRange, Ticket, TicketRef, Tier, TimeBucket, TimeIndexManifest,
+    TimeSegmentDescriptor, TimelineBucket, TimelineEntry, TimelineQuery, TimelineQueryBuilder, Toc,
+    VecEmbedder, VecIndexManifest, VecRescore, VecSegmentDescriptor, VectorCompression,
+    VerificationCheck, VerificationReport, VerificationStatus,
 };
 #[cfg(feature = "temporal_track")]
 pub use types::{
-    AnchorSource, SearchHitTemporal, SearchHitTemporalAnchor, SearchHitTemporalMention,
-    TEMPORAL_TRACK_FLAG_HAS_ANCHORS, TEMPORAL_TRACK_FLAG_HAS_MENTIONS, TemporalAnchor,
-    TemporalCapabilities, TemporalFilter, TemporalMention, TemporalMentionFlags,
-    TemporalMentionKind, TemporalTrack, TemporalTrackManifest,
+    AnchorSource, RecurrenceFrequency, SearchHitTemporal, SearchHitTemporalAnchor,
+    SearchHitTemporalMention, TEMPORAL_TRACK_FLAG_HAS_ANCHORS, TEMPORAL_TRACK_FLAG_HAS_MENTIONS,
+    TemporalAnchor, TemporalCapabilities, TemporalFilter, TemporalMention, TemporalMentionFlags,
+    TemporalMentionKind, TemporalRecurrence, TemporalTrack, TemporalTrackManifest,
 };
 // Memory card types for structured memory extraction and storage
+pub use types::{ACCESS_STATS_EXTENSION, AccessStats, FrameAccess, HotFrame};
+pub use types::{ANALYZER_CONFIG_EXTENSION, AnalyzerConfig};
+pub use types::{ApproxTokenCounter, ContextWindow, TokenCounter};
+pub use types::{
+    BLOB_EXTENT_EXTENSION, BlobExtent, BlobExtentOptions, BlobExtentStats, BlobExtentStore,
+};
+pub use types::{COLLECTION_EXTENSION, Collection, CollectionRegistry, CollectionStats};
+pub use types::{COMPRESSION_EXTENSION, CompressionCodec, CompressionDefaults, DEFAULT_ZSTD_LEVEL};
+pub use types::{
+    CapacityBreakdown, CapacityReport, CapacitySource, DEFAULT_CAPACITY_WARNING_PERCENT,
+    PayloadUsage, StorageBreakdown,
+};
+pub use types::{
+    CardConflict, ConflictKind, EngineStamp, EnrichmentManifest, EnrichmentRecord,
+    MEMORIES_TRACK_MAGIC, MEMORIES_TRACK_VERSION, MemoriesStats, MemoriesTrack, MemoryCard,
+    MemoryCardBuilder, MemoryCardBuilderError, MemoryCardId, MemoryKind, Polarity, SlotIndex,
+    VersionRelation,
+};
+pub use types::{ClusterOptions, TopicCluster};
+pub use types::{CommitHookEvent, EnrichmentEvent, MemvidHooks, PutEvent};
+pub use types::{
+    DEFAULT_MEMORY_QUERY_LIMIT, MemoryQuery, MemoryQueryHit, MemoryQueryResult, MemorySortOrder,
+};
+pub use types::{DEFAULT_SKETCH_PREFILTER_THRESHOLD, FILE_CONFIG_EXTENSION, FileConfig};
+pub use types::{DEFAULT_TAG_LOG_CAPACITY, TAG_LOG_EXTENSION, TagEdit, TagEditRecord, TagLog};
+pub use types::{DecayPolicy, DecayReport, ImportanceRecord, ImportanceTable};
 pub use types::{
-    EngineStamp, EnrichmentManifest, EnrichmentRecord, MEMORIES_TRACK_MAGIC,
-    MEMORIES_TRACK_VERSION, MemoriesStats, MemoriesTrack, MemoryCard, MemoryCardBuilder,
-    MemoryCardBuilderError, MemoryCardId, MemoryKind, Polarity, SlotIndex, VersionRelation,
+    ENRICHMENT_PRIORITY_EXTENSION, EnrichmentPriorities, EnrichmentPriority, EnrichmentStage,
 };
+pub use types::{ExplainFilter, HitExplain, QueryExplain, StageTiming};
+pub use types::{
+    FrameLink, LinkDirection, LinkKind, RELATIONS_EXTENSION, RelatedFrame, RelationsTrack,
+};
+pub use types::{INGEST_PROFILE_EXTENSION, IngestProfile, IngestProfileRegistry};
+pub use types::{SUGGEST_INDEX_EXTENSION, SuggestIndex, Suggestion, SuggestionKind};
+pub use types::{SalvageReport, SalvageSource, SalvagedFrame, UnrecoverableRange};
 // Logic-Mesh types for entity-relationship graph traversal
+pub use git::GitReader;
+pub use types::CODE_SYMBOLS_KEY;
+pub use types::{ARCHIVE_FRAME_KIND, ARCHIVE_MEMBER_KEY, ArchiveReceipt};
+pub use types::{
+    AbsorbedEntity, ENTITY_MERGE_LOG_EXTENSION, EntityMerge, EntityMergeLog,
+    EntityResolutionReport, EntityResolutionStrategy, MergeSignal,
+};
+pub use types::{
+    DEFAULT_FRAME_EXPORT_BATCH_SIZE, FrameExportColumn, FrameExportFilter, FrameExportFormat,
+};
+pub use types::{
+    DEFAULT_GIT_MAX_FILE_BYTES, GIT_AUTHOR_KEY, GIT_BLOB_KEY, GIT_COMMIT_FRAME_KIND,
+    GIT_COMMIT_KEY, GIT_FILE_FRAME_KIND, GIT_LANGUAGE_KEY, GIT_PATH_KEY, GitCommit,
+    GitIngestOptions, GitIngestReport, GitTreeEntry,
+};
+pub use types::{
+    DEFAULT_INGEST_BATCH_SIZE, DirSyncReport, IngestDirOptions, IngestDirReport, IngestFailure,
+    IngestFileEvent, IngestFileOutcome, SOURCE_HASH_KEY,
+};
+pub use types::{ERASURE_LOG_EXTENSION, ErasureLog, ErasureReceipt};
+pub use types::{
+    EXTERNAL_INDEX_EXTENSION, ExternalIndexManifest, SIDECAR_EXTENSION, SIDECAR_MAGIC,
+    SIDECAR_VERSION, sidecar_path,
+};
 pub use types::{
     EdgeDirection, EntityKind, FollowResult, LOGIC_MESH_MAGIC, LOGIC_MESH_VERSION, LinkType,
     LogicMesh, LogicMeshManifest, MeshEdge, MeshNode,
 };
+pub use types::{GraphExportFormat, GraphExportStats};
+pub use types::{JSONL_LINE_KEY, JsonlReceipt};
 // Sketch track types for fast candidate generation
 pub use types::{
     DEFAULT_HAMMING_THRESHOLD, QuerySketch, SKETCH_TRACK_MAGIC, SKETCH_TRACK_VERSION, SketchEntry,
@@ -237,9 +379,22 @@ pub use types::{
     hash_token, hash_token_u32, read_sketch_track, term_filter_maybe_contains, tokenize_for_sketch,
     write_sketch_track,
 };
+// Learned sparse (SPLADE-style) retrieval track
+pub use types::{
+    SPARSE_TRACK_EXTENSION, SPARSE_TRACK_MAGIC, SPARSE_TRACK_VERSION, SparseEncoder,
+    SparseIndexReport, SparseTrack, SparseTrackManifest, SparseTrackStats, SparseVector,
+    read_sparse_track, write_sparse_track,
+};
+// Token embeddings for late-interaction (ColBERT-style) reranking
+pub use types::{
+    TOKEN_TRACK_EXTENSION, TOKEN_TRACK_MAGIC, TOKEN_TRACK_VERSION, TokenEmbeddings, TokenEncoder,
+    TokenIndexReport, TokenTrack, TokenTrackManifest, TokenTrackStats, read_token_track,
+    write_token_track,
+};
 // Schema types for predicate validation and type checking
 pub use types::{
-    Cardinality, PredicateId, PredicateSchema, SchemaError, SchemaRegistry, ValueType,
+    Cardinality, MAX_VIOLATION_SAMPLES, PredicateId, PredicateSchema, PredicateViolations,
+    SchemaError, SchemaRegistry, SchemaViolation, SchemaViolationLog, ValueType,
 };
 // Schema inference summary type
 pub use memvid::memory::SchemaSummaryEntry;
@@ -252,15 +407,18 @@ pub use analysis::ner::{
     is_ner_model_installed, ner_model_path, ner_tokenizer_path,
 };
 // Enrichment engine types for extracting memory cards from frames
-pub use enrich::{EnrichmentContext, EnrichmentEngine, EnrichmentResult, RulesEngine};
+pub use enrich::{
+    EngineRegistry, EnrichmentContext, EnrichmentEngine, EnrichmentResult, RegisteredEngine,
+    RulesEngine,
+};
 // Triplet extraction types for automatic SPO extraction
-pub use triplet::{ExtractionMode, ExtractionStats, TripletExtractor};
+pub use triplet::{ExtractionMode, ExtractionStats, TripletExtractor, TripletSource};
 // Graph-aware search for hybrid retrieval
 pub use graph_search::{GraphMatcher, QueryPlanner, hybrid_search};
 // Embedding provider types for vector embedding generation
 pub use types::{
-    BatchEmbeddingResult, EmbeddingConfig, EmbeddingProvider, EmbeddingProviderKind,
-    EmbeddingResult,
+    BatchEmbeddingResult, EmbedderRequestStats, EmbeddingConfig, EmbeddingProvider,
+    EmbeddingProviderKind, EmbeddingResult,
 };
 // Reranker types for second-stage ranking in RAG pipelines
 pub use types::reranker::{
@@ -270,21 +428,25 @@ pub use types::reranker::{
 pub use types::{IndexSegmentRef, SegmentKind, SegmentStats};
 pub use vec::{VecIndex, VecIndexArtifact, VecSearchHit};
 pub use vec_pq::{
-    CompressionStats, ProductQuantizer, QuantizedVecIndex, QuantizedVecIndexArtifact,
-    QuantizedVecIndexBuilder,
+    CompressionStats, FullPrecisionStore, ProductQuantizer, QuantizedVecIndex,
+    QuantizedVecIndexArtifact, QuantizedVecIndexBuilder,
 };
+pub use vec_scalar::{ScalarEncoding, ScalarQuantizedVecIndex, ScalarQuantizedVecIndexBuilder};
 // Local text embedding provider - feature-gated
 #[cfg(feature = "vec")]
 pub use text_embed::{
-    LocalTextEmbedder, TEXT_EMBED_MODELS, TextEmbedConfig, TextEmbedModelInfo,
+    EmbeddingPooling, LocalTextEmbedder, TEXT_EMBED_MODELS, TextEmbedConfig, TextEmbedModelInfo,
     default_text_model_info, get_text_model_info,
 };
 // API-based embedding providers - feature-gated
 #[cfg(feature = "api_embed")]
 pub use api_embed::{
-    OPENAI_MODELS, OpenAIConfig, OpenAIEmbedder, OpenAIModelInfo, default_openai_model_info,
-    get_openai_model_info,
+    ApiEmbedder, ApiEmbedderConfig, ApiFormat, ApiModelInfo, COHERE_MODELS, OPENAI_MODELS,
+    OpenAIConfig, OpenAIEmbedder, OpenAIModelInfo, VOYAGE_MODELS, default_openai_model_info,
+    embedder_for_kind, get_openai_model_info,
 };
+#[cfg(feature = "async")]
+pub use async_api::{AsyncMemvid, AsyncRemoteMemvid, BlockingRemoteAdapter};
 // CLIP visual embeddings - types always available for serde compatibility
 pub use clip::{
     CLIP_MODELS, ClipConfig, ClipDocument, ClipEmbeddingProvider, ClipError, ClipIndex,
@@ -297,16 +459,20 @@ pub use clip::{
 pub use clip::{ClipModel, calculate_color_variance, get_image_info};
 // Whisper audio transcription - types always available
 pub use whisper::{
-    TranscriptionResult, TranscriptionSegment, WHISPER_MODELS, WhisperConfig, WhisperError,
-    WhisperModelInfo, default_whisper_model_info, get_whisper_model_info,
+    DiarizationConfig, SPEAKER_KEY, TranscriptionResult, TranscriptionSegment, WHISPER_MODELS,
+    WhisperConfig, WhisperError, WhisperModelInfo, default_whisper_model_info, diarize_segments,
+    get_whisper_model_info,
 };
 // Audio decoding and transcription require the "whisper" feature
 #[cfg(feature = "whisper")]
-pub use whisper::{WHISPER_SAMPLE_RATE, WhisperTranscriber, decode_audio_file};
+pub use whisper::{WHISPER_SAMPLE_RATE, WhisperTranscriber, decode_audio_bytes, decode_audio_file};
+// Video keyframe and audio extraction via ffmpeg
+pub use sql::{QueryResult, QueryValue};
+pub use video::{DEFAULT_KEYFRAME_INTERVAL_MS, FfmpegConfig, FfmpegVideoDecoder};
 // Structure-aware chunking for preserving tables and code blocks
 pub use structure::{
-    ChunkType, ChunkingOptions, ChunkingResult, StructuralChunker, StructuredChunk,
-    StructuredDocument, TableChunkingStrategy, chunk_structured, detect_structure,
+    ChunkType, ChunkingOptions, ChunkingResult, CodeSymbol, StructuralChunker, StructuredChunk,
+    StructuredDocument, TableChunkingStrategy, chunk_structured, code_language, detect_structure,
 };
 // Adaptive retrieval for dynamic result set sizing
 pub use types::adaptive::{
@@ -327,7 +493,6 @@ pub use replay::{
 
 #[cfg(test)]
 use once_cell::sync::Lazy;
-use std::fs::File;
 use std::io::Cursor;
 use std::path::Path;
 #[cfg(test)]
@@ -335,6 +500,7 @@ use std::sync::Mutex;
 
 use bincode::config::{self, Config};
 use io::header::HeaderCodec;
+use io::storage::StorageFile;
 
 const TIMELINE_PREVIEW_BYTES: usize = 120;
 const MAX_INDEX_BYTES: u64 = 512 * 1024 * 1024; // Increased from 64MB to 512MB for large datasets
@@ -417,7 +583,20 @@ impl Memvid {
         self.read_only
     }
 
+    /// Take the writer lock for a change to the file, refusing snapshot views and files under
+    /// a frozen legal hold. Every write path, `commit` included, goes through here.
     pub(crate) fn ensure_writable(&mut self) -> Result<()> {
+        self.acquire_writer()?;
+        self.ensure_not_frozen()
+    }
+
+    /// Take the writer lock without the legal hold check, for placing and lifting holds.
+    pub(crate) fn acquire_writer(&mut self) -> Result<()> {
+        if let Some(label) = &self.snapshot_view {
+            return Err(MemvidError::SnapshotReadOnly {
+                label: label.clone(),
+            });
+        }
         if self.read_only {
             self.lock.upgrade_to_exclusive()?;
             self.read_only = false;
@@ -440,8 +619,14 @@ impl Memvid {
 
 impl Drop for Memvid {
     fn drop(&mut self) {
-        if self.dirty {
-            let _ = self.commit();
+        // Best effort: `flush` or `commit` first if this commit's failure must be seen.
+        if self.dirty || self.commit_window.is_some() {
+            if let Err(err) = self.commit() {
+                tracing::warn!(
+                    ?err,
+                    "commit on drop failed; uncommitted puts remain in the WAL"
+                );
+            }
         }
         // Clean up temporary manifest.wal file (parallel_segments feature)
         #[cfg(feature = "parallel_segments")]
@@ -452,8 +637,10 @@ impl Drop for Memvid {
     }
 }
 
-pub(crate) fn persist_header(file: &mut File, header: &Header) -> Result<()> {
-    HeaderCodec::write(file, header)
+pub(crate) fn persist_header(file: &mut StorageFile, header: &Header) -> Result<()> {
+    let bytes = HeaderCodec::encode(header)?;
+    file.write_header(&bytes)?;
+    Ok(())
 }
 
 fn wal_config() -> impl Config {
@@ -475,6 +662,12 @@ pub(crate) fn decode_canonical_bytes(
                 reason: "failed to decode canonical payload",
             })
         }
+        CanonicalEncoding::Lz4 => {
+            lz4_flex::decompress_size_prepended(payload).map_err(|_| MemvidError::InvalidFrame {
+                frame_id,
+                reason: "failed to decode canonical payload",
+            })
+        }
     }
 }
 
@@ -650,6 +843,7 @@ mod tests {
                     reverse: false,
                     #[cfg(feature = "temporal_track")]
                     temporal: None,
+                    geo: None,
                 })
                 .expect("timeline limit");
             assert_eq!(limited.len(), 1);
@@ -663,6 +857,7 @@ mod tests {
                     reverse: true,
                     #[cfg(feature = "temporal_track")]
                     temporal: None,
+                    geo: None,
                 })
                 .expect("timeline reverse");
             assert_eq!(reversed.len(), 1);
@@ -670,6 +865,78 @@ mod tests {
         });
     }
 
+    #[test]
+    fn timeline_histogram_buckets_counts_and_bytes() {
+        run_serial_test(|| {
+            let dir = tempdir().expect("tmp");
+            let path = dir.path().join("histogram.mv2");
+
+            // Monday 2024-01-01, Tuesday 2024-01-02, Thursday 2024-02-15 (UTC noon)
+            let days = [1_704_110_400, 1_704_196_800, 1_707_998_400];
+            let mut mem = Memvid::create(&path).expect("create");
+            for (index, timestamp) in days.into_iter().enumerate() {
+                let payload = "x".repeat(10 * (index + 1));
+                let options = PutOptions::builder()
+                    .timestamp(timestamp)
+                    .auto_tag(false)
+                    .build();
+                mem.put_bytes_with_options(payload.as_bytes(), options)
+                    .expect("put");
+            }
+            mem.commit().expect("commit");
+
+            let all = TimelineQuery::builder().no_limit().build();
+            let daily = mem
+                .timeline_histogram(TimeBucket::Day, &all)
+                .expect("daily");
+            assert_eq!(daily.len(), 3);
+            assert_eq!(daily[0].start, 1_704_067_200);
+            assert_eq!(daily[0].end, 1_704_153_600);
+
+            let weekly = mem
+                .timeline_histogram(TimeBucket::Week, &all)
+                .expect("weekly");
+            assert_eq!(weekly.len(), 2);
+            assert_eq!(weekly[0].start, 1_704_067_200);
+            assert_eq!(weekly[0].frames, 2);
+
+            let monthly = mem
+                .timeline_histogram(TimeBucket::Month, &all)
+                .expect("monthly");
+            assert_eq!(
+                monthly
+                    .iter()
+                    .map(|bucket| (bucket.start, bucket.end, bucket.frames))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (1_704_067_200, 1_706_745_600, 2),
+                    (1_706_745_600, 1_709_251_200, 1)
+                ]
+            );
+            assert!(monthly[0].bytes >= 30);
+
+            let latest = mem
+                .timeline_histogram(
+                    TimeBucket::Month,
+                    &TimelineQuery::builder()
+                        .reverse(true)
+                        .limit(NonZeroU64::new(1).expect("non-zero"))
+                        .build(),
+                )
+                .expect("latest");
+            assert_eq!(latest.len(), 1);
+            assert_eq!(latest[0].frames, 1);
+
+            let since = mem
+                .timeline_histogram(
+                    TimeBucket::Day,
+                    &TimelineQuery::builder().since(1_704_150_000).build(),
+                )
+                .expect("since");
+            assert_eq!(since.len(), 2);
+        });
+    }
+
     #[test]
     fn lex_search_roundtrip() {
         run_serial_test(|| {
@@ -697,6 +964,18 @@ mod tests {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: crate::types::SearchMode::Lexical,
+                time_budget_ms: None,
             };
             let response = mem.search(request).expect("search");
             assert_eq!(response.hits.len(), 1);
@@ -718,6 +997,18 @@ mod tests {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: crate::types::SearchMode::Lexical,
+                time_budget_ms: None,
             };
             let response = reopened.search(request).expect("search reopened");
             assert_eq!(response.hits.len(), 1);
@@ -791,6 +1082,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("search");
 
@@ -854,6 +1157,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("search");
 
@@ -940,6 +1255,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("uri search");
             assert_eq!(uri_response.engine, SearchEngineKind::Tantivy);
@@ -965,6 +1292,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("scope search");
             assert_eq!(scope_response.engine, SearchEngineKind::Tantivy);
@@ -1020,6 +1359,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("page one");
             assert_eq!(first_page.engine, SearchEngineKind::Tantivy);
@@ -1045,6 +1396,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("page two");
             assert_eq!(second_page.engine, SearchEngineKind::Tantivy);
@@ -1088,6 +1451,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("search with tantivy");
 
@@ -1406,4 +1781,106 @@ mod tests {
             assert!(matches!(err, MemvidError::CapacityExceeded { .. }));
         });
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn capacity_report_warns_before_capacity_exceeded() {
+        run_serial_test(|| {
+            let dir = tempdir().expect("tmp");
+            let path = dir.path().join("capacity-report.mv2");
+
+            let mut mem = Memvid::create(&path).expect("create");
+            let report = mem.capacity_report().expect("report");
+            assert_eq!(report.source, CapacitySource::Tier);
+            assert_eq!(report.capacity_bytes, Tier::Free.capacity_bytes());
+            assert!(!report.warning);
+
+            let base = mem.data_end;
+            mem.apply_ticket(Ticket::new("issuer", 2).capacity_bytes(base + 100))
+                .expect("apply ticket");
+            mem.set_config(FileConfig {
+                capacity_warning_percent: Some(90),
+                ..FileConfig::default()
+            })
+            .expect("set config");
+            mem.put_bytes(&[0xAB; 32]).expect("put");
+            mem.commit().expect("commit");
+
+            let report = mem.capacity_report().expect("report");
+            assert_eq!(report.source, CapacitySource::Ticket);
+            assert_eq!(report.capacity_bytes, base + 100);
+            assert_eq!(
+                report.remaining_bytes,
+                report.capacity_bytes - report.used_bytes
+            );
+            assert!(report.breakdown.payload_bytes > 0);
+            assert_eq!(report.warning_percent, 90);
+            assert!(report.warning, "{report:?}");
+            let stats = mem.stats().expect("stats");
+            assert!(stats.capacity_warning);
+            assert_eq!(stats.capacity_warning_percent, 90);
+        });
+    }
+
+    #[test]
+    fn storage_breakdown_accounts_payloads_by_kind_and_mime() {
+        run_serial_test(|| {
+            let dir = tempdir().expect("tmp");
+            let path = dir.path().join("storage-breakdown.mv2");
+
+            let mut mem = Memvid::create(&path).expect("create");
+            let with_mime = |kind: &str, mime: &str| {
+                PutOptions::builder()
+                    .kind(kind)
+                    .metadata(DocMetadata {
+                        mime: Some(mime.to_string()),
+                        ..DocMetadata::default()
+                    })
+                    .build()
+            };
+            mem.put_bytes_with_options(&[0x11; 64], with_mime("image", "image/png"))
+                .expect("put image");
+            mem.put_bytes_with_options(&[0x22; 48], with_mime("image", "image/jpeg"))
+                .expect("put image");
+            mem.put_bytes_with_options(b"plain notes", with_mime("note", "text/plain"))
+                .expect("put note");
+            mem.commit().expect("commit");
+            let jpeg = mem.toc.frames[1].id;
+            mem.delete_frame(jpeg).expect("delete");
+            mem.commit().expect("commit");
+
+            let storage = mem.storage_breakdown().expect("storage");
+            let images = storage.payload_by_kind["image"];
+            assert_eq!(images.frame_count, 2);
+            assert_eq!(images.logical_bytes, 64 + 48);
+            assert_eq!(storage.payload_by_kind["note"].frame_count, 1);
+            assert_eq!(storage.payload_by_mime["image/png"].frame_count, 1);
+            assert_eq!(storage.payload_by_mime["text/plain"].logical_bytes, 11);
+            assert_eq!(
+                storage.payload_bytes,
+                storage
+                    .payload_by_kind
+                    .values()
+                    .map(|usage| usage.stored_bytes)
+                    .sum::<u64>()
+            );
+            assert_eq!(
+                storage.inactive_payload_bytes,
+                storage.payload_by_mime["image/jpeg"].stored_bytes
+            );
+            assert_eq!(
+                storage.wal_bytes,
+                storage.wal_pending_bytes + storage.wal_slack_bytes
+            );
+            assert_eq!(
+                storage.payload_bytes
+                    + storage.index_bytes()
+                    + storage.extension_bytes
+                    + storage.wal_bytes
+                    + storage.unaccounted_bytes,
+                storage.file_bytes
+            );
+            assert_eq!(mem.stats().expect("stats").storage, storage);
+        });
+    }
 }
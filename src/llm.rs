@@ -0,0 +1,497 @@
+//! [`LlmBackend`](crate::types::LlmBackend) implementations for generative `ask`.
+//!
+//! - [`OpenAICompatibleLlm`] (`llm_openai` feature) talks to any server exposing the OpenAI
+//!   `/chat/completions` endpoint: OpenAI itself, Azure, vLLM, llama.cpp server, Ollama.
+//! - [`GgufLlm`] (`llm_local` feature) runs a quantized Llama-family GGUF model in-process with
+//!   Candle, so answers can be generated without network access.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use memvid_core::llm::{OpenAICompatibleConfig, OpenAICompatibleLlm};
+//! use memvid_core::{AskMode, LlmParams};
+//!
+//! let llm = OpenAICompatibleLlm::new(OpenAICompatibleConfig::default())?;
+//! request.mode = AskMode::Generative;
+//! let response = mem.ask_with_llm(request, None::<&dyn VecEmbedder>, &llm, &LlmParams::default())?;
+//! println!("{}", response.answer.unwrap_or_default());
+//! ```
+
+#[cfg(feature = "llm_local")]
+pub use local::{GgufLlm, GgufLlmConfig};
+#[cfg(feature = "llm_openai")]
+pub use openai::{OpenAICompatibleConfig, OpenAICompatibleLlm};
+
+#[cfg(feature = "llm_openai")]
+mod openai {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+    use reqwest::blocking::Client;
+    use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+    use serde::{Deserialize, Serialize};
+
+    use crate::error::{MemvidError, Result};
+    use crate::types::{LlmBackend, LlmCompletion, LlmParams};
+
+    /// Configuration for an OpenAI-compatible chat completion endpoint.
+    #[derive(Debug, Clone)]
+    pub struct OpenAICompatibleConfig {
+        /// Model name sent with every request (e.g., "gpt-4o-mini")
+        pub model: String,
+        /// Base URL without the trailing `/chat/completions`
+        /// Default: "https://api.openai.com/v1"
+        pub base_url: String,
+        /// Environment variable holding the API key; local servers usually need none
+        pub api_key_env: Option<String>,
+        /// Context window of the model in tokens
+        pub context_window: usize,
+        /// Request timeout in seconds
+        pub timeout_secs: u64,
+        /// Maximum retries on rate limit (429) and server errors
+        pub max_retries: u32,
+        /// Initial backoff in milliseconds for exponential retry
+        pub initial_backoff_ms: u64,
+    }
+
+    impl Default for OpenAICompatibleConfig {
+        fn default() -> Self {
+            Self {
+                model: "gpt-4o-mini".to_string(),
+                base_url: "https://api.openai.com/v1".to_string(),
+                api_key_env: Some("OPENAI_API_KEY".to_string()),
+                context_window: 128_000,
+                timeout_secs: 120,
+                max_retries: 3,
+                initial_backoff_ms: 1000,
+            }
+        }
+    }
+
+    impl OpenAICompatibleConfig {
+        /// Config for an unauthenticated local server such as llama.cpp or Ollama.
+        #[must_use]
+        pub fn local(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+            Self {
+                model: model.into(),
+                base_url: base_url.into(),
+                api_key_env: None,
+                context_window: 8192,
+                ..Default::default()
+            }
+        }
+
+        #[must_use]
+        pub fn with_context_window(mut self, tokens: usize) -> Self {
+            self.context_window = tokens;
+            self
+        }
+
+        #[must_use]
+        pub fn with_timeout(mut self, secs: u64) -> Self {
+            self.timeout_secs = secs;
+            self
+        }
+    }
+
+    #[derive(Serialize)]
+    struct ChatRequest<'a> {
+        model: &'a str,
+        messages: [ChatMessage<'a>; 1],
+        max_tokens: usize,
+        temperature: f32,
+        #[serde(skip_serializing_if = "<[String]>::is_empty")]
+        stop: &'a [String],
+    }
+
+    #[derive(Serialize)]
+    struct ChatMessage<'a> {
+        role: &'a str,
+        content: &'a str,
+    }
+
+    #[derive(Deserialize)]
+    struct ChatResponse {
+        choices: Vec<ChatChoice>,
+        #[serde(default)]
+        usage: Option<ChatUsage>,
+    }
+
+    #[derive(Deserialize)]
+    struct ChatChoice {
+        message: ChatChoiceMessage,
+    }
+
+    #[derive(Deserialize)]
+    struct ChatChoiceMessage {
+        #[serde(default)]
+        content: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct ChatUsage {
+        prompt_tokens: usize,
+        completion_tokens: usize,
+    }
+
+    /// Chat completion client for OpenAI-compatible servers.
+    pub struct OpenAICompatibleLlm {
+        config: OpenAICompatibleConfig,
+        client: Client,
+        api_key: Option<String>,
+    }
+
+    impl OpenAICompatibleLlm {
+        /// Create a client, reading the API key from `config.api_key_env` when set.
+        pub fn new(config: OpenAICompatibleConfig) -> Result<Self> {
+            let api_key = match &config.api_key_env {
+                Some(env) => {
+                    let key = std::env::var(env).map_err(|_| MemvidError::LlmFailed {
+                        reason: format!("API key not found. Set the {env} environment variable.")
+                            .into(),
+                    })?;
+                    if key.is_empty() {
+                        return Err(MemvidError::LlmFailed {
+                            reason: format!("{env} environment variable is empty").into(),
+                        });
+                    }
+                    Some(key)
+                }
+                None => None,
+            };
+            let client = Client::builder()
+                .timeout(Duration::from_secs(config.timeout_secs))
+                .build()
+                .map_err(|err| MemvidError::LlmFailed {
+                    reason: format!("Failed to create HTTP client: {err}").into(),
+                })?;
+            Ok(Self {
+                config,
+                client,
+                api_key,
+            })
+        }
+
+        fn request_headers(&self) -> Result<HeaderMap> {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            if let Some(key) = &self.api_key {
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("Bearer {key}")).map_err(|_| {
+                        MemvidError::LlmFailed {
+                            reason: "Invalid API key format".into(),
+                        }
+                    })?,
+                );
+            }
+            Ok(headers)
+        }
+    }
+
+    impl LlmBackend for OpenAICompatibleLlm {
+        fn kind(&self) -> &str {
+            &self.config.model
+        }
+
+        fn context_window(&self) -> usize {
+            self.config.context_window
+        }
+
+        fn complete(&self, prompt: &str, params: &LlmParams) -> Result<LlmCompletion> {
+            let url = format!(
+                "{}/chat/completions",
+                self.config.base_url.trim_end_matches('/')
+            );
+            let body = ChatRequest {
+                model: &self.config.model,
+                messages: [ChatMessage {
+                    role: "user",
+                    content: prompt,
+                }],
+                max_tokens: params.max_tokens,
+                temperature: params.temperature,
+                stop: &params.stop,
+            };
+            let headers = self.request_headers()?;
+
+            let mut backoff_ms = self.config.initial_backoff_ms;
+            let mut last_error = String::new();
+            for attempt in 0..=self.config.max_retries {
+                if attempt > 0 {
+                    std::thread::sleep(Duration::from_millis(backoff_ms));
+                    backoff_ms = backoff_ms.saturating_mul(2);
+                }
+                let response = match self
+                    .client
+                    .post(&url)
+                    .headers(headers.clone())
+                    .json(&body)
+                    .send()
+                {
+                    Ok(response) => response,
+                    Err(err) => {
+                        last_error = format!("request failed: {err}");
+                        continue;
+                    }
+                };
+                let status = response.status();
+                if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                    last_error = format!("server returned {status}");
+                    continue;
+                }
+                if !status.is_success() {
+                    let detail = response.text().unwrap_or_default();
+                    return Err(MemvidError::LlmFailed {
+                        reason: format!("server returned {status}: {detail}").into(),
+                    });
+                }
+                let parsed: ChatResponse =
+                    response.json().map_err(|err| MemvidError::LlmFailed {
+                        reason: format!("invalid completion response: {err}").into(),
+                    })?;
+                let text = parsed
+                    .choices
+                    .into_iter()
+                    .next()
+                    .and_then(|choice| choice.message.content)
+                    .unwrap_or_default();
+                let (prompt_tokens, completion_tokens) = match parsed.usage {
+                    Some(usage) => (usage.prompt_tokens, usage.completion_tokens),
+                    None => (self.count_tokens(prompt), self.count_tokens(&text)),
+                };
+                return Ok(LlmCompletion {
+                    text,
+                    prompt_tokens,
+                    completion_tokens,
+                });
+            }
+            Err(MemvidError::LlmFailed {
+                reason: format!(
+                    "giving up after {} attempts: {last_error}",
+                    self.config.max_retries + 1
+                )
+                .into(),
+            })
+        }
+    }
+}
+
+#[cfg(feature = "llm_local")]
+mod local {
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    use candle_core::quantized::gguf_file;
+    use candle_core::{Device, Tensor};
+    use candle_transformers::generation::LogitsProcessor;
+    use candle_transformers::models::quantized_llama::ModelWeights;
+    use tokenizers::Tokenizer;
+
+    use crate::error::{MemvidError, Result};
+    use crate::inference_device::InferenceDevice;
+    use crate::types::{LlmBackend, LlmCompletion, LlmParams};
+
+    /// Configuration for a local GGUF model.
+    #[derive(Debug, Clone)]
+    pub struct GgufLlmConfig {
+        /// Path to the quantized `.gguf` weights (Llama, Mistral, Qwen2, ...)
+        pub model_path: PathBuf,
+        /// Path to the matching `tokenizer.json`
+        pub tokenizer_path: PathBuf,
+        /// Context window of the model in tokens
+        pub context_window: usize,
+        /// Tokens that end generation, e.g. `</s>`, `<|eot_id|>`, `<|im_end|>`
+        pub eos_tokens: Vec<String>,
+        /// Device to run on; falls back to CPU when unavailable
+        pub device: InferenceDevice,
+        /// Sampling seed, so non-greedy runs are reproducible
+        pub seed: u64,
+    }
+
+    impl GgufLlmConfig {
+        #[must_use]
+        pub fn new(model_path: impl Into<PathBuf>, tokenizer_path: impl Into<PathBuf>) -> Self {
+            Self {
+                model_path: model_path.into(),
+                tokenizer_path: tokenizer_path.into(),
+                context_window: 4096,
+                eos_tokens: vec![
+                    "</s>".to_string(),
+                    "<|eot_id|>".to_string(),
+                    "<|im_end|>".to_string(),
+                    "<|endoftext|>".to_string(),
+                ],
+                device: InferenceDevice::Auto,
+                seed: 299_792_458,
+            }
+        }
+
+        #[must_use]
+        pub fn with_context_window(mut self, tokens: usize) -> Self {
+            self.context_window = tokens;
+            self
+        }
+
+        #[must_use]
+        pub fn with_device(mut self, device: InferenceDevice) -> Self {
+            self.device = device;
+            self
+        }
+    }
+
+    /// In-process quantized Llama-family model.
+    pub struct GgufLlm {
+        config: GgufLlmConfig,
+        name: String,
+        model: Mutex<ModelWeights>,
+        tokenizer: Tokenizer,
+        eos_ids: Vec<u32>,
+        device: Device,
+    }
+
+    impl GgufLlm {
+        /// Load the GGUF weights and tokenizer.
+        pub fn load(config: GgufLlmConfig) -> Result<Self> {
+            let device = select_device(config.device);
+            let mut file = File::open(&config.model_path).map_err(|err| llm_error("open", err))?;
+            let content =
+                gguf_file::Content::read(&mut file).map_err(|err| llm_error("read gguf", err))?;
+            let model = ModelWeights::from_gguf(content, &mut file, &device)
+                .map_err(|err| llm_error("load weights", err))?;
+            let tokenizer = Tokenizer::from_file(&config.tokenizer_path)
+                .map_err(|err| llm_error("load tokenizer", err))?;
+            let eos_ids = config
+                .eos_tokens
+                .iter()
+                .filter_map(|token| tokenizer.token_to_id(token))
+                .collect();
+            let name = config
+                .model_path
+                .file_stem()
+                .map_or_else(|| "gguf".to_string(), |stem| stem.to_string_lossy().into());
+            tracing::info!(model = %name, device = ?device, "GGUF model loaded");
+            Ok(Self {
+                config,
+                name,
+                model: Mutex::new(model),
+                tokenizer,
+                eos_ids,
+                device,
+            })
+        }
+
+        fn encode(&self, text: &str) -> Result<Vec<u32>> {
+            self.tokenizer
+                .encode(text, true)
+                .map(|encoding| encoding.get_ids().to_vec())
+                .map_err(|err| llm_error("tokenize", err))
+        }
+    }
+
+    impl LlmBackend for GgufLlm {
+        fn kind(&self) -> &str {
+            &self.name
+        }
+
+        fn context_window(&self) -> usize {
+            self.config.context_window
+        }
+
+        fn count_tokens(&self, text: &str) -> usize {
+            self.encode(text)
+                .map_or_else(|_| text.len().div_ceil(4), |ids| ids.len())
+        }
+
+        fn complete(&self, prompt: &str, params: &LlmParams) -> Result<LlmCompletion> {
+            let prompt_ids = self.encode(prompt)?;
+            let max_new = params
+                .max_tokens
+                .min(self.config.context_window.saturating_sub(prompt_ids.len()));
+            let temperature = (params.temperature > 0.0).then_some(f64::from(params.temperature));
+            let mut sampler = LogitsProcessor::new(self.config.seed, temperature, None);
+            let mut model = self.model.lock().map_err(|_| MemvidError::LlmFailed {
+                reason: "model lock poisoned".into(),
+            })?;
+
+            let mut generated: Vec<u32> = Vec::new();
+            let mut input = prompt_ids.clone();
+            let mut position = 0;
+            while generated.len() < max_new {
+                let tensor = Tensor::new(input.as_slice(), &self.device)
+                    .and_then(|tensor| tensor.unsqueeze(0))
+                    .map_err(|err| llm_error("build input", err))?;
+                // Position 0 resets the KV cache, so every call starts fresh.
+                let logits = model
+                    .forward(&tensor, position)
+                    .and_then(|logits| logits.squeeze(0))
+                    .map_err(|err| llm_error("forward", err))?;
+                position += input.len();
+                let next = sampler
+                    .sample(&logits)
+                    .map_err(|err| llm_error("sample", err))?;
+                if self.eos_ids.contains(&next) {
+                    break;
+                }
+                generated.push(next);
+                input = vec![next];
+
+                if !params.stop.is_empty() {
+                    let text = self
+                        .tokenizer
+                        .decode(&generated, true)
+                        .map_err(|err| llm_error("decode", err))?;
+                    if params.stop.iter().any(|stop| text.contains(stop.as_str())) {
+                        break;
+                    }
+                }
+            }
+
+            let mut text = self
+                .tokenizer
+                .decode(&generated, true)
+                .map_err(|err| llm_error("decode", err))?;
+            if let Some(cut) = params
+                .stop
+                .iter()
+                .filter_map(|stop| text.find(stop.as_str()))
+                .min()
+            {
+                text.truncate(cut);
+            }
+            Ok(LlmCompletion {
+                text,
+                prompt_tokens: prompt_ids.len(),
+                completion_tokens: generated.len(),
+            })
+        }
+    }
+
+    fn select_device(preferred: InferenceDevice) -> Device {
+        for candidate in preferred.candidates() {
+            match candidate {
+                #[cfg(feature = "cuda")]
+                InferenceDevice::Cuda { device_id } => {
+                    if let Ok(device) = Device::new_cuda(usize::try_from(device_id).unwrap_or(0)) {
+                        return device;
+                    }
+                }
+                #[cfg(feature = "metal")]
+                InferenceDevice::Metal => {
+                    if let Ok(device) = Device::new_metal(0) {
+                        return device;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Device::Cpu
+    }
+
+    fn llm_error(stage: &str, err: impl std::fmt::Display) -> MemvidError {
+        MemvidError::LlmFailed {
+            reason: format!("{stage}: {err}").into(),
+        }
+    }
+}
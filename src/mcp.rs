@@ -0,0 +1,523 @@
+//! Model Context Protocol (MCP) tool adapter.
+//!
+//! Exposes a single memory as a set of MCP tools (`memvid_search`, `memvid_ask`, `memvid_put`,
+//! `memvid_timeline`, `memvid_memory_cards`) and drives them over the stdio transport:
+//! newline-delimited JSON-RPC 2.0 on stdin/stdout. Tool arguments deserialize straight into the
+//! core request types (`SearchRequest`, `AskRequest`, `PutOptions`, `TimelineQuery`), and each
+//! tool's input schema is derived from the same type with `schemars`. Fields the application
+//! embedding the server controls, such as the caller's ACL context, are marked
+//! `schemars(skip)` on the type and stay out of the schemas. Requires the `mcp` feature.
+
+use std::io::{BufRead, Write};
+
+use schemars::JsonSchema;
+use schemars::generate::SchemaSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::Memvid;
+use crate::types::{AskRequest, PutOptions, SearchRequest, TimelineQuery, VecEmbedder};
+
+/// MCP protocol revision implemented by [`McpServer`].
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+const DEFAULT_TOP_K: u64 = 8;
+const DEFAULT_SNIPPET_CHARS: u64 = 240;
+
+/// Tool metadata returned from `tools/list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+    #[serde(rename = "inputSchema")]
+    pub input_schema: Value,
+}
+
+/// Arguments of `memvid_put`: the text to store and how to store it.
+#[derive(Deserialize, JsonSchema)]
+struct PutArgs {
+    /// Text to store.
+    text: String,
+    #[serde(flatten)]
+    options: PutOptions,
+}
+
+/// Arguments of `memvid_memory_cards`.
+#[derive(Deserialize, JsonSchema)]
+struct MemoryCardsArgs {
+    /// Entity whose cards to return.
+    entity: String,
+    /// Return only the current value for this slot.
+    #[serde(default)]
+    slot: Option<String>,
+}
+
+/// Input schema of a tool whose arguments deserialize into `T`, with nested types inlined.
+///
+/// `defaults` are filled in by `call_tool` when the arguments omit them, so those properties
+/// are optional whatever `T` requires.
+fn input_schema<T: JsonSchema>(defaults: &[(&str, Value)]) -> Value {
+    let mut schema = SchemaSettings::draft07()
+        .with(|settings| settings.inline_subschemas = true)
+        .for_deserialize()
+        .into_generator()
+        .into_root_schema_for::<T>()
+        .to_value();
+    let Some(object) = schema.as_object_mut() else {
+        return schema;
+    };
+    object.remove("$schema");
+    object.remove("title");
+    for (name, default) in defaults {
+        if let Some(property) = object
+            .get_mut("properties")
+            .and_then(|properties| properties.get_mut(*name))
+            .and_then(Value::as_object_mut)
+        {
+            property.insert("default".into(), default.clone());
+        }
+        if let Some(Value::Array(required)) = object.get_mut("required") {
+            required.retain(|required| required != name);
+        }
+    }
+    schema
+}
+
+/// Definitions for every tool served by [`McpServer`].
+#[must_use]
+pub fn tool_definitions() -> Vec<ToolDefinition> {
+    let search_defaults = [
+        ("top_k", json!(DEFAULT_TOP_K)),
+        ("snippet_chars", json!(DEFAULT_SNIPPET_CHARS)),
+    ];
+    vec![
+        ToolDefinition {
+            name: "memvid_search",
+            description: "Full-text search over the memory. Returns ranked snippets with frame ids and URIs.",
+            input_schema: input_schema::<SearchRequest>(&search_defaults),
+        },
+        ToolDefinition {
+            name: "memvid_ask",
+            description: "Retrieve context for a question and synthesize an answer with citations.",
+            input_schema: input_schema::<AskRequest>(&search_defaults),
+        },
+        ToolDefinition {
+            name: "memvid_put",
+            description: "Store a piece of text in the memory and commit it. Returns the new frame's id.",
+            input_schema: input_schema::<PutArgs>(&[]),
+        },
+        ToolDefinition {
+            name: "memvid_timeline",
+            description: "List frames chronologically with short previews.",
+            input_schema: input_schema::<TimelineQuery>(&[]),
+        },
+        ToolDefinition {
+            name: "memvid_memory_cards",
+            description: "Look up structured memory cards (facts, preferences, events) for an entity.",
+            input_schema: input_schema::<MemoryCardsArgs>(&[]),
+        },
+    ]
+}
+
+/// Serves MCP tool calls against one open memory.
+pub struct McpServer {
+    memvid: Memvid,
+    read_only: bool,
+}
+
+impl McpServer {
+    #[must_use]
+    pub fn new(memvid: Memvid) -> Self {
+        Self {
+            memvid,
+            read_only: false,
+        }
+    }
+
+    /// Hide `memvid_put` and reject writes.
+    #[must_use]
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Memvid {
+        self.memvid
+    }
+
+    #[must_use]
+    pub fn tools(&self) -> Vec<ToolDefinition> {
+        tool_definitions()
+            .into_iter()
+            .filter(|tool| !(self.read_only && tool.name == "memvid_put"))
+            .collect()
+    }
+
+    /// Execute a tool and return its JSON result.
+    pub fn call_tool(&mut self, name: &str, arguments: Value) -> Result<Value> {
+        let args = match arguments {
+            Value::Object(map) => map,
+            Value::Null => Map::new(),
+            _ => {
+                return Err(MemvidError::InvalidQuery {
+                    reason: "tool arguments must be an object".into(),
+                });
+            }
+        };
+        match name {
+            "memvid_search" => {
+                let request: SearchRequest = from_args(with_search_defaults(args))?;
+                to_json(&self.memvid.search(request)?)
+            }
+            "memvid_ask" => {
+                let request: AskRequest = from_args(with_search_defaults(args))?;
+                to_json(&self.memvid.ask(request, None::<&dyn VecEmbedder>)?)
+            }
+            "memvid_put" => {
+                if self.read_only {
+                    return Err(MemvidError::InvalidQuery {
+                        reason: "memory is read-only".into(),
+                    });
+                }
+                let PutArgs { text, options } = from_args(args)?;
+                let frame_id = self.memvid.next_frame_id();
+                let sequence = self
+                    .memvid
+                    .put_bytes_with_options(text.as_bytes(), options)?;
+                self.memvid.commit()?;
+                Ok(json!({ "frame_id": frame_id, "sequence": sequence }))
+            }
+            "memvid_timeline" => {
+                let query: TimelineQuery = from_args(args)?;
+                to_json(&self.memvid.timeline(query)?)
+            }
+            "memvid_memory_cards" => {
+                let MemoryCardsArgs { entity, slot } = from_args(args)?;
+                match slot {
+                    Some(slot) => to_json(&self.memvid.get_current_memory(&entity, &slot)),
+                    None => to_json(&self.memvid.get_entity_memories(&entity)),
+                }
+            }
+            other => Err(MemvidError::InvalidQuery {
+                reason: format!("unknown tool: {other}"),
+            }),
+        }
+    }
+
+    /// Handle one JSON-RPC message. Returns `None` for notifications.
+    pub fn handle_message(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        // Notifications carry no id and expect no response.
+        let id = id?;
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": { "listChanged": false } },
+                "serverInfo": { "name": "memvid", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": self.tools() })),
+            "tools/call" => {
+                let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+                let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+                // Tool failures are reported in-band so the model can see and react to them.
+                let (text, is_error) = match self.call_tool(name, arguments) {
+                    Ok(value) => (value.to_string(), false),
+                    Err(err) => (err.to_string(), true),
+                };
+                Ok(json!({
+                    "content": [{ "type": "text", "text": text }],
+                    "isError": is_error,
+                }))
+            }
+            other => Err((-32601, format!("method not found: {other}"))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        })
+    }
+
+    /// Run the stdio transport loop until `reader` reaches EOF.
+    pub fn run<R: BufRead, W: Write>(&mut self, reader: R, mut writer: W) -> Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle_message(&message),
+                Err(err) => Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": Value::Null,
+                    "error": { "code": -32700, "message": format!("parse error: {err}") },
+                })),
+            };
+            if let Some(response) = response {
+                writeln!(writer, "{response}")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve over the process's stdin/stdout.
+    pub fn serve_stdio(&mut self) -> Result<()> {
+        let stdin = std::io::stdin();
+        let stdout = std::io::stdout();
+        self.run(stdin.lock(), stdout.lock())
+    }
+}
+
+fn with_search_defaults(mut args: Map<String, Value>) -> Map<String, Value> {
+    args.entry("top_k").or_insert(json!(DEFAULT_TOP_K));
+    args.entry("snippet_chars")
+        .or_insert(json!(DEFAULT_SNIPPET_CHARS));
+    args
+}
+
+fn from_args<T: serde::de::DeserializeOwned>(args: Map<String, Value>) -> Result<T> {
+    serde_json::from_value(Value::Object(args)).map_err(|err| MemvidError::InvalidQuery {
+        reason: format!("invalid tool arguments: {err}"),
+    })
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|err| MemvidError::InvalidQuery {
+        reason: format!("failed to encode tool result: {err}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdio_loop_lists_and_calls_tools() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("mcp.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        let mut server = McpServer::new(mem);
+
+        let input = [
+            json!({"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}),
+            json!({"jsonrpc":"2.0","method":"notifications/initialized"}),
+            json!({"jsonrpc":"2.0","id":2,"method":"tools/list"}),
+            json!({"jsonrpc":"2.0","id":3,"method":"tools/call","params":{
+                "name":"memvid_put","arguments":{"text":"mcp agents remember things","tags":["agent"]}}}),
+            json!({"jsonrpc":"2.0","id":4,"method":"tools/call","params":{
+                "name":"memvid_search","arguments":{"query":"remember"}}}),
+            json!({"jsonrpc":"2.0","id":5,"method":"tools/call","params":{
+                "name":"no_such_tool","arguments":{}}}),
+        ]
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let mut output = Vec::new();
+        server.run(input.as_bytes(), &mut output).expect("run");
+        let responses: Vec<Value> = String::from_utf8(output)
+            .expect("utf8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+
+        assert_eq!(responses.len(), 5, "notification must not be answered");
+        assert_eq!(responses[0]["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert_eq!(
+            responses[1]["result"]["tools"].as_array().map(Vec::len),
+            Some(5)
+        );
+        assert_eq!(responses[2]["result"]["isError"], json!(false));
+        let search_text = responses[3]["result"]["content"][0]["text"]
+            .as_str()
+            .expect("text");
+        let search: Value = serde_json::from_str(search_text).expect("search json");
+        assert_eq!(search["hits"].as_array().map(Vec::len), Some(1));
+        assert_eq!(responses[4]["result"]["isError"], json!(true));
+    }
+
+    /// A value of the kind `property` declares: its constant or first enum value, its first
+    /// non-null alternative, else a placeholder of its type with every object property set.
+    fn sample(property: &Value) -> Value {
+        if let Some(value) = property.get("const") {
+            return value.clone();
+        }
+        if let Some(value) = property["enum"].get(0) {
+            return value.clone();
+        }
+        for alternatives in ["oneOf", "anyOf"] {
+            if let Some(alternatives) = property[alternatives].as_array() {
+                let alternative = alternatives
+                    .iter()
+                    .find(|alternative| alternative["type"] != "null")
+                    .expect("non-null alternative");
+                return sample(alternative);
+            }
+        }
+        let ty = match &property["type"] {
+            Value::Array(types) => types
+                .iter()
+                .find(|ty| *ty != "null")
+                .and_then(Value::as_str),
+            ty => ty.as_str(),
+        };
+        match ty {
+            Some("integer") => property.get("minimum").cloned().unwrap_or(json!(1)),
+            Some("number") => json!(0.5),
+            Some("boolean") => json!(true),
+            Some("string") => json!("sample"),
+            Some("array") => json!([sample(&property["items"])]),
+            Some("object") => match property["properties"].as_object() {
+                Some(properties) => properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), sample(property)))
+                    .collect(),
+                None => json!({ "sample": sample(&property["additionalProperties"]) }),
+            },
+            other => panic!("no sample for a property of type {other:?}"),
+        }
+    }
+
+    /// Every property `tool` declares, except those in `skip` that `call_tool` reads itself,
+    /// must reach `T` unchanged; every field `T` serializes must be declared or `hidden`.
+    fn assert_schema_matches<T>(tool: &str, skip: &[&str], hidden: &[&str])
+    where
+        T: serde::de::DeserializeOwned + Serialize,
+    {
+        let definition = tool_definitions()
+            .into_iter()
+            .find(|definition| definition.name == tool)
+            .expect("tool");
+        let properties = definition.input_schema["properties"]
+            .as_object()
+            .expect("properties");
+        for required in definition.input_schema["required"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            assert!(properties.contains_key(required.as_str().expect("name")));
+        }
+        let args: Map<String, Value> = properties
+            .iter()
+            .filter(|(name, _)| !skip.contains(&name.as_str()))
+            .map(|(name, property)| (name.clone(), sample(property)))
+            .collect();
+        let parsed: T = from_args(args.clone()).unwrap_or_else(|err| panic!("{tool}: {err}"));
+        let fields = to_json(&parsed).expect("encode");
+        let fields = fields.as_object().expect("object");
+        for (name, value) in &args {
+            assert_eq!(
+                fields.get(name),
+                Some(value),
+                "{tool}: `{name}` did not round-trip"
+            );
+        }
+        for name in fields.keys() {
+            assert!(
+                properties.contains_key(name) || hidden.contains(&name.as_str()),
+                "{tool}: `{name}` is missing from the schema"
+            );
+        }
+    }
+
+    #[test]
+    fn schemas_match_request_types() {
+        // Caller identity and storage tuning stay with the application embedding the server.
+        assert_schema_matches::<SearchRequest>(
+            "memvid_search",
+            &[],
+            &["acl_context", "acl_enforcement_mode", "temporal"],
+        );
+        assert_schema_matches::<AskRequest>(
+            "memvid_ask",
+            &[],
+            &[
+                "acl_context",
+                "acl_enforcement_mode",
+                "temporal",
+                "adaptive",
+            ],
+        );
+        assert_schema_matches::<PutOptions>(
+            "memvid_put",
+            &["text"],
+            &[
+                "metadata",
+                "enable_embedding",
+                "parent_id",
+                "role",
+                "no_raw",
+                "source_path",
+                "instant_index",
+                "extraction_budget_ms",
+                "compression",
+            ],
+        );
+        assert_schema_matches::<TimelineQuery>("memvid_timeline", &[], &["temporal"]);
+
+        // Arguments `call_tool` defaults are optional even though the type requires them.
+        let search = &tool_definitions()[0].input_schema;
+        assert_eq!(search["required"], json!(["query"]));
+        assert_eq!(
+            search["properties"]["top_k"]["default"],
+            json!(DEFAULT_TOP_K)
+        );
+    }
+
+    #[test]
+    fn timeline_and_memory_cards_read_their_arguments() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mem = Memvid::create(dir.path().join("tools.mv2")).expect("create");
+        let mut server = McpServer::new(mem);
+        for (expected, text) in ["first note", "second note"].into_iter().enumerate() {
+            let put = server
+                .call_tool("memvid_put", json!({ "text": text }))
+                .expect("put");
+            assert_eq!(put["frame_id"], json!(expected));
+        }
+        assert!(
+            server
+                .memvid
+                .frame_text_by_id(1)
+                .expect("text")
+                .starts_with("second note")
+        );
+
+        let timeline = server
+            .call_tool("memvid_timeline", json!({ "limit": 1, "reverse": true }))
+            .expect("timeline");
+        assert_eq!(timeline.as_array().map(Vec::len), Some(1));
+        assert!(
+            server
+                .call_tool("memvid_memory_cards", json!({ "entity": "nobody" }))
+                .is_ok()
+        );
+        assert!(server.call_tool("memvid_memory_cards", json!({})).is_err());
+        assert!(server.call_tool("memvid_put", json!({})).is_err());
+    }
+
+    #[test]
+    fn read_only_hides_put() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mem = Memvid::create(dir.path().join("ro.mv2")).expect("create");
+        let mut server = McpServer::new(mem).read_only(true);
+        assert!(server.tools().iter().all(|tool| tool.name != "memvid_put"));
+        assert!(
+            server
+                .call_tool("memvid_put", json!({"text": "x"}))
+                .is_err()
+        );
+    }
+}
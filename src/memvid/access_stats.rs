@@ -0,0 +1,183 @@
+//! Retrieval counters recorded by search and ask, and the optional access boost.
+//!
+//! Queries only touch the in-memory `pending_access` table; the next commit folds it into the
+//! persisted [`AccessStats`] (see [`crate::types::access_stats`]).
+
+use crate::error::Result;
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    ACCESS_STATS_EXTENSION, AccessStats, AskCitation, FrameAccess, HotFrame, SearchHit,
+};
+
+/// Largest relative score increase the access boost applies, reached by the hottest frame.
+const ACCESS_BOOST: f32 = 0.25;
+
+impl Memvid {
+    /// Retrieval counters for every frame, including those not yet committed.
+    pub fn access_stats(&self) -> Result<AccessStats> {
+        let mut stats = self
+            .toc
+            .extension::<AccessStats>(ACCESS_STATS_EXTENSION)?
+            .unwrap_or_default();
+        stats.merge(&self.pending_access);
+        Ok(stats)
+    }
+
+    /// The `limit` most returned or cited frames; citations weigh double.
+    pub fn hot_frames(&self, limit: usize) -> Result<Vec<HotFrame>> {
+        Ok(self.access_stats()?.hot_frames(limit))
+    }
+
+    /// Frozen files accept no writes, so their reads leave no access counters to commit.
+    fn records_access(&self) -> bool {
+        self.ensure_not_frozen().is_ok()
+    }
+
+    pub(crate) fn record_search_access(&mut self, hits: &[SearchHit]) {
+        if !self.records_access() {
+            return;
+        }
+        self.pending_access
+            .record_returned(hits.iter().map(|hit| hit.frame_id), unix_now());
+    }
+
+    pub(crate) fn record_citation_access(&mut self, citations: &[AskCitation]) {
+        if !self.records_access() {
+            return;
+        }
+        self.pending_access.record_cited(
+            citations.iter().map(|citation| citation.frame_id),
+            unix_now(),
+        );
+    }
+
+    /// Fold the counters recorded since the last commit into the TOC.
+    pub(crate) fn flush_access_stats(&mut self) -> Result<()> {
+        if self.pending_access.is_empty() {
+            return Ok(());
+        }
+        let mut stats = self
+            .toc
+            .extension::<AccessStats>(ACCESS_STATS_EXTENSION)?
+            .unwrap_or_default();
+        stats.merge(&std::mem::take(&mut self.pending_access));
+        self.toc.set_extension(ACCESS_STATS_EXTENSION, &stats)?;
+        Ok(())
+    }
+
+    /// Raise the scores of hits in proportion to the log of their retrieval count, relative
+    /// to the hottest frame, then re-rank by score. Returns whether any hit changed.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn apply_access_boost(&self, hits: &mut [SearchHit]) -> Result<bool> {
+        if hits.iter().all(|hit| hit.score.is_none()) {
+            return Ok(false);
+        }
+        let stats = self.access_stats()?;
+        let max_heat = stats.frames.values().map(FrameAccess::heat).max();
+        let Some(max_heat) = max_heat.filter(|heat| *heat > 0) else {
+            return Ok(false);
+        };
+        let scale = (max_heat as f32).ln_1p();
+
+        let mut boosted = false;
+        for hit in hits.iter_mut() {
+            let heat = stats.get(hit.frame_id).map_or(0, FrameAccess::heat);
+            if let Some(score) = hit.score.as_mut().filter(|_| heat > 0) {
+                *score *= 1.0 + ACCESS_BOOST * (heat as f32).ln_1p() / scale;
+                boosted = true;
+            }
+        }
+        if boosted {
+            hits.sort_by(|a, b| {
+                b.score
+                    .unwrap_or(f32::NEG_INFINITY)
+                    .total_cmp(&a.score.unwrap_or(f32::NEG_INFINITY))
+            });
+            for (index, hit) in hits.iter_mut().enumerate() {
+                hit.rank = index + 1;
+            }
+        }
+        Ok(boosted)
+    }
+}
+
+#[cfg(all(test, feature = "lex"))]
+mod tests {
+    use super::*;
+    use crate::types::{AclEnforcementMode, PutOptions, SearchMode, SearchRequest};
+
+    fn request(query: &str, access_boost: bool) -> SearchRequest {
+        SearchRequest {
+            query: query.into(),
+            top_k: 5,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        }
+    }
+
+    #[test]
+    fn searches_feed_hot_frames_and_persist_on_commit() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("access.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for (uri, body) in [
+            ("mv2://notes", "release notes for the launch"),
+            ("mv2://checklist", "launch checklist and release"),
+        ] {
+            let options = PutOptions::builder().uri(uri).build();
+            mem.put_bytes_with_options(body.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let baseline = mem
+            .search(request("release launch", false))
+            .expect("search");
+        let last = baseline.hits.last().expect("hits");
+        let (last, only_in_last) = if last.uri.ends_with("notes") {
+            (last.frame_id, "notes")
+        } else {
+            (last.frame_id, "checklist")
+        };
+        for _ in 0..3 {
+            mem.search(request(only_in_last, false)).expect("search");
+        }
+        let hot = mem.hot_frames(1).expect("hot frames");
+        assert_eq!((hot[0].frame_id, hot[0].returned), (last, 4));
+
+        let boosted = mem.search(request("release launch", true)).expect("search");
+        assert_eq!(boosted.hits[0].frame_id, last);
+
+        mem.commit().expect("commit");
+        drop(mem);
+        let reopened = Memvid::open(&path).expect("open");
+        let stats = reopened.access_stats().expect("stats");
+        assert_eq!(stats.get(last).map(|access| access.returned), Some(5));
+        assert_eq!(
+            reopened.stats().expect("stats").hot_frames[0].frame_id,
+            last
+        );
+    }
+}
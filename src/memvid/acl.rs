@@ -3,7 +3,9 @@ use std::collections::{BTreeMap, HashSet};
 use crate::memvid::lifecycle::Memvid;
 use crate::types::{
     ACL_READ_GROUPS_KEY, ACL_READ_PRINCIPALS_KEY, ACL_READ_ROLES_KEY, ACL_TENANT_ID_KEY,
-    ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode, SearchHit,
+    ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode, CLASSIFICATION_KEY, CONSENT_KEY,
+    Classification, ClassificationAction, ClassificationPolicy, FrameId, REDACTED_TEXT, SearchHit,
+    Toc,
 };
 use crate::{MemvidError, Result};
 
@@ -13,6 +15,12 @@ pub(crate) struct AclFilterStats {
     pub denied: usize,
     pub cross_tenant_denied: usize,
     pub missing_metadata: usize,
+    /// Hits dropped by the classification policy.
+    pub withheld: usize,
+    /// Frames whose hits the classification policy redacted.
+    pub redacted: HashSet<FrameId>,
+    /// Whether `hits` was rewritten, so totals and context need rebuilding.
+    pub rewritten: bool,
 }
 
 impl AclFilterStats {
@@ -100,42 +108,116 @@ impl Memvid {
         acl_context: Option<&AclContext>,
         acl_enforcement_mode: AclEnforcementMode,
     ) -> Result<AclFilterStats> {
-        let normalized_context = match acl_enforcement_mode {
-            AclEnforcementMode::Audit => normalize_acl_context(acl_context),
-            AclEnforcementMode::Enforce => Some(validate_enforce_acl_context(acl_context)?),
-        };
+        apply_acl_to_hits(&self.toc, hits, acl_context, acl_enforcement_mode)
+    }
+}
 
-        let mut stats = AclFilterStats::default();
-        if normalized_context.is_none() {
-            stats.allowed = hits.len();
-            return Ok(stats);
-        }
+/// Filter and redact `hits` against the frames in `toc`; shared by local and remote handles.
+pub(crate) fn apply_acl_to_hits(
+    toc: &Toc,
+    hits: &mut Vec<SearchHit>,
+    acl_context: Option<&AclContext>,
+    acl_enforcement_mode: AclEnforcementMode,
+) -> Result<AclFilterStats> {
+    let normalized_context = match acl_enforcement_mode {
+        AclEnforcementMode::Audit => normalize_acl_context(acl_context),
+        AclEnforcementMode::Enforce => Some(validate_enforce_acl_context(acl_context)?),
+    };
 
-        let mut filtered_hits = Vec::with_capacity(hits.len());
-        for hit in &*hits {
-            let decision = match self.frame_by_id(hit.frame_id) {
-                Ok(frame) => {
-                    evaluate_acl_metadata(&frame.extra_metadata, normalized_context.as_ref())
+    let classification = acl_context.and_then(|context| context.classification.as_ref());
+
+    let mut stats = AclFilterStats::default();
+    if normalized_context.is_none() && classification.is_none() {
+        stats.allowed = hits.len();
+        return Ok(stats);
+    }
+
+    let enforce = acl_enforcement_mode == AclEnforcementMode::Enforce;
+    let mut filtered_hits = Vec::with_capacity(hits.len());
+    for hit in &*hits {
+        let frame = usize::try_from(hit.frame_id)
+            .ok()
+            .and_then(|index| toc.frames.get(index));
+        let decision = match &frame {
+            Some(frame) => {
+                evaluate_acl_metadata(&frame.extra_metadata, normalized_context.as_ref())
+            }
+            None => AclDecision::deny_missing_metadata(),
+        };
+        stats.record(decision);
+        if !decision.allowed && enforce {
+            continue;
+        }
+        let mut hit = hit.clone();
+        if let Some(policy) = classification {
+            let cleared = frame
+                .as_ref()
+                .is_some_and(|frame| classification_allows(&frame.extra_metadata, policy));
+            if !cleared {
+                match policy.action {
+                    ClassificationAction::Filter => {
+                        stats.withheld += 1;
+                        continue;
+                    }
+                    ClassificationAction::Redact => {
+                        stats.redacted.insert(hit.frame_id);
+                        redact_hit(&mut hit);
+                    }
                 }
-                Err(_) => AclDecision::deny_missing_metadata(),
-            };
-            stats.record(decision);
-            if decision.allowed || acl_enforcement_mode == AclEnforcementMode::Audit {
-                filtered_hits.push(hit.clone());
             }
         }
+        filtered_hits.push(hit);
+    }
 
-        if acl_enforcement_mode == AclEnforcementMode::Enforce {
-            for (index, hit) in filtered_hits.iter_mut().enumerate() {
-                hit.rank = index + 1;
-            }
-            *hits = filtered_hits;
+    stats.rewritten = enforce || stats.withheld > 0 || !stats.redacted.is_empty();
+    if stats.rewritten {
+        for (index, hit) in filtered_hits.iter_mut().enumerate() {
+            hit.rank = index + 1;
         }
+        *hits = filtered_hits;
+    }
+
+    Ok(stats)
+}
 
-        Ok(stats)
+/// Whether a frame with `metadata` is within `policy`'s clearance and consented to for
+/// every purpose it names.
+fn classification_allows(
+    metadata: &BTreeMap<String, String>,
+    policy: &ClassificationPolicy,
+) -> bool {
+    let level = match metadata.get(CLASSIFICATION_KEY) {
+        Some(raw) => normalize_scalar(Some(raw))
+            .and_then(|value| Classification::parse(&value))
+            .unwrap_or(Classification::Secret),
+        None => policy.unlabeled,
+    };
+    if level > policy.clearance {
+        return false;
+    }
+    if policy.purposes.is_empty() {
+        return true;
+    }
+    if !metadata.contains_key(CONSENT_KEY) {
+        return !policy.require_consent;
+    }
+    match parse_acl_list(metadata, CONSENT_KEY) {
+        Ok(consented) => policy.purposes.iter().all(|purpose| {
+            normalize_scalar(Some(purpose)).is_some_and(|purpose| consented.contains(&purpose))
+        }),
+        Err(()) => false,
     }
 }
 
+fn redact_hit(hit: &mut SearchHit) {
+    hit.uri = REDACTED_TEXT.to_string();
+    hit.title = None;
+    hit.text = REDACTED_TEXT.to_string();
+    hit.chunk_text = None;
+    hit.metadata = None;
+    hit.highlights.clear();
+}
+
 fn validate_enforce_acl_context(context: Option<&AclContext>) -> Result<NormalizedAclContext> {
     let Some(context) = context else {
         return Err(MemvidError::InvalidQuery {
@@ -328,6 +410,133 @@ mod tests {
         assert!(decision.allowed);
     }
 
+    #[test]
+    fn classification_checks_clearance_and_consent() {
+        let metadata = BTreeMap::from([
+            (CLASSIFICATION_KEY.to_string(), "internal".to_string()),
+            (CONSENT_KEY.to_string(), "[\"search\"]".to_string()),
+        ]);
+        let mut policy = ClassificationPolicy::clearance(Classification::Public);
+        assert!(!classification_allows(&metadata, &policy));
+        policy.clearance = Classification::Internal;
+        assert!(classification_allows(&metadata, &policy));
+        policy.purposes = vec!["search".to_string(), "llm".to_string()];
+        assert!(!classification_allows(&metadata, &policy));
+
+        let unknown = BTreeMap::from([(CLASSIFICATION_KEY.to_string(), "top".to_string())]);
+        assert!(!classification_allows(
+            &unknown,
+            &ClassificationPolicy::clearance(Classification::Internal)
+        ));
+        policy.purposes = vec!["llm".to_string()];
+        assert!(classification_allows(&BTreeMap::new(), &policy));
+        policy.require_consent = true;
+        assert!(!classification_allows(&BTreeMap::new(), &policy));
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn classification_policy_filters_and_redacts_search_hits() {
+        use crate::types::{EntityKind, MeshNode, PutOptions, SearchRequest};
+
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("labels.mv2")).expect("create");
+        for (uri, classification) in [
+            ("mv2://work/roadmap", Classification::Internal),
+            ("mv2://personal/diary", Classification::Secret),
+        ] {
+            let options = PutOptions::builder()
+                .uri(uri)
+                .classification(classification)
+                .build();
+            mem.put_bytes_with_options(b"quarterly plans and private thoughts", options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        mem.add_mesh_nodes(
+            [("Ada Lovelace", 0), ("Charles Babbage", 1)]
+                .into_iter()
+                .map(|(name, frame_id)| {
+                    MeshNode::new(
+                        name.to_lowercase(),
+                        name.to_string(),
+                        EntityKind::Person,
+                        0.9,
+                        frame_id,
+                        0,
+                        4,
+                    )
+                })
+                .collect(),
+        );
+
+        let search = |mem: &mut Memvid, action| {
+            let policy = ClassificationPolicy {
+                action,
+                ..ClassificationPolicy::clearance(Classification::Internal)
+            };
+            mem.search(SearchRequest {
+                query: "quarterly plans".into(),
+                top_k: 5,
+                snippet_chars: 80,
+                uri: None,
+                scope: None,
+                cursor: None,
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+                as_of_frame: None,
+                as_of_ts: None,
+                no_sketch: false,
+                acl_context: Some(AclContext {
+                    classification: Some(policy),
+                    ..AclContext::default()
+                }),
+                acl_enforcement_mode: AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: crate::types::SearchMode::Lexical,
+                time_budget_ms: None,
+            })
+            .expect("search")
+        };
+
+        let filtered = search(&mut mem, ClassificationAction::Filter);
+        assert_eq!(filtered.total_hits, 1);
+        assert_eq!(filtered.hits[0].uri, "mv2://work/roadmap");
+
+        let redacted = search(&mut mem, ClassificationAction::Redact);
+        assert_eq!(redacted.hits.len(), 2);
+        let hidden = redacted
+            .hits
+            .iter()
+            .find(|hit| hit.uri != "mv2://work/roadmap")
+            .expect("redacted hit");
+        assert_eq!(
+            (hidden.uri.as_str(), hidden.text.as_str()),
+            (REDACTED_TEXT, REDACTED_TEXT)
+        );
+        assert!(hidden.title.is_none() && hidden.chunk_text.is_none());
+        assert!(
+            hidden.metadata.is_none(),
+            "no mesh entities on redacted hits"
+        );
+        let visible = redacted
+            .hits
+            .iter()
+            .find(|hit| hit.uri == "mv2://work/roadmap")
+            .expect("visible hit");
+        let entities = &visible.metadata.as_ref().expect("metadata").entities;
+        assert_eq!(entities[0].name, "Ada Lovelace");
+    }
+
     #[test]
     fn evaluate_acl_denies_missing_metadata() {
         let metadata = BTreeMap::new();
@@ -0,0 +1,48 @@
+//! Per-file lexical analyzer configuration (see [`crate::types::analyzer`]).
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{ANALYZER_CONFIG_EXTENSION, AnalyzerConfig};
+
+impl Memvid {
+    /// Stopwords, synonyms, and unstemmed terms for lexical search; an empty config restores
+    /// the default analyzer.
+    ///
+    /// Synonyms apply from the next query. Stopwords and protected terms change how content is
+    /// tokenized, so the lexical index is rebuilt with them right away. Saved in the file on
+    /// the next commit.
+    pub fn set_analyzer_config(&mut self, config: AnalyzerConfig) -> Result<()> {
+        self.ensure_writable()?;
+        let config = config.normalized();
+        #[cfg(feature = "lex")]
+        let previous = self.analyzer_config()?;
+        if config.is_empty() {
+            self.toc.extensions.remove(ANALYZER_CONFIG_EXTENSION);
+        } else {
+            self.toc.set_extension(ANALYZER_CONFIG_EXTENSION, &config)?;
+        }
+        self.dirty = true;
+
+        #[cfg(feature = "lex")]
+        if previous.stopwords != config.stopwords
+            || previous.protected_terms != config.protected_terms
+        {
+            if let Some(mut engine) = self.tantivy.take() {
+                engine.set_analyzer_config(&config);
+                let rebuilt = self.rebuild_tantivy_engine(&mut engine);
+                self.tantivy = Some(engine);
+                rebuilt?;
+                self.tantivy_dirty = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// The file's analyzer configuration, or the empty default.
+    pub fn analyzer_config(&self) -> Result<AnalyzerConfig> {
+        Ok(self
+            .toc
+            .extension(ANALYZER_CONFIG_EXTENSION)?
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,153 @@
+//! Archive ingestion for `Memvid`.
+//!
+//! An archive lands as one parent frame holding its member listing and one child frame per
+//! member, all in the same WAL batch so the children resolve their parent on commit. Each
+//! member goes through the reader registry like any put, routed by its path.
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::reader::{ArchiveOptions, ArchiveReader};
+use crate::types::{ARCHIVE_FRAME_KIND, ARCHIVE_MEMBER_KEY, ArchiveReceipt, PutOptions};
+
+impl Memvid {
+    /// Store a zip, tar, or tar.gz archive as a listing frame plus one child frame per member.
+    ///
+    /// `options` describe the parent frame; members inherit its track, tags, labels, extra
+    /// metadata, and enrichment flags. Member URIs are `<options.uri>#<path>`, or the bare
+    /// path when the archive has no URI, and member timestamps come from the archive when it
+    /// records them. Call `commit` to persist.
+    ///
+    /// # Errors
+    /// Fails if the payload is not a supported archive or a write fails.
+    pub fn put_archive(
+        &mut self,
+        bytes: &[u8],
+        options: PutOptions,
+        archive: &ArchiveOptions,
+    ) -> Result<ArchiveReceipt> {
+        self.ensure_mutation_allowed()?;
+        let contents = ArchiveReader::with_options(archive.clone()).read(bytes)?;
+
+        let mut parent_options = options.clone();
+        parent_options.kind = Some(ARCHIVE_FRAME_KIND.to_string());
+        let listing = contents.listing();
+        let archive_sequence = self.put_internal(
+            Some(listing.as_bytes()),
+            None,
+            None,
+            None,
+            parent_options,
+            None,
+            None,
+        )?;
+
+        let mut receipt = ArchiveReceipt {
+            archive_sequence,
+            skipped: contents.skipped,
+            ..ArchiveReceipt::default()
+        };
+        for member in contents.members {
+            let mut member_options = PutOptions {
+                timestamp: member.modified.or(options.timestamp),
+                track: options.track.clone(),
+                uri: Some(match &options.uri {
+                    Some(uri) => format!("{uri}#{}", member.path),
+                    None => member.path.clone(),
+                }),
+                title: member.path.rsplit('/').next().map(str::to_string),
+                tags: options.tags.clone(),
+                labels: options.labels.clone(),
+                extra_metadata: options.extra_metadata.clone(),
+                enable_embedding: options.enable_embedding,
+                auto_tag: options.auto_tag,
+                extract_dates: options.extract_dates,
+                extract_triplets: options.extract_triplets,
+                instant_index: options.instant_index,
+                ..PutOptions::default()
+            };
+            member_options
+                .extra_metadata
+                .insert(ARCHIVE_MEMBER_KEY.to_string(), member.path.clone());
+            receipt.member_sequences.push(self.put_internal(
+                Some(&member.bytes),
+                None,
+                None,
+                None,
+                member_options,
+                None,
+                Some(archive_sequence),
+            )?);
+            receipt.members.push(member.path);
+        }
+        Ok(receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FrameStatus;
+    use std::io::{Cursor, Write};
+    use tempfile::tempdir;
+
+    fn zip_bytes(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, text) in files {
+            writer
+                .start_file(*path, zip::write::SimpleFileOptions::default())
+                .expect("start file");
+            writer.write_all(text.as_bytes()).expect("write");
+        }
+        writer.finish().expect("finish").into_inner()
+    }
+
+    #[test]
+    fn put_archive_expands_members_into_children() {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = Memvid::create(dir.path().join("archive.mv2")).expect("create");
+        let bytes = zip_bytes(&[
+            (
+                "project/README.md",
+                "# Project\n\nDeployment runbook notes.",
+            ),
+            ("project/src/main.rs", "fn main() { println!(\"hi\"); }"),
+        ]);
+        let mut options = PutOptions::builder()
+            .uri("mv2://uploads/project.zip")
+            .build();
+        options.tags.push("upload".to_string());
+
+        let receipt = mem
+            .put_archive(&bytes, options, &ArchiveOptions::default())
+            .expect("put archive");
+        mem.commit().expect("commit");
+
+        assert_eq!(
+            receipt.members,
+            ["project/README.md", "project/src/main.rs"]
+        );
+        let parent = mem
+            .frame_by_uri("mv2://uploads/project.zip")
+            .expect("parent frame");
+        assert_eq!(parent.kind.as_deref(), Some(ARCHIVE_FRAME_KIND));
+        let listing = mem.frame_text_by_id(parent.id).expect("listing");
+        assert!(listing.contains("project/src/main.rs"));
+
+        let readme = mem
+            .frame_by_uri("mv2://uploads/project.zip#project/README.md")
+            .expect("member frame");
+        assert_eq!(readme.parent_id, Some(parent.id));
+        assert_eq!(readme.status, FrameStatus::Active);
+        assert_eq!(readme.title.as_deref(), Some("README.md"));
+        assert!(readme.tags.contains(&"upload".to_string()));
+        assert_eq!(
+            readme
+                .extra_metadata
+                .get(ARCHIVE_MEMBER_KEY)
+                .map(String::as_str),
+            Some("project/README.md")
+        );
+        let text = mem.frame_text_by_id(readme.id).expect("member text");
+        assert!(text.contains("Deployment runbook notes."));
+    }
+}
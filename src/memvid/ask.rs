@@ -5,28 +5,70 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::num::NonZeroU64;
 use std::time::Instant;
 
+#[cfg(feature = "lex")]
+use crate::memvid::audit::pdf_location;
 use crate::memvid::lifecycle::Memvid;
 use crate::memvid::search::helpers::{build_context, reorder_hits_by_token_matches};
 #[cfg(feature = "temporal_track")]
 use crate::types::TemporalFilter;
+use crate::types::summary::summary_track;
 use crate::types::{
     AskCitation, AskContextFragment, AskContextFragmentKind, AskMode, AskRequest, AskResponse,
-    AskRetriever, AskStats, SearchEngineKind, SearchHit, SearchParams, SearchRequest,
-    SearchResponse, TimelineQueryBuilder,
+    AskRetriever, AskStats, FrameId, LlmBackend, LlmParams, SearchEngineKind, SearchHit,
+    SearchMode, SearchParams, SearchRequest, SearchResponse, SourceSpan, TimelineQueryBuilder,
+    VecRescore,
 };
 use crate::{MemvidError, Result, VecEmbedder};
 
-const RRF_K: f32 = 60.0;
+pub(crate) const RRF_K: f32 = 60.0;
+/// Most sentences an extractive answer quotes.
+const EXTRACTIVE_MAX_SENTENCES: usize = 3;
 
 #[cfg(feature = "lex")]
 impl Memvid {
     pub fn ask<E>(&mut self, request: AskRequest, embedder: Option<&E>) -> Result<AskResponse>
+    where
+        E: VecEmbedder + ?Sized,
+    {
+        self.ask_inner(request, embedder, None)
+    }
+
+    /// `ask` with an answer written by `llm` from the retrieved context.
+    ///
+    /// Hits are added to the prompt in rank order until the prompt would leave fewer than
+    /// `params.max_tokens` of the backend's context window; only those hits are cited. Token
+    /// counts are reported in `AskStats`. `request.mode` only selects retrieval here, and
+    /// `AskMode::Generative` retrieves like `AskMode::Hybrid`.
+    pub fn ask_with_llm<E>(
+        &mut self,
+        request: AskRequest,
+        embedder: Option<&E>,
+        llm: &dyn LlmBackend,
+        params: &LlmParams,
+    ) -> Result<AskResponse>
+    where
+        E: VecEmbedder + ?Sized,
+    {
+        self.ask_inner(request, embedder, Some((llm, params)))
+    }
+
+    fn ask_inner<E>(
+        &mut self,
+        request: AskRequest,
+        embedder: Option<&E>,
+        llm: Option<(&dyn LlmBackend, &LlmParams)>,
+    ) -> Result<AskResponse>
     where
         E: VecEmbedder + ?Sized,
     {
         if !self.lex_enabled {
             return Err(MemvidError::LexNotEnabled);
         }
+        if request.mode == AskMode::Generative && llm.is_none() && !request.context_only {
+            return Err(MemvidError::InvalidQuery {
+                reason: "generative ask requires an LLM backend; use ask_with_llm".into(),
+            });
+        }
 
         let total_start = Instant::now();
         let lexical_query = sanitize_question_for_lexical(&request.question);
@@ -95,6 +137,7 @@ impl Memvid {
                         end_utc: request.end,
                         phrase: None,
                         tz: None,
+                        recurring_only: false,
                     })
                 } else {
                     None
@@ -107,6 +150,18 @@ impl Memvid {
             no_sketch: true,
             acl_context: request.acl_context.clone(),
             acl_enforcement_mode: request.acl_enforcement_mode,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
         };
 
         // Pre-compute the query embedding once so we can reuse it for vector recall and semantic re-rank
@@ -118,7 +173,7 @@ impl Memvid {
         }
 
         tracing::debug!("ask search query: {}", search_request.query);
-        let mut retrieval = self.search(search_request.clone())?;
+        let mut retrieval = self.search_unrecorded(search_request.clone())?;
         self.filter_hits_in_time_range(
             &mut retrieval.hits,
             request.start,
@@ -134,7 +189,7 @@ impl Memvid {
                     if or_query != search_request.query {
                         let mut or_request = search_request.clone();
                         or_request.query = or_query.clone();
-                        let mut or_response = self.search(or_request)?;
+                        let mut or_response = self.search_unrecorded(or_request)?;
                         self.filter_hits_in_time_range(
                             &mut or_response.hits,
                             request.start,
@@ -154,7 +209,7 @@ impl Memvid {
                     if fallback_query != search_request.query {
                         let mut fallback_request = search_request.clone();
                         fallback_request.query = fallback_query.clone();
-                        let mut fallback_response = self.search(fallback_request)?;
+                        let mut fallback_response = self.search_unrecorded(fallback_request)?;
                         self.filter_hits_in_time_range(
                             &mut fallback_response.hits,
                             request.start,
@@ -176,7 +231,7 @@ impl Memvid {
                     if expanded_query != search_request.query {
                         let mut expanded_request = search_request.clone();
                         expanded_request.query = expanded_query.clone();
-                        let mut expanded_response = self.search(expanded_request)?;
+                        let mut expanded_response = self.search_unrecorded(expanded_request)?;
                         self.filter_hits_in_time_range(
                             &mut expanded_response.hits,
                             request.start,
@@ -247,7 +302,7 @@ impl Memvid {
                 if or_query != search_request.query {
                     let mut or_request = search_request.clone();
                     or_request.query = or_query.clone();
-                    let mut or_response = self.search(or_request)?;
+                    let mut or_response = self.search_unrecorded(or_request)?;
                     self.filter_hits_in_time_range(
                         &mut or_response.hits,
                         request.start,
@@ -285,7 +340,7 @@ impl Memvid {
             let mut correction_request = search_request.clone();
             correction_request.query = correction_query;
             correction_request.top_k = 10; // Limit to 10 corrections
-            if let Ok(correction_response) = self.search(correction_request) {
+            if let Ok(correction_response) = self.search_unrecorded(correction_request) {
                 if !correction_response.hits.is_empty() {
                     tracing::debug!(
                         "found {} potential corrections for question",
@@ -368,23 +423,47 @@ impl Memvid {
         // This ensures user corrections override all other ranking signals
         promote_corrections(self, &mut retrieval.hits)?;
 
-        self.apply_acl_to_search_hits(
+        let acl = self.apply_acl_to_search_hits(
             &mut retrieval.hits,
             request.acl_context.as_ref(),
             request.acl_enforcement_mode,
         )?;
-        if request.acl_enforcement_mode == crate::types::AclEnforcementMode::Enforce {
+        if acl.rewritten {
             retrieval.total_hits = retrieval.hits.len();
         }
 
         retrieval.context = build_context(&retrieval.hits);
 
+        let mut sources = Vec::new();
+        let mut prompt_tokens = 0;
+        let mut completion_tokens = 0;
         let (answer, citations, synthesis_ms) = if request.context_only {
             (None, Vec::new(), 0)
         } else {
             let synth_start = Instant::now();
-            let citations = build_citations(&retrieval.hits, &semantic_scores);
-            let answer = synthesize_answer(&request.question, &retrieval.hits, &citations);
+            let extracted = if request.mode == AskMode::Extractive {
+                extractive_answer(&request.question, &retrieval.hits, &semantic_scores)
+            } else {
+                None
+            };
+            let (answer, citations) = if let Some((llm, params)) = llm {
+                let (prompt, included) =
+                    build_generative_prompt(&request.question, &retrieval.hits, llm, params);
+                let completion = llm.complete(&prompt, params)?;
+                prompt_tokens = completion.prompt_tokens;
+                completion_tokens = completion.completion_tokens;
+                let mut citations = build_citations(&retrieval.hits, &semantic_scores);
+                citations.truncate(included);
+                (Some(completion.text.trim().to_string()), citations)
+            } else if let Some((answer, citations)) = extracted {
+                sources = self.citation_sources(&retrieval.hits, &citations);
+                sources.retain(|source| !acl.redacted.contains(&source.frame_id));
+                (Some(answer), citations)
+            } else {
+                let citations = build_citations(&retrieval.hits, &semantic_scores);
+                let answer = synthesize_answer(&request.question, &retrieval.hits, &citations);
+                (answer, citations)
+            };
             let synth_ms = synth_start.elapsed().as_millis();
             (answer, citations, synth_ms)
         };
@@ -399,9 +478,11 @@ impl Memvid {
             retrieval_ms,
             synthesis_ms,
             latency_ms: total_start.elapsed().as_millis(),
+            prompt_tokens,
+            completion_tokens,
         };
 
-        let context_fragments: Vec<AskContextFragment> = retrieval
+        let mut context_fragments: Vec<AskContextFragment> = retrieval
             .hits
             .iter()
             .map(|hit| AskContextFragment {
@@ -422,6 +503,11 @@ impl Memvid {
                     .and_then(|metadata| metadata.temporal.clone()),
             })
             .collect();
+        let summary_fragments =
+            self.summary_fragments(&retrieval.hits, request.scope.as_deref(), &acl.redacted);
+        context_fragments.extend(summary_fragments);
+        self.record_search_access(&retrieval.hits);
+        self.record_citation_access(&citations);
 
         Ok(AskResponse {
             question: request.question,
@@ -432,10 +518,116 @@ impl Memvid {
             answer,
             citations,
             context_fragments,
+            sources,
             stats,
         })
     }
 
+    /// Resolve extractive citations to source spans quoting the cited sentence.
+    fn citation_sources(&self, hits: &[SearchHit], citations: &[AskCitation]) -> Vec<SourceSpan> {
+        citations
+            .iter()
+            .map(|citation| {
+                let frame = self.frame_by_id(citation.frame_id).ok();
+                let snippet = citation.chunk_range.and_then(|range| {
+                    hits.iter()
+                        .filter(|hit| hit.frame_id == citation.frame_id)
+                        .find_map(|hit| hit_slice(hit, range))
+                        .map(str::to_string)
+                });
+                let (page, rect) = frame
+                    .as_ref()
+                    .map(|frame| pdf_location(frame, citation.chunk_range))
+                    .unwrap_or_default();
+                SourceSpan {
+                    index: citation.index,
+                    frame_id: citation.frame_id,
+                    uri: citation.uri.clone(),
+                    title: frame.as_ref().and_then(|frame| frame.title.clone()),
+                    chunk_range: citation.chunk_range,
+                    score: citation.score,
+                    tags: frame
+                        .as_ref()
+                        .map(|frame| frame.tags.clone())
+                        .unwrap_or_default(),
+                    labels: frame
+                        .as_ref()
+                        .map(|frame| frame.labels.clone())
+                        .unwrap_or_default(),
+                    frame_timestamp: frame.as_ref().map(|frame| frame.timestamp),
+                    content_dates: frame
+                        .as_ref()
+                        .map(|frame| frame.content_dates.clone())
+                        .unwrap_or_default(),
+                    snippet,
+                    page,
+                    rect,
+                }
+            })
+            .collect()
+    }
+
+    /// Summary fragments for the documents behind `hits`, preceded by the scope summary.
+    /// Hits on `redacted` frames contribute none.
+    fn summary_fragments(
+        &self,
+        hits: &[SearchHit],
+        scope: Option<&str>,
+        redacted: &HashSet<FrameId>,
+    ) -> Vec<AskContextFragment> {
+        let summaries = summary_track(&self.toc);
+        if summaries.is_empty() {
+            return Vec::new();
+        }
+        let fragment =
+            |rank: usize, frame_id: FrameId, uri: String, title: Option<String>, text: &str| {
+                AskContextFragment {
+                    rank,
+                    frame_id,
+                    uri,
+                    title,
+                    score: None,
+                    matches: 0,
+                    range: None,
+                    chunk_range: None,
+                    text: text.to_string(),
+                    kind: Some(AskContextFragmentKind::Summary),
+                    #[cfg(feature = "temporal_track")]
+                    temporal: None,
+                }
+            };
+
+        let mut fragments = Vec::new();
+        if let Some((scope, card)) =
+            scope.and_then(|scope| summaries.scope(scope).map(|card| (scope, card)))
+        {
+            let frame_id = card.sources.first().copied().unwrap_or_default();
+            fragments.push(fragment(0, frame_id, scope.to_string(), None, &card.text));
+        }
+        let mut seen = HashSet::new();
+        for hit in hits.iter().filter(|hit| !redacted.contains(&hit.frame_id)) {
+            let document = self
+                .toc
+                .frames
+                .get(usize::try_from(hit.frame_id).unwrap_or(usize::MAX))
+                .and_then(|frame| frame.parent_id)
+                .unwrap_or(hit.frame_id);
+            if !seen.insert(document) {
+                continue;
+            }
+            if let Some(card) = summaries.frame(document) {
+                fragments.push(fragment(
+                    hit.rank,
+                    document,
+                    hit.uri.clone(),
+                    hit.title.clone(),
+                    &card.text,
+                ));
+            }
+        }
+        fragments
+    }
+
     fn filter_hits_in_time_range(
         &mut self,
         hits: &mut Vec<SearchHit>,
@@ -575,8 +767,13 @@ impl Memvid {
                     top_k: request.top_k,
                     snippet_chars: request.snippet_chars,
                     cursor: search_request.cursor.clone(),
+                    vec_rescore: VecRescore::default(),
                 },
                 stale_index_skips: 0,
+                suggestions: Vec::new(),
+                explain: None,
+                partial: false,
+                skipped_stages: Vec::new(),
             });
         }
 
@@ -641,6 +838,7 @@ impl Memvid {
                 text: frame_text.clone(),
                 chunk_text: Some(frame_text.clone()),
                 metadata: None,
+                highlights: Vec::new(),
             });
         }
 
@@ -659,8 +857,13 @@ impl Memvid {
                 top_k: request.top_k,
                 snippet_chars: request.snippet_chars,
                 cursor: search_request.cursor.clone(),
+                vec_rescore: VecRescore::default(),
             },
             stale_index_skips: 0,
+            suggestions: Vec::new(),
+            explain: None,
+            partial: false,
+            skipped_stages: Vec::new(),
         })
     }
 }
@@ -673,6 +876,19 @@ impl Memvid {
     {
         Err(MemvidError::LexNotEnabled)
     }
+
+    pub fn ask_with_llm<E>(
+        &mut self,
+        _request: AskRequest,
+        _embedder: Option<&E>,
+        _llm: &dyn LlmBackend,
+        _params: &LlmParams,
+    ) -> Result<AskResponse>
+    where
+        E: VecEmbedder + ?Sized,
+    {
+        Err(MemvidError::LexNotEnabled)
+    }
 }
 
 fn determine_retriever(
@@ -697,7 +913,7 @@ fn determine_retriever(
                 AskRetriever::LexFallback
             }
         }
-        AskMode::Hybrid => {
+        AskMode::Hybrid | AskMode::Extractive | AskMode::Generative => {
             if semantics_applied {
                 AskRetriever::Hybrid
             } else if lex_fallback_used {
@@ -732,7 +948,7 @@ fn reorder_hits_with_semantic_scores(
             let semantic_score = semantic_scores.get(&hit.frame_id).copied().unwrap_or(0.0);
             let combined = match mode {
                 AskMode::Sem => semantic_score,
-                AskMode::Hybrid => {
+                AskMode::Hybrid | AskMode::Extractive | AskMode::Generative => {
                     let lexical_rrf = 1.0 / (RRF_K + lexical_rank as f32);
                     let semantic_rrf = semantic_rank
                         .get(&hit.frame_id)
@@ -772,6 +988,7 @@ fn build_citations(hits: &[SearchHit], semantic_scores: &HashMap<u64, f32>) -> V
             uri: hit.uri.clone(),
             chunk_range: hit.chunk_range.or(Some(hit.range)),
             score: semantic_scores.get(&hit.frame_id).copied().or(hit.score),
+            answer_range: None,
         })
         .collect()
 }
@@ -812,6 +1029,191 @@ fn synthesize_answer(
     Some(segments.join(" "))
 }
 
+/// Prompt asking `llm` to answer from the leading hits, and how many hits it includes.
+///
+/// Each hit is numbered to match its citation index. Hits are added in rank order while the
+/// prompt leaves `params.max_tokens` of the context window free; the first hit that does not
+/// fit ends the list so citation numbers stay contiguous.
+fn build_generative_prompt(
+    question: &str,
+    hits: &[SearchHit],
+    llm: &dyn LlmBackend,
+    params: &LlmParams,
+) -> (String, usize) {
+    const INSTRUCTIONS: &str = "Answer the question using only the numbered context passages. \
+        Cite the passages you rely on as [n]. If the context does not contain the answer, say \
+        that you do not know.\n\nContext:\n";
+    let question_block = format!("\nQuestion: {question}\nAnswer:");
+    let budget = llm.context_window().saturating_sub(params.max_tokens);
+    let mut used = llm.count_tokens(INSTRUCTIONS) + llm.count_tokens(&question_block);
+
+    let mut passages = String::new();
+    let mut included = 0;
+    for (idx, hit) in hits.iter().enumerate() {
+        let text = hit.chunk_text.as_deref().unwrap_or(&hit.text);
+        let passage = format!("[{}] {}\n\n", idx + 1, text.trim());
+        let cost = llm.count_tokens(&passage);
+        if used + cost > budget {
+            break;
+        }
+        used += cost;
+        passages.push_str(&passage);
+        included += 1;
+    }
+    (
+        format!("{INSTRUCTIONS}{passages}{question_block}"),
+        included,
+    )
+}
+
+/// Build an answer from the hit sentences that cover the most question terms.
+///
+/// Sentences covering at most half of the best sentence's terms are dropped; up to
+/// [`EXTRACTIVE_MAX_SENTENCES`] of the rest are quoted in retrieval order, each followed by
+/// its citation marker. Returns `None` when no sentence shares a term with the question.
+fn extractive_answer(
+    question: &str,
+    hits: &[SearchHit],
+    semantic_scores: &HashMap<u64, f32>,
+) -> Option<(String, Vec<AskCitation>)> {
+    let terms: HashSet<String> = word_tokens(question)
+        .filter(|token| token.len() > 2 && !is_stopword(token))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    // (coverage, hit index, sentence range within the hit text)
+    let mut candidates: Vec<(usize, usize, (usize, usize))> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    for (hit_idx, hit) in hits.iter().enumerate() {
+        let (text, _) = hit_text(hit);
+        for (start, end) in sentence_ranges(text) {
+            if is_metadata_line(text, start) {
+                continue;
+            }
+            let sentence = &text[start..end];
+            let words: Vec<String> = word_tokens(sentence).collect();
+            let coverage = terms
+                .iter()
+                .filter(|term| words.iter().any(|word| terms_match(term, word)))
+                .count();
+            if coverage == 0 {
+                continue;
+            }
+            let normalized = sentence
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+            if seen.insert(normalized) {
+                candidates.push((coverage, hit_idx, (start, end)));
+            }
+        }
+    }
+    // Keep sentences covering more than half as many terms as the best one.
+    let best = candidates.iter().map(|candidate| candidate.0).max()?;
+    candidates.retain(|candidate| candidate.0 * 2 > best);
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+    candidates.truncate(EXTRACTIVE_MAX_SENTENCES);
+    candidates.sort_by(|a, b| a.1.cmp(&b.1).then(a.2.cmp(&b.2)));
+
+    let mut answer = String::new();
+    let mut citations = Vec::with_capacity(candidates.len());
+    for (position, (_, hit_idx, (start, end))) in candidates.into_iter().enumerate() {
+        let hit = &hits[hit_idx];
+        let (text, base) = hit_text(hit);
+        let quoted = text[start..end].split_whitespace().collect::<Vec<_>>();
+        if !answer.is_empty() {
+            answer.push(' ');
+        }
+        let answer_start = answer.len();
+        answer.push_str(&quoted.join(" "));
+        let answer_end = answer.len();
+        let index = position + 1;
+        answer.push_str(&format!(" [{index}]"));
+        citations.push(AskCitation {
+            index,
+            frame_id: hit.frame_id,
+            uri: hit.uri.clone(),
+            chunk_range: Some((base + start, base + end)),
+            score: semantic_scores.get(&hit.frame_id).copied().or(hit.score),
+            answer_range: Some((answer_start, answer_end)),
+        });
+    }
+    Some((answer, citations))
+}
+
+/// Text of `hit` and the frame byte offset it starts at.
+fn hit_text(hit: &SearchHit) -> (&str, usize) {
+    match (&hit.chunk_text, hit.chunk_range) {
+        (Some(text), Some((start, _))) => (text.as_str(), start),
+        _ => (hit.text.as_str(), hit.range.0),
+    }
+}
+
+/// Slice of `hit` covering the frame byte `range`, if the hit contains it.
+fn hit_slice(hit: &SearchHit, range: (usize, usize)) -> Option<&str> {
+    let (text, base) = hit_text(hit);
+    text.get(range.0.checked_sub(base)?..range.1.checked_sub(base)?)
+}
+
+/// Trimmed sentence byte ranges in `text`. Sentences end at a newline or at `.`, `!`, or `?`
+/// followed by whitespace, so decimals and abbreviations like "v2.1" stay intact.
+fn sentence_ranges(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, ch)) = chars.next() {
+        let boundary = match ch {
+            '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if boundary {
+            let end = idx + ch.len_utf8();
+            push_trimmed_range(text, start, end, &mut ranges);
+            start = end;
+        }
+    }
+    push_trimmed_range(text, start, text.len(), &mut ranges);
+    ranges
+}
+
+fn push_trimmed_range(text: &str, start: usize, end: usize, ranges: &mut Vec<(usize, usize)>) {
+    let slice = &text[start..end];
+    let trimmed = slice.trim();
+    if !trimmed.is_empty() {
+        let offset = start + (slice.len() - slice.trim_start().len());
+        ranges.push((offset, offset + trimmed.len()));
+    }
+}
+
+/// Whether the line containing byte `offset` is a `key: value` line appended to the search
+/// text at ingest (tags, labels, extra metadata) rather than document prose.
+fn is_metadata_line(text: &str, offset: usize) -> bool {
+    let line_start = text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+    let line = text[line_start..].lines().next().unwrap_or_default();
+    line.split_once(": ").is_some_and(|(key, _)| {
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+    })
+}
+
+/// Exact match, or a shared stem of at least four characters ("decide" / "decided").
+fn terms_match(term: &str, word: &str) -> bool {
+    term == word
+        || (term.len().min(word.len()) >= 4 && (word.starts_with(term) || term.starts_with(word)))
+}
+
+fn word_tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
+
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     let mut dot = 0.0f32;
     let mut sum_a = 0.0f32;
@@ -1570,7 +1972,60 @@ fn promote_temporal_extremes(
 
 #[cfg(test)]
 mod tests {
-    use super::{build_disjunctive_query, lexical_fallback_query, sanitize_question_for_lexical};
+    use super::{
+        build_disjunctive_query, lexical_fallback_query, sanitize_question_for_lexical,
+        sentence_ranges,
+    };
+    use crate::memvid::lifecycle::Memvid;
+    use crate::types::{
+        AclEnforcementMode, AskMode, AskRequest, LlmBackend, LlmCompletion, LlmParams, PutOptions,
+        VecEmbedder,
+    };
+
+    fn request(question: &str, mode: AskMode) -> AskRequest {
+        AskRequest {
+            question: question.into(),
+            top_k: 5,
+            snippet_chars: 400,
+            uri: None,
+            scope: None,
+            cursor: None,
+            start: None,
+            end: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            context_only: false,
+            mode,
+            as_of_frame: None,
+            as_of_ts: None,
+            adaptive: None,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+        }
+    }
+
+    /// Echoes the prompt back so tests can inspect what was sent.
+    struct EchoLlm {
+        window: usize,
+    }
+
+    impl LlmBackend for EchoLlm {
+        fn kind(&self) -> &'static str {
+            "echo"
+        }
+
+        fn context_window(&self) -> usize {
+            self.window
+        }
+
+        fn complete(&self, prompt: &str, _params: &LlmParams) -> crate::Result<LlmCompletion> {
+            Ok(LlmCompletion {
+                text: prompt.to_string(),
+                prompt_tokens: self.count_tokens(prompt),
+                completion_tokens: 7,
+            })
+        }
+    }
 
     #[test]
     fn sanitize_question_strips_trailing_punctuation() {
@@ -1612,4 +2067,98 @@ mod tests {
         let query = build_disjunctive_query(&tokens).expect("query");
         assert_eq!(query, "checksum OR header");
     }
+
+    #[test]
+    fn sentence_ranges_split_on_terminators_only() {
+        let text = "Ship v2.1 on Friday. Then rest!\n  Done";
+        let sentences: Vec<&str> = sentence_ranges(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect();
+        assert_eq!(sentences, ["Ship v2.1 on Friday.", "Then rest!", "Done"]);
+    }
+
+    #[test]
+    fn extractive_ask_cites_sentence_ranges() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("extractive.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        let notes = [
+            "Weekly sync notes. We decided to migrate the billing service to Postgres. Lunch was pizza.",
+            "Unrelated memo about the office plants.",
+        ];
+        for note in notes {
+            mem.put_bytes_with_options(note.as_bytes(), PutOptions::default())
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let request = request("What did we decide about billing?", AskMode::Extractive);
+        let response = mem.ask(request, None::<&dyn VecEmbedder>).expect("ask");
+        let answer = response.answer.expect("answer");
+        assert_eq!(
+            answer,
+            "We decided to migrate the billing service to Postgres. [1]"
+        );
+        let citation = &response.citations[0];
+        let (start, end) = citation.answer_range.expect("answer range");
+        assert_eq!(
+            &answer[start..end],
+            "We decided to migrate the billing service to Postgres."
+        );
+        let (start, end) = citation.chunk_range.expect("chunk range");
+        assert_eq!(
+            &notes[0][start..end],
+            "We decided to migrate the billing service to Postgres."
+        );
+        assert_eq!(
+            response.sources[0].snippet.as_deref(),
+            Some("We decided to migrate the billing service to Postgres.")
+        );
+    }
+
+    #[test]
+    fn generative_ask_budgets_prompt_to_context_window() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("generative.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for idx in 0..4 {
+            let text = format!("Deployment note {idx}: {}", "rollout details ".repeat(40));
+            mem.put_bytes_with_options(text.as_bytes(), PutOptions::default())
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        assert!(
+            mem.ask(
+                request("deployment rollout", AskMode::Generative),
+                None::<&dyn VecEmbedder>
+            )
+            .is_err()
+        );
+
+        // Room for the instructions and roughly two passages once completion tokens are reserved.
+        let llm = EchoLlm { window: 700 };
+        let params = LlmParams {
+            max_tokens: 100,
+            ..LlmParams::default()
+        };
+        let response = mem
+            .ask_with_llm(
+                request("deployment rollout", AskMode::Generative),
+                None::<&dyn VecEmbedder>,
+                &llm,
+                &params,
+            )
+            .expect("ask");
+        let prompt = response.answer.expect("answer");
+        assert!(prompt.contains("[1] "));
+        assert!(prompt.ends_with("Question: deployment rollout\nAnswer:"));
+        assert!(llm.count_tokens(&prompt) <= 600);
+        assert!(!response.citations.is_empty());
+        assert!(response.citations.len() < 4);
+        assert!(!prompt.contains(&format!("[{}] ", response.citations.len() + 1)));
+        assert_eq!(response.stats.prompt_tokens, llm.count_tokens(&prompt));
+        assert_eq!(response.stats.completion_tokens, 7);
+    }
 }
@@ -0,0 +1,326 @@
+//! Audio ingestion for `Memvid`.
+//!
+//! A recording lands as one media frame holding the raw bytes and the full transcript, plus one
+//! child frame per transcript segment, all written in the same WAL batch so the children
+//! resolve their parent on commit. Segment frames are timestamped at recording time plus their
+//! start offset, which anchors them in the time index and the temporal track.
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::metadata::seconds_to_ms;
+use crate::types::{
+    AUDIO_END_MS_KEY, AUDIO_FRAME_KIND, AUDIO_SEGMENT_FRAME_KIND, AUDIO_START_MS_KEY, AudioReceipt,
+    AudioSegmentMetadata, DocAudioMetadata, DocMetadata, MediaManifest, PutOptions,
+    VIDEO_KEYFRAME_MS_KEY,
+};
+use crate::whisper::{SPEAKER_KEY, TranscriptionResult};
+
+impl Memvid {
+    /// Decode, transcribe, and store an audio recording.
+    ///
+    /// Loads the default Whisper model; use [`Memvid::put_audio_with_transcriber`] to reuse a
+    /// loaded model across recordings. See [`Memvid::put_transcribed_audio`] for the frames
+    /// written.
+    #[cfg(feature = "whisper")]
+    pub fn put_audio(&mut self, bytes: &[u8], options: PutOptions) -> Result<AudioReceipt> {
+        let mut transcriber =
+            crate::whisper::WhisperTranscriber::new(&crate::whisper::WhisperConfig::default())?;
+        self.put_audio_with_transcriber(bytes, options, &mut transcriber)
+    }
+
+    /// [`Memvid::put_audio`] with a caller-provided transcriber.
+    #[cfg(feature = "whisper")]
+    pub fn put_audio_with_transcriber(
+        &mut self,
+        bytes: &[u8],
+        options: PutOptions,
+        transcriber: &mut crate::whisper::WhisperTranscriber,
+    ) -> Result<AudioReceipt> {
+        self.ensure_mutation_allowed()?;
+        let (_, extension) = sniff_audio(bytes);
+        let (pcm, duration_secs) = crate::whisper::decode_audio_bytes(bytes, extension)?;
+        let transcription = transcriber.transcribe_pcm(&pcm, duration_secs)?;
+        self.put_transcribed_audio(bytes, &transcription, options)
+    }
+
+    /// Store a recording with an existing transcript as a media frame plus one child frame per
+    /// segment.
+    ///
+    /// `options` describe the recording frame; `options.timestamp` is the recording start and
+    /// defaults to now. Segment frames inherit its track, tags, labels, and enrichment flags,
+    /// and carry their offsets under [`AUDIO_START_MS_KEY`] / [`AUDIO_END_MS_KEY`] and their
+    /// diarized speaker under [`SPEAKER_KEY`]. Call `commit` to persist.
+    pub fn put_transcribed_audio(
+        &mut self,
+        bytes: &[u8],
+        transcription: &TranscriptionResult,
+        options: PutOptions,
+    ) -> Result<AudioReceipt> {
+        self.ensure_mutation_allowed()?;
+        if bytes.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "audio payload is empty".into(),
+            });
+        }
+
+        let recorded_at = options.timestamp.unwrap_or_else(unix_now);
+        let duration_ms = seconds_to_ms(transcription.duration_secs);
+        let speakers = transcription.speakers();
+        let segments: Vec<AudioSegmentMetadata> = transcription
+            .segments
+            .iter()
+            .map(|segment| AudioSegmentMetadata {
+                start_seconds: segment.start,
+                end_seconds: segment.end,
+                label: segment.speaker.clone(),
+            })
+            .collect();
+
+        let mut metadata = options.metadata.clone().unwrap_or_default();
+        let (sniffed_mime, _) = sniff_audio(bytes);
+        let mime = metadata
+            .mime
+            .clone()
+            .unwrap_or_else(|| sniffed_mime.to_string());
+        metadata.mime = Some(mime.clone());
+        metadata.bytes = Some(bytes.len() as u64);
+        let audio = metadata.audio.get_or_insert_with(DocAudioMetadata::default);
+        audio.duration_secs = Some(transcription.duration_secs);
+        audio.segments.clone_from(&segments);
+        metadata.media = Some(MediaManifest {
+            kind: AUDIO_FRAME_KIND.to_string(),
+            mime,
+            bytes: bytes.len() as u64,
+            filename: options
+                .uri
+                .as_deref()
+                .and_then(|uri| uri.rsplit('/').next())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+            duration_ms: Some(duration_ms),
+            ..MediaManifest::default()
+        });
+
+        let mut parent_options = options.clone();
+        parent_options.timestamp = Some(recorded_at);
+        parent_options.kind = Some(AUDIO_FRAME_KIND.to_string());
+        parent_options.metadata = Some(metadata);
+        parent_options.search_text = Some(transcription.text.clone());
+        if !speakers.is_empty() {
+            parent_options
+                .extra_metadata
+                .insert(SPEAKER_KEY.to_string(), speakers.join(", "));
+        }
+        let parent_frame_id = self.next_frame_id();
+        let parent_sequence =
+            self.put_internal(Some(bytes), None, None, None, parent_options, None, None)?;
+        self.add_speaker_entities(parent_frame_id, &speakers);
+
+        let segment_sequences = self.put_transcript_segments(
+            transcription,
+            parent_sequence,
+            recorded_at,
+            &options,
+            &[],
+        )?;
+
+        Ok(AudioReceipt {
+            parent_sequence,
+            segment_sequences,
+            duration_ms,
+            speakers,
+        })
+    }
+
+    /// Write one child frame per non-empty transcript segment under `parent_sequence`.
+    ///
+    /// When `keyframes_ms` is non-empty, each segment also records the latest keyframe at or
+    /// before its start under [`VIDEO_KEYFRAME_MS_KEY`].
+    pub(crate) fn put_transcript_segments(
+        &mut self,
+        transcription: &TranscriptionResult,
+        parent_sequence: u64,
+        recorded_at: i64,
+        options: &PutOptions,
+        keyframes_ms: &[u64],
+    ) -> Result<Vec<u64>> {
+        let mut segment_sequences = Vec::with_capacity(transcription.segments.len());
+        for segment in &transcription.segments {
+            let text = segment.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let meta = AudioSegmentMetadata {
+                start_seconds: segment.start,
+                end_seconds: segment.end,
+                label: segment.speaker.clone(),
+            };
+            let start_ms = meta.start_ms();
+            let end_ms = meta.end_ms();
+            let mut segment_options = PutOptions {
+                timestamp: Some(
+                    recorded_at.saturating_add(i64::try_from(start_ms / 1000).unwrap_or(0)),
+                ),
+                track: options.track.clone(),
+                kind: Some(AUDIO_SEGMENT_FRAME_KIND.to_string()),
+                uri: options
+                    .uri
+                    .as_ref()
+                    .map(|uri| format!("{uri}#t={start_ms}")),
+                title: options.title.clone(),
+                metadata: Some(DocMetadata {
+                    mime: Some("text/plain".to_string()),
+                    audio: Some(DocAudioMetadata {
+                        segments: vec![meta],
+                        ..DocAudioMetadata::default()
+                    }),
+                    ..DocMetadata::default()
+                }),
+                tags: options.tags.clone(),
+                labels: options.labels.clone(),
+                extra_metadata: options.extra_metadata.clone(),
+                enable_embedding: options.enable_embedding,
+                auto_tag: options.auto_tag,
+                extract_dates: options.extract_dates,
+                extract_triplets: options.extract_triplets,
+                instant_index: options.instant_index,
+                ..PutOptions::default()
+            };
+            let extra = &mut segment_options.extra_metadata;
+            extra.insert(AUDIO_START_MS_KEY.to_string(), start_ms.to_string());
+            extra.insert(AUDIO_END_MS_KEY.to_string(), end_ms.to_string());
+            if let Some(speaker) = &segment.speaker {
+                extra.insert(SPEAKER_KEY.to_string(), speaker.clone());
+            }
+            if let Some(keyframe_ms) = keyframes_ms
+                .iter()
+                .copied()
+                .filter(|offset| *offset <= start_ms)
+                .max()
+            {
+                extra.insert(VIDEO_KEYFRAME_MS_KEY.to_string(), keyframe_ms.to_string());
+            }
+            let frame_id = self.next_frame_id();
+            segment_sequences.push(self.put_internal(
+                Some(text.as_bytes()),
+                None,
+                None,
+                None,
+                segment_options,
+                None,
+                Some(parent_sequence),
+            )?);
+            if let Some(speaker) = &segment.speaker {
+                self.add_speaker_entities(frame_id, std::slice::from_ref(speaker));
+            }
+        }
+        Ok(segment_sequences)
+    }
+}
+
+/// MIME type and decoder extension hint from the container's magic bytes.
+fn sniff_audio(bytes: &[u8]) -> (&'static str, Option<&'static str>) {
+    match bytes {
+        [
+            b'R',
+            b'I',
+            b'F',
+            b'F',
+            _,
+            _,
+            _,
+            _,
+            b'W',
+            b'A',
+            b'V',
+            b'E',
+            ..,
+        ] => ("audio/wav", Some("wav")),
+        [b'f', b'L', b'a', b'C', ..] => ("audio/flac", Some("flac")),
+        [b'O', b'g', b'g', b'S', ..] => ("audio/ogg", Some("ogg")),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => ("audio/mp4", Some("m4a")),
+        [b'I', b'D', b'3', ..] => ("audio/mpeg", Some("mp3")),
+        [0xFF, second, ..] if second & 0xE0 == 0xE0 => ("audio/mpeg", Some("mp3")),
+        _ => ("application/octet-stream", None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FrameRole;
+    use crate::whisper::TranscriptionSegment;
+
+    #[test]
+    fn transcribed_audio_lands_as_timestamped_segments() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("audio.mv2");
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        wav.extend_from_slice(&[0u8; 64]);
+        let transcription = TranscriptionResult {
+            text: "welcome to the standup. the deploy is blocked on review.".into(),
+            language: "en".into(),
+            duration_secs: 12.5,
+            segments: vec![
+                TranscriptionSegment {
+                    start: 0.0,
+                    end: 4.25,
+                    text: " welcome to the standup.".into(),
+                    speaker: Some("Speaker 1".into()),
+                },
+                TranscriptionSegment {
+                    start: 7.5,
+                    end: 12.5,
+                    text: " the deploy is blocked on review.".into(),
+                    speaker: Some("Speaker 2".into()),
+                },
+            ],
+        };
+
+        let mut mem = Memvid::create(&path).expect("create");
+        let options = PutOptions {
+            uri: Some("mv2://audio/standup.wav".into()),
+            timestamp: Some(1_700_000_000),
+            ..Default::default()
+        };
+        let receipt = mem
+            .put_transcribed_audio(&wav, &transcription, options)
+            .expect("put");
+        mem.commit().expect("commit");
+        assert_eq!(receipt.segment_sequences.len(), 2);
+        assert_eq!(receipt.duration_ms, 12_500);
+        assert_eq!(receipt.speakers, vec!["Speaker 1", "Speaker 2"]);
+
+        let manifest = mem
+            .media_manifest_by_uri("mv2://audio/standup.wav")
+            .expect("manifest lookup")
+            .expect("manifest");
+        assert_eq!(manifest.kind, AUDIO_FRAME_KIND);
+        assert_eq!(manifest.mime, "audio/wav");
+        assert_eq!(manifest.duration_ms, Some(12_500));
+        assert_eq!(manifest.filename.as_deref(), Some("standup.wav"));
+
+        let segment = mem
+            .frame_by_uri("mv2://audio/standup.wav#t=7500")
+            .expect("segment");
+        assert_eq!(segment.kind.as_deref(), Some(AUDIO_SEGMENT_FRAME_KIND));
+        assert_eq!(segment.timestamp, 1_700_000_007);
+        let parent = mem.frame_by_uri("mv2://audio/standup.wav").expect("parent");
+        assert_eq!(segment.parent_id, Some(parent.id));
+        assert_eq!(parent.role, FrameRole::Document);
+        assert_eq!(
+            segment
+                .extra_metadata
+                .get(AUDIO_END_MS_KEY)
+                .map(String::as_str),
+            Some("12500")
+        );
+        assert_eq!(
+            segment.extra_metadata.get(SPEAKER_KEY).map(String::as_str),
+            Some("Speaker 2")
+        );
+        let meta = segment.metadata.and_then(|meta| meta.audio).expect("audio");
+        assert_eq!(meta.segments[0].start_ms(), 7_500);
+    }
+}
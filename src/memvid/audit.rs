@@ -3,12 +3,18 @@
 //! This module provides the `audit` method on `Memvid` that generates
 //! structured audit reports showing all sources used to answer a question.
 
+use std::ops::Range;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::memvid::lifecycle::Memvid;
+use crate::types::FrameId;
 use crate::types::ask::{AskMode, AskRequest};
-use crate::types::audit::{AuditOptions, AuditReport, SourceSpan};
-use crate::{Result, VecEmbedder};
+use crate::types::audit::{
+    AuditOptions, AuditReport, CitationHighlight, PDF_SPANS_KEY, PdfTextSpan, SourceSpan,
+};
+#[cfg(feature = "lex")]
+use crate::types::{Frame, PDF_PAGE_KEY, PageRect};
+use crate::{MemvidError, Result, VecEmbedder};
 
 /// Default top-k for audit queries.
 const DEFAULT_AUDIT_TOP_K: usize = 10;
@@ -98,6 +104,10 @@ impl Memvid {
                 None
             };
 
+            let (page, rect) = frame_data
+                .as_ref()
+                .map(|frame| pdf_location(frame, citation.chunk_range))
+                .unwrap_or_default();
             let source = SourceSpan {
                 index: idx + 1,
                 frame_id: citation.frame_id,
@@ -119,6 +129,8 @@ impl Memvid {
                     .map(|f| f.content_dates.clone())
                     .unwrap_or_default(),
                 snippet,
+                page,
+                rect,
             };
 
             sources.push(source);
@@ -177,6 +189,10 @@ impl Memvid {
                 None
             };
 
+            let (page, rect) = frame_data
+                .as_ref()
+                .map(|frame| pdf_location(frame, citation.chunk_range))
+                .unwrap_or_default();
             let source = SourceSpan {
                 index: idx + 1,
                 frame_id: citation.frame_id,
@@ -198,6 +214,8 @@ impl Memvid {
                     .map(|f| f.content_dates.clone())
                     .unwrap_or_default(),
                 snippet,
+                page,
+                rect,
             };
 
             sources.push(source);
@@ -230,13 +248,80 @@ impl Memvid {
     }
 }
 
+impl Memvid {
+    /// Locate the byte `range` of a frame's text in the PDF it was extracted from: the page
+    /// the range starts on and, when the PDF reader recorded text positions, the rectangle
+    /// a viewer highlights.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the frame does not exist, was not extracted from a PDF, or `range` lies
+    /// outside the text located on its pages.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let highlight = mem.render_citation(citation.frame_id, start..end)?;
+    /// viewer.highlight(highlight.page, highlight.rect);
+    /// ```
+    pub fn render_citation(
+        &self,
+        frame_id: FrameId,
+        range: Range<usize>,
+    ) -> Result<CitationHighlight> {
+        let frame = self.frame_by_id(frame_id)?;
+        let spans = frame
+            .extra_metadata
+            .get(PDF_SPANS_KEY)
+            .map(|value| PdfTextSpan::decode_list(value))
+            .filter(|spans| !spans.is_empty())
+            .ok_or(MemvidError::InvalidFrame {
+                frame_id,
+                reason: "frame has no recorded PDF page positions",
+            })?;
+        CitationHighlight::from_spans(frame_id, &spans, range.clone()).ok_or_else(|| {
+            MemvidError::InvalidQuery {
+                reason: format!(
+                    "range {}..{} of frame {frame_id} is not on a recorded PDF page",
+                    range.start, range.end
+                ),
+            }
+        })
+    }
+}
+
+/// Page and highlight rectangle for a citation of `frame`; the frame's first page when the
+/// citation has no range.
+#[cfg(feature = "lex")]
+pub(crate) fn pdf_location(
+    frame: &Frame,
+    range: Option<(usize, usize)>,
+) -> (Option<u32>, Option<PageRect>) {
+    let highlight = range.and_then(|(start, end)| {
+        let spans = PdfTextSpan::decode_list(frame.extra_metadata.get(PDF_SPANS_KEY)?);
+        CitationHighlight::from_spans(frame.id, &spans, start..end)
+    });
+    match highlight {
+        Some(highlight) => (Some(highlight.page), highlight.rect),
+        None => (
+            frame
+                .extra_metadata
+                .get(PDF_PAGE_KEY)
+                .and_then(|page| page.parse().ok()),
+            None,
+        ),
+    }
+}
+
 // Convert AskMode to AskRetriever for comparison
 impl From<AskMode> for crate::AskRetriever {
     fn from(mode: AskMode) -> Self {
         match mode {
             AskMode::Lex => crate::AskRetriever::Lex,
             AskMode::Sem => crate::AskRetriever::Semantic,
-            AskMode::Hybrid => crate::AskRetriever::Hybrid,
+            AskMode::Hybrid | AskMode::Extractive | AskMode::Generative => {
+                crate::AskRetriever::Hybrid
+            }
         }
     }
 }
@@ -325,4 +410,133 @@ mod tests {
             }
         });
     }
+
+    /// A letter-size PDF with one Helvetica line of text per entry of each page.
+    fn pdf_with_pages(pages: &[&[&str]]) -> Vec<u8> {
+        use lopdf::content::{Content, Operation};
+        use lopdf::{Document, Object, Stream, dictionary};
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+        let mut kids: Vec<Object> = Vec::new();
+        for lines in pages {
+            let mut operations = vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                Operation::new("TL", vec![14.into()]),
+                Operation::new("Td", vec![72.into(), 720.into()]),
+            ];
+            for line in *lines {
+                operations.push(Operation::new("Tj", vec![Object::string_literal(*line)]));
+                operations.push(Operation::new("T*", vec![]));
+            }
+            operations.push(Operation::new("ET", vec![]));
+            let content = Content { operations }.encode().expect("encode content");
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content));
+            let page_id = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(page_id.into());
+        }
+        let count = i64::try_from(kids.len()).expect("page count");
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => count,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).expect("save pdf");
+        bytes
+    }
+
+    #[test]
+    fn pdf_chunks_record_pages_for_citations() {
+        run_serial_test(|| {
+            let dir = tempdir().expect("tmp");
+            let path = dir.path().join("citations.mv2");
+            let mut mem = Memvid::create(&path).expect("create");
+
+            let pdf = pdf_with_pages(&[
+                &[
+                    "Alpha section sets out the lease of the harbor warehouse.",
+                    "The tenant pays rent monthly in advance to the landlord.",
+                    "Rent reviews happen every third year of the lease term.",
+                ],
+                &[
+                    "Bravo clause caps the annual rent increase at four percent.",
+                    "Disputes about the review go to an independent surveyor.",
+                    "The surveyor decides within thirty days of appointment.",
+                ],
+                &[
+                    "Charlie schedule lists the fixtures the tenant may remove.",
+                    "Shelving racks and the loading crane stay with the building.",
+                    "Office furniture and computers leave with the tenant.",
+                ],
+            ]);
+            let options = PutOptions::builder()
+                .uri("mv2://contracts/lease.pdf")
+                .chunk_chars(120)
+                .build();
+            mem.put_bytes_with_options(&pdf, options).expect("put");
+            mem.commit().expect("commit");
+
+            let chunks: Vec<Frame> = mem
+                .toc
+                .frames
+                .iter()
+                .filter(|frame| frame.role == crate::FrameRole::DocumentChunk)
+                .cloned()
+                .collect();
+            assert!(chunks.len() > 1, "the PDF is chunked");
+            assert!(
+                chunks
+                    .iter()
+                    .all(|chunk| chunk.extra_metadata.contains_key(PDF_PAGE_KEY))
+            );
+
+            let mut found = false;
+            for chunk in &chunks {
+                let text = mem.frame_text_by_id(chunk.id).expect("text");
+                let Some(start) = text.find("Bravo clause") else {
+                    continue;
+                };
+                let highlight = mem
+                    .render_citation(chunk.id, start..start + "Bravo clause caps".len())
+                    .expect("highlight");
+                assert_eq!(highlight.page, 2);
+                assert_eq!(highlight.frame_id, chunk.id);
+                found = true;
+            }
+            assert!(found, "a chunk quotes page two");
+
+            let parent = mem
+                .toc
+                .frames
+                .iter()
+                .find(|frame| frame.role != crate::FrameRole::DocumentChunk)
+                .map(|frame| frame.id)
+                .expect("parent frame");
+            assert!(mem.render_citation(parent, 0..10).is_err());
+        });
+    }
 }
@@ -0,0 +1,382 @@
+//! Tamper-evident audit chain: recording and verification.
+//!
+//! Commits append entries for the frames they put, update, and delete after the WAL records
+//! are applied and before the TOC is rewritten, so an entry is durable exactly when its
+//! mutation is. Ticket changes append their entry before the ticket's own TOC rewrite.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    AUDIT_CHAIN_EXTENSION, AuditAction, AuditChain, AuditChainReport, AuditEntry, FrameId,
+};
+
+/// A mutation waiting to be chained.
+struct AuditMutation {
+    action: AuditAction,
+    frame_id: Option<FrameId>,
+    previous_frame_id: Option<FrameId>,
+    content_hash: Option<[u8; 32]>,
+    detail: Option<String>,
+}
+
+impl Memvid {
+    /// Start recording mutations in the audit chain. Commits from then on append an entry
+    /// for every frame put, updated, or deleted, and ticket changes append one each. Enabling
+    /// an enabled chain does nothing; a chain cannot be disabled.
+    pub fn enable_audit_chain(&mut self) -> Result<()> {
+        self.ensure_writable()?;
+        if self.audit_chain_enabled() {
+            return Ok(());
+        }
+        self.toc
+            .set_extension(AUDIT_CHAIN_EXTENSION, &AuditChain::default())?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Whether mutations are recorded in the audit chain.
+    #[must_use]
+    pub fn audit_chain_enabled(&self) -> bool {
+        self.toc.extensions.contains_key(AUDIT_CHAIN_EXTENSION)
+    }
+
+    /// Sign entries appended through this handle with `key`. The key is held in memory only.
+    ///
+    /// Fails if the chain already has entries signed with a different key.
+    pub fn set_audit_signing_key(&mut self, key: SigningKey) -> Result<()> {
+        if let Some(signer) = self.audit_chain()?.and_then(|chain| chain.signer) {
+            if signer != key.verifying_key().to_bytes() {
+                return Err(MemvidError::InvalidConfig {
+                    reason: "audit chain is signed with a different key".to_string(),
+                });
+            }
+        }
+        self.audit_signer = Some(key);
+        Ok(())
+    }
+
+    /// Every entry of the audit chain, oldest first; empty when it is not enabled.
+    pub fn audit_entries(&self) -> Result<Vec<AuditEntry>> {
+        Ok(self
+            .audit_chain()?
+            .map(|chain| chain.entries)
+            .unwrap_or_default())
+    }
+
+    /// Check that every entry links to its predecessor, hashes to its recorded hash, and,
+    /// where signed, carries a valid signature from the chain's signer.
+    ///
+    /// # Errors
+    ///
+    /// [`MemvidError::AuditChainBroken`] names the first entry that fails, and
+    /// [`MemvidError::InvalidConfig`] is returned when the chain is not enabled.
+    pub fn verify_audit_chain(&self) -> Result<AuditChainReport> {
+        let chain = self
+            .audit_chain()?
+            .ok_or_else(|| MemvidError::InvalidConfig {
+                reason: "audit chain is not enabled".to_string(),
+            })?;
+        let signer = chain
+            .signer
+            .map(|bytes| {
+                VerifyingKey::from_bytes(&bytes).map_err(|err| MemvidError::AuditChainBroken {
+                    seq: 0,
+                    reason: format!("invalid signer key: {err}"),
+                })
+            })
+            .transpose()?;
+
+        let mut prev_hash = [0; 32];
+        let mut signed = 0;
+        for (index, entry) in chain.entries.iter().enumerate() {
+            let broken = |reason: &str| MemvidError::AuditChainBroken {
+                seq: entry.seq,
+                reason: reason.to_string(),
+            };
+            if entry.seq != index as u64 {
+                return Err(broken("entry is out of sequence"));
+            }
+            if entry.prev_hash != prev_hash {
+                return Err(broken("entry does not link to the previous entry"));
+            }
+            if entry.compute_hash() != entry.hash {
+                return Err(broken("entry does not match its hash"));
+            }
+            if let Some(bytes) = &entry.signature {
+                let key = signer
+                    .as_ref()
+                    .ok_or_else(|| broken("entry is signed but the chain has no signer"))?;
+                let signature =
+                    Signature::from_slice(bytes).map_err(|_| broken("signature is malformed"))?;
+                key.verify(&entry.hash, &signature)
+                    .map_err(|_| broken("signature does not verify"))?;
+                signed += 1;
+            }
+            prev_hash = entry.hash;
+        }
+
+        Ok(AuditChainReport {
+            entries: chain.entries.len(),
+            signed,
+            unsigned: chain.entries.len() - signed,
+            head: chain
+                .entries
+                .last()
+                .map(|entry| blake3::Hash::from(entry.hash).to_hex().to_string()),
+            signer: chain.signer.map(|key| BASE64_STANDARD.encode(key)),
+        })
+    }
+
+    fn audit_chain(&self) -> Result<Option<AuditChain>> {
+        self.toc.extension::<AuditChain>(AUDIT_CHAIN_EXTENSION)
+    }
+
+    /// Chain the frame mutations of the commit in progress: an update for each inserted
+    /// frame that supersedes another, a put for every other inserted frame, and a delete for
+    /// each tombstoned frame.
+    ///
+    /// Must run after WAL records are applied and the generation is bumped, and before the
+    /// commit's provenance is recorded and the TOC is rewritten.
+    pub(crate) fn record_audit_mutations(
+        &mut self,
+        inserted_frames: &[FrameId],
+        tombstoned_frames: &[FrameId],
+    ) -> Result<()> {
+        if !self.audit_chain_enabled() {
+            return Ok(());
+        }
+        let mut mutations = Vec::with_capacity(inserted_frames.len() + tombstoned_frames.len());
+        for &frame_id in inserted_frames {
+            let frame = self.frame_by_id(frame_id)?;
+            mutations.push(AuditMutation {
+                action: if frame.supersedes.is_some() {
+                    AuditAction::Update
+                } else {
+                    AuditAction::Put
+                },
+                frame_id: Some(frame_id),
+                previous_frame_id: frame.supersedes,
+                content_hash: Some(frame.checksum),
+                detail: None,
+            });
+        }
+        mutations.extend(tombstoned_frames.iter().map(|&frame_id| AuditMutation {
+            action: AuditAction::Delete,
+            frame_id: Some(frame_id),
+            previous_frame_id: None,
+            content_hash: None,
+            detail: None,
+        }));
+        self.append_audit_entries(self.generation, mutations)
+    }
+
+    /// Chain the ticket now in the TOC, about to be written in `generation`.
+    pub(crate) fn record_audit_ticket(&mut self, generation: u64) -> Result<()> {
+        if !self.audit_chain_enabled() {
+            return Ok(());
+        }
+        let ticket = &self.toc.ticket_ref;
+        let detail = format!(
+            "{} seq {}{}",
+            ticket.issuer,
+            ticket.seq_no,
+            if ticket.verified { " (verified)" } else { "" }
+        );
+        self.append_audit_entries(
+            generation,
+            vec![AuditMutation {
+                action: AuditAction::Ticket,
+                frame_id: None,
+                previous_frame_id: None,
+                content_hash: None,
+                detail: Some(detail),
+            }],
+        )
+    }
+
+    fn append_audit_entries(
+        &mut self,
+        generation: u64,
+        mutations: Vec<AuditMutation>,
+    ) -> Result<()> {
+        if mutations.is_empty() {
+            return Ok(());
+        }
+        let mut chain = self.audit_chain()?.unwrap_or_default();
+        if let Some(key) = &self.audit_signer {
+            let public = key.verifying_key().to_bytes();
+            match chain.signer {
+                Some(signer) if signer != public => {
+                    return Err(MemvidError::InvalidConfig {
+                        reason: "audit chain is signed with a different key".to_string(),
+                    });
+                }
+                Some(_) => {}
+                None => chain.signer = Some(public),
+            }
+        }
+        let author = self
+            .pending_commit_metadata
+            .as_ref()
+            .unwrap_or(&self.commit_identity)
+            .author
+            .clone();
+        let recorded_at = unix_now();
+        for mutation in mutations {
+            let mut entry = AuditEntry {
+                seq: chain.entries.len() as u64,
+                generation,
+                recorded_at,
+                action: mutation.action,
+                frame_id: mutation.frame_id,
+                previous_frame_id: mutation.previous_frame_id,
+                content_hash: mutation.content_hash,
+                detail: mutation.detail,
+                author: author.clone(),
+                prev_hash: chain.head(),
+                hash: [0; 32],
+                signature: None,
+            };
+            entry.hash = entry.compute_hash();
+            entry.signature = self
+                .audit_signer
+                .as_ref()
+                .map(|key| key.sign(&entry.hash).to_bytes().to_vec());
+            chain.entries.push(entry);
+        }
+        self.toc.set_extension(AUDIT_CHAIN_EXTENSION, &chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PutOptions;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    #[test]
+    fn mutations_form_a_signed_chain_that_survives_reopen() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("audit.mv2");
+
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_audit_chain().expect("enable");
+        mem.set_audit_signing_key(signing_key()).expect("key");
+        mem.set_commit_author(Some("alice".to_string()), None);
+        mem.put_bytes(b"first").expect("put");
+        mem.put_bytes(b"second").expect("put");
+        mem.commit().expect("commit");
+        mem.update_frame(
+            0,
+            Some(b"first, revised".to_vec()),
+            PutOptions::default(),
+            None,
+        )
+        .expect("update");
+        mem.delete_frame(1).expect("delete");
+        mem.commit().expect("commit");
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).expect("open");
+        let actions: Vec<(AuditAction, Option<FrameId>)> = mem
+            .audit_entries()
+            .expect("entries")
+            .iter()
+            .map(|entry| (entry.action, entry.frame_id))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                (AuditAction::Put, Some(0)),
+                (AuditAction::Put, Some(1)),
+                (AuditAction::Update, Some(2)),
+                (AuditAction::Delete, Some(1)),
+            ]
+        );
+        let entries = mem.audit_entries().expect("entries");
+        assert_eq!(entries[2].previous_frame_id, Some(0));
+        assert_eq!(entries[0].author.as_deref(), Some("alice"));
+
+        let report = mem.verify_audit_chain().expect("verify");
+        assert_eq!((report.entries, report.signed, report.unsigned), (4, 4, 0));
+        assert_eq!(
+            report.signer,
+            Some(BASE64_STANDARD.encode(signing_key().verifying_key().to_bytes()))
+        );
+
+        // Entries written without the key still chain, and are reported as unsigned.
+        mem.put_bytes(b"third").expect("put");
+        mem.commit().expect("commit");
+        let report = mem.verify_audit_chain().expect("verify");
+        assert_eq!((report.entries, report.unsigned), (5, 1));
+
+        assert!(
+            mem.set_audit_signing_key(SigningKey::from_bytes(&[9u8; 32]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn edited_entries_break_the_chain() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("tamper.mv2");
+
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_audit_chain().expect("enable");
+        mem.set_audit_signing_key(signing_key()).expect("key");
+        for payload in [b"one".as_slice(), b"two", b"three"] {
+            mem.put_bytes(payload).expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let mut chain = mem.audit_chain().expect("chain").expect("enabled");
+        chain.entries[1].content_hash = Some([0; 32]);
+        mem.toc
+            .set_extension(AUDIT_CHAIN_EXTENSION, &chain)
+            .expect("set");
+        match mem.verify_audit_chain() {
+            Err(MemvidError::AuditChainBroken { seq, .. }) => assert_eq!(seq, 1),
+            other => panic!("expected a broken chain, got {other:?}"),
+        }
+
+        // Recomputing the hash is not enough without the signing key.
+        chain.entries[1].hash = chain.entries[1].compute_hash();
+        chain.entries[2].prev_hash = chain.entries[1].hash;
+        chain.entries[2].hash = chain.entries[2].compute_hash();
+        mem.toc
+            .set_extension(AUDIT_CHAIN_EXTENSION, &chain)
+            .expect("set");
+        assert!(matches!(
+            mem.verify_audit_chain(),
+            Err(MemvidError::AuditChainBroken { seq: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn ticket_changes_are_chained() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("ticket.mv2");
+
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_audit_chain().expect("enable");
+        mem.commit().expect("commit");
+        #[allow(deprecated)]
+        mem.apply_ticket(crate::types::Ticket::new("issuer", 2))
+            .expect("ticket");
+
+        let entries = mem.audit_entries().expect("entries");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Ticket);
+        assert_eq!(entries[0].generation, mem.generation());
+        assert_eq!(entries[0].detail.as_deref(), Some("issuer seq 2"));
+        mem.verify_audit_chain().expect("verify");
+    }
+}
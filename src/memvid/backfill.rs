@@ -0,0 +1,289 @@
+//! Embedding backfill for frames ingested before an embedder was configured.
+//!
+//! Each batch is embedded, stamped with the provider's embedding identity, and committed before
+//! the next one starts, so an interrupted backfill keeps the work already done and a rerun
+//! picks up where it stopped.
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    BackfillReport, EmbeddingIdentity, EmbeddingProvider, FrameId, FrameRole, FrameStatus,
+};
+
+impl Memvid {
+    /// Embed every active frame that has no vector yet and add it to the vec index.
+    ///
+    /// See [`Memvid::backfill_embeddings_with_progress`].
+    pub fn backfill_embeddings(
+        &mut self,
+        provider: &dyn EmbeddingProvider,
+        batch: usize,
+    ) -> Result<BackfillReport> {
+        self.backfill_embeddings_with_progress(provider, batch, |_| {})
+    }
+
+    /// Embed every active frame that has no vector yet, committing `batch` frames at a time and
+    /// calling `on_progress` after each commit.
+    ///
+    /// Pending changes are committed first. Frames whose recorded embedding identity names a
+    /// different provider, model, or dimension are left alone. Fails with
+    /// [`MemvidError::ModelMismatch`] or [`MemvidError::VecDimensionMismatch`] if the vec index
+    /// is already bound to another model or dimension, or already holds vectors from another
+    /// provider.
+    pub fn backfill_embeddings_with_progress<F>(
+        &mut self,
+        provider: &dyn EmbeddingProvider,
+        batch: usize,
+        mut on_progress: F,
+    ) -> Result<BackfillReport>
+    where
+        F: FnMut(&BackfillReport),
+    {
+        self.ensure_writable()?;
+        let dimension = provider.dimension();
+        if let Some(existing) = self.effective_vec_index_dimension()? {
+            if existing as usize != dimension {
+                return Err(MemvidError::VecDimensionMismatch {
+                    expected: existing,
+                    actual: dimension,
+                });
+            }
+        }
+        self.set_vec_model(provider.model())?;
+        if !self.vec_enabled {
+            self.enable_vec()?;
+        }
+        self.commit()?;
+        self.ensure_vec_index()?;
+
+        let target = EmbeddingIdentity::of_provider(provider);
+        self.ensure_compatible_vectors(&target)?;
+        let mut report = BackfillReport::default();
+        let mut candidates: Vec<FrameId> = Vec::new();
+        for frame in &self.toc.frames {
+            if frame.status != FrameStatus::Active
+                || frame.role == FrameRole::ExtractedImage
+                || self
+                    .vec_index
+                    .as_ref()
+                    .is_some_and(|index| index.contains(frame.id))
+            {
+                continue;
+            }
+            report.candidates += 1;
+            match EmbeddingIdentity::from_extra_metadata(&frame.extra_metadata) {
+                Some(identity) if !identity.is_compatible_with(&target) => {
+                    report.skipped_identity += 1;
+                }
+                _ => candidates.push(frame.id),
+            }
+        }
+
+        for ids in candidates.chunks(batch.max(1)) {
+            let mut frame_ids = Vec::with_capacity(ids.len());
+            let mut texts = Vec::with_capacity(ids.len());
+            for &frame_id in ids {
+                let frame = self.frame_by_id(frame_id)?;
+                let text = self.frame_search_text(&frame)?;
+                if text.trim().is_empty() {
+                    report.skipped_empty += 1;
+                } else {
+                    frame_ids.push(frame_id);
+                    texts.push(text);
+                }
+            }
+            if !frame_ids.is_empty() {
+                let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+                let embeddings = provider.embed_batch(&refs)?;
+                if embeddings.len() != frame_ids.len() {
+                    return Err(MemvidError::EmbeddingFailed {
+                        reason: format!(
+                            "provider returned {} embeddings for {} texts",
+                            embeddings.len(),
+                            frame_ids.len()
+                        )
+                        .into(),
+                    });
+                }
+                for (&frame_id, embedding) in frame_ids.iter().zip(embeddings) {
+                    if embedding.len() != dimension {
+                        return Err(MemvidError::VecDimensionMismatch {
+                            expected: u32::try_from(dimension).unwrap_or(u32::MAX),
+                            actual: embedding.len(),
+                        });
+                    }
+                    self.stamp_embedding_identity(frame_id, &target);
+                    self.pending_embeddings.push((frame_id, embedding));
+                }
+                self.dirty = true;
+                self.commit()?;
+                report.embedded += frame_ids.len();
+            }
+            report.batches += 1;
+            tracing::debug!(
+                embedded = report.embedded,
+                remaining = report.remaining(),
+                "embedding backfill batch committed"
+            );
+            on_progress(&report);
+        }
+        Ok(report)
+    }
+
+    /// Fail with [`MemvidError::ModelMismatch`] if a frame already in the vec index was
+    /// embedded by a provider, model, or dimension other than `target`. Vectors from two
+    /// providers can share a model name and dimension yet live in different spaces.
+    pub(crate) fn ensure_compatible_vectors(&self, target: &EmbeddingIdentity) -> Result<()> {
+        let Some(index) = self.vec_index.as_ref() else {
+            return Ok(());
+        };
+        for frame in &self.toc.frames {
+            if frame.status != FrameStatus::Active || !index.contains(frame.id) {
+                continue;
+            }
+            if let Some(identity) = EmbeddingIdentity::from_extra_metadata(&frame.extra_metadata) {
+                if !identity.is_compatible_with(target) {
+                    return Err(MemvidError::ModelMismatch {
+                        expected: identity.to_string(),
+                        actual: target.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn stamp_embedding_identity(
+        &mut self,
+        frame_id: FrameId,
+        identity: &EmbeddingIdentity,
+    ) {
+        if let Some(frame) = usize::try_from(frame_id)
+            .ok()
+            .and_then(|index| self.toc.frames.get_mut(index))
+        {
+            identity.write_extra_metadata(&mut frame.extra_metadata);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MEMVID_EMBEDDING_MODEL_KEY, PutOptions};
+
+    struct LengthProvider;
+
+    impl EmbeddingProvider for LengthProvider {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn kind(&self) -> &str {
+            "mock"
+        }
+
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn model(&self) -> &str {
+            "length-v1"
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+            Ok(vec![text.len() as f32, 1.0, 0.0, 0.5])
+        }
+    }
+
+    #[test]
+    fn backfill_embeds_frames_without_vectors() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("backfill.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for text in ["alpha notes", "beta notes", "gamma notes"] {
+            mem.put_bytes(text.as_bytes()).expect("put");
+        }
+        let mut foreign = PutOptions::default();
+        foreign
+            .extra_metadata
+            .insert(MEMVID_EMBEDDING_MODEL_KEY.into(), "other-model".into());
+        mem.put_bytes_with_options(b"delta notes", foreign)
+            .expect("put");
+        mem.commit().expect("commit");
+
+        let mut seen = Vec::new();
+        let report = mem
+            .backfill_embeddings_with_progress(&LengthProvider, 2, |progress| {
+                seen.push(progress.embedded);
+            })
+            .expect("backfill");
+        assert_eq!(report.candidates, 4);
+        assert_eq!(report.embedded, 3);
+        assert_eq!(report.skipped_identity, 1);
+        assert_eq!(report.remaining(), 0);
+        assert_eq!(seen, vec![2, 3]);
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        assert_eq!(mem.vector_count(), 3);
+        let embedding = mem.frame_embedding(1).expect("embedding").expect("vector");
+        assert_eq!(embedding.len(), 4);
+        assert!(mem.frame_embedding(3).expect("embedding").is_none());
+        assert_eq!(
+            mem.frame_by_id(0)
+                .expect("frame")
+                .extra_metadata
+                .get(MEMVID_EMBEDDING_MODEL_KEY)
+                .map(String::as_str),
+            Some("length-v1")
+        );
+
+        let again = mem.backfill_embeddings(&LengthProvider, 2).expect("rerun");
+        assert_eq!(again.embedded, 0);
+        assert_eq!(again.candidates, 1);
+    }
+
+    /// Same model name and dimension as `LengthProvider`, served by another provider.
+    struct OtherHostProvider;
+
+    impl EmbeddingProvider for OtherHostProvider {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn kind(&self) -> &str {
+            "ollama"
+        }
+
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn model(&self) -> &str {
+            "length-v1"
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+
+        fn embed_text(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![0.0, 1.0, 0.0, 0.5])
+        }
+    }
+
+    #[test]
+    fn backfill_refuses_vectors_from_another_provider() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("mixed.mv2")).expect("create");
+        mem.put_bytes(b"alpha notes").expect("put");
+        mem.commit().expect("commit");
+        mem.backfill_embeddings(&LengthProvider, 8)
+            .expect("backfill");
+
+        mem.put_bytes(b"beta notes").expect("put");
+        let err = mem
+            .backfill_embeddings(&OtherHostProvider, 8)
+            .expect_err("mixed providers");
+        assert!(
+            matches!(&err, MemvidError::ModelMismatch { expected, actual }
+                if expected == "mock/length-v1 (4d)" && actual == "ollama/length-v1 (4d)"),
+            "{err:?}"
+        );
+        assert_eq!(mem.vector_count(), 1);
+    }
+}
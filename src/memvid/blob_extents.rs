@@ -0,0 +1,150 @@
+//! Writing, reading, and compacting chunked blob storage (see [`crate::types::blob_extents`]).
+
+use std::collections::BTreeSet;
+use std::collections::btree_map::Entry;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use blake3::hash;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::blob_extents::frame_blob_extents;
+use crate::types::{
+    BLOB_EXTENT_EXTENSION, BlobExtent, BlobExtentOptions, BlobExtentStats, BlobExtentStore, Frame,
+    FrameId, FrameStatus,
+};
+
+impl Memvid {
+    /// Store payloads of at least `options.min_blob_bytes` as shared, content-addressed
+    /// extents from the next commit on, or stop doing so with `None`.
+    ///
+    /// The setting is saved in the file. Disabling it keeps existing extents readable.
+    pub fn set_blob_extents(&mut self, options: Option<BlobExtentOptions>) -> Result<()> {
+        self.ensure_writable()?;
+        let mut store = self.blob_extent_store()?.unwrap_or_default();
+        store.rebase(&self.header);
+        store.options = options;
+        self.toc.set_extension(BLOB_EXTENT_EXTENSION, &store)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Chunked storage settings saved in the file, if enabled.
+    pub fn blob_extent_options(&self) -> Result<Option<BlobExtentOptions>> {
+        Ok(self.blob_extent_store()?.and_then(|store| store.options))
+    }
+
+    /// How many frames and bytes are stored as extents, and how much sharing saved.
+    pub fn blob_extent_stats(&self) -> Result<BlobExtentStats> {
+        Ok(self
+            .blob_extent_store()?
+            .map(|store| store.stats())
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn blob_extent_store(&self) -> Result<Option<BlobExtentStore>> {
+        self.toc.extension(BLOB_EXTENT_EXTENSION)
+    }
+
+    pub(crate) fn frame_blob_extents(&self, frame: &Frame) -> Result<Option<Vec<BlobExtent>>> {
+        frame_blob_extents(&self.toc, &self.header, frame)
+    }
+
+    /// Concatenated bytes of `extents`.
+    pub(crate) fn read_blob_extents(&self, extents: &[BlobExtent]) -> Result<Vec<u8>> {
+        let total: u64 = extents.iter().map(|extent| extent.length).sum();
+        // Safe: extent tables only describe payloads that were held in memory when written
+        #[allow(clippy::cast_possible_truncation)]
+        let mut buffer = Vec::with_capacity(total as usize);
+        let mut file = &*self.file;
+        for extent in extents {
+            file.seek(SeekFrom::Start(extent.offset))?;
+            file.take(extent.length).read_to_end(&mut buffer)?;
+        }
+        if buffer.len() as u64 != total {
+            return Err(MemvidError::InvalidToc {
+                reason: "blob extent exceeds file length".into(),
+            });
+        }
+        Ok(buffer)
+    }
+
+    /// Split `payload` into extents, writing those not yet in `store` at `cursor`, and record
+    /// the extent table of `frame_id`. Returns the new cursor.
+    pub(crate) fn write_blob_extents(
+        &mut self,
+        store: &mut BlobExtentStore,
+        frame_id: FrameId,
+        payload: &[u8],
+        mut cursor: u64,
+    ) -> Result<u64> {
+        let extent_size = store
+            .options
+            .map_or(payload.len() as u64, |options| options.extent_size)
+            .max(1);
+        // Safe: extent sizes above the address space just mean a single extent
+        let chunk_len = usize::try_from(extent_size).unwrap_or(usize::MAX);
+        let mut table = Vec::new();
+        for chunk in payload.chunks(chunk_len) {
+            let digest = *hash(chunk).as_bytes();
+            if let Entry::Vacant(slot) = store.extents.entry(digest) {
+                self.file.seek(SeekFrom::Start(cursor))?;
+                self.file.write_all(chunk)?;
+                slot.insert(BlobExtent {
+                    offset: cursor,
+                    length: chunk.len() as u64,
+                });
+                cursor += chunk.len() as u64;
+            }
+            table.push(digest);
+        }
+        store.tables.insert(frame_id, table);
+        Ok(cursor)
+    }
+
+    /// Move the extents still used by active frames to the start of the data region, one
+    /// extent at a time and in file order, dropping the rest. Returns the end of the moved
+    /// extents.
+    ///
+    /// Called by vacuum after every contiguous payload has been read into memory, so only
+    /// other extents can live in the bytes being overwritten; moving in ascending order never
+    /// overwrites one that has not been moved yet.
+    pub(crate) fn compact_blob_extents(&mut self) -> Result<u64> {
+        let data_start = self.header.data_start;
+        let Some(mut store) = self.blob_extent_store()? else {
+            return Ok(data_start);
+        };
+        store.rebase(&self.header);
+        let live: BTreeSet<FrameId> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .map(|frame| frame.id)
+            .collect();
+        store.retain_frames(&live);
+
+        let mut order: Vec<[u8; 32]> = store.extents.keys().copied().collect();
+        order.sort_by_key(|digest| store.extents[digest].offset);
+        let mut cursor = data_start;
+        let mut buffer = Vec::new();
+        for digest in order {
+            let Some(extent) = store.extents.get_mut(&digest) else {
+                continue;
+            };
+            if extent.offset != cursor {
+                buffer.clear();
+                self.file.seek(SeekFrom::Start(extent.offset))?;
+                (&mut self.file)
+                    .take(extent.length)
+                    .read_to_end(&mut buffer)?;
+                self.file.seek(SeekFrom::Start(cursor))?;
+                self.file.write_all(&buffer)?;
+                extent.offset = cursor;
+            }
+            cursor += extent.length;
+        }
+        self.toc.set_extension(BLOB_EXTENT_EXTENSION, &store)?;
+        Ok(cursor)
+    }
+}
@@ -10,26 +10,31 @@
 //! Tables are split between rows with header propagation to ensure each chunk
 //! maintains context about the table structure.
 
+use std::collections::BTreeMap;
+
 use crate::{
     normalize_text,
     structure::{ChunkingOptions, StructuralChunker, detect_structure},
-    types::{TextChunkManifest, TextChunkRange},
+    types::{CODE_SYMBOLS_KEY, CodeSymbol, TextChunkManifest, TextChunkRange},
 };
 
 pub(crate) const DEFAULT_CHUNK_CHARS: usize = 1_200;
-pub(crate) const CHUNK_MIN_CHARS: usize = DEFAULT_CHUNK_CHARS * 2;
 
 #[derive(Debug, Clone)]
 pub(crate) struct DocumentChunkPlan {
     pub manifest: TextChunkManifest,
     pub chunks: Vec<String>,
+    /// Extra metadata for each chunk frame, parallel to `chunks`; empty when none.
+    pub chunk_metadata: Vec<BTreeMap<String, String>>,
 }
 
-pub(crate) fn plan_document_chunks(raw: &[u8]) -> Option<DocumentChunkPlan> {
+/// Plan chunks of about `chunk_chars` characters from a UTF-8 payload; text shorter than
+/// two chunks is not split.
+pub(crate) fn plan_document_chunks(raw: &[u8], chunk_chars: usize) -> Option<DocumentChunkPlan> {
     let Ok(text) = String::from_utf8(raw.to_vec()) else {
         return None;
     };
-    plan_text_chunks(&text)
+    plan_text_chunks(&text, chunk_chars)
 }
 
 /// Plan chunks from already-extracted text (e.g., from PDF extraction).
@@ -40,10 +45,10 @@ pub(crate) fn plan_document_chunks(raw: &[u8]) -> Option<DocumentChunkPlan> {
 /// - Tables are split between rows (not mid-row)
 /// - Table headers are propagated to continuation chunks
 /// - Code blocks are kept whole when possible
-pub(crate) fn plan_text_chunks(text: &str) -> Option<DocumentChunkPlan> {
+pub(crate) fn plan_text_chunks(text: &str, chunk_chars: usize) -> Option<DocumentChunkPlan> {
     let normalized = normalize_text(text, usize::MAX)?.text;
 
-    if normalized.chars().count() < CHUNK_MIN_CHARS {
+    if normalized.chars().count() < chunk_chars.saturating_mul(2) {
         return None;
     }
 
@@ -52,20 +57,62 @@ pub(crate) fn plan_text_chunks(text: &str) -> Option<DocumentChunkPlan> {
 
     if doc.has_structure() {
         // Use structure-aware chunking for documents with tables/code
-        plan_structural_chunks(&normalized, &doc)
+        plan_structural_chunks(&normalized, &doc, chunk_chars)
     } else {
         // Fall back to naive chunking for plain text (faster)
-        plan_naive_chunks(&normalized)
+        plan_naive_chunks(&normalized, chunk_chars)
     }
 }
 
+/// Plan chunks of a source file in `language` with `StructuralChunker::chunk_code`. Each
+/// chunk is a fenced code block naming the language, and chunks that define symbols carry
+/// them under [`CODE_SYMBOLS_KEY`]. The text is kept as written, indentation included, apart
+/// from line endings; the manifest ranges cover each chunk's code lines. Text shorter than
+/// two chunks is not split.
+pub(crate) fn plan_code_chunks(
+    text: &str,
+    language: &str,
+    chunk_chars: usize,
+) -> Option<DocumentChunkPlan> {
+    let text = text.replace("\r\n", "\n");
+    if chunk_chars == 0 || text.chars().count() < chunk_chars.saturating_mul(2) {
+        return None;
+    }
+
+    let result = StructuralChunker::with_max_chars(chunk_chars).chunk_code(&text, language);
+    if result.chunks.len() <= 1 {
+        return None;
+    }
+
+    let manifest = build_manifest_from_structural(&result.chunks, &text, chunk_chars);
+    let chunk_metadata = result
+        .chunks
+        .iter()
+        .map(|chunk| {
+            let mut metadata = BTreeMap::new();
+            if !chunk.symbols.is_empty() {
+                let symbols: Vec<String> = chunk.symbols.iter().map(CodeSymbol::label).collect();
+                metadata.insert(CODE_SYMBOLS_KEY.to_string(), symbols.join(", "));
+            }
+            metadata
+        })
+        .collect();
+    let chunks = result.chunks.into_iter().map(|chunk| chunk.text).collect();
+    Some(DocumentChunkPlan {
+        manifest,
+        chunks,
+        chunk_metadata,
+    })
+}
+
 /// Structure-aware chunking that preserves tables and code blocks.
 fn plan_structural_chunks(
     text: &str,
     doc: &crate::structure::StructuredDocument,
+    chunk_chars: usize,
 ) -> Option<DocumentChunkPlan> {
     let options = ChunkingOptions {
-        max_chars: DEFAULT_CHUNK_CHARS,
+        max_chars: chunk_chars,
         ..Default::default()
     };
 
@@ -80,15 +127,20 @@ fn plan_structural_chunks(
     let chunks: Vec<String> = result.chunks.iter().map(|c| c.text.clone()).collect();
 
     // Build manifest with accurate character ranges
-    let manifest = build_manifest_from_structural(&result.chunks, text);
+    let manifest = build_manifest_from_structural(&result.chunks, text, chunk_chars);
 
-    Some(DocumentChunkPlan { manifest, chunks })
+    Some(DocumentChunkPlan {
+        manifest,
+        chunks,
+        chunk_metadata: Vec::new(),
+    })
 }
 
 /// Build `TextChunkManifest` from structural chunks.
 fn build_manifest_from_structural(
     chunks: &[crate::structure::StructuredChunk],
     text: &str,
+    chunk_chars: usize,
 ) -> TextChunkManifest {
     let chunk_ranges: Vec<TextChunkRange> = chunks
         .iter()
@@ -102,14 +154,14 @@ fn build_manifest_from_structural(
         .collect();
 
     TextChunkManifest {
-        chunk_chars: DEFAULT_CHUNK_CHARS,
+        chunk_chars,
         chunks: chunk_ranges,
     }
 }
 
 /// Naive character-based chunking (original implementation).
-fn plan_naive_chunks(text: &str) -> Option<DocumentChunkPlan> {
-    let manifest = build_chunk_manifest(text, DEFAULT_CHUNK_CHARS)?;
+fn plan_naive_chunks(text: &str, chunk_chars: usize) -> Option<DocumentChunkPlan> {
+    let manifest = build_chunk_manifest(text, chunk_chars)?;
     if manifest.chunks.len() <= 1 {
         return None;
     }
@@ -118,7 +170,11 @@ fn plan_naive_chunks(text: &str) -> Option<DocumentChunkPlan> {
         .iter()
         .map(|range| slice_text_range(text, range))
         .collect();
-    Some(DocumentChunkPlan { manifest, chunks })
+    Some(DocumentChunkPlan {
+        manifest,
+        chunks,
+        chunk_metadata: Vec::new(),
+    })
 }
 
 fn build_chunk_manifest(text: &str, chunk_chars: usize) -> Option<TextChunkManifest> {
@@ -238,7 +294,7 @@ mod tests {
     #[test]
     fn splits_long_text_into_chunks() {
         let text = "Lorem ipsum dolor sit amet. ".repeat(200);
-        let plan = plan_document_chunks(text.as_bytes()).expect("chunk plan");
+        let plan = plan_document_chunks(text.as_bytes(), DEFAULT_CHUNK_CHARS).expect("chunk plan");
         assert!(plan.manifest.chunks.len() > 1);
         assert_eq!(plan.manifest.chunk_chars, DEFAULT_CHUNK_CHARS);
         assert_eq!(plan.chunks.len(), plan.manifest.chunks.len());
@@ -263,7 +319,7 @@ mod tests {
         }
         text.push_str("\n\nThis is the conclusion.\n");
 
-        let plan = plan_document_chunks(text.as_bytes()).expect("chunk plan");
+        let plan = plan_document_chunks(text.as_bytes(), DEFAULT_CHUNK_CHARS).expect("chunk plan");
 
         // Should have multiple chunks
         assert!(
@@ -310,7 +366,7 @@ Conclusion.
 "
         .repeat(50); // Repeat to meet minimum size
 
-        let plan = plan_document_chunks(text.as_bytes()).expect("chunk plan");
+        let plan = plan_document_chunks(text.as_bytes(), DEFAULT_CHUNK_CHARS).expect("chunk plan");
 
         // Small tables should not be split mid-row
         for chunk in &plan.chunks {
@@ -350,7 +406,7 @@ class DataProcessor:
 More explanation here. "
             .repeat(20);
 
-        let plan = plan_document_chunks(text.as_bytes()).expect("chunk plan");
+        let plan = plan_document_chunks(text.as_bytes(), DEFAULT_CHUNK_CHARS).expect("chunk plan");
 
         // Code blocks should be kept together when possible
         // Check that we have at least one chunk with a complete code block
@@ -365,9 +421,30 @@ More explanation here. "
         );
     }
 
+    #[test]
+    fn code_chunks_split_at_functions_with_ranges() {
+        let mut code = String::new();
+        for i in 0..80 {
+            code.push_str(&format!(
+                "fn handler_{i}(input: &str) -> usize {{\n    input.len() + {i}\n}}\n\n"
+            ));
+        }
+
+        let plan = plan_code_chunks(&code, "rust", 400).expect("chunk plan");
+
+        assert!(plan.chunks.len() > 1);
+        assert_eq!(plan.chunks.len(), plan.manifest.chunks.len());
+        for (chunk, range) in plan.chunks.iter().zip(&plan.manifest.chunks) {
+            assert!(chunk.starts_with("```rust\nfn handler_"));
+            assert!(chunk.contains(&slice_text_range(&code, range)));
+            assert!(chunk.contains("\n    input.len()"));
+        }
+        assert!(plan_code_chunks("fn main() {}", "rust", 400).is_none());
+    }
+
     #[test]
     fn skips_short_text() {
         let text = "short snippet";
-        assert!(plan_document_chunks(text.as_bytes()).is_none());
+        assert!(plan_document_chunks(text.as_bytes(), DEFAULT_CHUNK_CHARS).is_none());
     }
 }
@@ -0,0 +1,250 @@
+//! Topic discovery by k-means over stored embeddings (see [`crate::types::cluster`]).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::simd::l2_distance_squared_simd;
+use crate::types::sketch_track::tokenize_for_sketch;
+use crate::types::{ClusterOptions, FrameId, TopicCluster};
+use crate::vec_pq::kmeans;
+
+impl Memvid {
+    /// Group the active frames that have embeddings into at most `k` topics, largest first.
+    ///
+    /// Clusters are labelled with the terms that best separate them from the others, using the
+    /// lexical analyzer when one is loaded. Runs are deterministic for the same vectors. With
+    /// `options.label_prefix` set, each frame's cluster is also stored as a frame label.
+    pub fn cluster(&mut self, k: usize, options: &ClusterOptions) -> Result<Vec<TopicCluster>> {
+        if k == 0 {
+            return Err(MemvidError::InvalidQuery {
+                reason: "cluster count must be at least 1".into(),
+            });
+        }
+        if !self.vec_enabled {
+            return Err(MemvidError::VecNotEnabled);
+        }
+        if options.label_prefix.is_some() {
+            self.ensure_writable()?;
+        }
+        self.ensure_vec_index()?;
+        let quarantined = self.quarantine_set()?;
+        let mut documents = self.retained_vec_documents()?;
+        documents.retain(|(frame_id, _)| !quarantined.contains(frame_id));
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        documents.sort_by_key(|(frame_id, _)| *frame_id);
+
+        let vectors: Vec<Vec<f32>> = documents.iter().map(|(_, vector)| vector.clone()).collect();
+        let centroids = kmeans(
+            &vectors,
+            k.min(vectors.len()),
+            options.max_iterations.max(1),
+        )?;
+        let mut members: Vec<Vec<(FrameId, f32)>> = vec![Vec::new(); centroids.len()];
+        for (frame_id, vector) in &documents {
+            let (nearest, distance) = centroids
+                .iter()
+                .map(|centroid| l2_distance_squared_simd(vector, centroid))
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap_or((0, 0.0));
+            members[nearest].push((*frame_id, distance.sqrt()));
+        }
+
+        let mut clusters: Vec<TopicCluster> = members
+            .into_iter()
+            .zip(centroids)
+            .filter(|(frames, _)| !frames.is_empty())
+            .map(|(frames, centroid)| {
+                // Safe: cluster sizes are far below f32 precision limits
+                #[allow(clippy::cast_precision_loss)]
+                let spread =
+                    frames.iter().map(|(_, distance)| distance).sum::<f32>() / frames.len() as f32;
+                TopicCluster {
+                    id: 0,
+                    frames: frames.into_iter().map(|(frame_id, _)| frame_id).collect(),
+                    centroid,
+                    top_terms: Vec::new(),
+                    spread,
+                }
+            })
+            .collect();
+        clusters.sort_by(|a, b| {
+            b.frames
+                .len()
+                .cmp(&a.frames.len())
+                .then(a.frames.first().cmp(&b.frames.first()))
+        });
+        for (id, cluster) in clusters.iter_mut().enumerate() {
+            cluster.id = id;
+        }
+
+        self.label_cluster_terms(&mut clusters, options.top_terms)?;
+        if let Some(prefix) = options.label_prefix.as_deref() {
+            self.store_cluster_labels(&clusters, prefix);
+        }
+        Ok(clusters)
+    }
+
+    /// Fill each cluster's `top_terms` with its most distinctive terms: how many of its frames
+    /// use a term, weighted by how rare the term is across all clustered frames (class-based
+    /// TF-IDF). Terms used by every clustered frame say nothing about a topic and are skipped.
+    fn label_cluster_terms(&mut self, clusters: &mut [TopicCluster], limit: usize) -> Result<()> {
+        if limit == 0 {
+            return Ok(());
+        }
+        let mut per_cluster: Vec<BTreeMap<String, usize>> = Vec::with_capacity(clusters.len());
+        let mut frequency: BTreeMap<String, usize> = BTreeMap::new();
+        let mut total = 0usize;
+        for cluster in clusters.iter() {
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for &frame_id in &cluster.frames {
+                let frame = self.frame_by_id(frame_id)?;
+                let text = self.frame_search_text(&frame)?;
+                for term in self.topic_terms(&text) {
+                    *counts.entry(term.clone()).or_default() += 1;
+                    *frequency.entry(term).or_default() += 1;
+                }
+                total += 1;
+            }
+            per_cluster.push(counts);
+        }
+
+        for (cluster, counts) in clusters.iter_mut().zip(per_cluster) {
+            let mut scored: Vec<(f64, String)> = counts
+                .into_iter()
+                .filter_map(|(term, count)| {
+                    // Safe: frame counts are far below f64 precision limits
+                    #[allow(clippy::cast_precision_loss)]
+                    let idf = (1.0 + total as f64 / frequency[&term] as f64).ln();
+                    #[allow(clippy::cast_precision_loss)]
+                    let score = count as f64 * idf;
+                    (frequency[&term] < total).then_some((score, term))
+                })
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+            cluster.top_terms = scored
+                .into_iter()
+                .take(limit)
+                .map(|(_, term)| term)
+                .collect();
+        }
+        Ok(())
+    }
+
+    /// Distinct terms of `text` worth reporting as topic labels.
+    fn topic_terms(&self, text: &str) -> BTreeSet<String> {
+        #[cfg(feature = "lex")]
+        let tokens = match self.tantivy.as_ref() {
+            Some(engine) => engine.analyse_text(text),
+            None => tokenize_for_sketch(text),
+        };
+        #[cfg(not(feature = "lex"))]
+        let tokens = tokenize_for_sketch(text);
+        tokens
+            .into_iter()
+            .filter(|token| {
+                token.chars().count() >= 3 && !token.chars().all(|c| c.is_ascii_digit())
+            })
+            .collect()
+    }
+
+    fn store_cluster_labels(&mut self, clusters: &[TopicCluster], prefix: &str) {
+        for cluster in clusters {
+            let label = format!("{prefix}{}", cluster.id);
+            for &frame_id in &cluster.frames {
+                let Some(frame) = usize::try_from(frame_id)
+                    .ok()
+                    .and_then(|index| self.toc.frames.get_mut(index))
+                else {
+                    continue;
+                };
+                frame
+                    .labels
+                    .retain(|existing| !existing.starts_with(prefix));
+                frame.labels.push(label.clone());
+            }
+        }
+        self.dirty = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PutOptions;
+
+    #[test]
+    fn clusters_group_embeddings_and_store_labels() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("clusters.mv2");
+        let docs = [
+            (
+                "sourdough starter needs flour and water every day",
+                [1.0, 0.1, 0.0],
+            ),
+            (
+                "bake the sourdough loaf in a hot dutch oven",
+                [0.9, 0.0, 0.1],
+            ),
+            ("sourdough crumb depends on the starter", [1.0, 0.0, 0.0]),
+            (
+                "the marathon plan adds one long run each week",
+                [0.0, 1.0, 0.1],
+            ),
+            ("stretch after every marathon training run", [0.1, 0.9, 0.0]),
+        ];
+
+        let mut mem = Memvid::create(&path).expect("create");
+        for (index, (text, embedding)) in docs.iter().enumerate() {
+            mem.put_with_embedding_and_options(
+                text.as_bytes(),
+                embedding.to_vec(),
+                PutOptions::builder()
+                    .uri(format!("mv2://notes/{index}"))
+                    .auto_tag(false)
+                    .build(),
+            )
+            .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let options = ClusterOptions::default().label_prefix("topic-");
+        let clusters = mem.cluster(2, &options).expect("cluster");
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].frames.len(), 3);
+        assert_eq!(clusters[1].frames.len(), 2);
+        assert!(
+            clusters[0]
+                .top_terms
+                .iter()
+                .any(|term| term.starts_with("sourdough"))
+        );
+        assert!(
+            clusters[1]
+                .top_terms
+                .iter()
+                .any(|term| term.starts_with("marathon"))
+        );
+        assert!(clusters.iter().all(|cluster| cluster.spread < 0.2));
+        mem.commit().expect("commit labels");
+
+        let again = mem.cluster(2, &options).expect("recluster");
+        assert_eq!(again[0].frames, clusters[0].frames);
+        let frame = mem.frame_by_id(clusters[1].frames[0]).expect("frame");
+        assert_eq!(
+            frame
+                .labels
+                .iter()
+                .filter(|label| label.starts_with("topic-"))
+                .collect::<Vec<_>>(),
+            vec!["topic-1"]
+        );
+        assert!(matches!(
+            mem.cluster(0, &options),
+            Err(MemvidError::InvalidQuery { .. })
+        ));
+    }
+}
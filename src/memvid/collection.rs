@@ -0,0 +1,127 @@
+//! Collection registry, scoped puts, stats, and search (see [`crate::types::collection`]).
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    COLLECTION_EXTENSION, Collection, CollectionRegistry, CollectionStats, FrameStatus, PutOptions,
+    SearchRequest, SearchResponse,
+};
+
+impl Memvid {
+    /// Register `collection`. Saved on the next commit.
+    pub fn create_collection(&mut self, collection: Collection) -> Result<()> {
+        self.ensure_writable()?;
+        if collection.name.trim().is_empty() || collection.scope.trim().is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "collections need a name and a scope".into(),
+            });
+        }
+        let mut registry = self.collection_registry()?;
+        if registry.contains_key(&collection.name) {
+            return Err(MemvidError::CollectionExists {
+                name: collection.name,
+            });
+        }
+        registry.insert(collection.name.clone(), collection);
+        self.toc.set_extension(COLLECTION_EXTENSION, &registry)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Unregister a collection, keeping its frames. Saved on the next commit.
+    pub fn remove_collection(&mut self, name: &str) -> Result<Collection> {
+        self.ensure_writable()?;
+        self.ensure_not_held("remove collections")?;
+        let mut registry = self.collection_registry()?;
+        let removed = registry
+            .remove(name)
+            .ok_or_else(|| MemvidError::CollectionNotFound {
+                name: name.to_string(),
+            })?;
+        self.toc.set_extension(COLLECTION_EXTENSION, &registry)?;
+        self.dirty = true;
+        Ok(removed)
+    }
+
+    /// Registered collections, by name.
+    pub fn collections(&self) -> Result<Vec<Collection>> {
+        Ok(self.collection_registry()?.into_values().collect())
+    }
+
+    pub fn collection(&self, name: &str) -> Result<Collection> {
+        self.collection_registry()?
+            .remove(name)
+            .ok_or_else(|| MemvidError::CollectionNotFound {
+                name: name.to_string(),
+            })
+    }
+
+    /// Put `payload` into a collection: the collection defaults are merged under `options`
+    /// and the URI is resolved inside the collection's scope. Without a URI the frame is
+    /// named after the payload hash.
+    pub fn put_into(
+        &mut self,
+        collection: &str,
+        payload: &[u8],
+        options: Option<PutOptions>,
+    ) -> Result<u64> {
+        let collection = self.collection(collection)?;
+        let mut options = match options {
+            Some(options) => collection.merge_options(options),
+            None => collection.defaults.clone(),
+        };
+        let uri = options
+            .uri
+            .take()
+            .unwrap_or_else(|| blake3::hash(payload).to_hex()[..16].to_string());
+        options.uri = Some(collection.resolve_uri(&uri));
+        self.put_bytes_with_options(payload, options)
+    }
+
+    /// Frame count, size, and time span of a collection's active frames.
+    pub fn collection_stats(&self, name: &str) -> Result<CollectionStats> {
+        let collection = self.collection(name)?;
+        let mut stats = CollectionStats::default();
+        let frames = self.toc.frames.iter().filter(|frame| {
+            frame.status == FrameStatus::Active
+                && frame
+                    .uri
+                    .as_deref()
+                    .is_some_and(|uri| collection.contains(uri))
+        });
+        for frame in frames {
+            stats.frame_count += 1;
+            stats.payload_bytes += frame.payload_length;
+            stats.logical_bytes += frame.canonical_length.unwrap_or(frame.payload_length);
+            stats.oldest_timestamp = Some(
+                stats
+                    .oldest_timestamp
+                    .map_or(frame.timestamp, |ts| ts.min(frame.timestamp)),
+            );
+            stats.newest_timestamp = Some(
+                stats
+                    .newest_timestamp
+                    .map_or(frame.timestamp, |ts| ts.max(frame.timestamp)),
+            );
+        }
+        Ok(stats)
+    }
+
+    /// `search` restricted to a collection's scope.
+    pub fn search_collection(
+        &mut self,
+        name: &str,
+        mut request: SearchRequest,
+    ) -> Result<SearchResponse> {
+        request.scope = Some(self.collection(name)?.scope);
+        request.uri = None;
+        self.search(request)
+    }
+
+    fn collection_registry(&self) -> Result<CollectionRegistry> {
+        Ok(self
+            .toc
+            .extension::<CollectionRegistry>(COLLECTION_EXTENSION)?
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,307 @@
+//! Change-data-capture: commit event ring and subscriptions.
+//!
+//! Commits that insert or tombstone frames, or add memory cards, append a [`CommitEvent`] to the
+//! [`CommitLog`] stored in the TOC. The event is written as part of the commit itself, so it is
+//! durable exactly when the data is. Live subscribers are notified only after the commit has been
+//! made durable; rolled-back commits publish nothing.
+//!
+//! Every commit also appends its provenance (author, agent, reason) to the
+//! [`CommitHistory`], readable through [`Memvid::commit_history`].
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    COMMIT_HISTORY_EXTENSION, COMMIT_LOG_EXTENSION, CommitEvent, CommitHistory, CommitLog,
+    CommitProvenance, FrameId, MemoryCardId,
+};
+
+/// Stream of [`CommitEvent`]s for one subscriber.
+///
+/// Iterating blocks until the next commit; iteration ends once the owning `Memvid` is dropped.
+pub struct CommitSubscription {
+    receiver: Receiver<CommitEvent>,
+}
+
+impl CommitSubscription {
+    /// Next event if one is already queued.
+    #[must_use]
+    pub fn try_next(&self) -> Option<CommitEvent> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Wait up to `timeout` for the next event.
+    #[must_use]
+    pub fn next_timeout(&self, timeout: Duration) -> Option<CommitEvent> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+}
+
+impl Iterator for CommitSubscription {
+    type Item = CommitEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Memvid {
+    /// Generation written by the most recent commit.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Subscribe to events for commits made through this handle from now on.
+    pub fn subscribe(&mut self) -> CommitSubscription {
+        let (sender, receiver) = mpsc::channel();
+        self.commit_subscribers.push(sender);
+        CommitSubscription { receiver }
+    }
+
+    /// Subscribe after replaying every retained event newer than `generation`.
+    ///
+    /// Use this when a consumer restarts: pass the last generation it processed. Fails with
+    /// [`MemvidError::CommitLogTruncated`] if the ring no longer covers that generation.
+    pub fn subscribe_from(&mut self, generation: u64) -> Result<CommitSubscription> {
+        let backlog = self.commit_events_since(generation)?;
+        let (sender, receiver) = mpsc::channel();
+        for event in backlog {
+            // The receiver is alive in this scope, so sends cannot fail.
+            let _ = sender.send(event);
+        }
+        self.commit_subscribers.push(sender);
+        Ok(CommitSubscription { receiver })
+    }
+
+    /// Retained commit events with a generation greater than `generation`, oldest first.
+    pub fn commit_events_since(&self, generation: u64) -> Result<Vec<CommitEvent>> {
+        let log = self.commit_log()?;
+        log.since(generation)
+            .ok_or(MemvidError::CommitLogTruncated {
+                requested: generation,
+                oldest: log.resumable_from(),
+            })
+    }
+
+    /// Change how many commit events are retained. Takes effect on the next commit.
+    pub fn set_commit_log_capacity(&mut self, capacity: usize) -> Result<()> {
+        self.ensure_writable()?;
+        let mut log = self.commit_log()?;
+        log.set_capacity(capacity);
+        self.toc.set_extension(COMMIT_LOG_EXTENSION, &log)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn commit_log(&self) -> Result<CommitLog> {
+        Ok(self
+            .toc
+            .extension::<CommitLog>(COMMIT_LOG_EXTENSION)?
+            .unwrap_or_default())
+    }
+
+    /// Append the event for the commit in progress to the persisted ring.
+    ///
+    /// Must run after WAL records are applied and before the TOC is rewritten, and before the
+    /// memories track manifest is refreshed for this commit.
+    pub(crate) fn record_commit_event(
+        &mut self,
+        inserted_frames: &[FrameId],
+        tombstoned_frames: &[FrameId],
+    ) -> Result<()> {
+        let existing = self.toc.extension::<CommitLog>(COMMIT_LOG_EXTENSION)?;
+        // Files written before the ring existed: treat already persisted cards as reported.
+        let last_card_id = match &existing {
+            Some(log) => log.last_card_id(),
+            None => self
+                .toc
+                .memories_track
+                .as_ref()
+                .and_then(|manifest| manifest.card_count.checked_sub(1)),
+        };
+        let mut new_memory_cards: Vec<MemoryCardId> = self
+            .memories_track
+            .cards()
+            .iter()
+            .map(|card| card.id)
+            .filter(|id| last_card_id.is_none_or(|last| *id > last))
+            .collect();
+        new_memory_cards.sort_unstable();
+
+        let event = CommitEvent {
+            generation: self.generation,
+            inserted_frames: inserted_frames.to_vec(),
+            tombstoned_frames: tombstoned_frames.to_vec(),
+            new_memory_cards,
+        };
+        if event.is_empty() {
+            return Ok(());
+        }
+        let mut log = existing.unwrap_or_default();
+        log.push(&event);
+        self.toc.set_extension(COMMIT_LOG_EXTENSION, &log)?;
+        self.pending_commit_event = Some(event);
+        Ok(())
+    }
+
+    /// Set the author and agent id recorded with commits from this handle. `CommitOptions`
+    /// can override either for a single commit.
+    pub fn set_commit_author(&mut self, author: Option<String>, agent_id: Option<String>) {
+        self.commit_identity.author = author;
+        self.commit_identity.agent_id = agent_id;
+    }
+
+    /// Provenance of retained commits, oldest first.
+    pub fn commit_history(&self) -> Result<Vec<CommitProvenance>> {
+        Ok(self
+            .toc
+            .extension::<CommitHistory>(COMMIT_HISTORY_EXTENSION)?
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Change how many commits keep their provenance. Takes effect on the next commit.
+    pub fn set_commit_history_capacity(&mut self, capacity: usize) -> Result<()> {
+        self.ensure_writable()?;
+        let mut history = self
+            .toc
+            .extension::<CommitHistory>(COMMIT_HISTORY_EXTENSION)?
+            .unwrap_or_default();
+        history.set_capacity(capacity);
+        self.toc.set_extension(COMMIT_HISTORY_EXTENSION, &history)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Append the provenance of the commit in progress to the persisted history.
+    ///
+    /// Must run after the generation is bumped and before the TOC is rewritten.
+    pub(crate) fn record_commit_provenance(&mut self) -> Result<()> {
+        let mut metadata = self
+            .pending_commit_metadata
+            .take()
+            .unwrap_or_else(|| self.commit_identity.clone());
+        // Every commit path advances the generation by exactly one.
+        metadata.parent_generation = self.generation.wrapping_sub(1);
+        let mut history = self
+            .toc
+            .extension::<CommitHistory>(COMMIT_HISTORY_EXTENSION)?
+            .unwrap_or_default();
+        history.push(CommitProvenance {
+            generation: self.generation,
+            committed_at: unix_now(),
+            metadata,
+        });
+        self.toc.set_extension(COMMIT_HISTORY_EXTENSION, &history)?;
+        Ok(())
+    }
+
+    /// Deliver the event recorded by the last durable commit to live subscribers and hooks.
+    pub(crate) fn publish_commit_event(&mut self) {
+        if let Some(event) = self.pending_commit_event.take() {
+            self.commit_subscribers
+                .retain(|sender: &Sender<CommitEvent>| sender.send(event.clone()).is_ok());
+            self.fire_commit_hooks(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memvid::mutation::{CommitMode, CommitOptions};
+    use crate::types::PutOptions;
+
+    #[test]
+    fn subscribers_receive_events_and_restarts_catch_up() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("cdc.mv2");
+
+        let mut mem = Memvid::create(&path).expect("create");
+        let subscription = mem.subscribe();
+        mem.put_bytes(b"first").expect("put");
+        mem.put_bytes(b"second").expect("put");
+        mem.commit().expect("commit");
+
+        let first = subscription.try_next().expect("event after commit");
+        assert_eq!(first.generation, mem.generation());
+        assert_eq!(first.inserted_frames, vec![0, 1]);
+        assert!(subscription.try_next().is_none());
+
+        mem.delete_frame(0).expect("delete");
+        mem.put_bytes_with_options(b"third", PutOptions::default())
+            .expect("put");
+        mem.commit().expect("commit");
+        let second = subscription.try_next().expect("second event");
+        assert_eq!(second.tombstoned_frames, vec![0]);
+        assert_eq!(second.inserted_frames, vec![2]);
+        drop(mem);
+
+        let mut reopened = Memvid::open(&path).expect("reopen");
+        let events = reopened
+            .commit_events_since(first.generation)
+            .expect("catch up");
+        assert_eq!(events, vec![second.clone()]);
+        let replay = reopened.subscribe_from(0).expect("subscribe from start");
+        assert_eq!(replay.try_next(), Some(first));
+        assert_eq!(replay.try_next(), Some(second));
+    }
+
+    #[test]
+    fn truncated_ring_reports_gap() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("ring.mv2")).expect("create");
+        mem.set_commit_log_capacity(1).expect("capacity");
+        for payload in [b"a", b"b"] {
+            mem.put_bytes(payload).expect("put");
+            mem.commit().expect("commit");
+        }
+        let err = mem.commit_events_since(0).expect_err("gap");
+        assert!(matches!(err, MemvidError::CommitLogTruncated { .. }));
+        assert_eq!(
+            mem.commit_events_since(mem.generation() - 1)
+                .expect("covered")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn commit_history_records_provenance() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("provenance.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.set_commit_author(Some("ingest".into()), Some("worker-1".into()));
+        mem.put_bytes(b"first").expect("put");
+        mem.commit().expect("commit");
+        mem.put_bytes(b"second").expect("put");
+        let options = CommitOptions::new(CommitMode::Full)
+            .agent_id("worker-2")
+            .reason("nightly import");
+        mem.commit_with_options(options).expect("commit");
+        let head = mem.generation();
+        drop(mem);
+
+        let history = Memvid::open(&path)
+            .expect("reopen")
+            .commit_history()
+            .expect("history");
+        let last = history.last().expect("entry");
+        assert_eq!(last.generation, head);
+        assert_eq!(
+            last.metadata.parent_generation,
+            history[history.len() - 2].generation
+        );
+        assert_eq!(last.metadata.author.as_deref(), Some("ingest"));
+        assert_eq!(last.metadata.agent_id.as_deref(), Some("worker-2"));
+        assert_eq!(last.metadata.reason.as_deref(), Some("nightly import"));
+        assert_eq!(
+            history[history.len() - 2].metadata.agent_id.as_deref(),
+            Some("worker-1")
+        );
+    }
+}
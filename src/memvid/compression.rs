@@ -0,0 +1,40 @@
+//! Per-file compression defaults (see [`crate::types::compression`]).
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{COMPRESSION_EXTENSION, CompressionCodec, CompressionDefaults};
+
+impl Memvid {
+    /// Codecs used for new payloads and index segments when a put does not choose one.
+    ///
+    /// Saved in the file on the next commit; existing payloads and segments keep the codec
+    /// they were written with.
+    pub fn set_compression_defaults(&mut self, defaults: CompressionDefaults) -> Result<()> {
+        self.ensure_writable()?;
+        self.toc.set_extension(COMPRESSION_EXTENSION, &defaults)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// The file's compression defaults, or the built-in ones if none were set.
+    pub fn compression_defaults(&self) -> Result<CompressionDefaults> {
+        Ok(self
+            .toc
+            .extension(COMPRESSION_EXTENSION)?
+            .unwrap_or_default())
+    }
+
+    /// Codec for a payload: the put's own choice, then the batch level, then the file default.
+    pub(crate) fn payload_codec(
+        &self,
+        requested: Option<CompressionCodec>,
+    ) -> Result<CompressionCodec> {
+        if let Some(codec) = requested {
+            return Ok(codec);
+        }
+        if let Some(opts) = self.batch_opts.as_ref() {
+            return Ok(CompressionCodec::from_zstd_level(opts.compression_level));
+        }
+        Ok(self.compression_defaults()?.payloads)
+    }
+}
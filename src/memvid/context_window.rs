@@ -0,0 +1,119 @@
+//! Token-budgeted prompt context built from a search (see [`crate::types::context_window`]).
+
+use std::collections::HashSet;
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    AclEnforcementMode, AskContextFragment, AskContextFragmentKind, ContextWindow, SearchHit,
+    SearchMode, SearchRequest, TokenCounter,
+};
+
+/// Hits retrieved as packing candidates.
+const CONTEXT_CANDIDATES: usize = 32;
+const CONTEXT_SNIPPET_CHARS: usize = 200;
+
+impl Memvid {
+    /// Search for `query` and pack the retrieved chunks into a context of at most
+    /// `token_budget` tokens, as counted by `tokenizer`.
+    ///
+    /// Chunks returned more than once are kept once. Candidates are taken by score, newer
+    /// frames first on ties; a chunk that does not fit is skipped so smaller ones further
+    /// down can still use the budget. Each passage starts with a `[n]` marker and its title
+    /// or URI, and `fragments[n - 1]` describes its source.
+    pub fn build_context(
+        &mut self,
+        query: &str,
+        token_budget: usize,
+        tokenizer: &dyn TokenCounter,
+    ) -> Result<ContextWindow> {
+        let response = self.search_unrecorded(SearchRequest {
+            query: query.to_string(),
+            top_k: CONTEXT_CANDIDATES,
+            snippet_chars: CONTEXT_SNIPPET_CHARS,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::default(),
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        })?;
+
+        let mut seen_chunks = HashSet::new();
+        let mut seen_texts = HashSet::new();
+        let mut candidates: Vec<(SearchHit, i64)> = Vec::new();
+        for hit in response.hits {
+            let text = hit.chunk_text.as_deref().unwrap_or(&hit.text).trim();
+            if text.is_empty()
+                || !seen_chunks.insert((hit.frame_id, hit.chunk_range))
+                || !seen_texts.insert(text.to_string())
+            {
+                continue;
+            }
+            let timestamp = self
+                .frame_by_id(hit.frame_id)
+                .map_or(0, |frame| frame.timestamp);
+            candidates.push((hit, timestamp));
+        }
+        candidates.sort_by(|(a, a_ts), (b, b_ts)| {
+            b.score
+                .unwrap_or(0.0)
+                .total_cmp(&a.score.unwrap_or(0.0))
+                .then(b_ts.cmp(a_ts))
+        });
+
+        let mut window = ContextWindow::default();
+        let mut included: Vec<SearchHit> = Vec::new();
+        for (hit, _) in candidates {
+            let marker = window.fragments.len() + 1;
+            let text = hit.chunk_text.as_deref().unwrap_or(&hit.text).trim();
+            let heading = hit.title.as_deref().unwrap_or(&hit.uri);
+            let passage = format!("[{marker}] {heading}\n{text}\n\n");
+            let cost = tokenizer.count_tokens(&passage);
+            if window.tokens + cost > token_budget {
+                window.omitted += 1;
+                continue;
+            }
+            window.tokens += cost;
+            window.text.push_str(&passage);
+            window.fragments.push(AskContextFragment {
+                rank: marker,
+                frame_id: hit.frame_id,
+                uri: hit.uri.clone(),
+                title: hit.title.clone(),
+                score: hit.score,
+                matches: hit.matches,
+                range: Some(hit.range),
+                chunk_range: hit.chunk_range,
+                text: text.to_string(),
+                kind: Some(AskContextFragmentKind::Full),
+                #[cfg(feature = "temporal_track")]
+                temporal: hit
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.temporal.clone()),
+            });
+            included.push(hit);
+        }
+        window.text.truncate(window.text.trim_end().len());
+        window.tokens = tokenizer.count_tokens(&window.text);
+        self.record_search_access(&included);
+        Ok(window)
+    }
+}
@@ -0,0 +1,515 @@
+//! Conversation ingestion for `Memvid`.
+//!
+//! A conversation lands as one session frame with the rendered transcript and one child frame
+//! per message, all written in the same WAL batch so the children resolve their parent on
+//! commit. Message frames are timestamped individually, which anchors them in the time index
+//! and the temporal track like any other frame.
+
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    AclEnforcementMode, CHAT_AUTHOR_KEY, CHAT_INDEX_KEY, CHAT_ROLE_KEY, ChatMessage, ChatRole,
+    ChatSession, ChatSessionMessage, ChatSessionQuery, ChatSessionSummary, ConversationReceipt,
+    Frame, FrameId, FrameStatus, MESSAGE_FRAME_KIND, PutOptions, SESSION_FRAME_KIND,
+    SESSION_ID_KEY, SearchMode, SearchRequest, TimelineEntry,
+};
+
+/// Search hits examined when `sessions` ranks sessions by topic.
+const SESSION_TOPIC_CANDIDATES: usize = 100;
+
+impl Memvid {
+    /// Store a chat transcript as a session frame plus one child frame per message.
+    ///
+    /// `options` describe the session frame; message frames inherit its track, tags, labels,
+    /// and enrichment flags. The session id is taken from `options.extra_metadata` under
+    /// [`SESSION_ID_KEY`] when present, otherwise a new UUID is generated. Call `commit` to
+    /// persist.
+    pub fn put_conversation(
+        &mut self,
+        messages: &[ChatMessage],
+        options: PutOptions,
+    ) -> Result<ConversationReceipt> {
+        self.ensure_mutation_allowed()?;
+        if messages.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "conversation has no messages".into(),
+            });
+        }
+
+        let session_id = options
+            .extra_metadata
+            .get(SESSION_ID_KEY)
+            .cloned()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let session_ts = options
+            .timestamp
+            .or_else(|| messages.iter().find_map(|message| message.timestamp))
+            .unwrap_or_else(unix_now);
+
+        let transcript = messages
+            .iter()
+            .map(|message| match &message.author {
+                Some(author) => format!("{} ({author}): {}", message.role, message.content),
+                None => format!("{}: {}", message.role, message.content),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut session_options = options.clone();
+        session_options.timestamp = Some(session_ts);
+        session_options.kind = Some(SESSION_FRAME_KIND.to_string());
+        session_options
+            .extra_metadata
+            .insert(SESSION_ID_KEY.to_string(), session_id.clone());
+        let session_sequence = self.put_internal(
+            Some(transcript.as_bytes()),
+            None,
+            None,
+            None,
+            session_options,
+            None,
+            None,
+        )?;
+
+        let mut message_sequences = Vec::with_capacity(messages.len());
+        for (index, message) in messages.iter().enumerate() {
+            let mut message_options = PutOptions {
+                timestamp: Some(message.timestamp.unwrap_or(session_ts)),
+                track: options.track.clone(),
+                kind: Some(MESSAGE_FRAME_KIND.to_string()),
+                uri: options
+                    .uri
+                    .as_ref()
+                    .map(|uri| format!("{uri}#message-{index}")),
+                title: message.author.clone(),
+                tags: options.tags.clone(),
+                labels: options.labels.clone(),
+                extra_metadata: options.extra_metadata.clone(),
+                enable_embedding: options.enable_embedding,
+                auto_tag: options.auto_tag,
+                extract_dates: options.extract_dates,
+                extract_triplets: options.extract_triplets,
+                instant_index: options.instant_index,
+                ..PutOptions::default()
+            };
+            let extra = &mut message_options.extra_metadata;
+            extra.insert(SESSION_ID_KEY.to_string(), session_id.clone());
+            extra.insert(CHAT_ROLE_KEY.to_string(), message.role.as_str().to_string());
+            extra.insert(CHAT_INDEX_KEY.to_string(), index.to_string());
+            if let Some(author) = &message.author {
+                extra.insert(CHAT_AUTHOR_KEY.to_string(), author.clone());
+            }
+            message_sequences.push(self.put_internal(
+                Some(message.content.as_bytes()),
+                None,
+                None,
+                None,
+                message_options,
+                None,
+                Some(session_sequence),
+            )?);
+        }
+
+        Ok(ConversationReceipt {
+            session_id,
+            session_sequence,
+            message_sequences,
+        })
+    }
+
+    /// Committed message frames of a session, in conversation order.
+    pub fn timeline_for_session(&mut self, session_id: &str) -> Result<Vec<TimelineEntry>> {
+        let mut messages: Vec<Frame> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .filter(|frame| frame.kind.as_deref() == Some(MESSAGE_FRAME_KIND))
+            .filter(|frame| {
+                frame.extra_metadata.get(SESSION_ID_KEY).map(String::as_str) == Some(session_id)
+            })
+            .cloned()
+            .collect();
+        messages.sort_by_key(message_order);
+
+        #[cfg(feature = "temporal_track")]
+        let temporal_track = self.temporal_track_ref()?.cloned();
+        let mut entries = Vec::with_capacity(messages.len());
+        for frame in messages {
+            #[cfg(feature = "temporal_track")]
+            let temporal = match temporal_track.as_ref() {
+                Some(track) => {
+                    crate::memvid::timeline::build_timeline_temporal_metadata(self, track, &frame)?
+                }
+                None => None,
+            };
+            entries.push(TimelineEntry {
+                frame_id: frame.id,
+                timestamp: frame.timestamp,
+                preview: self.frame_preview(&frame)?,
+                uri: frame
+                    .uri
+                    .clone()
+                    .or_else(|| Some(crate::default_uri(frame.id))),
+                child_frames: Vec::new(),
+                #[cfg(feature = "temporal_track")]
+                temporal,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// A session's messages in conversation order, with their roles and full text.
+    ///
+    /// `session_id` is the id `put_conversation` returned, or the track of frames that carry a
+    /// [`CHAT_ROLE_KEY`] without a session id.
+    pub fn session(&mut self, session_id: &str) -> Result<ChatSession> {
+        let mut session_frame = None;
+        let mut frames = Vec::new();
+        for frame in self.toc.frames.iter().filter(|frame| {
+            frame.status == FrameStatus::Active && session_key(frame) == Some(session_id)
+        }) {
+            if frame.kind.as_deref() == Some(SESSION_FRAME_KIND) {
+                session_frame = Some(frame.clone());
+            } else {
+                frames.push(frame.clone());
+            }
+        }
+        if frames.is_empty() && session_frame.is_none() {
+            return Err(MemvidError::SessionNotFound {
+                session_id: session_id.to_string(),
+            });
+        }
+        frames.sort_by_key(message_order);
+
+        let mut messages = Vec::with_capacity(frames.len());
+        for frame in frames {
+            messages.push(ChatSessionMessage {
+                frame_id: frame.id,
+                role: frame
+                    .extra_metadata
+                    .get(CHAT_ROLE_KEY)
+                    .and_then(|role| ChatRole::parse(role)),
+                author: frame.extra_metadata.get(CHAT_AUTHOR_KEY).cloned(),
+                timestamp: frame.timestamp,
+                text: String::from_utf8_lossy(&self.frame_canonical_bytes(&frame)?).into_owned(),
+            });
+        }
+        Ok(ChatSession {
+            session_id: session_id.to_string(),
+            title: session_frame.as_ref().and_then(|frame| frame.title.clone()),
+            session_frame: session_frame.map(|frame| frame.id),
+            messages,
+        })
+    }
+
+    /// Sessions with at least one message: the most recently active first, or, for a
+    /// `topic`, those whose messages or transcript match it, best match first.
+    pub fn sessions(&mut self, query: &ChatSessionQuery) -> Result<Vec<ChatSessionSummary>> {
+        let mut summaries: BTreeMap<String, ChatSessionSummary> = BTreeMap::new();
+        for frame in self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+        {
+            let Some(session_id) = session_key(frame) else {
+                continue;
+            };
+            let summary =
+                summaries
+                    .entry(session_id.to_string())
+                    .or_insert_with(|| ChatSessionSummary {
+                        session_id: session_id.to_string(),
+                        title: None,
+                        message_count: 0,
+                        first_timestamp: i64::MAX,
+                        last_timestamp: i64::MIN,
+                        score: None,
+                    });
+            if frame.kind.as_deref() == Some(SESSION_FRAME_KIND) {
+                summary.title.clone_from(&frame.title);
+            } else {
+                summary.message_count += 1;
+                summary.first_timestamp = summary.first_timestamp.min(frame.timestamp);
+                summary.last_timestamp = summary.last_timestamp.max(frame.timestamp);
+            }
+        }
+        summaries.retain(|_, summary| summary.message_count > 0);
+
+        let mut listed = match &query.topic {
+            None => {
+                let mut listed: Vec<ChatSessionSummary> = summaries.into_values().collect();
+                listed.sort_by(|a, b| {
+                    b.last_timestamp
+                        .cmp(&a.last_timestamp)
+                        .then_with(|| a.session_id.cmp(&b.session_id))
+                });
+                listed
+            }
+            Some(topic) => {
+                let hits = self.search_unrecorded(topic_request(topic))?.hits;
+                let mut listed = Vec::new();
+                for hit in hits {
+                    let Some(session_id) = self.hit_session(hit.frame_id) else {
+                        continue;
+                    };
+                    if let Some(mut summary) = summaries.remove(&session_id) {
+                        summary.score = hit.score;
+                        listed.push(summary);
+                    }
+                }
+                listed
+            }
+        };
+        if let Some(limit) = query.limit {
+            listed.truncate(limit);
+        }
+        Ok(listed)
+    }
+
+    /// Session of a search hit's frame, or of its parent for chunks.
+    fn hit_session(&self, frame_id: FrameId) -> Option<String> {
+        let frame = self.frame_by_id(frame_id).ok()?;
+        if let Some(session_id) = session_key(&frame) {
+            return Some(session_id.to_string());
+        }
+        let parent = self.frame_by_id(frame.parent_id?).ok()?;
+        session_key(&parent).map(str::to_string)
+    }
+}
+
+/// Session a frame belongs to: its [`SESSION_ID_KEY`], or the track of a frame that carries a
+/// [`CHAT_ROLE_KEY`].
+fn session_key(frame: &Frame) -> Option<&str> {
+    frame
+        .extra_metadata
+        .get(SESSION_ID_KEY)
+        .map(String::as_str)
+        .or_else(|| {
+            frame
+                .track
+                .as_deref()
+                .filter(|_| frame.extra_metadata.contains_key(CHAT_ROLE_KEY))
+        })
+}
+
+/// Conversation order: by timestamp, then position in the session, then frame id.
+fn message_order(frame: &Frame) -> (i64, Option<u64>, FrameId) {
+    let index = frame
+        .extra_metadata
+        .get(CHAT_INDEX_KEY)
+        .and_then(|value| value.parse::<u64>().ok());
+    (frame.timestamp, index, frame.id)
+}
+
+fn topic_request(topic: &str) -> SearchRequest {
+    SearchRequest {
+        query: topic.to_string(),
+        top_k: SESSION_TOPIC_CANDIDATES,
+        snippet_chars: 40,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: AclEnforcementMode::default(),
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: SearchMode::Lexical,
+        time_budget_ms: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChatRole, FrameRole};
+
+    #[test]
+    fn conversation_round_trips_in_order() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("chat.mv2");
+        let messages = vec![
+            ChatMessage::new(ChatRole::User, "should we ship the parser rewrite friday")
+                .author("dana")
+                .timestamp(1_700_000_100),
+            ChatMessage::new(ChatRole::Assistant, "yes, once the fuzz suite passes")
+                .timestamp(1_700_000_160),
+            ChatMessage::new(ChatRole::User, "agreed").timestamp(1_700_000_160),
+        ];
+        let receipt = {
+            let mut mem = Memvid::create(&path).expect("create");
+            let options = PutOptions {
+                uri: Some("mv2://chats/standup".into()),
+                ..Default::default()
+            };
+            let receipt = mem.put_conversation(&messages, options).expect("put");
+            assert_eq!(receipt.message_sequences.len(), 3);
+            assert!(mem.put_conversation(&[], PutOptions::default()).is_err());
+            mem.commit().expect("commit");
+            receipt
+        };
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        let session = mem
+            .toc
+            .frames
+            .iter()
+            .find(|frame| frame.kind.as_deref() == Some(SESSION_FRAME_KIND))
+            .cloned()
+            .expect("session frame");
+        assert_eq!(session.timestamp, 1_700_000_100);
+        assert_eq!(session.role, FrameRole::Document);
+
+        let timeline = mem
+            .timeline_for_session(&receipt.session_id)
+            .expect("timeline");
+        assert_eq!(timeline.len(), 3);
+        for (entry, message) in timeline.iter().zip(&messages) {
+            assert!(entry.preview.starts_with(&message.content));
+        }
+        assert_eq!(
+            timeline[0].uri.as_deref(),
+            Some("mv2://chats/standup#message-0")
+        );
+        let first = mem.frame_by_id(timeline[0].frame_id).expect("message");
+        assert_eq!(first.parent_id, Some(session.id));
+        assert_eq!(
+            first
+                .extra_metadata
+                .get(CHAT_AUTHOR_KEY)
+                .map(String::as_str),
+            Some("dana")
+        );
+        assert_eq!(
+            first.extra_metadata.get(CHAT_ROLE_KEY).map(String::as_str),
+            Some("user")
+        );
+        assert!(
+            mem.timeline_for_session("missing")
+                .expect("empty")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lex")]
+    fn sessions_read_back_and_list_by_recency_and_topic() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("sessions.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        let older = mem
+            .put_conversation(
+                &[
+                    ChatMessage::new(ChatRole::User, "when does the parser rewrite ship")
+                        .timestamp(1_700_000_000),
+                    ChatMessage::new(ChatRole::Assistant, "friday, after the fuzz suite")
+                        .author("bot")
+                        .timestamp(1_700_000_060),
+                ],
+                PutOptions {
+                    title: Some("Parser planning".into()),
+                    ..Default::default()
+                },
+            )
+            .expect("put");
+        let newer = mem
+            .put_conversation(
+                &[ChatMessage::new(ChatRole::User, "book the lakeside venue")
+                    .timestamp(1_700_100_000)],
+                PutOptions::default(),
+            )
+            .expect("put");
+        for (role, text, timestamp) in [
+            ("user", "my invoice is wrong", 1_700_050_000),
+            (
+                "assistant",
+                "a corrected invoice is on its way",
+                1_700_050_030,
+            ),
+        ] {
+            let mut options = PutOptions::builder()
+                .track("support-42")
+                .timestamp(timestamp)
+                .build();
+            options
+                .extra_metadata
+                .insert(CHAT_ROLE_KEY.to_string(), role.to_string());
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let session = mem.session(&older.session_id).expect("session");
+        assert_eq!(session.title.as_deref(), Some("Parser planning"));
+        assert!(session.session_frame.is_some());
+        assert_eq!(
+            session
+                .messages
+                .iter()
+                .map(|message| (message.role, message.text.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (Some(ChatRole::User), "when does the parser rewrite ship"),
+                (Some(ChatRole::Assistant), "friday, after the fuzz suite"),
+            ]
+        );
+        assert_eq!(session.messages[1].author.as_deref(), Some("bot"));
+        let support = mem.session("support-42").expect("track session");
+        assert_eq!(support.messages.len(), 2);
+        assert_eq!(support.messages[0].role, Some(ChatRole::User));
+        assert!(matches!(
+            mem.session("missing"),
+            Err(MemvidError::SessionNotFound { .. })
+        ));
+
+        let recent = mem
+            .sessions(&ChatSessionQuery::default())
+            .expect("sessions");
+        assert_eq!(
+            recent
+                .iter()
+                .map(|summary| summary.session_id.as_str())
+                .collect::<Vec<_>>(),
+            [
+                newer.session_id.as_str(),
+                "support-42",
+                older.session_id.as_str()
+            ]
+        );
+        assert_eq!(recent[2].message_count, 2);
+        assert_eq!(recent[2].first_timestamp, 1_700_000_000);
+        assert_eq!(
+            mem.sessions(&ChatSessionQuery::recent(1))
+                .expect("sessions")
+                .len(),
+            1
+        );
+
+        let about_invoices = mem
+            .sessions(&ChatSessionQuery::topic("invoice"))
+            .expect("topic");
+        assert_eq!(about_invoices.len(), 1);
+        assert_eq!(about_invoices[0].session_id, "support-42");
+        assert!(about_invoices[0].score.is_some());
+    }
+}
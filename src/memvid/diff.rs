@@ -0,0 +1,185 @@
+//! `Memvid::diff`: what changed between two commits.
+//!
+//! Frame and card changes come from the commit log, so the range must still be covered by the
+//! ring (see [`crate::types::CommitLog`]). Supersessions, contradictions, and mesh edges are
+//! resolved against the current state of the memory.
+
+use std::collections::BTreeSet;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    CardContradiction, FrameId, FrameSupersession, MemoryCardId, MemoryDiff, VersionRelation,
+};
+
+impl Memvid {
+    /// Changes committed after `from_generation` up to and including `to_generation`.
+    ///
+    /// Fails with [`MemvidError::CommitLogTruncated`] if the commit log no longer reaches
+    /// back to `from_generation`.
+    pub fn diff(&self, from_generation: u64, to_generation: u64) -> Result<MemoryDiff> {
+        if from_generation > to_generation {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!(
+                    "diff range is reversed: {from_generation} is after {to_generation}"
+                ),
+            });
+        }
+        if to_generation > self.generation {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!(
+                    "generation {to_generation} has not been committed (latest is {})",
+                    self.generation
+                ),
+            });
+        }
+
+        let mut added: BTreeSet<FrameId> = BTreeSet::new();
+        let mut tombstoned: BTreeSet<FrameId> = BTreeSet::new();
+        let mut cards: BTreeSet<MemoryCardId> = BTreeSet::new();
+        for event in self
+            .commit_events_since(from_generation)?
+            .into_iter()
+            .filter(|event| event.generation <= to_generation)
+        {
+            added.extend(event.inserted_frames);
+            tombstoned.extend(event.tombstoned_frames);
+            cards.extend(event.new_memory_cards);
+        }
+
+        let frames_superseded = added
+            .iter()
+            .filter_map(|&id| {
+                usize::try_from(id)
+                    .ok()
+                    .and_then(|i| self.toc.frames.get(i))
+            })
+            .filter_map(|frame| {
+                frame.supersedes.map(|previous| FrameSupersession {
+                    previous,
+                    replacement: frame.id,
+                })
+            })
+            .collect();
+
+        let cards_contradicted = cards
+            .iter()
+            .filter_map(|&id| self.memories_track.get_card(id))
+            .filter(|card| {
+                matches!(
+                    card.version_relation,
+                    VersionRelation::Updates | VersionRelation::Retracts
+                )
+            })
+            .filter_map(|card| {
+                let contradicts: Vec<MemoryCardId> = self
+                    .memories_track
+                    .get_cards(&card.entity, &card.slot)
+                    .into_iter()
+                    .filter(|earlier| earlier.id < card.id)
+                    .filter(|earlier| {
+                        card.version_relation == VersionRelation::Retracts
+                            || earlier.value != card.value
+                    })
+                    .map(|earlier| earlier.id)
+                    .collect();
+                (!contradicts.is_empty()).then_some(CardContradiction {
+                    card: card.id,
+                    relation: card.version_relation,
+                    contradicts,
+                })
+            })
+            .collect();
+
+        let edges = &self.logic_mesh.edges;
+        Ok(MemoryDiff {
+            from_generation,
+            to_generation,
+            frames_superseded,
+            cards_contradicted,
+            mesh_edges_added: edges
+                .iter()
+                .filter(|edge| added.contains(&edge.frame_id))
+                .cloned()
+                .collect(),
+            mesh_edges_orphaned: edges
+                .iter()
+                .filter(|edge| tombstoned.contains(&edge.frame_id))
+                .cloned()
+                .collect(),
+            frames_added: added.into_iter().collect(),
+            frames_tombstoned: tombstoned.into_iter().collect(),
+            cards_created: cards.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{LinkType, MemoryCard, MemoryCardBuilder, MeshEdge, PutOptions};
+
+    fn city(value: &str, update: bool) -> MemoryCard {
+        let builder = MemoryCardBuilder::new()
+            .fact()
+            .entity("user")
+            .slot("city")
+            .value(value)
+            .source(0, None)
+            .engine("test", "1.0.0");
+        let builder = if update { builder.updates() } else { builder };
+        builder.build(0).expect("card")
+    }
+
+    #[test]
+    fn diff_reports_frames_cards_and_edges() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("diff.mv2")).expect("create");
+
+        mem.put_bytes(b"lives in lisbon").expect("put");
+        let first = mem.put_memory_card(city("Lisbon", false)).expect("card");
+        mem.commit().expect("commit");
+        let g1 = mem.generation();
+
+        mem.update_frame(
+            0,
+            Some(b"moved to porto".to_vec()),
+            PutOptions::default(),
+            None,
+        )
+        .expect("update");
+        mem.put_bytes(b"scratch note").expect("put");
+        let second = mem.put_memory_card(city("Porto", true)).expect("card");
+        mem.add_mesh_edge(MeshEdge::new(1, 2, LinkType::from_str("lives_in"), 0.9, 1));
+        mem.add_mesh_edge(MeshEdge::new(3, 4, LinkType::from_str("mentions"), 0.5, 2));
+        mem.commit().expect("commit");
+        let g2 = mem.generation();
+
+        mem.delete_frame(2).expect("delete");
+        mem.commit().expect("commit");
+        let g3 = mem.generation();
+
+        let diff = mem.diff(g1, g3).expect("diff");
+        assert_eq!(diff.frames_added, vec![1, 2]);
+        assert_eq!(diff.frames_tombstoned, vec![2]);
+        assert_eq!(
+            diff.frames_superseded,
+            vec![FrameSupersession {
+                previous: 0,
+                replacement: 1
+            }]
+        );
+        assert_eq!(diff.cards_created, vec![second]);
+        assert_eq!(diff.cards_contradicted.len(), 1);
+        assert_eq!(diff.cards_contradicted[0].contradicts, vec![first]);
+        assert_eq!(diff.mesh_edges_added.len(), 2);
+        assert_eq!(diff.mesh_edges_orphaned.len(), 1);
+
+        let tail = mem.diff(g2, g3).expect("diff");
+        assert!(tail.frames_added.is_empty());
+        assert_eq!(tail.frames_tombstoned, vec![2]);
+        assert_eq!(tail.mesh_edges_orphaned[0].frame_id, 2);
+        assert!(mem.diff(g3, g3).expect("empty").is_empty());
+        assert!(mem.diff(g3, g1).is_err());
+    }
+}
@@ -7,17 +7,21 @@ use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+use crate::constants::HEADER_SIZE;
 use crate::error::{MemvidError, Result};
 use crate::io::header::HeaderCodec;
 use crate::io::time_index::{calculate_checksum as time_index_checksum, read_track};
 use crate::io::wal::EmbeddedWal;
-use crate::memvid::lifecycle::{Memvid, ensure_single_file, read_toc, recover_toc};
+use crate::memvid::legal_hold::hold_on_disk;
+use crate::memvid::lifecycle::{
+    Memvid, detect_generation, ensure_single_file, read_toc, recover_toc, referenced_byte_ranges,
+};
 use crate::types::{
-    DOCTOR_PLAN_VERSION, DoctorActionDetail, DoctorActionKind, DoctorActionPlan,
-    DoctorActionReport, DoctorActionStatus, DoctorFinding, DoctorFindingCode, DoctorMetrics,
-    DoctorOptions, DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan, DoctorPhaseReport,
-    DoctorPhaseStatus, DoctorPlan, DoctorReport, DoctorStatus, VerificationReport,
-    VerificationStatus,
+    DOCTOR_PLAN_VERSION, DOCTOR_REPORT_VERSION, DoctorActionDetail, DoctorActionKind,
+    DoctorActionPlan, DoctorActionReport, DoctorActionStatus, DoctorByteRange, DoctorFinding,
+    DoctorFindingCode, DoctorIndexBump, DoctorIndexKind, DoctorMetrics, DoctorOptions,
+    DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan, DoctorPhaseReport, DoctorPhaseStatus,
+    DoctorPlan, DoctorPlanDiff, DoctorReport, DoctorStatus, VerificationReport, VerificationStatus,
 };
 use crate::types::{Header, Toc};
 
@@ -71,6 +75,10 @@ struct PlanProbe {
     wal_to_sequence: u64,
     index: IndexProbe,
     file_len: u64,
+    /// Unreferenced `(offset, length)` ranges between the WAL and the footer.
+    orphaned: Vec<(u64, u64)>,
+    /// Footer generation of the committed state.
+    generation: u64,
 }
 
 pub(crate) fn doctor_plan(path: &Path, options: DoctorOptions) -> Result<DoctorPlan> {
@@ -82,8 +90,29 @@ pub(crate) fn doctor_plan(path: &Path, options: DoctorOptions) -> Result<DoctorP
         options.rebuild_vec_index,
     );
     ensure_single_file(path)?;
+    // A file under a legal hold is only inspected, never repaired.
+    let hold = hold_finding(path);
+    let mut options = options;
+    if hold.is_some() {
+        options.dry_run = true;
+    }
     let planner = DoctorPlanner::new(path.to_path_buf(), options);
-    planner.compute()
+    let mut plan = planner.compute()?;
+    plan.findings.extend(hold);
+    Ok(plan)
+}
+
+/// Why the file at `path` may only be inspected: the legal hold in force, or a hold that
+/// could not be read, which counts as one.
+fn hold_finding(path: &Path) -> Option<DoctorFinding> {
+    let message = match hold_on_disk(path) {
+        Ok(hold) => format!(
+            "file is under a {} legal hold; planning only",
+            hold?.mode.as_str()
+        ),
+        Err(err) => format!("legal hold could not be read ({err}); planning only"),
+    };
+    Some(DoctorFinding::info(DoctorFindingCode::LegalHold, message))
 }
 
 /// Attempt to recover from WAL corruption by rebuilding a clean WAL
@@ -137,7 +166,10 @@ fn try_recover_from_wal_corruption(path: &Path) -> Result<Memvid> {
     Memvid::try_open(path)
 }
 
-pub(crate) fn doctor_apply(path: &Path, plan: DoctorPlan) -> Result<DoctorReport> {
+pub(crate) fn doctor_apply(path: &Path, mut plan: DoctorPlan) -> Result<DoctorReport> {
+    if hold_finding(path).is_some() {
+        plan.options.dry_run = true;
+    }
     if plan.options.dry_run {
         let findings = plan.findings.clone();
         let status = if plan.is_noop() {
@@ -145,12 +177,17 @@ pub(crate) fn doctor_apply(path: &Path, plan: DoctorPlan) -> Result<DoctorReport
         } else {
             DoctorStatus::PlanOnly
         };
+        let metrics = DoctorMetrics {
+            reclaimable_bytes: plan.reclaimable_bytes,
+            ..DoctorMetrics::default()
+        };
         return Ok(DoctorReport {
+            version: DOCTOR_REPORT_VERSION,
             plan,
             status,
             phases: Vec::new(),
             findings,
-            metrics: DoctorMetrics::default(),
+            metrics,
             verification: None,
         });
     }
@@ -293,8 +330,21 @@ impl DoctorPlanner {
                 }),
             });
         }
+        if self.options.supersede_duplicates {
+            phases.push(DoctorPhasePlan {
+                phase: DoctorPhaseKind::Deduplicate,
+                actions: vec![DoctorActionPlan {
+                    action: DoctorActionKind::SupersedeDuplicates,
+                    required: true,
+                    reasons: Vec::new(),
+                    note: Some("supersede exact duplicate frames".to_string()),
+                    detail: None,
+                }],
+            });
+        }
         // FIX: Run vacuum BEFORE index rebuild to avoid orphaning segments
         // Vacuum compacts frames first, then index rebuild writes fresh indexes
+        let reclaimable_bytes: u64 = probe.orphaned.iter().map(|(_, length)| length).sum();
         if self.options.vacuum {
             phases.push(DoctorPhasePlan {
                 phase: DoctorPhaseKind::Vacuum,
@@ -315,25 +365,38 @@ impl DoctorPlanner {
             });
         }
 
-        if probe.toc_recovered || !phases.is_empty() {
+        let reclaim = self.options.reclaim_orphans && reclaimable_bytes > 0;
+        if probe.toc_recovered || !phases.is_empty() || reclaim {
+            let mut actions = vec![DoctorActionPlan {
+                action: DoctorActionKind::RecomputeToc,
+                required: true,
+                reasons: Vec::new(),
+                note: Some("persist rebuilt manifests".to_string()),
+                detail: None,
+            }];
+            // After RecomputeToc, whose commit writes the tail tracks once more.
+            if reclaim {
+                actions.push(DoctorActionPlan {
+                    action: DoctorActionKind::ReclaimOrphans,
+                    required: true,
+                    reasons: vec![DoctorFindingCode::OrphanedBytes],
+                    note: Some(format!("reclaim {reclaimable_bytes} orphaned bytes")),
+                    detail: Some(DoctorActionDetail::OrphanedBytes {
+                        orphaned_ranges: probe.orphaned.len(),
+                        reclaimable_bytes,
+                    }),
+                });
+            }
+            actions.push(DoctorActionPlan {
+                action: DoctorActionKind::UpdateHeader,
+                required: true,
+                reasons: Vec::new(),
+                note: Some("update header pointer".to_string()),
+                detail: None,
+            });
             phases.push(DoctorPhasePlan {
                 phase: DoctorPhaseKind::Finalize,
-                actions: vec![
-                    DoctorActionPlan {
-                        action: DoctorActionKind::RecomputeToc,
-                        required: true,
-                        reasons: Vec::new(),
-                        note: Some("persist rebuilt manifests".to_string()),
-                        detail: None,
-                    },
-                    DoctorActionPlan {
-                        action: DoctorActionKind::UpdateHeader,
-                        required: true,
-                        reasons: Vec::new(),
-                        note: Some("update header pointer".to_string()),
-                        detail: None,
-                    },
-                ],
+                actions,
             });
         }
 
@@ -355,10 +418,120 @@ impl DoctorPlanner {
             file_path: self.path,
             options: self.options,
             findings,
+            diff: Self::diff(&probe, &phases),
             phases,
+            reclaimable_bytes,
         })
     }
 
+    /// Summarize what `phases` would change, from the probed state alone.
+    fn diff(probe: &PlanProbe, phases: &[DoctorPhasePlan]) -> DoctorPlanDiff {
+        fn push_range(
+            diff: &mut DoctorPlanDiff,
+            range: Option<(u64, u64)>,
+            action: DoctorActionKind,
+        ) {
+            if let Some((offset, length)) = range.filter(|(_, length)| *length > 0) {
+                diff.rewritten_ranges.push(DoctorByteRange {
+                    offset,
+                    length,
+                    action,
+                });
+            }
+        }
+
+        let mut diff = DoctorPlanDiff::default();
+        let frame_count = probe.toc.as_ref().map_or(0, |toc| toc.frames.len() as u64);
+        for action in phases.iter().flat_map(|phase| &phase.actions) {
+            let toc = probe.toc.as_ref();
+            let header = probe.header.as_ref();
+            match action.action {
+                DoctorActionKind::HealHeaderPointer
+                | DoctorActionKind::HealTocChecksum
+                | DoctorActionKind::UpdateHeader => {
+                    push_range(&mut diff, Some((0, HEADER_SIZE as u64)), action.action);
+                }
+                DoctorActionKind::ReplayWal | DoctorActionKind::DiscardWal => {
+                    push_range(
+                        &mut diff,
+                        header.map(|header| (header.wal_offset, header.wal_size)),
+                        action.action,
+                    );
+                    diff.frames_affected = diff.frames_affected.max(probe.wal_pending as u64);
+                }
+                DoctorActionKind::RebuildTimeIndex => {
+                    push_range(
+                        &mut diff,
+                        toc.and_then(|toc| toc.time_index.as_ref())
+                            .map(|manifest| (manifest.bytes_offset, manifest.bytes_length)),
+                        action.action,
+                    );
+                    diff.frames_affected = diff.frames_affected.max(frame_count);
+                    diff.index_bumps.push(DoctorIndexBump {
+                        index: DoctorIndexKind::Time,
+                        from_generation: probe.generation,
+                    });
+                }
+                DoctorActionKind::RebuildLexIndex => {
+                    push_range(
+                        &mut diff,
+                        toc.and_then(|toc| toc.indexes.lex.as_ref())
+                            .map(|manifest| (manifest.bytes_offset, manifest.bytes_length)),
+                        action.action,
+                    );
+                    diff.frames_affected = diff.frames_affected.max(frame_count);
+                    diff.index_bumps.push(DoctorIndexBump {
+                        index: DoctorIndexKind::Lex,
+                        from_generation: probe.generation,
+                    });
+                }
+                DoctorActionKind::RebuildVecIndex => {
+                    push_range(
+                        &mut diff,
+                        toc.and_then(|toc| toc.indexes.vec.as_ref())
+                            .map(|manifest| (manifest.bytes_offset, manifest.bytes_length)),
+                        action.action,
+                    );
+                    diff.frames_affected = diff.frames_affected.max(frame_count);
+                    diff.index_bumps.push(DoctorIndexBump {
+                        index: DoctorIndexKind::Vec,
+                        from_generation: probe.generation,
+                    });
+                }
+                DoctorActionKind::VacuumCompaction => {
+                    if let (Some(header), Some(toc_offset)) = (header, probe.toc_offset) {
+                        let start = header.data_start;
+                        push_range(
+                            &mut diff,
+                            Some((start, toc_offset.saturating_sub(start))),
+                            action.action,
+                        );
+                    }
+                    diff.frames_affected = diff.frames_affected.max(frame_count);
+                }
+                DoctorActionKind::SupersedeDuplicates => {
+                    diff.frames_affected = diff.frames_affected.max(frame_count);
+                }
+                DoctorActionKind::ReclaimOrphans => {
+                    for range in &probe.orphaned {
+                        push_range(&mut diff, Some(*range), action.action);
+                    }
+                }
+                DoctorActionKind::RecomputeToc => {
+                    push_range(
+                        &mut diff,
+                        probe
+                            .toc_offset
+                            .map(|offset| (offset, probe.file_len.saturating_sub(offset))),
+                        action.action,
+                    );
+                }
+                DoctorActionKind::DeepVerify | DoctorActionKind::NoOp => {}
+            }
+        }
+        diff
+    }
+
     fn probe(&mut self) -> Result<PlanProbe> {
         doctor_log!("doctor: probe start");
         let mut probe = PlanProbe {
@@ -372,10 +545,13 @@ impl DoctorPlanner {
             wal_to_sequence: 0,
             index: IndexProbe::default(),
             file_len: 0,
+            orphaned: Vec::new(),
+            generation: 0,
         };
 
         let mut file = OpenOptions::new().read(true).write(true).open(&self.path)?;
         probe.file_len = file.metadata()?.len();
+        probe.generation = detect_generation(&file).ok().flatten().unwrap_or(0);
         doctor_log!("doctor: probe file len {}", probe.file_len);
 
         doctor_log!("doctor: reading header");
@@ -493,10 +669,52 @@ impl DoctorPlanner {
         self.inspect_time_index(&mut probe, &mut file);
         self.inspect_lex_index(&mut probe, &mut file);
         self.inspect_vec_index(&mut probe, &mut file);
+        Self::inspect_orphans(&mut probe);
 
         Ok(probe)
     }
 
+    /// Find bytes between the WAL and the footer that nothing in the TOC references, left
+    /// behind when index rebuilds write fresh segments at `data_end`.
+    fn inspect_orphans(probe: &mut PlanProbe) {
+        let (Some(header), Some(toc), Some(toc_offset)) =
+            (probe.header.as_ref(), probe.toc.as_ref(), probe.toc_offset)
+        else {
+            return;
+        };
+        let mut referenced = referenced_byte_ranges(toc, header);
+        referenced.sort_unstable();
+        let mut cursor = header.data_start;
+        for (offset, length) in referenced {
+            if offset >= toc_offset {
+                break;
+            }
+            if offset > cursor {
+                probe.orphaned.push((cursor, offset - cursor));
+            }
+            cursor = cursor.max(offset.saturating_add(length));
+        }
+        if toc_offset > cursor {
+            probe.orphaned.push((cursor, toc_offset - cursor));
+        }
+
+        let reclaimable: u64 = probe.orphaned.iter().map(|(_, length)| length).sum();
+        if reclaimable > 0 {
+            doctor_log!(
+                "doctor: {} orphaned bytes in {} ranges",
+                reclaimable,
+                probe.orphaned.len()
+            );
+            probe.findings.push(DoctorFinding::info(
+                DoctorFindingCode::OrphanedBytes,
+                format!(
+                    "{reclaimable} bytes in {} ranges are no longer referenced",
+                    probe.orphaned.len()
+                ),
+            ));
+        }
+    }
+
     fn inspect_time_index(&self, probe: &mut PlanProbe, file: &mut std::fs::File) {
         let Some(toc) = probe.toc.as_ref() else {
             return;
@@ -748,9 +966,18 @@ impl DoctorPlanner {
                     ));
                     continue;
                 }
-                if let Err(err) =
-                    VecIndex::decode_with_compression(&buf, segment.vector_compression.clone())
-                {
+                let decoded = segment
+                    .common
+                    .compression
+                    .decompress(&buf)
+                    .map_err(MemvidError::from)
+                    .and_then(|bytes| {
+                        VecIndex::decode_with_compression(
+                            &bytes,
+                            segment.vector_compression.clone(),
+                        )
+                    });
+                if let Err(err) = decoded {
                     probe.index.needs_vec = true;
                     probe.findings.push(DoctorFinding::warning(
                         DoctorFindingCode::VecIndexCorrupt,
@@ -854,7 +1081,11 @@ impl DoctorExecutor {
     fn run(self) -> Result<DoctorReport> {
         doctor_log!("doctor: starting executor");
         let DoctorExecutor { path, plan } = self;
-        let mut metrics = DoctorMetrics::default();
+        let mut metrics = DoctorMetrics {
+            reclaimable_bytes: plan.reclaimable_bytes,
+            ..DoctorMetrics::default()
+        };
+        let initial_len = std::fs::metadata(&path).map_or(0, |meta| meta.len());
         let mut phase_reports = Vec::new();
         let mut additional_findings = Vec::new();
         let mut verification: Option<VerificationReport> = None;
@@ -868,6 +1099,7 @@ impl DoctorExecutor {
                 ),
             ));
             return Ok(DoctorReport {
+                version: DOCTOR_REPORT_VERSION,
                 plan,
                 status: DoctorStatus::Failed,
                 phases: phase_reports,
@@ -903,6 +1135,7 @@ impl DoctorExecutor {
                         format!("WAL corrupted and recovery failed: {err}"),
                     ));
                     return Ok(DoctorReport {
+                        version: DOCTOR_REPORT_VERSION,
                         plan,
                         status: DoctorStatus::Failed,
                         phases: phase_reports,
@@ -943,6 +1176,7 @@ impl DoctorExecutor {
                                             format!("Aggressive repair succeeded but file still corrupt: {retry_err}"),
                                         ));
                                         return Ok(DoctorReport {
+                                            version: DOCTOR_REPORT_VERSION,
                                             plan,
                                             status: DoctorStatus::Failed,
                                             phases: phase_reports,
@@ -960,6 +1194,7 @@ impl DoctorExecutor {
                                     format!("Aggressive repair failed: {repair_err}"),
                                 ));
                                 return Ok(DoctorReport {
+                                    version: DOCTOR_REPORT_VERSION,
                                     plan,
                                     status: DoctorStatus::Failed,
                                     phases: phase_reports,
@@ -976,6 +1211,7 @@ impl DoctorExecutor {
                             err.to_string(),
                         ));
                         return Ok(DoctorReport {
+                            version: DOCTOR_REPORT_VERSION,
                             plan,
                             status: DoctorStatus::Failed,
                             phases: phase_reports,
@@ -1260,6 +1496,10 @@ impl DoctorExecutor {
         }
 
         metrics.total_duration_ms = start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+        if !overall_failed {
+            let final_len = std::fs::metadata(&path).map_or(initial_len, |meta| meta.len());
+            metrics.reclaimed_bytes = initial_len.saturating_sub(final_len);
+        }
 
         if overall_failed {
             if let Some(original) = &original_header {
@@ -1283,6 +1523,7 @@ impl DoctorExecutor {
         };
 
         Ok(DoctorReport {
+            version: DOCTOR_REPORT_VERSION,
             plan,
             status,
             phases: phase_reports,
@@ -1374,6 +1615,24 @@ impl DoctorExecutor {
                     detail: Some("scheduled vector index rebuild".into()),
                 })
             }
+            DoctorActionKind::SupersedeDuplicates => {
+                let superseded = mem.supersede_exact_duplicates()?;
+                if superseded == 0 {
+                    return Ok(DoctorActionReport {
+                        action: action.action,
+                        status: DoctorActionStatus::Skipped,
+                        detail: Some("no exact duplicates".into()),
+                    });
+                }
+                // Rebuild from the frame list so the on-disk indexes drop superseded frames.
+                *pending_time = true;
+                *pending_lex |= mem.lex_enabled;
+                Ok(DoctorActionReport {
+                    action: action.action,
+                    status: DoctorActionStatus::Executed,
+                    detail: Some(format!("superseded {superseded} duplicate frames")),
+                })
+            }
             DoctorActionKind::VacuumCompaction => {
                 mem.vacuum()?;
                 Ok(DoctorActionReport {
@@ -1382,6 +1641,17 @@ impl DoctorExecutor {
                     detail: Some("vacuum completed".into()),
                 })
             }
+            DoctorActionKind::ReclaimOrphans => {
+                // Vacuum rewrites payloads and indexes contiguously; tracks persisted after the
+                // old footer are then pulled back behind them.
+                mem.vacuum()?;
+                mem.compact_tail_tracks()?;
+                Ok(DoctorActionReport {
+                    action: action.action,
+                    status: DoctorActionStatus::Executed,
+                    detail: Some("compacted orphaned bytes".into()),
+                })
+            }
             DoctorActionKind::RecomputeToc => {
                 // Force a commit even if no WAL records are pending, so the TOC trailer and
                 // commit footer are rewritten and the header stays consistent.
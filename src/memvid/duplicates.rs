@@ -0,0 +1,200 @@
+//! Duplicate and near-duplicate detection for `Memvid`.
+//!
+//! Exact duplicates share a payload checksum or source file hash. Near duplicates are found
+//! through the sketch track: frames whose `SimHash` values differ in at most `threshold` bits.
+//! Candidate pairs come from splitting each `SimHash` into `threshold + 1` bands — two hashes
+//! within the threshold must agree on at least one band — so only frames sharing a band are
+//! compared.
+
+use std::collections::HashMap;
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::blob_extents::stored_in_extents;
+use crate::types::{
+    DuplicateCluster, DuplicateKind, Frame, FrameId, FrameStatus, SketchEntry, SketchFlags,
+};
+
+impl Memvid {
+    /// Cluster active frames holding the same or nearly the same content.
+    ///
+    /// Frames with equal payload checksums or source hashes always cluster together; frames
+    /// with sketches also cluster when their `SimHash` distance is at most `threshold` bits.
+    /// Frames without a sketch (see [`Memvid::build_all_sketches`]) or without any tokens only
+    /// take part in exact matching. Clusters are returned largest first.
+    #[must_use]
+    pub fn find_duplicates(&self, threshold: u32) -> Vec<DuplicateCluster> {
+        let frames: Vec<&Frame> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.status == FrameStatus::Active
+                    && (frame.payload_length > 0 || stored_in_extents(frame))
+            })
+            .collect();
+        let mut sets = DisjointSets::new(frames.len());
+
+        let mut by_checksum: HashMap<[u8; 32], usize> = HashMap::new();
+        let mut by_source: HashMap<[u8; 32], usize> = HashMap::new();
+        for (index, frame) in frames.iter().enumerate() {
+            let first = *by_checksum.entry(frame.checksum).or_insert(index);
+            sets.union(first, index);
+            if let Some(source) = frame.source_sha256 {
+                let first = *by_source.entry(source).or_insert(index);
+                sets.union(first, index);
+            }
+        }
+        let exact_roots: Vec<usize> = (0..frames.len()).map(|index| sets.find(index)).collect();
+
+        let sketches: Vec<Option<&SketchEntry>> = frames
+            .iter()
+            .map(|frame| {
+                self.sketch_track
+                    .get(frame.id)
+                    .filter(|entry| entry.flags.has(SketchFlags::HAS_SIMHASH))
+            })
+            .collect();
+        let bands = threshold.saturating_add(1).min(64);
+        let mut buckets: HashMap<(u32, u64), Vec<usize>> = HashMap::new();
+        for (index, entry) in sketches.iter().enumerate() {
+            if let Some(entry) = entry {
+                for band in 0..bands {
+                    buckets
+                        .entry((band, band_bits(entry.simhash, band, bands)))
+                        .or_default()
+                        .push(index);
+                }
+            }
+        }
+        for members in buckets.values() {
+            for (position, &left) in members.iter().enumerate() {
+                for &right in &members[position + 1..] {
+                    if sets.find(left) == sets.find(right) {
+                        continue;
+                    }
+                    if let (Some(a), Some(b)) = (sketches[left], sketches[right]) {
+                        if a.hamming_distance(b.simhash) <= threshold {
+                            sets.union(left, right);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for index in 0..frames.len() {
+            groups.entry(sets.find(index)).or_default().push(index);
+        }
+        let mut clusters: Vec<DuplicateCluster> = groups
+            .into_values()
+            .filter(|members| members.len() > 1)
+            .filter_map(|members| {
+                let canonical = *members
+                    .iter()
+                    .min_by_key(|&&index| (frames[index].timestamp, frames[index].id))?;
+                let kind = if members
+                    .iter()
+                    .all(|&index| exact_roots[index] == exact_roots[canonical])
+                {
+                    DuplicateKind::Exact
+                } else {
+                    DuplicateKind::Near
+                };
+                let max_distance = sketches[canonical].map_or(0, |anchor| {
+                    members
+                        .iter()
+                        .filter_map(|&index| sketches[index])
+                        .map(|entry| entry.hamming_distance(anchor.simhash))
+                        .max()
+                        .unwrap_or(0)
+                });
+                let uri = frames[canonical].uri.clone().filter(|uri| {
+                    members
+                        .iter()
+                        .all(|&index| frames[index].uri.as_deref() == Some(uri.as_str()))
+                });
+                let mut frame_ids: Vec<FrameId> =
+                    members.iter().map(|&index| frames[index].id).collect();
+                frame_ids.sort_unstable();
+                Some(DuplicateCluster {
+                    kind,
+                    canonical: frames[canonical].id,
+                    frame_ids,
+                    max_distance,
+                    uri,
+                })
+            })
+            .collect();
+        clusters.sort_by(|a, b| {
+            b.frame_ids
+                .len()
+                .cmp(&a.frame_ids.len())
+                .then(a.canonical.cmp(&b.canonical))
+        });
+        clusters
+    }
+
+    /// Mark every non-canonical member of an exact duplicate cluster as superseded by its
+    /// canonical frame, returning how many frames changed. Indexes are updated in memory only;
+    /// callers rebuild and commit them.
+    pub(crate) fn supersede_exact_duplicates(&mut self) -> Result<usize> {
+        self.ensure_mutation_allowed()?;
+        self.ensure_not_held("supersede duplicates")?;
+        let mut superseded = 0;
+        for cluster in self.find_duplicates(0) {
+            if cluster.kind != DuplicateKind::Exact {
+                continue;
+            }
+            for frame_id in cluster.duplicates() {
+                self.mark_frame_superseded(frame_id, cluster.canonical)?;
+                superseded += 1;
+            }
+        }
+        if superseded > 0 {
+            self.dirty = true;
+        }
+        Ok(superseded)
+    }
+}
+
+/// Bits of `simhash` belonging to `band` when 64 bits are split into `bands` contiguous bands.
+fn band_bits(simhash: u64, band: u32, bands: u32) -> u64 {
+    let start = band * 64 / bands;
+    let end = (band + 1) * 64 / bands;
+    let width = end - start;
+    let mask = if width >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    };
+    (simhash >> start) & mask
+}
+
+/// Union-find over frame positions.
+struct DisjointSets {
+    parent: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parent[index] != index {
+            self.parent[index] = self.parent[self.parent[index]];
+            index = self.parent[index];
+        }
+        index
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent[root_a.max(root_b)] = root_a.min(root_b);
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Re-embedding every vector when the embedding model changes.
+//!
+//! `migrate_embeddings` re-embeds frames in batches. Each batch is written as a staged vec
+//! segment beside the live index (see [`crate::types::embedding_migration`]) and committed, so
+//! searches keep using the old vectors while the migration runs. Frames still waiting are kept
+//! in the enrichment queue; rerunning the migration after an interruption picks up from there.
+//! Once every frame is staged, one commit replaces the old index with the staged vectors and
+//! rebinds the vec model. Doctor rebuilds and vacuum discard staged segments; the next run then
+//! starts over.
+
+use std::collections::HashSet;
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    EMBEDDING_MIGRATION_EXTENSION, EmbeddingIdentity, EmbeddingMigrationReport,
+    EmbeddingMigrationState, EmbeddingProvider, FrameId, FrameRole, FrameStatus, StagedVecSegment,
+};
+use crate::vec::{VecIndex, VecIndexBuilder};
+
+/// Frames re-embedded per commit by [`Memvid::migrate_embeddings`].
+pub const DEFAULT_MIGRATION_BATCH: usize = 64;
+
+impl Memvid {
+    /// Re-embed every vector in `old_identity`'s space with `new_provider` and swap the vec
+    /// index once all frames are done.
+    ///
+    /// See [`Memvid::migrate_embeddings_with_progress`].
+    pub fn migrate_embeddings(
+        &mut self,
+        old_identity: &EmbeddingIdentity,
+        new_provider: &dyn EmbeddingProvider,
+    ) -> Result<EmbeddingMigrationReport> {
+        self.migrate_embeddings_with_progress(
+            old_identity,
+            new_provider,
+            DEFAULT_MIGRATION_BATCH,
+            |_| {},
+        )
+    }
+
+    /// Re-embed every vector in `old_identity`'s space with `new_provider`, committing `batch`
+    /// frames at a time and calling `on_progress` after each commit.
+    ///
+    /// Frames with no text to embed lose their vector. Calling this again after an error
+    /// resumes the migration; calling it with a different target while one is in progress
+    /// fails until [`Memvid::abort_embedding_migration`] is called.
+    pub fn migrate_embeddings_with_progress<F>(
+        &mut self,
+        old_identity: &EmbeddingIdentity,
+        new_provider: &dyn EmbeddingProvider,
+        batch: usize,
+        mut on_progress: F,
+    ) -> Result<EmbeddingMigrationReport>
+    where
+        F: FnMut(&EmbeddingMigrationReport),
+    {
+        self.ensure_writable()?;
+        self.commit()?;
+        self.ensure_vec_index()?;
+        let target = EmbeddingIdentity::of_provider(new_provider);
+        let dimension = new_provider.dimension();
+
+        let mut state = match self.embedding_migration()? {
+            Some(state) if state.target != target || state.source != *old_identity => {
+                return Err(MemvidError::InvalidQuery {
+                    reason: "another embedding migration is in progress; abort it first".into(),
+                });
+            }
+            Some(state) => state,
+            None => {
+                self.check_source_identity(old_identity)?;
+                EmbeddingMigrationState {
+                    source: old_identity.clone(),
+                    target: target.clone(),
+                    frames: Vec::new(),
+                    data_start: self.header.data_start,
+                    segments: Vec::new(),
+                    started_at: unix_now(),
+                }
+            }
+        };
+
+        let staged = match self.staged_vectors(&state) {
+            Ok(vectors) => vectors.into_iter().map(|(id, _)| id).collect(),
+            Err(err) => {
+                tracing::warn!(?err, "staged migration vectors unreadable; restarting");
+                state.segments.clear();
+                state.data_start = self.header.data_start;
+                HashSet::new()
+            }
+        };
+        // Frames that gained an old-space vector since the migration started join it too.
+        let known: HashSet<FrameId> = state.frames.iter().copied().collect();
+        let joined: Vec<FrameId> = self
+            .migration_candidates(old_identity)
+            .into_iter()
+            .filter(|id| !known.contains(id))
+            .collect();
+        state.frames.extend(joined);
+        state.frames.retain(|&id| self.frame_is_active(id));
+
+        let pending: Vec<FrameId> = state
+            .frames
+            .iter()
+            .copied()
+            .filter(|id| !staged.contains(id))
+            .collect();
+        for &frame_id in &pending {
+            let queue = &mut self.toc.enrichment_queue;
+            if !queue.tasks.iter().any(|task| task.frame_id == frame_id) {
+                queue.push(frame_id);
+            }
+        }
+
+        let mut report = EmbeddingMigrationReport {
+            frames_total: state.frames.len(),
+            resumed: state.frames.len() - pending.len(),
+            migrated: state.frames.len() - pending.len(),
+            ..EmbeddingMigrationReport::default()
+        };
+        self.toc
+            .set_extension(EMBEDDING_MIGRATION_EXTENSION, &state)?;
+        self.dirty = true;
+        self.commit()?;
+
+        for ids in pending.chunks(batch.max(1)) {
+            let mut frame_ids = Vec::with_capacity(ids.len());
+            let mut texts = Vec::with_capacity(ids.len());
+            for &frame_id in ids {
+                let frame = self.frame_by_id(frame_id)?;
+                let text = self.frame_search_text(&frame)?;
+                if text.trim().is_empty() {
+                    state.frames.retain(|&id| id != frame_id);
+                    report.frames_total -= 1;
+                } else {
+                    frame_ids.push(frame_id);
+                    texts.push(text);
+                }
+            }
+            if !frame_ids.is_empty() {
+                let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+                let embeddings = new_provider.embed_batch(&refs)?;
+                if embeddings.len() != frame_ids.len() {
+                    return Err(MemvidError::EmbeddingFailed {
+                        reason: format!(
+                            "provider returned {} embeddings for {} texts",
+                            embeddings.len(),
+                            frame_ids.len()
+                        )
+                        .into(),
+                    });
+                }
+                let mut builder = VecIndexBuilder::new();
+                for (&frame_id, embedding) in frame_ids.iter().zip(embeddings) {
+                    if embedding.len() != dimension {
+                        return Err(MemvidError::VecDimensionMismatch {
+                            expected: u32::try_from(dimension).unwrap_or(u32::MAX),
+                            actual: embedding.len(),
+                        });
+                    }
+                    builder.add_document(frame_id, embedding);
+                }
+                let artifact = builder.finish()?;
+                state.segments.push(self.append_staged_segment(
+                    &state,
+                    &artifact.bytes,
+                    artifact.vector_count,
+                )?);
+            }
+            for &frame_id in ids {
+                self.toc.enrichment_queue.remove(frame_id);
+            }
+            self.toc
+                .set_extension(EMBEDDING_MIGRATION_EXTENSION, &state)?;
+            self.dirty = true;
+            self.commit()?;
+
+            report.migrated += frame_ids.len();
+            report.batches += 1;
+            on_progress(&report);
+        }
+
+        self.finish_embedding_migration(&state)?;
+        report.completed = true;
+        on_progress(&report);
+        Ok(report)
+    }
+
+    /// State of the embedding migration in progress, if any.
+    pub fn embedding_migration(&self) -> Result<Option<EmbeddingMigrationState>> {
+        self.toc
+            .extension::<EmbeddingMigrationState>(EMBEDDING_MIGRATION_EXTENSION)
+    }
+
+    /// Drop an unfinished embedding migration and keep the current vectors. Staged segments are
+    /// reclaimed by the next vacuum.
+    pub fn abort_embedding_migration(&mut self) -> Result<()> {
+        self.ensure_writable()?;
+        let Some(state) = self.embedding_migration()? else {
+            return Ok(());
+        };
+        for &frame_id in &state.frames {
+            self.toc.enrichment_queue.remove(frame_id);
+        }
+        self.toc.extensions.remove(EMBEDDING_MIGRATION_EXTENSION);
+        self.dirty = true;
+        self.commit()
+    }
+
+    fn check_source_identity(&self, old_identity: &EmbeddingIdentity) -> Result<()> {
+        if let (Some(expected), Some(actual)) = (&self.vec_model, old_identity.model.as_deref()) {
+            if expected != actual {
+                return Err(MemvidError::ModelMismatch {
+                    expected: expected.clone(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+        if let (Some(expected), Some(actual)) = (
+            self.effective_vec_index_dimension()?,
+            old_identity.dimension,
+        ) {
+            if expected != actual {
+                return Err(MemvidError::VecDimensionMismatch {
+                    expected,
+                    actual: actual as usize,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Active frames with a vector in the current index or an identity matching `identity`.
+    fn migration_candidates(&self, identity: &EmbeddingIdentity) -> Vec<FrameId> {
+        self.toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.status == FrameStatus::Active && frame.role != FrameRole::ExtractedImage
+            })
+            .filter(|frame| {
+                self.vec_index
+                    .as_ref()
+                    .is_some_and(|index| index.contains(frame.id))
+                    || EmbeddingIdentity::from_extra_metadata(&frame.extra_metadata)
+                        .is_some_and(|recorded| recorded.is_compatible_with(identity))
+            })
+            .map(|frame| frame.id)
+            .collect()
+    }
+
+    /// Append a staged segment after the committed footer and reserve it in the payload region.
+    fn append_staged_segment(
+        &mut self,
+        state: &EmbeddingMigrationState,
+        bytes: &[u8],
+        vector_count: u64,
+    ) -> Result<StagedVecSegment> {
+        let offset = self.file.metadata()?.len();
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.write_all(bytes)?;
+        let end = offset + bytes.len() as u64;
+        self.cached_payload_end = self.cached_payload_end.max(end);
+        self.data_end = self.data_end.max(end);
+        self.header.footer_offset = self.header.footer_offset.max(end);
+        Ok(StagedVecSegment {
+            offset: offset - state.shift(&self.header),
+            length: bytes.len() as u64,
+            vector_count,
+            hash: *blake3::hash(bytes).as_bytes(),
+        })
+    }
+
+    fn staged_vectors(
+        &mut self,
+        state: &EmbeddingMigrationState,
+    ) -> Result<Vec<(FrameId, Vec<f32>)>> {
+        let shift = state.shift(&self.header);
+        let mut vectors = Vec::new();
+        for segment in &state.segments {
+            let bytes = self.read_range(segment.offset + shift, segment.length)?;
+            if blake3::hash(&bytes).as_bytes() != &segment.hash {
+                return Err(MemvidError::InvalidToc {
+                    reason: "staged embedding migration segment is damaged".into(),
+                });
+            }
+            let index = VecIndex::decode(&bytes)?;
+            vectors.extend(
+                index
+                    .entries()
+                    .map(|(frame_id, embedding)| (frame_id, embedding.to_vec())),
+            );
+        }
+        Ok(vectors)
+    }
+
+    /// Replace the vec index with the staged vectors in a single commit.
+    fn finish_embedding_migration(&mut self, state: &EmbeddingMigrationState) -> Result<()> {
+        let mut vectors = self.staged_vectors(state)?;
+        vectors.retain(|(frame_id, _)| self.frame_is_active(*frame_id));
+        for (frame_id, _) in &vectors {
+            self.stamp_embedding_identity(*frame_id, &state.target);
+        }
+        for &frame_id in &state.frames {
+            self.toc.enrichment_queue.remove(frame_id);
+        }
+        self.vec_index = None;
+        self.vec_model = state.target.model.as_deref().map(str::to_string);
+        self.toc.indexes.vec = None;
+        self.toc.segment_catalog.vec_segments.clear();
+        self.enable_vec()?;
+        self.pending_embeddings = vectors;
+        self.toc.extensions.remove(EMBEDDING_MIGRATION_EXTENSION);
+        self.dirty = true;
+        self.commit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Provider {
+        model: &'static str,
+        dimension: usize,
+        /// Fail once this many batches have been embedded.
+        fail_after: Option<usize>,
+        calls: AtomicUsize,
+    }
+
+    impl Provider {
+        fn new(model: &'static str, dimension: usize, fail_after: Option<usize>) -> Self {
+            Self {
+                model,
+                dimension,
+                fail_after,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl EmbeddingProvider for Provider {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn kind(&self) -> &str {
+            "mock"
+        }
+
+        fn model(&self) -> &str {
+            self.model
+        }
+
+        fn dimension(&self) -> usize {
+            self.dimension
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+            let mut embedding = vec![0.5; self.dimension];
+            embedding[0] = text.len() as f32;
+            Ok(embedding)
+        }
+
+        fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail_after.is_some_and(|limit| call >= limit) {
+                return Err(MemvidError::EmbeddingFailed {
+                    reason: "provider offline".into(),
+                });
+            }
+            texts.iter().map(|text| self.embed_text(text)).collect()
+        }
+    }
+
+    #[test]
+    fn migration_resumes_and_swaps_index() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("migrate.mv2");
+        let old = Provider::new("old-v1", 4, None);
+        let mut mem = Memvid::create(&path).expect("create");
+        for text in ["alpha", "beta", "gamma", "delta", "epsilon"] {
+            mem.put_bytes(text.as_bytes()).expect("put");
+        }
+        mem.backfill_embeddings(&old, 8).expect("backfill");
+        let old_identity = EmbeddingIdentity::of_provider(&old);
+
+        let flaky = Provider::new("new-v2", 6, Some(1));
+        let err = mem
+            .migrate_embeddings_with_progress(&old_identity, &flaky, 2, |_| {})
+            .expect_err("provider fails");
+        assert!(matches!(err, MemvidError::EmbeddingFailed { .. }));
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        let state = mem
+            .embedding_migration()
+            .expect("state")
+            .expect("in progress");
+        assert_eq!(state.staged_vectors(), 2);
+        assert_eq!(mem.enrichment_queue_len(), 3);
+        assert_eq!(mem.effective_vec_index_dimension().expect("dim"), Some(4));
+        let other = Provider::new("other", 3, None);
+        assert!(mem.migrate_embeddings(&old_identity, &other).is_err());
+
+        let new = Provider::new("new-v2", 6, None);
+        let report = mem
+            .migrate_embeddings_with_progress(&old_identity, &new, 2, |_| {})
+            .expect("resume");
+        assert!(report.completed);
+        assert_eq!(report.frames_total, 5);
+        assert_eq!(report.resumed, 2);
+        assert_eq!(report.migrated, 5);
+        assert!(mem.embedding_migration().expect("state").is_none());
+        assert_eq!(mem.enrichment_queue_len(), 0);
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        assert_eq!(mem.vector_count(), 5);
+        assert_eq!(mem.effective_vec_index_dimension().expect("dim"), Some(6));
+        let embedding = mem.frame_embedding(4).expect("embedding").expect("vector");
+        assert_eq!(embedding.len(), 6);
+        assert!(mem.set_vec_model("old-v1").is_err());
+    }
+}
@@ -5,17 +5,29 @@
 //! - Process enrichment queue
 //! - Full text re-extraction for skim frames
 //! - Embedding generation
+//! - Priority scheduling, pause/resume/drain, and rate limiting
+//! - Per-stage progress, so a restarted worker resumes where it stopped
+//! - Custom enrichment engines registered on the worker configuration
 
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
+use std::time::Duration;
 
+use crate::enrich::EngineRegistry;
 use crate::enrichment_worker::{
     EmbeddingBatcher, EnrichmentWorkerConfig, EnrichmentWorkerHandle, EnrichmentWorkerStats,
     TaskResult,
 };
-use crate::error::Result;
+use crate::error::{MemvidError, Result};
 use crate::extract_budgeted::ExtractionBudget;
-use crate::types::{EnrichmentState, EnrichmentTask, FrameId, FrameStatus, VecEmbedder};
+use crate::triplet::TripletExtractor;
+use crate::types::{
+    AccessStats, ENRICHMENT_PRIORITY_EXTENSION, EngineStamp, EnrichmentEvent, EnrichmentPriorities,
+    EnrichmentPriority, EnrichmentStage, EnrichmentState, EnrichmentTask, FrameId, FrameStatus,
+    MemoryCardId, VecEmbedder,
+};
 use crate::vec::VecIndexBuilder;
 
 use super::Memvid;
@@ -24,8 +36,8 @@ use super::Memvid;
 pub struct EnrichmentHandle {
     /// Control handle for the worker.
     pub handle: EnrichmentWorkerHandle,
-    /// Thread join handle.
-    thread: Option<JoinHandle<()>>,
+    /// Worker thread join handles.
+    threads: Vec<JoinHandle<()>>,
 }
 
 impl EnrichmentHandle {
@@ -33,10 +45,40 @@ impl EnrichmentHandle {
     #[must_use]
     pub fn stop_and_wait(mut self) -> EnrichmentWorkerStats {
         self.handle.stop();
-        if let Some(thread) = self.thread.take() {
+        self.join();
+        self.handle.stats()
+    }
+
+    /// Let the worker finish every schedulable task, then wait for it to stop.
+    ///
+    /// Tasks of deferred kinds stay queued. A paused worker is resumed first.
+    #[must_use]
+    pub fn drain(mut self) -> EnrichmentWorkerStats {
+        self.handle.drain();
+        self.join();
+        self.handle.stats()
+    }
+
+    /// Hold the worker between tasks, e.g. while interactive work needs the memory.
+    pub fn pause(&self) {
+        self.handle.pause();
+    }
+
+    /// Let a paused worker pick up tasks again.
+    pub fn resume(&self) {
+        self.handle.resume();
+    }
+
+    /// Check if the worker is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.handle.is_paused()
+    }
+
+    fn join(&mut self) {
+        for thread in self.threads.drain(..) {
             let _ = thread.join();
         }
-        self.handle.stats()
     }
 
     /// Check if worker is still running.
@@ -88,58 +130,68 @@ pub fn start_enrichment_worker(
     config: Option<EnrichmentWorkerConfig>,
 ) -> EnrichmentHandle {
     let config = config.unwrap_or_default();
-    let handle = EnrichmentWorkerHandle::new();
-    let worker_handle = handle.clone_handle();
-
-    let memvid_clone = Arc::clone(&memvid);
-    let config_clone = config.clone();
-
-    let thread = std::thread::spawn(move || {
-        crate::enrichment_worker::run_worker_loop(
-            &worker_handle,
-            &config_clone,
-            // get_next_task
-            || {
-                let mv = memvid_clone.lock().ok()?;
-                mv.next_enrichment_task()
-            },
-            // process_task
-            |task| {
-                let mut mv = match memvid_clone.lock() {
-                    Ok(mv) => mv,
-                    Err(_) => {
-                        return TaskResult {
-                            frame_id: task.frame_id,
-                            re_extracted: false,
-                            embeddings_generated: 0,
-                            elapsed_ms: 0,
-                            error: Some("Failed to acquire lock".to_string()),
+    let mut handle = EnrichmentWorkerHandle::new();
+    handle.set_rate_limit(config.rate_limit);
+    // Frames claimed by a worker thread and not yet completed.
+    let in_flight = Arc::new(Mutex::new(HashSet::new()));
+
+    let threads = (0..config.concurrency.max(1))
+        .map(|_| {
+            let worker_handle = handle.clone_handle();
+            let memvid = Arc::clone(&memvid);
+            let config = config.clone();
+            let in_flight = Arc::clone(&in_flight);
+            std::thread::spawn(move || {
+                crate::enrichment_worker::run_worker_loop(
+                    &worker_handle,
+                    &config,
+                    // get_next_task
+                    || {
+                        let mv = memvid.lock().ok()?;
+                        let mut claimed = in_flight.lock().ok()?;
+                        let task = mv.next_scheduled_enrichment_task(&config, &claimed)?;
+                        claimed.insert(task.frame_id);
+                        Some(task)
+                    },
+                    // process_task
+                    |task| {
+                        let mut mv = match memvid.lock() {
+                            Ok(mv) => mv,
+                            Err(_) => {
+                                return TaskResult {
+                                    frame_id: task.frame_id,
+                                    re_extracted: false,
+                                    embeddings_generated: 0,
+                                    elapsed_ms: 0,
+                                    error: Some("Failed to acquire lock".to_string()),
+                                };
+                            }
                         };
-                    }
-                };
-                mv.process_enrichment_task(task)
-            },
-            // mark_complete
-            |frame_id| {
-                if let Ok(mut mv) = memvid_clone.lock() {
-                    mv.complete_enrichment_task(frame_id);
-                }
-            },
-            // checkpoint
-            || {
-                if let Ok(mut mv) = memvid_clone.lock() {
-                    if let Err(err) = mv.commit() {
-                        tracing::warn!(?err, "enrichment checkpoint commit failed");
-                    }
-                }
-            },
-        );
-    });
+                        mv.process_enrichment_task_with_engines(task, &config.engines)
+                    },
+                    // mark_complete
+                    |frame_id| {
+                        if let Ok(mut mv) = memvid.lock() {
+                            mv.complete_enrichment_task(frame_id);
+                        }
+                        if let Ok(mut claimed) = in_flight.lock() {
+                            claimed.remove(&frame_id);
+                        }
+                    },
+                    // checkpoint
+                    || {
+                        if let Ok(mut mv) = memvid.lock() {
+                            if let Err(err) = mv.commit() {
+                                tracing::warn!(?err, "enrichment checkpoint commit failed");
+                            }
+                        }
+                    },
+                );
+            })
+        })
+        .collect();
 
-    EnrichmentHandle {
-        handle,
-        thread: Some(thread),
-    }
+    EnrichmentHandle { handle, threads }
 }
 
 /// Start a background enrichment worker with embedding generation.
@@ -163,40 +215,120 @@ where
     E: VecEmbedder + Send + 'static,
 {
     let config = config.unwrap_or_default();
-    let handle = EnrichmentWorkerHandle::new();
+    let mut handle = EnrichmentWorkerHandle::new();
+    handle.set_rate_limit(config.rate_limit);
     let worker_handle = handle.clone_handle();
-    let batch_size = config.embedding_batch_size;
+    let batch_size = config.embedding_batch_size.max(1);
 
     let thread = std::thread::spawn(move || {
         worker_handle.set_running(true);
         tracing::info!("enrichment worker with embeddings started");
 
-        // Process all enrichment with embeddings
-        match memvid.lock() {
-            Ok(mut mv) => {
-                match mv.process_enrichment_with_embeddings(embedder, batch_size) {
-                    Ok((frames, embeddings)) => {
-                        // Update stats
-                        for _ in 0..frames {
-                            worker_handle.inc_frames_processed();
-                        }
-                        worker_handle.inc_embeddings(embeddings as u64);
+        let mut batcher = EmbeddingBatcher::new(embedder, batch_size);
+        let mut since_checkpoint = 0;
+        // The memory is locked per batch, and not while the embedder runs, so readers and
+        // writers get a turn between batches.
+        while !worker_handle.should_stop() {
+            if worker_handle.is_paused() {
+                std::thread::sleep(Duration::from_millis(config.task_delay_ms * 10));
+                continue;
+            }
 
-                        // Commit changes
-                        if let Err(err) = mv.commit() {
-                            tracing::warn!(?err, "final commit failed");
-                            worker_handle.inc_errors();
-                        }
-                    }
-                    Err(err) => {
-                        tracing::error!(?err, "enrichment with embeddings failed");
-                        worker_handle.inc_errors();
-                    }
+            let (tasks, prepared) = match memvid.lock() {
+                Ok(mut mv) => {
+                    let tasks = mv.next_enrichment_batch(&config, batch_size);
+                    let prepared = mv.prepare_enrichment_batch(&tasks);
+                    (tasks, prepared)
+                }
+                Err(err) => {
+                    tracing::error!(?err, "failed to acquire lock for enrichment");
+                    worker_handle.inc_errors();
+                    break;
                 }
+            };
+            if tasks.is_empty() {
+                break;
+            }
+
+            let texts = texts_to_embed(&prepared);
+            if !texts.is_empty() {
+                worker_handle.wait_for_rate_limit();
             }
-            Err(err) => {
-                tracing::error!(?err, "failed to acquire lock for enrichment");
+            let mut retry_after = None;
+            let embeddings = match embed_batch(&mut batcher, texts) {
+                Ok(embeddings) => embeddings,
+                Err(MemvidError::EmbedderUnavailable {
+                    reason,
+                    retry_after_ms,
+                }) => {
+                    tracing::warn!(%reason, retry_after_ms, "embedder unavailable, deferring frames");
+                    retry_after = Some(Duration::from_millis(retry_after_ms));
+                    Vec::new()
+                }
+                Err(err) => {
+                    tracing::warn!(?err, "batch embedding failed");
+                    worker_handle.inc_errors();
+                    Vec::new()
+                }
+            };
+            worker_handle.inc_embeddings(embeddings.len() as u64);
+            worker_handle.set_embedder_stats(batcher.request_stats());
+            // While the embedder is unavailable, frames waiting on it stay queued for a later
+            // run instead of completing without embeddings.
+            let (tasks, prepared) = if retry_after.is_some() {
+                let (deferred, prepared): (Vec<_>, Vec<_>) =
+                    prepared.into_iter().partition(awaits_embedding);
+                worker_handle.inc_deferred(deferred.len() as u64);
+                let deferred: HashSet<FrameId> =
+                    deferred.iter().map(|frame| frame.frame_id).collect();
+                let tasks: Vec<EnrichmentTask> = tasks
+                    .into_iter()
+                    .filter(|task| !deferred.contains(&task.frame_id))
+                    .collect();
+                (tasks, prepared)
+            } else {
+                (tasks, prepared)
+            };
+
+            let Ok(mut mv) = memvid.lock() else {
                 worker_handle.inc_errors();
+                break;
+            };
+            for _ in &prepared {
+                worker_handle.inc_frames_processed();
+            }
+            mv.finish_enrichment_batch(&tasks, prepared, embeddings, &config.engines);
+            since_checkpoint += tasks.len();
+            if since_checkpoint >= config.checkpoint_interval {
+                if let Err(err) = mv.commit() {
+                    tracing::warn!(?err, "enrichment checkpoint commit failed");
+                    worker_handle.inc_errors();
+                }
+                since_checkpoint = 0;
+            }
+            drop(mv);
+
+            if let Some(delay) = retry_after {
+                if worker_handle.is_draining() {
+                    break;
+                }
+                sleep_unless_stopped(&worker_handle, delay);
+            }
+            std::thread::sleep(Duration::from_millis(config.task_delay_ms));
+        }
+
+        if since_checkpoint > 0 {
+            match memvid.lock() {
+                Ok(mut mv) => {
+                    if let Err(err) = mv.commit() {
+                        tracing::warn!(?err, "final commit failed");
+                        worker_handle.inc_errors();
+                    }
+                }
+                Err(err) => {
+                    tracing::error!(?err, "failed to acquire lock for final commit");
+                    worker_handle.inc_errors();
+                }
             }
         }
 
@@ -210,7 +342,55 @@ where
 
     EnrichmentHandle {
         handle,
-        thread: Some(thread),
+        threads: vec![thread],
+    }
+}
+
+/// Frame text after the extraction stage.
+struct ExtractedFrame {
+    frame_id: FrameId,
+    text: String,
+    is_skim: bool,
+    /// Full text was re-extracted by this run.
+    re_extracted: bool,
+    /// The frame still needs embeddings: it has none and no earlier run embedded it.
+    needs_embedding: bool,
+}
+
+/// Whether `frame` has text that still needs embedding.
+fn awaits_embedding(frame: &ExtractedFrame) -> bool {
+    frame.needs_embedding && !frame.text.trim().is_empty()
+}
+
+/// Texts of prepared frames that still need embeddings.
+fn texts_to_embed(prepared: &[ExtractedFrame]) -> Vec<(FrameId, String)> {
+    prepared
+        .iter()
+        .filter(|frame| awaits_embedding(frame))
+        .map(|frame| (frame.frame_id, frame.text.clone()))
+        .collect()
+}
+
+/// Embed `texts` in one embedder request.
+fn embed_batch<E: VecEmbedder>(
+    batcher: &mut EmbeddingBatcher<E>,
+    texts: Vec<(FrameId, String)>,
+) -> Result<Vec<(FrameId, Vec<f32>)>> {
+    for (frame_id, text) in texts {
+        batcher.add(frame_id, text);
+    }
+    batcher.flush()?;
+    Ok(batcher.take_embeddings())
+}
+
+/// Sleep for `delay`, waking early if the worker is told to stop.
+fn sleep_unless_stopped(handle: &EnrichmentWorkerHandle, delay: Duration) {
+    const STEP: Duration = Duration::from_millis(100);
+    let mut remaining = delay;
+    while !remaining.is_zero() && !handle.should_stop() {
+        let step = remaining.min(STEP);
+        std::thread::sleep(step);
+        remaining -= step;
     }
 }
 
@@ -227,18 +407,164 @@ impl Memvid {
         !self.toc.enrichment_queue.is_empty()
     }
 
-    /// Get the next task from the enrichment queue.
+    /// Get the next task from the enrichment queue, as scheduled under the default
+    /// worker configuration (see [`Memvid::enrichment_schedule`]).
     #[must_use]
     pub fn next_enrichment_task(&self) -> Option<EnrichmentTask> {
-        self.toc.enrichment_queue.tasks.first().cloned()
+        self.next_scheduled_enrichment_task(&EnrichmentWorkerConfig::default(), &HashSet::new())
     }
 
     /// Mark an enrichment task as complete.
     pub fn complete_enrichment_task(&mut self, frame_id: FrameId) {
         self.toc.enrichment_queue.remove(frame_id);
+        if self
+            .toc
+            .extensions
+            .contains_key(ENRICHMENT_PRIORITY_EXTENSION)
+        {
+            let cleared = self.enrichment_priorities().and_then(|mut priorities| {
+                if priorities.frames.remove(&frame_id).is_some() {
+                    self.store_enrichment_priorities(&priorities)?;
+                }
+                Ok(())
+            });
+            if let Err(err) = cleared {
+                tracing::warn!(frame_id, ?err, "failed to clear enrichment priority");
+            }
+        }
         self.dirty = true;
     }
 
+    /// Explicit per-frame enrichment priorities, including ones not yet committed.
+    pub fn enrichment_priorities(&self) -> Result<EnrichmentPriorities> {
+        Ok(self
+            .toc
+            .extension::<EnrichmentPriorities>(ENRICHMENT_PRIORITY_EXTENSION)?
+            .unwrap_or_default())
+    }
+
+    /// Run the queued tasks of `frame_ids` at `priority`, overriding kind policies and
+    /// access heat; use [`EnrichmentPriority::Interactive`] for frames a user has open.
+    /// Frames without a queued task are ignored. The priorities persist with the next commit
+    /// and are dropped as the tasks complete.
+    pub fn set_enrichment_priority(
+        &mut self,
+        frame_ids: &[FrameId],
+        priority: EnrichmentPriority,
+    ) -> Result<()> {
+        let mut priorities = self.enrichment_priorities()?;
+        let queued: HashSet<FrameId> = self
+            .toc
+            .enrichment_queue
+            .tasks
+            .iter()
+            .map(|task| task.frame_id)
+            .collect();
+        for frame_id in frame_ids.iter().filter(|id| queued.contains(id)) {
+            priorities.frames.insert(*frame_id, priority);
+        }
+        self.store_enrichment_priorities(&priorities)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn store_enrichment_priorities(&mut self, priorities: &EnrichmentPriorities) -> Result<()> {
+        if priorities.is_empty() {
+            self.toc.extensions.remove(ENRICHMENT_PRIORITY_EXTENSION);
+            Ok(())
+        } else {
+            self.toc
+                .set_extension(ENRICHMENT_PRIORITY_EXTENSION, priorities)
+        }
+    }
+
+    /// Queued tasks in the order a worker with `config` runs them: highest priority first,
+    /// then queue order. A task's priority is its explicit priority if one was set, else its
+    /// kind's policy, raised to [`EnrichmentPriority::High`] for frames search or ask already
+    /// returned (`prioritize_accessed`). Tasks of deferred kinds without an explicit priority
+    /// are left out.
+    pub fn enrichment_schedule(
+        &self,
+        config: &EnrichmentWorkerConfig,
+    ) -> Result<Vec<EnrichmentTask>> {
+        let explicit = self.enrichment_priorities()?;
+        let access = if config.prioritize_accessed {
+            self.access_stats()?
+        } else {
+            AccessStats::default()
+        };
+
+        let mut scheduled: Vec<(EnrichmentPriority, &EnrichmentTask)> = self
+            .toc
+            .enrichment_queue
+            .tasks
+            .iter()
+            .filter_map(|task| {
+                if let Some(priority) = explicit.get(task.frame_id) {
+                    return Some((priority, task));
+                }
+                let policy = usize::try_from(task.frame_id)
+                    .ok()
+                    .and_then(|index| self.toc.frames.get(index))
+                    .and_then(|frame| frame.kind.as_ref())
+                    .and_then(|kind| config.kind_policies.get(kind))
+                    .copied()
+                    .unwrap_or_default();
+                if policy.deferred {
+                    return None;
+                }
+                let priority = if access.get(task.frame_id).is_some() {
+                    policy.priority.max(EnrichmentPriority::High)
+                } else {
+                    policy.priority
+                };
+                Some((priority, task))
+            })
+            .collect();
+        // Stable, so equal priorities keep queue order.
+        scheduled.sort_by_key(|(priority, _)| Reverse(*priority));
+        Ok(scheduled
+            .into_iter()
+            .map(|(_, task)| task.clone())
+            .collect())
+    }
+
+    /// The first scheduled task not claimed by another worker thread. Falls back to queue
+    /// order if the schedule cannot be read.
+    pub(crate) fn next_scheduled_enrichment_task(
+        &self,
+        config: &EnrichmentWorkerConfig,
+        in_flight: &HashSet<FrameId>,
+    ) -> Option<EnrichmentTask> {
+        self.next_enrichment_batch_excluding(config, 1, in_flight)
+            .pop()
+    }
+
+    fn next_enrichment_batch(
+        &self,
+        config: &EnrichmentWorkerConfig,
+        limit: usize,
+    ) -> Vec<EnrichmentTask> {
+        self.next_enrichment_batch_excluding(config, limit, &HashSet::new())
+    }
+
+    fn next_enrichment_batch_excluding(
+        &self,
+        config: &EnrichmentWorkerConfig,
+        limit: usize,
+        in_flight: &HashSet<FrameId>,
+    ) -> Vec<EnrichmentTask> {
+        let tasks = self.enrichment_schedule(config).unwrap_or_else(|err| {
+            tracing::warn!(?err, "enrichment schedule unreadable, using queue order");
+            self.toc.enrichment_queue.tasks.clone()
+        });
+        tasks
+            .into_iter()
+            .filter(|task| !in_flight.contains(&task.frame_id))
+            .take(limit)
+            .collect()
+    }
+
     /// Read frame data needed for enrichment.
     ///
     /// Returns (`search_text`, `is_skim`, `needs_embedding`) if frame exists.
@@ -277,8 +603,8 @@ impl Memvid {
             .cloned()
             .ok_or(crate::MemvidError::FrameNotFound { frame_id })?;
 
-        // Read the payload
-        let payload = self.read_frame_payload_bytes(&frame)?;
+        // Read the decoded payload
+        let payload = self.frame_canonical_bytes(&frame)?;
 
         // Extract with no time budget
         let mime_hint = frame.metadata.as_ref().and_then(|m| m.mime.as_deref());
@@ -339,31 +665,30 @@ impl Memvid {
             .find(|f| f.id == frame_id && f.status == FrameStatus::Active)
         {
             frame.enrichment_state = EnrichmentState::Enriched;
+            let event = EnrichmentEvent {
+                frame_id,
+                uri: frame.uri.clone(),
+            };
             self.dirty = true;
+            self.fire_enrichment_hooks(&event);
         }
     }
 
     /// Process a single enrichment task synchronously.
     ///
+    /// Stages an earlier run completed (see [`EnrichmentStage`]) are skipped.
     /// This is useful for testing or when you don't want background processing.
     pub fn process_enrichment_task(&mut self, task: &EnrichmentTask) -> TaskResult {
-        // Read frame data
-        let frame_data = self.read_frame_for_enrichment(task.frame_id);
-
-        // Process with closures that capture self
-        let (search_text, is_skim, _needs_embedding) = match frame_data {
-            Some(data) => data,
-            None => {
-                return TaskResult {
-                    frame_id: task.frame_id,
-                    re_extracted: false,
-                    embeddings_generated: 0,
-                    elapsed_ms: 0,
-                    error: Some("Frame not found".to_string()),
-                };
-            }
-        };
+        self.process_enrichment_task_with_engines(task, &EngineRegistry::default())
+    }
 
+    /// Process a single enrichment task, then run the custom `engines` that have not yet
+    /// enriched the frame at their current version.
+    pub fn process_enrichment_task_with_engines(
+        &mut self,
+        task: &EnrichmentTask,
+        engines: &EngineRegistry,
+    ) -> TaskResult {
         let start = std::time::Instant::now();
         let mut result = TaskResult {
             frame_id: task.frame_id,
@@ -373,45 +698,222 @@ impl Memvid {
             error: None,
         };
 
+        let Some((extracted, indexed)) = self.run_extraction_stage(task.frame_id) else {
+            result.error = Some("Frame not found".to_string());
+            return result;
+        };
+        result.re_extracted = extracted.re_extracted;
+        if let Err(err) = indexed {
+            result.error = Some(format!("Index update failed: {err}"));
+        }
+        if let Err(err) = self
+            .run_triplet_stage(&extracted)
+            .and_then(|()| self.run_engine_stage(&extracted, engines))
+        {
+            result.error = Some(format!("Enrichment failed: {err}"));
+        } else {
+            // Mark frame as enriched
+            self.mark_frame_enriched(task.frame_id);
+        }
+
+        result.elapsed_ms = start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+        result
+    }
+
+    /// Record that a background enrichment stage finished for a frame, e.g. NER run by the
+    /// host. Persisted in the memories track with the next commit.
+    pub fn record_enrichment_stage(&mut self, frame_id: FrameId, stage: EnrichmentStage) {
+        self.memories_track
+            .enrichment_manifest_mut()
+            .record_stage(frame_id, stage);
+        self.dirty = true;
+    }
+
+    /// Background enrichment stages completed for a frame, in pipeline order.
+    #[must_use]
+    pub fn enrichment_stages(&self, frame_id: FrameId) -> Vec<EnrichmentStage> {
+        let manifest = self.memories_track.enrichment_manifest();
+        EnrichmentStage::ALL
+            .into_iter()
+            .filter(|stage| manifest.has_completed_stage(frame_id, *stage))
+            .collect()
+    }
+
+    fn has_completed_enrichment_stage(&self, frame_id: FrameId, stage: EnrichmentStage) -> bool {
+        self.memories_track
+            .enrichment_manifest()
+            .has_completed_stage(frame_id, stage)
+    }
+
+    /// Run the extraction stage unless an earlier run finished it: re-extract a skim frame,
+    /// keep the full text as its search text so later stages and restarts reuse it, and
+    /// update the lexical index. Returns `None` if the frame is gone, else the frame text and
+    /// the outcome of the index update.
+    fn run_extraction_stage(&mut self, frame_id: FrameId) -> Option<(ExtractedFrame, Result<()>)> {
+        let (search_text, is_skim, needs_embedding) = self.read_frame_for_enrichment(frame_id)?;
+        let mut extracted = ExtractedFrame {
+            frame_id,
+            text: search_text,
+            is_skim,
+            re_extracted: false,
+            needs_embedding: needs_embedding
+                && !self.has_completed_enrichment_stage(frame_id, EnrichmentStage::Embedded),
+        };
+        if self.has_completed_enrichment_stage(frame_id, EnrichmentStage::Extracted) {
+            return Some((extracted, Ok(())));
+        }
+
         // Re-extract if this was a skim
-        let final_text = if is_skim {
-            match self.extract_full_text(task.frame_id) {
+        if is_skim {
+            match self.extract_full_text(frame_id) {
                 Ok(full_text) => {
-                    result.re_extracted = true;
-                    full_text
+                    extracted.re_extracted = true;
+                    extracted.text = full_text;
+                    if let Some(frame) = usize::try_from(frame_id)
+                        .ok()
+                        .and_then(|index| self.toc.frames.get_mut(index))
+                    {
+                        frame.search_text = Some(extracted.text.clone());
+                        self.dirty = true;
+                    }
                 }
                 Err(err) => {
-                    tracing::warn!(
-                        frame_id = task.frame_id,
-                        ?err,
-                        "re-extraction failed, using skim text"
-                    );
-                    search_text
+                    tracing::warn!(frame_id, ?err, "re-extraction failed, using skim text");
                 }
             }
-        } else {
-            search_text
+        }
+
+        // Update Tantivy index with enriched text
+        let indexed = self.update_tantivy_for_enrichment(frame_id, &extracted.text);
+        if indexed.is_ok() {
+            self.record_enrichment_stage(frame_id, EnrichmentStage::Extracted);
+        }
+        Some((extracted, indexed))
+    }
+
+    /// Extract triplets from the full text of a skim frame, whose put only saw the skim text,
+    /// adding the valid cards whose entity, slot, and value are not in the track yet.
+    ///
+    /// In strict schema mode a schema violation fails the stage, so it reruns next time.
+    fn run_triplet_stage(&mut self, extracted: &ExtractedFrame) -> Result<()> {
+        let frame_id = extracted.frame_id;
+        if !extracted.is_skim
+            || self.has_completed_enrichment_stage(frame_id, EnrichmentStage::Triplets)
+        {
+            return Ok(());
+        }
+        let Some(frame) = usize::try_from(frame_id)
+            .ok()
+            .and_then(|index| self.toc.frames.get(index))
+        else {
+            return Ok(());
         };
 
-        // Update Tantivy index
-        if let Err(err) = self.update_tantivy_for_enrichment(task.frame_id, &final_text) {
-            result.error = Some(format!("Index update failed: {err}"));
+        let (cards, _stats) = TripletExtractor::default().extract(
+            frame_id,
+            &extracted.text,
+            frame.uri.as_deref(),
+            frame.title.as_deref(),
+            frame.timestamp,
+        );
+        let (cards, rejected) = self.drop_invalid_cards(cards);
+        self.ensure_no_schema_rejections(rejected)?;
+        let fresh: Vec<_> = cards
+            .into_iter()
+            .filter(|card| {
+                !self
+                    .memories_track
+                    .get_cards(&card.entity, &card.slot)
+                    .iter()
+                    .any(|known| known.value == card.value)
+            })
+            .collect();
+        if !fresh.is_empty() {
+            let card_ids = self.memories_track.add_cards(fresh);
+            self.detect_card_conflicts(&card_ids);
+            self.memories_track
+                .record_enrichment(frame_id, "rules", "1.0.0", card_ids);
         }
+        self.record_enrichment_stage(frame_id, EnrichmentStage::Triplets);
+        Ok(())
+    }
 
-        // Mark frame as enriched
-        self.mark_frame_enriched(task.frame_id);
+    /// Run each registered engine that has not enriched the frame at its current version,
+    /// stamping the cards it produces with the engine's kind and version. A failing engine
+    /// is retried on the frame's next enrichment.
+    ///
+    /// # Errors
+    /// In strict schema mode, fails on the first engine producing a card that violates the
+    /// schema; its cards are not stored.
+    fn run_engine_stage(
+        &mut self,
+        extracted: &ExtractedFrame,
+        engines: &EngineRegistry,
+    ) -> Result<()> {
+        let frame_id = extracted.frame_id;
+        for registered in engines.iter() {
+            let engine = &registered.engine;
+            if !self.memories_track.enrichment_manifest().needs_enrichment(
+                frame_id,
+                engine.kind(),
+                engine.version(),
+            ) {
+                continue;
+            }
+            let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+            else {
+                return Ok(());
+            };
+            let ctx = Self::enrichment_context(frame, extracted.text.clone());
+            let result = engine.enrich(&ctx);
+            if !result.success {
+                tracing::warn!(
+                    frame_id,
+                    engine = engine.kind(),
+                    error = ?result.error,
+                    "enrichment engine failed"
+                );
+                continue;
+            }
 
-        result.elapsed_ms = start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
-        result
+            let mut cards = result.cards;
+            for card in &mut cards {
+                card.engine = engine.kind().to_string();
+                card.engine_version = engine.version().to_string();
+            }
+            let card_ids = if cards.is_empty() {
+                Vec::new()
+            } else {
+                self.put_memory_cards(cards)?
+            };
+            self.memories_track.record_enrichment(
+                frame_id,
+                engine.kind(),
+                engine.version(),
+                card_ids,
+            );
+            self.dirty = true;
+        }
+        Ok(())
     }
 
-    /// Process all pending enrichment tasks synchronously.
+    /// The engine run that produced `card_id`, if it came from enrichment.
+    #[must_use]
+    pub fn card_provenance(&self, card_id: MemoryCardId) -> Option<&EngineStamp> {
+        self.memories_track
+            .enrichment_manifest()
+            .stamp_for_card(card_id)
+    }
+
+    /// Process all pending enrichment tasks synchronously, in schedule order.
     ///
     /// Returns the number of tasks processed.
     pub fn process_all_enrichment(&mut self) -> usize {
         let mut processed = 0;
 
-        while let Some(task) = self.next_enrichment_task() {
+        for task in self.next_enrichment_batch(&EnrichmentWorkerConfig::default(), usize::MAX) {
             let result = self.process_enrichment_task(&task);
             self.complete_enrichment_task(task.frame_id);
 
@@ -463,6 +965,36 @@ impl Memvid {
         }
     }
 
+    /// Enrichment progress for UIs: frames enriched and pending, and how many pending frames
+    /// are past each stage, i.e. work a restarted worker will not redo.
+    #[must_use]
+    pub fn enrichment_progress(&self) -> EnrichmentProgress {
+        let stats = self.enrichment_stats();
+        let manifest = self.memories_track.enrichment_manifest();
+        let mut stages: BTreeMap<EnrichmentStage, usize> = BTreeMap::new();
+        for task in &self.toc.enrichment_queue.tasks {
+            for stage in EnrichmentStage::ALL {
+                if manifest.has_completed_stage(task.frame_id, stage) {
+                    *stages.entry(stage).or_default() += 1;
+                }
+            }
+        }
+        let settled = stats.enriched_frames + stats.pending_frames;
+        #[allow(clippy::cast_precision_loss)]
+        let percent_complete = if settled == 0 {
+            100.0
+        } else {
+            stats.enriched_frames as f64 * 100.0 / settled as f64
+        };
+        EnrichmentProgress {
+            total_frames: stats.total_frames,
+            enriched_frames: stats.enriched_frames,
+            pending_frames: stats.pending_frames,
+            stages,
+            percent_complete,
+        }
+    }
+
     /// Add embeddings to the vector index.
     ///
     /// This method adds new embeddings for frames that were enriched.
@@ -528,7 +1060,8 @@ impl Memvid {
 
     /// Process enrichment with embeddings using a batched embedder.
     ///
-    /// This method processes the enrichment queue with embedding generation:
+    /// This method processes the enrichment queue, in schedule order, with embedding
+    /// generation:
     /// 1. Re-extracts full text for skim frames
     /// 2. Generates embeddings in batches
     /// 3. Updates indexes
@@ -540,102 +1073,81 @@ impl Memvid {
         embedder: E,
         batch_size: usize,
     ) -> Result<(usize, usize)> {
+        let batch_size = batch_size.max(1);
         let mut batcher = EmbeddingBatcher::new(embedder, batch_size);
         let mut frames_processed = 0;
         let mut embeddings_generated = 0;
 
-        // Collect all tasks first to avoid borrow conflicts
-        let tasks: Vec<_> = self.toc.enrichment_queue.tasks.clone();
+        let tasks = self.next_enrichment_batch(&EnrichmentWorkerConfig::default(), usize::MAX);
+        for batch in tasks.chunks(batch_size) {
+            let prepared = self.prepare_enrichment_batch(batch);
+            let embeddings =
+                embed_batch(&mut batcher, texts_to_embed(&prepared)).unwrap_or_else(|err| {
+                    tracing::warn!(?err, "batch embedding failed");
+                    Vec::new()
+                });
+            embeddings_generated += embeddings.len();
+            frames_processed += prepared.len();
+            self.finish_enrichment_batch(batch, prepared, embeddings, &EngineRegistry::default());
+        }
 
-        for task in tasks {
-            // Read frame data
-            let frame_data = self.read_frame_for_enrichment(task.frame_id);
-            let (search_text, is_skim, needs_embedding) = match frame_data {
-                Some(data) => data,
-                None => continue, // Frame not found, skip
-            };
+        tracing::info!(
+            frames_processed,
+            embeddings_generated,
+            "enrichment with embeddings complete"
+        );
 
-            // Re-extract if this was a skim
-            let final_text = if is_skim {
-                match self.extract_full_text(task.frame_id) {
-                    Ok(full_text) => full_text,
-                    Err(err) => {
-                        tracing::warn!(frame_id = task.frame_id, ?err, "re-extraction failed");
-                        search_text
-                    }
-                }
-            } else {
-                search_text
-            };
+        Ok((frames_processed, embeddings_generated))
+    }
 
-            // Update Tantivy index with enriched text
-            if let Err(err) = self.update_tantivy_for_enrichment(task.frame_id, &final_text) {
-                tracing::warn!(frame_id = task.frame_id, ?err, "tantivy update failed");
-            }
+    /// Run the extraction stage for the frames of `tasks`; frames that no longer exist are
+    /// left out.
+    fn prepare_enrichment_batch(&mut self, tasks: &[EnrichmentTask]) -> Vec<ExtractedFrame> {
+        tasks
+            .iter()
+            .filter_map(|task| {
+                let (extracted, indexed) = self.run_extraction_stage(task.frame_id)?;
+                if let Err(err) = indexed {
+                    tracing::warn!(frame_id = task.frame_id, ?err, "tantivy update failed");
+                }
+                Some(extracted)
+            })
+            .collect()
+    }
 
-            // Queue for embedding if needed
-            if needs_embedding && !final_text.trim().is_empty() {
-                batcher.add(task.frame_id, final_text);
-
-                // Flush batch if ready
-                if batcher.should_flush() {
-                    match batcher.flush() {
-                        Ok(count) => {
-                            embeddings_generated += count;
-                            // Store ready embeddings
-                            let ready = batcher.take_embeddings();
-                            if !ready.is_empty() {
-                                if let Err(err) = self.add_embeddings(ready) {
-                                    tracing::warn!(?err, "failed to add embeddings");
-                                }
-                            }
-                        }
-                        Err(err) => {
-                            tracing::warn!(?err, "batch embedding failed");
-                        }
-                    }
+    /// Store the embeddings of a prepared batch, run the triplet stage and `engines`, mark its
+    /// frames enriched, and drop all of `tasks` from the queue, including those whose frames no
+    /// longer exist.
+    fn finish_enrichment_batch(
+        &mut self,
+        tasks: &[EnrichmentTask],
+        prepared: Vec<ExtractedFrame>,
+        embeddings: Vec<(FrameId, Vec<f32>)>,
+        engines: &EngineRegistry,
+    ) {
+        let embedded: Vec<FrameId> = embeddings.iter().map(|(frame_id, _)| *frame_id).collect();
+        match self.add_embeddings(embeddings) {
+            Ok(_) => {
+                for frame_id in embedded {
+                    self.record_enrichment_stage(frame_id, EnrichmentStage::Embedded);
                 }
             }
-
-            // Mark frame as enriched
-            self.mark_frame_enriched(task.frame_id);
-            self.complete_enrichment_task(task.frame_id);
-            frames_processed += 1;
-
-            // Update checkpoint in queue for crash recovery
-            let chunks_done = u32::try_from(embeddings_generated).unwrap_or(u32::MAX);
-            self.toc.enrichment_queue.update_checkpoint(
-                task.frame_id,
-                chunks_done,
-                task.chunks_total.max(chunks_done),
-            );
+            Err(err) => tracing::warn!(?err, "failed to add embeddings"),
         }
-
-        // Flush remaining batch
-        if batcher.pending_count() > 0 {
-            match batcher.flush() {
-                Ok(count) => {
-                    embeddings_generated += count;
-                    let ready = batcher.take_embeddings();
-                    if !ready.is_empty() {
-                        if let Err(err) = self.add_embeddings(ready) {
-                            tracing::warn!(?err, "failed to add final embeddings");
-                        }
-                    }
-                }
+        for extracted in &prepared {
+            match self
+                .run_triplet_stage(extracted)
+                .and_then(|()| self.run_engine_stage(extracted, engines))
+            {
+                Ok(()) => self.mark_frame_enriched(extracted.frame_id),
                 Err(err) => {
-                    tracing::warn!(?err, "final batch embedding failed");
+                    tracing::warn!(frame_id = extracted.frame_id, ?err, "enrichment failed");
                 }
             }
         }
-
-        tracing::info!(
-            frames_processed,
-            embeddings_generated,
-            "enrichment with embeddings complete"
-        );
-
-        Ok((frames_processed, embeddings_generated))
+        for task in tasks {
+            self.complete_enrichment_task(task.frame_id);
+        }
     }
 
     /// Check if vector embeddings are enabled.
@@ -668,9 +1180,299 @@ pub struct EnrichmentStats {
     pub searchable_only: usize,
 }
 
+/// Enrichment progress, as reported by `Memvid::enrichment_progress`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnrichmentProgress {
+    /// Total active frames.
+    pub total_frames: usize,
+    /// Frames that have been fully enriched.
+    pub enriched_frames: usize,
+    /// Frames pending enrichment.
+    pub pending_frames: usize,
+    /// Pending frames that have completed each stage.
+    pub stages: BTreeMap<EnrichmentStage, usize>,
+    /// Enriched frames as a percentage of enriched and pending frames.
+    pub percent_complete: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::enrichment_worker::KindPolicy;
+    use crate::types::PutOptions;
+    use tempfile::tempdir;
+
+    #[test]
+    fn enrichment_schedule_orders_by_priority() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("enrichment-schedule.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for (kind, text) in [
+            ("bulk", "bulk import row"),
+            ("note", "note a user searched for"),
+            ("note", "note a user has open"),
+            ("log", "verbose log line"),
+        ] {
+            let options = PutOptions::builder().kind(kind).build();
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        let ids: Vec<FrameId> = mem.toc.frames.iter().map(|frame| frame.id).collect();
+        for &frame_id in &ids {
+            mem.toc.enrichment_queue.push(frame_id);
+        }
+
+        let mut config = EnrichmentWorkerConfig::default();
+        config.kind_policies.insert(
+            "bulk".into(),
+            KindPolicy {
+                priority: EnrichmentPriority::Background,
+                deferred: false,
+            },
+        );
+        config.kind_policies.insert(
+            "log".into(),
+            KindPolicy {
+                deferred: true,
+                ..KindPolicy::default()
+            },
+        );
+        mem.pending_access.record_returned([ids[1]], 0);
+        mem.set_enrichment_priority(&[ids[2]], EnrichmentPriority::Interactive)
+            .expect("set priority");
+
+        let order: Vec<FrameId> = mem
+            .enrichment_schedule(&config)
+            .expect("schedule")
+            .iter()
+            .map(|task| task.frame_id)
+            .collect();
+        assert_eq!(order, vec![ids[2], ids[1], ids[0]]);
+
+        mem.complete_enrichment_task(ids[2]);
+        assert!(mem.enrichment_priorities().expect("priorities").is_empty());
+        assert!(
+            !mem.toc
+                .extensions
+                .contains_key(ENRICHMENT_PRIORITY_EXTENSION)
+        );
+        // The default configuration has no deferred kinds.
+        assert_eq!(mem.process_all_enrichment(), 3);
+        assert_eq!(mem.enrichment_queue_len(), 0);
+    }
+
+    #[test]
+    fn enrichment_resumes_after_completed_stages() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("enrichment-resume.mv2");
+        let full = "I work at Anthropic. I live in San Francisco.";
+        let frame_id = {
+            let mut mem = Memvid::create(&path).expect("create");
+            mem.put_bytes(full.as_bytes()).expect("put");
+            mem.commit().expect("commit");
+            let frame_id = mem.toc.frames[0].id;
+            let frame = &mut mem.toc.frames[0];
+            frame.search_text = Some("I work at".to_string());
+            frame.extra_metadata.insert("skim".into(), "true".into());
+            mem.toc.enrichment_queue.push(frame_id);
+
+            // The process dies after the extraction stage.
+            let (extracted, indexed) = mem.run_extraction_stage(frame_id).expect("frame");
+            indexed.expect("index update");
+            assert!(extracted.re_extracted);
+            mem.commit().expect("commit");
+            frame_id
+        };
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        assert_eq!(
+            mem.enrichment_stages(frame_id),
+            vec![EnrichmentStage::Extracted]
+        );
+        assert_eq!(
+            mem.frame_by_id(frame_id)
+                .expect("frame")
+                .search_text
+                .as_deref(),
+            Some(full)
+        );
+        let progress = mem.enrichment_progress();
+        assert_eq!(progress.pending_frames, 1);
+        assert_eq!(progress.stages.get(&EnrichmentStage::Extracted), Some(&1));
+        assert!(progress.percent_complete < 100.0);
+
+        let cards = mem.memories_track.card_count();
+        let task = mem.next_enrichment_task().expect("queued task");
+        let result = mem.process_enrichment_task(&task);
+        mem.complete_enrichment_task(task.frame_id);
+        assert!(result.error.is_none());
+        assert!(
+            !result.re_extracted,
+            "extraction already done before restart"
+        );
+        assert_eq!(
+            mem.enrichment_stages(frame_id),
+            vec![EnrichmentStage::Extracted, EnrichmentStage::Triplets]
+        );
+        // The put already extracted these triplets from the full payload.
+        assert_eq!(mem.memories_track.card_count(), cards);
+
+        let progress = mem.enrichment_progress();
+        assert_eq!(progress.pending_frames, 0);
+        assert!(progress.stages.is_empty());
+        assert!((progress.percent_complete - 100.0).abs() < f64::EPSILON);
+    }
+
+    struct TierEngine {
+        kind: &'static str,
+        value: &'static str,
+    }
+
+    impl crate::enrich::EnrichmentEngine for TierEngine {
+        fn kind(&self) -> &str {
+            self.kind
+        }
+        fn version(&self) -> &'static str {
+            "2.0.0"
+        }
+        fn enrich(
+            &self,
+            ctx: &crate::enrich::EnrichmentContext,
+        ) -> crate::enrich::EnrichmentResult {
+            let card = crate::types::MemoryCardBuilder::new()
+                .fact()
+                .entity("acme")
+                .slot(self.kind)
+                .value(self.value)
+                .source(ctx.frame_id, Some(ctx.uri.clone()))
+                .engine("unset", "0")
+                .build(0)
+                .expect("card");
+            crate::enrich::EnrichmentResult::success(vec![card])
+        }
+    }
+
+    #[test]
+    fn registered_engines_run_in_priority_order_and_stamp_cards() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("enrichment-engines.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.put_bytes(b"Acme renewed its contract.").expect("put");
+        mem.commit().expect("commit");
+        let frame_id = mem.toc.frames[0].id;
+        mem.toc.enrichment_queue.push(frame_id);
+
+        let mut config = EnrichmentWorkerConfig::default();
+        config
+            .register_engine(
+                Box::new(TierEngine {
+                    kind: "crm",
+                    value: "enterprise",
+                }),
+                0,
+            )
+            .expect("register");
+        config
+            .register_engine(
+                Box::new(TierEngine {
+                    kind: "domain-regex",
+                    value: "gold",
+                }),
+                10,
+            )
+            .expect("register");
+
+        let cards_before = mem.memories_track.card_count();
+        let task = mem.next_enrichment_task().expect("queued task");
+        let result = mem.process_enrichment_task_with_engines(&task, &config.engines);
+        assert!(result.error.is_none());
+
+        let record = mem
+            .memories_track
+            .enrichment_manifest()
+            .get_record(frame_id)
+            .expect("record");
+        let kinds: Vec<_> = record
+            .stamps
+            .iter()
+            .map(|stamp| stamp.engine_kind.as_str())
+            .collect();
+        assert_eq!(kinds, ["domain-regex", "crm"]);
+
+        let card_id = record.stamps[0].card_ids[0];
+        let card = mem.memories_track.get_card(card_id).expect("card");
+        assert_eq!(card.engine, "domain-regex");
+        assert_eq!(card.engine_version, "2.0.0");
+        let stamp = mem.card_provenance(card_id).expect("provenance");
+        assert_eq!(stamp.engine_kind, "domain-regex");
+        assert_eq!(mem.memories_track.card_count(), cards_before + 2);
+
+        // Engines that already ran at this version are skipped.
+        mem.process_enrichment_task_with_engines(&task, &config.engines);
+        assert_eq!(mem.memories_track.card_count(), cards_before + 2);
+    }
+
+    struct UnavailableEmbedder;
+
+    impl VecEmbedder for UnavailableEmbedder {
+        fn embed_query(&self, _text: &str) -> Result<Vec<f32>> {
+            Err(MemvidError::EmbedderUnavailable {
+                reason: "rate limited".into(),
+                retry_after_ms: 0,
+            })
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            4
+        }
+
+        fn request_stats(&self) -> Option<crate::types::EmbedderRequestStats> {
+            Some(crate::types::EmbedderRequestStats {
+                rate_limited: 1,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn unavailable_embedder_leaves_frames_queued() {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = Memvid::create(dir.path().join("deferred.mv2")).expect("create");
+        mem.put_bytes(b"waiting on an embedding").expect("put");
+        mem.commit().expect("commit");
+        let frame_id = mem.toc.frames[0].id;
+        mem.toc.frames[0].enrichment_state = EnrichmentState::Searchable;
+        mem.toc.enrichment_queue.push(frame_id);
+
+        let memvid = Arc::new(Mutex::new(mem));
+        let config = EnrichmentWorkerConfig {
+            task_delay_ms: 0,
+            ..EnrichmentWorkerConfig::default()
+        };
+        let stats = start_enrichment_worker_with_embeddings(
+            Arc::clone(&memvid),
+            UnavailableEmbedder,
+            Some(config),
+        )
+        .drain();
+        assert_eq!((stats.deferred, stats.errors), (1, 0));
+        assert_eq!(
+            stats.embedder.map(|embedder| embedder.rate_limited),
+            Some(1)
+        );
+
+        let mem = memvid.lock().expect("lock");
+        assert_eq!(
+            mem.enrichment_queue_len(),
+            1,
+            "frame waits for the embedder"
+        );
+        assert_eq!(
+            mem.frame_by_id(frame_id).expect("frame").enrichment_state,
+            EnrichmentState::Searchable
+        );
+    }
 
     #[test]
     fn test_enrichment_stats_default() {
@@ -0,0 +1,491 @@
+//! Entity resolution over the Logic-Mesh: merge duplicate nodes and undo merges.
+//!
+//! Candidate pairs share an [`EntityKind`](crate::types::EntityKind) and match by alias list,
+//! name variant, or edit distance; non-alias pairs must also clear the context-similarity
+//! threshold when both nodes have embedded frames. Accepted pairs are clustered, and each
+//! cluster collapses onto the node seen in the most frames.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    AbsorbedEntity, ENTITY_MERGE_LOG_EXTENSION, EntityMerge, EntityMergeLog,
+    EntityResolutionReport, EntityResolutionStrategy, MergeSignal, MeshEdge, MeshNode,
+};
+
+/// Accepted same-entity pairs keyed by `(min_id, max_id)`, with the name signal and the
+/// context similarity when it was measured.
+type EntityLinks = HashMap<(u64, u64), (MergeSignal, Option<f32>)>;
+
+impl Memvid {
+    /// Merge mesh nodes that refer to the same entity.
+    ///
+    /// Edges are repointed at each cluster's survivor; edges that become self-loops or
+    /// duplicates are dropped. Every merge is logged so [`Memvid::undo_entity_merge`] can
+    /// reverse it. With `strategy.dry_run` the proposed merges are returned and nothing changes.
+    pub fn resolve_entities(
+        &mut self,
+        strategy: &EntityResolutionStrategy,
+    ) -> Result<EntityResolutionReport> {
+        if !strategy.dry_run {
+            self.ensure_writable()?;
+            self.ensure_not_held("merge entities")?;
+        }
+        let nodes_before = self.logic_mesh.nodes.len();
+        let links = self.entity_links(strategy)?;
+
+        let mut log = self.entity_merge_log()?;
+        let merged_at = unix_now();
+        let mut merges = Vec::new();
+        for cluster in clusters(&links) {
+            let mut members: Vec<&MeshNode> = cluster
+                .iter()
+                .filter_map(|id| self.logic_mesh.find_node_by_id(*id))
+                .collect();
+            members.sort_by(|a, b| {
+                b.frame_ids
+                    .len()
+                    .cmp(&a.frame_ids.len())
+                    .then(b.display_name.len().cmp(&a.display_name.len()))
+                    .then(a.id.cmp(&b.id))
+            });
+            let Some((survivor, rest)) = members.split_first() else {
+                continue;
+            };
+            let absorbed = rest
+                .iter()
+                .map(|node| {
+                    let (signal, context_similarity) = links
+                        .get(&pair(survivor.id, node.id))
+                        .or_else(|| {
+                            links
+                                .iter()
+                                .find(|((a, b), _)| *a == node.id || *b == node.id)
+                                .map(|(_, link)| link)
+                        })
+                        .copied()
+                        .unwrap_or((MergeSignal::Alias, None));
+                    AbsorbedEntity {
+                        node: (*node).clone(),
+                        signal,
+                        context_similarity,
+                    }
+                })
+                .collect();
+            merges.push(EntityMerge {
+                id: log.next_id,
+                merged_at,
+                survivor: (*survivor).clone(),
+                absorbed,
+                original_edges: Vec::new(),
+                added_edges: Vec::new(),
+                undone: false,
+            });
+            log.next_id += 1;
+        }
+
+        let mut edges_rewritten = 0;
+        if !strategy.dry_run && !merges.is_empty() {
+            for merge in &mut merges {
+                edges_rewritten += self.apply_entity_merge(merge);
+            }
+            self.logic_mesh.finalize();
+            log.merges.extend(merges.iter().cloned());
+            self.toc.set_extension(ENTITY_MERGE_LOG_EXTENSION, &log)?;
+            self.dirty = true;
+        }
+
+        Ok(EntityResolutionReport {
+            nodes_before,
+            nodes_after: nodes_before - merges.iter().map(|m| m.absorbed.len()).sum::<usize>(),
+            merges,
+            edges_rewritten,
+            dry_run: strategy.dry_run,
+        })
+    }
+
+    /// Split a merge made by [`Memvid::resolve_entities`] back into its original nodes.
+    ///
+    /// The survivor loses the frames and mentions it gained from the merge, absorbed nodes
+    /// and their edges are restored, and edges the merge introduced are removed. A merge
+    /// cannot be undone while a later merge involving the same nodes is still in effect.
+    pub fn undo_entity_merge(&mut self, merge_id: u64) -> Result<()> {
+        self.ensure_writable()?;
+        self.ensure_not_held("undo entity merges")?;
+        let mut log = self.entity_merge_log()?;
+        let Some(position) = log.merges.iter().position(|merge| merge.id == merge_id) else {
+            return Err(MemvidError::InvalidLogicMesh {
+                reason: format!("no entity merge with id {merge_id}").into(),
+            });
+        };
+        let merge = log.merges[position].clone();
+        if merge.undone {
+            return Err(MemvidError::InvalidLogicMesh {
+                reason: format!("entity merge {merge_id} is already undone").into(),
+            });
+        }
+        if let Some(later) = log.merges[position + 1..].iter().find(|later| {
+            !later.undone
+                && std::iter::once(merge.survivor.id)
+                    .chain(merge.absorbed.iter().map(|absorbed| absorbed.node.id))
+                    .any(|id| later.involves(id))
+        }) {
+            return Err(MemvidError::InvalidLogicMesh {
+                reason: format!("undo entity merge {} before {merge_id}", later.id).into(),
+            });
+        }
+
+        let absorbed_frames: HashSet<_> = merge
+            .absorbed
+            .iter()
+            .flat_map(|absorbed| absorbed.node.frame_ids.iter().copied())
+            .collect();
+        let absorbed_mentions: HashSet<_> = merge
+            .absorbed
+            .iter()
+            .flat_map(|absorbed| absorbed.node.mentions.iter().copied())
+            .collect();
+        let Some(survivor) = self
+            .logic_mesh
+            .nodes
+            .iter_mut()
+            .find(|node| node.id == merge.survivor.id)
+        else {
+            return Err(MemvidError::InvalidLogicMesh {
+                reason: format!("survivor of entity merge {merge_id} is no longer in the mesh")
+                    .into(),
+            });
+        };
+        survivor.frame_ids.retain(|frame_id| {
+            merge.survivor.frame_ids.contains(frame_id) || !absorbed_frames.contains(frame_id)
+        });
+        survivor.mentions.retain(|mention| {
+            merge.survivor.mentions.contains(mention) || !absorbed_mentions.contains(mention)
+        });
+        survivor.confidence = merge.survivor.confidence;
+
+        for absorbed in &merge.absorbed {
+            self.logic_mesh.merge_node(absorbed.node.clone());
+        }
+        self.logic_mesh
+            .edges
+            .retain(|edge| !merge.added_edges.contains(edge));
+        for edge in &merge.original_edges {
+            self.logic_mesh.merge_edge(edge.clone());
+        }
+        self.logic_mesh.finalize();
+
+        log.merges[position].undone = true;
+        self.toc.set_extension(ENTITY_MERGE_LOG_EXTENSION, &log)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Every entity merge recorded in this memory, oldest first.
+    pub fn entity_merges(&self) -> Result<Vec<EntityMerge>> {
+        Ok(self.entity_merge_log()?.merges)
+    }
+
+    pub(crate) fn entity_merge_log(&self) -> Result<EntityMergeLog> {
+        Ok(self
+            .toc
+            .extension::<EntityMergeLog>(ENTITY_MERGE_LOG_EXTENSION)?
+            .unwrap_or_default())
+    }
+
+    /// Same-entity pairs under `strategy`, with ambiguous weak links removed.
+    fn entity_links(&mut self, strategy: &EntityResolutionStrategy) -> Result<EntityLinks> {
+        let nodes = &self.logic_mesh.nodes;
+        let mut candidates = Vec::new();
+        for (i, a) in nodes.iter().enumerate() {
+            for b in &nodes[i + 1..] {
+                if a.kind != b.kind {
+                    continue;
+                }
+                if let Some(signal) = strategy.name_signal(&a.display_name, &b.display_name) {
+                    candidates.push((a.id, b.id, signal));
+                }
+            }
+        }
+
+        let mut centroids: HashMap<u64, Option<Vec<f32>>> = HashMap::new();
+        let mut links = HashMap::new();
+        for (a, b, signal) in candidates {
+            let mut context_similarity = None;
+            if let Some(threshold) = strategy.min_context_similarity {
+                for id in [a, b] {
+                    if let Entry::Vacant(slot) = centroids.entry(id) {
+                        slot.insert(self.entity_centroid(id)?);
+                    }
+                }
+                if let (Some(Some(ca)), Some(Some(cb))) = (centroids.get(&a), centroids.get(&b)) {
+                    let similarity = cosine_similarity(ca, cb);
+                    if signal != MergeSignal::Alias && similarity < threshold {
+                        continue;
+                    }
+                    context_similarity = Some(similarity);
+                }
+            }
+            links.insert(pair(a, b), (signal, context_similarity));
+        }
+
+        // A node that weakly matches two nodes that do not match each other ("B. Smith" against
+        // "Bob Smith" and "Ben Smith") is ambiguous; drop its non-alias links.
+        let mut partners: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (&(a, b), (signal, _)) in &links {
+            if *signal != MergeSignal::Alias {
+                partners.entry(a).or_default().push(b);
+                partners.entry(b).or_default().push(a);
+            }
+        }
+        let ambiguous: HashSet<u64> = partners
+            .iter()
+            .filter(|(_, ids)| {
+                ids.iter().enumerate().any(|(i, x)| {
+                    ids[i + 1..]
+                        .iter()
+                        .any(|y| !links.contains_key(&pair(*x, *y)))
+                })
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        links.retain(|(a, b), (signal, _)| {
+            *signal == MergeSignal::Alias || !(ambiguous.contains(a) || ambiguous.contains(b))
+        });
+        Ok(links)
+    }
+
+    /// Mean embedding of the frames a node appears in, if any are embedded.
+    #[allow(clippy::cast_precision_loss)]
+    fn entity_centroid(&mut self, node_id: u64) -> Result<Option<Vec<f32>>> {
+        let frame_ids = self
+            .logic_mesh
+            .find_node_by_id(node_id)
+            .map(|node| node.frame_ids.clone())
+            .unwrap_or_default();
+        let mut sum: Option<Vec<f32>> = None;
+        let mut count = 0usize;
+        for frame_id in frame_ids {
+            let Some(embedding) = self.frame_embedding(frame_id)? else {
+                continue;
+            };
+            match sum.as_mut() {
+                Some(sum) if sum.len() == embedding.len() => {
+                    for (total, value) in sum.iter_mut().zip(&embedding) {
+                        *total += value;
+                    }
+                }
+                Some(_) => continue,
+                None => sum = Some(embedding),
+            }
+            count += 1;
+        }
+        Ok(sum.map(|sum| sum.into_iter().map(|v| v / count as f32).collect()))
+    }
+
+    /// Fold the absorbed nodes of `merge` into its survivor and rewrite their edges.
+    /// Records the edges touched and added on `merge`; returns the number rewritten.
+    fn apply_entity_merge(&mut self, merge: &mut EntityMerge) -> usize {
+        let absorbed_ids: HashSet<u64> = merge
+            .absorbed
+            .iter()
+            .map(|absorbed| absorbed.node.id)
+            .collect();
+        let survivor_id = merge.survivor.id;
+        let mesh = &mut self.logic_mesh;
+        if let Some(survivor) = mesh.nodes.iter_mut().find(|node| node.id == survivor_id) {
+            for absorbed in &merge.absorbed {
+                for frame_id in &absorbed.node.frame_ids {
+                    if !survivor.frame_ids.contains(frame_id) {
+                        survivor.frame_ids.push(*frame_id);
+                    }
+                }
+                survivor
+                    .mentions
+                    .extend(absorbed.node.mentions.iter().copied());
+                survivor.confidence = survivor.confidence.max(absorbed.node.confidence);
+            }
+        }
+        mesh.nodes.retain(|node| !absorbed_ids.contains(&node.id));
+
+        let (touched, kept): (Vec<MeshEdge>, Vec<MeshEdge>) = std::mem::take(&mut mesh.edges)
+            .into_iter()
+            .partition(|edge| {
+                absorbed_ids.contains(&edge.from_node) || absorbed_ids.contains(&edge.to_node)
+            });
+        mesh.edges = kept;
+        let remap = |id: u64| {
+            if absorbed_ids.contains(&id) {
+                survivor_id
+            } else {
+                id
+            }
+        };
+        for edge in &touched {
+            let rewritten = MeshEdge {
+                from_node: remap(edge.from_node),
+                to_node: remap(edge.to_node),
+                ..edge.clone()
+            };
+            if rewritten.from_node == rewritten.to_node {
+                continue;
+            }
+            let before = mesh.edges.len();
+            mesh.merge_edge(rewritten.clone());
+            if mesh.edges.len() > before {
+                merge.added_edges.push(rewritten);
+            }
+        }
+        let rewritten = touched.len();
+        merge.original_edges = touched;
+        rewritten
+    }
+}
+
+fn pair(a: u64, b: u64) -> (u64, u64) {
+    (a.min(b), a.max(b))
+}
+
+/// Connected components of the link graph with more than one node.
+fn clusters(links: &EntityLinks) -> Vec<Vec<u64>> {
+    let mut parent: HashMap<u64, u64> = HashMap::new();
+    let mut keys: Vec<_> = links.keys().copied().collect();
+    keys.sort_unstable();
+    for (a, b) in keys {
+        let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+        if ra != rb {
+            parent.insert(ra.max(rb), ra.min(rb));
+        }
+    }
+    let mut groups: HashMap<u64, Vec<u64>> = HashMap::new();
+    let ids: Vec<u64> = parent.keys().copied().collect();
+    for id in ids {
+        let top = root(&mut parent, id);
+        groups.entry(top).or_default().push(id);
+    }
+    let mut clusters: Vec<Vec<u64>> = groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .collect();
+    for cluster in &mut clusters {
+        cluster.sort_unstable();
+    }
+    clusters.sort();
+    clusters
+}
+
+/// Union-find root of `id`, compressing the path.
+fn root(parent: &mut HashMap<u64, u64>, id: u64) -> u64 {
+    let next = *parent.entry(id).or_insert(id);
+    if next == id {
+        return id;
+    }
+    let top = root(parent, next);
+    parent.insert(id, top);
+    top
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EntityKind, LinkType};
+
+    fn person(name: &str, frame_id: u64) -> MeshNode {
+        MeshNode::new(
+            name.to_lowercase(),
+            name.to_string(),
+            EntityKind::Person,
+            0.9,
+            frame_id,
+            0,
+            u16::try_from(name.len()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn resolve_entities_merges_and_undo_restores() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("entities.mv2")).expect("create");
+        let acme = MeshNode::new(
+            "acme".into(),
+            "Acme".into(),
+            EntityKind::Organization,
+            0.9,
+            1,
+            0,
+            4,
+        );
+        let nodes = [
+            person("Robert Smith", 1),
+            person("Robert Smith", 2),
+            person("Bob Smith", 3),
+            person("B. Smith", 4),
+            person("Alice Jones", 5),
+        ];
+        let (robert, bob, initial) = (nodes[0].id, nodes[2].id, nodes[3].id);
+        mem.add_mesh_nodes(nodes.into_iter().chain([acme.clone()]).collect());
+        mem.add_mesh_edges(vec![
+            MeshEdge::new(robert, acme.id, LinkType::Employer, 0.8, 1),
+            MeshEdge::new(bob, acme.id, LinkType::Employer, 0.8, 3),
+            MeshEdge::new(initial, robert, LinkType::Custom("alias".into()), 0.5, 4),
+        ]);
+        let original_edges = mem.logic_mesh().edges.clone();
+
+        let strategy = EntityResolutionStrategy::default();
+        let dry = mem
+            .resolve_entities(&EntityResolutionStrategy {
+                dry_run: true,
+                ..strategy.clone()
+            })
+            .expect("dry run");
+        assert_eq!(dry.merges.len(), 1);
+        assert_eq!(mem.mesh_node_count(), 5);
+
+        let report = mem.resolve_entities(&strategy).expect("resolve");
+        assert_eq!((report.nodes_before, report.nodes_after), (5, 3));
+        let merge = &report.merges[0];
+        assert_eq!(merge.survivor.id, robert);
+        assert_eq!(merge.absorbed.len(), 2);
+        assert_eq!(mem.mesh_edge_count(), 1);
+        assert_eq!(
+            mem.find_entity("B. Smith").map(|node| node.id),
+            Some(robert)
+        );
+        assert_eq!(
+            mem.find_entity("Robert Smith")
+                .map(|node| node.frame_ids.len()),
+            Some(4)
+        );
+        assert_eq!(mem.follow("Bob Smith", "employer", 1).len(), 1);
+
+        mem.undo_entity_merge(merge.id).expect("undo");
+        assert_eq!(mem.mesh_node_count(), 5);
+        assert_eq!(mem.logic_mesh().edges, {
+            let mut mesh = crate::types::LogicMesh::new();
+            mesh.edges = original_edges;
+            mesh.finalize();
+            mesh.edges
+        });
+        assert_eq!(
+            mem.find_entity("Robert Smith")
+                .map(|node| node.frame_ids.clone()),
+            Some(vec![1, 2])
+        );
+        assert!(mem.entity_merges().expect("log")[0].undone);
+        assert!(mem.undo_entity_merge(merge.id).is_err());
+    }
+}
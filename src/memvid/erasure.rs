@@ -0,0 +1,326 @@
+//! Shredding frames for right-to-be-forgotten requests (see [`crate::types::erasure`]).
+
+use std::collections::{BTreeSet, HashSet};
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::error::Result;
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::{Memvid, referenced_byte_ranges};
+use crate::types::{
+    BLOB_EXTENT_EXTENSION, BlobExtentStore, ERASURE_LOG_EXTENSION, ErasureLog, ErasureReceipt,
+    Frame, FrameId, FrameStatus, SummaryTarget,
+};
+
+/// Zeros written per call when overwriting a range.
+const ZERO_CHUNK: u64 = 1 << 20;
+
+impl Memvid {
+    /// Erase `frame_id` beyond recovery: overwrite its payload bytes in place, together with
+    /// every version it superseded, their chunks, and frames sharing those bytes; drop their
+    /// text, titles, URIs, and metadata from the TOC; and purge them from the lexical, vector,
+    /// and suggestion indexes, memory cards, Logic-Mesh, and frame summaries. Pending changes
+    /// are committed first, and the erasure is committed before this returns.
+    ///
+    /// Deleting a frame only tombstones it; this is the call for erasure requests. Newer
+    /// versions of the frame are kept. Afterwards every unreferenced byte range and the WAL
+    /// are zeroed too, since they may hold stale copies. Snapshot archives are not rewritten;
+    /// the receipt lists those that still hold the frame.
+    ///
+    /// Pinned frames are refused with [`crate::MemvidError::FramePinned`], and files under a
+    /// legal hold with [`crate::MemvidError::LegalHold`].
+    pub fn shred_frame(&mut self, frame_id: FrameId) -> Result<ErasureReceipt> {
+        self.ensure_mutation_allowed()?;
+        self.ensure_not_held("shred frames")?;
+        self.ensure_frame_unpinned(frame_id)?;
+        self.frame_by_id(frame_id)?;
+        self.commit()?;
+
+        let targets = self.erasure_targets(frame_id);
+        let erased: HashSet<FrameId> = targets.iter().copied().collect();
+        let active: Vec<FrameId> = targets
+            .iter()
+            .copied()
+            .filter(|&id| self.frame_is_active(id))
+            .collect();
+        // Suggestion terms are counted from the text, so they go before the text does.
+        self.refresh_suggest_index(&[], &active)?;
+
+        let mut ranges: BTreeSet<(u64, u64)> = targets
+            .iter()
+            .filter_map(|&id| self.toc.frames.get(usize::try_from(id).ok()?))
+            .filter(|frame| frame.payload_length > 0)
+            .map(|frame| (frame.payload_offset, frame.payload_length))
+            .collect();
+        if let Some(mut store) = self
+            .toc
+            .extension::<BlobExtentStore>(BLOB_EXTENT_EXTENSION)?
+        {
+            let shared: HashSet<[u8; 32]> = store
+                .tables
+                .iter()
+                .filter(|(id, _)| !erased.contains(id))
+                .flat_map(|(_, hashes)| hashes.iter().copied())
+                .collect();
+            let shift = store.shift(&self.header);
+            ranges.extend(
+                targets
+                    .iter()
+                    .filter_map(|id| store.tables.get(id))
+                    .flatten()
+                    .filter(|hash| !shared.contains(*hash))
+                    .filter_map(|hash| store.extents.get(hash))
+                    .map(|extent| (extent.offset.saturating_add(shift), extent.length)),
+            );
+            let live = store
+                .tables
+                .keys()
+                .copied()
+                .filter(|id| !erased.contains(id))
+                .collect();
+            store.retain_frames(&live);
+            self.toc.set_extension(BLOB_EXTENT_EXTENSION, &store)?;
+        }
+        let mut bytes_overwritten = 0;
+        for &(offset, length) in &ranges {
+            self.zero_range(offset, length)?;
+            bytes_overwritten += length;
+        }
+
+        for &id in &targets {
+            self.remove_frame_from_indexes(id)?;
+            if let Some(frame) = usize::try_from(id)
+                .ok()
+                .and_then(|index| self.toc.frames.get_mut(index))
+            {
+                scrub_frame(frame);
+            }
+        }
+        // Deleted documents stay in Tantivy's segment files until a merge; rebuilding
+        // drops them now.
+        #[cfg(feature = "lex")]
+        if let Some(mut engine) = self.tantivy.take() {
+            let rebuilt = self.rebuild_tantivy_engine(&mut engine);
+            self.tantivy = Some(engine);
+            rebuilt?;
+            self.tantivy_dirty = true;
+        }
+
+        let cards_removed = self.memories_track.forget_frames(&erased);
+        let mesh_references_removed = self.logic_mesh.forget_frames(&erased);
+        let mut summaries = self.summaries();
+        let before = summaries.cards.len();
+        summaries.cards.retain(
+            |card| !matches!(card.target, SummaryTarget::Frame(id) if erased.contains(&id)),
+        );
+        if summaries.cards.len() != before {
+            self.store_summaries(&summaries)?;
+        }
+        let retained_in_snapshots = self
+            .snapshot_table()?
+            .snapshots()
+            .iter()
+            .filter(|snapshot| snapshot.frame_count > frame_id)
+            .map(|snapshot| snapshot.label.clone())
+            .collect();
+
+        self.dirty = true;
+        self.commit()?;
+        let generation = self.generation;
+        let orphaned_bytes_overwritten = self.zero_orphaned_ranges()?;
+        self.wal.scrub(&mut self.header)?;
+        crate::persist_header(&mut self.file, &self.header)?;
+
+        let receipt = ErasureReceipt {
+            frame_id,
+            frames: targets.into_iter().collect(),
+            bytes_overwritten,
+            orphaned_bytes_overwritten,
+            cards_removed,
+            mesh_references_removed,
+            retained_in_snapshots,
+            generation,
+            erased_at: unix_now(),
+        };
+        let mut log = self
+            .toc
+            .extension::<ErasureLog>(ERASURE_LOG_EXTENSION)?
+            .unwrap_or_default();
+        log.receipts.push(receipt.clone());
+        self.toc.set_extension(ERASURE_LOG_EXTENSION, &log)?;
+        self.dirty = true;
+        self.commit()?;
+        self.file.sync_all()?;
+        Ok(receipt)
+    }
+
+    /// Receipts of every `shred_frame` call, oldest first.
+    pub fn erasure_receipts(&self) -> Result<Vec<ErasureReceipt>> {
+        Ok(self
+            .toc
+            .extension::<ErasureLog>(ERASURE_LOG_EXTENSION)?
+            .map(|log| log.receipts)
+            .unwrap_or_default())
+    }
+
+    /// `frame_id`, the versions it superseded, their chunks, and frames stored at the same
+    /// payload bytes, repeated until nothing new is found.
+    fn erasure_targets(&self, frame_id: FrameId) -> BTreeSet<FrameId> {
+        let mut targets = BTreeSet::from([frame_id]);
+        loop {
+            let payloads: HashSet<(u64, u64)> = targets
+                .iter()
+                .filter_map(|&id| self.toc.frames.get(usize::try_from(id).ok()?))
+                .filter(|frame| frame.payload_length > 0)
+                .map(|frame| (frame.payload_offset, frame.payload_length))
+                .collect();
+            let found: Vec<FrameId> = self
+                .toc
+                .frames
+                .iter()
+                .filter_map(|frame| {
+                    let linked = if targets.contains(&frame.id) {
+                        frame.supersedes
+                    } else if frame
+                        .parent_id
+                        .is_some_and(|parent| targets.contains(&parent))
+                        || (frame.payload_length > 0
+                            && payloads.contains(&(frame.payload_offset, frame.payload_length)))
+                    {
+                        Some(frame.id)
+                    } else {
+                        None
+                    };
+                    linked.filter(|id| !targets.contains(id))
+                })
+                .collect();
+            if found.is_empty() {
+                return targets;
+            }
+            targets.extend(found);
+        }
+    }
+
+    fn zero_range(&mut self, offset: u64, length: u64) -> Result<()> {
+        let zeros = vec![0u8; usize::try_from(length.min(ZERO_CHUNK)).unwrap_or(0)];
+        let mut written = 0;
+        while written < length {
+            let len = (length - written).min(zeros.len() as u64);
+            self.file.seek(SeekFrom::Start(offset + written))?;
+            #[allow(clippy::cast_possible_truncation)]
+            self.file.write_all(&zeros[..len as usize])?;
+            written += len;
+        }
+        Ok(())
+    }
+
+    /// Zero every byte between the WAL and the footer that the TOC no longer references:
+    /// index segments and tracks superseded by later commits, which may quote erased text.
+    fn zero_orphaned_ranges(&mut self) -> Result<u64> {
+        let mut referenced = referenced_byte_ranges(&self.toc, &self.header);
+        referenced.sort_unstable();
+        let footer = self.header.footer_offset;
+        let mut orphaned = Vec::new();
+        let mut cursor = self.header.data_start;
+        for (offset, length) in referenced {
+            if offset >= footer {
+                break;
+            }
+            if offset > cursor {
+                orphaned.push((cursor, offset - cursor));
+            }
+            cursor = cursor.max(offset.saturating_add(length));
+        }
+        if footer > cursor {
+            orphaned.push((cursor, footer - cursor));
+        }
+        let mut total = 0;
+        for (offset, length) in orphaned {
+            self.zero_range(offset, length)?;
+            total += length;
+        }
+        Ok(total)
+    }
+}
+
+/// Keep only what identifies `frame` as an erased frame: its id, time, kind, role, and links.
+fn scrub_frame(frame: &mut Frame) {
+    frame.status = FrameStatus::Deleted;
+    frame.payload_offset = 0;
+    frame.payload_length = 0;
+    frame.checksum = [0; 32];
+    frame.uri = None;
+    frame.title = None;
+    frame.canonical_length = None;
+    frame.metadata = None;
+    frame.search_text = None;
+    frame.tags.clear();
+    frame.labels.clear();
+    frame.extra_metadata.clear();
+    frame.content_dates.clear();
+    frame.chunk_manifest = None;
+    frame.source_sha256 = None;
+    frame.source_path = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::Memvid;
+    use crate::types::{FrameStatus, PutOptions};
+
+    fn file_contains(path: &std::path::Path, needle: &[u8]) -> bool {
+        let mut bytes = Vec::new();
+        std::fs::File::open(path)
+            .expect("open")
+            .read_to_end(&mut bytes)
+            .expect("read");
+        bytes.windows(needle.len()).any(|window| window == needle)
+    }
+
+    #[test]
+    fn shredding_erases_every_version_from_disk() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("erase.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        let options = || PutOptions {
+            uri: Some("mv2://people/alice".to_string()),
+            ..PutOptions::default()
+        };
+        mem.put_bytes_with_options(b"Alice Zyxwvut lives at 12 Quokka Lane", options())
+            .expect("put");
+        mem.commit().expect("commit");
+        let updated = mem
+            .update_frame(
+                0,
+                Some(b"Alice Zyxwvut moved to 9 Wombat Road".to_vec()),
+                options(),
+                None,
+            )
+            .expect("update");
+        mem.put_bytes_with_options(b"Unrelated note about gardening", PutOptions::default())
+            .expect("put");
+        mem.commit().expect("commit");
+        let current = mem.frame_count() as u64 - 2;
+        assert!(updated > 0);
+
+        let receipt = mem.shred_frame(current).expect("shred");
+        assert_eq!(receipt.frames, vec![0, current]);
+        assert!(receipt.bytes_overwritten > 0);
+        assert!(receipt.retained_in_snapshots.is_empty());
+        for id in [0, current] {
+            let frame = mem.frame_by_id(id).expect("frame");
+            assert_eq!(frame.status, FrameStatus::Deleted);
+            assert!(frame.search_text.is_none() && frame.uri.is_none());
+        }
+        drop(mem);
+
+        for needle in [&b"Zyxwvut"[..], b"Quokka", b"Wombat"] {
+            assert!(!file_contains(&path, needle), "{needle:?} still on disk");
+        }
+        assert!(file_contains(&path, b"gardening"), "other frames untouched");
+
+        let mem = Memvid::open(&path).expect("reopen");
+        assert_eq!(mem.erasure_receipts().expect("receipts"), vec![receipt]);
+    }
+}
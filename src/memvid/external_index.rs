@@ -0,0 +1,363 @@
+//! Lexical and vector indexes kept in a `.mv2x` sidecar.
+//!
+//! [`Memvid::externalize_indexes`] moves the Tantivy segment files and the vector index into the
+//! sidecar and shrinks the primary file to frames and tracks. When the memory is opened the
+//! sidecar is checked against the checksums in the TOC and attached: Tantivy is materialized
+//! from it and the vector index decoded from it. A missing or mismatched sidecar is skipped
+//! with a warning; lexical search then rebuilds from the frames, as it does for any file
+//! without segments, while vector search stays empty until the sidecar is restored.
+//!
+//! Every commit that rebuilds the indexes writes them back into the primary file and drops the
+//! sidecar's manifest. [`Memvid::internalize_indexes`] does the same without a rebuild by
+//! copying the sidecar blobs back.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::external_index::{
+    EXTERNAL_INDEX_EXTENSION, ExternalIndexManifest, SIDECAR_HEADER_SIZE, SIDECAR_MAGIC,
+    SIDECAR_VERSION, external_index_manifest, sidecar_path, validate_sidecar,
+};
+use crate::types::{LexSegmentManifest, SegmentCommon, TantivySegmentDescriptor};
+use crate::vec::VecIndex;
+
+/// A validated sidecar, open for reading.
+pub(crate) struct AttachedIndexes {
+    file: File,
+    manifest: ExternalIndexManifest,
+}
+
+impl AttachedIndexes {
+    fn open(path: &Path, manifest: ExternalIndexManifest) -> Result<Self> {
+        let mut file = File::open(path)?;
+        validate_sidecar(&mut file, &manifest)?;
+        Ok(Self { file, manifest })
+    }
+
+    fn read(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        if length > crate::MAX_INDEX_BYTES {
+            return Err(MemvidError::InvalidToc {
+                reason: "index sidecar range invalid".into(),
+            });
+        }
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        // Safe: length is checked against MAX_INDEX_BYTES above
+        #[allow(clippy::cast_possible_truncation)]
+        let mut buf = vec![0u8; length as usize];
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl std::fmt::Debug for AttachedIndexes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttachedIndexes")
+            .field("manifest", &self.manifest)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Memvid {
+    /// Move the lexical and vector indexes into the `.mv2x` sidecar next to this file and
+    /// shrink the file to what remains. Returns the sidecar path.
+    ///
+    /// Pending changes are committed first. Indexes still split into parallel-builder
+    /// segments must be merged with [`Memvid::finalize_indexes`] before they can move.
+    pub fn externalize_indexes(&mut self) -> Result<PathBuf> {
+        self.ensure_writable()?;
+        self.commit()?;
+        let path = sidecar_path(&self.path);
+        if external_index_manifest(&self.toc).is_some() {
+            return Ok(path);
+        }
+        let catalog = &self.toc.segment_catalog;
+        if !catalog.lex_segments.is_empty() || !catalog.vec_segments.is_empty() {
+            return Err(MemvidError::InvalidConfig {
+                reason: "indexes are split into parallel segments; run finalize_indexes first"
+                    .into(),
+            });
+        }
+
+        let lex_segments: Vec<LexSegmentManifest> = if catalog.tantivy_segments.is_empty() {
+            self.toc.indexes.lex_segments.clone()
+        } else {
+            catalog
+                .tantivy_segments
+                .iter()
+                .map(|descriptor| LexSegmentManifest {
+                    path: descriptor.path.clone(),
+                    bytes_offset: descriptor.common.bytes_offset,
+                    bytes_length: descriptor.common.bytes_length,
+                    checksum: descriptor.common.checksum,
+                })
+                .collect()
+        };
+        let vec = self
+            .toc
+            .indexes
+            .vec
+            .clone()
+            .filter(|manifest| manifest.bytes_length > 0);
+        if lex_segments.is_empty() && vec.is_none() {
+            return Err(MemvidError::InvalidConfig {
+                reason: "memory has no lexical or vector index to externalize".into(),
+            });
+        }
+
+        // Write the sidecar completely before the primary stops referencing its own copies.
+        let staging = path.with_extension(format!("{}.tmp", crate::types::SIDECAR_EXTENSION));
+        let mut out = File::create(&staging)?;
+        out.write_all(&SIDECAR_MAGIC)?;
+        out.write_all(&SIDECAR_VERSION.to_le_bytes())?;
+        let mut manifest = ExternalIndexManifest {
+            sidecar_len: SIDECAR_HEADER_SIZE,
+            lex_segments: Vec::with_capacity(lex_segments.len()),
+            vec: None,
+        };
+        for segment in lex_segments {
+            let bytes =
+                self.read_checked(segment.bytes_offset, segment.bytes_length, segment.checksum)?;
+            out.write_all(&bytes)?;
+            manifest.lex_segments.push(LexSegmentManifest {
+                bytes_offset: manifest.sidecar_len,
+                ..segment
+            });
+            manifest.sidecar_len += bytes.len() as u64;
+        }
+        if let Some(vec) = vec {
+            let bytes = self.read_checked(vec.bytes_offset, vec.bytes_length, vec.checksum)?;
+            out.write_all(&bytes)?;
+            manifest.vec = Some(crate::types::VecIndexManifest {
+                bytes_offset: manifest.sidecar_len,
+                ..vec
+            });
+            manifest.sidecar_len += bytes.len() as u64;
+        }
+        out.sync_all()?;
+        drop(out);
+        fs::rename(&staging, &path)?;
+
+        let attached = AttachedIndexes::open(&path, manifest.clone())?;
+        self.with_staging_lock(|mem| {
+            mem.toc.set_extension(EXTERNAL_INDEX_EXTENSION, &manifest)?;
+            if !manifest.lex_segments.is_empty() {
+                mem.toc.segment_catalog.tantivy_segments.clear();
+                mem.toc.indexes.lex_segments.clear();
+                mem.toc.segment_catalog.lex_enabled = true;
+            }
+            if manifest.vec.is_some() {
+                if let Some(vec) = mem.toc.indexes.vec.as_mut() {
+                    vec.bytes_offset = 0;
+                    vec.bytes_length = 0;
+                }
+            }
+            mem.rewrite_toc_footer()?;
+            mem.header.toc_checksum = mem.toc.toc_checksum;
+            crate::persist_header(&mut mem.file, &mem.header)
+        })?;
+        #[cfg(feature = "lex")]
+        if let Ok(mut storage) = self.lex_storage.write() {
+            storage.clear();
+        }
+        self.external_indexes = Some(attached);
+        // The index blobs sat between the time index and the tail tracks.
+        self.compact_tail_tracks()?;
+        Ok(path)
+    }
+
+    /// Copy indexes held in the `.mv2x` sidecar back into this file and delete the sidecar.
+    ///
+    /// Without a valid sidecar the lexical index is rebuilt from the frames; the vector index
+    /// cannot be recovered and is left empty.
+    pub fn internalize_indexes(&mut self) -> Result<()> {
+        self.ensure_writable()?;
+        self.commit()?;
+        if external_index_manifest(&self.toc).is_none() {
+            return Ok(());
+        }
+        let Some(attached) = self.external_indexes.take() else {
+            // `rebuild_indexes` drops the sidecar manifest along the way.
+            return self.finalize_indexes();
+        };
+
+        self.with_staging_lock(|mem| {
+            let mut offset = mem.header.footer_offset;
+            mem.file.seek(SeekFrom::Start(offset))?;
+            if mem.toc.segment_catalog.tantivy_segments.is_empty()
+                && mem.toc.indexes.lex_segments.is_empty()
+            {
+                let mut restored = Vec::with_capacity(attached.manifest.lex_segments.len());
+                for segment in &attached.manifest.lex_segments {
+                    let bytes = attached.read(segment.bytes_offset, segment.bytes_length)?;
+                    mem.file.write_all(&bytes)?;
+                    restored.push(LexSegmentManifest {
+                        bytes_offset: offset,
+                        ..segment.clone()
+                    });
+                    offset += segment.bytes_length;
+                }
+                let mut next_segment_id = mem.toc.segment_catalog.next_segment_id;
+                mem.toc.segment_catalog.tantivy_segments = restored
+                    .iter()
+                    .map(|segment| {
+                        let common = SegmentCommon::new(
+                            next_segment_id,
+                            segment.bytes_offset,
+                            segment.bytes_length,
+                            segment.checksum,
+                        );
+                        next_segment_id = next_segment_id.saturating_add(1);
+                        TantivySegmentDescriptor::from_common(common, segment.path.clone())
+                    })
+                    .collect();
+                mem.toc.segment_catalog.next_segment_id = next_segment_id;
+                mem.toc.indexes.lex_segments = restored;
+            }
+            if let (Some(external), Some(vec)) =
+                (attached.manifest.vec.as_ref(), mem.toc.indexes.vec.as_mut())
+            {
+                if vec.bytes_length == 0 {
+                    let bytes = attached.read(external.bytes_offset, external.bytes_length)?;
+                    mem.file.write_all(&bytes)?;
+                    vec.bytes_offset = offset;
+                    vec.bytes_length = external.bytes_length;
+                    offset += external.bytes_length;
+                }
+            }
+            mem.header.footer_offset = offset;
+            mem.toc.extensions.remove(EXTERNAL_INDEX_EXTENSION);
+            mem.rewrite_toc_footer()?;
+            mem.header.toc_checksum = mem.toc.toc_checksum;
+            crate::persist_header(&mut mem.file, &mem.header)
+        })?;
+        #[cfg(feature = "lex")]
+        if let Ok(mut storage) = self.lex_storage.write() {
+            *storage = crate::search::EmbeddedLexStorage::from_manifest(
+                self.toc.indexes.lex.as_ref(),
+                &self.toc.indexes.lex_segments,
+            );
+        }
+        match fs::remove_file(sidecar_path(&self.path)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether this memory's indexes live in a `.mv2x` sidecar, attached or not.
+    #[must_use]
+    pub fn indexes_externalized(&self) -> bool {
+        self.toc.extensions.contains_key(EXTERNAL_INDEX_EXTENSION)
+    }
+
+    /// Open and validate the sidecar named in the TOC, if any. Failures leave the indexes
+    /// detached rather than failing the open.
+    pub(crate) fn attach_external_indexes(&mut self) {
+        self.external_indexes = None;
+        let Some(manifest) = external_index_manifest(&self.toc) else {
+            return;
+        };
+        let path = sidecar_path(&self.path);
+        match AttachedIndexes::open(&path, manifest) {
+            Ok(attached) => self.external_indexes = Some(attached),
+            Err(err) => tracing::warn!(
+                "index sidecar {} is unusable ({}); indexes stay detached",
+                path.display(),
+                err
+            ),
+        }
+    }
+
+    /// Forget the sidecar once the indexes are about to be written into this file again.
+    pub(crate) fn detach_external_indexes(&mut self) {
+        self.toc.extensions.remove(EXTERNAL_INDEX_EXTENSION);
+        self.external_indexes = None;
+    }
+
+    /// Decode the vector index from the attached sidecar, if it holds one.
+    pub(crate) fn load_external_vec_index(&self) -> Option<VecIndex> {
+        let attached = self.external_indexes.as_ref()?;
+        let (offset, length) = attached
+            .manifest
+            .vec
+            .as_ref()
+            .map(|vec| (vec.bytes_offset, vec.bytes_length))?;
+        let bytes = attached.read(offset, length).ok()?;
+        std::panic::catch_unwind(|| VecIndex::decode(&bytes))
+            .ok()
+            .and_then(Result::ok)
+    }
+
+    /// Read `length` bytes at `offset` within the vector index blob, wherever it lives.
+    pub(crate) fn read_vec_index_range(&mut self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        let base = self
+            .toc
+            .indexes
+            .vec
+            .as_ref()
+            .filter(|manifest| manifest.bytes_length > 0)
+            .map(|manifest| manifest.bytes_offset);
+        if let Some(base) = base {
+            return self.read_range(base + offset, length);
+        }
+        let attached = self
+            .external_indexes
+            .as_ref()
+            .ok_or(MemvidError::VecNotEnabled)?;
+        let base = attached
+            .manifest
+            .vec
+            .as_ref()
+            .map(|vec| vec.bytes_offset)
+            .ok_or(MemvidError::VecNotEnabled)?;
+        attached.read(base + offset, length)
+    }
+
+    /// Whether the attached sidecar holds Tantivy segment files.
+    #[cfg(feature = "lex")]
+    pub(crate) fn has_external_lex(&self) -> bool {
+        self.external_indexes
+            .as_ref()
+            .is_some_and(|attached| !attached.manifest.lex_segments.is_empty())
+    }
+
+    /// Write the sidecar's Tantivy segment files into a fresh work directory.
+    #[cfg(feature = "lex")]
+    pub(crate) fn materialize_external_lex(&self) -> Result<tempfile::TempDir> {
+        let tantivy_err = |reason: String| MemvidError::Tantivy { reason };
+        let dir = tempfile::TempDir::new().map_err(|err| {
+            tantivy_err(format!("failed to allocate Tantivy work directory: {err}"))
+        })?;
+        let Some(attached) = self.external_indexes.as_ref() else {
+            return Ok(dir);
+        };
+        for segment in &attached.manifest.lex_segments {
+            let dest = dir.path().join(&segment.path);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let bytes = attached.read(segment.bytes_offset, segment.bytes_length)?;
+            fs::write(&dest, bytes).map_err(|err| {
+                tantivy_err(format!(
+                    "failed to materialize Tantivy segment {}: {err}",
+                    dest.display()
+                ))
+            })?;
+        }
+        Ok(dir)
+    }
+
+    /// Read a blob of this file and check it against the checksum its manifest recorded.
+    fn read_checked(&mut self, offset: u64, length: u64, checksum: [u8; 32]) -> Result<Vec<u8>> {
+        let bytes = self.read_range(offset, length)?;
+        if *blake3::hash(&bytes).as_bytes() != checksum {
+            return Err(MemvidError::ChecksumMismatch {
+                context: "index blob",
+            });
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,140 @@
+//! Per-file configuration (see [`crate::types::file_config`]).
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::chunks::DEFAULT_CHUNK_CHARS;
+use crate::memvid::lifecycle::Memvid;
+#[cfg(feature = "lex")]
+use crate::types::DEFAULT_SKETCH_PREFILTER_THRESHOLD;
+use crate::types::{COMPRESSION_EXTENSION, FILE_CONFIG_EXTENSION, FileConfig};
+
+impl Memvid {
+    /// Replace the file's configuration; `None` fields return to the built-in behavior.
+    ///
+    /// Saved in the file on the next commit and read back whenever the file is opened.
+    /// Frames already committed keep the chunks, dates, and codecs they were written with.
+    pub fn set_config(&mut self, config: FileConfig) -> Result<()> {
+        self.ensure_writable()?;
+        self.ensure_not_held("change the file configuration")?;
+        if config.snippet_chars == Some(0) || config.chunk_chars == Some(0) {
+            return Err(MemvidError::InvalidConfig {
+                reason: "snippet_chars and chunk_chars must be positive".into(),
+            });
+        }
+        if config
+            .sketch_hamming_threshold
+            .is_some_and(|bits| bits > 64)
+        {
+            return Err(MemvidError::InvalidConfig {
+                reason: "sketch_hamming_threshold cannot exceed 64 bits".into(),
+            });
+        }
+        if config
+            .capacity_warning_percent
+            .is_some_and(|percent| percent == 0 || percent > 100)
+        {
+            return Err(MemvidError::InvalidConfig {
+                reason: "capacity_warning_percent must be between 1 and 100".into(),
+            });
+        }
+        self.set_default_timezone(config.timezone.as_deref())?;
+        match config.compression {
+            Some(defaults) => self.set_compression_defaults(defaults)?,
+            None => {
+                self.toc.extensions.remove(COMPRESSION_EXTENSION);
+            }
+        }
+        let stored = FileConfig {
+            timezone: None,
+            compression: None,
+            ..config
+        };
+        if stored == FileConfig::default() {
+            self.toc.extensions.remove(FILE_CONFIG_EXTENSION);
+        } else {
+            self.toc.set_extension(FILE_CONFIG_EXTENSION, &stored)?;
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// The file's configuration as last set, including its time zone and compression
+    /// defaults.
+    pub fn config(&self) -> Result<FileConfig> {
+        let mut config: FileConfig = self
+            .toc
+            .extension(FILE_CONFIG_EXTENSION)?
+            .unwrap_or_default();
+        config.timezone = self.default_timezone()?;
+        config.compression = self.toc.extension(COMPRESSION_EXTENSION)?;
+        Ok(config)
+    }
+
+    /// Chunk size for a put: its own, then the file's, then the built-in one. An unreadable
+    /// configuration falls back to the built-in size rather than failing the put.
+    pub(crate) fn chunk_chars(&self, requested: Option<usize>) -> usize {
+        requested
+            .or_else(|| self.config().ok().and_then(|config| config.chunk_chars))
+            .unwrap_or(DEFAULT_CHUNK_CHARS)
+    }
+
+    /// Hamming threshold of the search sketch pre-filter.
+    #[cfg(feature = "lex")]
+    pub(crate) fn sketch_prefilter_threshold(&self) -> Result<u32> {
+        Ok(self
+            .config()?
+            .sketch_hamming_threshold
+            .unwrap_or(DEFAULT_SKETCH_PREFILTER_THRESHOLD))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CompressionCodec, CompressionDefaults};
+
+    #[test]
+    fn config_persists_and_drives_chunking() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("config.mv2");
+        let text = "Operators tune memories without recompiling. ".repeat(30);
+        let mut mem = Memvid::create(&path).expect("create");
+        assert_eq!(mem.config().expect("config"), FileConfig::default());
+        assert!(mem.preview_chunks(text.as_bytes()).is_none());
+
+        let invalid = FileConfig {
+            sketch_hamming_threshold: Some(65),
+            ..FileConfig::default()
+        };
+        assert!(mem.set_config(invalid).is_err());
+        let config = FileConfig {
+            snippet_chars: Some(120),
+            chunk_chars: Some(400),
+            sketch_hamming_threshold: Some(20),
+            capacity_warning_percent: Some(90),
+            timezone: Some("Europe/Berlin".into()),
+            compression: Some(CompressionDefaults {
+                payloads: CompressionCodec::Lz4,
+                segments: CompressionCodec::None,
+            }),
+        };
+        mem.set_config(config.clone()).expect("set config");
+        assert!(mem.preview_chunks(text.as_bytes()).expect("chunks").len() > 2);
+        assert_eq!(mem.chunk_chars(Some(900)), 900);
+        mem.commit().expect("commit");
+        drop(mem);
+
+        let mut reopened = Memvid::open(&path).expect("open");
+        assert_eq!(reopened.config().expect("config"), config);
+        assert_eq!(
+            reopened.sketch_prefilter_threshold().expect("threshold"),
+            20
+        );
+        reopened.set_default_timezone(None).expect("clear zone");
+        assert_eq!(reopened.config().expect("config").timezone, None);
+        reopened
+            .set_config(FileConfig::default())
+            .expect("reset config");
+        assert_eq!(reopened.config().expect("config"), FileConfig::default());
+        assert_eq!(reopened.chunk_chars(None), DEFAULT_CHUNK_CHARS);
+    }
+}
@@ -1,11 +1,18 @@
 //! Frame payload and preview helpers for `Memvid`.
 
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::{self, Cursor, Read, Seek, SeekFrom};
 
+use memmap2::Mmap;
+
 use crate::error::{MemvidError, Result};
 use crate::memvid::lifecycle::Memvid;
-use crate::types::{CanonicalEncoding, Frame, FrameId, FrameRole, FrameStatus, MediaManifest};
+use crate::types::blob_extents::stored_in_extents;
+use crate::types::seekable::{SEEK_TABLE_FOOTER_SIZE, SeekTable};
+use crate::types::{
+    BlobExtent, CanonicalEncoding, Frame, FrameId, FrameRole, FrameStatus, MediaManifest,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ChunkInfo {
@@ -137,6 +144,19 @@ impl Seek for BlobReader {
     }
 }
 
+/// Reader over `len` bytes of `bytes` starting at `start`.
+fn slice_range(frame: &Frame, bytes: &[u8], start: u64, len: u64) -> Result<BlobReader> {
+    usize::try_from(start)
+        .ok()
+        .zip(usize::try_from(len).ok())
+        .and_then(|(start, len)| bytes.get(start..start.checked_add(len)?))
+        .map(|range| BlobReader::from_memory(range.to_vec()))
+        .ok_or(MemvidError::InvalidFrame {
+            frame_id: frame.id,
+            reason: "canonical length mismatch",
+        })
+}
+
 fn mime_is_text(mime: &str) -> bool {
     let normalized = mime
         .split(';')
@@ -223,6 +243,112 @@ impl Memvid {
         self.blob_reader_from_frame(frame)
     }
 
+    /// Reader over `len` bytes of a blob starting at `start`, for serving ranged requests.
+    ///
+    /// The range is checked against the frame's `MediaManifest` (or its canonical length when
+    /// it has none). Plain payloads are read in place; compressed payloads are decoded only up
+    /// to the end of the range.
+    pub fn blob_reader_range(&mut self, uri: &str, start: u64, len: u64) -> Result<BlobReader> {
+        let frame = self.frame_by_uri(uri)?;
+        if frame.payload_length == 0 && frame.chunk_manifest.is_some() && !stored_in_extents(&frame)
+        {
+            return Err(MemvidError::InvalidFrame {
+                frame_id: frame.id,
+                reason: "chunked document has no single payload",
+            });
+        }
+        let logical = frame.canonical_length.unwrap_or(frame.payload_length);
+        let size = match frame.metadata.as_ref().and_then(|meta| meta.media.as_ref()) {
+            Some(manifest) if manifest.bytes != logical => {
+                return Err(MemvidError::InvalidFrame {
+                    frame_id: frame.id,
+                    reason: "media manifest size does not match payload",
+                });
+            }
+            _ => logical,
+        };
+        let end = start.checked_add(len).filter(|end| *end <= size).ok_or(
+            MemvidError::BlobRangeOutOfBounds {
+                start,
+                end: start.saturating_add(len),
+                size,
+            },
+        )?;
+
+        self.ensure_not_quarantined(frame.id)?;
+        if let Some(extents) = self.frame_blob_extents(&frame)? {
+            return self.blob_extent_range(&frame, &extents, start, end);
+        }
+        self.validate_frame_bounds(&frame)?;
+        let mut file = self.file.try_clone()?;
+        if self.verify_payloads {
+            self.verify_payload_stream(&mut file, &frame)?;
+        }
+        match frame.canonical_encoding {
+            CanonicalEncoding::Plain => {
+                file.seek(SeekFrom::Start(frame.payload_offset + start))?;
+                Ok(BlobReader::from_file(
+                    file,
+                    frame.payload_offset + start,
+                    len,
+                ))
+            }
+            CanonicalEncoding::Zstd => {
+                if let Some(bytes) = self.seekable_range(&frame, start, end)? {
+                    return Ok(BlobReader::from_memory(bytes));
+                }
+                file.seek(SeekFrom::Start(frame.payload_offset))?;
+                let mut decoder =
+                    zstd::stream::read::Decoder::new(file.take(frame.payload_length))?;
+                io::copy(&mut (&mut decoder).take(start), &mut io::sink())?;
+                // Safe: bounded by the canonical length, which fits in memory for zstd frames
+                #[allow(clippy::cast_possible_truncation)]
+                let mut bytes = vec![0u8; (end - start) as usize];
+                decoder.read_exact(&mut bytes)?;
+                Ok(BlobReader::from_memory(bytes))
+            }
+            CanonicalEncoding::Lz4 => {
+                let bytes = self.frame_canonical_bytes(&frame)?;
+                slice_range(&frame, &bytes, start, end - start)
+            }
+        }
+    }
+
+    /// Bytes `start..end` of a blob stored as extents. Plain blobs read only the extents
+    /// overlapping the range; compressed ones are decoded in full.
+    fn blob_extent_range(
+        &mut self,
+        frame: &Frame,
+        extents: &[BlobExtent],
+        start: u64,
+        end: u64,
+    ) -> Result<BlobReader> {
+        if self.verify_payloads || frame.canonical_encoding != CanonicalEncoding::Plain {
+            let raw = self.read_blob_extents(extents)?;
+            if self.verify_payloads {
+                self.check_payload_checksum(frame, &blake3::hash(&raw))?;
+            }
+            let canonical =
+                crate::decode_canonical_bytes(&raw, frame.canonical_encoding, frame.id)?;
+            return slice_range(frame, &canonical, start, end - start);
+        }
+        let mut position = 0u64;
+        let mut skip = 0u64;
+        let mut covering = Vec::new();
+        for extent in extents {
+            let extent_end = position + extent.length;
+            if extent_end > start && position < end {
+                if covering.is_empty() {
+                    skip = start - position;
+                }
+                covering.push(*extent);
+            }
+            position = extent_end;
+        }
+        let bytes = self.read_blob_extents(&covering)?;
+        slice_range(frame, &bytes, skip, end - start)
+    }
+
     pub fn media_manifest(&self, frame_id: FrameId) -> Result<Option<MediaManifest>> {
         let frame = self.frame_by_id(frame_id)?;
         Ok(frame.metadata.and_then(|meta| meta.media))
@@ -235,8 +361,13 @@ impl Memvid {
 
     fn blob_reader_from_frame(&mut self, frame: Frame) -> Result<BlobReader> {
         match frame.canonical_encoding {
-            CanonicalEncoding::Plain => {
+            CanonicalEncoding::Plain if !stored_in_extents(&frame) => {
+                self.ensure_not_quarantined(frame.id)?;
                 let mut file = self.file.try_clone()?;
+                if self.verify_payloads {
+                    self.validate_frame_bounds(&frame)?;
+                    self.verify_payload_stream(&mut file, &frame)?;
+                }
                 file.seek(SeekFrom::Start(frame.payload_offset))?;
                 Ok(BlobReader::from_file(
                     file,
@@ -244,18 +375,153 @@ impl Memvid {
                     frame.payload_length,
                 ))
             }
-            CanonicalEncoding::Zstd => {
+            CanonicalEncoding::Plain => {
+                let bytes = self.read_frame_payload_bytes(&frame)?;
+                Ok(BlobReader::from_memory(bytes))
+            }
+            CanonicalEncoding::Zstd | CanonicalEncoding::Lz4 => {
                 let bytes = self.frame_canonical_bytes(&frame)?;
                 Ok(BlobReader::from_memory(bytes))
             }
         }
     }
 
+    /// Hash the stored payload of `frame` through `file` and check it against the TOC.
+    fn verify_payload_stream(&mut self, file: &mut File, frame: &Frame) -> Result<()> {
+        file.seek(SeekFrom::Start(frame.payload_offset))?;
+        let mut hasher = blake3::Hasher::new();
+        io::copy(&mut file.take(frame.payload_length), &mut hasher)?;
+        self.check_payload_checksum(frame, &hasher.finalize())
+    }
+
     pub fn frame_canonical_payload(&mut self, frame_id: FrameId) -> Result<Vec<u8>> {
         let frame = self.frame_by_id(frame_id)?;
         self.frame_canonical_bytes(&frame)
     }
 
+    /// Canonical bytes of a frame without copying where possible.
+    ///
+    /// On read-only handles, plain-encoded payloads are borrowed straight from a memory map of
+    /// the file; other payloads are read and decoded into an owned buffer. Chunked documents
+    /// have no single payload; use [`Self::frame_canonical_payload`] for those.
+    pub fn frame_bytes_ref(&self, frame_id: FrameId) -> Result<Cow<'_, [u8]>> {
+        let index =
+            usize::try_from(frame_id).map_err(|_| MemvidError::FrameNotFound { frame_id })?;
+        let frame = self
+            .toc
+            .frames
+            .get(index)
+            .ok_or(MemvidError::FrameNotFound { frame_id })?;
+        self.frame_payload_ref(frame)
+    }
+
+    pub(crate) fn frame_payload_ref(&self, frame: &Frame) -> Result<Cow<'_, [u8]>> {
+        if frame.role == FrameRole::Document && frame.chunk_manifest.is_some() {
+            return Err(MemvidError::InvalidFrame {
+                frame_id: frame.id,
+                reason: "chunked document has no single payload",
+            });
+        }
+        self.ensure_not_quarantined(frame.id)?;
+        self.validate_frame_bounds(frame)?;
+        let raw = if let Some(extents) = self.frame_blob_extents(frame)? {
+            Cow::Owned(self.read_blob_extents(&extents)?)
+        } else if frame.payload_length == 0 {
+            Cow::Borrowed(&[][..])
+        } else if self.read_only {
+            Cow::Borrowed(self.mapped_range(frame.payload_offset, frame.payload_length)?)
+        } else {
+            let mut file = &*self.file;
+            file.seek(SeekFrom::Start(frame.payload_offset))?;
+            // Safe: guarded by MAX_FRAME_BYTES check
+            #[allow(clippy::cast_possible_truncation)]
+            let mut buf = vec![0u8; frame.payload_length as usize];
+            file.read_exact(&mut buf)?;
+            Cow::Owned(buf)
+        };
+        if self.verify_payloads {
+            self.verify_payload_checksum(frame, &blake3::hash(&raw))?;
+        }
+        let canonical = match frame.canonical_encoding {
+            CanonicalEncoding::Plain => raw,
+            CanonicalEncoding::Zstd | CanonicalEncoding::Lz4 => Cow::Owned(
+                crate::decode_canonical_bytes(&raw, frame.canonical_encoding, frame.id)?,
+            ),
+        };
+        if frame
+            .canonical_length
+            .is_some_and(|expected| canonical.len() as u64 != expected)
+        {
+            return Err(MemvidError::InvalidFrame {
+                frame_id: frame.id,
+                reason: "canonical length mismatch",
+            });
+        }
+        Ok(canonical)
+    }
+
+    /// Text used to build search snippets, borrowed from the TOC or the mapped file when
+    /// possible. Returns `None` for chunked documents, which need `frame_content`.
+    pub(crate) fn frame_snippet_text<'a>(
+        &'a self,
+        frame: &'a Frame,
+    ) -> Option<Result<Cow<'a, str>>> {
+        if let Some(search) = frame.search_text.as_deref().filter(|text| !text.is_empty()) {
+            return Some(Ok(Cow::Borrowed(search)));
+        }
+        if frame.payload_length == 0 && frame.chunk_manifest.is_none() && !stored_in_extents(frame)
+        {
+            return Some(Ok(Cow::Borrowed("")));
+        }
+        if frame.role == FrameRole::Document && frame.chunk_manifest.is_some() {
+            return None;
+        }
+        if let Some(mime) = frame
+            .metadata
+            .as_ref()
+            .and_then(|meta| meta.mime.as_deref())
+        {
+            if !mime_is_text(mime) {
+                let logical = frame.canonical_length.unwrap_or(frame.payload_length);
+                #[allow(clippy::cast_possible_truncation)]
+                return Some(Ok(Cow::Owned(Self::render_binary_summary(
+                    logical as usize,
+                ))));
+            }
+        }
+        Some(self.frame_payload_ref(frame).map(|bytes| {
+            match bytes {
+                Cow::Borrowed(bytes) => std::str::from_utf8(bytes).map_or_else(
+                    |_| Cow::Owned(Self::render_binary_summary(bytes.len())),
+                    Cow::Borrowed,
+                ),
+                Cow::Owned(bytes) => Cow::Owned(
+                    String::from_utf8(bytes)
+                        .unwrap_or_else(|err| Self::render_binary_summary(err.into_bytes().len())),
+                ),
+            }
+        }))
+    }
+
+    /// `length` bytes at `offset` of the memory-mapped file, mapping it on first use.
+    fn mapped_range(&self, offset: u64, length: u64) -> Result<&[u8]> {
+        let map = if let Some(map) = self.payload_map.get() {
+            map
+        } else {
+            // Safety: only read-only handles map the file; they hold a shared lock, so no
+            // writer truncates or rewrites it in place while the map is alive.
+            let map = unsafe { Mmap::map(&*self.file)? };
+            self.payload_map.get_or_init(|| map)
+        };
+        usize::try_from(offset)
+            .ok()
+            .zip(usize::try_from(length).ok())
+            .and_then(|(start, length)| map.get(start..start.checked_add(length)?))
+            .ok_or(MemvidError::InvalidToc {
+                reason: "payload range exceeds mapped file".into(),
+            })
+    }
+
     pub fn frame_preview_by_id(&mut self, frame_id: FrameId) -> Result<String> {
         let index = usize::try_from(frame_id).map_err(|_| MemvidError::InvalidTimeIndex {
             reason: "frame id too large".into(),
@@ -331,7 +597,7 @@ impl Memvid {
         if let Some(search) = &frame.search_text {
             return Ok(crate::truncate_preview(search));
         }
-        if frame.payload_length == 0 {
+        if frame.payload_length == 0 && !stored_in_extents(frame) {
             return Ok(String::new());
         }
         match self.frame_canonical_text(frame) {
@@ -348,21 +614,134 @@ impl Memvid {
                 return Ok(search.clone());
             }
         }
-        if frame.payload_length == 0 && frame.chunk_manifest.is_none() {
+        if frame.payload_length == 0 && frame.chunk_manifest.is_none() && !stored_in_extents(frame)
+        {
             return Ok(String::new());
         }
         self.frame_canonical_text(frame)
     }
 
+    /// Bytes `start..end` of the text [`Self::frame_content`] returns, clamped to its length,
+    /// and the length of that text. Seekable zstd payloads decode only the frames the window
+    /// overlaps.
+    pub(crate) fn frame_content_window(
+        &mut self,
+        frame: &Frame,
+        start: usize,
+        end: usize,
+    ) -> Result<(Vec<u8>, usize)> {
+        let reads_payload = frame.search_text.is_none()
+            && frame.chunk_manifest.is_none()
+            && frame
+                .metadata
+                .as_ref()
+                .and_then(|meta| meta.mime.as_deref())
+                .is_none_or(mime_is_text);
+        if reads_payload && !self.verify_payloads {
+            if let Some(length) = frame
+                .canonical_length
+                .and_then(|length| usize::try_from(length).ok())
+            {
+                let end = end.min(length);
+                let start = start.min(end);
+                if let Some(bytes) = self.seekable_range(frame, start as u64, end as u64)? {
+                    return Ok((bytes, length));
+                }
+            }
+        }
+        let content = self.frame_content(frame)?;
+        let end = end.min(content.len());
+        let start = start.min(end);
+        Ok((content.as_bytes()[start..end].to_vec(), content.len()))
+    }
+
+    /// Canonical bytes `start..end` of a contiguous zstd payload written with a seek table,
+    /// decoding only the frames covering them; `None` for other payloads. Does not verify the
+    /// payload checksum.
+    fn seekable_range(&self, frame: &Frame, start: u64, end: u64) -> Result<Option<Vec<u8>>> {
+        if frame.canonical_encoding != CanonicalEncoding::Zstd
+            || frame.payload_length < SEEK_TABLE_FOOTER_SIZE as u64
+            || start >= end
+        {
+            return Ok(None);
+        }
+        self.ensure_not_quarantined(frame.id)?;
+        self.validate_frame_bounds(frame)?;
+        let payload_end = frame.payload_offset + frame.payload_length;
+        let mut file = &*self.file;
+        let mut footer = [0u8; SEEK_TABLE_FOOTER_SIZE];
+        file.seek(SeekFrom::Start(payload_end - SEEK_TABLE_FOOTER_SIZE as u64))?;
+        file.read_exact(&mut footer)?;
+        let Some(table_len) =
+            SeekTable::stored_len(&footer).filter(|len| *len <= frame.payload_length)
+        else {
+            return Ok(None);
+        };
+        // Safe: bounded by the payload length, checked against MAX_FRAME_BYTES
+        #[allow(clippy::cast_possible_truncation)]
+        let mut table = vec![0u8; table_len as usize];
+        file.seek(SeekFrom::Start(payload_end - table_len))?;
+        file.read_exact(&mut table)?;
+        let Some(table) = SeekTable::parse(&table)
+            .filter(|table| Some(table.canonical_len()) == frame.canonical_length)
+        else {
+            return Ok(None);
+        };
+        let Some(span) = table
+            .span(start, end)
+            .filter(|span| span.compressed_offset + span.compressed_length <= frame.payload_length)
+        else {
+            return Ok(None);
+        };
+
+        file.seek(SeekFrom::Start(
+            frame.payload_offset + span.compressed_offset,
+        ))?;
+        let decoder = zstd::stream::read::Decoder::new(file.take(span.compressed_length))?;
+        let mut decoded = Vec::new();
+        decoder
+            .take(end - span.canonical_offset)
+            .read_to_end(&mut decoded)
+            .map_err(|_| MemvidError::InvalidFrame {
+                frame_id: frame.id,
+                reason: "failed to decode canonical payload",
+            })?;
+        if decoded.len() as u64 != end - span.canonical_offset {
+            return Err(MemvidError::InvalidFrame {
+                frame_id: frame.id,
+                reason: "canonical length mismatch",
+            });
+        }
+        // Safe: bounded by the range, which lies within the decoded frames
+        #[allow(clippy::cast_possible_truncation)]
+        decoded.drain(..(start - span.canonical_offset) as usize);
+        Ok(Some(decoded))
+    }
+
     pub fn frame_embedding(&mut self, frame_id: FrameId) -> Result<Option<Vec<f32>>> {
         if !self.vec_enabled {
             return Ok(None);
         }
         self.ensure_vec_index()?;
-        Ok(self
-            .vec_index
-            .as_ref()
-            .and_then(|index| index.embedding_for(frame_id).map(<[f32]>::to_vec)))
+        let Some(index) = self.vec_index.as_ref() else {
+            return Ok(None);
+        };
+        if let Some(embedding) = index.embedding_for(frame_id) {
+            return Ok(Some(embedding.to_vec()));
+        }
+        // Quantized indexes keep their full-precision vectors on disk.
+        let range = index
+            .full_precision()
+            .and_then(|store| store.vector_range(frame_id));
+        match (range, self.toc.indexes.vec.as_ref()) {
+            (Some((offset, len)), Some(_)) => {
+                let bytes = self.read_vec_index_range(offset, len)?;
+                Ok(Some(crate::vec_pq::FullPrecisionStore::decode_vector(
+                    &bytes,
+                )))
+            }
+            _ => Ok(None),
+        }
     }
 
     pub fn frame_context(&mut self, frame_id: FrameId, query: &str) -> Result<(String, usize)> {
@@ -492,6 +871,46 @@ impl Memvid {
         Ok(payloads)
     }
 
+    /// Offset of chunk `index` in `parent`'s text and the chunk's bytes, or `None` past the
+    /// last chunk. Only that chunk is decoded when the chunks before it record their lengths.
+    fn document_chunk_at(
+        &mut self,
+        parent: &Frame,
+        index: usize,
+    ) -> Result<Option<(usize, Vec<u8>)>> {
+        let mut children = self.document_chunk_frames(parent.id);
+        let complete = parent
+            .chunk_manifest
+            .as_ref()
+            .is_some_and(|manifest| manifest.chunks.len() == children.len());
+        children.sort_by_key(|child| (child.chunk_index.unwrap_or(u32::MAX), child.id));
+        let offset = children
+            .get(..index)
+            .filter(|_| complete && index < children.len())
+            .and_then(|before| {
+                before
+                    .iter()
+                    .map(|child| {
+                        child
+                            .canonical_length
+                            .and_then(|length| usize::try_from(length).ok())
+                    })
+                    .sum::<Option<usize>>()
+            });
+        if let Some(offset) = offset {
+            let child = &children[index];
+            let bytes = self.frame_canonical_bytes(child)?;
+            return Ok(Some((offset, bytes)));
+        }
+
+        let mut payloads = self.document_chunk_payloads(parent)?;
+        if index >= payloads.len() {
+            return Ok(None);
+        }
+        let offset = payloads[..index].iter().map(|(_, bytes)| bytes.len()).sum();
+        Ok(Some((offset, payloads.swap_remove(index).1)))
+    }
+
     fn document_chunk_frames(&self, parent_id: FrameId) -> Vec<Frame> {
         let mut frames: Vec<Frame> = self
             .toc
@@ -560,23 +979,17 @@ impl Memvid {
                     if let Ok(index) = usize::try_from(parent_id) {
                         if let Some(parent) = self.toc.frames.get(index).cloned() {
                             if parent.chunk_manifest.is_some() {
-                                if let Ok(payloads) = self.document_chunk_payloads(&parent) {
-                                    if let Some(idx) = frame.chunk_index {
-                                        let idx = idx as usize;
-                                        if idx < payloads.len() {
-                                            let mut offset = 0usize;
-                                            for (_, bytes) in payloads.iter().take(idx) {
-                                                offset += bytes.len();
-                                            }
-                                            let (_, bytes) = &payloads[idx];
-                                            let text = String::from_utf8_lossy(bytes).into_owned();
-                                            let end = offset + bytes.len();
-                                            return Ok(ChunkInfo {
-                                                start: offset,
-                                                end,
-                                                text,
-                                            });
-                                        }
+                                if let Some(idx) = frame.chunk_index {
+                                    if let Ok(Some((offset, bytes))) =
+                                        self.document_chunk_at(&parent, idx as usize)
+                                    {
+                                        let text = String::from_utf8_lossy(&bytes).into_owned();
+                                        let end = offset + bytes.len();
+                                        return Ok(ChunkInfo {
+                                            start: offset,
+                                            end,
+                                            text,
+                                        });
                                     }
                                 }
                             }
@@ -616,16 +1029,25 @@ impl Memvid {
     }
 
     pub(crate) fn read_frame_payload_bytes(&mut self, frame: &Frame) -> Result<Vec<u8>> {
-        self.validate_frame_bounds(frame)?;
-        self.file.seek(SeekFrom::Start(frame.payload_offset))?;
-        // Safe: guarded by MAX_FRAME_BYTES check
-        #[allow(clippy::cast_possible_truncation)]
-        let mut buf = vec![0u8; frame.payload_length as usize];
-        self.file.read_exact(&mut buf)?;
+        self.ensure_not_quarantined(frame.id)?;
+        let buf = if let Some(extents) = self.frame_blob_extents(frame)? {
+            self.read_blob_extents(&extents)?
+        } else {
+            self.validate_frame_bounds(frame)?;
+            self.file.seek(SeekFrom::Start(frame.payload_offset))?;
+            // Safe: guarded by MAX_FRAME_BYTES check
+            #[allow(clippy::cast_possible_truncation)]
+            let mut buf = vec![0u8; frame.payload_length as usize];
+            self.file.read_exact(&mut buf)?;
+            buf
+        };
+        if self.verify_payloads {
+            self.check_payload_checksum(frame, &blake3::hash(&buf))?;
+        }
         Ok(buf)
     }
 
-    pub(crate) fn validate_frame_bounds(&mut self, frame: &Frame) -> Result<()> {
+    pub(crate) fn validate_frame_bounds(&self, frame: &Frame) -> Result<()> {
         if frame.payload_length == 0 {
             return Ok(());
         }
@@ -637,30 +1059,24 @@ impl Memvid {
             });
         }
 
-        let wal_region_end = self
-            .header
-            .wal_offset
-            .checked_add(self.header.wal_size)
+        let frame_end = frame
+            .payload_offset
+            .checked_add(frame.payload_length)
             .ok_or(MemvidError::InvalidFrame {
                 frame_id: frame.id,
-                reason: "wal region overflow",
+                reason: "payload range overflow",
             })?;
 
-        if frame.payload_offset < wal_region_end {
+        let overlaps_relocated_wal = self.header.wal_relocated()
+            && frame.payload_offset < self.header.wal_end()
+            && frame_end > self.header.wal_offset;
+        if frame.payload_offset < self.header.data_start || overlaps_relocated_wal {
             return Err(MemvidError::InvalidFrame {
                 frame_id: frame.id,
                 reason: "payload overlaps wal region",
             });
         }
 
-        let frame_end = frame
-            .payload_offset
-            .checked_add(frame.payload_length)
-            .ok_or(MemvidError::InvalidFrame {
-                frame_id: frame.id,
-                reason: "payload range overflow",
-            })?;
-
         if frame_end > self.data_end {
             return Err(MemvidError::InvalidFrame {
                 frame_id: frame.id,
@@ -0,0 +1,502 @@
+//! Export of frames to NDJSON and Parquet.
+//!
+//! Frames are selected and ordered through the time index, like the timeline, then read in
+//! batches so only one batch of extracted text is held in memory. NDJSON writes one object
+//! per frame; Parquet writes one row group per batch.
+
+use std::io::{BufWriter, Write};
+
+use serde_json::{Map, Value};
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::timeline::timeline_entries;
+use crate::types::{
+    DEFAULT_FRAME_EXPORT_BATCH_SIZE, Frame, FrameExportColumn, FrameExportFilter,
+    FrameExportFormat, FrameId, FrameStatus,
+};
+
+impl Memvid {
+    /// Write the frames `filter` selects to `writer` in `format`, in time order (newest
+    /// first with `filter.query.reverse`). Returns the number of frames written.
+    ///
+    /// # Errors
+    /// Returns [`MemvidError::FeatureUnavailable`](crate::MemvidError::FeatureUnavailable) for Parquet without the `parquet` feature,
+    /// or an error if the time index or a frame cannot be read, or writing fails.
+    pub fn export_frames<W: Write + Send>(
+        &mut self,
+        format: FrameExportFormat,
+        filter: &FrameExportFilter,
+        writer: W,
+    ) -> Result<usize> {
+        #[cfg(not(feature = "parquet"))]
+        if format == FrameExportFormat::Parquet {
+            return Err(crate::MemvidError::FeatureUnavailable { feature: "parquet" });
+        }
+
+        let frame_ids = self.export_frame_ids(filter)?;
+        let columns = filter.selected_columns();
+        let batch_size = match filter.batch_size {
+            0 => DEFAULT_FRAME_EXPORT_BATCH_SIZE,
+            size => size,
+        };
+
+        let mut out = BufWriter::new(writer);
+        match format {
+            FrameExportFormat::Ndjson => {
+                for batch in frame_ids.chunks(batch_size) {
+                    for row in self.export_rows(batch, &columns)? {
+                        serde_json::to_writer(&mut out, &row.to_json(&columns))
+                            .map_err(std::io::Error::from)?;
+                        out.write_all(b"\n")?;
+                    }
+                }
+                out.flush()?;
+            }
+            #[cfg(feature = "parquet")]
+            FrameExportFormat::Parquet => {
+                let mut file = parquet_export::ParquetFrameWriter::new(&mut out, &columns)?;
+                for batch in frame_ids.chunks(batch_size) {
+                    let rows = self.export_rows(batch, &columns)?;
+                    file.write_batch(&rows)?;
+                }
+                file.finish()?;
+                out.flush()?;
+            }
+            #[cfg(not(feature = "parquet"))]
+            FrameExportFormat::Parquet => unreachable!("rejected above"),
+        }
+        Ok(frame_ids.len())
+    }
+
+    /// Active frames passing `filter`, in output order.
+    fn export_frame_ids(&mut self, filter: &FrameExportFilter) -> Result<Vec<FrameId>> {
+        let query = &filter.query;
+        let entries = timeline_entries(
+            self,
+            query.since,
+            query.until,
+            query.geo.as_ref(),
+            #[cfg(feature = "temporal_track")]
+            query.temporal.as_ref(),
+        )?;
+        let limit = query.limit.map_or(usize::MAX, |nz| {
+            usize::try_from(nz.get()).unwrap_or(usize::MAX)
+        });
+        let selected = |id: &FrameId| {
+            usize::try_from(*id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+                .is_some_and(|frame| {
+                    frame.status == FrameStatus::Active
+                        && filter.tags.iter().all(|tag| frame.tags.contains(tag))
+                })
+        };
+        let ids = entries.iter().map(|entry| entry.frame_id);
+        Ok(if query.reverse {
+            ids.rev().filter(selected).take(limit).collect()
+        } else {
+            ids.filter(selected).take(limit).collect()
+        })
+    }
+
+    fn export_rows(
+        &mut self,
+        frame_ids: &[FrameId],
+        columns: &[FrameExportColumn],
+    ) -> Result<Vec<FrameRow>> {
+        let with_text = columns.contains(&FrameExportColumn::Text);
+        let with_metadata = columns.contains(&FrameExportColumn::Metadata);
+        let mut rows = Vec::with_capacity(frame_ids.len());
+        for &frame_id in frame_ids {
+            let frame = self.frame_by_id(frame_id)?;
+            let text = if with_text {
+                Some(self.frame_content(&frame)?)
+            } else {
+                None
+            };
+            let metadata = if with_metadata {
+                metadata_object(&frame)
+            } else {
+                None
+            };
+            rows.push(FrameRow {
+                id: frame.id,
+                uri: frame
+                    .uri
+                    .clone()
+                    .unwrap_or_else(|| crate::default_uri(frame.id)),
+                title: frame.title,
+                timestamp: frame.timestamp,
+                tags: frame.tags,
+                text,
+                metadata,
+            });
+        }
+        Ok(rows)
+    }
+}
+
+/// One exported frame. `text` and `metadata` are only filled when selected.
+struct FrameRow {
+    id: FrameId,
+    uri: String,
+    title: Option<String>,
+    timestamp: i64,
+    tags: Vec<String>,
+    text: Option<String>,
+    metadata: Option<Map<String, Value>>,
+}
+
+impl FrameRow {
+    fn to_json(&self, columns: &[FrameExportColumn]) -> Value {
+        let mut object = Map::new();
+        for &column in columns {
+            let value = match column {
+                FrameExportColumn::Id => Value::from(self.id),
+                FrameExportColumn::Uri => Value::from(self.uri.as_str()),
+                FrameExportColumn::Title => self.title.as_deref().map_or(Value::Null, Value::from),
+                FrameExportColumn::Timestamp => Value::from(self.timestamp),
+                FrameExportColumn::Tags => Value::from(self.tags.clone()),
+                FrameExportColumn::Text => self.text.as_deref().map_or(Value::Null, Value::from),
+                FrameExportColumn::Metadata => {
+                    self.metadata.clone().map_or(Value::Null, Value::Object)
+                }
+            };
+            object.insert(column.name().to_string(), value);
+        }
+        Value::Object(object)
+    }
+}
+
+/// Extra metadata entries, with document metadata under `document`. Values that hold JSON
+/// (as written by `PutOptionsBuilder::metadata_entry`) are embedded as JSON.
+fn metadata_object(frame: &Frame) -> Option<Map<String, Value>> {
+    let mut object: Map<String, Value> = frame
+        .extra_metadata
+        .iter()
+        .map(|(key, value)| {
+            let value = serde_json::from_str::<Value>(value)
+                .ok()
+                .filter(|parsed| parsed.is_object() || parsed.is_array())
+                .unwrap_or_else(|| Value::from(value.as_str()));
+            (key.clone(), value)
+        })
+        .collect();
+    if let Some(document) = frame
+        .metadata
+        .as_ref()
+        .and_then(|metadata| serde_json::to_value(metadata).ok())
+    {
+        object.insert("document".to_string(), document);
+    }
+    (!object.is_empty()).then_some(object)
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_export {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use parquet::basic::Compression;
+    use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::{SerializedColumnWriter, SerializedFileWriter};
+    use parquet::schema::parser::parse_message_type;
+
+    use super::FrameRow;
+    use crate::error::Result;
+    use crate::types::FrameExportColumn;
+
+    /// A Parquet file being written, one row group per batch.
+    pub(super) struct ParquetFrameWriter<'c, W: Write + Send> {
+        inner: SerializedFileWriter<W>,
+        columns: &'c [FrameExportColumn],
+    }
+
+    impl<'c, W: Write + Send> ParquetFrameWriter<'c, W> {
+        pub(super) fn new(writer: W, columns: &'c [FrameExportColumn]) -> Result<Self> {
+            let fields: Vec<&str> = columns.iter().map(|&column| field(column)).collect();
+            let schema =
+                parse_message_type(&format!("message frame {{ {}; }}", fields.join("; ")))?;
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let inner = SerializedFileWriter::new(writer, Arc::new(schema), Arc::new(properties))?;
+            Ok(Self { inner, columns })
+        }
+
+        pub(super) fn write_batch(&mut self, rows: &[FrameRow]) -> Result<()> {
+            if rows.is_empty() {
+                return Ok(());
+            }
+            let mut group = self.inner.next_row_group()?;
+            for &column in self.columns {
+                let Some(mut writer) = group.next_column()? else {
+                    break;
+                };
+                write_column(&mut writer, column, rows)?;
+                writer.close()?;
+            }
+            group.close()?;
+            Ok(())
+        }
+
+        pub(super) fn finish(self) -> Result<()> {
+            self.inner.close()?;
+            Ok(())
+        }
+    }
+
+    fn field(column: FrameExportColumn) -> &'static str {
+        match column {
+            FrameExportColumn::Id => "required int64 id",
+            FrameExportColumn::Uri => "required binary uri (UTF8)",
+            FrameExportColumn::Title => "optional binary title (UTF8)",
+            FrameExportColumn::Timestamp => "required int64 timestamp (TIMESTAMP(MILLIS,true))",
+            FrameExportColumn::Tags => "repeated binary tags (UTF8)",
+            FrameExportColumn::Text => "optional binary text (UTF8)",
+            FrameExportColumn::Metadata => "optional binary metadata (JSON)",
+        }
+    }
+
+    fn write_column(
+        writer: &mut SerializedColumnWriter<'_>,
+        column: FrameExportColumn,
+        rows: &[FrameRow],
+    ) -> Result<()> {
+        match column {
+            FrameExportColumn::Id => {
+                let ids: Vec<i64> = rows
+                    .iter()
+                    .map(|row| i64::try_from(row.id).unwrap_or(i64::MAX))
+                    .collect();
+                writer.typed::<Int64Type>().write_batch(&ids, None, None)?;
+            }
+            FrameExportColumn::Uri => {
+                let uris: Vec<ByteArray> = rows.iter().map(|row| row.uri.as_str().into()).collect();
+                writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&uris, None, None)?;
+            }
+            FrameExportColumn::Title => {
+                write_optional(writer, rows.iter().map(|row| row.title.clone()))?;
+            }
+            FrameExportColumn::Timestamp => {
+                let millis: Vec<i64> = rows
+                    .iter()
+                    .map(|row| row.timestamp.saturating_mul(1000))
+                    .collect();
+                writer
+                    .typed::<Int64Type>()
+                    .write_batch(&millis, None, None)?;
+            }
+            FrameExportColumn::Tags => {
+                let mut values = Vec::new();
+                let mut definitions = Vec::new();
+                let mut repetitions = Vec::new();
+                for row in rows {
+                    if row.tags.is_empty() {
+                        definitions.push(0);
+                        repetitions.push(0);
+                    }
+                    for (index, tag) in row.tags.iter().enumerate() {
+                        values.push(ByteArray::from(tag.as_str()));
+                        definitions.push(1);
+                        repetitions.push(i16::from(index > 0));
+                    }
+                }
+                writer.typed::<ByteArrayType>().write_batch(
+                    &values,
+                    Some(&definitions),
+                    Some(&repetitions),
+                )?;
+            }
+            FrameExportColumn::Text => {
+                write_optional(writer, rows.iter().map(|row| row.text.clone()))?;
+            }
+            FrameExportColumn::Metadata => {
+                write_optional(
+                    writer,
+                    rows.iter().map(|row| {
+                        row.metadata
+                            .as_ref()
+                            .map(|object| serde_json::Value::Object(object.clone()).to_string())
+                    }),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_optional(
+        writer: &mut SerializedColumnWriter<'_>,
+        values: impl Iterator<Item = Option<String>>,
+    ) -> Result<()> {
+        let mut present = Vec::new();
+        let mut definitions = Vec::new();
+        for value in values {
+            definitions.push(i16::from(value.is_some()));
+            present.extend(value.map(|value| ByteArray::from(value.into_bytes())));
+        }
+        writer
+            .typed::<ByteArrayType>()
+            .write_batch(&present, Some(&definitions), None)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DocMetadata, PutOptions};
+    use std::num::NonZeroU64;
+    use tempfile::tempdir;
+
+    fn sample(dir: &tempfile::TempDir) -> Memvid {
+        let mut mem = Memvid::create(dir.path().join("export.mv2")).expect("create");
+        for (timestamp, uri, tag) in [
+            (300, "mv2://notes/c", "work"),
+            (100, "mv2://notes/a", "work"),
+            (200, "mv2://notes/b", "home"),
+        ] {
+            let options = PutOptions::builder()
+                .timestamp(timestamp)
+                .uri(uri)
+                .title(uri.rsplit('/').next().unwrap())
+                .push_tag(tag)
+                .metadata_entry("source", serde_json::json!({"app": "notes"}))
+                .build();
+            let mut options = options;
+            options.metadata = Some(DocMetadata {
+                mime: Some("text/plain".into()),
+                ..DocMetadata::default()
+            });
+            mem.put_bytes_with_options(format!("note {uri}").as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        mem
+    }
+
+    fn ndjson_rows(mem: &mut Memvid, filter: &FrameExportFilter) -> Vec<Value> {
+        let mut out = Vec::new();
+        let written = mem
+            .export_frames(FrameExportFormat::Ndjson, filter, &mut out)
+            .expect("export");
+        let rows: Vec<Value> = String::from_utf8(out)
+            .expect("utf8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        assert_eq!(rows.len(), written);
+        rows
+    }
+
+    #[test]
+    fn ndjson_export_follows_time_order_and_columns() {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = sample(&dir);
+
+        let rows = ndjson_rows(&mut mem, &FrameExportFilter::default());
+        let uris: Vec<_> = rows
+            .iter()
+            .map(|row| row["uri"].as_str().unwrap())
+            .collect();
+        assert_eq!(uris, ["mv2://notes/a", "mv2://notes/b", "mv2://notes/c"]);
+        assert_eq!(rows[0]["timestamp"], 100);
+        assert_eq!(rows[0]["title"], "a");
+        assert!(
+            rows[0]["tags"]
+                .as_array()
+                .unwrap()
+                .contains(&Value::from("work"))
+        );
+        assert!(
+            rows[0]["text"]
+                .as_str()
+                .unwrap()
+                .contains("note mv2://notes/a")
+        );
+        assert_eq!(rows[0]["metadata"]["source"]["app"], "notes");
+        assert_eq!(rows[0]["metadata"]["document"]["mime"], "text/plain");
+
+        let filter = FrameExportFilter {
+            query: crate::types::TimelineQuery {
+                reverse: true,
+                limit: NonZeroU64::new(1),
+                ..Default::default()
+            },
+            tags: vec!["work".into()],
+            columns: vec![FrameExportColumn::Uri, FrameExportColumn::Id],
+            batch_size: 1,
+        };
+        let rows = ndjson_rows(&mut mem, &filter);
+        assert_eq!(rows.len(), 1);
+        let row = rows[0].as_object().unwrap();
+        assert_eq!(row.keys().collect::<Vec<_>>(), ["id", "uri"]);
+        assert_eq!(row["uri"], "mv2://notes/c");
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    #[test]
+    fn parquet_export_requires_feature() {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = sample(&dir);
+        let err = mem
+            .export_frames(
+                FrameExportFormat::Parquet,
+                &FrameExportFilter::default(),
+                Vec::new(),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::MemvidError::FeatureUnavailable { feature: "parquet" }
+        ));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_export_writes_row_group_per_batch() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let dir = tempdir().expect("tempdir");
+        let mut mem = sample(&dir);
+        let path = dir.path().join("frames.parquet");
+        let filter = FrameExportFilter {
+            batch_size: 2,
+            ..FrameExportFilter::default()
+        };
+        let file = std::fs::File::create(&path).expect("create parquet");
+        let written = mem
+            .export_frames(FrameExportFormat::Parquet, &filter, file)
+            .expect("export");
+        assert_eq!(written, 3);
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).expect("open"))
+            .expect("parquet reader");
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 3);
+        assert_eq!(metadata.num_row_groups(), 2);
+        let names: Vec<_> = metadata
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "id",
+                "uri",
+                "title",
+                "timestamp",
+                "tags",
+                "text",
+                "metadata"
+            ]
+        );
+    }
+}
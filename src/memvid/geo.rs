@@ -0,0 +1,133 @@
+//! Geospatial lookups for `Memvid`.
+//!
+//! Frame locations are derived from metadata, so the index is not persisted: it is built on
+//! the first geo query and extended with frames committed since. Frames are append-only, which
+//! keeps the catch-up a scan of the new tail only.
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    Frame, FrameId, FrameStatus, GEO_LAT_KEY, GEO_LON_KEY, GeoFilter, GeoIndex, GeoPoint,
+};
+
+/// Location index plus the number of TOC frames it has seen.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GeoTrack {
+    index: GeoIndex,
+    scanned: usize,
+}
+
+impl Memvid {
+    /// Location of a frame from its EXIF GPS block or its `geo_lat`/`geo_lon` extra metadata.
+    #[must_use]
+    pub fn frame_location(frame: &Frame) -> Option<GeoPoint> {
+        if let Some(gps) = frame
+            .metadata
+            .as_ref()
+            .and_then(|meta| meta.exif.as_ref())
+            .and_then(|exif| exif.gps.as_ref())
+        {
+            return GeoPoint::new(gps.latitude, gps.longitude);
+        }
+        let coordinate = |key: &str| {
+            frame
+                .extra_metadata
+                .get(key)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+        };
+        GeoPoint::new(coordinate(GEO_LAT_KEY)?, coordinate(GEO_LON_KEY)?)
+    }
+
+    /// Active frames located inside `filter`, in ascending id order.
+    pub fn frame_ids_in_geo(&mut self, filter: &GeoFilter) -> Result<Vec<FrameId>> {
+        let track = &mut self.geo_track;
+        for frame in self.toc.frames.iter().skip(track.scanned) {
+            if let Some(point) = Self::frame_location(frame) {
+                track.index.insert(frame.id, point);
+            }
+        }
+        track.scanned = self.toc.frames.len();
+
+        // Re-check against the current frame: status and metadata can change in place.
+        Ok(track
+            .index
+            .query(filter)
+            .into_iter()
+            .filter(|id| {
+                usize::try_from(*id)
+                    .ok()
+                    .and_then(|index| self.toc.frames.get(index))
+                    .is_some_and(|frame| {
+                        frame.status == FrameStatus::Active
+                            && Self::frame_location(frame).is_some_and(|p| filter.contains(&p))
+                    })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DocExifMetadata, DocGpsMetadata, DocMetadata, PutOptions, TimelineQuery};
+
+    #[test]
+    fn geo_filter_restricts_timeline_and_catches_up() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("geo.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        let photo = |lat: f64, lon: f64, ts: i64| PutOptions {
+            timestamp: Some(ts),
+            metadata: Some(DocMetadata {
+                exif: Some(DocExifMetadata {
+                    gps: Some(DocGpsMetadata {
+                        latitude: lat,
+                        longitude: lon,
+                    }),
+                    ..DocExifMetadata::default()
+                }),
+                ..DocMetadata::default()
+            }),
+            ..PutOptions::default()
+        };
+        mem.put_bytes_with_options(b"office party", photo(37.7749, -122.4194, 1_709_300_000))
+            .expect("put");
+        mem.put_bytes_with_options(b"trip to london", photo(51.5072, -0.1276, 1_709_400_000))
+            .expect("put");
+        mem.put_bytes_with_options(
+            b"lunch near the office",
+            photo(37.779, -122.4312, 1_700_000_000),
+        )
+        .expect("put");
+        mem.commit().expect("commit");
+
+        let near_office = GeoFilter::within_km(37.7749, -122.4194, 5.0);
+        assert_eq!(mem.frame_ids_in_geo(&near_office).expect("geo"), [0, 2]);
+
+        let mut tagged = PutOptions::default();
+        tagged
+            .extra_metadata
+            .insert(GEO_LAT_KEY.into(), "37.776".into());
+        tagged
+            .extra_metadata
+            .insert(GEO_LON_KEY.into(), "-122.42".into());
+        tagged.timestamp = Some(1_709_500_000);
+        mem.put_bytes_with_options(b"office notes", tagged)
+            .expect("put");
+        mem.commit().expect("commit");
+        assert_eq!(mem.frame_ids_in_geo(&near_office).expect("geo"), [0, 2, 3]);
+
+        let march = mem
+            .timeline(
+                TimelineQuery::builder()
+                    .since(1_709_251_200)
+                    .until(1_711_929_599)
+                    .geo(near_office)
+                    .no_limit()
+                    .build(),
+            )
+            .expect("timeline");
+        let ids: Vec<FrameId> = march.iter().map(|entry| entry.frame_id).collect();
+        assert_eq!(ids, [0, 3]);
+    }
+}
@@ -0,0 +1,424 @@
+//! Ingestion of a git repository at a revision.
+//!
+//! Files are read from the revision's tree, not the working directory, in batches of
+//! [`DEFAULT_INGEST_BATCH_SIZE`] with one commit per batch. Source files in a known language
+//! are chunked at function and block boundaries; other text goes through the usual put path.
+//! Every file frame is attributed to the newest commit that touched the file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::error::{MemvidError, Result};
+use crate::git::GitReader;
+use crate::memvid::chunks::plan_code_chunks;
+use crate::memvid::ingest_dir::GlobMatcher;
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::pipeline::PreparedPut;
+use crate::structure::code_language;
+use crate::types::{
+    DEFAULT_INGEST_BATCH_SIZE, FrameId, FrameRole, FrameStatus, GIT_AUTHOR_KEY, GIT_BLOB_KEY,
+    GIT_COMMIT_FRAME_KIND, GIT_COMMIT_KEY, GIT_FILE_FRAME_KIND, GIT_LANGUAGE_KEY, GIT_PATH_KEY,
+    GitCommit, GitIngestOptions, GitIngestReport, GitTreeEntry, PutOptions,
+};
+
+/// Bytes inspected for NUL when telling binary files from text, as git does.
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+impl Memvid {
+    /// Ingest the files of the repository containing `path` at `options.revision`, and
+    /// optionally its commit messages, committing after each batch.
+    ///
+    /// Files become frames of kind [`GIT_FILE_FRAME_KIND`] with URI `git://<repo>/<path>`,
+    /// timestamped at and tagged with the newest commit that touched them. A file already
+    /// stored under its URI is superseded if its blob changed and left alone otherwise; file
+    /// frames of the repository whose path left the tree are tombstoned. Commit frames are
+    /// written once per commit.
+    ///
+    /// # Errors
+    /// Fails if the repository or revision cannot be read, a glob is invalid, or a write or
+    /// commit fails.
+    pub fn ingest_git_repo(
+        &mut self,
+        path: impl AsRef<Path>,
+        options: &GitIngestOptions,
+    ) -> Result<GitIngestReport> {
+        self.ensure_writable()?;
+        let reader = GitReader::open(path)?;
+        let matcher = GlobMatcher::new(&options.globs)?;
+        let head = reader.resolve(&options.revision)?;
+        let history = reader.commits(&head, None)?;
+        let Some(head_commit) = history.first() else {
+            return Err(MemvidError::InvalidConfig {
+                reason: format!("revision {} has no commits", options.revision),
+            });
+        };
+        let mut last_touched: HashMap<&str, &GitCommit> = HashMap::new();
+        for commit in &history {
+            for file in &commit.files {
+                last_touched.entry(file.as_str()).or_insert(commit);
+            }
+        }
+
+        let repo = reader.root().file_name().map_or_else(
+            || "repo".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let file_prefix = format!("git://{repo}/");
+        let stored: HashMap<String, (FrameId, Option<String>)> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.status == FrameStatus::Active
+                    && frame.role == FrameRole::Document
+                    && frame.kind.as_deref() == Some(GIT_FILE_FRAME_KIND)
+            })
+            .filter_map(|frame| {
+                let uri = frame
+                    .uri
+                    .as_ref()
+                    .filter(|uri| uri.starts_with(&file_prefix))?;
+                let blob = frame.extra_metadata.get(GIT_BLOB_KEY).cloned();
+                Some((uri.clone(), (frame.id, blob)))
+            })
+            .collect();
+
+        let chunk_chars = self.chunk_chars(options.chunk_chars);
+        let entries: Vec<GitTreeEntry> = reader
+            .tree(&head)?
+            .into_iter()
+            .filter(|entry| matcher.matches(&entry.path))
+            .collect();
+        let mut report = GitIngestReport {
+            head: head.clone(),
+            ..GitIngestReport::default()
+        };
+        let mut present = HashSet::new();
+
+        for batch in entries.chunks(DEFAULT_INGEST_BATCH_SIZE) {
+            let mut wanted = Vec::new();
+            for entry in batch {
+                let uri = format!("{file_prefix}{}", entry.path);
+                let previous = stored.get(&uri);
+                present.insert(uri);
+                if previous.is_some_and(|(_, blob)| blob.as_deref() == Some(entry.blob.as_str())) {
+                    report.unchanged += 1;
+                } else if entry.size == 0 || entry.size > options.max_file_bytes {
+                    report.skipped += 1;
+                } else {
+                    wanted.push(entry);
+                }
+            }
+            let blobs: Vec<&str> = wanted.iter().map(|entry| entry.blob.as_str()).collect();
+            let mut changed = false;
+            for (entry, bytes) in wanted.iter().zip(reader.read_blobs(&blobs)?) {
+                let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+                let Ok(text) = std::str::from_utf8(&bytes) else {
+                    report.skipped += 1;
+                    continue;
+                };
+                if sniff.contains(&0) {
+                    report.skipped += 1;
+                    continue;
+                }
+                let commit = last_touched
+                    .get(entry.path.as_str())
+                    .copied()
+                    .unwrap_or(head_commit);
+                let uri = format!("{file_prefix}{}", entry.path);
+                let language = code_language(&entry.path);
+                let put = git_file_put_options(uri.clone(), entry, commit, language);
+                if let Some(plan) =
+                    language.and_then(|language| plan_code_chunks(text, language, chunk_chars))
+                {
+                    self.prepared_put = Some(PreparedPut {
+                        raw_chunk_plan: Some(plan),
+                        ..PreparedPut::default()
+                    });
+                }
+                let frame_id = self.next_frame_id();
+                if let Some(&(previous, _)) = stored.get(&uri) {
+                    self.update_frame(previous, Some(bytes), put, None)?;
+                    report.updated.push(frame_id);
+                } else {
+                    self.put_bytes_with_options(&bytes, put)?;
+                    report.added.push(frame_id);
+                }
+                changed = true;
+            }
+            if changed {
+                self.commit()?;
+            }
+        }
+
+        let mut removed: Vec<FrameId> = stored
+            .iter()
+            .filter(|(uri, _)| {
+                !present.contains(*uri)
+                    && uri
+                        .strip_prefix(&file_prefix)
+                        .is_some_and(|path| matcher.matches(path))
+            })
+            .map(|(_, (frame_id, _))| *frame_id)
+            .collect();
+        removed.sort_unstable();
+        for frame_id in removed {
+            match self.delete_frame(frame_id) {
+                Ok(_) => report.deleted.push(frame_id),
+                // Pinned frames outlive their file.
+                Err(MemvidError::FramePinned { .. }) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        if options.include_commits {
+            let commit_prefix = format!("git-commit://{repo}/");
+            let stored_commits: HashSet<&str> = self
+                .toc
+                .frames
+                .iter()
+                .filter(|frame| frame.status == FrameStatus::Active)
+                .filter_map(|frame| frame.uri.as_deref())
+                .filter(|uri| uri.starts_with(&commit_prefix))
+                .collect();
+            let fresh: Vec<&GitCommit> = history
+                .iter()
+                .take(options.max_commits.unwrap_or(usize::MAX))
+                .filter(|commit| {
+                    !stored_commits.contains(format!("{commit_prefix}{}", commit.hash).as_str())
+                })
+                .collect();
+            for commit in fresh {
+                let frame_id = self.next_frame_id();
+                let put = git_commit_put_options(format!("{commit_prefix}{}", commit.hash), commit);
+                self.put_bytes_with_options(commit_text(commit).as_bytes(), put)?;
+                report.commits.push(frame_id);
+            }
+        }
+        if !report.deleted.is_empty() || !report.commits.is_empty() {
+            self.commit()?;
+        }
+        Ok(report)
+    }
+}
+
+fn git_file_put_options(
+    uri: String,
+    entry: &GitTreeEntry,
+    commit: &GitCommit,
+    language: Option<&str>,
+) -> PutOptions {
+    let mut put = PutOptions::builder()
+        .uri(uri)
+        .title(entry.path.clone())
+        .kind(GIT_FILE_FRAME_KIND)
+        .timestamp(commit.timestamp)
+        .build();
+    let extra = &mut put.extra_metadata;
+    extra.insert(GIT_COMMIT_KEY.to_string(), commit.hash.clone());
+    extra.insert(GIT_AUTHOR_KEY.to_string(), commit.author_line());
+    extra.insert(GIT_PATH_KEY.to_string(), entry.path.clone());
+    extra.insert(GIT_BLOB_KEY.to_string(), entry.blob.clone());
+    if let Some(language) = language {
+        extra.insert(GIT_LANGUAGE_KEY.to_string(), language.to_string());
+    }
+    put
+}
+
+fn git_commit_put_options(uri: String, commit: &GitCommit) -> PutOptions {
+    let mut put = PutOptions::builder()
+        .uri(uri)
+        .title(commit.subject.clone())
+        .kind(GIT_COMMIT_FRAME_KIND)
+        .timestamp(commit.timestamp)
+        .build();
+    put.extra_metadata
+        .insert(GIT_COMMIT_KEY.to_string(), commit.hash.clone());
+    put.extra_metadata
+        .insert(GIT_AUTHOR_KEY.to_string(), commit.author_line());
+    put
+}
+
+/// Commit message followed by the paths it changed, one per line.
+fn commit_text(commit: &GitCommit) -> String {
+    let mut text = commit.subject.clone();
+    if !commit.body.is_empty() {
+        text.push_str("\n\n");
+        text.push_str(&commit.body);
+    }
+    if !commit.files.is_empty() {
+        text.push_str("\n\nChanged files:\n");
+        text.push_str(&commit.files.join("\n"));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", "2024-03-01T12:00:00Z")
+            .env("GIT_COMMITTER_DATE", "2024-03-01T12:00:00Z")
+            .status()
+            .expect("git");
+        assert!(status.success(), "git {args:?}");
+    }
+
+    fn source(functions: usize) -> String {
+        (0..functions)
+            .map(|i| {
+                format!("fn handler_{i}(input: &str) -> usize {{\n    input.len() + {i}\n}}\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn ingests_files_and_commits_and_updates_by_uri() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempdir().expect("tempdir");
+        let repo = dir.path().join("project");
+        fs::create_dir_all(repo.join("src")).expect("mkdir");
+        git(&repo, &["init", "-q"]);
+        fs::write(repo.join("src/lib.rs"), source(60)).expect("write");
+        fs::write(repo.join("README.md"), "Project notes").expect("write");
+        fs::write(repo.join("logo.bin"), [0u8, 1, 2, 3]).expect("write");
+        git(&repo, &["add", "."]);
+        git(
+            &repo,
+            &["commit", "-q", "-m", "Add handlers", "-m", "First cut."],
+        );
+
+        let mut mem = Memvid::create(dir.path().join("git.mv2")).expect("create");
+        let options = GitIngestOptions {
+            include_commits: true,
+            chunk_chars: Some(400),
+            ..GitIngestOptions::default()
+        };
+        let first = mem.ingest_git_repo(&repo, &options).expect("ingest");
+        assert_eq!(first.added.len(), 2);
+        assert_eq!(first.skipped, 1);
+        assert_eq!(first.commits.len(), 1);
+
+        let lib = mem
+            .frame_by_uri("git://project/src/lib.rs")
+            .expect("lib frame");
+        assert_eq!(lib.kind.as_deref(), Some(GIT_FILE_FRAME_KIND));
+        assert_eq!(lib.timestamp, 1_709_294_400);
+        assert_eq!(lib.extra_metadata.get(GIT_COMMIT_KEY), Some(&first.head));
+        assert_eq!(
+            lib.extra_metadata.get(GIT_AUTHOR_KEY).map(String::as_str),
+            Some("Ada <ada@example.com>")
+        );
+        assert_eq!(
+            lib.extra_metadata.get(GIT_LANGUAGE_KEY).map(String::as_str),
+            Some("rust")
+        );
+        assert!(
+            lib.chunk_manifest
+                .is_some_and(|manifest| manifest.chunks.len() > 1)
+        );
+
+        let commit = mem.frame_by_id(first.commits[0]).expect("commit frame");
+        assert_eq!(commit.kind.as_deref(), Some(GIT_COMMIT_FRAME_KIND));
+        assert_eq!(commit.title.as_deref(), Some("Add handlers"));
+        assert_eq!(commit.timestamp, 1_709_294_400);
+        let text = mem.frame_text_by_id(first.commits[0]).expect("commit text");
+        assert!(text.contains("First cut."));
+        assert!(text.contains("src/lib.rs"));
+
+        fs::write(repo.join("src/lib.rs"), source(61)).expect("rewrite");
+        fs::remove_file(repo.join("README.md")).expect("remove");
+        git(&repo, &["commit", "-q", "-am", "Add a handler"]);
+        let second = mem.ingest_git_repo(&repo, &options).expect("reingest");
+        assert_eq!(second.updated.len(), 1);
+        assert!(second.added.is_empty());
+        assert_eq!(second.deleted, [first.added[0]]);
+        assert_eq!(second.commits.len(), 1);
+
+        let revised = mem.frame_by_id(second.updated[0]).expect("revised frame");
+        assert_eq!(revised.supersedes, Some(lib.id));
+        assert_eq!(
+            revised.extra_metadata.get(GIT_COMMIT_KEY),
+            Some(&second.head)
+        );
+    }
+
+    #[cfg(all(feature = "lex", feature = "tree_sitter"))]
+    #[test]
+    fn symbol_field_finds_definition_chunk() {
+        use crate::types::{AclEnforcementMode, CODE_SYMBOLS_KEY, SearchMode, SearchRequest};
+
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempdir().expect("tempdir");
+        let repo = dir.path().join("project");
+        fs::create_dir_all(repo.join("src")).expect("mkdir");
+        git(&repo, &["init", "-q"]);
+        let code = format!(
+            "{}\npub fn parse_query(input: &str) -> usize {{\n    input.split(' ').count()\n}}\n\nfn run() -> usize {{\n    parse_query(\"a b\")\n}}\n",
+            source(30)
+        );
+        fs::write(repo.join("src/lib.rs"), code).expect("write");
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "Add parser"]);
+
+        let mut mem = Memvid::create(dir.path().join("git.mv2")).expect("create");
+        let options = GitIngestOptions {
+            chunk_chars: Some(200),
+            ..GitIngestOptions::default()
+        };
+        mem.ingest_git_repo(&repo, &options).expect("ingest");
+
+        let response = mem
+            .search(SearchRequest {
+                query: "symbol:parse_query".into(),
+                top_k: 10,
+                snippet_chars: 200,
+                uri: None,
+                scope: None,
+                cursor: None,
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+                as_of_frame: None,
+                as_of_ts: None,
+                no_sketch: false,
+                acl_context: None,
+                acl_enforcement_mode: AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: SearchMode::Lexical,
+                time_budget_ms: None,
+            })
+            .expect("search");
+        assert_eq!(response.hits.len(), 1);
+        let chunk = mem
+            .frame_by_id(response.hits[0].frame_id)
+            .expect("chunk frame");
+        assert_eq!(chunk.role, FrameRole::DocumentChunk);
+        let symbols = chunk.extra_metadata.get(CODE_SYMBOLS_KEY).expect("symbols");
+        assert!(symbols.contains("function parse_query@"));
+        let text = mem.frame_text_by_id(chunk.id).expect("chunk text");
+        assert!(text.contains("pub fn parse_query(input: &str)"));
+    }
+}
@@ -0,0 +1,473 @@
+//! Export of the logic mesh and memory cards to GraphML, JSON-LD, and Cypher.
+//!
+//! Mesh entities become entity nodes and mesh relationships become edges. Each memory card
+//! becomes a node with an `about` edge to its entity: the mesh node of the same name if
+//! there is one, else an entity node created for the card's entity. Output is streamed to
+//! the writer node by node.
+
+use std::collections::HashMap;
+use std::io::{BufWriter, Write};
+
+use serde_json::{Value, json};
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{GraphExportFormat, GraphExportStats, LogicMesh, MemoryCard};
+
+/// Vocabulary and node IRI prefix of JSON-LD exports.
+const JSONLD_PREFIX: &str = "urn:memvid:";
+
+impl Memvid {
+    /// Write the logic mesh and memory cards to `writer` in `format`.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    pub fn export_graph<W: Write>(
+        &self,
+        format: GraphExportFormat,
+        writer: W,
+    ) -> Result<GraphExportStats> {
+        let graph = ExportGraph::new(&self.logic_mesh, self.memories_track.cards());
+        let mut out = BufWriter::new(writer);
+        match format {
+            GraphExportFormat::GraphMl => graph.write_graphml(&mut out)?,
+            GraphExportFormat::JsonLd => graph.write_jsonld(&mut out)?,
+            GraphExportFormat::Cypher => graph.write_cypher(&mut out)?,
+        }
+        out.flush()?;
+        Ok(graph.stats())
+    }
+}
+
+/// The mesh and cards, with node IDs resolved for card entities.
+struct ExportGraph<'a> {
+    mesh: &'a LogicMesh,
+    cards: &'a [MemoryCard],
+    /// Node ID of each card, by position in `cards`.
+    card_entities: Vec<String>,
+    /// Entities named only by cards, as (node ID, name), in first-seen order.
+    extra_entities: Vec<(String, &'a str)>,
+}
+
+impl<'a> ExportGraph<'a> {
+    fn new(mesh: &'a LogicMesh, cards: &'a [MemoryCard]) -> Self {
+        let mut by_name: HashMap<String, String> = mesh
+            .nodes
+            .iter()
+            .map(|node| (node.canonical_name.clone(), mesh_node_id(node.id)))
+            .collect();
+        let mut extra_entities = Vec::new();
+        let card_entities = cards
+            .iter()
+            .map(|card| {
+                let name = card.entity.trim().to_lowercase();
+                by_name
+                    .entry(name)
+                    .or_insert_with_key(|name| {
+                        let id = format!("e:{}", encode_id(name));
+                        extra_entities.push((id.clone(), card.entity.as_str()));
+                        id
+                    })
+                    .clone()
+            })
+            .collect();
+        Self {
+            mesh,
+            cards,
+            card_entities,
+            extra_entities,
+        }
+    }
+
+    fn stats(&self) -> GraphExportStats {
+        GraphExportStats {
+            entity_nodes: self.mesh.nodes.len() + self.extra_entities.len(),
+            card_nodes: self.cards.len(),
+            edges: self.mesh.edges.len() + self.cards.len(),
+        }
+    }
+
+    fn write_graphml<W: Write>(&self, out: &mut W) -> Result<()> {
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+        )?;
+        for (id, target, kind) in [
+            ("type", "all", "string"),
+            ("label", "node", "string"),
+            ("kind", "node", "string"),
+            ("slot", "node", "string"),
+            ("value", "node", "string"),
+            ("engine", "node", "string"),
+            ("superseded", "node", "boolean"),
+            ("confidence", "all", "double"),
+            ("frames", "all", "string"),
+        ] {
+            writeln!(
+                out,
+                r#"  <key id="{id}" for="{target}" attr.name="{id}" attr.type="{kind}"/>"#
+            )?;
+        }
+        writeln!(out, r#"  <graph id="memvid" edgedefault="directed">"#)?;
+
+        for node in &self.mesh.nodes {
+            writeln!(out, r#"    <node id="n{}">"#, node.id)?;
+            write_data(out, "type", "entity")?;
+            write_data(out, "label", &node.display_name)?;
+            write_data(out, "kind", node.kind.as_str())?;
+            write_data(out, "confidence", &format_confidence(node.confidence_f32()))?;
+            write_data(out, "frames", &join_frames(&node.frame_ids))?;
+            writeln!(out, "    </node>")?;
+        }
+        for (id, name) in &self.extra_entities {
+            writeln!(out, r#"    <node id="{}">"#, xml_escape(id))?;
+            write_data(out, "type", "entity")?;
+            write_data(out, "label", name)?;
+            writeln!(out, "    </node>")?;
+        }
+        for card in self.cards {
+            writeln!(out, r#"    <node id="c{}">"#, card.id)?;
+            write_data(out, "type", "memory")?;
+            write_data(out, "label", &format!("{}: {}", card.slot, card.value))?;
+            write_data(out, "kind", card.kind.as_str())?;
+            write_data(out, "slot", &card.slot)?;
+            write_data(out, "value", &card.value)?;
+            write_data(out, "engine", &card.engine)?;
+            write_data(out, "superseded", &card.is_superseded().to_string())?;
+            if let Some(confidence) = card.confidence {
+                write_data(out, "confidence", &format_confidence(confidence))?;
+            }
+            write_data(out, "frames", &card.source_frame_id.to_string())?;
+            writeln!(out, "    </node>")?;
+        }
+
+        for edge in &self.mesh.edges {
+            writeln!(
+                out,
+                r#"    <edge source="n{}" target="n{}">"#,
+                edge.from_node, edge.to_node
+            )?;
+            write_data(out, "type", edge.link.as_str())?;
+            write_data(out, "confidence", &format_confidence(edge.confidence_f32()))?;
+            write_data(out, "frames", &edge.frame_id.to_string())?;
+            writeln!(out, "    </edge>")?;
+        }
+        for (card, entity) in self.cards.iter().zip(&self.card_entities) {
+            writeln!(
+                out,
+                r#"    <edge source="c{}" target="{}">"#,
+                card.id,
+                xml_escape(entity)
+            )?;
+            write_data(out, "type", "about")?;
+            writeln!(out, "    </edge>")?;
+        }
+
+        writeln!(out, "  </graph>")?;
+        writeln!(out, "</graphml>")?;
+        Ok(())
+    }
+
+    fn write_jsonld<W: Write>(&self, out: &mut W) -> Result<()> {
+        write!(
+            out,
+            r#"{{"@context":{{"@vocab":"{JSONLD_PREFIX}vocab:"}},"@graph":["#
+        )?;
+        let iri = |id: &str| json!({ "@id": format!("{JSONLD_PREFIX}{id}") });
+        let mut first = true;
+        let mut emit = |out: &mut W, value: Value| -> Result<()> {
+            if !first {
+                out.write_all(b",")?;
+            }
+            first = false;
+            out.write_all(b"\n")?;
+            serde_json::to_writer(&mut *out, &value).map_err(std::io::Error::from)?;
+            Ok(())
+        };
+
+        for node in &self.mesh.nodes {
+            let mut value = iri(&mesh_node_id(node.id));
+            value["@type"] = json!("Entity");
+            value["name"] = json!(node.display_name);
+            value["kind"] = json!(node.kind.as_str());
+            value["confidence"] = json!(node.confidence_f32());
+            value["frames"] = json!(node.frame_ids);
+            emit(out, value)?;
+        }
+        for (id, name) in &self.extra_entities {
+            let mut value = iri(id);
+            value["@type"] = json!("Entity");
+            value["name"] = json!(name);
+            emit(out, value)?;
+        }
+        for (index, edge) in self.mesh.edges.iter().enumerate() {
+            let mut value = iri(&format!("r{index}"));
+            value["@type"] = json!("Relationship");
+            value["link"] = json!(edge.link.as_str());
+            value["source"] = iri(&mesh_node_id(edge.from_node));
+            value["target"] = iri(&mesh_node_id(edge.to_node));
+            value["confidence"] = json!(edge.confidence_f32());
+            value["frame"] = json!(edge.frame_id);
+            emit(out, value)?;
+        }
+        for (card, entity) in self.cards.iter().zip(&self.card_entities) {
+            let mut value = iri(&format!("c{}", card.id));
+            value["@type"] = json!("Memory");
+            value["about"] = iri(entity);
+            value["kind"] = json!(card.kind.as_str());
+            value["slot"] = json!(card.slot);
+            value["value"] = json!(card.value);
+            value["engine"] = json!(card.engine);
+            value["frame"] = json!(card.source_frame_id);
+            value["superseded"] = json!(card.is_superseded());
+            if let Some(confidence) = card.confidence {
+                value["confidence"] = json!(confidence);
+            }
+            emit(out, value)?;
+        }
+
+        out.write_all(b"\n]}\n")?;
+        Ok(())
+    }
+
+    fn write_cypher<W: Write>(&self, out: &mut W) -> Result<()> {
+        for node in &self.mesh.nodes {
+            writeln!(
+                out,
+                "CREATE (:Entity {{id: {}, name: {}, kind: {}, confidence: {}, frames: [{}]}});",
+                cypher_string(&mesh_node_id(node.id)),
+                cypher_string(&node.display_name),
+                cypher_string(node.kind.as_str()),
+                format_confidence(node.confidence_f32()),
+                join_frames(&node.frame_ids),
+            )?;
+        }
+        for (id, name) in &self.extra_entities {
+            writeln!(
+                out,
+                "CREATE (:Entity {{id: {}, name: {}}});",
+                cypher_string(id),
+                cypher_string(name),
+            )?;
+        }
+        for card in self.cards {
+            let confidence = card
+                .confidence
+                .map(|c| format!(", confidence: {}", format_confidence(c)))
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "CREATE (:Memory {{id: 'c{}', kind: {}, entity: {}, slot: {}, value: {}, engine: {}, frame: {}, superseded: {}{confidence}}});",
+                card.id,
+                cypher_string(card.kind.as_str()),
+                cypher_string(&card.entity),
+                cypher_string(&card.slot),
+                cypher_string(&card.value),
+                cypher_string(&card.engine),
+                card.source_frame_id,
+                card.is_superseded(),
+            )?;
+        }
+        for edge in &self.mesh.edges {
+            writeln!(
+                out,
+                "MATCH (a:Entity {{id: 'n{}'}}), (b:Entity {{id: 'n{}'}}) CREATE (a)-[:{} {{confidence: {}, frame: {}}}]->(b);",
+                edge.from_node,
+                edge.to_node,
+                cypher_relationship(edge.link.as_str()),
+                format_confidence(edge.confidence_f32()),
+                edge.frame_id,
+            )?;
+        }
+        for (card, entity) in self.cards.iter().zip(&self.card_entities) {
+            writeln!(
+                out,
+                "MATCH (m:Memory {{id: 'c{}'}}), (e:Entity {{id: {}}}) CREATE (m)-[:ABOUT]->(e);",
+                card.id,
+                cypher_string(entity),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn mesh_node_id(id: u64) -> String {
+    format!("n{id}")
+}
+
+/// Percent-encode everything but unreserved characters, so IDs are valid IRI segments.
+fn encode_id(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn format_confidence(confidence: f32) -> String {
+    format!("{confidence:.2}")
+}
+
+fn join_frames(frames: &[u64]) -> String {
+    frames
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn write_data<W: Write>(out: &mut W, key: &str, value: &str) -> Result<()> {
+    writeln!(
+        out,
+        r#"      <data key="{key}">{}</data>"#,
+        xml_escape(value)
+    )?;
+    Ok(())
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than whitespace are not allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Single-quoted Cypher string literal.
+fn cypher_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\'' => quoted.push_str("\\'"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Upper-case relationship type, backquoted if the link name is not a plain identifier.
+fn cypher_relationship(link: &str) -> String {
+    let name = link.to_uppercase();
+    if !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+    {
+        name
+    } else {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{EntityKind, LinkType, MemoryCardBuilder, MeshEdge, MeshNode};
+    use tempfile::tempdir;
+
+    fn sample() -> Memvid {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = Memvid::create(dir.path().join("graph.mv2")).expect("create");
+        let alice = MeshNode::new(
+            "alice".into(),
+            "Alice".into(),
+            EntityKind::Person,
+            0.9,
+            0,
+            0,
+            5,
+        );
+        let acme = MeshNode::new(
+            "acme & co".into(),
+            "Acme & Co".into(),
+            EntityKind::Organization,
+            0.8,
+            0,
+            20,
+            9,
+        );
+        let edge = MeshEdge::new(alice.id, acme.id, LinkType::Employer, 0.7, 0);
+        mem.logic_mesh.merge_node(alice);
+        mem.logic_mesh.merge_node(acme);
+        mem.logic_mesh.merge_edge(edge);
+        mem.logic_mesh.finalize();
+        for (entity, value) in [("alice", "chess"), ("Bob O'Neil", "tea")] {
+            let card = MemoryCardBuilder::new()
+                .preference()
+                .entity(entity)
+                .slot("likes")
+                .value(value)
+                .source(0, None)
+                .engine("rules", "1.0.0")
+                .build(0)
+                .expect("card");
+            mem.put_memory_card(card).expect("put card");
+        }
+        mem
+    }
+
+    #[test]
+    fn graph_export_links_cards_to_mesh_entities() {
+        let mem = sample();
+        let expected = GraphExportStats {
+            entity_nodes: 3,
+            card_nodes: 2,
+            edges: 3,
+        };
+
+        let mut graphml = Vec::new();
+        let stats = mem
+            .export_graph(GraphExportFormat::GraphMl, &mut graphml)
+            .expect("graphml");
+        assert_eq!(stats, expected);
+        let graphml = String::from_utf8(graphml).expect("utf8");
+        assert!(graphml.contains("Acme &amp; Co"));
+        assert!(graphml.contains(r#"<edge source="c0" target="n"#));
+        assert!(graphml.contains(r#"<node id="e:bob%20o%27neil">"#));
+
+        let mut jsonld = Vec::new();
+        mem.export_graph(GraphExportFormat::JsonLd, &mut jsonld)
+            .expect("jsonld");
+        let doc: Value = serde_json::from_slice(&jsonld).expect("valid json");
+        let graph = doc["@graph"].as_array().expect("graph");
+        assert_eq!(graph.len(), 6);
+        assert!(
+            graph
+                .iter()
+                .any(|item| item["@type"] == "Relationship" && item["link"] == "employer")
+        );
+
+        let mut cypher = Vec::new();
+        mem.export_graph(GraphExportFormat::Cypher, &mut cypher)
+            .expect("cypher");
+        let cypher = String::from_utf8(cypher).expect("utf8");
+        assert_eq!(cypher.lines().count(), 8);
+        assert!(cypher.contains("-[:EMPLOYER {confidence: 0.70, frame: 0}]->"));
+        assert!(cypher.contains(r"name: 'Bob O\'Neil'"));
+    }
+
+    #[test]
+    fn cypher_relationship_quotes_odd_names() {
+        assert_eq!(cypher_relationship("works_at"), "WORKS_AT");
+        assert_eq!(cypher_relationship("reports to"), "`REPORTS TO`");
+    }
+}
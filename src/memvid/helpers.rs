@@ -1,6 +1,7 @@
 // Safe expect: guaranteed non-empty iterators after length check.
 #![allow(clippy::expect_used)]
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::memvid::lifecycle::Memvid;
 use crate::memvid::mutation::augment_search_text;
@@ -165,3 +166,12 @@ impl Memvid {
         }
     }
 }
+
+/// Current unix time in whole seconds; a clock set before 1970 reads as 0.
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX)
+        })
+}
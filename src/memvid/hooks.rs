@@ -0,0 +1,126 @@
+//! Registering hooks and delivering events to them (see [`crate::types::hooks`]).
+
+use std::collections::BTreeSet;
+
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{CommitEvent, CommitHookEvent, EnrichmentEvent, MemvidHooks, PutEvent};
+
+/// Hooks registered on one handle, called in registration order.
+#[derive(Default)]
+pub(crate) struct HookRegistry {
+    hooks: Vec<Box<dyn MemvidHooks>>,
+}
+
+impl HookRegistry {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+}
+
+impl std::fmt::Debug for HookRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HookRegistry")
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+struct PutHook<F>(F);
+
+impl<F: FnMut(&PutEvent) + Send> MemvidHooks for PutHook<F> {
+    fn on_put(&mut self, event: &PutEvent) {
+        (self.0)(event);
+    }
+}
+
+struct CommitHook<F>(F);
+
+impl<F: FnMut(&CommitHookEvent) + Send> MemvidHooks for CommitHook<F> {
+    fn on_commit(&mut self, event: &CommitHookEvent) {
+        (self.0)(event);
+    }
+}
+
+struct EnrichmentHook<F>(F);
+
+impl<F: FnMut(&EnrichmentEvent) + Send> MemvidHooks for EnrichmentHook<F> {
+    fn on_enrichment_complete(&mut self, event: &EnrichmentEvent) {
+        (self.0)(event);
+    }
+}
+
+impl Memvid {
+    /// Register a hook object receiving every event kind. Hooks live as long as this handle.
+    pub fn register_hooks(&mut self, hooks: Box<dyn MemvidHooks>) {
+        self.hooks.hooks.push(hooks);
+    }
+
+    /// Call `hook` after every successful put.
+    pub fn on_put<F>(&mut self, hook: F)
+    where
+        F: FnMut(&PutEvent) + Send + 'static,
+    {
+        self.register_hooks(Box::new(PutHook(hook)));
+    }
+
+    /// Call `hook` after every durable commit that inserted or tombstoned frames.
+    pub fn on_commit<F>(&mut self, hook: F)
+    where
+        F: FnMut(&CommitHookEvent) + Send + 'static,
+    {
+        self.register_hooks(Box::new(CommitHook(hook)));
+    }
+
+    /// Call `hook` whenever a frame's enrichment completes.
+    pub fn on_enrichment_complete<F>(&mut self, hook: F)
+    where
+        F: FnMut(&EnrichmentEvent) + Send + 'static,
+    {
+        self.register_hooks(Box::new(EnrichmentHook(hook)));
+    }
+
+    /// Remove every registered hook.
+    pub fn clear_hooks(&mut self) {
+        self.hooks.hooks.clear();
+    }
+
+    fn dispatch_hooks(&mut self, mut deliver: impl FnMut(&mut dyn MemvidHooks)) {
+        for hook in &mut self.hooks.hooks {
+            deliver(hook.as_mut());
+        }
+    }
+
+    pub(crate) fn fire_put_hooks(&mut self, event: &PutEvent) {
+        if !self.hooks.is_empty() {
+            self.dispatch_hooks(|hook| hook.on_put(event));
+        }
+    }
+
+    pub(crate) fn fire_commit_hooks(&mut self, event: &CommitEvent) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        let uris: BTreeSet<String> = event
+            .inserted_frames
+            .iter()
+            .chain(&event.tombstoned_frames)
+            .filter_map(|&frame_id| {
+                let index = usize::try_from(frame_id).ok()?;
+                self.toc.frames.get(index)?.uri.clone()
+            })
+            .collect();
+        let event = CommitHookEvent {
+            generation: event.generation,
+            inserted_frames: event.inserted_frames.clone(),
+            tombstoned_frames: event.tombstoned_frames.clone(),
+            uris: uris.into_iter().collect(),
+        };
+        self.dispatch_hooks(|hook| hook.on_commit(&event));
+    }
+
+    pub(crate) fn fire_enrichment_hooks(&mut self, event: &EnrichmentEvent) {
+        if !self.hooks.is_empty() {
+            self.dispatch_hooks(|hook| hook.on_enrichment_complete(event));
+        }
+    }
+}
@@ -0,0 +1,485 @@
+//! Importance tracking, decay, and the search-time importance boost.
+//!
+//! Records live in the memories track (see [`crate::types::importance`]) and are persisted
+//! with it on commit. Frame pins also protect frames: `delete_frame` refuses them, so vacuum
+//! never reclaims their payloads.
+
+use std::collections::HashMap;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    DecayPolicy, DecayReport, FrameId, FrameStatus, ImportanceRecord, MemoryCardId,
+};
+
+/// Largest relative score increase the importance boost applies to a search hit.
+const IMPORTANCE_BOOST: f32 = 0.5;
+
+impl Memvid {
+    /// Pin or unpin a memory card. Pinned cards are never demoted and score 1.0.
+    pub fn pin_card(&mut self, card_id: MemoryCardId, pinned: bool) -> Result<()> {
+        self.ensure_writable()?;
+        self.ensure_card(card_id)?;
+        let record = self
+            .memories_track
+            .importance_mut()
+            .cards
+            .entry(card_id)
+            .or_default();
+        record.pinned = pinned;
+        record.demoted &= !pinned;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Pin an active frame. Pinned frames get the full search boost and cannot be deleted;
+    /// an update moves the pin to the new version.
+    pub fn pin_frame(&mut self, frame_id: FrameId) -> Result<()> {
+        self.ensure_writable()?;
+        if self.frame_by_id(frame_id)?.status != FrameStatus::Active {
+            return Err(MemvidError::InvalidFrame {
+                frame_id,
+                reason: "frame is not active",
+            });
+        }
+        self.set_frame_pinned(frame_id, true);
+        Ok(())
+    }
+
+    /// Remove a frame's pin, making it deletable again.
+    pub fn unpin_frame(&mut self, frame_id: FrameId) -> Result<()> {
+        self.ensure_writable()?;
+        self.ensure_not_held("unpin frames")?;
+        self.frame_by_id(frame_id)?;
+        self.set_frame_pinned(frame_id, false);
+        Ok(())
+    }
+
+    /// Ids of pinned frames, in id order.
+    #[must_use]
+    pub fn pinned_frames(&self) -> Vec<FrameId> {
+        self.memories_track
+            .importance()
+            .frames
+            .iter()
+            .filter(|(_, record)| record.pinned)
+            .map(|(frame_id, _)| *frame_id)
+            .collect()
+    }
+
+    pub(crate) fn is_frame_pinned(&self, frame_id: FrameId) -> bool {
+        self.memories_track
+            .importance()
+            .frames
+            .get(&frame_id)
+            .is_some_and(|record| record.pinned)
+    }
+
+    /// Fail with [`MemvidError::FramePinned`] if `frame_id` is pinned.
+    pub(crate) fn ensure_frame_unpinned(&self, frame_id: FrameId) -> Result<()> {
+        if self.is_frame_pinned(frame_id) {
+            return Err(MemvidError::FramePinned { frame_id });
+        }
+        Ok(())
+    }
+
+    fn set_frame_pinned(&mut self, frame_id: FrameId, pinned: bool) {
+        let record = self
+            .memories_track
+            .importance_mut()
+            .frames
+            .entry(frame_id)
+            .or_default();
+        record.pinned = pinned;
+        record.demoted &= !pinned;
+        self.dirty = true;
+    }
+
+    /// Move the pin of a superseded frame to its successor.
+    pub(crate) fn carry_frame_pin(&mut self, frame_id: FrameId, successor_id: FrameId) {
+        if self.is_frame_pinned(frame_id) {
+            self.set_frame_pinned(frame_id, false);
+            self.set_frame_pinned(successor_id, true);
+        }
+    }
+
+    /// Count an access to each card, reviving any that were demoted.
+    pub fn record_card_access(&mut self, card_ids: &[MemoryCardId]) -> Result<()> {
+        for &card_id in card_ids {
+            self.ensure_card(card_id)?;
+        }
+        let now = unix_now();
+        let table = self.memories_track.importance_mut();
+        for &card_id in card_ids {
+            table.cards.entry(card_id).or_default().touch(now);
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Count an access to each frame.
+    pub fn record_frame_access(&mut self, frame_ids: &[FrameId]) -> Result<()> {
+        for &frame_id in frame_ids {
+            self.frame_by_id(frame_id)?;
+        }
+        let now = unix_now();
+        let table = self.memories_track.importance_mut();
+        for &frame_id in frame_ids {
+            table.frames.entry(frame_id).or_default().touch(now);
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Importance of a card under `policy`, in `0.0..=1.0`.
+    #[must_use]
+    pub fn card_importance(&self, card_id: MemoryCardId, policy: &DecayPolicy) -> Option<f32> {
+        let card = self.memories_track.get_card(card_id)?;
+        let record = self.card_record(card_id);
+        Some(record.score(card.created_at, unix_now(), policy))
+    }
+
+    /// Importance of a frame under `policy`, in `0.0..=1.0`.
+    #[must_use]
+    pub fn frame_importance(&self, frame_id: FrameId, policy: &DecayPolicy) -> Option<f32> {
+        let frame = usize::try_from(frame_id)
+            .ok()
+            .and_then(|index| self.toc.frames.get(index))?;
+        let record = self
+            .memories_track
+            .importance()
+            .frames
+            .get(&frame_id)
+            .copied()
+            .unwrap_or_default();
+        Some(record.score(frame.timestamp, unix_now(), policy))
+    }
+
+    /// Demote current cards that have sat idle past `policy.min_idle_secs` and score below
+    /// `policy.demote_below`.
+    ///
+    /// Pinned and superseded cards are skipped. Demotion is a ranking signal only: demoted cards
+    /// stay queryable but no longer lift their source frames in search, and the next access or
+    /// pin revives them.
+    pub fn decay(&mut self, policy: &DecayPolicy) -> Result<DecayReport> {
+        self.ensure_writable()?;
+        self.ensure_not_held("demote memories")?;
+        let now = unix_now();
+        let mut report = DecayReport::default();
+        let stale: Vec<MemoryCardId> = self
+            .memories_track
+            .cards()
+            .iter()
+            .filter(|card| !card.is_superseded())
+            .filter_map(|card| {
+                let record = self.card_record(card.id);
+                if record.pinned || record.demoted {
+                    return None;
+                }
+                report.examined += 1;
+                (record.idle_secs(card.created_at, now) >= policy.min_idle_secs
+                    && record.score(card.created_at, now, policy) < policy.demote_below)
+                    .then_some(card.id)
+            })
+            .collect();
+        if !stale.is_empty() {
+            let table = self.memories_track.importance_mut();
+            for &card_id in &stale {
+                table.cards.entry(card_id).or_default().demoted = true;
+            }
+            self.dirty = true;
+        }
+        report.demoted = stale;
+        Ok(report)
+    }
+
+    /// Cards demoted by [`Memvid::decay`] and not accessed since.
+    #[must_use]
+    pub fn demoted_cards(&self) -> Vec<MemoryCardId> {
+        self.memories_track
+            .importance()
+            .cards
+            .iter()
+            .filter(|(_, record)| record.demoted)
+            .map(|(card_id, _)| *card_id)
+            .collect()
+    }
+
+    /// Search-time importance of every frame that has a record of its own or a live card
+    /// extracted from it, taking the higher of the two. Empty when nothing has a record.
+    ///
+    /// Retrieval scales candidate scores by [`importance_factor`] before the page is cut, so
+    /// important frames can rise into the page rather than only reorder it.
+    pub(crate) fn importance_weights(&self) -> HashMap<FrameId, f32> {
+        let table = self.memories_track.importance();
+        let mut importance: HashMap<FrameId, f32> = HashMap::new();
+        if table.is_empty() {
+            return importance;
+        }
+        let policy = DecayPolicy::default();
+        let now = unix_now();
+        for (&card_id, record) in &table.cards {
+            let Some(card) = self.memories_track.get_card(card_id) else {
+                continue;
+            };
+            if record.demoted || card.is_superseded() {
+                continue;
+            }
+            let score = record.score(card.created_at, now, &policy);
+            let entry = importance.entry(card.source_frame_id).or_default();
+            *entry = entry.max(score);
+        }
+        for (&frame_id, record) in &table.frames {
+            let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+            else {
+                continue;
+            };
+            let score = record.score(frame.timestamp, now, &policy);
+            let entry = importance.entry(frame_id).or_default();
+            *entry = entry.max(score);
+        }
+        importance
+    }
+
+    fn card_record(&self, card_id: MemoryCardId) -> ImportanceRecord {
+        self.memories_track
+            .importance()
+            .cards
+            .get(&card_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn ensure_card(&self, card_id: MemoryCardId) -> Result<()> {
+        if self.memories_track.get_card(card_id).is_none() {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("no memory card with id {card_id}"),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Multiplier the importance boost applies to the score of a hit on `frame_id`.
+pub(crate) fn importance_factor(weights: &HashMap<FrameId, f32>, frame_id: FrameId) -> f32 {
+    weights
+        .get(&frame_id)
+        .map_or(1.0, |weight| 1.0 + IMPORTANCE_BOOST * weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MemoryCardBuilder, PutOptions, SearchRequest};
+
+    #[test]
+    fn decay_demotes_idle_unpinned_cards() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("importance.mv2")).expect("create");
+        let year_ago = unix_now() - 365 * 24 * 60 * 60;
+        let mut ids = Vec::new();
+        for slot in ["city", "employer", "pet"] {
+            let mut card = MemoryCardBuilder::new()
+                .fact()
+                .entity("user")
+                .slot(slot)
+                .value("x")
+                .source(0, None)
+                .engine("test", "1.0.0")
+                .build(0)
+                .expect("card");
+            card.created_at = year_ago;
+            ids.push(mem.put_memory_card(card).expect("put"));
+        }
+        mem.pin_card(ids[1], true).expect("pin");
+        mem.record_card_access(&[ids[2]]).expect("access");
+
+        let report = mem.decay(&DecayPolicy::default()).expect("decay");
+        assert_eq!(report.demoted, [ids[0]]);
+        assert_eq!(mem.demoted_cards(), [ids[0]]);
+        mem.record_card_access(&[ids[0]]).expect("revive");
+        assert!(mem.demoted_cards().is_empty());
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn pinned_frames_rank_first() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("boost.mv2")).expect("create");
+        for (uri, body) in [
+            ("mv2://notes", "release notes for the launch"),
+            ("mv2://checklist", "launch checklist and release"),
+        ] {
+            let options = PutOptions::builder().uri(uri).build();
+            mem.put_bytes_with_options(body.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        let request = SearchRequest {
+            query: "release launch".into(),
+            top_k: 5,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        };
+        let before = mem.search(request.clone()).expect("search");
+        let last = before.hits.last().expect("hits").frame_id;
+        mem.pin_frame(last).expect("pin frame");
+        let after = mem.search(request).expect("search");
+        assert_eq!(after.hits[0].frame_id, last);
+        assert_eq!(after.hits[0].rank, 1);
+
+        // Frame pins persist even when the memories track holds no cards.
+        mem.commit().expect("commit");
+        drop(mem);
+        let reopened = Memvid::open(dir.path().join("boost.mv2")).expect("open");
+        let policy = DecayPolicy::default();
+        assert_eq!(reopened.frame_importance(last, &policy), Some(1.0));
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn pinned_frame_just_outside_the_page_moves_into_it() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("boost-page.mv2")).expect("create");
+        mem.enable_lex().expect("lex");
+        for (uri, body) in [
+            ("mv2://most", "budget budget budget budget"),
+            ("mv2://more", "budget budget budget"),
+            ("mv2://some", "budget budget plus a few more words here"),
+        ] {
+            let options = PutOptions::builder().uri(uri).build();
+            mem.put_bytes_with_options(body.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        let request = SearchRequest {
+            query: "budget".into(),
+            top_k: 2,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        };
+        let outside = mem.frame_by_uri("mv2://some").expect("frame").id;
+        let before = mem.search(request.clone()).expect("search");
+        assert_eq!(before.hits.len(), 2);
+        assert!(before.hits.iter().all(|hit| hit.frame_id != outside));
+
+        mem.pin_frame(outside).expect("pin");
+        let first = mem.search(request.clone()).expect("search");
+        assert!(first.hits.iter().any(|hit| hit.frame_id == outside));
+        // The next page continues the boosted order rather than repeating the pinned frame.
+        let second = mem
+            .search(SearchRequest {
+                cursor: first.next_cursor.clone(),
+                ..request
+            })
+            .expect("next page");
+        assert!(second.hits.iter().all(|hit| hit.frame_id != outside));
+        let mut seen: Vec<FrameId> = first
+            .hits
+            .iter()
+            .chain(&second.hits)
+            .map(|hit| hit.frame_id)
+            .collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    fn pinned_frames_survive_deletes_and_follow_updates() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("pins.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for uri in ["mv2://reference", "mv2://scratch"] {
+            let options = PutOptions::builder().uri(uri).build();
+            mem.put_bytes_with_options(b"canonical reference text", options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        let reference = mem.frame_by_uri("mv2://reference").expect("frame").id;
+
+        mem.pin_frame(reference).expect("pin");
+        assert_eq!(mem.pinned_frames(), [reference]);
+        assert!(matches!(
+            mem.delete_frame(reference),
+            Err(MemvidError::FramePinned { frame_id }) if frame_id == reference
+        ));
+
+        mem.update_frame(
+            reference,
+            Some(b"revised reference".to_vec()),
+            PutOptions::default(),
+            None,
+        )
+        .expect("update");
+        mem.commit().expect("commit");
+        let revised = mem.frame_by_uri("mv2://reference").expect("frame").id;
+        assert_ne!(revised, reference);
+        assert_eq!(mem.pinned_frames(), [revised]);
+
+        mem.unpin_frame(revised).expect("unpin");
+        assert!(mem.pinned_frames().is_empty());
+        mem.delete_frame(revised).expect("delete");
+
+        // Handles that cannot write refuse pins up front instead of at commit.
+        mem.snapshot("before-freeze").expect("snapshot");
+        let scratch = mem.frame_by_uri("mv2://scratch").expect("frame").id;
+        mem.freeze(None).expect("freeze");
+        assert!(matches!(
+            mem.pin_frame(scratch),
+            Err(MemvidError::LegalHold { .. })
+        ));
+        assert!(mem.pinned_frames().is_empty());
+        drop(mem);
+        let mut view = Memvid::open_at_snapshot(&path, "before-freeze").expect("view");
+        assert!(matches!(
+            view.unpin_frame(scratch),
+            Err(MemvidError::SnapshotReadOnly { .. })
+        ));
+    }
+}
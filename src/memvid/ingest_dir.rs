@@ -0,0 +1,603 @@
+//! Bulk ingestion of a directory tree.
+//!
+//! The tree is walked up front, in name order, so progress can report a total. Files are
+//! then handled in batches: each batch is read and hashed on worker threads, written in walk
+//! order through the reader registry (as any put is), and committed.
+//!
+//! Syncing upserts files by source path instead, comparing content hashes, and tombstones
+//! frames whose file is gone; the folder watcher syncs the paths it sees change.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use regex::Regex;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    DEFAULT_INGEST_BATCH_SIZE, DirSyncReport, FrameId, FrameStatus, IngestDirOptions,
+    IngestDirReport, IngestFailure, IngestFileEvent, IngestFileOutcome, PutOptions,
+    SOURCE_HASH_KEY,
+};
+
+impl Memvid {
+    /// Ingest the files under `root` that `options` selects.
+    ///
+    /// See [`Memvid::ingest_dir_with_progress`].
+    pub fn ingest_dir(
+        &mut self,
+        root: impl AsRef<Path>,
+        options: &IngestDirOptions,
+    ) -> Result<IngestDirReport> {
+        self.ingest_dir_with_progress(root, options, |_| {})
+    }
+
+    /// Ingest the files under `root` that `options` selects, committing every
+    /// `options.batch_size` files and calling `on_file` once per file.
+    ///
+    /// Each file becomes a frame with URI `file://<path>`, its path as source path, its
+    /// modification time as timestamp, and the BLAKE3 hash of its bytes under
+    /// [`SOURCE_HASH_KEY`]. Files that cannot be read are reported and skipped.
+    ///
+    /// # Errors
+    /// Fails if `root` is not a readable directory, a glob is invalid, or a write or commit
+    /// fails; frames written before a failed write stay pending until the next commit.
+    pub fn ingest_dir_with_progress<F>(
+        &mut self,
+        root: impl AsRef<Path>,
+        options: &IngestDirOptions,
+        mut on_file: F,
+    ) -> Result<IngestDirReport>
+    where
+        F: FnMut(&IngestFileEvent<'_>),
+    {
+        self.ensure_writable()?;
+        let matcher = GlobMatcher::new(&options.globs)?;
+        let mut report = IngestDirReport::default();
+        let root = fs::canonicalize(root.as_ref())?;
+        let files = walk(&root, "", options, &matcher, &mut report.failures)?;
+        report.files = files.len();
+
+        let mut known = if options.dedup {
+            self.source_hashes()
+        } else {
+            HashMap::new()
+        };
+        let batch_size = match options.batch_size {
+            0 => DEFAULT_INGEST_BATCH_SIZE,
+            size => size,
+        };
+        let mut done = 0;
+        for batch in files.chunks(batch_size) {
+            let mut written = false;
+            for (path, read) in batch
+                .iter()
+                .zip(read_files(batch, options.concurrency.max(1)))
+            {
+                let outcome = match read {
+                    Ok(file) => {
+                        self.ingest_file(path, &file, options.dedup.then_some(&mut known))?
+                    }
+                    Err(err) => IngestFileOutcome::Failed {
+                        error: err.to_string(),
+                    },
+                };
+                match &outcome {
+                    IngestFileOutcome::Ingested { frame_id } => {
+                        report.frame_ids.push(*frame_id);
+                        written = true;
+                    }
+                    IngestFileOutcome::Duplicate { .. } => report.duplicates += 1,
+                    IngestFileOutcome::Failed { error } => report.failures.push(IngestFailure {
+                        path: path.clone(),
+                        error: error.clone(),
+                    }),
+                }
+                done += 1;
+                on_file(&IngestFileEvent {
+                    path,
+                    outcome: &outcome,
+                    done,
+                    total: report.files,
+                });
+            }
+            if written {
+                self.commit()?;
+                report.batches += 1;
+            }
+        }
+        Ok(report)
+    }
+
+    fn ingest_file(
+        &mut self,
+        path: &Path,
+        file: &FileRead,
+        known: Option<&mut HashMap<String, FrameId>>,
+    ) -> Result<IngestFileOutcome> {
+        let hash = blake3::hash(&file.bytes).to_hex().to_string();
+        if let Some(&frame_id) = known.as_ref().and_then(|known| known.get(&hash)) {
+            return Ok(IngestFileOutcome::Duplicate { frame_id });
+        }
+
+        let frame_id = self.next_frame_id();
+        self.put_bytes_with_options(&file.bytes, file_put_options(path, file, &hash))?;
+        if let Some(known) = known {
+            known.insert(hash, frame_id);
+        }
+        Ok(IngestFileOutcome::Ingested { frame_id })
+    }
+
+    /// Bring the frames of files under `root` in line with the disk, then commit.
+    ///
+    /// Files that `options` selects are upserted by source path: new files are added, a file
+    /// whose BLAKE3 hash changed gets a frame superseding its old one, and unchanged files are
+    /// left alone. Frames whose file no longer exists are tombstoned; frames of files that
+    /// still exist but are no longer selected are kept. `options.dedup` and
+    /// `options.batch_size` do not apply.
+    ///
+    /// # Errors
+    /// Fails if `root` is not a readable directory, a glob is invalid, or a write or commit
+    /// fails.
+    pub fn sync_dir(
+        &mut self,
+        root: impl AsRef<Path>,
+        options: &IngestDirOptions,
+    ) -> Result<DirSyncReport> {
+        let root = fs::canonicalize(root.as_ref())?;
+        let report = self.sync_paths(&root, std::slice::from_ref(&root), options)?;
+        if report.has_changes() {
+            self.commit()?;
+        }
+        Ok(report)
+    }
+
+    /// Sync `paths` below the canonical `root` without committing. Each path may name a file,
+    /// a directory to walk, or a file or directory that no longer exists.
+    pub(crate) fn sync_paths(
+        &mut self,
+        root: &Path,
+        paths: &[PathBuf],
+        options: &IngestDirOptions,
+    ) -> Result<DirSyncReport> {
+        self.ensure_writable()?;
+        let matcher = GlobMatcher::new(&options.globs)?;
+        let mut report = DirSyncReport::default();
+        let mut files = Vec::new();
+        let mut scopes = Vec::new();
+        for path in paths {
+            let Some(relative) = relative_path(root, path) else {
+                continue;
+            };
+            if !options.include_hidden && relative.split('/').any(|part| part.starts_with('.')) {
+                continue;
+            }
+            scopes.push(source_path(path));
+            let nested = relative.contains('/');
+            let is_link = fs::symlink_metadata(path).is_ok_and(|meta| meta.is_symlink());
+            if is_link && !options.follow_symlinks {
+                continue;
+            }
+            match fs::metadata(path) {
+                Ok(meta) if meta.is_dir() && (relative.is_empty() || options.recursive) => {
+                    let prefix = if relative.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{relative}/")
+                    };
+                    files.extend(walk(
+                        path,
+                        &prefix,
+                        options,
+                        &matcher,
+                        &mut report.failures,
+                    )?);
+                }
+                Ok(meta)
+                    if meta.is_file()
+                        && (options.recursive || !nested)
+                        && matcher.matches(&relative) =>
+                {
+                    files.push(path.clone());
+                }
+                _ => {}
+            }
+        }
+        files.sort();
+        files.dedup();
+
+        let stored: HashMap<String, (FrameId, Option<String>)> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .filter_map(|frame| {
+                let source = frame.source_path.clone()?;
+                let hash = frame.extra_metadata.get(SOURCE_HASH_KEY).cloned();
+                Some((source, (frame.id, hash)))
+            })
+            .collect();
+
+        for batch in files.chunks(DEFAULT_INGEST_BATCH_SIZE) {
+            for (path, read) in batch
+                .iter()
+                .zip(read_files(batch, options.concurrency.max(1)))
+            {
+                let file = match read {
+                    Ok(file) => file,
+                    Err(err) => {
+                        report.failures.push(IngestFailure {
+                            path: path.clone(),
+                            error: err.to_string(),
+                        });
+                        continue;
+                    }
+                };
+                let hash = blake3::hash(&file.bytes).to_hex().to_string();
+                let frame_id = self.next_frame_id();
+                match stored.get(&source_path(path)) {
+                    Some((_, Some(stored_hash))) if *stored_hash == hash => report.unchanged += 1,
+                    Some(&(previous, _)) => {
+                        let put = file_put_options(path, &file, &hash);
+                        self.update_frame(previous, Some(file.bytes), put, None)?;
+                        report.updated.push(frame_id);
+                    }
+                    None => {
+                        let put = file_put_options(path, &file, &hash);
+                        self.put_bytes_with_options(&file.bytes, put)?;
+                        report.added.push(frame_id);
+                    }
+                }
+            }
+        }
+
+        let mut removed: Vec<(FrameId, &String)> = stored
+            .iter()
+            .filter(|(source, _)| {
+                scopes.iter().any(|scope| {
+                    source
+                        .strip_prefix(scope.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                }) && fs::symlink_metadata(source).is_err()
+            })
+            .map(|(source, (frame_id, _))| (*frame_id, source))
+            .collect();
+        removed.sort_unstable();
+        for (frame_id, source) in removed {
+            match self.delete_frame(frame_id) {
+                Ok(_) => report.deleted.push(frame_id),
+                Err(err @ MemvidError::FramePinned { .. }) => report.failures.push(IngestFailure {
+                    path: PathBuf::from(source),
+                    error: err.to_string(),
+                }),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Source hashes of active frames, hex-encoded, mapped to the frame holding them.
+    fn source_hashes(&self) -> HashMap<String, FrameId> {
+        self.toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .filter_map(|frame| {
+                let hash = frame
+                    .extra_metadata
+                    .get(SOURCE_HASH_KEY)
+                    .cloned()
+                    .or_else(|| frame.source_sha256.map(hex::encode))?;
+                Some((hash, frame.id))
+            })
+            .collect()
+    }
+}
+
+/// Source path recorded for a file: its path with `/` separators.
+fn source_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// `path` relative to `root` with `/` separators, or `None` if it lies outside `root`.
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Put options for a file: URI, source path, modification time, and content hash.
+fn file_put_options(path: &Path, file: &FileRead, hash: &str) -> PutOptions {
+    let source = source_path(path);
+    let mut put = PutOptions::builder()
+        .uri(format!("file://{source}"))
+        .source_path(source)
+        .build();
+    put.timestamp = file.modified;
+    put.extra_metadata
+        .insert(SOURCE_HASH_KEY.to_string(), hash.to_string());
+    put
+}
+
+/// Include and exclude patterns compiled from `IngestDirOptions::globs`.
+pub(crate) struct GlobMatcher {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl GlobMatcher {
+    pub(crate) fn new(globs: &[String]) -> Result<Self> {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        for glob in globs {
+            match glob.strip_prefix('!') {
+                Some(pattern) => exclude.push(glob_regex(pattern)?),
+                None => include.push(glob_regex(glob)?),
+            }
+        }
+        Ok(Self { include, exclude })
+    }
+
+    /// Check a path relative to the walk root, with `/` separators.
+    pub(crate) fn matches(&self, relative: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(relative)))
+            && !self.exclude.iter().any(|re| re.is_match(relative))
+    }
+}
+
+fn glob_regex(glob: &str) -> Result<Regex> {
+    let glob = glob.trim().trim_start_matches("./").trim_start_matches('/');
+    let mut pattern = String::from("^");
+    if !glob.contains('/') {
+        pattern.push_str("(?:.*/)?");
+    }
+    let mut chars = glob.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|err| MemvidError::InvalidConfig {
+        reason: format!("invalid glob {glob:?}: {err}"),
+    })
+}
+
+/// Files under `start` selected by `options` and `matcher`, in name order. `prefix` is the
+/// path of `start` relative to the walk root, empty or ending in `/`. Directories below
+/// `start` that cannot be read are added to `failures`.
+fn walk(
+    start: &Path,
+    prefix: &str,
+    options: &IngestDirOptions,
+    matcher: &GlobMatcher,
+    failures: &mut Vec<IngestFailure>,
+) -> Result<Vec<PathBuf>> {
+    let mut visited = HashSet::new();
+    visited.insert(fs::canonicalize(start)?);
+    let mut files = Vec::new();
+    let mut pending = vec![(start.to_path_buf(), prefix.to_string())];
+    while let Some((dir, prefix)) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if dir == start => return Err(err.into()),
+            Err(err) => {
+                failures.push(IngestFailure {
+                    path: dir,
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let mut entries: Vec<_> = entries.filter_map(std::result::Result::ok).collect();
+        entries.sort_by_key(fs::DirEntry::file_name);
+
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') && !options.include_hidden {
+                continue;
+            }
+            let Ok(mut file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_symlink() {
+                if !options.follow_symlinks {
+                    continue;
+                }
+                let Ok(target) = fs::metadata(&path) else {
+                    continue;
+                };
+                file_type = target.file_type();
+            }
+            let relative = format!("{prefix}{name}");
+            if file_type.is_dir() {
+                if options.recursive
+                    && fs::canonicalize(&path).is_ok_and(|real| visited.insert(real))
+                {
+                    subdirs.push((path, format!("{relative}/")));
+                }
+            } else if file_type.is_file() && matcher.matches(&relative) {
+                files.push(path);
+            }
+        }
+        // Visit subdirectories after this directory's files, in name order.
+        pending.extend(subdirs.into_iter().rev());
+    }
+    Ok(files)
+}
+
+struct FileRead {
+    bytes: Vec<u8>,
+    modified: Option<i64>,
+}
+
+fn read_file(path: &Path) -> std::io::Result<FileRead> {
+    let bytes = fs::read(path)?;
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .and_then(|elapsed| i64::try_from(elapsed.as_secs()).ok());
+    Ok(FileRead { bytes, modified })
+}
+
+/// Read `paths` on up to `threads` threads, returning results in input order.
+fn read_files(paths: &[PathBuf], threads: usize) -> Vec<std::io::Result<FileRead>> {
+    if threads <= 1 || paths.len() <= 1 {
+        return paths.iter().map(|path| read_file(path)).collect();
+    }
+    let per_thread = paths.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = paths
+            .chunks(per_thread)
+            .map(|chunk| {
+                scope.spawn(move || chunk.iter().map(|path| read_file(path)).collect::<Vec<_>>())
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn glob_matcher_handles_segments_and_exclusions() {
+        let matcher = GlobMatcher::new(&[
+            "*.md".to_string(),
+            "docs/**/*.txt".to_string(),
+            "!drafts/**".to_string(),
+        ])
+        .expect("globs");
+        assert!(matcher.matches("a.md"));
+        assert!(matcher.matches("notes/deep/a.md"));
+        assert!(matcher.matches("docs/a.txt"));
+        assert!(matcher.matches("docs/x/y/a.txt"));
+        assert!(!matcher.matches("a.txt"));
+        assert!(!matcher.matches("drafts/a.md"));
+        assert!(GlobMatcher::new(&[]).expect("empty").matches("any/file"));
+    }
+
+    #[test]
+    fn ingest_dir_batches_dedups_and_reports() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path().join("tree");
+        for (path, body) in [
+            ("a.md", "alpha notes"),
+            ("b.txt", "bravo notes"),
+            ("skip.log", "log line"),
+            ("sub/c.md", "charlie notes"),
+            ("sub/copy.md", "alpha notes"),
+            (".hidden/d.md", "delta notes"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).expect("mkdir");
+            fs::write(path, body).expect("write");
+        }
+        let mut mem = Memvid::create(dir.path().join("ingest.mv2")).expect("create");
+        let options = IngestDirOptions {
+            globs: vec!["*.md".into(), "*.txt".into()],
+            batch_size: 2,
+            ..IngestDirOptions::default()
+        };
+
+        let mut events = Vec::new();
+        let report = mem
+            .ingest_dir_with_progress(&root, &options, |event| {
+                events.push((event.outcome.clone(), event.done, event.total));
+            })
+            .expect("ingest");
+        assert_eq!(report.files, 4);
+        assert_eq!(report.frame_ids.len(), 3);
+        assert_eq!(report.duplicates, 1);
+        assert!(report.failures.is_empty());
+        assert_eq!(report.batches, 2);
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[3].1, 4);
+        assert_eq!(
+            events[3].0,
+            IngestFileOutcome::Duplicate {
+                frame_id: report.frame_ids[0]
+            }
+        );
+
+        let first = mem.frame_by_id(report.frame_ids[0]).expect("frame");
+        assert!(first.uri.as_deref().unwrap().ends_with("/tree/a.md"));
+        assert!(first.extra_metadata.contains_key(SOURCE_HASH_KEY));
+
+        // A second run finds every file already stored.
+        let again = mem.ingest_dir(&root, &options).expect("ingest again");
+        assert!(again.frame_ids.is_empty());
+        assert_eq!(again.duplicates, 4);
+        assert_eq!(again.batches, 0);
+
+        let shallow = IngestDirOptions {
+            recursive: false,
+            dedup: false,
+            ..IngestDirOptions::default()
+        };
+        let report = mem.ingest_dir(&root, &shallow).expect("shallow");
+        assert_eq!(report.files, 3);
+        assert_eq!(report.frame_ids.len(), 3);
+    }
+
+    #[test]
+    fn sync_dir_upserts_by_path_and_tombstones_deleted_files() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path().join("tree");
+        fs::create_dir_all(root.join("sub")).expect("mkdir");
+        fs::write(root.join("a.md"), "alpha notes").expect("write");
+        fs::write(root.join("sub/b.md"), "bravo notes").expect("write");
+        let mut mem = Memvid::create(dir.path().join("sync.mv2")).expect("create");
+        let options = IngestDirOptions::default();
+
+        let first = mem.sync_dir(&root, &options).expect("sync");
+        assert_eq!(first.added.len(), 2);
+        let unchanged = mem.sync_dir(&root, &options).expect("resync");
+        assert!(!unchanged.has_changes());
+        assert_eq!(unchanged.unchanged, 2);
+
+        fs::write(root.join("a.md"), "alpha notes, revised").expect("rewrite");
+        fs::remove_dir_all(root.join("sub")).expect("remove");
+        fs::write(root.join("c.md"), "charlie notes").expect("write");
+        let report = mem.sync_dir(&root, &options).expect("sync changes");
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.deleted, [first.added[1]]);
+
+        let revised = mem.frame_by_id(report.updated[0]).expect("frame");
+        assert_eq!(revised.supersedes, Some(first.added[0]));
+        assert_eq!(revised.status, FrameStatus::Active);
+        let deleted = mem.frame_by_id(first.added[1]).expect("frame");
+        assert_ne!(deleted.status, FrameStatus::Active);
+    }
+}
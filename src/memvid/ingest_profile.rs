@@ -0,0 +1,74 @@
+//! Ingest profile registry and resolution (see [`crate::types::ingest_profile`]).
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{INGEST_PROFILE_EXTENSION, IngestProfile, IngestProfileRegistry, PutOptions};
+
+impl Memvid {
+    /// Store `profile` under `name`, replacing any profile of that name. Saved on the next
+    /// commit.
+    pub fn set_ingest_profile(&mut self, name: &str, profile: IngestProfile) -> Result<()> {
+        self.ensure_writable()?;
+        if name.trim().is_empty() {
+            return Err(MemvidError::InvalidConfig {
+                reason: "ingest profiles need a name".into(),
+            });
+        }
+        if profile.chunk_chars == Some(0) {
+            return Err(MemvidError::InvalidConfig {
+                reason: "chunk_chars must be positive".into(),
+            });
+        }
+        let mut registry = self.ingest_profiles()?;
+        registry.insert(name.to_string(), profile);
+        self.toc
+            .set_extension(INGEST_PROFILE_EXTENSION, &registry)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// The profile stored under `name`.
+    pub fn ingest_profile(&self, name: &str) -> Result<IngestProfile> {
+        self.ingest_profiles()?
+            .remove(name)
+            .ok_or_else(|| MemvidError::IngestProfileNotFound {
+                name: name.to_string(),
+            })
+    }
+
+    /// Every stored profile, by name.
+    pub fn ingest_profiles(&self) -> Result<IngestProfileRegistry> {
+        Ok(self
+            .toc
+            .extension(INGEST_PROFILE_EXTENSION)?
+            .unwrap_or_default())
+    }
+
+    /// Remove the profile stored under `name`. Puts naming it fail afterwards.
+    pub fn remove_ingest_profile(&mut self, name: &str) -> Result<IngestProfile> {
+        self.ensure_writable()?;
+        self.ensure_not_held("remove ingest profiles")?;
+        let mut registry = self.ingest_profiles()?;
+        let profile = registry
+            .remove(name)
+            .ok_or_else(|| MemvidError::IngestProfileNotFound {
+                name: name.to_string(),
+            })?;
+        if registry.is_empty() {
+            self.toc.extensions.remove(INGEST_PROFILE_EXTENSION);
+        } else {
+            self.toc
+                .set_extension(INGEST_PROFILE_EXTENSION, &registry)?;
+        }
+        self.dirty = true;
+        Ok(profile)
+    }
+
+    /// Resolve the profile `options` names, if any, into the options themselves.
+    pub(crate) fn apply_ingest_profile(&self, mut options: PutOptions) -> Result<PutOptions> {
+        match options.profile.take() {
+            Some(name) => Ok(self.ingest_profile(&name)?.apply(options)),
+            None => Ok(options),
+        }
+    }
+}
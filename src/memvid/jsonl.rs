@@ -0,0 +1,142 @@
+//! JSONL ingestion for `Memvid`: one frame per record.
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::reader::{JsonlFieldMapping, JsonlLineError, JsonlReader};
+use crate::types::{JSONL_LINE_KEY, JsonlReceipt, PutOptions};
+
+impl Memvid {
+    /// Store each record of a JSONL document as its own frame.
+    ///
+    /// `options` is the template for every frame. Fields mapped by `mapping` override its
+    /// title, URI, timestamp, and search text, and record tags are added to its tags; a record
+    /// without a URI gets `<options.uri>#line-<n>` when the template has one. Malformed lines
+    /// and records with nothing to store are skipped and listed in the receipt. Call `commit`
+    /// to persist.
+    pub fn put_jsonl(
+        &mut self,
+        bytes: &[u8],
+        mapping: &JsonlFieldMapping,
+        options: PutOptions,
+    ) -> Result<JsonlReceipt> {
+        self.ensure_mutation_allowed()?;
+        let parsed = JsonlReader::with_mapping(mapping.clone()).parse(bytes);
+        let mut receipt = JsonlReceipt {
+            errors: parsed.errors,
+            ..JsonlReceipt::default()
+        };
+
+        for record in parsed.records {
+            let payload = if record.text.trim().is_empty() {
+                record
+                    .search_text
+                    .clone()
+                    .or_else(|| record.title.clone())
+                    .unwrap_or_default()
+            } else {
+                record.text
+            };
+            if payload.trim().is_empty() {
+                receipt.errors.push(JsonlLineError {
+                    line: record.line,
+                    error: "record has no payload".to_string(),
+                });
+                continue;
+            }
+
+            let mut put = options.clone();
+            if record.title.is_some() {
+                put.title = record.title;
+            }
+            put.uri = record.uri.or_else(|| {
+                options
+                    .uri
+                    .as_ref()
+                    .map(|uri| format!("{uri}#line-{}", record.line))
+            });
+            if record.timestamp.is_some() {
+                put.timestamp = record.timestamp;
+            }
+            if record.search_text.is_some() {
+                put.search_text = record.search_text;
+            }
+            for tag in record.tags {
+                if !put.tags.contains(&tag) {
+                    put.tags.push(tag);
+                }
+            }
+            put.extra_metadata
+                .insert(JSONL_LINE_KEY.to_string(), record.line.to_string());
+
+            receipt
+                .sequences
+                .push(self.put_bytes_with_options(payload.as_bytes(), put)?);
+            receipt.lines.push(record.line);
+        }
+        Ok(receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn put_jsonl_writes_one_frame_per_record() {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = Memvid::create(dir.path().join("jsonl.mv2")).expect("create");
+        let input = concat!(
+            r#"{"role":"user","content":"How do I rotate keys?","ts":1709294400,"tags":["security"]}"#,
+            "\n{broken\n",
+            r#"{"role":"assistant","content":"Run the rotate command.","ts":1709294460}"#,
+            "\n",
+            r#"{"role":"assistant","ts":1709294520}"#,
+            "\n",
+        );
+        let mapping = JsonlFieldMapping {
+            title: Some("role".to_string()),
+            timestamp: Some("ts".to_string()),
+            payload: vec!["content".to_string()],
+            ..JsonlFieldMapping::default()
+        };
+        let template = PutOptions::builder()
+            .uri("mv2://chat/export.jsonl")
+            .kind("chat_line")
+            .build();
+
+        let receipt = mem
+            .put_jsonl(input.as_bytes(), &mapping, template)
+            .expect("put");
+        mem.commit().expect("commit");
+
+        assert_eq!(receipt.lines, [1, 3, 4]);
+        assert_eq!(
+            receipt
+                .errors
+                .iter()
+                .map(|error| error.line)
+                .collect::<Vec<_>>(),
+            [2]
+        );
+        let first = mem
+            .frame_by_uri("mv2://chat/export.jsonl#line-1")
+            .expect("first frame");
+        assert_eq!(first.title.as_deref(), Some("user"));
+        assert_eq!(first.kind.as_deref(), Some("chat_line"));
+        assert_eq!(first.timestamp, 1_709_294_400);
+        assert!(first.tags.contains(&"security".to_string()));
+        assert_eq!(
+            first.extra_metadata.get(JSONL_LINE_KEY).map(String::as_str),
+            Some("1")
+        );
+        let text = mem.frame_text_by_id(first.id).expect("text");
+        assert!(text.starts_with("How do I rotate keys?\n"));
+        // A record without its payload field falls back to its title.
+        let last = mem
+            .frame_by_uri("mv2://chat/export.jsonl#line-4")
+            .expect("last frame");
+        let text = mem.frame_text_by_id(last.id).expect("text");
+        assert!(text.starts_with("assistant\n"));
+    }
+}
@@ -0,0 +1,422 @@
+//! Late-interaction (ColBERT-style) reranking over stored token embeddings.
+//!
+//! Install a [`TokenEncoder`] with [`Memvid::set_token_encoder`], store token embeddings for
+//! the scopes that need the highest precision with [`Memvid::index_token_embeddings`], then
+//! search with `SearchRequest::rerank` set to [`RerankerConfig::late_interaction`]. BM25 or
+//! sketch candidates are rescored by MaxSim against their stored embeddings; candidates outside
+//! the indexed scopes are encoded at query time.
+
+use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::reranker::{Reranker, RerankerDocument, RerankerResult};
+use crate::types::token_track::{TOKEN_TRACK_EXTENSION, TokenTrackManifest};
+use crate::types::{
+    FrameId, FrameRole, FrameStatus, SearchHit, TokenEmbeddings, TokenEncoder, TokenIndexReport,
+    TokenTrackStats,
+};
+
+#[cfg(doc)]
+use crate::types::reranker::RerankerConfig;
+
+impl Memvid {
+    /// Install the encoder used by [`Memvid::index_token_embeddings`] and late-interaction
+    /// reranking.
+    pub fn set_token_encoder(&mut self, encoder: Arc<dyn TokenEncoder>) {
+        self.token_encoder = Some(encoder);
+    }
+
+    /// Remove the installed token encoder, returning it.
+    pub fn clear_token_encoder(&mut self) -> Option<Arc<dyn TokenEncoder>> {
+        self.token_encoder.take()
+    }
+
+    /// The installed token encoder, if any.
+    #[must_use]
+    pub fn token_encoder(&self) -> Option<&Arc<dyn TokenEncoder>> {
+        self.token_encoder.as_ref()
+    }
+
+    /// Size and scopes of the token track and the encoder that built it.
+    #[must_use]
+    pub fn token_track_stats(&self) -> TokenTrackStats {
+        self.token_track.stats()
+    }
+
+    /// Stored token embeddings of `frame_id`, dequantized from int8.
+    #[must_use]
+    pub fn frame_token_embeddings(&self, frame_id: FrameId) -> Option<TokenEmbeddings> {
+        self.token_track.embeddings(frame_id)
+    }
+
+    /// Store token embeddings for every active frame under `scopes` (URI prefixes; empty means
+    /// every frame) that is missing from the token track.
+    ///
+    /// See [`Memvid::index_token_embeddings_with_progress`].
+    pub fn index_token_embeddings(
+        &mut self,
+        scopes: &[&str],
+        batch: usize,
+    ) -> Result<TokenIndexReport> {
+        self.index_token_embeddings_with_progress(scopes, batch, |_| {})
+    }
+
+    /// Encode every active frame under `scopes` missing from the token track with the installed
+    /// encoder, committing `batch` frames at a time and calling `on_progress` after each commit.
+    ///
+    /// The scopes are recorded on the track. Pending changes are committed first and frames that
+    /// are no longer active are dropped. Fails with [`MemvidError::TokenEncoderMissing`] without
+    /// an encoder, and with [`MemvidError::ModelMismatch`] if the track was built by another
+    /// encoder.
+    pub fn index_token_embeddings_with_progress<F>(
+        &mut self,
+        scopes: &[&str],
+        batch: usize,
+        mut on_progress: F,
+    ) -> Result<TokenIndexReport>
+    where
+        F: FnMut(&TokenIndexReport),
+    {
+        self.ensure_writable()?;
+        let encoder = self
+            .token_encoder
+            .clone()
+            .ok_or(MemvidError::TokenEncoderMissing)?;
+        self.ensure_token_encoder_matches(encoder.as_ref())?;
+        self.commit()?;
+
+        let mut report = TokenIndexReport::default();
+        let stale: Vec<FrameId> = self
+            .token_track
+            .frame_ids()
+            .filter(|&frame_id| !self.frame_is_active(frame_id))
+            .collect();
+        for frame_id in stale {
+            self.token_track.remove(frame_id);
+            report.removed += 1;
+        }
+        let in_scope = |uri: &str| scopes.is_empty() || scopes.iter().any(|s| uri.starts_with(s));
+        let candidates: Vec<FrameId> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.status == FrameStatus::Active
+                    && frame.role != FrameRole::ExtractedImage
+                    && !self.token_track.contains(frame.id)
+                    && in_scope(
+                        &frame
+                            .uri
+                            .clone()
+                            .unwrap_or_else(|| crate::default_uri(frame.id)),
+                    )
+            })
+            .map(|frame| frame.id)
+            .collect();
+        report.candidates = candidates.len();
+        let scopes: Vec<String> = scopes.iter().map(|scope| (*scope).to_string()).collect();
+        let previous_scopes = self.token_track.scopes().to_vec();
+        self.token_track
+            .set_encoder(encoder.kind(), encoder.dimension());
+        self.token_track.add_scopes(&scopes);
+        if report.removed > 0 || self.token_track.scopes() != previous_scopes.as_slice() {
+            self.dirty = true;
+        }
+
+        for ids in candidates.chunks(batch.max(1)) {
+            let mut frame_ids = Vec::with_capacity(ids.len());
+            let mut texts = Vec::with_capacity(ids.len());
+            for &frame_id in ids {
+                let frame = self.frame_by_id(frame_id)?;
+                let text = self.frame_search_text(&frame)?;
+                if text.trim().is_empty() {
+                    report.skipped_empty += 1;
+                } else {
+                    frame_ids.push(frame_id);
+                    texts.push(text);
+                }
+            }
+            if !frame_ids.is_empty() {
+                let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+                let embeddings = encoder.encode_batch(&refs)?;
+                if embeddings.len() != frame_ids.len() {
+                    return Err(MemvidError::EmbeddingFailed {
+                        reason: format!(
+                            "token encoder returned {} results for {} texts",
+                            embeddings.len(),
+                            frame_ids.len()
+                        )
+                        .into(),
+                    });
+                }
+                for (&frame_id, tokens) in frame_ids.iter().zip(&embeddings) {
+                    self.token_track.insert(frame_id, tokens)?;
+                }
+                self.dirty = true;
+                self.commit()?;
+                report.encoded += frame_ids.len();
+            }
+            report.batches += 1;
+            tracing::debug!(
+                encoded = report.encoded,
+                remaining = report.remaining(),
+                "token index batch committed"
+            );
+            on_progress(&report);
+        }
+        if self.dirty {
+            self.commit()?;
+        }
+        Ok(report)
+    }
+
+    /// Fail with [`MemvidError::ModelMismatch`] if the token track holds embeddings from an
+    /// encoder other than `encoder`.
+    fn ensure_token_encoder_matches(&self, encoder: &dyn TokenEncoder) -> Result<()> {
+        match self.token_track.encoder() {
+            Some(existing) if existing != encoder.kind() && !self.token_track.is_empty() => {
+                Err(MemvidError::ModelMismatch {
+                    expected: existing.to_string(),
+                    actual: encoder.kind().to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The installed token encoder, checked against the track, for late-interaction reranking.
+    pub(crate) fn late_interaction_encoder(&self) -> Result<Arc<dyn TokenEncoder>> {
+        let encoder = self
+            .token_encoder
+            .clone()
+            .ok_or(MemvidError::TokenEncoderMissing)?;
+        self.ensure_token_encoder_matches(encoder.as_ref())?;
+        Ok(encoder)
+    }
+
+    /// A reranker scoring `hits` by position against their frames' stored token embeddings.
+    pub(crate) fn late_interaction_scorer(
+        &self,
+        encoder: Arc<dyn TokenEncoder>,
+        hits: &[SearchHit],
+    ) -> LateInteractionScorer {
+        LateInteractionScorer {
+            encoder,
+            stored: hits
+                .iter()
+                .map(|hit| self.token_track.embeddings(hit.frame_id))
+                .collect(),
+        }
+    }
+
+    /// Write the token track after the footer, or drop its manifest once the track is empty.
+    pub(crate) fn persist_token_track(&mut self) -> Result<()> {
+        if self.token_track.is_empty() {
+            self.toc.extensions.remove(TOKEN_TRACK_EXTENSION);
+            return Ok(());
+        }
+
+        self.file.seek(SeekFrom::Start(self.header.footer_offset))?;
+        let (offset, length, checksum) =
+            crate::types::write_token_track(&mut self.file, &self.token_track)?;
+        let stats = self.token_track.stats();
+        let manifest = TokenTrackManifest {
+            bytes_offset: offset,
+            bytes_length: length,
+            frame_count: stats.frames,
+            token_count: stats.tokens,
+            dimension: stats.dimension,
+            encoder: stats.encoder.unwrap_or_default(),
+            scopes: stats.scopes,
+            checksum,
+        };
+        self.toc.set_extension(TOKEN_TRACK_EXTENSION, &manifest)?;
+        self.header.footer_offset = offset + length;
+        if self.file.metadata()?.len() < self.header.footer_offset {
+            self.file.set_len(self.header.footer_offset)?;
+        }
+        tracing::debug!(
+            frames = stats.frames,
+            offset,
+            "persist_token_track: persisted token track"
+        );
+        Ok(())
+    }
+}
+
+/// MaxSim scorer over one search's candidates; document ids are hit positions.
+pub(crate) struct LateInteractionScorer {
+    encoder: Arc<dyn TokenEncoder>,
+    /// Stored embeddings of each candidate's frame, `None` outside the indexed scopes.
+    stored: Vec<Option<TokenEmbeddings>>,
+}
+
+impl Reranker for LateInteractionScorer {
+    fn kind(&self) -> &'static str {
+        "late-interaction"
+    }
+
+    fn rerank(
+        &self,
+        query: &str,
+        documents: &[RerankerDocument],
+        top_k: usize,
+    ) -> Result<Vec<RerankerResult>> {
+        let query = self.encoder.encode_query(query)?;
+        let stored = |document: &RerankerDocument| {
+            usize::try_from(document.id)
+                .ok()
+                .and_then(|index| self.stored.get(index))
+                .and_then(Option::as_ref)
+        };
+        let missing: Vec<&str> = documents
+            .iter()
+            .filter(|document| stored(document).is_none())
+            .map(|document| document.text.as_str())
+            .collect();
+        let mut encoded = self.encoder.encode_batch(&missing)?.into_iter();
+
+        let mut results = Vec::with_capacity(documents.len());
+        for (rank, document) in documents.iter().enumerate() {
+            let score = match stored(document) {
+                Some(tokens) => query.max_sim(tokens),
+                None => query.max_sim(&encoded.next().unwrap_or_default()),
+            };
+            results.push(RerankerResult {
+                id: document.id,
+                score,
+                original_rank: rank + 1,
+                new_rank: 0,
+            });
+        }
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a.original_rank.cmp(&b.original_rank))
+        });
+        for (rank, result) in results.iter_mut().enumerate() {
+            result.new_rank = rank + 1;
+        }
+        results.truncate(top_k);
+        Ok(results)
+    }
+}
+
+#[cfg(all(test, feature = "lex"))]
+mod tests {
+    use super::*;
+    use crate::types::reranker::RerankerConfig;
+    use crate::types::{AclEnforcementMode, PutOptions, SearchMode, SearchRequest};
+
+    /// One unit vector per word: "rust" and "borrow" on their own axes, everything else on a
+    /// third, so MaxSim rewards documents containing both query words.
+    struct AxisEncoder;
+
+    impl TokenEncoder for AxisEncoder {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn kind(&self) -> &str {
+            "axis-v1"
+        }
+
+        fn dimension(&self) -> usize {
+            3
+        }
+
+        fn encode(&self, text: &str) -> Result<TokenEmbeddings> {
+            TokenEmbeddings::new(
+                3,
+                text.split_whitespace().map(|word| match word {
+                    "rust" => vec![1.0, 0.0, 0.0],
+                    "borrow" => vec![0.0, 1.0, 0.0],
+                    _ => vec![0.0, 0.0, 1.0],
+                }),
+            )
+        }
+    }
+
+    fn request(rerank: RerankerConfig) -> SearchRequest {
+        SearchRequest {
+            query: "rust borrow".into(),
+            top_k: 3,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+            rerank: Some(rerank),
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        }
+    }
+
+    #[test]
+    fn late_interaction_reranks_with_stored_and_query_time_embeddings() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("colbert.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        for (uri, text) in [
+            ("mv2://docs/a", "rust rust rust rust"),
+            ("mv2://docs/b", "the borrow checker in rust"),
+            ("mv2://notes/c", "borrow rust"),
+        ] {
+            let options = PutOptions {
+                uri: Some(uri.into()),
+                ..Default::default()
+            };
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        assert!(matches!(
+            mem.search(request(RerankerConfig::late_interaction())),
+            Err(MemvidError::TokenEncoderMissing)
+        ));
+        mem.set_token_encoder(Arc::new(AxisEncoder));
+        let report = mem
+            .index_token_embeddings(&["mv2://docs/"], 8)
+            .expect("index");
+        assert_eq!(report.candidates, 2);
+        assert_eq!(report.encoded, 2);
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        let stats = mem.token_track_stats();
+        assert_eq!(stats.frames, 2);
+        assert_eq!(stats.dimension, 3);
+        assert_eq!(stats.scopes, vec!["mv2://docs/".to_string()]);
+        mem.set_token_encoder(Arc::new(AxisEncoder));
+
+        let response = mem
+            .search(request(RerankerConfig {
+                min_score: 0.75,
+                ..RerankerConfig::late_interaction()
+            }))
+            .expect("search");
+        let uris: Vec<&str> = response.hits.iter().map(|hit| hit.uri.as_str()).collect();
+        // Both two-word documents score 1.0; the unscoped one is encoded at query time.
+        assert_eq!(uris.len(), 2);
+        assert!(uris.contains(&"mv2://docs/b"));
+        assert!(uris.contains(&"mv2://notes/c"));
+        assert_eq!(response.hits[0].rank, 1);
+        assert!((response.hits[0].score.unwrap_or_default() - 1.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,319 @@
+//! Setting, inspecting, and enforcing legal holds (see [`crate::types::legal_hold`]).
+
+use std::fs::File;
+use std::path::Path;
+
+use crate::error::{MemvidError, Result};
+use crate::io::header::HeaderCodec;
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{ImmutableHold, ImmutableMode};
+
+impl Memvid {
+    /// Put the memory under an append-only hold until unix time `until`, or until released
+    /// when `None`. Pending changes are committed first; afterwards new frames may still be
+    /// added, but deletes, updates, tag edits, deduplication, vacuum, unpinning, config
+    /// changes, and removing collections, profiles, or entity merges fail with
+    /// [`MemvidError::LegalHold`].
+    ///
+    /// A hold that is in force can only be tightened or extended: replacing a frozen hold
+    /// with an append-only one, or moving `until` earlier, is refused.
+    pub fn set_immutable(&mut self, until: Option<i64>) -> Result<()> {
+        self.place_hold(ImmutableHold {
+            mode: ImmutableMode::AppendOnly,
+            until,
+        })
+    }
+
+    /// Like [`Memvid::set_immutable`], but the file accepts no writes at all, appends
+    /// included, until the hold lapses or is released.
+    pub fn freeze(&mut self, until: Option<i64>) -> Result<()> {
+        self.place_hold(ImmutableHold {
+            mode: ImmutableMode::Frozen,
+            until,
+        })
+    }
+
+    /// The hold in force, if any. Lapsed holds are not returned.
+    #[must_use]
+    pub fn immutable_hold(&self) -> Option<ImmutableHold> {
+        self.header
+            .immutable
+            .filter(|hold| hold.is_active(unix_now()))
+    }
+
+    /// Lift a hold set without an end time. A hold with an `until` in the future cannot be
+    /// lifted early and fails with [`MemvidError::LegalHold`].
+    pub fn release_immutable(&mut self) -> Result<()> {
+        if let Some(hold) = self.immutable_hold() {
+            if hold.until.is_some() {
+                return Err(MemvidError::LegalHold {
+                    mode: hold.mode.as_str(),
+                    operation: "release a hold before it lapses",
+                });
+            }
+        }
+        if self.header.immutable.is_none() {
+            return Ok(());
+        }
+        self.acquire_writer()?;
+        self.header.immutable = None;
+        self.persist_hold()
+    }
+
+    fn place_hold(&mut self, hold: ImmutableHold) -> Result<()> {
+        if let Some(current) = self.immutable_hold() {
+            if !hold.covers(&current, unix_now()) {
+                return Err(MemvidError::LegalHold {
+                    mode: current.mode.as_str(),
+                    operation: "loosen or shorten the hold",
+                });
+            }
+        }
+        self.acquire_writer()?;
+        // A frozen file has nothing pending and refuses commits.
+        if self.ensure_not_frozen().is_ok() {
+            self.commit()?;
+        }
+        self.header.immutable = Some(hold);
+        self.persist_hold()
+    }
+
+    fn persist_hold(&mut self) -> Result<()> {
+        crate::persist_header(&mut self.file, &self.header)?;
+        self.file.sync_all()?;
+        Ok(())
+    }
+
+    /// Refuse `operation`, which changes or removes committed content, while any hold is in
+    /// force.
+    pub(crate) fn ensure_not_held(&self, operation: &'static str) -> Result<()> {
+        match self.immutable_hold() {
+            Some(hold) => Err(MemvidError::LegalHold {
+                mode: hold.mode.as_str(),
+                operation,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse new writes while the file is frozen.
+    pub(crate) fn ensure_not_frozen(&self) -> Result<()> {
+        match self.immutable_hold() {
+            Some(hold) if hold.mode == ImmutableMode::Frozen => Err(MemvidError::LegalHold {
+                mode: hold.mode.as_str(),
+                operation: "write",
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The hold in force on the file at `path`, read from its header without opening it for
+/// writing. Callers treat an error as a hold, so the check fails closed.
+pub(crate) fn hold_on_disk(path: &Path) -> Result<Option<ImmutableHold>> {
+    let mut file = File::open(path)?;
+    let header = HeaderCodec::read(&mut file)?;
+    Ok(header.immutable.filter(|hold| hold.is_active(unix_now())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Memvid;
+    use crate::error::MemvidError;
+    use crate::types::{
+        Collection, DecayPolicy, DoctorOptions, DoctorStatus, EntityKind, EntityResolutionStrategy,
+        FileConfig, ImmutableMode, IngestProfile, LinkKind, MeshNode, PutOptions,
+    };
+
+    fn put(mem: &mut Memvid, text: &str) -> u64 {
+        mem.put_bytes_with_options(text.as_bytes(), PutOptions::default())
+            .expect("put");
+        mem.commit().expect("commit");
+        mem.frame_count() as u64 - 1
+    }
+
+    fn held(result: crate::Result<impl std::fmt::Debug>) -> bool {
+        matches!(
+            result,
+            Err(MemvidError::LegalHold {
+                mode: "append-only",
+                ..
+            })
+        )
+    }
+
+    fn held_memory(name: &str) -> (tempfile::TempDir, Memvid, u64) {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join(name)).expect("create");
+        let frame = put(&mut mem, "evidence exhibit one");
+        (dir, mem, frame)
+    }
+
+    #[test]
+    fn append_only_hold_blocks_unpinning() {
+        let (_dir, mut mem, frame) = held_memory("unpin.mv2");
+        mem.pin_frame(frame).expect("pin");
+        mem.set_immutable(None).expect("hold");
+        assert!(held(mem.unpin_frame(frame)));
+        assert_eq!(mem.pinned_frames(), vec![frame]);
+    }
+
+    #[test]
+    fn append_only_hold_blocks_removing_collections() {
+        let (_dir, mut mem, _) = held_memory("collection.mv2");
+        mem.create_collection(Collection::new("cases", "mv2://cases/"))
+            .expect("collection");
+        mem.set_immutable(None).expect("hold");
+        assert!(held(mem.remove_collection("cases")));
+        assert!(mem.collection("cases").is_ok());
+    }
+
+    #[test]
+    fn append_only_hold_blocks_config_changes() {
+        let (_dir, mut mem, _) = held_memory("config.mv2");
+        mem.set_immutable(None).expect("hold");
+        let config = FileConfig {
+            snippet_chars: Some(64),
+            ..FileConfig::default()
+        };
+        assert!(held(mem.set_config(config)));
+        assert_eq!(mem.config().expect("config"), FileConfig::default());
+    }
+
+    #[test]
+    fn append_only_hold_blocks_removing_ingest_profiles() {
+        let (_dir, mut mem, _) = held_memory("profile.mv2");
+        mem.set_ingest_profile("email", IngestProfile::email())
+            .expect("profile");
+        mem.set_immutable(None).expect("hold");
+        assert!(held(mem.remove_ingest_profile("email")));
+        assert!(mem.ingest_profile("email").is_ok());
+    }
+
+    #[test]
+    fn append_only_hold_blocks_undoing_entity_merges() {
+        let (_dir, mut mem, _) = held_memory("entities.mv2");
+        let person = |name: &str, frame_id| {
+            MeshNode::new(
+                name.to_lowercase(),
+                name.to_string(),
+                EntityKind::Person,
+                0.9,
+                frame_id,
+                0,
+                4,
+            )
+        };
+        mem.add_mesh_nodes(vec![
+            person("Robert Smith", 1),
+            person("Robert Smith", 2),
+            person("Bob Smith", 3),
+        ]);
+        let report = mem
+            .resolve_entities(&EntityResolutionStrategy::default())
+            .expect("resolve");
+        let merge = report.merges.first().expect("one merge").id;
+        mem.set_immutable(None).expect("hold");
+        assert!(held(mem.undo_entity_merge(merge)));
+        assert_eq!(mem.mesh_node_count(), report.nodes_after);
+    }
+
+    #[test]
+    fn append_only_hold_blocks_destructive_changes_and_persists() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("hold.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        let first = put(&mut mem, "evidence exhibit one");
+        mem.set_immutable(None).expect("hold");
+
+        let held = |err: MemvidError| matches!(err, MemvidError::LegalHold { .. });
+        assert!(held(mem.delete_frame(first).expect_err("delete")));
+        assert!(held(
+            mem.update_frame(first, Some(b"edited".to_vec()), PutOptions::default(), None)
+                .expect_err("update")
+        ));
+        assert!(held(mem.vacuum().expect_err("vacuum")));
+        assert!(held(mem.decay(&DecayPolicy::default()).expect_err("decay")));
+        put(&mut mem, "evidence exhibit two");
+        drop(mem);
+
+        let mem = Memvid::open(&path).expect("reopen");
+        let hold = mem.immutable_hold().expect("hold persisted");
+        assert_eq!(hold.mode, ImmutableMode::AppendOnly);
+        assert_eq!(mem.frame_count(), 2);
+        drop(mem);
+
+        let options = DoctorOptions {
+            vacuum: true,
+            ..DoctorOptions::default()
+        };
+        let report = Memvid::doctor(&path, options).expect("doctor");
+        assert!(
+            report.plan.options.dry_run,
+            "doctor only plans under a hold"
+        );
+        assert_ne!(report.status, DoctorStatus::Healed);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        mem.release_immutable().expect("release");
+        assert!(mem.immutable_hold().is_none());
+        mem.delete_frame(first).expect("delete after release");
+    }
+
+    #[test]
+    fn frozen_hold_blocks_appends_and_cannot_be_shortened() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("frozen.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        put(&mut mem, "sealed record");
+        let until = crate::memvid::helpers::unix_now() + 3_600;
+        mem.freeze(Some(until)).expect("freeze");
+
+        let err = mem
+            .put_bytes_with_options(b"late addition", PutOptions::default())
+            .expect_err("frozen");
+        assert!(matches!(err, MemvidError::LegalHold { mode: "frozen", .. }));
+        let frozen = |result: crate::Result<()>| {
+            matches!(result, Err(MemvidError::LegalHold { mode: "frozen", .. }))
+        };
+        assert!(frozen(mem.commit()), "no new generation");
+        assert!(frozen(mem.set_config(FileConfig::default())));
+        assert!(frozen(mem.link_frames(0, 0, LinkKind::References)));
+        let len = std::fs::metadata(&path).expect("metadata").len();
+        mem.search(crate::types::SearchRequest {
+            query: "sealed".into(),
+            top_k: 5,
+            snippet_chars: 40,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::default(),
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .expect("reads still work");
+        assert!(mem.access_stats().expect("stats").is_empty());
+        assert_eq!(std::fs::metadata(&path).expect("metadata").len(), len);
+        assert!(mem.set_immutable(Some(until + 60)).is_err(), "no downgrade");
+        assert!(mem.freeze(Some(until - 60)).is_err(), "no shortening");
+        assert!(mem.release_immutable().is_err(), "timed holds run out");
+        mem.freeze(None).expect("extend indefinitely");
+    }
+}
@@ -11,7 +11,7 @@ use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom};
 use std::panic;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
 use crate::constants::{MAGIC, SPEC_VERSION, WAL_OFFSET, WAL_SIZE_TINY};
 use crate::error::{MemvidError, Result};
@@ -19,14 +19,25 @@ use crate::footer::{FooterSlice, find_last_valid_footer};
 use crate::io::header::HeaderCodec;
 #[cfg(feature = "parallel_segments")]
 use crate::io::manifest_wal::ManifestWal;
+use crate::io::storage::StorageFile;
 use crate::io::wal::EmbeddedWal;
 use crate::lock::{FileLock, LockMode};
+use crate::memvid::mutation::DurabilityProfile;
 #[cfg(feature = "lex")]
 use crate::search::{EmbeddedLexStorage, TantivyEngine};
-#[cfg(feature = "temporal_track")]
 use crate::types::FrameId;
 #[cfg(feature = "parallel_segments")]
 use crate::types::IndexSegmentRef;
+use crate::types::blob_extents::blob_extent_ranges;
+use crate::types::embedding_migration::staged_segment_ranges;
+use crate::types::reranker::Reranker;
+use crate::types::snapshot::snapshot_archive_ranges;
+use crate::types::sparse::{
+    SPARSE_TRACK_EXTENSION, SparseEncoder, SparseTrack, SparseTrackManifest, sparse_track_range,
+};
+use crate::types::token_track::{
+    TOKEN_TRACK_EXTENSION, TokenEncoder, TokenTrack, TokenTrackManifest, token_track_range,
+};
 use crate::types::{
     FrameStatus, Header, IndexManifests, LogicMesh, MemoriesTrack, PutManyOpts, SchemaRegistry,
     SegmentCatalog, SketchTrack, TicketRef, Tier, Toc, VectorCompression,
@@ -46,7 +57,7 @@ const DEFAULT_STALE_GRACE_MS: u64 = 10_000;
 /// Holds the file descriptor, lock, header, TOC, and in-memory index state. Mutations
 /// append to the embedded WAL and are materialized at commit time to keep the layout deterministic.
 pub struct Memvid {
-    pub(crate) file: File,
+    pub(crate) file: StorageFile,
     pub(crate) path: PathBuf,
     pub(crate) lock: FileLock,
     pub(crate) read_only: bool,
@@ -72,6 +83,8 @@ pub struct Memvid {
     pub(crate) vec_compression: VectorCompression,
     pub(crate) vec_model: Option<String>,
     pub(crate) vec_index: Option<VecIndex>,
+    /// Vectors for already committed frames, folded into the vec index by the next commit.
+    pub(crate) pending_embeddings: Vec<(FrameId, Vec<f32>)>,
     /// CLIP visual embeddings index (separate from vec due to different dimensions)
     pub(crate) clip_enabled: bool,
     pub(crate) clip_index: Option<crate::clip::ClipIndex>,
@@ -90,6 +103,10 @@ pub struct Memvid {
     pub(crate) logic_mesh: LogicMesh,
     /// In-memory sketch track for fast candidate generation.
     pub(crate) sketch_track: SketchTrack,
+    /// In-memory learned sparse index, searched by `SearchMode::Sparse`.
+    pub(crate) sparse_track: SparseTrack,
+    /// In-memory token embeddings for late-interaction reranking.
+    pub(crate) token_track: TokenTrack,
     /// Schema registry for predicate validation.
     pub(crate) schema_registry: SchemaRegistry,
     /// Whether to enforce strict schema validation on card insert.
@@ -102,12 +119,65 @@ pub struct Memvid {
     /// Completed sessions stored in memory (until persisted to file).
     #[cfg(feature = "replay")]
     pub(crate) completed_sessions: Vec<crate::replay::ReplaySession>,
+    /// Live commit event subscribers (see `Memvid::subscribe`).
+    pub(crate) commit_subscribers: Vec<std::sync::mpsc::Sender<crate::types::CommitEvent>>,
+    /// Callbacks registered with `Memvid::register_hooks` and friends.
+    pub(crate) hooks: crate::memvid::hooks::HookRegistry,
+    /// Event recorded by the in-flight commit, published once it is durable.
+    pub(crate) pending_commit_event: Option<crate::types::CommitEvent>,
+    /// Label of the snapshot this handle is pinned to (see `Memvid::open_at_snapshot`).
+    pub(crate) snapshot_view: Option<String>,
+    /// Second-stage ranker used by searches that set `SearchRequest::rerank`.
+    pub(crate) reranker: Option<Arc<dyn Reranker>>,
+    /// Encoder used by `Memvid::index_sparse` and sparse-mode searches.
+    pub(crate) sparse_encoder: Option<Arc<dyn SparseEncoder>>,
+    /// Encoder used by `Memvid::index_token_embeddings` and late-interaction reranking.
+    pub(crate) token_encoder: Option<Arc<dyn TokenEncoder>>,
+    /// Frame locations, indexed lazily by `geo` filters.
+    pub(crate) geo_track: crate::memvid::geo::GeoTrack,
+    /// Sorted indexes over metadata keys marked `indexed`, built lazily by `meta.` filters.
+    pub(crate) meta_track: crate::memvid::meta::MetaTrack,
+    /// Retrieval counters recorded since the last commit (see `Memvid::access_stats`).
+    pub(crate) pending_access: crate::types::AccessStats,
+    /// Default author and agent id recorded with each commit (see `Memvid::set_commit_author`).
+    pub(crate) commit_identity: crate::types::CommitMetadata,
+    /// Provenance for the in-flight commit, set by `commit_with_options`.
+    pub(crate) pending_commit_metadata: Option<crate::types::CommitMetadata>,
+    /// When the open `CommitMode::Coalesced` window started, if a commit is being deferred.
+    pub(crate) commit_window: Option<std::time::Instant>,
+    /// The `.mv2x` sidecar holding this memory's indexes, once validated.
+    pub(crate) external_indexes: Option<crate::memvid::external_index::AttachedIndexes>,
+    /// Key that signs audit chain entries appended through this handle.
+    pub(crate) audit_signer: Option<ed25519_dalek::SigningKey>,
+    /// Whether payload reads are checked against the frame's BLAKE3 checksum.
+    pub(crate) verify_payloads: bool,
+    /// Frames that failed verification on this handle (see `Memvid::quarantined_frames`).
+    pub(crate) quarantined: Mutex<std::collections::BTreeSet<FrameId>>,
+    /// Extraction and chunking done ahead of time by `put_many` for the next `put_internal`.
+    pub(crate) prepared_put: Option<crate::memvid::pipeline::PreparedPut>,
+    /// Lazily mapped file backing `Memvid::frame_bytes_ref` on read-only handles.
+    pub(crate) payload_map: OnceLock<Mmap>,
+    /// Decoded suggestion index, dropped whenever a commit changes it (see `Memvid::suggest`).
+    pub(crate) suggest_index: OnceLock<crate::types::SuggestIndex>,
+    /// Spelling dictionary built from the suggestion index's word counts.
+    #[cfg(feature = "spelling")]
+    pub(crate) spelling_dictionary: OnceLock<crate::memvid::spelling::SpellingDictionary>,
 }
 
 /// Controls read-only open behaviour for `.mv2` memories.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct OpenReadOptions {
     pub allow_repair: bool,
+    /// Verify each payload against its BLAKE3 checksum on read, quarantining frames that fail.
+    pub verify_payloads: bool,
+}
+
+impl OpenReadOptions {
+    #[must_use]
+    pub fn verify_payloads(mut self, verify: bool) -> Self {
+        self.verify_payloads = verify;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -117,6 +187,8 @@ pub struct LockSettings {
     pub stale_grace_ms: u64,
     pub force_stale: bool,
     pub command: Option<String>,
+    /// How often writes are fsynced; `CommitOptions::durability` overrides it per commit.
+    pub durability: DurabilityProfile,
 }
 
 impl Default for LockSettings {
@@ -127,6 +199,7 @@ impl Default for LockSettings {
             stale_grace_ms: DEFAULT_STALE_GRACE_MS,
             force_stale: false,
             command: None,
+            durability: DurabilityProfile::default(),
         }
     }
 }
@@ -155,6 +228,8 @@ impl Memvid {
             wal_checkpoint_pos: 0,
             wal_sequence: 0,
             toc_checksum: [0u8; 32],
+            immutable: None,
+            data_start: WAL_OFFSET + WAL_SIZE_TINY,
         };
 
         let mut toc = empty_toc();
@@ -176,10 +251,10 @@ impl Memvid {
         let manifest_wal_entries = manifest_wal.replay()?;
 
         // No frames yet, so payload region ends at WAL boundary
-        let cached_payload_end = header.wal_offset + header.wal_size;
+        let cached_payload_end = header.data_start;
 
         let mut memvid = Self {
-            file,
+            file: StorageFile::new(file),
             path: path_ref.to_path_buf(),
             lock,
             read_only: false,
@@ -213,6 +288,8 @@ impl Memvid {
             memories_track: MemoriesTrack::new(),
             logic_mesh: LogicMesh::new(),
             sketch_track: SketchTrack::default(),
+            sparse_track: SparseTrack::default(),
+            token_track: TokenTrack::default(),
             schema_registry: SchemaRegistry::new(),
             schema_strict: false,
             batch_opts: None,
@@ -220,6 +297,29 @@ impl Memvid {
             active_session: None,
             #[cfg(feature = "replay")]
             completed_sessions: Vec::new(),
+            commit_subscribers: Vec::new(),
+            hooks: crate::memvid::hooks::HookRegistry::default(),
+            pending_commit_event: None,
+            snapshot_view: None,
+            reranker: None,
+            sparse_encoder: None,
+            token_encoder: None,
+            geo_track: crate::memvid::geo::GeoTrack::default(),
+            meta_track: crate::memvid::meta::MetaTrack::default(),
+            pending_access: crate::types::AccessStats::default(),
+            commit_identity: crate::types::CommitMetadata::default(),
+            audit_signer: None,
+            pending_commit_metadata: None,
+            commit_window: None,
+            external_indexes: None,
+            verify_payloads: false,
+            quarantined: Mutex::default(),
+            payload_map: OnceLock::new(),
+            suggest_index: OnceLock::new(),
+            #[cfg(feature = "spelling")]
+            spelling_dictionary: OnceLock::new(),
+            prepared_put: None,
+            pending_embeddings: Vec::new(),
         };
 
         #[cfg(feature = "lex")]
@@ -305,7 +405,8 @@ impl Memvid {
         self.toc.frames.len()
     }
 
-    fn open_locked(mut file: File, lock: FileLock, path_ref: &Path) -> Result<Self> {
+    fn open_locked(file: File, lock: FileLock, path_ref: &Path) -> Result<Self> {
+        let mut file = StorageFile::new(file);
         // Fast-path detection for encrypted capsules (.mv2e).
         // This avoids confusing "invalid header" errors and provides an actionable hint.
         let mut magic = [0u8; 4];
@@ -395,6 +496,8 @@ impl Memvid {
             memories_track: MemoriesTrack::new(),
             logic_mesh: LogicMesh::new(),
             sketch_track: SketchTrack::default(),
+            sparse_track: SparseTrack::default(),
+            token_track: TokenTrack::default(),
             schema_registry: SchemaRegistry::new(),
             schema_strict: false,
             batch_opts: None,
@@ -402,10 +505,34 @@ impl Memvid {
             active_session: None,
             #[cfg(feature = "replay")]
             completed_sessions: Vec::new(),
+            commit_subscribers: Vec::new(),
+            hooks: crate::memvid::hooks::HookRegistry::default(),
+            pending_commit_event: None,
+            snapshot_view: None,
+            reranker: None,
+            sparse_encoder: None,
+            token_encoder: None,
+            geo_track: crate::memvid::geo::GeoTrack::default(),
+            meta_track: crate::memvid::meta::MetaTrack::default(),
+            pending_access: crate::types::AccessStats::default(),
+            commit_identity: crate::types::CommitMetadata::default(),
+            audit_signer: None,
+            pending_commit_metadata: None,
+            commit_window: None,
+            external_indexes: None,
+            verify_payloads: false,
+            quarantined: Mutex::default(),
+            payload_map: OnceLock::new(),
+            suggest_index: OnceLock::new(),
+            #[cfg(feature = "spelling")]
+            spelling_dictionary: OnceLock::new(),
+            prepared_put: None,
+            pending_embeddings: Vec::new(),
         };
         memvid.data_end = compute_data_end(&memvid.toc, &memvid.header);
         // One-time O(n) scan to initialize cached_payload_end from existing frames
         memvid.cached_payload_end = compute_payload_region_end(&memvid.toc, &memvid.header);
+        memvid.attach_external_indexes();
         // Use consolidated helper for lex_enabled check
         memvid.lex_enabled = has_lex_index(&memvid.toc);
         if memvid.lex_enabled {
@@ -433,6 +560,8 @@ impl Memvid {
         memvid.load_memories_track()?;
         memvid.load_logic_mesh()?;
         memvid.load_sketch_track()?;
+        memvid.load_sparse_track()?;
+        memvid.load_token_track()?;
         if checksum_result.is_err() {
             memvid.toc.verify_checksum()?;
             if memvid.toc.toc_checksum != memvid.header.toc_checksum {
@@ -464,11 +593,13 @@ impl Memvid {
         let path_ref = path.as_ref();
         ensure_single_file(path_ref)?;
 
-        if options.allow_repair {
-            return Self::open(path_ref);
-        }
-
-        Self::open_read_only_snapshot(path_ref)
+        let mut memvid = if options.allow_repair {
+            Self::open(path_ref)?
+        } else {
+            Self::open_read_only_snapshot(path_ref)?
+        };
+        memvid.verify_payloads = options.verify_payloads;
+        Ok(memvid)
     }
 
     fn open_read_only_snapshot(path_ref: &Path) -> Result<Self> {
@@ -483,7 +614,19 @@ impl Memvid {
         let mut header = HeaderCodec::read(&mut file)?;
         header.footer_offset = footer_offset;
         header.toc_checksum = toc.toc_checksum;
-
+        Self::open_committed_view(file, path_ref, header, toc, data_end, generation)
+    }
+
+    /// Build a read-only handle around an already decoded, committed TOC. Pending WAL
+    /// records are ignored.
+    pub(crate) fn open_committed_view(
+        file: File,
+        path_ref: &Path,
+        header: Header,
+        toc: Toc,
+        data_end: u64,
+        generation: u64,
+    ) -> Result<Self> {
         let lock = FileLock::acquire_with_mode(&file, LockMode::Shared)?;
         let wal = EmbeddedWal::open_read_only(&file, &header)?;
 
@@ -496,7 +639,7 @@ impl Memvid {
         let cached_payload_end = compute_payload_region_end(&toc, &header);
 
         let mut memvid = Self {
-            file,
+            file: StorageFile::new(file),
             path: path_ref.to_path_buf(),
             lock,
             read_only: true,
@@ -530,6 +673,8 @@ impl Memvid {
             memories_track: MemoriesTrack::new(),
             logic_mesh: LogicMesh::new(),
             sketch_track: SketchTrack::default(),
+            sparse_track: SparseTrack::default(),
+            token_track: TokenTrack::default(),
             schema_registry: SchemaRegistry::new(),
             schema_strict: false,
             batch_opts: None,
@@ -537,8 +682,32 @@ impl Memvid {
             active_session: None,
             #[cfg(feature = "replay")]
             completed_sessions: Vec::new(),
+            commit_subscribers: Vec::new(),
+            hooks: crate::memvid::hooks::HookRegistry::default(),
+            pending_commit_event: None,
+            snapshot_view: None,
+            reranker: None,
+            sparse_encoder: None,
+            token_encoder: None,
+            geo_track: crate::memvid::geo::GeoTrack::default(),
+            meta_track: crate::memvid::meta::MetaTrack::default(),
+            pending_access: crate::types::AccessStats::default(),
+            commit_identity: crate::types::CommitMetadata::default(),
+            audit_signer: None,
+            pending_commit_metadata: None,
+            commit_window: None,
+            external_indexes: None,
+            verify_payloads: false,
+            quarantined: Mutex::default(),
+            payload_map: OnceLock::new(),
+            suggest_index: OnceLock::new(),
+            #[cfg(feature = "spelling")]
+            spelling_dictionary: OnceLock::new(),
+            prepared_put: None,
+            pending_embeddings: Vec::new(),
         };
 
+        memvid.attach_external_indexes();
         // Use consolidated helper for lex_enabled check
         memvid.lex_enabled = has_lex_index(&memvid.toc);
         if memvid.lex_enabled {
@@ -556,10 +725,12 @@ impl Memvid {
         if memvid.clip_enabled {
             memvid.load_clip_index_from_manifest()?;
         }
-        // Load memories track, Logic-Mesh, and sketch track if present
+        // Load memories track, Logic-Mesh, sketch, sparse, and token tracks if present
         memvid.load_memories_track()?;
         memvid.load_logic_mesh()?;
         memvid.load_sketch_track()?;
+        memvid.load_sparse_track()?;
+        memvid.load_token_track()?;
 
         memvid.bootstrap_segment_catalog();
         #[cfg(feature = "temporal_track")]
@@ -584,6 +755,27 @@ impl Memvid {
         Self::open_locked(file, lock, path_ref)
     }
 
+    /// Rebuild header, TOC, WAL, and index state from the bytes on disk, keeping the OS lock
+    /// and handle-level settings. Used after the file was rewritten underneath this handle.
+    ///
+    /// Callers must ensure there is no uncommitted state; it is discarded.
+    pub(crate) fn reload_from_disk(&mut self) -> Result<()> {
+        let file = self.file.try_clone()?;
+        let mut fresh = Self::open_locked(file, FileLock::unlocked(&self.file)?, &self.path)?;
+        // Hand the real lock to the new state; the old state drops with the placeholder.
+        std::mem::swap(&mut fresh.lock, &mut self.lock);
+        fresh.read_only = self.read_only;
+        fresh.lock_settings = self.lock_settings.clone();
+        fresh.vec_compression = self.vec_compression.clone();
+        fresh.schema_registry = std::mem::take(&mut self.schema_registry);
+        fresh.schema_strict = self.schema_strict;
+        fresh.commit_subscribers = std::mem::take(&mut self.commit_subscribers);
+        fresh.hooks = std::mem::take(&mut self.hooks);
+        self.dirty = false;
+        *self = fresh;
+        Ok(())
+    }
+
     fn bootstrap_segment_catalog(&mut self) {
         let catalog = &mut self.toc.segment_catalog;
         if catalog.version == 0 {
@@ -717,6 +909,30 @@ impl Memvid {
         Ok(())
     }
 
+    /// Load the sparse track from the manifest if present.
+    fn load_sparse_track(&mut self) -> Result<()> {
+        let Some(manifest) = self
+            .toc
+            .extension::<SparseTrackManifest>(SPARSE_TRACK_EXTENSION)?
+        else {
+            return Ok(());
+        };
+        self.sparse_track = crate::types::read_sparse_track(&mut self.file, &manifest)?;
+        Ok(())
+    }
+
+    /// Load the token track from the manifest if present.
+    fn load_token_track(&mut self) -> Result<()> {
+        let Some(manifest) = self
+            .toc
+            .extension::<TokenTrackManifest>(TOKEN_TRACK_EXTENSION)?
+        else {
+            return Ok(());
+        };
+        self.token_track = crate::types::read_token_track(&mut self.file, &manifest)?;
+        Ok(())
+    }
+
     #[cfg(feature = "temporal_track")]
     pub(crate) fn ensure_temporal_track_loaded(&mut self) -> Result<()> {
         if self.temporal_track.is_some() {
@@ -878,6 +1094,8 @@ impl Memvid {
             capacity_bytes: crate::types::Tier::Free.capacity_bytes(),
             verified: false,
         };
+        // Written by the next commit.
+        self.record_audit_ticket(self.generation.wrapping_add(1))?;
         self.dirty = true;
         Ok(())
     }
@@ -1191,16 +1409,107 @@ pub(crate) fn empty_toc() -> Toc {
         memory_binding: None,
         replay_manifest: None,
         enrichment_queue: crate::types::EnrichmentQueueManifest::default(),
+        extensions: std::collections::BTreeMap::new(),
         merkle_root: [0u8; 32],
         toc_checksum: [0u8; 32],
     }
 }
 
+/// Blobs kept in the payload region that no frame references directly: snapshot archives,
+/// staged embedding migration segments, and blob extents.
+pub(crate) fn reserved_payload_ranges(toc: &Toc, header: &Header) -> Vec<(u64, u64)> {
+    let mut ranges = snapshot_archive_ranges(toc, header);
+    ranges.extend(staged_segment_ranges(toc, header));
+    ranges.extend(blob_extent_ranges(toc, header));
+    ranges
+}
+
+/// Every `(offset, length)` the TOC references: frame payloads (tombstoned frames included),
+/// index and track blobs, catalog segments, and reserved payload blobs, plus a relocated WAL.
+/// Bytes between `Header::data_start` and the footer outside these ranges are orphaned.
+pub(crate) fn referenced_byte_ranges(toc: &Toc, header: &Header) -> Vec<(u64, u64)> {
+    let mut ranges = reserved_payload_ranges(toc, header);
+    if header.wal_relocated() {
+        ranges.push((header.wal_offset, header.wal_size));
+    }
+    ranges.extend(
+        toc.frames
+            .iter()
+            .map(|frame| (frame.payload_offset, frame.payload_length)),
+    );
+    ranges.extend(
+        toc.segments
+            .iter()
+            .map(|segment| (segment.bytes_offset, segment.bytes_length)),
+    );
+    ranges.extend(
+        toc.indexes
+            .lex_segments
+            .iter()
+            .map(|segment| (segment.bytes_offset, segment.bytes_length)),
+    );
+    let catalog = &toc.segment_catalog;
+    ranges.extend(
+        catalog
+            .lex_segments
+            .iter()
+            .map(|seg| &seg.common)
+            .chain(catalog.vec_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.time_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.temporal_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.tantivy_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.index_segments.iter().map(|seg| &seg.common))
+            .map(|common| (common.bytes_offset, common.bytes_length)),
+    );
+    let manifests = [
+        toc.indexes
+            .lex
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.indexes
+            .vec
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.indexes
+            .clip
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.time_index
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.temporal_track
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.memories_track
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.logic_mesh
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.sketch_track
+            .as_ref()
+            .map(|m| (m.bytes_offset, m.bytes_length)),
+        toc.replay_manifest
+            .as_ref()
+            .map(|m| (m.segment_offset, m.segment_size)),
+        sparse_track_range(toc),
+        token_track_range(toc),
+    ];
+    ranges.extend(manifests.into_iter().flatten());
+    ranges.retain(|(_, length)| *length != 0);
+    ranges
+}
+
 /// Compute the end of the payload region from frame payloads only.
 /// Used once at open time to seed `cached_payload_end`.
 pub(crate) fn compute_payload_region_end(toc: &Toc, header: &Header) -> u64 {
-    let wal_region_end = header.wal_offset.saturating_add(header.wal_size);
+    let wal_region_end = header.wal_end();
     let mut max_end = wal_region_end;
+    // Snapshot archives and staged migration vectors sit in the payload region so index
+    // rebuilds never overwrite them.
+    for (offset, len) in reserved_payload_ranges(toc, header) {
+        max_end = max_end.max(offset.saturating_add(len));
+    }
     for frame in &toc.frames {
         if frame.payload_length != 0 {
             if let Some(end) = frame.payload_offset.checked_add(frame.payload_length) {
@@ -1218,7 +1527,7 @@ pub(crate) fn compute_data_end(toc: &Toc, header: &Header) -> u64 {
     // - the current footer boundary (TOC offset), since callers may safely overwrite old TOCs
     //
     // Keeping this conservative prevents WAL replay / appends from corrupting embedded segments.
-    let wal_region_end = header.wal_offset.saturating_add(header.wal_size);
+    let wal_region_end = header.wal_end();
     let mut max_end = wal_region_end.max(header.footer_offset);
 
     // Frame payloads (active only).
@@ -1232,6 +1541,10 @@ pub(crate) fn compute_data_end(toc: &Toc, header: &Header) -> u64 {
         }
     }
 
+    for (offset, len) in reserved_payload_ranges(toc, header) {
+        max_end = max_end.max(offset.saturating_add(len));
+    }
+
     // Segment catalog entries.
     let catalog = &toc.segment_catalog;
     for seg in &catalog.lex_segments {
@@ -1310,6 +1623,14 @@ pub(crate) fn compute_data_end(toc: &Toc, header: &Header) -> u64 {
             max_end = max_end.max(end);
         }
     }
+    for (offset, length) in [sparse_track_range(toc), token_track_range(toc)]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(end) = offset.checked_add(length) {
+            max_end = max_end.max(end);
+        }
+    }
     #[cfg(feature = "replay")]
     if let Some(manifest) = toc.replay_manifest.as_ref() {
         if let Some(end) = manifest.segment_offset.checked_add(manifest.segment_size) {
@@ -1327,11 +1648,11 @@ pub(crate) fn compute_data_end(toc: &Toc, header: &Header) -> u64 {
     max_end
 }
 
-struct TailSnapshot {
-    toc: Toc,
-    footer_offset: u64,
-    data_end: u64,
-    generation: u64,
+pub(crate) struct TailSnapshot {
+    pub(crate) toc: Toc,
+    pub(crate) footer_offset: u64,
+    pub(crate) data_end: u64,
+    pub(crate) generation: u64,
 }
 
 fn locate_footer_window(mmap: &[u8]) -> Option<(FooterSlice<'_>, usize)> {
@@ -1353,7 +1674,7 @@ fn locate_footer_window(mmap: &[u8]) -> Option<(FooterSlice<'_>, usize)> {
     None
 }
 
-fn load_tail_snapshot(file: &File) -> Result<TailSnapshot> {
+pub(crate) fn load_tail_snapshot(file: &File) -> Result<TailSnapshot> {
     // Safety: we only create a read-only mapping over the stable file bytes.
     let mmap = unsafe { Mmap::map(file)? };
 
@@ -1373,7 +1694,7 @@ fn load_tail_snapshot(file: &File) -> Result<TailSnapshot> {
     })
 }
 
-fn detect_generation(file: &File) -> Result<Option<u64>> {
+pub(crate) fn detect_generation(file: &File) -> Result<Option<u64>> {
     // Safety: read-only mapping for footer inspection.
     let mmap = unsafe { Mmap::map(file)? };
 
@@ -0,0 +1,128 @@
+//! Typed frame links and their traversal (see [`crate::types::links`]).
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    FrameId, FrameLink, FrameStatus, LinkDirection, LinkKind, RELATIONS_EXTENSION, RelatedFrame,
+    RelationsTrack, SearchHit, SearchHitMetadata,
+};
+
+impl Memvid {
+    /// Link `from` to `to` with `kind`; linking the same pair with the same kind again is a
+    /// no-op. Both frames must be committed and not deleted. Saved on the next commit.
+    pub fn link_frames(&mut self, from: FrameId, to: FrameId, kind: LinkKind) -> Result<()> {
+        self.ensure_writable()?;
+        for frame_id in [from, to] {
+            if self.frame_by_id(frame_id)?.status == FrameStatus::Deleted {
+                return Err(MemvidError::InvalidFrame {
+                    frame_id,
+                    reason: "cannot link a deleted frame",
+                });
+            }
+        }
+        if from == to {
+            return Err(MemvidError::InvalidFrame {
+                frame_id: from,
+                reason: "cannot link a frame to itself",
+            });
+        }
+        let link = FrameLink { from, to, kind };
+        let mut track = self.relations_track()?;
+        if !track.links.contains(&link) {
+            track.links.push(link);
+            self.toc.set_extension(RELATIONS_EXTENSION, &track)?;
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
+    /// Remove the link from `from` to `to` with `kind`; returns whether it existed.
+    pub fn unlink_frames(&mut self, from: FrameId, to: FrameId, kind: LinkKind) -> Result<bool> {
+        self.ensure_writable()?;
+        self.ensure_not_held("remove links")?;
+        let mut track = self.relations_track()?;
+        let before = track.links.len();
+        track
+            .links
+            .retain(|link| *link != FrameLink { from, to, kind });
+        if track.links.len() == before {
+            return Ok(false);
+        }
+        if track.links.is_empty() {
+            self.toc.extensions.remove(RELATIONS_EXTENSION);
+        } else {
+            self.toc.set_extension(RELATIONS_EXTENSION, &track)?;
+        }
+        self.dirty = true;
+        Ok(true)
+    }
+
+    /// Frames linked to `frame_id` in either direction, in link order. Links to deleted frames
+    /// are skipped; superseded frames stay reachable so revisions can be followed.
+    pub fn related(&self, frame_id: FrameId) -> Result<Vec<RelatedFrame>> {
+        self.frame_by_id(frame_id)?;
+        Ok(self.related_in(&self.relations_track()?, frame_id))
+    }
+
+    /// Every link stored in the relations track.
+    pub fn frame_links(&self) -> Result<Vec<FrameLink>> {
+        Ok(self.relations_track()?.links)
+    }
+
+    fn relations_track(&self) -> Result<RelationsTrack> {
+        Ok(self.toc.extension(RELATIONS_EXTENSION)?.unwrap_or_default())
+    }
+
+    fn related_in(&self, track: &RelationsTrack, frame_id: FrameId) -> Vec<RelatedFrame> {
+        track
+            .links
+            .iter()
+            .filter_map(|link| {
+                let (other, direction) = if link.from == frame_id {
+                    (link.to, LinkDirection::Outgoing)
+                } else if link.to == frame_id {
+                    (link.from, LinkDirection::Incoming)
+                } else {
+                    return None;
+                };
+                let frame = self
+                    .frame_by_id(other)
+                    .ok()
+                    .filter(|frame| frame.status != FrameStatus::Deleted)?;
+                Some(RelatedFrame {
+                    frame_id: other,
+                    kind: link.kind,
+                    direction,
+                    uri: frame.uri,
+                    title: frame.title,
+                })
+            })
+            .collect()
+    }
+
+    /// Attach each hit's linked frames to its metadata. Chunks use their parent's links.
+    pub(crate) fn attach_related_frames(&self, hits: &mut [SearchHit]) -> Result<()> {
+        let track = self.relations_track()?;
+        if track.links.is_empty() {
+            return Ok(());
+        }
+        for hit in hits.iter_mut() {
+            let mut related = self.related_in(&track, hit.frame_id);
+            if related.is_empty() {
+                if let Some(parent_id) = self
+                    .frame_by_id(hit.frame_id)
+                    .ok()
+                    .and_then(|frame| frame.parent_id)
+                {
+                    related = self.related_in(&track, parent_id);
+                }
+            }
+            if !related.is_empty() {
+                hit.metadata
+                    .get_or_insert_with(SearchHitMetadata::default)
+                    .related = related;
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,11 +4,16 @@
 //! an MV2 file, including adding cards, querying by entity/slot, temporal
 //! lookups, and enrichment tracking.
 
+use std::collections::BTreeMap;
+
+use crate::enrich::EnrichmentContext;
 use crate::error::Result;
 use crate::memvid::lifecycle::Memvid;
+use crate::triplet::{ExtractionStats, TripletExtractor, TripletSource};
 use crate::types::{
-    Cardinality, EntityKind, FrameId, MemoriesStats, MemoriesTrack, MemoryCard, MemoryCardId,
-    PredicateSchema, SchemaError, SchemaRegistry,
+    CardConflict, Cardinality, EntityKind, Frame, FrameId, MemoriesStats, MemoriesTrack,
+    MemoryCard, MemoryCardId, MemoryKind, MemoryQuery, MemoryQueryResult, PredicateSchema,
+    SchemaError, SchemaRegistry, SchemaViolation, SchemaViolationLog,
 };
 use serde::Serialize;
 
@@ -78,6 +83,7 @@ impl Memvid {
     pub fn put_memory_card(&mut self, card: MemoryCard) -> Result<MemoryCardId> {
         // Validate against schema
         if let Err(e) = self.validate_card(&card) {
+            self.record_schema_violation(&card, &e);
             if self.schema_strict {
                 return Err(crate::error::MemvidError::SchemaValidation {
                     reason: e.to_string(),
@@ -95,6 +101,7 @@ impl Memvid {
 
         self.dirty = true;
         let id = self.memories_track.add_card(card);
+        self.detect_card_conflicts(&[id]);
         Ok(id)
     }
 
@@ -118,6 +125,9 @@ impl Memvid {
     pub fn put_memory_cards(&mut self, cards: Vec<MemoryCard>) -> Result<Vec<MemoryCardId>> {
         // Validate all cards first
         let validation_errors = self.validate_cards(&cards);
+        for (i, e) in &validation_errors {
+            self.record_schema_violation(&cards[*i], e);
+        }
 
         if !validation_errors.is_empty() {
             if self.schema_strict {
@@ -151,9 +161,121 @@ impl Memvid {
 
         self.dirty = true;
         let ids = self.memories_track.add_cards(cards);
+        self.detect_card_conflicts(&ids);
         Ok(ids)
     }
 
+    /// Cards that failed schema validation, with counts per predicate and sample offenders.
+    ///
+    /// Validation happens when cards are added and when triplets are extracted; in strict
+    /// mode (see [`Memvid::set_schema_strict`]) the offending operation fails as well.
+    /// Persisted with the memories track.
+    #[must_use]
+    pub fn schema_violations(&self) -> &SchemaViolationLog {
+        self.memories_track.schema_violations()
+    }
+
+    /// Forget recorded schema violations, e.g. after fixing the extraction rules.
+    pub fn clear_schema_violations(&mut self) {
+        self.memories_track.schema_violations_mut().clear();
+        self.dirty = true;
+    }
+
+    fn record_schema_violation(&mut self, card: &MemoryCard, error: &SchemaError) {
+        tracing::debug!(
+            entity = %card.entity,
+            slot = %card.slot,
+            value = %card.value,
+            %error,
+            "memory card violates schema"
+        );
+        self.memories_track
+            .schema_violations_mut()
+            .record(SchemaViolation {
+                entity: card.entity.clone(),
+                slot: card.slot.clone(),
+                value: card.value.clone(),
+                reason: error.to_string(),
+                source_frame_id: card.source_frame_id,
+                engine: card.engine.clone(),
+                detected_at: crate::memvid::helpers::unix_now(),
+            });
+        self.dirty = true;
+    }
+
+    /// Drop extracted cards that fail schema validation, recording each as a violation.
+    /// Returns the valid cards and the number dropped.
+    pub(crate) fn drop_invalid_cards(
+        &mut self,
+        cards: Vec<MemoryCard>,
+    ) -> (Vec<MemoryCard>, usize) {
+        let mut valid = Vec::with_capacity(cards.len());
+        let mut rejected = 0;
+        for card in cards {
+            match self.validate_card(&card) {
+                Ok(()) => valid.push(card),
+                Err(e) => {
+                    self.record_schema_violation(&card, &e);
+                    rejected += 1;
+                }
+            }
+        }
+        (valid, rejected)
+    }
+
+    /// Fail enrichment that dropped `rejected` cards when strict schema mode is on.
+    ///
+    /// # Errors
+    /// Returns a schema validation error in strict mode if `rejected` is non-zero.
+    pub(crate) fn ensure_no_schema_rejections(&self, rejected: usize) -> Result<()> {
+        if rejected > 0 && self.schema_strict {
+            return Err(crate::error::MemvidError::SchemaValidation {
+                reason: format!(
+                    "{rejected} extracted cards failed validation; see schema_violations()"
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Link newly added cards to the earlier cards they contradict.
+    ///
+    /// Slots the schema registry declares `Cardinality::Multiple`, and events, only conflict on
+    /// a polarity flip; every other slot is treated as single-valued.
+    pub(crate) fn detect_card_conflicts(&mut self, ids: &[MemoryCardId]) {
+        let detected_at = crate::memvid::helpers::unix_now();
+        for &id in ids {
+            let Some(card) = self.memories_track.get_card(id) else {
+                continue;
+            };
+            let multi_valued = card.kind == MemoryKind::Event
+                || self
+                    .schema_registry
+                    .get(&card.slot)
+                    .is_some_and(|schema| schema.cardinality == Cardinality::Multiple);
+            let conflicts = self
+                .memories_track
+                .detect_conflicts(id, multi_valued, detected_at);
+            for conflict in conflicts {
+                tracing::debug!(
+                    entity = %conflict.entity,
+                    slot = %conflict.slot,
+                    older = conflict.older,
+                    newer = conflict.newer,
+                    "memory card superseded by contradicting card"
+                );
+            }
+        }
+    }
+
+    /// Contradictions between memory cards, oldest first, for review.
+    ///
+    /// Each entry names the card that was superseded and the card that replaced it.
+    #[must_use]
+    pub fn memories_conflicts(&self) -> &[CardConflict] {
+        self.memories_track.conflicts()
+    }
+
     /// Record that a frame was enriched by an engine.
     ///
     /// This is used to track which frames have been processed by which
@@ -242,6 +364,13 @@ impl Memvid {
         self.memories_track.get_at_time(entity, slot, timestamp)
     }
 
+    /// Query memory cards by entity, slot, kind, polarity, confidence, and time, one page
+    /// at a time; each hit lists the frames supporting it.
+    #[must_use]
+    pub fn memories_query(&self, query: MemoryQuery) -> MemoryQueryResult {
+        self.memories_track.query(&query)
+    }
+
     /// Get all memory cards for an entity.
     ///
     /// # Arguments
@@ -583,8 +712,6 @@ impl Memvid {
         &mut self,
         engine: &dyn crate::enrich::EnrichmentEngine,
     ) -> Result<(usize, usize)> {
-        use crate::enrich::EnrichmentContext;
-
         let unenriched = self.get_unenriched_frames(engine.kind(), engine.version());
         let mut frames_processed = 0;
         let mut total_cards = 0;
@@ -606,23 +733,7 @@ impl Memvid {
                 Err(_) => continue,
             };
 
-            // Create enrichment context
-            let uri = frame
-                .uri
-                .clone()
-                .unwrap_or_else(|| crate::default_uri(frame_id));
-            let metadata_json = frame
-                .metadata
-                .as_ref()
-                .and_then(|m| serde_json::to_string(m).ok());
-            let ctx = EnrichmentContext::new(
-                frame_id,
-                uri,
-                text,
-                frame.title.clone(),
-                frame.timestamp,
-                metadata_json,
-            );
+            let ctx = Self::enrichment_context(&frame, text);
 
             // Run enrichment
             let result = engine.enrich(&ctx);
@@ -649,6 +760,109 @@ impl Memvid {
 
         Ok((frames_processed, total_cards))
     }
+
+    /// Extract triplets from `frame_ids` with `extractor` and store them as memory cards.
+    ///
+    /// Triplets that fail schema validation are dropped and logged in
+    /// [`Memvid::schema_violations`], and cards whose entity, slot, and value are already
+    /// stored are skipped. Each frame's cards are recorded as enriched by the engine that
+    /// produced them.
+    ///
+    /// # Errors
+    /// Returns an error if a frame's content cannot be read, or in strict schema mode if
+    /// any triplet fails validation; nothing is stored then.
+    pub fn extract_triplets(
+        &mut self,
+        extractor: &TripletExtractor,
+        frame_ids: &[FrameId],
+    ) -> Result<ExtractionStats> {
+        let mut frames = Vec::with_capacity(frame_ids.len());
+        for &frame_id in frame_ids {
+            let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+                .cloned()
+            else {
+                continue;
+            };
+            let text = self.frame_content(&frame)?;
+            frames.push((frame, text));
+        }
+        let sources: Vec<TripletSource<'_>> = frames
+            .iter()
+            .map(|(frame, text)| TripletSource {
+                frame_id: frame.id,
+                text,
+                uri: frame.uri.as_deref(),
+                title: frame.title.as_deref(),
+                timestamp: frame.timestamp,
+            })
+            .collect();
+        let (cards, mut stats) = extractor.extract_batch(&sources);
+
+        let (cards, rejected) = self.drop_invalid_cards(cards);
+        self.ensure_no_schema_rejections(rejected)?;
+        stats.schema_rejected = rejected;
+
+        let mut fresh: Vec<MemoryCard> = Vec::new();
+        for card in cards {
+            let known = self
+                .memories_track
+                .get_cards(&card.entity, &card.slot)
+                .iter()
+                .any(|existing| existing.value == card.value);
+            let repeated = fresh.iter().any(|other| {
+                other.entity == card.entity && other.slot == card.slot && other.value == card.value
+            });
+            if known || repeated {
+                stats.duplicates_removed += 1;
+                continue;
+            }
+            fresh.push(card);
+        }
+        stats.total_stored = fresh.len();
+
+        // Group by frame and engine so each stamp lists the cards that run produced.
+        let mut groups: BTreeMap<(FrameId, String, String), Vec<MemoryCard>> = BTreeMap::new();
+        for card in fresh {
+            groups
+                .entry((
+                    card.source_frame_id,
+                    card.engine.clone(),
+                    card.engine_version.clone(),
+                ))
+                .or_default()
+                .push(card);
+        }
+        for ((frame_id, engine, version), cards) in groups {
+            let card_ids = self.memories_track.add_cards(cards);
+            self.detect_card_conflicts(&card_ids);
+            self.memories_track
+                .record_enrichment(frame_id, &engine, &version, card_ids);
+        }
+        self.dirty = true;
+        Ok(stats)
+    }
+
+    /// Build the context an enrichment engine sees for `frame` with content `text`.
+    pub(crate) fn enrichment_context(frame: &Frame, text: String) -> EnrichmentContext {
+        let uri = frame
+            .uri
+            .clone()
+            .unwrap_or_else(|| crate::default_uri(frame.id));
+        let metadata_json = frame
+            .metadata
+            .as_ref()
+            .and_then(|m| serde_json::to_string(m).ok());
+        EnrichmentContext::new(
+            frame.id,
+            uri,
+            text,
+            frame.title.clone(),
+            frame.timestamp,
+            metadata_json,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -892,4 +1106,155 @@ mod tests {
 
         assert!(memvid.put_memory_card(invalid_card).is_err());
     }
+
+    #[test]
+    fn test_memories_conflicts_respect_cardinality() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        std::fs::remove_file(path).ok();
+
+        let mut memvid = Memvid::create(path).unwrap();
+        let card = |slot: &str, value: &str| {
+            MemoryCardBuilder::new()
+                .fact()
+                .entity("user")
+                .slot(slot)
+                .value(value)
+                .source(0, None)
+                .engine("test", "1.0.0")
+                .build(0)
+                .unwrap()
+        };
+
+        let lisbon = memvid.put_memory_card(card("city", "Lisbon")).unwrap();
+        let porto = memvid.put_memory_card(card("city", "Porto")).unwrap();
+        // `hobby` is a built-in multi-valued predicate.
+        memvid
+            .put_memory_cards(vec![card("hobby", "chess"), card("hobby", "climbing")])
+            .unwrap();
+
+        let conflicts = memvid.memories_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!((conflicts[0].older, conflicts[0].newer), (lisbon, porto));
+        assert!(memvid.memories().get_card(lisbon).unwrap().is_superseded());
+    }
+    struct CannedLlm(&'static str);
+
+    impl crate::types::LlmBackend for CannedLlm {
+        fn kind(&self) -> &'static str {
+            "canned"
+        }
+        fn context_window(&self) -> usize {
+            4096
+        }
+        fn complete(
+            &self,
+            _prompt: &str,
+            _params: &crate::types::LlmParams,
+        ) -> Result<crate::types::LlmCompletion> {
+            Ok(crate::types::LlmCompletion {
+                text: self.0.to_string(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn test_extract_triplets_with_llm_validates_and_dedups() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        std::fs::remove_file(path).ok();
+
+        let mut memvid = Memvid::create(path).unwrap();
+        memvid
+            .put_bytes(b"I work at Anthropic. On weekends I go climbing.")
+            .unwrap();
+        memvid.commit().unwrap();
+        let frame_id = memvid.toc.frames[0].id;
+        let cards_before = memvid.memories().card_count();
+
+        let llm = CannedLlm(concat!(
+            "{\"passage\": 1, \"subject\": \"user\", \"predicate\": \"employer\", \"object\": \"Anthropic\"}\n",
+            "{\"passage\": 1, \"subject\": \"user\", \"predicate\": \"Age\", \"object\": \"thirty\"}\n",
+            "{\"passage\": 1, \"subject\": \"user\", \"predicate\": \"hobby\", \"object\": \"climbing\", \"confidence\": 0.8}\n",
+        ));
+        let extractor = TripletExtractor::llm(std::sync::Arc::new(llm));
+        let stats = memvid.extract_triplets(&extractor, &[frame_id]).unwrap();
+
+        assert!(!stats.llm_fallback);
+        assert_eq!(stats.llm_extracted, 3);
+        assert_eq!(stats.schema_rejected, 1, "age must be a number");
+        assert_eq!(
+            stats.total_stored, 1,
+            "employer was already extracted on put"
+        );
+        assert_eq!(memvid.memories().card_count(), cards_before + 1);
+        let hobby = memvid.get_current_memory("user", "hobby").unwrap();
+        assert_eq!(hobby.engine, "llm:canned");
+        assert!(!memvid.is_frame_enriched(frame_id, "llm:canned", "0.0.0"));
+        assert!(memvid.is_frame_enriched(frame_id, "llm:canned", "1.0.0"));
+
+        // Running again finds nothing new.
+        let stats = memvid.extract_triplets(&extractor, &[frame_id]).unwrap();
+        assert_eq!(stats.total_stored, 0);
+    }
+    #[test]
+    fn test_schema_violations_are_logged_and_persisted() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        std::fs::remove_file(path).ok();
+
+        {
+            let mut memvid = Memvid::create(path).unwrap();
+            for i in 0..7 {
+                let card = MemoryCardBuilder::new()
+                    .fact()
+                    .entity(format!("person-{i}"))
+                    .slot("age")
+                    .value("thirty")
+                    .source(0, None)
+                    .engine("rules", "1.0.0")
+                    .build(0)
+                    .unwrap();
+                // Non-strict mode keeps the card but logs the violation.
+                memvid.put_memory_card(card).unwrap();
+            }
+            memvid.commit().unwrap();
+        }
+
+        let mut memvid = Memvid::open(path).unwrap();
+        let violations = memvid.schema_violations();
+        assert_eq!(violations.total(), 7);
+        let age = &violations.predicates["age"];
+        assert_eq!(age.count, 7);
+        assert_eq!(age.samples.len(), crate::types::MAX_VIOLATION_SAMPLES);
+        assert_eq!(age.samples[0].entity, "person-0");
+        assert!(age.samples[0].reason.contains("expected"));
+
+        memvid.clear_schema_violations();
+        assert!(memvid.schema_violations().is_empty());
+    }
+
+    #[test]
+    fn test_strict_schema_fails_triplet_extraction() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path();
+        std::fs::remove_file(path).ok();
+
+        let mut memvid = Memvid::create(path).unwrap();
+        memvid.put_bytes(b"The weather is nice today.").unwrap();
+        memvid.commit().unwrap();
+        let frame_id = memvid.toc.frames[0].id;
+        let cards_before = memvid.memories().card_count();
+        memvid.set_schema_strict(true);
+
+        let llm = CannedLlm(concat!(
+            "{\"passage\": 1, \"subject\": \"user\", \"predicate\": \"hobby\", \"object\": \"sailing\"}\n",
+            "{\"passage\": 1, \"subject\": \"user\", \"predicate\": \"age\", \"object\": \"old\"}\n",
+        ));
+        let extractor = TripletExtractor::llm(std::sync::Arc::new(llm));
+        assert!(memvid.extract_triplets(&extractor, &[frame_id]).is_err());
+        assert_eq!(memvid.memories().card_count(), cards_before);
+        assert_eq!(memvid.schema_violations().predicates["age"].count, 1);
+    }
 }
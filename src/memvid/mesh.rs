@@ -10,6 +10,9 @@ use crate::types::{
     SearchHitEntity,
 };
 
+/// Confidence recorded for diarized speakers, which are clustered rather than recognised.
+const SPEAKER_CONFIDENCE: f32 = 0.5;
+
 impl Memvid {
     /// Get an immutable reference to the Logic-Mesh.
     ///
@@ -61,6 +64,30 @@ impl Memvid {
         }
     }
 
+    /// Record the diarized speakers of a frame as person entities.
+    ///
+    /// Speakers merge with existing nodes of the same name, so every frame a speaker
+    /// appears in is reachable from one node.
+    pub fn add_speaker_entities(&mut self, frame_id: FrameId, speakers: &[String]) {
+        let nodes = speakers
+            .iter()
+            .map(|speaker| speaker.trim())
+            .filter(|speaker| !speaker.is_empty())
+            .map(|speaker| {
+                MeshNode::new(
+                    speaker.to_lowercase(),
+                    speaker.to_string(),
+                    EntityKind::Person,
+                    SPEAKER_CONFIDENCE,
+                    frame_id,
+                    0,
+                    0,
+                )
+            })
+            .collect();
+        self.add_mesh_nodes(nodes);
+    }
+
     /// Add a mesh edge (relationship) to the Logic-Mesh.
     ///
     /// The edge is deduplicated by (from, to, `link_type`).
@@ -97,11 +124,16 @@ impl Memvid {
     /// A list of entities found by traversing the relationships.
     #[must_use]
     pub fn follow(&self, start: &str, link: &str, hops: usize) -> Vec<FollowResult> {
-        self.logic_mesh.follow(start, link, hops)
+        match self.find_entity(start) {
+            Some(node) => self.logic_mesh.follow(&node.canonical_name, link, hops),
+            None => Vec::new(),
+        }
     }
 
     /// Find an entity node by name.
     ///
+    /// Names merged away by [`Memvid::resolve_entities`] resolve to the surviving node.
+    ///
     /// # Arguments
     /// * `name` - The entity name to search for (case-insensitive)
     ///
@@ -109,7 +141,10 @@ impl Memvid {
     /// The matching node if found.
     #[must_use]
     pub fn find_entity(&self, name: &str) -> Option<&MeshNode> {
-        self.logic_mesh.find_node(name)
+        self.logic_mesh.find_node(name).or_else(|| {
+            let survivor = self.entity_merge_log().ok()?.resolve_alias(name)?;
+            self.logic_mesh.find_node_by_id(survivor)
+        })
     }
 
     /// Get all entities mentioned in a specific frame.
@@ -0,0 +1,213 @@
+//! Typed metadata filters for `Memvid`.
+//!
+//! The [`MetaSchema`] lives in the TOC. Indexes over the keys marked `indexed` are derived
+//! from frame metadata, so like the geo index they are built on first use and extended with
+//! frames committed since; unindexed keys are answered by scanning the frames.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{
+    FrameId, FrameStatus, META_SCHEMA_EXTENSION, MetaFilter, MetaIndex, MetaSchema, MetaType,
+    MetaValue,
+};
+
+/// Per-key indexes plus the number of TOC frames each has seen.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MetaTrack {
+    indexes: HashMap<String, (MetaIndex, usize)>,
+}
+
+impl Memvid {
+    /// Declared metadata keys of this memory.
+    pub fn meta_schema(&self) -> Result<MetaSchema> {
+        Ok(self
+            .toc
+            .extension::<MetaSchema>(META_SCHEMA_EXTENSION)?
+            .unwrap_or_default())
+    }
+
+    /// Declare `key` as `ty` and maintain a sorted index over it, so range filters on the key
+    /// no longer scan every frame.
+    pub fn index_meta_key(&mut self, key: &str, ty: MetaType) -> Result<()> {
+        self.ensure_writable()?;
+        let mut schema = self.meta_schema()?;
+        schema
+            .declare(key, ty)
+            .map_err(|declared| type_conflict(key, declared, ty))?;
+        if let Some(field) = schema.fields.get_mut(key) {
+            field.indexed = true;
+        }
+        self.toc.set_extension(META_SCHEMA_EXTENSION, &schema)?;
+        self.meta_track.indexes.remove(key);
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Typed value of `key` on a frame, read as the key's declared type when it has one.
+    pub fn frame_meta(&self, frame_id: FrameId, key: &str) -> Result<Option<MetaValue>> {
+        let frame = self.frame_by_id(frame_id)?;
+        let Some(raw) = frame.extra_metadata.get(key) else {
+            return Ok(None);
+        };
+        Ok(Some(match self.meta_schema()?.ty(key) {
+            Some(ty) => MetaValue::parse(ty, raw).unwrap_or_else(|| MetaValue::Text(raw.clone())),
+            None => MetaValue::infer(raw),
+        }))
+    }
+
+    /// Active frames satisfying every filter, in ascending id order.
+    pub fn frame_ids_matching_meta(&mut self, filters: &[MetaFilter]) -> Result<Vec<FrameId>> {
+        let schema = self.meta_schema()?;
+        let seeded = filters
+            .iter()
+            .filter(|filter| schema.is_indexed(&filter.key))
+            .find_map(|filter| self.query_meta_index(&schema, filter));
+
+        let frames = &self.toc.frames;
+        let matches = |frame_id: &FrameId| {
+            usize::try_from(*frame_id)
+                .ok()
+                .and_then(|index| frames.get(index))
+                .is_some_and(|frame| {
+                    frame.status == FrameStatus::Active
+                        && filters
+                            .iter()
+                            .all(|filter| filter.matches_frame(frame, &schema))
+                })
+        };
+        Ok(match seeded {
+            // Re-check seeded ids: status and metadata can change in place.
+            Some(ids) => ids.into_iter().filter(matches).collect(),
+            None => frames
+                .iter()
+                .map(|frame| frame.id)
+                .filter(matches)
+                .collect(),
+        })
+    }
+
+    fn query_meta_index(
+        &mut self,
+        schema: &MetaSchema,
+        filter: &MetaFilter,
+    ) -> Option<Vec<FrameId>> {
+        let ty = schema.ty(&filter.key)?;
+        let (index, scanned) = self
+            .meta_track
+            .indexes
+            .entry(filter.key.clone())
+            .or_insert_with(|| (MetaIndex::new(ty), 0));
+        for frame in self.toc.frames.iter().skip(*scanned) {
+            if let Some(raw) = frame.extra_metadata.get(&filter.key) {
+                index.insert(frame.id, raw);
+            }
+        }
+        *scanned = self.toc.frames.len();
+        index.query(filter)
+    }
+
+    /// Encode `typed` into `extra_metadata`, declaring new keys in the schema. Fails when a
+    /// value's type conflicts with its key's declared type.
+    pub(crate) fn apply_typed_metadata(
+        &mut self,
+        typed: BTreeMap<String, MetaValue>,
+        extra_metadata: &mut BTreeMap<String, String>,
+    ) -> Result<()> {
+        if typed.is_empty() {
+            return Ok(());
+        }
+        let mut schema = self.meta_schema()?;
+        let mut changed = false;
+        for (key, value) in typed {
+            changed |= schema
+                .declare(&key, value.ty())
+                .map_err(|declared| type_conflict(&key, declared, value.ty()))?;
+            extra_metadata.insert(key, value.encode());
+        }
+        if changed {
+            self.toc.set_extension(META_SCHEMA_EXTENSION, &schema)?;
+            self.dirty = true;
+        }
+        Ok(())
+    }
+}
+
+fn type_conflict(key: &str, declared: MetaType, actual: MetaType) -> MemvidError {
+    MemvidError::SchemaValidation {
+        reason: format!("metadata key '{key}' is declared as {declared}, got {actual}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MetaOp, PutOptions};
+
+    #[test]
+    fn typed_metadata_filters_through_index_and_scan() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("meta.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for (text, priority, status) in [
+            ("rotate keys", 5, "open"),
+            ("update docs", 1, "open"),
+            ("fix login", 10, "done"),
+            ("triage inbox", 3, "open"),
+        ] {
+            let options = PutOptions::builder()
+                .meta("priority", priority)
+                .meta("status", status)
+                .build();
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let err = mem
+            .put_bytes_with_options(
+                b"oops",
+                PutOptions::builder().meta("priority", "high").build(),
+            )
+            .expect_err("type conflict");
+        assert!(matches!(err, MemvidError::SchemaValidation { .. }));
+
+        let filters = [
+            MetaFilter::new("priority", MetaOp::Ge, 3),
+            MetaFilter::new("status", MetaOp::Ne, "done"),
+        ];
+        assert_eq!(mem.frame_ids_matching_meta(&filters).expect("scan"), [0, 3]);
+
+        mem.index_meta_key("priority", MetaType::Int)
+            .expect("index");
+        assert_eq!(
+            mem.frame_ids_matching_meta(&filters).expect("index"),
+            [0, 3]
+        );
+        mem.put_bytes_with_options(
+            b"page oncall",
+            PutOptions::builder().meta("priority", 7).build(),
+        )
+        .expect("put");
+        mem.commit().expect("commit");
+        assert_eq!(
+            mem.frame_ids_matching_meta(&filters[..1])
+                .expect("catch up"),
+            [0, 2, 3, 4]
+        );
+        assert_eq!(
+            mem.frame_meta(2, "priority").expect("meta"),
+            Some(MetaValue::Int(10))
+        );
+
+        drop(mem);
+        let reopened = Memvid::open(&path).expect("open");
+        assert!(
+            reopened
+                .meta_schema()
+                .expect("schema")
+                .is_indexed("priority")
+        );
+    }
+}
@@ -1,38 +1,93 @@
 //! Core `Memvid` type orchestrating `.mv2` lifecycle and mutations.
 
+pub mod access_stats;
 mod acl;
+pub mod analyzer;
+pub mod archive;
 pub mod ask;
+pub mod audio;
 pub mod audit;
+pub mod audit_chain;
+pub mod backfill;
+pub mod blob_extents;
 #[cfg(feature = "parallel_segments")]
 pub mod builder;
 pub mod chunks;
+pub mod cluster;
+pub mod collection;
+pub mod commit_log;
+pub mod compression;
+pub mod context_window;
+pub mod conversation;
+pub mod diff;
 pub mod doctor;
+pub mod duplicates;
+pub mod embedding_migration;
 pub mod enrichment;
+pub mod entity_resolution;
+pub mod erasure;
+pub mod external_index;
+pub mod file_config;
 pub mod frame;
+pub mod frame_export;
+pub mod geo;
+pub mod git;
+pub mod graph_export;
 mod helpers;
+pub mod hooks;
+pub mod importance;
+pub mod ingest_dir;
+pub mod ingest_profile;
+pub mod jsonl;
+pub mod late_interaction;
+pub mod legal_hold;
 pub mod lifecycle;
+pub mod links;
 pub mod maintenance;
 pub mod memory;
 pub mod mesh;
+pub mod meta;
 pub mod mutation;
+mod pipeline;
 #[cfg(feature = "parallel_segments")]
 pub mod planner;
+pub mod quarantine;
+pub mod remote;
 #[cfg(feature = "replay")]
 pub mod replay_ops;
+pub mod replication;
+pub mod rerank;
+pub mod salvage;
 pub mod search;
 mod segments;
+pub mod similar;
 pub mod sketch;
+pub mod snapshot;
+pub mod sparse;
+#[cfg(feature = "spelling")]
+pub mod spelling;
+pub mod sql;
+pub mod suggest;
+pub mod summary;
+pub mod table_query;
+pub mod tags;
 pub mod ticket;
 pub mod timeline;
+pub mod timezone;
+pub mod union;
+pub mod video;
 #[cfg(feature = "parallel_segments")]
 pub mod workers;
 
 #[cfg(feature = "parallel_segments")]
 pub use builder::{BuildOpts, ParallelInput, ParallelPayload};
+pub use commit_log::CommitSubscription;
 pub use enrichment::{
-    EnrichmentHandle, EnrichmentStats, start_enrichment_worker,
+    EnrichmentHandle, EnrichmentProgress, EnrichmentStats, start_enrichment_worker,
     start_enrichment_worker_with_embeddings,
 };
 pub use frame::BlobReader;
 pub use lifecycle::{LockSettings, Memvid, OpenReadOptions};
+pub use remote::RemoteMemvid;
 pub use sketch::{SketchCandidate, SketchSearchOptions, SketchSearchStats};
+pub use union::MemvidUnion;
@@ -7,10 +7,9 @@
 //! The long-term structure will split into ingestion/chunking/WAL staging modules. For now
 //! everything lives here, grouped by section so the pipeline is easy to scan.
 
-use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::{File, OpenOptions};
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::io::{Seek, SeekFrom, Write};
 use std::path::Path;
 use std::sync::OnceLock;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -24,7 +23,6 @@ use once_cell::sync::OnceCell;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use zstd;
 
 use atomic_write_file::AtomicWriteFile;
 
@@ -39,23 +37,31 @@ use super::{
 #[cfg(feature = "temporal_track")]
 use crate::TemporalTrackManifest;
 use crate::analysis::auto_tag::AutoTagger;
-use crate::constants::{WAL_SIZE_LARGE, WAL_SIZE_MEDIUM};
+use crate::analysis::language::{LANGUAGE_KEY, detect_language};
+use crate::analysis::timezone::{TEMPORAL_TZ_KEY, timezone_offset};
+use crate::constants::{WAL_EXTENT_VERSION, WAL_SIZE_LARGE, WAL_SIZE_MEDIUM};
 use crate::footer::CommitFooter;
 use crate::io::wal::{EmbeddedWal, WalRecord};
 use crate::memvid::chunks::{plan_document_chunks, plan_text_chunks};
-use crate::memvid::lifecycle::{Memvid, prepare_toc_bytes};
+use crate::memvid::lifecycle::{Memvid, prepare_toc_bytes, referenced_byte_ranges};
 use crate::reader::{
     DocumentFormat, DocumentReader, PassthroughReader, ReaderDiagnostics, ReaderHint, ReaderOutput,
-    ReaderRegistry,
+    ReaderRegistry, locate_pdf_texts,
 };
 #[cfg(feature = "lex")]
 use crate::search::{EmbeddedLexSegment, LexWalBatch, TantivySnapshot};
 use crate::triplet::TripletExtractor;
 #[cfg(feature = "lex")]
 use crate::types::TantivySegmentDescriptor;
+use crate::types::blob_extents::stored_in_extents;
+use crate::types::seekable::{SEEKABLE_BLOCK_SIZE, compress_seekable};
+use crate::types::sparse::{SPARSE_TRACK_EXTENSION, sparse_track_range};
+use crate::types::token_track::{TOKEN_TRACK_EXTENSION, token_track_range};
 use crate::types::{
-    CanonicalEncoding, DocMetadata, Frame, FrameId, FrameRole, FrameStatus, PutManyOpts,
-    PutOptions, SegmentCommon, TextChunkManifest, Tier,
+    BLOB_EXTENT_EXTENSION, CODE_SYMBOLS_KEY, CanonicalEncoding, CodeSymbol, CommitMetadata,
+    CompressionCodec, DocMetadata, Frame, FrameId, FrameRole, FrameStatus, PDF_PAGE_KEY,
+    PDF_SPANS_KEY, PdfTextSpan, PutEvent, PutManyOpts, PutOptions, SegmentCommon,
+    TextChunkManifest, Tier,
 };
 #[cfg(feature = "parallel_segments")]
 use crate::types::{IndexSegmentRef, SegmentKind, SegmentSpan, SegmentStats};
@@ -67,17 +73,13 @@ use crate::{
 };
 use crate::{
     DEFAULT_SEARCH_TEXT_LIMIT, ExtractedDocument, MemvidError, Result, TimeIndexEntry,
-    TimeIndexManifest, VecIndexManifest, normalize_text, time_index_append, wal_config,
+    TimeIndexManifest, VecIndexManifest, metrics, normalize_text, time_index_append, wal_config,
 };
 #[cfg(feature = "temporal_track")]
 use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 
 const MAGIC_SNIFF_BYTES: usize = 16;
 const WAL_ENTRY_HEADER_SIZE: u64 = 48;
-const WAL_SHIFT_BUFFER_SIZE: usize = 8 * 1024 * 1024;
-
-#[cfg(feature = "temporal_track")]
-const DEFAULT_TEMPORAL_TZ: &str = "America/Chicago";
 
 #[cfg(feature = "temporal_track")]
 const STATIC_TEMPORAL_PHRASES: &[&str] = &[
@@ -120,6 +122,10 @@ const STATIC_TEMPORAL_PHRASES: &[&str] = &[
     "sunday",
 ];
 
+/// Recurring phrases ("every other friday", "quarterly") recognised at ingest.
+#[cfg(feature = "temporal_track")]
+const RECURRING_TEMPORAL_PATTERN: &str = r"\b(?:daily|weekly|biweekly|fortnightly|monthly|quarterly|yearly|annually|every (?:other |[[:word:]]+ )?(?:day|week|month|quarter|year)s?|every (?:other )?(?:mon|tues|wednes|thurs|fri|satur|sun|week)days?(?:(?:, *| +)(?:and +)?(?:mon|tues|wednes|thurs|fri|satur|sun)days?)*)\b";
+
 struct CommitStaging {
     atomic: AtomicWriteFile,
 }
@@ -132,7 +138,7 @@ impl CommitStaging {
         Ok(Self { atomic })
     }
 
-    fn copy_from(&mut self, source: &File) -> Result<()> {
+    fn copy_from(&mut self, source: &File, sync: bool) -> Result<()> {
         let mut reader = source.try_clone()?;
         reader.seek(SeekFrom::Start(0))?;
 
@@ -141,7 +147,9 @@ impl CommitStaging {
         writer.seek(SeekFrom::Start(0))?;
         std::io::copy(&mut reader, writer)?;
         writer.flush()?;
-        writer.sync_all()?;
+        if sync {
+            writer.sync_all()?;
+        }
         Ok(())
     }
 
@@ -161,6 +169,7 @@ impl CommitStaging {
 #[derive(Debug, Default)]
 struct IngestionDelta {
     inserted_frames: Vec<FrameId>,
+    tombstoned_frames: Vec<FrameId>,
     inserted_embeddings: Vec<(FrameId, Vec<f32>)>,
     inserted_time_entries: Vec<TimeIndexEntry>,
     mutated_frames: bool,
@@ -191,6 +200,18 @@ impl IngestionDelta {
 pub enum CommitMode {
     Full,
     Incremental,
+    /// Group commit: the first coalesced commit opens a window and later ones inside it are
+    /// deferred, so a burst of small puts pays for one staging copy, Tantivy commit, and fsync.
+    /// The first coalesced commit after `max_delay`, any other commit, `Memvid::flush`, or
+    /// dropping the handle flushes the window; `max_delay` is only checked when a commit is
+    /// made, not by a timer. Deferred puts stay in the WAL and are invisible to search until
+    /// then. The commit made on drop cannot report an error, so call `flush` before dropping
+    /// a handle whose window must land: if that last commit fails, the deferred puts are only
+    /// in the WAL and come back when the file is next opened for writing, or are lost if the
+    /// WAL cannot be replayed.
+    Coalesced {
+        max_delay: Duration,
+    },
 }
 
 impl Default for CommitMode {
@@ -199,10 +220,48 @@ impl Default for CommitMode {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+/// How aggressively writes are forced to stable storage.
+///
+/// Whatever the profile, a commit that goes through a staged copy ends by atomically
+/// replacing the file, and that replace fsyncs the new file and its directory; the profiles
+/// only decide which syncs happen before it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DurabilityProfile {
+    /// fsync after every WAL append, staging copy, and footer write.
+    #[default]
+    Paranoid,
+    /// One fsync of the memory file per commit; appends since the last commit may be lost on
+    /// power failure.
+    Balanced,
+    /// Appends and commits issue no fsync of the memory file or its WAL: whatever is written in
+    /// place (WAL records, checkpoints, `finalize_indexes`) reaches the disk when the operating
+    /// system writes it back. The atomic replace of a staged commit still syncs, as do
+    /// `vacuum` and WAL recovery on open.
+    Fast,
+}
+
+impl DurabilityProfile {
+    fn syncs_every_write(self) -> bool {
+        self == Self::Paranoid
+    }
+
+    fn syncs_commits(self) -> bool {
+        self != Self::Fast
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct CommitOptions {
     pub mode: CommitMode,
     pub background: bool,
+    /// Overrides the handle's durability profile (see `LockSettings::durability`) for this commit.
+    pub durability: Option<DurabilityProfile>,
+    /// Overrides the handle's author (see `Memvid::set_commit_author`) for this commit.
+    pub author: Option<String>,
+    /// Overrides the handle's agent id for this commit.
+    pub agent_id: Option<String>,
+    /// Why the commit was made, recorded in `Memvid::commit_history`.
+    pub reason: Option<String>,
 }
 
 impl CommitOptions {
@@ -210,7 +269,7 @@ impl CommitOptions {
     pub fn new(mode: CommitMode) -> Self {
         Self {
             mode,
-            background: false,
+            ..Self::default()
         }
     }
 
@@ -219,6 +278,30 @@ impl CommitOptions {
         self.background = background;
         self
     }
+
+    #[must_use]
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    #[must_use]
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    #[must_use]
+    pub fn durability(mut self, durability: DurabilityProfile) -> Self {
+        self.durability = Some(durability);
+        self
+    }
 }
 
 fn default_reader_registry() -> &'static ReaderRegistry {
@@ -265,6 +348,12 @@ fn infer_document_format(
             "application/vnd.openxmlformats-officedocument.presentationml.presentation" => {
                 Some(DocumentFormat::Pptx)
             }
+            "application/x-ndjson" | "application/jsonl" | "application/x-jsonlines" => {
+                Some(DocumentFormat::Jsonl)
+            }
+            "application/zip" | "application/x-zip-compressed" | "application/x-tar" => {
+                Some(DocumentFormat::Archive)
+            }
             other if other.starts_with("text/") => Some(DocumentFormat::PlainText),
             _ => None,
         };
@@ -288,6 +377,9 @@ fn infer_format_from_extension(uri: Option<&str>) -> Option<DocumentFormat> {
         "xlsx" => Some(DocumentFormat::Xlsx),
         "xls" => Some(DocumentFormat::Xls),
         "pptx" => Some(DocumentFormat::Pptx),
+        "jsonl" | "ndjson" => Some(DocumentFormat::Jsonl),
+        "zip" | "tar" | "tgz" => Some(DocumentFormat::Archive),
+        "gz" if uri.to_ascii_lowercase().ends_with(".tar.gz") => Some(DocumentFormat::Archive),
         "txt" | "text" | "log" | "cfg" | "ini" | "json" | "yaml" | "yml" | "toml" | "csv"
         | "tsv" | "rs" | "py" | "js" | "ts" | "tsx" | "jsx" | "c" | "h" | "cpp" | "hpp" | "go"
         | "rb" | "php" | "css" | "scss" | "sh" | "bash" | "swift" | "kt" | "java" | "scala"
@@ -367,6 +459,64 @@ fn extract_via_registry(
     Ok(finalize_reader_output(output, start))
 }
 
+/// Extract text and metadata for a put, honouring the instant-index time budget. Returns the
+/// document and whether the extraction was cut short (a skim).
+pub(crate) fn extract_for_put(
+    bytes: &[u8],
+    mime_hint: Option<&str>,
+    options: &PutOptions,
+) -> Result<(ExtractedDocument, bool)> {
+    let uri_hint = options.uri.as_deref();
+
+    // Use time-budgeted extraction for instant indexing with a budget
+    if !(options.instant_index && options.extraction_budget_ms > 0) {
+        return Ok((extract_via_registry(bytes, mime_hint, uri_hint)?, false));
+    }
+
+    // Time-budgeted extraction for sub-second ingestion
+    let budget = crate::extract_budgeted::ExtractionBudget::with_ms(options.extraction_budget_ms);
+    let start = Instant::now();
+    match crate::extract_budgeted::extract_with_budget(bytes, mime_hint, uri_hint, budget) {
+        Ok(result) => {
+            metrics::duration(
+                metrics::EXTRACT_DURATION,
+                start.elapsed(),
+                &[("reader", "budgeted")],
+            );
+            let is_skim = result.is_skim();
+            if is_skim {
+                tracing::debug!(
+                    coverage = result.coverage,
+                    elapsed_ms = result.elapsed_ms,
+                    sections = %format!("{}/{}", result.sections_extracted, result.sections_total),
+                    "time-budgeted extraction (skim)"
+                );
+            }
+            // Convert BudgetedExtractionResult to ExtractedDocument
+            let doc = ExtractedDocument {
+                text: if result.text.is_empty() {
+                    None
+                } else {
+                    Some(result.text)
+                },
+                metadata: serde_json::json!({
+                    "skim": is_skim,
+                    "coverage": result.coverage,
+                    "sections_extracted": result.sections_extracted,
+                    "sections_total": result.sections_total,
+                }),
+                mime_type: mime_hint.map(std::string::ToString::to_string),
+            };
+            Ok((doc, is_skim))
+        }
+        Err(err) => {
+            // Fall back to full extraction on budgeted extraction error
+            tracing::warn!(?err, "budgeted extraction failed, trying full extraction");
+            Ok((extract_via_registry(bytes, mime_hint, uri_hint)?, false))
+        }
+    }
+}
+
 fn finalize_reader_output(output: ReaderOutput, start: Instant) -> ExtractedDocument {
     let elapsed = start.elapsed();
     let ReaderOutput {
@@ -384,6 +534,10 @@ fn log_reader_result(reader: &str, diagnostics: &ReaderDiagnostics, elapsed: Dur
         .unwrap_or(elapsed.as_millis().try_into().unwrap_or(u64::MAX));
     let warnings = diagnostics.warnings.len();
     let pages = diagnostics.pages_processed;
+    metrics::duration(metrics::EXTRACT_DURATION, elapsed, &[("reader", reader)]);
+    if warnings > 0 || diagnostics.fallback {
+        metrics::counter(metrics::EXTRACT_WARNINGS, 1, &[("reader", reader)]);
+    }
 
     if warnings > 0 || diagnostics.fallback {
         tracing::warn!(
@@ -412,17 +566,17 @@ fn log_reader_result(reader: &str, diagnostics: &ReaderDiagnostics, elapsed: Dur
 impl Memvid {
     // -- Public ingestion entrypoints ---------------------------------------------------------
 
-    fn with_staging_lock<F>(&mut self, op: F) -> Result<()>
+    pub(crate) fn with_staging_lock<F>(&mut self, op: F) -> Result<()>
     where
         F: FnOnce(&mut Self) -> Result<()>,
     {
-        self.file.sync_all()?;
+        self.sync_write()?;
         let mut staging = CommitStaging::prepare(self.path())?;
-        staging.copy_from(&self.file)?;
+        staging.copy_from(&self.file, self.durability().syncs_every_write())?;
 
         let staging_handle = staging.clone_file()?;
         let new_wal = EmbeddedWal::open(&staging_handle, &self.header)?;
-        let original_file = std::mem::replace(&mut self.file, staging_handle);
+        let original_file = self.file.replace(staging_handle);
         let original_wal = std::mem::replace(&mut self.wal, new_wal);
         let original_header = self.header.clone();
         let original_toc = self.toc.clone();
@@ -439,21 +593,25 @@ impl Memvid {
 
         match op(self) {
             Ok(()) => {
-                self.file.sync_all()?;
+                self.sync_commit()?;
                 match staging.commit() {
                     Ok(()) => {
                         drop(original_file.take());
                         drop(original_wal.take());
-                        self.file = OpenOptions::new()
-                            .read(true)
-                            .write(true)
-                            .open(&destination_path)?;
+                        self.file.replace(
+                            OpenOptions::new()
+                                .read(true)
+                                .write(true)
+                                .open(&destination_path)?,
+                        );
                         self.wal = EmbeddedWal::open(&self.file, &self.header)?;
+                        self.publish_commit_event();
                         Ok(())
                     }
                     Err(commit_err) => {
+                        self.pending_commit_event = None;
                         if let Some(file) = original_file.take() {
-                            self.file = file;
+                            self.file.replace(file);
                         }
                         if let Some(wal) = original_wal.take() {
                             self.wal = wal;
@@ -474,8 +632,9 @@ impl Memvid {
             }
             Err(err) => {
                 let _ = staging.discard();
+                self.pending_commit_event = None;
                 if let Some(file) = original_file.take() {
-                    self.file = file;
+                    self.file.replace(file);
                 }
                 if let Some(wal) = original_wal.take() {
                     self.wal = wal;
@@ -567,7 +726,30 @@ impl Memvid {
         self.cached_payload_end
     }
 
+    fn durability(&self) -> DurabilityProfile {
+        self.lock_settings.durability
+    }
+
+    /// fsync after an intermediate write; only `DurabilityProfile::Paranoid` pays for these.
+    fn sync_write(&self) -> Result<()> {
+        if self.durability().syncs_every_write() {
+            self.file.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// fsync that makes a commit durable; skipped by `DurabilityProfile::Fast`.
+    fn sync_commit(&self) -> Result<()> {
+        if self.durability().syncs_commits() {
+            self.file.sync_all()?;
+        }
+        Ok(())
+    }
+
     fn append_wal_entry(&mut self, payload: &[u8]) -> Result<u64> {
+        let skip_sync = !self.durability().syncs_every_write()
+            || self.batch_opts.as_ref().is_some_and(|opts| opts.skip_sync);
+        self.wal.set_skip_sync(skip_sync);
         loop {
             match self.wal.append_entry(payload) {
                 Ok(seq) => return Ok(seq),
@@ -600,153 +782,91 @@ impl Memvid {
                     reason: "wal_size overflow".into(),
                 })?;
         }
-        let delta = new_size - self.header.wal_size;
-        if delta == 0 {
+        if new_size == self.header.wal_size {
             return Ok(());
         }
+        self.relocate_wal(new_size)
+    }
 
-        self.shift_data_for_wal_growth(delta)?;
-        self.header.wal_size = new_size;
-        self.header.footer_offset = self.header.footer_offset.saturating_add(delta);
-        self.data_end = self.data_end.saturating_add(delta);
-        self.adjust_offsets_after_wal_growth(delta);
-
-        let catalog_end = self.catalog_data_end();
-        self.header.footer_offset = catalog_end
-            .max(self.header.footer_offset)
-            .max(self.data_end);
+    /// Move the WAL into a `size`-byte extent at the end of the file.
+    ///
+    /// Nothing else moves: the extent lands past the current footer, the TOC is rewritten
+    /// after it, and only then does the header switch over, so a crash part-way leaves the
+    /// previous WAL and TOC in charge. The region the WAL leaves behind becomes orphaned bytes
+    /// for `vacuum` to reclaim.
+    fn relocate_wal(&mut self, size: u64) -> Result<()> {
+        let offset = self.file.metadata()?.len().max(self.data_end);
+        let end = offset
+            .checked_add(size)
+            .ok_or_else(|| MemvidError::CheckpointFailed {
+                reason: "wal extent overflow".into(),
+            })?;
+        self.file.set_len(end)?;
+        self.wal.relocate(&mut self.header, offset, size)?;
+        self.header.version = WAL_EXTENT_VERSION;
+        self.data_end = self.data_end.max(end);
+        self.cached_payload_end = self.cached_payload_end.max(end);
+        self.header.footer_offset = self.header.footer_offset.max(end);
 
         self.rewrite_toc_footer()?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
-        self.file.sync_all()?;
-        self.wal = EmbeddedWal::open(&self.file, &self.header)?;
-        Ok(())
-    }
-
-    fn shift_data_for_wal_growth(&mut self, delta: u64) -> Result<()> {
-        if delta == 0 {
-            return Ok(());
-        }
-        let original_len = self.file.metadata()?.len();
-        let data_start = self.header.wal_offset + self.header.wal_size;
-        self.file.set_len(original_len + delta)?;
-
-        let mut remaining = original_len.saturating_sub(data_start);
-        let mut buffer = vec![0u8; WAL_SHIFT_BUFFER_SIZE];
-        while remaining > 0 {
-            let chunk = min(remaining, buffer.len() as u64);
-            let src = data_start + remaining - chunk;
-            self.file.seek(SeekFrom::Start(src))?;
-            #[allow(clippy::cast_possible_truncation)]
-            self.file.read_exact(&mut buffer[..chunk as usize])?;
-            let dst = src + delta;
-            self.file.seek(SeekFrom::Start(dst))?;
-            #[allow(clippy::cast_possible_truncation)]
-            self.file.write_all(&buffer[..chunk as usize])?;
-            remaining -= chunk;
-        }
-
-        self.file.seek(SeekFrom::Start(data_start))?;
-        let zero_buf = vec![0u8; WAL_SHIFT_BUFFER_SIZE];
-        let mut remaining = delta;
-        while remaining > 0 {
-            let write = min(remaining, zero_buf.len() as u64);
-            #[allow(clippy::cast_possible_truncation)]
-            self.file.write_all(&zero_buf[..write as usize])?;
-            remaining -= write;
-        }
+        self.sync_commit()?;
         Ok(())
     }
 
-    fn adjust_offsets_after_wal_growth(&mut self, delta: u64) {
-        if delta == 0 {
-            return;
-        }
-
-        for frame in &mut self.toc.frames {
-            if frame.payload_offset != 0 {
-                frame.payload_offset += delta;
-            }
-        }
-
-        for segment in &mut self.toc.segments {
-            if segment.bytes_offset != 0 {
-                segment.bytes_offset += delta;
-            }
-        }
-
-        if let Some(lex) = self.toc.indexes.lex.as_mut() {
-            if lex.bytes_offset != 0 {
-                lex.bytes_offset += delta;
-            }
-        }
-        for manifest in &mut self.toc.indexes.lex_segments {
-            if manifest.bytes_offset != 0 {
-                manifest.bytes_offset += delta;
-            }
-        }
-        if let Some(vec) = self.toc.indexes.vec.as_mut() {
-            if vec.bytes_offset != 0 {
-                vec.bytes_offset += delta;
-            }
-        }
-        if let Some(time_index) = self.toc.time_index.as_mut() {
-            if time_index.bytes_offset != 0 {
-                time_index.bytes_offset += delta;
-            }
-        }
-        #[cfg(feature = "temporal_track")]
-        if let Some(track) = self.toc.temporal_track.as_mut() {
-            if track.bytes_offset != 0 {
-                track.bytes_offset += delta;
-            }
-        }
-
-        let catalog = &mut self.toc.segment_catalog;
-        for descriptor in &mut catalog.lex_segments {
-            if descriptor.common.bytes_offset != 0 {
-                descriptor.common.bytes_offset += delta;
-            }
-        }
-        for descriptor in &mut catalog.vec_segments {
-            if descriptor.common.bytes_offset != 0 {
-                descriptor.common.bytes_offset += delta;
-            }
-        }
-        for descriptor in &mut catalog.time_segments {
-            if descriptor.common.bytes_offset != 0 {
-                descriptor.common.bytes_offset += delta;
-            }
-        }
-        #[cfg(feature = "temporal_track")]
-        for descriptor in &mut catalog.temporal_segments {
-            if descriptor.common.bytes_offset != 0 {
-                descriptor.common.bytes_offset += delta;
-            }
-        }
-        for descriptor in &mut catalog.tantivy_segments {
-            if descriptor.common.bytes_offset != 0 {
-                descriptor.common.bytes_offset += delta;
-            }
-        }
-
-        #[cfg(feature = "lex")]
-        if let Ok(mut storage) = self.lex_storage.write() {
-            storage.adjust_offsets(delta);
-        }
-    }
     pub fn commit_with_options(&mut self, options: CommitOptions) -> Result<()> {
+        // Refuses frozen files before anything, even an empty commit, is written.
         self.ensure_writable()?;
         if options.background {
             tracing::debug!("commit background flag ignored; running synchronously");
         }
         let mode = options.mode;
+        if let CommitMode::Coalesced { max_delay } = mode {
+            let opened = *self.commit_window.get_or_insert_with(Instant::now);
+            if opened.elapsed() < max_delay {
+                return Ok(());
+            }
+        }
+        let window = self.commit_window.take();
+        let result = self.commit_now(options, mode);
+        if result.is_err() {
+            // Keep the window open so `flush` or drop tries again.
+            self.commit_window = window;
+        }
+        result
+    }
+
+    fn commit_now(&mut self, options: CommitOptions, mode: CommitMode) -> Result<()> {
         let records = self.wal.pending_records()?;
-        if records.is_empty() && !self.dirty && !self.tantivy_index_pending() {
+        if records.is_empty()
+            && !self.dirty
+            && !self.tantivy_index_pending()
+            && self.pending_access.is_empty()
+        {
             return Ok(());
         }
-        self.with_staging_lock(move |mem| mem.commit_from_records(records, mode))
+        let durability = self.lock_settings.durability;
+        if let Some(profile) = options.durability {
+            self.lock_settings.durability = profile;
+        }
+        self.pending_commit_metadata = Some(CommitMetadata {
+            author: options
+                .author
+                .or_else(|| self.commit_identity.author.clone()),
+            agent_id: options
+                .agent_id
+                .or_else(|| self.commit_identity.agent_id.clone()),
+            reason: options.reason,
+            ..CommitMetadata::default()
+        });
+        let started = Instant::now();
+        let result = self.with_staging_lock(move |mem| mem.commit_from_records(records, mode));
+        self.lock_settings.durability = durability;
+        if result.is_ok() {
+            metrics::duration(metrics::COMMIT_DURATION, started.elapsed(), &[]);
+        }
+        result
     }
 
     pub fn commit(&mut self) -> Result<()> {
@@ -754,13 +874,29 @@ impl Memvid {
         self.commit_with_options(CommitOptions::new(CommitMode::Full))
     }
 
+    /// Commit the puts an open `CommitMode::Coalesced` window is deferring without waiting for
+    /// `max_delay`; a no-op when no window is open. Unlike the commit made on drop, its error
+    /// reaches the caller, and the window stays open so the flush can be retried.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.commit_window.is_none() {
+            return Ok(());
+        }
+        self.commit()
+    }
+
+    /// Whether a `CommitMode::Coalesced` window is open with its commit still deferred.
+    #[must_use]
+    pub fn commit_deferred(&self) -> bool {
+        self.commit_window.is_some()
+    }
+
     /// Enter batch mode for high-throughput ingestion.
     ///
     /// While batch mode is active:
     /// - WAL fsync is skipped on every append (controlled by `opts.skip_sync`)
     /// - Auto-checkpoint is suppressed (controlled by `opts.disable_auto_checkpoint`)
     /// - Compression level is lowered (controlled by `opts.compression_level`)
-    /// - WAL is pre-sized to avoid expensive mid-batch growth (controlled by `opts.wal_pre_size_bytes`)
+    /// - WAL is pre-sized to skip repeated mid-batch growth (controlled by `opts.wal_pre_size_bytes`)
     ///
     /// **You must call [`end_batch()`](Self::end_batch) when done** to flush the WAL
     /// and restore normal operation.
@@ -773,49 +909,22 @@ impl Memvid {
         Ok(())
     }
 
-    /// Pre-grow the embedded WAL to at least `min_bytes` in a single operation.
+    /// Grow the embedded WAL to at least `min_bytes` in a single relocation.
     ///
-    /// When `disable_auto_checkpoint` is true, all WAL entries accumulate for
-    /// the entire batch.  If the region is too small it must grow repeatedly,
-    /// and every growth shifts **all** payload data — O(file_size) per shift.
-    ///
-    /// Calling this once before the batch eliminates all mid-batch shifts.
-    /// On a freshly-created file the shift is essentially free (no data to move).
+    /// Growth never moves payload data, so this only saves the relocations a batch with
+    /// `disable_auto_checkpoint` would otherwise trigger as its records accumulate.
     fn ensure_wal_capacity(&mut self, min_bytes: u64) -> Result<()> {
         if min_bytes <= self.header.wal_size {
             return Ok(());
         }
         // Jump directly to the target size (next power of two for alignment)
         let target = min_bytes.next_power_of_two();
-        let delta = target.saturating_sub(self.header.wal_size);
-        if delta == 0 {
-            return Ok(());
-        }
-
         tracing::info!(
             current_wal = self.header.wal_size,
             target_wal = target,
-            delta,
             "pre-sizing WAL for batch mode"
         );
-
-        self.shift_data_for_wal_growth(delta)?;
-        self.header.wal_size = target;
-        self.header.footer_offset = self.header.footer_offset.saturating_add(delta);
-        self.data_end = self.data_end.saturating_add(delta);
-        self.adjust_offsets_after_wal_growth(delta);
-
-        let catalog_end = self.catalog_data_end();
-        self.header.footer_offset = catalog_end
-            .max(self.header.footer_offset)
-            .max(self.data_end);
-
-        self.rewrite_toc_footer()?;
-        self.header.toc_checksum = self.toc.toc_checksum;
-        crate::persist_header(&mut self.file, &self.header)?;
-        self.file.sync_all()?;
-        self.wal = EmbeddedWal::open(&self.file, &self.header)?;
-        Ok(())
+        self.relocate_wal(target)
     }
 
     /// Exit batch mode, flushing the WAL and restoring per-entry fsync.
@@ -841,9 +950,10 @@ impl Memvid {
     pub fn commit_skip_indexes(&mut self) -> Result<()> {
         self.ensure_writable()?;
         let records = self.wal.pending_records()?;
-        if records.is_empty() && !self.dirty {
+        if records.is_empty() && !self.dirty && self.pending_access.is_empty() {
             return Ok(());
         }
+        self.pending_commit_metadata = None;
         self.commit_skip_indexes_inner(records)
     }
 
@@ -864,7 +974,12 @@ impl Memvid {
             self.tantivy_dirty = false;
         }
 
-        let _delta = result?;
+        let delta = result?;
+        self.record_commit_event(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.refresh_suggest_index(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.flush_access_stats()?;
+        self.record_audit_mutations(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.record_commit_provenance()?;
 
         // Set footer_offset to right after payloads (no index data written)
         self.header.footer_offset = self.data_end;
@@ -893,15 +1008,18 @@ impl Memvid {
         self.toc.memories_track = None;
         self.toc.logic_mesh = None;
         self.toc.sketch_track = None;
+        self.toc.extensions.remove(SPARSE_TRACK_EXTENSION);
+        self.toc.extensions.remove(TOKEN_TRACK_EXTENSION);
 
         self.rewrite_toc_footer()?;
         self.header.toc_checksum = self.toc.toc_checksum;
         self.wal.record_checkpoint(&mut self.header)?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
-        self.file.sync_all()?;
+        self.sync_commit()?;
         self.pending_frame_inserts = 0;
         self.dirty = false;
+        self.publish_commit_event();
         Ok(())
     }
 
@@ -916,14 +1034,33 @@ impl Memvid {
         self.rewrite_toc_footer()?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
-        self.file.sync_all()?;
+        self.sync_commit()?;
         Ok(())
     }
 
     fn commit_from_records(&mut self, records: Vec<WalRecord>, _mode: CommitMode) -> Result<()> {
         self.generation = self.generation.wrapping_add(1);
 
-        let delta = self.apply_records(records)?;
+        let mut phase = Instant::now();
+        let mut delta = self.apply_records(records)?;
+        delta
+            .inserted_embeddings
+            .append(&mut self.pending_embeddings);
+        self.record_commit_event(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.refresh_suggest_index(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.flush_access_stats()?;
+        self.record_audit_mutations(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.record_commit_provenance()?;
+        metrics::counter(
+            metrics::COMMIT_FRAMES,
+            delta.inserted_frames.len() as u64,
+            &[],
+        );
+        metrics::lap(
+            metrics::COMMIT_PHASE_DURATION,
+            &mut phase,
+            &[("phase", "apply")],
+        );
         let mut indexes_rebuilt = false;
 
         // Check if CLIP index has pending embeddings that need to be persisted
@@ -955,7 +1092,7 @@ impl Memvid {
         }
 
         // Persist memories track if it has cards and wasn't already persisted by rebuild_indexes
-        if !indexes_rebuilt && self.memories_track.card_count() > 0 {
+        if !indexes_rebuilt && !self.memories_track.is_empty() {
             self.persist_memories_track()?;
         }
 
@@ -969,6 +1106,16 @@ impl Memvid {
             self.persist_sketch_track()?;
         }
 
+        // Persist sparse and token tracks if they have entries
+        self.persist_sparse_track()?;
+        self.persist_token_track()?;
+
+        metrics::lap(
+            metrics::COMMIT_PHASE_DURATION,
+            &mut phase,
+            &[("phase", "indexes")],
+        );
+
         // flush_tantivy() and rebuild_indexes() have already set footer_offset correctly.
         // DO NOT overwrite it with catalog_data_end() as that would include orphaned segments.
 
@@ -977,7 +1124,17 @@ impl Memvid {
         self.wal.record_checkpoint(&mut self.header)?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
-        self.file.sync_all()?;
+        metrics::lap(
+            metrics::COMMIT_PHASE_DURATION,
+            &mut phase,
+            &[("phase", "persist")],
+        );
+        self.sync_write()?;
+        metrics::lap(
+            metrics::COMMIT_PHASE_DURATION,
+            &mut phase,
+            &[("phase", "sync")],
+        );
         #[cfg(feature = "parallel_segments")]
         if let Some(wal) = self.manifest_wal.as_mut() {
             wal.flush()?;
@@ -1006,6 +1163,11 @@ impl Memvid {
         let records = self.wal.pending_records()?;
         let delta = self.apply_records(records)?;
         self.generation = self.generation.wrapping_add(1);
+        self.record_commit_event(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.refresh_suggest_index(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.flush_access_stats()?;
+        self.record_audit_mutations(&delta.inserted_frames, &delta.tombstoned_frames)?;
+        self.record_commit_provenance()?;
         let mut indexes_rebuilt = false;
         if !delta.is_empty() {
             tracing::info!(
@@ -1167,8 +1329,8 @@ impl Memvid {
             }
         }
 
-        // Persist memories track if it has cards
-        if self.memories_track.card_count() > 0 {
+        // Persist memories track if it has cards or importance records
+        if !self.memories_track.is_empty() {
             self.persist_memories_track()?;
         }
 
@@ -1182,6 +1344,10 @@ impl Memvid {
             self.persist_sketch_track()?;
         }
 
+        // Persist sparse and token tracks if they have entries
+        self.persist_sparse_track()?;
+        self.persist_token_track()?;
+
         // flush_tantivy() has already set footer_offset correctly
         // DO NOT overwrite with catalog_data_end()
         self.rewrite_toc_footer()?;
@@ -1189,7 +1355,7 @@ impl Memvid {
         self.wal.record_checkpoint(&mut self.header)?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
-        self.file.sync_all()?;
+        self.sync_write()?;
         if let Some(wal) = self.manifest_wal.as_mut() {
             wal.flush()?;
             wal.truncate()?;
@@ -1245,6 +1411,11 @@ impl Memvid {
         // all data including index segments.
         let mut data_cursor = self.data_end;
         let mut sequence_to_frame: HashMap<u64, FrameId> = HashMap::new();
+        let mut extent_store = self.blob_extent_store()?;
+        if let Some(store) = extent_store.as_mut() {
+            store.rebase(&self.header);
+        }
+        let mut extents_changed = false;
 
         if !records.is_empty() {
             self.file.seek(SeekFrom::Start(data_cursor))?;
@@ -1286,6 +1457,16 @@ impl Memvid {
                                     reason: "reused payload source missing",
                                 },
                             )?;
+                            if let Some(table) = extent_store
+                                .as_ref()
+                                .and_then(|store| store.tables.get(&source_id))
+                                .cloned()
+                            {
+                                if let Some(store) = extent_store.as_mut() {
+                                    store.tables.insert(frame_id, table);
+                                    extents_changed = true;
+                                }
+                            }
                             (
                                 source.payload_offset,
                                 source.payload_length,
@@ -1296,27 +1477,39 @@ impl Memvid {
                                     .unwrap_or(source.payload_length),
                             )
                         } else {
-                            self.file.seek(SeekFrom::Start(data_cursor))?;
-                            self.file.write_all(&entry.payload)?;
                             let checksum = hash(&entry.payload);
                             let payload_length = entry.payload.len() as u64;
-                            let canonical_length =
-                                if entry.canonical_encoding == CanonicalEncoding::Zstd {
-                                    if let Some(len) = entry.canonical_length {
-                                        len
-                                    } else {
-                                        let decoded = crate::decode_canonical_bytes(
-                                            &entry.payload,
-                                            CanonicalEncoding::Zstd,
-                                            frame_id,
-                                        )?;
-                                        decoded.len() as u64
-                                    }
-                                } else {
-                                    entry.canonical_length.unwrap_or(entry.payload.len() as u64)
-                                };
-                            let payload_offset = data_cursor;
-                            data_cursor += payload_length;
+                            let canonical_length = if let Some(len) = entry.canonical_length {
+                                len
+                            } else if entry.canonical_encoding == CanonicalEncoding::Plain {
+                                entry.payload.len() as u64
+                            } else {
+                                let decoded = crate::decode_canonical_bytes(
+                                    &entry.payload,
+                                    entry.canonical_encoding,
+                                    frame_id,
+                                )?;
+                                decoded.len() as u64
+                            };
+                            let (payload_offset, payload_length) = if let Some(store) = extent_store
+                                .as_mut()
+                                .filter(|store| store.applies_to(payload_length))
+                            {
+                                data_cursor = self.write_blob_extents(
+                                    store,
+                                    frame_id,
+                                    &entry.payload,
+                                    data_cursor,
+                                )?;
+                                extents_changed = true;
+                                (0, 0)
+                            } else {
+                                self.file.seek(SeekFrom::Start(data_cursor))?;
+                                self.file.write_all(&entry.payload)?;
+                                let payload_offset = data_cursor;
+                                data_cursor += payload_length;
+                                (payload_offset, payload_length)
+                            };
                             // Keep cached_payload_end in sync (monotonically increasing)
                             self.cached_payload_end = self.cached_payload_end.max(data_cursor);
                             (
@@ -1337,8 +1530,10 @@ impl Memvid {
                             .or_else(|| crate::infer_title_from_uri(&uri));
 
                         #[cfg(feature = "temporal_track")]
-                        let (anchor_ts, anchor_source) =
-                            self.determine_temporal_anchor(entry.timestamp);
+                        let (anchor, anchor_source, timezone) =
+                            self.determine_temporal_anchor(entry.timestamp, &entry.extra_metadata);
+                        #[cfg(feature = "temporal_track")]
+                        let anchor_ts = anchor.unix_timestamp();
 
                         let mut frame = Frame {
                             id: frame_id,
@@ -1487,7 +1682,8 @@ impl Memvid {
                                     Self::collect_temporal_mentions(
                                         entry.search_text.as_deref(),
                                         frame_id,
-                                        anchor_ts,
+                                        anchor,
+                                        &timezone,
                                     ),
                                 );
                             }
@@ -1507,12 +1703,16 @@ impl Memvid {
                             reason: "tombstone missing frame reference",
                         })?;
                         self.mark_frame_deleted(target)?;
+                        delta.tombstoned_frames.push(target);
                         delta.mutated_frames = true;
                     }
                 }
             }
             self.data_end = self.data_end.max(data_cursor);
         }
+        if let Some(store) = extent_store.filter(|_| extents_changed) {
+            self.toc.set_extension(BLOB_EXTENT_EXTENSION, &store)?;
+        }
 
         // Second pass: resolve any orphan DocumentChunk frames that are missing parent_id.
         // This handles edge cases where chunks couldn't be linked during the first pass.
@@ -1562,28 +1762,40 @@ impl Memvid {
         Ok(delta)
     }
 
+    /// Anchor instant of a frame, in the local time of the zone its relative dates resolve in,
+    /// and that zone's name.
     #[cfg(feature = "temporal_track")]
-    fn determine_temporal_anchor(&self, timestamp: i64) -> (i64, AnchorSource) {
-        (timestamp, AnchorSource::FrameTimestamp)
+    fn determine_temporal_anchor(
+        &self,
+        timestamp: i64,
+        extra_metadata: &BTreeMap<String, String>,
+    ) -> (OffsetDateTime, AnchorSource, String) {
+        let instant =
+            OffsetDateTime::from_unix_timestamp(timestamp).unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        let timezone = self.frame_timezone(extra_metadata);
+        let anchor = match timezone_offset(&timezone, instant) {
+            Ok(offset) => instant.to_offset(offset),
+            Err(err) => {
+                tracing::warn!(target = "memvid::temporal", %timezone, %err, "unknown frame timezone, anchoring in UTC");
+                instant
+            }
+        };
+        (anchor, AnchorSource::FrameTimestamp, timezone)
     }
 
     #[cfg(feature = "temporal_track")]
     fn collect_temporal_mentions(
         text: Option<&str>,
         frame_id: FrameId,
-        anchor_ts: i64,
+        anchor: OffsetDateTime,
+        timezone: &str,
     ) -> Vec<TemporalMention> {
         let text = match text {
             Some(value) if !value.trim().is_empty() => value,
             _ => return Vec::new(),
         };
 
-        let anchor = match OffsetDateTime::from_unix_timestamp(anchor_ts) {
-            Ok(ts) => ts,
-            Err(_) => return Vec::new(),
-        };
-
-        let context = TemporalContext::new(anchor, DEFAULT_TEMPORAL_TZ.to_string());
+        let context = TemporalContext::new(anchor, timezone);
         let normalizer = TemporalNormalizer::new(context);
         let mut spans: Vec<(usize, usize)> = Vec::new();
         let lower = text.to_ascii_lowercase();
@@ -1613,6 +1825,28 @@ impl Memvid {
             spans.push((mat.start(), mat.end()));
         }
 
+        static RECURRING: OnceCell<std::result::Result<Regex, String>> = OnceCell::new();
+        match RECURRING
+            .get_or_init(|| Regex::new(RECURRING_TEMPORAL_PATTERN).map_err(|err| err.to_string()))
+        {
+            Ok(re) => {
+                let recurring: Vec<(usize, usize)> = re
+                    .find_iter(&lower)
+                    .map(|mat| (mat.start(), mat.end()))
+                    .collect();
+                // "monday" inside "every monday" belongs to the rule, not a date of its own
+                spans.retain(|(start, end)| {
+                    !recurring
+                        .iter()
+                        .any(|(outer_start, outer_end)| outer_start <= start && end <= outer_end)
+                });
+                spans.extend(recurring);
+            }
+            Err(msg) => {
+                tracing::error!(target = "memvid::temporal", error = %msg, "recurring phrase regex init failed");
+            }
+        }
+
         spans.sort_unstable();
         spans.dedup();
 
@@ -1729,6 +1963,21 @@ impl Memvid {
                     flags,
                 ));
             }
+            TemporalResolutionValue::Recurring { start, rule } => {
+                results.push(
+                    TemporalMention::new(
+                        Self::date_to_timestamp(start),
+                        frame_id,
+                        byte_start,
+                        byte_len,
+                        TemporalMentionKind::Recurring,
+                        resolution.confidence,
+                        0,
+                        base_flags,
+                    )
+                    .with_recurrence(rule),
+                );
+            }
             TemporalResolutionValue::Month { year, month } => {
                 let start_date = match Date::from_calendar_date(year, month, 1) {
                     Ok(date) => date,
@@ -2044,7 +2293,11 @@ impl Memvid {
         Ok(true)
     }
 
-    fn mark_frame_superseded(&mut self, frame_id: FrameId, successor_id: FrameId) -> Result<()> {
+    pub(crate) fn mark_frame_superseded(
+        &mut self,
+        frame_id: FrameId,
+        successor_id: FrameId,
+    ) -> Result<()> {
         let index = usize::try_from(frame_id).map_err(|_| MemvidError::InvalidFrame {
             frame_id,
             reason: "frame id too large",
@@ -2059,6 +2312,7 @@ impl Memvid {
             })?;
         frame.status = FrameStatus::Superseded;
         frame.superseded_by = Some(successor_id);
+        self.carry_frame_pin(frame_id, successor_id);
         self.remove_frame_from_indexes(frame_id)
     }
 
@@ -2071,6 +2325,10 @@ impl Memvid {
             return Ok(());
         }
 
+        // Read the surviving vectors before the index region below is overwritten.
+        let retained_vectors = self.retained_vec_documents()?;
+        // Everything below is written into this file, so a `.mv2x` sidecar stops being used.
+        self.detach_external_indexes();
         let payload_end = self.payload_region_end();
         self.data_end = payload_end;
         // Don't truncate if footer_offset is higher - there may be replay segments
@@ -2239,7 +2497,7 @@ impl Memvid {
             }
         }
 
-        if let Some((artifact, index)) = self.build_vec_artifact(new_vec_docs)? {
+        if let Some((artifact, index)) = self.build_vec_artifact(retained_vectors, new_vec_docs)? {
             let vec_offset = footer_offset;
             self.file.seek(SeekFrom::Start(vec_offset))?;
             self.file.write_all(&artifact.bytes)?;
@@ -2290,8 +2548,10 @@ impl Memvid {
             self.toc.indexes.clip = None;
         }
 
-        // Persist memories track if it has cards
-        if self.memories_track.card_count() > 0 {
+        // Persist memories track if it has cards or importance records
+        if self.memories_track.is_empty() {
+            self.toc.memories_track = None;
+        } else {
             let memories_offset = footer_offset;
             let memories_bytes = self.memories_track.serialize()?;
             let memories_checksum = blake3::hash(&memories_bytes).into();
@@ -2307,8 +2567,6 @@ impl Memvid {
                 entity_count: stats.entity_count as u64,
                 checksum: memories_checksum,
             });
-        } else {
-            self.toc.memories_track = None;
         }
 
         // Persist logic mesh if it has nodes
@@ -2400,7 +2658,7 @@ impl Memvid {
     /// This is used when the memories track has been modified but no frame
     /// changes were made (e.g., after running enrichment).
     fn persist_memories_track(&mut self) -> Result<()> {
-        if self.memories_track.card_count() == 0 {
+        if self.memories_track.is_empty() {
             self.toc.memories_track = None;
             return Ok(());
         }
@@ -2533,12 +2791,35 @@ impl Memvid {
             return Ok(());
         }
 
-        // Seek to write after the current footer_offset
-        self.file.seek(SeekFrom::Start(self.header.footer_offset))?;
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        let (_, sketch_length, sketch_checksum) =
+            crate::types::write_sketch_track(&mut encoded, &self.sketch_track)?;
+
+        // An unchanged track whose bytes nothing written this commit overlaps is kept in place,
+        // so the file does not grow and replicas are not re-sent it.
+        if let Some(existing) = self.toc.sketch_track.as_ref() {
+            let (start, end) = (
+                existing.bytes_offset,
+                existing.bytes_offset + existing.bytes_length,
+            );
+            let intact = start >= self.data_end
+                && end <= self.header.footer_offset
+                && referenced_byte_ranges(&self.toc, &self.header)
+                    .into_iter()
+                    .filter(|&range| range != (start, existing.bytes_length))
+                    .all(|(offset, len)| offset + len <= start || offset >= end);
+            if existing.bytes_length == sketch_length
+                && existing.checksum == sketch_checksum
+                && intact
+            {
+                return Ok(());
+            }
+        }
 
-        // Write the sketch track and get (offset, length, checksum)
-        let (sketch_offset, sketch_length, sketch_checksum) =
-            crate::types::write_sketch_track(&mut self.file, &self.sketch_track)?;
+        // Write after the current footer_offset
+        let sketch_offset = self.header.footer_offset;
+        self.file.seek(SeekFrom::Start(sketch_offset))?;
+        self.file.write_all(encoded.get_ref())?;
 
         let stats = self.sketch_track.stats();
         self.toc.sketch_track = Some(crate::types::SketchTrackManifest {
@@ -2723,7 +3004,7 @@ impl Memvid {
         self.remove_frame_from_indexes(frame_id)
     }
 
-    fn remove_frame_from_indexes(&mut self, frame_id: FrameId) -> Result<()> {
+    pub(crate) fn remove_frame_from_indexes(&mut self, frame_id: FrameId) -> Result<()> {
         #[cfg(feature = "lex")]
         if let Some(engine) = self.tantivy.as_mut() {
             engine.delete_frame(frame_id)?;
@@ -2735,6 +3016,8 @@ impl Memvid {
         if let Some(index) = self.vec_index.as_mut() {
             index.remove(frame_id);
         }
+        self.sparse_track.remove(frame_id);
+        self.token_track.remove(frame_id);
         Ok(())
     }
 
@@ -2818,7 +3101,7 @@ impl Memvid {
         Ok(())
     }
 
-    fn ensure_mutation_allowed(&mut self) -> Result<()> {
+    pub(crate) fn ensure_mutation_allowed(&mut self) -> Result<()> {
         self.ensure_writable()?;
         if self.toc.ticket_ref.issuer == "free-tier" {
             return Ok(());
@@ -2871,6 +3154,7 @@ impl Memvid {
             data_end = self.data_end,
             "rewrite_toc_footer: about to serialize TOC"
         );
+        self.stamp_blob_generations()?;
         let toc_bytes = prepare_toc_bytes(&mut self.toc)?;
         let footer_offset = self.header.footer_offset;
         self.file.seek(SeekFrom::Start(footer_offset))?;
@@ -2898,8 +3182,9 @@ impl Memvid {
         }
 
         self.file.set_len(final_len)?;
-        // Ensure footer is flushed to disk so mmap-based readers can find it
-        self.file.sync_all()?;
+        // Ensure footer is flushed to disk so mmap-based readers can find it; relaxed
+        // durability profiles defer this to the commit's final sync.
+        self.sync_write()?;
         Ok(())
     }
 }
@@ -2997,6 +3282,7 @@ impl Memvid {
 
 impl Memvid {
     pub fn vacuum(&mut self) -> Result<()> {
+        self.ensure_not_held("vacuum")?;
         self.commit()?;
 
         let mut active_payloads: HashMap<FrameId, Vec<u8>> = HashMap::new();
@@ -3008,11 +3294,14 @@ impl Memvid {
             .cloned()
             .collect();
         for frame in frames {
+            if stored_in_extents(&frame) {
+                continue;
+            }
             let bytes = self.read_frame_payload_bytes(&frame)?;
             active_payloads.insert(frame.id, bytes);
         }
 
-        let mut cursor = self.header.wal_offset + self.header.wal_size;
+        let mut cursor = self.compact_blob_extents()?;
         self.file.seek(SeekFrom::Start(cursor))?;
         for frame in &mut self.toc.frames {
             if frame.status == FrameStatus::Active {
@@ -3030,6 +3319,14 @@ impl Memvid {
                 frame.payload_length = 0;
             }
         }
+        // The payloads may have been written over a relocated WAL; it is empty after the commit
+        // above, so it simply moves in behind them.
+        if self.header.wal_relocated() {
+            let size = self.header.wal_size;
+            self.wal.relocate(&mut self.header, cursor, size)?;
+            cursor += size;
+            self.cached_payload_end = self.cached_payload_end.max(cursor);
+        }
 
         self.data_end = cursor;
 
@@ -3064,11 +3361,75 @@ impl Memvid {
         Ok(())
     }
 
+    /// Rewrite the tracks persisted after the footer (memories, Logic-Mesh, sketch, sparse,
+    /// token, and a loaded CLIP index) directly behind the last fixed blob, then truncate.
+    ///
+    /// Each commit that touches one of these tracks writes a fresh copy at the footer and never
+    /// reuses the old one, so without this the orphaned copies accumulate.
+    pub(crate) fn compact_tail_tracks(&mut self) -> Result<()> {
+        self.ensure_writable()?;
+        self.commit()?;
+
+        let clip_loaded = self.clip_enabled
+            && self
+                .clip_index
+                .as_ref()
+                .is_some_and(|index| !index.is_empty());
+        let mut relocatable: Vec<(u64, u64)> = Vec::new();
+        if let Some(track) = self.toc.memories_track.as_ref() {
+            relocatable.push((track.bytes_offset, track.bytes_length));
+        }
+        if let Some(mesh) = self.toc.logic_mesh.as_ref() {
+            relocatable.push((mesh.bytes_offset, mesh.bytes_length));
+        }
+        if let Some(track) = self.toc.sketch_track.as_ref() {
+            relocatable.push((track.bytes_offset, track.bytes_length));
+        }
+        relocatable.extend(sparse_track_range(&self.toc));
+        relocatable.extend(token_track_range(&self.toc));
+        if clip_loaded {
+            if let Some(manifest) = self.toc.indexes.clip.as_ref() {
+                relocatable.push((manifest.bytes_offset, manifest.bytes_length));
+            }
+        }
+        let fixed_end = crate::memvid::lifecycle::referenced_byte_ranges(&self.toc, &self.header)
+            .into_iter()
+            .filter(|range| !relocatable.contains(range))
+            .map(|(offset, length)| offset.saturating_add(length))
+            .fold(self.payload_region_end(), u64::max)
+            .max(self.header.wal_offset + self.header.wal_size);
+        if fixed_end >= self.header.footer_offset {
+            return Ok(());
+        }
+
+        self.header.footer_offset = fixed_end;
+        if clip_loaded {
+            self.persist_clip_index()?;
+        }
+        if !self.memories_track.is_empty() {
+            self.persist_memories_track()?;
+        }
+        if !self.logic_mesh.is_empty() {
+            self.persist_logic_mesh()?;
+        }
+        if !self.sketch_track.is_empty() {
+            self.persist_sketch_track()?;
+        }
+        self.persist_sparse_track()?;
+        self.persist_token_track()?;
+        self.rewrite_toc_footer()?;
+        self.header.toc_checksum = self.toc.toc_checksum;
+        crate::persist_header(&mut self.file, &self.header)?;
+        self.file.sync_all()?;
+        Ok(())
+    }
+
     /// Preview how a document would be chunked without actually ingesting it.
     ///
     /// This is useful when you need to compute embeddings for each chunk externally
     /// before calling `put_with_chunk_embeddings()`. Returns `None` if the document
-    /// is too small to be chunked (< 2400 chars after normalization).
+    /// is too small to be chunked (under two chunks of the file's `chunk_chars`, 2400
+    /// chars by default, after normalization).
     ///
     /// # Example
     /// ```ignore
@@ -3083,17 +3444,25 @@ impl Memvid {
     /// ```
     #[must_use]
     pub fn preview_chunks(&self, payload: &[u8]) -> Option<Vec<String>> {
-        plan_document_chunks(payload).map(|plan| plan.chunks)
+        plan_document_chunks(payload, self.chunk_chars(None)).map(|plan| plan.chunks)
     }
 
     /// Append raw bytes as a document frame.
     pub fn put_bytes(&mut self, payload: &[u8]) -> Result<u64> {
-        self.put_internal(Some(payload), None, None, None, PutOptions::default(), None)
+        self.put_internal(
+            Some(payload),
+            None,
+            None,
+            None,
+            PutOptions::default(),
+            None,
+            None,
+        )
     }
 
     /// Append raw bytes with explicit metadata/options.
     pub fn put_bytes_with_options(&mut self, payload: &[u8], options: PutOptions) -> Result<u64> {
-        self.put_internal(Some(payload), None, None, None, options, None)
+        self.put_internal(Some(payload), None, None, None, options, None, None)
     }
 
     /// Append bytes and an existing embedding (bypasses on-device embedding).
@@ -3105,6 +3474,7 @@ impl Memvid {
             None,
             PutOptions::default(),
             None,
+            None,
         )
     }
 
@@ -3114,7 +3484,15 @@ impl Memvid {
         embedding: Vec<f32>,
         options: PutOptions,
     ) -> Result<u64> {
-        self.put_internal(Some(payload), None, Some(embedding), None, options, None)
+        self.put_internal(
+            Some(payload),
+            None,
+            Some(embedding),
+            None,
+            options,
+            None,
+            None,
+        )
     }
 
     /// Ingest a document with pre-computed embeddings for both parent and chunks.
@@ -3143,6 +3521,7 @@ impl Memvid {
             Some(chunk_embeddings),
             options,
             None,
+            None,
         )
     }
 
@@ -3155,6 +3534,7 @@ impl Memvid {
         embedding: Option<Vec<f32>>,
     ) -> Result<u64> {
         self.ensure_mutation_allowed()?;
+        self.ensure_not_held("update frames")?;
         let existing = self.frame_by_id(frame_id)?;
         if existing.status != FrameStatus::Active {
             return Err(MemvidError::InvalidFrame {
@@ -3220,6 +3600,7 @@ impl Memvid {
             None, // No chunk embeddings for update
             options,
             Some(frame_id),
+            None,
         )?;
         info!(
             "frame_update frame_id={frame_id} seq={seq} reused_payload={reuse_flag} replaced_payload={replace_flag}"
@@ -3227,8 +3608,12 @@ impl Memvid {
         Ok(seq)
     }
 
+    /// Stage a tombstone for an active frame. Pinned frames are refused with
+    /// [`MemvidError::FramePinned`].
     pub fn delete_frame(&mut self, frame_id: FrameId) -> Result<u64> {
         self.ensure_mutation_allowed()?;
+        self.ensure_not_held("delete frames")?;
+        self.ensure_frame_unpinned(frame_id)?;
         let frame = self.frame_by_id(frame_id)?;
         if frame.status != FrameStatus::Active {
             return Err(MemvidError::InvalidFrame {
@@ -3288,16 +3673,22 @@ impl Memvid {
 }
 
 impl Memvid {
-    fn put_internal(
+    pub(crate) fn put_internal(
         &mut self,
         payload: Option<&[u8]>,
         reuse_frame: Option<Frame>,
         embedding: Option<Vec<f32>>,
         chunk_embeddings: Option<Vec<Vec<f32>>>,
-        mut options: PutOptions,
+        options: PutOptions,
         supersedes: Option<FrameId>,
+        parent_sequence: Option<u64>,
     ) -> Result<u64> {
+        let started = Instant::now();
+        let mut prepared = self.prepared_put.take();
         self.ensure_mutation_allowed()?;
+        let mut options = self.apply_ingest_profile(options)?;
+        let codec = self.payload_codec(options.compression)?;
+        let chunk_chars = self.chunk_chars(options.chunk_chars);
 
         // Deduplication: if enabled and we have payload, check if identical content exists
         if options.dedup {
@@ -3326,6 +3717,19 @@ impl Memvid {
             });
         }
 
+        let put_event = (!self.hooks.is_empty()).then(|| PutEvent {
+            sequence: 0,
+            uri: options.uri.clone(),
+            title: options.title.clone(),
+            track: options.track.clone(),
+            kind: options.kind.clone(),
+            tags: options.tags.clone(),
+            payload_bytes: payload.map_or_else(
+                || reuse_frame.as_ref().map_or(0, |frame| frame.payload_length),
+                |bytes| bytes.len() as u64,
+            ),
+        });
+
         // If the caller supplies embeddings, enforce a single vector dimension contract
         // for the entire memory (fail fast, never silently accept mixed dimensions).
         let incoming_dimension = {
@@ -3387,11 +3791,7 @@ impl Memvid {
         let mut prepared_payload: Option<(Vec<u8>, CanonicalEncoding, Option<u64>)> = None;
         let payload_tail = self.payload_region_end();
         let projected = if let Some(bytes) = payload {
-            let (prepared, encoding, length) = if let Some(ref opts) = self.batch_opts {
-                prepare_canonical_payload_with_level(bytes, opts.compression_level)?
-            } else {
-                prepare_canonical_payload(bytes)?
-            };
+            let (prepared, encoding, length) = prepare_canonical_payload(bytes, codec)?;
             let len = prepared.len();
             prepared_payload = Some((prepared, encoding, length));
             payload_tail.saturating_add(len as u64)
@@ -3434,7 +3834,10 @@ impl Memvid {
 
         // Try to create a chunk plan from raw UTF-8 bytes first
         let raw_chunk_plan = match (payload, reuse_frame.as_ref()) {
-            (Some(bytes), None) => plan_document_chunks(bytes),
+            (Some(_), None) if prepared.is_some() => {
+                prepared.as_mut().and_then(|p| p.raw_chunk_plan.take())
+            }
+            (Some(bytes), None) => plan_document_chunks(bytes, chunk_chars),
             _ => None,
         };
 
@@ -3465,11 +3868,7 @@ impl Memvid {
             } else if let Some((prepared, encoding, length)) = prepared_payload.take() {
                 (prepared, encoding, length, None)
             } else if let Some(bytes) = payload {
-                let (prepared, encoding, length) = if let Some(ref opts) = self.batch_opts {
-                    prepare_canonical_payload_with_level(bytes, opts.compression_level)?
-                } else {
-                    prepare_canonical_payload(bytes)?
-                };
+                let (prepared, encoding, length) = prepare_canonical_payload(bytes, codec)?;
                 (prepared, encoding, length, None)
             } else if let Some(frame) = reuse_frame.as_ref() {
                 (
@@ -3496,6 +3895,14 @@ impl Memvid {
         let mut tags = std::mem::take(&mut options.tags);
         let mut labels = std::mem::take(&mut options.labels);
         let mut extra_metadata = std::mem::take(&mut options.extra_metadata);
+        self.apply_typed_metadata(
+            std::mem::take(&mut options.typed_metadata),
+            &mut extra_metadata,
+        )?;
+        if let Some(timezone) = options.timezone.take() {
+            timezone_offset(&timezone, time::OffsetDateTime::now_utc())?;
+            extra_metadata.insert(TEMPORAL_TZ_KEY.to_string(), timezone.trim().to_string());
+        }
         let mut content_dates: Vec<String> = Vec::new();
 
         let need_search_text = search_text
@@ -3504,88 +3911,25 @@ impl Memvid {
         let need_metadata = metadata.is_none();
         let run_extractor = need_search_text || need_metadata || options.auto_tag;
 
-        let mut extraction_error = None;
-        let mut is_skim_extraction = false; // Track if extraction was time-limited
-
-        let extracted = if run_extractor {
+        let (extracted, is_skim_extraction) = if run_extractor {
             if let Some(bytes) = payload_for_processing {
-                let mime_hint = metadata.as_ref().and_then(|m| m.mime.as_deref());
-                let uri_hint = options.uri.as_deref();
-
-                // Use time-budgeted extraction for instant indexing with a budget
-                let use_budgeted = options.instant_index && options.extraction_budget_ms > 0;
-
-                if use_budgeted {
-                    // Time-budgeted extraction for sub-second ingestion
-                    let budget = crate::extract_budgeted::ExtractionBudget::with_ms(
-                        options.extraction_budget_ms,
-                    );
-                    match crate::extract_budgeted::extract_with_budget(
-                        bytes, mime_hint, uri_hint, budget,
-                    ) {
-                        Ok(result) => {
-                            is_skim_extraction = result.is_skim();
-                            if is_skim_extraction {
-                                tracing::debug!(
-                                    coverage = result.coverage,
-                                    elapsed_ms = result.elapsed_ms,
-                                    sections = %format!("{}/{}", result.sections_extracted, result.sections_total),
-                                    "time-budgeted extraction (skim)"
-                                );
-                            }
-                            // Convert BudgetedExtractionResult to ExtractedDocument
-                            let doc = crate::extract::ExtractedDocument {
-                                text: if result.text.is_empty() {
-                                    None
-                                } else {
-                                    Some(result.text)
-                                },
-                                metadata: serde_json::json!({
-                                    "skim": is_skim_extraction,
-                                    "coverage": result.coverage,
-                                    "sections_extracted": result.sections_extracted,
-                                    "sections_total": result.sections_total,
-                                }),
-                                mime_type: mime_hint.map(std::string::ToString::to_string),
-                            };
-                            Some(doc)
-                        }
-                        Err(err) => {
-                            // Fall back to full extraction on budgeted extraction error
-                            tracing::warn!(
-                                ?err,
-                                "budgeted extraction failed, trying full extraction"
-                            );
-                            match extract_via_registry(bytes, mime_hint, uri_hint) {
-                                Ok(doc) => Some(doc),
-                                Err(err) => {
-                                    extraction_error = Some(err);
-                                    None
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    // Full extraction (no time budget)
-                    match extract_via_registry(bytes, mime_hint, uri_hint) {
-                        Ok(doc) => Some(doc),
-                        Err(err) => {
-                            extraction_error = Some(err);
-                            None
-                        }
-                    }
-                }
+                let extraction = match prepared.as_mut().and_then(|p| p.extraction.take()) {
+                    Some(extraction) => extraction,
+                    None => extract_for_put(
+                        bytes,
+                        metadata.as_ref().and_then(|m| m.mime.as_deref()),
+                        &options,
+                    ),
+                };
+                let (doc, skim) = extraction?;
+                (Some(doc), skim)
             } else {
-                None
+                (None, false)
             }
         } else {
-            None
+            (None, false)
         };
 
-        if let Some(err) = extraction_error {
-            return Err(err);
-        }
-
         if let Some(doc) = &extracted {
             if need_search_text {
                 if let Some(text) = &doc.text {
@@ -3601,7 +3945,10 @@ impl Memvid {
             // from extracted text. This ensures large documents like PDFs get fully indexed.
             if chunk_plan.is_none() {
                 if let Some(text) = &doc.text {
-                    chunk_plan = plan_text_chunks(text);
+                    chunk_plan = match prepared.as_mut().and_then(|p| p.text_chunk_plan.take()) {
+                        Some(plan) => Some(plan),
+                        None => plan_text_chunks(text, chunk_chars),
+                    };
                 }
             }
 
@@ -3632,7 +3979,10 @@ impl Memvid {
         if options.auto_tag {
             if let Some(ref text) = search_text {
                 if !text.trim().is_empty() {
-                    let result = AutoTagger.analyse(text, options.extract_dates);
+                    let result = prepared
+                        .as_mut()
+                        .and_then(|p| p.auto_tags.take())
+                        .unwrap_or_else(|| AutoTagger.analyse(text, options.extract_dates));
                     merge_unique(&mut tags, result.tags);
                     merge_unique(&mut labels, result.labels);
                     if options.extract_dates && content_dates.is_empty() {
@@ -3648,6 +3998,32 @@ impl Memvid {
             }
         }
 
+        // Locate PDF text on its pages: each chunk gets its own spans, and an unchunked
+        // document gets spans over its search text.
+        let mut pdf_spans: Option<Vec<PdfTextSpan>> = None;
+        if let Some(bytes) = payload_for_processing {
+            if let Some(plan) = chunk_plan.as_mut() {
+                let texts: Vec<&str> = plan.chunks.iter().map(String::as_str).collect();
+                if let Some(located) = locate_pdf_texts(bytes, &texts) {
+                    plan.chunk_metadata
+                        .resize_with(plan.chunks.len(), BTreeMap::new);
+                    for (metadata, spans) in plan.chunk_metadata.iter_mut().zip(located) {
+                        insert_pdf_spans(metadata, &spans);
+                    }
+                }
+            } else if let Some(text) = search_text.as_deref() {
+                pdf_spans =
+                    locate_pdf_texts(bytes, &[text.trim()]).and_then(|mut located| located.pop());
+            }
+        }
+
+        // Detect on the document text alone, and record the result only after the metadata
+        // lines are appended so it does not become a search term of every frame.
+        let detected_language = if extra_metadata.contains_key(LANGUAGE_KEY) {
+            None
+        } else {
+            search_text.as_deref().and_then(detect_language)
+        };
         let metadata_ref = metadata.as_ref();
         let mut search_text = augment_search_text(
             search_text,
@@ -3660,6 +4036,12 @@ impl Memvid {
             &content_dates,
             metadata_ref,
         );
+        if let Some(language) = detected_language {
+            extra_metadata.insert(LANGUAGE_KEY.to_string(), language.to_string());
+        }
+        if let Some(spans) = pdf_spans {
+            insert_pdf_spans(&mut extra_metadata, &spans);
+        }
         let mut chunk_entries: Vec<WalEntryData> = Vec::new();
         let mut parent_chunk_manifest: Option<TextChunkManifest> = None;
         let mut parent_chunk_count: Option<u32> = None;
@@ -3696,10 +4078,25 @@ impl Memvid {
 
             for (idx, chunk_text) in plan.chunks.iter().enumerate() {
                 let (chunk_payload, chunk_encoding, chunk_length) =
-                    prepare_canonical_payload(chunk_text.as_bytes())?;
-                let chunk_search_text = normalize_text(chunk_text, DEFAULT_SEARCH_TEXT_LIMIT)
+                    prepare_canonical_payload(chunk_text.as_bytes(), codec)?;
+                let mut chunk_search_text = normalize_text(chunk_text, DEFAULT_SEARCH_TEXT_LIMIT)
                     .map(|n| n.text)
                     .filter(|text| !text.trim().is_empty());
+                let mut chunk_extra = chunk_extra_metadata.clone();
+                if let Some(own) = plan.chunk_metadata.get(idx) {
+                    chunk_extra.extend(own.clone());
+                }
+                // Index symbol names with the chunk so `symbol:` and plain queries reach it.
+                if let Some(symbols) = chunk_extra.get(CODE_SYMBOLS_KEY) {
+                    let names: Vec<String> = CodeSymbol::parse_labels(symbols)
+                        .map(|(kind, name)| format!("{kind} {name}"))
+                        .collect();
+                    let line = format!("{CODE_SYMBOLS_KEY}: {}", names.join(", "));
+                    chunk_search_text = Some(match chunk_search_text {
+                        Some(text) => format!("{text}\n{line}"),
+                        None => line,
+                    });
+                }
 
                 let chunk_uri = uri_value
                     .as_ref()
@@ -3727,7 +4124,7 @@ impl Memvid {
                     search_text: chunk_search_text,
                     tags: chunk_tags.clone(),
                     labels: chunk_labels.clone(),
-                    extra_metadata: chunk_extra_metadata.clone(),
+                    extra_metadata: chunk_extra,
                     content_dates: chunk_content_dates.clone(),
                     chunk_manifest: None,
                     role: FrameRole::DocumentChunk,
@@ -3749,9 +4146,11 @@ impl Memvid {
         let parent_uri = uri_value.clone();
         let parent_title = title_value.clone();
 
-        // Get parent_sequence from options.parent_id if provided
+        // Get parent_sequence from options.parent_id if the caller did not pass one
         // We need the WAL sequence of the parent frame to link them
-        let parent_sequence = if let Some(parent_id) = options.parent_id {
+        let parent_sequence = if parent_sequence.is_some() {
+            parent_sequence
+        } else if let Some(parent_id) = options.parent_id {
             // Look up the parent frame to get its WAL sequence
             // Since frame.id corresponds to the array index, we need to find the sequence
             // For now, we'll use the frame_id + WAL_START_SEQUENCE as an approximation
@@ -3942,9 +4341,12 @@ impl Memvid {
                         timestamp,
                     );
 
+                    // Put stays lenient in strict schema mode: the frame is already written.
+                    let (cards, _rejected) = self.drop_invalid_cards(cards);
                     if !cards.is_empty() {
                         // Add cards to memories track
                         let card_ids = self.memories_track.add_cards(cards);
+                        self.detect_card_conflicts(&card_ids);
 
                         // Record enrichment for incremental processing
                         self.memories_track
@@ -3954,6 +4356,17 @@ impl Memvid {
             }
         }
 
+        metrics::duration(metrics::PUT_DURATION, started.elapsed(), &[]);
+        if let Some(bytes) = payload {
+            metrics::counter(metrics::PUT_BYTES, bytes.len() as u64, &[]);
+        }
+        if let Some(event) = put_event {
+            self.fire_put_hooks(&PutEvent {
+                sequence: parent_seq,
+                ..event
+            });
+        }
+
         Ok(parent_seq)
     }
 }
@@ -3986,6 +4399,15 @@ fn decode_wal_entry(bytes: &[u8]) -> Result<WalEntry> {
     Ok(WalEntry::Frame(legacy))
 }
 
+/// Decode a WAL record that carries a frame operation; other records yield `None`.
+pub(crate) fn decode_wal_frame(bytes: &[u8]) -> Option<WalEntryData> {
+    match decode_wal_entry(bytes).ok()? {
+        WalEntry::Frame(entry) => Some(entry),
+        #[cfg(feature = "lex")]
+        WalEntry::Lex(_) => None,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct WalEntryData {
     pub(crate) timestamp: i64,
@@ -4042,38 +4464,26 @@ pub(crate) struct WalEntryData {
     pub(crate) enrichment_state: crate::types::EnrichmentState,
 }
 
+/// Compress a UTF-8 `payload` with `codec`; binary payloads are stored as-is.
 pub(crate) fn prepare_canonical_payload(
     payload: &[u8],
+    codec: CompressionCodec,
 ) -> Result<(Vec<u8>, CanonicalEncoding, Option<u64>)> {
-    prepare_canonical_payload_with_level(payload, 3)
-}
-
-pub(crate) fn prepare_canonical_payload_with_level(
-    payload: &[u8],
-    level: i32,
-) -> Result<(Vec<u8>, CanonicalEncoding, Option<u64>)> {
-    if level == 0 {
-        // No compression — store as plain text
-        return Ok((
-            payload.to_vec(),
-            CanonicalEncoding::Plain,
-            Some(payload.len() as u64),
-        ));
+    let length = Some(payload.len() as u64);
+    if codec == CompressionCodec::None || std::str::from_utf8(payload).is_err() {
+        return Ok((payload.to_vec(), CanonicalEncoding::Plain, length));
     }
-    if std::str::from_utf8(payload).is_ok() {
-        let compressed = zstd::encode_all(std::io::Cursor::new(payload), level)?;
-        Ok((
-            compressed,
-            CanonicalEncoding::Zstd,
-            Some(payload.len() as u64),
-        ))
-    } else {
-        Ok((
-            payload.to_vec(),
-            CanonicalEncoding::Plain,
-            Some(payload.len() as u64),
-        ))
+    // Long texts get a seek table so snippets can decode only the frames they need
+    if let CompressionCodec::Zstd { level } = codec {
+        if payload.len() > SEEKABLE_BLOCK_SIZE {
+            return Ok((
+                compress_seekable(payload, level)?,
+                CanonicalEncoding::Zstd,
+                length,
+            ));
+        }
     }
+    Ok((codec.compress(payload)?, codec.canonical_encoding(), length))
 }
 
 pub(crate) fn augment_search_text(
@@ -4123,7 +4533,8 @@ pub(crate) fn augment_search_text(
 
     if !extra_metadata.is_empty() {
         for (key, value) in extra_metadata {
-            if value.trim().is_empty() {
+            // Page coordinates are not search terms.
+            if value.trim().is_empty() || key == PDF_SPANS_KEY {
                 continue;
             }
             segments.push(format!("{key}: {value}"));
@@ -4147,6 +4558,15 @@ pub(crate) fn augment_search_text(
     }
 }
 
+/// Record where a frame's text sits in its source PDF.
+fn insert_pdf_spans(extra_metadata: &mut BTreeMap<String, String>, spans: &[PdfTextSpan]) {
+    let Some(first) = spans.first() else {
+        return;
+    };
+    extra_metadata.insert(PDF_PAGE_KEY.to_string(), first.page.to_string());
+    extra_metadata.insert(PDF_SPANS_KEY.to_string(), PdfTextSpan::encode_list(spans));
+}
+
 pub(crate) fn merge_unique(target: &mut Vec<String>, additions: Vec<String>) {
     if additions.is_empty() {
         return;
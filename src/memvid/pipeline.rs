@@ -0,0 +1,156 @@
+//! Parallel ingestion pipeline behind `Memvid::put_many`.
+//!
+//! Extraction, chunk planning, and auto-tagging only depend on a document's bytes and
+//! options, so worker threads run them for a window of documents at a time. The results are
+//! handed to `put_internal` in input order, which keeps WAL appends (and frame ids) serial and
+//! deterministic.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::analysis::auto_tag::{AutoTagResult, AutoTagger};
+use crate::error::Result;
+use crate::memvid::chunks::{DocumentChunkPlan, plan_document_chunks, plan_text_chunks};
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::mutation::extract_for_put;
+use crate::types::PutOptions;
+use crate::{DEFAULT_SEARCH_TEXT_LIMIT, ExtractedDocument, normalize_text};
+
+/// Documents prepared per worker thread before their WAL entries are appended.
+const WINDOW_PER_WORKER: usize = 4;
+
+/// Work done off the caller thread for one document, consumed by `put_internal`.
+#[derive(Default)]
+pub(crate) struct PreparedPut {
+    pub(crate) extraction: Option<Result<(ExtractedDocument, bool)>>,
+    pub(crate) raw_chunk_plan: Option<DocumentChunkPlan>,
+    pub(crate) text_chunk_plan: Option<DocumentChunkPlan>,
+    pub(crate) auto_tags: Option<AutoTagResult>,
+}
+
+impl PreparedPut {
+    /// Mirror the extraction, chunking, and tagging decisions `put_internal` makes for a fresh
+    /// payload, chunking at `default_chunk_chars` unless the put chooses a size.
+    fn prepare(payload: &[u8], options: &PutOptions, default_chunk_chars: usize) -> Self {
+        let chunk_chars = options.chunk_chars.unwrap_or(default_chunk_chars);
+        let mut search_text = options
+            .search_text
+            .as_deref()
+            .and_then(|text| normalize_text(text, DEFAULT_SEARCH_TEXT_LIMIT).map(|n| n.text));
+        let need_search_text = search_text
+            .as_ref()
+            .is_none_or(|text| text.trim().is_empty());
+        let run_extractor = need_search_text || options.metadata.is_none() || options.auto_tag;
+        let raw_chunk_plan = plan_document_chunks(payload, chunk_chars);
+
+        let mut prepared = Self {
+            raw_chunk_plan,
+            ..Self::default()
+        };
+        if run_extractor {
+            let mime_hint = options.metadata.as_ref().and_then(|m| m.mime.as_deref());
+            let extraction = extract_for_put(payload, mime_hint, options);
+            if let Ok((doc, _)) = &extraction {
+                if let Some(text) = &doc.text {
+                    if need_search_text {
+                        if let Some(normalized) =
+                            normalize_text(text, DEFAULT_SEARCH_TEXT_LIMIT).map(|n| n.text)
+                        {
+                            search_text = Some(normalized);
+                        }
+                    }
+                    if prepared.raw_chunk_plan.is_none() {
+                        prepared.text_chunk_plan = plan_text_chunks(text, chunk_chars);
+                    }
+                }
+            }
+            prepared.extraction = Some(extraction);
+        }
+        if options.auto_tag {
+            prepared.auto_tags = search_text
+                .filter(|text| !text.trim().is_empty())
+                .map(|text| AutoTagger.analyse(&text, options.extract_dates));
+        }
+        prepared
+    }
+}
+
+impl Memvid {
+    /// Insert many documents, extracting, chunking, and auto-tagging them on worker threads.
+    ///
+    /// Produces the same frames, in the same order, as calling `put_bytes_with_options` for
+    /// each document; only the WAL appends run on the caller thread. Combine with
+    /// `begin_batch` to also relax fsync and checkpointing. Returns the WAL sequence of each
+    /// document.
+    pub fn put_many<I>(&mut self, documents: I) -> Result<Vec<u64>>
+    where
+        I: IntoIterator<Item = (Vec<u8>, PutOptions)>,
+    {
+        let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let mut documents = documents.into_iter();
+        let mut sequences = Vec::new();
+        loop {
+            // Profiles are resolved up front so chunk plans use their chunk size.
+            let window = documents
+                .by_ref()
+                .take(workers * WINDOW_PER_WORKER)
+                .map(|(payload, options)| Ok((payload, self.apply_ingest_profile(options)?)))
+                .collect::<Result<Vec<_>>>()?;
+            if window.is_empty() {
+                return Ok(sequences);
+            }
+            let prepared = prepare_window(&window, workers, self.chunk_chars(None));
+            for ((payload, options), prepared) in window.into_iter().zip(prepared) {
+                self.prepared_put = Some(prepared);
+                sequences.push(self.put_bytes_with_options(&payload, options)?);
+            }
+        }
+    }
+}
+
+/// Prepare every document of `window` on up to `workers` threads, returned in input order.
+fn prepare_window(
+    window: &[(Vec<u8>, PutOptions)],
+    workers: usize,
+    default_chunk_chars: usize,
+) -> Vec<PreparedPut> {
+    let threads = workers.min(window.len());
+    if threads <= 1 {
+        return window
+            .iter()
+            .map(|(payload, options)| PreparedPut::prepare(payload, options, default_chunk_chars))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut prepared: Vec<(usize, PreparedPut)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((payload, options)) = window.get(index) else {
+                            return done;
+                        };
+                        done.push((
+                            index,
+                            PreparedPut::prepare(payload, options, default_chunk_chars),
+                        ));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    prepared.sort_unstable_by_key(|(index, _)| *index);
+    prepared.into_iter().map(|(_, prepared)| prepared).collect()
+}
@@ -0,0 +1,178 @@
+//! Payload checksum verification and the quarantine of frames that fail it.
+//!
+//! With verification enabled (see `OpenReadOptions::verify_payloads`), every payload read is
+//! hashed and compared with the frame's stored BLAKE3 checksum. A mismatching frame is
+//! quarantined: reads fail with [`MemvidError::FrameQuarantined`] and searches drop it, so
+//! corrupted bytes never reach a prompt. Writable handles record the quarantine in the TOC
+//! on the next commit; read-only handles keep it for the life of the handle.
+
+use std::collections::BTreeSet;
+use std::sync::{MutexGuard, PoisonError};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{Frame, FrameId, SearchHit};
+
+/// TOC extension holding the ids of quarantined frames.
+pub const QUARANTINE_EXTENSION: &str = "memvid.quarantine";
+
+impl Memvid {
+    /// Enable or disable payload checksum verification on this handle.
+    pub fn set_verify_payloads(&mut self, verify: bool) {
+        self.verify_payloads = verify;
+    }
+
+    /// Ids of frames that failed payload verification, persisted or found by this handle.
+    pub fn quarantined_frames(&self) -> Result<Vec<FrameId>> {
+        Ok(self.quarantine_set()?.into_iter().collect())
+    }
+
+    pub(crate) fn quarantine_set(&self) -> Result<BTreeSet<FrameId>> {
+        let mut set = self
+            .toc
+            .extension::<BTreeSet<FrameId>>(QUARANTINE_EXTENSION)?
+            .unwrap_or_default();
+        set.extend(self.session_quarantine().iter().copied());
+        Ok(set)
+    }
+
+    fn session_quarantine(&self) -> MutexGuard<'_, BTreeSet<FrameId>> {
+        self.quarantined
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn ensure_not_quarantined(&self, frame_id: FrameId) -> Result<()> {
+        if self.quarantine_set()?.contains(&frame_id) {
+            return Err(MemvidError::FrameQuarantined { frame_id });
+        }
+        Ok(())
+    }
+
+    /// Compare `actual` with the frame's stored checksum, quarantining the frame for the life
+    /// of this handle on mismatch.
+    pub(crate) fn verify_payload_checksum(
+        &self,
+        frame: &Frame,
+        actual: &blake3::Hash,
+    ) -> Result<()> {
+        if actual.as_bytes() == &frame.checksum {
+            return Ok(());
+        }
+        tracing::warn!(
+            frame_id = frame.id,
+            "payload checksum mismatch; quarantining frame"
+        );
+        self.session_quarantine().insert(frame.id);
+        Err(MemvidError::FrameQuarantined { frame_id: frame.id })
+    }
+
+    /// Like [`Self::verify_payload_checksum`], also recording the quarantine in the TOC of a
+    /// writable handle.
+    pub(crate) fn check_payload_checksum(
+        &mut self,
+        frame: &Frame,
+        actual: &blake3::Hash,
+    ) -> Result<()> {
+        let result = self.verify_payload_checksum(frame, actual);
+        if result.is_err() && !self.read_only {
+            let mut set = self
+                .toc
+                .extension::<BTreeSet<FrameId>>(QUARANTINE_EXTENSION)?
+                .unwrap_or_default();
+            set.insert(frame.id);
+            self.toc.set_extension(QUARANTINE_EXTENSION, &set)?;
+            self.dirty = true;
+        }
+        result
+    }
+
+    /// Drop quarantined frames from `hits` and re-rank the rest. Returns how many were dropped.
+    pub(crate) fn exclude_quarantined_hits(&self, hits: &mut Vec<SearchHit>) -> Result<usize> {
+        let quarantined = self.quarantine_set()?;
+        if quarantined.is_empty() {
+            return Ok(0);
+        }
+        let before = hits.len();
+        hits.retain(|hit| !quarantined.contains(&hit.frame_id));
+        for (index, hit) in hits.iter_mut().enumerate() {
+            hit.rank = index + 1;
+        }
+        Ok(before - hits.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PutOptions;
+    use std::io::{Read, Seek, SeekFrom, Write};
+    use tempfile::tempdir;
+
+    #[test]
+    fn corrupted_payload_is_quarantined_on_verified_read() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("quarantine.mv2");
+        {
+            let mut mem = Memvid::create(&path).expect("create");
+            for (uri, text) in [
+                ("mv2://good", "healthy payload"),
+                ("mv2://bad", "damaged payload"),
+            ] {
+                let options = PutOptions {
+                    uri: Some(uri.to_string()),
+                    ..PutOptions::default()
+                };
+                mem.put_bytes_with_options(text.as_bytes(), options)
+                    .expect("put");
+            }
+            mem.commit().expect("commit");
+        }
+
+        let (good, bad, offset) = {
+            let mem = Memvid::open_read_only(&path).expect("open");
+            let good = mem.frame_by_uri("mv2://good").expect("good");
+            let bad = mem.frame_by_uri("mv2://bad").expect("bad");
+            (good.id, bad.id, bad.payload_offset)
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .expect("open file");
+        file.seek(SeekFrom::Start(offset)).expect("seek");
+        let mut byte = [0u8; 1];
+        file.read_exact(&mut byte).expect("read");
+        file.seek(SeekFrom::Start(offset)).expect("seek");
+        file.write_all(&[byte[0] ^ 0xFF]).expect("write");
+        drop(file);
+
+        let options = crate::OpenReadOptions::default().verify_payloads(true);
+        let mut mem = Memvid::open_read_only_with_options(&path, options).expect("open");
+        assert!(mem.frame_canonical_payload(good).is_ok());
+        let err = mem
+            .frame_canonical_payload(bad)
+            .expect_err("corrupted read");
+        assert!(matches!(err, MemvidError::FrameQuarantined { frame_id } if frame_id == bad));
+        assert_eq!(mem.quarantined_frames().expect("quarantined"), vec![bad]);
+
+        let hit = |frame_id, rank| SearchHit {
+            rank,
+            score: None,
+            frame_id,
+            uri: String::new(),
+            title: None,
+            matches: 0,
+            range: (0, 0),
+            chunk_range: None,
+            text: String::new(),
+            chunk_text: None,
+            metadata: None,
+            highlights: Vec::new(),
+        };
+        let mut hits = vec![hit(bad, 1), hit(good, 2)];
+        assert_eq!(mem.exclude_quarantined_hits(&mut hits).expect("exclude"), 1);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].frame_id, hits[0].rank), (good, 1));
+    }
+}
@@ -0,0 +1,954 @@
+//! Read-only access to `.mv2` files stored behind a [`RangeFetcher`] (S3, GCS, HTTP).
+//!
+//! Opening a remote memory only touches the 4 KB header, the commit footer, and the TOC.
+//! Index blobs (Tantivy segments, vector index) are range-read the first time a query needs
+//! them and cached for the lifetime of the handle; frame payloads are fetched one byte range
+//! at a time. Pending WAL entries are ignored: a remote reader always sees the last commit.
+
+use std::collections::HashSet;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "lex")]
+use std::time::Instant;
+
+#[cfg(feature = "lex")]
+use crate::analysis::language::normalize_language;
+use crate::constants::HEADER_SIZE;
+use crate::error::{MemvidError, Result};
+use crate::footer::{CommitFooter, FOOTER_SIZE};
+use crate::io::header::HeaderCodec;
+use crate::io::remote::RangeFetcher;
+#[cfg(feature = "lex")]
+use crate::memvid::acl::apply_acl_to_hits;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::blob_extents::{frame_blob_extents, stored_in_extents};
+use crate::types::summary::summary_track;
+#[cfg(feature = "lex")]
+use crate::types::{
+    ANALYZER_CONFIG_EXTENSION, AnalyzerConfig, SearchEngineKind, SearchHit, SearchHitMetadata,
+    SearchMode, SearchParams, SearchRequest, SearchResponse, VecRescore,
+};
+use crate::types::{
+    Frame, FrameId, FrameRole, FrameStatus, Header, TimelineEntry, TimelineQuery, Toc,
+};
+use crate::vec::{VecIndex, VecSearchHit};
+
+/// Read-only handle over a remote `.mv2` object.
+pub struct RemoteMemvid {
+    url: String,
+    fetcher: Arc<dyn RangeFetcher>,
+    header: Header,
+    toc: Toc,
+    generation: u64,
+    file_len: u64,
+    vec_index: Option<VecIndex>,
+    #[cfg(feature = "lex")]
+    tantivy: Option<crate::search::TantivyEngine>,
+    bytes_fetched: AtomicU64,
+    range_requests: AtomicU64,
+}
+
+impl Memvid {
+    /// Open a memory stored behind `fetcher` in read-only mode.
+    ///
+    /// `url` is only used for diagnostics; all IO goes through the fetcher.
+    pub fn open_remote<F>(url: impl Into<String>, fetcher: F) -> Result<RemoteMemvid>
+    where
+        F: RangeFetcher + 'static,
+    {
+        RemoteMemvid::open(url, Arc::new(fetcher))
+    }
+}
+
+impl RemoteMemvid {
+    pub fn open(url: impl Into<String>, fetcher: Arc<dyn RangeFetcher>) -> Result<Self> {
+        let file_len = fetcher.len()?;
+        check_remote_len(file_len)?;
+        let mut bytes_fetched = 0u64;
+        let mut range_requests = 0u64;
+        let mut fetch = |offset: u64, length: u64| {
+            bytes_fetched += length;
+            range_requests += 1;
+            fetch_exact(fetcher.as_ref(), file_len, offset, length)
+        };
+
+        let header = decode_remote_header(&fetch(0, HEADER_SIZE as u64)?)?;
+        let footer_offset = file_len - FOOTER_SIZE as u64;
+        let footer =
+            decode_remote_footer(footer_offset, &fetch(footer_offset, FOOTER_SIZE as u64)?)?;
+        let toc = decode_remote_toc(
+            &footer,
+            &fetch(footer_offset - footer.toc_len, footer.toc_len)?,
+        )?;
+
+        Ok(Self {
+            url: url.into(),
+            fetcher,
+            header,
+            toc,
+            generation: footer.generation,
+            file_len,
+            vec_index: None,
+            #[cfg(feature = "lex")]
+            tantivy: None,
+            bytes_fetched: AtomicU64::new(bytes_fetched),
+            range_requests: AtomicU64::new(range_requests),
+        })
+    }
+
+    fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        let bytes = fetch_exact(self.fetcher.as_ref(), self.file_len, offset, length)?;
+        self.bytes_fetched.fetch_add(length, Ordering::Relaxed);
+        self.range_requests.fetch_add(1, Ordering::Relaxed);
+        Ok(bytes)
+    }
+
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    #[must_use]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    #[must_use]
+    pub fn toc(&self) -> &Toc {
+        &self.toc
+    }
+
+    /// Commit generation recorded in the remote footer.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    #[must_use]
+    pub fn frame_count(&self) -> usize {
+        self.toc.frames.len()
+    }
+
+    /// Total bytes pulled through the fetcher since the handle was opened.
+    #[must_use]
+    pub fn bytes_fetched(&self) -> u64 {
+        self.bytes_fetched.load(Ordering::Relaxed)
+    }
+
+    /// Number of range requests issued since the handle was opened.
+    #[must_use]
+    pub fn range_requests(&self) -> u64 {
+        self.range_requests.load(Ordering::Relaxed)
+    }
+
+    pub fn frame_by_id(&self, frame_id: FrameId) -> Result<Frame> {
+        remote_frame_by_id(&self.toc, frame_id).cloned()
+    }
+
+    pub fn frame_by_uri(&self, uri: &str) -> Result<Frame> {
+        self.toc
+            .frames
+            .iter()
+            .rev()
+            .find(|frame| frame.uri.as_deref() == Some(uri) && frame.status == FrameStatus::Active)
+            .or_else(|| {
+                self.toc
+                    .frames
+                    .iter()
+                    .rev()
+                    .find(|frame| frame.uri.as_deref() == Some(uri))
+            })
+            .cloned()
+            .ok_or_else(|| MemvidError::FrameNotFoundByUri {
+                uri: uri.to_string(),
+            })
+    }
+
+    /// Decoded canonical bytes for a frame, fetched with one range request per payload.
+    pub fn frame_canonical_payload(&self, frame_id: FrameId) -> Result<Vec<u8>> {
+        let frame = self.frame_by_id(frame_id)?;
+        self.frame_canonical_bytes(&frame)
+    }
+
+    /// Full text of a frame, preferring the indexed search text (no payload fetch).
+    pub fn frame_text_by_id(&self, frame_id: FrameId) -> Result<String> {
+        let frame = self.frame_by_id(frame_id)?;
+        if let Some(text) = text_without_fetch(&frame) {
+            return Ok(text);
+        }
+        let bytes = self.frame_canonical_bytes(&frame)?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn frame_canonical_bytes(&self, frame: &Frame) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        for source in canonical_sources(&self.toc, frame)? {
+            buffer.extend_from_slice(&self.decode_payload(source)?);
+        }
+        Ok(buffer)
+    }
+
+    fn decode_payload(&self, frame: &Frame) -> Result<Vec<u8>> {
+        let Some(ranges) = payload_ranges(&self.toc, &self.header, frame)? else {
+            return Ok(Vec::new());
+        };
+        let mut raw = Vec::new();
+        for (offset, length) in ranges {
+            raw.extend(self.fetch(offset, length)?);
+        }
+        decode_remote_payload(frame, &raw)
+    }
+
+    fn frame_preview(&self, frame: &Frame) -> String {
+        if let Some(search) = &frame.search_text {
+            return crate::truncate_preview(search);
+        }
+        if frame.payload_length == 0 && !stored_in_extents(frame) {
+            return String::new();
+        }
+        match self.frame_canonical_bytes(frame) {
+            Ok(bytes) => crate::truncate_preview(&String::from_utf8_lossy(&bytes)),
+            Err(_) => "<invalid frame>".into(),
+        }
+    }
+
+    /// Chronological listing built from TOC frame metadata.
+    ///
+    /// Previews come from indexed search text when available, so listing a timeline usually
+    /// costs no payload reads.
+    pub fn timeline(&self, query: TimelineQuery) -> Result<Vec<TimelineEntry>> {
+        let mut frames: Vec<&Frame> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .filter(|frame| frame.role != FrameRole::DocumentChunk)
+            .filter(|frame| query.since.is_none_or(|since| frame.timestamp >= since))
+            .filter(|frame| query.until.is_none_or(|until| frame.timestamp <= until))
+            .collect();
+        frames.sort_by_key(|frame| (frame.timestamp, frame.id));
+        if query.reverse {
+            frames.reverse();
+        }
+        let limit = query.limit.map_or(frames.len(), |nz: NonZeroU64| {
+            usize::try_from(nz.get()).unwrap_or(usize::MAX)
+        });
+        let summaries = summary_track(&self.toc);
+        Ok(frames
+            .into_iter()
+            .take(limit)
+            .map(|frame| TimelineEntry {
+                frame_id: frame.id,
+                timestamp: frame.timestamp,
+                preview: summaries.frame(frame.id).map_or_else(
+                    || self.frame_preview(frame),
+                    |card| crate::truncate_preview(&card.text),
+                ),
+                uri: frame
+                    .uri
+                    .clone()
+                    .or_else(|| Some(crate::default_uri(frame.id))),
+                child_frames: self
+                    .toc
+                    .frames
+                    .iter()
+                    .filter(|candidate| {
+                        candidate.status == FrameStatus::Active
+                            && candidate.parent_id == Some(frame.id)
+                    })
+                    .map(|candidate| candidate.id)
+                    .collect(),
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+            })
+            .collect())
+    }
+
+    fn ensure_vec_index(&mut self) -> Result<()> {
+        if self.vec_index.is_none() {
+            if let Some((offset, length)) = vec_index_range(&self.toc) {
+                let bytes = self.fetch(offset, length)?;
+                self.vec_index = Some(VecIndex::decode(&bytes)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Nearest-neighbour search over the embedded vector index. The index blob is fetched
+    /// on first use; returns an empty list when the file has no vector index.
+    pub fn search_vec(&mut self, query: &[f32], top_k: usize) -> Result<Vec<VecSearchHit>> {
+        self.ensure_vec_index()?;
+        Ok(search_remote_vec(
+            &self.toc,
+            self.vec_index.as_ref(),
+            query,
+            top_k,
+        ))
+    }
+}
+
+/// Bounds-checked range read that rejects short responses from the fetcher.
+fn fetch_exact(
+    fetcher: &dyn RangeFetcher,
+    file_len: u64,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>> {
+    check_remote_range(file_len, offset, length)?;
+    let bytes = fetcher.fetch(offset, length)?;
+    check_fetched_len(&bytes, length)?;
+    Ok(bytes)
+}
+
+// The checks and decoding below are shared with the async handle
+// (`async_api::AsyncRemoteMemvid`); the two differ only in how they fetch ranges.
+
+/// Reject objects too small to hold a header and a commit footer.
+pub(crate) fn check_remote_len(file_len: u64) -> Result<()> {
+    if file_len < HEADER_SIZE as u64 + FOOTER_SIZE as u64 {
+        return Err(MemvidError::InvalidHeader {
+            reason: "remote object too small to be an mv2 file".into(),
+        });
+    }
+    Ok(())
+}
+
+/// Reject a range that overflows or extends past the end of the object.
+pub(crate) fn check_remote_range(file_len: u64, offset: u64, length: u64) -> Result<()> {
+    let end = offset
+        .checked_add(length)
+        .ok_or_else(|| MemvidError::InvalidToc {
+            reason: "range read overflow".into(),
+        })?;
+    if end > file_len {
+        return Err(MemvidError::InvalidToc {
+            reason: format!("range {offset}..{end} extends past remote object length {file_len}")
+                .into(),
+        });
+    }
+    Ok(())
+}
+
+/// Reject a short (or long) response from a fetcher.
+pub(crate) fn check_fetched_len(bytes: &[u8], length: u64) -> Result<()> {
+    if bytes.len() as u64 != length {
+        return Err(MemvidError::InvalidToc {
+            reason: format!(
+                "range fetcher returned {} bytes, expected {length}",
+                bytes.len()
+            )
+            .into(),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn decode_remote_header(bytes: &[u8]) -> Result<Header> {
+    let header_bytes: &[u8; HEADER_SIZE] =
+        bytes.try_into().map_err(|_| MemvidError::InvalidHeader {
+            reason: "short header read".into(),
+        })?;
+    HeaderCodec::decode(header_bytes)
+}
+
+/// Decode the commit footer read at `footer_offset` and check that the TOC it points at lies
+/// inside the object. The TOC is the `footer.toc_len` bytes just before the footer.
+pub(crate) fn decode_remote_footer(footer_offset: u64, bytes: &[u8]) -> Result<CommitFooter> {
+    let footer = CommitFooter::decode(bytes).ok_or(MemvidError::InvalidToc {
+        reason: "remote object does not end with a commit footer".into(),
+    })?;
+    if footer.toc_len == 0 || footer.toc_len > footer_offset {
+        return Err(MemvidError::InvalidToc {
+            reason: "commit footer toc length out of bounds".into(),
+        });
+    }
+    if footer.toc_len > crate::MAX_INDEX_BYTES {
+        return Err(MemvidError::InvalidToc {
+            reason: "toc region exceeds safety limit".into(),
+        });
+    }
+    Ok(footer)
+}
+
+/// Decode the TOC, checking it against the footer's hash and its own checksum.
+pub(crate) fn decode_remote_toc(footer: &CommitFooter, bytes: &[u8]) -> Result<Toc> {
+    if !footer.hash_matches(bytes) {
+        return Err(MemvidError::InvalidToc {
+            reason: "commit footer toc hash mismatch".into(),
+        });
+    }
+    let toc = Toc::decode(bytes)?;
+    toc.verify_checksum()?;
+    Ok(toc)
+}
+
+pub(crate) fn remote_frame_by_id(toc: &Toc, frame_id: FrameId) -> Result<&Frame> {
+    usize::try_from(frame_id)
+        .ok()
+        .and_then(|index| toc.frames.get(index))
+        .ok_or(MemvidError::FrameNotFound { frame_id })
+}
+
+/// A frame's text when it can be had without a payload fetch: its indexed search text, or
+/// nothing at all when it stores no payload.
+pub(crate) fn text_without_fetch(frame: &Frame) -> Option<String> {
+    if let Some(search) = frame.search_text.as_deref().filter(|s| !s.is_empty()) {
+        return Some(search.to_string());
+    }
+    (frame.payload_length == 0 && frame.chunk_manifest.is_none() && !stored_in_extents(frame))
+        .then(String::new)
+}
+
+/// Frames whose decoded payloads make up `frame`'s canonical bytes, in order: the chunks of
+/// a chunked document, else the frame itself.
+pub(crate) fn canonical_sources<'a>(toc: &'a Toc, frame: &'a Frame) -> Result<Vec<&'a Frame>> {
+    if frame.role != FrameRole::Document || frame.chunk_manifest.is_none() {
+        return Ok(vec![frame]);
+    }
+    let mut children: Vec<&Frame> = toc
+        .frames
+        .iter()
+        .filter(|candidate| {
+            candidate.status == FrameStatus::Active
+                && candidate.role == FrameRole::DocumentChunk
+                && candidate.parent_id == Some(frame.id)
+        })
+        .collect();
+    if children.is_empty() {
+        return Err(MemvidError::InvalidFrame {
+            frame_id: frame.id,
+            reason: "document chunk manifest missing children",
+        });
+    }
+    children.sort_by_key(|child| (child.chunk_index.unwrap_or(u32::MAX), child.id));
+    Ok(children)
+}
+
+/// Byte ranges holding `frame`'s stored payload, in order; `None` when it stores none.
+pub(crate) fn payload_ranges(
+    toc: &Toc,
+    header: &Header,
+    frame: &Frame,
+) -> Result<Option<Vec<(u64, u64)>>> {
+    if let Some(extents) = frame_blob_extents(toc, header, frame)? {
+        return Ok(Some(
+            extents
+                .into_iter()
+                .map(|extent| (extent.offset, extent.length))
+                .collect(),
+        ));
+    }
+    if frame.payload_length == 0 {
+        return Ok(None);
+    }
+    if frame.payload_length > crate::MAX_FRAME_BYTES {
+        return Err(MemvidError::InvalidFrame {
+            frame_id: frame.id,
+            reason: "payload length exceeds maximum",
+        });
+    }
+    Ok(Some(vec![(frame.payload_offset, frame.payload_length)]))
+}
+
+/// Decode `frame`'s payload from the concatenated bytes of its `payload_ranges`.
+pub(crate) fn decode_remote_payload(frame: &Frame, raw: &[u8]) -> Result<Vec<u8>> {
+    let decoded = crate::decode_canonical_bytes(raw, frame.canonical_encoding, frame.id)?;
+    if let Some(expected) = frame.canonical_length {
+        if decoded.len() as u64 != expected {
+            return Err(MemvidError::InvalidFrame {
+                frame_id: frame.id,
+                reason: "canonical length mismatch",
+            });
+        }
+    }
+    Ok(decoded)
+}
+
+/// Offset and length of the vector index blob, if the object has one.
+pub(crate) fn vec_index_range(toc: &Toc) -> Option<(u64, u64)> {
+    toc.indexes
+        .vec
+        .as_ref()
+        .filter(|manifest| manifest.bytes_length > 0)
+        .map(|manifest| (manifest.bytes_offset, manifest.bytes_length))
+}
+
+/// Nearest active frames to `query`; empty without a vector index.
+pub(crate) fn search_remote_vec(
+    toc: &Toc,
+    index: Option<&VecIndex>,
+    query: &[f32],
+    top_k: usize,
+) -> Vec<VecSearchHit> {
+    let Some(index) = index else {
+        return Vec::new();
+    };
+    let limit = top_k.max(1);
+    let active: HashSet<FrameId> = toc
+        .frames
+        .iter()
+        .filter(|frame| frame.status == FrameStatus::Active)
+        .map(|frame| frame.id)
+        .collect();
+    let mut hits = index.search(query, limit.saturating_mul(2));
+    hits.retain(|hit| active.contains(&hit.frame_id));
+    hits.truncate(limit);
+    hits
+}
+
+#[cfg(feature = "lex")]
+impl RemoteMemvid {
+    fn ensure_tantivy(&mut self) -> Result<()> {
+        if self.tantivy.is_none() {
+            let ranges = tantivy_segment_ranges(&self.toc);
+            if ranges.is_empty() {
+                return Ok(());
+            }
+            let mut segments = Vec::with_capacity(ranges.len());
+            for (path, offset, length) in ranges {
+                let bytes = if length > 0 {
+                    self.fetch(offset, length)?
+                } else {
+                    Vec::new()
+                };
+                segments.push((path, bytes));
+            }
+            self.tantivy = Some(open_remote_tantivy(&remote_analyzer(&self.toc)?, segments)?);
+        }
+        Ok(())
+    }
+
+    /// Lexical search over the embedded Tantivy index.
+    ///
+    /// Snippets are cut from the text stored in the index, so a query costs the segment
+    /// fetch on first use and no payload reads afterwards. ACL and classification checks
+    /// apply as they do locally; options only the local pipeline implements (filters, geo,
+    /// reranking, diversification, parent retrieval, sparse mode, cursors) fail with
+    /// [`MemvidError::InvalidQuery`] rather than being ignored.
+    pub fn search(&mut self, request: SearchRequest) -> Result<SearchResponse> {
+        let start_time = Instant::now();
+        check_remote_search(&request)?;
+        self.ensure_tantivy()?;
+        search_remote_index(&self.toc, self.tantivy.as_ref(), &request, start_time)
+    }
+}
+
+#[cfg(feature = "lex")]
+pub(crate) fn remote_analyzer(toc: &Toc) -> Result<AnalyzerConfig> {
+    Ok(toc
+        .extension::<AnalyzerConfig>(ANALYZER_CONFIG_EXTENSION)?
+        .unwrap_or_default())
+}
+
+/// Path, offset, and length of every Tantivy segment file stored in the object.
+#[cfg(feature = "lex")]
+pub(crate) fn tantivy_segment_ranges(toc: &Toc) -> Vec<(String, u64, u64)> {
+    if toc.segment_catalog.tantivy_segments.is_empty() {
+        toc.indexes
+            .lex_segments
+            .iter()
+            .map(|seg| (seg.path.clone(), seg.bytes_offset, seg.bytes_length))
+            .collect()
+    } else {
+        toc.segment_catalog
+            .tantivy_segments
+            .iter()
+            .map(|seg| {
+                (
+                    seg.path.clone(),
+                    seg.common.bytes_offset,
+                    seg.common.bytes_length,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Open a Tantivy engine over segment files fetched from a remote object.
+#[cfg(feature = "lex")]
+pub(crate) fn open_remote_tantivy(
+    analyzer: &AnalyzerConfig,
+    segments: Vec<(String, Vec<u8>)>,
+) -> Result<crate::search::TantivyEngine> {
+    let dir = tempfile::TempDir::new().map_err(|err| MemvidError::Tantivy {
+        reason: format!("failed to allocate Tantivy work directory: {err}"),
+    })?;
+    for (path, bytes) in segments {
+        let dest = dir.path().join(&path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, bytes)?;
+    }
+    let engine = crate::search::TantivyEngine::open_from_dir(dir)?;
+    engine.set_analyzer_config(analyzer);
+    Ok(engine)
+}
+
+/// Reject empty queries and options the remote lexical path cannot honour, before any index
+/// is fetched.
+#[cfg(feature = "lex")]
+pub(crate) fn check_remote_search(request: &SearchRequest) -> Result<()> {
+    if request.query.trim().is_empty() {
+        return Err(MemvidError::InvalidQuery {
+            reason: "query must not be empty".into(),
+        });
+    }
+    if let Some(field) = unsupported_remote_field(request) {
+        return Err(MemvidError::InvalidQuery {
+            reason: format!("`{field}` is not supported when searching a remote memory"),
+        });
+    }
+    Ok(())
+}
+
+/// Search a remote object's Tantivy index; no hits when the object has none.
+#[cfg(feature = "lex")]
+pub(crate) fn search_remote_index(
+    toc: &Toc,
+    engine: Option<&crate::search::TantivyEngine>,
+    request: &SearchRequest,
+    start_time: Instant,
+) -> Result<SearchResponse> {
+    use crate::lex::compute_snippet_slices;
+    use crate::memvid::search::helpers::{
+        build_context, collect_token_occurrences, highlight_spans, timestamp_to_rfc3339,
+    };
+    use crate::search::{EvaluationContext, parse_query};
+
+    let params = SearchParams {
+        top_k: request.top_k,
+        snippet_chars: request.snippet_chars,
+        cursor: request.cursor.clone(),
+        vec_rescore: VecRescore::default(),
+    };
+    let analyzer = remote_analyzer(toc)?;
+    let mut parsed = parse_query(&request.query)?;
+    parsed.remove_stopwords(&analyzer);
+    parsed.expand_synonyms(&analyzer);
+    let top_k = request.top_k.max(1);
+    let doc_limit = top_k.saturating_mul(4).max(20);
+
+    let Some(engine) = engine else {
+        return Ok(SearchResponse {
+            query: request.query.clone(),
+            elapsed_ms: start_time.elapsed().as_millis(),
+            total_hits: 0,
+            params,
+            hits: Vec::new(),
+            context: String::new(),
+            next_cursor: None,
+            engine: SearchEngineKind::Tantivy,
+            stale_index_skips: 0,
+            suggestions: Vec::new(),
+            explain: None,
+            partial: false,
+            skipped_stages: Vec::new(),
+        });
+    };
+    let stemmed: Vec<String> = parsed
+        .text_tokens()
+        .iter()
+        .flat_map(|token| engine.analyse_text(token))
+        .collect();
+    let scope = if request.uri.is_some() {
+        None
+    } else {
+        request.scope.as_deref()
+    };
+    let doc_hits = engine.search_documents(
+        &parsed,
+        request.uri.as_deref(),
+        scope,
+        None,
+        request.language.as_deref().and_then(normalize_language),
+        doc_limit,
+    )?;
+
+    let mut hits = Vec::new();
+    let mut stale_index_skips = 0u32;
+    for doc in doc_hits {
+        let Some(frame) = usize::try_from(doc.frame_id)
+            .ok()
+            .and_then(|idx| toc.frames.get(idx))
+        else {
+            stale_index_skips = stale_index_skips.saturating_add(1);
+            continue;
+        };
+        if frame.status != FrameStatus::Active {
+            continue;
+        }
+        if request.as_of_frame.is_some_and(|cutoff| frame.id > cutoff)
+            || request
+                .as_of_ts
+                .is_some_and(|cutoff| frame.timestamp > cutoff)
+        {
+            continue;
+        }
+        let text = frame.search_text.clone().unwrap_or(doc.content);
+        let text_lower = text.to_ascii_lowercase();
+        let ctx = EvaluationContext {
+            frame,
+            content_lower: &text_lower,
+        };
+        if !parsed.evaluate(&ctx) {
+            continue;
+        }
+        let occurrences = collect_token_occurrences(&text_lower, &stemmed);
+        let Some(&(start, end)) =
+            compute_snippet_slices(&text, &occurrences, request.snippet_chars.max(80), 1).first()
+        else {
+            continue;
+        };
+        let matches = occurrences
+            .iter()
+            .filter(|(s, e)| *s >= start && *e <= end)
+            .count()
+            .max(1);
+        let uri = frame
+            .uri
+            .clone()
+            .unwrap_or_else(|| crate::default_uri(frame.id));
+        hits.push(SearchHit {
+            rank: hits.len() + 1,
+            frame_id: frame.id,
+            title: frame
+                .title
+                .clone()
+                .or_else(|| crate::infer_title_from_uri(&uri)),
+            uri,
+            range: (start, end),
+            text: text[start..end].to_string(),
+            matches,
+            chunk_range: None,
+            chunk_text: None,
+            score: Some(doc.score),
+            metadata: Some(SearchHitMetadata {
+                matches,
+                tags: frame.tags.clone(),
+                labels: frame.labels.clone(),
+                track: frame.track.clone(),
+                created_at: timestamp_to_rfc3339(frame.timestamp),
+                content_dates: frame.content_dates.clone(),
+                entities: Vec::new(),
+                related: Vec::new(),
+                extra_metadata: frame.extra_metadata.clone(),
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+            }),
+            highlights: highlight_spans(
+                &text,
+                &occurrences,
+                0,
+                request.snippet_chars.max(80),
+                request.highlight_windows,
+            ),
+        });
+        if hits.len() == top_k {
+            break;
+        }
+    }
+    apply_acl_to_hits(
+        toc,
+        &mut hits,
+        request.acl_context.as_ref(),
+        request.acl_enforcement_mode,
+    )?;
+
+    Ok(SearchResponse {
+        query: request.query.clone(),
+        elapsed_ms: start_time.elapsed().as_millis().max(1),
+        total_hits: hits.len(),
+        params,
+        context: build_context(&hits),
+        hits,
+        next_cursor: None,
+        engine: SearchEngineKind::Tantivy,
+        stale_index_skips,
+        suggestions: Vec::new(),
+        explain: None,
+        partial: false,
+        skipped_stages: Vec::new(),
+    })
+}
+
+/// The first request field set to something the remote lexical path cannot honour.
+#[cfg(feature = "lex")]
+fn unsupported_remote_field(request: &SearchRequest) -> Option<&'static str> {
+    let unsupported = [
+        ("cursor", request.cursor.is_some()),
+        #[cfg(feature = "temporal_track")]
+        ("temporal", request.temporal.is_some()),
+        ("rerank", request.rerank.is_some()),
+        ("geo", request.geo.is_some()),
+        ("filters", !request.filters.is_empty()),
+        ("access_boost", request.access_boost),
+        ("diversify", request.diversify.is_some()),
+        ("group_by_parent", request.group_by_parent),
+        ("return_parents", request.return_parents),
+        ("explain", request.explain),
+        ("mode", request.mode != SearchMode::Lexical),
+        ("time_budget_ms", request.time_budget_ms.is_some()),
+    ];
+    unsupported
+        .into_iter()
+        .find_map(|(field, set)| set.then_some(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::remote::FileRangeFetcher;
+    use crate::types::PutOptions;
+    use tempfile::tempdir;
+
+    #[test]
+    fn remote_open_reads_only_metadata() {
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("remote.mv2");
+        {
+            let mut mem = Memvid::create(&path).expect("create");
+            mem.enable_lex().expect("lex");
+            for (idx, text) in ["alpha remote payload", "beta remote payload"]
+                .iter()
+                .enumerate()
+            {
+                let opts = PutOptions::builder()
+                    .uri(format!("mv2://doc/{idx}"))
+                    .timestamp(1_700_000_000 + idx as i64)
+                    .build();
+                mem.put_bytes_with_options(text.as_bytes(), opts)
+                    .expect("put");
+            }
+            mem.commit().expect("commit");
+        }
+        let file_len = std::fs::metadata(&path).expect("meta").len();
+
+        let fetcher = FileRangeFetcher::open(&path).expect("fetcher");
+        let remote = Memvid::open_remote(path.display().to_string(), fetcher).expect("remote");
+        assert_eq!(remote.frame_count(), 2);
+        assert!(remote.bytes_fetched() < file_len);
+
+        let frame = remote.frame_by_uri("mv2://doc/1").expect("frame");
+        let payload = remote.frame_canonical_payload(frame.id).expect("payload");
+        assert_eq!(payload, b"beta remote payload");
+
+        let timeline = remote
+            .timeline(TimelineQuery::builder().reverse(true).build())
+            .expect("timeline");
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[0].frame_id, frame.id);
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn remote_search_fetches_lex_segments_lazily() {
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("remote-search.mv2");
+        {
+            let mut mem = Memvid::create(&path).expect("create");
+            mem.enable_lex().expect("lex");
+            mem.put_bytes(b"the quick brown fox").expect("put");
+            mem.put_bytes(b"lazy dogs sleep all day").expect("put");
+            mem.commit().expect("commit");
+        }
+
+        let fetcher = FileRangeFetcher::open(&path).expect("fetcher");
+        let mut remote = Memvid::open_remote("file://test", fetcher).expect("remote");
+        let before = remote.bytes_fetched();
+        let response = remote.search(search_request("fox")).expect("search");
+        assert_eq!(response.hits.len(), 1);
+        assert!(response.hits[0].text.contains("fox"));
+        assert!(remote.bytes_fetched() > before);
+    }
+
+    #[cfg(feature = "lex")]
+    fn search_request(query: &str) -> SearchRequest {
+        SearchRequest {
+            query: query.into(),
+            top_k: 5,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        }
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn remote_search_applies_acl_and_rejects_unsupported_fields() {
+        use crate::types::{AclContext, Classification, ClassificationPolicy};
+
+        let dir = tempdir().expect("tmp");
+        let path = dir.path().join("remote-acl.mv2");
+        {
+            let mut mem = Memvid::create(&path).expect("create");
+            mem.enable_lex().expect("lex");
+            for (uri, classification) in [
+                ("mv2://work/roadmap", Classification::Internal),
+                ("mv2://personal/diary", Classification::Secret),
+            ] {
+                let options = PutOptions::builder()
+                    .uri(uri)
+                    .classification(classification)
+                    .build();
+                mem.put_bytes_with_options(b"quarterly plans", options)
+                    .expect("put");
+            }
+            mem.commit().expect("commit");
+        }
+
+        let fetcher = FileRangeFetcher::open(&path).expect("fetcher");
+        let mut remote = Memvid::open_remote("file://acl", fetcher).expect("remote");
+        let cleared = remote
+            .search(SearchRequest {
+                acl_context: Some(AclContext {
+                    classification: Some(ClassificationPolicy::clearance(Classification::Internal)),
+                    ..AclContext::default()
+                }),
+                ..search_request("quarterly")
+            })
+            .expect("search");
+        let uris: Vec<&str> = cleared.hits.iter().map(|hit| hit.uri.as_str()).collect();
+        assert_eq!(uris, ["mv2://work/roadmap"]);
+
+        let err = remote
+            .search(SearchRequest {
+                return_parents: true,
+                ..search_request("quarterly")
+            })
+            .expect_err("unsupported");
+        assert!(
+            matches!(&err, MemvidError::InvalidQuery { reason } if reason.contains("return_parents")),
+            "{err}"
+        );
+        assert!(
+            remote
+                .search(SearchRequest {
+                    mode: SearchMode::Sparse,
+                    ..search_request("quarterly")
+                })
+                .is_err()
+        );
+    }
+}
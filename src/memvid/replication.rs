@@ -0,0 +1,471 @@
+//! Leader → follower replication via [`DeltaBundle`]s.
+//!
+//! `export_delta` reads the leader's last committed state from disk and packages the payloads
+//! of frames inserted after the follower's generation together with the index and track blobs
+//! (plus snapshot archives and staged migration segments) written after that generation.
+//! Index blobs are rewritten in place, so every TOC write stamps each blob's
+//! `(offset, length, checksum)` with the generation that last changed it. `apply_delta` writes
+//! those ranges into a staging copy of the follower, installs the TOC and footer, swaps the
+//! copy in, and reloads the handle in place. Existing frame payloads never move on a normal
+//! commit, so they are not shipped; if the leader was compacted or rebuilt, the follower
+//! detects the mismatch and asks for a full resync instead of corrupting itself.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use blake3::hash;
+
+use crate::error::{MemvidError, Result};
+use crate::footer::CommitFooter;
+use crate::memvid::lifecycle::{
+    Memvid, detect_generation, prepare_toc_bytes, read_toc, reserved_payload_ranges,
+};
+use crate::types::replication::{BlobFingerprint, BlobGenerations};
+use crate::types::sparse::sparse_track_manifest;
+use crate::types::token_track::token_track_manifest;
+use crate::types::{
+    BLOB_GENERATIONS_EXTENSION, COMMIT_LOG_EXTENSION, CommitEvent, CommitLog, DeltaBundle,
+    DeltaRange, Header, Toc,
+};
+
+fn replication_error(reason: impl Into<String>) -> MemvidError {
+    MemvidError::Replication {
+        reason: reason.into(),
+    }
+}
+
+impl Memvid {
+    /// Record which tracked blobs the TOC about to be written changed, under the current
+    /// generation. Must run right before the TOC is serialized.
+    pub(crate) fn stamp_blob_generations(&mut self) -> Result<()> {
+        let previous = self
+            .toc
+            .extension::<BlobGenerations>(BLOB_GENERATIONS_EXTENSION)?;
+        let current = tracked_blobs(&self.toc, &self.header);
+        let stamps = BlobGenerations::restamp(previous.as_ref(), &current, self.generation);
+        self.toc.set_extension(BLOB_GENERATIONS_EXTENSION, &stamps)
+    }
+}
+
+/// Sort `(offset, length)` pairs and merge overlapping or touching ranges into `(start, end)`.
+fn coalesce(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.retain(|&(_, len)| len > 0);
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (offset, len) in ranges {
+        let end = offset.saturating_add(len);
+        match merged.last_mut() {
+            Some((_, last_end)) if offset <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((offset, end)),
+        }
+    }
+    merged
+}
+
+/// Fingerprints of every blob that [`BlobGenerations`] tracks: checksummed index, track and
+/// segment blobs, plus reserved payload blobs. The latter live in the append-only payload
+/// region and never change in place, so their offset and length identify them.
+fn tracked_blobs(toc: &Toc, header: &Header) -> Vec<BlobFingerprint> {
+    let mut blobs = toc.blob_fingerprints();
+    blobs.extend(sparse_track_manifest(toc).map(|manifest| {
+        (
+            manifest.bytes_offset,
+            manifest.bytes_length,
+            manifest.checksum,
+        )
+    }));
+    blobs.extend(token_track_manifest(toc).map(|manifest| {
+        (
+            manifest.bytes_offset,
+            manifest.bytes_length,
+            manifest.checksum,
+        )
+    }));
+    blobs.extend(
+        reserved_payload_ranges(toc, header)
+            .into_iter()
+            .map(|(offset, len)| (offset, len, [0u8; 32])),
+    );
+    blobs.retain(|(_, len, _)| *len > 0);
+    blobs
+}
+
+/// Commit events newer than `since` from a TOC read off disk.
+fn events_since(toc: &Toc, since: u64, generation: u64) -> Result<Vec<CommitEvent>> {
+    if since == generation {
+        return Ok(Vec::new());
+    }
+    match toc.extension::<CommitLog>(COMMIT_LOG_EXTENSION)? {
+        Some(log) => log.since(since).ok_or(MemvidError::CommitLogTruncated {
+            requested: since,
+            oldest: log.resumable_from(),
+        }),
+        // Frames written before the ring existed cannot be attributed to a generation.
+        None if !toc.frames.is_empty() => Err(MemvidError::CommitLogTruncated {
+            requested: since,
+            oldest: generation,
+        }),
+        None => Ok(Vec::new()),
+    }
+}
+
+impl Memvid {
+    /// Package everything committed after `since_generation` for a follower at that generation.
+    ///
+    /// Only the last durable commit is exported; uncommitted puts on this handle are not
+    /// included. Fails with [`MemvidError::CommitLogTruncated`] when the commit log no longer
+    /// reaches back to `since_generation`, in which case the follower needs a full copy.
+    pub fn export_delta(&self, since_generation: u64) -> Result<DeltaBundle> {
+        let mut file = self.file.try_clone()?;
+        let header: Header = self.header.clone();
+        let mut toc = read_toc(&mut file, &header)?;
+        let generation = detect_generation(&file)?.unwrap_or(0);
+        if since_generation > generation {
+            return Err(replication_error(format!(
+                "follower generation {since_generation} is ahead of leader generation {generation}"
+            )));
+        }
+        let events = events_since(&toc, since_generation, generation)?;
+
+        let mut wanted: Vec<(u64, u64)> = events
+            .iter()
+            .flat_map(|event| event.inserted_frames.iter())
+            .filter_map(|&frame_id| usize::try_from(frame_id).ok())
+            .filter_map(|index| toc.frames.get(index))
+            .map(|frame| (frame.payload_offset, frame.payload_length))
+            .collect();
+        // Blobs stamped at or before the follower's generation are already on its disk.
+        // Files written before stamping existed have no record, so everything ships.
+        let written = toc
+            .extension::<BlobGenerations>(BLOB_GENERATIONS_EXTENSION)?
+            .map(|stamps| stamps.by_fingerprint())
+            .unwrap_or_default();
+        let tracked = tracked_blobs(&toc, &header);
+        wanted.extend(
+            tracked
+                .iter()
+                .filter(|blob| written.get(*blob).is_none_or(|&at| at > since_generation))
+                .map(|&(offset, len, _)| (offset, len)),
+        );
+        // Blobs without a checksum (the replay segment) cannot be compared and always ship.
+        wanted.extend(
+            toc.blob_ranges_mut()
+                .into_iter()
+                .map(|(offset, len)| (*offset, len))
+                .filter(|&(offset, len)| !tracked.iter().any(|&(o, l, _)| o == offset && l == len)),
+        );
+
+        let mut ranges = Vec::new();
+        for (start, end) in coalesce(wanted) {
+            let len = usize::try_from(end - start)
+                .map_err(|_| replication_error("delta range exceeds addressable memory"))?;
+            let mut bytes = vec![0u8; len];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut bytes)?;
+            ranges.push(DeltaRange {
+                offset: start,
+                bytes,
+            });
+        }
+
+        let toc_bytes = prepare_toc_bytes(&mut toc)?;
+        Ok(DeltaBundle {
+            base_generation: since_generation,
+            target_generation: generation,
+            header,
+            ranges,
+            toc_bytes,
+            events,
+        })
+    }
+
+    /// Bring this follower up to the bundle's target generation.
+    ///
+    /// The follower must be at exactly `bundle.base_generation` with no uncommitted changes,
+    /// and must share the leader's data region start and existing frame payloads; where either
+    /// WAL lives does not matter. The bundle is applied to a staging copy and swapped in like
+    /// a commit. Events carried by the bundle are delivered to this handle's subscribers once
+    /// the new state is durable.
+    pub fn apply_delta(&mut self, bundle: &DeltaBundle) -> Result<()> {
+        self.ensure_writable()?;
+        if self.dirty || self.pending_frame_inserts > 0 || self.wal.stats().pending_bytes > 0 {
+            return Err(replication_error(
+                "follower has uncommitted changes; replicas must not be written to directly",
+            ));
+        }
+        if bundle.base_generation != self.generation {
+            return Err(replication_error(format!(
+                "follower is at generation {}, delta starts at {}",
+                self.generation, bundle.base_generation
+            )));
+        }
+        if bundle.is_noop() {
+            return Ok(());
+        }
+        let leader = &bundle.header;
+        if leader.data_start != self.header.data_start {
+            return Err(replication_error(
+                "leader data region starts elsewhere; a full resync is required",
+            ));
+        }
+
+        let toc = Toc::decode(&bundle.toc_bytes)?;
+        toc.verify_checksum()?;
+        if toc.frames.len() < self.toc.frames.len() {
+            return Err(replication_error(
+                "leader has fewer frames than follower; a full resync is required",
+            ));
+        }
+        for (ours, theirs) in self.toc.frames.iter().zip(&toc.frames) {
+            if ours.payload_offset != theirs.payload_offset
+                || ours.payload_length != theirs.payload_length
+                || ours.checksum != theirs.checksum
+            {
+                return Err(replication_error(format!(
+                    "frame {} payload moved on the leader (compaction or rebuild); a full resync is required",
+                    ours.id
+                )));
+            }
+        }
+        let data_start = leader.data_start;
+        if let Some(range) = bundle
+            .ranges
+            .iter()
+            .find(|range| range.offset < data_start || range.end() > leader.footer_offset)
+        {
+            return Err(replication_error(format!(
+                "delta range at offset {} falls outside the data region",
+                range.offset
+            )));
+        }
+
+        // Staged like a commit, so a crash mid-apply leaves the follower at its old generation.
+        self.with_staging_lock(|mem| {
+            for range in &bundle.ranges {
+                mem.file.seek(SeekFrom::Start(range.offset))?;
+                mem.file.write_all(&range.bytes)?;
+            }
+            mem.file.seek(SeekFrom::Start(leader.footer_offset))?;
+            mem.file.write_all(&bundle.toc_bytes)?;
+            let footer = CommitFooter {
+                toc_len: bundle.toc_bytes.len() as u64,
+                toc_hash: *hash(&bundle.toc_bytes).as_bytes(),
+                generation: bundle.target_generation,
+            };
+            let encoded_footer = footer.encode();
+            mem.file.write_all(&encoded_footer)?;
+            let end =
+                leader.footer_offset + bundle.toc_bytes.len() as u64 + encoded_footer.len() as u64;
+            mem.file.set_len(end.max(leader.wal_end()))?;
+
+            // The leader's WAL extent is the one region its layout keeps free of data and TOC,
+            // so our own (empty) WAL moves there; our sequence carries on.
+            let mut header = leader.clone();
+            header.wal_offset = mem.header.wal_offset;
+            header.wal_size = mem.header.wal_size;
+            header.wal_checkpoint_pos = mem.header.wal_checkpoint_pos;
+            header.wal_sequence = mem.header.wal_sequence;
+            header.toc_checksum = toc.toc_checksum;
+            mem.wal
+                .relocate(&mut header, leader.wal_offset, leader.wal_size)?;
+            crate::persist_header(&mut mem.file, &header)?;
+            mem.header = header;
+            Ok(())
+        })?;
+
+        self.reload_from_disk()?;
+        for event in &bundle.events {
+            self.commit_subscribers
+                .retain(|sender| sender.send(event.clone()).is_ok());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PutOptions, SearchRequest};
+
+    fn search_hits(mem: &mut Memvid, query: &str) -> usize {
+        mem.search(SearchRequest {
+            query: query.into(),
+            top_k: 10,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .expect("search")
+        .hits
+        .len()
+    }
+
+    #[test]
+    #[cfg(feature = "lex")]
+    fn follower_catches_up_from_delta() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let leader_path = dir.path().join("leader.mv2");
+        let follower_path = dir.path().join("follower.mv2");
+
+        let mut leader = Memvid::create(&leader_path).expect("create");
+        leader.enable_lex().expect("lex");
+        leader.put_bytes(b"alpha replication seed").expect("put");
+        leader.commit().expect("commit");
+        std::fs::copy(&leader_path, &follower_path).expect("copy");
+
+        let mut follower = Memvid::open(&follower_path).expect("open follower");
+        let subscription = follower.subscribe();
+        let base = follower.generation();
+        assert_eq!(base, leader.generation());
+
+        leader
+            .put_bytes_with_options(b"bravo shipped payload", PutOptions::default())
+            .expect("put");
+        leader.put_bytes(b"charlie shipped payload").expect("put");
+        leader.commit().expect("commit");
+
+        let bundle = DeltaBundle::decode(
+            &leader
+                .export_delta(base)
+                .expect("export")
+                .encode()
+                .expect("encode"),
+        )
+        .expect("decode");
+        assert_eq!(bundle.target_generation, leader.generation());
+        follower.apply_delta(&bundle).expect("apply");
+
+        assert_eq!(follower.generation(), leader.generation());
+        assert_eq!(follower.frame_count(), 3);
+        assert!(
+            follower
+                .frame_text_by_id(2)
+                .expect("text")
+                .starts_with("charlie shipped payload")
+        );
+        assert_eq!(search_hits(&mut follower, "shipped"), 2);
+        let event = subscription.try_next().expect("replicated event");
+        assert_eq!(event.inserted_frames, vec![1, 2]);
+        drop(follower);
+
+        let mut reopened = Memvid::open(&follower_path).expect("reopen follower");
+        assert_eq!(reopened.frame_count(), 3);
+        assert_eq!(search_hits(&mut reopened, "bravo"), 1);
+
+        let err = reopened.apply_delta(&bundle).expect_err("stale base");
+        assert!(matches!(err, MemvidError::Replication { .. }));
+    }
+
+    #[test]
+    fn follower_applies_delta_after_leader_wal_relocates() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let leader_path = dir.path().join("leader.mv2");
+        let follower_path = dir.path().join("follower.mv2");
+
+        let mut leader = Memvid::create(&leader_path).expect("create");
+        leader.put_bytes(b"harbor seed note").expect("put");
+        leader.commit().expect("commit");
+        std::fs::copy(&leader_path, &follower_path).expect("copy");
+        let mut follower = Memvid::open(&follower_path).expect("open follower");
+        let base = follower.generation();
+
+        let mut state = 0x9E37_79B9_u32;
+        let noise: Vec<u8> = (0..256 * 1024)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                state.to_le_bytes()[3]
+            })
+            .collect();
+        leader.put_bytes(&noise).expect("put");
+        leader.commit().expect("commit");
+        assert!(
+            leader.header.wal_relocated(),
+            "routine growth moved the WAL"
+        );
+
+        let bundle = leader.export_delta(base).expect("export");
+        follower.apply_delta(&bundle).expect("apply");
+        assert_eq!(follower.generation(), leader.generation());
+        assert_eq!(
+            (follower.header.wal_offset, follower.header.wal_size),
+            (leader.header.wal_offset, leader.header.wal_size)
+        );
+        let payload = leader.frame_canonical_payload(1).expect("leader payload");
+        drop(follower);
+
+        let mut reopened = Memvid::open(&follower_path).expect("reopen follower");
+        assert_eq!(reopened.frame_count(), leader.frame_count());
+        assert_eq!(
+            reopened.frame_canonical_payload(1).expect("payload"),
+            payload
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lex")]
+    fn delta_without_index_changes_carries_no_index_bytes() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let leader_path = dir.path().join("leader.mv2");
+        let follower_path = dir.path().join("follower.mv2");
+
+        let mut leader = Memvid::create(&leader_path).expect("create");
+        leader.enable_lex().expect("lex");
+        leader.put_bytes(b"delta seed note").expect("put");
+        leader.commit().expect("commit");
+        std::fs::copy(&leader_path, &follower_path).expect("copy");
+        let mut follower = Memvid::open(&follower_path).expect("open follower");
+        let base = follower.generation();
+
+        leader.set_commit_log_capacity(64).expect("capacity");
+        leader.commit().expect("commit");
+        assert!(leader.generation() > base);
+
+        let bundle = leader.export_delta(base).expect("export");
+        let mut toc = leader.toc.clone();
+        let index_ranges: Vec<(u64, u64)> = toc
+            .blob_ranges_mut()
+            .into_iter()
+            .map(|(offset, len)| (*offset, offset.saturating_add(len)))
+            .collect();
+        assert!(!index_ranges.is_empty());
+        for range in &bundle.ranges {
+            assert!(
+                index_ranges
+                    .iter()
+                    .all(|&(start, end)| range.end() <= start || range.offset >= end),
+                "range at {} overlaps an index blob",
+                range.offset
+            );
+        }
+        assert_eq!(bundle.range_bytes(), 0);
+
+        follower.apply_delta(&bundle).expect("apply");
+        assert_eq!(follower.generation(), leader.generation());
+        assert_eq!(search_hits(&mut follower, "seed"), 1);
+
+        leader.put_bytes(b"delta second note").expect("put");
+        leader.commit().expect("commit");
+        let bundle = leader.export_delta(follower.generation()).expect("export");
+        follower.apply_delta(&bundle).expect("apply");
+        assert_eq!(search_hits(&mut follower, "delta"), 2);
+    }
+}
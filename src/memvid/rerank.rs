@@ -0,0 +1,277 @@
+//! Second-stage reranking of search hits.
+//!
+//! A search with `SearchRequest::rerank` set retrieves `max_candidates` hits with the normal
+//! engine, scores them with the reranker installed on the handle, and keeps the best
+//! `min(top_k, config.top_k)` hits above `min_score`. When retrieval spends the request's
+//! `time_budget_ms`, the reranker is skipped and the engine's best hits are returned. Rerankers
+//! live in `crate::rerank` behind the `vec` and `api_embed` features; any other model can be
+//! installed by implementing [`Reranker`]. `RerankerKind::LateInteraction` scores with the
+//! installed token encoder instead (see `memvid::late_interaction`).
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::search::helpers::{budget_spent, build_context};
+use crate::types::reranker::{Reranker, RerankerConfig, RerankerDocument, RerankerKind};
+use crate::types::{SearchHit, SearchRequest, SearchResponse, SkippedStage};
+
+impl Memvid {
+    /// Install the reranker used by searches that set `SearchRequest::rerank`.
+    pub fn set_reranker(&mut self, reranker: Arc<dyn Reranker>) {
+        self.reranker = Some(reranker);
+    }
+
+    /// Remove the installed reranker, returning it.
+    pub fn clear_reranker(&mut self) -> Option<Arc<dyn Reranker>> {
+        self.reranker.take()
+    }
+
+    /// The installed reranker, if any.
+    #[must_use]
+    pub fn reranker(&self) -> Option<&Arc<dyn Reranker>> {
+        self.reranker.as_ref()
+    }
+
+    /// Retrieve `config.max_candidates` hits and rerank them.
+    pub(crate) fn search_reranked(
+        &mut self,
+        mut request: SearchRequest,
+        config: &RerankerConfig,
+    ) -> Result<SearchResponse> {
+        // Late interaction scores with the token encoder once the candidates are known.
+        let installed = if config.kind == RerankerKind::LateInteraction {
+            self.late_interaction_encoder()?;
+            None
+        } else {
+            Some(
+                self.reranker
+                    .clone()
+                    .ok_or_else(|| MemvidError::RerankFailed {
+                        reason: "search requested reranking but no reranker is installed".into(),
+                    })?,
+            )
+        };
+        let start = Instant::now();
+        let top_k = request.top_k.min(config.top_k);
+        let query = request.query.clone();
+        let time_budget_ms = request.time_budget_ms;
+        request.top_k = request.top_k.max(config.max_candidates);
+        request.rerank = None;
+
+        let mut response = self.search_unrecorded(request)?;
+        let stage = Instant::now();
+        if budget_spent(time_budget_ms, start) {
+            // Out of time: keep the engine's best hits
+            response.hits.truncate(top_k);
+            response.skip_stage(SkippedStage::Rerank);
+        } else {
+            let reranker: Arc<dyn Reranker> = if let Some(reranker) = installed {
+                reranker
+            } else {
+                response.hits.truncate(config.max_candidates);
+                let encoder = self.late_interaction_encoder()?;
+                Arc::new(self.late_interaction_scorer(encoder, &response.hits))
+            };
+            rerank_hits(reranker.as_ref(), &query, &mut response.hits, config, top_k)?;
+        }
+        response.params.top_k = top_k;
+        // Reordered hits no longer line up with the engine's page boundaries.
+        response.params.cursor = None;
+        response.next_cursor = None;
+        response.context = build_context(&response.hits);
+        if let Some(explain) = response.explain.as_mut() {
+            explain.align_hits(&response.hits);
+            explain.record_stage("rerank", stage.elapsed());
+        }
+        response.elapsed_ms = start.elapsed().as_millis();
+        Ok(response)
+    }
+}
+
+/// Reorder `hits` by reranker score, keeping at most `top_k` hits scoring `config.min_score` or
+/// more. Scores replace the engine's and ranks are renumbered from 1.
+pub(crate) fn rerank_hits(
+    reranker: &dyn Reranker,
+    query: &str,
+    hits: &mut Vec<SearchHit>,
+    config: &RerankerConfig,
+    top_k: usize,
+) -> Result<()> {
+    hits.truncate(config.max_candidates);
+    if hits.is_empty() || top_k == 0 {
+        hits.clear();
+        return Ok(());
+    }
+
+    // Ids are hit positions: one frame can contribute several snippets.
+    let documents: Vec<RerankerDocument> = hits
+        .iter()
+        .enumerate()
+        .map(|(index, hit)| {
+            let text = hit.chunk_text.as_deref().unwrap_or(&hit.text);
+            let id = index as u64;
+            if config.use_metadata {
+                let metadata = hit.title.as_deref().unwrap_or(&hit.uri);
+                RerankerDocument::with_metadata(id, text, metadata)
+            } else {
+                RerankerDocument::new(id, text)
+            }
+        })
+        .collect();
+    let results = reranker.rerank(query, &documents, top_k)?;
+
+    let mut slots: Vec<Option<SearchHit>> = std::mem::take(hits).into_iter().map(Some).collect();
+    for result in results {
+        if hits.len() == top_k {
+            break;
+        }
+        if result.score < config.min_score {
+            continue;
+        }
+        let slot = usize::try_from(result.id)
+            .ok()
+            .and_then(|index| slots.get_mut(index))
+            .ok_or_else(|| MemvidError::RerankFailed {
+                reason: format!(
+                    "{} returned unknown document id {}",
+                    reranker.kind(),
+                    result.id
+                )
+                .into(),
+            })?;
+        if let Some(mut hit) = slot.take() {
+            hit.score = Some(result.score);
+            hit.rank = hits.len() + 1;
+            hits.push(hit);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "lex"))]
+mod tests {
+    use super::*;
+    use crate::types::PutOptions;
+    use crate::types::reranker::RerankerResult;
+
+    /// Prefers documents mentioning "second", which BM25 ranks below the `rust`-heavy one.
+    struct KeywordReranker;
+
+    impl Reranker for KeywordReranker {
+        fn kind(&self) -> &'static str {
+            "keyword"
+        }
+
+        fn rerank(
+            &self,
+            _query: &str,
+            documents: &[RerankerDocument],
+            top_k: usize,
+        ) -> Result<Vec<RerankerResult>> {
+            let mut results: Vec<RerankerResult> = documents
+                .iter()
+                .enumerate()
+                .map(|(rank, document)| RerankerResult {
+                    id: document.id,
+                    score: if document.text.contains("second") {
+                        0.9
+                    } else {
+                        0.1
+                    },
+                    original_rank: rank + 1,
+                    new_rank: 0,
+                })
+                .collect();
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+            for (rank, result) in results.iter_mut().enumerate() {
+                result.new_rank = rank + 1;
+            }
+            results.truncate(top_k);
+            Ok(results)
+        }
+    }
+
+    #[test]
+    fn search_reranks_candidates_with_installed_reranker() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("rerank.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        for (uri, text) in [
+            ("mv2://a", "rust rust rust compiler notes"),
+            ("mv2://b", "rust second opinion on the borrow checker"),
+            ("mv2://c", "rust release schedule"),
+        ] {
+            let options = PutOptions {
+                uri: Some(uri.into()),
+                ..Default::default()
+            };
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let request = SearchRequest {
+            query: "rust".into(),
+            top_k: 2,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: Some(RerankerConfig {
+                min_score: 0.5,
+                ..RerankerConfig::default()
+            }),
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        };
+        assert!(matches!(
+            mem.search(request.clone()),
+            Err(MemvidError::RerankFailed { .. })
+        ));
+
+        mem.set_reranker(Arc::new(KeywordReranker));
+        let response = mem.search(request.clone()).expect("search");
+        assert_eq!(response.hits.len(), 1);
+        assert_eq!(response.hits[0].uri, "mv2://b");
+        assert_eq!(response.hits[0].rank, 1);
+        assert_eq!(response.hits[0].score, Some(0.9));
+        assert_eq!(response.params.top_k, 2);
+        assert!(!response.partial);
+
+        // With the budget spent by retrieval the engine's hits are kept as they are.
+        let response = mem
+            .search(SearchRequest {
+                time_budget_ms: Some(0),
+                ..request
+            })
+            .expect("search");
+        assert!(response.partial);
+        assert!(response.skipped_stages.contains(&SkippedStage::Rerank));
+        assert_eq!(response.hits.len(), 2);
+        assert!(
+            response
+                .hits
+                .iter()
+                .all(|hit| hit.score != Some(0.9) && hit.score != Some(0.1))
+        );
+    }
+}
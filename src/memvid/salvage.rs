@@ -0,0 +1,372 @@
+//! Salvage readable frames out of a memory whose TOC and footers are damaged.
+//!
+//! Frames are gathered from three places, in order of how much they preserve: a table of
+//! contents that still decodes (possibly an older one found by scanning for footers), the
+//! WAL region, and finally a raw scan of the file for zstd-compressed payloads. Content seen
+//! twice is copied once. Everything recovered is written into a fresh file with `put`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::constants::{HEADER_SIZE, WAL_OFFSET};
+use crate::error::{MemvidError, Result};
+use crate::io::header::HeaderCodec;
+use crate::io::wal::EmbeddedWal;
+use crate::memvid::lifecycle::{Memvid, read_toc, recover_toc, referenced_byte_ranges};
+use crate::memvid::mutation::{FrameWalOp, WalEntryData, decode_wal_frame};
+use crate::types::{
+    Frame, FrameId, FrameRole, FrameStatus, PutOptions, SalvageReport, SalvageSource,
+    SalvagedFrame, Toc, UnrecoverableRange,
+};
+
+/// zstd frame magic number, little-endian.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+struct Candidate {
+    frame: SalvagedFrame,
+    content: Vec<u8>,
+    options: PutOptions,
+}
+
+impl Memvid {
+    /// Copy every frame that can still be read from `source` into a new memory at `dest`.
+    ///
+    /// Works when `open` and `doctor` cannot: the header, TOC, and every footer may be
+    /// damaged. Frames listed in a readable TOC or WAL record keep their URI, title, tags, and
+    /// timestamps; payloads found only by scanning keep their text alone. The report lists
+    /// what was recovered and the byte ranges that yielded nothing. `dest` must not exist.
+    pub fn salvage<P: AsRef<Path>, Q: AsRef<Path>>(source: P, dest: Q) -> Result<SalvageReport> {
+        let source = source.as_ref();
+        let dest = dest.as_ref();
+        if dest.exists() {
+            return Err(MemvidError::Doctor {
+                reason: format!("salvage destination {} already exists", dest.display()),
+            });
+        }
+
+        let mut file = File::open(source)?;
+        // Safety: read-only mapping; the damaged file is never written.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let header = HeaderCodec::read(&mut file).ok();
+        let toc = header
+            .as_ref()
+            .and_then(|header| read_toc(&mut file, header).ok())
+            .or_else(|| {
+                let hint = header.as_ref().map(|header| header.footer_offset);
+                recover_toc(&mut file, hint).ok().map(|(toc, _)| toc)
+            });
+
+        let mut report = SalvageReport {
+            source_path: source.to_path_buf(),
+            dest_path: dest.to_path_buf(),
+            toc_found: toc.is_some(),
+            ..SalvageReport::default()
+        };
+        let mut candidates = Vec::new();
+        let mut seen = HashSet::new();
+        let mut claimed = Vec::new();
+
+        if let Some(toc) = &toc {
+            salvage_toc_frames(&mmap, toc, &mut candidates, &mut report.unrecoverable);
+            if let Some(header) = &header {
+                claimed = referenced_byte_ranges(toc, header);
+            }
+        }
+
+        // Records at or below the checkpoint are already in a readable TOC; replaying them
+        // could resurrect frames deleted since.
+        let (wal_offset, wal_end, checkpoint) = match &header {
+            Some(header) => (
+                header.wal_offset,
+                header.wal_offset.saturating_add(header.wal_size),
+                if toc.is_some() {
+                    header.wal_sequence
+                } else {
+                    0
+                },
+            ),
+            None => (WAL_OFFSET, mmap.len() as u64, 0),
+        };
+        let walked = salvage_wal_records(&mmap, wal_offset, wal_end, checkpoint, &mut candidates);
+        // Without a header the region's size is unknown; only the records walked are claimed.
+        let wal_end = if header.is_some() { wal_end } else { walked };
+        claimed.push((wal_offset, wal_end.saturating_sub(wal_offset)));
+
+        if toc.is_none() {
+            claimed.push((0, HEADER_SIZE as u64));
+            scan_payloads(&mmap, &claimed, &mut candidates, &mut report.unrecoverable);
+        }
+
+        let mut mem = Memvid::create(dest)?;
+        for candidate in candidates {
+            if !seen.insert(blake3::hash(&candidate.content)) {
+                continue;
+            }
+            match mem.put_bytes_with_options(&candidate.content, candidate.options) {
+                Ok(_) => report.recovered.push(candidate.frame),
+                Err(err) => report.unrecoverable.push(UnrecoverableRange {
+                    offset: candidate.frame.offset,
+                    length: candidate.content.len() as u64,
+                    reason: format!("failed to write salvaged frame: {err}"),
+                }),
+            }
+        }
+        mem.commit()?;
+        Ok(report)
+    }
+}
+
+fn salvage_toc_frames(
+    mmap: &[u8],
+    toc: &Toc,
+    candidates: &mut Vec<Candidate>,
+    unrecoverable: &mut Vec<UnrecoverableRange>,
+) {
+    let mut children: HashMap<FrameId, Vec<&Frame>> = HashMap::new();
+    for frame in &toc.frames {
+        if let (FrameRole::DocumentChunk, Some(parent)) = (frame.role, frame.parent_id) {
+            children.entry(parent).or_default().push(frame);
+        }
+    }
+
+    for frame in &toc.frames {
+        if frame.status != FrameStatus::Active || frame.role == FrameRole::DocumentChunk {
+            continue;
+        }
+        let content = if frame.chunk_manifest.is_some() {
+            let mut parts = children.get(&frame.id).cloned().unwrap_or_default();
+            if parts.is_empty() {
+                unrecoverable.push(UnrecoverableRange {
+                    offset: frame.payload_offset,
+                    length: frame.payload_length,
+                    reason: format!("frame {}: chunk frames missing", frame.id),
+                });
+                continue;
+            }
+            parts.sort_by_key(|child| (child.chunk_index.unwrap_or(u32::MAX), child.id));
+            parts.iter().try_fold(Vec::new(), |mut buffer, child| {
+                buffer.extend(frame_payload(mmap, child)?);
+                Ok(buffer)
+            })
+        } else if frame.payload_length == 0 {
+            Ok(frame.search_text.clone().unwrap_or_default().into_bytes())
+        } else {
+            frame_payload(mmap, frame)
+        };
+        match content {
+            Ok(content) if !content.is_empty() => candidates.push(Candidate {
+                frame: SalvagedFrame {
+                    source: SalvageSource::Toc,
+                    offset: frame.payload_offset,
+                    original_id: Some(frame.id),
+                    uri: frame.uri.clone(),
+                },
+                content,
+                options: PutOptions {
+                    timestamp: Some(frame.timestamp),
+                    track: frame.track.clone(),
+                    kind: frame.kind.clone(),
+                    uri: frame.uri.clone(),
+                    title: frame.title.clone(),
+                    metadata: frame.metadata.clone(),
+                    tags: frame.tags.clone(),
+                    labels: frame.labels.clone(),
+                    extra_metadata: frame.extra_metadata.clone(),
+                    role: frame.role,
+                    ..salvage_put_options()
+                },
+            }),
+            Ok(_) => {}
+            Err((offset, length, reason)) => unrecoverable.push(UnrecoverableRange {
+                offset,
+                length,
+                reason: format!("frame {}: {reason}", frame.id),
+            }),
+        }
+    }
+}
+
+/// Verified, decoded payload of `frame`, or the failing range and why.
+fn frame_payload(
+    mmap: &[u8],
+    frame: &Frame,
+) -> std::result::Result<Vec<u8>, (u64, u64, &'static str)> {
+    let failure = |reason| (frame.payload_offset, frame.payload_length, reason);
+    let raw = usize::try_from(frame.payload_offset)
+        .ok()
+        .zip(usize::try_from(frame.payload_length).ok())
+        .and_then(|(start, length)| mmap.get(start..start.checked_add(length)?))
+        .ok_or_else(|| failure("payload lies outside the file"))?;
+    if blake3::hash(raw).as_bytes() != &frame.checksum {
+        return Err(failure("payload checksum mismatch"));
+    }
+    crate::decode_canonical_bytes(raw, frame.canonical_encoding, frame.id)
+        .map_err(|_| failure("payload does not decode"))
+}
+
+fn salvage_wal_records(
+    mmap: &[u8],
+    wal_offset: u64,
+    wal_end: u64,
+    checkpoint: u64,
+    candidates: &mut Vec<Candidate>,
+) -> u64 {
+    let (Ok(start), Ok(end)) = (usize::try_from(wal_offset), usize::try_from(wal_end)) else {
+        return wal_offset;
+    };
+    let Some(region) = mmap.get(start..end.min(mmap.len())) else {
+        return wal_offset;
+    };
+    let (records, walked) = EmbeddedWal::salvage_records(region);
+    for (record_offset, record) in records {
+        if record.sequence <= checkpoint {
+            continue;
+        }
+        let Some(entry) = decode_wal_frame(&record.payload) else {
+            continue;
+        };
+        if entry.op != FrameWalOp::Insert
+            || entry.role == FrameRole::DocumentChunk
+            || entry.reuse_payload_from.is_some()
+        {
+            continue;
+        }
+        let Ok(content) =
+            crate::decode_canonical_bytes(&entry.payload, entry.canonical_encoding, 0)
+        else {
+            continue;
+        };
+        if content.is_empty() {
+            continue;
+        }
+        candidates.push(Candidate {
+            frame: SalvagedFrame {
+                source: SalvageSource::Wal,
+                offset: wal_offset + record_offset as u64,
+                original_id: None,
+                uri: entry.uri.clone(),
+            },
+            content,
+            options: wal_put_options(entry),
+        });
+    }
+    wal_offset + walked as u64
+}
+
+fn wal_put_options(entry: WalEntryData) -> PutOptions {
+    PutOptions {
+        timestamp: Some(entry.timestamp),
+        track: entry.track,
+        kind: entry.kind,
+        uri: entry.uri,
+        title: entry.title,
+        metadata: entry.metadata,
+        tags: entry.tags,
+        labels: entry.labels,
+        extra_metadata: entry.extra_metadata,
+        role: entry.role,
+        ..salvage_put_options()
+    }
+}
+
+/// Scan everything outside `claimed` for zstd frames that decode to UTF-8 text, reporting
+/// the unclaimed bytes that yield none.
+///
+/// Track blobs (memories, Logic-Mesh) prefix their zstd stream with its length as a `u64`;
+/// frame payloads never do, so length-prefixed streams are skipped.
+fn scan_payloads(
+    mmap: &[u8],
+    claimed: &[(u64, u64)],
+    candidates: &mut Vec<Candidate>,
+    unrecoverable: &mut Vec<UnrecoverableRange>,
+) {
+    let mut claimed: Vec<(usize, usize)> = claimed
+        .iter()
+        .filter_map(|(offset, length)| {
+            let start = usize::try_from(*offset).ok()?;
+            Some((start, start.saturating_add(usize::try_from(*length).ok()?)))
+        })
+        .collect();
+    claimed.sort_unstable();
+    let mut next_claim = claimed.iter().peekable();
+    let mut position = 0usize;
+    let mut gap_start = None;
+    while position + ZSTD_MAGIC.len() <= mmap.len() {
+        while next_claim.next_if(|(_, end)| *end <= position).is_some() {}
+        if let Some(&&(start, end)) = next_claim.peek() {
+            if start <= position {
+                close_gap(&mut gap_start, position, unrecoverable);
+                position = end;
+                continue;
+            }
+        }
+        gap_start.get_or_insert(position);
+        if mmap[position..position + ZSTD_MAGIC.len()] != ZSTD_MAGIC {
+            position += 1;
+            continue;
+        }
+        let Some(length) = zstd::zstd_safe::find_frame_compressed_size(&mmap[position..])
+            .ok()
+            .filter(|length| !length_prefixed(mmap, position, *length))
+        else {
+            position += 1;
+            continue;
+        };
+        match zstd::decode_all(&mmap[position..position + length]).map(String::from_utf8) {
+            Ok(Ok(text)) if !text.is_empty() => {
+                close_gap(&mut gap_start, position, unrecoverable);
+                candidates.push(Candidate {
+                    frame: SalvagedFrame {
+                        source: SalvageSource::Scan,
+                        offset: position as u64,
+                        original_id: None,
+                        uri: None,
+                    },
+                    content: text.into_bytes(),
+                    options: salvage_put_options(),
+                });
+                position += length;
+            }
+            _ => position += 1,
+        }
+    }
+    close_gap(&mut gap_start, mmap.len(), unrecoverable);
+}
+
+fn length_prefixed(mmap: &[u8], position: usize, length: usize) -> bool {
+    position
+        .checked_sub(8)
+        .and_then(|start| mmap.get(start..position))
+        .is_some_and(|prefix| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(prefix);
+            u64::from_le_bytes(bytes) == length as u64
+        })
+}
+
+fn close_gap(
+    gap_start: &mut Option<usize>,
+    end: usize,
+    unrecoverable: &mut Vec<UnrecoverableRange>,
+) {
+    if let Some(start) = gap_start.take().filter(|start| *start < end) {
+        unrecoverable.push(UnrecoverableRange {
+            offset: start as u64,
+            length: (end - start) as u64,
+            reason: "no recoverable frame".into(),
+        });
+    }
+}
+
+/// Salvaged content is copied as-is: no re-derived tags, dates, or triplets.
+fn salvage_put_options() -> PutOptions {
+    PutOptions {
+        auto_tag: false,
+        extract_dates: false,
+        extract_triplets: false,
+        ..PutOptions::default()
+    }
+}
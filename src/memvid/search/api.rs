@@ -10,9 +10,11 @@ use tempfile::TempDir;
 use crate::memvid::lifecycle::Memvid;
 use crate::types::{
     AclContext, AclEnforcementMode, AdaptiveConfig, AdaptiveResult, AdaptiveStats,
-    EmbeddingQualityStats, Frame, FrameId, FrameStatus, SearchHit, TimelineEntry, TimelineQuery,
-    VecSegmentDescriptor, compute_embedding_quality, find_adaptive_cutoff,
+    EmbeddingQualityStats, Frame, FrameId, FrameStatus, SearchHit, SearchParams, TimelineEntry,
+    TimelineQuery, VecRescore, VecSegmentDescriptor, VectorCompression, compute_embedding_quality,
+    find_adaptive_cutoff,
 };
+use crate::vec_pq::FullPrecisionStore;
 use crate::{LexSearchHit, MemvidError, Result, VecSearchHit};
 
 impl Memvid {
@@ -102,6 +104,20 @@ impl Memvid {
         Ok(())
     }
 
+    /// Enable the vector index with the given storage encoding.
+    ///
+    /// `Int8` stores one byte per dimension and `Binary` one bit, trading exact float scores
+    /// for 4x and 32x smaller vec segments. Vectors already in the index are re-encoded when
+    /// the index is next rebuilt.
+    pub fn enable_vec_with_compression(&mut self, compression: VectorCompression) -> Result<()> {
+        self.ensure_writable()?;
+        if let Some(manifest) = self.toc.indexes.vec.as_mut() {
+            manifest.compression_mode = compression.clone();
+        }
+        self.vec_compression = compression;
+        self.enable_vec()
+    }
+
     /// Set the expected embedding model for the vector index.
     ///
     /// If the index is already bound to a model (from a previous session or call),
@@ -127,6 +143,27 @@ impl Memvid {
     }
 
     pub fn search_vec(&mut self, query: &[f32], limit: usize) -> Result<Vec<VecSearchHit>> {
+        self.search_vec_rescored(query, limit, VecRescore::Auto)
+    }
+
+    /// Vector search returning `params.top_k` hits.
+    ///
+    /// For product-quantized indexes stored with full-precision vectors, `params.vec_rescore`
+    /// picks how many PQ candidates are rescored by exact distance before truncating.
+    pub fn search_vec_with_params(
+        &mut self,
+        query: &[f32],
+        params: &SearchParams,
+    ) -> Result<Vec<VecSearchHit>> {
+        self.search_vec_rescored(query, params.top_k, params.vec_rescore)
+    }
+
+    fn search_vec_rescored(
+        &mut self,
+        query: &[f32],
+        limit: usize,
+        rescore: VecRescore,
+    ) -> Result<Vec<VecSearchHit>> {
         if !self.vec_enabled {
             return Err(MemvidError::VecNotEnabled);
         }
@@ -159,7 +196,36 @@ impl Memvid {
             self.ensure_vec_index()?;
         }
         let index = self.vec_index.as_ref().ok_or(MemvidError::VecNotEnabled)?;
-        Ok(index.search(query, limit))
+        let (Some(store), Some(candidates), Some(_)) = (
+            index.full_precision(),
+            rescore.candidates(limit),
+            self.toc.indexes.vec.as_ref(),
+        ) else {
+            return Ok(index.search(query, limit));
+        };
+
+        // Stage 1: PQ candidates. Stage 2: exact distance from the stored vectors.
+        let ranges: Vec<(FrameId, u64, u64)> = index
+            .search(query, candidates)
+            .into_iter()
+            .filter_map(|hit| {
+                store
+                    .vector_range(hit.frame_id)
+                    .map(|(offset, len)| (hit.frame_id, offset, len))
+            })
+            .collect();
+        let mut hits = Vec::with_capacity(ranges.len());
+        for (frame_id, offset, len) in ranges {
+            let vector =
+                FullPrecisionStore::decode_vector(&self.read_vec_index_range(offset, len)?);
+            hits.push(VecSearchHit {
+                frame_id,
+                distance: crate::simd::l2_distance_simd(query, &vector),
+            });
+        }
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits.truncate(limit);
+        Ok(hits)
     }
 
     /// Enable CLIP visual embeddings index.
@@ -297,9 +363,7 @@ impl Memvid {
         acl_enforcement_mode: AclEnforcementMode,
     ) -> Result<crate::types::SearchResponse> {
         use super::helpers::{build_context, timestamp_to_rfc3339};
-        use crate::types::{
-            SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams, SearchResponse,
-        };
+        use crate::types::{SearchEngineKind, SearchHit, SearchHitMetadata, SearchResponse};
         use std::time::Instant;
 
         if !self.vec_enabled {
@@ -355,18 +419,24 @@ impl Memvid {
                     top_k,
                     snippet_chars,
                     cursor: None,
+                    vec_rescore: VecRescore::default(),
                 },
                 hits: Vec::new(),
                 context: build_context(&[]),
                 next_cursor: None,
                 engine: SearchEngineKind::Hybrid,
                 stale_index_skips: 0,
+                suggestions: Vec::new(),
+                explain: None,
+                partial: false,
+                skipped_stages: Vec::new(),
             });
         }
 
         // Convert VecSearchHit to SearchHit with full metadata
         let mut hits = Vec::new();
         let snippet_limit = snippet_chars.max(80);
+        let quarantined = self.quarantine_set()?;
 
         for vec_hit in vec_hits {
             // Apply scope filter if provided
@@ -390,13 +460,19 @@ impl Memvid {
                 }
             }
 
-            // Get frame content for snippet
-            let content = match self.frame_content(&frame) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
+            if quarantined.contains(&frame.id) {
+                continue;
+            }
 
-            let snippet: String = content.chars().take(snippet_limit).collect();
+            // Build the snippet from borrowed text where possible
+            let snippet: String = match self.frame_snippet_text(&frame) {
+                Some(Ok(text)) => text.chars().take(snippet_limit).collect(),
+                Some(Err(_)) => continue,
+                None => match self.frame_content(&frame) {
+                    Ok(content) => content.chars().take(snippet_limit).collect(),
+                    Err(_) => continue,
+                },
+            };
             let snippet_bytes = snippet.len();
 
             let uri = frame
@@ -420,6 +496,7 @@ impl Memvid {
                 created_at: timestamp_to_rfc3339(frame.timestamp),
                 content_dates: frame.content_dates.clone(),
                 entities: Vec::new(),
+                related: Vec::new(),
                 extra_metadata: frame.extra_metadata.clone(),
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
@@ -437,6 +514,7 @@ impl Memvid {
                 chunk_text: Some(snippet),
                 score: Some(similarity_score),
                 metadata: Some(metadata),
+                highlights: Vec::new(),
             });
 
             if hits.len() >= top_k {
@@ -460,12 +538,17 @@ impl Memvid {
                 top_k,
                 snippet_chars,
                 cursor: None,
+                vec_rescore: VecRescore::default(),
             },
             hits,
             context,
             next_cursor: None,
             engine: SearchEngineKind::Hybrid,
             stale_index_skips: 0,
+            suggestions: Vec::new(),
+            explain: None,
+            partial: false,
+            skipped_stages: Vec::new(),
         })
     }
 
@@ -702,6 +785,7 @@ impl Memvid {
             reverse,
             #[cfg(feature = "temporal_track")]
             temporal,
+            geo,
         } = query;
 
         #[cfg(feature = "temporal_track")]
@@ -712,12 +796,20 @@ impl Memvid {
                 since,
                 until,
                 reverse,
+                geo.as_ref(),
                 temporal.as_ref(),
             )
         }
         #[cfg(not(feature = "temporal_track"))]
         {
-            crate::memvid::timeline::build_timeline(self, limit, since, until, reverse)
+            crate::memvid::timeline::build_timeline(
+                self,
+                limit,
+                since,
+                until,
+                reverse,
+                geo.as_ref(),
+            )
         }
     }
 }
@@ -851,6 +943,7 @@ impl Memvid {
             )
         };
 
+        let mut external = false;
         let mut engine = match segments {
             Some(segments) => {
                 match self
@@ -867,8 +960,27 @@ impl Memvid {
                     }
                 }
             }
+            None if self.has_external_lex() => {
+                match self
+                    .materialize_external_lex()
+                    .and_then(TantivyEngine::open_from_dir)
+                {
+                    Ok(engine) => {
+                        external = true;
+                        engine
+                    }
+                    Err(err) => {
+                        tracing::debug!(
+                            "failed to open Tantivy index from sidecar: {}, rebuilding",
+                            err
+                        );
+                        TantivyEngine::create()?
+                    }
+                }
+            }
             None => TantivyEngine::create()?,
         };
+        engine.set_analyzer_config(&self.analyzer_config()?);
 
         // Use consolidated helper for expected doc count
         let expected_docs = self
@@ -880,7 +992,8 @@ impl Memvid {
         let mut rebuilt = false;
         let actual_docs = engine.num_docs();
 
-        let has_tantivy_segments = !self.toc.segment_catalog.tantivy_segments.is_empty();
+        let has_tantivy_segments =
+            external || !self.toc.segment_catalog.tantivy_segments.is_empty();
         let needs_rebuild = if has_tantivy_segments {
             // Trust existing Tantivy segments, don't rebuild
             false
@@ -927,10 +1040,7 @@ impl Memvid {
         let Some(descriptor) = self.vec_segment_descriptor(segment_id) else {
             return Ok(None);
         };
-        let bytes = self.read_range(
-            descriptor.common.bytes_offset,
-            descriptor.common.bytes_length,
-        )?;
+        let bytes = self.read_segment_bytes(&descriptor.common)?;
         Ok(Some((descriptor, bytes)))
     }
 }
@@ -1,12 +1,28 @@
 use std::collections::HashMap;
 use std::io::{Read, Seek, SeekFrom};
 use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::time::Instant;
 
 use crate::lex::{LexIndex, LexIndexArtifact, LexIndexBuilder};
 use crate::memvid::lifecycle::Memvid;
-use crate::types::{Frame, FrameId, FrameStatus, VectorCompression};
+use crate::memvid::search::helpers::{budget_spent, build_context, timestamp_to_rfc3339};
+use crate::types::{
+    Frame, FrameId, FrameStatus, SearchHit, SearchRequest, SearchResponse, SkippedStage,
+    VectorCompression,
+};
+use crate::vec::VecIndexBuilder;
+use crate::vec_pq::QuantizedVecIndexBuilder;
+use crate::vec_scalar::{ScalarEncoding, ScalarQuantizedVecIndexBuilder};
 use crate::{MemvidError, Result, VecIndex, VecIndexArtifact};
 
+/// Below this many vectors a PQ codebook is not worth training; the index stays uncompressed.
+const MIN_VECTORS_FOR_PQ: usize = 100;
+/// The only dimension `vec_pq` supports.
+const PQ_DIMENSION: u32 = 384;
+/// Candidates retrieved per requested hit when regrouping, so merging or diversifying still
+/// leaves `top_k` hits.
+const DIVERSITY_POOL_FACTOR: usize = 4;
+
 impl Memvid {
     #[allow(dead_code)]
     pub(crate) fn build_lex_artifact(&mut self) -> Result<Option<(LexIndexArtifact, LexIndex)>> {
@@ -41,29 +57,83 @@ impl Memvid {
         Ok(Some((artifact, index)))
     }
 
+    /// Active vectors of the current index, read before a rebuild starts overwriting the
+    /// index region. Quantized indexes contribute their stored full-precision vectors.
+    pub(crate) fn retained_vec_documents(&mut self) -> Result<Vec<(FrameId, Vec<f32>)>> {
+        let Some(index) = self.vec_index.as_ref() else {
+            return Ok(Vec::new());
+        };
+        let full_precision = index.full_precision().cloned();
+        let mut documents: Vec<(FrameId, Vec<f32>)> =
+            match (full_precision, self.toc.indexes.vec.as_ref()) {
+                (Some(store), Some(_)) => {
+                    let (offset, len) = store.section_range();
+                    let bytes = self.read_vec_index_range(offset, len)?;
+                    store.decode_section(&bytes)
+                }
+                _ => index.owned_entries(),
+            };
+        documents.retain(|(frame_id, _)| self.frame_is_active(*frame_id));
+        Ok(documents)
+    }
+
     pub(crate) fn build_vec_artifact(
         &mut self,
+        retained: Vec<(FrameId, Vec<f32>)>,
         new_docs: &[(FrameId, Vec<f32>)],
     ) -> Result<Option<(VecIndexArtifact, VecIndex)>> {
         if !self.vec_enabled {
             return Ok(None);
         }
-        let mut builder = VecIndexBuilder::new();
-        if let Some(index) = self.vec_index.as_ref() {
-            for (frame_id, embedding) in index.entries() {
-                if self.frame_is_active(frame_id) {
-                    builder.add_document(frame_id, embedding.to_vec());
+        let mut documents = retained;
+        documents.extend(new_docs.iter().cloned());
+        let pq_eligible = self.vec_compression == VectorCompression::Pq96
+            && documents.len() >= MIN_VECTORS_FOR_PQ
+            && documents
+                .iter()
+                .all(|(_, embedding)| embedding.len() == PQ_DIMENSION as usize);
+        let artifact =
+            if let Some(encoding) = ScalarEncoding::from_compression(&self.vec_compression) {
+                let mut builder = ScalarQuantizedVecIndexBuilder::new(encoding);
+                for (frame_id, embedding) in &documents {
+                    builder.add_document(*frame_id, embedding)?;
                 }
-            }
-        }
-        for (frame_id, embedding) in new_docs {
-            builder.add_document(*frame_id, embedding.clone());
-        }
-        let artifact = builder.finish()?;
+                builder.finish()?
+            } else if pq_eligible {
+                Self::build_full_precision_pq(documents)?
+            } else {
+                let mut builder = VecIndexBuilder::new();
+                for (frame_id, embedding) in documents {
+                    builder.add_document(frame_id, embedding);
+                }
+                builder.finish()?
+            };
         let index = VecIndex::decode(&artifact.bytes)?;
         Ok(Some((artifact, index)))
     }
 
+    /// PQ codes plus the full-precision vectors used to rescore PQ candidates.
+    fn build_full_precision_pq(documents: Vec<(FrameId, Vec<f32>)>) -> Result<VecIndexArtifact> {
+        let training: Vec<Vec<f32>> = documents.iter().map(|(_, e)| e.clone()).collect();
+        let mut builder = QuantizedVecIndexBuilder::new().with_full_precision();
+        builder.train_quantizer(&training, PQ_DIMENSION)?;
+        #[cfg(feature = "parallel_segments")]
+        let bytes_uncompressed = training.len() as u64 * u64::from(PQ_DIMENSION) * 4;
+        drop(training);
+        for (frame_id, embedding) in documents {
+            builder.add_document(frame_id, embedding)?;
+        }
+        let artifact = builder.finish()?;
+        Ok(VecIndexArtifact {
+            bytes: artifact.bytes,
+            vector_count: artifact.vector_count,
+            dimension: artifact.dimension,
+            checksum: artifact.checksum,
+            #[cfg(feature = "parallel_segments")]
+            bytes_uncompressed,
+        })
+    }
+
     pub(crate) fn ensure_lex_index(&mut self) -> Result<()> {
         if self.lex_index.is_some() {
             return Ok(());
@@ -125,23 +195,40 @@ impl Memvid {
     pub(crate) fn load_vec_index_from_manifest(&mut self) -> Result<()> {
         // Load the model name from the manifest regardless of validation success
         self.vec_model = self.toc.indexes.vec.as_ref().and_then(|m| m.model.clone());
+        if let Some(manifest) = &self.toc.indexes.vec {
+            self.vec_compression = manifest.compression_mode.clone();
+        }
 
         if let Some(manifest) = &self.toc.indexes.vec {
-            // Empty manifest (placeholder for enabled but not yet populated index)
+            // Empty manifest: a placeholder for an enabled but unpopulated index, or an index
+            // held in the `.mv2x` sidecar
             if manifest.bytes_length == 0 {
-                self.vec_index = None;
+                self.vec_index = self.load_external_vec_index();
                 return Ok(());
             }
 
-            let bytes =
-                if let Ok(bytes) = self.read_range(manifest.bytes_offset, manifest.bytes_length) {
-                    bytes
-                } else {
-                    self.vec_index = None;
-                    // Don't disable vec if loading fails - keep it enabled
-                    // self.vec_enabled = false;
-                    return Ok(());
-                };
+            // Quantized indexes with full-precision vectors only need their PQ section in
+            // memory; the vectors are read on demand when rescoring.
+            let offset = manifest.bytes_offset;
+            let mut length = manifest.bytes_length;
+            let preamble = crate::vec_pq::FULL_PRECISION_PQ_PREAMBLE as u64;
+            if length > preamble {
+                if let Some(pq_len) = self
+                    .read_range(offset, preamble)
+                    .ok()
+                    .and_then(|bytes| crate::vec_pq::full_precision_pq_section_len(&bytes))
+                {
+                    length = length.min(preamble.saturating_add(pq_len));
+                }
+            }
+            let bytes = if let Ok(bytes) = self.read_range(offset, length) {
+                bytes
+            } else {
+                self.vec_index = None;
+                // Don't disable vec if loading fails - keep it enabled
+                // self.vec_enabled = false;
+                return Ok(());
+            };
             match catch_unwind(AssertUnwindSafe(|| VecIndex::decode(&bytes))) {
                 Ok(Ok(index)) => self.vec_index = Some(index),
                 Ok(Err(_)) | Err(_) => {
@@ -214,18 +301,13 @@ impl Memvid {
     }
 
     fn build_vec_index_from_segments(&mut self) -> Result<()> {
-        use crate::vec::VecIndexBuilder;
-
-        let mut builder = VecIndexBuilder::new();
+        let mut documents: Vec<(FrameId, Vec<f32>)> = Vec::new();
 
         // Clone segments to avoid borrow checker issues
         let segments = self.toc.segment_catalog.vec_segments.clone();
 
         for segment_desc in &segments {
-            let bytes = match self.read_range(
-                segment_desc.common.bytes_offset,
-                segment_desc.common.bytes_length,
-            ) {
+            let bytes = match self.read_segment_bytes(&segment_desc.common) {
                 Ok(bytes) => bytes,
                 Err(err) => {
                     tracing::warn!(
@@ -250,9 +332,9 @@ impl Memvid {
 
             match VecIndex::decode_with_compression(&bytes, compression_hint) {
                 Ok(segment_index) => {
-                    for (frame_id, embedding) in segment_index.entries() {
+                    for (frame_id, embedding) in segment_index.owned_entries() {
                         if self.frame_is_active(frame_id) {
-                            builder.add_document(frame_id, embedding.to_vec());
+                            documents.push((frame_id, embedding));
                         }
                     }
                 }
@@ -266,7 +348,20 @@ impl Memvid {
             }
         }
 
-        let artifact = builder.finish()?;
+        let artifact =
+            if let Some(encoding) = ScalarEncoding::from_compression(&self.vec_compression) {
+                let mut builder = ScalarQuantizedVecIndexBuilder::new(encoding);
+                for (frame_id, embedding) in &documents {
+                    builder.add_document(*frame_id, embedding)?;
+                }
+                builder.finish()?
+            } else {
+                let mut builder = VecIndexBuilder::new();
+                for (frame_id, embedding) in documents {
+                    builder.add_document(frame_id, embedding);
+                }
+                builder.finish()?
+            };
         if artifact.vector_count > 0 {
             let index =
                 VecIndex::decode_with_compression(&artifact.bytes, VectorCompression::None)?;
@@ -303,4 +398,239 @@ impl Memvid {
     }
 }
 
-use crate::vec::VecIndexBuilder;
+impl Memvid {
+    /// Search for a request with `return_parents`, `group_by_parent`, or `diversify` set:
+    /// retrieve a wider pool, merge chunk hits into their documents or collapse them, then
+    /// reorder the pool by maximal marginal relevance and keep `top_k`.
+    pub(crate) fn search_regrouped(
+        &mut self,
+        mut request: SearchRequest,
+    ) -> Result<SearchResponse> {
+        let start = Instant::now();
+        let top_k = request.top_k;
+        let time_budget_ms = request.time_budget_ms;
+        let lambda = request.diversify.take();
+        let group_by_parent = std::mem::take(&mut request.group_by_parent);
+        let return_parents = std::mem::take(&mut request.return_parents);
+        request.top_k = top_k.saturating_mul(DIVERSITY_POOL_FACTOR);
+
+        let mut response = self.search_unrecorded(request)?;
+        let stage = Instant::now();
+        if return_parents {
+            self.merge_hits_into_parents(&mut response.hits)?;
+        }
+        if group_by_parent {
+            self.group_hits_by_parent(&mut response.hits);
+        }
+        if let Some(lambda) = lambda {
+            if budget_spent(time_budget_ms, start) {
+                response.skip_stage(SkippedStage::Diversify);
+            } else {
+                self.diversify_hits(&mut response.hits, lambda, top_k)?;
+            }
+        }
+        response.hits.truncate(top_k);
+        for (index, hit) in response.hits.iter_mut().enumerate() {
+            hit.rank = index + 1;
+        }
+        response.params.top_k = top_k;
+        // Reordered hits no longer line up with the engine's page boundaries.
+        response.params.cursor = None;
+        response.next_cursor = None;
+        response.context = build_context(&response.hits);
+        if let Some(explain) = response.explain.as_mut() {
+            explain.align_hits(&response.hits);
+            explain.record_stage("regroup", stage.elapsed());
+        }
+        response.elapsed_ms = start.elapsed().as_millis();
+        Ok(response)
+    }
+
+    /// Replace chunk hits with one hit for their parent document. The parent hit scores the
+    /// sum of its chunks' scores and counts their matches; its snippet, range, and highlights
+    /// are the best chunk's, located in the document, and `chunk_text` holds the whole
+    /// document. Hits are then reordered by score.
+    pub(crate) fn merge_hits_into_parents(&mut self, hits: &mut Vec<SearchHit>) -> Result<()> {
+        let mut groups: Vec<(FrameId, Vec<SearchHit>)> = Vec::new();
+        let mut merges = false;
+        for hit in hits.drain(..) {
+            let document = self.hit_document(&hit);
+            merges |= document != hit.frame_id;
+            match groups.iter_mut().find(|(id, _)| *id == document) {
+                Some((_, members)) => members.push(hit),
+                None => groups.push((document, vec![hit])),
+            }
+        }
+        for (document, members) in groups {
+            if members.iter().all(|hit| hit.frame_id == document) {
+                hits.extend(members);
+            } else {
+                hits.push(self.parent_hit(document, &members)?);
+            }
+        }
+        if merges && hits.iter().all(|hit| hit.score.is_some()) {
+            hits.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+        }
+        Ok(())
+    }
+
+    fn parent_hit(&mut self, parent_id: FrameId, members: &[SearchHit]) -> Result<SearchHit> {
+        let parent = self.frame_by_id(parent_id)?;
+        let text = self.frame_content(&parent)?;
+        let score = members
+            .iter()
+            .filter_map(|hit| hit.score)
+            .reduce(|sum, score| sum + score);
+        let matches = members.iter().map(|hit| hit.matches).sum();
+        // Members keep the engine's order, so the first is the best chunk
+        let mut hit = members[0].clone();
+        let uri = parent
+            .uri
+            .clone()
+            .unwrap_or_else(|| crate::default_uri(parent_id));
+
+        if let Some(start) = text.find(&hit.text) {
+            let shift = |range: &mut (usize, usize)| {
+                let length = range.1.saturating_sub(range.0);
+                let offset = range.0.saturating_sub(hit.range.0);
+                *range = (start + offset, start + offset + length);
+            };
+            for span in &mut hit.highlights {
+                shift(&mut span.range);
+                for term in &mut span.terms {
+                    shift(term);
+                }
+            }
+            hit.range = (start, start + hit.text.len());
+        }
+        hit.frame_id = parent_id;
+        hit.title = parent
+            .title
+            .clone()
+            .or_else(|| crate::infer_title_from_uri(&uri));
+        hit.uri = uri;
+        hit.matches = matches;
+        hit.score = score;
+        hit.chunk_range = Some((0, text.len()));
+        hit.chunk_text = Some(text);
+        if let Some(metadata) = hit.metadata.as_mut() {
+            metadata.matches = matches;
+            metadata.tags.clone_from(&parent.tags);
+            metadata.labels.clone_from(&parent.labels);
+            metadata.track.clone_from(&parent.track);
+            metadata.created_at = timestamp_to_rfc3339(parent.timestamp);
+            metadata.content_dates.clone_from(&parent.content_dates);
+            metadata.extra_metadata.clone_from(&parent.extra_metadata);
+        }
+        Ok(hit)
+    }
+
+    /// Keep only the best hit of each document, where chunk frames belong to their parent and
+    /// several snippets of one frame to that frame.
+    pub(crate) fn group_hits_by_parent(&self, hits: &mut Vec<SearchHit>) {
+        let mut seen = std::collections::HashSet::new();
+        hits.retain(|hit| seen.insert(self.hit_document(hit)));
+    }
+
+    /// Reorder `hits` greedily by maximal marginal relevance, picking at most `limit`: each
+    /// pick maximizes `lambda * relevance - (1 - lambda) * similarity` to the hits already
+    /// picked. Similarity is the cosine of frame embeddings when both hits have one, otherwise
+    /// 1 for hits from the same document and 0 for others. Relevance is the engine score
+    /// scaled to the best hit, or the rank when hits are unscored. Unpicked hits are dropped.
+    pub(crate) fn diversify_hits(
+        &mut self,
+        hits: &mut Vec<SearchHit>,
+        lambda: f32,
+        limit: usize,
+    ) -> Result<()> {
+        let lambda = lambda.clamp(0.0, 1.0);
+        if hits.len() < 2 || lambda >= 1.0 {
+            return Ok(());
+        }
+        let best = hits
+            .iter()
+            .filter_map(|hit| hit.score)
+            .fold(f32::MIN, f32::max);
+        #[allow(clippy::cast_precision_loss)]
+        let count = hits.len() as f32;
+        #[allow(clippy::cast_precision_loss)]
+        let relevance: Vec<f32> = hits
+            .iter()
+            .enumerate()
+            .map(|(index, hit)| match hit.score {
+                Some(score) if best > 0.0 => score / best,
+                _ => 1.0 - index as f32 / count,
+            })
+            .collect();
+        let documents: Vec<FrameId> = hits.iter().map(|hit| self.hit_document(hit)).collect();
+        let mut embeddings: HashMap<FrameId, Option<Vec<f32>>> = HashMap::new();
+        for hit in hits.iter() {
+            if let std::collections::hash_map::Entry::Vacant(entry) = embeddings.entry(hit.frame_id)
+            {
+                entry.insert(self.frame_embedding(hit.frame_id)?);
+            }
+        }
+        let similarity = |a: usize, b: usize| -> f32 {
+            let embedding = |index: usize| {
+                embeddings
+                    .get(&hits[index].frame_id)
+                    .and_then(Option::as_deref)
+            };
+            match (embedding(a), embedding(b)) {
+                (Some(x), Some(y)) => cosine_similarity(x, y),
+                _ if documents[a] == documents[b] => 1.0,
+                _ => 0.0,
+            }
+        };
+
+        let mut picked: Vec<usize> = Vec::new();
+        let mut remaining: Vec<usize> = (0..hits.len()).collect();
+        while picked.len() < limit && !remaining.is_empty() {
+            let mmr = |candidate: usize| {
+                let redundancy = picked
+                    .iter()
+                    .map(|&chosen| similarity(candidate, chosen))
+                    .fold(0.0_f32, f32::max);
+                lambda * relevance[candidate] - (1.0 - lambda) * redundancy
+            };
+            // First maximum wins, so ties keep the engine's order
+            let mut best_position = 0;
+            let mut best_mmr = f32::MIN;
+            for (position, &candidate) in remaining.iter().enumerate() {
+                let value = mmr(candidate);
+                if value > best_mmr {
+                    best_mmr = value;
+                    best_position = position;
+                }
+            }
+            picked.push(remaining.remove(best_position));
+        }
+
+        let mut slots: Vec<Option<SearchHit>> = hits.drain(..).map(Some).collect();
+        hits.extend(picked.into_iter().filter_map(|index| slots[index].take()));
+        Ok(())
+    }
+
+    /// Document a hit belongs to: its frame's parent, or the frame itself.
+    fn hit_document(&self, hit: &SearchHit) -> FrameId {
+        usize::try_from(hit.frame_id)
+            .ok()
+            .and_then(|index| self.toc.frames.get(index))
+            .and_then(|frame| frame.parent_id)
+            .unwrap_or(hit.frame_id)
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
@@ -2,8 +2,11 @@
 
 #[cfg(feature = "temporal_track")]
 use super::helpers::attach_temporal_metadata;
-use super::helpers::{build_context, empty_search_response, parse_cursor, timestamp_to_rfc3339};
+use super::helpers::{
+    build_context, empty_search_response, highlight_spans, parse_cursor, timestamp_to_rfc3339,
+};
 use crate::lex::{LexMatch, compute_snippet_slices};
+use crate::memvid::importance::importance_factor;
 use crate::memvid::lifecycle::Memvid;
 use crate::search::{EvaluationContext, ParsedQuery};
 use crate::types::{
@@ -34,7 +37,28 @@ pub(super) fn search_with_lex_fallback(
         request.scope.as_deref()
     };
 
-    let matches: Vec<LexMatch> = index.compute_matches(query_tokens, uri_filter, scope_filter);
+    // Each synonym variant of the tokens is matched separately; the lex index requires every
+    // token it is given to appear.
+    let variants = memvid.analyzer_config()?.token_variants(query_tokens);
+    let mut matches: Vec<LexMatch> = Vec::new();
+    for variant in &variants {
+        for matched in index.compute_matches(variant, uri_filter, scope_filter) {
+            if !matches.iter().any(|existing| {
+                existing.frame_id == matched.frame_id
+                    && existing.chunk_offset == matched.chunk_offset
+            }) {
+                matches.push(matched);
+            }
+        }
+    }
+    // Importance is applied to the whole match list so boosted frames can enter the page.
+    let importance = memvid.importance_weights();
+    for matched in &mut matches {
+        matched.score *= importance_factor(&importance, matched.frame_id);
+    }
+    if variants.len() > 1 || !importance.is_empty() {
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    }
     let snippet_window = request.snippet_chars.max(80);
     let max_snippets_per_doc = request.top_k.max(1);
 
@@ -104,9 +128,15 @@ pub(super) fn search_with_lex_fallback(
             );
             continue;
         };
-        let canonical = memvid.frame_content(&frame_meta)?;
+        // Only the matched chunk of the frame's text is read
+        let chunk_start = matched.chunk_offset;
+        let (chunk_bytes, canonical_len) = memvid.frame_content_window(
+            &frame_meta,
+            chunk_start,
+            chunk_start + matched.content.len(),
+        )?;
         let canonical_limit = frame_meta.canonical_length.map_or_else(
-            || canonical.len(),
+            || canonical_len,
             |len| {
                 // Safe: canonical length is reasonably small string length
                 #[allow(clippy::cast_possible_truncation)]
@@ -114,7 +144,6 @@ pub(super) fn search_with_lex_fallback(
                 l
             },
         );
-        let canonical_len = canonical.len();
         let effective_len = canonical_limit.min(canonical_len);
         let uri = matched
             .uri
@@ -126,6 +155,13 @@ pub(super) fn search_with_lex_fallback(
             .clone()
             .or_else(|| frame_meta.title.clone())
             .or_else(|| crate::infer_title_from_uri(&uri));
+        let highlights = highlight_spans(
+            &matched.content,
+            &matched.occurrences,
+            matched.chunk_offset,
+            snippet_window,
+            request.highlight_windows,
+        );
 
         for (start, end) in slices {
             if produced < offset {
@@ -150,12 +186,12 @@ pub(super) fn search_with_lex_fallback(
                 created_at: timestamp_to_rfc3339(frame_meta.timestamp),
                 content_dates: frame_meta.content_dates.clone(),
                 entities: Vec::new(),
+                related: Vec::new(),
                 extra_metadata: frame_meta.extra_metadata.clone(),
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
             };
 
-            let chunk_start = matched.chunk_offset;
             let chunk_end = (chunk_start + matched.content.len()).min(effective_len);
             if chunk_end <= chunk_start {
                 produced += 1;
@@ -168,11 +204,12 @@ pub(super) fn search_with_lex_fallback(
                 produced += 1;
                 continue;
             }
-            let canonical_bytes = canonical.as_bytes();
-            let snippet_text =
-                String::from_utf8_lossy(&canonical_bytes[global_start..global_end]).to_string();
+            let snippet_text = String::from_utf8_lossy(
+                &chunk_bytes[global_start - chunk_start..global_end - chunk_start],
+            )
+            .to_string();
             let chunk_text =
-                String::from_utf8_lossy(&canonical_bytes[chunk_start..chunk_end]).to_string();
+                String::from_utf8_lossy(&chunk_bytes[..chunk_end - chunk_start]).to_string();
             hits.push(SearchHit {
                 rank: hits.len() + 1,
                 frame_id: matched.frame_id,
@@ -185,6 +222,7 @@ pub(super) fn search_with_lex_fallback(
                 chunk_text: Some(chunk_text),
                 score: Some(matched.score),
                 metadata: Some(metadata),
+                highlights: highlights.clone(),
             });
             produced += 1;
         }
@@ -211,6 +249,10 @@ pub(super) fn search_with_lex_fallback(
         next_cursor,
         engine: SearchEngineKind::LexFallback,
         stale_index_skips: stale_skips,
+        suggestions: Vec::new(),
+        explain: None,
+        partial: false,
+        skipped_stages: Vec::new(),
     })
 }
 
@@ -262,6 +304,10 @@ pub(super) fn search_with_filters_only(
             next_cursor: None,
             engine: SearchEngineKind::LexFallback,
             stale_index_skips: 0,
+            suggestions: Vec::new(),
+            explain: None,
+            partial: false,
+            skipped_stages: Vec::new(),
         });
     }
 
@@ -284,6 +330,7 @@ pub(super) fn search_with_filters_only(
             created_at: timestamp_to_rfc3339(frame.timestamp),
             content_dates: frame.content_dates.clone(),
             entities: Vec::new(),
+            related: Vec::new(),
             extra_metadata: frame.extra_metadata.clone(),
             #[cfg(feature = "temporal_track")]
             temporal: None,
@@ -310,6 +357,7 @@ pub(super) fn search_with_filters_only(
             chunk_text: Some(snippet),
             score: None,
             metadata: Some(metadata),
+            highlights: Vec::new(),
         });
         produced += 1;
     }
@@ -335,5 +383,9 @@ pub(super) fn search_with_filters_only(
         next_cursor,
         engine: SearchEngineKind::LexFallback,
         stale_index_skips: 0,
+        suggestions: Vec::new(),
+        explain: None,
+        partial: false,
+        skipped_stages: Vec::new(),
     })
 }
@@ -2,15 +2,20 @@
 #![allow(clippy::unwrap_used)]
 use crate::MemvidError;
 use crate::Result;
+use crate::lex::compute_snippet_slices;
 use crate::memvid::lifecycle::Memvid;
 #[cfg(not(feature = "temporal_track"))]
 #[allow(unused_imports)]
 use crate::types::FrameId;
+#[cfg(feature = "lex")]
+use crate::types::{ExplainFilter, HitExplain, QueryExplain};
 #[cfg(feature = "temporal_track")]
 use crate::types::{
     FrameId, SearchHitTemporal, SearchHitTemporalAnchor, SearchHitTemporalMention, TemporalMention,
 };
-use crate::types::{SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams, SearchResponse};
+use crate::types::{
+    HighlightSpan, SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams, SearchResponse,
+};
 #[cfg(feature = "temporal_track")]
 use std::collections::HashMap;
 #[cfg(feature = "temporal_track")]
@@ -34,10 +39,109 @@ pub(super) fn empty_search_response(
         next_cursor: None,
         engine,
         stale_index_skips: 0,
+        suggestions: Vec::new(),
+        explain: None,
+        partial: false,
+        skipped_stages: Vec::new(),
+    }
+}
+
+#[cfg(feature = "lex")]
+/// Finish a search whose `filter` left no candidate frames, attaching the explain if one was
+/// requested.
+pub(super) fn filtered_out_response(
+    mut response: SearchResponse,
+    explain: Option<QueryExplain>,
+    filter: &str,
+) -> SearchResponse {
+    if let Some(mut explain) = explain {
+        explain.filters.push(ExplainFilter {
+            name: filter.to_string(),
+            candidates: 0,
+        });
+        explain.engine = response.engine.clone();
+        response.explain = Some(explain);
+    }
+    response
+}
+
+#[cfg(feature = "lex")]
+/// Note in a requested explain that `filter` narrowed the search to `candidates`; filters
+/// that did not restrict the candidates are left out.
+pub(super) fn explain_filter(
+    explain: Option<&mut QueryExplain>,
+    filter: &str,
+    candidates: Option<&StdHashSet<FrameId>>,
+) {
+    if let (Some(explain), Some(candidates)) = (explain, candidates) {
+        explain.filters.push(ExplainFilter {
+            name: filter.to_string(),
+            candidates: candidates.len(),
+        });
+    }
+}
+
+/// Whether a search started at `start` has spent its `SearchRequest::time_budget_ms`.
+pub(crate) fn budget_spent(time_budget_ms: Option<u64>, start: std::time::Instant) -> bool {
+    time_budget_ms.is_some_and(|budget| start.elapsed() >= std::time::Duration::from_millis(budget))
+}
+
+#[cfg(feature = "lex")]
+/// Close a search stage: report its duration as a metric and in a requested explain.
+pub(super) fn lap_stage(
+    stage: &mut std::time::Instant,
+    name: &'static str,
+    explain: Option<&mut QueryExplain>,
+) {
+    if let Some(explain) = explain {
+        explain.record_stage(name, stage.elapsed());
     }
+    crate::metrics::lap(
+        crate::metrics::SEARCH_STAGE_DURATION,
+        stage,
+        &[("stage", name)],
+    );
 }
 
-pub(super) fn timestamp_to_rfc3339(timestamp: i64) -> Option<String> {
+#[cfg(feature = "lex")]
+/// Scores of `hits` keyed by frame and snippet range.
+pub(super) fn hit_scores(hits: &[SearchHit]) -> BTreeMap<(FrameId, (usize, usize)), f32> {
+    hits.iter()
+        .filter_map(|hit| Some(((hit.frame_id, hit.range), hit.score?)))
+        .collect()
+}
+
+#[cfg(feature = "lex")]
+/// Score components of `hits` from their scores before any boost and after the importance
+/// boost; the access boost is what remains.
+pub(super) fn explain_hits(
+    hits: &[SearchHit],
+    engine_scores: &BTreeMap<(FrameId, (usize, usize)), f32>,
+    importance_scores: &BTreeMap<(FrameId, (usize, usize)), f32>,
+) -> Vec<HitExplain> {
+    let factor = |after: Option<f32>, before: Option<f32>| match (after, before) {
+        (Some(after), Some(before)) if before != 0.0 => after / before,
+        _ => 1.0,
+    };
+    hits.iter()
+        .map(|hit| {
+            let key = (hit.frame_id, hit.range);
+            let engine_score = engine_scores.get(&key).copied();
+            let importance_score = importance_scores.get(&key).copied();
+            HitExplain {
+                rank: hit.rank,
+                frame_id: hit.frame_id,
+                range: hit.range,
+                engine_score,
+                importance_boost: factor(importance_score, engine_score),
+                access_boost: factor(hit.score, importance_score),
+                score: hit.score,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn timestamp_to_rfc3339(timestamp: i64) -> Option<String> {
     OffsetDateTime::from_unix_timestamp(timestamp)
         .ok()
         .map(|dt| {
@@ -181,7 +285,7 @@ fn render_hit(hit: &SearchHit) -> String {
     )
 }
 
-pub(super) fn collect_token_occurrences(
+pub(crate) fn collect_token_occurrences(
     content_lower: &str,
     tokens: &[String],
 ) -> Vec<(usize, usize)> {
@@ -204,6 +308,38 @@ pub(super) fn collect_token_occurrences(
     occurrences
 }
 
+/// Up to `max_windows` snippet windows of `text` that contain matched terms, each listing the
+/// terms it covers. Offsets are shifted by `offset`, the position of `text` in the frame.
+pub(crate) fn highlight_spans(
+    text: &str,
+    occurrences: &[(usize, usize)],
+    offset: usize,
+    window: usize,
+    max_windows: usize,
+) -> Vec<HighlightSpan> {
+    if max_windows == 0 || occurrences.is_empty() {
+        return Vec::new();
+    }
+    compute_snippet_slices(text, occurrences, window, max_windows)
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let terms: Vec<(usize, usize)> = occurrences
+                .iter()
+                .filter(|(s, e)| *s >= start && *e <= end)
+                .map(|(s, e)| (offset + s, offset + e))
+                .collect();
+            if terms.is_empty() {
+                return None;
+            }
+            Some(HighlightSpan {
+                range: (offset + start, offset + end),
+                text: text.get(start..end)?.to_string(),
+                terms,
+            })
+        })
+        .collect()
+}
+
 pub(crate) fn reorder_hits_by_token_matches(hits: &mut Vec<SearchHit>, tokens: &[String]) {
     if hits.is_empty() || tokens.is_empty() {
         return;
@@ -385,6 +521,7 @@ pub(crate) fn attach_temporal_metadata(memvid: &mut Memvid, hits: &mut [SearchHi
                     text,
                     byte_start: mention.byte_start,
                     byte_len: mention.byte_len,
+                    recurrence: mention.recurrence.map(|rule| rule.to_rrule()),
                 });
             }
 
@@ -406,8 +543,16 @@ pub(crate) fn attach_temporal_metadata(memvid: &mut Memvid, hits: &mut [SearchHi
 /// For each hit, looks up entities that are associated with the hit's frame.
 /// If the frame is a `DocumentChunk` (page), also checks the parent document frame
 /// for entities since NER extraction happens on the full document.
-pub(super) fn enrich_hits_with_entities(hits: &mut [SearchHit], memvid: &Memvid) {
+pub(super) fn enrich_hits_with_entities(
+    hits: &mut [SearchHit],
+    memvid: &Memvid,
+    redacted: &StdHashSet<FrameId>,
+) {
     for hit in hits.iter_mut() {
+        // A redacted hit must not regain the names its frame mentions.
+        if redacted.contains(&hit.frame_id) {
+            continue;
+        }
         let mut entities = memvid.frame_entities_for_search(hit.frame_id);
 
         // If no entities found and this is a chunk, check the parent frame
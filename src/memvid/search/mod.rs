@@ -8,10 +8,26 @@
 #[cfg(feature = "lex")]
 use std::collections::{BTreeSet, HashSet};
 #[cfg(feature = "lex")]
+use std::thread;
+#[cfg(feature = "lex")]
 use std::time::Instant;
 
+#[cfg(feature = "lex")]
+use crate::analysis::language::normalize_language;
+#[cfg(feature = "lex")]
+use crate::memvid::importance::importance_factor;
 use crate::memvid::lifecycle::Memvid;
-use crate::types::{FrameId, SearchEngineKind, SearchParams, SearchRequest, SearchResponse};
+#[cfg(feature = "lex")]
+use crate::memvid::sketch::SketchCandidate;
+#[cfg(feature = "lex")]
+use crate::metrics;
+#[cfg(feature = "lex")]
+use crate::search::{ParsedQuery, TantivyDocHit};
+use crate::types::{
+    FrameId, SearchEngineKind, SearchMode, SearchParams, SearchRequest, SearchResponse, VecRescore,
+};
+#[cfg(feature = "lex")]
+use crate::types::{QueryExplain, SkippedStage};
 use crate::{MemvidError, Result};
 
 mod api;
@@ -31,19 +47,311 @@ pub use api::{
 
 #[cfg(feature = "lex")]
 use fallback::{search_with_filters_only, search_with_lex_fallback};
+#[cfg(feature = "lex")]
+use helpers::{
+    budget_spent, explain_filter, explain_hits, filtered_out_response, hit_scores, lap_stage,
+};
 use helpers::{build_context, empty_search_response};
 #[cfg(feature = "lex")]
 pub use tantivy::parse_content_date_to_timestamp;
 #[cfg(feature = "lex")]
-use tantivy::try_tantivy_search;
+use tantivy::{tantivy_doc_hits, try_tantivy_search};
 #[cfg(feature = "temporal_track")]
 pub use time_filter::frame_ids_for_temporal_filter;
 #[cfg(feature = "lex")]
 use time_filter::frame_ids_in_date_range;
 
+/// A lexical search whose filters are resolved, waiting for retrieval.
+#[cfg(feature = "lex")]
+struct LexPlan {
+    request: SearchRequest,
+    parsed: ParsedQuery,
+    query_tokens: Vec<String>,
+    has_text_terms: bool,
+    /// Whether the sketch pre-filter may narrow the candidates.
+    wants_sketch: bool,
+    params: SearchParams,
+    explain: Option<QueryExplain>,
+    candidate_filter: Option<HashSet<FrameId>>,
+    start_time: Instant,
+    /// Start of the stage in progress, for explain and stage metrics.
+    stage: Instant,
+}
+
+/// Where `plan_search` left a request.
+#[cfg(feature = "lex")]
+enum Planned {
+    /// Answered without lexical retrieval, or filtered down to nothing.
+    Answered(SearchResponse),
+    Lex(Box<LexPlan>),
+}
+
+#[cfg(feature = "lex")]
+impl LexPlan {
+    /// Narrow the candidates to the frames the sketch pre-filter kept.
+    fn apply_sketch(&mut self, sketch_candidates: &[SketchCandidate]) {
+        if !sketch_candidates.is_empty() {
+            let sketch_set: HashSet<FrameId> =
+                sketch_candidates.iter().map(|c| c.frame_id).collect();
+
+            tracing::debug!(
+                sketch_candidates = sketch_candidates.len(),
+                "sketch pre-filter applied"
+            );
+
+            self.candidate_filter = match self.candidate_filter.take() {
+                Some(existing) => {
+                    // Intersection: keep only IDs that pass both filters
+                    let filtered: HashSet<FrameId> = existing
+                        .into_iter()
+                        .filter(|id| sketch_set.contains(id))
+                        .collect();
+                    if filtered.is_empty() {
+                        // Fall back to sketch-only if intersection is empty
+                        Some(sketch_set)
+                    } else {
+                        Some(filtered)
+                    }
+                }
+                None => Some(sketch_set),
+            };
+            explain_filter(
+                self.explain.as_mut(),
+                "sketch",
+                self.candidate_filter.as_ref(),
+            );
+        }
+        if let Some(explain) = self.explain.as_mut() {
+            explain.sketch_candidates = Some(sketch_candidates.len());
+        }
+        lap_stage(&mut self.stage, "sketch", self.explain.as_mut());
+    }
+}
+
+/// Sketch candidates to keep for a search: more than needed, BM25 selects the best.
+#[cfg(feature = "lex")]
+fn sketch_candidate_limit(params: &SearchParams) -> usize {
+    (params.top_k * 10).max(500)
+}
+
 #[cfg(feature = "lex")]
 impl Memvid {
     pub fn search(&mut self, request: SearchRequest) -> Result<SearchResponse> {
+        let started = Instant::now();
+        let response = self.search_unrecorded(request)?;
+        metrics::duration(
+            metrics::SEARCH_DURATION,
+            started.elapsed(),
+            &[("engine", response.engine.label())],
+        );
+        self.record_search_access(&response.hits);
+        Ok(response)
+    }
+
+    /// Run several searches in one call, e.g. the reformulations an agent issues per turn.
+    ///
+    /// Responses come back in request order. A request equal to an earlier one in the batch is
+    /// answered from its response instead of being searched again, so its hits count once in
+    /// the access statistics. The distinct lexical requests share one scan of the sketch track
+    /// and one Tantivy searcher, so every request sees the same index snapshot; requests the
+    /// lexical engine does not serve, such as `SearchMode::Sparse`, are answered as `search`
+    /// answers them. The batch is timed as a whole under `metrics::SEARCH_BATCH_DURATION`,
+    /// not per request. Fails on the first request that fails.
+    pub fn search_many(&mut self, requests: Vec<SearchRequest>) -> Result<Vec<SearchResponse>> {
+        self.search_many_with_workers(requests, 1)
+    }
+
+    /// `search_many`, scoring the distinct lexical requests against the shared searcher on up
+    /// to `workers` threads. Hits are resolved into responses on the caller thread, so the
+    /// responses are the same as `search_many`'s.
+    pub fn search_many_with_workers(
+        &mut self,
+        requests: Vec<SearchRequest>,
+        workers: usize,
+    ) -> Result<Vec<SearchResponse>> {
+        let started = Instant::now();
+        // Each request's index among the distinct requests.
+        let mut distinct: Vec<SearchRequest> = Vec::new();
+        let mut slots = Vec::with_capacity(requests.len());
+        for request in requests {
+            let slot = if let Some(slot) = distinct.iter().position(|seen| *seen == request) {
+                slot
+            } else {
+                distinct.push(request);
+                distinct.len() - 1
+            };
+            slots.push(slot);
+        }
+
+        let mut planned = distinct
+            .into_iter()
+            .map(|request| self.plan_search(request))
+            .collect::<Result<Vec<_>>>()?;
+
+        let sketched: Vec<usize> = planned
+            .iter()
+            .enumerate()
+            .filter_map(|(index, planned)| match planned {
+                Planned::Lex(plan) if plan.wants_sketch => Some(index),
+                _ => None,
+            })
+            .collect();
+        if !sketched.is_empty() {
+            let sketch_start = Instant::now();
+            let queries: Vec<(&str, usize)> = sketched
+                .iter()
+                .filter_map(|&index| match &planned[index] {
+                    Planned::Lex(plan) => Some((
+                        plan.request.query.as_str(),
+                        sketch_candidate_limit(&plan.params),
+                    )),
+                    Planned::Answered(_) => None,
+                })
+                .collect();
+            let found =
+                self.find_sketch_candidates_many(&queries, self.sketch_prefilter_threshold()?);
+            metrics::counter(
+                metrics::SKETCH_FRAMES_SCANNED,
+                self.sketch_track.len() as u64,
+                &[],
+            );
+            for (index, candidates) in sketched.into_iter().zip(found) {
+                if let Planned::Lex(plan) = &mut planned[index] {
+                    // The shared scan is this request's sketch stage.
+                    plan.stage = sketch_start;
+                    plan.apply_sketch(&candidates);
+                }
+            }
+        }
+
+        let scoring_start = Instant::now();
+        for planned in &mut planned {
+            if let Planned::Lex(plan) = planned {
+                plan.stage = scoring_start;
+            }
+        }
+        let mut scored = self.score_batch(&planned, workers).into_iter();
+        let mut responses = Vec::with_capacity(planned.len());
+        for planned in planned {
+            let doc_hits = scored.next().flatten();
+            let response = match planned {
+                Planned::Answered(response) => response,
+                Planned::Lex(mut plan) => {
+                    let response = self.retrieve(&mut plan, doc_hits)?;
+                    self.finish_search(*plan, response)?
+                }
+            };
+            responses.push(response);
+        }
+        // Per-request latency is not separable once work is shared, so the batch is timed as a
+        // whole rather than feeding `SEARCH_DURATION`.
+        metrics::duration(metrics::SEARCH_BATCH_DURATION, started.elapsed(), &[]);
+        metrics::counter(metrics::SEARCH_BATCH_REQUESTS, responses.len() as u64, &[]);
+        for response in &responses {
+            self.record_search_access(&response.hits);
+        }
+        Ok(slots
+            .into_iter()
+            .map(|slot| responses[slot].clone())
+            .collect())
+    }
+
+    /// Score the lexical plans of a batch through one searcher, on up to `workers` threads.
+    ///
+    /// Returns one entry per plan; `None` where the plan was already answered or there is no
+    /// Tantivy engine to score against.
+    fn score_batch(
+        &self,
+        planned: &[Planned],
+        workers: usize,
+    ) -> Vec<Option<Result<Vec<TantivyDocHit>>>> {
+        let Some(engine) = self.tantivy.as_ref() else {
+            return planned.iter().map(|_| None).collect();
+        };
+        let searcher = engine.searcher();
+        let score = |planned: &Planned| match planned {
+            Planned::Lex(plan) => Some(tantivy_doc_hits(
+                engine,
+                &searcher,
+                &plan.parsed,
+                &plan.request,
+                plan.candidate_filter.as_ref(),
+            )),
+            Planned::Answered(_) => None,
+        };
+        let threads = workers.min(planned.len());
+        if threads <= 1 {
+            return planned.iter().map(score).collect();
+        }
+
+        let score = &score;
+        thread::scope(|scope| {
+            let handles: Vec<_> = planned
+                .chunks(planned.len().div_ceil(threads))
+                .map(|plans| scope.spawn(move || plans.iter().map(score).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+
+    /// `search` without counting the returned hits in the access statistics; used by callers
+    /// such as `ask` that run several searches and record only the final result.
+    pub(crate) fn search_unrecorded(&mut self, request: SearchRequest) -> Result<SearchResponse> {
+        let mut plan = match self.plan_search(request)? {
+            Planned::Answered(response) => return Ok(response),
+            Planned::Lex(plan) => plan,
+        };
+        // SKETCH PRE-FILTER: Use sketch track for fast candidate generation if available
+        // This dramatically reduces the number of documents sent to BM25/Tantivy.
+        if plan.wants_sketch {
+            let sketch_options = crate::SketchSearchOptions {
+                // Use relaxed threshold for better recall - BM25 will rerank anyway
+                hamming_threshold: self.sketch_prefilter_threshold()?,
+                max_candidates: sketch_candidate_limit(&plan.params),
+                min_score: 0.0,
+            };
+            let candidates = self.find_sketch_candidates(&plan.request.query, Some(sketch_options));
+            metrics::counter(
+                metrics::SKETCH_FRAMES_SCANNED,
+                self.sketch_track.len() as u64,
+                &[],
+            );
+            plan.apply_sketch(&candidates);
+        }
+        let response = self.retrieve(&mut plan, None)?;
+        self.finish_search(*plan, response)
+    }
+
+    /// Resolve `request`'s filters, answering it outright when it needs no lexical retrieval
+    /// or its filters leave no candidates.
+    fn plan_search(&mut self, mut request: SearchRequest) -> Result<Planned> {
+        if request.snippet_chars == 0 {
+            if let Some(snippet_chars) = self.config()?.snippet_chars {
+                request.snippet_chars = snippet_chars;
+            }
+        }
+
+        // Diversify the reranked pool rather than rerank a diversified one
+        if request.return_parents || request.group_by_parent || request.diversify.is_some() {
+            return self.search_regrouped(request).map(Planned::Answered);
+        }
+        if let Some(config) = request.rerank.clone() {
+            return self
+                .search_reranked(request, &config)
+                .map(Planned::Answered);
+        }
+        // The sparse track is independent of the lexical index
+        if request.mode == SearchMode::Sparse {
+            return self.search_sparse(&request).map(Planned::Answered);
+        }
+
         if !self.lex_enabled {
             return Err(MemvidError::LexNotEnabled);
         }
@@ -57,7 +365,12 @@ impl Memvid {
 
         let start_time = Instant::now();
         // parse_query can return structured tokens; we only keep non-empty, lower-cased terms.
-        let parsed = crate::search::parse_query(&request.query)?;
+        let mut parsed = crate::search::parse_query(&request.query)?;
+        parsed.bind_meta_types(&self.meta_schema()?);
+        let analyzer = self.analyzer_config()?;
+        parsed.remove_stopwords(&analyzer);
+        let mut meta_filters = parsed.required_meta_filters();
+        meta_filters.extend(request.filters.iter().cloned());
         let mut query_tokens = parsed.text_tokens();
         query_tokens.retain(|token| !token.trim().is_empty());
         query_tokens = query_tokens
@@ -68,41 +381,60 @@ impl Memvid {
             .collect();
         let has_text_terms = !query_tokens.is_empty();
         let has_field_terms = parsed.contains_field_terms();
+        // Query tokens stay unexpanded: the lex fallback requires every token it is given.
+        parsed.expand_synonyms(&analyzer);
 
         if !has_text_terms && !has_field_terms {
             return Err(MemvidError::InvalidQuery {
                 reason: "query must include at least one search term or field filter".into(),
             });
         }
+        if let Some(hint) = request.language.as_deref() {
+            if normalize_language(hint).is_none() {
+                return Err(MemvidError::InvalidQuery {
+                    reason: format!("unsupported query language: {hint}"),
+                });
+            }
+        }
 
         let params = SearchParams {
             top_k: request.top_k,
             snippet_chars: request.snippet_chars,
             cursor: request.cursor.clone(),
+            vec_rescore: VecRescore::default(),
         };
+        let mut explain = request.explain.then(QueryExplain::default);
 
         let date_range = parsed.required_date_range();
         #[allow(unused_mut)]
         let mut candidate_filter: Option<HashSet<FrameId>> = if let Some(ref range) = date_range {
             if range.is_empty() {
                 let elapsed = start_time.elapsed().as_millis();
-                return Ok(empty_search_response(
-                    request.query.clone(),
-                    params.clone(),
-                    elapsed,
-                    SearchEngineKind::Tantivy,
-                ));
+                return Ok(Planned::Answered(filtered_out_response(
+                    empty_search_response(
+                        request.query.clone(),
+                        params.clone(),
+                        elapsed,
+                        SearchEngineKind::Tantivy,
+                    ),
+                    explain,
+                    "date",
+                )));
             }
             match frame_ids_in_date_range(self, range)? {
                 Some(ids) => {
                     if ids.is_empty() {
                         let elapsed = start_time.elapsed().as_millis();
-                        return Ok(empty_search_response(
-                            request.query.clone(),
-                            params.clone(),
-                            elapsed,
-                            SearchEngineKind::Tantivy,
-                        ));
+                        return Ok(Planned::Answered(filtered_out_response(
+                            empty_search_response(
+                                request.query.clone(),
+                                params.clone(),
+                                elapsed,
+                                SearchEngineKind::Tantivy,
+                            ),
+                            explain,
+                            "date",
+                        )));
                     }
                     Some(ids.into_iter().collect())
                 }
@@ -111,6 +443,7 @@ impl Memvid {
         } else {
             None
         };
+        explain_filter(explain.as_mut(), "date", candidate_filter.as_ref());
 
         #[cfg(feature = "temporal_track")]
         if let Some(ref temporal_filter) = request.temporal {
@@ -119,12 +452,16 @@ impl Memvid {
                     Some(ids) => {
                         if ids.is_empty() {
                             let elapsed = start_time.elapsed().as_millis();
-                            return Ok(empty_search_response(
-                                request.query.clone(),
-                                params.clone(),
-                                elapsed,
-                                SearchEngineKind::Tantivy,
-                            ));
+                            return Ok(Planned::Answered(filtered_out_response(
+                                empty_search_response(
+                                    request.query.clone(),
+                                    params.clone(),
+                                    elapsed,
+                                    SearchEngineKind::Tantivy,
+                                ),
+                                explain,
+                                "temporal",
+                            )));
                         }
                         let new_set: HashSet<FrameId> = ids.into_iter().collect();
                         candidate_filter = match candidate_filter {
@@ -135,17 +472,22 @@ impl Memvid {
                                     .collect();
                                 if filtered.is_empty() {
                                     let elapsed = start_time.elapsed().as_millis();
-                                    return Ok(empty_search_response(
-                                        request.query.clone(),
-                                        params.clone(),
-                                        elapsed,
-                                        SearchEngineKind::Tantivy,
-                                    ));
+                                    return Ok(Planned::Answered(filtered_out_response(
+                                        empty_search_response(
+                                            request.query.clone(),
+                                            params.clone(),
+                                            elapsed,
+                                            SearchEngineKind::Tantivy,
+                                        ),
+                                        explain,
+                                        "temporal",
+                                    )));
                                 }
                                 Some(filtered)
                             }
                             None => Some(new_set),
                         };
+                        explain_filter(explain.as_mut(), "temporal", candidate_filter.as_ref());
                     }
                     None => {}
                 }
@@ -157,12 +499,16 @@ impl Memvid {
             let replay_ids = self.get_replay_frame_ids(&request)?;
             if replay_ids.is_empty() {
                 let elapsed = start_time.elapsed().as_millis();
-                return Ok(empty_search_response(
-                    request.query.clone(),
-                    params.clone(),
-                    elapsed,
-                    SearchEngineKind::Tantivy,
-                ));
+                return Ok(Planned::Answered(filtered_out_response(
+                    empty_search_response(
+                        request.query.clone(),
+                        params.clone(),
+                        elapsed,
+                        SearchEngineKind::Tantivy,
+                    ),
+                    explain,
+                    "replay",
+                )));
             }
             let replay_set: HashSet<FrameId> = replay_ids.into_iter().collect();
             candidate_filter = match candidate_filter {
@@ -173,70 +519,134 @@ impl Memvid {
                         .collect();
                     if filtered.is_empty() {
                         let elapsed = start_time.elapsed().as_millis();
-                        return Ok(empty_search_response(
-                            request.query.clone(),
-                            params.clone(),
-                            elapsed,
-                            SearchEngineKind::Tantivy,
-                        ));
+                        return Ok(Planned::Answered(filtered_out_response(
+                            empty_search_response(
+                                request.query.clone(),
+                                params.clone(),
+                                elapsed,
+                                SearchEngineKind::Tantivy,
+                            ),
+                            explain,
+                            "replay",
+                        )));
                     }
                     Some(filtered)
                 }
                 None => Some(replay_set),
             };
+            explain_filter(explain.as_mut(), "replay", candidate_filter.as_ref());
         }
 
-        // SKETCH PRE-FILTER: Use sketch track for fast candidate generation if available
-        // This dramatically reduces the number of documents sent to BM25/Tantivy
-        if self.has_sketches() && has_text_terms && !request.no_sketch {
-            let sketch_start = Instant::now();
-            let sketch_options = crate::SketchSearchOptions {
-                // Use relaxed threshold for better recall - BM25 will rerank anyway
-                hamming_threshold: 32,
-                // Get more candidates than needed - BM25 will select the best
-                max_candidates: (params.top_k * 10).max(500),
-                min_score: 0.0,
+        if let Some(ref geo) = request.geo {
+            let geo_set: HashSet<FrameId> = self.frame_ids_in_geo(geo)?.into_iter().collect();
+            candidate_filter = match candidate_filter {
+                Some(existing) => Some(
+                    existing
+                        .into_iter()
+                        .filter(|id| geo_set.contains(id))
+                        .collect(),
+                ),
+                None => Some(geo_set),
             };
-            let sketch_candidates =
-                self.find_sketch_candidates(&request.query, Some(sketch_options));
-
-            if !sketch_candidates.is_empty() {
-                let sketch_set: HashSet<FrameId> =
-                    sketch_candidates.iter().map(|c| c.frame_id).collect();
-
-                tracing::debug!(
-                    sketch_candidates = sketch_candidates.len(),
-                    sketch_time_us = sketch_start.elapsed().as_micros(),
-                    "sketch pre-filter applied"
-                );
-
-                candidate_filter = match candidate_filter {
-                    Some(existing) => {
-                        // Intersection: keep only IDs that pass both filters
-                        let filtered: HashSet<FrameId> = existing
-                            .into_iter()
-                            .filter(|id| sketch_set.contains(id))
-                            .collect();
-                        if filtered.is_empty() {
-                            // Fall back to sketch-only if intersection is empty
-                            Some(sketch_set)
-                        } else {
-                            Some(filtered)
-                        }
-                    }
-                    None => Some(sketch_set),
-                };
+            if candidate_filter.as_ref().is_some_and(HashSet::is_empty) {
+                let elapsed = start_time.elapsed().as_millis();
+                return Ok(Planned::Answered(filtered_out_response(
+                    empty_search_response(
+                        request.query.clone(),
+                        params.clone(),
+                        elapsed,
+                        SearchEngineKind::Tantivy,
+                    ),
+                    explain,
+                    "geo",
+                )));
             }
+            explain_filter(explain.as_mut(), "geo", candidate_filter.as_ref());
         }
 
-        let mut response = if let Some(response) = try_tantivy_search(
+        if !meta_filters.is_empty() {
+            let meta_set: HashSet<FrameId> = self
+                .frame_ids_matching_meta(&meta_filters)?
+                .into_iter()
+                .collect();
+            candidate_filter = match candidate_filter {
+                Some(existing) => Some(
+                    existing
+                        .into_iter()
+                        .filter(|id| meta_set.contains(id))
+                        .collect(),
+                ),
+                None => Some(meta_set),
+            };
+            if candidate_filter.as_ref().is_some_and(HashSet::is_empty) {
+                let elapsed = start_time.elapsed().as_millis();
+                return Ok(Planned::Answered(filtered_out_response(
+                    empty_search_response(
+                        request.query.clone(),
+                        params.clone(),
+                        elapsed,
+                        SearchEngineKind::Tantivy,
+                    ),
+                    explain,
+                    "meta",
+                )));
+            }
+            explain_filter(explain.as_mut(), "meta", candidate_filter.as_ref());
+        }
+
+        let mut stage = start_time;
+        lap_stage(&mut stage, "filters", explain.as_mut());
+
+        // The sketch pre-filter is skipped under geo and metadata filters, whose candidates
+        // must not be widened by its fallback.
+        let wants_sketch = self.has_sketches()
+            && has_text_terms
+            && !request.no_sketch
+            && request.geo.is_none()
+            && meta_filters.is_empty();
+        Ok(Planned::Lex(Box::new(LexPlan {
+            request,
+            parsed,
+            query_tokens,
+            has_text_terms,
+            wants_sketch,
+            params,
+            explain,
+            candidate_filter,
+            start_time,
+            stage,
+        })))
+    }
+
+    /// Retrieve `plan`'s hits from Tantivy, or from the lex fallback when Tantivy cannot
+    /// answer. `doc_hits` are documents already scored for the plan by a batch.
+    fn retrieve(
+        &mut self,
+        plan: &mut LexPlan,
+        doc_hits: Option<Result<Vec<TantivyDocHit>>>,
+    ) -> Result<SearchResponse> {
+        let LexPlan {
+            request,
+            parsed,
+            query_tokens,
+            has_text_terms,
+            params,
+            explain,
+            candidate_filter,
+            start_time,
+            stage,
+            ..
+        } = plan;
+        let (has_text_terms, start_time) = (*has_text_terms, *start_time);
+        let response = if let Some(response) = try_tantivy_search(
             self,
-            &parsed,
-            &query_tokens,
-            &request,
-            &params,
+            parsed,
+            query_tokens,
+            request,
+            params,
             start_time,
             candidate_filter.as_ref(),
+            doc_hits,
         )? {
             response
         } else {
@@ -244,38 +654,106 @@ impl Memvid {
             if has_text_terms {
                 search_with_lex_fallback(
                     self,
-                    &parsed,
-                    &query_tokens,
-                    &request,
-                    &params,
+                    parsed,
+                    query_tokens,
+                    request,
+                    params,
                     start_time,
                     candidate_filter.as_ref(),
                 )?
             } else {
                 search_with_filters_only(
                     self,
-                    &parsed,
-                    &request,
-                    &params,
+                    parsed,
+                    request,
+                    params,
                     start_time,
                     candidate_filter.as_ref(),
                 )?
             }
         };
 
-        self.apply_acl_to_search_hits(
+        lap_stage(stage, "retrieve", explain.as_mut());
+        Ok(response)
+    }
+
+    /// Post-process retrieved hits: ACL, quarantine, boosts, entities, and suggestions.
+    fn finish_search(
+        &mut self,
+        plan: LexPlan,
+        mut response: SearchResponse,
+    ) -> Result<SearchResponse> {
+        let LexPlan {
+            request,
+            mut explain,
+            start_time,
+            mut stage,
+            ..
+        } = plan;
+        // Before ACL filtering, so redacted hits lose their links along with their metadata.
+        self.attach_related_frames(&mut response.hits)?;
+        let acl = self.apply_acl_to_search_hits(
             &mut response.hits,
             request.acl_context.as_ref(),
             request.acl_enforcement_mode,
         )?;
-        if request.acl_enforcement_mode == crate::types::AclEnforcementMode::Enforce {
+        if acl.rewritten {
             response.total_hits = response.hits.len();
             response.context = build_context(&response.hits);
         }
+        let excluded = self.exclude_quarantined_hits(&mut response.hits)?;
+        if excluded > 0 {
+            response.total_hits = response.total_hits.saturating_sub(excluded);
+            response.context = build_context(&response.hits);
+        }
+        // Retrieval already scaled the scores by importance; undo it to explain the engine score.
+        let importance_scores = explain.as_ref().map(|_| hit_scores(&response.hits));
+        let engine_scores = importance_scores.as_ref().map(|scores| {
+            let weights = self.importance_weights();
+            scores
+                .iter()
+                .map(|(&key, &score)| (key, score / importance_factor(&weights, key.0)))
+                .collect()
+        });
+        if request.access_boost && self.apply_access_boost(&mut response.hits)? {
+            response.context = build_context(&response.hits);
+        }
+        if let (Some(explain), Some(engine_scores), Some(importance_scores)) =
+            (explain.as_mut(), engine_scores, importance_scores)
+        {
+            explain.acl_denied = request.acl_context.is_some().then_some(acl.denied);
+            explain.quarantined = excluded;
+            explain.hits = explain_hits(&response.hits, &engine_scores, &importance_scores);
+        }
 
         // Enrich hits with Logic-Mesh entities if mesh is available
         if self.has_logic_mesh() {
-            helpers::enrich_hits_with_entities(&mut response.hits, self);
+            if budget_spent(request.time_budget_ms, start_time) {
+                response.skip_stage(SkippedStage::Entities);
+            } else {
+                helpers::enrich_hits_with_entities(&mut response.hits, self, &acl.redacted);
+            }
+        }
+        // The spelling dictionary is built from every frame, so a classification policy turns
+        // suggestions off rather than let them surface words from frames above clearance.
+        #[cfg(feature = "spelling")]
+        if response.total_hits < crate::memvid::spelling::SPELLING_HIT_THRESHOLD
+            && request
+                .acl_context
+                .as_ref()
+                .is_none_or(|context| context.classification.is_none())
+        {
+            if budget_spent(request.time_budget_ms, start_time) {
+                response.skip_stage(SkippedStage::Spelling);
+            } else {
+                response.suggestions = self.spelling_suggestions(&request.query)?;
+            }
+        }
+
+        lap_stage(&mut stage, "post", explain.as_mut());
+        if let Some(mut explain) = explain {
+            explain.engine = response.engine.clone();
+            response.explain = Some(explain);
         }
 
         // Record the search action if a replay session is active
@@ -296,7 +774,29 @@ impl Memvid {
 
 #[cfg(not(feature = "lex"))]
 impl Memvid {
-    pub fn search(&mut self, _request: SearchRequest) -> Result<SearchResponse> {
+    pub fn search(&mut self, request: SearchRequest) -> Result<SearchResponse> {
+        self.search_unrecorded(request)
+    }
+
+    pub fn search_many(&mut self, requests: Vec<SearchRequest>) -> Result<Vec<SearchResponse>> {
+        requests
+            .into_iter()
+            .map(|request| self.search_unrecorded(request))
+            .collect()
+    }
+
+    pub fn search_many_with_workers(
+        &mut self,
+        requests: Vec<SearchRequest>,
+        _workers: usize,
+    ) -> Result<Vec<SearchResponse>> {
+        self.search_many(requests)
+    }
+
+    pub(crate) fn search_unrecorded(&mut self, request: SearchRequest) -> Result<SearchResponse> {
+        if request.mode == SearchMode::Sparse {
+            return self.search_sparse(&request);
+        }
         Err(MemvidError::LexNotEnabled)
     }
 }
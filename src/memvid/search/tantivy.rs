@@ -4,21 +4,77 @@
 #[cfg(feature = "temporal_track")]
 use super::helpers::attach_temporal_metadata;
 use super::helpers::{
-    build_context, collect_token_occurrences, parse_cursor, timestamp_to_rfc3339,
+    budget_spent, build_context, collect_token_occurrences, highlight_spans, parse_cursor,
+    timestamp_to_rfc3339,
 };
 use crate::Result;
+use crate::analysis::language::normalize_language;
 use crate::lex::compute_snippet_slices;
 use crate::memvid::frame::ChunkInfo;
+use crate::memvid::importance::importance_factor;
 use crate::memvid::lifecycle::Memvid;
-use crate::search::{EvaluationContext, ParsedQuery};
+use crate::search::{EvaluationContext, ParsedQuery, TantivyDocHit, TantivyEngine};
 use crate::types::{
     FrameId, SearchEngineKind, SearchHit, SearchHitMetadata, SearchParams, SearchRequest,
-    SearchResponse,
+    SearchResponse, SkippedStage,
 };
 use log::warn;
 use std::collections::HashSet;
 use std::time::Instant;
+use tantivy::Searcher;
+
+/// Score `request` against the index through `searcher`, returning the matching documents.
+///
+/// Only reads the engine, so the searches of a batch can run on several threads against one
+/// searcher before `try_tantivy_search` turns each result into hits.
+pub(super) fn tantivy_doc_hits(
+    engine: &TantivyEngine,
+    searcher: &Searcher,
+    parsed: &ParsedQuery,
+    request: &SearchRequest,
+    candidate_filter: Option<&HashSet<FrameId>>,
+) -> Result<Vec<TantivyDocHit>> {
+    let mut doc_limit = page_docs(request).saturating_mul(4).max(20);
+    if let Some(filter) = candidate_filter {
+        doc_limit = doc_limit.min(filter.len().max(1));
+    }
+    let (uri_filter, scope_filter) = uri_filters(request);
+    let frame_filter_vec: Option<Vec<u64>> =
+        candidate_filter.map(|set| set.iter().copied().collect());
+    engine.search_documents_in(
+        searcher,
+        parsed,
+        uri_filter,
+        scope_filter,
+        frame_filter_vec.as_deref(),
+        request.language.as_deref().and_then(normalize_language),
+        doc_limit,
+    )
+}
+
+/// Documents needed to fill the requested page.
+fn page_docs(request: &SearchRequest) -> usize {
+    let offset_hint = request
+        .cursor
+        .as_deref()
+        .and_then(|cursor| cursor.parse::<usize>().ok())
+        .unwrap_or(0);
+    request.top_k.max(1) + offset_hint
+}
+
+/// The URI filter, or the scope prefix when no exact URI is requested.
+fn uri_filters(request: &SearchRequest) -> (Option<&str>, Option<&str>) {
+    let uri_filter = request.uri.as_deref();
+    let scope_filter = if uri_filter.is_some() {
+        None
+    } else {
+        request.scope.as_deref()
+    };
+    (uri_filter, scope_filter)
+}
 
+/// `doc_hits` carries documents already scored by `tantivy_doc_hits`; when `None` the
+/// request is scored here.
 pub(super) fn try_tantivy_search(
     memvid: &mut Memvid,
     parsed: &ParsedQuery,
@@ -27,6 +83,7 @@ pub(super) fn try_tantivy_search(
     params: &SearchParams,
     start_time: Instant,
     candidate_filter: Option<&HashSet<FrameId>>,
+    doc_hits: Option<Result<Vec<TantivyDocHit>>>,
 ) -> Result<Option<SearchResponse>> {
     let engine = match memvid.tantivy.as_ref() {
         Some(engine) => engine,
@@ -38,41 +95,30 @@ pub(super) fn try_tantivy_search(
     // Use stemmed tokens for evaluation to match what Tantivy indexed.
     // Tantivy stems during indexing (e.g., "technology" → "technolog"), so we need
     // to search for stemmed forms in the content as well.
+    // Synonyms are analyzed too so hits matched through them get snippets.
+    let analyzer = memvid.analyzer_config()?;
     let mut stemmed_tokens = Vec::new();
     for token in query_tokens {
-        let analyzed = engine.analyse_text(token);
-        stemmed_tokens.extend(analyzed);
+        stemmed_tokens.extend(engine.analyse_text(token));
+        for synonym in analyzer.synonyms_of(token) {
+            stemmed_tokens.extend(engine.analyse_text(synonym));
+        }
     }
     let stemmed_tokens = stemmed_tokens;
 
-    let offset_hint = request
-        .cursor
-        .as_deref()
-        .and_then(|cursor| cursor.parse::<usize>().ok())
-        .unwrap_or(0);
-    let base_docs = request.top_k.max(1) + offset_hint;
-    let mut doc_limit = base_docs.saturating_mul(4).max(20);
-    if let Some(filter) = candidate_filter {
-        doc_limit = doc_limit.min(filter.len().max(1));
-    }
-    let uri_filter = request.uri.as_deref();
-    let scope_filter = if uri_filter.is_some() {
-        None
-    } else {
-        request.scope.as_deref()
-    };
-
-    let frame_filter_vec: Option<Vec<u64>> =
-        candidate_filter.map(|set| set.iter().copied().collect());
-    let frame_filter_slice = frame_filter_vec.as_deref();
+    let base_docs = page_docs(request);
+    let (uri_filter, scope_filter) = uri_filters(request);
 
-    let search_hits = match engine.search_documents(
-        parsed,
-        uri_filter,
-        scope_filter,
-        frame_filter_slice,
-        doc_limit,
-    ) {
+    let doc_hits = doc_hits.unwrap_or_else(|| {
+        tantivy_doc_hits(
+            engine,
+            &engine.searcher(),
+            parsed,
+            request,
+            candidate_filter,
+        )
+    });
+    let search_hits = match doc_hits {
         Ok(hits) => hits,
         Err(err) => {
             warn!("tantivy search failed: {err}");
@@ -120,7 +166,14 @@ pub(super) fn try_tantivy_search(
     let max_snippets_per_doc = request.top_k.max(1);
     let mut evaluated = Vec::new();
     let mut stale_skips = 0u32;
-    for hit in search_hits {
+    let total_candidates = search_hits.len();
+    let mut truncated = None;
+    for (index, hit) in search_hits.into_iter().enumerate() {
+        // Over budget, stop once the requested page can be filled
+        if evaluated.len() >= base_docs && budget_spent(request.time_budget_ms, start_time) {
+            truncated = Some(index);
+            break;
+        }
         let Some(frame_meta) = memvid
             .toc
             .frames
@@ -198,6 +251,15 @@ pub(super) fn try_tantivy_search(
         evaluated.push((hit, occurrences, slices, chunk_info, effective_ts));
     }
 
+    // Importance scales BM25 before the recency blend, so boosted frames compete for the
+    // page instead of only being reordered within it.
+    let importance = memvid.importance_weights();
+    if !importance.is_empty() {
+        for (hit, ..) in &mut evaluated {
+            hit.score *= importance_factor(&importance, hit.frame_id);
+        }
+    }
+
     // Apply recency boosting: re-sort by combined score (BM25 + recency)
     // This helps knowledge-update questions find the most recent information
     if evaluated.len() > 1 {
@@ -308,6 +370,13 @@ pub(super) fn try_tantivy_search(
         } = chunk_info;
         let chunk_bytes = chunk_text.as_bytes();
         let chunk_range = (chunk_start, chunk_end);
+        let highlights = highlight_spans(
+            &chunk_text,
+            &occurrences,
+            chunk_start,
+            snippet_window,
+            request.highlight_windows,
+        );
 
         for (start, end) in slices {
             if produced < offset {
@@ -336,6 +405,7 @@ pub(super) fn try_tantivy_search(
                 created_at: timestamp_to_rfc3339(frame_meta.timestamp),
                 content_dates: frame_meta.content_dates.clone(),
                 entities: Vec::new(),
+                related: Vec::new(),
                 extra_metadata: frame_meta.extra_metadata.clone(),
                 #[cfg(feature = "temporal_track")]
                 temporal: None,
@@ -359,6 +429,7 @@ pub(super) fn try_tantivy_search(
                 chunk_text: Some(chunk_text.clone()),
                 score: Some(hit.score),
                 metadata: Some(metadata),
+                highlights: highlights.clone(),
             });
             produced += 1;
         }
@@ -374,7 +445,7 @@ pub(super) fn try_tantivy_search(
     let elapsed_ms = start_time.elapsed().as_millis().max(1);
     let context = build_context(&hits);
 
-    Ok(Some(SearchResponse {
+    let mut response = SearchResponse {
         query: request.query.clone(),
         elapsed_ms,
         total_hits: total_slices,
@@ -384,7 +455,18 @@ pub(super) fn try_tantivy_search(
         next_cursor,
         engine: SearchEngineKind::Tantivy,
         stale_index_skips: stale_skips,
-    }))
+        suggestions: Vec::new(),
+        explain: None,
+        partial: false,
+        skipped_stages: Vec::new(),
+    };
+    if let Some(evaluated) = truncated {
+        response.skip_stage(SkippedStage::Candidates {
+            evaluated,
+            total: total_candidates,
+        });
+    }
+    Ok(Some(response))
 }
 
 fn uri_matches(candidate: Option<&str>, expected: &str) -> bool {
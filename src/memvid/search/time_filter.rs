@@ -9,6 +9,8 @@ use crate::analysis::temporal::{
     TemporalContext, TemporalNormalizer, TemporalResolution, TemporalResolutionValue,
 };
 #[cfg(feature = "temporal_track")]
+use crate::analysis::timezone::timezone_offset;
+#[cfg(feature = "temporal_track")]
 use crate::types::{TemporalFilter, TemporalMention, TemporalMentionKind};
 #[cfg(feature = "temporal_track")]
 use std::collections::{HashMap, HashSet};
@@ -63,8 +65,10 @@ pub fn frame_ids_for_temporal_filter(
         return Ok(None);
     }
 
-    let Some(bounds) = resolve_temporal_bounds(filter)? else {
-        return Ok(None);
+    let bounds = match resolve_temporal_bounds(filter)? {
+        Some(bounds) => bounds,
+        None if filter.recurring_only => Bounds::new(None, None),
+        None => return Ok(None),
     };
 
     if let (Some(start), Some(end)) = (bounds.start, bounds.end) {
@@ -76,8 +80,8 @@ pub fn frame_ids_for_temporal_filter(
     if let Some(track) = memvid.temporal_track_ref()? {
         let capabilities = track.capabilities();
         if capabilities.has_mentions {
-            let ids = frame_ids_from_mentions(&track.mentions, &bounds);
-            if !ids.is_empty() || !capabilities.has_anchors {
+            let ids = frame_ids_from_mentions(&track.mentions, &bounds, filter.recurring_only);
+            if !ids.is_empty() || !capabilities.has_anchors || filter.recurring_only {
                 return Ok(Some(ids));
             }
         }
@@ -87,6 +91,10 @@ pub fn frame_ids_for_temporal_filter(
         }
     }
 
+    if filter.recurring_only {
+        return Ok(Some(Vec::new()));
+    }
+
     let fallback = crate::search::DateRange {
         start: bounds.start,
         end: bounds.end,
@@ -213,6 +221,10 @@ fn resolution_to_bounds(resolution: &TemporalResolution) -> Result<Bounds> {
             Some(start.unix_timestamp()),
             Some(end.unix_timestamp()),
         )),
+        TemporalResolutionValue::Recurring { start, .. } => {
+            let ts = date_to_timestamp(*start);
+            Ok(Bounds::new(Some(ts), Some(ts)))
+        }
         TemporalResolutionValue::Month { year, month } => {
             let start_date = Date::from_calendar_date(*year, *month, 1).map_err(|_| {
                 MemvidError::InvalidQuery {
@@ -230,49 +242,18 @@ fn resolution_to_bounds(resolution: &TemporalResolution) -> Result<Bounds> {
 
 #[cfg(feature = "temporal_track")]
 fn parse_utc_offset(spec: Option<&str>) -> Result<UtcOffset> {
-    let Some(value) = spec.filter(|s| !s.trim().is_empty()) else {
-        return Ok(UtcOffset::UTC);
-    };
-    let trimmed = value.trim();
-    if trimmed.eq_ignore_ascii_case("utc") || trimmed.eq_ignore_ascii_case("z") {
-        return Ok(UtcOffset::UTC);
-    }
-    if let Some(stripped) = trimmed
-        .strip_prefix('+')
-        .or_else(|| trimmed.strip_prefix('-'))
-    {
-        let sign = if trimmed.starts_with('-') { -1 } else { 1 };
-        let digits: String = stripped.chars().filter(|c| c.is_ascii_digit()).collect();
-        if digits.len() == 2 || digits.len() == 4 {
-            let hours: i32 = digits[0..2]
-                .parse()
-                .map_err(|_| MemvidError::InvalidQuery {
-                    reason: format!("invalid timezone offset: {value}"),
-                })?;
-            let minutes: i32 = if digits.len() == 4 {
-                digits[2..4]
-                    .parse()
-                    .map_err(|_| MemvidError::InvalidQuery {
-                        reason: format!("invalid timezone offset: {value}"),
-                    })?
-            } else {
-                0
-            };
-            let total_minutes = sign * (hours * 60 + minutes);
-            return UtcOffset::from_whole_seconds(total_minutes * 60).map_err(|_| {
-                MemvidError::InvalidQuery {
-                    reason: format!("invalid timezone offset: {value}"),
-                }
-            });
-        }
+    match spec.filter(|s| !s.trim().is_empty()) {
+        Some(value) => timezone_offset(value, OffsetDateTime::now_utc()),
+        None => Ok(UtcOffset::UTC),
     }
-    Err(MemvidError::InvalidQuery {
-        reason: format!("unsupported timezone specifier: {value}"),
-    })
 }
 
 #[cfg(feature = "temporal_track")]
-fn frame_ids_from_mentions(mentions: &[TemporalMention], bounds: &Bounds) -> Vec<u64> {
+fn frame_ids_from_mentions(
+    mentions: &[TemporalMention],
+    bounds: &Bounds,
+    recurring_only: bool,
+) -> Vec<u64> {
     if mentions.is_empty() {
         return Vec::new();
     }
@@ -281,6 +262,17 @@ fn frame_ids_from_mentions(mentions: &[TemporalMention], bounds: &Bounds) -> Vec
     let mut ranges: HashMap<(u64, u32, u32), Vec<i64>> = HashMap::new();
 
     for mention in mentions {
+        if mention.recurrence.is_some() {
+            let from = bounds.start.unwrap_or(i64::MIN);
+            let until = bounds.end.unwrap_or(i64::MAX);
+            if mention.occurs_between(from, until) {
+                frames.insert(mention.frame_id);
+            }
+            continue;
+        }
+        if recurring_only {
+            continue;
+        }
         let key = (mention.frame_id, mention.byte_start, mention.byte_len);
         match mention.kind {
             TemporalMentionKind::RangeStart => {
@@ -345,3 +337,75 @@ fn last_day_in_month(year: i32, month: time::Month) -> Date {
     }
     date
 }
+
+#[cfg(all(test, feature = "temporal_track"))]
+mod tests {
+    use super::*;
+    use crate::types::{TemporalMentionFlags, TemporalRecurrence};
+
+    // Wednesday 2024-01-03 12:00 UTC
+    const WEDNESDAY_NOON: i64 = 1_704_283_200;
+    const DAY: i64 = 86_400;
+
+    fn resolve_recurrence(phrase: &str) -> (i64, TemporalRecurrence) {
+        let anchor = OffsetDateTime::from_unix_timestamp(WEDNESDAY_NOON).expect("anchor");
+        let normalizer = TemporalNormalizer::new(TemporalContext::new(anchor, "UTC"));
+        match normalizer.resolve(phrase).expect("resolve").value {
+            TemporalResolutionValue::Recurring { start, rule } => {
+                (start.midnight().assume_utc().unix_timestamp(), rule)
+            }
+            other => panic!("expected a recurring resolution, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn recurring_mentions_match_windows_they_repeat_into() {
+        let (first, rule) = resolve_recurrence("every monday and thursday");
+        assert_eq!(rule.to_rrule(), "FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,TH");
+        let standup = TemporalMention::new(
+            first,
+            1,
+            0,
+            25,
+            TemporalMentionKind::Recurring,
+            800,
+            0,
+            TemporalMentionFlags(0),
+        )
+        .with_recurrence(rule);
+        let dentist = TemporalMention::new(
+            WEDNESDAY_NOON - DAY / 2 + DAY,
+            2,
+            0,
+            8,
+            TemporalMentionKind::Date,
+            800,
+            0,
+            TemporalMentionFlags(0),
+        );
+        let mentions = [standup, dentist];
+        let window = |start: i64, len: i64| Bounds::new(Some(start), Some(start + len - 1));
+
+        // Monday 2024-01-08 through Sunday 2024-01-14
+        let next_week = WEDNESDAY_NOON - DAY / 2 + 5 * DAY;
+        assert_eq!(
+            frame_ids_from_mentions(&mentions, &window(next_week, 7 * DAY), false),
+            vec![1]
+        );
+        // Thursday 2024-01-04 holds both the dentist and a standup
+        let thursday = WEDNESDAY_NOON - DAY / 2 + DAY;
+        let mut ids = frame_ids_from_mentions(&mentions, &window(thursday, DAY), false);
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(
+            frame_ids_from_mentions(&mentions, &window(thursday, DAY), true),
+            vec![1]
+        );
+        // Tuesday 2024-03-05 matches nothing
+        let tuesday = thursday + 61 * DAY;
+        assert!(frame_ids_from_mentions(&mentions, &window(tuesday, DAY), false).is_empty());
+
+        let (_, fortnightly) = resolve_recurrence("every other friday");
+        assert_eq!(fortnightly.to_rrule(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=FR");
+    }
+}
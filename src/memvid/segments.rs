@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::io::{Read, Seek, SeekFrom, Write};
 
 #[cfg(feature = "lex")]
@@ -7,11 +8,12 @@ use crate::lex::{LexIndexArtifact, LexIndexBuilder};
 #[cfg(feature = "lex")]
 use crate::types::TantivySegmentDescriptor;
 use crate::types::{
-    FrameId, FrameRole, FrameStatus, LexSegmentDescriptor, SegmentCommon, TimeSegmentDescriptor,
-    VecSegmentDescriptor, VectorCompression,
+    FrameId, FrameRole, FrameStatus, LexSegmentDescriptor, SegmentCommon, SegmentCompression,
+    TimeSegmentDescriptor, VecSegmentDescriptor, VectorCompression,
 };
 use crate::vec::{VecIndexArtifact, VecIndexBuilder};
 use crate::vec_pq::{QuantizedVecIndexArtifact, QuantizedVecIndexBuilder};
+use crate::vec_scalar::{ScalarEncoding, ScalarQuantizedVecIndexBuilder};
 use crate::{MemvidError, Result, TimeIndexEntry, time_index_append};
 #[cfg(feature = "temporal_track")]
 use crate::{
@@ -289,9 +291,65 @@ impl Memvid {
                     bytes_uncompressed: 0, // PQ doesn't track uncompressed size
                 }))
             }
+            VectorCompression::Int8 | VectorCompression::Binary => {
+                let Some(mut builder) = ScalarEncoding::from_compression(&effective_compression)
+                    .map(ScalarQuantizedVecIndexBuilder::new)
+                else {
+                    return Ok(None);
+                };
+                for (frame_id, vector) in embeddings {
+                    if vector.is_empty() {
+                        continue;
+                    }
+                    builder.add_document(*frame_id, vector)?;
+                }
+
+                let VecIndexArtifact {
+                    bytes,
+                    vector_count,
+                    dimension: artifact_dimension,
+                    checksum,
+                    #[cfg(feature = "parallel_segments")]
+                    bytes_uncompressed,
+                } = builder.finish()?;
+
+                if vector_count == 0 {
+                    return Ok(None);
+                }
+
+                Ok(Some(VecSegmentArtifact {
+                    bytes,
+                    vector_count,
+                    dimension: artifact_dimension.max(dimension),
+                    checksum,
+                    compression: effective_compression,
+                    #[cfg(feature = "parallel_segments")]
+                    bytes_uncompressed,
+                }))
+            }
         }
     }
 
+    /// Compress segment `bytes` with the file's default segment codec, returning the bytes to
+    /// store and the compression to record in the descriptor.
+    fn encode_segment_bytes<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> Result<(Cow<'a, [u8]>, SegmentCompression)> {
+        let codec = self.compression_defaults()?.segments;
+        let compression = codec.segment_compression();
+        if compression == SegmentCompression::None {
+            return Ok((Cow::Borrowed(bytes), compression));
+        }
+        Ok((Cow::Owned(codec.compress(bytes)?), compression))
+    }
+
+    /// Stored bytes of a catalog segment with its compression undone.
+    pub(crate) fn read_segment_bytes(&mut self, common: &SegmentCommon) -> Result<Vec<u8>> {
+        let bytes = self.read_range(common.bytes_offset, common.bytes_length)?;
+        Ok(common.compression.decompress(&bytes)?)
+    }
+
     pub(crate) fn append_lex_segment(
         &mut self,
         artifact: &LexSegmentArtifact,
@@ -303,21 +361,19 @@ impl Memvid {
             });
         }
 
+        let (bytes, compression) = self.encode_segment_bytes(&artifact.bytes)?;
         let offset = self.data_end;
-        let new_end = offset + artifact.bytes.len() as u64;
+        let new_end = offset + bytes.len() as u64;
 
         // Write at current data_end
         self.file.seek(SeekFrom::Start(offset))?;
-        self.file.write_all(&artifact.bytes)?;
+        self.file.write_all(&bytes)?;
         self.file.sync_all()?;
         self.data_end = new_end;
 
-        let common = SegmentCommon::new(
-            segment_id,
-            offset,
-            artifact.bytes.len() as u64,
-            artifact.checksum,
-        );
+        let mut common =
+            SegmentCommon::new(segment_id, offset, bytes.len() as u64, artifact.checksum);
+        common.compression = compression;
         Ok(LexSegmentDescriptor::from_common(
             common,
             artifact.doc_count,
@@ -335,21 +391,22 @@ impl Memvid {
             });
         }
 
+        let (bytes, compression) = self.encode_segment_bytes(&artifact.bytes)?;
         let offset = self.data_end;
-        let new_end = offset + artifact.bytes.len() as u64;
+        let new_end = offset + bytes.len() as u64;
 
         // Seek to write position
         self.file.seek(SeekFrom::Start(offset))?;
 
         // Write the actual data
-        self.file.write_all(&artifact.bytes)?;
+        self.file.write_all(&bytes)?;
         self.file.sync_all()?;
 
         // VERIFY: Read back the first few bytes to confirm write persisted
         self.file.seek(SeekFrom::Start(offset))?;
-        let mut verify_buf = vec![0u8; 16.min(artifact.bytes.len())];
+        let mut verify_buf = vec![0u8; 16.min(bytes.len())];
         self.file.read_exact(&mut verify_buf)?;
-        let expected = &artifact.bytes[..verify_buf.len()];
+        let expected = &bytes[..verify_buf.len()];
         if verify_buf != expected {
             return Err(MemvidError::CheckpointFailed {
                 reason: format!("vec segment write verification failed at offset {offset}"),
@@ -358,12 +415,9 @@ impl Memvid {
 
         self.data_end = new_end;
 
-        let common = SegmentCommon::new(
-            segment_id,
-            offset,
-            artifact.bytes.len() as u64,
-            artifact.checksum,
-        );
+        let mut common =
+            SegmentCommon::new(segment_id, offset, bytes.len() as u64, artifact.checksum);
+        common.compression = compression;
 
         tracing::debug!(
             segment_id = common.segment_id,
@@ -464,21 +518,19 @@ impl Memvid {
             });
         }
 
+        let (bytes, compression) = self.encode_segment_bytes(&artifact.bytes)?;
         let offset = self.data_end;
-        let new_end = offset + artifact.bytes.len() as u64;
+        let new_end = offset + bytes.len() as u64;
 
         // Write at current data_end
         self.file.seek(SeekFrom::Start(offset))?;
-        self.file.write_all(&artifact.bytes)?;
+        self.file.write_all(&bytes)?;
         self.file.sync_all()?;
         self.data_end = new_end;
 
-        let common = SegmentCommon::new(
-            segment_id,
-            offset,
-            artifact.bytes.len() as u64,
-            artifact.checksum,
-        );
+        let mut common =
+            SegmentCommon::new(segment_id, offset, bytes.len() as u64, artifact.checksum);
+        common.compression = compression;
         Ok(TimeSegmentDescriptor::from_common(
             common,
             artifact.entry_count,
@@ -0,0 +1,133 @@
+//! "More like this": frames related to an existing frame.
+//!
+//! Uses the frame's stored embedding when the vector index has one, otherwise the `SimHash`
+//! of its sketch (see `Memvid::build_all_sketches`). Either way the text is never re-embedded.
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::search::helpers::timestamp_to_rfc3339;
+use crate::types::sketch_track::generate_sketch;
+use crate::types::{Frame, FrameId, FrameStatus, SearchHit, SearchHitMetadata};
+
+/// Characters of each similar frame's text returned as its snippet.
+const SIMILAR_SNIPPET_CHARS: usize = 200;
+
+impl Memvid {
+    /// Up to `top_k` active frames most similar to `frame_id`, best first.
+    ///
+    /// The frame itself and every chunk of the same parent document are excluded. Returns no
+    /// hits when the frame has neither an embedding nor text to sketch, or when no other frame
+    /// has one to compare against.
+    pub fn similar(&mut self, frame_id: FrameId, top_k: usize) -> Result<Vec<SearchHit>> {
+        let frame = self.frame_by_id(frame_id)?;
+        if top_k == 0 {
+            return Ok(Vec::new());
+        }
+        let family = frame.parent_id.unwrap_or(frame.id);
+        let scored = match self.frame_embedding(frame_id)? {
+            Some(embedding) => {
+                let limit = top_k.saturating_mul(4).max(top_k + 16);
+                self.search_vec(&embedding, limit)?
+                    .into_iter()
+                    .map(|hit| (hit.frame_id, 1.0 - hit.distance))
+                    .collect()
+            }
+            None => self.similar_by_sketch(&frame),
+        };
+
+        let quarantined = self.quarantine_set()?;
+        let mut hits = Vec::new();
+        for (candidate_id, score) in scored {
+            if hits.len() == top_k {
+                break;
+            }
+            let Some(candidate) = usize::try_from(candidate_id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+                .cloned()
+            else {
+                continue;
+            };
+            if candidate.status != FrameStatus::Active
+                || candidate.parent_id.unwrap_or(candidate.id) == family
+                || quarantined.contains(&candidate.id)
+            {
+                continue;
+            }
+            let rank = hits.len() + 1;
+            hits.push(self.similar_hit(&candidate, rank, score)?);
+        }
+        Ok(hits)
+    }
+
+    /// Sketched frames ordered by `SimHash` agreement with `frame`, scored in `[0, 1]`.
+    fn similar_by_sketch(&self, frame: &Frame) -> Vec<(FrameId, f32)> {
+        let simhash = match self.sketch_track.get(frame.id) {
+            Some(entry) => entry.simhash,
+            None => match frame.search_text.as_deref().filter(|text| !text.is_empty()) {
+                Some(text) => {
+                    generate_sketch(frame.id, text, self.sketch_track.variant, None).simhash
+                }
+                None => return Vec::new(),
+            },
+        };
+        let mut scored: Vec<(FrameId, f32)> = self
+            .sketch_track
+            .iter()
+            .map(|entry| {
+                // Safe: a Hamming distance between u64s is at most 64
+                #[allow(clippy::cast_precision_loss)]
+                let distance = entry.hamming_distance(simhash) as f32;
+                (entry.frame_id, 1.0 - distance / 64.0)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored
+    }
+
+    fn similar_hit(&mut self, frame: &Frame, rank: usize, score: f32) -> Result<SearchHit> {
+        let snippet: String = match self.frame_snippet_text(frame) {
+            Some(text) => text?.chars().take(SIMILAR_SNIPPET_CHARS).collect(),
+            None => self
+                .frame_content(frame)?
+                .chars()
+                .take(SIMILAR_SNIPPET_CHARS)
+                .collect(),
+        };
+        let uri = frame
+            .uri
+            .clone()
+            .unwrap_or_else(|| crate::default_uri(frame.id));
+        let title = frame
+            .title
+            .clone()
+            .or_else(|| crate::infer_title_from_uri(&uri));
+        let snippet_bytes = snippet.len();
+        Ok(SearchHit {
+            rank,
+            frame_id: frame.id,
+            uri,
+            title,
+            range: (0, snippet_bytes),
+            text: snippet.clone(),
+            matches: 0,
+            chunk_range: Some((0, snippet_bytes)),
+            chunk_text: Some(snippet),
+            score: Some(score),
+            metadata: Some(SearchHitMetadata {
+                matches: 0,
+                tags: frame.tags.clone(),
+                labels: frame.labels.clone(),
+                track: frame.track.clone(),
+                created_at: timestamp_to_rfc3339(frame.timestamp),
+                content_dates: frame.content_dates.clone(),
+                entities: Vec::new(),
+                related: Vec::new(),
+                extra_metadata: frame.extra_metadata.clone(),
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+            }),
+            highlights: Vec::new(),
+        })
+    }
+}
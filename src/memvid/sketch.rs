@@ -176,17 +176,56 @@ impl Memvid {
         // Build query sketch using same variant as track
         let query_sketch = QuerySketch::from_query(query, self.sketch_track.variant);
 
-        // Find candidates
         let raw_candidates = self.sketch_track.find_candidates(
             &query_sketch,
             opts.hamming_threshold,
             opts.max_candidates,
         );
 
-        // Convert to SketchCandidate with additional details
+        self.sketch_candidates_from(&query_sketch, raw_candidates, opts.min_score)
+    }
+
+    /// Find candidates for several queries in one scan of the sketch track.
+    ///
+    /// Each query comes with its own `max_candidates`; returns one list per query, in query
+    /// order, equal to what `find_sketch_candidates` returns for it alone.
+    #[cfg(feature = "lex")]
+    pub(crate) fn find_sketch_candidates_many(
+        &self,
+        queries: &[(&str, usize)],
+        hamming_threshold: u32,
+    ) -> Vec<Vec<SketchCandidate>> {
+        let sketches: Vec<(QuerySketch, usize)> = queries
+            .iter()
+            .map(|&(query, max_candidates)| {
+                (
+                    QuerySketch::from_query(query, self.sketch_track.variant),
+                    max_candidates,
+                )
+            })
+            .collect();
+        let raw = self
+            .sketch_track
+            .find_candidates_many(&sketches, hamming_threshold);
+        sketches
+            .iter()
+            .zip(raw)
+            .map(|((query_sketch, _), raw_candidates)| {
+                self.sketch_candidates_from(query_sketch, raw_candidates, 0.0)
+            })
+            .collect()
+    }
+
+    /// Convert raw track matches to `SketchCandidate`s with additional details.
+    fn sketch_candidates_from(
+        &self,
+        query_sketch: &QuerySketch,
+        raw_candidates: Vec<(FrameId, f32)>,
+        min_score: f32,
+    ) -> Vec<SketchCandidate> {
         raw_candidates
             .into_iter()
-            .filter(|(_, score)| *score >= opts.min_score)
+            .filter(|(_, score)| *score >= min_score)
             .map(|(frame_id, score)| {
                 let entry = self.sketch_track.get(frame_id);
                 let hamming_distance =
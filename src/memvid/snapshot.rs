@@ -0,0 +1,343 @@
+//! Named point-in-time snapshots and read-only views over them.
+//!
+//! `snapshot` commits pending changes, then archives the committed TOC and every index and
+//! track blob it references (see [`crate::types::snapshot`]). `open_at_snapshot` opens a
+//! read-only handle that sees frames, indexes, and memory cards exactly as they were at that
+//! commit. Doctor rebuilds and vacuum rewrite the payload region and invalidate snapshots;
+//! opening one afterwards fails its checksum instead of returning mixed state.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::error::{MemvidError, Result};
+use crate::io::header::HeaderCodec;
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::{
+    Memvid, TailSnapshot, detect_generation, ensure_single_file, load_tail_snapshot,
+    prepare_toc_bytes, read_toc,
+};
+use crate::types::sparse::{SPARSE_TRACK_EXTENSION, sparse_track_manifest};
+use crate::types::token_track::{TOKEN_TRACK_EXTENSION, token_track_manifest};
+use crate::types::{SNAPSHOT_EXTENSION, Snapshot, SnapshotTable, Toc};
+
+fn blob_len(len: u64) -> Result<usize> {
+    usize::try_from(len).map_err(|_| MemvidError::InvalidToc {
+        reason: "snapshot blob exceeds addressable memory".into(),
+    })
+}
+
+/// Move every payload and blob offset in an archived TOC by `shift` bytes.
+fn shift_offsets(toc: &mut Toc, shift: u64) -> Result<()> {
+    for frame in &mut toc.frames {
+        if frame.payload_offset != 0 {
+            frame.payload_offset += shift;
+        }
+    }
+    for (offset, _) in toc.blob_ranges_mut() {
+        *offset += shift;
+    }
+    if let Some(mut manifest) = sparse_track_manifest(toc) {
+        manifest.bytes_offset += shift;
+        toc.set_extension(SPARSE_TRACK_EXTENSION, &manifest)?;
+    }
+    if let Some(mut manifest) = token_track_manifest(toc) {
+        manifest.bytes_offset += shift;
+        toc.set_extension(TOKEN_TRACK_EXTENSION, &manifest)?;
+    }
+    Ok(())
+}
+
+/// Copy `len` bytes at `offset` to `*cursor`, advancing it, and return the copy's offset.
+fn copy_blob(
+    file: &mut std::fs::File,
+    buffer: &mut Vec<u8>,
+    offset: u64,
+    len: u64,
+    cursor: &mut u64,
+) -> Result<u64> {
+    buffer.resize(blob_len(len)?, 0);
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buffer)?;
+    file.seek(SeekFrom::Start(*cursor))?;
+    file.write_all(buffer)?;
+    let copied = *cursor;
+    *cursor += len;
+    Ok(copied)
+}
+
+impl Memvid {
+    /// Commit pending changes and record the resulting state under `label`.
+    ///
+    /// The snapshot stays readable through [`Memvid::open_at_snapshot`] while later commits
+    /// keep changing the memory. Fails with [`MemvidError::SnapshotExists`] if the label is
+    /// taken.
+    pub fn snapshot(&mut self, label: &str) -> Result<Snapshot> {
+        self.ensure_writable()?;
+        let mut table = self.snapshot_table()?;
+        if table.get(label).is_some() {
+            return Err(MemvidError::SnapshotExists {
+                label: label.to_string(),
+            });
+        }
+        self.commit()?;
+
+        let mut file = self.file.try_clone()?;
+        let mut captured = read_toc(&mut file, &self.header)?;
+        let generation = detect_generation(&file)?.unwrap_or(0);
+        captured.extensions.remove(SNAPSHOT_EXTENSION);
+
+        // Append after the committed footer so a crash mid-archive leaves the file unchanged.
+        let archive_offset = file.metadata()?.len();
+        let mut cursor = archive_offset;
+        let mut buffer = Vec::new();
+        for (offset, len) in captured.blob_ranges_mut() {
+            *offset = copy_blob(&mut file, &mut buffer, *offset, len, &mut cursor)?;
+        }
+        // The sparse and token tracks are located through TOC extensions rather than manifest
+        // fields.
+        if let Some(mut manifest) = sparse_track_manifest(&captured) {
+            manifest.bytes_offset = copy_blob(
+                &mut file,
+                &mut buffer,
+                manifest.bytes_offset,
+                manifest.bytes_length,
+                &mut cursor,
+            )?;
+            captured.set_extension(SPARSE_TRACK_EXTENSION, &manifest)?;
+        }
+        if let Some(mut manifest) = token_track_manifest(&captured) {
+            manifest.bytes_offset = copy_blob(
+                &mut file,
+                &mut buffer,
+                manifest.bytes_offset,
+                manifest.bytes_length,
+                &mut cursor,
+            )?;
+            captured.set_extension(TOKEN_TRACK_EXTENSION, &manifest)?;
+        }
+        let toc_bytes = prepare_toc_bytes(&mut captured)?;
+        file.seek(SeekFrom::Start(cursor))?;
+        file.write_all(&toc_bytes)?;
+        let toc_offset = cursor;
+        cursor += toc_bytes.len() as u64;
+
+        let snapshot = Snapshot {
+            label: label.to_string(),
+            generation,
+            created_at: unix_now(),
+            frame_count: captured.frames.len() as u64,
+            data_start: self.header.data_start,
+            archive_offset,
+            archive_length: cursor - archive_offset,
+            toc_offset,
+            toc_length: toc_bytes.len() as u64,
+            toc_hash: *blake3::hash(&toc_bytes).as_bytes(),
+        };
+        table.insert(snapshot.clone());
+        self.toc.set_extension(SNAPSHOT_EXTENSION, &table)?;
+
+        // Treat the archive as part of the payload region from now on.
+        self.cached_payload_end = self.cached_payload_end.max(cursor);
+        self.data_end = self.data_end.max(cursor);
+        self.header.footer_offset = self.header.footer_offset.max(cursor);
+        self.dirty = true;
+        self.commit()?;
+        Ok(snapshot)
+    }
+
+    /// Snapshots recorded in this memory, oldest first.
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        Ok(self.snapshot_table()?.snapshots().to_vec())
+    }
+
+    /// Forget the snapshot named `label` and commit. Its archive space is not reclaimed until
+    /// the next vacuum.
+    pub fn delete_snapshot(&mut self, label: &str) -> Result<()> {
+        self.ensure_writable()?;
+        self.ensure_not_held("delete snapshots")?;
+        let mut table = self.snapshot_table()?;
+        if table.remove(label).is_none() {
+            return Err(MemvidError::SnapshotNotFound {
+                label: label.to_string(),
+            });
+        }
+        if table.is_empty() {
+            self.toc.extensions.remove(SNAPSHOT_EXTENSION);
+        } else {
+            self.toc.set_extension(SNAPSHOT_EXTENSION, &table)?;
+        }
+        self.dirty = true;
+        self.commit()
+    }
+
+    /// Label of the snapshot this handle is pinned to, if it was opened with
+    /// [`Memvid::open_at_snapshot`].
+    #[must_use]
+    pub fn snapshot_label(&self) -> Option<&str> {
+        self.snapshot_view.as_deref()
+    }
+
+    /// Open a read-only view of the memory exactly as it was when `label` was recorded.
+    ///
+    /// Every mutation on the returned handle fails with [`MemvidError::SnapshotReadOnly`].
+    pub fn open_at_snapshot<P: AsRef<Path>>(path: P, label: &str) -> Result<Self> {
+        let path_ref = path.as_ref();
+        ensure_single_file(path_ref)?;
+        let mut file = OpenOptions::new().read(true).write(true).open(path_ref)?;
+        let TailSnapshot {
+            toc: current,
+            footer_offset,
+            data_end,
+            ..
+        } = load_tail_snapshot(&file)?;
+        let mut header = HeaderCodec::read(&mut file)?;
+        header.footer_offset = footer_offset;
+
+        let table = current
+            .extension::<SnapshotTable>(SNAPSHOT_EXTENSION)?
+            .unwrap_or_default();
+        let snapshot = table
+            .get(label)
+            .cloned()
+            .ok_or_else(|| MemvidError::SnapshotNotFound {
+                label: label.to_string(),
+            })?;
+        let shift = snapshot.shift(&header);
+
+        let mut toc_bytes = vec![0u8; blob_len(snapshot.toc_length)?];
+        file.seek(SeekFrom::Start(snapshot.toc_offset + shift))?;
+        file.read_exact(&mut toc_bytes)?;
+        if blake3::hash(&toc_bytes).as_bytes() != &snapshot.toc_hash {
+            return Err(MemvidError::InvalidToc {
+                reason: format!("snapshot '{label}' archive is damaged or was compacted away")
+                    .into(),
+            });
+        }
+        let mut toc = Toc::decode(&toc_bytes)?;
+        if shift > 0 {
+            shift_offsets(&mut toc, shift)?;
+        }
+        header.toc_checksum = toc.toc_checksum;
+
+        let mut view =
+            Self::open_committed_view(file, path_ref, header, toc, data_end, snapshot.generation)?;
+        view.snapshot_view = Some(label.to_string());
+        Ok(view)
+    }
+
+    pub(crate) fn snapshot_table(&self) -> Result<SnapshotTable> {
+        Ok(self
+            .toc
+            .extension::<SnapshotTable>(SNAPSHOT_EXTENSION)?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AclEnforcementMode, MemoryCardBuilder, SearchMode, SearchRequest};
+
+    fn hits(mem: &mut Memvid, query: &str) -> usize {
+        mem.search(SearchRequest {
+            query: query.into(),
+            top_k: 10,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .expect("search")
+        .hits
+        .len()
+    }
+
+    #[test]
+    #[cfg(feature = "lex")]
+    fn snapshot_view_is_frozen_while_memory_moves_on() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("snap.mv2");
+
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        mem.put_bytes(b"original harbor note").expect("put");
+        let card = MemoryCardBuilder::new()
+            .fact()
+            .entity("user")
+            .slot("city")
+            .value("Lisbon")
+            .source(0, None)
+            .engine("test", "1.0.0")
+            .build(0)
+            .expect("card");
+        mem.put_memory_card(card).expect("card");
+        let snapshot = mem.snapshot("v1").expect("snapshot");
+        assert_eq!(snapshot.frame_count, 1);
+        assert!(matches!(
+            mem.snapshot("v1"),
+            Err(MemvidError::SnapshotExists { .. })
+        ));
+
+        // Larger than the embedded WAL: forces WAL growth, which relocates it past the data.
+        let wal_size = mem.header.wal_size;
+        let mut state = 0x9E37_79B9_u32;
+        let noise: Vec<u8> = (0..96 * 1024)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                state.to_le_bytes()[3]
+            })
+            .collect();
+        mem.put_bytes(&noise).expect("large put");
+        assert!(mem.header.wal_size > wal_size);
+        mem.put_bytes(b"later harbor note").expect("put");
+        mem.put_bytes(b"another harbor note").expect("put");
+        mem.commit().expect("commit");
+        mem.clear_memories();
+        mem.commit().expect("commit");
+        assert_eq!(hits(&mut mem, "harbor"), 3);
+        assert!(mem.frame_count() > 3);
+        drop(mem);
+
+        let mut view = Memvid::open_at_snapshot(&path, "v1").expect("open snapshot");
+        assert_eq!(view.snapshot_label(), Some("v1"));
+        assert_eq!(view.generation(), snapshot.generation);
+        assert_eq!(view.frame_count(), 1);
+        assert_eq!(hits(&mut view, "harbor"), 1);
+        assert_eq!(view.memory_card_count(), 1);
+        assert!(matches!(
+            view.put_bytes(b"nope"),
+            Err(MemvidError::SnapshotReadOnly { .. })
+        ));
+        drop(view);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        assert!(mem.frame_count() > 3);
+        assert_eq!(mem.snapshots().expect("list").len(), 1);
+        mem.delete_snapshot("v1").expect("delete");
+        drop(mem);
+        assert!(matches!(
+            Memvid::open_at_snapshot(&path, "v1"),
+            Err(MemvidError::SnapshotNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,489 @@
+//! Learned sparse retrieval over the sparse track.
+//!
+//! Install a [`SparseEncoder`] with [`Memvid::set_sparse_encoder`], encode the memory's frames
+//! with [`Memvid::index_sparse`], then search with `SearchRequest::mode` set to
+//! [`SearchMode::Sparse`]. Frames ingested later are picked up by the next `index_sparse`;
+//! deleted frames drop out of the track as they are tombstoned.
+
+use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::search::helpers::{build_context, timestamp_to_rfc3339};
+use crate::types::sparse::{SPARSE_TRACK_EXTENSION, SparseTrackManifest};
+use crate::types::{
+    FrameId, FrameRole, FrameStatus, QueryExplain, SearchEngineKind, SearchHit, SearchHitMetadata,
+    SearchParams, SearchRequest, SearchResponse, SparseEncoder, SparseIndexReport,
+    SparseTrackStats, SparseVector, VecRescore,
+};
+
+impl Memvid {
+    /// Install the encoder used by [`Memvid::index_sparse`] and sparse-mode searches.
+    pub fn set_sparse_encoder(&mut self, encoder: Arc<dyn SparseEncoder>) {
+        self.sparse_encoder = Some(encoder);
+    }
+
+    /// Remove the installed sparse encoder, returning it.
+    pub fn clear_sparse_encoder(&mut self) -> Option<Arc<dyn SparseEncoder>> {
+        self.sparse_encoder.take()
+    }
+
+    /// The installed sparse encoder, if any.
+    #[must_use]
+    pub fn sparse_encoder(&self) -> Option<&Arc<dyn SparseEncoder>> {
+        self.sparse_encoder.as_ref()
+    }
+
+    /// Size of the sparse track and the encoder that built it.
+    #[must_use]
+    pub fn sparse_stats(&self) -> SparseTrackStats {
+        self.sparse_track.stats()
+    }
+
+    /// Stored term weights of `frame_id`, quantized once the track has been persisted.
+    #[must_use]
+    pub fn frame_sparse_vector(&self, frame_id: FrameId) -> Option<SparseVector> {
+        self.sparse_track.vector(frame_id)
+    }
+
+    /// Encode every active frame missing from the sparse track.
+    ///
+    /// See [`Memvid::index_sparse_with_progress`].
+    pub fn index_sparse(&mut self, batch: usize) -> Result<SparseIndexReport> {
+        self.index_sparse_with_progress(batch, |_| {})
+    }
+
+    /// Encode every active frame missing from the sparse track with the installed encoder,
+    /// committing `batch` frames at a time and calling `on_progress` after each commit.
+    ///
+    /// Pending changes are committed first and frames that are no longer active are dropped
+    /// from the track. Fails with [`MemvidError::SparseEncoderMissing`] without an encoder,
+    /// and with [`MemvidError::ModelMismatch`] if the track was built by another encoder.
+    pub fn index_sparse_with_progress<F>(
+        &mut self,
+        batch: usize,
+        mut on_progress: F,
+    ) -> Result<SparseIndexReport>
+    where
+        F: FnMut(&SparseIndexReport),
+    {
+        self.ensure_writable()?;
+        let encoder = self
+            .sparse_encoder
+            .clone()
+            .ok_or(MemvidError::SparseEncoderMissing)?;
+        self.ensure_sparse_encoder_matches(encoder.as_ref())?;
+        self.commit()?;
+
+        let mut report = SparseIndexReport::default();
+        let stale: Vec<FrameId> = self
+            .sparse_track
+            .frame_ids()
+            .filter(|&frame_id| !self.frame_is_active(frame_id))
+            .collect();
+        for frame_id in stale {
+            self.sparse_track.remove(frame_id);
+            report.removed += 1;
+        }
+        let candidates: Vec<FrameId> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| {
+                frame.status == FrameStatus::Active
+                    && frame.role != FrameRole::ExtractedImage
+                    && !self.sparse_track.contains(frame.id)
+            })
+            .map(|frame| frame.id)
+            .collect();
+        report.candidates = candidates.len();
+        if report.removed > 0 {
+            self.dirty = true;
+        }
+        self.sparse_track.set_encoder(encoder.kind());
+
+        for ids in candidates.chunks(batch.max(1)) {
+            let mut frame_ids = Vec::with_capacity(ids.len());
+            let mut texts = Vec::with_capacity(ids.len());
+            for &frame_id in ids {
+                let frame = self.frame_by_id(frame_id)?;
+                let text = self.frame_search_text(&frame)?;
+                if text.trim().is_empty() {
+                    report.skipped_empty += 1;
+                } else {
+                    frame_ids.push(frame_id);
+                    texts.push(text);
+                }
+            }
+            if !frame_ids.is_empty() {
+                let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+                let vectors = encoder.encode_batch(&refs)?;
+                if vectors.len() != frame_ids.len() {
+                    return Err(MemvidError::EmbeddingFailed {
+                        reason: format!(
+                            "sparse encoder returned {} vectors for {} texts",
+                            vectors.len(),
+                            frame_ids.len()
+                        )
+                        .into(),
+                    });
+                }
+                for (&frame_id, vector) in frame_ids.iter().zip(&vectors) {
+                    self.sparse_track.insert(frame_id, vector);
+                }
+                self.dirty = true;
+                self.commit()?;
+                report.encoded += frame_ids.len();
+            }
+            report.batches += 1;
+            tracing::debug!(
+                encoded = report.encoded,
+                remaining = report.remaining(),
+                "sparse index batch committed"
+            );
+            on_progress(&report);
+        }
+        if report.batches == 0 && self.dirty {
+            self.commit()?;
+        }
+        Ok(report)
+    }
+
+    /// Fail with [`MemvidError::ModelMismatch`] if the sparse track holds weights from an
+    /// encoder other than `encoder`; term ids of two vocabularies are unrelated.
+    fn ensure_sparse_encoder_matches(&self, encoder: &dyn SparseEncoder) -> Result<()> {
+        match self.sparse_track.encoder() {
+            Some(existing) if existing != encoder.kind() && !self.sparse_track.is_empty() => {
+                Err(MemvidError::ModelMismatch {
+                    expected: existing.to_string(),
+                    actual: encoder.kind().to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Answer `request` from the sparse track.
+    pub(crate) fn search_sparse(&mut self, request: &SearchRequest) -> Result<SearchResponse> {
+        let start_time = Instant::now();
+        let encoder = self
+            .sparse_encoder
+            .clone()
+            .ok_or(MemvidError::SparseEncoderMissing)?;
+        self.ensure_sparse_encoder_matches(encoder.as_ref())?;
+        if request.query.trim().is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "query must include at least one search term or field filter".into(),
+            });
+        }
+        let mut explain = request.explain.then(QueryExplain::default);
+        let stage = Instant::now();
+        let query = encoder.encode_query(&request.query)?;
+        if let Some(explain) = explain.as_mut() {
+            explain.record_stage("encode", stage.elapsed());
+        }
+
+        let stage = Instant::now();
+        let schema = self.meta_schema()?;
+        let quarantined = self.quarantine_set()?;
+        let snippet_limit = request.snippet_chars.max(80);
+        let mut hits: Vec<SearchHit> = Vec::new();
+        let mut total_hits = 0;
+        for (frame_id, score) in self.sparse_track.search(&query) {
+            let Some(frame) = usize::try_from(frame_id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+                .cloned()
+            else {
+                continue;
+            };
+            let uri = frame
+                .uri
+                .clone()
+                .unwrap_or_else(|| crate::default_uri(frame.id));
+            if frame.status != FrameStatus::Active
+                || quarantined.contains(&frame.id)
+                || request.uri.as_ref().is_some_and(|wanted| &uri != wanted)
+                || request
+                    .scope
+                    .as_ref()
+                    .is_some_and(|scope| !uri.starts_with(scope.as_str()))
+                || request.as_of_frame.is_some_and(|as_of| frame.id > as_of)
+                || request
+                    .as_of_ts
+                    .is_some_and(|as_of| frame.timestamp > as_of)
+                || !request
+                    .filters
+                    .iter()
+                    .all(|filter| filter.matches_frame(&frame, &schema))
+            {
+                continue;
+            }
+            total_hits += 1;
+            if hits.len() >= request.top_k {
+                continue;
+            }
+
+            let snippet: String = match self.frame_snippet_text(&frame) {
+                Some(Ok(text)) => text.chars().take(snippet_limit).collect(),
+                Some(Err(_)) => continue,
+                None => match self.frame_content(&frame) {
+                    Ok(content) => content.chars().take(snippet_limit).collect(),
+                    Err(_) => continue,
+                },
+            };
+            let snippet_bytes = snippet.len();
+            let title = frame
+                .title
+                .clone()
+                .or_else(|| crate::infer_title_from_uri(&uri));
+            let stored = self.sparse_track.vector(frame.id).unwrap_or_default();
+            let metadata = SearchHitMetadata {
+                matches: query
+                    .terms()
+                    .iter()
+                    .filter(|(term, _)| stored.weight(*term).is_some())
+                    .count(),
+                tags: frame.tags.clone(),
+                labels: frame.labels.clone(),
+                track: frame.track.clone(),
+                created_at: timestamp_to_rfc3339(frame.timestamp),
+                content_dates: frame.content_dates.clone(),
+                entities: Vec::new(),
+                related: Vec::new(),
+                extra_metadata: frame.extra_metadata.clone(),
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+            };
+            hits.push(SearchHit {
+                rank: hits.len() + 1,
+                frame_id: frame.id,
+                uri,
+                title,
+                range: (0, snippet_bytes),
+                text: snippet.clone(),
+                matches: metadata.matches,
+                chunk_range: Some((0, snippet_bytes)),
+                chunk_text: Some(snippet),
+                score: Some(score),
+                metadata: Some(metadata),
+                highlights: Vec::new(),
+            });
+        }
+        if let Some(explain) = explain.as_mut() {
+            explain.record_stage("retrieve", stage.elapsed());
+        }
+
+        #[cfg(feature = "temporal_track")]
+        crate::memvid::search::helpers::attach_temporal_metadata(self, &mut hits)?;
+        let acl = self.apply_acl_to_search_hits(
+            &mut hits,
+            request.acl_context.as_ref(),
+            request.acl_enforcement_mode,
+        )?;
+        if acl.rewritten {
+            total_hits = hits.len();
+        }
+        if let Some(explain) = explain.as_mut() {
+            explain.acl_denied = request.acl_context.is_some().then_some(acl.denied);
+            explain.engine = SearchEngineKind::Sparse;
+        }
+        let context = build_context(&hits);
+
+        Ok(SearchResponse {
+            query: request.query.clone(),
+            elapsed_ms: start_time.elapsed().as_millis(),
+            total_hits,
+            params: SearchParams {
+                top_k: request.top_k,
+                snippet_chars: request.snippet_chars,
+                cursor: None,
+                vec_rescore: VecRescore::default(),
+            },
+            hits,
+            context,
+            next_cursor: None,
+            engine: SearchEngineKind::Sparse,
+            stale_index_skips: 0,
+            suggestions: Vec::new(),
+            explain,
+            partial: false,
+            skipped_stages: Vec::new(),
+        })
+    }
+
+    /// Write the sparse track after the footer, or drop its manifest once the track is empty.
+    pub(crate) fn persist_sparse_track(&mut self) -> Result<()> {
+        if self.sparse_track.is_empty() {
+            self.toc.extensions.remove(SPARSE_TRACK_EXTENSION);
+            return Ok(());
+        }
+
+        self.file.seek(SeekFrom::Start(self.header.footer_offset))?;
+        let (offset, length, checksum) =
+            crate::types::write_sparse_track(&mut self.file, &self.sparse_track)?;
+        let stats = self.sparse_track.stats();
+        let manifest = SparseTrackManifest {
+            bytes_offset: offset,
+            bytes_length: length,
+            frame_count: stats.frames,
+            term_count: stats.terms,
+            posting_count: stats.postings,
+            encoder: stats.encoder.unwrap_or_default(),
+            checksum,
+        };
+        self.toc.set_extension(SPARSE_TRACK_EXTENSION, &manifest)?;
+        self.header.footer_offset = offset + length;
+        if self.file.metadata()?.len() < self.header.footer_offset {
+            self.file.set_len(self.header.footer_offset)?;
+        }
+        tracing::debug!(
+            frames = stats.frames,
+            offset,
+            "persist_sparse_track: persisted sparse track"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AclEnforcementMode, SearchMode};
+
+    /// Weights each word by its length, expanding "car" to "automobile".
+    struct WordEncoder;
+
+    fn term_id(word: &str) -> u32 {
+        let hash = blake3::hash(word.as_bytes());
+        u32::from_le_bytes([
+            hash.as_bytes()[0],
+            hash.as_bytes()[1],
+            hash.as_bytes()[2],
+            hash.as_bytes()[3],
+        ])
+    }
+
+    impl SparseEncoder for WordEncoder {
+        #[allow(clippy::unnecessary_literal_bound)]
+        fn kind(&self) -> &str {
+            "word-v1"
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        fn encode(&self, text: &str) -> Result<SparseVector> {
+            let mut terms = Vec::new();
+            for word in text.split_whitespace().map(str::to_lowercase) {
+                if word == "car" {
+                    terms.push((term_id("automobile"), 1.0));
+                }
+                terms.push((term_id(&word), word.len() as f32 / 10.0));
+            }
+            Ok(SparseVector::new(terms))
+        }
+    }
+
+    fn request(query: &str) -> SearchRequest {
+        SearchRequest {
+            query: query.to_string(),
+            top_k: 10,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Sparse,
+            time_budget_ms: None,
+        }
+    }
+
+    #[test]
+    fn sparse_search_finds_expanded_terms_and_survives_reopen() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("sparse.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for text in [
+            "a red car parked outside",
+            "rail timetable",
+            "bicycle repairs",
+        ] {
+            mem.put_bytes(text.as_bytes()).expect("put");
+        }
+        mem.commit().expect("commit");
+
+        assert!(matches!(
+            mem.index_sparse(8),
+            Err(MemvidError::SparseEncoderMissing)
+        ));
+        mem.set_sparse_encoder(Arc::new(WordEncoder));
+        let report = mem.index_sparse(2).expect("index");
+        assert_eq!(report.candidates, 3);
+        assert_eq!(report.encoded, 3);
+        assert_eq!(report.batches, 2);
+
+        let response = mem.search(request("automobile")).expect("search");
+        assert_eq!(response.engine, SearchEngineKind::Sparse);
+        assert_eq!(response.hits.len(), 1);
+        assert!(response.hits[0].text.contains("red car"));
+
+        mem.delete_frame(response.hits[0].frame_id).expect("delete");
+        mem.put_bytes(b"another car for sale").expect("put");
+        mem.commit().expect("commit");
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        assert_eq!(mem.sparse_stats().frames, 2);
+        assert_eq!(mem.sparse_stats().encoder.as_deref(), Some("word-v1"));
+        mem.set_sparse_encoder(Arc::new(WordEncoder));
+        let report = mem.index_sparse(8).expect("index");
+        assert_eq!(report.encoded, 1);
+        assert_eq!(mem.sparse_stats().frames, 3);
+        let response = mem.search(request("automobile")).expect("search");
+        assert_eq!(response.hits.len(), 1);
+        assert!(response.hits[0].text.contains("for sale"));
+    }
+    #[test]
+    fn search_many_serves_sparse_requests_without_lex() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let mut mem = Memvid::create(dir.path().join("sparse.mv2")).expect("create");
+        for text in ["a red car parked outside", "rail timetable"] {
+            mem.put_bytes(text.as_bytes()).expect("put");
+        }
+        mem.commit().expect("commit");
+        mem.set_sparse_encoder(Arc::new(WordEncoder));
+        mem.index_sparse(8).expect("index");
+        mem.lex_enabled = false;
+
+        let responses = mem
+            .search_many(vec![request("automobile"), request("timetable")])
+            .expect("search many");
+        assert_eq!(responses.len(), 2);
+        assert!(responses[0].hits[0].text.contains("red car"));
+        assert!(responses[1].hits[0].text.contains("rail"));
+
+        let mut lexical = request("automobile");
+        lexical.mode = SearchMode::Lexical;
+        assert!(matches!(
+            mem.search_many(vec![request("automobile"), lexical]),
+            Err(MemvidError::LexNotEnabled)
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+//! "Did you mean" suggestions for searches that find little.
+//!
+//! The dictionary is the word-frequency table of the suggestion index (see
+//! [`crate::types::suggest`]), which every commit keeps current, loaded into a SymSpell
+//! lookup the first time a search on the handle needs it.
+
+use std::ops::Range;
+
+use symspell::{SymSpell, UnicodeStringStrategy, Verbosity};
+
+use crate::analysis::language::is_common_stopword;
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+
+/// Searches with fewer hits than this get spelling suggestions.
+pub(crate) const SPELLING_HIT_THRESHOLD: usize = 3;
+/// Corrected queries returned per search.
+const MAX_SPELLING_SUGGESTIONS: usize = 3;
+/// Query words shorter than this are never corrected.
+const MIN_WORD_CHARS: usize = 3;
+
+pub(crate) type SpellingDictionary = SymSpell<UnicodeStringStrategy>;
+
+impl Memvid {
+    /// Rewrites of `query` with each word the file does not contain replaced by a known word
+    /// at most two edits away (one for words of four characters or fewer), best first. Empty
+    /// when no word needs or has a correction.
+    pub(crate) fn spelling_suggestions(&self, query: &str) -> Result<Vec<String>> {
+        let index = self.cached_suggest_index()?;
+        if index.terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let dictionary = self.spelling_dictionary.get_or_init(|| {
+            let mut dictionary = SpellingDictionary::default();
+            for (word, frames) in &index.terms {
+                dictionary.load_dictionary_line(&format!("{word} {frames}"), 0, 1, " ");
+            }
+            dictionary
+        });
+
+        // (byte range in the query, candidates ordered best first)
+        let mut corrections: Vec<(Range<usize>, Vec<String>)> = Vec::new();
+        for (range, word) in query_words(query) {
+            let word = word.to_lowercase();
+            let chars = word.chars().count();
+            if chars < MIN_WORD_CHARS
+                || index.terms.contains_key(&word)
+                || is_common_stopword(&word)
+            {
+                continue;
+            }
+            let max_distance = if chars <= 4 { 1 } else { 2 };
+            let mut candidates = dictionary.lookup(&word, Verbosity::Closest, max_distance);
+            candidates.retain(|candidate| candidate.term != word);
+            candidates.sort_by(|a, b| {
+                a.distance
+                    .cmp(&b.distance)
+                    .then(b.count.cmp(&a.count))
+                    .then_with(|| a.term.cmp(&b.term))
+            });
+            if !candidates.is_empty() {
+                corrections.push((
+                    range,
+                    candidates
+                        .into_iter()
+                        .map(|candidate| candidate.term)
+                        .collect(),
+                ));
+            }
+        }
+        let Some((_, first)) = corrections.first() else {
+            return Ok(Vec::new());
+        };
+
+        // The best rewrite, then alternatives for the first corrected word
+        let mut suggestions: Vec<String> = Vec::new();
+        for alternative in 0..first.len().min(MAX_SPELLING_SUGGESTIONS) {
+            let mut rewritten = query.to_string();
+            for (position, (range, candidates)) in corrections.iter().enumerate().rev() {
+                let choice = if position == 0 { alternative } else { 0 };
+                rewritten.replace_range(range.clone(), &candidates[choice]);
+            }
+            if !suggestions.contains(&rewritten) {
+                suggestions.push(rewritten);
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+/// Words of a query that are search terms, with their byte ranges: field names (`title:`) and
+/// the boolean operators `AND`, `OR`, and `NOT` are skipped.
+fn query_words(query: &str) -> Vec<(Range<usize>, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (offset, ch) in query.char_indices().chain([(query.len(), ' ')]) {
+        match (start, ch.is_alphanumeric()) {
+            (None, true) => start = Some(offset),
+            (Some(begin), false) => {
+                let word = &query[begin..offset];
+                if ch != ':' && !matches!(word, "AND" | "OR" | "NOT") {
+                    words.push((begin..offset, word));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_words_skip_fields_and_operators() {
+        let words: Vec<&str> = query_words("title:kubernets AND \"helm chrat\" -NOT")
+            .into_iter()
+            .map(|(_, word)| word)
+            .collect();
+        assert_eq!(words, vec!["kubernets", "helm", "chrat"]);
+    }
+}
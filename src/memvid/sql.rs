@@ -0,0 +1,278 @@
+//! `Memvid::query`: read-only SQL over the TOC and the memories track.
+//!
+//! `frames` rows are the active frames. When the `WHERE` clause bounds `ts`, candidates come
+//! from the time index instead of a full TOC scan; frames the index does not cover (such as
+//! extracted images) are still checked.
+
+use std::collections::HashSet;
+
+use crate::error::Result;
+use crate::io::time_index::read_track as time_index_read;
+use crate::memvid::lifecycle::Memvid;
+use crate::sql::{self, Condition, Projection, QueryResult, QueryValue, Select, Table};
+use crate::types::{Frame, FrameId, FrameStatus, MemoryCard};
+
+impl Memvid {
+    /// Run a read-only `SELECT` against `frames` or `cards`; see [`crate::sql`] for the grammar.
+    ///
+    /// ```no_run
+    /// # fn demo(mem: &mut memvid_core::Memvid) -> memvid_core::Result<()> {
+    /// let recent = mem.query(
+    ///     "SELECT uri, ts FROM frames WHERE kind = 'email' AND ts > '2024-06-01' \
+    ///      ORDER BY ts DESC LIMIT 50",
+    /// )?;
+    /// std::fs::write("recent.csv", recent.to_csv())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query(&mut self, statement: &str) -> Result<QueryResult> {
+        let select = sql::parse(statement)?;
+        let rows = match select.table {
+            Table::Frames => {
+                let candidates = self.frame_query_candidates(select.filter.as_ref())?;
+                let frames: Vec<&Frame> = self
+                    .toc
+                    .frames
+                    .iter()
+                    .filter(|frame| {
+                        frame.status == FrameStatus::Active
+                            && candidates
+                                .as_ref()
+                                .is_none_or(|ids| ids.contains(&frame.id))
+                    })
+                    .collect();
+                run_select(&select, &frames, frame_cell)
+            }
+            Table::Cards => {
+                let cards: Vec<&MemoryCard> = self.memories_track.cards().iter().collect();
+                run_select(&select, &cards, card_cell)
+            }
+        };
+        Ok(QueryResult {
+            columns: select.labels(),
+            rows,
+        })
+    }
+
+    /// Frames that can satisfy the `ts` bounds of `filter`, or `None` to scan every frame.
+    fn frame_query_candidates(
+        &mut self,
+        filter: Option<&Condition>,
+    ) -> Result<Option<HashSet<FrameId>>> {
+        let Some((since, until)) = filter
+            .map(|filter| filter.int_bounds("ts"))
+            .filter(|bounds| *bounds != (None, None))
+        else {
+            return Ok(None);
+        };
+        let Some(manifest) = self.toc.time_index.clone() else {
+            return Ok(None);
+        };
+        let entries =
+            time_index_read(&mut self.file, manifest.bytes_offset, manifest.bytes_length)?;
+        let indexed: HashSet<FrameId> = entries.iter().map(|entry| entry.frame_id).collect();
+        let mut candidates: HashSet<FrameId> = entries
+            .iter()
+            .filter(|entry| {
+                since.is_none_or(|since| entry.timestamp >= since)
+                    && until.is_none_or(|until| entry.timestamp <= until)
+            })
+            .map(|entry| entry.frame_id)
+            .collect();
+        candidates.extend(
+            self.toc
+                .frames
+                .iter()
+                .map(|frame| frame.id)
+                .filter(|id| !indexed.contains(id)),
+        );
+        Ok(Some(candidates))
+    }
+}
+
+/// Filter, sort, page and project `rows`.
+fn run_select<T>(
+    select: &Select,
+    rows: &[&T],
+    cell: impl Fn(&T, &str) -> QueryValue,
+) -> Vec<Vec<QueryValue>> {
+    let matching = rows.iter().copied().filter(|row| {
+        select
+            .filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(&|column| cell(row, column)))
+    });
+    if select.projection == Projection::Count {
+        let count = i64::try_from(matching.count()).unwrap_or(i64::MAX);
+        return vec![vec![QueryValue::Int(count)]];
+    }
+    let mut keyed: Vec<(Vec<QueryValue>, &T)> = matching
+        .map(|row| {
+            let keys = select
+                .order_by
+                .iter()
+                .map(|(column, _)| cell(row, column))
+                .collect();
+            (keys, row)
+        })
+        .collect();
+    sql::sort_rows(&mut keyed, &select.order_by);
+    let columns = select.projected_columns();
+    keyed
+        .into_iter()
+        .skip(select.offset)
+        .take(select.limit.unwrap_or(usize::MAX))
+        .map(|(_, row)| columns.iter().map(|column| cell(row, column)).collect())
+        .collect()
+}
+
+fn frame_cell(frame: &Frame, column: &str) -> QueryValue {
+    let id = |id: Option<FrameId>| QueryValue::from(id.and_then(|id| i64::try_from(id).ok()));
+    let list = |values: &[String]| {
+        if values.is_empty() {
+            QueryValue::Null
+        } else {
+            QueryValue::Text(values.join(","))
+        }
+    };
+    if let Some(key) = column.strip_prefix("meta.") {
+        return frame.extra_metadata.get(key).cloned().into();
+    }
+    match column {
+        "id" => id(Some(frame.id)),
+        "uri" => frame.uri.clone().into(),
+        "title" => frame.title.clone().into(),
+        "kind" => frame.kind.clone().into(),
+        "track" => frame.track.clone().into(),
+        "ts" => QueryValue::Int(frame.timestamp),
+        "anchor_ts" => frame.anchor_ts.into(),
+        "role" => QueryValue::Text(
+            match frame.role {
+                crate::types::FrameRole::Document => "document",
+                crate::types::FrameRole::DocumentChunk => "document_chunk",
+                crate::types::FrameRole::ExtractedImage => "extracted_image",
+            }
+            .to_string(),
+        ),
+        "mime" => frame
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.mime.clone())
+            .into(),
+        "tags" => list(&frame.tags),
+        "labels" => list(&frame.labels),
+        "parent_id" => id(frame.parent_id),
+        "chunk_index" => frame.chunk_index.map(i64::from).into(),
+        "chunk_count" => frame.chunk_count.map(i64::from).into(),
+        "supersedes" => id(frame.supersedes),
+        "size" => QueryValue::Int(i64::try_from(frame.payload_length).unwrap_or(i64::MAX)),
+        "search_text" => frame.search_text.clone().into(),
+        _ => QueryValue::Null,
+    }
+}
+
+fn card_cell(card: &MemoryCard, column: &str) -> QueryValue {
+    match column {
+        "id" => QueryValue::Int(i64::try_from(card.id).unwrap_or(i64::MAX)),
+        "kind" => QueryValue::Text(card.kind.as_str().to_string()),
+        "entity" => QueryValue::Text(card.entity.clone()),
+        "slot" => QueryValue::Text(card.slot.clone()),
+        "value" => QueryValue::Text(card.value.clone()),
+        "polarity" => card
+            .polarity
+            .map(|polarity| polarity.as_str().to_string())
+            .into(),
+        "event_date" => card.event_date.into(),
+        "document_date" => card.document_date.into(),
+        "version_key" => card.version_key.clone().into(),
+        "version_relation" => QueryValue::Text(card.version_relation.as_str().to_string()),
+        "frame_id" => QueryValue::Int(i64::try_from(card.source_frame_id).unwrap_or(i64::MAX)),
+        "source_uri" => card.source_uri.clone().into(),
+        "engine" => QueryValue::Text(card.engine.clone()),
+        "confidence" => card.confidence.map_or(QueryValue::Null, |confidence| {
+            QueryValue::Float(f64::from(confidence))
+        }),
+        "created_at" => QueryValue::Int(card.created_at),
+        "superseded_by" => card
+            .superseded_by
+            .and_then(|id| i64::try_from(id).ok())
+            .into(),
+        _ => QueryValue::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MemoryCardBuilder, PutOptions};
+
+    #[test]
+    fn query_selects_frames_and_cards() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("sql.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        for (uri, kind, ts) in [
+            ("mv2://mail/1", "email", 1_717_000_000),
+            ("mv2://mail/2", "email", 1_717_300_000),
+            ("mv2://notes/1", "note", 1_717_400_000),
+            ("mv2://mail/3", "email", 1_717_500_000),
+        ] {
+            let options = PutOptions::builder()
+                .uri(uri)
+                .kind(kind)
+                .timestamp(ts)
+                .meta("priority", 2)
+                .build();
+            mem.put_bytes_with_options(b"quarterly numbers", options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+
+        let result = mem
+            .query(
+                "SELECT uri, ts FROM frames WHERE kind = 'email' AND ts > '2024-06-01' \
+                 ORDER BY ts DESC LIMIT 50",
+            )
+            .expect("query");
+        assert_eq!(result.columns, ["uri", "ts"]);
+        assert_eq!(
+            result.rows,
+            [
+                vec![
+                    QueryValue::Text("mv2://mail/3".into()),
+                    QueryValue::Int(1_717_500_000)
+                ],
+                vec![
+                    QueryValue::Text("mv2://mail/2".into()),
+                    QueryValue::Int(1_717_300_000)
+                ],
+            ]
+        );
+
+        let count = mem
+            .query("SELECT COUNT(*) FROM frames WHERE meta.priority >= 2 AND uri LIKE '%mail%'")
+            .expect("count");
+        assert_eq!(count.rows, [vec![QueryValue::Int(3)]]);
+
+        let card = MemoryCardBuilder::new()
+            .preference()
+            .entity("user")
+            .slot("theme")
+            .value("dark")
+            .source(0, Some("mv2://mail/1".into()))
+            .engine("test", "1")
+            .build(0)
+            .expect("card");
+        mem.put_memory_card(card).expect("card");
+        let cards = mem
+            .query("SELECT entity, value FROM cards WHERE kind = 'preference'")
+            .expect("cards");
+        assert_eq!(
+            cards.rows,
+            [vec![
+                QueryValue::Text("user".into()),
+                QueryValue::Text("dark".into())
+            ]]
+        );
+    }
+}
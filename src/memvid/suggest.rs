@@ -0,0 +1,124 @@
+//! Prefix suggestions (see [`crate::types::suggest`]).
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::analysis::language::is_common_stopword;
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::search::{is_text_indexable_mime, max_index_payload};
+use crate::types::suggest::suggestion_terms;
+use crate::types::{
+    Frame, FrameId, FrameStatus, SUGGEST_INDEX_EXTENSION, SuggestIndex, Suggestion, SuggestionKind,
+};
+
+impl Memvid {
+    /// Up to `limit` completions of `prefix` drawn from titles, entity names, tags, and words
+    /// appearing in at least two frames, most frequent first. Reflects the last commit.
+    ///
+    /// Answered from an index kept in the file, so no search runs; the index is decoded on the
+    /// first call after each commit and cached on the handle.
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<Suggestion>> {
+        Ok(self.cached_suggest_index()?.suggest(prefix, limit))
+    }
+
+    pub(crate) fn cached_suggest_index(&self) -> Result<&SuggestIndex> {
+        if let Some(index) = self.suggest_index.get() {
+            return Ok(index);
+        }
+        let index = self
+            .toc
+            .extension::<SuggestIndex>(SUGGEST_INDEX_EXTENSION)?
+            .unwrap_or_default();
+        Ok(self.suggest_index.get_or_init(|| index))
+    }
+
+    /// Bring the suggestion index up to date with a commit: re-read titles, tags, and entity
+    /// names, and count the words of inserted frames in and tombstoned frames out.
+    ///
+    /// Must run after WAL records are applied and before the TOC is rewritten.
+    pub(crate) fn refresh_suggest_index(
+        &mut self,
+        inserted_frames: &[FrameId],
+        tombstoned_frames: &[FrameId],
+    ) -> Result<()> {
+        let existing = self
+            .toc
+            .extension::<SuggestIndex>(SUGGEST_INDEX_EXTENSION)?;
+        let mut index = existing.clone().unwrap_or_default();
+
+        let analyzer = self.analyzer_config()?;
+        let is_stopword = |word: &str| is_common_stopword(word) || analyzer.is_stopword(word);
+        let inserted: HashSet<FrameId> = inserted_frames.iter().copied().collect();
+        for &frame_id in tombstoned_frames {
+            if inserted.contains(&frame_id) {
+                continue;
+            }
+            // A payload that no longer reads leaves its words counted rather than failing
+            // the commit.
+            if let Ok(Some(text)) = self.suggest_text(frame_id, false) {
+                index.remove_terms(&suggestion_terms(&text, is_stopword));
+            }
+        }
+        for &frame_id in inserted_frames {
+            if let Some(text) = self.suggest_text(frame_id, true)? {
+                index.add_terms(&suggestion_terms(&text, is_stopword));
+            }
+        }
+
+        index.phrases.clear();
+        for frame in &self.toc.frames {
+            if frame.status != FrameStatus::Active {
+                continue;
+            }
+            if let Some(title) = &frame.title {
+                index.add_phrase(SuggestionKind::Title, title, 1);
+            }
+            for tag in &frame.tags {
+                index.add_phrase(SuggestionKind::Tag, tag, 1);
+            }
+        }
+        for node in &self.logic_mesh.nodes {
+            let frames = u32::try_from(node.frame_ids.len()).unwrap_or(u32::MAX);
+            index.add_phrase(SuggestionKind::Entity, &node.display_name, frames);
+        }
+
+        if existing.as_ref() != Some(&index) {
+            self.toc.set_extension(SUGGEST_INDEX_EXTENSION, &index)?;
+            self.suggest_index = OnceLock::new();
+            #[cfg(feature = "spelling")]
+            {
+                self.spelling_dictionary = OnceLock::new();
+            }
+        }
+        Ok(())
+    }
+
+    /// Indexable text of a frame counted by the suggestion index, skipping binary and
+    /// oversized payloads like the lexical index does. Inserted frames count only while
+    /// `active`, tombstoned ones only once they no longer are.
+    fn suggest_text(&mut self, frame_id: FrameId, active: bool) -> Result<Option<String>> {
+        let Some(frame) = usize::try_from(frame_id)
+            .ok()
+            .and_then(|index| self.toc.frames.get(index))
+        else {
+            return Ok(None);
+        };
+        if (frame.status == FrameStatus::Active) != active {
+            return Ok(None);
+        }
+        let frame: Frame = frame.clone();
+        if frame.search_text.is_none() {
+            let mime = frame
+                .metadata
+                .as_ref()
+                .and_then(|m| m.mime.as_deref())
+                .unwrap_or("application/octet-stream");
+            if !is_text_indexable_mime(mime) || frame.payload_length > max_index_payload() {
+                return Ok(None);
+            }
+        }
+        let text = self.frame_search_text(&frame)?;
+        Ok((!text.trim().is_empty()).then_some(text))
+    }
+}
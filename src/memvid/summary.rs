@@ -0,0 +1,299 @@
+//! Document and scope summaries for `Memvid`.
+//!
+//! Summaries are written with a caller-supplied [`Summarizer`] and stored in the TOC, so they
+//! become durable with the next commit. A card records the checksums of the frames it was
+//! built from; summarizing again with the same summarizer is a no-op until those frames change.
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::summary::summary_track;
+use crate::types::{
+    Frame, FrameId, FrameRole, FrameStatus, SUMMARY_TRACK_EXTENSION, Summarizer, SummaryCard,
+    SummaryTarget, SummaryTrack,
+};
+
+impl Memvid {
+    /// All stored summaries.
+    #[must_use]
+    pub fn summaries(&self) -> SummaryTrack {
+        summary_track(&self.toc)
+    }
+
+    /// Summary of a document frame, if one has been written.
+    #[must_use]
+    pub fn frame_summary(&self, frame_id: FrameId) -> Option<SummaryCard> {
+        self.summaries().frame(frame_id).cloned()
+    }
+
+    /// Summary of a URI scope, if one has been written.
+    #[must_use]
+    pub fn scope_summary(&self, scope: &str) -> Option<SummaryCard> {
+        self.summaries().scope(scope).cloned()
+    }
+
+    /// Summarize a document frame, including the text of its chunks, and store the card.
+    ///
+    /// Returns the existing card without calling `summarizer` when it was written by the same
+    /// summarizer and the document has not changed since. Call `commit` to persist.
+    pub fn summarize_frame(
+        &mut self,
+        frame_id: FrameId,
+        summarizer: &dyn Summarizer,
+    ) -> Result<SummaryCard> {
+        self.ensure_mutation_allowed()?;
+        let frame = self.frame_by_id(frame_id)?;
+        if frame.status != FrameStatus::Active {
+            return Err(MemvidError::InvalidFrame {
+                frame_id,
+                reason: "frame is not active",
+            });
+        }
+        let mut track = self.summaries();
+        let card = self.summarize_document(&mut track, &frame, summarizer)?;
+        self.store_summaries(&track)?;
+        Ok(card)
+    }
+
+    /// Summarize every active document whose URI starts with `scope` and store the card.
+    ///
+    /// Documents are summarized individually first, reusing stored summaries that are still
+    /// current, and the results are merged with [`Summarizer::combine`]. Call `commit` to
+    /// persist.
+    pub fn summarize_scope(
+        &mut self,
+        scope: &str,
+        summarizer: &dyn Summarizer,
+    ) -> Result<SummaryCard> {
+        self.ensure_mutation_allowed()?;
+        let documents: Vec<Frame> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .filter(|frame| frame.role != FrameRole::DocumentChunk)
+            .filter(|frame| {
+                frame
+                    .uri
+                    .as_deref()
+                    .is_some_and(|uri| uri.starts_with(scope))
+            })
+            .cloned()
+            .collect();
+        if documents.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("no documents under scope '{scope}'"),
+            });
+        }
+
+        let mut track = self.summaries();
+        let mut document_cards = Vec::with_capacity(documents.len());
+        for frame in &documents {
+            document_cards.push(self.summarize_document(&mut track, frame, summarizer)?);
+        }
+        let sources: Vec<FrameId> = documents.iter().map(|frame| frame.id).collect();
+        let mut hasher = blake3::Hasher::new();
+        for card in &document_cards {
+            hasher.update(&card.source_checksum);
+        }
+        let source_checksum: [u8; 32] = hasher.finalize().into();
+
+        let target = SummaryTarget::Scope(scope.to_string());
+        let card = match track.get(&target) {
+            Some(card)
+                if card.summarizer == summarizer.kind()
+                    && card.source_checksum == source_checksum =>
+            {
+                card.clone()
+            }
+            _ => {
+                let texts: Vec<&str> = document_cards
+                    .iter()
+                    .map(|card| card.text.as_str())
+                    .collect();
+                let card = SummaryCard {
+                    target,
+                    text: summarizer.combine(&texts)?,
+                    summarizer: summarizer.kind().to_string(),
+                    sources,
+                    source_checksum,
+                    created_at: unix_now(),
+                };
+                track.upsert(card.clone());
+                card
+            }
+        };
+        self.store_summaries(&track)?;
+        Ok(card)
+    }
+
+    /// Delete a stored summary, returning it. Call `commit` to persist.
+    pub fn remove_summary(&mut self, target: &SummaryTarget) -> Result<Option<SummaryCard>> {
+        self.ensure_mutation_allowed()?;
+        self.ensure_not_held("remove summaries")?;
+        let mut track = self.summaries();
+        let removed = track.remove(target);
+        if removed.is_some() {
+            self.store_summaries(&track)?;
+        }
+        Ok(removed)
+    }
+
+    /// Current card for `frame`, summarizing it into `track` if missing or stale.
+    fn summarize_document(
+        &mut self,
+        track: &mut SummaryTrack,
+        frame: &Frame,
+        summarizer: &dyn Summarizer,
+    ) -> Result<SummaryCard> {
+        let mut chunks: Vec<Frame> = self
+            .toc
+            .frames
+            .iter()
+            .filter(|candidate| {
+                candidate.status == FrameStatus::Active
+                    && candidate.role == FrameRole::DocumentChunk
+                    && candidate.parent_id == Some(frame.id)
+            })
+            .cloned()
+            .collect();
+        chunks.sort_by_key(|chunk| (chunk.chunk_index, chunk.id));
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&frame.checksum);
+        for chunk in &chunks {
+            hasher.update(&chunk.checksum);
+        }
+        let source_checksum: [u8; 32] = hasher.finalize().into();
+        if let Some(card) = track.frame(frame.id) {
+            if card.summarizer == summarizer.kind() && card.source_checksum == source_checksum {
+                return Ok(card.clone());
+            }
+        }
+
+        let text = if chunks.is_empty() {
+            self.frame_content(frame)?
+        } else {
+            let mut parts = Vec::with_capacity(chunks.len());
+            for chunk in &chunks {
+                parts.push(self.frame_content(chunk)?);
+            }
+            parts.join("\n")
+        };
+        let card = SummaryCard {
+            target: SummaryTarget::Frame(frame.id),
+            text: summarizer.summarize(&text)?,
+            summarizer: summarizer.kind().to_string(),
+            sources: vec![frame.id],
+            source_checksum,
+            created_at: unix_now(),
+        };
+        track.upsert(card.clone());
+        Ok(card)
+    }
+
+    pub(crate) fn store_summaries(&mut self, track: &SummaryTrack) -> Result<()> {
+        if track.is_empty() {
+            self.toc.extensions.remove(SUMMARY_TRACK_EXTENSION);
+        } else {
+            self.toc.set_extension(SUMMARY_TRACK_EXTENSION, track)?;
+        }
+        self.dirty = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::types::{PutOptions, TimelineQuery};
+
+    /// Keeps the first three words and counts calls.
+    struct FirstWords(AtomicUsize);
+
+    impl Summarizer for FirstWords {
+        fn kind(&self) -> &'static str {
+            "first-words"
+        }
+
+        fn summarize(&self, text: &str) -> Result<String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(text
+                .split_whitespace()
+                .take(3)
+                .collect::<Vec<_>>()
+                .join(" "))
+        }
+    }
+
+    #[test]
+    fn summaries_persist_and_replace_previews() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("summaries.mv2");
+        let summarizer = FirstWords(AtomicUsize::new(0));
+        {
+            let mut mem = Memvid::create(&path).expect("create");
+            for (uri, text) in [
+                (
+                    "mv2://reports/q1",
+                    "revenue grew strongly across every region this quarter",
+                ),
+                (
+                    "mv2://reports/q2",
+                    "hiring slowed while support costs kept rising",
+                ),
+                ("mv2://notes/misc", "unrelated scratch notes"),
+            ] {
+                let options = PutOptions {
+                    uri: Some(uri.into()),
+                    ..Default::default()
+                };
+                mem.put_bytes_with_options(text.as_bytes(), options)
+                    .expect("put");
+            }
+            mem.commit().expect("commit");
+
+            let card = mem.summarize_frame(0, &summarizer).expect("summarize");
+            assert_eq!(card.text, "revenue grew strongly");
+            let scope = mem
+                .summarize_scope("mv2://reports/", &summarizer)
+                .expect("scope");
+            assert_eq!(scope.sources, vec![0, 1]);
+            assert_eq!(scope.text, "revenue grew strongly");
+            // Frame 0 was reused; frame 1 and the combine step ran.
+            assert_eq!(summarizer.0.load(Ordering::SeqCst), 3);
+            mem.summarize_scope("mv2://reports/", &summarizer)
+                .expect("scope again");
+            assert_eq!(summarizer.0.load(Ordering::SeqCst), 3);
+            assert!(mem.summarize_scope("mv2://missing/", &summarizer).is_err());
+            mem.commit().expect("commit summaries");
+        }
+
+        let mut mem = Memvid::open(&path).expect("reopen");
+        assert_eq!(mem.summaries().len(), 3);
+        assert_eq!(
+            mem.frame_summary(1).map(|card| card.text),
+            Some("hiring slowed while".to_string())
+        );
+        assert!(mem.scope_summary("mv2://reports/").is_some());
+
+        let timeline = mem.timeline(TimelineQuery::default()).expect("timeline");
+        let previews: Vec<&str> = timeline
+            .iter()
+            .map(|entry| entry.preview.as_str())
+            .collect();
+        assert_eq!(
+            previews[..2],
+            ["revenue grew strongly", "hiring slowed while"]
+        );
+        assert!(previews[2].starts_with("unrelated scratch notes"));
+
+        let removed = mem
+            .remove_summary(&SummaryTarget::Frame(1))
+            .expect("remove");
+        assert!(removed.is_some());
+        assert!(mem.frame_summary(1).is_none());
+    }
+}
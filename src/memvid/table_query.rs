@@ -0,0 +1,194 @@
+//! `Memvid::table_query`: typed cells from stored tables.
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::table::{
+    TABLE_META_KIND, TableQueryResult, TableSelector, get_table, resolve_column, select,
+    table_matches,
+};
+use crate::types::{FrameId, FrameStatus};
+
+impl Memvid {
+    /// Select typed cells from a table stored with `table::store_table`.
+    ///
+    /// `frame_id` is a `table_meta` or `table_row` frame, or the frame of the document the
+    /// tables were extracted from, matched against each table's `source_uri` (a chunk frame's
+    /// `#page-N` suffix is ignored). When the document has several tables, `selector.table`
+    /// picks one by id or sheet name; otherwise the first table with every selected column
+    /// is used.
+    ///
+    /// ```no_run
+    /// # fn demo(mem: &mut memvid_core::Memvid, frame_id: u64) -> memvid_core::Result<()> {
+    /// use memvid_core::table::TableSelector;
+    ///
+    /// let selector = TableSelector::default().table("Forecast").row("Revenue").column("Q3");
+    /// let q3_revenue = mem.table_query(frame_id, &selector)?.value().cloned();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn table_query(
+        &mut self,
+        frame_id: FrameId,
+        selector: &TableSelector,
+    ) -> Result<TableQueryResult> {
+        let frame = self.frame_by_id(frame_id)?;
+        let table_ids: Vec<String> = if let Some(table_id) = frame.extra_metadata.get("table_id") {
+            vec![table_id.clone()]
+        } else {
+            let source_uri = frame
+                .uri
+                .as_deref()
+                .map(|uri| uri.split_once('#').map_or(uri, |(base, _)| base));
+            self.toc
+                .frames
+                .iter()
+                .filter(|candidate| {
+                    candidate.status == FrameStatus::Active
+                        && candidate.kind.as_deref() == Some(TABLE_META_KIND)
+                        && source_uri.is_some()
+                        && candidate
+                            .extra_metadata
+                            .get("source_uri")
+                            .map(String::as_str)
+                            == source_uri
+                })
+                .filter_map(|candidate| candidate.extra_metadata.get("table_id").cloned())
+                .collect()
+        };
+
+        let mut tables = Vec::new();
+        for table_id in &table_ids {
+            if let Some(table) = get_table(self, table_id)? {
+                tables.push(table);
+            }
+        }
+        if let Some(name) = selector.table.as_deref() {
+            tables.retain(|table| table_matches(table, name));
+        }
+        let table = tables
+            .iter()
+            .find(|table| {
+                selector
+                    .columns
+                    .iter()
+                    .all(|column| resolve_column(&table.headers, column).is_some())
+            })
+            .or_else(|| tables.first())
+            .ok_or_else(|| MemvidError::TableExtraction {
+                reason: format!("no stored table matches frame {frame_id}"),
+            })?;
+        select(table, selector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sql::QueryValue;
+    use crate::table::{ExtractedTable, TableCell, TableRow, TableSelector, store_table};
+    use crate::types::PutOptions;
+    use crate::{Memvid, MemvidError};
+    use tempfile::tempdir;
+
+    fn sheet(table_id: &str, sheet_name: &str, rows: &[[&str; 3]]) -> ExtractedTable {
+        let mut table = ExtractedTable::new(table_id, "plan.xlsx");
+        table.source_uri = Some("mv2://docs/plan.xlsx".to_string());
+        table.sheet_name = Some(sheet_name.to_string());
+        table.headers = ["Metric", "Q2", "Q3"].map(String::from).to_vec();
+        table.n_cols = 3;
+        table.n_rows = rows.len();
+        for (row_index, cells) in rows.iter().enumerate() {
+            let cells = cells
+                .iter()
+                .enumerate()
+                .map(|(col, text)| TableCell::new(*text, col))
+                .collect();
+            table.rows.push(TableRow::new(row_index, 1, cells));
+        }
+        table
+    }
+
+    #[test]
+    fn answers_from_the_named_sheet_of_a_document() {
+        let dir = tempdir().expect("tempdir");
+        let mut mem = Memvid::create(dir.path().join("tables.mv2")).expect("create");
+        let doc = mem.next_frame_id();
+        mem.put_bytes_with_options(
+            b"quarterly plan",
+            PutOptions::builder().uri("mv2://docs/plan.xlsx").build(),
+        )
+        .expect("put doc");
+        let actuals = sheet("tbl_1", "Actuals", &[["Revenue", "1,100", "1,150"]]);
+        let forecast = sheet(
+            "tbl_2",
+            "Forecast",
+            &[["Revenue", "1,200", "$1,480.50"], ["Costs", "900", "950"]],
+        );
+        store_table(&mut mem, &actuals, false).expect("store actuals");
+        let (_, forecast_rows) = store_table(&mut mem, &forecast, false).expect("store forecast");
+        mem.commit().expect("commit");
+
+        let selector = TableSelector::default()
+            .table("forecast")
+            .row("Revenue")
+            .column("Q3");
+        let result = mem.table_query(doc, &selector).expect("query");
+        assert_eq!(result.table_id, "tbl_2");
+        assert_eq!(result.value(), Some(&QueryValue::Float(1480.5)));
+
+        // A row frame resolves to its own table.
+        let costs = mem
+            .table_query(forecast_rows[1], &TableSelector::default().row("costs"))
+            .expect("row query");
+        assert_eq!(costs.rows[0].cells[2].value, QueryValue::Int(950));
+
+        let missing = mem.table_query(doc, &TableSelector::default().table("Budget"));
+        assert!(matches!(missing, Err(MemvidError::TableExtraction { .. })));
+    }
+
+    #[cfg(feature = "lex")]
+    #[test]
+    fn column_field_finds_table_rows() {
+        use crate::types::{AclEnforcementMode, SearchMode, SearchRequest};
+
+        let dir = tempdir().expect("tempdir");
+        let mut mem = Memvid::create(dir.path().join("tables.mv2")).expect("create");
+        let forecast = sheet("tbl_1", "Forecast", &[["Revenue", "1,200", "1,480"]]);
+        let (meta, rows) = store_table(&mut mem, &forecast, false).expect("store");
+        mem.put_bytes_with_options(b"Q3 revenue grew", PutOptions::default())
+            .expect("put note");
+        mem.commit().expect("commit");
+
+        let response = mem
+            .search(SearchRequest {
+                query: "column:q3 revenue".into(),
+                top_k: 10,
+                snippet_chars: 80,
+                uri: None,
+                scope: None,
+                cursor: None,
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+                as_of_frame: None,
+                as_of_ts: None,
+                no_sketch: false,
+                acl_context: None,
+                acl_enforcement_mode: AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: SearchMode::Lexical,
+                time_budget_ms: None,
+            })
+            .expect("search");
+        let mut hits: Vec<u64> = response.hits.iter().map(|hit| hit.frame_id).collect();
+        hits.sort_unstable();
+        assert_eq!(hits, [meta, rows[0]]);
+    }
+}
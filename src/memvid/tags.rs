@@ -0,0 +1,131 @@
+//! Renaming, merging, and deleting tags across every frame (see [`crate::types::tags`]).
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::helpers::unix_now;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{FrameId, FrameStatus, TAG_LOG_EXTENSION, TagEdit, TagEditRecord, TagLog};
+
+impl Memvid {
+    /// Replace tag `old` with `new` on every active frame. Returns the frames that changed.
+    pub fn rename_tag(&mut self, old: &str, new: &str) -> Result<Vec<FrameId>> {
+        self.apply_tag_edit(TagEdit::Rename {
+            from: old.to_string(),
+            to: checked_tag(new)?,
+        })
+    }
+
+    /// Replace every tag in `tags` with `into` on every active frame. Returns the frames that
+    /// changed.
+    pub fn merge_tags(&mut self, tags: &[&str], into: &str) -> Result<Vec<FrameId>> {
+        self.apply_tag_edit(TagEdit::Merge {
+            sources: tags.iter().map(ToString::to_string).collect(),
+            into: checked_tag(into)?,
+        })
+    }
+
+    /// Remove `tag` from every active frame. Returns the frames that changed.
+    pub fn delete_tag(&mut self, tag: &str) -> Result<Vec<FrameId>> {
+        self.apply_tag_edit(TagEdit::Delete {
+            tag: tag.to_string(),
+        })
+    }
+
+    /// Tag edits retained in the file, oldest first.
+    pub fn tag_edits(&self) -> Result<Vec<TagEditRecord>> {
+        Ok(self
+            .toc
+            .extension::<TagLog>(TAG_LOG_EXTENSION)?
+            .map(|log| log.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Rewrite the tags of committed frames, reindex the changed ones, and log the edit.
+    /// Saved on the next commit.
+    fn apply_tag_edit(&mut self, edit: TagEdit) -> Result<Vec<FrameId>> {
+        self.ensure_writable()?;
+        self.ensure_not_held("edit tags")?;
+        let mut changed = Vec::new();
+        for frame in &mut self.toc.frames {
+            if frame.status != FrameStatus::Active || !edit.apply(&mut frame.tags) {
+                continue;
+            }
+            if let Some(text) = frame.search_text.as_mut() {
+                *text = with_tags_line(text, &frame.tags);
+            }
+            changed.push(frame.id);
+        }
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+        self.reindex_tagged_frames(&changed)?;
+
+        let mut log = self
+            .toc
+            .extension::<TagLog>(TAG_LOG_EXTENSION)?
+            .unwrap_or_default();
+        log.push(TagEditRecord {
+            edit,
+            frames: changed.clone(),
+            applied_at: unix_now(),
+            author: self.commit_identity.author.clone(),
+            agent_id: self.commit_identity.agent_id.clone(),
+        });
+        self.toc.set_extension(TAG_LOG_EXTENSION, &log)?;
+        self.dirty = true;
+        Ok(changed)
+    }
+
+    /// Replace the lexical documents of `frame_ids` so tag queries see the new tags.
+    #[cfg(feature = "lex")]
+    fn reindex_tagged_frames(&mut self, frame_ids: &[FrameId]) -> Result<()> {
+        if self.tantivy.is_none() {
+            return Ok(());
+        }
+        for &frame_id in frame_ids {
+            let frame = self.frame_by_id(frame_id)?;
+            let text = match frame.search_text.as_deref() {
+                Some(text) if !text.trim().is_empty() => text.to_string(),
+                _ => self.frame_content(&frame)?,
+            };
+            if let Some(engine) = self.tantivy.as_mut() {
+                engine.delete_frame(frame_id)?;
+                engine.add_frame(&frame, &text)?;
+            }
+        }
+        if let Some(engine) = self.tantivy.as_mut() {
+            engine.soft_commit()?;
+        }
+        self.tantivy_dirty = true;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "lex"))]
+    #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
+    fn reindex_tagged_frames(&mut self, _frame_ids: &[FrameId]) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn checked_tag(tag: &str) -> Result<String> {
+    if tag.trim().is_empty() {
+        return Err(MemvidError::InvalidTag {
+            tag: tag.to_string(),
+            reason: "tags must not be empty",
+        });
+    }
+    Ok(tag.to_string())
+}
+
+/// `text` with the `tags:` line written at put time replaced by `tags`, or removed when no
+/// tags are left.
+fn with_tags_line(text: &str, tags: &[String]) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    if let Some(index) = lines.iter().rposition(|line| line.starts_with("tags: ")) {
+        if tags.is_empty() {
+            lines.remove(index);
+        } else {
+            lines[index] = format!("tags: {}", tags.join(" "));
+        }
+    }
+    lines.join("\n")
+}
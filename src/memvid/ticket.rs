@@ -2,7 +2,13 @@ use crate::constants::MEMVID_TICKET_PUBKEY;
 use crate::error::{MemvidError, Result};
 use crate::memvid::lifecycle::Memvid;
 use crate::signature::{parse_ed25519_public_key_base64, verify_ticket_signature};
-use crate::types::{FrameStatus, SignedTicket, Stats, Ticket, TicketRef};
+use crate::types::{
+    CapacityBreakdown, CapacityReport, CapacitySource, DEFAULT_CAPACITY_WARNING_PERCENT,
+    FrameStatus, SignedTicket, Stats, StorageBreakdown, Ticket, TicketRef,
+};
+
+/// Hottest frames reported in `Stats::hot_frames`.
+const STATS_HOT_FRAMES: usize = 10;
 
 impl Memvid {
     pub fn stats(&self) -> Result<Stats> {
@@ -87,6 +93,8 @@ impl Memvid {
 
         // CLIP image count from clip index manifest
         let clip_image_count = self.toc.indexes.clip.as_ref().map_or(0, |c| c.vector_count);
+        let storage = self.storage_breakdown()?;
+        let capacity = self.capacity_report_for(&storage)?;
 
         Ok(Stats {
             frame_count: self.toc.frames.len() as u64,
@@ -119,9 +127,177 @@ impl Memvid {
             clip_image_count,
             lex_enabled: self.lex_enabled,
             vec_enabled: self.vec_enabled,
+            hot_frames: self.hot_frames(STATS_HOT_FRAMES)?,
+            capacity_warning_percent: capacity.warning_percent,
+            capacity_warning: capacity.warning,
+            storage,
+        })
+    }
+
+    /// Usage against the ticket's or tier's capacity, what takes the space, and whether the
+    /// soft-warning threshold (`FileConfig::capacity_warning_percent`) has been reached, so
+    /// hosts can warn before puts start failing with `CapacityExceeded`.
+    pub fn capacity_report(&self) -> Result<CapacityReport> {
+        self.capacity_report_for(&self.storage_breakdown()?)
+    }
+
+    fn capacity_report_for(&self, storage: &StorageBreakdown) -> Result<CapacityReport> {
+        let file_bytes = storage.file_bytes;
+        let capacity_bytes = self.capacity_limit();
+        let used_bytes = self.cached_payload_end;
+
+        let mut breakdown = CapacityBreakdown {
+            payload_bytes: storage.payload_bytes,
+            lex_index_bytes: storage
+                .lex_index_bytes
+                .saturating_add(storage.tantivy_segment_bytes),
+            vec_index_bytes: storage.vec_index_bytes,
+            clip_index_bytes: storage.clip_index_bytes,
+            time_index_bytes: storage.time_index_bytes,
+            track_bytes: storage.track_bytes(),
+            wal_bytes: storage.wal_bytes,
+            other_bytes: 0,
+        };
+        let accounted = breakdown.payload_bytes
+            + breakdown.lex_index_bytes
+            + breakdown.vec_index_bytes
+            + breakdown.clip_index_bytes
+            + breakdown.time_index_bytes
+            + breakdown.track_bytes
+            + breakdown.wal_bytes;
+        breakdown.other_bytes = file_bytes.saturating_sub(accounted);
+
+        let warning_percent = self
+            .config()?
+            .capacity_warning_percent
+            .unwrap_or(DEFAULT_CAPACITY_WARNING_PERCENT);
+        let utilisation_percent = if capacity_bytes > 0 {
+            ((used_bytes as f64 / capacity_bytes as f64) * 10_000.0).round() / 100.0
+        } else {
+            0.0
+        };
+        Ok(CapacityReport {
+            capacity_bytes,
+            source: if self.toc.ticket_ref.capacity_bytes != 0
+                && self.toc.ticket_ref.issuer != "free-tier"
+            {
+                CapacitySource::Ticket
+            } else {
+                CapacitySource::Tier
+            },
+            tier: self.tier(),
+            used_bytes,
+            remaining_bytes: capacity_bytes.saturating_sub(used_bytes),
+            utilisation_percent,
+            file_bytes,
+            breakdown,
+            warning_percent,
+            warning: u128::from(used_bytes) * 100
+                >= u128::from(capacity_bytes) * u128::from(warning_percent),
         })
     }
 
+    /// Per-component byte accounting: stored payloads by kind and MIME type, each index and
+    /// track, TOC extensions, and the WAL, computed from the TOC and segment catalog.
+    pub fn storage_breakdown(&self) -> Result<StorageBreakdown> {
+        let mut storage = StorageBreakdown {
+            file_bytes: self.file.metadata()?.len(),
+            ..StorageBreakdown::default()
+        };
+        for frame in &self.toc.frames {
+            let stored = frame.payload_length;
+            storage.record_payload(
+                frame.kind.as_deref(),
+                frame
+                    .metadata
+                    .as_ref()
+                    .and_then(|meta| meta.mime.as_deref()),
+                stored,
+                frame.canonical_length.unwrap_or(stored),
+                frame.status == FrameStatus::Active,
+            );
+        }
+
+        let indexes = &self.toc.indexes;
+        let catalog = &self.toc.segment_catalog;
+        storage.lex_index_bytes = indexes.lex.as_ref().map_or(0, |lex| lex.bytes_length)
+            + catalog
+                .lex_segments
+                .iter()
+                .map(|seg| seg.common.bytes_length)
+                .sum::<u64>();
+        // The lex manifest mirrors the catalog's Tantivy segments; older files only have the
+        // former.
+        if catalog.tantivy_segments.is_empty() {
+            storage.tantivy_segment_count = indexes.lex_segments.len() as u64;
+            storage.tantivy_segment_bytes = indexes
+                .lex_segments
+                .iter()
+                .map(|seg| seg.bytes_length)
+                .sum::<u64>();
+        } else {
+            storage.tantivy_segment_count = catalog.tantivy_segments.len() as u64;
+            storage.tantivy_segment_bytes = catalog
+                .tantivy_segments
+                .iter()
+                .map(|seg| seg.common.bytes_length)
+                .sum::<u64>();
+        }
+        storage.vec_segment_count = catalog.vec_segments.len() as u64;
+        storage.vec_index_bytes = indexes.vec.as_ref().map_or(0, |vec| vec.bytes_length)
+            + catalog
+                .vec_segments
+                .iter()
+                .map(|seg| seg.common.bytes_length)
+                .sum::<u64>();
+        storage.clip_index_bytes = indexes.clip.as_ref().map_or(0, |clip| clip.bytes_length);
+        storage.time_index_bytes = self.toc.time_index.as_ref().map_or(0, |t| t.bytes_length)
+            + catalog
+                .time_segments
+                .iter()
+                .map(|seg| seg.common.bytes_length)
+                .sum::<u64>();
+        storage.temporal_track_bytes = self
+            .toc
+            .temporal_track
+            .as_ref()
+            .map_or(0, |t| t.bytes_length)
+            + catalog
+                .temporal_segments
+                .iter()
+                .map(|seg| seg.common.bytes_length)
+                .sum::<u64>();
+        storage.memories_track_bytes = self
+            .toc
+            .memories_track
+            .as_ref()
+            .map_or(0, |t| t.bytes_length);
+        storage.logic_mesh_bytes = self.toc.logic_mesh.as_ref().map_or(0, |t| t.bytes_length);
+        storage.sketch_track_bytes = self.toc.sketch_track.as_ref().map_or(0, |t| t.bytes_length);
+        storage.extension_bytes = self
+            .toc
+            .extensions
+            .iter()
+            .map(|(key, value)| (key.len() + value.len()) as u64)
+            .sum::<u64>();
+
+        let wal = self.wal.stats();
+        storage.wal_bytes = self.header.wal_size;
+        storage.wal_pending_bytes = wal.pending_bytes;
+        storage.wal_slack_bytes = storage.wal_bytes.saturating_sub(wal.pending_bytes);
+
+        let accounted = [
+            storage.payload_bytes,
+            storage.index_bytes(),
+            storage.extension_bytes,
+            storage.wal_bytes,
+        ]
+        .into_iter()
+        .fold(0u64, u64::saturating_add);
+        storage.unaccounted_bytes = storage.file_bytes.saturating_sub(accounted);
+        Ok(storage)
+    }
+
     /// Applies an unsigned ticket to this memory.
     ///
     /// # Deprecation
@@ -149,6 +325,7 @@ impl Memvid {
         self.toc.ticket_ref.verified = false; // Unsigned tickets are not verified
 
         self.generation = self.generation.wrapping_add(1);
+        self.record_audit_ticket(self.generation)?;
         self.rewrite_toc_footer()?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
@@ -237,6 +414,7 @@ impl Memvid {
         self.toc.ticket_ref.verified = true; // Mark as cryptographically verified
 
         self.generation = self.generation.wrapping_add(1);
+        self.record_audit_ticket(self.generation)?;
         self.rewrite_toc_footer()?;
         self.header.toc_checksum = self.toc.toc_checksum;
         crate::persist_header(&mut self.file, &self.header)?;
@@ -5,26 +5,177 @@ use crate::io::time_index::{TimeIndexEntry, read_track as time_index_read};
 use crate::memvid::lifecycle::Memvid;
 #[cfg(feature = "temporal_track")]
 use crate::memvid::search::frame_ids_for_temporal_filter;
-use crate::types::{FrameId, FrameRole, FrameStatus, TimelineEntry};
+use crate::types::summary::summary_track;
+use crate::types::{
+    FrameId, FrameRole, FrameStatus, GeoFilter, TimeBucket, TimelineBucket, TimelineEntry,
+    TimelineQuery,
+};
 #[cfg(feature = "temporal_track")]
 use crate::types::{
     SearchHitTemporal, SearchHitTemporalAnchor, SearchHitTemporalMention, TemporalFilter,
     TemporalTrack,
 };
-#[cfg(feature = "temporal_track")]
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::num::NonZeroU64;
 #[cfg(feature = "temporal_track")]
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
+impl Memvid {
+    /// Frame counts and payload bytes per `bucket` for the frames `query` selects, oldest
+    /// bucket first or newest first with `query.reverse`. Empty buckets are left out and
+    /// `query.limit` caps the number of buckets.
+    ///
+    /// Only the time index and the frame table are read, never payloads, so this stays cheap
+    /// for activity heatmaps over very large memories.
+    pub fn timeline_histogram(
+        &mut self,
+        bucket: TimeBucket,
+        query: &TimelineQuery,
+    ) -> Result<Vec<TimelineBucket>> {
+        let entries = timeline_entries(
+            self,
+            query.since,
+            query.until,
+            query.geo.as_ref(),
+            #[cfg(feature = "temporal_track")]
+            query.temporal.as_ref(),
+        )?;
+        let mut buckets: BTreeMap<i64, TimelineBucket> = BTreeMap::new();
+        for entry in entries {
+            let Some(frame) = usize::try_from(entry.frame_id)
+                .ok()
+                .and_then(|index| self.toc.frames.get(index))
+            else {
+                continue;
+            };
+            if frame.status != FrameStatus::Active {
+                continue;
+            }
+            let start = bucket.floor(entry.timestamp);
+            let slot = buckets.entry(start).or_insert_with(|| TimelineBucket {
+                start,
+                end: bucket.end(start),
+                frames: 0,
+                bytes: 0,
+            });
+            slot.frames += 1;
+            slot.bytes += frame.payload_length;
+        }
+
+        let limit = query.limit.map_or(usize::MAX, |nz| {
+            usize::try_from(nz.get()).unwrap_or(usize::MAX)
+        });
+        let buckets = buckets.into_values();
+        Ok(if query.reverse {
+            buckets.rev().take(limit).collect()
+        } else {
+            buckets.take(limit).collect()
+        })
+    }
+}
+
 pub(crate) fn build_timeline(
     memvid: &mut Memvid,
     limit: Option<NonZeroU64>,
     since: Option<i64>,
     until: Option<i64>,
     reverse: bool,
+    geo: Option<&GeoFilter>,
     #[cfg(feature = "temporal_track")] temporal: Option<&TemporalFilter>,
 ) -> Result<Vec<TimelineEntry>> {
+    let mut entries = timeline_entries(
+        memvid,
+        since,
+        until,
+        geo,
+        #[cfg(feature = "temporal_track")]
+        temporal,
+    )?;
+
+    if reverse {
+        entries.reverse();
+    }
+
+    let limit = limit.map_or(entries.len(), |nz| {
+        usize::try_from(nz.get()).unwrap_or(usize::MAX)
+    });
+    let mut result = Vec::with_capacity(entries.len().min(limit));
+    #[cfg(feature = "temporal_track")]
+    let temporal_track_snapshot = memvid.temporal_track_ref()?.cloned();
+    let summaries = summary_track(&memvid.toc);
+    for entry in entries.into_iter().take(limit) {
+        let Some(frame) = memvid
+            .toc
+            .frames
+            .get(usize::try_from(entry.frame_id).unwrap_or(usize::MAX))
+            .cloned()
+        else {
+            tracing::warn!(
+                frame_id = entry.frame_id,
+                "skipping time index entry with out-of-range frame id"
+            );
+            continue;
+        };
+        if frame.status != FrameStatus::Active {
+            continue;
+        }
+        let preview = match summaries.frame(frame.id) {
+            Some(card) => crate::truncate_preview(&card.text),
+            None => memvid.frame_preview(&frame)?,
+        };
+        let uri = frame
+            .uri
+            .clone()
+            .or_else(|| Some(crate::default_uri(frame.id)));
+        let child_frames: Vec<FrameId> = memvid
+            .toc
+            .frames
+            .iter()
+            .filter(|candidate| {
+                candidate.status == FrameStatus::Active && candidate.parent_id == Some(frame.id)
+            })
+            .map(|candidate| candidate.id)
+            .collect();
+        #[cfg(feature = "temporal_track")]
+        let temporal_info = if let Some(track) = temporal_track_snapshot.as_ref() {
+            build_timeline_temporal_metadata(memvid, track, &frame)?
+        } else {
+            None
+        };
+
+        result.push(TimelineEntry {
+            frame_id: frame.id,
+            timestamp: frame.timestamp,
+            preview,
+            uri,
+            child_frames,
+            #[cfg(feature = "temporal_track")]
+            temporal: temporal_info,
+        });
+    }
+    Ok(result)
+}
+
+/// Time index entries, oldest first, that pass the timeline filters. Callers still skip
+/// frames that are no longer active.
+pub(crate) fn timeline_entries(
+    memvid: &mut Memvid,
+    since: Option<i64>,
+    until: Option<i64>,
+    geo: Option<&GeoFilter>,
+    #[cfg(feature = "temporal_track")] temporal: Option<&TemporalFilter>,
+) -> Result<Vec<TimeIndexEntry>> {
+    let geo_candidates: Option<HashSet<FrameId>> = match geo {
+        Some(filter) => {
+            let ids = memvid.frame_ids_in_geo(filter)?;
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            Some(ids.into_iter().collect())
+        }
+        None => None,
+    };
+
     #[cfg(feature = "temporal_track")]
     let temporal_candidates: Option<HashSet<FrameId>> = if let Some(filter) = temporal {
         if filter.is_empty() {
@@ -78,74 +229,21 @@ pub(crate) fn build_timeline(
         entries.retain(|entry| candidates.contains(&entry.frame_id));
     }
 
+    if let Some(ref candidates) = geo_candidates {
+        entries.retain(|entry| candidates.contains(&entry.frame_id));
+    }
+
     entries.retain(|entry| {
         let after_since = since.is_none_or(|s| entry.timestamp >= s);
         let before_until = until.is_none_or(|u| entry.timestamp <= u);
         after_since && before_until
     });
 
-    if reverse {
-        entries.reverse();
-    }
-
-    let limit = limit.map_or(entries.len(), |nz| {
-        usize::try_from(nz.get()).unwrap_or(usize::MAX)
-    });
-    let mut result = Vec::with_capacity(entries.len().min(limit));
-    #[cfg(feature = "temporal_track")]
-    let temporal_track_snapshot = memvid.temporal_track_ref()?.cloned();
-    for entry in entries.into_iter().take(limit) {
-        let Some(frame) = memvid
-            .toc
-            .frames
-            .get(usize::try_from(entry.frame_id).unwrap_or(usize::MAX))
-            .cloned()
-        else {
-            tracing::warn!(
-                frame_id = entry.frame_id,
-                "skipping time index entry with out-of-range frame id"
-            );
-            continue;
-        };
-        if frame.status != FrameStatus::Active {
-            continue;
-        }
-        let preview = memvid.frame_preview(&frame)?;
-        let uri = frame
-            .uri
-            .clone()
-            .or_else(|| Some(crate::default_uri(frame.id)));
-        let child_frames: Vec<FrameId> = memvid
-            .toc
-            .frames
-            .iter()
-            .filter(|candidate| {
-                candidate.status == FrameStatus::Active && candidate.parent_id == Some(frame.id)
-            })
-            .map(|candidate| candidate.id)
-            .collect();
-        #[cfg(feature = "temporal_track")]
-        let temporal_info = if let Some(track) = temporal_track_snapshot.as_ref() {
-            build_timeline_temporal_metadata(memvid, track, &frame)?
-        } else {
-            None
-        };
-
-        result.push(TimelineEntry {
-            frame_id: frame.id,
-            timestamp: frame.timestamp,
-            preview,
-            uri,
-            child_frames,
-            #[cfg(feature = "temporal_track")]
-            temporal: temporal_info,
-        });
-    }
-    Ok(result)
+    Ok(entries)
 }
 
 #[cfg(feature = "temporal_track")]
-fn build_timeline_temporal_metadata(
+pub(crate) fn build_timeline_temporal_metadata(
     memvid: &mut Memvid,
     track: &TemporalTrack,
     frame: &crate::types::Frame,
@@ -194,6 +292,7 @@ fn build_timeline_temporal_metadata(
                 },
                 byte_start: mention.byte_start,
                 byte_len: mention.byte_len,
+                recurrence: mention.recurrence.map(|rule| rule.to_rrule()),
             });
         }
         if !collected.is_empty() {
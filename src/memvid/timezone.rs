@@ -0,0 +1,115 @@
+//! Time zones used to resolve relative dates at ingest (see [`crate::analysis::timezone`]).
+
+#[cfg(feature = "temporal_track")]
+use std::collections::BTreeMap;
+
+use time::OffsetDateTime;
+
+#[cfg(feature = "temporal_track")]
+use crate::analysis::timezone::TEMPORAL_TZ_KEY;
+use crate::analysis::timezone::{TEMPORAL_TZ_EXTENSION, timezone_offset};
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+
+/// Zone used when neither the put nor the file names one.
+#[cfg(feature = "temporal_track")]
+pub(crate) const DEFAULT_TEMPORAL_TZ: &str = "America/Chicago";
+
+impl Memvid {
+    /// Time zone that phrases like "tomorrow at 9" resolve in for puts that do not set
+    /// `PutOptions::timezone`, or the built-in default (America/Chicago) with `None`.
+    ///
+    /// Accepts fixed offsets and common IANA names (see [`timezone_offset`]). Saved in the file
+    /// on the next commit; frames already committed keep the mentions they were given.
+    pub fn set_default_timezone(&mut self, timezone: Option<&str>) -> Result<()> {
+        self.ensure_writable()?;
+        match timezone {
+            Some(timezone) => {
+                timezone_offset(timezone, OffsetDateTime::now_utc())?;
+                self.toc
+                    .set_extension(TEMPORAL_TZ_EXTENSION, &timezone.trim().to_string())?;
+            }
+            None => {
+                self.toc.extensions.remove(TEMPORAL_TZ_EXTENSION);
+            }
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// The file's default time zone, if one was set.
+    pub fn default_timezone(&self) -> Result<Option<String>> {
+        self.toc.extension(TEMPORAL_TZ_EXTENSION)
+    }
+
+    /// Zone for a frame: its own, then the file default, then the built-in one.
+    #[cfg(feature = "temporal_track")]
+    pub(crate) fn frame_timezone(&self, extra_metadata: &BTreeMap<String, String>) -> String {
+        if let Some(timezone) = extra_metadata.get(TEMPORAL_TZ_KEY) {
+            return timezone.clone();
+        }
+        self.default_timezone()
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| DEFAULT_TEMPORAL_TZ.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PutOptions;
+
+    #[test]
+    fn default_timezone_persists_and_rejects_unknown_zones() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("timezone.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        assert_eq!(mem.default_timezone().expect("default"), None);
+        assert!(mem.set_default_timezone(Some("Mars/Olympus_Mons")).is_err());
+        mem.set_default_timezone(Some("Europe/Berlin"))
+            .expect("set default");
+        let bad = PutOptions::builder().timezone("nowhere").build();
+        assert!(mem.put_bytes_with_options(b"note", bad).is_err());
+        mem.commit().expect("commit");
+        drop(mem);
+
+        let mut reopened = Memvid::open(&path).expect("open");
+        assert_eq!(
+            reopened.default_timezone().expect("default").as_deref(),
+            Some("Europe/Berlin")
+        );
+        reopened.set_default_timezone(None).expect("clear");
+        assert_eq!(reopened.default_timezone().expect("default"), None);
+    }
+
+    #[cfg(feature = "temporal_track")]
+    #[test]
+    fn relative_dates_resolve_in_the_frame_timezone() {
+        use crate::analysis::temporal::{
+            TemporalContext, TemporalNormalizer, TemporalResolutionValue,
+        };
+
+        // 2024-07-15 23:30 UTC: already the 16th in Berlin, still the 15th in Chicago
+        let late_evening = OffsetDateTime::from_unix_timestamp(1_721_086_200).expect("ts");
+        let dir = tempfile::tempdir().expect("tmp");
+        let mem = Memvid::create(dir.path().join("anchoring.mv2")).expect("create");
+
+        let tomorrow = |extra_metadata: &BTreeMap<String, String>| {
+            let timezone = mem.frame_timezone(extra_metadata);
+            let offset = timezone_offset(&timezone, late_evening).expect("offset");
+            let context = TemporalContext::new(late_evening.to_offset(offset), timezone);
+            match TemporalNormalizer::new(context)
+                .resolve("tomorrow")
+                .expect("resolve")
+                .value
+            {
+                TemporalResolutionValue::Date(date) => date.to_string(),
+                other => panic!("expected a date, got {other:?}"),
+            }
+        };
+        let berlin = BTreeMap::from([(TEMPORAL_TZ_KEY.to_string(), "Europe/Berlin".to_string())]);
+        assert_eq!(tomorrow(&berlin), "2024-07-17");
+        assert_eq!(tomorrow(&BTreeMap::new()), "2024-07-16");
+    }
+}
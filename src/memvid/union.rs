@@ -0,0 +1,288 @@
+//! Read-only union of several `.mv2` files queried as one memory.
+//!
+//! Each member is opened with `Memvid::open_read_only` and answers the query on its own; the
+//! union merges the results. Search and ask hits are fused by reciprocal rank, the same way
+//! `ask` fuses its lexical and semantic lists, so members with differently scaled scores
+//! still interleave fairly. Timelines merge by timestamp. Nothing is ever written.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::ask::RRF_K;
+use crate::memvid::lifecycle::Memvid;
+use crate::memvid::search::helpers::build_context;
+use crate::types::{
+    AskRequest, SearchHit, SearchRequest, TimelineEntry, TimelineQuery, UnionAskResponse,
+    UnionSearchHit, UnionSearchResponse, UnionTimelineEntry, VecEmbedder,
+};
+
+/// Read-only handle over several memories.
+pub struct MemvidUnion {
+    paths: Vec<PathBuf>,
+    members: Vec<Memvid>,
+}
+
+impl MemvidUnion {
+    /// Open every file in `paths` read-only. Fails if any member fails to open.
+    pub fn open<I, P>(paths: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut union = Self {
+            paths: Vec::new(),
+            members: Vec::new(),
+        };
+        for path in paths {
+            let path = path.as_ref();
+            union.members.push(Memvid::open_read_only(path)?);
+            union.paths.push(path.to_path_buf());
+        }
+        if union.members.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "a memory union needs at least one file".into(),
+            });
+        }
+        Ok(union)
+    }
+
+    /// Member paths, in the order `source` indexes refer to.
+    #[must_use]
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// The open members, for queries the union does not merge. Shared references only:
+    /// writes take `&mut Memvid` and would upgrade a member's read-only lock.
+    #[must_use]
+    pub fn members(&self) -> &[Memvid] {
+        &self.members
+    }
+
+    /// Run `request` on every member and return the best `top_k` hits across them. Cursors
+    /// are per file, so paging is not supported.
+    pub fn search(&mut self, request: SearchRequest) -> Result<UnionSearchResponse> {
+        if request.cursor.is_some() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "search cursors are not supported across a memory union".into(),
+            });
+        }
+        let start = Instant::now();
+        let top_k = request.top_k;
+        let query = request.query.clone();
+        let mut lists = Vec::with_capacity(self.members.len());
+        let mut total_hits = 0;
+        for member in &mut self.members {
+            let response = member.search(request.clone())?;
+            total_hits += response.total_hits;
+            lists.push(response.hits);
+        }
+        let hits = self.fuse(lists, top_k);
+        Ok(UnionSearchResponse {
+            query,
+            elapsed_ms: start.elapsed().as_millis(),
+            total_hits,
+            context: union_context(&hits),
+            hits,
+        })
+    }
+
+    /// Timeline entries of all members, ordered by timestamp and cut to `query.limit`.
+    pub fn timeline(&mut self, query: TimelineQuery) -> Result<Vec<UnionTimelineEntry>> {
+        let mut entries = Vec::new();
+        for (source, member) in self.members.iter_mut().enumerate() {
+            entries.extend(member.timeline(query.clone())?.into_iter().map(
+                |entry: TimelineEntry| UnionTimelineEntry {
+                    source,
+                    path: self.paths[source].clone(),
+                    entry,
+                },
+            ));
+        }
+        entries.sort_by_key(|item| (item.entry.timestamp, item.source, item.entry.frame_id));
+        if query.reverse {
+            entries.reverse();
+        }
+        if let Some(limit) = query.limit {
+            entries.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+        }
+        Ok(entries)
+    }
+
+    /// `ask` every member, merge their retrieval, and keep the answer of the member that holds
+    /// the best merged hit.
+    pub fn ask<E>(&mut self, request: AskRequest, embedder: Option<&E>) -> Result<UnionAskResponse>
+    where
+        E: VecEmbedder + ?Sized,
+    {
+        let start = Instant::now();
+        let top_k = request.top_k;
+        let question = request.question.clone();
+        let mut responses = Vec::with_capacity(self.members.len());
+        for member in &mut self.members {
+            responses.push(member.ask(request.clone(), embedder)?);
+        }
+        let lists = responses
+            .iter_mut()
+            .map(|response| std::mem::take(&mut response.retrieval.hits))
+            .collect();
+        let hits = self.fuse(lists, top_k);
+        let answer_source = hits.first().map(|hit| hit.source);
+        let (answer, citations) = answer_source
+            .map(|source| {
+                let response = &mut responses[source];
+                (
+                    response.answer.take(),
+                    std::mem::take(&mut response.citations),
+                )
+            })
+            .unwrap_or_default();
+        Ok(UnionAskResponse {
+            question,
+            answer,
+            answer_source,
+            citations,
+            context: union_context(&hits),
+            hits,
+            elapsed_ms: start.elapsed().as_millis(),
+        })
+    }
+
+    /// Reciprocal-rank fusion of one hit list per member; ties go to the earlier member.
+    fn fuse(&self, lists: Vec<Vec<SearchHit>>, top_k: usize) -> Vec<UnionSearchHit> {
+        let mut scored: Vec<(f32, UnionSearchHit)> = lists
+            .into_iter()
+            .enumerate()
+            .flat_map(|(source, hits)| {
+                hits.into_iter()
+                    .enumerate()
+                    .map(move |(rank, hit)| (1.0 / (RRF_K + (rank + 1) as f32), source, hit))
+            })
+            .map(|(score, source, hit)| {
+                let path = self.paths[source].clone();
+                (score, UnionSearchHit { source, path, hit })
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.source.cmp(&b.1.source)));
+        scored
+            .into_iter()
+            .take(top_k)
+            .enumerate()
+            .map(|(rank, (_, mut item))| {
+                item.hit.rank = rank + 1;
+                item
+            })
+            .collect()
+    }
+}
+
+fn union_context(hits: &[UnionSearchHit]) -> String {
+    let hits: Vec<SearchHit> = hits.iter().map(|item| item.hit.clone()).collect();
+    build_context(&hits)
+}
+
+#[cfg(all(test, feature = "lex"))]
+mod tests {
+    use super::*;
+    use crate::types::PutOptions;
+
+    fn memory(dir: &Path, name: &str, docs: &[(&str, &str)]) -> PathBuf {
+        let path = dir.join(name);
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        for (uri, text) in docs {
+            let options = PutOptions {
+                uri: Some((*uri).into()),
+                ..Default::default()
+            };
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+        path
+    }
+
+    #[test]
+    fn search_and_timeline_merge_members_with_provenance() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let alpha = memory(
+            dir.path(),
+            "alpha.mv2",
+            &[("mv2://alpha/deploy", "deploy notes for the billing service")],
+        );
+        let beta = memory(
+            dir.path(),
+            "beta.mv2",
+            &[
+                (
+                    "mv2://beta/deploy",
+                    "deploy checklist for the search cluster",
+                ),
+                ("mv2://beta/other", "unrelated gardening tips"),
+            ],
+        );
+        let before = [&alpha, &beta].map(|path| std::fs::read(path).expect("read"));
+        let mut union = MemvidUnion::open([&alpha, &beta]).expect("open union");
+
+        let request = SearchRequest {
+            query: "deploy".into(),
+            top_k: 10,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: crate::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        };
+        let response = union.search(request).expect("search");
+        let mut found: Vec<(usize, String)> = response
+            .hits
+            .iter()
+            .map(|item| (item.source, item.hit.uri.clone()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                (0, "mv2://alpha/deploy".to_string()),
+                (1, "mv2://beta/deploy".to_string()),
+            ]
+        );
+        assert_eq!(response.hits[0].hit.rank, 1);
+        assert_eq!(
+            response.hits[0].path,
+            union.paths()[response.hits[0].source]
+        );
+
+        let timeline = union.timeline(TimelineQuery::default()).expect("timeline");
+        assert_eq!(timeline.len(), 3);
+        assert!(
+            timeline
+                .windows(2)
+                .all(|pair| pair[0].entry.timestamp <= pair[1].entry.timestamp)
+        );
+        assert_eq!(union.members()[1].frame_count(), 2);
+
+        drop(union);
+        let after = [&alpha, &beta].map(|path| std::fs::read(path).expect("read"));
+        assert!(before == after, "members are left byte-for-byte unchanged");
+    }
+}
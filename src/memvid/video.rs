@@ -0,0 +1,394 @@
+//! Video ingestion for `Memvid`.
+//!
+//! A video lands as one media frame holding the raw bytes and the full transcript, one child
+//! frame per sampled keyframe, and one child frame per transcript segment, all in the same WAL
+//! batch. Keyframe embeddings go into the CLIP index under the keyframe's frame id, so a visual
+//! hit points at a frame that knows its offset into the video.
+
+use crate::clip::ClipEmbeddingProvider;
+use crate::error::{MemvidError, Result};
+use crate::memvid::lifecycle::Memvid;
+use crate::types::metadata::seconds_to_ms;
+use crate::types::video::VideoKeyframe;
+use crate::types::{
+    AUDIO_START_MS_KEY, DocMetadata, MediaManifest, PutOptions, VIDEO_FRAME_KIND,
+    VIDEO_KEYFRAME_FRAME_KIND, VIDEO_OFFSET_MS_KEY, VideoReceipt,
+};
+use crate::whisper::TranscriptionResult;
+
+impl Memvid {
+    /// Decode, embed, transcribe, and store a video.
+    ///
+    /// Uses [`crate::video::FfmpegVideoDecoder`], the default CLIP model, and the default
+    /// Whisper model, sampling a keyframe every
+    /// [`crate::video::DEFAULT_KEYFRAME_INTERVAL_MS`]. CLIP must be enabled on the memory.
+    #[cfg(all(feature = "clip", feature = "whisper"))]
+    pub fn put_video(&mut self, bytes: &[u8], options: PutOptions) -> Result<VideoReceipt> {
+        let decoder = crate::video::FfmpegVideoDecoder::default();
+        let clip = crate::clip::ClipModel::default_model()?;
+        let mut transcriber =
+            crate::whisper::WhisperTranscriber::new(&crate::whisper::WhisperConfig::default())?;
+        self.put_video_with(
+            bytes,
+            options,
+            &decoder,
+            &clip,
+            Some(&mut transcriber),
+            crate::video::DEFAULT_KEYFRAME_INTERVAL_MS,
+        )
+    }
+
+    /// [`Memvid::put_video`] with caller-provided models. Pass `None` as `transcriber` to skip
+    /// the audio track.
+    #[cfg(feature = "whisper")]
+    pub fn put_video_with(
+        &mut self,
+        bytes: &[u8],
+        options: PutOptions,
+        decoder: &dyn crate::types::VideoDecoder,
+        clip: &dyn ClipEmbeddingProvider,
+        transcriber: Option<&mut crate::whisper::WhisperTranscriber>,
+        keyframe_interval_ms: u64,
+    ) -> Result<VideoReceipt> {
+        self.ensure_mutation_allowed()?;
+        if !self.clip_enabled {
+            return Err(MemvidError::ClipNotEnabled);
+        }
+        let keyframes = decoder.keyframes(bytes, keyframe_interval_ms)?;
+        let transcription = match transcriber {
+            Some(transcriber) => match decoder.audio(bytes)? {
+                Some(audio) => Some(transcriber.transcribe_pcm(&audio.pcm, audio.duration_secs)?),
+                None => None,
+            },
+            None => None,
+        };
+        self.put_decoded_video(bytes, &keyframes, transcription.as_ref(), clip, options)
+    }
+
+    /// Store a video whose keyframes and transcript were extracted by the caller.
+    ///
+    /// `options` describe the video frame; `options.timestamp` is the recording start and
+    /// defaults to now. Each keyframe becomes a child frame carrying [`VIDEO_OFFSET_MS_KEY`] and
+    /// the transcript spoken until the next keyframe, and its CLIP embedding is added to the
+    /// index. Transcript segments are written as in [`Memvid::put_transcribed_audio`], linked to
+    /// their keyframe. Call `commit` to persist.
+    pub fn put_decoded_video(
+        &mut self,
+        bytes: &[u8],
+        keyframes: &[VideoKeyframe],
+        transcription: Option<&TranscriptionResult>,
+        clip: &dyn ClipEmbeddingProvider,
+        options: PutOptions,
+    ) -> Result<VideoReceipt> {
+        self.ensure_mutation_allowed()?;
+        if !self.clip_enabled {
+            return Err(MemvidError::ClipNotEnabled);
+        }
+        if bytes.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: "video payload is empty".into(),
+            });
+        }
+        let embeddings = keyframes
+            .iter()
+            .map(|keyframe| clip.embed_image_bytes(&keyframe.image))
+            .collect::<Result<Vec<_>>>()?;
+
+        let recorded_at = options.timestamp.unwrap_or_else(super::helpers::unix_now);
+        let last_keyframe_ms = keyframes.last().map_or(0, |keyframe| keyframe.offset_ms);
+        let duration_ms = transcription
+            .map_or(0, |t| seconds_to_ms(t.duration_secs))
+            .max(last_keyframe_ms);
+
+        let mut metadata = options.metadata.clone().unwrap_or_default();
+        let mime = metadata
+            .mime
+            .clone()
+            .unwrap_or_else(|| sniff_video(bytes).to_string());
+        metadata.mime = Some(mime.clone());
+        metadata.bytes = Some(bytes.len() as u64);
+        metadata.media = Some(MediaManifest {
+            kind: VIDEO_FRAME_KIND.to_string(),
+            mime,
+            bytes: bytes.len() as u64,
+            filename: options
+                .uri
+                .as_deref()
+                .and_then(|uri| uri.rsplit('/').next())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+            duration_ms: Some(duration_ms),
+            ..MediaManifest::default()
+        });
+
+        let mut parent_options = options.clone();
+        parent_options.timestamp = Some(recorded_at);
+        parent_options.kind = Some(VIDEO_FRAME_KIND.to_string());
+        parent_options.metadata = Some(metadata);
+        parent_options.search_text = Some(
+            transcription
+                .map(|t| t.text.clone())
+                .filter(|text| !text.trim().is_empty())
+                .unwrap_or_else(|| options.title.clone().unwrap_or_default()),
+        );
+        let parent_sequence =
+            self.put_internal(Some(bytes), None, None, None, parent_options, None, None)?;
+
+        let keyframes_ms: Vec<u64> = keyframes
+            .iter()
+            .map(|keyframe| keyframe.offset_ms)
+            .collect();
+        let mut keyframe_sequences = Vec::with_capacity(keyframes.len());
+        for (index, (keyframe, embedding)) in keyframes.iter().zip(embeddings).enumerate() {
+            let until_ms = keyframes_ms.get(index + 1).copied().unwrap_or(u64::MAX);
+            let spoken = transcription
+                .map(|t| {
+                    t.segments
+                        .iter()
+                        .filter(|segment| {
+                            let start = seconds_to_ms(segment.start);
+                            start >= keyframe.offset_ms && start < until_ms
+                        })
+                        .map(|segment| segment.text.trim())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            let mut keyframe_options = PutOptions {
+                timestamp: Some(
+                    recorded_at
+                        .saturating_add(i64::try_from(keyframe.offset_ms / 1000).unwrap_or(0)),
+                ),
+                track: options.track.clone(),
+                kind: Some(VIDEO_KEYFRAME_FRAME_KIND.to_string()),
+                uri: options
+                    .uri
+                    .as_ref()
+                    .map(|uri| format!("{uri}#keyframe={}", keyframe.offset_ms)),
+                title: options.title.clone(),
+                metadata: Some(DocMetadata {
+                    mime: Some(keyframe.mime.clone()),
+                    bytes: Some(keyframe.image.len() as u64),
+                    ..DocMetadata::default()
+                }),
+                search_text: (!spoken.is_empty()).then_some(spoken),
+                tags: options.tags.clone(),
+                labels: options.labels.clone(),
+                extra_metadata: options.extra_metadata.clone(),
+                enable_embedding: options.enable_embedding,
+                auto_tag: false,
+                extract_dates: options.extract_dates,
+                instant_index: options.instant_index,
+                ..PutOptions::default()
+            };
+            keyframe_options.extra_metadata.insert(
+                VIDEO_OFFSET_MS_KEY.to_string(),
+                keyframe.offset_ms.to_string(),
+            );
+            let frame_id = self.next_frame_id();
+            keyframe_sequences.push(self.put_internal(
+                Some(&keyframe.image),
+                None,
+                None,
+                None,
+                keyframe_options,
+                None,
+                Some(parent_sequence),
+            )?);
+            self.add_clip_embedding(frame_id, embedding)?;
+        }
+
+        let segment_sequences = match transcription {
+            Some(transcription) => self.put_transcript_segments(
+                transcription,
+                parent_sequence,
+                recorded_at,
+                &options,
+                &keyframes_ms,
+            )?,
+            None => Vec::new(),
+        };
+
+        Ok(VideoReceipt {
+            parent_sequence,
+            keyframe_sequences,
+            segment_sequences,
+            duration_ms,
+        })
+    }
+
+    /// Offset into the parent recording of a keyframe or transcript segment frame, in
+    /// milliseconds. `None` for frames that are not part of an audio or video recording.
+    pub fn media_offset_ms(&self, frame_id: u64) -> Result<Option<u64>> {
+        let frame = self.frame_by_id(frame_id)?;
+        Ok([VIDEO_OFFSET_MS_KEY, AUDIO_START_MS_KEY]
+            .iter()
+            .find_map(|key| frame.extra_metadata.get(*key))
+            .and_then(|value| value.parse().ok()))
+    }
+}
+
+/// MIME type from the container's magic bytes.
+fn sniff_video(bytes: &[u8]) -> &'static str {
+    match bytes {
+        [_, _, _, _, b'f', b't', b'y', b'p', b'q', b't', ..] => "video/quicktime",
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => "video/mp4",
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => "video/webm",
+        [
+            b'R',
+            b'I',
+            b'F',
+            b'F',
+            _,
+            _,
+            _,
+            _,
+            b'A',
+            b'V',
+            b'I',
+            b' ',
+            ..,
+        ] => "video/x-msvideo",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::types::VIDEO_KEYFRAME_MS_KEY;
+    use crate::whisper::TranscriptionSegment;
+
+    /// Embeds each image as a one-hot vector keyed by its first payload byte.
+    struct ByteClip;
+
+    impl ClipEmbeddingProvider for ByteClip {
+        fn kind(&self) -> &'static str {
+            "byte"
+        }
+
+        fn model(&self) -> &'static str {
+            "byte"
+        }
+
+        fn dimension(&self) -> usize {
+            4
+        }
+
+        fn embed_image_file(&self, path: &Path) -> Result<Vec<f32>> {
+            self.embed_image_bytes(&std::fs::read(path)?)
+        }
+
+        fn embed_image_bytes(&self, bytes: &[u8]) -> Result<Vec<f32>> {
+            let mut embedding = vec![0.0; 4];
+            embedding[usize::from(bytes[2]) % 4] = 1.0;
+            Ok(embedding)
+        }
+
+        fn embed_query(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![0.0, 1.0, 0.0, 0.0])
+        }
+    }
+
+    #[test]
+    fn keyframe_clip_hits_resolve_to_offsets() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("video.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        let keyframes: Vec<VideoKeyframe> = (0u8..3)
+            .map(|index| VideoKeyframe {
+                offset_ms: u64::from(index) * 5_000,
+                image: vec![0xFF, 0xD8, index, 0xFF, 0xD9],
+                mime: "image/jpeg".into(),
+            })
+            .collect();
+        let transcription = TranscriptionResult {
+            text: "intro. now the whiteboard diagram.".into(),
+            language: "en".into(),
+            duration_secs: 14.0,
+            segments: vec![
+                TranscriptionSegment {
+                    start: 0.5,
+                    end: 3.0,
+                    text: " intro.".into(),
+                    speaker: None,
+                },
+                TranscriptionSegment {
+                    start: 6.0,
+                    end: 9.0,
+                    text: " now the whiteboard diagram.".into(),
+                    speaker: None,
+                },
+            ],
+        };
+        let options = PutOptions {
+            uri: Some("mv2://video/talk.mp4".into()),
+            timestamp: Some(1_700_000_000),
+            ..Default::default()
+        };
+        assert!(matches!(
+            mem.put_decoded_video(
+                b"....ftypisom",
+                &keyframes,
+                None,
+                &ByteClip,
+                options.clone()
+            ),
+            Err(MemvidError::ClipNotEnabled)
+        ));
+
+        mem.enable_clip().expect("clip");
+        let receipt = mem
+            .put_decoded_video(
+                b"....ftypisom",
+                &keyframes,
+                Some(&transcription),
+                &ByteClip,
+                options,
+            )
+            .expect("put");
+        mem.commit().expect("commit");
+        assert_eq!(receipt.keyframe_sequences.len(), 3);
+        assert_eq!(receipt.segment_sequences.len(), 2);
+        assert_eq!(receipt.duration_ms, 14_000);
+
+        let query = ByteClip.embed_query("whiteboard diagram").expect("query");
+        let hits = mem.search_clip(&query, 1).expect("search");
+        let keyframe = mem.frame_by_id(hits[0].frame_id).expect("keyframe");
+        assert_eq!(keyframe.kind.as_deref(), Some(VIDEO_KEYFRAME_FRAME_KIND));
+        assert_eq!(keyframe.timestamp, 1_700_000_005);
+        assert_eq!(
+            mem.media_offset_ms(keyframe.id).expect("offset"),
+            Some(5_000)
+        );
+        assert!(
+            keyframe
+                .search_text
+                .as_deref()
+                .is_some_and(|text| text.contains("whiteboard diagram"))
+        );
+
+        let segment = mem
+            .frame_by_uri("mv2://video/talk.mp4#t=6000")
+            .expect("segment");
+        assert_eq!(
+            segment
+                .extra_metadata
+                .get(VIDEO_KEYFRAME_MS_KEY)
+                .map(String::as_str),
+            Some("5000")
+        );
+        assert_eq!(
+            mem.media_offset_ms(segment.id).expect("offset"),
+            Some(6_000)
+        );
+        let manifest = mem
+            .media_manifest_by_uri("mv2://video/talk.mp4")
+            .expect("lookup")
+            .expect("manifest");
+        assert_eq!(manifest.mime, "video/mp4");
+    }
+}
@@ -406,6 +406,46 @@ fn build_vec_artifact(
             };
             Ok(Some(SegmentArtifact { artifact, stats }))
         }
+        VectorCompression::Int8 | VectorCompression::Binary => {
+            let Some(mut builder) =
+                crate::vec_scalar::ScalarEncoding::from_compression(&effective_compression)
+                    .map(crate::vec_scalar::ScalarQuantizedVecIndexBuilder::new)
+            else {
+                return Ok(None);
+            };
+            for chunk in &plan.chunks {
+                let Some(embedding) = chunk.embedding.as_ref() else {
+                    continue;
+                };
+                if embedding.is_empty() {
+                    continue;
+                }
+                builder.add_document(chunk.frame_id, embedding)?;
+            }
+
+            let artifact = builder.finish()?;
+            if artifact.vector_count == 0 {
+                return Ok(None);
+            }
+
+            let artifact = VecSegmentArtifact {
+                bytes: artifact.bytes,
+                vector_count: artifact.vector_count,
+                dimension: artifact.dimension,
+                checksum: artifact.checksum,
+                compression: effective_compression,
+                #[cfg(feature = "parallel_segments")]
+                bytes_uncompressed: artifact.bytes_uncompressed,
+            };
+            let stats = SegmentStats {
+                doc_count: 0,
+                vector_count: artifact.vector_count,
+                time_entries: 0,
+                bytes_uncompressed: artifact.bytes_uncompressed,
+                build_micros: start.elapsed().as_micros() as u64,
+            };
+            Ok(Some(SegmentArtifact { artifact, stats }))
+        }
     }
 }
 
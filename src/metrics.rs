@@ -0,0 +1,195 @@
+//! Process-wide metrics facade.
+//!
+//! memvid reports counters and durations for ingestion, extraction, commits, and searches
+//! through the [`Metrics`] recorder installed with [`set_metrics`]. Nothing is recorded until
+//! a recorder is installed, so hosts that do not care pay only for a lock check. Implement the
+//! trait to forward values to Prometheus, statsd, or any other backend; `tracing` spans keep
+//! carrying the same timings for logs.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use memvid_core::metrics::{self, Label, Metrics};
+//!
+//! struct Statsd(statsd::Client);
+//!
+//! impl Metrics for Statsd {
+//!     fn duration(&self, name: &'static str, elapsed: Duration, _labels: &[Label<'_>]) {
+//!         self.0.timer(name, elapsed.as_secs_f64() * 1000.0);
+//!     }
+//! }
+//!
+//! metrics::set_metrics(Arc::new(Statsd(client)));
+//! ```
+
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Duration of one put, from validation to the WAL append. Labels: none.
+pub const PUT_DURATION: &str = "memvid.put.duration";
+/// Payload bytes accepted by puts. Labels: none.
+pub const PUT_BYTES: &str = "memvid.put.bytes";
+/// Text extraction time. Labels: `reader` (`budgeted` for time-budgeted instant indexing).
+pub const EXTRACT_DURATION: &str = "memvid.extract.duration";
+/// Extractions that finished with warnings or fell back to the default reader. Labels: `reader`.
+pub const EXTRACT_WARNINGS: &str = "memvid.extract.warnings";
+/// Duration of a whole commit, including the staging copy. Labels: none.
+pub const COMMIT_DURATION: &str = "memvid.commit.duration";
+/// Duration of one commit phase. Labels: `phase` (`apply`, `indexes`, `persist`, `sync`).
+pub const COMMIT_PHASE_DURATION: &str = "memvid.commit.phase.duration";
+/// Frames inserted by commits. Labels: none.
+pub const COMMIT_FRAMES: &str = "memvid.commit.frames";
+/// Duration of a whole `search`. Labels: `engine`.
+pub const SEARCH_DURATION: &str = "memvid.search.duration";
+/// Duration of a whole `search_many` batch. Labels: none.
+pub const SEARCH_BATCH_DURATION: &str = "memvid.search.batch.duration";
+/// Distinct requests searched by `search_many` batches. Labels: none.
+pub const SEARCH_BATCH_REQUESTS: &str = "memvid.search.batch.requests";
+/// Duration of one search stage. Labels: `stage` (`filters`, `sketch`, `retrieve`, `post`).
+pub const SEARCH_STAGE_DURATION: &str = "memvid.search.stage.duration";
+/// Frames scanned by the sketch pre-filter. Labels: none.
+pub const SKETCH_FRAMES_SCANNED: &str = "memvid.sketch.frames_scanned";
+
+/// A `(key, value)` dimension attached to a measurement.
+pub type Label<'a> = (&'static str, &'a str);
+
+/// Receiver of memvid's measurements. Both methods default to doing nothing, so recorders
+/// only implement what their backend supports. Calls happen on the thread doing the work and
+/// should return quickly.
+pub trait Metrics: Send + Sync {
+    /// Add `value` to the counter `name`.
+    fn counter(&self, _name: &'static str, _value: u64, _labels: &[Label<'_>]) {}
+
+    /// Record one observation of the histogram `name`.
+    fn duration(&self, _name: &'static str, _elapsed: Duration, _labels: &[Label<'_>]) {}
+}
+
+static RECORDER: RwLock<Option<Arc<dyn Metrics>>> = RwLock::new(None);
+
+/// Install `recorder` for the whole process, returning the one it replaces.
+pub fn set_metrics(recorder: Arc<dyn Metrics>) -> Option<Arc<dyn Metrics>> {
+    RECORDER
+        .write()
+        .map_or(None, |mut slot| slot.replace(recorder))
+}
+
+/// Remove the installed recorder, returning it.
+pub fn clear_metrics() -> Option<Arc<dyn Metrics>> {
+    RECORDER.write().map_or(None, |mut slot| slot.take())
+}
+
+fn with_recorder(record: impl FnOnce(&dyn Metrics)) {
+    if let Ok(slot) = RECORDER.read() {
+        if let Some(recorder) = slot.as_deref() {
+            record(recorder);
+        }
+    }
+}
+
+pub(crate) fn counter(name: &'static str, value: u64, labels: &[Label<'_>]) {
+    with_recorder(|recorder| recorder.counter(name, value, labels));
+}
+
+pub(crate) fn duration(name: &'static str, elapsed: Duration, labels: &[Label<'_>]) {
+    with_recorder(|recorder| recorder.duration(name, elapsed, labels));
+}
+
+/// Record the time since `start` and restart it, for timing consecutive phases.
+pub(crate) fn lap(name: &'static str, start: &mut Instant, labels: &[Label<'_>]) {
+    let now = Instant::now();
+    duration(name, now.duration_since(*start), labels);
+    *start = now;
+}
+
+#[cfg(all(test, feature = "lex"))]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::Memvid;
+    use crate::types::{AclEnforcementMode, PutOptions, SearchMode, SearchRequest};
+
+    #[derive(Default)]
+    struct Names(Mutex<BTreeSet<String>>);
+
+    impl Metrics for Names {
+        fn counter(&self, name: &'static str, _value: u64, _labels: &[Label<'_>]) {
+            self.0.lock().unwrap().insert(name.to_string());
+        }
+
+        fn duration(&self, name: &'static str, _elapsed: Duration, labels: &[Label<'_>]) {
+            let mut names = self.0.lock().unwrap();
+            names.insert(name.to_string());
+            for (key, value) in labels {
+                names.insert(format!("{name}{{{key}={value}}}"));
+            }
+        }
+    }
+
+    #[test]
+    fn recorder_receives_put_commit_and_search_timings() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("metrics.mv2");
+        let recorder = Arc::new(Names::default());
+        set_metrics(recorder.clone());
+
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.enable_lex().expect("lex");
+        mem.put_bytes_with_options(
+            b"quarterly metrics review",
+            PutOptions::builder().uri("mv2://metrics").build(),
+        )
+        .expect("put");
+        mem.commit().expect("commit");
+        let request = SearchRequest {
+            query: "quarterly".into(),
+            top_k: 5,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        };
+        mem.search(request.clone()).expect("search");
+        mem.search_many(vec![request.clone(), request])
+            .expect("search_many");
+        clear_metrics();
+
+        let names = recorder.0.lock().unwrap();
+        for expected in [
+            PUT_DURATION,
+            PUT_BYTES,
+            EXTRACT_DURATION,
+            COMMIT_DURATION,
+            "memvid.commit.phase.duration{phase=apply}",
+            "memvid.commit.phase.duration{phase=sync}",
+            SEARCH_DURATION,
+            "memvid.search.stage.duration{stage=retrieve}",
+            SEARCH_BATCH_DURATION,
+            SEARCH_BATCH_REQUESTS,
+        ] {
+            assert!(names.contains(expected), "missing {expected}");
+        }
+    }
+}
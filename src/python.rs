@@ -0,0 +1,337 @@
+//! Python bindings (feature `python`).
+//!
+//! Exposes `Memvid` to Python as `memvid_core.Memvid` with create/open/put/search/ask/timeline.
+//! Build the extension module with maturin:
+//!
+//! ```text
+//! maturin develop --features python,pyo3/extension-module
+//! ```
+//!
+//! Payloads passed as `bytes` are borrowed without copying, and every call that touches the
+//! file (put, commit, search, ask, timeline) runs with the GIL released so notebook threads and
+//! other Python work keep running. Structured results (search hits, answers, timeline entries,
+//! stats) are returned as plain `dict`/`list` values built from the same serde representation
+//! the JSON APIs use.
+
+use std::num::NonZeroU64;
+use std::sync::{Mutex, MutexGuard};
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+
+use crate::error::MemvidError;
+use crate::memvid::Memvid;
+use crate::types::{
+    AclEnforcementMode, AskMode, AskRequest, FrameId, PutOptions, SearchMode, SearchRequest,
+    TimelineQuery, VecEmbedder,
+};
+
+create_exception!(memvid_core, MemvidPyError, PyException);
+
+fn to_py_err(err: MemvidError) -> PyErr {
+    MemvidPyError::new_err(err.to_string())
+}
+
+/// Convert a serde value into native Python objects via the `json` module.
+fn to_py_object<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let encoded =
+        serde_json::to_string(value).map_err(|err| MemvidPyError::new_err(err.to_string()))?;
+    let json = py.import("json")?;
+    Ok(json.call_method1("loads", (encoded,))?.unbind())
+}
+
+fn parse_ask_mode(mode: &str) -> PyResult<AskMode> {
+    match mode {
+        "lex" => Ok(AskMode::Lex),
+        "sem" => Ok(AskMode::Sem),
+        "hybrid" => Ok(AskMode::Hybrid),
+        "extractive" => Ok(AskMode::Extractive),
+        other => Err(MemvidPyError::new_err(format!(
+            "unknown ask mode `{other}` (expected lex, sem, hybrid, or extractive)"
+        ))),
+    }
+}
+
+/// Python handle over a single `.mv2` file.
+#[pyclass(name = "Memvid", module = "memvid_core")]
+pub struct PyMemvid {
+    inner: Mutex<Memvid>,
+}
+
+impl PyMemvid {
+    fn wrap(memvid: Memvid) -> Self {
+        Self {
+            inner: Mutex::new(memvid),
+        }
+    }
+
+    fn lock(&self) -> PyResult<MutexGuard<'_, Memvid>> {
+        self.inner
+            .lock()
+            .map_err(|_| MemvidPyError::new_err("memvid handle mutex poisoned"))
+    }
+
+    /// Run `op` against the handle with the GIL released.
+    ///
+    /// The mutex is taken inside `allow_threads`, never while holding the GIL: otherwise a
+    /// second Python thread can take the GIL and block on the mutex while the first waits to
+    /// reacquire the GIL, and neither makes progress.
+    fn with_handle<R, F>(&self, py: Python<'_>, op: F) -> PyResult<R>
+    where
+        F: FnOnce(&mut Memvid) -> crate::Result<R> + Send,
+        R: Send,
+    {
+        py.allow_threads(|| {
+            let mut guard = self.lock()?;
+            op(&mut guard).map_err(to_py_err)
+        })
+    }
+}
+
+#[pymethods]
+impl PyMemvid {
+    #[staticmethod]
+    fn create(py: Python<'_>, path: std::path::PathBuf) -> PyResult<Self> {
+        py.allow_threads(|| Memvid::create(&path))
+            .map(Self::wrap)
+            .map_err(to_py_err)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (path, read_only = false))]
+    fn open(py: Python<'_>, path: std::path::PathBuf, read_only: bool) -> PyResult<Self> {
+        py.allow_threads(|| {
+            if read_only {
+                Memvid::open_read_only(&path)
+            } else {
+                Memvid::open(&path)
+            }
+        })
+        .map(Self::wrap)
+        .map_err(to_py_err)
+    }
+
+    /// Enable the lexical index so `search` can be used.
+    fn enable_lex(&self, py: Python<'_>) -> PyResult<()> {
+        self.with_handle(py, Memvid::enable_lex)
+    }
+
+    /// Append a payload (`bytes` or `str`) and return its WAL sequence number.
+    ///
+    /// Call `commit()` to make it durable and searchable.
+    #[pyo3(signature = (data, *, title = None, uri = None, track = None, kind = None, timestamp = None, tags = None, labels = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn put(
+        &self,
+        py: Python<'_>,
+        data: &Bound<'_, PyAny>,
+        title: Option<String>,
+        uri: Option<String>,
+        track: Option<String>,
+        kind: Option<String>,
+        timestamp: Option<i64>,
+        tags: Option<Vec<String>>,
+        labels: Option<Vec<String>>,
+    ) -> PyResult<u64> {
+        let options = PutOptions {
+            title,
+            uri,
+            track,
+            kind,
+            timestamp,
+            tags: tags.unwrap_or_default(),
+            labels: labels.unwrap_or_default(),
+            ..PutOptions::default()
+        };
+        let payload: &[u8] = if let Ok(bytes) = data.downcast::<PyBytes>() {
+            // `bytes` is immutable, so borrowing the buffer across the GIL release is sound.
+            bytes.as_bytes()
+        } else if let Ok(text) = data.downcast::<PyString>() {
+            text.to_str()?.as_bytes()
+        } else {
+            return Err(MemvidPyError::new_err("put() expects bytes or str"));
+        };
+        self.with_handle(py, |mem| mem.put_bytes_with_options(payload, options))
+    }
+
+    /// Persist pending frames. Releases the GIL for the duration of the commit.
+    fn commit(&self, py: Python<'_>) -> PyResult<()> {
+        self.with_handle(py, Memvid::commit)
+    }
+
+    #[pyo3(signature = (query, top_k = 8, snippet_chars = 240, uri = None, scope = None))]
+    fn search(
+        &self,
+        py: Python<'_>,
+        query: String,
+        top_k: usize,
+        snippet_chars: usize,
+        uri: Option<String>,
+        scope: Option<String>,
+    ) -> PyResult<PyObject> {
+        let request = SearchRequest {
+            query,
+            top_k,
+            snippet_chars,
+            uri,
+            scope,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: SearchMode::Lexical,
+            time_budget_ms: None,
+        };
+        let response = self.with_handle(py, |mem| mem.search(request))?;
+        to_py_object(py, &response)
+    }
+
+    #[pyo3(signature = (question, top_k = 8, snippet_chars = 240, mode = "hybrid", context_only = false, start = None, end = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn ask(
+        &self,
+        py: Python<'_>,
+        question: String,
+        top_k: usize,
+        snippet_chars: usize,
+        mode: &str,
+        context_only: bool,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> PyResult<PyObject> {
+        let request = AskRequest {
+            question,
+            top_k,
+            snippet_chars,
+            uri: None,
+            scope: None,
+            cursor: None,
+            start,
+            end,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            context_only,
+            mode: parse_ask_mode(mode)?,
+            as_of_frame: None,
+            as_of_ts: None,
+            adaptive: None,
+            acl_context: None,
+            acl_enforcement_mode: AclEnforcementMode::Audit,
+        };
+        let response = self.with_handle(py, |mem| mem.ask(request, None::<&dyn VecEmbedder>))?;
+        to_py_object(py, &response)
+    }
+
+    #[pyo3(signature = (limit = None, since = None, until = None, reverse = false))]
+    fn timeline(
+        &self,
+        py: Python<'_>,
+        limit: Option<u64>,
+        since: Option<i64>,
+        until: Option<i64>,
+        reverse: bool,
+    ) -> PyResult<PyObject> {
+        let mut builder = TimelineQuery::builder().reverse(reverse);
+        if let Some(limit) = limit.and_then(NonZeroU64::new) {
+            builder = builder.limit(limit);
+        }
+        if let Some(since) = since {
+            builder = builder.since(since);
+        }
+        if let Some(until) = until {
+            builder = builder.until(until);
+        }
+        let query = builder.build();
+        let entries = self.with_handle(py, |mem| mem.timeline(query))?;
+        to_py_object(py, &entries)
+    }
+
+    /// Canonical payload bytes for a frame.
+    fn frame_bytes(&self, py: Python<'_>, frame_id: FrameId) -> PyResult<Py<PyBytes>> {
+        let payload = self.with_handle(py, |mem| mem.frame_canonical_payload(frame_id))?;
+        Ok(PyBytes::new(py, &payload).unbind())
+    }
+
+    /// Searchable text for a frame.
+    fn frame_text(&self, py: Python<'_>, frame_id: FrameId) -> PyResult<String> {
+        self.with_handle(py, |mem| mem.frame_text_by_id(frame_id))
+    }
+
+    fn stats(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let stats = self.with_handle(py, |mem| mem.stats())?;
+        to_py_object(py, &stats)
+    }
+
+    fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+        let stats = self.with_handle(py, |mem| mem.stats())?;
+        usize::try_from(stats.frame_count).map_err(|err| MemvidPyError::new_err(err.to_string()))
+    }
+}
+
+/// `memvid_core` Python module entry point.
+#[pymodule]
+fn memvid_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyMemvid>()?;
+    m.add("MemvidError", m.py().get_type::<MemvidPyError>())?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_python_threads_do_not_deadlock() {
+        pyo3::prepare_freethreaded_python();
+        let dir = tempfile::tempdir().expect("tmp");
+        let path = dir.path().join("py.mv2");
+        let handle = Python::with_gil(|py| -> PyResult<Py<PyMemvid>> {
+            let memvid = PyMemvid::create(py, path)?;
+            memvid.enable_lex(py)?;
+            Py::new(py, memvid)
+        })
+        .expect("create");
+
+        let workers: Vec<_> = (0..2)
+            .map(|worker| {
+                let handle = Python::with_gil(|py| handle.clone_ref(py));
+                std::thread::spawn(move || {
+                    for round in 0..20 {
+                        Python::with_gil(|py| -> PyResult<()> {
+                            let memvid = handle.borrow(py);
+                            let text = PyString::new(py, &format!("worker {worker} round {round}"));
+                            memvid.put(py, &text, None, None, None, None, None, None, None)?;
+                            memvid.commit(py)?;
+                            memvid.search(py, "round".to_string(), 4, 80, None, None)?;
+                            Ok(())
+                        })
+                        .expect("python call");
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().expect("worker thread");
+        }
+
+        let frames = Python::with_gil(|py| handle.borrow(py).__len__(py)).expect("len");
+        assert_eq!(frames, 40);
+    }
+}
@@ -0,0 +1,455 @@
+use std::io::{Cursor, Read};
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::{Date, Month, PrimitiveDateTime, Time};
+use zip::ZipArchive;
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, MemvidError, ReaderDiagnostics, ReaderHint,
+    ReaderOutput, Result,
+};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZIP_MAGIC: [u8; 4] = [0x50, 0x4B, 0x03, 0x04];
+const EMPTY_ZIP_MAGIC: [u8; 4] = [0x50, 0x4B, 0x05, 0x06];
+/// Offset and value of the magic in a POSIX tar header.
+const TAR_MAGIC_OFFSET: usize = 257;
+const TAR_MAGIC: &[u8; 5] = b"ustar";
+
+/// Container formats understood by [`ArchiveReader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from the leading bytes of a payload.
+    #[must_use]
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&ZIP_MAGIC) || bytes.starts_with(&EMPTY_ZIP_MAGIC) {
+            Some(Self::Zip)
+        } else if bytes.starts_with(&GZIP_MAGIC) {
+            Some(Self::TarGz)
+        } else if bytes
+            .get(TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + TAR_MAGIC.len())
+            .is_some_and(|magic| magic == TAR_MAGIC)
+        {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Tar => "tar",
+            Self::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// Limits applied while expanding an archive, guarding against archive bombs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveOptions {
+    /// Members read before the rest are skipped.
+    pub max_members: usize,
+    /// Members larger than this, uncompressed, are skipped.
+    pub max_member_bytes: u64,
+    /// Uncompressed bytes read across all members before the rest are skipped.
+    pub max_total_bytes: u64,
+    /// Also read members whose path has a component starting with `.`, and `__MACOSX`
+    /// resource forks.
+    pub include_hidden: bool,
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        Self {
+            max_members: 10_000,
+            max_member_bytes: 64 * 1024 * 1024,
+            max_total_bytes: 1024 * 1024 * 1024,
+            include_hidden: false,
+        }
+    }
+}
+
+/// A regular file read from an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveMember {
+    /// Path inside the archive, with `/` separators.
+    pub path: String,
+    /// Last modification time, in Unix seconds, when the archive records one.
+    pub modified: Option<i64>,
+    pub bytes: Vec<u8>,
+}
+
+/// A member that was not read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveSkip {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Files read from an archive, in archive order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveContents {
+    pub format: ArchiveFormat,
+    pub members: Vec<ArchiveMember>,
+    pub skipped: Vec<ArchiveSkip>,
+}
+
+impl ArchiveContents {
+    /// One line per member with its size, followed by skipped members and why.
+    #[must_use]
+    pub fn listing(&self) -> String {
+        let mut lines = vec![format!(
+            "{} archive with {} files",
+            self.format.label(),
+            self.members.len()
+        )];
+        lines.extend(
+            self.members
+                .iter()
+                .map(|member| format!("{} ({} bytes)", member.path, member.bytes.len())),
+        );
+        lines.extend(
+            self.skipped
+                .iter()
+                .map(|skip| format!("{} (skipped: {})", skip.path, skip.reason)),
+        );
+        lines.join("\n")
+    }
+}
+
+/// Reader for zip, tar, and gzip-compressed tar archives.
+///
+/// Through the registry an archive becomes its member listing; `Memvid::put_archive` expands
+/// the members into child frames instead. Directories, links, and unsafe paths are left out.
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveReader {
+    options: ArchiveOptions,
+}
+
+impl ArchiveReader {
+    #[must_use]
+    pub fn with_options(options: ArchiveOptions) -> Self {
+        Self { options }
+    }
+
+    /// Read the regular files of an archive within the configured limits.
+    ///
+    /// # Errors
+    /// Fails if the payload is not a supported archive or cannot be decoded.
+    pub fn read(&self, bytes: &[u8]) -> Result<ArchiveContents> {
+        let format = ArchiveFormat::detect(bytes).ok_or_else(|| MemvidError::ExtractionFailed {
+            reason: "payload is not a zip or tar archive".into(),
+        })?;
+        let mut contents = ArchiveContents {
+            format,
+            members: Vec::new(),
+            skipped: Vec::new(),
+        };
+        let mut budget = Budget::new(&self.options);
+        match format {
+            ArchiveFormat::Zip => self.read_zip(bytes, &mut budget, &mut contents)?,
+            ArchiveFormat::Tar => self.read_tar(bytes, &mut budget, &mut contents)?,
+            ArchiveFormat::TarGz => {
+                self.read_tar(GzDecoder::new(bytes), &mut budget, &mut contents)?;
+            }
+        }
+        Ok(contents)
+    }
+
+    fn read_zip(
+        &self,
+        bytes: &[u8],
+        budget: &mut Budget,
+        contents: &mut ArchiveContents,
+    ) -> Result<()> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(archive_error)?;
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).map_err(archive_error)?;
+            if file.is_dir() || file.is_symlink() {
+                continue;
+            }
+            let name = file.name().to_string();
+            let Some(path) = file.enclosed_name().and_then(|path| normalize_path(&path)) else {
+                contents.skipped.push(skip(&name, "unsafe path"));
+                continue;
+            };
+            if !self.wanted(&path) {
+                continue;
+            }
+            if let Err(reason) = budget.admit(file.size()) {
+                contents.skipped.push(skip(&path, reason));
+                continue;
+            }
+            let modified = file.last_modified().and_then(|stamp| {
+                let date = Date::from_calendar_date(
+                    i32::from(stamp.year()),
+                    Month::try_from(stamp.month()).ok()?,
+                    stamp.day(),
+                )
+                .ok()?;
+                let time = Time::from_hms(stamp.hour(), stamp.minute(), stamp.second()).ok()?;
+                Some(
+                    PrimitiveDateTime::new(date, time)
+                        .assume_utc()
+                        .unix_timestamp(),
+                )
+            });
+            let bytes = read_limited(&mut file, budget.member_limit)?;
+            budget.consume(&bytes);
+            contents.members.push(ArchiveMember {
+                path,
+                modified,
+                bytes,
+            });
+        }
+        Ok(())
+    }
+
+    fn read_tar<R: Read>(
+        &self,
+        reader: R,
+        budget: &mut Budget,
+        contents: &mut ArchiveContents,
+    ) -> Result<()> {
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+            let Some(path) = entry.path().ok().and_then(|path| normalize_path(&path)) else {
+                contents.skipped.push(skip(&name, "unsafe path"));
+                continue;
+            };
+            if !self.wanted(&path) {
+                continue;
+            }
+            if let Err(reason) = budget.admit(entry.size()) {
+                contents.skipped.push(skip(&path, reason));
+                continue;
+            }
+            let modified = entry
+                .header()
+                .mtime()
+                .ok()
+                .and_then(|mtime| i64::try_from(mtime).ok());
+            let bytes = read_limited(&mut entry, budget.member_limit)?;
+            budget.consume(&bytes);
+            contents.members.push(ArchiveMember {
+                path,
+                modified,
+                bytes,
+            });
+        }
+        Ok(())
+    }
+
+    fn wanted(&self, path: &str) -> bool {
+        self.options.include_hidden
+            || !path
+                .split('/')
+                .any(|part| part.starts_with('.') || part == "__MACOSX")
+    }
+}
+
+impl DocumentReader for ArchiveReader {
+    fn name(&self) -> &'static str {
+        "archive"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Archive))
+    }
+
+    fn extract(&self, bytes: &[u8], _hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        let contents = self.read(bytes)?;
+        let metadata = json!({
+            "archive_format": contents.format.label(),
+            "members": contents.members.len(),
+            "skipped": contents.skipped.len(),
+        });
+        let mut diagnostics = ReaderDiagnostics::default();
+        for skipped in &contents.skipped {
+            diagnostics.record_warning(format!("{}: {}", skipped.path, skipped.reason));
+        }
+        let document = ExtractedDocument {
+            text: Some(contents.listing()),
+            metadata: metadata.clone(),
+            mime_type: Some(archive_mime(contents.format).to_string()),
+        };
+        Ok(ReaderOutput::new(document, self.name())
+            .with_diagnostics(diagnostics.with_metadata(metadata)))
+    }
+}
+
+/// Remaining member and byte allowance while reading one archive.
+struct Budget {
+    members_left: usize,
+    bytes_left: u64,
+    member_limit: u64,
+}
+
+impl Budget {
+    fn new(options: &ArchiveOptions) -> Self {
+        Self {
+            members_left: options.max_members,
+            bytes_left: options.max_total_bytes,
+            member_limit: options.max_member_bytes,
+        }
+    }
+
+    /// Check a member of `declared` uncompressed bytes against the limits.
+    fn admit(&mut self, declared: u64) -> std::result::Result<(), &'static str> {
+        if self.members_left == 0 {
+            return Err("member limit reached");
+        }
+        if declared > self.member_limit {
+            return Err("member too large");
+        }
+        if declared > self.bytes_left {
+            return Err("archive size limit reached");
+        }
+        self.members_left -= 1;
+        Ok(())
+    }
+
+    fn consume(&mut self, bytes: &[u8]) {
+        self.bytes_left = self.bytes_left.saturating_sub(bytes.len() as u64);
+    }
+}
+
+/// Read at most `limit` bytes, failing if the member holds more than it declared.
+fn read_limited<R: Read>(reader: &mut R, limit: u64) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        return Err(MemvidError::ExtractionFailed {
+            reason: "archive member exceeds its declared size".into(),
+        });
+    }
+    Ok(bytes)
+}
+
+/// Relative path with `/` separators, or `None` if it escapes the archive root.
+fn normalize_path(path: &std::path::Path) -> Option<String> {
+    use std::path::Component;
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?.to_string()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn skip(path: &str, reason: &str) -> ArchiveSkip {
+    ArchiveSkip {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn archive_error(err: zip::result::ZipError) -> MemvidError {
+    MemvidError::ExtractionFailed {
+        reason: format!("failed to read zip archive: {err}").into(),
+    }
+}
+
+fn archive_mime(format: ArchiveFormat) -> &'static str {
+    match format {
+        ArchiveFormat::Zip => "application/zip",
+        ArchiveFormat::Tar => "application/x-tar",
+        ArchiveFormat::TarGz => "application/gzip",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn zip_bytes(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, text) in files {
+            writer
+                .start_file(*path, zip::write::SimpleFileOptions::default())
+                .expect("start file");
+            writer.write_all(text.as_bytes()).expect("write");
+        }
+        writer.finish().expect("finish").into_inner()
+    }
+
+    fn tar_gz_bytes(files: &[(&str, &str)]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, text) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(text.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(1_709_294_400);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, text.as_bytes())
+                .expect("append");
+        }
+        builder.into_inner().expect("tar").finish().expect("gzip")
+    }
+
+    #[test]
+    fn reads_zip_members_and_skips_hidden() {
+        let bytes = zip_bytes(&[
+            ("project/README.md", "Project notes"),
+            ("project/.env", "SECRET=1"),
+            ("__MACOSX/project/._README.md", "fork"),
+        ]);
+        let contents = ArchiveReader::default().read(&bytes).expect("read");
+
+        assert_eq!(contents.format, ArchiveFormat::Zip);
+        assert_eq!(contents.members.len(), 1);
+        assert_eq!(contents.members[0].path, "project/README.md");
+        assert_eq!(contents.members[0].bytes, b"Project notes");
+        assert!(contents.listing().contains("project/README.md (13 bytes)"));
+    }
+
+    #[test]
+    fn reads_tar_gz_within_limits() {
+        let bytes = tar_gz_bytes(&[
+            ("a.txt", "alpha"),
+            ("big.txt", "0123456789"),
+            ("c.txt", "c"),
+        ]);
+        let reader = ArchiveReader::with_options(ArchiveOptions {
+            max_member_bytes: 8,
+            ..ArchiveOptions::default()
+        });
+        let contents = reader.read(&bytes).expect("read");
+
+        assert_eq!(contents.format, ArchiveFormat::TarGz);
+        let paths: Vec<&str> = contents.members.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, ["a.txt", "c.txt"]);
+        assert_eq!(contents.members[0].modified, Some(1_709_294_400));
+        assert_eq!(contents.skipped, [skip("big.txt", "member too large")]);
+    }
+
+    #[test]
+    fn rejects_non_archives() {
+        assert!(ArchiveReader::default().read(b"plain text").is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::{
+    DocumentFormat, DocumentReader, ExtractedDocument, ReaderDiagnostics, ReaderHint, ReaderOutput,
+    Result,
+};
+
+/// Numeric timestamps above this are taken as milliseconds rather than seconds.
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Which fields of a JSONL record fill which frame fields.
+///
+/// Field names may be dotted paths into nested objects (`"user.name"`). A field that is
+/// missing or of the wrong type leaves its frame field unset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonlFieldMapping {
+    pub title: Option<String>,
+    pub uri: Option<String>,
+    /// Unix seconds, Unix milliseconds, or an RFC 3339 string.
+    pub timestamp: Option<String>,
+    /// An array of strings or a comma-separated string.
+    pub tags: Option<String>,
+    pub search_text: Option<String>,
+    /// Fields rendered into the frame payload, in order. Empty means every top-level field not
+    /// mapped above.
+    pub payload: Vec<String>,
+}
+
+impl Default for JsonlFieldMapping {
+    fn default() -> Self {
+        Self {
+            title: Some("title".to_string()),
+            uri: Some("uri".to_string()),
+            timestamp: Some("timestamp".to_string()),
+            tags: Some("tags".to_string()),
+            search_text: None,
+            payload: Vec::new(),
+        }
+    }
+}
+
+/// One line of a JSONL document, mapped to frame fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonlRecord {
+    /// One-based line number.
+    pub line: usize,
+    pub title: Option<String>,
+    pub uri: Option<String>,
+    pub timestamp: Option<i64>,
+    pub tags: Vec<String>,
+    pub search_text: Option<String>,
+    /// Payload fields as `field: value` lines, or the bare value when a single string field
+    /// is mapped.
+    pub text: String,
+}
+
+/// A line that was skipped, such as one that is not a JSON object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonlLineError {
+    /// One-based line number.
+    pub line: usize,
+    pub error: String,
+}
+
+/// Records parsed from a JSONL document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonlParse {
+    pub records: Vec<JsonlRecord>,
+    pub errors: Vec<JsonlLineError>,
+}
+
+/// Reader for newline-delimited JSON. Blank lines are skipped.
+///
+/// Through the registry the whole document becomes one text with a paragraph per record;
+/// `Memvid::put_jsonl` writes one frame per record instead.
+#[derive(Debug, Clone, Default)]
+pub struct JsonlReader {
+    mapping: JsonlFieldMapping,
+}
+
+impl JsonlReader {
+    #[must_use]
+    pub fn with_mapping(mapping: JsonlFieldMapping) -> Self {
+        Self { mapping }
+    }
+
+    #[must_use]
+    pub fn mapping(&self) -> &JsonlFieldMapping {
+        &self.mapping
+    }
+
+    /// Parse every line of `bytes`, collecting malformed lines instead of failing.
+    #[must_use]
+    pub fn parse(&self, bytes: &[u8]) -> JsonlParse {
+        let text = String::from_utf8_lossy(bytes);
+        let mut parsed = JsonlParse::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let record = match serde_json::from_str::<Value>(line) {
+                Ok(Value::Object(object)) => self.map_record(index + 1, &object),
+                Ok(_) => {
+                    parsed.errors.push(JsonlLineError {
+                        line: index + 1,
+                        error: "line is not a JSON object".to_string(),
+                    });
+                    continue;
+                }
+                Err(err) => {
+                    parsed.errors.push(JsonlLineError {
+                        line: index + 1,
+                        error: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+            parsed.records.push(record);
+        }
+        parsed
+    }
+
+    fn map_record(&self, line: usize, object: &Map<String, Value>) -> JsonlRecord {
+        let mapping = &self.mapping;
+        let string_field = |field: &Option<String>| {
+            field
+                .as_deref()
+                .and_then(|path| lookup(object, path))
+                .and_then(scalar_text)
+                .filter(|text| !text.trim().is_empty())
+        };
+
+        let payload_fields: Vec<&str> = if mapping.payload.is_empty() {
+            let mapped: Vec<&str> = [
+                &mapping.title,
+                &mapping.uri,
+                &mapping.timestamp,
+                &mapping.tags,
+                &mapping.search_text,
+            ]
+            .into_iter()
+            .filter_map(Option::as_deref)
+            .collect();
+            object
+                .keys()
+                .map(String::as_str)
+                .filter(|key| !mapped.contains(key))
+                .collect()
+        } else {
+            mapping.payload.iter().map(String::as_str).collect()
+        };
+
+        JsonlRecord {
+            line,
+            title: string_field(&mapping.title),
+            uri: string_field(&mapping.uri),
+            timestamp: mapping
+                .timestamp
+                .as_deref()
+                .and_then(|path| lookup(object, path))
+                .and_then(parse_timestamp),
+            tags: mapping
+                .tags
+                .as_deref()
+                .and_then(|path| lookup(object, path))
+                .map(parse_tags)
+                .unwrap_or_default(),
+            search_text: string_field(&mapping.search_text),
+            text: render_payload(object, &payload_fields),
+        }
+    }
+}
+
+impl DocumentReader for JsonlReader {
+    fn name(&self) -> &'static str {
+        "jsonl"
+    }
+
+    fn supports(&self, hint: &ReaderHint<'_>) -> bool {
+        matches!(hint.format, Some(DocumentFormat::Jsonl))
+    }
+
+    fn extract(&self, bytes: &[u8], _hint: &ReaderHint<'_>) -> Result<ReaderOutput> {
+        let parsed = self.parse(bytes);
+        let text = parsed
+            .records
+            .iter()
+            .map(|record| match &record.title {
+                Some(title) => format!("{title}\n{}", record.text),
+                None => record.text.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let metadata = json!({
+            "records": parsed.records.len(),
+            "invalid_lines": parsed.errors.len(),
+        });
+        let mut diagnostics = ReaderDiagnostics::default();
+        for error in &parsed.errors {
+            diagnostics.record_warning(format!("line {}: {}", error.line, error.error));
+        }
+        let document = ExtractedDocument {
+            text: (!text.is_empty()).then_some(text),
+            metadata: metadata.clone(),
+            mime_type: Some("application/x-ndjson".to_string()),
+        };
+        Ok(ReaderOutput::new(document, self.name())
+            .with_diagnostics(diagnostics.with_metadata(metadata)))
+    }
+}
+
+fn lookup<'a>(object: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    if let Some(value) = object.get(path) {
+        return Some(value);
+    }
+    let mut parts = path.split('.');
+    let mut value = object.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_object()?.get(part)?;
+    }
+    Some(value)
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_timestamp(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => {
+            #[allow(clippy::cast_possible_truncation)]
+            let raw = number
+                .as_i64()
+                .or_else(|| number.as_f64().map(|float| float as i64))?;
+            Some(if raw.abs() >= MILLIS_THRESHOLD {
+                raw / 1000
+            } else {
+                raw
+            })
+        }
+        Value::String(text) => {
+            let text = text.trim();
+            OffsetDateTime::parse(text, &Rfc3339)
+                .ok()
+                .map(OffsetDateTime::unix_timestamp)
+                .or_else(|| parse_timestamp(&Value::from(text.parse::<i64>().ok()?)))
+        }
+        _ => None,
+    }
+}
+
+fn parse_tags(value: &Value) -> Vec<String> {
+    let tags: Vec<String> = match value {
+        Value::Array(items) => items.iter().filter_map(scalar_text).collect(),
+        Value::String(text) => text.split(',').map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+    tags.into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+fn render_payload(object: &Map<String, Value>, fields: &[&str]) -> String {
+    if let [field] = fields {
+        if let Some(Value::String(text)) = lookup(object, field) {
+            return text.clone();
+        }
+    }
+    fields
+        .iter()
+        .filter_map(|field| {
+            let value = lookup(object, field)?;
+            let rendered = match value {
+                Value::Null => return None,
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            Some(format!("{field}: {rendered}"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_fields_and_collects_bad_lines() {
+        let input = concat!(
+            r#"{"title":"Deploy","timestamp":"2024-03-01T12:00:00Z","tags":["ops","prod"],"service":"api","status":200}"#,
+            "\n\n",
+            "not json\n",
+            r#"{"title":"Page","timestamp":1709294400000,"tags":"oncall, sev2","message":"disk full"}"#,
+            "\n[1,2]\n",
+        );
+        let parsed = JsonlReader::default().parse(input.as_bytes());
+
+        assert_eq!(parsed.records.len(), 2);
+        assert_eq!(
+            parsed
+                .errors
+                .iter()
+                .map(|error| error.line)
+                .collect::<Vec<_>>(),
+            [3, 5]
+        );
+        let first = &parsed.records[0];
+        assert_eq!(first.line, 1);
+        assert_eq!(first.title.as_deref(), Some("Deploy"));
+        assert_eq!(first.timestamp, Some(1_709_294_400));
+        assert_eq!(first.tags, ["ops", "prod"]);
+        assert_eq!(first.text, "service: api\nstatus: 200");
+        let second = &parsed.records[1];
+        assert_eq!(second.timestamp, Some(1_709_294_400));
+        assert_eq!(second.tags, ["oncall", "sev2"]);
+        assert_eq!(second.text, "disk full");
+    }
+
+    #[test]
+    fn custom_mapping_reads_nested_fields() {
+        let reader = JsonlReader::with_mapping(JsonlFieldMapping {
+            title: Some("user.name".to_string()),
+            timestamp: Some("ts".to_string()),
+            payload: vec!["content".to_string()],
+            ..JsonlFieldMapping::default()
+        });
+        let input = r#"{"user":{"name":"ada"},"ts":1709294400,"content":"ship it","role":"user"}"#;
+        let parsed = reader.parse(input.as_bytes());
+
+        let record = &parsed.records[0];
+        assert_eq!(record.title.as_deref(), Some("ada"));
+        assert_eq!(record.timestamp, Some(1_709_294_400));
+        assert_eq!(record.text, "ship it");
+    }
+}
@@ -1,8 +1,11 @@
 //! Document reader traits and registry for unified format ingestion.
 
+mod archive;
 mod docx;
+mod jsonl;
 mod passthrough;
 mod pdf;
+mod pdf_spans;
 mod pptx;
 mod xls;
 mod xlsx;
@@ -12,9 +15,14 @@ pub mod xlsx_table_detect;
 
 use serde_json::Value;
 
+pub use archive::{
+    ArchiveContents, ArchiveFormat, ArchiveMember, ArchiveOptions, ArchiveReader, ArchiveSkip,
+};
 pub use docx::DocxReader;
+pub use jsonl::{JsonlFieldMapping, JsonlLineError, JsonlParse, JsonlReader, JsonlRecord};
 pub use passthrough::PassthroughReader;
 pub use pdf::PdfReader;
+pub(crate) use pdf_spans::locate_pdf_texts;
 pub use pptx::PptxReader;
 pub use xls::XlsReader;
 pub use xlsx::{XlsxReader, XlsxStructuredDiagnostics, XlsxStructuredResult};
@@ -35,6 +43,7 @@ pub enum DocumentFormat {
     Markdown,
     Html,
     Jsonl,
+    Archive,
     Unknown,
 }
 
@@ -51,6 +60,7 @@ impl DocumentFormat {
             Self::Markdown => "markdown",
             Self::Html => "html",
             Self::Jsonl => "jsonl",
+            Self::Archive => "archive",
             Self::Unknown => "unknown",
         }
     }
@@ -214,6 +224,8 @@ impl Default for ReaderRegistry {
         registry.register(XlsxReader);
         registry.register(XlsReader);
         registry.register(PptxReader);
+        registry.register(JsonlReader::default());
+        registry.register(ArchiveReader::default());
         registry.register(PassthroughReader);
         registry
     }
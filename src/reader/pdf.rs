@@ -37,7 +37,7 @@ impl PdfReader {
         mime.is_some_and(|m| m.eq_ignore_ascii_case("application/pdf"))
     }
 
-    fn supports_magic(magic: Option<&[u8]>) -> bool {
+    pub(super) fn supports_magic(magic: Option<&[u8]>) -> bool {
         let mut slice = match magic {
             Some(slice) if !slice.is_empty() => slice,
             _ => return false,
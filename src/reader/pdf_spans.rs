@@ -0,0 +1,260 @@
+//! Locating extracted PDF text on its source pages.
+//!
+//! Chunk text comes from whichever extractor handled the PDF, which may space, hyphenate,
+//! or order words differently from the page's own text runs. Texts are therefore matched
+//! against the runs on letters and digits alone, in order, resynchronising past text the
+//! runs do not contain. Each matched character takes its run's page and, with Pdfium, its
+//! run's bounding box; consecutive characters on one line become one [`PdfTextSpan`].
+
+use crate::error::Result;
+use crate::types::{PageRect, PdfTextSpan};
+
+/// Documents with more pages are not located.
+const MAX_PAGES: usize = 4_096;
+/// How far past the cursor a mismatch looks for its continuation, in characters.
+const RESYNC_WINDOW: usize = 2_048;
+/// Characters that must agree before the cursor jumps.
+const RESYNC_LEN: usize = 8;
+/// Shortest tail of a text that may still resynchronise.
+const RESYNC_MIN: usize = 4;
+
+/// A run of page text, with its box when the reader knows it.
+struct PageRun {
+    page: u32,
+    text: String,
+    rect: Option<PageRect>,
+}
+
+/// Locate each of `texts`, in document order, in the PDF `bytes`. Returns one span list per
+/// text, or `None` when the bytes are not a readable PDF.
+pub(crate) fn locate_pdf_texts(bytes: &[u8], texts: &[&str]) -> Option<Vec<Vec<PdfTextSpan>>> {
+    if !super::PdfReader::supports_magic(Some(bytes)) {
+        return None;
+    }
+    match page_runs(bytes) {
+        Ok(runs) => Some(locate_in_runs(&runs, texts)),
+        Err(err) => {
+            tracing::debug!(target: "memvid::pdf", error = %err, "pdf page location skipped");
+            None
+        }
+    }
+}
+
+#[cfg(feature = "pdfium")]
+fn page_runs(bytes: &[u8]) -> Result<Vec<PageRun>> {
+    let layouts = crate::table::extract_pdf_layout(bytes, MAX_PAGES)?;
+    Ok(layouts
+        .into_iter()
+        .flat_map(|layout| layout.text_boxes)
+        .map(|text_box| PageRun {
+            page: text_box.page,
+            rect: Some(PageRect {
+                x: text_box.x,
+                y: text_box.y,
+                width: text_box.width,
+                height: text_box.height,
+            }),
+            text: text_box.text,
+        })
+        .collect())
+}
+
+/// Without Pdfium only page text is known; lopdf's layout boxes are estimates, so no
+/// rects are recorded.
+#[cfg(not(feature = "pdfium"))]
+fn page_runs(bytes: &[u8]) -> Result<Vec<PageRun>> {
+    let mut document =
+        lopdf::Document::load_mem(bytes).map_err(|err| crate::MemvidError::ExtractionFailed {
+            reason: format!("failed to load pdf: {err}").into(),
+        })?;
+    if document.is_encrypted() && document.decrypt("").is_err() {
+        return Err(crate::MemvidError::ExtractionFailed {
+            reason: "cannot decrypt password-protected pdf".into(),
+        });
+    }
+    let pages: Vec<u32> = document.get_pages().keys().copied().collect();
+    if pages.len() > MAX_PAGES {
+        return Err(crate::MemvidError::ExtractionFailed {
+            reason: format!("pdf has more than {MAX_PAGES} pages").into(),
+        });
+    }
+    Ok(pages
+        .into_iter()
+        .filter_map(|page| {
+            let text = document.extract_text(&[page]).ok()?;
+            Some(PageRun {
+                page,
+                text,
+                rect: None,
+            })
+        })
+        .collect())
+}
+
+/// A letter or digit of page text, lowercased, and the run it belongs to.
+struct PageChar {
+    ch: char,
+    run: usize,
+}
+
+fn folded(ch: char) -> Option<char> {
+    ch.is_alphanumeric()
+        .then(|| ch.to_lowercase().next().unwrap_or(ch))
+}
+
+fn locate_in_runs(runs: &[PageRun], texts: &[&str]) -> Vec<Vec<PdfTextSpan>> {
+    let stream: Vec<PageChar> = runs
+        .iter()
+        .enumerate()
+        .flat_map(|(run, page_run)| {
+            page_run
+                .text
+                .chars()
+                .filter_map(folded)
+                .map(move |ch| PageChar { ch, run })
+        })
+        .collect();
+
+    let mut cursor = 0;
+    texts
+        .iter()
+        .map(|text| {
+            // (byte offset, byte length, folded char)
+            let chars: Vec<(usize, usize, char)> = text
+                .char_indices()
+                .filter_map(|(offset, ch)| Some((offset, ch.len_utf8(), folded(ch)?)))
+                .collect();
+            let mut spans: Vec<PdfTextSpan> = Vec::new();
+            for (index, &(offset, len, ch)) in chars.iter().enumerate() {
+                if stream
+                    .get(cursor)
+                    .is_none_or(|page_char| page_char.ch != ch)
+                {
+                    let needle: Vec<char> = chars[index..]
+                        .iter()
+                        .take(RESYNC_LEN)
+                        .map(|&(_, _, ch)| ch)
+                        .collect();
+                    match resync(&stream, cursor, &needle) {
+                        Some(at) => cursor = at,
+                        None => continue,
+                    }
+                }
+                extend(&mut spans, offset, offset + len, &runs[stream[cursor].run]);
+                cursor += 1;
+            }
+            spans
+        })
+        .collect()
+}
+
+/// Where `needle` next occurs in `stream` within the window after `cursor`.
+fn resync(stream: &[PageChar], cursor: usize, needle: &[char]) -> Option<usize> {
+    if needle.len() < RESYNC_MIN {
+        return None;
+    }
+    let end = stream.len().min(cursor + RESYNC_WINDOW);
+    (cursor..end).find(|&at| {
+        needle.iter().enumerate().all(|(k, ch)| {
+            stream
+                .get(at + k)
+                .is_some_and(|page_char| page_char.ch == *ch)
+        })
+    })
+}
+
+fn extend(spans: &mut Vec<PdfTextSpan>, start: usize, end: usize, run: &PageRun) {
+    if let Some(last) = spans.last_mut() {
+        if last.page == run.page {
+            match (last.rect, run.rect) {
+                (None, None) => {
+                    last.end = end;
+                    return;
+                }
+                (Some(line), Some(rect)) if line.same_line(rect) => {
+                    last.end = end;
+                    last.rect = Some(line.union(rect));
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+    spans.push(PdfTextSpan {
+        start,
+        end,
+        page: run.page,
+        rect: run.rect,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(page: u32, text: &str, y: f32) -> PageRun {
+        PageRun {
+            page,
+            text: text.to_string(),
+            rect: Some(PageRect {
+                x: 72.0,
+                y,
+                width: 300.0,
+                height: 12.0,
+            }),
+        }
+    }
+
+    #[test]
+    fn chunks_map_to_lines_despite_spacing_and_hyphenation() {
+        let runs = [
+            run(1, "Quarterly revenue grew", 700.0),
+            run(1, "by twelve per-", 686.0),
+            run(2, "cent over the prior year.", 700.0),
+            run(2, "Footer text", 40.0),
+        ];
+        let first = "Quarterly  revenue grew by twelve";
+        let second = "percent over the prior year. Unmatched aside. Footer text";
+        let located = locate_in_runs(&runs, &[first, second]);
+
+        let pages: Vec<(u32, usize, usize)> = located[0]
+            .iter()
+            .map(|span| (span.page, span.start, span.end))
+            .collect();
+        assert_eq!(pages, [(1, 0, 23), (1, 24, 33)]);
+        assert_eq!(&first[24..33], "by twelve");
+
+        let second_spans = &located[1];
+        assert_eq!(second_spans[0].page, 1, "\"per\" ends page 1");
+        assert_eq!(
+            &second[second_spans[1].start..second_spans[1].end],
+            "cent over the prior year"
+        );
+        let footer = second_spans.last().expect("footer");
+        assert_eq!(&second[footer.start..footer.end], "Footer text");
+        assert_eq!(footer.rect.map(|rect| rect.y), Some(40.0));
+    }
+
+    #[test]
+    fn runs_without_boxes_give_one_span_per_page() {
+        let runs = [
+            PageRun {
+                page: 1,
+                text: "Alpha beta gamma".to_string(),
+                rect: None,
+            },
+            PageRun {
+                page: 2,
+                text: "delta epsilon".to_string(),
+                rect: None,
+            },
+        ];
+        let text = "Alpha beta gamma delta epsilon";
+        let located = locate_in_runs(&runs, &[text]);
+        let spans: Vec<(u32, &str)> = located[0]
+            .iter()
+            .map(|span| (span.page, &text[span.start..span.end]))
+            .collect();
+        assert_eq!(spans, [(1, "Alpha beta gamma"), (2, "delta epsilon")]);
+    }
+}
@@ -33,7 +33,7 @@ pub struct XlsxReader;
 
 impl XlsxReader {
     /// Build `SheetGrid`s from raw XLSX bytes using calamine.
-    fn build_grids(bytes: &[u8]) -> Result<Vec<SheetGrid>> {
+    pub(crate) fn build_grids(bytes: &[u8]) -> Result<Vec<SheetGrid>> {
         let cursor = Cursor::new(bytes);
         let mut workbook =
             Xlsx::new(cursor).map_err(|err| crate::MemvidError::ExtractionFailed {
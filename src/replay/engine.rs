@@ -238,6 +238,18 @@ impl<'a> ReplayEngine<'a> {
                             no_sketch: false,
                             acl_context: None,
                             acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                            rerank: None,
+                            geo: None,
+                            filters: Vec::new(),
+                            access_boost: false,
+                            highlight_windows: 0,
+                            language: None,
+                            diversify: None,
+                            group_by_parent: false,
+                            return_parents: false,
+                            explain: false,
+                            mode: crate::types::SearchMode::Lexical,
+                            time_budget_ms: None,
                         };
                         match self.mem.search(search_request) {
                             Ok(response) => {
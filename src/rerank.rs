@@ -0,0 +1,647 @@
+//! [`Reranker`](crate::Reranker) implementations for second-stage search ranking.
+//!
+//! - [`CrossEncoderReranker`] (`vec` feature) scores query/passage pairs with a MiniLM-style
+//!   cross-encoder exported to ONNX, loaded from the models directory like the NER and CLIP
+//!   models.
+//! - [`ApiReranker`] (`api_embed` feature) calls a hosted `/rerank` endpoint (Cohere, Jina, or
+//!   any server speaking the same request shape).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use memvid_core::rerank::{CrossEncoderConfig, CrossEncoderReranker};
+//! use memvid_core::RerankerConfig;
+//!
+//! mem.set_reranker(Arc::new(CrossEncoderReranker::new(CrossEncoderConfig::default())?));
+//! request.rerank = Some(RerankerConfig::default());
+//! let response = mem.search(request)?;
+//! ```
+
+#[cfg(feature = "api_embed")]
+pub use api::{ApiReranker, ApiRerankerConfig};
+#[cfg(feature = "vec")]
+pub use cross_encoder::{
+    CROSS_ENCODER_MODELS, CrossEncoderConfig, CrossEncoderModelInfo, CrossEncoderReranker,
+    cross_encoder_model_path, cross_encoder_tokenizer_path, default_cross_encoder_model_info,
+    get_cross_encoder_model_info,
+};
+
+use crate::types::reranker::{RerankerDocument, RerankerResult};
+
+/// Rank `documents` by `scores` (same order), highest first, keeping `top_k`.
+fn rank_by_score(
+    documents: &[RerankerDocument],
+    scores: &[f32],
+    top_k: usize,
+) -> Vec<RerankerResult> {
+    let mut results: Vec<RerankerResult> = documents
+        .iter()
+        .zip(scores)
+        .enumerate()
+        .map(|(index, (document, score))| RerankerResult {
+            id: document.id,
+            score: *score,
+            original_rank: index + 1,
+            new_rank: 0,
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(top_k);
+    for (index, result) in results.iter_mut().enumerate() {
+        result.new_rank = index + 1;
+    }
+    results
+}
+
+/// Passage text sent to a reranker, prefixed by the document's metadata when present.
+fn passage(document: &RerankerDocument) -> String {
+    match &document.metadata {
+        Some(metadata) => format!("{metadata}\n{}", document.text),
+        None => document.text.clone(),
+    }
+}
+
+#[cfg(feature = "vec")]
+mod cross_encoder {
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+
+    use ndarray::Array;
+    use ort::session::Session;
+    use ort::value::Tensor;
+    use tokenizers::{Tokenizer, TruncationDirection, TruncationParams, TruncationStrategy};
+
+    use super::{passage, rank_by_score};
+    use crate::error::{MemvidError, Result};
+    use crate::inference_device::{InferenceDevice, build_session};
+    use crate::types::reranker::{Reranker, RerankerDocument, RerankerResult};
+
+    /// Cross-encoder model info for the models registry
+    #[derive(Debug, Clone)]
+    pub struct CrossEncoderModelInfo {
+        /// Model identifier, also the directory name under the models directory
+        pub name: &'static str,
+        /// URL for ONNX model
+        pub model_url: &'static str,
+        /// URL for tokenizer JSON
+        pub tokenizer_url: &'static str,
+        /// Model size in MB
+        pub size_mb: f32,
+        /// Maximum sequence length of a query/passage pair
+        pub max_seq_len: usize,
+        /// Whether this is the default model
+        pub is_default: bool,
+    }
+
+    /// Available cross-encoder models
+    pub static CROSS_ENCODER_MODELS: &[CrossEncoderModelInfo] = &[
+        CrossEncoderModelInfo {
+            name: "ms-marco-MiniLM-L-6-v2",
+            model_url: "https://huggingface.co/cross-encoder/ms-marco-MiniLM-L-6-v2/resolve/main/onnx/model.onnx",
+            tokenizer_url: "https://huggingface.co/cross-encoder/ms-marco-MiniLM-L-6-v2/resolve/main/tokenizer.json",
+            size_mb: 91.0,
+            max_seq_len: 512,
+            is_default: true,
+        },
+        CrossEncoderModelInfo {
+            name: "ms-marco-MiniLM-L-12-v2",
+            model_url: "https://huggingface.co/cross-encoder/ms-marco-MiniLM-L-12-v2/resolve/main/onnx/model.onnx",
+            tokenizer_url: "https://huggingface.co/cross-encoder/ms-marco-MiniLM-L-12-v2/resolve/main/tokenizer.json",
+            size_mb: 133.0,
+            max_seq_len: 512,
+            is_default: false,
+        },
+        CrossEncoderModelInfo {
+            name: "bge-reranker-base",
+            model_url: "https://huggingface.co/BAAI/bge-reranker-base/resolve/main/onnx/model.onnx",
+            tokenizer_url: "https://huggingface.co/BAAI/bge-reranker-base/resolve/main/tokenizer.json",
+            size_mb: 1110.0,
+            max_seq_len: 512,
+            is_default: false,
+        },
+    ];
+
+    /// Get cross-encoder model info by name
+    #[must_use]
+    pub fn get_cross_encoder_model_info(name: &str) -> Option<&'static CrossEncoderModelInfo> {
+        CROSS_ENCODER_MODELS.iter().find(|m| m.name == name)
+    }
+
+    /// Get default cross-encoder model info
+    #[must_use]
+    pub fn default_cross_encoder_model_info() -> &'static CrossEncoderModelInfo {
+        &CROSS_ENCODER_MODELS[0]
+    }
+
+    /// Get the expected path for a cross-encoder model in the models directory
+    #[must_use]
+    pub fn cross_encoder_model_path(models_dir: &Path, name: &str) -> PathBuf {
+        models_dir.join(name).join("model.onnx")
+    }
+
+    /// Get the expected path for a cross-encoder tokenizer in the models directory
+    #[must_use]
+    pub fn cross_encoder_tokenizer_path(models_dir: &Path, name: &str) -> PathBuf {
+        models_dir.join(name).join("tokenizer.json")
+    }
+
+    /// Configuration for the local cross-encoder reranker
+    #[derive(Debug, Clone)]
+    pub struct CrossEncoderConfig {
+        /// Model name from [`CROSS_ENCODER_MODELS`]
+        pub model_name: String,
+        /// Directory holding `<model_name>/model.onnx` and `<model_name>/tokenizer.json`
+        pub models_dir: PathBuf,
+        /// Device to run the model on; falls back to CPU if unavailable (default: auto)
+        pub device: InferenceDevice,
+        /// Query/passage pairs per inference call (default: 16)
+        pub batch_size: usize,
+    }
+
+    impl Default for CrossEncoderConfig {
+        fn default() -> Self {
+            let models_dir = dirs_next::cache_dir()
+                .map(|p| p.join("memvid").join("rerank-models"))
+                .unwrap_or_else(|| PathBuf::from(".memvid-cache/rerank-models"));
+            Self {
+                model_name: default_cross_encoder_model_info().name.to_string(),
+                models_dir,
+                device: InferenceDevice::default(),
+                batch_size: 16,
+            }
+        }
+    }
+
+    /// Reranker scoring each query/passage pair jointly with a cross-encoder.
+    ///
+    /// Scores are the sigmoid of the model's relevance logit, so they fall in `0.0..=1.0`
+    /// and `RerankerConfig::min_score` is meaningful across queries.
+    pub struct CrossEncoderReranker {
+        model_info: &'static CrossEncoderModelInfo,
+        session: Mutex<Session>,
+        tokenizer: Tokenizer,
+        batch_size: usize,
+        device: InferenceDevice,
+    }
+
+    impl CrossEncoderReranker {
+        /// Load the model and tokenizer, returning download instructions if they are missing.
+        pub fn new(config: CrossEncoderConfig) -> Result<Self> {
+            let model_info = get_cross_encoder_model_info(&config.model_name).ok_or_else(|| {
+                MemvidError::RerankFailed {
+                    reason: format!(
+                        "unknown cross-encoder model '{}'; available: {}",
+                        config.model_name,
+                        CROSS_ENCODER_MODELS
+                            .iter()
+                            .map(|m| m.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                    .into(),
+                }
+            })?;
+            let model_path = cross_encoder_model_path(&config.models_dir, model_info.name);
+            let tokenizer_path = cross_encoder_tokenizer_path(&config.models_dir, model_info.name);
+            for (path, url) in [
+                (&model_path, model_info.model_url),
+                (&tokenizer_path, model_info.tokenizer_url),
+            ] {
+                if !path.exists() {
+                    return Err(MemvidError::RerankFailed {
+                        reason: format!(
+                            "Cross-encoder file not found at {}. Please download manually:\n\
+                             mkdir -p {} && curl -L '{}' -o '{}'",
+                            path.display(),
+                            path.parent().unwrap_or(&config.models_dir).display(),
+                            url,
+                            path.display()
+                        )
+                        .into(),
+                    });
+                }
+            }
+
+            let mut tokenizer =
+                Tokenizer::from_file(&tokenizer_path).map_err(|e| MemvidError::RerankFailed {
+                    reason: format!("Failed to load tokenizer: {e}").into(),
+                })?;
+            tokenizer.with_padding(None);
+            tokenizer
+                .with_truncation(Some(TruncationParams {
+                    max_length: model_info.max_seq_len,
+                    // Trim the passage, never the query
+                    strategy: TruncationStrategy::OnlySecond,
+                    stride: 0,
+                    direction: TruncationDirection::Right,
+                }))
+                .map_err(|e| MemvidError::RerankFailed {
+                    reason: format!("Failed to apply truncation config: {e}").into(),
+                })?;
+
+            let (session, device) = build_session(config.device, &model_path, 4).map_err(|e| {
+                MemvidError::RerankFailed {
+                    reason: format!("Failed to load cross-encoder model: {e}").into(),
+                }
+            })?;
+            tracing::info!(model = %model_info.name, device = %device, "Cross-encoder loaded");
+
+            Ok(Self {
+                model_info,
+                session: Mutex::new(session),
+                tokenizer,
+                batch_size: config.batch_size.max(1),
+                device,
+            })
+        }
+
+        /// Get model info
+        #[must_use]
+        pub fn model_info(&self) -> &'static CrossEncoderModelInfo {
+            self.model_info
+        }
+
+        /// Device the session is running on
+        #[must_use]
+        pub fn device(&self) -> InferenceDevice {
+            self.device
+        }
+
+        /// Relevance score of each passage for `query`, in input order
+        fn score_batch(&self, query: &str, passages: &[String]) -> Result<Vec<f32>> {
+            let pairs: Vec<(&str, &str)> = passages.iter().map(|p| (query, p.as_str())).collect();
+            let encodings = self.tokenizer.encode_batch(pairs, true).map_err(|e| {
+                MemvidError::RerankFailed {
+                    reason: format!("Tokenization failed: {e}").into(),
+                }
+            })?;
+
+            let batch_size = encodings.len();
+            let max_length = encodings
+                .iter()
+                .map(|encoding| encoding.get_ids().len())
+                .max()
+                .unwrap_or(0);
+            let mut input_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+            let mut attention_mask: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+            let mut token_type_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+            for encoding in &encodings {
+                let padding = max_length - encoding.get_ids().len();
+                input_ids.extend(encoding.get_ids().iter().map(|id| i64::from(*id)));
+                input_ids.extend(std::iter::repeat_n(0, padding));
+                attention_mask.extend(encoding.get_attention_mask().iter().map(|m| i64::from(*m)));
+                attention_mask.extend(std::iter::repeat_n(0, padding));
+                token_type_ids.extend(encoding.get_type_ids().iter().map(|t| i64::from(*t)));
+                token_type_ids.extend(std::iter::repeat_n(0, padding));
+            }
+
+            let tensor = |name: &str, values: Vec<i64>| {
+                Array::from_shape_vec((batch_size, max_length), values)
+                    .map_err(|e| e.to_string())
+                    .and_then(|array| Tensor::from_array(array).map_err(|e| e.to_string()))
+                    .map_err(|e| MemvidError::RerankFailed {
+                        reason: format!("Failed to create {name} tensor: {e}").into(),
+                    })
+            };
+            let input_ids = tensor("input_ids", input_ids)?;
+            let attention_mask = tensor("attention_mask", attention_mask)?;
+            let token_type_ids = tensor("token_type_ids", token_type_ids)?;
+
+            let mut session = self
+                .session
+                .lock()
+                .map_err(|_| MemvidError::Lock("Failed to lock session".into()))?;
+            let input_names: Vec<String> = session.inputs.iter().map(|i| i.name.clone()).collect();
+            let output_name = session
+                .outputs
+                .first()
+                .map_or_else(|| "logits".to_string(), |o| o.name.clone());
+            let outputs = if input_names.len() >= 3 {
+                session.run(ort::inputs![
+                    input_names[0].clone() => input_ids,
+                    input_names[1].clone() => attention_mask,
+                    input_names[2].clone() => token_type_ids
+                ])
+            } else {
+                // XLM-R based rerankers (bge-reranker) take no token_type_ids
+                session.run(ort::inputs![
+                    "input_ids" => input_ids,
+                    "attention_mask" => attention_mask
+                ])
+            }
+            .map_err(|e| MemvidError::RerankFailed {
+                reason: format!("Cross-encoder inference failed: {e}").into(),
+            })?;
+
+            let (shape, data) = outputs[output_name.as_str()]
+                .try_extract_tensor::<f32>()
+                .map_err(|e| MemvidError::RerankFailed {
+                    reason: format!("Failed to extract logits: {e}").into(),
+                })?;
+            // Logits are [batch, 1], or [batch, 2] for binary classification heads where the
+            // last column is the "relevant" class.
+            let labels = shape
+                .get(1)
+                .and_then(|dim| usize::try_from(*dim).ok())
+                .unwrap_or(1)
+                .max(1);
+            if data.len() != batch_size * labels {
+                return Err(MemvidError::RerankFailed {
+                    reason: format!("unexpected logits shape: {shape:?}").into(),
+                });
+            }
+            Ok(data
+                .chunks(labels)
+                .map(|row| {
+                    let logit = row[labels - 1];
+                    1.0 / (1.0 + (-logit).exp())
+                })
+                .collect())
+        }
+    }
+
+    impl Reranker for CrossEncoderReranker {
+        fn kind(&self) -> &'static str {
+            "cross-encoder"
+        }
+
+        fn rerank(
+            &self,
+            query: &str,
+            documents: &[RerankerDocument],
+            top_k: usize,
+        ) -> Result<Vec<RerankerResult>> {
+            let passages: Vec<String> = documents.iter().map(passage).collect();
+            let mut scores = Vec::with_capacity(passages.len());
+            for batch in passages.chunks(self.batch_size) {
+                scores.extend(self.score_batch(query, batch)?);
+            }
+            Ok(rank_by_score(documents, &scores, top_k))
+        }
+    }
+
+    impl std::fmt::Debug for CrossEncoderReranker {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("CrossEncoderReranker")
+                .field("model", &self.model_info.name)
+                .field("device", &self.device)
+                .finish_non_exhaustive()
+        }
+    }
+}
+
+#[cfg(feature = "api_embed")]
+mod api {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+    use reqwest::blocking::Client;
+    use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+    use serde::{Deserialize, Serialize};
+
+    use super::{passage, rank_by_score};
+    use crate::error::{MemvidError, Result};
+    use crate::types::reranker::{Reranker, RerankerDocument, RerankerResult};
+
+    /// Configuration for a hosted `/rerank` endpoint.
+    #[derive(Debug, Clone)]
+    pub struct ApiRerankerConfig {
+        /// Model name sent with every request (e.g., "rerank-v3.5")
+        pub model: String,
+        /// Base URL without the trailing `/rerank`
+        /// Default: "https://api.cohere.com/v2"
+        pub base_url: String,
+        /// Environment variable holding the API key
+        pub api_key_env: String,
+        /// Request timeout in seconds
+        pub timeout_secs: u64,
+        /// Maximum retries on rate limit (429) and server errors
+        pub max_retries: u32,
+        /// Initial backoff in milliseconds for exponential retry
+        pub initial_backoff_ms: u64,
+    }
+
+    impl Default for ApiRerankerConfig {
+        fn default() -> Self {
+            Self::cohere()
+        }
+    }
+
+    impl ApiRerankerConfig {
+        /// Cohere Rerank (`COHERE_API_KEY`)
+        #[must_use]
+        pub fn cohere() -> Self {
+            Self {
+                model: "rerank-v3.5".to_string(),
+                base_url: "https://api.cohere.com/v2".to_string(),
+                api_key_env: "COHERE_API_KEY".to_string(),
+                timeout_secs: 30,
+                max_retries: 3,
+                initial_backoff_ms: 1000,
+            }
+        }
+
+        /// Jina Reranker (`JINA_API_KEY`)
+        #[must_use]
+        pub fn jina() -> Self {
+            Self {
+                model: "jina-reranker-v2-base-multilingual".to_string(),
+                base_url: "https://api.jina.ai/v1".to_string(),
+                api_key_env: "JINA_API_KEY".to_string(),
+                ..Self::cohere()
+            }
+        }
+
+        #[must_use]
+        pub fn with_model(mut self, model: impl Into<String>) -> Self {
+            self.model = model.into();
+            self
+        }
+
+        #[must_use]
+        pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+            self.base_url = url.into();
+            self
+        }
+
+        #[must_use]
+        pub fn with_timeout(mut self, secs: u64) -> Self {
+            self.timeout_secs = secs;
+            self
+        }
+    }
+
+    #[derive(Serialize)]
+    struct RerankRequest<'a> {
+        model: &'a str,
+        query: &'a str,
+        documents: &'a [String],
+        top_n: usize,
+    }
+
+    #[derive(Deserialize)]
+    struct RerankResponse {
+        results: Vec<RerankResponseItem>,
+    }
+
+    #[derive(Deserialize)]
+    struct RerankResponseItem {
+        index: usize,
+        relevance_score: f32,
+    }
+
+    /// Reranker backed by a Cohere-style `/rerank` HTTP endpoint.
+    pub struct ApiReranker {
+        config: ApiRerankerConfig,
+        client: Client,
+        api_key: String,
+    }
+
+    impl ApiReranker {
+        /// Create a client, reading the API key from `config.api_key_env`.
+        pub fn new(config: ApiRerankerConfig) -> Result<Self> {
+            let api_key =
+                std::env::var(&config.api_key_env).map_err(|_| MemvidError::RerankFailed {
+                    reason: format!(
+                        "API key not found. Set the {} environment variable.",
+                        config.api_key_env
+                    )
+                    .into(),
+                })?;
+            if api_key.is_empty() {
+                return Err(MemvidError::RerankFailed {
+                    reason: format!("{} environment variable is empty", config.api_key_env).into(),
+                });
+            }
+            let client = Client::builder()
+                .timeout(Duration::from_secs(config.timeout_secs))
+                .build()
+                .map_err(|err| MemvidError::RerankFailed {
+                    reason: format!("Failed to create HTTP client: {err}").into(),
+                })?;
+            Ok(Self {
+                config,
+                client,
+                api_key,
+            })
+        }
+
+        fn request_headers(&self) -> Result<HeaderMap> {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", self.api_key)).map_err(|_| {
+                    MemvidError::RerankFailed {
+                        reason: "Invalid API key format".into(),
+                    }
+                })?,
+            );
+            Ok(headers)
+        }
+
+        fn request_scores(&self, query: &str, passages: &[String]) -> Result<Vec<f32>> {
+            let url = format!("{}/rerank", self.config.base_url.trim_end_matches('/'));
+            let body = RerankRequest {
+                model: &self.config.model,
+                query,
+                documents: passages,
+                top_n: passages.len(),
+            };
+            let headers = self.request_headers()?;
+
+            let mut backoff_ms = self.config.initial_backoff_ms;
+            let mut last_error = String::new();
+            for attempt in 0..=self.config.max_retries {
+                if attempt > 0 {
+                    std::thread::sleep(Duration::from_millis(backoff_ms));
+                    backoff_ms = backoff_ms.saturating_mul(2);
+                }
+                let response = match self
+                    .client
+                    .post(&url)
+                    .headers(headers.clone())
+                    .json(&body)
+                    .send()
+                {
+                    Ok(response) => response,
+                    Err(err) => {
+                        last_error = format!("request failed: {err}");
+                        continue;
+                    }
+                };
+                let status = response.status();
+                if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                    last_error = format!("server returned {status}");
+                    continue;
+                }
+                if !status.is_success() {
+                    let detail = response.text().unwrap_or_default();
+                    return Err(MemvidError::RerankFailed {
+                        reason: format!("server returned {status}: {detail}").into(),
+                    });
+                }
+                let parsed: RerankResponse =
+                    response.json().map_err(|err| MemvidError::RerankFailed {
+                        reason: format!("invalid rerank response: {err}").into(),
+                    })?;
+                // Documents the server leaves out score below anything it returned.
+                let mut scores = vec![f32::NEG_INFINITY; passages.len()];
+                for item in parsed.results {
+                    if let Some(score) = scores.get_mut(item.index) {
+                        *score = item.relevance_score;
+                    }
+                }
+                return Ok(scores);
+            }
+            Err(MemvidError::RerankFailed {
+                reason: format!(
+                    "giving up after {} attempts: {last_error}",
+                    self.config.max_retries + 1
+                )
+                .into(),
+            })
+        }
+    }
+
+    impl Reranker for ApiReranker {
+        fn kind(&self) -> &'static str {
+            "api"
+        }
+
+        fn rerank(
+            &self,
+            query: &str,
+            documents: &[RerankerDocument],
+            top_k: usize,
+        ) -> Result<Vec<RerankerResult>> {
+            if documents.is_empty() {
+                return Ok(Vec::new());
+            }
+            let passages: Vec<String> = documents.iter().map(passage).collect();
+            let scores = self.request_scores(query, &passages)?;
+            Ok(rank_by_score(documents, &scores, top_k))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_by_score_orders_and_truncates() {
+        let documents = vec![
+            RerankerDocument::new(7, "a"),
+            RerankerDocument::with_metadata(8, "b", "Title"),
+            RerankerDocument::new(9, "c"),
+        ];
+        let results = rank_by_score(&documents, &[0.2, 0.9, 0.5], 2);
+        let ids: Vec<u64> = results.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![8, 9]);
+        assert_eq!(results[0].original_rank, 2);
+        assert_eq!(results[1].new_rank, 2);
+        assert_eq!(passage(&documents[1]), "Title\nb");
+    }
+}
@@ -3,7 +3,12 @@ mod parser;
 #[cfg(feature = "lex")]
 mod tantivy;
 
-use crate::types::Frame;
+use crate::table::TABLE_HEADERS_KEY;
+use crate::types::{
+    AnalyzerConfig, CHAT_AUTHOR_KEY, CODE_SYMBOLS_KEY, CodeSymbol, Frame, MetaFilter, MetaSchema,
+    MetaValue,
+};
+use crate::whisper::SPEAKER_KEY;
 use parser::{Expr, FieldTerm, Term, TextTerm};
 
 pub(crate) use parser::parse_query;
@@ -12,7 +17,8 @@ pub(crate) use parser::{DateRange, ParsedQuery};
 #[cfg(feature = "lex")]
 #[allow(unused_imports)]
 pub(crate) use tantivy::{
-    EmbeddedLexSegment, EmbeddedLexStorage, LexWalBatch, TantivyEngine, TantivySnapshot,
+    EmbeddedLexSegment, EmbeddedLexStorage, LexWalBatch, TantivyDocHit, TantivyEngine,
+    TantivySnapshot,
 };
 
 pub struct EvaluationContext<'a> {
@@ -36,6 +42,38 @@ impl ParsedQuery {
     pub fn contains_field_terms(&self) -> bool {
         self.expr.contains_field_terms()
     }
+
+    /// Metadata comparisons every hit must satisfy (those not under `OR` or `NOT`).
+    pub fn required_meta_filters(&self) -> Vec<MetaFilter> {
+        let mut filters = Vec::new();
+        self.expr.collect_required_meta(&mut filters);
+        filters
+    }
+
+    /// Re-read metadata literals as their keys' declared types, so `meta.zip = 02139` stays
+    /// text on a text key instead of being inferred as a number.
+    pub fn bind_meta_types(&mut self, schema: &MetaSchema) {
+        self.expr.bind_meta_types(schema);
+    }
+
+    /// Drop configured stopwords from AND/OR groups, unless that would leave nothing to search.
+    pub fn remove_stopwords(&mut self, config: &AnalyzerConfig) {
+        if config.stopwords.is_empty() {
+            return;
+        }
+        let mut pruned = self.expr.clone();
+        pruned.remove_stopwords(config);
+        if !pruned.collect_tokens().is_empty() || pruned.contains_field_terms() {
+            self.expr = pruned;
+        }
+    }
+
+    /// Replace each word or phrase that has synonyms with an OR over it and its synonyms.
+    pub fn expand_synonyms(&mut self, config: &AnalyzerConfig) {
+        if !config.synonyms.is_empty() {
+            self.expr.expand_synonyms(config);
+        }
+    }
 }
 
 impl TextTerm {
@@ -82,7 +120,42 @@ impl FieldTerm {
                 .labels
                 .iter()
                 .any(|value| value.eq_ignore_ascii_case(label)),
+            FieldTerm::Speaker(speaker) => {
+                let extra = &ctx.frame.extra_metadata;
+                extra.get(SPEAKER_KEY).is_some_and(|value| {
+                    value
+                        .split(',')
+                        .any(|name| name.trim().eq_ignore_ascii_case(speaker))
+                }) || extra
+                    .get(CHAT_AUTHOR_KEY)
+                    .is_some_and(|author| author.eq_ignore_ascii_case(speaker))
+            }
+            FieldTerm::Symbol(symbol) => ctx
+                .frame
+                .extra_metadata
+                .get(CODE_SYMBOLS_KEY)
+                .is_some_and(|value| {
+                    CodeSymbol::parse_labels(value)
+                        .any(|(_, name)| name.eq_ignore_ascii_case(symbol))
+                }),
+            FieldTerm::Column(column) => ctx
+                .frame
+                .extra_metadata
+                .get(TABLE_HEADERS_KEY)
+                .and_then(|headers| serde_json::from_str::<Vec<String>>(headers).ok())
+                .is_some_and(|headers| {
+                    headers
+                        .iter()
+                        .any(|header| header.trim().eq_ignore_ascii_case(column))
+                }),
             FieldTerm::DateRange(range) => range.matches(ctx.frame),
+            FieldTerm::Meta { filter, .. } => filter.matches(
+                ctx.frame
+                    .extra_metadata
+                    .get(&filter.key)
+                    .map(String::as_str),
+                None,
+            ),
         }
     }
 }
@@ -200,6 +273,79 @@ impl Expr {
         }
     }
 
+    fn collect_required_meta(&self, filters: &mut Vec<MetaFilter>) {
+        match self {
+            Expr::Term(Term::Field(FieldTerm::Meta { filter, .. })) => filters.push(filter.clone()),
+            Expr::And(children) => {
+                for child in children {
+                    child.collect_required_meta(filters);
+                }
+            }
+            Expr::Or(_) | Expr::Not(_) | Expr::Term(_) => {}
+        }
+    }
+
+    fn bind_meta_types(&mut self, schema: &MetaSchema) {
+        match self {
+            Expr::Or(children) | Expr::And(children) => {
+                for child in children {
+                    child.bind_meta_types(schema);
+                }
+            }
+            Expr::Not(child) => child.bind_meta_types(schema),
+            Expr::Term(Term::Field(FieldTerm::Meta { filter, raw })) => {
+                if let Some(value) = schema
+                    .ty(&filter.key)
+                    .and_then(|ty| MetaValue::parse(ty, raw))
+                {
+                    filter.value = value;
+                }
+            }
+            Expr::Term(_) => {}
+        }
+    }
+
+    fn remove_stopwords(&mut self, config: &AnalyzerConfig) {
+        if let Expr::Or(children) | Expr::And(children) = self {
+            for child in children.iter_mut() {
+                child.remove_stopwords(config);
+            }
+            children.retain(|child| match child {
+                Expr::Term(Term::Text(TextTerm::Word(word))) => !config.is_stopword(word),
+                Expr::Or(grandchildren) | Expr::And(grandchildren) => !grandchildren.is_empty(),
+                _ => true,
+            });
+        }
+    }
+
+    fn expand_synonyms(&mut self, config: &AnalyzerConfig) {
+        match self {
+            Expr::Or(children) | Expr::And(children) => {
+                for child in children {
+                    child.expand_synonyms(config);
+                }
+            }
+            Expr::Not(child) => child.expand_synonyms(config),
+            Expr::Term(Term::Text(TextTerm::Word(term) | TextTerm::Phrase(term))) => {
+                let synonyms = config.synonyms_of(term);
+                if synonyms.is_empty() {
+                    return;
+                }
+                let mut alternatives = vec![self.clone()];
+                alternatives.extend(synonyms.into_iter().map(|synonym| {
+                    let text = if synonym.contains(' ') {
+                        TextTerm::Phrase(synonym.to_string())
+                    } else {
+                        TextTerm::Word(synonym.to_string())
+                    };
+                    Expr::Term(Term::Text(text))
+                }));
+                *self = Expr::Or(alternatives);
+            }
+            Expr::Term(_) => {}
+        }
+    }
+
     fn contains_field_terms(&self) -> bool {
         match self {
             Expr::Or(children) | Expr::And(children) => {
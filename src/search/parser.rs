@@ -1,6 +1,7 @@
 // Safe unwrap/expect: regex patterns from validated input strings.
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 use crate::error::MemvidError;
+use crate::types::{MetaFilter, MetaOp, MetaValue};
 use regex::Regex;
 use std::convert::TryFrom;
 use time::{Date, Month, OffsetDateTime};
@@ -52,7 +53,18 @@ pub(crate) enum FieldTerm {
     Track(String),
     Tag(String),
     Label(String),
+    Speaker(String),
+    /// A definition listed in a code chunk's symbol metadata.
+    Symbol(String),
+    /// A column header of a stored table, on its `table_meta` and `table_row` frames.
+    Column(String),
     DateRange(DateRange),
+    /// Metadata comparison; `raw` is the literal as written, kept so the value can be
+    /// re-read as the key's declared type.
+    Meta {
+        filter: MetaFilter,
+        raw: String,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -67,6 +79,8 @@ enum Token {
     Phrase(String),
     Field(String, String),
     DateRange(String, String, String),
+    /// `meta.<key> <op> <value>`
+    Meta(String, MetaOp, String),
     LParen,
     RParen,
     And,
@@ -121,11 +135,16 @@ impl<'a> Lexer<'a> {
     }
 
     /// Known field names that should be treated as field queries when followed by `:`
-    const KNOWN_FIELDS: &'static [&'static str] =
-        &["uri", "scope", "track", "tag", "label", "date"];
+    const KNOWN_FIELDS: &'static [&'static str] = &[
+        "uri", "scope", "track", "tag", "label", "speaker", "symbol", "column", "date",
+    ];
 
     fn read_field_or_word(&mut self) -> Result<Option<Token>, MemvidError> {
         let start = self.index;
+        if let Some(token) = self.read_meta()? {
+            return Ok(Some(token));
+        }
+        self.index = start;
         let mut colon_pos: Option<usize> = None;
 
         // First pass: scan the word and note if there's a colon
@@ -193,6 +212,64 @@ impl<'a> Lexer<'a> {
         Ok(Some(value_token))
     }
 
+    /// Read `meta.<key> <op> <value>`, where whitespace around the operator is optional and
+    /// the value may be quoted. Returns `None` when the input is not a metadata comparison.
+    fn read_meta(&mut self) -> Result<Option<Token>, MemvidError> {
+        let prefix: String = self.chars.iter().skip(self.index).take(5).collect();
+        if !prefix.eq_ignore_ascii_case("meta.") {
+            return Ok(None);
+        }
+        self.index += 5;
+        let key_start = self.index;
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+        {
+            self.index += 1;
+        }
+        let key: String = self.chars[key_start..self.index].iter().collect();
+        self.skip_whitespace();
+        let op_start = self.index;
+        while self
+            .peek()
+            .is_some_and(|ch| matches!(ch, '=' | '!' | '<' | '>' | ':'))
+        {
+            self.index += 1;
+        }
+        let symbol: String = self.chars[op_start..self.index].iter().collect();
+        if key.is_empty() || symbol.is_empty() {
+            return Ok(None);
+        }
+        let op = MetaOp::from_symbol(&symbol).ok_or_else(|| MemvidError::InvalidQuery {
+            reason: format!("unsupported metadata operator '{symbol}' for meta.{key}"),
+        })?;
+        self.skip_whitespace();
+        let value = if self.peek() == Some('"') {
+            self.read_quoted()?
+        } else {
+            let value_start = self.index;
+            while let Some(ch) = self.peek() {
+                if ch.is_whitespace() || ch == '(' || ch == ')' {
+                    break;
+                }
+                self.index += 1;
+            }
+            self.chars[value_start..self.index].iter().collect()
+        };
+        if value.is_empty() {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("missing value for meta.{key}"),
+            });
+        }
+        Ok(Some(Token::Meta(key, op, value)))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+    }
+
     fn read_quoted(&mut self) -> Result<String, MemvidError> {
         self.index += 1; // skip opening quote
         let value = self.read_until_quote()?;
@@ -322,6 +399,14 @@ impl Parser {
                 let term = FieldTerm::from_pair(&field, &value)?;
                 Ok(Expr::Term(Term::Field(term)))
             }
+            Some(Token::Meta(key, op, raw)) => {
+                let filter = MetaFilter {
+                    key,
+                    op,
+                    value: MetaValue::infer(&raw),
+                };
+                Ok(Expr::Term(Term::Field(FieldTerm::Meta { filter, raw })))
+            }
             Some(Token::DateRange(field, start, end)) => {
                 let term = FieldTerm::from_date_range(&field, &start, &end)?;
                 Ok(Expr::Term(Term::Field(term)))
@@ -431,6 +516,9 @@ impl FieldTerm {
             "track" => Ok(FieldTerm::Track(normalized)),
             "tag" => Ok(FieldTerm::Tag(normalized)),
             "label" => Ok(FieldTerm::Label(normalized)),
+            "speaker" => Ok(FieldTerm::Speaker(normalized)),
+            "symbol" => Ok(FieldTerm::Symbol(normalized)),
+            "column" => Ok(FieldTerm::Column(normalized)),
             _ => Err(MemvidError::InvalidQuery {
                 reason: format!("unsupported field: {field}"),
             }),
@@ -539,6 +627,34 @@ mod tests {
         parse_query("date:[2024-01-01 TO 2024-12-31] AND rust").expect("parse");
     }
 
+    #[test]
+    fn parses_meta_comparisons() {
+        let result =
+            parse_query("meta.priority >= 3 AND meta.status != \"done\" meta.due<2024-06-01")
+                .expect("parse");
+        let Expr::And(children) = result.expr else {
+            panic!("expected Expr::And");
+        };
+        let filters: Vec<&MetaFilter> = children
+            .iter()
+            .map(|child| match child {
+                Expr::Term(Term::Field(FieldTerm::Meta { filter, .. })) => filter,
+                other => panic!("expected meta term, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(filters[0], &MetaFilter::new("priority", MetaOp::Ge, 3));
+        assert_eq!(filters[1], &MetaFilter::new("status", MetaOp::Ne, "done"));
+        assert_eq!(filters[2].op, MetaOp::Lt);
+        assert_eq!(filters[2].value, MetaValue::Date(1_717_200_000));
+
+        // Without an operator `meta.` is plain text.
+        assert!(matches!(
+            parse_query("meta.analysis").expect("parse").expr,
+            Expr::Term(Term::Text(_))
+        ));
+        assert!(parse_query("meta.priority =< 3").is_err());
+    }
+
     #[test]
     fn unknown_field_colon_treated_as_word() {
         // "IRR:" should NOT be treated as a field query - it's just text with a colon
@@ -564,6 +680,8 @@ mod tests {
         assert!(parse_query("scope:project").is_ok());
         assert!(parse_query("track:main").is_ok());
         assert!(parse_query("label:todo").is_ok());
+        assert!(parse_query("symbol:parse_query").is_ok());
+        assert!(parse_query("column:revenue").is_ok());
     }
 
     #[test]
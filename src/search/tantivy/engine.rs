@@ -1,14 +1,17 @@
+use super::language::{CJK_TOKENIZER, analyzer_for};
 use super::query;
-use super::schema::{build_schema, initialise_tokenizer};
+use super::schema::{build_schema, initialise_tokenizer, register_default_analyzer};
 use super::util::to_search_value;
+use crate::analysis::language::{LANGUAGE_KEY, is_cjk, normalize_language};
 use crate::search::parser::ParsedQuery;
-use crate::types::{Frame, FrameId};
+use crate::types::{AnalyzerConfig, Frame, FrameId};
 use crate::{MemvidError, Result};
 use blake3::{Hasher, hash};
 use tantivy::collector::TopDocs;
 use tantivy::indexer::IndexWriter;
 use tantivy::schema::{Field, OwnedValue, Schema, TantivyDocument};
-use tantivy::{Index, IndexReader, Term, doc};
+use tantivy::tokenizer::{PreTokenizedString, Token};
+use tantivy::{Index, IndexReader, Searcher, Term, doc};
 use tempfile::TempDir;
 
 /// Tantivy-backed search index used when the `lex` feature is enabled.
@@ -163,10 +166,24 @@ impl TantivyEngine {
             return Ok(());
         }
         let mut document = doc!(
-            self.content => content,
             self.timestamp => frame.timestamp,
             self.frame_id => frame.id,
         );
+        let analyzer = frame
+            .extra_metadata
+            .get(LANGUAGE_KEY)
+            .and_then(|language| normalize_language(language))
+            .and_then(analyzer_for);
+        match analyzer {
+            Some(name) => document.add_pre_tokenized_text(
+                self.content,
+                PreTokenizedString {
+                    text: content.to_string(),
+                    tokens: self.tokenize_with(&name, content),
+                },
+            ),
+            None => document.add_text(self.content, content),
+        }
         for tag in &frame.tags {
             document.add_text(self.tags, to_search_value(tag));
         }
@@ -262,12 +279,44 @@ impl TantivyEngine {
         Ok(())
     }
 
+    /// A point-in-time view of the index; searches run through one searcher see the same
+    /// segments even if the index is reloaded meanwhile.
+    #[must_use]
+    pub fn searcher(&self) -> Searcher {
+        self.reader.searcher()
+    }
+
+    /// `language` is an ISO 639-1 hint: query text is also analyzed the way documents in that
+    /// language were indexed.
     pub fn search_documents(
         &self,
         parsed: &ParsedQuery,
         uri_filter: Option<&str>,
         scope_filter: Option<&str>,
         frame_filter: Option<&[u64]>,
+        language: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TantivyDocHit>> {
+        self.search_documents_in(
+            &self.reader.searcher(),
+            parsed,
+            uri_filter,
+            scope_filter,
+            frame_filter,
+            language,
+            limit,
+        )
+    }
+
+    /// `search_documents` through a searcher the caller holds, e.g. one shared by a batch.
+    pub fn search_documents_in(
+        &self,
+        searcher: &Searcher,
+        parsed: &ParsedQuery,
+        uri_filter: Option<&str>,
+        scope_filter: Option<&str>,
+        frame_filter: Option<&[u64]>,
+        language: Option<&str>,
         limit: usize,
     ) -> Result<Vec<TantivyDocHit>> {
         if let Some(ids) = frame_filter {
@@ -276,9 +325,15 @@ impl TantivyEngine {
             }
         }
 
-        let query = query::build_root_query(self, parsed, uri_filter, scope_filter, frame_filter)?;
+        let query = query::build_root_query(
+            self,
+            parsed,
+            uri_filter,
+            scope_filter,
+            frame_filter,
+            language,
+        )?;
         let doc_limit = limit.max(1);
-        let searcher = self.reader.searcher();
         let top_docs = searcher
             .search(&query, &TopDocs::with_limit(doc_limit))
             .map_err(|err| MemvidError::Tantivy {
@@ -308,6 +363,7 @@ impl TantivyEngine {
             let content = match document.get_first(self.content) {
                 Some(value) => match OwnedValue::from(value) {
                     OwnedValue::Str(text) => text,
+                    OwnedValue::PreTokStr(pre_tokenized) => pre_tokenized.text,
                     _ => String::new(),
                 },
                 None => String::new(),
@@ -383,15 +439,19 @@ impl TantivyEngine {
         })
     }
 
+    /// Tokenize content and queries with `config`'s stopwords and protected terms from now on.
+    pub(crate) fn set_analyzer_config(&self, config: &AnalyzerConfig) {
+        register_default_analyzer(&self.index, config);
+    }
+
     pub(crate) fn analyse_text(&self, text: &str) -> Vec<String> {
         if let Some(name) = &self.tokenizer {
-            if let Some(mut analyzer) = self.index.tokenizers().get(name) {
-                let mut stream = analyzer.token_stream(text);
-                let mut tokens = Vec::new();
-                while stream.advance() {
-                    tokens.push(stream.token().text.to_string());
-                }
-                return tokens;
+            if self.index.tokenizers().get(name).is_some() {
+                return self
+                    .tokenize_with(name, text)
+                    .into_iter()
+                    .map(|token| token.text)
+                    .collect();
             }
         }
         if text.trim().is_empty() {
@@ -401,6 +461,44 @@ impl TantivyEngine {
         }
     }
 
+    /// Distinct analyses of query text: the default one, plus the hinted language's, or the
+    /// CJK bigrams when the text contains CJK characters and no hint was given.
+    pub(crate) fn analyse_query_text(
+        &self,
+        text: &str,
+        language: Option<&str>,
+    ) -> Vec<Vec<String>> {
+        let mut analyses = vec![self.analyse_text(text)];
+        let analyzer = match language {
+            Some(language) => analyzer_for(language),
+            None => text.chars().any(is_cjk).then(|| CJK_TOKENIZER.to_string()),
+        };
+        if let Some(name) = analyzer {
+            let tokens: Vec<String> = self
+                .tokenize_with(&name, text)
+                .into_iter()
+                .map(|token| token.text)
+                .collect();
+            if !analyses.contains(&tokens) {
+                analyses.push(tokens);
+            }
+        }
+        analyses.retain(|tokens| !tokens.is_empty());
+        analyses
+    }
+
+    fn tokenize_with(&self, name: &str, text: &str) -> Vec<Token> {
+        let Some(mut analyzer) = self.index.tokenizers().get(name) else {
+            return Vec::new();
+        };
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while stream.advance() {
+            tokens.push(stream.token().clone());
+        }
+        tokens
+    }
+
     pub fn num_docs(&self) -> u64 {
         self.reader.searcher().num_docs()
     }
@@ -0,0 +1,157 @@
+//! Per-language analyzers for frames whose detected language is not English.
+//!
+//! Frames carrying a [`LANGUAGE_KEY`](crate::analysis::language::LANGUAGE_KEY) are tokenized
+//! with that language's stemmer, or with overlapping character bigrams for Chinese, Japanese,
+//! and Korean, and indexed pre-tokenized into the shared `content` field. Queries are analyzed
+//! with the default analyzer plus the hinted (or script-detected) language so both kinds of
+//! document match.
+
+use tantivy::Index;
+use tantivy::tokenizer::{
+    Language, LowerCaser, SimpleTokenizer, Stemmer, TextAnalyzer, Token, TokenStream, Tokenizer,
+};
+
+use crate::analysis::language::is_cjk;
+
+pub(super) const CJK_TOKENIZER: &str = "memvid_cjk";
+
+const STEMMED_LANGUAGES: &[(&str, Language)] = &[
+    ("ar", Language::Arabic),
+    ("da", Language::Danish),
+    ("de", Language::German),
+    ("el", Language::Greek),
+    ("es", Language::Spanish),
+    ("fi", Language::Finnish),
+    ("fr", Language::French),
+    ("hu", Language::Hungarian),
+    ("it", Language::Italian),
+    ("nl", Language::Dutch),
+    ("no", Language::Norwegian),
+    ("pt", Language::Portuguese),
+    ("ro", Language::Romanian),
+    ("ru", Language::Russian),
+    ("sv", Language::Swedish),
+    ("ta", Language::Tamil),
+    ("tr", Language::Turkish),
+];
+
+pub(super) fn register_language_analyzers(index: &Index) {
+    for (code, language) in STEMMED_LANGUAGES {
+        let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(LowerCaser)
+            .filter(Stemmer::new(*language))
+            .build();
+        index
+            .tokenizers()
+            .register(&format!("memvid_{code}"), analyzer);
+    }
+    let cjk = TextAnalyzer::builder(CjkBigramTokenizer)
+        .filter(LowerCaser)
+        .build();
+    index.tokenizers().register(CJK_TOKENIZER, cjk);
+}
+
+/// Registered analyzer for an ISO 639-1 code; `None` means the default English analyzer.
+pub(super) fn analyzer_for(language: &str) -> Option<String> {
+    if matches!(language, "ja" | "zh" | "ko") {
+        return Some(CJK_TOKENIZER.to_string());
+    }
+    STEMMED_LANGUAGES
+        .iter()
+        .any(|(code, _)| *code == language)
+        .then(|| format!("memvid_{language}"))
+}
+
+/// Splits runs of CJK characters into overlapping bigrams (a lone character stays a unigram)
+/// and everything else on non-alphanumeric boundaries, like `SimpleTokenizer`.
+#[derive(Clone, Default)]
+pub(super) struct CjkBigramTokenizer;
+
+pub(super) struct CjkBigramStream {
+    tokens: Vec<Token>,
+    cursor: usize,
+}
+
+impl Tokenizer for CjkBigramTokenizer {
+    type TokenStream<'a> = CjkBigramStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> CjkBigramStream {
+        let mut tokens = Vec::new();
+        let mut push = |from: usize, to: usize| {
+            tokens.push(Token {
+                offset_from: from,
+                offset_to: to,
+                position: tokens.len(),
+                text: text[from..to].to_string(),
+                position_length: 1,
+            });
+        };
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let end_of = |index: usize| chars.get(index).map_or(text.len(), |(offset, _)| *offset);
+        let mut index = 0;
+        while index < chars.len() {
+            let (start, c) = chars[index];
+            if is_cjk(c) {
+                let run_end = (index..chars.len())
+                    .find(|&i| !is_cjk(chars[i].1))
+                    .unwrap_or(chars.len());
+                if run_end - index == 1 {
+                    push(start, end_of(index + 1));
+                }
+                for i in index..run_end.saturating_sub(1) {
+                    push(chars[i].0, end_of(i + 2));
+                }
+                index = run_end;
+            } else if c.is_alphanumeric() {
+                let run_end = (index..chars.len())
+                    .find(|&i| !chars[i].1.is_alphanumeric() || is_cjk(chars[i].1))
+                    .unwrap_or(chars.len());
+                push(start, end_of(run_end));
+                index = run_end;
+            } else {
+                index += 1;
+            }
+        }
+        CjkBigramStream { tokens, cursor: 0 }
+    }
+}
+
+impl TokenStream for CjkBigramStream {
+    fn advance(&mut self) -> bool {
+        self.cursor += 1;
+        self.cursor <= self.tokens.len()
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.cursor - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.cursor - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(text: &str) -> Vec<String> {
+        let mut tokenizer = CjkBigramTokenizer;
+        let mut stream = tokenizer.token_stream(text);
+        let mut out = Vec::new();
+        while stream.advance() {
+            out.push(stream.token().text.clone());
+        }
+        out
+    }
+
+    #[test]
+    fn cjk_runs_become_bigrams() {
+        assert_eq!(
+            tokens("東京タワー in Tokyo"),
+            vec!["東京", "京タ", "タワ", "ワー", "in", "Tokyo"]
+        );
+        assert_eq!(tokens("猫, v2"), vec!["猫", "v2"]);
+        assert!(tokens(" ,.").is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 //! Tantivy-backed lexical search integration.
 
 mod engine;
+mod language;
 mod query;
 mod schema;
 mod storage;
@@ -19,12 +19,19 @@ pub(super) fn build_root_query(
     uri_filter: Option<&str>,
     scope_filter: Option<&str>,
     frame_filter: Option<&[u64]>,
+    language: Option<&str>,
 ) -> Result<Box<dyn Query>> {
-    QueryPlanner { engine }.build_root_query(parsed, uri_filter, scope_filter, frame_filter)
+    QueryPlanner { engine, language }.build_root_query(
+        parsed,
+        uri_filter,
+        scope_filter,
+        frame_filter,
+    )
 }
 
 struct QueryPlanner<'a> {
     engine: &'a TantivyEngine,
+    language: Option<&'a str>,
 }
 
 impl QueryPlanner<'_> {
@@ -94,6 +101,16 @@ impl QueryPlanner<'_> {
                 }
                 Ok(Box::new(BooleanQuery::new(clauses)))
             }
+            // Metadata comparisons are checked when hits are evaluated; excluding their
+            // match-all query here would exclude everything.
+            Expr::Not(child)
+                if matches!(
+                    **child,
+                    Expr::Term(ParsedTerm::Field(FieldTerm::Meta { .. }))
+                ) =>
+            {
+                Ok(Box::new(AllQuery))
+            }
             Expr::Not(child) => Ok(Box::new(BooleanQuery::new(vec![
                 (Occur::Must, Box::new(AllQuery)),
                 (Occur::MustNot, self.build_expr_query(child)?),
@@ -135,7 +152,7 @@ impl QueryPlanner<'_> {
                     IndexRecordOption::Basic,
                 )))
             }
-            FieldTerm::Scope(_) => Ok(Box::new(AllQuery)),
+            FieldTerm::Scope(_) | FieldTerm::Meta { .. } => Ok(Box::new(AllQuery)),
             FieldTerm::Track(value) => {
                 let normalized = to_search_value(value);
                 Ok(Box::new(TermQuery::new(
@@ -157,6 +174,13 @@ impl QueryPlanner<'_> {
                     IndexRecordOption::Basic,
                 )))
             }
+            // Speaker names are indexed as `speaker:`/`chat_author:` metadata lines in the
+            // content; hits are narrowed to exact matches when the query is evaluated.
+            FieldTerm::Speaker(value) => self.build_word_query(value),
+            // Symbol names are indexed as a `code_symbols:` line in code chunk content.
+            FieldTerm::Symbol(value) => self.build_word_query(value),
+            // Table headers are indexed as a `headers_json:` metadata line.
+            FieldTerm::Column(value) => self.build_word_query(value),
             FieldTerm::DateRange(range) => {
                 let lower = range.start.map_or(Bound::Unbounded, |value| {
                     Bound::Included(Term::from_field_i64(self.engine.timestamp, value))
@@ -175,25 +199,12 @@ impl QueryPlanner<'_> {
             return Ok(Box::new(AllQuery));
         }
 
-        let tokens = self.engine.analyse_text(word);
-        if tokens.is_empty() {
+        let mut queries = self.content_queries(word);
+        if queries.is_empty() {
             // Word produced no tokens after analysis - match all instead of erroring
             // This can happen with punctuation-only or stop-word-only terms
             return Ok(Box::new(AllQuery));
         }
-        let mut queries: Vec<Box<dyn Query>> = Vec::new();
-        if tokens.len() == 1 {
-            queries.push(Box::new(TermQuery::new(
-                Term::from_field_text(self.engine.content, &tokens[0]),
-                IndexRecordOption::WithFreqsAndPositions,
-            )));
-        } else {
-            let terms: Vec<Term> = tokens
-                .iter()
-                .map(|token| Term::from_field_text(self.engine.content, token))
-                .collect();
-            queries.push(Box::new(PhraseQuery::new(terms)));
-        }
 
         let normalized = to_search_value(word);
         queries.push(Box::new(TermQuery::new(
@@ -222,24 +233,11 @@ impl QueryPlanner<'_> {
             return Ok(Box::new(AllQuery));
         }
 
-        let tokens = self.engine.analyse_text(phrase);
-        if tokens.is_empty() {
+        let mut queries = self.content_queries(phrase);
+        if queries.is_empty() {
             // Phrase produced no tokens after analysis - match all instead of erroring
             return Ok(Box::new(AllQuery));
         }
-        let mut queries: Vec<Box<dyn Query>> = Vec::new();
-        if tokens.len() == 1 {
-            queries.push(Box::new(TermQuery::new(
-                Term::from_field_text(self.engine.content, &tokens[0]),
-                IndexRecordOption::WithFreqsAndPositions,
-            )));
-        } else {
-            let terms: Vec<Term> = tokens
-                .iter()
-                .map(|token| Term::from_field_text(self.engine.content, token))
-                .collect();
-            queries.push(Box::new(PhraseQuery::new(terms)));
-        }
 
         let normalized = to_search_value(phrase);
         queries.push(Box::new(TermQuery::new(
@@ -261,4 +259,26 @@ impl QueryPlanner<'_> {
 
         Ok(combine_should_queries(queries))
     }
+
+    /// One content query per distinct analysis of `text` (see `analyse_query_text`).
+    fn content_queries(&self, text: &str) -> Vec<Box<dyn Query>> {
+        self.engine
+            .analyse_query_text(text, self.language)
+            .into_iter()
+            .map(|tokens| -> Box<dyn Query> {
+                if tokens.len() == 1 {
+                    Box::new(TermQuery::new(
+                        Term::from_field_text(self.engine.content, &tokens[0]),
+                        IndexRecordOption::WithFreqsAndPositions,
+                    ))
+                } else {
+                    let terms: Vec<Term> = tokens
+                        .iter()
+                        .map(|token| Term::from_field_text(self.engine.content, token))
+                        .collect();
+                    Box::new(PhraseQuery::new(terms))
+                }
+            })
+            .collect()
+    }
 }
@@ -1,16 +1,41 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::language::register_language_analyzers;
+use crate::types::AnalyzerConfig;
 use tantivy::Index;
 use tantivy::schema::{IndexRecordOption, NumericOptions, STRING, Schema, TEXT, TextFieldIndexing};
 use tantivy::tokenizer::{
-    Language, LowerCaser, RawTokenizer, SimpleTokenizer, Stemmer, TextAnalyzer,
+    Language, LowerCaser, RawTokenizer, SimpleTokenizer, Stemmer, StopWordFilter, TextAnalyzer,
+    Token, TokenFilter, TokenStream, Tokenizer,
 };
 
 pub(super) fn initialise_tokenizer(index: &Index) {
-    let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+    register_default_analyzer(index, &AnalyzerConfig::default());
+    index.tokenizers().register("raw", RawTokenizer::default());
+    register_language_analyzers(index);
+}
+
+/// (Re)register `memvid_default` with the file's stopwords and protected terms. Segments
+/// already written keep the tokens they were indexed with.
+pub(super) fn register_default_analyzer(index: &Index, config: &AnalyzerConfig) {
+    let builder = TextAnalyzer::builder(SimpleTokenizer::default())
         .filter(LowerCaser)
-        .filter(Stemmer::new(Language::English))
-        .build();
+        .dynamic();
+    let builder = if config.stopwords.is_empty() {
+        builder
+    } else {
+        builder.filter_dynamic(StopWordFilter::remove(config.stopwords.iter().cloned()))
+    };
+    let analyzer = if config.protected_terms.is_empty() {
+        builder.filter_dynamic(Stemmer::new(Language::English))
+    } else {
+        builder.filter_dynamic(ProtectedStemmer {
+            protected: Arc::new(config.protected_terms.iter().cloned().collect()),
+        })
+    }
+    .build();
     index.tokenizers().register("memvid_default", analyzer);
-    index.tokenizers().register("raw", RawTokenizer::default());
 }
 
 pub(super) fn build_schema() -> Schema {
@@ -49,3 +74,105 @@ pub(super) fn build_schema() -> Schema {
 
     schema_builder.build()
 }
+
+/// English stemmer that passes protected terms through unchanged.
+#[derive(Clone)]
+struct ProtectedStemmer {
+    protected: Arc<HashSet<String>>,
+}
+
+impl TokenFilter for ProtectedStemmer {
+    type Tokenizer<T: Tokenizer> = ProtectedStemmerFilter<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> ProtectedStemmerFilter<T> {
+        ProtectedStemmerFilter {
+            protected: self.protected,
+            stemmer: TextAnalyzer::builder(RawTokenizer::default())
+                .filter(Stemmer::new(Language::English))
+                .build(),
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ProtectedStemmerFilter<T> {
+    protected: Arc<HashSet<String>>,
+    stemmer: TextAnalyzer,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for ProtectedStemmerFilter<T> {
+    type TokenStream<'a> = ProtectedStemmerStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        ProtectedStemmerStream {
+            tail: self.inner.token_stream(text),
+            protected: &self.protected,
+            stemmer: &mut self.stemmer,
+        }
+    }
+}
+
+struct ProtectedStemmerStream<'a, T> {
+    tail: T,
+    protected: &'a HashSet<String>,
+    stemmer: &'a mut TextAnalyzer,
+}
+
+impl<T: TokenStream> TokenStream for ProtectedStemmerStream<'_, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        let token = self.tail.token_mut();
+        if !self.protected.contains(&token.text) {
+            let stemmed = {
+                let mut stream = self.stemmer.token_stream(&token.text);
+                stream.advance().then(|| stream.token().text.clone())
+            };
+            if let Some(stemmed) = stemmed {
+                token.text = stemmed;
+            }
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyse(config: &AnalyzerConfig, text: &str) -> Vec<String> {
+        let index = Index::create_in_ram(build_schema());
+        register_default_analyzer(&index, config);
+        let mut analyzer = index.tokenizers().get("memvid_default").expect("analyzer");
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while stream.advance() {
+            tokens.push(stream.token().text.clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn configured_stopwords_and_protected_terms_apply() {
+        let text = "Acme runs Kubernetes clusters";
+        assert_eq!(
+            analyse(&AnalyzerConfig::default(), text),
+            vec!["acm", "run", "kubernet", "cluster"]
+        );
+        let config = AnalyzerConfig::new()
+            .with_stopwords(["acme"])
+            .with_protected_terms(["Kubernetes"]);
+        assert_eq!(analyse(&config, text), vec!["run", "kubernetes", "cluster"]);
+    }
+}
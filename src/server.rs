@@ -0,0 +1,581 @@
+//! HTTP/JSON serving layer exposing core operations for a directory of memories.
+//!
+//! Requires the `server` feature. Each `.mv2` file under [`ServerConfig::root`] is addressable
+//! by name (`/memories/{name}/…` maps to `{root}/{name}.mv2`). Handles are opened lazily and
+//! kept for the lifetime of the server; every request locks its memory for the duration of the
+//! call, so writers never overlap. Requests are served by a pool of
+//! [`ServerConfig::workers`] threads, so a slow call only holds up its own memory.
+//!
+//! Routes:
+//!
+//! | Method | Path                          | Body                 | Response           |
+//! | ------ | ----------------------------- | -------------------- | ------------------ |
+//! | GET    | `/health`                     | —                    | `{"status":"ok"}`  |
+//! | GET    | `/memories`                   | —                    | memory names       |
+//! | POST   | `/memories/{name}`            | —                    | creates the file   |
+//! | POST   | `/memories/{name}/put`        | [`PutBody`]          | `{"frame_id":…}`   |
+//! | POST   | `/memories/{name}/search`     | `SearchRequest`      | `SearchResponse`   |
+//! | POST   | `/memories/{name}/ask`        | `AskRequest`         | `AskResponse`      |
+//! | POST   | `/memories/{name}/timeline`   | `TimelineQuery`      | `[TimelineEntry]`  |
+//! | GET    | `/memories/{name}/stats`      | —                    | `Stats`            |
+//! | POST   | `/memories/{name}/doctor`     | `DoctorOptions`      | `DoctorReport`     |
+//!
+//! `put` answers with the new frame's id, which it has from commit on, and the WAL `sequence`
+//! of the write.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::{MemvidError, Result};
+use crate::memvid::Memvid;
+use crate::types::{
+    AskRequest, DoctorOptions, PutOptions, SearchRequest, TimelineQuery, VecEmbedder,
+};
+
+/// Largest request body accepted by [`MemoryServer::serve`].
+pub const MAX_REQUEST_BYTES: usize = 32 * 1024 * 1024;
+
+/// Settings for [`MemoryServer`].
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Address to bind, e.g. `127.0.0.1:8080`.
+    pub bind: String,
+    /// Directory holding the `.mv2` files served by name.
+    pub root: PathBuf,
+    /// Reject `put`, create, and doctor requests.
+    pub read_only: bool,
+    /// Threads answering requests; defaults to the available parallelism.
+    pub workers: usize,
+}
+
+impl ServerConfig {
+    pub fn new(bind: impl Into<String>, root: impl Into<PathBuf>) -> Self {
+        Self {
+            bind: bind.into(),
+            root: root.into(),
+            read_only: false,
+            workers: std::thread::available_parallelism().map_or(4, usize::from),
+        }
+    }
+
+    #[must_use]
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    #[must_use]
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+}
+
+/// Body of `POST /memories/{name}/put`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PutBody {
+    /// UTF-8 content to store.
+    pub text: String,
+    #[serde(default)]
+    pub options: Option<PutOptions>,
+    /// Commit immediately after the put (default: true).
+    #[serde(default = "default_commit")]
+    pub commit: bool,
+}
+
+fn default_commit() -> bool {
+    true
+}
+
+/// JSON response produced by [`MemoryServer::handle`].
+#[derive(Debug, Clone)]
+pub struct ServerResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ServerResponse {
+    fn ok<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_value(value) {
+            Ok(body) => Self { status: 200, body },
+            Err(err) => Self::error(500, &err.to_string()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+
+    fn from_error(err: &MemvidError) -> Self {
+        let status = match err {
+            MemvidError::FrameNotFound { .. } | MemvidError::FrameNotFoundByUri { .. } => 404,
+            MemvidError::InvalidQuery { .. }
+            | MemvidError::InvalidCursor { .. }
+            | MemvidError::SchemaValidation { .. }
+            | MemvidError::VecDimensionMismatch { .. } => 400,
+            MemvidError::Lock(_) | MemvidError::Locked(_) => 409,
+            MemvidError::LexNotEnabled
+            | MemvidError::VecNotEnabled
+            | MemvidError::FeatureUnavailable { .. } => 422,
+            MemvidError::CapacityExceeded { .. } => 507,
+            _ => 500,
+        };
+        Self::error(status, &err.to_string())
+    }
+}
+
+/// A registry slot; `None` until the memory is first used, and again while doctor runs.
+type MemorySlot = Arc<Mutex<Option<Memvid>>>;
+
+/// Routes JSON requests to memories under a root directory.
+pub struct MemoryServer {
+    config: ServerConfig,
+    memories: Mutex<HashMap<String, MemorySlot>>,
+}
+
+impl MemoryServer {
+    #[must_use]
+    pub fn new(config: ServerConfig) -> Self {
+        Self {
+            config,
+            memories: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// Bind and serve requests on [`ServerConfig::workers`] threads until the listener fails.
+    pub fn serve(&self) -> Result<()> {
+        let server = tiny_http::Server::http(&self.config.bind).map_err(|err| MemvidError::Io {
+            source: std::io::Error::other(err.to_string()),
+            path: None,
+        })?;
+        tracing::info!(
+            bind = %self.config.bind,
+            root = %self.config.root.display(),
+            workers = self.config.workers,
+            "memvid server listening"
+        );
+        std::thread::scope(|scope| {
+            for _ in 0..self.config.workers.max(1) {
+                scope.spawn(|| {
+                    loop {
+                        match server.recv() {
+                            Ok(request) => self.respond(request),
+                            Err(err) => {
+                                tracing::warn!("listener failed: {err}");
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+
+    fn respond(&self, mut request: tiny_http::Request) {
+        let method = request.method().as_str().to_ascii_uppercase();
+        let url = request.url().to_string();
+        let mut body = Vec::new();
+        let read = request
+            .as_reader()
+            .take(MAX_REQUEST_BYTES as u64 + 1)
+            .read_to_end(&mut body);
+        let response = match read {
+            Ok(_) if body.len() > MAX_REQUEST_BYTES => {
+                ServerResponse::error(413, "request body too large")
+            }
+            Ok(_) => self.handle(&method, &url, &body),
+            Err(err) => ServerResponse::error(400, &err.to_string()),
+        };
+        let payload = serde_json::to_vec(&response.body).unwrap_or_default();
+        let mut reply = tiny_http::Response::from_data(payload).with_status_code(response.status);
+        if let Ok(header) =
+            tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        {
+            reply = reply.with_header(header);
+        }
+        if let Err(err) = request.respond(reply) {
+            tracing::warn!("failed to send response for {method} {url}: {err}");
+        }
+    }
+
+    /// Dispatch a single request. Exposed so embedders can mount the routes in their own
+    /// HTTP stack and so routing can be exercised without sockets.
+    pub fn handle(&self, method: &str, url: &str, body: &[u8]) -> ServerResponse {
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["health"]) => ServerResponse::ok(&json!({ "status": "ok" })),
+            ("GET", ["memories"]) => match self.list_memories() {
+                Ok(names) => ServerResponse::ok(&names),
+                Err(err) => ServerResponse::from_error(&err),
+            },
+            ("POST", ["memories", name]) => self.create_memory(name),
+            (_, ["memories", name, op]) => self.dispatch(method, name, op, body),
+            _ => ServerResponse::error(404, "no such route"),
+        }
+    }
+
+    fn dispatch(&self, method: &str, name: &str, op: &str, body: &[u8]) -> ServerResponse {
+        let writes = matches!(op, "put" | "doctor");
+        if writes && self.config.read_only {
+            return ServerResponse::error(403, "server is read-only");
+        }
+        match self.memory_path(name) {
+            Ok(path) if path.is_file() => {}
+            Ok(_) => return ServerResponse::error(404, "memory not found"),
+            Err(err) => return ServerResponse::from_error(&err),
+        }
+        let result = match (method, op) {
+            ("POST", "put") => parse_body::<PutBody>(body).and_then(|put| {
+                self.with_memory(name, |mem| {
+                    let options = put.options.unwrap_or_default();
+                    let frame_id = mem.next_frame_id();
+                    let sequence = mem.put_bytes_with_options(put.text.as_bytes(), options)?;
+                    if put.commit {
+                        mem.commit()?;
+                    }
+                    Ok(json!({ "frame_id": frame_id, "sequence": sequence }))
+                })
+            }),
+            ("POST", "search") => parse_body::<SearchRequest>(body)
+                .and_then(|request| self.with_memory(name, |mem| to_value(&mem.search(request)?))),
+            ("POST", "ask") => parse_body::<AskRequest>(body).and_then(|request| {
+                self.with_memory(name, |mem| {
+                    to_value(&mem.ask(request, None::<&dyn VecEmbedder>)?)
+                })
+            }),
+            ("POST" | "GET", "timeline") => {
+                let query = if body.is_empty() {
+                    Ok(TimelineQuery::builder().build())
+                } else {
+                    parse_body::<TimelineQuery>(body)
+                };
+                query
+                    .and_then(|query| self.with_memory(name, |mem| to_value(&mem.timeline(query)?)))
+            }
+            ("GET", "stats") => self.with_memory(name, |mem| to_value(&mem.stats()?)),
+            ("POST", "doctor") => {
+                let options = if body.is_empty() {
+                    Ok(DoctorOptions::default())
+                } else {
+                    parse_body::<DoctorOptions>(body)
+                };
+                options.and_then(|options| self.doctor(name, options))
+            }
+            _ => return ServerResponse::error(404, "no such route"),
+        };
+        match result {
+            Ok(body) => ServerResponse { status: 200, body },
+            Err(err) => ServerResponse::from_error(&err),
+        }
+    }
+
+    fn memory_path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name.len() <= 128
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+            && !name.starts_with('.');
+        if !valid {
+            return Err(MemvidError::InvalidQuery {
+                reason: format!("invalid memory name: {name}"),
+            });
+        }
+        Ok(self.config.root.join(format!("{name}.mv2")))
+    }
+
+    fn registry(&self) -> Result<MutexGuard<'_, HashMap<String, MemorySlot>>> {
+        self.memories
+            .lock()
+            .map_err(|_| MemvidError::Lock("server registry mutex poisoned".into()))
+    }
+
+    fn slot(&self, name: &str) -> Result<MemorySlot> {
+        let mut registry = self.registry()?;
+        Ok(Arc::clone(registry.entry(name.to_string()).or_default()))
+    }
+
+    fn lock_slot<'a>(name: &str, slot: &'a MemorySlot) -> Result<MutexGuard<'a, Option<Memvid>>> {
+        slot.lock()
+            .map_err(|_| MemvidError::Lock(format!("memory {name} mutex poisoned")))
+    }
+
+    /// Run `f` on the memory, opening it first if needed. The file is opened under the
+    /// memory's own lock, so other memories are not held up.
+    fn with_memory<F>(&self, name: &str, f: F) -> Result<Value>
+    where
+        F: FnOnce(&mut Memvid) -> Result<Value>,
+    {
+        let path = self.memory_path(name)?;
+        let slot = self.slot(name)?;
+        let mut guard = Self::lock_slot(name, &slot)?;
+        let memvid = match guard.take() {
+            Some(memvid) => guard.insert(memvid),
+            None if self.config.read_only => guard.insert(Memvid::open_read_only(&path)?),
+            None => guard.insert(Memvid::open(&path)?),
+        };
+        f(memvid)
+    }
+
+    fn list_memories(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&self.config.root)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "mv2") {
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn create_memory(&self, name: &str) -> ServerResponse {
+        if self.config.read_only {
+            return ServerResponse::error(403, "server is read-only");
+        }
+        let result = self.memory_path(name).and_then(|path| {
+            let exists = || MemvidError::InvalidQuery {
+                reason: format!("memory already exists: {name}"),
+            };
+            // Held until the new slot is in place, so concurrent creates cannot both succeed.
+            let mut registry = self.registry()?;
+            let Entry::Vacant(entry) = registry.entry(name.to_string()) else {
+                return Err(exists());
+            };
+            // Claim the path with create-new; `Memvid::create` then lays out the empty file.
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => return Err(exists()),
+                Err(err) => return Err(err.into()),
+            }
+            let created = Memvid::create(&path).and_then(|mut memvid| {
+                memvid.enable_lex()?;
+                memvid.commit()?;
+                Ok(memvid)
+            });
+            let memvid = match created {
+                Ok(memvid) => memvid,
+                Err(err) => {
+                    let _ = std::fs::remove_file(&path);
+                    return Err(err);
+                }
+            };
+            entry.insert(Arc::new(Mutex::new(Some(memvid))));
+            Ok(json!({ "name": name }))
+        });
+        match result {
+            Ok(body) => ServerResponse { status: 201, body },
+            Err(err) => ServerResponse::from_error(&err),
+        }
+    }
+
+    /// Doctor needs exclusive access to the file, so the cached handle is closed first. The
+    /// memory stays locked until doctor returns; the next request reopens it.
+    fn doctor(&self, name: &str, options: DoctorOptions) -> Result<Value> {
+        let path = self.memory_path(name)?;
+        let slot = self.slot(name)?;
+        let mut guard = Self::lock_slot(name, &slot)?;
+        drop(guard.take());
+        to_value(&Memvid::doctor(&path, options)?)
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    serde_json::from_slice(body).map_err(|err| MemvidError::InvalidQuery {
+        reason: format!("invalid request body: {err}"),
+    })
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|err| MemvidError::InvalidQuery {
+        reason: format!("failed to encode response: {err}"),
+    })
+}
+
+/// Convenience wrapper: serve `root` on `bind` until the listener fails.
+pub fn serve(bind: &str, root: &Path) -> Result<()> {
+    MemoryServer::new(ServerConfig::new(bind, root)).serve()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_put_search_and_stats() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let server = MemoryServer::new(ServerConfig::new("127.0.0.1:0", dir.path()));
+
+        assert_eq!(server.handle("POST", "/memories/notes", b"").status, 201);
+        assert_eq!(server.handle("POST", "/memories/notes", b"").status, 400);
+        assert_eq!(
+            server.handle("GET", "/memories", b"").body,
+            json!(["notes"])
+        );
+
+        let put = server.handle(
+            "POST",
+            "/memories/notes/put",
+            br#"{"text":"server routed payload"}"#,
+        );
+        assert_eq!(put.status, 200, "{:?}", put.body);
+        assert_eq!(put.body["frame_id"], json!(0), "{:?}", put.body);
+        assert!(put.body["sequence"].is_u64(), "{:?}", put.body);
+
+        let search = server.handle(
+            "POST",
+            "/memories/notes/search",
+            br#"{"query":"routed","top_k":5,"snippet_chars":120}"#,
+        );
+        assert_eq!(search.status, 200, "{:?}", search.body);
+        assert_eq!(search.body["hits"].as_array().map(Vec::len), Some(1));
+
+        let stats = server.handle("GET", "/memories/notes/stats", b"");
+        assert_eq!(stats.body["frame_count"], json!(1));
+
+        assert_eq!(server.handle("GET", "/memories/../stats", b"").status, 400);
+        assert_eq!(
+            server.handle("GET", "/memories/missing/stats", b"").status,
+            404
+        );
+    }
+
+    fn http_get(addr: std::net::SocketAddr, path: &str) -> String {
+        use std::io::Write;
+
+        // The listener may still be starting up.
+        let mut stream = loop {
+            match std::net::TcpStream::connect(addr) {
+                Ok(stream) => break stream,
+                Err(_) => std::thread::sleep(std::time::Duration::from_millis(10)),
+            }
+        };
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        )
+        .expect("send");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).expect("read");
+        response
+    }
+
+    #[test]
+    fn busy_memory_does_not_block_other_memories() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("free port");
+        let server = Arc::new(MemoryServer::new(
+            ServerConfig::new(addr.to_string(), dir.path()).workers(2),
+        ));
+        for name in ["busy", "idle"] {
+            assert_eq!(
+                server
+                    .handle("POST", &format!("/memories/{name}"), b"")
+                    .status,
+                201
+            );
+        }
+        let listener = Arc::clone(&server);
+        std::thread::spawn(move || listener.serve());
+
+        // Stand in for a slow call by holding the busy memory's lock.
+        let busy = server.slot("busy").expect("slot");
+        let held = busy.lock().expect("lock");
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let waiting = std::thread::spawn(move || {
+            sender
+                .send(http_get(addr, "/memories/busy/stats"))
+                .expect("report");
+        });
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        let idle = http_get(addr, "/memories/idle/stats");
+        assert!(idle.starts_with("HTTP/1.1 200"), "{idle}");
+        assert!(receiver.try_recv().is_err(), "busy request still waiting");
+
+        drop(held);
+        let busy = receiver.recv().expect("busy response");
+        assert!(busy.starts_with("HTTP/1.1 200"), "{busy}");
+        waiting.join().expect("waiting thread");
+    }
+
+    #[test]
+    fn concurrent_creates_make_one_memory_and_keep_existing_files() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let server = MemoryServer::new(ServerConfig::new("127.0.0.1:0", dir.path()));
+        let statuses: Vec<u16> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| server.handle("POST", "/memories/race", b"").status))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("create"))
+                .collect()
+        });
+        assert_eq!(statuses.iter().filter(|&&status| status == 201).count(), 1);
+        assert!(
+            statuses
+                .iter()
+                .all(|&status| status == 201 || status == 400)
+        );
+
+        // A file the registry has not seen yet is not overwritten either.
+        let existing = dir.path().join("existing.mv2");
+        std::fs::write(&existing, b"not yours").expect("write");
+        assert_eq!(server.handle("POST", "/memories/existing", b"").status, 400);
+        assert_eq!(std::fs::read(&existing).expect("read"), b"not yours");
+    }
+
+    #[test]
+    fn doctor_closes_the_cached_handle_until_the_next_request() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let server = MemoryServer::new(ServerConfig::new("127.0.0.1:0", dir.path()));
+        assert_eq!(server.handle("POST", "/memories/notes", b"").status, 201);
+
+        let doctor = server.handle("POST", "/memories/notes/doctor", b"");
+        assert_eq!(doctor.status, 200, "{:?}", doctor.body);
+        let slot = server.slot("notes").expect("slot");
+        assert!(slot.lock().expect("lock").is_none(), "reopened lazily");
+        assert_eq!(
+            server.handle("GET", "/memories/notes/stats", b"").status,
+            200
+        );
+        assert!(slot.lock().expect("lock").is_some());
+    }
+
+    #[test]
+    fn read_only_rejects_writes() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let server =
+            MemoryServer::new(ServerConfig::new("127.0.0.1:0", dir.path()).read_only(true));
+        assert_eq!(server.handle("POST", "/memories/notes", b"").status, 403);
+        assert_eq!(
+            server
+                .handle("POST", "/memories/notes/put", br#"{"text":"x"}"#)
+                .status,
+            403
+        );
+    }
+}
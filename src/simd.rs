@@ -1,10 +1,11 @@
 //! SIMD-accelerated distance calculations for vector search.
 //!
 //! This module provides optimized L2 (Euclidean) distance functions using
-//! the `wide` crate for portable SIMD across `x86_64` and aarch64.
+//! the `wide` crate for portable SIMD across `x86_64` and aarch64, plus the
+//! int8 dot product and hamming kernels used by scalar-quantized indexes.
 
 #[cfg(feature = "simd")]
-use wide::f32x8;
+use wide::{f32x8, i32x8, u64x4};
 
 /// Compute squared L2 distance between two f32 slices using SIMD.
 ///
@@ -69,6 +70,60 @@ pub fn l2_distance_simd(a: &[f32], b: &[f32]) -> f32 {
     l2_distance_squared_simd(a, b).sqrt()
 }
 
+/// Dot product of two int8 slices using SIMD, accumulated in i32.
+///
+/// A 384-dim product of full-range codes stays far below `i32::MAX`.
+#[cfg(feature = "simd")]
+#[must_use]
+pub fn dot_i8_simd(a: &[i8], b: &[i8]) -> i32 {
+    debug_assert_eq!(a.len(), b.len(), "vectors must have same length");
+
+    let chunks = a.len() / 8;
+    let mut sum = i32x8::ZERO;
+    for (a_chunk, b_chunk) in a.chunks_exact(8).zip(b.chunks_exact(8)) {
+        let a_lanes = i32x8::new(std::array::from_fn(|i| i32::from(a_chunk[i])));
+        let b_lanes = i32x8::new(std::array::from_fn(|i| i32::from(b_chunk[i])));
+        sum += a_lanes * b_lanes;
+    }
+
+    let sum_array: [i32; 8] = sum.into();
+    let mut total: i32 = sum_array.iter().sum();
+    let offset = chunks * 8;
+    for (x, y) in a[offset..].iter().zip(&b[offset..]) {
+        total += i32::from(*x) * i32::from(*y);
+    }
+    total
+}
+
+/// Number of differing bits between two packed bit vectors using SIMD.
+#[cfg(feature = "simd")]
+#[must_use]
+pub fn hamming_distance_simd(a: &[u8], b: &[u8]) -> u32 {
+    debug_assert_eq!(a.len(), b.len(), "vectors must have same length");
+
+    let chunks = a.len() / 32;
+    let mut total = 0u32;
+    for (a_block, b_block) in a.chunks_exact(32).zip(b.chunks_exact(32)) {
+        let a_lanes = u64x4::new(std::array::from_fn(|i| read_u64_le(a_block, i * 8)));
+        let b_lanes = u64x4::new(std::array::from_fn(|i| read_u64_le(b_block, i * 8)));
+        let diff: [u64; 4] = (a_lanes ^ b_lanes).into();
+        total += diff.iter().map(|lane| lane.count_ones()).sum::<u32>();
+    }
+
+    let offset = chunks * 32;
+    total
+        + a[offset..]
+            .iter()
+            .zip(&b[offset..])
+            .map(|(x, y)| (x ^ y).count_ones())
+            .sum::<u32>()
+}
+
+#[cfg(feature = "simd")]
+fn read_u64_le(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(std::array::from_fn(|i| bytes[offset + i]))
+}
+
 // Scalar fallbacks when SIMD feature is disabled
 
 /// Compute squared L2 distance using scalar math.
@@ -89,6 +144,24 @@ pub fn l2_distance_simd(a: &[f32], b: &[f32]) -> f32 {
     l2_distance_squared_simd(a, b).sqrt()
 }
 
+/// Dot product of two int8 slices using scalar math.
+#[cfg(not(feature = "simd"))]
+pub fn dot_i8_simd(a: &[i8], b: &[i8]) -> i32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| i32::from(*x) * i32::from(*y))
+        .sum()
+}
+
+/// Number of differing bits between two packed bit vectors using scalar math.
+#[cfg(not(feature = "simd"))]
+pub fn hamming_distance_simd(a: &[u8], b: &[u8]) -> u32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x ^ y).count_ones())
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +209,22 @@ mod tests {
             dist_scalar
         );
     }
+
+    #[test]
+    fn test_int8_and_hamming_kernels() {
+        let a: Vec<i8> = (0..387).map(|i| (i % 255 - 127) as i8).collect();
+        let b: Vec<i8> = (0..387).map(|i| ((i * 7) % 255 - 127) as i8).collect();
+        let expected: i32 = a
+            .iter()
+            .zip(&b)
+            .map(|(x, y)| i32::from(*x) * i32::from(*y))
+            .sum();
+        assert_eq!(dot_i8_simd(&a, &b), expected);
+
+        let x: Vec<u8> = (0..49).map(|i| (i * 37) as u8).collect();
+        let y: Vec<u8> = (0..49).map(|i| (i * 11 + 3) as u8).collect();
+        let expected: u32 = x.iter().zip(&y).map(|(p, q)| (p ^ q).count_ones()).sum();
+        assert_eq!(hamming_distance_simd(&x, &y), expected);
+        assert_eq!(hamming_distance_simd(&x, &x), 0);
+    }
 }
@@ -0,0 +1,322 @@
+//! SPLADE [`SparseEncoder`](crate::SparseEncoder) for the learned sparse track.
+//!
+//! [`SpladeEncoder`] runs a SPLADE masked-language model exported to ONNX, loaded from the
+//! models directory like the cross-encoder reranker. A passage's weight for each vocabulary
+//! term is the largest `log(1 + relu(logit))` over its tokens, so the vector covers the words
+//! it contains plus the expansions the model predicts for them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use memvid_core::splade::{SpladeConfig, SpladeEncoder};
+//! use memvid_core::SearchMode;
+//!
+//! mem.set_sparse_encoder(Arc::new(SpladeEncoder::new(SpladeConfig::default())?));
+//! mem.index_sparse(32)?;
+//! request.mode = SearchMode::Sparse;
+//! let response = mem.search(request)?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ndarray::Array;
+use ort::session::Session;
+use ort::value::Tensor;
+use tokenizers::{Tokenizer, TruncationDirection, TruncationParams, TruncationStrategy};
+
+use crate::error::{MemvidError, Result};
+use crate::inference_device::{InferenceDevice, build_session};
+use crate::types::sparse::{SparseEncoder, SparseVector};
+
+/// SPLADE model info for the models registry
+#[derive(Debug, Clone)]
+pub struct SpladeModelInfo {
+    /// Model identifier, also the directory name under the models directory
+    pub name: &'static str,
+    /// URL for ONNX model
+    pub model_url: &'static str,
+    /// URL for tokenizer JSON
+    pub tokenizer_url: &'static str,
+    /// Model size in MB
+    pub size_mb: f32,
+    /// Maximum sequence length of a passage
+    pub max_seq_len: usize,
+    /// Whether this is the default model
+    pub is_default: bool,
+}
+
+/// Available SPLADE models
+pub static SPLADE_MODELS: &[SpladeModelInfo] = &[SpladeModelInfo {
+    name: "Splade_PP_en_v1",
+    model_url: "https://huggingface.co/Qdrant/Splade_PP_en_v1/resolve/main/model.onnx",
+    tokenizer_url: "https://huggingface.co/Qdrant/Splade_PP_en_v1/resolve/main/tokenizer.json",
+    size_mb: 532.0,
+    max_seq_len: 512,
+    is_default: true,
+}];
+
+/// Get SPLADE model info by name
+#[must_use]
+pub fn get_splade_model_info(name: &str) -> Option<&'static SpladeModelInfo> {
+    SPLADE_MODELS.iter().find(|m| m.name == name)
+}
+
+/// Get default SPLADE model info
+#[must_use]
+pub fn default_splade_model_info() -> &'static SpladeModelInfo {
+    &SPLADE_MODELS[0]
+}
+
+/// Get the expected path for a SPLADE model in the models directory
+#[must_use]
+pub fn splade_model_path(models_dir: &Path, name: &str) -> PathBuf {
+    models_dir.join(name).join("model.onnx")
+}
+
+/// Get the expected path for a SPLADE tokenizer in the models directory
+#[must_use]
+pub fn splade_tokenizer_path(models_dir: &Path, name: &str) -> PathBuf {
+    models_dir.join(name).join("tokenizer.json")
+}
+
+/// Configuration for the local SPLADE encoder
+#[derive(Debug, Clone)]
+pub struct SpladeConfig {
+    /// Model name from [`SPLADE_MODELS`]
+    pub model_name: String,
+    /// Directory holding `<model_name>/model.onnx` and `<model_name>/tokenizer.json`
+    pub models_dir: PathBuf,
+    /// Device to run the model on; falls back to CPU if unavailable (default: auto)
+    pub device: InferenceDevice,
+    /// Passages per inference call (default: 8)
+    pub batch_size: usize,
+    /// Heaviest terms kept per passage; queries keep all of theirs (default: 256)
+    pub max_terms: usize,
+}
+
+impl Default for SpladeConfig {
+    fn default() -> Self {
+        let models_dir = dirs_next::cache_dir()
+            .map(|p| p.join("memvid").join("sparse-models"))
+            .unwrap_or_else(|| PathBuf::from(".memvid-cache/sparse-models"));
+        Self {
+            model_name: default_splade_model_info().name.to_string(),
+            models_dir,
+            device: InferenceDevice::default(),
+            batch_size: 8,
+            max_terms: 256,
+        }
+    }
+}
+
+fn encoding_error(reason: String) -> MemvidError {
+    MemvidError::EmbeddingFailed {
+        reason: reason.into(),
+    }
+}
+
+/// Sparse encoder running a SPLADE model locally.
+pub struct SpladeEncoder {
+    model_info: &'static SpladeModelInfo,
+    kind: String,
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    batch_size: usize,
+    max_terms: usize,
+    device: InferenceDevice,
+}
+
+impl SpladeEncoder {
+    /// Load the model and tokenizer, returning download instructions if they are missing.
+    pub fn new(config: SpladeConfig) -> Result<Self> {
+        let model_info = get_splade_model_info(&config.model_name).ok_or_else(|| {
+            encoding_error(format!(
+                "unknown SPLADE model '{}'; available: {}",
+                config.model_name,
+                SPLADE_MODELS
+                    .iter()
+                    .map(|m| m.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        let model_path = splade_model_path(&config.models_dir, model_info.name);
+        let tokenizer_path = splade_tokenizer_path(&config.models_dir, model_info.name);
+        for (path, url) in [
+            (&model_path, model_info.model_url),
+            (&tokenizer_path, model_info.tokenizer_url),
+        ] {
+            if !path.exists() {
+                return Err(encoding_error(format!(
+                    "SPLADE file not found at {}. Please download manually:\n\
+                     mkdir -p {} && curl -L '{}' -o '{}'",
+                    path.display(),
+                    path.parent().unwrap_or(&config.models_dir).display(),
+                    url,
+                    path.display()
+                )));
+            }
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| encoding_error(format!("Failed to load tokenizer: {e}")))?;
+        tokenizer.with_padding(None);
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: model_info.max_seq_len,
+                strategy: TruncationStrategy::LongestFirst,
+                stride: 0,
+                direction: TruncationDirection::Right,
+            }))
+            .map_err(|e| encoding_error(format!("Failed to apply truncation config: {e}")))?;
+
+        let (session, device) = build_session(config.device, &model_path, 4)
+            .map_err(|e| encoding_error(format!("Failed to load SPLADE model: {e}")))?;
+        tracing::info!(model = %model_info.name, device = %device, "SPLADE encoder loaded");
+
+        Ok(Self {
+            model_info,
+            kind: format!("splade/{}", model_info.name),
+            session: Mutex::new(session),
+            tokenizer,
+            batch_size: config.batch_size.max(1),
+            max_terms: config.max_terms.max(1),
+            device,
+        })
+    }
+
+    /// Get model info
+    #[must_use]
+    pub fn model_info(&self) -> &'static SpladeModelInfo {
+        self.model_info
+    }
+
+    /// Device the session is running on
+    #[must_use]
+    pub fn device(&self) -> InferenceDevice {
+        self.device
+    }
+
+    /// Term weights of each text, in input order
+    fn encode_texts(&self, texts: &[&str]) -> Result<Vec<SparseVector>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| encoding_error(format!("Tokenization failed: {e}")))?;
+
+        let batch_size = encodings.len();
+        let max_length = encodings
+            .iter()
+            .map(|encoding| encoding.get_ids().len())
+            .max()
+            .unwrap_or(0);
+        let mut input_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        let mut attention_mask: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        let mut token_type_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        for encoding in &encodings {
+            let padding = max_length - encoding.get_ids().len();
+            input_ids.extend(encoding.get_ids().iter().map(|id| i64::from(*id)));
+            input_ids.extend(std::iter::repeat_n(0, padding));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|m| i64::from(*m)));
+            attention_mask.extend(std::iter::repeat_n(0, padding));
+            token_type_ids.extend(encoding.get_type_ids().iter().map(|t| i64::from(*t)));
+            token_type_ids.extend(std::iter::repeat_n(0, padding));
+        }
+        let mask = attention_mask.clone();
+
+        let tensor = |name: &str, values: Vec<i64>| {
+            Array::from_shape_vec((batch_size, max_length), values)
+                .map_err(|e| e.to_string())
+                .and_then(|array| Tensor::from_array(array).map_err(|e| e.to_string()))
+                .map_err(|e| encoding_error(format!("Failed to create {name} tensor: {e}")))
+        };
+        let input_ids = tensor("input_ids", input_ids)?;
+        let attention_mask = tensor("attention_mask", attention_mask)?;
+        let token_type_ids = tensor("token_type_ids", token_type_ids)?;
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| MemvidError::Lock("Failed to lock session".into()))?;
+        let input_names: Vec<String> = session.inputs.iter().map(|i| i.name.clone()).collect();
+        let output_name = session
+            .outputs
+            .first()
+            .map_or_else(|| "logits".to_string(), |o| o.name.clone());
+        let outputs = if input_names.len() >= 3 {
+            session.run(ort::inputs![
+                input_names[0].clone() => input_ids,
+                input_names[1].clone() => attention_mask,
+                input_names[2].clone() => token_type_ids
+            ])
+        } else {
+            session.run(ort::inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask
+            ])
+        }
+        .map_err(|e| encoding_error(format!("SPLADE inference failed: {e}")))?;
+
+        let (shape, data) = outputs[output_name.as_str()]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| encoding_error(format!("Failed to extract logits: {e}")))?;
+        // Logits are [batch, tokens, vocabulary]
+        let vocab = shape
+            .get(2)
+            .and_then(|dim| usize::try_from(*dim).ok())
+            .unwrap_or(0);
+        if vocab == 0 || data.len() != batch_size * max_length * vocab {
+            return Err(encoding_error(format!(
+                "unexpected logits shape: {shape:?}"
+            )));
+        }
+
+        let mut vectors = Vec::with_capacity(batch_size);
+        for row in 0..batch_size {
+            let mut weights = vec![0.0f32; vocab];
+            for token in 0..max_length {
+                if mask[row * max_length + token] == 0 {
+                    continue;
+                }
+                let start = (row * max_length + token) * vocab;
+                for (weight, logit) in weights.iter_mut().zip(&data[start..start + vocab]) {
+                    *weight = weight.max(logit.max(0.0).ln_1p());
+                }
+            }
+            vectors.push(SparseVector::new(
+                weights
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(term, weight)| Some((u32::try_from(term).ok()?, weight))),
+            ));
+        }
+        Ok(vectors)
+    }
+}
+
+impl SparseEncoder for SpladeEncoder {
+    fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    fn encode(&self, text: &str) -> Result<SparseVector> {
+        Ok(self.encode_batch(&[text])?.pop().unwrap_or_default())
+    }
+
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<SparseVector>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            vectors.extend(self.encode_texts(batch)?.into_iter().map(|mut vector| {
+                vector.prune(self.max_terms);
+                vector
+            }));
+        }
+        Ok(vectors)
+    }
+
+    fn encode_query(&self, text: &str) -> Result<SparseVector> {
+        Ok(self.encode_texts(&[text])?.pop().unwrap_or_default())
+    }
+}
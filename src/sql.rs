@@ -0,0 +1,866 @@
+//! Read-only SQL subset for `Memvid::query`.
+//!
+//! ```text
+//! SELECT <* | COUNT(*) | column [AS label], ...>
+//! FROM <frames | cards>
+//! [WHERE <condition>]
+//! [ORDER BY column [ASC | DESC], ...]
+//! [LIMIT n] [OFFSET n]
+//! ```
+//!
+//! Conditions combine `=`, `!=`/`<>`, `<`, `<=`, `>`, `>=`, `[NOT] LIKE`, `[NOT] IN (...)` and
+//! `IS [NOT] NULL` with `AND`, `OR`, `NOT` and parentheses. Keywords are case-insensitive;
+//! strings use single quotes. Timestamp columns also compare against date strings such as
+//! `'2024-06-01'`.
+
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MemvidError, Result};
+use crate::types::{MetaType, MetaValue};
+
+/// Columns of the `frames` table. `meta.<key>` additionally reads `extra_metadata`.
+pub const FRAME_COLUMNS: &[&str] = &[
+    "id",
+    "uri",
+    "title",
+    "kind",
+    "track",
+    "ts",
+    "anchor_ts",
+    "role",
+    "mime",
+    "tags",
+    "labels",
+    "parent_id",
+    "chunk_index",
+    "chunk_count",
+    "supersedes",
+    "size",
+    "search_text",
+];
+
+/// Columns of the `cards` table.
+pub const CARD_COLUMNS: &[&str] = &[
+    "id",
+    "kind",
+    "entity",
+    "slot",
+    "value",
+    "polarity",
+    "event_date",
+    "document_date",
+    "version_key",
+    "version_relation",
+    "frame_id",
+    "source_uri",
+    "engine",
+    "confidence",
+    "created_at",
+    "superseded_by",
+];
+
+/// Columns holding Unix timestamps.
+const TIME_COLUMNS: &[&str] = &[
+    "ts",
+    "anchor_ts",
+    "event_date",
+    "document_date",
+    "created_at",
+];
+
+/// A cell of a [`QueryResult`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum QueryValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl QueryValue {
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            #[allow(clippy::cast_precision_loss)]
+            Self::Int(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            Self::Text(value) => value.trim().parse().ok(),
+            Self::Null | Self::Bool(_) => None,
+        }
+    }
+
+    /// SQL comparison; `None` when either side is null or the types are incomparable.
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Null, _) | (_, Self::Null) => None,
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+            (Self::Text(a), Self::Text(b)) => Some(a.cmp(b)),
+            (Self::Bool(a), Self::Bool(b)) => Some(a.cmp(b)),
+            (Self::Bool(a), Self::Text(b)) => Some(a.cmp(&b.parse().ok()?)),
+            (Self::Text(a), Self::Bool(b)) => Some(a.parse::<bool>().ok()?.cmp(b)),
+            _ => Some(self.as_f64()?.total_cmp(&other.as_f64()?)),
+        }
+    }
+
+    /// Ordering for `ORDER BY`: nulls first, incomparable values by type.
+    fn sort_cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+            .unwrap_or_else(|| self.type_rank().cmp(&other.type_rank()))
+    }
+
+    fn type_rank(&self) -> u8 {
+        match self {
+            Self::Null => 0,
+            Self::Bool(_) => 1,
+            Self::Int(_) | Self::Float(_) => 2,
+            Self::Text(_) => 3,
+        }
+    }
+
+    fn to_csv_field(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Bool(value) => value.to_string(),
+            Self::Int(value) => value.to_string(),
+            Self::Float(value) => value.to_string(),
+            Self::Text(value) if value.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            }
+            Self::Text(value) => value.clone(),
+        }
+    }
+}
+
+impl From<Option<String>> for QueryValue {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(Self::Null, Self::Text)
+    }
+}
+
+impl From<Option<i64>> for QueryValue {
+    fn from(value: Option<i64>) -> Self {
+        value.map_or(Self::Null, Self::Int)
+    }
+}
+
+/// Rows returned by `Memvid::query`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<QueryValue>>,
+}
+
+impl QueryResult {
+    /// RFC 4180 CSV with a header row.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header: Vec<QueryValue> = self
+            .columns
+            .iter()
+            .map(|column| QueryValue::Text(column.clone()))
+            .collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let fields: Vec<String> = row.iter().map(QueryValue::to_csv_field).collect();
+            let _ = writeln!(csv, "{}", fields.join(","));
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Table {
+    Frames,
+    Cards,
+}
+
+impl Table {
+    fn has_column(self, column: &str) -> bool {
+        match self {
+            Self::Frames => {
+                FRAME_COLUMNS.contains(&column)
+                    || column
+                        .strip_prefix("meta.")
+                        .is_some_and(|key| !key.is_empty())
+            }
+            Self::Cards => CARD_COLUMNS.contains(&column),
+        }
+    }
+
+    fn columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Frames => FRAME_COLUMNS,
+            Self::Cards => CARD_COLUMNS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Projection {
+    All,
+    Count,
+    /// `(column, label)` pairs.
+    Columns(Vec<(String, String)>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn accepts(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering == Ordering::Equal,
+            Self::Ne => ordering != Ordering::Equal,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Le => ordering != Ordering::Greater,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Compare {
+        column: String,
+        op: CmpOp,
+        value: QueryValue,
+    },
+    Like {
+        column: String,
+        pattern: String,
+        negated: bool,
+    },
+    In {
+        column: String,
+        values: Vec<QueryValue>,
+        negated: bool,
+    },
+    IsNull {
+        column: String,
+        negated: bool,
+    },
+}
+
+impl Condition {
+    /// Evaluate against a row whose cells are looked up by column name.
+    pub(crate) fn matches(&self, row: &dyn Fn(&str) -> QueryValue) -> bool {
+        match self {
+            Self::And(left, right) => left.matches(row) && right.matches(row),
+            Self::Or(left, right) => left.matches(row) || right.matches(row),
+            Self::Not(inner) => !inner.matches(row),
+            Self::Compare { column, op, value } => row(column)
+                .compare(value)
+                .is_some_and(|ordering| op.accepts(ordering)),
+            Self::Like {
+                column,
+                pattern,
+                negated,
+            } => match row(column) {
+                QueryValue::Null => false,
+                QueryValue::Text(text) => like(&text, pattern) != *negated,
+                other => like(&other.to_csv_field(), pattern) != *negated,
+            },
+            Self::In {
+                column,
+                values,
+                negated,
+            } => {
+                let cell = row(column);
+                if cell == QueryValue::Null {
+                    return false;
+                }
+                values
+                    .iter()
+                    .any(|value| cell.compare(value) == Some(Ordering::Equal))
+                    != *negated
+            }
+            Self::IsNull { column, negated } => (row(column) == QueryValue::Null) != *negated,
+        }
+    }
+
+    /// Inclusive `(lower, upper)` bounds on `column` implied by the top-level `AND` chain.
+    pub(crate) fn int_bounds(&self, column: &str) -> (Option<i64>, Option<i64>) {
+        match self {
+            Self::And(left, right) => {
+                let (left_lo, left_hi) = left.int_bounds(column);
+                let (right_lo, right_hi) = right.int_bounds(column);
+                (
+                    left_lo.max(right_lo),
+                    match (left_hi, right_hi) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    },
+                )
+            }
+            Self::Compare {
+                column: name,
+                op,
+                value: QueryValue::Int(value),
+            } if name == column => match op {
+                CmpOp::Eq => (Some(*value), Some(*value)),
+                CmpOp::Gt => (value.checked_add(1), None),
+                CmpOp::Ge => (Some(*value), None),
+                CmpOp::Lt => (None, value.checked_sub(1)),
+                CmpOp::Le => (None, Some(*value)),
+                CmpOp::Ne => (None, None),
+            },
+            _ => (None, None),
+        }
+    }
+
+    fn columns<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            Self::And(left, right) | Self::Or(left, right) => {
+                left.columns(out);
+                right.columns(out);
+            }
+            Self::Not(inner) => inner.columns(out),
+            Self::Compare { column, .. }
+            | Self::Like { column, .. }
+            | Self::In { column, .. }
+            | Self::IsNull { column, .. } => out.push(column),
+        }
+    }
+}
+
+/// A parsed `SELECT` statement.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Select {
+    pub projection: Projection,
+    pub table: Table,
+    pub filter: Option<Condition>,
+    /// `(column, descending)` keys.
+    pub order_by: Vec<(String, bool)>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl Select {
+    /// Output column labels.
+    pub(crate) fn labels(&self) -> Vec<String> {
+        match &self.projection {
+            Projection::All => self
+                .table
+                .columns()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            Projection::Count => vec!["count".to_string()],
+            Projection::Columns(columns) => {
+                columns.iter().map(|(_, label)| label.clone()).collect()
+            }
+        }
+    }
+
+    /// Source columns read for each output cell.
+    pub(crate) fn projected_columns(&self) -> Vec<&str> {
+        match &self.projection {
+            Projection::All => self.table.columns().to_vec(),
+            Projection::Count => Vec::new(),
+            Projection::Columns(columns) => {
+                columns.iter().map(|(column, _)| column.as_str()).collect()
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut columns = self.projected_columns();
+        if let Some(filter) = &self.filter {
+            filter.columns(&mut columns);
+        }
+        columns.extend(self.order_by.iter().map(|(column, _)| column.as_str()));
+        match columns
+            .into_iter()
+            .find(|column| !self.table.has_column(column))
+        {
+            Some(unknown) => Err(invalid(format!("unknown column '{unknown}'"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse a statement and check its columns against the table.
+pub(crate) fn parse(sql: &str) -> Result<Select> {
+    let tokens = tokenize(sql)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    let select = parser.select()?;
+    if let Some(token) = parser.peek() {
+        return Err(invalid(format!("unexpected {token:?} after statement")));
+    }
+    select.validate()?;
+    Ok(select)
+}
+
+fn invalid(reason: String) -> MemvidError {
+    MemvidError::InvalidQuery { reason }
+}
+
+/// Case-insensitive `LIKE` with `%` and `_` wildcards.
+fn like(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let (mut t, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '_' || pattern[p] == text[t]) {
+            t += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '%' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '%')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Symbol(&'static str),
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>> {
+    const SYMBOLS: &[&str] = &["<=", ">=", "!=", "<>", "=", "<", ">", ",", "(", ")", "*"];
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        if ch.is_whitespace() || ch == ';' {
+            index += 1;
+        } else if ch == '\'' {
+            let mut value = String::new();
+            index += 1;
+            loop {
+                match chars.get(index) {
+                    Some('\'') if chars.get(index + 1) == Some(&'\'') => {
+                        value.push('\'');
+                        index += 2;
+                    }
+                    Some('\'') => {
+                        index += 1;
+                        break;
+                    }
+                    Some(ch) => {
+                        value.push(*ch);
+                        index += 1;
+                    }
+                    None => return Err(invalid("unterminated string literal".into())),
+                }
+            }
+            tokens.push(Token::Str(value));
+        } else if ch.is_ascii_digit()
+            || (ch == '-' && chars.get(index + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = index;
+            index += 1;
+            while chars
+                .get(index)
+                .is_some_and(|ch| ch.is_ascii_digit() || *ch == '.')
+            {
+                index += 1;
+            }
+            tokens.push(Token::Number(chars[start..index].iter().collect()));
+        } else if ch.is_alphabetic() || ch == '_' {
+            let start = index;
+            while chars
+                .get(index)
+                .is_some_and(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '.' | '-'))
+            {
+                index += 1;
+            }
+            tokens.push(Token::Ident(chars[start..index].iter().collect()));
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| {
+            symbol
+                .chars()
+                .enumerate()
+                .all(|(offset, expected)| chars.get(index + offset) == Some(&expected))
+        }) {
+            index += symbol.len();
+            tokens.push(Token::Symbol(symbol));
+        } else {
+            return Err(invalid(format!("unexpected character '{ch}'")));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn select(&mut self) -> Result<Select> {
+        self.expect_keyword("SELECT")?;
+        let projection = self.projection()?;
+        self.expect_keyword("FROM")?;
+        let table = match self.ident()?.to_ascii_lowercase().as_str() {
+            "frames" => Table::Frames,
+            "cards" | "memories" => Table::Cards,
+            other => return Err(invalid(format!("unknown table '{other}'"))),
+        };
+        let filter = if self.keyword("WHERE") {
+            Some(self.or_condition()?)
+        } else {
+            None
+        };
+        let mut order_by = Vec::new();
+        if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let column = self.column()?;
+                let descending = if self.keyword("DESC") {
+                    true
+                } else {
+                    self.keyword("ASC");
+                    false
+                };
+                order_by.push((column, descending));
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+        }
+        let limit = if self.keyword("LIMIT") {
+            Some(self.count()?)
+        } else {
+            None
+        };
+        let offset = if self.keyword("OFFSET") {
+            self.count()?
+        } else {
+            0
+        };
+        let mut select = Select {
+            projection,
+            table,
+            filter,
+            order_by,
+            limit,
+            offset,
+        };
+        if let Some(filter) = select.filter.as_mut() {
+            coerce_dates(filter);
+        }
+        Ok(select)
+    }
+
+    fn projection(&mut self) -> Result<Projection> {
+        if self.symbol("*") {
+            return Ok(Projection::All);
+        }
+        if self.peek_keyword("COUNT") {
+            self.position += 1;
+            self.expect_symbol("(")?;
+            self.expect_symbol("*")?;
+            self.expect_symbol(")")?;
+            return Ok(Projection::Count);
+        }
+        let mut columns = Vec::new();
+        loop {
+            let column = self.column()?;
+            let label = if self.keyword("AS") {
+                self.ident()?
+            } else {
+                column.clone()
+            };
+            columns.push((column, label));
+            if !self.symbol(",") {
+                return Ok(Projection::Columns(columns));
+            }
+        }
+    }
+
+    fn or_condition(&mut self) -> Result<Condition> {
+        let mut condition = self.and_condition()?;
+        while self.keyword("OR") {
+            let rhs = self.and_condition()?;
+            condition = Condition::Or(Box::new(condition), Box::new(rhs));
+        }
+        Ok(condition)
+    }
+
+    fn and_condition(&mut self) -> Result<Condition> {
+        let mut condition = self.unary_condition()?;
+        while self.keyword("AND") {
+            let rhs = self.unary_condition()?;
+            condition = Condition::And(Box::new(condition), Box::new(rhs));
+        }
+        Ok(condition)
+    }
+
+    fn unary_condition(&mut self) -> Result<Condition> {
+        if self.keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.unary_condition()?)));
+        }
+        if self.symbol("(") {
+            let condition = self.or_condition()?;
+            self.expect_symbol(")")?;
+            return Ok(condition);
+        }
+        let column = self.column()?;
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(Condition::IsNull { column, negated });
+        }
+        let negated = self.keyword("NOT");
+        if self.keyword("LIKE") {
+            let QueryValue::Text(pattern) = self.literal()? else {
+                return Err(invalid("LIKE expects a string pattern".into()));
+            };
+            return Ok(Condition::Like {
+                column,
+                pattern,
+                negated,
+            });
+        }
+        if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let mut values = vec![self.literal()?];
+            while self.symbol(",") {
+                values.push(self.literal()?);
+            }
+            self.expect_symbol(")")?;
+            return Ok(Condition::In {
+                column,
+                values,
+                negated,
+            });
+        }
+        if negated {
+            return Err(invalid("expected LIKE or IN after NOT".into()));
+        }
+        let op = match self.next() {
+            Some(Token::Symbol("=")) => CmpOp::Eq,
+            Some(Token::Symbol("!=" | "<>")) => CmpOp::Ne,
+            Some(Token::Symbol("<")) => CmpOp::Lt,
+            Some(Token::Symbol("<=")) => CmpOp::Le,
+            Some(Token::Symbol(">")) => CmpOp::Gt,
+            Some(Token::Symbol(">=")) => CmpOp::Ge,
+            other => {
+                return Err(invalid(format!(
+                    "expected comparison after '{column}', found {other:?}"
+                )));
+            }
+        };
+        let value = self.literal()?;
+        Ok(Condition::Compare { column, op, value })
+    }
+
+    fn literal(&mut self) -> Result<QueryValue> {
+        match self.next() {
+            Some(Token::Str(value)) => Ok(QueryValue::Text(value)),
+            Some(Token::Number(raw)) => raw
+                .parse::<i64>()
+                .map(QueryValue::Int)
+                .or_else(|_| raw.parse::<f64>().map(QueryValue::Float))
+                .map_err(|_| invalid(format!("invalid number '{raw}'"))),
+            Some(Token::Ident(word)) => match word.to_ascii_uppercase().as_str() {
+                "NULL" => Ok(QueryValue::Null),
+                "TRUE" => Ok(QueryValue::Bool(true)),
+                "FALSE" => Ok(QueryValue::Bool(false)),
+                _ => Err(invalid(format!("expected a literal, found '{word}'"))),
+            },
+            other => Err(invalid(format!("expected a literal, found {other:?}"))),
+        }
+    }
+
+    fn column(&mut self) -> Result<String> {
+        let ident = self.ident()?;
+        Ok(match ident.strip_prefix("meta.") {
+            Some(key) => format!("meta.{key}"),
+            None => ident.to_ascii_lowercase(),
+        })
+    }
+
+    fn count(&mut self) -> Result<usize> {
+        match self.next() {
+            Some(Token::Number(raw)) => raw
+                .parse()
+                .map_err(|_| invalid(format!("invalid count '{raw}'"))),
+            other => Err(invalid(format!("expected a count, found {other:?}"))),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Ident(ident)) => Ok(ident),
+            other => Err(invalid(format!("expected an identifier, found {other:?}"))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.keyword(keyword) {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "expected {keyword}, found {:?}",
+                self.peek()
+            )))
+        }
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<()> {
+        if self.symbol(symbol) {
+            Ok(())
+        } else {
+            Err(invalid(format!(
+                "expected '{symbol}', found {:?}",
+                self.peek()
+            )))
+        }
+    }
+}
+
+/// Turn date strings compared against timestamp columns into Unix seconds.
+fn coerce_dates(condition: &mut Condition) {
+    let to_seconds = |value: &mut QueryValue| {
+        if let QueryValue::Text(raw) = value {
+            if let Some(MetaValue::Date(seconds)) = MetaValue::parse(MetaType::Date, raw) {
+                *value = QueryValue::Int(seconds);
+            }
+        }
+    };
+    match condition {
+        Condition::And(left, right) | Condition::Or(left, right) => {
+            coerce_dates(left);
+            coerce_dates(right);
+        }
+        Condition::Not(inner) => coerce_dates(inner),
+        Condition::Compare { column, value, .. } if TIME_COLUMNS.contains(&column.as_str()) => {
+            to_seconds(value);
+        }
+        Condition::In { column, values, .. } if TIME_COLUMNS.contains(&column.as_str()) => {
+            values.iter_mut().for_each(to_seconds);
+        }
+        _ => {}
+    }
+}
+
+/// Sort rows by the `ORDER BY` keys, given each row's key cells.
+pub(crate) fn sort_rows<T>(rows: &mut [(Vec<QueryValue>, T)], order_by: &[(String, bool)]) {
+    rows.sort_by(|(a, _), (b, _)| {
+        a.iter()
+            .zip(b)
+            .zip(order_by)
+            .map(|((a, b), (_, descending))| {
+                let ordering = a.sort_cmp(b);
+                if *descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_statements_and_rejects_unknown_columns() {
+        let select = parse(
+            "select uri, ts AS at FROM frames WHERE kind = 'email' AND ts > '2024-06-01' \
+             AND NOT (uri LIKE '%draft%' OR meta.priority IN (1, 2)) ORDER BY ts DESC LIMIT 50;",
+        )
+        .expect("parse");
+        assert_eq!(select.table, Table::Frames);
+        assert_eq!(select.labels(), ["uri", "at"]);
+        assert_eq!(select.order_by, [("ts".to_string(), true)]);
+        assert_eq!(select.limit, Some(50));
+        let filter = select.filter.expect("filter");
+        assert_eq!(filter.int_bounds("ts"), (Some(1_717_200_001), None));
+
+        let row = |column: &str| match column {
+            "kind" => QueryValue::Text("email".into()),
+            "ts" => QueryValue::Int(1_717_300_000),
+            "uri" => QueryValue::Text("mv2://mail/Inbox/1".into()),
+            "meta.priority" => QueryValue::Text("3".into()),
+            _ => QueryValue::Null,
+        };
+        assert!(filter.matches(&row));
+
+        assert!(like("Weekly DRAFT notes", "%draft%"));
+        assert!(like("a1c", "a_c"));
+        assert!(!like("abc", "a_"));
+
+        let count = parse("SELECT COUNT(*) FROM cards WHERE entity = 'user'").expect("count");
+        assert_eq!(count.projection, Projection::Count);
+        assert!(parse("SELECT nope FROM frames").is_err());
+        assert!(parse("SELECT * FROM files").is_err());
+        assert!(parse("DELETE FROM frames").is_err());
+
+        let result = QueryResult {
+            columns: vec!["uri".into(), "title".into()],
+            rows: vec![vec![
+                QueryValue::Text("mv2://a".into()),
+                QueryValue::Text("Hello, \"world\"".into()),
+            ]],
+        };
+        assert_eq!(
+            result.to_csv(),
+            "uri,title\nmv2://a,\"Hello, \"\"world\"\"\"\n"
+        );
+    }
+}
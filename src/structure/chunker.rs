@@ -5,6 +5,8 @@
 //! propagation, code blocks are kept whole or split at boundaries, and
 //! sections include their heading context.
 
+use std::ops::Range;
+
 use crate::types::structure::{
     ChunkType, ChunkingOptions, ChunkingResult, CodeChunkingStrategy, ElementData, StructuredChunk,
     StructuredDocument, StructuredTable, TableChunkingStrategy,
@@ -56,6 +58,79 @@ impl StructuralChunker {
         }
     }
 
+    /// Chunk source code in `language` into fenced code chunks with exact character offsets.
+    ///
+    /// With the `tree_sitter` feature and a Rust, Python, JavaScript, TypeScript, or Go
+    /// source, chunks are cut between definitions and list the symbols defined in them.
+    /// Otherwise the code is cut at blank lines and definition-like lines, as
+    /// [`CodeChunkingStrategy::SplitAtBoundaries`] does for code blocks.
+    #[must_use]
+    pub fn chunk_code(&self, source: &str, language: &str) -> ChunkingResult {
+        let mut result = ChunkingResult::empty();
+        let mut lines = Vec::new();
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        for line in source.split_inclusive('\n') {
+            offsets.push(offset);
+            offset += line.chars().count();
+            let line = line.strip_suffix('\n').unwrap_or(line);
+            lines.push(line.strip_suffix('\r').unwrap_or(line));
+        }
+        if lines.iter().all(|line| line.trim().is_empty()) {
+            return result;
+        }
+
+        let max_chars = self.options.max_chars.max(1);
+        let outline =
+            super::code::outline(source, &lines, language, max_chars).unwrap_or_else(|| {
+                super::code::CodeOutline {
+                    ranges: group_code_lines(&lines, max_chars),
+                    symbols: Vec::new(),
+                }
+            });
+
+        // Blank lines between definitions belong to neither chunk.
+        let is_blank = |row: &usize| lines[*row].trim().is_empty();
+        let ranges: Vec<Range<usize>> = outline
+            .ranges
+            .into_iter()
+            .filter_map(|range| {
+                let start = range.clone().find(|row| !is_blank(row))?;
+                let end = range.clone().rev().find(|row| !is_blank(row))? + 1;
+                Some(start..end)
+            })
+            .collect();
+
+        let total_parts = u32::try_from(ranges.len()).unwrap_or(0);
+        for (i, range) in ranges.into_iter().enumerate() {
+            let last = range.end - 1;
+            let symbols = outline
+                .symbols
+                .iter()
+                .filter(|symbol| range.contains(&(symbol.start_line - 1)))
+                .cloned()
+                .collect();
+            result.chunks.push(StructuredChunk {
+                text: format!("```{language}\n{}\n```", lines[range.clone()].join("\n")),
+                chunk_type: if i == 0 {
+                    ChunkType::CodeBlock
+                } else {
+                    ChunkType::CodeBlockContinuation
+                },
+                index: i,
+                element_id: None,
+                part: Some(u32::try_from(i + 1).unwrap_or(0)),
+                total_parts: Some(total_parts),
+                context: Some(language.to_string()),
+                char_start: offsets[range.start],
+                char_end: offsets[last] + lines[last].chars().count(),
+                symbols,
+            });
+        }
+        result.code_blocks_processed = 1;
+        result
+    }
+
     /// Chunk a structured document.
     #[must_use]
     pub fn chunk(&self, doc: &StructuredDocument) -> ChunkingResult {
@@ -413,6 +488,7 @@ impl StructuralChunker {
                     context: language.map(std::string::ToString::to_string),
                     char_start,
                     char_end,
+                    symbols: Vec::new(),
                 });
             }
 
@@ -432,6 +508,7 @@ impl StructuralChunker {
                         context: language.map(std::string::ToString::to_string),
                         char_start,
                         char_end,
+                        symbols: Vec::new(),
                     });
                 } else {
                     // Split at function boundaries or fall back to line boundaries
@@ -467,57 +544,23 @@ impl StructuralChunker {
         char_start: usize,
         char_end: usize,
     ) {
-        // Simple heuristic: split at empty lines that likely indicate function boundaries
         let lines: Vec<&str> = formatted_text.lines().collect();
-        let mut chunks = Vec::new();
-        let mut current_chunk = Vec::new();
-        let mut current_chars = 0;
 
         // Find fence markers to preserve
         let fence_start = lines.first().copied().unwrap_or("```");
         let fence_end = lines.last().copied().unwrap_or("```");
         let content_lines = &lines[1..lines.len().saturating_sub(1)];
-
-        for (i, line) in content_lines.iter().enumerate() {
-            let line_chars = line.chars().count() + 1;
-
-            // Check for good split point (empty line or function start)
-            let is_boundary = line.trim().is_empty()
-                || line.trim().starts_with("fn ")
-                || line.trim().starts_with("def ")
-                || line.trim().starts_with("function ")
-                || line.trim().starts_with("class ")
-                || line.trim().starts_with("impl ");
-
-            if is_boundary && current_chars > self.options.max_chars / 2 && i > 0 {
-                // Emit current chunk
-                if !current_chunk.is_empty() {
-                    chunks.push(current_chunk.join("\n"));
-                    current_chunk.clear();
-                    current_chars = 0;
-                }
-            }
-
-            current_chunk.push(*line);
-            current_chars += line_chars;
-        }
-
-        // Emit remaining
-        if !current_chunk.is_empty() {
-            chunks.push(current_chunk.join("\n"));
-        }
+        let chunks: Vec<String> = group_code_lines(content_lines, self.options.max_chars)
+            .into_iter()
+            .map(|range| content_lines[range].join("\n"))
+            .collect();
 
         // Emit as continuation chunks
         let total_parts = chunks.len();
         for (i, chunk_content) in chunks.into_iter().enumerate() {
             let index = result.chunks.len();
-            let chunk_text = format!(
-                "{}{}\n{}\n{}",
-                fence_start,
-                language.unwrap_or(""),
-                chunk_content,
-                fence_end
-            );
+            // The opening fence already names the language.
+            let chunk_text = format!("{fence_start}\n{chunk_content}\n{fence_end}");
 
             if i == 0 {
                 result.chunks.push(StructuredChunk {
@@ -530,6 +573,7 @@ impl StructuralChunker {
                     context: language.map(std::string::ToString::to_string),
                     char_start,
                     char_end,
+                    symbols: Vec::new(),
                 });
             } else {
                 result.chunks.push(StructuredChunk {
@@ -542,6 +586,7 @@ impl StructuralChunker {
                     context: language.map(std::string::ToString::to_string),
                     char_start,
                     char_end,
+                    symbols: Vec::new(),
                 });
             }
         }
@@ -598,13 +643,8 @@ impl StructuralChunker {
         let total_parts = chunks.len();
         for (i, chunk_content) in chunks.into_iter().enumerate() {
             let index = result.chunks.len();
-            let chunk_text = format!(
-                "{}{}\n{}\n{}",
-                fence_start,
-                language.unwrap_or(""),
-                chunk_content,
-                fence_end
-            );
+            // The opening fence already names the language.
+            let chunk_text = format!("{fence_start}\n{chunk_content}\n{fence_end}");
 
             let chunk_type = if i == 0 {
                 ChunkType::CodeBlock
@@ -622,6 +662,7 @@ impl StructuralChunker {
                 context: language.map(std::string::ToString::to_string),
                 char_start,
                 char_end,
+                symbols: Vec::new(),
             });
         }
     }
@@ -639,6 +680,46 @@ pub fn chunk_structured_with_max(doc: &StructuredDocument, max_chars: usize) ->
     StructuralChunker::with_max_chars(max_chars).chunk(doc)
 }
 
+/// Group code lines into ranges of about `max_chars`, preferring to cut at blank lines and
+/// lines that look like the start of a definition.
+fn group_code_lines(lines: &[&str], max_chars: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut current_chars = 0;
+
+    for (i, line) in lines.iter().enumerate() {
+        let line_chars = line.chars().count() + 1;
+
+        // Check for good split point (empty line or function start)
+        let is_boundary = line.trim().is_empty()
+            || line.trim().starts_with("fn ")
+            || line.trim().starts_with("def ")
+            || line.trim().starts_with("function ")
+            || line.trim().starts_with("class ")
+            || line.trim().starts_with("impl ")
+            || line.trim().starts_with("pub fn ")
+            || line.trim().starts_with("async fn ")
+            || line.trim().starts_with("func ")
+            || line.trim().starts_with("struct ")
+            || line.trim().starts_with("trait ");
+
+        // A block without boundaries is still cut once it reaches the limit.
+        let is_full = current_chars + line_chars > max_chars;
+        if ((is_boundary && current_chars > max_chars / 2) || is_full) && i > start {
+            ranges.push(start..i);
+            start = i;
+            current_chars = 0;
+        }
+
+        current_chars += line_chars;
+    }
+
+    if start < lines.len() {
+        ranges.push(start..lines.len());
+    }
+    ranges
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -747,6 +828,57 @@ Done."#;
         assert!(code_chunks[0].text.contains("fn main()"));
     }
 
+    #[test]
+    fn test_code_split_at_boundaries() {
+        let mut code = String::new();
+        for i in 0..12 {
+            code.push_str(&format!(
+                "fn step_{i}() {{\n    let value = {i};\n    value + 1\n}}\n\n"
+            ));
+        }
+        let block = crate::types::structure::StructuredCodeBlock::new(code).with_language("rust");
+        let mut doc = StructuredDocument::new();
+        doc.add_element(crate::types::structure::DocumentElement::code_block(
+            block, 0, 0,
+        ));
+
+        let chunker = StructuralChunker::new(ChunkingOptions {
+            max_chars: 200,
+            code_handling: CodeChunkingStrategy::SplitAtBoundaries,
+            ..Default::default()
+        });
+        let result = chunker.chunk(&doc);
+
+        assert!(result.chunks.len() > 1);
+        for chunk in &result.chunks {
+            assert!(chunk.text.starts_with("```rust\n"));
+            assert!(chunk.text.ends_with("\n```"));
+            assert!(chunk.text.chars().count() <= 200 + "```rust\n\n```".len());
+        }
+        assert!(result.chunks[1].text.contains("\nfn step_"));
+    }
+
+    #[test]
+    fn test_chunk_code_offsets() {
+        let mut code = String::new();
+        for i in 0..12 {
+            code.push_str(&format!("def step_{i}():\n    return {i}\n\n"));
+        }
+        let result = StructuralChunker::with_max_chars(120).chunk_code(&code, "text");
+
+        assert!(result.chunks.len() > 1);
+        for chunk in &result.chunks {
+            let body: String = code.chars().skip(chunk.char_start).collect();
+            let body: String = body
+                .chars()
+                .take(chunk.char_end - chunk.char_start)
+                .collect();
+            assert_eq!(chunk.text, format!("```text\n{body}\n```"));
+            assert!(body.starts_with("def step_"));
+            assert!(chunk.symbols.is_empty());
+        }
+    }
+
     #[test]
     fn test_mixed_content() {
         let text = r#"# Report
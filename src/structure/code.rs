@@ -0,0 +1,382 @@
+//! Source-code awareness for chunking.
+//!
+//! With the `tree_sitter` feature, code in a supported language is parsed and split between
+//! top-level definitions, descending into classes and impl blocks that exceed the chunk size.
+//! Every chunk then lists the definitions it contains. Without the feature (or for other
+//! languages) `StructuralChunker::chunk_code` falls back to the line heuristic used for code
+//! blocks.
+
+use std::ops::Range;
+
+use crate::types::structure::CodeSymbol;
+
+/// The language of a source file, from its path's extension.
+#[must_use]
+pub fn code_language(path: &str) -> Option<&'static str> {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let extension = name.rsplit_once('.')?.1.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "scala" => "scala",
+        "sh" | "bash" | "zsh" => "shell",
+        "sql" => "sql",
+        "lua" => "lua",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Line ranges to cut code into, with the definitions found in it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CodeOutline {
+    /// Half-open, zero-based line ranges covering every line in order.
+    pub ranges: Vec<Range<usize>>,
+    /// Definitions, with 1-based lines.
+    pub symbols: Vec<CodeSymbol>,
+}
+
+/// Outline `lines` of `language` source into ranges of at most `max_chars` where possible.
+/// `None` when the language has no grammar or nothing was defined at the top level.
+#[cfg(feature = "tree_sitter")]
+pub(crate) fn outline(
+    source: &str,
+    lines: &[&str],
+    language: &str,
+    max_chars: usize,
+) -> Option<CodeOutline> {
+    syntax::outline(source, lines, language, max_chars)
+}
+
+#[cfg(not(feature = "tree_sitter"))]
+pub(crate) fn outline(
+    _source: &str,
+    _lines: &[&str],
+    _language: &str,
+    _max_chars: usize,
+) -> Option<CodeOutline> {
+    None
+}
+
+#[cfg(feature = "tree_sitter")]
+mod syntax {
+    use std::ops::Range;
+
+    use tree_sitter::{Language, Node, Parser};
+
+    use super::CodeOutline;
+    use crate::types::structure::CodeSymbol;
+
+    /// A definition and, for classes and impl blocks, the definitions in its body.
+    struct Definition {
+        /// Lines spanned including decorators and `export`, zero-based and inclusive.
+        start_row: usize,
+        end_row: usize,
+        symbol: Option<CodeSymbol>,
+        members: Vec<Definition>,
+    }
+
+    fn grammar(language: &str) -> Option<Language> {
+        let grammar = match language {
+            "rust" => tree_sitter_rust::LANGUAGE.into(),
+            "python" => tree_sitter_python::LANGUAGE.into(),
+            "javascript" => tree_sitter_javascript::LANGUAGE.into(),
+            "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            "go" => tree_sitter_go::LANGUAGE.into(),
+            _ => return None,
+        };
+        Some(grammar)
+    }
+
+    fn top_level_kind(node_kind: &str) -> Option<&'static str> {
+        let kind = match node_kind {
+            "function_item"
+            | "function_definition"
+            | "function_declaration"
+            | "generator_function_declaration" => "function",
+            "method_declaration" => "method",
+            "class_definition" | "class_declaration" | "abstract_class_declaration" => "class",
+            "struct_item" | "union_item" => "struct",
+            "enum_item" | "enum_declaration" => "enum",
+            "trait_item" => "trait",
+            "interface_declaration" => "interface",
+            "impl_item" => "impl",
+            "mod_item" => "module",
+            "type_item" | "type_alias_declaration" | "type_declaration" => "type",
+            "macro_definition" => "macro",
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn is_member(node_kind: &str) -> bool {
+        matches!(
+            node_kind,
+            "function_item"
+                | "function_signature_item"
+                | "function_definition"
+                | "method_definition"
+        )
+    }
+
+    /// The declaration inside decorator and export wrappers.
+    fn unwrap(node: Node<'_>) -> Node<'_> {
+        match node.kind() {
+            "decorated_definition" => node.child_by_field_name("definition").unwrap_or(node),
+            "export_statement" => node.child_by_field_name("declaration").unwrap_or(node),
+            _ => node,
+        }
+    }
+
+    fn name(node: Node<'_>, source: &[u8]) -> Option<String> {
+        let text = |node: Node<'_>| node.utf8_text(source).ok().map(str::to_string);
+        match node.kind() {
+            "impl_item" => {
+                let target = text(node.child_by_field_name("type")?)?;
+                Some(match node.child_by_field_name("trait").and_then(text) {
+                    Some(implemented) => format!("{implemented} for {target}"),
+                    None => target,
+                })
+            }
+            "type_declaration" => {
+                let mut cursor = node.walk();
+                let spec = node
+                    .named_children(&mut cursor)
+                    .find(|child| child.kind() == "type_spec")?;
+                text(spec.child_by_field_name("name")?)
+            }
+            _ => text(node.child_by_field_name("name")?),
+        }
+    }
+
+    fn symbol(node: Node<'_>, kind: &str, source: &[u8]) -> Option<CodeSymbol> {
+        Some(CodeSymbol {
+            name: name(node, source)?,
+            kind: kind.to_string(),
+            start_line: node.start_position().row + 1,
+            end_line: node.end_position().row + 1,
+        })
+    }
+
+    fn definitions(root: Node<'_>, source: &[u8]) -> Vec<Definition> {
+        let mut cursor = root.walk();
+        root.named_children(&mut cursor)
+            .filter_map(|outer| {
+                let inner = unwrap(outer);
+                let kind = top_level_kind(inner.kind())?;
+                let members = matches!(kind, "class" | "impl" | "trait")
+                    .then(|| inner.child_by_field_name("body"))
+                    .flatten()
+                    .map(|body| {
+                        let mut cursor = body.walk();
+                        body.named_children(&mut cursor)
+                            .filter(|member| is_member(unwrap(*member).kind()))
+                            .map(|member| Definition {
+                                start_row: member.start_position().row,
+                                end_row: member.end_position().row,
+                                symbol: symbol(unwrap(member), "method", source),
+                                members: Vec::new(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(Definition {
+                    start_row: outer.start_position().row,
+                    end_row: outer.end_position().row,
+                    symbol: symbol(inner, kind, source),
+                    members,
+                })
+            })
+            .collect()
+    }
+
+    /// Cut `span` after each definition ending at `end_rows`, so comments and attributes stay
+    /// with the definition below them and trailing lines with the last one.
+    fn partition(span: Range<usize>, end_rows: impl Iterator<Item = usize>) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        let mut next = span.start;
+        for end_row in end_rows {
+            let end = (end_row + 1).min(span.end);
+            if end > next {
+                ranges.push(next..end);
+                next = end;
+            }
+        }
+        match ranges.last_mut() {
+            Some(last) => last.end = span.end,
+            None => ranges.push(span),
+        }
+        ranges
+    }
+
+    pub(super) fn outline(
+        source: &str,
+        lines: &[&str],
+        language: &str,
+        max_chars: usize,
+    ) -> Option<CodeOutline> {
+        let mut parser = Parser::new();
+        parser.set_language(&grammar(language)?).ok()?;
+        let tree = parser.parse(source, None)?;
+        let bytes = source.as_bytes();
+        let definitions = definitions(tree.root_node(), bytes);
+        if definitions.is_empty() {
+            return None;
+        }
+
+        let size = |range: &Range<usize>| -> usize {
+            lines[range.clone()]
+                .iter()
+                .map(|line| line.chars().count() + 1)
+                .sum()
+        };
+
+        let mut pieces = Vec::new();
+        let ends = definitions.iter().map(|definition| definition.end_row);
+        for range in partition(0..lines.len(), ends) {
+            if size(&range) <= max_chars {
+                pieces.push(range);
+                continue;
+            }
+            // An oversized class or impl is cut between its members, and anything still too
+            // large between lines.
+            let member_ends = definitions
+                .iter()
+                .filter(|definition| range.contains(&definition.start_row))
+                .flat_map(|definition| &definition.members)
+                .map(|member| member.end_row);
+            for sub in partition(range.clone(), member_ends) {
+                if size(&sub) <= max_chars {
+                    pieces.push(sub);
+                    continue;
+                }
+                let mut start = sub.start;
+                let mut chars = 0;
+                for row in sub.clone() {
+                    let line_chars = lines[row].chars().count() + 1;
+                    if chars > 0 && chars + line_chars > max_chars {
+                        pieces.push(start..row);
+                        start = row;
+                        chars = 0;
+                    }
+                    chars += line_chars;
+                }
+                pieces.push(start..sub.end);
+            }
+        }
+
+        // Pack neighbouring pieces up to the chunk size.
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for piece in pieces {
+            match ranges.last_mut() {
+                Some(last) if size(last) + size(&piece) <= max_chars => last.end = piece.end,
+                _ => ranges.push(piece),
+            }
+        }
+
+        let symbols = definitions
+            .into_iter()
+            .flat_map(|definition| {
+                std::iter::once(definition.symbol)
+                    .chain(definition.members.into_iter().map(|member| member.symbol))
+            })
+            .flatten()
+            .collect();
+        Some(CodeOutline { ranges, symbols })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_from_paths() {
+        assert_eq!(code_language("src/lib.rs"), Some("rust"));
+        assert_eq!(code_language("web/App.TSX"), Some("typescript"));
+        assert_eq!(code_language("v1.2/README"), None);
+        assert_eq!(code_language("Makefile"), None);
+    }
+
+    #[cfg(feature = "tree_sitter")]
+    mod syntax {
+        use crate::structure::StructuralChunker;
+
+        fn filler(lines: usize) -> String {
+            (0..lines)
+                .map(|i| format!("    let step_{i} = {i};"))
+                .collect::<Vec<_>>()
+                .join("\n")
+                + "\n"
+        }
+
+        #[test]
+        fn rust_chunks_list_their_definitions() {
+            let source = format!(
+                "use std::fmt;\n\n/// Parses a query.\npub fn parse_query(input: &str) -> usize {{\n{}    input.len()\n}}\n\nstruct Parser;\n\nimpl Parser {{\n    fn run(&self) {{\n{}    }}\n\n    fn stop(&self) {{\n{}    }}\n}}\n",
+                filler(8),
+                filler(8),
+                filler(8),
+            );
+            let result = StructuralChunker::with_max_chars(320).chunk_code(&source, "rust");
+
+            let first = &result.chunks[0];
+            assert!(
+                first
+                    .text
+                    .contains("/// Parses a query.\npub fn parse_query")
+            );
+            let parse = &first.symbols[0];
+            assert_eq!(
+                (parse.name.as_str(), parse.kind.as_str()),
+                ("parse_query", "function")
+            );
+            assert_eq!((parse.start_line, parse.end_line), (4, 14));
+
+            let names: Vec<&str> = result
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.symbols.iter().map(|symbol| symbol.name.as_str()))
+                .collect();
+            assert_eq!(names, ["parse_query", "Parser", "Parser", "run", "stop"]);
+            let stop = result
+                .chunks
+                .iter()
+                .find(|chunk| chunk.symbols.iter().any(|symbol| symbol.name == "stop"))
+                .expect("stop chunk");
+            assert!(stop.text.contains("    fn stop(&self) {"));
+            assert!(!stop.text.contains("fn run"));
+        }
+
+        #[test]
+        fn python_keeps_decorators_and_indentation() {
+            let body = "        total += 1\n".repeat(10);
+            let source = format!(
+                "import os\n\n\n@cached\ndef load(path):\n    return os.path.exists(path)\n\n\nclass Store:\n    def read(self):\n        total = 0\n{body}        return total\n\n    def write(self):\n        total = 0\n{body}        return total\n"
+            );
+            let result = StructuralChunker::with_max_chars(260).chunk_code(&source, "python");
+
+            let load = &result.chunks[0];
+            assert!(load.text.contains("@cached\ndef load(path):\n    return"));
+            assert_eq!(load.symbols[0].name, "load");
+            let write = result
+                .chunks
+                .iter()
+                .find(|chunk| chunk.symbols.iter().any(|symbol| symbol.name == "write"))
+                .expect("write chunk");
+            assert_eq!(write.symbols[0].kind, "method");
+            assert!(write.text.contains("\n        return total"));
+        }
+    }
+}
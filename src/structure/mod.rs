@@ -33,14 +33,17 @@
 //! ```
 
 mod chunker;
+mod code;
 mod detector;
 
 pub use chunker::{StructuralChunker, chunk_structured, chunk_structured_with_max};
+pub use code::code_language;
 pub use detector::{detect_ascii_tables, detect_structure};
 
 // Re-export types for convenience
 pub use crate::types::structure::{
-    ChunkType, ChunkingOptions, ChunkingResult, CodeChunkingStrategy, DocumentElement, ElementData,
-    ElementType, StructuredCell, StructuredChunk, StructuredCodeBlock, StructuredDocument,
-    StructuredHeading, StructuredList, StructuredRow, StructuredTable, TableChunkingStrategy,
+    ChunkType, ChunkingOptions, ChunkingResult, CodeChunkingStrategy, CodeSymbol, DocumentElement,
+    ElementData, ElementType, StructuredCell, StructuredChunk, StructuredCodeBlock,
+    StructuredDocument, StructuredHeading, StructuredList, StructuredRow, StructuredTable,
+    TableChunkingStrategy,
 };
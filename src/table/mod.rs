@@ -1,7 +1,7 @@
 //! Table extraction module for Memvid.
 //!
 //! This module provides comprehensive table extraction capabilities for
-//! PDF documents (and in the future, DOCX, XLSX, HTML). It supports:
+//! PDF and XLSX documents (and in the future, DOCX, HTML). It supports:
 //!
 //! - **Lattice detection**: Tables with visible grid lines
 //! - **Stream detection**: Tables inferred from text alignment
@@ -45,26 +45,33 @@
 //! - `table_meta`: Contains table structure, headers, and metadata
 //! - `table_row`: Contains individual row data (one frame per row)
 //!
-//! This allows both full table reconstruction and row-level search.
+//! This allows both full table reconstruction and row-level search. Stored
+//! tables are queried for typed cells with `Memvid::table_query` and a
+//! [`TableSelector`], and found by column with the `column:` search field.
 
 mod layout;
 mod multi_page;
 mod pdf_extractor;
+mod query;
 mod storage;
 mod types;
+mod xlsx_extractor;
 
 // Re-export public types
 pub use layout::{LineSegment, PageLayout, TextBox, cluster_values, extract_pdf_layout};
 pub use multi_page::{find_continuation_candidates, merge_multi_page_tables};
 pub use pdf_extractor::extract_tables_from_pdf;
+pub use query::{RowMatch, TableQueryResult, TableQueryRow, TableSelector, TypedCell, typed_value};
+pub(crate) use query::{resolve_column, select, table_matches};
 pub use storage::{
-    TABLE_META_KIND, TABLE_ROW_KIND, TABLE_TRACK, export_to_csv, export_to_json, get_table,
-    list_tables, store_table, store_table_with_embedder,
+    TABLE_HEADERS_KEY, TABLE_META_KIND, TABLE_ROW_KIND, TABLE_TRACK, export_to_csv, export_to_json,
+    get_table, list_tables, store_table, store_table_with_embedder,
 };
 pub use types::{
     DetectionMode, ExtractedTable, ExtractionMode, TableCell, TableExtractionOptions,
     TableExtractionOptionsBuilder, TableExtractionResult, TableQuality, TableRow, TableSummary,
 };
+pub use xlsx_extractor::extract_tables_from_xlsx;
 
 use crate::error::Result;
 
@@ -89,7 +96,9 @@ pub fn extract_tables(
 
     if lower.ends_with(".pdf") || is_pdf_magic(bytes) {
         extract_tables_from_pdf(bytes, filename, options)
-    } else if lower.ends_with(".xlsx") || lower.ends_with(".xls") {
+    } else if lower.ends_with(".xlsx") {
+        extract_tables_from_xlsx(bytes, filename, options)
+    } else if lower.ends_with(".xls") {
         Ok(TableExtractionResult::empty())
     } else if lower.ends_with(".docx") || lower.ends_with(".doc") {
         Ok(TableExtractionResult::empty())
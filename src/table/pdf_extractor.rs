@@ -117,7 +117,10 @@ pub fn extract_tables_from_pdf(
 }
 
 /// Check if a table passes the quality filter.
-fn passes_quality_filter(table: &ExtractedTable, options: &TableExtractionOptions) -> bool {
+pub(super) fn passes_quality_filter(
+    table: &ExtractedTable,
+    options: &TableExtractionOptions,
+) -> bool {
     // Check minimum dimensions
     if table.n_rows < options.min_rows || table.n_cols < options.min_cols {
         return false;
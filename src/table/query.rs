@@ -0,0 +1,337 @@
+//! Selecting typed cells from a stored table (see `Memvid::table_query`).
+//!
+//! A [`TableSelector`] names the table (by id or sheet), the columns to return, and cell
+//! values that rows must contain. Cells come back as [`QueryValue`]s, with currency symbols,
+//! thousands separators, accounting negatives, and percentages read as numbers, so
+//! "Q3 revenue in the forecast sheet" is one selector and one value:
+//!
+//! ```ignore
+//! let selector = TableSelector::default().table("Forecast").row("Revenue").column("Q3");
+//! let q3 = mem.table_query(frame_id, &selector)?.value().cloned();
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{ExtractedTable, TableRow};
+use crate::error::{MemvidError, Result};
+use crate::sql::QueryValue;
+
+/// Which table, columns, and rows `Memvid::table_query` returns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableSelector {
+    /// Table id or sheet name, compared case-insensitively. `None` takes the first table
+    /// that has every selected column.
+    pub table: Option<String>,
+    /// Columns to return, in order; empty returns every column. A name matches a header
+    /// exactly (ignoring case) or, failing that, as the only header containing it.
+    pub columns: Vec<String>,
+    /// Conditions every returned row meets.
+    pub rows: Vec<RowMatch>,
+    /// Maximum rows returned.
+    pub limit: Option<usize>,
+}
+
+/// A row condition: a cell equal to `value`, ignoring case and surrounding whitespace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowMatch {
+    /// Column the cell is in; `None` checks every cell, as for a row label.
+    pub column: Option<String>,
+    pub value: String,
+}
+
+impl TableSelector {
+    #[must_use]
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    #[must_use]
+    pub fn column(mut self, column: impl Into<String>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Keep rows with a cell equal to `value` in any column.
+    #[must_use]
+    pub fn row(mut self, value: impl Into<String>) -> Self {
+        self.rows.push(RowMatch {
+            column: None,
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Keep rows whose `column` cell equals `value`.
+    #[must_use]
+    pub fn row_where(mut self, column: impl Into<String>, value: impl Into<String>) -> Self {
+        self.rows.push(RowMatch {
+            column: Some(column.into()),
+            value: value.into(),
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// A cell as stored and as a typed value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypedCell {
+    pub column: String,
+    pub text: String,
+    pub value: QueryValue,
+}
+
+/// A selected row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableQueryRow {
+    /// Row index within the table.
+    pub row_index: usize,
+    /// Source page (1 for spreadsheets).
+    pub page: u32,
+    /// Cells of the selected columns, in selector order.
+    pub cells: Vec<TypedCell>,
+}
+
+/// Cells selected from a stored table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableQueryResult {
+    pub table_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sheet_name: Option<String>,
+    pub source_file: String,
+    /// Headers of the selected columns.
+    pub columns: Vec<String>,
+    pub rows: Vec<TableQueryRow>,
+}
+
+impl TableQueryResult {
+    /// The first selected cell's value; the answer when the selector narrows to one row and
+    /// one column.
+    #[must_use]
+    pub fn value(&self) -> Option<&QueryValue> {
+        Some(&self.rows.first()?.cells.first()?.value)
+    }
+}
+
+/// Read a cell's text as a typed value; text that is not a number or boolean stays text.
+#[must_use]
+pub fn typed_value(text: &str) -> QueryValue {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return QueryValue::Null;
+    }
+    if trimmed.eq_ignore_ascii_case("true") {
+        return QueryValue::Bool(true);
+    }
+    if trimmed.eq_ignore_ascii_case("false") {
+        return QueryValue::Bool(false);
+    }
+
+    let (negative, inner) = match trimmed
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+    {
+        Some(inner) => (true, inner),
+        None => (false, trimmed),
+    };
+    let (percent, inner) = match inner.strip_suffix('%') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+    let digits: String = inner
+        .chars()
+        .filter(|ch| !matches!(ch, '$' | '€' | '£' | '¥' | ',' | ' '))
+        .collect();
+    if !digits.chars().any(|ch| ch.is_ascii_digit()) {
+        return QueryValue::Text(text.to_string());
+    }
+    let sign = if negative { -1.0 } else { 1.0 };
+    if !percent {
+        if let Ok(value) = digits.parse::<i64>() {
+            return QueryValue::Int(if negative { -value } else { value });
+        }
+    }
+    match digits.parse::<f64>() {
+        Ok(value) if percent => QueryValue::Float(sign * value / 100.0),
+        Ok(value) => QueryValue::Float(sign * value),
+        Err(_) => QueryValue::Text(text.to_string()),
+    }
+}
+
+/// Whether `table` is the one `name` refers to.
+pub(crate) fn table_matches(table: &ExtractedTable, name: &str) -> bool {
+    table.table_id.eq_ignore_ascii_case(name)
+        || table
+            .sheet_name
+            .as_deref()
+            .is_some_and(|sheet| sheet.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Index of the header `name` refers to.
+pub(crate) fn resolve_column(headers: &[String], name: &str) -> Option<usize> {
+    let name = name.trim();
+    if let Some(index) = headers
+        .iter()
+        .position(|header| header.trim().eq_ignore_ascii_case(name))
+    {
+        return Some(index);
+    }
+    let needle = name.to_lowercase();
+    let mut containing = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| header.to_lowercase().contains(&needle));
+    match (containing.next(), containing.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+fn cell_text(row: &TableRow, index: usize) -> &str {
+    row.cells
+        .iter()
+        .find(|cell| cell.col_index == index)
+        .map_or("", |cell| cell.text.as_str())
+}
+
+/// Apply `selector`'s columns, rows, and limit to `table`.
+pub(crate) fn select(table: &ExtractedTable, selector: &TableSelector) -> Result<TableQueryResult> {
+    let column_index = |name: &str| {
+        resolve_column(&table.headers, name).ok_or_else(|| MemvidError::InvalidQuery {
+            reason: format!(
+                "no column {name:?} in table {} (columns: {})",
+                table.table_id,
+                table.headers.join(", ")
+            ),
+        })
+    };
+
+    let columns: Vec<usize> = if selector.columns.is_empty() {
+        (0..table.headers.len()).collect()
+    } else {
+        selector
+            .columns
+            .iter()
+            .map(|name| column_index(name))
+            .collect::<Result<_>>()?
+    };
+    let conditions: Vec<(Option<usize>, &str)> = selector
+        .rows
+        .iter()
+        .map(|condition| {
+            let column = condition.column.as_deref().map(column_index).transpose()?;
+            Ok((column, condition.value.trim()))
+        })
+        .collect::<Result<_>>()?;
+
+    let rows = table
+        .data_rows()
+        .into_iter()
+        .filter(|row| {
+            conditions.iter().all(|(column, value)| match column {
+                Some(index) => cell_text(row, *index).trim().eq_ignore_ascii_case(value),
+                None => row
+                    .cells
+                    .iter()
+                    .any(|cell| cell.text.trim().eq_ignore_ascii_case(value)),
+            })
+        })
+        .take(selector.limit.unwrap_or(usize::MAX))
+        .map(|row| TableQueryRow {
+            row_index: row.row_index,
+            page: row.page,
+            cells: columns
+                .iter()
+                .map(|&index| {
+                    let text = cell_text(row, index).to_string();
+                    TypedCell {
+                        column: table.headers[index].clone(),
+                        value: typed_value(&text),
+                        text,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(TableQueryResult {
+        table_id: table.table_id.clone(),
+        sheet_name: table.sheet_name.clone(),
+        source_file: table.source_file.clone(),
+        columns: columns
+            .iter()
+            .map(|&index| table.headers[index].clone())
+            .collect(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::TableCell;
+
+    fn forecast() -> ExtractedTable {
+        let mut table = ExtractedTable::new("tbl_plan_xlsx_1", "plan.xlsx");
+        table.sheet_name = Some("Forecast".to_string());
+        table.headers = ["Metric", "Q1 2024", "Q2 2024", "Q3 2024"]
+            .map(String::from)
+            .to_vec();
+        let rows = [
+            ["Revenue", "$1,200", "$1,350", "$1,480.50"],
+            ["Costs", "(300)", "310", "12%"],
+        ];
+        for (row_index, cells) in rows.iter().enumerate() {
+            let cells = cells
+                .iter()
+                .enumerate()
+                .map(|(col, text)| TableCell::new(*text, col))
+                .collect();
+            table.rows.push(TableRow::new(row_index, 1, cells));
+        }
+        table
+    }
+
+    #[test]
+    fn selects_a_typed_cell_by_row_label_and_column() {
+        let table = forecast();
+        assert!(table_matches(&table, "forecast"));
+
+        let selector = TableSelector::default().row("revenue").column("Q3");
+        let result = select(&table, &selector).expect("select");
+        assert_eq!(result.columns, ["Q3 2024"]);
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0].cells[0].text, "$1,480.50");
+        assert_eq!(result.value(), Some(&QueryValue::Float(1480.5)));
+
+        let costs = select(
+            &table,
+            &TableSelector::default().row_where("metric", "Costs"),
+        )
+        .expect("select");
+        let values: Vec<&QueryValue> = costs.rows[0].cells.iter().map(|c| &c.value).collect();
+        assert_eq!(
+            values,
+            [
+                &QueryValue::Text("Costs".to_string()),
+                &QueryValue::Int(-300),
+                &QueryValue::Int(310),
+                &QueryValue::Float(0.12),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_or_ambiguous_columns_are_errors() {
+        let table = forecast();
+        assert!(select(&table, &TableSelector::default().column("Q4")).is_err());
+        assert!(select(&table, &TableSelector::default().column("2024")).is_err());
+    }
+}
@@ -28,6 +28,10 @@ pub const TABLE_META_KIND: &str = "table_meta";
 /// Kind value for table row frames.
 pub const TABLE_ROW_KIND: &str = "table_row";
 
+/// Extra-metadata key holding a table's column headers as a JSON array, on both
+/// `table_meta` and `table_row` frames. Searched by the `column:` query field.
+pub const TABLE_HEADERS_KEY: &str = "headers_json";
+
 /// Store an extracted table in the MV2 file.
 ///
 /// Creates two types of frames:
@@ -78,6 +82,7 @@ fn store_table_impl(
         "table_id": table_id,
         "source_file": table.source_file,
         "source_uri": table.source_uri,
+        "sheet_name": table.sheet_name,
         "page_start": table.page_start,
         "page_end": table.page_end,
         "headers": table.headers,
@@ -105,9 +110,17 @@ fn store_table_impl(
         table.detection_mode.to_string(),
     );
 
+    if let Some(source_uri) = &table.source_uri {
+        meta_extra.insert("source_uri".to_string(), source_uri.clone());
+    }
+    if let Some(sheet_name) = &table.sheet_name {
+        meta_extra.insert("sheet_name".to_string(), sheet_name.clone());
+    }
+
     // Serialize headers for searchability
-    if let Ok(headers_json) = serde_json::to_string(&table.headers) {
-        meta_extra.insert("headers_json".to_string(), headers_json);
+    let headers_json = serde_json::to_string(&table.headers).ok();
+    if let Some(headers_json) = &headers_json {
+        meta_extra.insert(TABLE_HEADERS_KEY.to_string(), headers_json.clone());
     }
 
     let meta_options = PutOptions {
@@ -115,10 +128,13 @@ fn store_table_impl(
         track: Some(TABLE_TRACK.to_string()),
         kind: Some(TABLE_META_KIND.to_string()),
         uri: Some(format!("mv2://tables/{table_id}")),
-        title: Some(format!(
-            "Table from {} (pages {}-{})",
-            table.source_file, table.page_start, table.page_end
-        )),
+        title: Some(match &table.sheet_name {
+            Some(sheet_name) => format!("Table from {} (sheet {sheet_name})", table.source_file),
+            None => format!(
+                "Table from {} (pages {}-{})",
+                table.source_file, table.page_start, table.page_end
+            ),
+        }),
         metadata: None,
         search_text: Some(table.to_search_text()),
         tags: vec![
@@ -128,6 +144,7 @@ fn store_table_impl(
         ],
         labels: vec![format!("{}_detected", table.detection_mode)],
         extra_metadata: meta_extra,
+        typed_metadata: BTreeMap::new(),
         enable_embedding: false, // Don't embed metadata frame
         auto_tag: false,
         extract_dates: false,
@@ -139,6 +156,10 @@ fn store_table_impl(
         dedup: false,
         instant_index: false,    // Tables are batch operations, commit at end
         extraction_budget_ms: 0, // No budget for table metadata
+        compression: None,
+        timezone: None,
+        chunk_chars: None,
+        profile: None,
     };
 
     let meta_frame_id = mem.next_frame_id();
@@ -188,6 +209,9 @@ fn store_table_impl(
         row_extra.insert("row_index".to_string(), row.row_index.to_string());
         row_extra.insert("page".to_string(), row.page.to_string());
         row_extra.insert("parent_frame".to_string(), meta_frame_id.to_string());
+        if let Some(headers_json) = &headers_json {
+            row_extra.insert(TABLE_HEADERS_KEY.to_string(), headers_json.clone());
+        }
 
         let mut row_options = PutOptions {
             timestamp: None,
@@ -200,6 +224,7 @@ fn store_table_impl(
             tags: vec!["table_row".to_string(), table_id.clone()],
             labels: Vec::new(),
             extra_metadata: row_extra,
+            typed_metadata: BTreeMap::new(),
             enable_embedding: embed_rows,
             auto_tag: false,
             extract_dates: true,     // Extract dates from cell values
@@ -211,6 +236,10 @@ fn store_table_impl(
             dedup: false,
             instant_index: false, // Tables are batch operations, commit at end
             extraction_budget_ms: 0, // No budget for table rows
+            compression: None,
+            timezone: None,
+            chunk_chars: None,
+            profile: None,
         };
 
         let should_embed = embed_rows && embedder.is_some();
@@ -380,6 +409,7 @@ pub fn get_table(mem: &mut Memvid, table_id: &str) -> Result<Option<ExtractedTab
     );
 
     table.source_uri = meta["source_uri"].as_str().map(String::from);
+    table.sheet_name = meta["sheet_name"].as_str().map(String::from);
     table.page_start = u32::try_from(meta["page_start"].as_u64().unwrap_or(1)).unwrap_or(1);
     #[allow(clippy::cast_possible_truncation)]
     {
@@ -165,6 +165,9 @@ pub struct ExtractedTable {
     pub source_file: String,
     /// URI of the source frame (if from existing MV2 content)
     pub source_uri: Option<String>,
+    /// Worksheet the table came from (spreadsheets only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sheet_name: Option<String>,
 
     // Page span
     /// Starting page number (1-indexed)
@@ -205,6 +208,7 @@ impl ExtractedTable {
             table_id: table_id.into(),
             source_file: source_file.into(),
             source_uri: None,
+            sheet_name: None,
             page_start: 1,
             page_end: 1,
             headers: Vec::new(),
@@ -0,0 +1,189 @@
+//! XLSX table extraction from the spreadsheet reader's table detection.
+//!
+//! Spreadsheets already hold a grid, so tables come from `detect_tables` (OOXML table
+//! definitions first, then header heuristics) rather than from layout analysis. Cells are
+//! rendered with the workbook's number formats, as the XLSX chunker renders them.
+
+use std::time::Instant;
+
+use super::pdf_extractor::passes_quality_filter;
+use super::types::{
+    DetectionMode, ExtractedTable, TableCell, TableExtractionOptions, TableExtractionResult,
+    TableQuality, TableRow,
+};
+use crate::error::Result;
+use crate::reader::XlsxReader;
+use crate::reader::xlsx_chunker::format_cell_value;
+use crate::reader::xlsx_ooxml::{OoxmlMetadata, parse_ooxml_metadata};
+use crate::reader::xlsx_table_detect::{DetectedTable, SheetGrid, detect_tables};
+
+/// Extract tables from an XLSX workbook, one per detected table on each sheet.
+///
+/// # Arguments
+/// * `bytes` - Raw XLSX bytes
+/// * `source_file` - Original filename for metadata
+/// * `options` - Extraction options (dimension and quality filters apply)
+pub fn extract_tables_from_xlsx(
+    bytes: &[u8],
+    source_file: &str,
+    options: &TableExtractionOptions,
+) -> Result<TableExtractionResult> {
+    let start = Instant::now();
+    let grids = XlsxReader::build_grids(bytes)?;
+    let metadata = parse_ooxml_metadata(bytes).unwrap_or_default();
+    let mut result = tables_from_grids(&grids, &metadata, source_file, options);
+    result.total_ms = start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+    Ok(result)
+}
+
+fn tables_from_grids(
+    grids: &[SheetGrid],
+    metadata: &OoxmlMetadata,
+    source_file: &str,
+    options: &TableExtractionOptions,
+) -> TableExtractionResult {
+    let mut tables = Vec::new();
+    for grid in grids {
+        let sheet_merged = metadata
+            .merged_regions
+            .get(&grid.sheet_name)
+            .cloned()
+            .unwrap_or_default();
+        let sheet_tables: Vec<_> = metadata
+            .table_defs
+            .iter()
+            .filter(|table| table.sheet_name == grid.sheet_name)
+            .cloned()
+            .collect();
+        for detected in detect_tables(grid, &sheet_tables, &sheet_merged) {
+            let table = to_extracted(grid, &detected, metadata, source_file);
+            if passes_quality_filter(&table, options) {
+                tables.push(table);
+            }
+        }
+    }
+
+    for (i, table) in tables.iter_mut().enumerate() {
+        table.table_id = format!("tbl_{}_{}", source_file.replace('.', "_"), i + 1);
+    }
+
+    let mut warnings = Vec::new();
+    if tables.is_empty() && !grids.is_empty() {
+        warnings.push("No tables detected in workbook".to_string());
+    }
+    TableExtractionResult {
+        tables,
+        pages_processed: u32::try_from(grids.len()).unwrap_or(u32::MAX),
+        total_ms: 0,
+        warnings,
+    }
+}
+
+fn to_extracted(
+    grid: &SheetGrid,
+    detected: &DetectedTable,
+    metadata: &OoxmlMetadata,
+    source_file: &str,
+) -> ExtractedTable {
+    let n_cols = (detected.last_col - detected.first_col + 1) as usize;
+    // Row frames key cells by header, so every column needs a distinct name.
+    let headers: Vec<String> = (0..n_cols)
+        .map(|i| {
+            detected
+                .headers
+                .get(i)
+                .map(|header| header.trim())
+                .filter(|header| !header.is_empty())
+                .map_or_else(|| format!("Column {}", i + 1), str::to_string)
+        })
+        .collect();
+
+    let mut table = ExtractedTable::new(String::new(), source_file);
+    table.sheet_name = Some(grid.sheet_name.clone());
+    table.detection_mode = DetectionMode::Native;
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        table.confidence_score = detected.confidence as f32;
+    }
+    table.quality = if detected.confidence >= 0.8 {
+        TableQuality::High
+    } else if detected.confidence >= 0.5 {
+        TableQuality::Medium
+    } else {
+        TableQuality::Low
+    };
+
+    if detected.header_row.is_some() {
+        let cells = headers
+            .iter()
+            .enumerate()
+            .map(|(col, header)| TableCell::new(header.clone(), col))
+            .collect();
+        table.rows.push(TableRow::new(0, 1, cells).as_header());
+    }
+    for row in detected.first_data_row..=detected.last_data_row {
+        if grid.is_row_empty(row) {
+            continue;
+        }
+        let cells = (detected.first_col..=detected.last_col)
+            .enumerate()
+            .map(|(col_index, col)| {
+                let text = format_cell_value(grid.cell(row, col), grid.num_fmt(row, col), metadata);
+                TableCell::new(text, col_index)
+            })
+            .collect();
+        let row_index = table.rows.len();
+        table.rows.push(TableRow::new(row_index, 1, cells));
+    }
+
+    table.n_rows = table.data_rows().len();
+    table.n_cols = n_cols;
+    table.headers = headers;
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::xlsx_table_detect::CellValue;
+
+    #[test]
+    fn detected_tables_become_extracted_tables() {
+        let text = |value: &str| CellValue::Text(value.to_string());
+        let mut grid = SheetGrid::new("Forecast".to_string());
+        grid.rows = vec![
+            vec![text("Metric"), text("Q1"), text("Q2"), text("Q3")],
+            vec![
+                text("Revenue"),
+                CellValue::Integer(100),
+                CellValue::Integer(120),
+                CellValue::Number(135.5),
+            ],
+            vec![
+                text("Costs"),
+                CellValue::Integer(80),
+                CellValue::Integer(85),
+                CellValue::Integer(90),
+            ],
+        ];
+        grid.num_rows = 3;
+        grid.num_cols = 4;
+
+        let result = tables_from_grids(
+            &[grid],
+            &OoxmlMetadata::default(),
+            "plan.xlsx",
+            &TableExtractionOptions::default(),
+        );
+
+        assert_eq!(result.tables.len(), 1);
+        let table = &result.tables[0];
+        assert_eq!(table.table_id, "tbl_plan_xlsx_1");
+        assert_eq!(table.sheet_name.as_deref(), Some("Forecast"));
+        assert_eq!(table.headers, ["Metric", "Q1", "Q2", "Q3"]);
+        assert_eq!((table.n_rows, table.n_cols), (2, 4));
+        assert_eq!(table.detection_mode, DetectionMode::Native);
+        let revenue = table.data_rows()[0];
+        assert_eq!(revenue.cell_texts(), ["Revenue", "100", "120", "135.5"]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Crash-safety test utilities.
+//!
+//! [`FaultyStorage`] installs a fault plan on the current thread: while an operation runs under
+//! [`FaultyStorage::run`], every write, truncate, and fsync a `Memvid` handle makes is counted,
+//! and once the [`Fault`] fires the power is cut — the failing write and everything after it
+//! return an error and never reach the file. Dropping the handle afterwards and calling
+//! [`check_invariants`] tells whether the file survived the crash. [`crash_sweep`] repeats this
+//! for every fault in a list against fresh copies of a seed file.
+//!
+//! The plan is thread-local, so work a handle hands to other threads runs unfaulted, and it
+//! models a process crash rather than lost page cache: bytes written before the cut stay.
+
+use std::cell::RefCell;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{FrameStatus, VerificationStatus};
+
+/// Where a [`FaultyStorage`] cuts the power.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Never fires; useful to measure how many bytes an operation writes.
+    None,
+    /// Refuse the write that would take the total past this many bytes.
+    FailAfterBytes(u64),
+    /// Like `FailAfterBytes`, but the crossing write lands up to the limit before failing.
+    TornWrite { after_bytes: u64 },
+    /// Cut the power right before a header write, letting the first `skip` through. Every
+    /// commit writes its footer before the header, so this lands between the two.
+    PowerCutBeforeHeader { skip: usize },
+}
+
+#[derive(Debug)]
+struct FaultState {
+    fault: Fault,
+    bytes_written: u64,
+    headers_written: usize,
+    syncs: usize,
+    tripped: bool,
+}
+
+/// A fault plan for the storage writes made on this thread.
+#[derive(Clone, Debug)]
+pub struct FaultyStorage {
+    state: Rc<RefCell<FaultState>>,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Rc<RefCell<FaultState>>>> = const { RefCell::new(None) };
+}
+
+impl FaultyStorage {
+    #[must_use]
+    pub fn new(fault: Fault) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(FaultState {
+                fault,
+                bytes_written: 0,
+                headers_written: 0,
+                syncs: 0,
+                tripped: false,
+            })),
+        }
+    }
+
+    /// Run `op` with this plan applied to the current thread's storage writes.
+    pub fn run<T>(&self, op: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Rc<RefCell<FaultState>>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                ACTIVE.with(|active| *active.borrow_mut() = previous);
+            }
+        }
+
+        let previous = ACTIVE.with(|active| active.borrow_mut().replace(Rc::clone(&self.state)));
+        let _restore = Restore(previous);
+        op()
+    }
+
+    /// Whether the fault has fired.
+    #[must_use]
+    pub fn tripped(&self) -> bool {
+        self.state.borrow().tripped
+    }
+
+    /// Bytes that reached the file under this plan.
+    #[must_use]
+    pub fn bytes_written(&self) -> u64 {
+        self.state.borrow().bytes_written
+    }
+
+    /// Header writes that reached the file under this plan.
+    #[must_use]
+    pub fn headers_written(&self) -> usize {
+        self.state.borrow().headers_written
+    }
+
+    /// fsyncs of the memory file and its WAL issued under this plan. The sync the atomic
+    /// replace of a staged commit makes happens outside `StorageFile` and is not counted.
+    #[must_use]
+    pub fn syncs(&self) -> usize {
+        self.state.borrow().syncs
+    }
+}
+
+/// Hooks `StorageFile` calls before touching the file.
+pub(crate) mod faults {
+    use super::{ACTIVE, Fault, FaultState, io};
+
+    fn with_state<T>(f: impl FnOnce(&mut FaultState) -> T) -> Option<T> {
+        ACTIVE.with(|active| {
+            active
+                .borrow()
+                .as_ref()
+                .map(|state| f(&mut state.borrow_mut()))
+        })
+    }
+
+    pub(crate) fn power_cut() -> io::Error {
+        io::Error::other("simulated power cut")
+    }
+
+    /// How many of `len` bytes may be written; fewer than `len` means the write is torn.
+    pub(crate) fn admit_write(len: usize) -> io::Result<usize> {
+        let admitted = with_state(|state| {
+            if state.tripped {
+                return None;
+            }
+            let limit = match state.fault {
+                Fault::FailAfterBytes(limit) | Fault::TornWrite { after_bytes: limit } => limit,
+                Fault::None | Fault::PowerCutBeforeHeader { .. } => return Some(len),
+            };
+            let remaining = limit.saturating_sub(state.bytes_written);
+            if remaining >= len as u64 {
+                return Some(len);
+            }
+            state.tripped = true;
+            if matches!(state.fault, Fault::TornWrite { .. }) {
+                // remaining < len, so it fits in usize.
+                let torn = usize::try_from(remaining).unwrap_or(0);
+                state.bytes_written += remaining;
+                Some(torn)
+            } else {
+                Some(0)
+            }
+        });
+        match admitted {
+            None => Ok(len),
+            Some(None) => Err(power_cut()),
+            Some(Some(admitted)) => Ok(admitted),
+        }
+    }
+
+    pub(crate) fn record_write(len: usize) {
+        with_state(|state| state.bytes_written += len as u64);
+    }
+
+    pub(crate) fn admit_metadata() -> io::Result<()> {
+        if with_state(|state| state.tripped).unwrap_or(false) {
+            return Err(power_cut());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn record_sync() {
+        with_state(|state| state.syncs += 1);
+    }
+
+    pub(crate) fn admit_header() -> io::Result<()> {
+        let refused = with_state(|state| {
+            if state.tripped {
+                return true;
+            }
+            if let Fault::PowerCutBeforeHeader { skip } = state.fault {
+                if state.headers_written == skip {
+                    state.tripped = true;
+                    return true;
+                }
+            }
+            state.headers_written += 1;
+            false
+        });
+        if refused.unwrap_or(false) {
+            return Err(power_cut());
+        }
+        Ok(())
+    }
+}
+
+/// What [`check_invariants`] found in a file after a simulated crash.
+#[derive(Debug, Clone, Default)]
+pub struct InvariantReport {
+    /// Active frames in the reopened file.
+    pub frame_count: u64,
+    /// Broken invariants, empty when the file is sound.
+    pub violations: Vec<String>,
+}
+
+impl InvariantReport {
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Reopen `path` and check what every crash must preserve: the file opens (recovering its TOC
+/// if needed), at least `committed_frames` frames are active, every active frame's payload
+/// decodes, and a deep verification passes. Drop any handle on `path` first.
+#[must_use]
+pub fn check_invariants(path: &Path, committed_frames: u64) -> InvariantReport {
+    let mut report = InvariantReport::default();
+    {
+        let mut mem = match Memvid::open(path) {
+            Ok(mem) => mem,
+            Err(err) => {
+                report.violations.push(format!("reopen failed: {err}"));
+                return report;
+            }
+        };
+        let active: Vec<_> = mem
+            .toc
+            .frames
+            .iter()
+            .filter(|frame| frame.status == FrameStatus::Active)
+            .map(|frame| frame.id)
+            .collect();
+        report.frame_count = active.len() as u64;
+        if report.frame_count < committed_frames {
+            report.violations.push(format!(
+                "{committed_frames} frames were committed but only {} survived",
+                report.frame_count
+            ));
+        }
+        for frame_id in active {
+            if let Err(err) = mem.frame_canonical_payload(frame_id) {
+                report
+                    .violations
+                    .push(format!("frame {frame_id} payload unreadable: {err}"));
+            }
+        }
+    }
+    match Memvid::verify(path, true) {
+        Ok(verification) => report.violations.extend(
+            verification
+                .checks
+                .into_iter()
+                .filter(|check| check.status == VerificationStatus::Failed)
+                .map(|check| match check.details {
+                    Some(details) => format!("verify {} failed: {details}", check.name),
+                    None => format!("verify {} failed", check.name),
+                }),
+        ),
+        Err(err) => report.violations.push(format!("verify failed: {err}")),
+    }
+    report
+}
+
+/// Run `scenario` once per fault against a fresh copy of `seed`, then check the copy with
+/// [`check_invariants`]. `scenario` receives the copy's path and should drop its handle
+/// before returning; its own errors are expected once the fault fires and are ignored.
+pub fn crash_sweep<F>(
+    seed: &Path,
+    faults: impl IntoIterator<Item = Fault>,
+    committed_frames: u64,
+    scenario: F,
+) -> Result<Vec<(Fault, InvariantReport)>>
+where
+    F: Fn(&Path) -> Result<()>,
+{
+    let dir = tempfile::tempdir()?;
+    let copy: PathBuf = dir.path().join(
+        seed.file_name()
+            .unwrap_or_else(|| std::ffi::OsStr::new("sweep.mv2")),
+    );
+    let mut reports = Vec::new();
+    for fault in faults {
+        std::fs::copy(seed, &copy)?;
+        let storage = FaultyStorage::new(fault);
+        let _ = storage.run(|| scenario(&copy));
+        reports.push((fault, check_invariants(&copy, committed_frames)));
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed(dir: &Path) -> PathBuf {
+        let path = dir.join("seed.mv2");
+        let mut mem = Memvid::create(&path).expect("create");
+        mem.put_bytes(b"committed before the crash").expect("put");
+        mem.commit().expect("commit");
+        path
+    }
+
+    fn put_and_commit(path: &Path) -> Result<()> {
+        let mut mem = Memvid::open(path)?;
+        mem.put_bytes(b"written while the power fails")?;
+        mem.commit()
+    }
+
+    #[test]
+    fn committed_frames_survive_every_fault() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let seed = seed(dir.path());
+
+        let probe = FaultyStorage::new(Fault::None);
+        let copy = dir.path().join("probe.mv2");
+        std::fs::copy(&seed, &copy).expect("copy");
+        probe.run(|| put_and_commit(&copy)).expect("unfaulted run");
+        assert!(!probe.tripped());
+        let total = probe.bytes_written();
+        let headers = probe.headers_written();
+        assert!(total > 0 && headers > 0);
+
+        let step = (total / 8).max(1);
+        let faults = (0..total)
+            .step_by(usize::try_from(step).expect("step"))
+            .flat_map(|limit| {
+                [
+                    Fault::FailAfterBytes(limit),
+                    Fault::TornWrite { after_bytes: limit },
+                ]
+            })
+            .chain((0..headers).map(|skip| Fault::PowerCutBeforeHeader { skip }));
+        for (fault, report) in crash_sweep(&seed, faults, 1, put_and_commit).expect("sweep") {
+            assert!(report.is_ok(), "{fault:?}: {:?}", report.violations);
+            assert!(report.frame_count >= 1);
+        }
+    }
+
+    #[test]
+    fn tripped_plan_refuses_later_writes() {
+        let dir = tempfile::tempdir().expect("tmp");
+        let seed = seed(dir.path());
+        let storage = FaultyStorage::new(Fault::FailAfterBytes(0));
+        let result = storage.run(|| put_and_commit(&seed));
+        assert!(result.is_err());
+        assert!(storage.tripped());
+        assert_eq!(storage.bytes_written(), 0);
+    }
+
+    #[test]
+    fn relaxed_durability_issues_fewer_syncs() {
+        use crate::DurabilityProfile;
+
+        let dir = tempfile::tempdir().expect("tmp");
+        let seed = seed(dir.path());
+        let syncs = |durability| {
+            let copy = dir.path().join("profile.mv2");
+            std::fs::copy(&seed, &copy).expect("copy");
+            let storage = FaultyStorage::new(Fault::None);
+            storage
+                .run(|| -> Result<()> {
+                    let mut mem = Memvid::open(&copy)?;
+                    mem.lock_settings_mut().durability = durability;
+                    for text in ["first put", "second put", "third put"] {
+                        mem.put_bytes(text.as_bytes())?;
+                    }
+                    mem.commit()
+                })
+                .expect("put and commit");
+            storage.syncs()
+        };
+
+        let paranoid = syncs(DurabilityProfile::Paranoid);
+        let balanced = syncs(DurabilityProfile::Balanced);
+        let fast = syncs(DurabilityProfile::Fast);
+        assert!(paranoid > balanced, "{paranoid} vs {balanced}");
+        assert_eq!(balanced, 1);
+        assert_eq!(fast, 0);
+    }
+}
@@ -71,6 +71,18 @@ mod tests {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                        rerank: None,
+                        geo: None,
+                        filters: Vec::new(),
+                        access_boost: false,
+                        highlight_windows: 0,
+                        language: None,
+                        diversify: None,
+                        group_by_parent: false,
+                        return_parents: false,
+                        explain: false,
+                        mode: crate::types::SearchMode::Lexical,
+                        time_budget_ms: None,
                     })
                     .expect("search must succeed");
 
@@ -150,6 +162,18 @@ mod tests {
                         no_sketch: false,
                         acl_context: None,
                         acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                        rerank: None,
+                        geo: None,
+                        filters: Vec::new(),
+                        access_boost: false,
+                        highlight_windows: 0,
+                        language: None,
+                        diversify: None,
+                        group_by_parent: false,
+                        return_parents: false,
+                        explain: false,
+                        mode: crate::types::SearchMode::Lexical,
+                        time_budget_ms: None,
                     })
                     .expect("search must succeed through mutex wrapper");
 
@@ -204,6 +228,18 @@ mod tests {
                     no_sketch: false,
                     acl_context: None,
                     acl_enforcement_mode: crate::types::AclEnforcementMode::Audit,
+                    rerank: None,
+                    geo: None,
+                    filters: Vec::new(),
+                    access_boost: false,
+                    highlight_windows: 0,
+                    language: None,
+                    diversify: None,
+                    group_by_parent: false,
+                    return_parents: false,
+                    explain: false,
+                    mode: crate::types::SearchMode::Lexical,
+                    time_budget_ms: None,
                 })
                 .expect("search must succeed");
 
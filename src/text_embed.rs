@@ -10,6 +10,12 @@
 //! - **BGE-base-en-v1.5**: 768 dimensions, better quality
 //! - **nomic-embed-text-v1.5**: 768 dimensions, versatile
 //! - **GTE-large**: 1024 dimensions, highest quality
+//! - **multilingual-e5-small** / **multilingual-e5-base**: 384 / 768 dimensions, ~100 languages
+//!   in one vector space, so an English query retrieves Spanish or German documents
+//! - **paraphrase-multilingual-MiniLM-L12-v2**: 384 dimensions, multilingual sentence similarity
+//!
+//! Multilingual models pool by averaging token states rather than taking `[CLS]`, and E5
+//! models expect `query: ` / `passage: ` prefixes; both come from [`TextEmbedModelInfo`].
 //!
 //! ## Usage
 //!
@@ -23,10 +29,11 @@
 //! assert_eq!(embedding.len(), 384);
 //! ```
 
+use crate::inference_device::{InferenceDevice, build_session};
 use crate::types::embedding::EmbeddingProvider;
 use crate::{MemvidError, Result};
 use ndarray::Array;
-use ort::session::{Session, builder::GraphOptimizationLevel};
+use ort::session::Session;
 use ort::value::Tensor;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, VecDeque};
@@ -146,10 +153,24 @@ pub const MODEL_UNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
 /// Default cache capacity (number of embeddings to cache)
 const DEFAULT_CACHE_CAPACITY: usize = 1000;
 
+/// Default number of texts per inference call in `encode_batch`
+pub const DEFAULT_EMBED_BATCH_SIZE: usize = 32;
+
 // ============================================================================
 // Model Registry
 // ============================================================================
 
+/// How token states of the model output are reduced to one vector per text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmbeddingPooling {
+    /// First token (`[CLS]`), as BGE and GTE are trained
+    #[default]
+    Cls,
+    /// Mean of the token states under the attention mask, as E5 and sentence-transformers
+    /// models are trained
+    Mean,
+}
+
 /// Available text embedding models with verified HuggingFace URLs
 #[derive(Debug, Clone)]
 pub struct TextEmbedModelInfo {
@@ -165,6 +186,14 @@ pub struct TextEmbedModelInfo {
     pub max_tokens: usize,
     /// Whether this is the default model
     pub is_default: bool,
+    /// Pooling the model was trained with
+    pub pooling: EmbeddingPooling,
+    /// Prepended to search queries (E5: `"query: "`)
+    pub query_prefix: &'static str,
+    /// Prepended to ingested documents (E5: `"passage: "`)
+    pub document_prefix: &'static str,
+    /// Whether texts in different languages share one vector space
+    pub multilingual: bool,
 }
 
 /// Available text embedding models registry
@@ -177,6 +206,10 @@ pub static TEXT_EMBED_MODELS: &[TextEmbedModelInfo] = &[
         dims: 384,
         max_tokens: 512,
         is_default: true,
+        pooling: EmbeddingPooling::Cls,
+        query_prefix: "",
+        document_prefix: "",
+        multilingual: false,
     },
     // BGE-base: Better quality, still fast (768d)
     TextEmbedModelInfo {
@@ -186,6 +219,10 @@ pub static TEXT_EMBED_MODELS: &[TextEmbedModelInfo] = &[
         dims: 768,
         max_tokens: 512,
         is_default: false,
+        pooling: EmbeddingPooling::Cls,
+        query_prefix: "",
+        document_prefix: "",
+        multilingual: false,
     },
     // Nomic: Versatile, good for various tasks (768d)
     TextEmbedModelInfo {
@@ -195,6 +232,10 @@ pub static TEXT_EMBED_MODELS: &[TextEmbedModelInfo] = &[
         dims: 768,
         max_tokens: 512,
         is_default: false,
+        pooling: EmbeddingPooling::Cls,
+        query_prefix: "",
+        document_prefix: "",
+        multilingual: false,
     },
     // GTE-large: Highest quality, slower (1024d)
     TextEmbedModelInfo {
@@ -204,6 +245,48 @@ pub static TEXT_EMBED_MODELS: &[TextEmbedModelInfo] = &[
         dims: 1024,
         max_tokens: 512,
         is_default: false,
+        pooling: EmbeddingPooling::Cls,
+        query_prefix: "",
+        document_prefix: "",
+        multilingual: false,
+    }, // Multilingual E5-small: cross-lingual retrieval, fast (384d)
+    TextEmbedModelInfo {
+        name: "multilingual-e5-small",
+        model_url: "https://huggingface.co/intfloat/multilingual-e5-small/resolve/main/onnx/model.onnx",
+        tokenizer_url: "https://huggingface.co/intfloat/multilingual-e5-small/resolve/main/tokenizer.json",
+        dims: 384,
+        max_tokens: 512,
+        is_default: false,
+        pooling: EmbeddingPooling::Mean,
+        query_prefix: "query: ",
+        document_prefix: "passage: ",
+        multilingual: true,
+    },
+    // Multilingual E5-base: cross-lingual retrieval, better quality (768d)
+    TextEmbedModelInfo {
+        name: "multilingual-e5-base",
+        model_url: "https://huggingface.co/intfloat/multilingual-e5-base/resolve/main/onnx/model.onnx",
+        tokenizer_url: "https://huggingface.co/intfloat/multilingual-e5-base/resolve/main/tokenizer.json",
+        dims: 768,
+        max_tokens: 512,
+        is_default: false,
+        pooling: EmbeddingPooling::Mean,
+        query_prefix: "query: ",
+        document_prefix: "passage: ",
+        multilingual: true,
+    },
+    // Multilingual MiniLM: symmetric sentence similarity in 50+ languages (384d)
+    TextEmbedModelInfo {
+        name: "paraphrase-multilingual-MiniLM-L12-v2",
+        model_url: "https://huggingface.co/sentence-transformers/paraphrase-multilingual-MiniLM-L12-v2/resolve/main/onnx/model.onnx",
+        tokenizer_url: "https://huggingface.co/sentence-transformers/paraphrase-multilingual-MiniLM-L12-v2/resolve/main/tokenizer.json",
+        dims: 384,
+        max_tokens: 128,
+        is_default: false,
+        pooling: EmbeddingPooling::Mean,
+        query_prefix: "",
+        document_prefix: "",
+        multilingual: true,
     },
 ];
 
@@ -242,6 +325,14 @@ pub struct TextEmbedConfig {
     pub enable_cache: bool,
     /// Maximum number of embeddings to cache (default: 1000)
     pub cache_capacity: usize,
+    /// Device to run the model on; falls back to CPU if unavailable (default: auto)
+    pub device: InferenceDevice,
+    /// Texts per inference call when embedding in batches (default: 32)
+    pub batch_size: usize,
+    /// Pooling override; `None` uses the pooling the model was trained with
+    pub pooling: Option<EmbeddingPooling>,
+    /// L2-normalize embeddings to unit length (default: true)
+    pub normalize: bool,
 }
 
 impl Default for TextEmbedConfig {
@@ -259,6 +350,10 @@ impl Default for TextEmbedConfig {
             offline: true,      // Default to offline (no auto-download)
             enable_cache: true, // Cache enabled by default
             cache_capacity: DEFAULT_CACHE_CAPACITY,
+            device: InferenceDevice::default(),
+            batch_size: DEFAULT_EMBED_BATCH_SIZE,
+            pooling: None,
+            normalize: true,
         }
     }
 }
@@ -299,6 +394,31 @@ impl TextEmbedConfig {
             ..Default::default()
         }
     }
+
+    /// Create config for multilingual E5-small model
+    #[must_use]
+    pub fn multilingual_e5_small() -> Self {
+        Self {
+            model_name: "multilingual-e5-small".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Create config for multilingual E5-base model
+    #[must_use]
+    pub fn multilingual_e5_base() -> Self {
+        Self {
+            model_name: "multilingual-e5-base".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Pooling applied to model output: the override if set, else the model's own
+    #[must_use]
+    pub fn effective_pooling(&self) -> EmbeddingPooling {
+        self.pooling
+            .unwrap_or_else(|| get_text_model_info(&self.model_name).pooling)
+    }
 }
 
 // ============================================================================
@@ -421,6 +541,8 @@ pub struct LocalTextEmbedder {
     model_info: &'static TextEmbedModelInfo,
     /// Lazy-loaded ONNX session
     session: Mutex<Option<Session>>,
+    /// Device the loaded session runs on
+    active_device: Mutex<Option<InferenceDevice>>,
     /// Lazy-loaded tokenizer
     tokenizer: Mutex<Option<Tokenizer>>,
     /// Last time the model was used (for idle unloading)
@@ -445,6 +567,7 @@ impl LocalTextEmbedder {
             config,
             model_info,
             session: Mutex::new(None),
+            active_device: Mutex::new(None),
             tokenizer: Mutex::new(None),
             last_used: Mutex::new(Instant::now()),
             cache: Mutex::new(cache),
@@ -524,27 +647,19 @@ impl LocalTextEmbedder {
         // Suppress stderr during ONNX session creation (macOS emits harmless warnings)
         let _stderr_guard = stderr_suppress::StderrSuppressor::new().ok();
 
-        let session = Session::builder()
-            .map_err(|e| MemvidError::EmbeddingFailed {
-                reason: format!("Failed to create session builder: {}", e).into(),
-            })?
-            .with_optimization_level(GraphOptimizationLevel::Level3)
-            .map_err(|e| MemvidError::EmbeddingFailed {
-                reason: format!("Failed to set optimization level: {}", e).into(),
-            })?
-            .with_intra_threads(4)
-            .map_err(|e| MemvidError::EmbeddingFailed {
-                reason: format!("Failed to set intra threads: {}", e).into(),
-            })?
-            .commit_from_file(&model_path)
-            .map_err(|e| MemvidError::EmbeddingFailed {
+        let (session, device) = build_session(self.config.device, &model_path, 4).map_err(|e| {
+            MemvidError::EmbeddingFailed {
                 reason: format!("Failed to load text embedding model: {}", e).into(),
-            })?;
+            }
+        })?;
 
         // _stderr_guard is dropped here, restoring stderr
 
         *session_guard = Some(session);
-        tracing::info!(model = %self.model_info.name, "Text embedding model loaded");
+        if let Ok(mut active) = self.active_device.lock() {
+            *active = Some(device);
+        }
+        tracing::info!(model = %self.model_info.name, device = %device, "Text embedding model loaded");
 
         Ok(())
     }
@@ -604,31 +719,118 @@ impl LocalTextEmbedder {
         hasher.finish()
     }
 
-    /// Encode text to embedding (with caching support)
+    /// Encode a document text to embedding (with caching support)
+    ///
+    /// The model's document prefix (E5: `"passage: "`) is prepended.
     pub fn encode_text(&self, text: &str) -> Result<Vec<f32>> {
+        self.encode_batch(&[text])?
+            .pop()
+            .ok_or_else(|| MemvidError::EmbeddingFailed {
+                reason: "Text embedding produced no output".into(),
+            })
+    }
+
+    /// Encode a search query to embedding, prepending the model's query prefix
+    /// (E5: `"query: "`) so it lands in the same space as encoded documents
+    pub fn encode_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.encode_prefixed(&[text], self.model_info.query_prefix)?
+            .pop()
+            .ok_or_else(|| MemvidError::EmbeddingFailed {
+                reason: "Text embedding produced no output".into(),
+            })
+    }
+
+    /// Encode multiple document texts, running uncached ones through the model
+    /// `config.batch_size` at a time
+    pub fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        self.encode_prefixed(texts, self.model_info.document_prefix)
+    }
+
+    fn encode_prefixed(&self, texts: &[&str], prefix: &str) -> Result<Vec<Vec<f32>>> {
+        if !prefix.is_empty() {
+            let prefixed: Vec<String> =
+                texts.iter().map(|text| format!("{prefix}{text}")).collect();
+            let prefixed: Vec<&str> = prefixed.iter().map(String::as_str).collect();
+            return self.encode_uncached(&prefixed);
+        }
+        self.encode_uncached(texts)
+    }
+
+    fn encode_uncached(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         // 1. Check cache first
+        let mut embeddings: Vec<Option<Vec<f32>>> = vec![None; texts.len()];
         if let Ok(mut cache_guard) = self.cache.lock() {
             if let Some(ref mut cache) = *cache_guard {
-                let key = Self::cache_key(text);
-                if let Some(embedding) = cache.get(key) {
-                    tracing::debug!(text_len = text.len(), "Cache hit");
-                    return Ok(embedding);
+                for (slot, text) in embeddings.iter_mut().zip(texts) {
+                    *slot = cache.get(Self::cache_key(text));
                 }
-                tracing::debug!(text_len = text.len(), "Cache miss");
             }
         }
+        let missing: Vec<usize> = embeddings
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.is_none().then_some(index))
+            .collect();
+        tracing::debug!(
+            texts = texts.len(),
+            cache_misses = missing.len(),
+            "Encoding text batch"
+        );
 
-        // 2. Cache miss - generate embedding normally
-        // Suppress stderr during model loading (macOS emits harmless "Context leak detected" warnings)
-        // This must be set before load_session() to catch ONNX Runtime's global initialization
-        let _stderr_guard = stderr_suppress::StderrSuppressor::new().ok();
+        if !missing.is_empty() {
+            // 2. Cache misses - generate embeddings
+            // Suppress stderr during model loading (macOS emits harmless "Context leak detected" warnings)
+            // This must be set before load_session() to catch ONNX Runtime's global initialization
+            let _stderr_guard = stderr_suppress::StderrSuppressor::new().ok();
+
+            // Ensure session and tokenizer are loaded
+            self.load_session()?;
+            self.load_tokenizer()?;
+
+            for chunk in missing.chunks(self.config.batch_size.max(1)) {
+                let batch: Vec<&str> = chunk.iter().map(|&index| texts[index]).collect();
+                let generated = match self.run_inference(&batch) {
+                    Ok(generated) => generated,
+                    // Some exported models have a fixed batch dimension of 1
+                    Err(err) if batch.len() > 1 => {
+                        tracing::debug!(error = %err, "Batched inference failed, encoding one by one");
+                        let mut generated = Vec::with_capacity(batch.len());
+                        for text in &batch {
+                            generated.extend(self.run_inference(&[text])?);
+                        }
+                        generated
+                    }
+                    Err(err) => return Err(err),
+                };
+
+                // 3. Store in cache
+                if let Ok(mut cache_guard) = self.cache.lock() {
+                    if let Some(ref mut cache) = *cache_guard {
+                        for (text, embedding) in batch.iter().zip(&generated) {
+                            cache.insert(Self::cache_key(text), embedding.clone());
+                        }
+                    }
+                }
+                for (&index, embedding) in chunk.iter().zip(generated) {
+                    embeddings[index] = Some(embedding);
+                }
+            }
+        }
 
-        // Ensure session and tokenizer are loaded
-        self.load_session()?;
-        self.load_tokenizer()?;
+        embeddings
+            .into_iter()
+            .map(|slot| {
+                slot.ok_or_else(|| MemvidError::EmbeddingFailed {
+                    reason: "Text embedding missing from batch output".into(),
+                })
+            })
+            .collect()
+    }
 
-        // Tokenize the text
-        let encoding = {
+    /// Run one inference call over `texts`, returning one pooled embedding per text
+    fn run_inference(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        // Tokenize the texts
+        let encodings = {
             let tokenizer_guard = self
                 .tokenizer
                 .lock()
@@ -640,43 +842,49 @@ impl LocalTextEmbedder {
                         reason: "Tokenizer not loaded".into(),
                     })?;
 
-            tokenizer
-                .encode(text, true)
-                .map_err(|e| MemvidError::EmbeddingFailed {
+            tokenizer.encode_batch(texts.to_vec(), true).map_err(|e| {
+                MemvidError::EmbeddingFailed {
                     reason: format!("Text tokenization failed: {}", e).into(),
-                })?
+                }
+            })?
         };
 
-        let input_ids: Vec<i64> = encoding.get_ids().iter().map(|id| *id as i64).collect();
-        let attention_mask: Vec<i64> = encoding
-            .get_attention_mask()
+        // Padding is fixed, but pad again in case a tokenizer ignores it
+        let batch_size = encodings.len();
+        let max_length = encodings
             .iter()
-            .map(|id| *id as i64)
-            .collect();
-        let token_type_ids: Vec<i64> = encoding
-            .get_type_ids()
-            .iter()
-            .map(|id| *id as i64)
-            .collect();
-        let max_length = input_ids.len();
+            .map(|encoding| encoding.get_ids().len())
+            .max()
+            .unwrap_or(0);
+        let mut input_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        let mut attention_mask: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        let mut token_type_ids: Vec<i64> = Vec::with_capacity(batch_size * max_length);
+        for encoding in &encodings {
+            let padding = max_length - encoding.get_ids().len();
+            input_ids.extend(encoding.get_ids().iter().map(|id| *id as i64));
+            input_ids.extend(std::iter::repeat_n(0, padding));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|id| *id as i64));
+            attention_mask.extend(std::iter::repeat_n(0, padding));
+            token_type_ids.extend(encoding.get_type_ids().iter().map(|id| *id as i64));
+            token_type_ids.extend(std::iter::repeat_n(0, padding));
+        }
 
         // Create input arrays
-        let input_ids_array = Array::from_shape_vec((1, max_length), input_ids).map_err(|e| {
-            MemvidError::EmbeddingFailed {
-                reason: format!("Failed to create input_ids array: {}", e).into(),
-            }
-        })?;
-        let attention_mask_array =
-            Array::from_shape_vec((1, max_length), attention_mask).map_err(|e| {
+        let input_ids_array =
+            Array::from_shape_vec((batch_size, max_length), input_ids).map_err(|e| {
                 MemvidError::EmbeddingFailed {
-                    reason: format!("Failed to create attention_mask array: {}", e).into(),
+                    reason: format!("Failed to create input_ids array: {}", e).into(),
                 }
             })?;
-        let token_type_ids_array =
-            Array::from_shape_vec((1, max_length), token_type_ids).map_err(|e| {
-                MemvidError::EmbeddingFailed {
-                    reason: format!("Failed to create token_type_ids array: {}", e).into(),
-                }
+        let attention_mask_array =
+            Array::from_shape_vec((batch_size, max_length), attention_mask.clone()).map_err(
+                |e| MemvidError::EmbeddingFailed {
+                    reason: format!("Failed to create attention_mask array: {}", e).into(),
+                },
+            )?;
+        let token_type_ids_array = Array::from_shape_vec((batch_size, max_length), token_type_ids)
+            .map_err(|e| MemvidError::EmbeddingFailed {
+                reason: format!("Failed to create token_type_ids array: {}", e).into(),
             })?;
 
         // Update last used timestamp
@@ -770,44 +978,57 @@ impl LocalTextEmbedder {
                     reason: format!("Failed to extract embeddings: {}", e).into(),
                 })?;
 
-        // For BERT-style models, use [CLS] token embedding (first token)
-        // The output shape is typically [batch_size, sequence_length, hidden_size]
+        // The output shape is typically [batch_size, sequence_length, hidden_size];
+        // models exported with pooling built in emit [batch_size, hidden_size]
+        let pooling = self.config.effective_pooling();
         let embedding_dim = self.model_info.dims as usize;
-        let embedding: Vec<f32> = data.iter().take(embedding_dim).copied().collect();
-
-        if embedding.iter().any(|v| !v.is_finite()) {
+        let row_len = if batch_size == 0 {
+            0
+        } else {
+            data.len() / batch_size
+        };
+        if row_len < embedding_dim {
             return Err(MemvidError::EmbeddingFailed {
-                reason: "Text embedding contains non-finite values".into(),
+                reason: format!(
+                    "Model output has {} values for {} texts, expected at least {} each",
+                    data.len(),
+                    batch_size,
+                    embedding_dim
+                )
+                .into(),
             });
         }
 
-        // L2 normalize
-        let normalized = l2_normalize(&embedding);
+        let mut embeddings = Vec::with_capacity(batch_size);
+        for (row, mask) in data
+            .chunks_exact(row_len)
+            .zip(attention_mask.chunks_exact(max_length.max(1)))
+        {
+            let embedding = pool_token_states(row, mask, embedding_dim, pooling);
+            if embedding.iter().any(|v| !v.is_finite()) {
+                return Err(MemvidError::EmbeddingFailed {
+                    reason: "Text embedding contains non-finite values".into(),
+                });
+            }
+            if self.config.normalize {
+                embeddings.push(l2_normalize(&embedding));
+            } else {
+                embeddings.push(embedding);
+            }
+        }
 
         tracing::debug!(
-            text_len = text.len(),
-            dims = normalized.len(),
-            "Generated text embedding"
+            texts = batch_size,
+            dims = embedding_dim,
+            "Generated text embeddings"
         );
 
-        // 3. Store in cache
-        if let Ok(mut cache_guard) = self.cache.lock() {
-            if let Some(ref mut cache) = *cache_guard {
-                let key = Self::cache_key(text);
-                cache.insert(key, normalized.clone());
-            }
-        }
-
-        Ok(normalized)
+        Ok(embeddings)
     }
 
-    /// Encode multiple texts in batch
-    pub fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
-        let mut embeddings = Vec::with_capacity(texts.len());
-        for text in texts {
-            embeddings.push(self.encode_text(text)?);
-        }
-        Ok(embeddings)
+    /// Device the loaded model runs on, or `None` before the first inference
+    pub fn active_device(&self) -> Option<InferenceDevice> {
+        self.active_device.lock().ok().and_then(|device| *device)
     }
 
     /// Get cache statistics
@@ -853,6 +1074,9 @@ impl LocalTextEmbedder {
             if let Ok(mut guard) = self.session.lock() {
                 *guard = None;
             }
+            if let Ok(mut guard) = self.active_device.lock() {
+                *guard = None;
+            }
 
             // Unload tokenizer
             if let Ok(mut guard) = self.tokenizer.lock() {
@@ -868,6 +1092,9 @@ impl LocalTextEmbedder {
         if let Ok(mut guard) = self.session.lock() {
             *guard = None;
         }
+        if let Ok(mut guard) = self.active_device.lock() {
+            *guard = None;
+        }
         if let Ok(mut guard) = self.tokenizer.lock() {
             *guard = None;
         }
@@ -901,6 +1128,14 @@ impl EmbeddingProvider for LocalTextEmbedder {
         self.encode_batch(texts)
     }
 
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.encode_query(text)
+    }
+
+    fn normalized(&self) -> Option<bool> {
+        Some(self.config.normalize)
+    }
+
     fn is_ready(&self) -> bool {
         // Models are lazy-loaded, so always "ready"
         true
@@ -916,6 +1151,40 @@ impl EmbeddingProvider for LocalTextEmbedder {
 // Utilities
 // ============================================================================
 
+/// Reduce one output row of `tokens x dims` values to a `dims` vector
+///
+/// Mean pooling averages the tokens whose attention mask is set; rows that are
+/// already pooled (exactly `dims` long) pass through unchanged.
+fn pool_token_states(
+    row: &[f32],
+    mask: &[i64],
+    dims: usize,
+    pooling: EmbeddingPooling,
+) -> Vec<f32> {
+    if pooling == EmbeddingPooling::Cls || row.len() <= dims {
+        return row[..dims].to_vec();
+    }
+    let mut sum = vec![0.0f32; dims];
+    let mut count = 0usize;
+    for (token, &keep) in row.chunks_exact(dims).zip(mask) {
+        if keep == 0 {
+            continue;
+        }
+        for (acc, value) in sum.iter_mut().zip(token) {
+            *acc += value;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return row[..dims].to_vec();
+    }
+    let count = count as f32;
+    for value in &mut sum {
+        *value /= count;
+    }
+    sum
+}
+
 /// L2 normalize a vector (unit length)
 fn l2_normalize(v: &[f32]) -> Vec<f32> {
     let norm: f32 = v.iter().map(|x| x * x).sum::<f32>().sqrt();
@@ -937,7 +1206,7 @@ mod tests {
 
     #[test]
     fn test_model_registry() {
-        assert_eq!(TEXT_EMBED_MODELS.len(), 4);
+        assert_eq!(TEXT_EMBED_MODELS.len(), 7);
 
         let default_model = default_text_model_info();
         assert_eq!(default_model.name, "bge-small-en-v1.5");
@@ -983,6 +1252,49 @@ mod tests {
         assert_eq!(gte.model_name, "gte-large");
     }
 
+    #[test]
+    fn test_multilingual_models() {
+        let e5 = get_text_model_info("multilingual-e5-small");
+        assert_eq!(e5.dims, 384);
+        assert!(e5.multilingual);
+        assert_eq!(e5.pooling, EmbeddingPooling::Mean);
+        assert_eq!(e5.query_prefix, "query: ");
+        assert_eq!(e5.document_prefix, "passage: ");
+        assert_eq!(get_text_model_info("multilingual-e5-base").dims, 768);
+
+        let config = TextEmbedConfig::multilingual_e5_small();
+        assert_eq!(config.effective_pooling(), EmbeddingPooling::Mean);
+        let cls = TextEmbedConfig {
+            pooling: Some(EmbeddingPooling::Cls),
+            ..config
+        };
+        assert_eq!(cls.effective_pooling(), EmbeddingPooling::Cls);
+        assert_eq!(
+            TextEmbedConfig::default().effective_pooling(),
+            EmbeddingPooling::Cls
+        );
+    }
+
+    #[test]
+    fn test_pool_token_states() {
+        // Three tokens of two dims; the last one is padding
+        let row = [1.0, 2.0, 3.0, 4.0, 100.0, 100.0];
+        let mask = [1, 1, 0];
+        assert_eq!(
+            pool_token_states(&row, &mask, 2, EmbeddingPooling::Cls),
+            vec![1.0, 2.0]
+        );
+        assert_eq!(
+            pool_token_states(&row, &mask, 2, EmbeddingPooling::Mean),
+            vec![2.0, 3.0]
+        );
+        // Already-pooled output passes through
+        assert_eq!(
+            pool_token_states(&[5.0, 6.0], &mask, 2, EmbeddingPooling::Mean),
+            vec![5.0, 6.0]
+        );
+    }
+
     #[test]
     fn test_l2_normalize() {
         let v = vec![3.0, 4.0];
@@ -1007,6 +1319,19 @@ mod tests {
         assert_eq!(embedder.model(), "bge-small-en-v1.5");
         assert_eq!(embedder.dimension(), 384);
         assert!(embedder.is_ready());
+        assert_eq!(embedder.normalized(), Some(true));
+
+        let identity = crate::types::EmbeddingIdentity::of_provider(&embedder);
+        assert_eq!(identity.normalized, Some(true));
+        let raw = LocalTextEmbedder::new(TextEmbedConfig {
+            normalize: false,
+            ..TextEmbedConfig::multilingual_e5_base()
+        })
+        .unwrap();
+        let identity = crate::types::EmbeddingIdentity::of_provider(&raw);
+        assert_eq!(identity.model.as_deref(), Some("multilingual-e5-base"));
+        assert_eq!(identity.dimension, Some(768));
+        assert_eq!(identity.normalized, Some(false));
     }
 
     // ========================================================================
@@ -1,6 +1,8 @@
 use bincode::serde::{decode_from_slice, encode_to_vec};
 use blake3::Hasher;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use std::collections::BTreeMap;
 
 use crate::{
     error::{MemvidError, Result},
@@ -56,6 +58,29 @@ struct LegacyTocV2 {
     pub toc_checksum: [u8; 32],
 }
 
+/// Legacy TOC format without `extensions` (pre-v2.0.140).
+/// Used for backwards compatibility with files written before keyed TOC extensions.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LegacyTocV3 {
+    pub toc_version: u64,
+    pub segments: Vec<SegmentMeta>,
+    pub frames: Vec<Frame>,
+    pub indexes: IndexManifests,
+    pub time_index: Option<TimeIndexManifest>,
+    pub temporal_track: Option<TemporalTrackManifest>,
+    pub memories_track: Option<crate::types::MemoriesTrackManifest>,
+    pub logic_mesh: Option<crate::types::LogicMeshManifest>,
+    pub sketch_track: Option<crate::types::SketchTrackManifest>,
+    pub segment_catalog: SegmentCatalog,
+    pub ticket_ref: TicketRef,
+    pub memory_binding: Option<MemoryBinding>,
+    pub replay_manifest: Option<crate::replay::ReplayManifest>,
+    pub enrichment_queue: crate::types::EnrichmentQueueManifest,
+    // Note: extensions NOT present in this version
+    pub merkle_root: [u8; 32],
+    pub toc_checksum: [u8; 32],
+}
+
 impl From<LegacyTocV1> for Toc {
     fn from(legacy: LegacyTocV1) -> Self {
         Toc {
@@ -73,6 +98,7 @@ impl From<LegacyTocV1> for Toc {
             memory_binding: legacy.memory_binding,
             replay_manifest: None,                // Default for legacy files
             enrichment_queue: Default::default(), // Default for legacy files
+            extensions: BTreeMap::new(),          // Default for legacy files
             merkle_root: legacy.merkle_root,
             toc_checksum: legacy.toc_checksum,
         }
@@ -96,6 +122,31 @@ impl From<LegacyTocV2> for Toc {
             memory_binding: legacy.memory_binding,
             replay_manifest: None, // Default for pre-replay files
             enrichment_queue: Default::default(), // Default for legacy files
+            extensions: BTreeMap::new(), // Default for legacy files
+            merkle_root: legacy.merkle_root,
+            toc_checksum: legacy.toc_checksum,
+        }
+    }
+}
+
+impl From<LegacyTocV3> for Toc {
+    fn from(legacy: LegacyTocV3) -> Self {
+        Toc {
+            toc_version: legacy.toc_version,
+            segments: legacy.segments,
+            frames: legacy.frames,
+            indexes: legacy.indexes,
+            time_index: legacy.time_index,
+            temporal_track: legacy.temporal_track,
+            memories_track: legacy.memories_track,
+            logic_mesh: legacy.logic_mesh,
+            sketch_track: legacy.sketch_track,
+            segment_catalog: legacy.segment_catalog,
+            ticket_ref: legacy.ticket_ref,
+            memory_binding: legacy.memory_binding,
+            replay_manifest: legacy.replay_manifest,
+            enrichment_queue: legacy.enrichment_queue,
+            extensions: BTreeMap::new(), // Default for pre-extension files
             merkle_root: legacy.merkle_root,
             toc_checksum: legacy.toc_checksum,
         }
@@ -121,6 +172,19 @@ impl Toc {
             return Ok(toc);
         }
 
+        // Try V3 format (with replay_manifest/enrichment_queue, without extensions)
+        if let Ok((legacy, bytes_read)) =
+            decode_from_slice::<LegacyTocV3, _>(bytes, canonical_config())
+        {
+            if bytes_read != bytes.len() {
+                return Err(MemvidError::InvalidToc {
+                    reason: "unexpected trailing bytes in V3 format".into(),
+                });
+            }
+            tracing::debug!("Decoded TOC V3 format (pre-extensions)");
+            return Ok(legacy.into());
+        }
+
         // Try V2 format (with memories_track/logic_mesh, without replay_manifest)
         if let Ok((legacy, bytes_read)) =
             decode_from_slice::<LegacyTocV2, _>(bytes, canonical_config())
@@ -156,6 +220,11 @@ impl Toc {
         if let Ok((toc, _)) = decode_from_slice::<Toc, _>(bytes, canonical_config()) {
             return Ok(toc);
         }
+        // Try V3 format (with replay_manifest/enrichment_queue, without extensions)
+        if let Ok((legacy, _)) = decode_from_slice::<LegacyTocV3, _>(bytes, canonical_config()) {
+            tracing::debug!("Decoded TOC V3 format (pre-extensions) in lenient mode");
+            return Ok(legacy.into());
+        }
         // Try V2 format (with memories_track/logic_mesh, without replay_manifest)
         if let Ok((legacy, _)) = decode_from_slice::<LegacyTocV2, _>(bytes, canonical_config()) {
             tracing::debug!("Decoded TOC V2 format (pre-replay_manifest) in lenient mode");
@@ -186,6 +255,155 @@ impl LegacyTocV2 {
     }
 }
 
+impl LegacyTocV3 {
+    /// Encode V3 TOC format for checksum verification.
+    fn encode(&self) -> Result<Vec<u8>> {
+        Ok(encode_to_vec(self, canonical_config())?)
+    }
+}
+
+impl Toc {
+    /// Decode the extension stored under `key`, if present.
+    pub(crate) fn extension<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let Some(bytes) = self.extensions.get(key) else {
+            return Ok(None);
+        };
+        let (value, _) = decode_from_slice::<T, _>(bytes, canonical_config()).map_err(|err| {
+            MemvidError::InvalidToc {
+                reason: format!("failed to decode TOC extension `{key}`: {err}").into(),
+            }
+        })?;
+        Ok(Some(value))
+    }
+
+    /// Store `value` under `key`, replacing any previous extension with the same key.
+    pub(crate) fn set_extension<T: Serialize>(&mut self, key: &str, value: &T) -> Result<()> {
+        let bytes = encode_to_vec(value, canonical_config())?;
+        self.extensions.insert(key.to_string(), bytes);
+        Ok(())
+    }
+
+    /// Offsets and lengths of every index, track, and segment blob the TOC references,
+    /// excluding frame payloads and anything stored inside extensions. Empty blobs are skipped.
+    pub(crate) fn blob_ranges_mut(&mut self) -> Vec<(&mut u64, u64)> {
+        let mut ranges: Vec<(&mut u64, u64)> = Vec::new();
+        let catalog = &mut self.segment_catalog;
+        for seg in &mut catalog.lex_segments {
+            ranges.push((&mut seg.common.bytes_offset, seg.common.bytes_length));
+        }
+        for seg in &mut catalog.vec_segments {
+            ranges.push((&mut seg.common.bytes_offset, seg.common.bytes_length));
+        }
+        for seg in &mut catalog.time_segments {
+            ranges.push((&mut seg.common.bytes_offset, seg.common.bytes_length));
+        }
+        for seg in &mut catalog.temporal_segments {
+            ranges.push((&mut seg.common.bytes_offset, seg.common.bytes_length));
+        }
+        for seg in &mut catalog.tantivy_segments {
+            ranges.push((&mut seg.common.bytes_offset, seg.common.bytes_length));
+        }
+        for seg in &mut catalog.index_segments {
+            ranges.push((&mut seg.common.bytes_offset, seg.common.bytes_length));
+        }
+        for seg in &mut self.segments {
+            ranges.push((&mut seg.bytes_offset, seg.bytes_length));
+        }
+        for seg in &mut self.indexes.lex_segments {
+            ranges.push((&mut seg.bytes_offset, seg.bytes_length));
+        }
+        if let Some(manifest) = self.indexes.lex.as_mut() {
+            ranges.push((&mut manifest.bytes_offset, manifest.bytes_length));
+        }
+        if let Some(manifest) = self.indexes.vec.as_mut() {
+            ranges.push((&mut manifest.bytes_offset, manifest.bytes_length));
+        }
+        if let Some(manifest) = self.indexes.clip.as_mut() {
+            ranges.push((&mut manifest.bytes_offset, manifest.bytes_length));
+        }
+        if let Some(manifest) = self.time_index.as_mut() {
+            ranges.push((&mut manifest.bytes_offset, manifest.bytes_length));
+        }
+        if let Some(track) = self.temporal_track.as_mut() {
+            ranges.push((&mut track.bytes_offset, track.bytes_length));
+        }
+        if let Some(track) = self.memories_track.as_mut() {
+            ranges.push((&mut track.bytes_offset, track.bytes_length));
+        }
+        if let Some(mesh) = self.logic_mesh.as_mut() {
+            ranges.push((&mut mesh.bytes_offset, mesh.bytes_length));
+        }
+        if let Some(track) = self.sketch_track.as_mut() {
+            ranges.push((&mut track.bytes_offset, track.bytes_length));
+        }
+        if let Some(manifest) = self.replay_manifest.as_mut() {
+            ranges.push((&mut manifest.segment_offset, manifest.segment_size));
+        }
+        ranges.retain(|(_, len)| *len > 0);
+        ranges
+    }
+
+    /// `(offset, length, checksum)` of every blob in [`Toc::blob_ranges_mut`] whose manifest
+    /// records a content checksum. The replay segment has none and is left out.
+    pub(crate) fn blob_fingerprints(&self) -> Vec<(u64, u64, [u8; 32])> {
+        let catalog = &self.segment_catalog;
+        let mut fingerprints: Vec<(u64, u64, [u8; 32])> = catalog
+            .lex_segments
+            .iter()
+            .map(|seg| &seg.common)
+            .chain(catalog.vec_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.time_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.temporal_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.tantivy_segments.iter().map(|seg| &seg.common))
+            .chain(catalog.index_segments.iter().map(|seg| &seg.common))
+            .map(|common| (common.bytes_offset, common.bytes_length, common.checksum))
+            .collect();
+        fingerprints.extend(
+            self.segments
+                .iter()
+                .map(|seg| (seg.bytes_offset, seg.bytes_length, seg.primary_checksum)),
+        );
+        fingerprints.extend(
+            self.indexes
+                .lex_segments
+                .iter()
+                .map(|seg| (seg.bytes_offset, seg.bytes_length, seg.checksum)),
+        );
+        let manifests = [
+            self.indexes
+                .lex
+                .as_ref()
+                .map(|m| (m.bytes_offset, m.bytes_length, m.checksum)),
+            self.indexes
+                .vec
+                .as_ref()
+                .map(|m| (m.bytes_offset, m.bytes_length, m.checksum)),
+            self.indexes
+                .clip
+                .as_ref()
+                .map(|m| (m.bytes_offset, m.bytes_length, m.checksum)),
+            self.time_index
+                .as_ref()
+                .map(|m| (m.bytes_offset, m.bytes_length, m.checksum)),
+            self.temporal_track
+                .as_ref()
+                .map(|t| (t.bytes_offset, t.bytes_length, t.checksum)),
+            self.memories_track
+                .as_ref()
+                .map(|t| (t.bytes_offset, t.bytes_length, t.checksum)),
+            self.logic_mesh
+                .as_ref()
+                .map(|m| (m.bytes_offset, m.bytes_length, m.checksum)),
+            self.sketch_track
+                .as_ref()
+                .map(|t| (t.bytes_offset, t.bytes_length, t.checksum)),
+        ];
+        fingerprints.extend(manifests.into_iter().flatten());
+        fingerprints.retain(|(_, len, _)| *len > 0);
+        fingerprints
+    }
+}
+
 impl Toc {
     /// Computes the BLAKE3 checksum used for the TOC integrity field.
     #[must_use]
@@ -207,6 +425,35 @@ impl Toc {
             return Ok(());
         }
 
+        // Try V3 format (without extensions)
+        // Only try if extensions is empty (indicates pre-extension origin)
+        if self.extensions.is_empty() {
+            let legacy_v3 = LegacyTocV3 {
+                toc_version: self.toc_version,
+                segments: self.segments.clone(),
+                frames: self.frames.clone(),
+                indexes: self.indexes.clone(),
+                time_index: self.time_index.clone(),
+                temporal_track: self.temporal_track.clone(),
+                memories_track: self.memories_track.clone(),
+                logic_mesh: self.logic_mesh.clone(),
+                sketch_track: self.sketch_track.clone(),
+                segment_catalog: self.segment_catalog.clone(),
+                ticket_ref: self.ticket_ref.clone(),
+                memory_binding: self.memory_binding.clone(),
+                replay_manifest: self.replay_manifest.clone(),
+                enrichment_queue: self.enrichment_queue.clone(),
+                merkle_root: self.merkle_root,
+                toc_checksum: [0u8; 32],
+            };
+            let v3_bytes = legacy_v3.encode()?;
+            let v3_digest = Self::calculate_checksum(&v3_bytes);
+            if v3_digest == self.toc_checksum {
+                tracing::debug!("TOC checksum verified using V3 format (pre-extensions)");
+                return Ok(());
+            }
+        }
+
         // Try V2 format (with memories_track/logic_mesh, without replay_manifest)
         // Only try if replay_manifest is None (indicates pre-replay origin)
         if self.replay_manifest.is_none() {
@@ -268,7 +515,6 @@ mod tests {
         CanonicalEncoding, Frame, FrameId, FrameRole, FrameStatus, IndexManifests, SegmentCatalog,
         SegmentCompression, SegmentMeta, TicketRef, TimeIndexManifest,
     };
-    use std::collections::BTreeMap;
 
     fn sample_toc() -> Toc {
         Toc {
@@ -369,6 +615,7 @@ mod tests {
             memory_binding: None,
             replay_manifest: None,
             enrichment_queue: Default::default(),
+            extensions: BTreeMap::new(),
             merkle_root: [0x55; 32],
             toc_checksum: [0u8; 32],
         }
@@ -407,4 +654,47 @@ mod tests {
         let err = Toc::decode(&bytes).expect_err("should reject");
         matches!(err, MemvidError::InvalidToc { .. });
     }
+
+    #[test]
+    fn decode_pre_extension_toc() {
+        let toc = sample_toc();
+        let mut legacy = LegacyTocV3 {
+            toc_version: toc.toc_version,
+            segments: toc.segments.clone(),
+            frames: toc.frames.clone(),
+            indexes: toc.indexes.clone(),
+            time_index: toc.time_index.clone(),
+            temporal_track: None,
+            memories_track: None,
+            logic_mesh: None,
+            sketch_track: None,
+            segment_catalog: toc.segment_catalog.clone(),
+            ticket_ref: toc.ticket_ref.clone(),
+            memory_binding: None,
+            replay_manifest: None,
+            enrichment_queue: Default::default(),
+            merkle_root: toc.merkle_root,
+            toc_checksum: [0u8; 32],
+        };
+        legacy.toc_checksum = Toc::calculate_checksum(&legacy.encode().expect("encode"));
+        let bytes = legacy.encode().expect("encode legacy");
+
+        let decoded = Toc::decode(&bytes).expect("decode legacy toc");
+        assert!(decoded.extensions.is_empty());
+        decoded.verify_checksum().expect("legacy checksum matches");
+    }
+
+    #[test]
+    fn extension_roundtrip() {
+        let mut toc = sample_toc();
+        toc.set_extension("test.ext", &vec![1u64, 2, 3])
+            .expect("set extension");
+        let toc = stamp_checksum(toc);
+        let decoded = Toc::decode(&toc.encode().expect("encode")).expect("decode");
+        decoded.verify_checksum().expect("checksum");
+        let value: Option<Vec<u64>> = decoded.extension("test.ext").expect("extension");
+        assert_eq!(value, Some(vec![1, 2, 3]));
+        let missing: Option<Vec<u64>> = decoded.extension("missing").expect("extension");
+        assert!(missing.is_none());
+    }
 }
@@ -2,24 +2,59 @@
 //!
 //! The extractor uses the configured `ExtractionMode` to determine which
 //! engines to run. By default, it uses the `RulesEngine` for fast, offline
-//! pattern-based extraction.
+//! pattern-based extraction; LLM extraction runs through an attached [`LlmBackend`].
 
+use std::fmt;
+use std::sync::Arc;
 use std::time::Instant;
 
 use crate::enrich::{EnrichmentContext, EnrichmentEngine, RulesEngine};
-use crate::types::{FrameId, MemoryCard};
+use crate::types::{FrameId, LlmBackend, MemoryCard};
 
+use super::llm::extract_with_llm;
 use super::types::{ExtractionMode, ExtractionStats};
 
+/// A passage to extract triplets from, with the provenance its cards carry.
+#[derive(Debug, Clone, Copy)]
+pub struct TripletSource<'a> {
+    pub frame_id: FrameId,
+    pub text: &'a str,
+    pub uri: Option<&'a str>,
+    pub title: Option<&'a str>,
+    /// Unix timestamp when the content was created.
+    pub timestamp: i64,
+}
+
+impl TripletSource<'_> {
+    /// The source URI, or the frame's default URI.
+    pub(super) fn uri(&self) -> String {
+        self.uri.map_or_else(
+            || format!("mv2://frames/{}", self.frame_id),
+            ToString::to_string,
+        )
+    }
+}
+
 /// Triplet extractor that runs enrichment engines on text.
 ///
 /// The extractor is stateless and can be reused across multiple extractions.
 /// It wraps the existing `RulesEngine` and adds support for LLM extraction
-/// when configured.
-#[derive(Debug)]
+/// when a backend is attached with [`TripletExtractor::with_llm`]. Without one, or
+/// when the backend fails, `Llm` mode falls back to rules.
 pub struct TripletExtractor {
     mode: ExtractionMode,
     rules_engine: RulesEngine,
+    llm: Option<Arc<dyn LlmBackend>>,
+}
+
+impl fmt::Debug for TripletExtractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TripletExtractor")
+            .field("mode", &self.mode)
+            .field("rules_engine", &self.rules_engine)
+            .field("llm", &self.llm.as_ref().map(|llm| llm.kind()))
+            .finish()
+    }
 }
 
 impl Default for TripletExtractor {
@@ -35,9 +70,24 @@ impl TripletExtractor {
         Self {
             mode,
             rules_engine: RulesEngine::new(),
+            llm: None,
         }
     }
 
+    /// Create an LLM-mode extractor running `backend`.
+    #[must_use]
+    pub fn llm(backend: Arc<dyn LlmBackend>) -> Self {
+        let model = backend.kind().to_string();
+        Self::new(ExtractionMode::Llm(model)).with_llm(backend)
+    }
+
+    /// Attach the backend used by `Llm` and `Hybrid` modes.
+    #[must_use]
+    pub fn with_llm(mut self, backend: Arc<dyn LlmBackend>) -> Self {
+        self.llm = Some(backend);
+        self
+    }
+
     /// Create an extractor with rules-only mode (default).
     #[must_use]
     pub fn rules_only() -> Self {
@@ -84,6 +134,7 @@ impl TripletExtractor {
     ///
     /// # Returns
     /// A tuple of (extracted cards, extraction stats)
+    #[must_use]
     pub fn extract(
         &self,
         frame_id: FrameId,
@@ -91,6 +142,24 @@ impl TripletExtractor {
         uri: Option<&str>,
         title: Option<&str>,
         timestamp: i64,
+    ) -> (Vec<MemoryCard>, ExtractionStats) {
+        self.extract_batch(&[TripletSource {
+            frame_id,
+            text,
+            uri,
+            title,
+            timestamp,
+        }])
+    }
+
+    /// Extract triplets from several passages; LLM extraction packs them into as few
+    /// prompts as the backend's context window allows.
+    ///
+    /// Duplicate entity:slot pairs within a passage are reduced to the most confident card.
+    #[must_use]
+    pub fn extract_batch(
+        &self,
+        sources: &[TripletSource<'_>],
     ) -> (Vec<MemoryCard>, ExtractionStats) {
         if !self.mode.is_enabled() {
             return (Vec::new(), ExtractionStats::default());
@@ -98,38 +167,58 @@ impl TripletExtractor {
 
         let start = Instant::now();
         let mut all_cards = Vec::new();
+        let mut run_rules = self.mode.should_run_rules();
+        let mut llm_fallback = false;
 
-        // Run rules-based extraction
-        if self.mode.should_run_rules() {
-            let ctx = EnrichmentContext::new(
-                frame_id,
-                uri.unwrap_or(&format!("mv2://frames/{frame_id}"))
-                    .to_string(),
-                text.to_string(),
-                title.map(String::from),
-                timestamp,
-                None,
-            );
-
-            let result = self.rules_engine.enrich(&ctx);
-            if result.success {
-                all_cards.extend(result.cards);
+        // Run LLM-based extraction
+        let mut llm_cards = Vec::new();
+        if self.mode.should_run_llm() {
+            if let Some(backend) = &self.llm {
+                let model = self.mode.llm_model().unwrap_or_else(|| backend.kind());
+                match extract_with_llm(backend.as_ref(), model, sources) {
+                    Ok(cards) => llm_cards = cards,
+                    Err(err) => {
+                        tracing::warn!(
+                            target: "memvid::triplet",
+                            ?err,
+                            "LLM extraction failed, falling back to rules"
+                        );
+                        llm_fallback = true;
+                    }
+                }
+            } else {
+                tracing::debug!(
+                    target: "memvid::triplet",
+                    "no LLM backend configured, falling back to rules"
+                );
+                llm_fallback = true;
             }
+            run_rules |= llm_fallback;
         }
 
-        // Run LLM-based extraction
-        let llm_count = if self.mode.should_run_llm() {
-            tracing::debug!(
-                target: "memvid::triplet",
-                "LLM extraction requested"
-            );
-            0
-        } else {
-            0
-        };
+        // Run rules-based extraction
+        if run_rules {
+            for source in sources {
+                let ctx = EnrichmentContext::new(
+                    source.frame_id,
+                    source.uri(),
+                    source.text.to_string(),
+                    source.title.map(String::from),
+                    source.timestamp,
+                    None,
+                );
+
+                let result = self.rules_engine.enrich(&ctx);
+                if result.success {
+                    all_cards.extend(result.cards);
+                }
+            }
+        }
 
         let elapsed_ms = start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
         let rules_count = all_cards.len();
+        let llm_count = llm_cards.len();
+        all_cards.extend(llm_cards);
 
         // Deduplicate cards with same entity:slot
         let (unique_cards, dedup_count) = deduplicate_cards(all_cards);
@@ -137,6 +226,7 @@ impl TripletExtractor {
         let mut stats = ExtractionStats::from_rules(rules_count, elapsed_ms);
         stats.add_llm(llm_count);
         stats.record_dedup(dedup_count);
+        stats.llm_fallback = llm_fallback;
 
         (unique_cards, stats)
     }
@@ -159,7 +249,7 @@ impl TripletExtractor {
     }
 }
 
-/// Deduplicate cards by entity:slot, keeping the highest confidence one.
+/// Deduplicate cards by source frame and entity:slot, keeping the highest confidence one.
 fn deduplicate_cards(mut cards: Vec<MemoryCard>) -> (Vec<MemoryCard>, usize) {
     use std::collections::HashMap;
 
@@ -170,11 +260,11 @@ fn deduplicate_cards(mut cards: Vec<MemoryCard>) -> (Vec<MemoryCard>, usize) {
     let original_count = cards.len();
 
     // Group by entity:slot, keep highest confidence
-    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut seen: HashMap<(FrameId, String), usize> = HashMap::new();
     let mut keep = vec![true; cards.len()];
 
     for (i, card) in cards.iter().enumerate() {
-        let key = card.default_version_key();
+        let key = (card.source_frame_id, card.default_version_key());
         if let Some(&existing_idx) = seen.get(&key) {
             // Compare confidence, keep higher one
             let existing_conf = cards[existing_idx].confidence.unwrap_or(0.0);
@@ -289,4 +379,39 @@ mod tests {
         assert_eq!(removed, 1);
         assert_eq!(unique[0].value, "Company B"); // Higher confidence kept
     }
+    struct OfflineLlm;
+
+    impl LlmBackend for OfflineLlm {
+        fn kind(&self) -> &'static str {
+            "offline"
+        }
+        fn context_window(&self) -> usize {
+            4096
+        }
+        fn complete(
+            &self,
+            _prompt: &str,
+            _params: &crate::types::LlmParams,
+        ) -> crate::Result<crate::types::LlmCompletion> {
+            Err(crate::MemvidError::LlmFailed {
+                reason: "model unavailable".into(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_llm_mode_falls_back_to_rules() {
+        let text = "I work at Anthropic.";
+
+        let (cards, stats) = TripletExtractor::new(ExtractionMode::Llm("phi".into()))
+            .extract(1, text, None, None, 0);
+        assert!(stats.llm_fallback);
+        assert!(cards.iter().any(|c| c.slot == "employer"));
+
+        let (cards, stats) =
+            TripletExtractor::llm(Arc::new(OfflineLlm)).extract(1, text, None, None, 0);
+        assert!(stats.llm_fallback);
+        assert_eq!(stats.llm_extracted, 0);
+        assert!(cards.iter().any(|c| c.engine == "rules"));
+    }
 }
@@ -0,0 +1,226 @@
+//! LLM-backed triplet extraction.
+//!
+//! Passages are packed into as few prompts as the backend's context window allows. The model
+//! answers with one JSON object per triplet, naming the passage it came from; lines that do
+//! not parse are ignored.
+
+use serde::Deserialize;
+
+use crate::error::Result;
+use crate::types::{LlmBackend, LlmParams, MemoryCard, MemoryCardBuilder};
+
+use super::extractor::TripletSource;
+
+/// Version stamped on cards produced by LLM extraction.
+pub(super) const LLM_ENGINE_VERSION: &str = "1.0.0";
+
+const PROMPT_HEADER: &str = "Extract factual relations from the numbered passages below as \
+subject-predicate-object triplets.
+Respond with one JSON object per line and nothing else, in the form
+{\"passage\": 1, \"subject\": \"Alice\", \"predicate\": \"employer\", \"object\": \"Acme Corp\", \"confidence\": 0.9}
+Use short snake_case predicates. Use \"user\" as the subject of statements the author makes \
+about themselves.
+";
+
+/// Sampling parameters for extraction prompts.
+fn extraction_params() -> LlmParams {
+    LlmParams {
+        max_tokens: 1024,
+        ..LlmParams::default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LlmTriplet {
+    passage: usize,
+    subject: String,
+    predicate: String,
+    object: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+/// Extract triplets from `sources` with `backend`, stamping cards with engine `llm:<model>`.
+///
+/// # Errors
+/// Returns the first backend error; no cards are returned in that case.
+pub(super) fn extract_with_llm(
+    backend: &dyn LlmBackend,
+    model: &str,
+    sources: &[TripletSource<'_>],
+) -> Result<Vec<MemoryCard>> {
+    let params = extraction_params();
+    let budget = backend
+        .context_window()
+        .saturating_sub(params.max_tokens)
+        .saturating_sub(backend.count_tokens(PROMPT_HEADER))
+        .max(1);
+    let engine = format!("llm:{model}");
+
+    let mut cards = Vec::new();
+    for batch in batches(backend, sources, budget) {
+        let prompt = build_prompt(backend, &batch, budget);
+        let completion = backend.complete(&prompt, &params)?;
+        cards.extend(parse_triplets(&completion.text, &batch, &engine));
+    }
+    Ok(cards)
+}
+
+/// Group consecutive sources so each group's passages fit in `budget` tokens.
+fn batches<'s, 'a>(
+    backend: &dyn LlmBackend,
+    sources: &'s [TripletSource<'a>],
+    budget: usize,
+) -> Vec<Vec<&'s TripletSource<'a>>> {
+    let mut batches = Vec::new();
+    let mut current = Vec::new();
+    let mut used = 0;
+    for source in sources.iter().filter(|s| !s.text.trim().is_empty()) {
+        let tokens = backend.count_tokens(source.text).min(budget);
+        if !current.is_empty() && used + tokens > budget {
+            batches.push(std::mem::take(&mut current));
+            used = 0;
+        }
+        used += tokens;
+        current.push(source);
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+fn build_prompt(backend: &dyn LlmBackend, batch: &[&TripletSource<'_>], budget: usize) -> String {
+    let mut prompt = PROMPT_HEADER.to_string();
+    for (index, source) in batch.iter().enumerate() {
+        let text = truncate_to_tokens(backend, source.text.trim(), budget);
+        prompt.push_str(&format!("\n[{}] {text}\n", index + 1));
+    }
+    prompt
+}
+
+/// Cut `text` at a char boundary so a passage larger than the whole budget still fits.
+fn truncate_to_tokens<'t>(backend: &dyn LlmBackend, text: &'t str, budget: usize) -> &'t str {
+    if backend.count_tokens(text) <= budget {
+        return text;
+    }
+    let mut end = text.len().min(budget.saturating_mul(4));
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn parse_triplets(output: &str, batch: &[&TripletSource<'_>], engine: &str) -> Vec<MemoryCard> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let start = line.find('{')?;
+            let end = line.rfind('}')?;
+            serde_json::from_str::<LlmTriplet>(line.get(start..=end)?).ok()
+        })
+        .filter_map(|triplet| {
+            let source = batch.get(triplet.passage.checked_sub(1)?)?;
+            let entity = triplet.subject.trim();
+            let slot = normalize_predicate(&triplet.predicate);
+            let value = triplet.object.trim();
+            if entity.is_empty() || slot.is_empty() || value.is_empty() {
+                return None;
+            }
+            let mut builder = MemoryCardBuilder::new()
+                .fact()
+                .entity(entity)
+                .slot(slot)
+                .value(value)
+                .source(source.frame_id, Some(source.uri()))
+                .engine(engine, LLM_ENGINE_VERSION);
+            if let Some(confidence) = triplet.confidence {
+                builder = builder.confidence(confidence.clamp(0.0, 1.0));
+            }
+            builder.build(0).ok()
+        })
+        .collect()
+}
+
+/// Lowercase snake_case, as the rules engine names its slots.
+fn normalize_predicate(predicate: &str) -> String {
+    predicate
+        .trim()
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LlmCompletion;
+    use std::sync::Mutex;
+
+    struct ScriptedLlm {
+        window: usize,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl LlmBackend for ScriptedLlm {
+        fn kind(&self) -> &'static str {
+            "scripted"
+        }
+        fn context_window(&self) -> usize {
+            self.window
+        }
+        fn complete(&self, prompt: &str, _params: &LlmParams) -> Result<LlmCompletion> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            let passages = prompt.matches("\n[").count();
+            let text = (1..=passages)
+                .map(|n| {
+                    format!(
+                        "{{\"passage\": {n}, \"subject\": \"Acme\", \"predicate\": \"Founded In\", \"object\": \"19{n:02}\"}}"
+                    )
+                })
+                .chain(std::iter::once("not json".to_string()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(LlmCompletion {
+                text,
+                ..LlmCompletion::default()
+            })
+        }
+    }
+
+    fn source(frame_id: u64, text: &str) -> TripletSource<'_> {
+        TripletSource {
+            frame_id,
+            text,
+            uri: None,
+            title: None,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_llm_extraction_batches_and_maps_passages() {
+        let texts = ["a".repeat(400), "b".repeat(400), "c".repeat(400)];
+        let sources: Vec<_> = texts
+            .iter()
+            .enumerate()
+            .map(|(i, t)| source(i as u64, t))
+            .collect();
+        let backend = ScriptedLlm {
+            // Room for two 100-token passages per prompt.
+            window: 1024 + PROMPT_HEADER.len().div_ceil(4) + 250,
+            prompts: Mutex::new(Vec::new()),
+        };
+
+        let cards = extract_with_llm(&backend, "test-model", &sources).unwrap();
+        assert_eq!(backend.prompts.lock().unwrap().len(), 2);
+        assert_eq!(cards.len(), 3);
+        let frames: Vec<_> = cards.iter().map(|c| c.source_frame_id).collect();
+        assert_eq!(frames, [0, 1, 2]);
+        assert_eq!(cards[0].slot, "founded_in");
+        assert_eq!(cards[0].engine, "llm:test-model");
+        assert_eq!(cards[2].value, "1901");
+    }
+}
@@ -11,7 +11,7 @@
 //!                  ↓
 //!        ┌────────┴────────┐
 //!        │  RulesEngine    │ ← Fast, offline pattern matching
-//!        │  (LLM Engine)   │ ← When configured (future)
+//!        │  LlmBackend     │ ← When attached with `with_llm`
 //!        └─────────────────┘
 //! ```
 //!
@@ -43,12 +43,15 @@
 //! # Extraction Modes
 //!
 //! - **Rules** (default): Fast regex-based pattern matching. No external dependencies.
-//! - **Llm**: LLM-based extraction for complex sentences. Requires model configuration.
+//! - **Llm**: LLM-based extraction for complex sentences. Requires an `LlmBackend`; falls back
+//!   to rules when none is attached or the model fails. `Memvid::extract_triplets` validates
+//!   triplets against the schema registry and skips ones already stored.
 //! - **Hybrid**: Run both rules and LLM, deduplicate results. Auto-enabled when LLM configured.
 //! - **Disabled**: No extraction.
 
 mod extractor;
+mod llm;
 mod types;
 
-pub use extractor::TripletExtractor;
+pub use extractor::{TripletExtractor, TripletSource};
 pub use types::{ExtractionMode, ExtractionStats};
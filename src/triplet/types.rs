@@ -12,8 +12,8 @@ pub enum ExtractionMode {
     /// Fast, offline pattern-based extraction (default).
     /// Uses regex patterns to identify common relationship patterns.
     Rules,
-    /// LLM-based extraction for complex sentences.
-    /// Requires an LLM model to be configured.
+    /// LLM-based extraction for complex sentences, naming the model.
+    /// Requires an LLM backend; falls back to rules without one.
     Llm(String),
     /// Hybrid mode: run both rules and LLM, deduplicate results.
     /// Automatically enabled when LLM is configured.
@@ -70,6 +70,12 @@ pub struct ExtractionStats {
     pub total_stored: usize,
     /// Extraction time in milliseconds.
     pub extraction_time_ms: u64,
+    /// Triplets dropped for failing schema validation (see `Memvid::schema_violations`).
+    #[serde(default)]
+    pub schema_rejected: usize,
+    /// LLM extraction was requested but the model was unavailable, so rules ran instead.
+    #[serde(default)]
+    pub llm_fallback: bool,
 }
 
 impl ExtractionStats {
@@ -82,6 +88,7 @@ impl ExtractionStats {
             duplicates_removed: 0,
             total_stored: count,
             extraction_time_ms: time_ms,
+            ..Self::default()
         }
     }
 
@@ -0,0 +1,141 @@
+//! Retrieval counters: how often each frame is returned by search or cited by ask.
+//!
+//! Counts accumulate in memory and are folded into a small table persisted in the TOC
+//! (extension key [`ACCESS_STATS_EXTENSION`]) by the next commit, so recording never costs an
+//! fsync per query.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// TOC extension key holding the persisted [`AccessStats`].
+pub const ACCESS_STATS_EXTENSION: &str = "memvid.access_stats";
+
+/// Counters for one frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameAccess {
+    /// Times the frame appeared in search results.
+    pub returned: u64,
+    /// Times the frame was cited by an ask answer.
+    pub cited: u64,
+    /// Unix seconds of the most recent return or citation.
+    pub last_accessed: i64,
+}
+
+impl FrameAccess {
+    /// Combined retrieval count; citations weigh double.
+    #[must_use]
+    pub fn heat(&self) -> u64 {
+        self.returned.saturating_add(self.cited.saturating_mul(2))
+    }
+
+    fn merge(&mut self, other: &FrameAccess) {
+        self.returned = self.returned.saturating_add(other.returned);
+        self.cited = self.cited.saturating_add(other.cited);
+        self.last_accessed = self.last_accessed.max(other.last_accessed);
+    }
+}
+
+/// A frame ranked by [`FrameAccess::heat`], as reported in `Stats::hot_frames`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotFrame {
+    pub frame_id: FrameId,
+    pub returned: u64,
+    pub cited: u64,
+    pub last_accessed: i64,
+}
+
+/// Per-frame retrieval counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessStats {
+    pub frames: BTreeMap<FrameId, FrameAccess>,
+}
+
+impl AccessStats {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Count one search return for each frame in `frame_ids`.
+    pub fn record_returned(&mut self, frame_ids: impl IntoIterator<Item = FrameId>, now: i64) {
+        for frame_id in frame_ids {
+            let entry = self.frames.entry(frame_id).or_default();
+            entry.returned = entry.returned.saturating_add(1);
+            entry.last_accessed = entry.last_accessed.max(now);
+        }
+    }
+
+    /// Count one ask citation for each frame in `frame_ids`.
+    pub fn record_cited(&mut self, frame_ids: impl IntoIterator<Item = FrameId>, now: i64) {
+        for frame_id in frame_ids {
+            let entry = self.frames.entry(frame_id).or_default();
+            entry.cited = entry.cited.saturating_add(1);
+            entry.last_accessed = entry.last_accessed.max(now);
+        }
+    }
+
+    /// Add every counter in `other` to this table.
+    pub fn merge(&mut self, other: &AccessStats) {
+        for (frame_id, access) in &other.frames {
+            self.frames.entry(*frame_id).or_default().merge(access);
+        }
+    }
+
+    /// Counters for `frame_id`, if it has been returned or cited.
+    #[must_use]
+    pub fn get(&self, frame_id: FrameId) -> Option<&FrameAccess> {
+        self.frames.get(&frame_id)
+    }
+
+    /// The `limit` hottest frames, most retrieved first; ties go to the most recent access.
+    #[must_use]
+    pub fn hot_frames(&self, limit: usize) -> Vec<HotFrame> {
+        let mut ranked: Vec<(&FrameId, &FrameAccess)> = self.frames.iter().collect();
+        ranked.sort_by(|(a_id, a), (b_id, b)| {
+            b.heat()
+                .cmp(&a.heat())
+                .then(b.last_accessed.cmp(&a.last_accessed))
+                .then(a_id.cmp(b_id))
+        });
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(frame_id, access)| HotFrame {
+                frame_id: *frame_id,
+                returned: access.returned,
+                cited: access.cited,
+                last_accessed: access.last_accessed,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_and_rank_hot_frames() {
+        let mut persisted = AccessStats::default();
+        persisted.record_returned([1, 2, 3], 100);
+        persisted.record_returned([2], 110);
+
+        let mut pending = AccessStats::default();
+        pending.record_cited([3], 200);
+        persisted.merge(&pending);
+
+        let hot = persisted.hot_frames(2);
+        assert_eq!(
+            hot.iter().map(|frame| frame.frame_id).collect::<Vec<_>>(),
+            [3, 2]
+        );
+        assert_eq!(
+            (hot[0].returned, hot[0].cited, hot[0].last_accessed),
+            (1, 1, 200)
+        );
+        assert_eq!(persisted.get(2).map(FrameAccess::heat), Some(2));
+    }
+}
@@ -16,6 +16,12 @@ pub const ACL_READ_GROUPS_KEY: &str = "acl_read_groups";
 pub const ACL_READ_PRINCIPALS_KEY: &str = "acl_read_principals";
 /// ACL policy schema version marker.
 pub const ACL_POLICY_VERSION_KEY: &str = "acl_policy_version";
+/// Data classification of the frame (`public`, `internal`, or `secret`).
+pub const CLASSIFICATION_KEY: &str = "classification";
+/// Purposes the data subject consented to (canonical JSON string array).
+pub const CONSENT_KEY: &str = "consent";
+/// Text substituted for the content of redacted hits.
+pub const REDACTED_TEXT: &str = "[redacted]";
 
 /// Enforcement mode for ACL checks.
 ///
@@ -44,4 +50,89 @@ pub struct AclContext {
     /// Caller group IDs used for group-based ACL checks.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub group_ids: Vec<String>,
+    /// Clearance and consent checks against frame classification labels. Applied whenever
+    /// set, independent of the tenant ACL and its enforcement mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<ClassificationPolicy>,
+}
+
+/// Sensitivity of a frame, stored under [`CLASSIFICATION_KEY`]. Levels are ordered from
+/// least to most sensitive.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Classification {
+    #[default]
+    Public,
+    Internal,
+    Secret,
+}
+
+impl Classification {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Internal => "internal",
+            Self::Secret => "secret",
+        }
+    }
+
+    /// Parse a stored label, case-insensitively. Unknown labels yield `None`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "public" => Some(Self::Public),
+            "internal" => Some(Self::Internal),
+            "secret" => Some(Self::Secret),
+            _ => None,
+        }
+    }
+}
+
+/// What happens to hits the caller is not cleared for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassificationAction {
+    /// Drop the hit.
+    #[default]
+    Filter,
+    /// Keep the hit's frame id, rank, and score, but replace its text with
+    /// [`REDACTED_TEXT`] and clear its title, URI, snippet, and metadata.
+    Redact,
+}
+
+/// Caller clearance checked against frame classification and consent labels.
+///
+/// A hit passes when its classification is at or below `clearance` and, for every entry in
+/// `purposes`, the frame's consent list includes it. Frames with an unknown classification
+/// label are treated as [`Classification::Secret`].
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
+pub struct ClassificationPolicy {
+    /// Highest classification the caller may read.
+    #[serde(default)]
+    pub clearance: Classification,
+    /// Classification assumed for frames without a label.
+    #[serde(default)]
+    pub unlabeled: Classification,
+    /// Purposes the caller retrieves for, e.g. `llm` or `share`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub purposes: Vec<String>,
+    /// Whether frames without a consent list fail `purposes`; by default they pass.
+    #[serde(default)]
+    pub require_consent: bool,
+    #[serde(default)]
+    pub action: ClassificationAction,
+}
+
+impl ClassificationPolicy {
+    /// Policy admitting frames up to `clearance`, dropping the rest.
+    #[must_use]
+    pub fn clearance(clearance: Classification) -> Self {
+        Self {
+            clearance,
+            ..Self::default()
+        }
+    }
 }
@@ -0,0 +1,177 @@
+//! Lexical analyzer configuration: custom stopwords, query-time synonyms, and terms that must
+//! not be stemmed.
+//!
+//! Stored in the TOC under [`ANALYZER_CONFIG_EXTENSION`]. Stopwords and protected terms change
+//! how content is indexed, so setting them reindexes existing frames; synonyms only expand
+//! queries.
+
+use serde::{Deserialize, Serialize};
+
+/// TOC extension key holding the file's [`AnalyzerConfig`].
+pub const ANALYZER_CONFIG_EXTENSION: &str = "memvid.analyzer";
+
+/// Most token lists the lex fallback tries when synonyms multiply a query.
+const MAX_TOKEN_VARIANTS: usize = 16;
+
+/// Per-file additions to the default English analyzer. Terms are matched case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyzerConfig {
+    /// Words dropped from indexed content and from queries.
+    pub stopwords: Vec<String>,
+    /// Groups of interchangeable terms; a query for any member also matches the others.
+    /// Members may be phrases ("google kubernetes engine").
+    pub synonyms: Vec<Vec<String>>,
+    /// Words indexed and queried as written instead of stemmed (e.g. "kubernetes", "pandas").
+    pub protected_terms: Vec<String>,
+}
+
+impl AnalyzerConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add words to drop from content and queries.
+    #[must_use]
+    pub fn with_stopwords<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.stopwords.extend(words.into_iter().map(Into::into));
+        self.normalized()
+    }
+
+    /// Add a group of interchangeable terms, e.g. `["k8s", "kubernetes"]`.
+    #[must_use]
+    pub fn with_synonyms<I, S>(mut self, group: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.synonyms
+            .push(group.into_iter().map(Into::into).collect());
+        self.normalized()
+    }
+
+    /// Add words that must not be stemmed.
+    #[must_use]
+    pub fn with_protected_terms<I, S>(mut self, terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.protected_terms
+            .extend(terms.into_iter().map(Into::into));
+        self.normalized()
+    }
+
+    /// Whether nothing is configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.stopwords.is_empty() && self.synonyms.is_empty() && self.protected_terms.is_empty()
+    }
+
+    /// Lowercased, trimmed, and deduplicated; blank terms and synonym groups with fewer than
+    /// two members are dropped.
+    #[must_use]
+    pub fn normalized(mut self) -> Self {
+        fn clean(terms: &mut Vec<String>) {
+            for term in terms.iter_mut() {
+                *term = term
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase();
+            }
+            terms.retain(|term| !term.is_empty());
+            let mut seen = std::collections::HashSet::new();
+            terms.retain(|term| seen.insert(term.clone()));
+        }
+        clean(&mut self.stopwords);
+        clean(&mut self.protected_terms);
+        for group in &mut self.synonyms {
+            clean(group);
+        }
+        self.synonyms.retain(|group| group.len() > 1);
+        self
+    }
+
+    #[must_use]
+    pub fn is_stopword(&self, word: &str) -> bool {
+        self.stopwords
+            .iter()
+            .any(|stopword| stopword.eq_ignore_ascii_case(word))
+    }
+
+    /// Every other member of the groups `term` belongs to.
+    #[must_use]
+    pub fn synonyms_of(&self, term: &str) -> Vec<&str> {
+        let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut found: Vec<&str> = Vec::new();
+        for group in &self.synonyms {
+            if !group
+                .iter()
+                .any(|member| member.eq_ignore_ascii_case(&term))
+            {
+                continue;
+            }
+            for member in group {
+                if !member.eq_ignore_ascii_case(&term) && !found.contains(&member.as_str()) {
+                    found.push(member);
+                }
+            }
+        }
+        found
+    }
+
+    /// `tokens` followed by copies with one token swapped for each of its synonyms (phrases
+    /// split into words), for matchers that require every token to appear.
+    #[must_use]
+    pub fn token_variants(&self, tokens: &[String]) -> Vec<Vec<String>> {
+        let mut variants = vec![tokens.to_vec()];
+        for (index, token) in tokens.iter().enumerate() {
+            for synonym in self.synonyms_of(token) {
+                if variants.len() >= MAX_TOKEN_VARIANTS {
+                    return variants;
+                }
+                let mut variant = tokens[..index].to_vec();
+                variant.extend(synonym.split(' ').map(str::to_string));
+                variant.extend_from_slice(&tokens[index + 1..]);
+                variants.push(variant);
+            }
+        }
+        variants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synonyms_are_symmetric_and_normalized() {
+        let config = AnalyzerConfig::new()
+            .with_synonyms(["K8s", " Kubernetes ", "google  kubernetes engine"])
+            .with_synonyms(["solo"])
+            .with_stopwords(["Acme", "acme", " "]);
+        assert_eq!(config.synonyms.len(), 1);
+        assert_eq!(config.stopwords, vec!["acme"]);
+        assert_eq!(
+            config.synonyms_of("kubernetes"),
+            vec!["k8s", "google kubernetes engine"]
+        );
+        assert_eq!(config.synonyms_of("k8s").len(), 2);
+        assert!(config.synonyms_of("docker").is_empty());
+        assert!(config.is_stopword("ACME"));
+
+        let tokens = vec!["deploy".to_string(), "k8s".to_string()];
+        let variants = config.token_variants(&tokens);
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[1], vec!["deploy", "kubernetes"]);
+        assert_eq!(
+            variants[2],
+            vec!["deploy", "google", "kubernetes", "engine"]
+        );
+    }
+}
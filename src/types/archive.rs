@@ -0,0 +1,23 @@
+//! Archive expansion into parent and child frames (see `Memvid::put_archive`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::reader::ArchiveSkip;
+
+/// `kind` of the frame holding an archive's member listing.
+pub const ARCHIVE_FRAME_KIND: &str = "archive";
+/// Extra-metadata key holding a child frame's path inside its archive.
+pub const ARCHIVE_MEMBER_KEY: &str = "archive_member";
+
+/// Frames written by `Memvid::put_archive`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveReceipt {
+    /// Sequence of the parent frame holding the listing.
+    pub archive_sequence: u64,
+    /// One sequence per member, in archive order.
+    pub member_sequences: Vec<u64>,
+    /// Member paths, parallel to `member_sequences`.
+    pub members: Vec<String>,
+    /// Members left out by the archive limits or for unsafe paths.
+    pub skipped: Vec<ArchiveSkip>,
+}
@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use super::acl::{AclContext, AclEnforcementMode};
 use super::adaptive::AdaptiveConfig;
+use super::audit::SourceSpan;
 use super::common::FrameId;
+use super::embedding::EmbedderRequestStats;
 #[cfg(feature = "temporal_track")]
 use super::search::SearchHitTemporal;
 use super::search::SearchResponse;
@@ -10,6 +12,7 @@ use super::search::SearchResponse;
 use super::temporal::TemporalFilter;
 use crate::Result;
 
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum AskMode {
@@ -19,6 +22,12 @@ pub enum AskMode {
     Sem,
     /// Hybrid (lexical + semantic) retrieval.
     Hybrid,
+    /// Hybrid retrieval with an answer assembled offline from the best-matching sentences of
+    /// the top hits; each sentence is cited by its byte range.
+    Extractive,
+    /// Hybrid retrieval with an answer written by an `LlmBackend` (`Memvid::ask_with_llm`).
+    #[cfg_attr(feature = "mcp", schemars(skip))]
+    Generative,
 }
 
 impl Default for AskMode {
@@ -43,6 +52,7 @@ pub enum AskRetriever {
 }
 
 /// Request payload for retrieval + synthesis.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AskRequest {
     pub question: String,
@@ -60,6 +70,7 @@ pub struct AskRequest {
     pub end: Option<i64>,
     #[cfg(feature = "temporal_track")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub temporal: Option<TemporalFilter>,
     #[serde(default)]
     pub context_only: bool,
@@ -74,12 +85,15 @@ pub struct AskRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// Adaptive retrieval configuration. When set, dynamically determines how many
     /// results to retrieve based on relevancy score distribution.
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub adaptive: Option<AdaptiveConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// Optional caller identity context used for ACL filtering.
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub acl_context: Option<AclContext>,
     #[serde(default)]
     /// ACL evaluation mode (`audit` or `enforce`).
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub acl_enforcement_mode: AclEnforcementMode,
 }
 
@@ -91,6 +105,12 @@ pub struct AskStats {
     pub synthesis_ms: u128,
     /// End-to-end latency in milliseconds.
     pub latency_ms: u128,
+    /// Prompt tokens sent to the LLM backend (generative mode).
+    #[serde(default)]
+    pub prompt_tokens: usize,
+    /// Tokens generated by the LLM backend (generative mode).
+    #[serde(default)]
+    pub completion_tokens: usize,
 }
 
 /// Structured citation pointing back into the memory.
@@ -103,6 +123,9 @@ pub struct AskCitation {
     pub chunk_range: Option<(usize, usize)>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub score: Option<f32>,
+    /// Byte range of the cited text within `AskResponse::answer` (extractive answers only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer_range: Option<(usize, usize)>,
 }
 
 /// Fragment of retrieval context sent to a synthesizer (with ranges and optional temporal info).
@@ -153,6 +176,9 @@ pub struct AskResponse {
     pub citations: Vec<AskCitation>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub context_fragments: Vec<AskContextFragment>,
+    /// Sources behind extractive answers, one per citation with the quoted sentence as snippet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceSpan>,
     pub stats: AskStats,
 }
 
@@ -168,4 +194,9 @@ pub trait VecEmbedder {
     }
 
     fn embedding_dimension(&self) -> usize;
+
+    /// Request counters of embedders backed by a remote API; `None` for local models.
+    fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        None
+    }
 }
@@ -0,0 +1,31 @@
+//! Audio recordings stored as a media frame plus timestamped transcript segments.
+//!
+//! `Memvid::put_audio` (feature `whisper`) and `Memvid::put_transcribed_audio` write one parent
+//! frame holding the raw recording and one child frame per transcript segment. Segment frames
+//! carry their offsets under [`AUDIO_START_MS_KEY`] and [`AUDIO_END_MS_KEY`] and are timestamped
+//! at recording time plus their start offset, so they anchor in the time index on their own.
+
+use serde::{Deserialize, Serialize};
+
+/// `kind` of the frame holding the raw recording.
+pub const AUDIO_FRAME_KIND: &str = "audio";
+/// `kind` of a transcript segment frame.
+pub const AUDIO_SEGMENT_FRAME_KIND: &str = "audio_segment";
+/// Extra-metadata key holding a segment's start offset in milliseconds.
+pub const AUDIO_START_MS_KEY: &str = "audio_start_ms";
+/// Extra-metadata key holding a segment's end offset in milliseconds.
+pub const AUDIO_END_MS_KEY: &str = "audio_end_ms";
+
+/// WAL sequences written by one audio ingestion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioReceipt {
+    /// Sequence of the recording frame.
+    pub parent_sequence: u64,
+    /// Sequences of the segment frames, in transcript order.
+    pub segment_sequences: Vec<u64>,
+    /// Length of the recording in milliseconds.
+    pub duration_ms: u64,
+    /// Distinct speakers found by diarization, in order of first appearance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub speakers: Vec<String>,
+}
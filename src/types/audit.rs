@@ -4,6 +4,8 @@
 //! the sources used to answer questions, enabling compliance, verification, and
 //! debugging of AI-generated responses.
 
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 
 use super::ask::{AskMode, AskRetriever, AskStats};
@@ -55,6 +57,165 @@ pub struct SourceSpan {
     /// The actual text snippet used as context.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub snippet: Option<String>,
+
+    /// Source PDF page the cited range starts on (1-based).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// Bounding box of the cited range on `page`, when text positions were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rect: Option<PageRect>,
+}
+
+/// Extra-metadata key holding the source PDF page a frame's text starts on.
+pub const PDF_PAGE_KEY: &str = "pdf_page";
+
+/// Extra-metadata key locating a frame's text in its source PDF, as
+/// `start end page [x y width height]` spans separated by `;` (see [`PdfTextSpan`]).
+pub const PDF_SPANS_KEY: &str = "pdf_spans";
+
+/// A rectangle on a PDF page, in points from the page's bottom-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PageRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PageRect {
+    /// The smallest rectangle containing both.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Self {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+
+    /// Whether the two share at least half the shorter one's height, as runs on one line do.
+    #[must_use]
+    pub fn same_line(self, other: Self) -> bool {
+        let overlap = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+        overlap > 0.0 && overlap * 2.0 >= self.height.min(other.height)
+    }
+}
+
+/// Where the byte range `start..end` of a frame's text appears in its source PDF: one line
+/// of text, or everything on one page when the reader had no text positions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PdfTextSpan {
+    pub start: usize,
+    pub end: usize,
+    /// 1-based page number.
+    pub page: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rect: Option<PageRect>,
+}
+
+impl PdfTextSpan {
+    /// Encode spans as the [`PDF_SPANS_KEY`] value, with coordinates to a tenth of a point.
+    #[must_use]
+    pub fn encode_list(spans: &[Self]) -> String {
+        spans
+            .iter()
+            .map(|span| {
+                let mut fields = format!("{} {} {}", span.start, span.end, span.page);
+                if let Some(rect) = span.rect {
+                    fields.push_str(&format!(
+                        " {:.1} {:.1} {:.1} {:.1}",
+                        rect.x, rect.y, rect.width, rect.height
+                    ));
+                }
+                fields
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Decode a [`PDF_SPANS_KEY`] value, skipping malformed spans.
+    #[must_use]
+    pub fn decode_list(value: &str) -> Vec<Self> {
+        value
+            .split(';')
+            .filter_map(|span| {
+                let fields: Vec<&str> = span.split_whitespace().collect();
+                let (start, end, page) = match fields.get(..3)? {
+                    [start, end, page] => {
+                        (start.parse().ok()?, end.parse().ok()?, page.parse().ok()?)
+                    }
+                    _ => return None,
+                };
+                let rect = match fields.get(3..)? {
+                    [] => None,
+                    [x, y, width, height] => Some(PageRect {
+                        x: x.parse().ok()?,
+                        y: y.parse().ok()?,
+                        width: width.parse().ok()?,
+                        height: height.parse().ok()?,
+                    }),
+                    _ => return None,
+                };
+                Some(Self {
+                    start,
+                    end,
+                    page,
+                    rect,
+                })
+            })
+            .collect()
+    }
+}
+
+/// What a PDF viewer needs to highlight a quote from a frame (see `Memvid::render_citation`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CitationHighlight {
+    pub frame_id: FrameId,
+    /// Page the quote starts on (1-based).
+    pub page: u32,
+    /// Bounding box of the quote's lines on `page`, when text positions were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rect: Option<PageRect>,
+    /// The quote's spans, clipped to the quoted range, including any that continue on
+    /// later pages. Rects cover whole lines.
+    pub spans: Vec<PdfTextSpan>,
+}
+
+impl CitationHighlight {
+    /// Highlight the byte `range` of a frame's text from the frame's [`PDF_SPANS_KEY`]
+    /// spans; `None` when no span overlaps it.
+    #[must_use]
+    pub fn from_spans(
+        frame_id: FrameId,
+        spans: &[PdfTextSpan],
+        range: Range<usize>,
+    ) -> Option<Self> {
+        let spans: Vec<PdfTextSpan> = spans
+            .iter()
+            .filter(|span| span.start < range.end && range.start < span.end)
+            .map(|span| PdfTextSpan {
+                start: span.start.max(range.start),
+                end: span.end.min(range.end),
+                ..*span
+            })
+            .collect();
+        let page = spans.first()?.page;
+        let rect = spans
+            .iter()
+            .filter(|span| span.page == page)
+            .filter_map(|span| span.rect)
+            .reduce(PageRect::union);
+        Some(Self {
+            frame_id,
+            page,
+            rect,
+            spans,
+        })
+    }
 }
 
 /// Options for generating an audit report.
@@ -213,6 +374,9 @@ impl AuditReport {
                     end - start
                 ));
             }
+            if let Some(page) = source.page {
+                output.push_str(&format!("    Page:        {page}\n"));
+            }
             if !source.tags.is_empty() {
                 let tags_display: Vec<_> = source.tags.iter().take(5).cloned().collect();
                 let tags_str = if source.tags.len() > 5 {
@@ -342,6 +506,9 @@ impl AuditReport {
                     end - start
                 ));
             }
+            if let Some(page) = source.page {
+                output.push_str(&format!("| Page | {page} |\n"));
+            }
             if let Some(ts) = source.frame_timestamp {
                 output.push_str(&format!("| Indexed | {} |\n", format_timestamp(ts)));
             }
@@ -396,6 +563,8 @@ fn format_mode(mode: AskMode) -> &'static str {
         AskMode::Lex => "Lexical (keyword search)",
         AskMode::Sem => "Semantic (vector similarity)",
         AskMode::Hybrid => "Hybrid (lexical + semantic)",
+        AskMode::Extractive => "Extractive (hybrid retrieval, offline answer)",
+        AskMode::Generative => "Generative (hybrid retrieval, LLM answer)",
     }
 }
 
@@ -531,6 +700,8 @@ mod tests {
             frame_timestamp: Some(1700000000),
             content_dates: vec![],
             snippet: Some("This is a test snippet.".to_string()),
+            page: Some(3),
+            rect: None,
         };
 
         let json = serde_json::to_string_pretty(&source).expect("serialize");
@@ -559,12 +730,16 @@ mod tests {
                 frame_timestamp: None,
                 content_dates: vec![],
                 snippet: Some("Memvid is...".to_string()),
+                page: None,
+                rect: None,
             }],
             total_hits: 5,
             stats: AskStats {
                 retrieval_ms: 10,
                 synthesis_ms: 5,
                 latency_ms: 15,
+                prompt_tokens: 0,
+                completion_tokens: 0,
             },
             notes: vec![],
         };
@@ -575,6 +750,56 @@ mod tests {
         assert!(text.contains("Introduction"));
     }
 
+    #[test]
+    fn test_pdf_spans_round_trip_and_highlight() {
+        let line = |y: f32| PageRect {
+            x: 72.0,
+            y,
+            width: 400.0,
+            height: 12.0,
+        };
+        let spans = [
+            PdfTextSpan {
+                start: 0,
+                end: 40,
+                page: 2,
+                rect: Some(line(700.0)),
+            },
+            PdfTextSpan {
+                start: 41,
+                end: 80,
+                page: 2,
+                rect: Some(line(686.0)),
+            },
+            PdfTextSpan {
+                start: 81,
+                end: 120,
+                page: 3,
+                rect: None,
+            },
+        ];
+        let encoded = PdfTextSpan::encode_list(&spans);
+        assert_eq!(PdfTextSpan::decode_list(&encoded), spans);
+
+        let highlight = CitationHighlight::from_spans(7, &spans, 30..100).expect("overlaps");
+        assert_eq!(highlight.page, 2);
+        assert_eq!(
+            highlight.rect,
+            Some(PageRect {
+                x: 72.0,
+                y: 686.0,
+                width: 400.0,
+                height: 26.0,
+            })
+        );
+        assert_eq!(highlight.spans.len(), 3);
+        assert_eq!(
+            (highlight.spans[0].start, highlight.spans[2].end),
+            (30, 100)
+        );
+        assert!(CitationHighlight::from_spans(7, &spans, 200..210).is_none());
+    }
+
     #[test]
     fn test_format_timestamp() {
         let ts = 1700000000; // 2023-11-14T22:13:20Z
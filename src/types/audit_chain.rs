@@ -0,0 +1,130 @@
+//! Tamper-evident audit chain of mutations.
+//!
+//! Once enabled with `Memvid::enable_audit_chain`, every commit appends one [`AuditEntry`] per
+//! frame it puts, updates, or deletes, and every ticket change appends one more. Each entry's
+//! hash covers its fields and the previous entry's hash, so editing, reordering, or removing
+//! any entry but the newest breaks the chain; with a signing key set, the hash is also signed
+//! with Ed25519. The chain is persisted in the TOC (extension key [`AUDIT_CHAIN_EXTENSION`])
+//! and checked with `Memvid::verify_audit_chain`.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// TOC extension key holding the persisted [`AuditChain`].
+pub const AUDIT_CHAIN_EXTENSION: &str = "memvid.audit_chain";
+
+/// Version of the hashed entry encoding.
+const AUDIT_ENTRY_VERSION: u8 = 1;
+
+/// The mutation an [`AuditEntry`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Put,
+    Update,
+    Delete,
+    Ticket,
+}
+
+/// One link of the audit chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the chain, from 0.
+    pub seq: u64,
+    /// Footer generation the mutation was committed in.
+    pub generation: u64,
+    /// Unix seconds.
+    pub recorded_at: i64,
+    pub action: AuditAction,
+    /// Frame put, deleted, or written by an update.
+    pub frame_id: Option<FrameId>,
+    /// Frame an update superseded.
+    pub previous_frame_id: Option<FrameId>,
+    /// BLAKE3 checksum of the frame's payload, for puts and updates.
+    pub content_hash: Option<[u8; 32]>,
+    /// Ticket issuer and sequence number, for ticket changes.
+    pub detail: Option<String>,
+    /// Commit author (see `Memvid::set_commit_author`).
+    pub author: Option<String>,
+    /// Hash of the previous entry; zeros for the first.
+    pub prev_hash: [u8; 32],
+    /// BLAKE3 hash of this entry's fields and `prev_hash` (see [`AuditEntry::compute_hash`]).
+    pub hash: [u8; 32],
+    /// Ed25519 signature over `hash`, when the writer had a signing key.
+    pub signature: Option<Vec<u8>>,
+}
+
+/// Fields covered by an entry's hash, in the order they are encoded.
+#[derive(Serialize)]
+struct HashedEntry<'a> {
+    version: u8,
+    seq: u64,
+    generation: u64,
+    recorded_at: i64,
+    action: AuditAction,
+    frame_id: Option<FrameId>,
+    previous_frame_id: Option<FrameId>,
+    content_hash: Option<String>,
+    detail: Option<&'a str>,
+    author: Option<&'a str>,
+    prev_hash: String,
+}
+
+impl AuditEntry {
+    /// BLAKE3 hash of the entry's JSON encoding: `version`, `seq`, `generation`,
+    /// `recorded_at`, `action`, `frame_id`, `previous_frame_id`, `content_hash`, `detail`,
+    /// `author`, and `prev_hash` in that order, with hashes as lowercase hex.
+    #[must_use]
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let hashed = HashedEntry {
+            version: AUDIT_ENTRY_VERSION,
+            seq: self.seq,
+            generation: self.generation,
+            recorded_at: self.recorded_at,
+            action: self.action,
+            frame_id: self.frame_id,
+            previous_frame_id: self.previous_frame_id,
+            content_hash: self
+                .content_hash
+                .map(|hash| blake3::Hash::from(hash).to_hex().to_string()),
+            detail: self.detail.as_deref(),
+            author: self.author.as_deref(),
+            prev_hash: blake3::Hash::from(self.prev_hash).to_hex().to_string(),
+        };
+        // Serializing a struct of plain fields cannot fail.
+        let bytes = serde_json::to_vec(&hashed).unwrap_or_default();
+        *blake3::hash(&bytes).as_bytes()
+    }
+}
+
+/// The persisted chain: every entry since it was enabled, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditChain {
+    /// Ed25519 public key of the first signed entry; later signatures must verify with it.
+    pub signer: Option<[u8; 32]>,
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditChain {
+    /// Hash the next entry links to.
+    #[must_use]
+    pub fn head(&self) -> [u8; 32] {
+        self.entries.last().map_or([0; 32], |entry| entry.hash)
+    }
+}
+
+/// Outcome of a successful `Memvid::verify_audit_chain`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditChainReport {
+    pub entries: usize,
+    /// Entries whose signature verified.
+    pub signed: usize,
+    /// Entries written without a signing key.
+    pub unsigned: usize,
+    /// Hash of the newest entry as hex; keep it to detect later truncation.
+    pub head: Option<String>,
+    /// Base64 Ed25519 public key the signatures verified with; compare it with the key you
+    /// trust, since a chain re-signed with another key also verifies.
+    pub signer: Option<String>,
+}
@@ -0,0 +1,28 @@
+//! Progress of `Memvid::backfill_embeddings`.
+
+use serde::{Deserialize, Serialize};
+
+/// Running totals for an embedding backfill, reported after every committed batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillReport {
+    /// Active frames that had no vector when the backfill started.
+    pub candidates: usize,
+    /// Frames embedded and committed so far.
+    pub embedded: usize,
+    /// Frames skipped because they have no text to embed.
+    pub skipped_empty: usize,
+    /// Frames skipped because their recorded embedding identity names another provider,
+    /// model, or dimension.
+    pub skipped_identity: usize,
+    /// Batches committed so far.
+    pub batches: usize,
+}
+
+impl BackfillReport {
+    /// Frames not yet embedded or skipped.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.candidates
+            .saturating_sub(self.embedded + self.skipped_empty + self.skipped_identity)
+    }
+}
@@ -0,0 +1,175 @@
+//! Chunked, content-addressed storage for large payloads.
+//!
+//! When enabled, commits split payloads of at least [`BlobExtentOptions::min_blob_bytes`] into
+//! fixed-size extents keyed by their BLAKE3 hash. Each extent is written once to the payload
+//! region and shared by every frame (or repeated range within a frame) with the same bytes;
+//! the frame itself keeps a zero-length payload and an extent table. Vacuum moves extents one
+//! at a time instead of loading whole blobs. The store lives in the TOC under
+//! [`BLOB_EXTENT_EXTENSION`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::frame::Frame;
+use super::manifest::{Header, Toc};
+use crate::error::Result;
+
+/// TOC extension key holding the [`BlobExtentStore`].
+pub const BLOB_EXTENT_EXTENSION: &str = "memvid.blob_extents";
+
+/// When and how payloads are split into extents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobExtentOptions {
+    /// Size of every extent except the last one of a blob.
+    pub extent_size: u64,
+    /// Stored payloads smaller than this stay contiguous.
+    pub min_blob_bytes: u64,
+}
+
+impl Default for BlobExtentOptions {
+    fn default() -> Self {
+        Self {
+            extent_size: 4 * 1024 * 1024,
+            min_blob_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Location of one extent in the payload region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobExtent {
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// Extents written so far and the extent table of every frame stored as extents.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlobExtentStore {
+    /// `None` once chunked storage is disabled; existing extents stay readable.
+    pub options: Option<BlobExtentOptions>,
+    /// Start of the data region (WAL end) when the offsets below were last written. WAL growth
+    /// shifts all data, so offsets are corrected by the difference when read.
+    pub data_start: u64,
+    pub extents: BTreeMap<[u8; 32], BlobExtent>,
+    /// Extent hashes of each frame's stored payload, in order.
+    pub tables: BTreeMap<FrameId, Vec<[u8; 32]>>,
+}
+
+/// Space used by chunked storage, as returned by `Memvid::blob_extent_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobExtentStats {
+    /// Frames stored as extents.
+    pub frames: usize,
+    /// Distinct extents on disk.
+    pub extents: usize,
+    /// Bytes the extents occupy on disk.
+    pub stored_bytes: u64,
+    /// Bytes the frames' extent tables add up to; the difference is saved by sharing.
+    pub referenced_bytes: u64,
+}
+
+impl BlobExtentStore {
+    /// Amount the data region has moved since the offsets were written.
+    #[must_use]
+    pub fn shift(&self, header: &Header) -> u64 {
+        header.data_start.saturating_sub(self.data_start)
+    }
+
+    /// Apply the pending WAL-growth shift to every offset so new extents can be recorded
+    /// against the current data region.
+    pub fn rebase(&mut self, header: &Header) {
+        let shift = self.shift(header);
+        for extent in self.extents.values_mut() {
+            extent.offset = extent.offset.saturating_add(shift);
+        }
+        self.data_start = header.data_start;
+    }
+
+    /// Whether a stored payload of `length` bytes should be split into extents.
+    #[must_use]
+    pub fn applies_to(&self, length: u64) -> bool {
+        self.options
+            .is_some_and(|options| options.extent_size > 0 && length >= options.min_blob_bytes)
+    }
+
+    /// Current locations of the extents making up `frame_id`'s payload.
+    #[must_use]
+    pub fn frame_extents(&self, frame_id: FrameId, header: &Header) -> Option<Vec<BlobExtent>> {
+        let shift = self.shift(header);
+        self.tables
+            .get(&frame_id)?
+            .iter()
+            .try_fold(Vec::new(), |mut extents, hash| {
+                let extent = self.extents.get(hash)?;
+                extents.push(BlobExtent {
+                    offset: extent.offset.saturating_add(shift),
+                    length: extent.length,
+                });
+                Some(extents)
+            })
+    }
+
+    /// Drop the tables of frames not in `live` and every extent no remaining table uses.
+    pub fn retain_frames(&mut self, live: &BTreeSet<FrameId>) {
+        self.tables.retain(|frame_id, _| live.contains(frame_id));
+        let used: BTreeSet<[u8; 32]> = self.tables.values().flatten().copied().collect();
+        self.extents.retain(|hash, _| used.contains(hash));
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> BlobExtentStats {
+        BlobExtentStats {
+            frames: self.tables.len(),
+            extents: self.extents.len(),
+            stored_bytes: self.extents.values().map(|extent| extent.length).sum(),
+            referenced_bytes: self
+                .tables
+                .values()
+                .flatten()
+                .filter_map(|hash| self.extents.get(hash))
+                .map(|extent| extent.length)
+                .sum(),
+        }
+    }
+}
+
+/// Current `(offset, length)` of every extent referenced by `toc`.
+///
+/// Decoding errors are treated as "no extents" so a damaged store never blocks opening.
+pub(crate) fn blob_extent_ranges(toc: &Toc, header: &Header) -> Vec<(u64, u64)> {
+    toc.extension::<BlobExtentStore>(BLOB_EXTENT_EXTENSION)
+        .ok()
+        .flatten()
+        .map(|store| {
+            let shift = store.shift(header);
+            store
+                .extents
+                .values()
+                .map(|extent| (extent.offset.saturating_add(shift), extent.length))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether `frame` keeps its payload in extents: it has canonical bytes but no contiguous
+/// payload of its own.
+#[must_use]
+pub(crate) fn stored_in_extents(frame: &Frame) -> bool {
+    frame.payload_length == 0 && frame.canonical_length.is_some_and(|length| length > 0)
+}
+
+/// Current extents of `frame`'s payload, or `None` when it is stored contiguously.
+pub(crate) fn frame_blob_extents(
+    toc: &Toc,
+    header: &Header,
+    frame: &Frame,
+) -> Result<Option<Vec<BlobExtent>>> {
+    if !stored_in_extents(frame) {
+        return Ok(None);
+    }
+    Ok(toc
+        .extension::<BlobExtentStore>(BLOB_EXTENT_EXTENSION)?
+        .and_then(|store| store.frame_extents(frame.id, header)))
+}
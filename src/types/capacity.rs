@@ -0,0 +1,170 @@
+//! Capacity and storage introspection (see `Memvid::capacity_report` and
+//! `Memvid::storage_breakdown`).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::Tier;
+
+/// Utilisation, in percent of capacity, at which `Stats::capacity_warning` is raised unless
+/// `FileConfig::capacity_warning_percent` sets another threshold.
+pub const DEFAULT_CAPACITY_WARNING_PERCENT: u8 = 80;
+
+/// Where a file's capacity comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacitySource {
+    /// Capacity granted by the applied ticket.
+    Ticket,
+    /// The free tier's default capacity, or the tier's nominal one; no ticket was applied.
+    Tier,
+}
+
+/// Bytes of the file taken by each kind of content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapacityBreakdown {
+    /// Stored frame payloads, including tombstoned frames not yet vacuumed.
+    pub payload_bytes: u64,
+    pub lex_index_bytes: u64,
+    pub vec_index_bytes: u64,
+    pub clip_index_bytes: u64,
+    pub time_index_bytes: u64,
+    /// Temporal, memories, Logic-Mesh, and sketch tracks.
+    pub track_bytes: u64,
+    /// Write-ahead log region.
+    pub wal_bytes: u64,
+    /// Header, TOC, and space no longer referenced.
+    pub other_bytes: u64,
+}
+
+/// Usage of a file against its capacity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapacityReport {
+    pub capacity_bytes: u64,
+    pub source: CapacitySource,
+    pub tier: Tier,
+    /// Bytes counted against the capacity: the end of the region new payloads are appended
+    /// to. A put fails with `CapacityExceeded` once it would grow past `capacity_bytes`.
+    pub used_bytes: u64,
+    pub remaining_bytes: u64,
+    pub utilisation_percent: f64,
+    /// Size of the file on disk.
+    pub file_bytes: u64,
+    pub breakdown: CapacityBreakdown,
+    /// Utilisation, in percent, at which `warning` is raised.
+    pub warning_percent: u8,
+    /// Utilisation has reached `warning_percent`.
+    pub warning: bool,
+}
+
+/// Stored payloads of one frame kind or MIME type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayloadUsage {
+    pub frame_count: u64,
+    /// Bytes stored in the file, after compression.
+    pub stored_bytes: u64,
+    /// Bytes before compression.
+    pub logical_bytes: u64,
+}
+
+impl PayloadUsage {
+    fn add(&mut self, stored: u64, logical: u64) {
+        self.frame_count = self.frame_count.saturating_add(1);
+        self.stored_bytes = self.stored_bytes.saturating_add(stored);
+        self.logical_bytes = self.logical_bytes.saturating_add(logical);
+    }
+}
+
+/// Per-component byte accounting of a file, computed from the TOC and segment catalog.
+///
+/// Every component is counted once; `unaccounted_bytes` is whatever remains of the file
+/// (header, TOC, padding, and regions no longer referenced by the catalog).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageBreakdown {
+    pub file_bytes: u64,
+    /// Payloads of every stored frame, keyed by frame kind (`"unknown"` when unset).
+    pub payload_by_kind: BTreeMap<String, PayloadUsage>,
+    /// Payloads of every stored frame, keyed by MIME type (`"unknown"` when unset).
+    pub payload_by_mime: BTreeMap<String, PayloadUsage>,
+    /// Total of all stored payloads.
+    pub payload_bytes: u64,
+    /// Part of `payload_bytes` held by deleted or superseded frames, reclaimable by vacuum.
+    pub inactive_payload_bytes: u64,
+    /// Legacy lexical index artifact and lexical catalog segments.
+    pub lex_index_bytes: u64,
+    pub tantivy_segment_count: u64,
+    pub tantivy_segment_bytes: u64,
+    pub vec_segment_count: u64,
+    /// Vector index artifact and vector catalog segments.
+    pub vec_index_bytes: u64,
+    pub clip_index_bytes: u64,
+    /// Time index artifact and time catalog segments.
+    pub time_index_bytes: u64,
+    /// Temporal track and temporal catalog segments.
+    pub temporal_track_bytes: u64,
+    pub memories_track_bytes: u64,
+    pub logic_mesh_bytes: u64,
+    pub sketch_track_bytes: u64,
+    /// Keyed TOC extensions (configuration, stamps, and newer manifests).
+    pub extension_bytes: u64,
+    /// Size of the write-ahead log region.
+    pub wal_bytes: u64,
+    /// WAL bytes holding records not yet checkpointed.
+    pub wal_pending_bytes: u64,
+    /// WAL bytes reserved but unused.
+    pub wal_slack_bytes: u64,
+    pub unaccounted_bytes: u64,
+}
+
+impl StorageBreakdown {
+    pub(crate) fn record_payload(
+        &mut self,
+        kind: Option<&str>,
+        mime: Option<&str>,
+        stored: u64,
+        logical: u64,
+        active: bool,
+    ) {
+        self.payload_by_kind
+            .entry(kind.unwrap_or("unknown").to_string())
+            .or_default()
+            .add(stored, logical);
+        self.payload_by_mime
+            .entry(mime.unwrap_or("unknown").to_string())
+            .or_default()
+            .add(stored, logical);
+        self.payload_bytes = self.payload_bytes.saturating_add(stored);
+        if !active {
+            self.inactive_payload_bytes = self.inactive_payload_bytes.saturating_add(stored);
+        }
+    }
+
+    /// Bytes of all the index and track components.
+    #[must_use]
+    pub fn index_bytes(&self) -> u64 {
+        [
+            self.lex_index_bytes,
+            self.tantivy_segment_bytes,
+            self.vec_index_bytes,
+            self.clip_index_bytes,
+            self.time_index_bytes,
+            self.track_bytes(),
+        ]
+        .into_iter()
+        .fold(0u64, u64::saturating_add)
+    }
+
+    /// Bytes of the temporal, memories, Logic-Mesh, and sketch tracks.
+    #[must_use]
+    pub fn track_bytes(&self) -> u64 {
+        [
+            self.temporal_track_bytes,
+            self.memories_track_bytes,
+            self.logic_mesh_bytes,
+            self.sketch_track_bytes,
+        ]
+        .into_iter()
+        .fold(0u64, u64::saturating_add)
+    }
+}
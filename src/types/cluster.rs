@@ -0,0 +1,60 @@
+//! Topic clusters over stored embeddings, as returned by `Memvid::cluster`.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// How `Memvid::cluster` groups frames and what it writes back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterOptions {
+    /// Upper bound on k-means refinement passes.
+    pub max_iterations: usize,
+    /// Terms reported per cluster.
+    pub top_terms: usize,
+    /// When set, every clustered frame gets the label `{prefix}{cluster id}`, replacing labels
+    /// with the same prefix from an earlier run. Saved on the next commit.
+    pub label_prefix: Option<String>,
+}
+
+impl Default for ClusterOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations: 50,
+            top_terms: 5,
+            label_prefix: None,
+        }
+    }
+}
+
+impl ClusterOptions {
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    #[must_use]
+    pub fn top_terms(mut self, top_terms: usize) -> Self {
+        self.top_terms = top_terms;
+        self
+    }
+
+    #[must_use]
+    pub fn label_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.label_prefix = Some(prefix.into());
+        self
+    }
+}
+
+/// A group of frames whose embeddings lie close together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopicCluster {
+    /// Position of the cluster in the result; clusters are ordered largest first.
+    pub id: usize,
+    pub frames: Vec<FrameId>,
+    pub centroid: Vec<f32>,
+    /// Terms frequent in this cluster but rare in the others, most distinctive first.
+    pub top_terms: Vec<String>,
+    /// Mean L2 distance of the members to the centroid; lower is tighter.
+    pub spread: f32,
+}
@@ -0,0 +1,186 @@
+//! Named collections: a URI scope with a description and default put options.
+//!
+//! Collections give the implicit structure of URI prefixes a first-class name, e.g. one per
+//! agent writing into a shared memory. The registry is stored in the TOC under
+//! [`COLLECTION_EXTENSION`]; frames belong to a collection by URI, so removing a collection
+//! keeps its frames.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::options::PutOptions;
+
+/// TOC extension key holding the [`CollectionRegistry`].
+pub const COLLECTION_EXTENSION: &str = "memvid.collections";
+
+/// A named URI scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    /// URI prefix shared by the collection's frames, e.g. `mv2://agents/planner/`.
+    pub scope: String,
+    pub description: Option<String>,
+    /// Options applied by `Memvid::put_into` before the caller's own.
+    #[serde(with = "put_options_json")]
+    pub defaults: PutOptions,
+}
+
+impl Collection {
+    #[must_use]
+    pub fn new(name: impl Into<String>, scope: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            scope: scope.into(),
+            description: None,
+            defaults: PutOptions::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    #[must_use]
+    pub fn defaults(mut self, defaults: PutOptions) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Whether `uri` lies inside the collection's scope.
+    #[must_use]
+    pub fn contains(&self, uri: &str) -> bool {
+        uri.starts_with(&self.scope)
+    }
+
+    /// `uri` resolved against the scope: URIs already inside it are kept, anything else is
+    /// treated as a path relative to it.
+    #[must_use]
+    pub fn resolve_uri(&self, uri: &str) -> String {
+        if self.contains(uri) {
+            return uri.to_string();
+        }
+        let scope = self.scope.trim_end_matches('/');
+        format!("{scope}/{}", uri.trim_start_matches('/'))
+    }
+
+    /// The collection defaults overlaid with `options`: options a put leaves unset take the
+    /// default, tags, labels, and extra metadata are added to the defaults, and flags come
+    /// from `options`.
+    #[must_use]
+    pub fn merge_options(&self, options: PutOptions) -> PutOptions {
+        let defaults = self.defaults.clone();
+        let mut tags = defaults.tags;
+        for tag in options.tags {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        let mut labels = defaults.labels;
+        for label in options.labels {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        let mut extra_metadata = defaults.extra_metadata;
+        extra_metadata.extend(options.extra_metadata);
+        let mut typed_metadata = defaults.typed_metadata;
+        typed_metadata.extend(options.typed_metadata);
+        PutOptions {
+            timestamp: options.timestamp.or(defaults.timestamp),
+            track: options.track.or(defaults.track),
+            kind: options.kind.or(defaults.kind),
+            uri: options.uri,
+            title: options.title.or(defaults.title),
+            metadata: options.metadata.or(defaults.metadata),
+            search_text: options.search_text.or(defaults.search_text),
+            tags,
+            labels,
+            extra_metadata,
+            typed_metadata,
+            parent_id: options.parent_id.or(defaults.parent_id),
+            source_path: options.source_path.or(defaults.source_path),
+            compression: options.compression.or(defaults.compression),
+            ..options
+        }
+    }
+}
+
+/// Collections by name.
+pub type CollectionRegistry = BTreeMap<String, Collection>;
+
+/// Size and time span of the frames in one collection, as returned by
+/// `Memvid::collection_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionStats {
+    /// Active frames whose URI lies in the scope.
+    pub frame_count: u64,
+    /// Bytes their payloads occupy on disk.
+    pub payload_bytes: u64,
+    /// Bytes of their canonical (uncompressed) content.
+    pub logical_bytes: u64,
+    /// Unix seconds of the oldest and newest frame.
+    pub oldest_timestamp: Option<i64>,
+    pub newest_timestamp: Option<i64>,
+}
+
+/// `PutOptions` skips empty fields when serialized, which the TOC's bincode encoding cannot
+/// read back, so collection defaults are stored as JSON.
+mod put_options_json {
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::PutOptions;
+
+    pub fn serialize<S: Serializer>(
+        options: &PutOptions,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let json = serde_json::to_string(options).map_err(S::Error::custom)?;
+        serializer.serialize_str(&json)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PutOptions, D::Error> {
+        let json = String::deserialize(deserializer)?;
+        serde_json::from_str(&json).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_options_merge_over_defaults() {
+        let collection = Collection::new("planner", "mv2://agents/planner/").defaults(
+            PutOptions::builder()
+                .track("plans")
+                .push_tag("agent")
+                .metadata_entry("team", serde_json::json!("core"))
+                .build(),
+        );
+        assert_eq!(
+            collection.resolve_uri("/today"),
+            "mv2://agents/planner/today"
+        );
+        assert_eq!(
+            collection.resolve_uri("mv2://agents/planner/x"),
+            "mv2://agents/planner/x"
+        );
+
+        let merged = collection.merge_options(
+            PutOptions::builder()
+                .title("Today")
+                .push_tag("agent")
+                .push_tag("daily")
+                .build(),
+        );
+        assert_eq!(merged.track.as_deref(), Some("plans"));
+        assert_eq!(merged.title.as_deref(), Some("Today"));
+        assert_eq!(merged.tags, vec!["agent".to_string(), "daily".to_string()]);
+        assert!(merged.extra_metadata.contains_key("team"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Per-commit provenance: who wrote each generation, and why.
+//!
+//! Every commit appends a [`CommitProvenance`] to a bounded history persisted in the TOC
+//! (extension key [`COMMIT_HISTORY_EXTENSION`]), written in the same TOC as the commit's
+//! footer, so an entry is durable exactly when its generation is. Read it back with
+//! `Memvid::commit_history`.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// TOC extension key holding the persisted [`CommitHistory`].
+pub const COMMIT_HISTORY_EXTENSION: &str = "memvid.commit_history";
+
+/// Number of commits whose provenance is retained by default.
+pub const DEFAULT_COMMIT_HISTORY_CAPACITY: usize = 1024;
+
+/// Who produced a commit and why.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitMetadata {
+    /// Person or service account responsible for the commit.
+    pub author: Option<String>,
+    /// Process or agent that wrote the commit.
+    pub agent_id: Option<String>,
+    /// Free-form description of the change.
+    pub reason: Option<String>,
+    /// Generation the commit was built on; filled in by the commit.
+    pub parent_generation: u64,
+}
+
+/// Provenance of one committed generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitProvenance {
+    /// Footer generation written by the commit.
+    pub generation: u64,
+    /// Unix seconds.
+    pub committed_at: i64,
+    pub metadata: CommitMetadata,
+}
+
+/// Bounded history of commit provenance, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitHistory {
+    capacity: usize,
+    entries: VecDeque<CommitProvenance>,
+}
+
+impl Default for CommitHistory {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_COMMIT_HISTORY_CAPACITY)
+    }
+}
+
+impl CommitHistory {
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change how many entries are retained, dropping the oldest if it shrinks.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict();
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, entry: CommitProvenance) {
+        self.entries.push_back(entry);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CommitProvenance> {
+        self.entries.iter()
+    }
+
+    /// Provenance of `generation`, if it is still retained.
+    #[must_use]
+    pub fn get(&self, generation: u64) -> Option<&CommitProvenance> {
+        self.entries
+            .iter()
+            .find(|entry| entry.generation == generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_keeps_newest_entries() {
+        let mut history = CommitHistory::with_capacity(2);
+        for generation in 1..=3 {
+            history.push(CommitProvenance {
+                generation,
+                committed_at: 0,
+                metadata: CommitMetadata {
+                    agent_id: Some(format!("worker-{generation}")),
+                    parent_generation: generation - 1,
+                    ..CommitMetadata::default()
+                },
+            });
+        }
+        assert_eq!(history.len(), 2);
+        assert!(history.get(1).is_none());
+        let third = history.get(3).expect("retained");
+        assert_eq!(third.metadata.agent_id.as_deref(), Some("worker-3"));
+        assert_eq!(third.metadata.parent_generation, 2);
+    }
+}
@@ -0,0 +1,224 @@
+//! Commit events for change-data-capture consumers.
+//!
+//! Every successful commit that changes frames or memory cards appends a [`CommitEvent`] to a
+//! small ring persisted in the TOC (extension key [`COMMIT_LOG_EXTENSION`]). Live consumers
+//! receive events through `Memvid::subscribe`; a restarted consumer catches up with
+//! `Memvid::commit_events_since(generation)` as long as the ring still covers that generation.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::memory_card::MemoryCardId;
+
+/// TOC extension key holding the persisted [`CommitLog`].
+pub const COMMIT_LOG_EXTENSION: &str = "memvid.commit_log";
+
+/// Number of commit events retained by default.
+pub const DEFAULT_COMMIT_LOG_CAPACITY: usize = 256;
+
+/// Changes published by a single commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitEvent {
+    /// Footer generation written by the commit.
+    pub generation: u64,
+    /// Frames appended by the commit, in id order.
+    pub inserted_frames: Vec<FrameId>,
+    /// Frames marked deleted by the commit.
+    pub tombstoned_frames: Vec<FrameId>,
+    /// Memory cards added since the previous commit.
+    pub new_memory_cards: Vec<MemoryCardId>,
+}
+
+impl CommitEvent {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inserted_frames.is_empty()
+            && self.tombstoned_frames.is_empty()
+            && self.new_memory_cards.is_empty()
+    }
+}
+
+/// Persisted form of a commit event. Inserted frame and card ids are stored as half-open
+/// ranges because commits append contiguously; a bulk import stays a single range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommitRecord {
+    generation: u64,
+    inserted_frames: Vec<(FrameId, FrameId)>,
+    tombstoned_frames: Vec<FrameId>,
+    new_memory_cards: Vec<(MemoryCardId, MemoryCardId)>,
+}
+
+fn compress_ids(ids: &[u64]) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+    for &id in ids {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == id => *end = id + 1,
+            _ => ranges.push((id, id + 1)),
+        }
+    }
+    ranges
+}
+
+fn expand_ids(ranges: &[(u64, u64)]) -> Vec<u64> {
+    ranges.iter().flat_map(|&(start, end)| start..end).collect()
+}
+
+impl From<&CommitEvent> for CommitRecord {
+    fn from(event: &CommitEvent) -> Self {
+        Self {
+            generation: event.generation,
+            inserted_frames: compress_ids(&event.inserted_frames),
+            tombstoned_frames: event.tombstoned_frames.clone(),
+            new_memory_cards: compress_ids(&event.new_memory_cards),
+        }
+    }
+}
+
+impl From<&CommitRecord> for CommitEvent {
+    fn from(record: &CommitRecord) -> Self {
+        Self {
+            generation: record.generation,
+            inserted_frames: expand_ids(&record.inserted_frames),
+            tombstoned_frames: record.tombstoned_frames.clone(),
+            new_memory_cards: expand_ids(&record.new_memory_cards),
+        }
+    }
+}
+
+/// Bounded ring of recent commit events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitLog {
+    capacity: usize,
+    records: VecDeque<CommitRecord>,
+    /// Generation of the newest event evicted from the ring (0 when nothing was evicted).
+    evicted_through: u64,
+    /// Highest memory card id already reported, used to find new cards on the next commit.
+    last_card_id: Option<MemoryCardId>,
+}
+
+impl Default for CommitLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_COMMIT_LOG_CAPACITY)
+    }
+}
+
+impl CommitLog {
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            records: VecDeque::new(),
+            evicted_through: 0,
+            last_card_id: None,
+        }
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the ring size, evicting the oldest events if it shrinks.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict();
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    #[must_use]
+    pub fn last_card_id(&self) -> Option<MemoryCardId> {
+        self.last_card_id
+    }
+
+    /// Oldest generation a consumer may resume from without missing events.
+    #[must_use]
+    pub fn resumable_from(&self) -> u64 {
+        self.evicted_through
+    }
+
+    pub fn push(&mut self, event: &CommitEvent) {
+        if let Some(&max) = event.new_memory_cards.iter().max() {
+            self.last_card_id = Some(self.last_card_id.map_or(max, |last| last.max(max)));
+        }
+        self.records.push_back(CommitRecord::from(event));
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.records.len() > self.capacity {
+            if let Some(evicted) = self.records.pop_front() {
+                self.evicted_through = evicted.generation;
+            }
+        }
+    }
+
+    /// Events with a generation strictly greater than `generation`, oldest first.
+    ///
+    /// Returns `None` when events after `generation` have already been evicted.
+    #[must_use]
+    pub fn since(&self, generation: u64) -> Option<Vec<CommitEvent>> {
+        if generation < self.evicted_through {
+            return None;
+        }
+        Some(
+            self.records
+                .iter()
+                .filter(|record| record.generation > generation)
+                .map(CommitEvent::from)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(generation: u64, inserted: Vec<FrameId>) -> CommitEvent {
+        CommitEvent {
+            generation,
+            inserted_frames: inserted,
+            tombstoned_frames: Vec::new(),
+            new_memory_cards: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn ring_evicts_and_reports_gaps() {
+        let mut log = CommitLog::with_capacity(2);
+        log.push(&event(1, vec![0, 1, 2]));
+        log.push(&event(2, vec![3, 5]));
+        assert_eq!(log.since(0).expect("covered").len(), 2);
+        assert_eq!(
+            log.since(1).expect("covered")[0].inserted_frames,
+            vec![3, 5]
+        );
+
+        log.push(&event(3, vec![6]));
+        assert_eq!(log.len(), 2);
+        assert!(log.since(0).is_none(), "generation 1 was evicted");
+        let events = log.since(1).expect("covered");
+        assert_eq!(
+            events.iter().map(|e| e.generation).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn ids_are_stored_as_ranges() {
+        let record = CommitRecord::from(&event(1, vec![4, 5, 6, 9]));
+        assert_eq!(record.inserted_frames, vec![(4, 7), (9, 10)]);
+        assert_eq!(CommitEvent::from(&record).inserted_frames, vec![4, 5, 6, 9]);
+    }
+}
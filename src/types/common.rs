@@ -15,6 +15,7 @@ pub type SegmentId = u64;
 pub enum CanonicalEncoding {
     Plain,
     Zstd,
+    Lz4,
 }
 
 impl CanonicalEncoding {
@@ -23,6 +24,7 @@ impl CanonicalEncoding {
         match value {
             0 => CanonicalEncoding::Plain,
             1 => CanonicalEncoding::Zstd,
+            2 => CanonicalEncoding::Lz4,
             _ => CanonicalEncoding::Plain,
         }
     }
@@ -32,6 +34,7 @@ impl CanonicalEncoding {
         match self {
             CanonicalEncoding::Plain => 0,
             CanonicalEncoding::Zstd => 1,
+            CanonicalEncoding::Lz4 => 2,
         }
     }
 }
@@ -155,6 +158,29 @@ impl EnrichmentState {
     }
 }
 
+/// Background enrichment stages, in the order they run for a frame.
+///
+/// Completed stages are recorded per frame in the memories track (`EnrichmentRecord::stages`),
+/// so a worker restarted after a crash skips them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnrichmentStage {
+    /// Full text re-extracted for skim frames and the lexical index updated.
+    Extracted,
+    /// Embeddings generated and added to the vector index.
+    Embedded,
+    /// Named entities extracted into the Logic-Mesh. The worker does not load an NER model;
+    /// hosts that run one record this stage with `Memvid::record_enrichment_stage`.
+    Ner,
+    /// Triplets re-extracted from the full text of skim frames into memory cards.
+    Triplets,
+}
+
+impl EnrichmentStage {
+    /// Every stage, in pipeline order.
+    pub const ALL: [Self; 4] = [Self::Extracted, Self::Embedded, Self::Ner, Self::Triplets];
+}
+
 /// Task in the enrichment queue, representing pending background work.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnrichmentTask {
@@ -0,0 +1,131 @@
+//! Compression codecs for payloads and index segments.
+//!
+//! Payloads record the codec they were written with in [`CanonicalEncoding`] and segments in
+//! [`SegmentCompression`]; the level only matters when writing, so it is not stored. Per-file
+//! defaults live in the TOC under [`COMPRESSION_EXTENSION`] and can be overridden per put.
+
+use std::io::Cursor;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::CanonicalEncoding;
+use super::manifest::SegmentCompression;
+
+/// TOC extension key holding the file's [`CompressionDefaults`].
+pub const COMPRESSION_EXTENSION: &str = "memvid.compression";
+
+/// Zstd level used when nothing else is configured.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Codec, and level where it has one, used to compress new bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressionCodec {
+    /// Store bytes as-is, e.g. for already-compressed media.
+    None,
+    /// Fast compression for hot data that is read often.
+    Lz4,
+    /// Dense compression; higher levels trade write speed for size.
+    Zstd { level: i32 },
+}
+
+impl Default for CompressionCodec {
+    fn default() -> Self {
+        Self::Zstd {
+            level: DEFAULT_ZSTD_LEVEL,
+        }
+    }
+}
+
+impl CompressionCodec {
+    /// Codec matching a `PutManyOpts::compression_level`, where 0 disables compression.
+    #[must_use]
+    pub const fn from_zstd_level(level: i32) -> Self {
+        if level == 0 {
+            Self::None
+        } else {
+            Self::Zstd { level }
+        }
+    }
+
+    #[must_use]
+    pub const fn canonical_encoding(self) -> CanonicalEncoding {
+        match self {
+            Self::None => CanonicalEncoding::Plain,
+            Self::Lz4 => CanonicalEncoding::Lz4,
+            Self::Zstd { .. } => CanonicalEncoding::Zstd,
+        }
+    }
+
+    #[must_use]
+    pub const fn segment_compression(self) -> SegmentCompression {
+        match self {
+            Self::None => SegmentCompression::None,
+            Self::Lz4 => SegmentCompression::Lz4,
+            Self::Zstd { .. } => SegmentCompression::Zstd,
+        }
+    }
+
+    /// Compress `bytes` with this codec.
+    pub fn compress(self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(bytes.to_vec()),
+            Self::Lz4 => Ok(lz4_flex::compress_prepend_size(bytes)),
+            Self::Zstd { level } => zstd::encode_all(Cursor::new(bytes), level),
+        }
+    }
+}
+
+/// Codecs used when a put or segment write does not choose one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressionDefaults {
+    /// Codec for UTF-8 payloads; binary payloads are always stored as-is.
+    pub payloads: CompressionCodec,
+    /// Codec for index segments.
+    pub segments: CompressionCodec,
+}
+
+impl Default for CompressionDefaults {
+    fn default() -> Self {
+        Self {
+            payloads: CompressionCodec::default(),
+            segments: CompressionCodec::None,
+        }
+    }
+}
+
+impl SegmentCompression {
+    /// Undo the compression of a segment's stored bytes.
+    pub fn decompress(&self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(bytes.to_vec()),
+            Self::Lz4 => lz4_flex::decompress_size_prepended(bytes)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err)),
+            Self::Zstd => zstd::decode_all(Cursor::new(bytes)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codecs_roundtrip_through_segment_compression() {
+        let bytes = "segment bytes ".repeat(64).into_bytes();
+        for codec in [
+            CompressionCodec::None,
+            CompressionCodec::Lz4,
+            CompressionCodec::Zstd { level: 19 },
+        ] {
+            let compressed = codec.compress(&bytes).expect("compress");
+            if codec != CompressionCodec::None {
+                assert!(compressed.len() < bytes.len());
+            }
+            let restored = codec
+                .segment_compression()
+                .decompress(&compressed)
+                .expect("decompress");
+            assert_eq!(restored, bytes);
+        }
+    }
+}
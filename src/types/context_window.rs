@@ -0,0 +1,41 @@
+//! Token-budgeted context assembly for prompts (see `Memvid::build_context`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::AskContextFragment;
+
+/// Counts tokens the way the model that will read the context does.
+///
+/// Closures `Fn(&str) -> usize` implement it, so an [`LlmBackend`](crate::LlmBackend) can be
+/// used as `|text: &str| llm.count_tokens(text)`.
+pub trait TokenCounter {
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+impl<F: Fn(&str) -> usize> TokenCounter for F {
+    fn count_tokens(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Estimates roughly four bytes per token, the same default `LlmBackend` uses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApproxTokenCounter;
+
+impl TokenCounter for ApproxTokenCounter {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.len().div_ceil(4)
+    }
+}
+
+/// Packed context: passages prefixed with `[n]` citation markers, and the fragment each
+/// marker refers to (`fragments[n - 1]`, whose `rank` is `n`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContextWindow {
+    pub text: String,
+    pub fragments: Vec<AskContextFragment>,
+    /// Tokens `text` uses as counted by the caller's counter.
+    pub tokens: usize,
+    /// Retrieved passages left out because they did not fit.
+    pub omitted: usize,
+}
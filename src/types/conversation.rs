@@ -0,0 +1,183 @@
+//! Chat transcripts stored as role-aware frames.
+//!
+//! `Memvid::put_conversation` writes one session frame holding the whole transcript and one
+//! child frame per message. Every frame in a session carries [`SESSION_ID_KEY`] in its extra
+//! metadata, and message frames also carry their role, author, and position, so a session can be
+//! read back in order with `Memvid::session` or `Memvid::timeline_for_session`, and
+//! `Memvid::sessions` lists them by recency or topic. Frames written one at a time join the
+//! session named by their track when they carry a [`CHAT_ROLE_KEY`].
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// Extra-metadata key holding the session identifier on session and message frames.
+pub const SESSION_ID_KEY: &str = "session_id";
+/// Extra-metadata key holding a message's [`ChatRole`].
+pub const CHAT_ROLE_KEY: &str = "chat_role";
+/// Extra-metadata key holding a message's author.
+pub const CHAT_AUTHOR_KEY: &str = "chat_author";
+/// Extra-metadata key holding a message's zero-based position in its session.
+pub const CHAT_INDEX_KEY: &str = "chat_index";
+
+/// `kind` of the frame holding a full transcript.
+pub const SESSION_FRAME_KIND: &str = "chat_session";
+/// `kind` of a single-message frame.
+pub const MESSAGE_FRAME_KIND: &str = "chat_message";
+
+/// Speaker of a chat message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+    Tool,
+}
+
+impl ChatRole {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::User => "user",
+            Self::Assistant => "assistant",
+            Self::Tool => "tool",
+        }
+    }
+
+    /// Parse the value stored under [`CHAT_ROLE_KEY`].
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "system" => Some(Self::System),
+            "user" => Some(Self::User),
+            "assistant" => Some(Self::Assistant),
+            "tool" => Some(Self::Tool),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ChatRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One message of a conversation passed to `Memvid::put_conversation`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    /// Display name of the speaker, e.g. a user handle or model name.
+    #[serde(default)]
+    pub author: Option<String>,
+    pub content: String,
+    /// Unix seconds; defaults to the session timestamp.
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+}
+
+impl ChatMessage {
+    #[must_use]
+    pub fn new(role: ChatRole, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            author: None,
+            content: content.into(),
+            timestamp: None,
+        }
+    }
+
+    #[must_use]
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    #[must_use]
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+}
+
+/// WAL sequences assigned by `Memvid::put_conversation`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationReceipt {
+    pub session_id: String,
+    pub session_sequence: u64,
+    /// One sequence per message, in input order.
+    pub message_sequences: Vec<u64>,
+}
+
+/// One message of a session, as returned by `Memvid::session`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatSessionMessage {
+    pub frame_id: FrameId,
+    /// Parsed from [`CHAT_ROLE_KEY`]; `None` for frames written without one.
+    pub role: Option<ChatRole>,
+    pub author: Option<String>,
+    pub timestamp: i64,
+    pub text: String,
+}
+
+/// A whole conversation read back by `Memvid::session`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatSession {
+    pub session_id: String,
+    /// Title of the session frame, when the session has one.
+    pub title: Option<String>,
+    /// Frame holding the rendered transcript, written by `Memvid::put_conversation`.
+    pub session_frame: Option<FrameId>,
+    /// Messages in conversation order.
+    pub messages: Vec<ChatSessionMessage>,
+}
+
+/// Which sessions `Memvid::sessions` lists, and in what order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatSessionQuery {
+    /// Only sessions with messages matching this search, best match first; without it,
+    /// every session is listed, most recently active first.
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Most sessions to return; `None` returns them all.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl ChatSessionQuery {
+    /// The `limit` most recently active sessions.
+    #[must_use]
+    pub fn recent(limit: usize) -> Self {
+        Self {
+            topic: None,
+            limit: Some(limit),
+        }
+    }
+
+    /// Sessions about `topic`, best match first.
+    #[must_use]
+    pub fn topic(topic: impl Into<String>) -> Self {
+        Self {
+            topic: Some(topic.into()),
+            limit: None,
+        }
+    }
+}
+
+/// One session listed by `Memvid::sessions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionSummary {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub message_count: usize,
+    /// Unix seconds of the first and last message.
+    pub first_timestamp: i64,
+    pub last_timestamp: i64,
+    /// Score of the session's best matching message, for topic queries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
@@ -0,0 +1,55 @@
+//! Changes between two committed generations.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::logic_mesh::MeshEdge;
+use super::memory_card::{MemoryCardId, VersionRelation};
+
+/// A frame replaced by a newer version (see `Memvid::update_frame`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameSupersession {
+    pub previous: FrameId,
+    pub replacement: FrameId,
+}
+
+/// A memory card that overrides or retracts earlier cards for the same entity and slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardContradiction {
+    pub card: MemoryCardId,
+    pub relation: VersionRelation,
+    /// Earlier cards for the same entity/slot that this card contradicts.
+    pub contradicts: Vec<MemoryCardId>,
+}
+
+/// What changed between two commits, as reported by `Memvid::diff`.
+///
+/// A frame added and deleted inside the range appears in both `frames_added` and
+/// `frames_tombstoned`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryDiff {
+    pub from_generation: u64,
+    pub to_generation: u64,
+    pub frames_added: Vec<FrameId>,
+    pub frames_tombstoned: Vec<FrameId>,
+    pub frames_superseded: Vec<FrameSupersession>,
+    pub cards_created: Vec<MemoryCardId>,
+    pub cards_contradicted: Vec<CardContradiction>,
+    /// Edges extracted from frames added in the range.
+    pub mesh_edges_added: Vec<MeshEdge>,
+    /// Edges whose source frame was tombstoned in the range.
+    pub mesh_edges_orphaned: Vec<MeshEdge>,
+}
+
+impl MemoryDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames_added.is_empty()
+            && self.frames_tombstoned.is_empty()
+            && self.frames_superseded.is_empty()
+            && self.cards_created.is_empty()
+            && self.cards_contradicted.is_empty()
+            && self.mesh_edges_added.is_empty()
+            && self.mesh_edges_orphaned.is_empty()
+    }
+}
@@ -0,0 +1,46 @@
+//! Duplicate and near-duplicate clusters reported by `Memvid::find_duplicates`.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// How the frames in a [`DuplicateCluster`] were matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Every frame has the same payload checksum or source file hash.
+    Exact,
+    /// At least one frame was only matched by `SimHash` distance.
+    Near,
+}
+
+/// A group of active frames holding the same or nearly the same content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateCluster {
+    pub kind: DuplicateKind,
+    /// Frame kept when duplicates are superseded: the oldest member, lowest id on ties.
+    pub canonical: FrameId,
+    /// All members including `canonical`, in ascending id order.
+    pub frame_ids: Vec<FrameId>,
+    /// Largest `SimHash` distance between `canonical` and another sketched member.
+    pub max_distance: u32,
+    /// URI shared by every member, if they all have the same one.
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+impl DuplicateCluster {
+    /// Members other than the canonical frame.
+    pub fn duplicates(&self) -> impl Iterator<Item = FrameId> + '_ {
+        self.frame_ids
+            .iter()
+            .copied()
+            .filter(move |frame_id| *frame_id != self.canonical)
+    }
+
+    /// Number of redundant frames in the cluster.
+    #[must_use]
+    pub fn redundant(&self) -> usize {
+        self.frame_ids.len().saturating_sub(1)
+    }
+}
@@ -6,6 +6,42 @@
 
 use crate::error::Result;
 
+/// Counters kept by API-backed embedders, surfaced through
+/// `EnrichmentWorkerStats::embedder`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbedderRequestStats {
+    /// HTTP requests sent, retries included.
+    pub requests: u64,
+    /// Texts embedded successfully.
+    pub texts: u64,
+    /// Requests repeated after a rate limit, server error, or network failure.
+    pub retries: u64,
+    /// Responses with status 429.
+    pub rate_limited: u64,
+    /// Responses with a 5xx status.
+    pub server_errors: u64,
+    /// Calls that failed once their retries ran out.
+    pub failures: u64,
+    /// Calls refused without a request while the circuit breaker was open.
+    pub rejected: u64,
+    /// Times the circuit breaker opened.
+    pub circuit_opened: u64,
+    /// Whether the circuit breaker is open now.
+    pub circuit_open: bool,
+    /// Wall time spent waiting on responses, summed over requests.
+    pub total_latency_ms: u64,
+    /// Slowest single request.
+    pub max_latency_ms: u64,
+}
+
+impl EmbedderRequestStats {
+    /// Mean latency per request, if any request was sent.
+    #[must_use]
+    pub fn mean_latency_ms(&self) -> Option<u64> {
+        self.total_latency_ms.checked_div(self.requests)
+    }
+}
+
 /// Configuration for an embedding provider.
 #[derive(Debug, Clone)]
 pub struct EmbeddingConfig {
@@ -162,11 +198,29 @@ pub trait EmbeddingProvider: Send + Sync {
         Ok(embeddings)
     }
 
+    /// Generate an embedding for a search query.
+    ///
+    /// Defaults to `embed_text`; asymmetric models (e.g. E5) override this to
+    /// apply their query prefix.
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        self.embed_text(text)
+    }
+
+    /// Whether embeddings are L2-normalized, if the provider knows.
+    fn normalized(&self) -> Option<bool> {
+        None
+    }
+
     /// Check if the provider is ready to generate embeddings.
     fn is_ready(&self) -> bool {
         true
     }
 
+    /// Request counters of providers backed by a remote API; `None` for local models.
+    fn request_stats(&self) -> Option<EmbedderRequestStats> {
+        None
+    }
+
     /// Initialize the provider (e.g., load models, verify API key).
     fn init(&mut self) -> Result<()> {
         Ok(())
@@ -180,12 +234,56 @@ pub enum EmbeddingProviderKind {
     Local(String),
     /// `OpenAI` API
     OpenAI { model: String, api_key_env: String },
+    /// Cohere Embed API
+    Cohere { model: String, api_key_env: String },
+    /// Voyage AI API
+    Voyage { model: String, api_key_env: String },
+    /// Any OpenAI-compatible `/embeddings` endpoint, such as a local Ollama server.
+    /// `provider` is the name recorded in embedding identities.
+    OpenAICompatible {
+        provider: String,
+        base_url: String,
+        model: String,
+        dimension: usize,
+        api_key_env: Option<String>,
+    },
     /// Anthropic API (future)
     Anthropic { model: String, api_key_env: String },
     /// Custom provider
     Custom(String),
 }
 
+impl EmbeddingProviderKind {
+    /// Provider name as reported by `EmbeddingProvider::kind` and recorded in embedding
+    /// identities.
+    #[must_use]
+    pub fn provider_name(&self) -> &str {
+        match self {
+            Self::Local(_) => "local",
+            Self::OpenAI { .. } => "openai",
+            Self::Cohere { .. } => "cohere",
+            Self::Voyage { .. } => "voyage",
+            Self::OpenAICompatible { provider, .. } => provider,
+            Self::Anthropic { .. } => "anthropic",
+            Self::Custom(name) => name,
+        }
+    }
+
+    /// Model identifier, if the kind names one.
+    #[must_use]
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            Self::Local(model)
+            | Self::OpenAI { model, .. }
+            | Self::Cohere { model, .. }
+            | Self::Voyage { model, .. }
+            | Self::OpenAICompatible { model, .. }
+            | Self::Anthropic { model, .. } => Some(model),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
 impl Default for EmbeddingProviderKind {
     fn default() -> Self {
         Self::Local("nomic-embed-text-v1.5".to_string())
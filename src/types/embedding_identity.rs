@@ -1,5 +1,9 @@
 use std::collections::BTreeMap;
 
+use serde::{Deserialize, Serialize};
+
+use super::embedding::EmbeddingProvider;
+
 /// Frame-level embedding metadata keys (stored in `Frame.extra_metadata`).
 ///
 /// These are intentionally persisted per-frame (instead of in the TOC schema) to avoid
@@ -14,7 +18,7 @@ pub const MEMVID_EMBEDDING_NORMALIZED_KEY: &str = "memvid.embedding.normalized";
 ///
 /// Dimensions alone are not sufficient to guarantee compatibility (multiple models can share a
 /// dimension), so production-safe auto-detection should prefer `provider` + `model` when present.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct EmbeddingIdentity {
     pub provider: Option<Box<str>>,
     pub model: Option<Box<str>>,
@@ -23,6 +27,63 @@ pub struct EmbeddingIdentity {
 }
 
 impl EmbeddingIdentity {
+    /// Identity of the vectors produced by `provider`.
+    #[must_use]
+    pub fn of_provider(provider: &dyn EmbeddingProvider) -> Self {
+        Self {
+            provider: Some(provider.kind().to_ascii_lowercase().into_boxed_str()),
+            model: Some(provider.model().into()),
+            dimension: u32::try_from(provider.dimension()).ok(),
+            normalized: provider.normalized(),
+        }
+    }
+
+    /// Whether the two identities can describe the same vector space: every field set on both
+    /// sides must agree (provider names compare case-insensitively).
+    #[must_use]
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        fn agree<T: PartialEq + ?Sized>(a: Option<&T>, b: Option<&T>) -> bool {
+            a.zip(b).is_none_or(|(a, b)| a == b)
+        }
+        let providers = self
+            .provider
+            .as_deref()
+            .zip(other.provider.as_deref())
+            .is_none_or(|(a, b)| a.eq_ignore_ascii_case(b));
+        providers
+            && agree(self.model.as_deref(), other.model.as_deref())
+            && agree(self.dimension.as_ref(), other.dimension.as_ref())
+            && agree(self.normalized.as_ref(), other.normalized.as_ref())
+    }
+
+    /// Record this identity in a frame's `extra_metadata`, replacing any previous one.
+    pub fn write_extra_metadata(&self, extra: &mut BTreeMap<String, String>) {
+        let fields = [
+            (
+                MEMVID_EMBEDDING_PROVIDER_KEY,
+                self.provider.as_deref().map(str::to_string),
+            ),
+            (
+                MEMVID_EMBEDDING_MODEL_KEY,
+                self.model.as_deref().map(str::to_string),
+            ),
+            (
+                MEMVID_EMBEDDING_DIMENSION_KEY,
+                self.dimension.map(|d| d.to_string()),
+            ),
+            (
+                MEMVID_EMBEDDING_NORMALIZED_KEY,
+                self.normalized.map(|n| n.to_string()),
+            ),
+        ];
+        for (key, value) in fields {
+            match value {
+                Some(value) => extra.insert(key.to_string(), value),
+                None => extra.remove(key),
+            };
+        }
+    }
+
     /// Parse an embedding identity from a frame's `extra_metadata`.
     ///
     /// Returns `None` if neither provider nor model is present.
@@ -68,6 +129,21 @@ impl EmbeddingIdentity {
     }
 }
 
+impl std::fmt::Display for EmbeddingIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.provider.as_deref().unwrap_or("unknown"),
+            self.model.as_deref().unwrap_or("unknown")
+        )?;
+        if let Some(dimension) = self.dimension {
+            write!(f, " ({dimension}d)")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EmbeddingIdentityCount {
     pub identity: EmbeddingIdentity,
@@ -0,0 +1,89 @@
+//! State of an in-progress embedding migration.
+//!
+//! Re-embedded vectors are staged in segments appended to the payload region, next to the live
+//! vec index, so searches keep using the old vectors until the migration finishes. The state is
+//! stored in the TOC under [`EMBEDDING_MIGRATION_EXTENSION`]; frames still waiting to be
+//! re-embedded sit in the enrichment queue.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::embedding_identity::EmbeddingIdentity;
+use super::manifest::{Header, Toc};
+
+/// TOC extension key holding the [`EmbeddingMigrationState`].
+pub const EMBEDDING_MIGRATION_EXTENSION: &str = "memvid.embedding_migration";
+
+/// One batch of re-embedded vectors, encoded as a vec index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StagedVecSegment {
+    pub offset: u64,
+    pub length: u64,
+    pub vector_count: u64,
+    pub hash: [u8; 32],
+}
+
+/// Persisted progress of `Memvid::migrate_embeddings`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingMigrationState {
+    pub source: EmbeddingIdentity,
+    pub target: EmbeddingIdentity,
+    /// Frames whose vectors are being replaced.
+    pub frames: Vec<FrameId>,
+    /// Start of the data region (WAL end) when the segments were written. WAL growth shifts all
+    /// data, so segment offsets are corrected by the difference when read.
+    pub data_start: u64,
+    pub segments: Vec<StagedVecSegment>,
+    /// Unix seconds when the migration started.
+    pub started_at: i64,
+}
+
+impl EmbeddingMigrationState {
+    /// Amount the data region has moved since the segments were written.
+    #[must_use]
+    pub fn shift(&self, header: &Header) -> u64 {
+        header.data_start.saturating_sub(self.data_start)
+    }
+
+    /// Vectors staged so far.
+    #[must_use]
+    pub fn staged_vectors(&self) -> u64 {
+        self.segments
+            .iter()
+            .map(|segment| segment.vector_count)
+            .sum()
+    }
+}
+
+/// Report returned by `Memvid::migrate_embeddings`, also passed to progress callbacks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingMigrationReport {
+    /// Frames whose vectors are being replaced.
+    pub frames_total: usize,
+    /// Frames re-embedded by earlier, interrupted runs.
+    pub resumed: usize,
+    /// Frames re-embedded so far, including `resumed`.
+    pub migrated: usize,
+    /// Batches committed by this run.
+    pub batches: usize,
+    /// Whether the new vectors replaced the old index.
+    pub completed: bool,
+}
+
+/// Current `(offset, length)` of every staged migration segment referenced by `toc`.
+///
+/// Decoding errors are treated as "no migration" so a damaged state never blocks opening.
+pub(crate) fn staged_segment_ranges(toc: &Toc, header: &Header) -> Vec<(u64, u64)> {
+    toc.extension::<EmbeddingMigrationState>(EMBEDDING_MIGRATION_EXTENSION)
+        .ok()
+        .flatten()
+        .map(|state| {
+            let shift = state.shift(header);
+            state
+                .segments
+                .iter()
+                .map(|segment| (segment.offset.saturating_add(shift), segment.length))
+                .collect()
+        })
+        .unwrap_or_default()
+}
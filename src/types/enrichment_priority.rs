@@ -0,0 +1,49 @@
+//! Scheduling priorities for the enrichment queue.
+//!
+//! Priorities set explicitly through `Memvid::set_enrichment_priority` are persisted in the TOC
+//! (extension key [`ENRICHMENT_PRIORITY_EXTENSION`]) so a restarted worker keeps honouring them;
+//! entries are dropped as their tasks complete.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// TOC extension key holding the persisted [`EnrichmentPriorities`].
+pub const ENRICHMENT_PRIORITY_EXTENSION: &str = "memvid.enrichment_priority";
+
+/// Scheduling priority of an enrichment task; higher priorities run first and tasks of equal
+/// priority run in queue order.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EnrichmentPriority {
+    /// Bulk backfill; runs once nothing else is queued.
+    Background,
+    #[default]
+    Normal,
+    /// Frames users have already seen in search results or citations.
+    High,
+    /// Frames a user is looking at right now.
+    Interactive,
+}
+
+/// Explicit per-frame priorities overriding kind policies and access heat.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrichmentPriorities {
+    pub frames: BTreeMap<FrameId, EnrichmentPriority>,
+}
+
+impl EnrichmentPriorities {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    #[must_use]
+    pub fn get(&self, frame_id: FrameId) -> Option<EnrichmentPriority> {
+        self.frames.get(&frame_id).copied()
+    }
+}
@@ -0,0 +1,279 @@
+//! Entity resolution for the Logic-Mesh.
+//!
+//! NER emits one node per surface form, so "Bob Smith", "Robert Smith" and "B. Smith" end up
+//! as separate entities. `Memvid::resolve_entities` finds nodes of the same kind that name the
+//! same entity and merges them into one survivor, rewriting edges onto it. Each merge is
+//! recorded in an [`EntityMergeLog`] (extension key [`ENTITY_MERGE_LOG_EXTENSION`]) with the
+//! nodes and edges as they were, so `Memvid::undo_entity_merge` can split it again.
+
+use serde::{Deserialize, Serialize};
+
+use super::logic_mesh::{MeshEdge, MeshNode};
+
+/// TOC extension key holding the persisted [`EntityMergeLog`].
+pub const ENTITY_MERGE_LOG_EXTENSION: &str = "memvid.entity_merges";
+
+/// Common given-name variants, canonical form first.
+const NICKNAMES: &[&[&str]] = &[
+    &["alexander", "alex", "sandy"],
+    &["anthony", "tony"],
+    &["charles", "charlie", "chuck"],
+    &["christopher", "chris"],
+    &["daniel", "dan", "danny"],
+    &["edward", "ed", "ted", "eddie"],
+    &["elizabeth", "liz", "beth", "betty", "lizzie"],
+    &["james", "jim", "jimmy"],
+    &["jennifer", "jen", "jenny"],
+    &["john", "jack", "johnny"],
+    &["joseph", "joe", "joey"],
+    &["katherine", "kate", "kathy", "katie"],
+    &["margaret", "maggie", "meg", "peggy"],
+    &["michael", "mike", "mikey"],
+    &["nicholas", "nick"],
+    &["richard", "rick", "dick", "rich"],
+    &["robert", "bob", "rob", "bobby", "robbie"],
+    &["samuel", "sam"],
+    &["thomas", "tom", "tommy"],
+    &["william", "bill", "will", "billy", "liam"],
+];
+
+/// How `Memvid::resolve_entities` decides that two nodes are the same entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityResolutionStrategy {
+    /// Groups of names known to refer to one entity, e.g. `["Robert Smith", "Bob Smith"]`.
+    /// Alias matches are trusted without a context check.
+    pub aliases: Vec<Vec<String>>,
+    /// Minimum normalized edit-distance similarity (0.0–1.0) for two names to match.
+    pub min_name_similarity: f32,
+    /// Match person names whose given names are initials or nicknames of each other and whose
+    /// surnames agree ("B. Smith", "Bob Smith", "Robert Smith").
+    pub match_name_variants: bool,
+    /// Minimum cosine similarity between the mean embeddings of the frames each node appears
+    /// in. Pairs where either side has no embedded frames are judged on names alone.
+    pub min_context_similarity: Option<f32>,
+    /// Report the merges without applying them.
+    pub dry_run: bool,
+}
+
+impl Default for EntityResolutionStrategy {
+    fn default() -> Self {
+        Self {
+            aliases: Vec::new(),
+            min_name_similarity: 0.9,
+            match_name_variants: true,
+            min_context_similarity: Some(0.5),
+            dry_run: false,
+        }
+    }
+}
+
+/// Why a node was merged into a survivor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeSignal {
+    /// Listed together in `EntityResolutionStrategy::aliases`.
+    Alias,
+    /// Same surname with a matching initial or nickname.
+    NameVariant,
+    /// Names within the edit-distance threshold; carries the similarity.
+    EditDistance(f32),
+}
+
+/// One merged node and the evidence for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbsorbedEntity {
+    /// The node as it was before the merge.
+    pub node: MeshNode,
+    pub signal: MergeSignal,
+    /// Context similarity to the survivor, when both had embedded frames.
+    pub context_similarity: Option<f32>,
+}
+
+/// Provenance of one merge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityMerge {
+    pub id: u64,
+    /// Unix seconds.
+    pub merged_at: i64,
+    /// The node everything merged into, as it was before the merge.
+    pub survivor: MeshNode,
+    pub absorbed: Vec<AbsorbedEntity>,
+    /// Edges touching an absorbed node, as they were before being rewritten.
+    pub original_edges: Vec<MeshEdge>,
+    /// Rewritten edges the merge added; rewrites that duplicated an existing edge are not
+    /// listed, so undo leaves that edge in place.
+    pub added_edges: Vec<MeshEdge>,
+    pub undone: bool,
+}
+
+impl EntityMerge {
+    /// Whether `node_id` is the survivor or one of the absorbed nodes.
+    #[must_use]
+    pub fn involves(&self, node_id: u64) -> bool {
+        self.survivor.id == node_id
+            || self
+                .absorbed
+                .iter()
+                .any(|absorbed| absorbed.node.id == node_id)
+    }
+}
+
+/// Every merge applied to the mesh, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityMergeLog {
+    pub merges: Vec<EntityMerge>,
+    pub next_id: u64,
+}
+
+impl EntityMergeLog {
+    /// Survivor id for a name that was merged away (case-insensitive), following later merges.
+    #[must_use]
+    pub fn resolve_alias(&self, name: &str) -> Option<u64> {
+        let name = name.trim().to_lowercase();
+        let mut resolved = self.merges.iter().rev().find_map(|merge| {
+            (!merge.undone
+                && merge.absorbed.iter().any(|absorbed| {
+                    absorbed.node.canonical_name == name
+                        || absorbed.node.display_name.to_lowercase() == name
+                }))
+            .then_some(merge.survivor.id)
+        })?;
+        // A survivor may itself have been absorbed by a later merge.
+        while let Some(next) = self.merges.iter().find_map(|merge| {
+            (!merge.undone
+                && merge
+                    .absorbed
+                    .iter()
+                    .any(|absorbed| absorbed.node.id == resolved))
+            .then_some(merge.survivor.id)
+        }) {
+            resolved = next;
+        }
+        Some(resolved)
+    }
+}
+
+/// Outcome of `Memvid::resolve_entities`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityResolutionReport {
+    /// Merges applied (or, on a dry run, proposed).
+    pub merges: Vec<EntityMerge>,
+    pub nodes_before: usize,
+    pub nodes_after: usize,
+    /// Edges repointed at a survivor, including those dropped as duplicates or self-loops.
+    pub edges_rewritten: usize,
+    pub dry_run: bool,
+}
+
+impl EntityResolutionStrategy {
+    /// Name-based evidence that `a` and `b` are the same entity.
+    #[must_use]
+    pub fn name_signal(&self, a: &str, b: &str) -> Option<MergeSignal> {
+        let (a, b) = (normalize(a), normalize(b));
+        if a == b {
+            return Some(MergeSignal::Alias);
+        }
+        if self.aliases.iter().any(|group| {
+            let names: Vec<String> = group.iter().map(|name| normalize(name)).collect();
+            names.contains(&a) && names.contains(&b)
+        }) {
+            return Some(MergeSignal::Alias);
+        }
+        if self.match_name_variants && name_variants(&a, &b) {
+            return Some(MergeSignal::NameVariant);
+        }
+        let similarity = name_similarity(&a, &b);
+        (similarity >= self.min_name_similarity).then_some(MergeSignal::EditDistance(similarity))
+    }
+}
+
+/// Lowercase, drop punctuation, and collapse whitespace.
+fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .split(|ch: char| ch.is_whitespace() || ch == '.' || ch == ',')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Same surname and compatible given names: equal, an initial of, or a nickname of each other.
+fn name_variants(a: &str, b: &str) -> bool {
+    let (a, b): (Vec<&str>, Vec<&str>) = (a.split(' ').collect(), b.split(' ').collect());
+    if a.len() < 2 || b.len() < 2 || a.last() != b.last() {
+        return false;
+    }
+    let (given_a, given_b) = (a[0], b[0]);
+    let initial = |short: &str, long: &str| {
+        short.chars().count() == 1 && long.starts_with(short) && long.chars().count() > 1
+    };
+    let nickname = |x: &str, y: &str| {
+        NICKNAMES
+            .iter()
+            .any(|group| group.contains(&x) && group.contains(&y))
+    };
+    given_a == given_b
+        || initial(given_a, given_b)
+        || initial(given_b, given_a)
+        || nickname(given_a, given_b)
+        // "b smith" against "robert smith" through "bob".
+        || [(given_a, given_b), (given_b, given_a)].iter().any(|(short, long)| {
+            short.chars().count() == 1
+                && NICKNAMES.iter().any(|group| {
+                    group.contains(long) && group.iter().any(|variant| variant.starts_with(short))
+                })
+        })
+}
+
+/// `1 - levenshtein(a, b) / max(len)`.
+#[allow(clippy::cast_precision_loss)]
+fn name_similarity(a: &str, b: &str) -> f32 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f32 / longest as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_signals_cover_aliases_variants_and_typos() {
+        let strategy = EntityResolutionStrategy {
+            aliases: vec![vec!["Acme Corp".into(), "ACME Corporation".into()]],
+            ..EntityResolutionStrategy::default()
+        };
+        assert_eq!(
+            strategy.name_signal("acme corp", "Acme Corporation"),
+            Some(MergeSignal::Alias)
+        );
+        for (a, b) in [
+            ("Bob Smith", "Robert Smith"),
+            ("B. Smith", "Bob Smith"),
+            ("B. Smith", "Robert Smith"),
+        ] {
+            assert_eq!(
+                strategy.name_signal(a, b),
+                Some(MergeSignal::NameVariant),
+                "{a} / {b}"
+            );
+        }
+        assert!(matches!(
+            strategy.name_signal("Jonathan Smyth", "Jonathon Smyth"),
+            Some(MergeSignal::EditDistance(_))
+        ));
+        assert_eq!(strategy.name_signal("Bob Smith", "Bob Jones"), None);
+        assert_eq!(strategy.name_signal("Ann Smith", "Bob Smith"), None);
+    }
+}
@@ -0,0 +1,47 @@
+//! Erasure receipts for shredded frames.
+//!
+//! `Memvid::shred_frame` overwrites a frame's payload bytes in place, along with every older
+//! version and chunk of it, drops the text and metadata the TOC keeps for those frames, and
+//! purges them from the search indexes, memory cards, and Logic-Mesh. Each shred appends an
+//! [`ErasureReceipt`] to the log persisted in the TOC (extension key [`ERASURE_LOG_EXTENSION`]);
+//! read it back with `Memvid::erasure_receipts`. Receipts hold frame ids and counts only,
+//! never content or content hashes.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// TOC extension key holding the persisted [`ErasureLog`].
+pub const ERASURE_LOG_EXTENSION: &str = "memvid.erasure_log";
+
+/// Proof of one `Memvid::shred_frame` call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErasureReceipt {
+    /// Frame the erasure was requested for.
+    pub frame_id: FrameId,
+    /// Every frame erased: the requested one, the versions it superseded, their chunks, and
+    /// frames sharing their payload bytes.
+    pub frames: Vec<FrameId>,
+    /// Payload bytes overwritten in place.
+    pub bytes_overwritten: u64,
+    /// Unreferenced bytes (stale indexes and tables of contents) overwritten after the erasure
+    /// committed.
+    pub orphaned_bytes_overwritten: u64,
+    /// Memory cards extracted from the erased frames and removed.
+    pub cards_removed: usize,
+    /// Logic-Mesh mentions and edges from the erased frames removed.
+    pub mesh_references_removed: usize,
+    /// Snapshots whose archives still hold the erased frames' text; delete them and vacuum
+    /// to finish the erasure.
+    pub retained_in_snapshots: Vec<String>,
+    /// Footer generation the erasure committed in.
+    pub generation: u64,
+    /// Unix seconds.
+    pub erased_at: i64,
+}
+
+/// Every erasure receipt, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErasureLog {
+    pub receipts: Vec<ErasureReceipt>,
+}
@@ -0,0 +1,114 @@
+//! Index sidecar for cold archives.
+//!
+//! `Memvid::externalize_indexes` moves the Tantivy segment files and the vector index out of a
+//! memory into `<name>.mv2x` next to it, so the primary file keeps only frames, tracks, and the
+//! vector manifest's metadata. The blobs' new locations are recorded in an
+//! [`ExternalIndexManifest`] stored in the TOC under [`EXTERNAL_INDEX_EXTENSION`]; offsets in it
+//! are relative to the sidecar, and every blob keeps the BLAKE3 checksum it had in the primary.
+//!
+//! ## Format
+//!
+//! ```text
+//! header (8 bytes): magic "MV2X", version u32
+//! blobs, back to back, in manifest order
+//! ```
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::manifest::{LexSegmentManifest, Toc, VecIndexManifest};
+use crate::error::{MemvidError, Result};
+
+/// TOC extension key holding the [`ExternalIndexManifest`].
+pub const EXTERNAL_INDEX_EXTENSION: &str = "memvid.external_indexes";
+
+/// File extension of the index sidecar.
+pub const SIDECAR_EXTENSION: &str = "mv2x";
+
+/// Magic bytes identifying an index sidecar: "MV2X"
+pub const SIDECAR_MAGIC: [u8; 4] = *b"MV2X";
+
+/// Current version of the sidecar format.
+pub const SIDECAR_VERSION: u32 = 1;
+
+/// Size of the sidecar header; the first blob starts here.
+pub const SIDECAR_HEADER_SIZE: u64 = 8;
+
+/// Indexes held in the sidecar, with offsets into it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExternalIndexManifest {
+    /// Expected length of the sidecar in bytes.
+    pub sidecar_len: u64,
+    /// Tantivy segment files.
+    pub lex_segments: Vec<LexSegmentManifest>,
+    /// The vector index; the primary keeps a copy of this manifest with zeroed offsets.
+    pub vec: Option<VecIndexManifest>,
+}
+
+impl ExternalIndexManifest {
+    /// `(offset, length, checksum)` of every blob, in sidecar order.
+    pub(crate) fn blobs(&self) -> impl Iterator<Item = (u64, u64, [u8; 32])> + '_ {
+        self.lex_segments
+            .iter()
+            .map(|segment| (segment.bytes_offset, segment.bytes_length, segment.checksum))
+            .chain(
+                self.vec
+                    .iter()
+                    .map(|vec| (vec.bytes_offset, vec.bytes_length, vec.checksum)),
+            )
+    }
+}
+
+/// Sidecar path for the memory at `path`: the same name with a `.mv2x` extension.
+#[must_use]
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    path.with_extension(SIDECAR_EXTENSION)
+}
+
+/// External index manifest stored in `toc`; decoding errors are treated as "none".
+pub(crate) fn external_index_manifest(toc: &Toc) -> Option<ExternalIndexManifest> {
+    toc.extension::<ExternalIndexManifest>(EXTERNAL_INDEX_EXTENSION)
+        .ok()
+        .flatten()
+}
+
+/// Check the sidecar's header, length, and every blob checksum against `manifest`.
+pub(crate) fn validate_sidecar(file: &mut File, manifest: &ExternalIndexManifest) -> Result<()> {
+    let invalid = |reason: String| MemvidError::InvalidToc {
+        reason: reason.into(),
+    };
+    let len = file.metadata()?.len();
+    if len != manifest.sidecar_len {
+        return Err(invalid(format!(
+            "index sidecar is {len} bytes, expected {}",
+            manifest.sidecar_len
+        )));
+    }
+    let mut header = [0u8; 8];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut header)?;
+    let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if header[..4] != SIDECAR_MAGIC || version != SIDECAR_VERSION {
+        return Err(invalid("index sidecar header is not recognised".into()));
+    }
+    for (offset, length, checksum) in manifest.blobs() {
+        let end = offset.saturating_add(length);
+        if offset < SIDECAR_HEADER_SIZE || end > len {
+            return Err(invalid(format!(
+                "index sidecar blob at {offset} (length {length}) is out of bounds"
+            )));
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut hasher = blake3::Hasher::new();
+        std::io::copy(&mut Read::by_ref(file).take(length), &mut hasher)?;
+        if *hasher.finalize().as_bytes() != checksum {
+            return Err(MemvidError::ChecksumMismatch {
+                context: "index sidecar",
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! Per-file tuning of behavior that is otherwise built in (see `Memvid::set_config`).
+//!
+//! Stored in the TOC under [`FILE_CONFIG_EXTENSION`]; unset fields keep the built-in values.
+//! The time zone and compression defaults live where `set_default_timezone` and
+//! `set_compression_defaults` keep them, so both APIs see each other's changes.
+
+use serde::{Deserialize, Serialize};
+
+use super::compression::CompressionDefaults;
+
+/// TOC extension key holding the file's [`FileConfig`], without its time zone and compression.
+pub const FILE_CONFIG_EXTENSION: &str = "memvid.config";
+
+/// Hamming distance the search sketch pre-filter accepts when the file does not set one.
+pub const DEFAULT_SKETCH_PREFILTER_THRESHOLD: u32 = 32;
+
+/// Operator-tunable defaults of a memory file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileConfig {
+    /// Snippet length for searches whose request asks for 0 characters.
+    #[serde(default)]
+    pub snippet_chars: Option<usize>,
+    /// Target characters per text chunk (1200 by default); documents shorter than twice this
+    /// are not chunked. `PutOptions::chunk_chars` overrides it per put.
+    #[serde(default)]
+    pub chunk_chars: Option<usize>,
+    /// Largest Hamming distance, out of 64 bits, the search sketch pre-filter accepts (32 by
+    /// default).
+    #[serde(default)]
+    pub sketch_hamming_threshold: Option<u32>,
+    /// Utilisation, in percent of capacity, at which `Stats::capacity_warning` is raised (80
+    /// by default).
+    #[serde(default)]
+    pub capacity_warning_percent: Option<u8>,
+    /// Time zone relative dates resolve in; `PutOptions::timezone` overrides it per put.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Codecs for new payloads and segments; `PutOptions::compression` overrides the payload
+    /// codec per put.
+    #[serde(default)]
+    pub compression: Option<CompressionDefaults>,
+}
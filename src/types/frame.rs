@@ -6,28 +6,38 @@ use serde::{
     Deserialize, Serialize,
     de::{self, MapAccess, SeqAccess, Visitor},
 };
+use time::{Date, Month, OffsetDateTime};
 
 #[cfg(feature = "temporal_track")]
 use super::search::SearchHitTemporal;
 #[cfg(feature = "temporal_track")]
 use super::temporal::TemporalFilter;
 use super::{
+    access_stats::HotFrame,
+    capacity::StorageBreakdown,
     common::{CanonicalEncoding, FrameId, FrameRole, FrameStatus, Tier},
+    geo::GeoFilter,
     metadata::{DocMetadata, TextChunkManifest},
 };
 
 // Note: AnchorSource is always defined (not feature-gated) to maintain binary compatibility
 
 /// Timeline query parameters for scanning frames chronologically or in reverse.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TimelineQuery {
     pub limit: Option<NonZeroU64>,
     pub since: Option<i64>,
     pub until: Option<i64>,
+    #[serde(default)]
     pub reverse: bool,
     #[cfg(feature = "temporal_track")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub temporal: Option<TemporalFilter>,
+    /// Only frames located inside this region.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo: Option<GeoFilter>,
 }
 
 impl TimelineQuery {
@@ -74,6 +84,12 @@ impl TimelineQueryBuilder {
         self
     }
 
+    #[must_use]
+    pub fn geo(mut self, filter: GeoFilter) -> Self {
+        self.inner.geo = Some(filter);
+        self
+    }
+
     #[must_use]
     pub fn no_limit(mut self) -> Self {
         self.inner.limit = None;
@@ -89,6 +105,70 @@ impl TimelineQueryBuilder {
     }
 }
 
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Width of the buckets returned by `Memvid::timeline_histogram`. Buckets follow UTC
+/// calendar boundaries; weeks start on Monday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl TimeBucket {
+    /// Start of the bucket containing `timestamp`.
+    #[must_use]
+    pub fn floor(self, timestamp: i64) -> i64 {
+        let day = timestamp.div_euclid(SECONDS_PER_DAY);
+        match self {
+            Self::Day => day * SECONDS_PER_DAY,
+            // 1970-01-01 was a Thursday, three days into its week
+            Self::Week => (day - (day + 3).rem_euclid(7)) * SECONDS_PER_DAY,
+            Self::Month => OffsetDateTime::from_unix_timestamp(timestamp)
+                .ok()
+                .and_then(|at| at.date().replace_day(1).ok())
+                .map_or(day * SECONDS_PER_DAY, |first| {
+                    first.midnight().assume_utc().unix_timestamp()
+                }),
+        }
+    }
+
+    /// End (exclusive) of the bucket starting at `start`.
+    #[must_use]
+    pub fn end(self, start: i64) -> i64 {
+        match self {
+            Self::Day => start + SECONDS_PER_DAY,
+            Self::Week => start + 7 * SECONDS_PER_DAY,
+            Self::Month => OffsetDateTime::from_unix_timestamp(start)
+                .ok()
+                .and_then(|at| {
+                    let date = at.date();
+                    let (year, month) = match date.month() {
+                        Month::December => (date.year() + 1, Month::January),
+                        month => (date.year(), month.next()),
+                    };
+                    Date::from_calendar_date(year, month, 1).ok()
+                })
+                .map_or(start + 31 * SECONDS_PER_DAY, |next| {
+                    next.midnight().assume_utc().unix_timestamp()
+                }),
+        }
+    }
+}
+
+/// Activity within one bucket of a timeline histogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    /// First second of the bucket.
+    pub start: i64,
+    /// First second after the bucket.
+    pub end: i64,
+    pub frames: u64,
+    /// Stored payload bytes of those frames.
+    pub bytes: u64,
+}
+
 /// Public-facing statistics summarising a memory.
 /// Aggregates counts, sizes, capacity, and index presence for quick health checks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,6 +222,19 @@ pub struct Stats {
     /// Whether the vec (vector/semantic) search engine is enabled at runtime.
     #[serde(default)]
     pub vec_enabled: bool,
+    /// Most returned or cited frames, hottest first (see `Memvid::hot_frames`).
+    #[serde(default)]
+    pub hot_frames: Vec<HotFrame>,
+    /// Utilisation, in percent of capacity, at which `capacity_warning` is raised.
+    #[serde(default)]
+    pub capacity_warning_percent: u8,
+    /// Usage has reached `capacity_warning_percent` (see `Memvid::capacity_report`).
+    #[serde(default)]
+    pub capacity_warning: bool,
+    /// Bytes taken by each payload kind, index, track, and the WAL (see
+    /// `Memvid::storage_breakdown`).
+    #[serde(default)]
+    pub storage: StorageBreakdown,
 }
 
 /// Entry returned by `timeline` queries, carrying a lightweight preview.
@@ -0,0 +1,112 @@
+//! Tabular frame export formats and filters (see `Memvid::export_frames`).
+
+use serde::{Deserialize, Serialize};
+
+use super::frame::TimelineQuery;
+
+/// Default number of frames read and written per batch.
+pub const DEFAULT_FRAME_EXPORT_BATCH_SIZE: usize = 1024;
+
+/// Output format of `Memvid::export_frames`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameExportFormat {
+    /// One JSON object per line.
+    Ndjson,
+    /// Apache Parquet with one row group per batch. Requires the `parquet` feature.
+    Parquet,
+}
+
+impl FrameExportFormat {
+    /// Conventional file extension.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// A column of a frame export, written in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameExportColumn {
+    Id,
+    /// The frame URI, or its default `mv2://frames/<id>` URI.
+    Uri,
+    Title,
+    /// Unix seconds in NDJSON; a UTC millisecond timestamp in Parquet.
+    Timestamp,
+    Tags,
+    /// Extracted text. The only column that reads frame payloads.
+    Text,
+    /// Extra metadata entries as a JSON object, with document metadata under `document`.
+    Metadata,
+}
+
+impl FrameExportColumn {
+    /// Every column, in output order.
+    pub const ALL: [Self; 7] = [
+        Self::Id,
+        Self::Uri,
+        Self::Title,
+        Self::Timestamp,
+        Self::Tags,
+        Self::Text,
+        Self::Metadata,
+    ];
+
+    /// Field name in the output.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Uri => "uri",
+            Self::Title => "title",
+            Self::Timestamp => "timestamp",
+            Self::Tags => "tags",
+            Self::Text => "text",
+            Self::Metadata => "metadata",
+        }
+    }
+}
+
+/// Which frames an export covers and which columns it writes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrameExportFilter {
+    /// Time range, order, limit, and geo filter, as for `Memvid::timeline`.
+    pub query: TimelineQuery,
+    /// Only frames carrying every one of these tags.
+    pub tags: Vec<String>,
+    /// Columns to write; empty means all. Duplicates are ignored.
+    pub columns: Vec<FrameExportColumn>,
+    /// Frames read and written per batch. `0` uses the default.
+    pub batch_size: usize,
+}
+
+impl Default for FrameExportFilter {
+    fn default() -> Self {
+        Self {
+            query: TimelineQuery::default(),
+            tags: Vec::new(),
+            columns: FrameExportColumn::ALL.to_vec(),
+            batch_size: DEFAULT_FRAME_EXPORT_BATCH_SIZE,
+        }
+    }
+}
+
+impl FrameExportFilter {
+    /// Selected columns, deduplicated, in output order.
+    #[must_use]
+    pub fn selected_columns(&self) -> Vec<FrameExportColumn> {
+        if self.columns.is_empty() {
+            return FrameExportColumn::ALL.to_vec();
+        }
+        let mut columns = self.columns.clone();
+        columns.sort_unstable();
+        columns.dedup();
+        columns
+    }
+}
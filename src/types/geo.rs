@@ -0,0 +1,323 @@
+//! Geospatial filters over frame locations.
+//!
+//! A frame is located by the GPS block of its EXIF metadata, or by [`GEO_LAT_KEY`] /
+//! [`GEO_LON_KEY`] in its extra metadata for content without EXIF. `SearchRequest::geo` and
+//! `TimelineQuery::geo` restrict results to frames inside a [`GeoFilter`]; frames without a
+//! location never match.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// Extra-metadata key holding a frame's latitude in decimal degrees.
+pub const GEO_LAT_KEY: &str = "geo_lat";
+/// Extra-metadata key holding a frame's longitude in decimal degrees.
+pub const GEO_LON_KEY: &str = "geo_lon";
+
+/// Mean Earth radius used for great-circle distances.
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+/// Geohash length of index cells (about 4.9km x 4.9km at the equator).
+const INDEX_PRECISION: usize = 5;
+/// Cell enumeration budget before a query falls back to scanning every entry.
+const MAX_QUERY_CELLS: usize = 64;
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// A WGS84 coordinate in decimal degrees.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    /// `None` unless both coordinates are finite and in range.
+    #[must_use]
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(
+            Self {
+                latitude,
+                longitude,
+            },
+        )
+    }
+
+    /// Great-circle (haversine) distance in kilometres.
+    #[must_use]
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.longitude - self.longitude).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+
+    /// Geohash of this point with `precision` characters.
+    #[must_use]
+    pub fn geohash(&self, precision: usize) -> String {
+        let (mut lat_range, mut lon_range) = ((-90.0, 90.0), (-180.0, 180.0));
+        let mut hash = String::with_capacity(precision);
+        let mut even = true;
+        let mut bits = 0u8;
+        let mut value = 0usize;
+        while hash.len() < precision {
+            let (range, coordinate) = if even {
+                (&mut lon_range, self.longitude)
+            } else {
+                (&mut lat_range, self.latitude)
+            };
+            let mid = f64::midpoint(range.0, range.1);
+            value <<= 1;
+            if coordinate >= mid {
+                value |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+            bits += 1;
+            if bits == 5 {
+                hash.push(char::from(GEOHASH_ALPHABET[value]));
+                bits = 0;
+                value = 0;
+            }
+        }
+        hash
+    }
+}
+
+/// Region a located frame must fall in.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeoFilter {
+    /// Within `radius_km` of a centre point.
+    Radius { center: GeoPoint, radius_km: f64 },
+    /// Inside a latitude/longitude box. A box whose `min_longitude` exceeds its
+    /// `max_longitude` wraps across the antimeridian.
+    BoundingBox {
+        min_latitude: f64,
+        min_longitude: f64,
+        max_latitude: f64,
+        max_longitude: f64,
+    },
+}
+
+impl GeoFilter {
+    /// Frames within `radius_km` of (`latitude`, `longitude`).
+    #[must_use]
+    pub fn within_km(latitude: f64, longitude: f64, radius_km: f64) -> Self {
+        Self::Radius {
+            center: GeoPoint {
+                latitude,
+                longitude,
+            },
+            radius_km,
+        }
+    }
+
+    /// Frames inside the box spanned by two corners.
+    #[must_use]
+    pub fn bounding_box(
+        min_latitude: f64,
+        min_longitude: f64,
+        max_latitude: f64,
+        max_longitude: f64,
+    ) -> Self {
+        Self::BoundingBox {
+            min_latitude,
+            min_longitude,
+            max_latitude,
+            max_longitude,
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        match *self {
+            Self::Radius { center, radius_km } => center.distance_km(point) <= radius_km,
+            Self::BoundingBox {
+                min_latitude,
+                min_longitude,
+                max_latitude,
+                max_longitude,
+            } => {
+                let in_lat = (min_latitude..=max_latitude).contains(&point.latitude);
+                let in_lon = if min_longitude <= max_longitude {
+                    (min_longitude..=max_longitude).contains(&point.longitude)
+                } else {
+                    point.longitude >= min_longitude || point.longitude <= max_longitude
+                };
+                in_lat && in_lon
+            }
+        }
+    }
+
+    /// Non-wrapping `(min_lat, min_lon, max_lat, max_lon)` enclosing the filter, or `None`
+    /// when it touches a pole or crosses the antimeridian.
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let bounds = match *self {
+            Self::Radius { center, radius_km } => {
+                let dlat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+                let cos_lat = center.latitude.to_radians().cos();
+                if cos_lat <= f64::EPSILON {
+                    return None;
+                }
+                let dlon = dlat / cos_lat;
+                (
+                    center.latitude - dlat,
+                    center.longitude - dlon,
+                    center.latitude + dlat,
+                    center.longitude + dlon,
+                )
+            }
+            Self::BoundingBox {
+                min_latitude,
+                min_longitude,
+                max_latitude,
+                max_longitude,
+            } => (min_latitude, min_longitude, max_latitude, max_longitude),
+        };
+        let (min_lat, min_lon, max_lat, max_lon) = bounds;
+        (min_lat >= -90.0
+            && max_lat <= 90.0
+            && -180.0 <= min_lon
+            && min_lon <= max_lon
+            && max_lon <= 180.0)
+            .then_some(bounds)
+    }
+}
+
+/// Geohash-bucketed frame locations.
+#[derive(Debug, Clone, Default)]
+pub struct GeoIndex {
+    cells: BTreeMap<String, Vec<(FrameId, GeoPoint)>>,
+    len: usize,
+}
+
+impl GeoIndex {
+    pub fn insert(&mut self, frame_id: FrameId, point: GeoPoint) {
+        self.cells
+            .entry(point.geohash(INDEX_PRECISION))
+            .or_default()
+            .push((frame_id, point));
+        self.len += 1;
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Frames located inside `filter`, in ascending id order.
+    #[must_use]
+    pub fn query(&self, filter: &GeoFilter) -> Vec<FrameId> {
+        let mut ids: Vec<FrameId> = match self.covering_prefixes(filter) {
+            Some(prefixes) => prefixes
+                .iter()
+                .flat_map(|prefix| {
+                    self.cells
+                        .range(prefix.clone()..)
+                        .take_while(move |(cell, _)| cell.starts_with(prefix.as_str()))
+                        .flat_map(|(_, entries)| entries)
+                })
+                .filter(|(_, point)| filter.contains(point))
+                .map(|(frame_id, _)| *frame_id)
+                .collect(),
+            None => self
+                .cells
+                .values()
+                .flatten()
+                .filter(|(_, point)| filter.contains(point))
+                .map(|(frame_id, _)| *frame_id)
+                .collect(),
+        };
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// Geohash prefixes whose cells cover the filter's bounds, using the finest precision that
+    /// stays within [`MAX_QUERY_CELLS`].
+    fn covering_prefixes(&self, filter: &GeoFilter) -> Option<Vec<String>> {
+        let (min_lat, min_lon, max_lat, max_lon) = filter.bounds()?;
+        for precision in (1..=INDEX_PRECISION).rev() {
+            let lon_bits = (5 * precision).div_ceil(2);
+            let lat_bits = 5 * precision / 2;
+            let cell_width = 360.0 / f64::from(1u32 << lon_bits);
+            let cell_height = 180.0 / f64::from(1u32 << lat_bits);
+            let columns = ((max_lon - min_lon) / cell_width).ceil() + 1.0;
+            let rows = ((max_lat - min_lat) / cell_height).ceil() + 1.0;
+            if columns * rows > MAX_QUERY_CELLS as f64 {
+                continue;
+            }
+            let mut prefixes = Vec::new();
+            let mut lat = min_lat;
+            loop {
+                let mut lon = min_lon;
+                loop {
+                    let hash = GeoPoint {
+                        latitude: lat.min(max_lat),
+                        longitude: lon.min(max_lon),
+                    }
+                    .geohash(precision);
+                    if !prefixes.contains(&hash) {
+                        prefixes.push(hash);
+                    }
+                    if lon >= max_lon {
+                        break;
+                    }
+                    lon += cell_width;
+                }
+                if lat >= max_lat {
+                    break;
+                }
+                lat += cell_height;
+            }
+            return Some(prefixes);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_queries_match_exact_filters() {
+        let office = GeoPoint::new(37.7749, -122.4194).expect("point");
+        assert_eq!(office.geohash(5), "9q8yy");
+
+        let mut index = GeoIndex::default();
+        index.insert(1, office);
+        index.insert(2, GeoPoint::new(37.8044, -122.2712).expect("oakland"));
+        index.insert(3, GeoPoint::new(51.5072, -0.1276).expect("london"));
+        index.insert(4, GeoPoint::new(37.7790, -122.4312).expect("nearby"));
+        index.insert(5, GeoPoint::new(-16.5, 179.9).expect("fiji"));
+
+        assert_eq!(
+            index.query(&GeoFilter::within_km(37.7749, -122.4194, 5.0)),
+            [1, 4]
+        );
+        assert_eq!(
+            index.query(&GeoFilter::within_km(37.7749, -122.4194, 20.0)),
+            [1, 2, 4]
+        );
+        assert_eq!(
+            index.query(&GeoFilter::bounding_box(50.0, -1.0, 52.0, 1.0)),
+            [3]
+        );
+        assert_eq!(
+            index.query(&GeoFilter::bounding_box(-20.0, 179.0, -10.0, -179.0)),
+            [5]
+        );
+    }
+}
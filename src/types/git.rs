@@ -0,0 +1,113 @@
+//! Git repository ingestion (see `Memvid::ingest_git_repo`).
+//!
+//! Source files at a revision become frames of kind [`GIT_FILE_FRAME_KIND`], chunked along
+//! code boundaries when their language is known. Each file frame carries the commit that last
+//! touched it: hash, author, and (as the frame timestamp) commit time. Commit messages can be
+//! ingested as frames of kind [`GIT_COMMIT_FRAME_KIND`], timestamped at commit time so the
+//! temporal track anchors them there.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// `kind` of a frame holding a file at a revision.
+pub const GIT_FILE_FRAME_KIND: &str = "git_file";
+/// `kind` of a frame holding a commit message.
+pub const GIT_COMMIT_FRAME_KIND: &str = "git_commit";
+/// Extra-metadata key holding the full commit hash.
+pub const GIT_COMMIT_KEY: &str = "git_commit";
+/// Extra-metadata key holding the commit author as `Name <email>`.
+pub const GIT_AUTHOR_KEY: &str = "git_author";
+/// Extra-metadata key holding a file's path relative to the repository root.
+pub const GIT_PATH_KEY: &str = "git_path";
+/// Extra-metadata key holding a file's blob hash.
+pub const GIT_BLOB_KEY: &str = "git_blob";
+/// Extra-metadata key holding a file's detected programming language.
+pub const GIT_LANGUAGE_KEY: &str = "git_language";
+
+/// Default size above which files are skipped.
+pub const DEFAULT_GIT_MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// A file in a revision's tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitTreeEntry {
+    /// Path relative to the repository root, with `/` separators.
+    pub path: String,
+    /// Blob hash.
+    pub blob: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// A commit, as read from the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    /// Author time, in Unix seconds.
+    pub timestamp: i64,
+    pub subject: String,
+    pub body: String,
+    /// Paths the commit changed. Empty for merge commits.
+    pub files: Vec<String>,
+}
+
+impl GitCommit {
+    /// The author as `Name <email>`.
+    #[must_use]
+    pub fn author_line(&self) -> String {
+        format!("{} <{}>", self.author, self.email)
+    }
+}
+
+/// How `Memvid::ingest_git_repo` selects files and commits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitIngestOptions {
+    /// Revision whose tree and history are read.
+    pub revision: String,
+    /// Glob patterns over repository paths, as for `IngestDirOptions::globs`.
+    pub globs: Vec<String>,
+    /// Files larger than this are skipped, as are binary files.
+    pub max_file_bytes: u64,
+    /// Also write one frame per commit message.
+    pub include_commits: bool,
+    /// Newest commits read from the log; `None` reads the whole history.
+    pub max_commits: Option<usize>,
+    /// Target chunk size for code; `None` uses the memory's configured size.
+    pub chunk_chars: Option<usize>,
+}
+
+impl Default for GitIngestOptions {
+    fn default() -> Self {
+        Self {
+            revision: "HEAD".to_string(),
+            globs: Vec::new(),
+            max_file_bytes: DEFAULT_GIT_MAX_FILE_BYTES,
+            include_commits: false,
+            max_commits: None,
+            chunk_chars: None,
+        }
+    }
+}
+
+/// Changes made by a repository ingestion. Files are matched to earlier frames by URI, so
+/// ingesting a later revision updates the memory in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitIngestReport {
+    /// Commit hash the revision resolved to.
+    pub head: String,
+    /// Frames of files not stored before.
+    pub added: Vec<FrameId>,
+    /// Frames superseding those of files whose blob changed.
+    pub updated: Vec<FrameId>,
+    /// Files whose stored blob still matches.
+    pub unchanged: usize,
+    /// Frames tombstoned because their file is no longer in the tree.
+    pub deleted: Vec<FrameId>,
+    /// Files skipped as binary or oversized.
+    pub skipped: usize,
+    /// Frames of commit messages not stored before.
+    pub commits: Vec<FrameId>,
+}
@@ -0,0 +1,38 @@
+//! Graph export formats for the logic mesh and memory cards (see `Memvid::export_graph`).
+
+use serde::{Deserialize, Serialize};
+
+/// Output format of `Memvid::export_graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphExportFormat {
+    /// GraphML XML, readable by Gephi, yEd, and `NetworkX`.
+    GraphMl,
+    /// A JSON-LD document whose `@graph` holds one object per node.
+    JsonLd,
+    /// Cypher `CREATE` statements, one per line, for Neo4j and Memgraph.
+    Cypher,
+}
+
+impl GraphExportFormat {
+    /// Conventional file extension.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::GraphMl => "graphml",
+            Self::JsonLd => "jsonld",
+            Self::Cypher => "cypher",
+        }
+    }
+}
+
+/// What an export wrote.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphExportStats {
+    /// Mesh entities plus entities only named by memory cards.
+    pub entity_nodes: usize,
+    /// Memory cards, each exported as a node linked to its entity.
+    pub card_nodes: usize,
+    /// Mesh relationships plus one `about` edge per card.
+    pub edges: usize,
+}
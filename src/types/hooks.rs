@@ -0,0 +1,56 @@
+//! Event payloads passed to hooks registered on a `Memvid` handle.
+//!
+//! Hooks run synchronously on the thread that performed the operation, after it succeeded:
+//! put hooks once the frame is in the WAL, commit hooks once the commit is durable, and
+//! enrichment hooks once a frame's enrichment has been applied.
+
+use super::common::FrameId;
+
+/// A frame staged by a put.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PutEvent {
+    /// Sequence returned by the put; it becomes the frame id on commit.
+    pub sequence: u64,
+    pub uri: Option<String>,
+    pub title: Option<String>,
+    pub track: Option<String>,
+    pub kind: Option<String>,
+    /// Tags supplied with the put, before auto-tagging.
+    pub tags: Vec<String>,
+    /// Size of the payload as given to the put.
+    pub payload_bytes: u64,
+}
+
+/// Frames changed by a durable commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitHookEvent {
+    /// Footer generation written by the commit.
+    pub generation: u64,
+    pub inserted_frames: Vec<FrameId>,
+    pub tombstoned_frames: Vec<FrameId>,
+    /// URIs of the inserted and tombstoned frames, sorted and deduplicated.
+    pub uris: Vec<String>,
+}
+
+impl CommitHookEvent {
+    /// Whether any changed frame's URI starts with `scope`.
+    #[must_use]
+    pub fn touches_scope(&self, scope: &str) -> bool {
+        self.uris.iter().any(|uri| uri.starts_with(scope))
+    }
+}
+
+/// A frame whose background enrichment finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnrichmentEvent {
+    pub frame_id: FrameId,
+    pub uri: Option<String>,
+}
+
+/// Receiver of `Memvid` events. Every method defaults to doing nothing, so implementors only
+/// override what they need; closures can be registered with `Memvid::on_put` and friends.
+pub trait MemvidHooks: Send {
+    fn on_put(&mut self, _event: &PutEvent) {}
+    fn on_commit(&mut self, _event: &CommitHookEvent) {}
+    fn on_enrichment_complete(&mut self, _event: &EnrichmentEvent) {}
+}
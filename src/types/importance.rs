@@ -0,0 +1,132 @@
+//! Importance and recency scoring for memory cards and frames.
+//!
+//! Each card or frame that has been pinned or accessed gets an [`ImportanceRecord`], kept in
+//! the memories track. Its score mixes recency (exponential decay with a configurable
+//! half-life since the last access, or since creation) and access frequency; pinned items
+//! always score 1.0. `Memvid::decay` demotes stale, low-scoring cards, and search boosts
+//! frames that score highly.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::memory_card::MemoryCardId;
+
+/// Accesses at which the frequency component reaches one half.
+const FREQUENCY_SATURATION: f32 = 5.0;
+
+/// Tuning for `Memvid::decay` and the search-time importance boost.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DecayPolicy {
+    /// Seconds for the recency component to halve.
+    pub half_life_secs: i64,
+    /// Cards scoring below this are demoted.
+    pub demote_below: f32,
+    /// Cards untouched for less than this are never demoted.
+    pub min_idle_secs: i64,
+}
+
+impl Default for DecayPolicy {
+    fn default() -> Self {
+        Self {
+            half_life_secs: 30 * 24 * 60 * 60,
+            demote_below: 0.1,
+            min_idle_secs: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// Usage signals for one card or frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportanceRecord {
+    pub access_count: u32,
+    /// Unix seconds of the most recent access.
+    pub last_accessed: Option<i64>,
+    /// Pinned items are never demoted and always score 1.0; pinned frames cannot be deleted.
+    pub pinned: bool,
+    /// Set by `Memvid::decay`; cleared by the next access or pin.
+    pub demoted: bool,
+}
+
+impl ImportanceRecord {
+    /// Record one access at `now`, reviving a demoted item.
+    pub fn touch(&mut self, now: i64) {
+        self.access_count = self.access_count.saturating_add(1);
+        self.last_accessed = Some(now);
+        self.demoted = false;
+    }
+
+    /// Seconds since the last access, or since `created_at` if never accessed.
+    #[must_use]
+    pub fn idle_secs(&self, created_at: i64, now: i64) -> i64 {
+        let last = self.last_accessed.unwrap_or(created_at).max(created_at);
+        (now - last).max(0)
+    }
+
+    /// Importance in `0.0..=1.0`: half recency, half access frequency.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn score(&self, created_at: i64, now: i64, policy: &DecayPolicy) -> f32 {
+        if self.pinned {
+            return 1.0;
+        }
+        let half_life = policy.half_life_secs.max(1) as f32;
+        let recency = 0.5f32.powf(self.idle_secs(created_at, now) as f32 / half_life);
+        let accesses = self.access_count as f32;
+        let frequency = accesses / (accesses + FREQUENCY_SATURATION);
+        0.5 * recency + 0.5 * frequency
+    }
+}
+
+/// Importance records for cards and frames, stored in the memories track.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportanceTable {
+    #[serde(default)]
+    pub cards: BTreeMap<MemoryCardId, ImportanceRecord>,
+    #[serde(default)]
+    pub frames: BTreeMap<FrameId, ImportanceRecord>,
+}
+
+impl ImportanceTable {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty() && self.frames.is_empty()
+    }
+}
+
+/// Outcome of `Memvid::decay`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecayReport {
+    /// Cards examined (current, unpinned, not already demoted).
+    pub examined: usize,
+    /// Cards demoted by this run.
+    pub demoted: Vec<MemoryCardId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_decays_with_idle_time_and_grows_with_access() {
+        let policy = DecayPolicy::default();
+        let day = 24 * 60 * 60;
+        let fresh = ImportanceRecord::default();
+        assert!((fresh.score(0, 0, &policy) - 0.5).abs() < f32::EPSILON);
+        let stale = fresh.score(0, policy.half_life_secs, &policy);
+        assert!((stale - 0.25).abs() < 1e-6);
+
+        let mut used = ImportanceRecord::default();
+        for _ in 0..5 {
+            used.touch(90 * day);
+        }
+        assert!((used.score(0, 90 * day, &policy) - 0.75).abs() < 1e-6);
+
+        let pinned = ImportanceRecord {
+            pinned: true,
+            ..ImportanceRecord::default()
+        };
+        assert!((pinned.score(0, 365 * day, &policy) - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,125 @@
+//! Options and reports of directory ingestion (see `Memvid::ingest_dir`).
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// Extra metadata key holding the hex BLAKE3 hash of an ingested file's bytes.
+pub const SOURCE_HASH_KEY: &str = "source_blake3";
+
+/// Default number of files written between commits.
+pub const DEFAULT_INGEST_BATCH_SIZE: usize = 64;
+
+/// How `Memvid::ingest_dir` walks a directory and writes its files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IngestDirOptions {
+    /// Glob patterns over paths relative to the root, with `/` separators. A pattern
+    /// starting with `!` excludes. Files must match an include pattern (if there are any)
+    /// and no exclude pattern. `*` and `?` stay within one path segment, `**` spans
+    /// segments, and a pattern without `/` matches file names at any depth.
+    pub globs: Vec<String>,
+    /// Descend into subdirectories.
+    pub recursive: bool,
+    /// Follow symbolic links to files and directories; otherwise they are skipped.
+    pub follow_symlinks: bool,
+    /// Also walk files and directories whose names start with `.`.
+    pub include_hidden: bool,
+    /// Threads reading and hashing files ahead of the writer. `0` means one.
+    pub concurrency: usize,
+    /// Skip files whose content matches an active frame or a file already ingested.
+    pub dedup: bool,
+    /// Files written between commits. `0` uses the default.
+    pub batch_size: usize,
+}
+
+impl Default for IngestDirOptions {
+    fn default() -> Self {
+        Self {
+            globs: Vec::new(),
+            recursive: true,
+            follow_symlinks: false,
+            include_hidden: false,
+            concurrency: 4,
+            dedup: true,
+            batch_size: DEFAULT_INGEST_BATCH_SIZE,
+        }
+    }
+}
+
+/// What happened to one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum IngestFileOutcome {
+    /// Written as a new frame.
+    Ingested { frame_id: FrameId },
+    /// Skipped because its content is already stored in `frame_id`.
+    Duplicate { frame_id: FrameId },
+    /// Not ingested; the walk continues.
+    Failed { error: String },
+}
+
+/// Per-file progress passed to the `Memvid::ingest_dir_with_progress` callback.
+#[derive(Debug, Clone, Copy)]
+pub struct IngestFileEvent<'a> {
+    pub path: &'a Path,
+    pub outcome: &'a IngestFileOutcome,
+    /// Files handled so far, including this one.
+    pub done: usize,
+    /// Files matched by the walk.
+    pub total: usize,
+}
+
+/// A file that could not be ingested.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Totals of a directory ingestion.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestDirReport {
+    /// Files matched by the walk.
+    pub files: usize,
+    /// New frames, in walk order.
+    pub frame_ids: Vec<FrameId>,
+    /// Files skipped as duplicates.
+    pub duplicates: usize,
+    pub failures: Vec<IngestFailure>,
+    /// Commits made.
+    pub batches: usize,
+}
+
+/// Changes made by `Memvid::sync_dir` or a folder watcher.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirSyncReport {
+    /// Frames of files not stored before.
+    pub added: Vec<FrameId>,
+    /// Frames superseding those of changed files.
+    pub updated: Vec<FrameId>,
+    /// Files whose stored hash still matches.
+    pub unchanged: usize,
+    /// Frames tombstoned because their file is gone.
+    pub deleted: Vec<FrameId>,
+    pub failures: Vec<IngestFailure>,
+}
+
+impl DirSyncReport {
+    /// Whether any frame was added, updated, or deleted.
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.updated.is_empty() && self.deleted.is_empty())
+    }
+
+    /// Fold `other`, a later sync, into this report.
+    pub fn merge(&mut self, other: Self) {
+        self.added.extend(other.added);
+        self.updated.extend(other.updated);
+        self.unchanged += other.unchanged;
+        self.deleted.extend(other.deleted);
+        self.failures.extend(other.failures);
+    }
+}
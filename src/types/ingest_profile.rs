@@ -0,0 +1,117 @@
+//! Named ingest profiles: put settings bundled under a name and stored in the file.
+//!
+//! A profile such as `email`, `code`, or `media` collects the chunk size, enrichment
+//! switches, embedding, compression, and temporal settings suited to one kind of content, so
+//! every writer of a memory ingests it the same way with `PutOptions::profile("email")`.
+//! Profiles are stored in the TOC under [`INGEST_PROFILE_EXTENSION`]; frames already written
+//! keep the settings they were put with when a profile changes.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::compression::CompressionCodec;
+use super::options::PutOptions;
+
+/// TOC extension key holding the [`IngestProfileRegistry`].
+pub const INGEST_PROFILE_EXTENSION: &str = "memvid.ingest_profiles";
+
+/// Put settings applied by name; `None` fields leave the put's own value alone.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestProfile {
+    /// Target characters per text chunk.
+    #[serde(default)]
+    pub chunk_chars: Option<usize>,
+    #[serde(default)]
+    pub auto_tag: Option<bool>,
+    /// Extract subject-predicate-object triplets into memory cards.
+    #[serde(default)]
+    pub extract_triplets: Option<bool>,
+    #[serde(default)]
+    pub enable_embedding: Option<bool>,
+    /// Payload codec, e.g. `CompressionCodec::None` for already-compressed media.
+    #[serde(default)]
+    pub compression: Option<CompressionCodec>,
+    #[serde(default)]
+    pub extract_dates: Option<bool>,
+    /// Time zone relative dates resolve in.
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+impl IngestProfile {
+    /// Short chunks for threaded messages, with dates and triplets extracted.
+    #[must_use]
+    pub fn email() -> Self {
+        Self {
+            chunk_chars: Some(800),
+            auto_tag: Some(true),
+            extract_triplets: Some(true),
+            extract_dates: Some(true),
+            ..Self::default()
+        }
+    }
+
+    /// Long chunks for source files, without date or triplet extraction.
+    #[must_use]
+    pub fn code() -> Self {
+        Self {
+            chunk_chars: Some(2400),
+            auto_tag: Some(false),
+            extract_triplets: Some(false),
+            extract_dates: Some(false),
+            ..Self::default()
+        }
+    }
+
+    /// Already-compressed binaries: stored as-is, with text enrichment off.
+    #[must_use]
+    pub fn media() -> Self {
+        Self {
+            auto_tag: Some(false),
+            extract_triplets: Some(false),
+            extract_dates: Some(false),
+            compression: Some(CompressionCodec::None),
+            ..Self::default()
+        }
+    }
+
+    /// Fill `options` from the profile. Settings the put chose itself win: an option the put
+    /// set is kept, and a switch is only changed while it still has its default value.
+    #[must_use]
+    pub fn apply(&self, mut options: PutOptions) -> PutOptions {
+        let defaults = PutOptions::default();
+        options.chunk_chars = options.chunk_chars.or(self.chunk_chars);
+        options.compression = options.compression.or(self.compression);
+        options.timezone = options.timezone.or_else(|| self.timezone.clone());
+        let switches = [
+            (&mut options.auto_tag, defaults.auto_tag, self.auto_tag),
+            (
+                &mut options.extract_triplets,
+                defaults.extract_triplets,
+                self.extract_triplets,
+            ),
+            (
+                &mut options.enable_embedding,
+                defaults.enable_embedding,
+                self.enable_embedding,
+            ),
+            (
+                &mut options.extract_dates,
+                defaults.extract_dates,
+                self.extract_dates,
+            ),
+        ];
+        for (value, default, profile) in switches {
+            if let Some(profile) = profile {
+                if *value == default {
+                    *value = profile;
+                }
+            }
+        }
+        options
+    }
+}
+
+/// Ingest profiles by name.
+pub type IngestProfileRegistry = BTreeMap<String, IngestProfile>;
@@ -0,0 +1,19 @@
+//! Receipt of JSONL ingestion (see `Memvid::put_jsonl`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::reader::JsonlLineError;
+
+/// Extra-metadata key holding the one-based line a frame was read from.
+pub const JSONL_LINE_KEY: &str = "jsonl_line";
+
+/// Frames written by `Memvid::put_jsonl`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonlReceipt {
+    /// One sequence per record, in line order.
+    pub sequences: Vec<u64>,
+    /// Line of each record, parallel to `sequences`.
+    pub lines: Vec<usize>,
+    /// Lines skipped as malformed or empty.
+    pub errors: Vec<JsonlLineError>,
+}
@@ -0,0 +1,91 @@
+//! Legal holds: keeping a memory's committed contents from being changed or removed.
+//!
+//! A hold is stored in the file header, so every handle that opens the file sees it, and
+//! it is set with `Memvid::set_immutable` or `Memvid::freeze`. An append-only hold still
+//! accepts new frames; a frozen file accepts nothing. Either way, deletes, updates, tag
+//! edits, deduplication, and vacuum fail with `MemvidError::LegalHold`, and doctor only
+//! plans.
+
+use serde::{Deserialize, Serialize};
+
+/// What a [`ImmutableHold`] still allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImmutableMode {
+    /// New frames may be added; nothing committed may change.
+    AppendOnly,
+    /// No writes at all.
+    Frozen,
+}
+
+impl ImmutableMode {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AppendOnly => "append-only",
+            Self::Frozen => "frozen",
+        }
+    }
+}
+
+/// A legal hold recorded in the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImmutableHold {
+    pub mode: ImmutableMode,
+    /// Unix seconds the hold lapses at; `None` holds until released.
+    pub until: Option<i64>,
+}
+
+impl ImmutableHold {
+    /// Whether the hold is in force at unix time `now`.
+    #[must_use]
+    pub fn is_active(&self, now: i64) -> bool {
+        self.until.is_none_or(|until| now < until)
+    }
+
+    /// Whether `self`, replacing `current` at `now`, keeps at least everything `current`
+    /// promised: the same or a stricter mode, lasting at least as long.
+    #[must_use]
+    pub fn covers(&self, current: &Self, now: i64) -> bool {
+        if !current.is_active(now) {
+            return true;
+        }
+        let lasts = match (self.until, current.until) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(new), Some(old)) => new >= old,
+        };
+        self.mode >= current.mode && lasts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holds_lapse_and_only_tighten() {
+        let timed = ImmutableHold {
+            mode: ImmutableMode::AppendOnly,
+            until: Some(100),
+        };
+        assert!(timed.is_active(99));
+        assert!(!timed.is_active(100));
+
+        let frozen = ImmutableHold {
+            mode: ImmutableMode::Frozen,
+            until: Some(200),
+        };
+        assert!(frozen.covers(&timed, 50));
+        assert!(!timed.covers(&frozen, 50));
+        let shorter = ImmutableHold {
+            mode: ImmutableMode::Frozen,
+            until: Some(150),
+        };
+        assert!(!shorter.covers(&frozen, 50));
+        assert!(
+            shorter.covers(&frozen, 250),
+            "a lapsed hold can be replaced"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Typed links between frames.
+//!
+//! `parent_id` and supersession give frames one structural relation each; links add the rest:
+//! a reply in an email thread, a translation of a document, a revision derived from another.
+//! Links are directed, stored in the relations track (the TOC extension
+//! [`RELATIONS_EXTENSION`]), and read back from either end with `Memvid::related`.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// TOC extension key holding the [`RelationsTrack`].
+pub const RELATIONS_EXTENSION: &str = "memvid.relations";
+
+/// How the `from` frame of a link relates to its `to` frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// `from` cites or mentions `to`.
+    References,
+    /// `from` is a translation of `to`.
+    Translates,
+    /// `from` answers `to`, e.g. an email reply.
+    ReplyTo,
+    /// `from` was produced from `to`, e.g. a revision or a summary.
+    DerivedFrom,
+}
+
+/// A directed, typed link between two frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FrameLink {
+    pub from: FrameId,
+    pub to: FrameId,
+    pub kind: LinkKind,
+}
+
+/// Every link of a memory, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationsTrack {
+    pub links: Vec<FrameLink>,
+}
+
+/// Which end of a link a frame is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkDirection {
+    /// The frame is the link's `from`.
+    Outgoing,
+    /// The frame is the link's `to`.
+    Incoming,
+}
+
+/// A frame linked to the one asked about, as returned by `Memvid::related` and attached to
+/// search hits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedFrame {
+    /// The frame at the other end of the link.
+    pub frame_id: FrameId,
+    pub kind: LinkKind,
+    pub direction: LinkDirection,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
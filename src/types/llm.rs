@@ -0,0 +1,58 @@
+//! Language model backends for generative `ask`.
+//!
+//! `Memvid::ask_with_llm` retrieves context as usual, packs as many hits as fit into the
+//! backend's context window, and asks the backend to answer with `[n]` citations. Backends
+//! live in `crate::llm` behind the `llm_openai` and `llm_local` features; any other model can
+//! be plugged in by implementing [`LlmBackend`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Sampling parameters for one completion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmParams {
+    /// Upper bound on generated tokens; reserved out of the context window.
+    pub max_tokens: usize,
+    /// `0.0` selects greedy decoding.
+    pub temperature: f32,
+    /// Generation stops before any of these strings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+}
+
+impl Default for LlmParams {
+    fn default() -> Self {
+        Self {
+            max_tokens: 512,
+            temperature: 0.0,
+            stop: Vec::new(),
+        }
+    }
+}
+
+/// Text produced by a backend plus its token accounting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LlmCompletion {
+    pub text: String,
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+}
+
+/// A text-completion model used by `AskMode::Generative`.
+pub trait LlmBackend: Send + Sync {
+    /// Model identifier, e.g. `gpt-4o-mini` or a GGUF file name.
+    fn kind(&self) -> &str;
+
+    /// Total tokens the model accepts, prompt and completion combined.
+    fn context_window(&self) -> usize;
+
+    /// Token count of `text`. The default assumes roughly four bytes per token; backends with
+    /// a tokenizer should count exactly so prompt budgeting is tight.
+    fn count_tokens(&self, text: &str) -> usize {
+        text.len().div_ceil(4)
+    }
+
+    /// Complete `prompt`.
+    fn complete(&self, prompt: &str, params: &LlmParams) -> Result<LlmCompletion>;
+}
@@ -423,6 +423,35 @@ impl LogicMesh {
         Ok(mesh)
     }
 
+    /// Remove every mention of and edge from `frames`, dropping nodes left with no frames.
+    /// Returns how many mentions and edges were removed.
+    pub fn forget_frames(&mut self, frames: &HashSet<FrameId>) -> usize {
+        let mut removed = 0;
+        for node in &mut self.nodes {
+            let before = node.mentions.len();
+            node.mentions
+                .retain(|(frame_id, _, _)| !frames.contains(frame_id));
+            removed += before - node.mentions.len();
+            node.frame_ids.retain(|frame_id| !frames.contains(frame_id));
+        }
+        let dropped: HashSet<u64> = self
+            .nodes
+            .iter()
+            .filter(|node| node.frame_ids.is_empty())
+            .map(|node| node.id)
+            .collect();
+        self.nodes.retain(|node| !dropped.contains(&node.id));
+        let before = self.edges.len();
+        self.edges.retain(|edge| {
+            !frames.contains(&edge.frame_id)
+                && !dropped.contains(&edge.from_node)
+                && !dropped.contains(&edge.to_node)
+        });
+        removed += before - self.edges.len();
+        self.build_adjacency();
+        removed
+    }
+
     /// Build adjacency index from edges.
     pub fn build_adjacency(&mut self) {
         self.adjacency.clear();
@@ -574,6 +603,35 @@ pub fn compute_node_id(canonical_name: &str, kind: EntityKind) -> u64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_forget_frames_drops_orphaned_nodes_and_edges() {
+        let mut mesh = LogicMesh::new();
+        let person = |name: &str, frame_id| {
+            MeshNode::new(
+                name.to_lowercase(),
+                name.to_string(),
+                EntityKind::Person,
+                0.9,
+                frame_id,
+                0,
+                5,
+            )
+        };
+        mesh.merge_node(person("Alice", 1));
+        mesh.merge_node(person("Alice", 2));
+        mesh.merge_node(person("Bob", 1));
+        let alice = compute_node_id("alice", EntityKind::Person);
+        let bob = compute_node_id("bob", EntityKind::Person);
+        mesh.merge_edge(MeshEdge::new(alice, bob, LinkType::Manager, 0.8, 1));
+        mesh.build_adjacency();
+
+        let removed = mesh.forget_frames(&HashSet::from([1]));
+        assert_eq!(removed, 3, "two mentions and one edge");
+        assert_eq!(mesh.nodes.len(), 1);
+        assert_eq!(mesh.nodes[0].frame_ids, vec![2]);
+        assert!(mesh.edges.is_empty());
+    }
+
     #[test]
     fn test_mesh_roundtrip() {
         let mut mesh = LogicMesh::new();
@@ -6,7 +6,7 @@ use serde::{
     ser::SerializeStruct,
 };
 
-use super::{common::FrameId, frame::Frame, ticket::TicketRef};
+use super::{common::FrameId, frame::Frame, legal_hold::ImmutableHold, ticket::TicketRef};
 
 use std::{fmt, marker::PhantomData};
 
@@ -704,7 +704,9 @@ pub struct LexSegmentManifest {
 pub enum VectorCompression {
     #[default]
     None, // Full f32 vectors (1,536 bytes for 384 dims)
-    Pq96, // Product quantization with 96 subspaces (96 bytes)
+    Pq96,   // Product quantization with 96 subspaces (96 bytes)
+    Int8,   // Symmetric int8 scalar quantization (1 byte per dimension)
+    Binary, // Sign bits packed 8 per byte, searched by hamming distance
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -723,7 +725,7 @@ pub struct VecIndexManifest {
     pub model: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum SegmentCompression {
     #[default]
     None,
@@ -751,6 +753,29 @@ pub struct Header {
     pub wal_checkpoint_pos: u64,
     pub wal_sequence: u64,
     pub toc_checksum: [u8; 32],
+    /// Legal hold set with `Memvid::set_immutable` or `Memvid::freeze`, kept even after it
+    /// lapses.
+    #[serde(default)]
+    pub immutable: Option<ImmutableHold>,
+    /// First byte of the data region: the end of the WAL slot reserved at creation. It stays put
+    /// when the WAL grows, since growth relocates the WAL to an extent past the data instead of
+    /// shifting the data.
+    #[serde(default)]
+    pub data_start: u64,
+}
+
+impl Header {
+    /// End of the active WAL region.
+    #[must_use]
+    pub fn wal_end(&self) -> u64 {
+        self.wal_offset.saturating_add(self.wal_size)
+    }
+
+    /// Whether the WAL lives in an extent inside the data region rather than its original slot.
+    #[must_use]
+    pub fn wal_relocated(&self) -> bool {
+        self.wal_offset >= self.data_start
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -788,6 +813,11 @@ pub struct Toc {
     /// Tracks frames needing background Phase 2 work (full extraction + embeddings).
     #[serde(default)]
     pub enrichment_queue: EnrichmentQueueManifest,
+    /// Keyed extension blobs for newer tracks and manifests.
+    /// Each value is an independently encoded payload, so new keys do not change the TOC layout
+    /// and readers skip keys they do not understand. Access via `Toc::extension`.
+    #[serde(default)]
+    pub extensions: std::collections::BTreeMap<String, Vec<u8>>,
     pub merkle_root: [u8; 32],
     pub toc_checksum: [u8; 32],
 }
@@ -4,13 +4,17 @@
 //! extracted memory cards along with indices for fast lookup and enrichment
 //! tracking metadata.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{MemvidError, Result};
-use crate::types::FrameId;
+use crate::types::importance::ImportanceTable;
 use crate::types::memory_card::{MemoryCard, MemoryCardId, MemoryKind, Polarity, VersionRelation};
+use crate::types::{
+    EnrichmentStage, FrameId, MemoryQuery, MemoryQueryHit, MemoryQueryResult, MemorySortOrder,
+    SchemaViolationLog,
+};
 
 /// Magic bytes identifying the memories track.
 pub const MEMORIES_TRACK_MAGIC: &[u8; 4] = b"MVMC";
@@ -127,6 +131,9 @@ pub struct EnrichmentRecord {
     pub frame_id: FrameId,
     /// Stamps for each engine that processed this frame.
     pub stamps: Vec<EngineStamp>,
+    /// Background enrichment stages completed for this frame, in completion order.
+    #[serde(default)]
+    pub stages: Vec<EnrichmentStage>,
 }
 
 /// Stamp recording when an engine processed a frame.
@@ -142,6 +149,30 @@ pub struct EngineStamp {
     pub card_ids: Vec<MemoryCardId>,
 }
 
+/// How two cards for the same entity and slot disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Different values for a single-valued slot ("prefers dark mode" vs "prefers light mode").
+    ValueChanged,
+    /// Same value with opposite polarity ("likes coffee" vs "dislikes coffee").
+    PolarityFlip,
+}
+
+/// A contradiction between two cards; the older card is marked superseded by the newer one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardConflict {
+    /// The card that lost, now `superseded_by` the newer card.
+    pub older: MemoryCardId,
+    /// The card whose value is current.
+    pub newer: MemoryCardId,
+    pub entity: String,
+    pub slot: String,
+    pub kind: ConflictKind,
+    /// Unix timestamp when the conflict was detected.
+    pub detected_at: i64,
+}
+
 /// Enrichment manifest tracking which frames have been processed.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct EnrichmentManifest {
@@ -215,17 +246,47 @@ impl EnrichmentManifest {
             card_ids: card_ids.clone(),
         };
 
-        let record = self.frames.entry(frame_id).or_insert_with(|| {
+        let record = self.record_mut(frame_id);
+        let first_stamp = record.stamps.is_empty();
+        record.stamps.push(stamp.clone());
+
+        if first_stamp {
             self.total_frames_enriched += 1;
-            EnrichmentRecord {
+        }
+        self.total_cards_created += card_ids.len();
+        self.last_enrichment = Some(stamp.enriched_at);
+    }
+
+    fn record_mut(&mut self, frame_id: FrameId) -> &mut EnrichmentRecord {
+        self.frames
+            .entry(frame_id)
+            .or_insert_with(|| EnrichmentRecord {
                 frame_id,
                 stamps: Vec::new(),
-            }
-        });
+                stages: Vec::new(),
+            })
+    }
 
-        self.total_cards_created += card_ids.len();
-        self.last_enrichment = Some(stamp.enriched_at);
-        record.stamps.push(stamp);
+    /// Record that a background enrichment stage finished for a frame.
+    pub fn record_stage(&mut self, frame_id: FrameId, stage: EnrichmentStage) {
+        let record = self.record_mut(frame_id);
+        if !record.stages.contains(&stage) {
+            record.stages.push(stage);
+        }
+    }
+
+    /// Check if a background enrichment stage has finished for a frame.
+    #[must_use]
+    pub fn has_completed_stage(&self, frame_id: FrameId, stage: EnrichmentStage) -> bool {
+        self.frames
+            .get(&frame_id)
+            .is_some_and(|record| record.stages.contains(&stage))
+    }
+
+    /// Check if no frame has a record.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
     }
 
     /// Get the enrichment record for a frame.
@@ -234,6 +295,15 @@ impl EnrichmentManifest {
         self.frames.get(&frame_id)
     }
 
+    /// Find the stamp of the engine run that produced `card_id`.
+    #[must_use]
+    pub fn stamp_for_card(&self, card_id: MemoryCardId) -> Option<&EngineStamp> {
+        self.frames
+            .values()
+            .flat_map(|record| &record.stamps)
+            .find(|stamp| stamp.card_ids.contains(&card_id))
+    }
+
     /// Get all enriched frame IDs.
     #[must_use]
     pub fn enriched_frames(&self) -> Vec<FrameId> {
@@ -263,6 +333,15 @@ pub struct MemoriesTrack {
     slot_index: SlotIndex,
     /// Enrichment tracking.
     enrichment_manifest: EnrichmentManifest,
+    /// Contradictions found between cards, oldest first.
+    #[serde(default)]
+    conflicts: Vec<CardConflict>,
+    /// Access, pin, and demotion state for cards and frames.
+    #[serde(default)]
+    importance: ImportanceTable,
+    /// Cards that failed schema validation, by predicate.
+    #[serde(default)]
+    schema_violations: SchemaViolationLog,
 }
 
 impl MemoriesTrack {
@@ -288,6 +367,143 @@ impl MemoriesTrack {
         id
     }
 
+    /// Link `card_id` to the live cards of its entity and slot that it contradicts.
+    ///
+    /// Of each conflicting pair, the card with the earlier effective timestamp is marked
+    /// superseded by the other. Cards that extend or retract a slot are explicit about how they
+    /// relate to earlier values and are never reported. When `multi_valued` is set (see
+    /// `Cardinality::Multiple`) only polarity flips count as conflicts.
+    pub fn detect_conflicts(
+        &mut self,
+        card_id: MemoryCardId,
+        multi_valued: bool,
+        detected_at: i64,
+    ) -> Vec<CardConflict> {
+        let Some(card) = self.get_card(card_id).cloned() else {
+            return Vec::new();
+        };
+        if card.is_superseded()
+            || card.is_retracted()
+            || card.version_relation == VersionRelation::Extends
+        {
+            return Vec::new();
+        }
+        let rivals: Vec<(MemoryCardId, ConflictKind)> = self
+            .get_cards(&card.entity, &card.slot)
+            .into_iter()
+            .filter(|other| {
+                other.id != card.id
+                    && other.version_key == card.version_key
+                    && !other.is_superseded()
+                    && !other.is_retracted()
+                    && other.version_relation != VersionRelation::Extends
+            })
+            .filter_map(|other| {
+                conflict_kind(&card, other, multi_valued).map(|kind| (other.id, kind))
+            })
+            .collect();
+
+        let mut found = Vec::new();
+        for (rival_id, kind) in rivals {
+            let Some(rival) = self.get_card(rival_id) else {
+                continue;
+            };
+            let (older, newer) = if (rival.effective_timestamp(), rival.id)
+                < (card.effective_timestamp(), card.id)
+            {
+                (rival_id, card.id)
+            } else {
+                (card.id, rival_id)
+            };
+            if let Some(loser) = self.cards.iter_mut().find(|c| c.id == older) {
+                loser.superseded_by = Some(newer);
+            }
+            let conflict = CardConflict {
+                older,
+                newer,
+                entity: card.entity.clone(),
+                slot: card.slot.clone(),
+                kind,
+                detected_at,
+            };
+            self.conflicts.push(conflict.clone());
+            found.push(conflict);
+        }
+        found
+    }
+
+    /// Contradictions recorded by [`MemoriesTrack::detect_conflicts`], oldest first.
+    #[must_use]
+    pub fn conflicts(&self) -> &[CardConflict] {
+        &self.conflicts
+    }
+
+    /// Whether there is nothing to persist: no cards, importance records, enrichment records,
+    /// or schema violations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+            && self.importance.is_empty()
+            && self.enrichment_manifest.is_empty()
+            && self.schema_violations.is_empty()
+    }
+
+    /// Cards rejected or flagged by schema validation.
+    #[must_use]
+    pub fn schema_violations(&self) -> &SchemaViolationLog {
+        &self.schema_violations
+    }
+
+    /// Mutable access to the schema violation log.
+    pub fn schema_violations_mut(&mut self) -> &mut SchemaViolationLog {
+        &mut self.schema_violations
+    }
+
+    /// Importance records for cards and frames.
+    #[must_use]
+    pub fn importance(&self) -> &ImportanceTable {
+        &self.importance
+    }
+
+    /// Mutable access to the importance records.
+    pub fn importance_mut(&mut self) -> &mut ImportanceTable {
+        &mut self.importance
+    }
+
+    /// Remove every card extracted from `frames`, with the enrichment records, conflicts, and
+    /// schema violation samples that mention them. Returns how many cards were removed.
+    pub fn forget_frames(&mut self, frames: &HashSet<FrameId>) -> usize {
+        let removed: HashSet<MemoryCardId> = self
+            .cards
+            .iter()
+            .filter(|card| frames.contains(&card.source_frame_id))
+            .map(|card| card.id)
+            .collect();
+        self.cards
+            .retain(|card| !frames.contains(&card.source_frame_id));
+        for card in &mut self.cards {
+            if card.superseded_by.is_some_and(|id| removed.contains(&id)) {
+                card.superseded_by = None;
+            }
+        }
+        self.conflicts.retain(|conflict| {
+            !removed.contains(&conflict.older) && !removed.contains(&conflict.newer)
+        });
+        self.slot_index.clear();
+        for card in &self.cards {
+            self.slot_index.insert(card);
+        }
+        self.enrichment_manifest
+            .frames
+            .retain(|frame_id, _| !frames.contains(frame_id));
+        for violations in self.schema_violations.predicates.values_mut() {
+            violations
+                .samples
+                .retain(|sample| !frames.contains(&sample.source_frame_id));
+        }
+        removed.len()
+    }
+
     /// Add multiple cards at once.
     pub fn add_cards(&mut self, cards: Vec<MemoryCard>) -> Vec<MemoryCardId> {
         cards.into_iter().map(|c| self.add_card(c)).collect()
@@ -394,6 +610,76 @@ impl MemoriesTrack {
         cards.into_iter().find(|c| !c.is_retracted())
     }
 
+    /// Run a filtered, sorted, paginated query; see [`MemoryQuery`].
+    ///
+    /// Entity and slot filters are answered from the slot index, so only their cards are
+    /// examined.
+    #[must_use]
+    pub fn query(&self, query: &MemoryQuery) -> MemoryQueryResult {
+        let candidates: Vec<&MemoryCard> = match (&query.entity, &query.slot) {
+            (Some(entity), Some(slot)) => self.get_cards(entity, slot),
+            (Some(entity), None) => self.get_entity_cards(entity),
+            (None, Some(slot)) => self
+                .cards
+                .iter()
+                .filter(|card| card.slot.eq_ignore_ascii_case(slot))
+                .collect(),
+            (None, None) => self.cards.iter().collect(),
+        };
+        let mut matched: Vec<&MemoryCard> = candidates
+            .into_iter()
+            .filter(|card| query.matches(card))
+            .collect();
+
+        match query.sort {
+            MemorySortOrder::Id => matched.sort_by_key(|card| card.id),
+            MemorySortOrder::Newest => {
+                matched
+                    .sort_by_key(|card| (std::cmp::Reverse(card.effective_timestamp()), card.id));
+            }
+            MemorySortOrder::Oldest => {
+                matched.sort_by_key(|card| (card.effective_timestamp(), card.id));
+            }
+            MemorySortOrder::Confidence => matched.sort_by(|a, b| {
+                b.confidence
+                    .unwrap_or(1.0)
+                    .total_cmp(&a.confidence.unwrap_or(1.0))
+                    .then(a.id.cmp(&b.id))
+            }),
+        }
+
+        let total = matched.len();
+        let end = query.offset.saturating_add(query.limit).min(total);
+        let hits = matched
+            .get(query.offset..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|card| MemoryQueryHit {
+                card: (*card).clone(),
+                frame_ids: self.supporting_frames(card),
+            })
+            .collect();
+        MemoryQueryResult {
+            hits,
+            total,
+            next_offset: (end < total).then_some(end),
+        }
+    }
+
+    /// Source frames of `card` and of every card stating the same entity, slot, and value.
+    fn supporting_frames(&self, card: &MemoryCard) -> Vec<FrameId> {
+        let mut frames: Vec<FrameId> = self
+            .get_cards(&card.entity, &card.slot)
+            .into_iter()
+            .filter(|other| other.value == card.value)
+            .map(|other| other.source_frame_id)
+            .chain(std::iter::once(card.source_frame_id))
+            .collect();
+        frames.sort_unstable();
+        frames.dedup();
+        frames
+    }
+
     /// Get all cards for an entity.
     #[must_use]
     pub fn get_entity_cards(&self, entity: &str) -> Vec<&MemoryCard> {
@@ -565,7 +851,23 @@ impl MemoriesTrack {
         self.next_id = 0;
         self.slot_index.clear();
         self.enrichment_manifest.clear();
+        self.conflicts.clear();
+        self.importance = ImportanceTable::default();
+    }
+}
+
+/// Whether `a` and `b` (same entity and slot) contradict each other.
+fn conflict_kind(a: &MemoryCard, b: &MemoryCard, multi_valued: bool) -> Option<ConflictKind> {
+    let opposed = matches!(
+        (a.polarity, b.polarity),
+        (Some(Polarity::Positive), Some(Polarity::Negative))
+            | (Some(Polarity::Negative), Some(Polarity::Positive))
+    );
+    if a.value.trim().eq_ignore_ascii_case(b.value.trim()) {
+        return opposed.then_some(ConflictKind::PolarityFlip);
     }
+    // "likes tea" and "dislikes coffee" can both hold.
+    (!multi_valued && !opposed).then_some(ConflictKind::ValueChanged)
 }
 
 /// Statistics about the memories track.
@@ -837,4 +1139,106 @@ mod tests {
         assert_eq!(timeline[1].value, "moved to SF");
         assert_eq!(timeline[2].value, "got promoted");
     }
+
+    #[test]
+    fn test_detect_conflicts() {
+        let mut track = MemoriesTrack::new();
+        let theme = |value: &str, ts: i64| {
+            MemoryCardBuilder::new()
+                .preference()
+                .entity("user")
+                .slot("ui_theme")
+                .value(value)
+                .positive()
+                .document_date(ts)
+                .source(1, None)
+                .engine("rules-v1", "1.0.0")
+                .build(0)
+                .unwrap()
+        };
+
+        let dark = track.add_card(theme("dark mode", 1000));
+        assert!(track.detect_conflicts(dark, false, 0).is_empty());
+        let light = track.add_card(theme("light mode", 2000));
+        let conflicts = track.detect_conflicts(light, false, 0);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!((conflicts[0].older, conflicts[0].newer), (dark, light));
+        assert_eq!(conflicts[0].kind, ConflictKind::ValueChanged);
+        assert_eq!(track.get_card(dark).unwrap().superseded_by, Some(light));
+
+        // Multi-valued slots only conflict on a polarity flip.
+        let mut hated = theme("light mode", 3000);
+        hated.polarity = Some(Polarity::Negative);
+        let hated = track.add_card(hated);
+        let sepia = track.add_card(theme("sepia", 4000));
+        assert!(track.detect_conflicts(sepia, true, 0).is_empty());
+        let flip = track.detect_conflicts(hated, true, 0);
+        assert_eq!(flip[0].kind, ConflictKind::PolarityFlip);
+        assert_eq!(track.get_card(light).unwrap().superseded_by, Some(hated));
+        assert_eq!(track.conflicts().len(), 2);
+    }
+    #[test]
+    fn test_query_filters_sorts_and_paginates() {
+        let mut track = MemoriesTrack::new();
+        let card =
+            |entity: &str, slot: &str, value: &str, frame: FrameId, date: i64, confidence: f32| {
+                MemoryCardBuilder::new()
+                    .fact()
+                    .entity(entity)
+                    .slot(slot)
+                    .value(value)
+                    .source(frame, None)
+                    .engine("rules-v1", "1.0.0")
+                    .document_date(date)
+                    .confidence(confidence)
+                    .build(0)
+                    .unwrap()
+            };
+        track.add_cards(vec![
+            card("alice", "hobby", "chess", 1, 100, 0.9),
+            card("alice", "hobby", "sailing", 2, 200, 0.5),
+            card("bob", "hobby", "chess", 3, 300, 0.7),
+            card("alice", "city", "Paris", 4, 400, 0.8),
+            card("alice", "hobby", "chess", 5, 500, 0.6),
+        ]);
+
+        let page = track.query(&MemoryQuery {
+            entity: Some("Alice".to_string()),
+            slot: Some("hobby".to_string()),
+            sort: MemorySortOrder::Newest,
+            limit: 2,
+            ..MemoryQuery::default()
+        });
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_offset, Some(2));
+        let values: Vec<_> = page.hits.iter().map(|h| h.card.value.as_str()).collect();
+        assert_eq!(values, ["chess", "sailing"]);
+        assert_eq!(page.hits[0].frame_ids, [1, 5]);
+
+        let rest = track.query(&MemoryQuery {
+            entity: Some("alice".to_string()),
+            slot: Some("hobby".to_string()),
+            sort: MemorySortOrder::Newest,
+            offset: 2,
+            limit: 2,
+            ..MemoryQuery::default()
+        });
+        assert_eq!(rest.hits.len(), 1);
+        assert_eq!(rest.next_offset, None);
+
+        let confident = track.query(&MemoryQuery {
+            slot: Some("hobby".to_string()),
+            min_confidence: Some(0.65),
+            since: Some(150),
+            sort: MemorySortOrder::Confidence,
+            ..MemoryQuery::default()
+        });
+        let entities: Vec<_> = confident
+            .hits
+            .iter()
+            .map(|h| h.card.entity.as_str())
+            .collect();
+        assert_eq!(entities, ["bob"]);
+        assert_eq!(track.query(&MemoryQuery::default()).total, 5);
+    }
 }
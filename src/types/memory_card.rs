@@ -234,6 +234,10 @@ pub struct MemoryCard {
 
     /// When this card was created (Unix timestamp).
     pub created_at: i64,
+
+    /// Newer card that contradicted this one (see `MemoriesTrack::detect_conflicts`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<MemoryCardId>,
 }
 
 impl MemoryCard {
@@ -284,6 +288,12 @@ impl MemoryCard {
     pub fn is_retracted(&self) -> bool {
         self.version_relation == VersionRelation::Retracts
     }
+
+    /// Check if a newer, contradicting card has replaced this one.
+    #[must_use]
+    pub fn is_superseded(&self) -> bool {
+        self.superseded_by.is_some()
+    }
 }
 
 /// Builder for constructing `MemoryCards`.
@@ -521,6 +531,7 @@ impl MemoryCardBuilder {
             engine_version,
             confidence: self.confidence,
             created_at,
+            superseded_by: None,
         })
     }
 }
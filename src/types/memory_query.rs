@@ -0,0 +1,105 @@
+//! Filtered, paginated access to memory cards (see `Memvid::memories_query`).
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::memory_card::{MemoryCard, MemoryKind, Polarity};
+
+/// Default page size of a [`MemoryQuery`].
+pub const DEFAULT_MEMORY_QUERY_LIMIT: usize = 100;
+
+/// Order of the cards returned by [`MemoryQuery`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemorySortOrder {
+    /// Insertion order.
+    #[default]
+    Id,
+    /// Latest effective time first (event date, else document date, else creation).
+    Newest,
+    /// Earliest effective time first.
+    Oldest,
+    /// Most confident first.
+    Confidence,
+}
+
+/// Filters, order, and page of a memory card query. Unset filters match every card.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryQuery {
+    /// Entity to match, case-insensitively.
+    pub entity: Option<String>,
+    /// Slot to match, case-insensitively.
+    pub slot: Option<String>,
+    pub kind: Option<MemoryKind>,
+    /// Cards without a polarity count as [`Polarity::Neutral`].
+    pub polarity: Option<Polarity>,
+    /// Cards without a confidence come from deterministic engines and count as `1.0`.
+    pub min_confidence: Option<f32>,
+    pub max_confidence: Option<f32>,
+    /// Inclusive lower bound on the card's effective time (Unix seconds).
+    pub since: Option<i64>,
+    /// Inclusive upper bound on the card's effective time (Unix seconds).
+    pub until: Option<i64>,
+    /// Also return cards superseded by a contradicting card, and retractions.
+    pub include_superseded: bool,
+    pub sort: MemorySortOrder,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for MemoryQuery {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            slot: None,
+            kind: None,
+            polarity: None,
+            min_confidence: None,
+            max_confidence: None,
+            since: None,
+            until: None,
+            include_superseded: false,
+            sort: MemorySortOrder::default(),
+            offset: 0,
+            limit: DEFAULT_MEMORY_QUERY_LIMIT,
+        }
+    }
+}
+
+impl MemoryQuery {
+    /// Check whether `card` passes every filter, ignoring entity and slot, which callers
+    /// resolve through the slot index.
+    #[must_use]
+    pub fn matches(&self, card: &MemoryCard) -> bool {
+        let confidence = card.confidence.unwrap_or(1.0);
+        let time = card.effective_timestamp();
+        (self.include_superseded || !(card.is_superseded() || card.is_retracted()))
+            && self.kind.is_none_or(|kind| card.kind == kind)
+            && self
+                .polarity
+                .is_none_or(|polarity| card.polarity.unwrap_or(Polarity::Neutral) == polarity)
+            && self.min_confidence.is_none_or(|min| confidence >= min)
+            && self.max_confidence.is_none_or(|max| confidence <= max)
+            && self.since.is_none_or(|since| time >= since)
+            && self.until.is_none_or(|until| time <= until)
+    }
+}
+
+/// A matching card and the frames that support it: its own source frame and those of
+/// other cards stating the same entity, slot, and value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryQueryHit {
+    pub card: MemoryCard,
+    pub frame_ids: Vec<FrameId>,
+}
+
+/// One page of a memory card query.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryQueryResult {
+    pub hits: Vec<MemoryQueryHit>,
+    /// Cards matching the filters across all pages.
+    pub total: usize,
+    /// Offset of the next page, if there is one.
+    pub next_offset: Option<usize>,
+}
@@ -0,0 +1,464 @@
+//! Typed frame metadata and comparison filters.
+//!
+//! Frames keep metadata as strings in `extra_metadata`; a [`MetaValue`] is encoded into that
+//! map on write and parsed back on read. The [`MetaSchema`] persisted with the memory records
+//! the declared type of every typed key and which keys are indexed, so filters such as
+//! `meta.priority >= 3` compare numerically rather than lexically.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Month, OffsetDateTime};
+
+use super::common::FrameId;
+use super::frame::Frame;
+
+/// TOC extension key holding the memory's [`MetaSchema`].
+pub const META_SCHEMA_EXTENSION: &str = "memvid.meta_schema";
+
+/// Value type of a metadata key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetaType {
+    Int,
+    Float,
+    /// Unix seconds, encoded as RFC 3339.
+    Date,
+    Bool,
+    Text,
+}
+
+impl MetaType {
+    fn is_numeric(self) -> bool {
+        matches!(self, Self::Int | Self::Float)
+    }
+}
+
+impl fmt::Display for MetaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Date => "date",
+            Self::Bool => "bool",
+            Self::Text => "text",
+        })
+    }
+}
+
+/// A typed metadata value.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum MetaValue {
+    Int(i64),
+    Float(f64),
+    /// Unix seconds.
+    Date(i64),
+    Bool(bool),
+    Text(String),
+}
+
+impl MetaValue {
+    #[must_use]
+    pub fn ty(&self) -> MetaType {
+        match self {
+            Self::Int(_) => MetaType::Int,
+            Self::Float(_) => MetaType::Float,
+            Self::Date(_) => MetaType::Date,
+            Self::Bool(_) => MetaType::Bool,
+            Self::Text(_) => MetaType::Text,
+        }
+    }
+
+    /// String form stored in `extra_metadata`.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        match self {
+            Self::Int(value) => value.to_string(),
+            Self::Date(value) => OffsetDateTime::from_unix_timestamp(*value)
+                .ok()
+                .and_then(|dt| dt.format(&Rfc3339).ok())
+                .unwrap_or_else(|| value.to_string()),
+            Self::Float(value) => value.to_string(),
+            Self::Bool(value) => value.to_string(),
+            Self::Text(value) => value.clone(),
+        }
+    }
+
+    /// Parse `raw` as a value of type `ty`.
+    #[must_use]
+    pub fn parse(ty: MetaType, raw: &str) -> Option<Self> {
+        let trimmed = raw.trim();
+        match ty {
+            MetaType::Int => trimmed.parse().ok().map(Self::Int),
+            MetaType::Float => trimmed
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(Self::Float),
+            MetaType::Date => parse_date(trimmed).map(Self::Date),
+            MetaType::Bool => match trimmed.to_ascii_lowercase().as_str() {
+                "true" | "yes" => Some(Self::Bool(true)),
+                "false" | "no" => Some(Self::Bool(false)),
+                _ => None,
+            },
+            MetaType::Text => Some(Self::Text(raw.to_string())),
+        }
+    }
+
+    /// Most specific type `raw` parses as: bool, int, float, date, then text.
+    #[must_use]
+    pub fn infer(raw: &str) -> Self {
+        [
+            MetaType::Bool,
+            MetaType::Int,
+            MetaType::Float,
+            MetaType::Date,
+        ]
+        .into_iter()
+        .find_map(|ty| Self::parse(ty, raw))
+        .unwrap_or_else(|| Self::Text(raw.to_string()))
+    }
+
+    /// Ordering between two values; ints and floats compare numerically, text
+    /// case-insensitively. `None` when the types are not comparable.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) | (Self::Date(a), Self::Date(b)) => Some(a.cmp(b)),
+            (Self::Float(a), Self::Float(b)) => Some(a.total_cmp(b)),
+            (Self::Int(a), Self::Float(b)) => Some((*a as f64).total_cmp(b)),
+            (Self::Float(a), Self::Int(b)) => Some(a.total_cmp(&(*b as f64))),
+            (Self::Bool(a), Self::Bool(b)) => Some(a.cmp(b)),
+            (Self::Text(a), Self::Text(b)) => {
+                Some(a.to_lowercase().trim().cmp(b.to_lowercase().trim()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for MetaValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<i32> for MetaValue {
+    fn from(value: i32) -> Self {
+        Self::Int(i64::from(value))
+    }
+}
+
+impl From<f64> for MetaValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for MetaValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for MetaValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for MetaValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+fn parse_date(raw: &str) -> Option<i64> {
+    if let Ok(dt) = OffsetDateTime::parse(raw, &Rfc3339) {
+        return Some(dt.unix_timestamp());
+    }
+    let mut parts = raw.splitn(3, '-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    let month: u8 = parts.next()?.parse().ok()?;
+    let day: u8 = parts.next()?.parse().ok()?;
+    let date = Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()?;
+    Some(date.midnight().assume_utc().unix_timestamp())
+}
+
+/// Comparison operator of a [`MetaFilter`].
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetaOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl MetaOp {
+    /// Operator spelled as in query syntax (`=`, `==`, `:`, `!=`, `<`, `<=`, `>`, `>=`).
+    #[must_use]
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "=" | "==" | ":" => Some(Self::Eq),
+            "!=" => Some(Self::Ne),
+            "<" => Some(Self::Lt),
+            "<=" => Some(Self::Le),
+            ">" => Some(Self::Gt),
+            ">=" => Some(Self::Ge),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn accepts(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering == Ordering::Equal,
+            Self::Ne => ordering != Ordering::Equal,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Le => ordering != Ordering::Greater,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// `meta.<key> <op> <value>`: compares a frame's metadata value against `value`.
+///
+/// Frames without the key, or whose value does not parse as a comparable type, never match,
+/// including under [`MetaOp::Ne`].
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaFilter {
+    pub key: String,
+    pub op: MetaOp,
+    pub value: MetaValue,
+}
+
+impl MetaFilter {
+    #[must_use]
+    pub fn new(key: impl Into<String>, op: MetaOp, value: impl Into<MetaValue>) -> Self {
+        Self {
+            key: key.into(),
+            op,
+            value: value.into(),
+        }
+    }
+
+    /// Whether a stored value satisfies the filter. `declared` is the key's schema type;
+    /// without one the stored value is read as the filter value's type.
+    #[must_use]
+    pub fn matches(&self, raw: Option<&str>, declared: Option<MetaType>) -> bool {
+        let Some(raw) = raw else {
+            return false;
+        };
+        let ty = declared.unwrap_or_else(|| self.value.ty());
+        let stored = MetaValue::parse(ty, raw).or_else(|| {
+            // An int filter still compares against a fractional value.
+            (ty == MetaType::Int)
+                .then(|| MetaValue::parse(MetaType::Float, raw))
+                .flatten()
+        });
+        stored
+            .and_then(|stored| stored.compare(&self.value))
+            .is_some_and(|ordering| self.op.accepts(ordering))
+    }
+
+    /// Whether `frame` satisfies the filter.
+    #[must_use]
+    pub fn matches_frame(&self, frame: &Frame, schema: &MetaSchema) -> bool {
+        self.matches(
+            frame.extra_metadata.get(&self.key).map(String::as_str),
+            schema.ty(&self.key),
+        )
+    }
+}
+
+/// Declared type and index flag of one metadata key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaField {
+    pub ty: MetaType,
+    #[serde(default)]
+    pub indexed: bool,
+}
+
+/// Declared metadata keys of a memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaSchema {
+    pub fields: BTreeMap<String, MetaField>,
+}
+
+impl MetaSchema {
+    #[must_use]
+    pub fn ty(&self, key: &str) -> Option<MetaType> {
+        self.fields.get(key).map(|field| field.ty)
+    }
+
+    #[must_use]
+    pub fn is_indexed(&self, key: &str) -> bool {
+        self.fields.get(key).is_some_and(|field| field.indexed)
+    }
+
+    /// Record `key` as `ty`, returning whether the schema changed. A key keeps its first
+    /// declared type; an int key widens to float, anything else is a conflict.
+    pub fn declare(&mut self, key: &str, ty: MetaType) -> Result<bool, MetaType> {
+        match self.fields.get_mut(key) {
+            None => {
+                self.fields
+                    .insert(key.to_string(), MetaField { ty, indexed: false });
+                Ok(true)
+            }
+            Some(field) if field.ty == ty => Ok(false),
+            Some(field) if field.ty == MetaType::Float && ty == MetaType::Int => Ok(false),
+            Some(field) if field.ty == MetaType::Int && ty == MetaType::Float => {
+                field.ty = MetaType::Float;
+                Ok(true)
+            }
+            Some(field) => Err(field.ty),
+        }
+    }
+}
+
+/// Values of one indexed key, sorted for range lookups.
+#[derive(Debug, Clone)]
+pub struct MetaIndex {
+    ty: MetaType,
+    entries: Vec<(MetaValue, FrameId)>,
+}
+
+impl MetaIndex {
+    #[must_use]
+    pub fn new(ty: MetaType) -> Self {
+        Self {
+            ty,
+            entries: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn ty(&self) -> MetaType {
+        self.ty
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Index `raw` for `frame_id`; values that do not parse as the key's type are skipped.
+    pub fn insert(&mut self, frame_id: FrameId, raw: &str) {
+        let Some(value) = MetaValue::parse(self.ty, raw) else {
+            return;
+        };
+        let position = self
+            .entries
+            .partition_point(|(existing, _)| existing.compare(&value) != Some(Ordering::Greater));
+        self.entries.insert(position, (value, frame_id));
+    }
+
+    /// Frames whose value satisfies `filter`, in ascending id order. `None` when the filter
+    /// value cannot be ordered against this key's type.
+    #[must_use]
+    pub fn query(&self, filter: &MetaFilter) -> Option<Vec<FrameId>> {
+        let comparable = self.ty == filter.value.ty()
+            || (self.ty.is_numeric() && filter.value.ty().is_numeric());
+        if !comparable {
+            return None;
+        }
+        let cmp = |value: &MetaValue| value.compare(&filter.value).unwrap_or(Ordering::Less);
+        let lower = self
+            .entries
+            .partition_point(|(value, _)| cmp(value) == Ordering::Less);
+        let upper = self
+            .entries
+            .partition_point(|(value, _)| cmp(value) != Ordering::Greater);
+        let ranges = match filter.op {
+            MetaOp::Eq => [lower..upper, 0..0],
+            MetaOp::Ne => [0..lower, upper..self.entries.len()],
+            MetaOp::Lt => [0..lower, 0..0],
+            MetaOp::Le => [0..upper, 0..0],
+            MetaOp::Gt => [upper..self.entries.len(), 0..0],
+            MetaOp::Ge => [lower..self.entries.len(), 0..0],
+        };
+        let mut ids: Vec<FrameId> = ranges
+            .into_iter()
+            .flat_map(|range| &self.entries[range])
+            .map(|(_, frame_id)| *frame_id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        Some(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_compare_by_type_and_index_agrees() {
+        assert_eq!(MetaValue::infer("3"), MetaValue::Int(3));
+        assert_eq!(MetaValue::infer("2.5"), MetaValue::Float(2.5));
+        assert_eq!(MetaValue::infer("TRUE"), MetaValue::Bool(true));
+        assert_eq!(
+            MetaValue::infer("2024-06-01"),
+            MetaValue::Date(1_717_200_000)
+        );
+        assert_eq!(MetaValue::infer("done"), MetaValue::Text("done".into()));
+        assert_eq!(
+            MetaValue::Date(1_717_200_000).encode(),
+            "2024-06-01T00:00:00Z"
+        );
+
+        // Numeric, not lexical: "10" > "3".
+        let at_least_three = MetaFilter::new("priority", MetaOp::Ge, 3);
+        assert!(at_least_three.matches(Some("10"), Some(MetaType::Int)));
+        assert!(!at_least_three.matches(Some("2"), None));
+        assert!(at_least_three.matches(Some("3.5"), None));
+        assert!(!at_least_three.matches(None, None));
+
+        let not_done = MetaFilter::new("status", MetaOp::Ne, "done");
+        assert!(not_done.matches(Some("open"), None));
+        assert!(!not_done.matches(Some("Done"), None));
+        assert!(!not_done.matches(None, None));
+
+        let mut index = MetaIndex::new(MetaType::Int);
+        for (frame_id, raw) in [(0, "5"), (1, "1"), (2, "3"), (3, "oops"), (4, "3")] {
+            index.insert(frame_id, raw);
+        }
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.query(&at_least_three), Some(vec![0, 2, 4]));
+        assert_eq!(
+            index.query(&MetaFilter::new("priority", MetaOp::Ne, 3)),
+            Some(vec![0, 1])
+        );
+        assert_eq!(
+            index.query(&MetaFilter::new("priority", MetaOp::Lt, 2.5)),
+            Some(vec![1])
+        );
+        assert_eq!(index.query(&not_done), None);
+
+        let mut schema = MetaSchema::default();
+        assert_eq!(schema.declare("priority", MetaType::Int), Ok(true));
+        assert_eq!(schema.declare("priority", MetaType::Float), Ok(true));
+        assert_eq!(schema.declare("priority", MetaType::Int), Ok(false));
+        assert_eq!(
+            schema.declare("priority", MetaType::Text),
+            Err(MetaType::Float)
+        );
+    }
+}
@@ -100,6 +100,38 @@ pub struct AudioSegmentMetadata {
     pub label: Option<String>,
 }
 
+// Offsets stay in seconds on disk: this struct is part of the bincode-encoded TOC, so adding
+// fields would make existing files unreadable. Millisecond accessors are derived instead.
+impl AudioSegmentMetadata {
+    /// Segment spanning `start_ms..end_ms`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_ms(start_ms: u64, end_ms: u64, label: Option<String>) -> Self {
+        Self {
+            start_seconds: start_ms as f32 / 1000.0,
+            end_seconds: end_ms as f32 / 1000.0,
+            label,
+        }
+    }
+
+    /// Start offset in milliseconds from the beginning of the recording.
+    #[must_use]
+    pub fn start_ms(&self) -> u64 {
+        seconds_to_ms(self.start_seconds)
+    }
+
+    /// End offset in milliseconds from the beginning of the recording.
+    #[must_use]
+    pub fn end_ms(&self) -> u64 {
+        seconds_to_ms(self.end_seconds)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn seconds_to_ms(seconds: f32) -> u64 {
+    (f64::from(seconds.max(0.0)) * 1000.0).round() as u64
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct TextChunkRange {
     pub start: usize,
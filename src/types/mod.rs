@@ -1,45 +1,171 @@
 //! Public types exposed by the `memvid-core` crate.
 
+pub mod access_stats;
 pub mod acl;
 pub mod adaptive;
+pub mod analyzer;
+pub mod archive;
 pub mod ask;
+pub mod audio;
 pub mod audit;
+pub mod audit_chain;
+pub mod backfill;
 pub mod binding;
+pub mod blob_extents;
+pub mod capacity;
+pub mod cluster;
+pub mod collection;
+pub mod commit_history;
+pub mod commit_log;
 pub mod common;
+pub mod compression;
+pub mod context_window;
+pub mod conversation;
+pub mod diff;
+pub mod duplicates;
 pub mod embedding;
 pub mod embedding_identity;
+pub mod embedding_migration;
+pub mod enrichment_priority;
+pub mod entity_resolution;
+pub mod erasure;
+pub mod external_index;
+pub mod file_config;
 pub mod frame;
+pub mod frame_export;
+pub mod geo;
+pub mod git;
+pub mod graph_export;
 pub mod graph_query;
+pub mod hooks;
+pub mod importance;
+pub mod ingest_dir;
+pub mod ingest_profile;
+pub mod jsonl;
+pub mod legal_hold;
+pub mod links;
+pub mod llm;
 pub mod logic_mesh;
 pub mod manifest;
 pub mod memories_track;
 pub mod memory_card;
+pub mod memory_query;
+pub mod meta;
 pub mod metadata;
 pub mod options;
+pub mod replication;
 pub mod reranker;
+pub mod salvage;
 pub mod schema;
 pub mod search;
+pub mod seekable;
 pub mod sketch_track;
+pub mod snapshot;
+pub mod sparse;
 pub mod structure;
+pub mod suggest;
+pub mod summary;
+pub mod tags;
 #[cfg(feature = "temporal_track")]
 pub mod temporal;
 pub mod ticket;
+pub mod token_track;
+pub mod union;
 pub mod verification;
+pub mod video;
 
+pub use access_stats::{ACCESS_STATS_EXTENSION, AccessStats, FrameAccess, HotFrame};
+pub use analyzer::{ANALYZER_CONFIG_EXTENSION, AnalyzerConfig};
+pub use archive::{ARCHIVE_FRAME_KIND, ARCHIVE_MEMBER_KEY, ArchiveReceipt};
 pub use ask::{
     AskCitation, AskContextFragment, AskContextFragmentKind, AskMode, AskRequest, AskResponse,
     AskRetriever, AskStats, VecEmbedder,
 };
-pub use audit::{AuditOptions, AuditReport, SourceSpan};
+pub use audio::{
+    AUDIO_END_MS_KEY, AUDIO_FRAME_KIND, AUDIO_SEGMENT_FRAME_KIND, AUDIO_START_MS_KEY, AudioReceipt,
+};
+pub use audit::{
+    AuditOptions, AuditReport, CitationHighlight, PDF_PAGE_KEY, PDF_SPANS_KEY, PageRect,
+    PdfTextSpan, SourceSpan,
+};
+pub use audit_chain::{
+    AUDIT_CHAIN_EXTENSION, AuditAction, AuditChain, AuditChainReport, AuditEntry,
+};
+pub use backfill::BackfillReport;
 pub use binding::{FileInfo, MemoryBinding};
+pub use blob_extents::{
+    BLOB_EXTENT_EXTENSION, BlobExtent, BlobExtentOptions, BlobExtentStats, BlobExtentStore,
+};
+pub use capacity::{
+    CapacityBreakdown, CapacityReport, CapacitySource, DEFAULT_CAPACITY_WARNING_PERCENT,
+    PayloadUsage, StorageBreakdown,
+};
+pub use cluster::{ClusterOptions, TopicCluster};
+pub use collection::{COLLECTION_EXTENSION, Collection, CollectionRegistry, CollectionStats};
+pub use commit_history::{
+    COMMIT_HISTORY_EXTENSION, CommitHistory, CommitMetadata, CommitProvenance,
+    DEFAULT_COMMIT_HISTORY_CAPACITY,
+};
+pub use commit_log::{COMMIT_LOG_EXTENSION, CommitEvent, CommitLog, DEFAULT_COMMIT_LOG_CAPACITY};
 pub use common::{
-    CanonicalEncoding, EnrichmentState, EnrichmentTask, FrameId, FrameRole, FrameStatus,
-    MemvidHandle, Open, Sealed, Tier,
+    CanonicalEncoding, EnrichmentStage, EnrichmentState, EnrichmentTask, FrameId, FrameRole,
+    FrameStatus, MemvidHandle, Open, Sealed, Tier,
+};
+pub use compression::{
+    COMPRESSION_EXTENSION, CompressionCodec, CompressionDefaults, DEFAULT_ZSTD_LEVEL,
+};
+pub use context_window::{ApproxTokenCounter, ContextWindow, TokenCounter};
+pub use conversation::{
+    CHAT_AUTHOR_KEY, CHAT_INDEX_KEY, CHAT_ROLE_KEY, ChatMessage, ChatRole, ChatSession,
+    ChatSessionMessage, ChatSessionQuery, ChatSessionSummary, ConversationReceipt,
+    MESSAGE_FRAME_KIND, SESSION_FRAME_KIND, SESSION_ID_KEY,
+};
+pub use diff::{CardContradiction, FrameSupersession, MemoryDiff};
+pub use duplicates::{DuplicateCluster, DuplicateKind};
+pub use embedding_migration::{
+    EMBEDDING_MIGRATION_EXTENSION, EmbeddingMigrationReport, EmbeddingMigrationState,
+    StagedVecSegment,
+};
+pub use git::{
+    DEFAULT_GIT_MAX_FILE_BYTES, GIT_AUTHOR_KEY, GIT_BLOB_KEY, GIT_COMMIT_FRAME_KIND,
+    GIT_COMMIT_KEY, GIT_FILE_FRAME_KIND, GIT_LANGUAGE_KEY, GIT_PATH_KEY, GitCommit,
+    GitIngestOptions, GitIngestReport, GitTreeEntry,
+};
+pub use ingest_dir::{
+    DEFAULT_INGEST_BATCH_SIZE, DirSyncReport, IngestDirOptions, IngestDirReport, IngestFailure,
+    IngestFileEvent, IngestFileOutcome, SOURCE_HASH_KEY,
+};
+pub use ingest_profile::{INGEST_PROFILE_EXTENSION, IngestProfile, IngestProfileRegistry};
+pub use jsonl::{JSONL_LINE_KEY, JsonlReceipt};
+pub use legal_hold::{ImmutableHold, ImmutableMode};
+pub use links::{
+    FrameLink, LinkDirection, LinkKind, RELATIONS_EXTENSION, RelatedFrame, RelationsTrack,
+};
+pub use video::{
+    VIDEO_FRAME_KIND, VIDEO_KEYFRAME_FRAME_KIND, VIDEO_KEYFRAME_MS_KEY, VIDEO_OFFSET_MS_KEY,
+    VideoAudio, VideoDecoder, VideoKeyframe, VideoReceipt,
 };
 // AnchorSource always exported - not feature-gated to maintain binary compatibility
 pub use frame::AnchorSource;
-pub use frame::{Frame, Stats, TimelineEntry, TimelineQuery, TimelineQueryBuilder};
+pub use frame::{
+    Frame, Stats, TimeBucket, TimelineBucket, TimelineEntry, TimelineQuery, TimelineQueryBuilder,
+};
 // Serialized manifest types - always exported for binary compatibility
+pub use enrichment_priority::{
+    ENRICHMENT_PRIORITY_EXTENSION, EnrichmentPriorities, EnrichmentPriority,
+};
+pub use entity_resolution::{
+    AbsorbedEntity, ENTITY_MERGE_LOG_EXTENSION, EntityMerge, EntityMergeLog,
+    EntityResolutionReport, EntityResolutionStrategy, MergeSignal,
+};
+pub use erasure::{ERASURE_LOG_EXTENSION, ErasureLog, ErasureReceipt};
+pub use external_index::{
+    EXTERNAL_INDEX_EXTENSION, ExternalIndexManifest, SIDECAR_EXTENSION, SIDECAR_MAGIC,
+    SIDECAR_VERSION, sidecar_path,
+};
+pub use file_config::{DEFAULT_SKETCH_PREFILTER_THRESHOLD, FILE_CONFIG_EXTENSION, FileConfig};
+pub use geo::{GEO_LAT_KEY, GEO_LON_KEY, GeoFilter, GeoIndex, GeoPoint};
+pub use llm::{LlmBackend, LlmCompletion, LlmParams};
 pub use manifest::TemporalSegmentDescriptor;
 pub use manifest::TemporalTrackManifest;
 pub use manifest::{
@@ -50,48 +176,78 @@ pub use manifest::{
     TimeSegmentDescriptor, Toc, VecIndexManifest, VecSegmentDescriptor, VectorCompression,
 };
 // Logic-Mesh types for entity-relationship graph traversal
+pub use hooks::{CommitHookEvent, EnrichmentEvent, MemvidHooks, PutEvent};
+pub use importance::{DecayPolicy, DecayReport, ImportanceRecord, ImportanceTable};
 pub use logic_mesh::{
     EdgeDirection, EntityKind, FollowResult, LOGIC_MESH_MAGIC, LOGIC_MESH_VERSION, LinkType,
     LogicMesh, LogicMeshStats, MeshEdge, MeshNode,
 };
+pub use meta::{
+    META_SCHEMA_EXTENSION, MetaField, MetaFilter, MetaIndex, MetaOp, MetaSchema, MetaType,
+    MetaValue,
+};
 pub use metadata::{
     AudioSegmentMetadata, DocAudioMetadata, DocExifMetadata, DocGpsMetadata, DocMetadata,
     MediaManifest, TextChunkManifest, TextChunkRange,
 };
 pub use options::{PutManyOpts, PutOptions, PutOptionsBuilder, PutRequest};
+pub use replication::{
+    BLOB_GENERATIONS_EXTENSION, DELTA_BUNDLE_MAGIC, DELTA_BUNDLE_VERSION, DeltaBundle, DeltaRange,
+};
+pub use salvage::{SalvageReport, SalvageSource, SalvagedFrame, UnrecoverableRange};
 pub use search::{
-    SearchEngineKind, SearchHit, SearchHitEntity, SearchHitMetadata, SearchParams, SearchRequest,
-    SearchResponse,
+    ExplainFilter, HighlightSpan, HitExplain, QueryExplain, SearchEngineKind, SearchHit,
+    SearchHitEntity, SearchHitMetadata, SearchMode, SearchParams, SearchRequest, SearchResponse,
+    SkippedStage, StageTiming, VecRescore,
 };
 #[cfg(feature = "temporal_track")]
 pub use search::{SearchHitTemporal, SearchHitTemporalAnchor, SearchHitTemporalMention};
+pub use snapshot::{SNAPSHOT_EXTENSION, Snapshot, SnapshotTable};
+pub use sparse::{
+    SPARSE_TRACK_EXTENSION, SPARSE_TRACK_MAGIC, SPARSE_TRACK_VERSION, SparseEncoder,
+    SparseIndexReport, SparseTrack, SparseTrackManifest, SparseTrackStats, SparseVector,
+    read_sparse_track, write_sparse_track,
+};
+pub use suggest::{SUGGEST_INDEX_EXTENSION, SuggestIndex, Suggestion, SuggestionKind};
+pub use summary::{SUMMARY_TRACK_EXTENSION, Summarizer, SummaryCard, SummaryTarget, SummaryTrack};
+pub use tags::{DEFAULT_TAG_LOG_CAPACITY, TAG_LOG_EXTENSION, TagEdit, TagEditRecord, TagLog};
 #[cfg(feature = "temporal_track")]
 pub use temporal::{
-    TEMPORAL_TRACK_FLAG_HAS_ANCHORS, TEMPORAL_TRACK_FLAG_HAS_MENTIONS, TemporalAnchor,
-    TemporalCapabilities, TemporalFilter, TemporalMention, TemporalMentionFlags,
-    TemporalMentionKind, TemporalTrack,
+    RecurrenceFrequency, TEMPORAL_TRACK_FLAG_HAS_ANCHORS, TEMPORAL_TRACK_FLAG_HAS_MENTIONS,
+    TemporalAnchor, TemporalCapabilities, TemporalFilter, TemporalMention, TemporalMentionFlags,
+    TemporalMentionKind, TemporalRecurrence, TemporalTrack,
 };
 pub use ticket::{SignedTicket, Ticket, TicketRef};
+pub use token_track::{
+    TOKEN_TRACK_EXTENSION, TOKEN_TRACK_MAGIC, TOKEN_TRACK_VERSION, TokenEmbeddings, TokenEncoder,
+    TokenIndexReport, TokenTrack, TokenTrackManifest, TokenTrackStats, read_token_track,
+    write_token_track,
+};
+pub use union::{UnionAskResponse, UnionSearchHit, UnionSearchResponse, UnionTimelineEntry};
 pub use verification::{
-    DOCTOR_PLAN_VERSION, DoctorActionDetail, DoctorActionKind, DoctorActionPlan,
-    DoctorActionReport, DoctorActionStatus, DoctorFinding, DoctorFindingCode, DoctorMetrics,
-    DoctorOptions, DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan, DoctorPhaseReport,
-    DoctorPhaseStatus, DoctorPlan, DoctorReport, DoctorSeverity, DoctorStatus, VerificationCheck,
+    DOCTOR_PLAN_VERSION, DOCTOR_REPORT_VERSION, DoctorActionDetail, DoctorActionKind,
+    DoctorActionPlan, DoctorActionReport, DoctorActionStatus, DoctorByteRange, DoctorFinding,
+    DoctorFindingCode, DoctorIndexBump, DoctorIndexKind, DoctorMetrics, DoctorOptions,
+    DoctorPhaseDuration, DoctorPhaseKind, DoctorPhasePlan, DoctorPhaseReport, DoctorPhaseStatus,
+    DoctorPlan, DoctorPlanDiff, DoctorReport, DoctorSeverity, DoctorStatus, VerificationCheck,
     VerificationReport, VerificationStatus,
 };
 // Memory card types for structured memory extraction
 pub use memories_track::{
-    EngineStamp, EnrichmentManifest, EnrichmentRecord, MEMORIES_TRACK_MAGIC,
-    MEMORIES_TRACK_VERSION, MemoriesStats, MemoriesTrack, SlotIndex,
+    CardConflict, ConflictKind, EngineStamp, EnrichmentManifest, EnrichmentRecord,
+    MEMORIES_TRACK_MAGIC, MEMORIES_TRACK_VERSION, MemoriesStats, MemoriesTrack, SlotIndex,
 };
 pub use memory_card::{
     MemoryCard, MemoryCardBuilder, MemoryCardBuilderError, MemoryCardId, MemoryKind, Polarity,
     VersionRelation,
 };
+pub use memory_query::{
+    DEFAULT_MEMORY_QUERY_LIMIT, MemoryQuery, MemoryQueryHit, MemoryQueryResult, MemorySortOrder,
+};
 // Embedding provider types for vector embedding generation
 pub use embedding::{
-    BatchEmbeddingResult, EmbeddingConfig, EmbeddingProvider, EmbeddingProviderKind,
-    EmbeddingResult,
+    BatchEmbeddingResult, EmbedderRequestStats, EmbeddingConfig, EmbeddingProvider,
+    EmbeddingProviderKind, EmbeddingResult,
 };
 pub use embedding_identity::{
     EmbeddingIdentity, EmbeddingIdentityCount, EmbeddingIdentitySummary,
@@ -100,26 +256,36 @@ pub use embedding_identity::{
 };
 // Structure-aware chunking types for preserving tables and code blocks
 pub use structure::{
-    ChunkType, ChunkingOptions, ChunkingResult, CodeChunkingStrategy, DocumentElement, ElementData,
-    ElementType, StructuredCell, StructuredChunk, StructuredCodeBlock, StructuredDocument,
-    StructuredHeading, StructuredList, StructuredRow, StructuredTable, TableChunkingStrategy,
+    CODE_SYMBOLS_KEY, ChunkType, ChunkingOptions, ChunkingResult, CodeChunkingStrategy, CodeSymbol,
+    DocumentElement, ElementData, ElementType, StructuredCell, StructuredChunk,
+    StructuredCodeBlock, StructuredDocument, StructuredHeading, StructuredList, StructuredRow,
+    StructuredTable, TableChunkingStrategy,
 };
 // Adaptive retrieval types for dynamic result set sizing
 pub use acl::{
     ACL_POLICY_VERSION_KEY, ACL_READ_GROUPS_KEY, ACL_READ_PRINCIPALS_KEY, ACL_READ_ROLES_KEY,
     ACL_RESOURCE_ID_KEY, ACL_TENANT_ID_KEY, ACL_VISIBILITY_KEY, AclContext, AclEnforcementMode,
+    CLASSIFICATION_KEY, CONSENT_KEY, Classification, ClassificationAction, ClassificationPolicy,
+    REDACTED_TEXT,
 };
 pub use adaptive::{
     AdaptiveConfig, AdaptiveResult, AdaptiveStats, CutoffStrategy, EmbeddingQualityStats,
     compute_embedding_quality, find_adaptive_cutoff, normalize_scores,
 };
+// Tabular frame export formats and filters
+pub use frame_export::{
+    DEFAULT_FRAME_EXPORT_BATCH_SIZE, FrameExportColumn, FrameExportFilter, FrameExportFormat,
+};
+// Graph export formats for the logic mesh and memory cards
+pub use graph_export::{GraphExportFormat, GraphExportStats};
 // Graph-aware query types for hybrid retrieval
 pub use graph_query::{
     GraphMatchResult, GraphPattern, HybridSearchHit, PatternTerm, QueryPlan, TriplePattern,
 };
 // Schema types for predicate validation
 pub use schema::{
-    Cardinality, PredicateId, PredicateSchema, SchemaError, SchemaRegistry, ValueType,
+    Cardinality, MAX_VIOLATION_SAMPLES, PredicateId, PredicateSchema, PredicateViolations,
+    SchemaError, SchemaRegistry, SchemaViolation, SchemaViolationLog, ValueType,
 };
 // Sketch track types for fast candidate generation
 pub use sketch_track::{
@@ -5,7 +5,10 @@ use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::acl::{CLASSIFICATION_KEY, CONSENT_KEY, Classification};
 use super::common::{FrameId, FrameRole};
+use super::compression::CompressionCodec;
+use super::meta::MetaValue;
 use super::metadata::DocMetadata;
 
 fn default_true() -> bool {
@@ -15,6 +18,7 @@ fn default_true() -> bool {
 /// Tunable options for writing frames into a memory.
 /// Attach metadata, control embeddings, auto-tagging, and URI/title hints. Builders make it
 /// easy to set only what you need.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PutOptions {
     pub timestamp: Option<i64>,
@@ -23,6 +27,7 @@ pub struct PutOptions {
     pub uri: Option<String>,
     pub title: Option<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub metadata: Option<DocMetadata>,
     #[serde(default)]
     pub search_text: Option<String>,
@@ -32,7 +37,12 @@ pub struct PutOptions {
     pub labels: Vec<String>,
     #[serde(default)]
     pub extra_metadata: BTreeMap<String, String>,
+    /// Typed metadata, encoded into `extra_metadata` on write and recorded in the memory's
+    /// metadata schema so `meta.<key>` filters compare by type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub typed_metadata: BTreeMap<String, MetaValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub enable_embedding: bool,
     #[serde(default = "default_true")]
     pub auto_tag: bool,
@@ -44,16 +54,20 @@ pub struct PutOptions {
     pub extract_triplets: bool,
     /// Parent frame ID for child frames (e.g., extracted images from a PDF)
     #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub parent_id: Option<FrameId>,
     /// Role of the frame (defaults to Document)
     #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub role: FrameRole,
     /// Don't store raw binary content, only extracted text + SHA256 hash.
     /// Saves storage for documents where only searchable text is needed.
     #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub no_raw: bool,
     /// Original source file path (for --no-raw reference tracking).
     #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub source_path: Option<String>,
     /// Skip ingestion if a frame with matching BLAKE3 hash already exists.
     /// When enabled, returns the existing frame's sequence number instead of creating a duplicate.
@@ -64,13 +78,30 @@ pub struct PutOptions {
     /// Frame becomes searchable immediately but full enrichment happens in background.
     /// Default: true for single-doc `put()`, false for `put_many()` batch.
     #[serde(default = "default_true")]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub instant_index: bool,
     /// Time budget for text extraction in milliseconds.
     /// When `instant_index` is enabled, extraction stops after this time.
     /// 0 means no budget (extract everything).
     /// Default: 350ms (optimized for sub-second total ingestion).
     #[serde(default = "default_extraction_budget_ms")]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub extraction_budget_ms: u64,
+    /// Codec for this payload, overriding the batch level and the file's default.
+    #[serde(default)]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
+    pub compression: Option<CompressionCodec>,
+    /// Time zone relative dates in this document resolve in, e.g. `Europe/Berlin` or
+    /// `+02:00`, overriding the file default. Stored on the frame under `temporal_tz`.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Target characters per text chunk, overriding the file's `FileConfig::chunk_chars`.
+    #[serde(default)]
+    pub chunk_chars: Option<usize>,
+    /// Ingest profile stored in the file whose settings fill in the ones this put leaves at
+    /// their defaults (see `Memvid::set_ingest_profile`).
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 fn default_extraction_budget_ms() -> u64 {
@@ -90,6 +121,7 @@ impl Default for PutOptions {
             tags: Vec::new(),
             labels: Vec::new(),
             extra_metadata: BTreeMap::new(),
+            typed_metadata: BTreeMap::new(),
             enable_embedding: false,
             auto_tag: true,
             extract_dates: true,
@@ -101,6 +133,10 @@ impl Default for PutOptions {
             dedup: false,
             instant_index: true, // Instant searchability by default
             extraction_budget_ms: default_extraction_budget_ms(),
+            compression: None,
+            timezone: None,
+            chunk_chars: None,
+            profile: None,
         }
     }
 }
@@ -111,6 +147,14 @@ impl PutOptions {
     pub fn builder() -> PutOptionsBuilder {
         PutOptionsBuilder::default()
     }
+
+    /// Options that ingest with the file's profile `name`, e.g. `PutOptions::profile("email")`.
+    pub fn profile<S: Into<String>>(name: S) -> Self {
+        Self {
+            profile: Some(name.into()),
+            ..Self::default()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -150,6 +194,36 @@ impl PutOptionsBuilder {
         self
     }
 
+    /// Attach a typed metadata value, filterable as `meta.<key> <op> <value>`.
+    pub fn meta<K: Into<String>, V: Into<MetaValue>>(mut self, key: K, value: V) -> Self {
+        self.inner.typed_metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Label the frame with a data classification, checked against
+    /// `AclContext::classification` at retrieval time.
+    #[must_use]
+    pub fn classification(mut self, classification: Classification) -> Self {
+        self.inner.extra_metadata.insert(
+            CLASSIFICATION_KEY.to_string(),
+            classification.as_str().to_string(),
+        );
+        self
+    }
+
+    /// Record the purposes the data subject consented to, e.g. `llm` or `share`.
+    pub fn consent<I, S>(mut self, purposes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let purposes: Vec<String> = purposes.into_iter().map(Into::into).collect();
+        self.inner
+            .extra_metadata
+            .insert(CONSENT_KEY.to_string(), Value::from(purposes).to_string());
+        self
+    }
+
     #[must_use]
     pub fn metadata(mut self, metadata: DocMetadata) -> Self {
         self.inner.metadata = Some(metadata);
@@ -253,6 +327,31 @@ impl PutOptionsBuilder {
         self
     }
 
+    /// Compress this payload with `codec` (e.g. `CompressionCodec::None` for media).
+    #[must_use]
+    pub fn compression(mut self, codec: CompressionCodec) -> Self {
+        self.inner.compression = Some(codec);
+        self
+    }
+
+    #[must_use]
+    pub fn timezone<S: Into<String>>(mut self, timezone: S) -> Self {
+        self.inner.timezone = Some(timezone.into());
+        self
+    }
+
+    #[must_use]
+    pub fn chunk_chars(mut self, chunk_chars: usize) -> Self {
+        self.inner.chunk_chars = Some(chunk_chars);
+        self
+    }
+
+    /// Ingest with the file's profile `name`; options set on the builder take precedence.
+    pub fn profile<S: Into<String>>(mut self, name: S) -> Self {
+        self.inner.profile = Some(name.into());
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> PutOptions {
         self.inner
@@ -347,13 +446,11 @@ pub struct PutManyOpts {
 
     /// Pre-allocate the embedded WAL to this many bytes before the batch starts.
     ///
-    /// When `disable_auto_checkpoint` is true, WAL entries accumulate for the
-    /// entire batch. If the WAL is too small it must grow repeatedly, shifting
-    /// all payload data each time — an O(file_size) operation per growth.
-    ///
-    /// Setting this to `num_entries * avg_entry_bytes` (e.g. 10 KB per entry
-    /// with 1536-dim embeddings, 2 KB without) eliminates mid-batch WAL growth
-    /// and can provide a **3-5× speedup** for large batches.
+    /// Only a hint: when `disable_auto_checkpoint` is true, WAL entries accumulate
+    /// for the entire batch and the WAL grows on demand by relocating to a larger
+    /// extent at the end of the file. Growth never moves payload data, but each one
+    /// copies the pending records, so a good estimate (`num_entries * avg_entry_bytes`)
+    /// saves those copies.
     ///
     /// 0 (default): no pre-sizing — WAL grows on demand.
     pub wal_pre_size_bytes: u64,
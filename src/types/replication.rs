@@ -0,0 +1,211 @@
+//! Replication deltas shipped from a leader `.mv2` to read replicas.
+//!
+//! A [`DeltaBundle`] carries everything a follower needs to move from one committed generation
+//! to the next: the byte ranges written since then (new frame payloads plus the index and track
+//! blobs rewritten after the follower's generation), the encoded TOC itself, and the leader
+//! header. The
+//! embedded WAL is never shipped; a follower only ever sees committed state.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::commit_log::CommitEvent;
+use super::manifest::Header;
+use crate::error::{MemvidError, Result};
+
+/// Magic bytes for an encoded [`DeltaBundle`].
+pub const DELTA_BUNDLE_MAGIC: &[u8; 4] = b"MVRD";
+
+/// Current [`DeltaBundle`] encoding version.
+pub const DELTA_BUNDLE_VERSION: u16 = 1;
+
+/// TOC extension key holding the persisted [`BlobGenerations`].
+pub const BLOB_GENERATIONS_EXTENSION: &str = "memvid.blob_generations";
+
+/// Length of the magic + version prefix.
+const PREFIX_LEN: usize = 6;
+
+/// Bytes to write at `offset` in the follower file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaRange {
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl DeltaRange {
+    #[must_use]
+    pub fn end(&self) -> u64 {
+        self.offset.saturating_add(self.bytes.len() as u64)
+    }
+}
+
+/// Changes between two committed generations of a memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaBundle {
+    /// Generation the follower must be at to apply this bundle.
+    pub base_generation: u64,
+    /// Generation the follower is at after applying it.
+    pub target_generation: u64,
+    /// Leader header at `target_generation`.
+    pub header: Header,
+    /// File ranges to copy, sorted by offset and non-overlapping.
+    pub ranges: Vec<DeltaRange>,
+    /// Encoded TOC at `target_generation`, written at `header.footer_offset`.
+    pub toc_bytes: Vec<u8>,
+    /// Commit events covered by the bundle, oldest first.
+    pub events: Vec<CommitEvent>,
+}
+
+fn bundle_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_fixed_int_encoding()
+        .with_little_endian()
+}
+
+impl DeltaBundle {
+    /// Total number of range bytes carried by the bundle, excluding the TOC.
+    #[must_use]
+    pub fn range_bytes(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| range.bytes.len() as u64)
+            .sum()
+    }
+
+    /// True when applying the bundle changes nothing.
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        self.base_generation == self.target_generation
+    }
+
+    /// Serialize for transport: magic, version, bincode payload, then a blake3 hash of the
+    /// payload so truncated or corrupted transfers are rejected by [`DeltaBundle::decode`].
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let payload = bincode::serde::encode_to_vec(self, bundle_config()).map_err(|err| {
+            MemvidError::Replication {
+                reason: format!("failed to encode delta bundle: {err}"),
+            }
+        })?;
+        let mut buf = Vec::with_capacity(PREFIX_LEN + payload.len() + 32);
+        buf.extend_from_slice(DELTA_BUNDLE_MAGIC);
+        buf.extend_from_slice(&DELTA_BUNDLE_VERSION.to_le_bytes());
+        buf.extend_from_slice(&payload);
+        buf.extend_from_slice(blake3::hash(&payload).as_bytes());
+        Ok(buf)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let invalid = |reason: &str| MemvidError::Replication {
+            reason: format!("invalid delta bundle: {reason}"),
+        };
+        if bytes.len() < PREFIX_LEN + 32 {
+            return Err(invalid("too short"));
+        }
+        if &bytes[..4] != DELTA_BUNDLE_MAGIC {
+            return Err(invalid("bad magic"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version > DELTA_BUNDLE_VERSION {
+            return Err(invalid(&format!("unsupported version {version}")));
+        }
+        let (payload, hash) = bytes[PREFIX_LEN..].split_at(bytes.len() - PREFIX_LEN - 32);
+        if blake3::hash(payload).as_bytes() != hash {
+            return Err(invalid("checksum mismatch"));
+        }
+        let (bundle, _) = bincode::serde::decode_from_slice(payload, bundle_config())
+            .map_err(|err| invalid(&err.to_string()))?;
+        Ok(bundle)
+    }
+}
+
+/// `(offset, length, checksum)` identifying one blob's bytes on disk.
+pub(crate) type BlobFingerprint = (u64, u64, [u8; 32]);
+
+/// Generation at which each index and track blob was last written.
+///
+/// Index blobs are rewritten in place at every commit, so their offsets alone say nothing
+/// about whether a follower already holds them. Each commit restamps the blobs the TOC
+/// references: a fingerprint seen in the previous TOC keeps its generation, anything else
+/// gets the committing generation. `export_delta` then ships only blobs stamped after the
+/// follower's generation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct BlobGenerations {
+    stamps: Vec<(BlobFingerprint, u64)>,
+}
+
+impl BlobGenerations {
+    /// Stamps for `current`, carrying generations over from `previous` where unchanged.
+    pub(crate) fn restamp(
+        previous: Option<&Self>,
+        current: &[BlobFingerprint],
+        generation: u64,
+    ) -> Self {
+        let known = previous.map(Self::by_fingerprint).unwrap_or_default();
+        let stamps = current
+            .iter()
+            .map(|fingerprint| {
+                let written = known.get(fingerprint).copied().unwrap_or(generation);
+                (*fingerprint, written)
+            })
+            .collect();
+        Self { stamps }
+    }
+
+    /// Generation each fingerprint was last written at.
+    pub(crate) fn by_fingerprint(&self) -> HashMap<BlobFingerprint, u64> {
+        self.stamps.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DeltaBundle {
+        DeltaBundle {
+            base_generation: 3,
+            target_generation: 5,
+            header: Header {
+                magic: *b"MV2\0",
+                version: 1,
+                footer_offset: 9000,
+                wal_offset: 4096,
+                wal_size: 4096,
+                wal_checkpoint_pos: 0,
+                wal_sequence: 7,
+                toc_checksum: [1; 32],
+                immutable: None,
+                data_start: 8192,
+            },
+            ranges: vec![
+                DeltaRange {
+                    offset: 8192,
+                    bytes: b"payload".to_vec(),
+                },
+                DeltaRange {
+                    offset: 8500,
+                    bytes: vec![0xAB; 40],
+                },
+            ],
+            toc_bytes: vec![9; 64],
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn encode_roundtrip_and_rejects_corruption() {
+        let bundle = sample();
+        let encoded = bundle.encode().expect("encode");
+        let decoded = DeltaBundle::decode(&encoded).expect("decode");
+        assert_eq!(decoded.ranges, bundle.ranges);
+        assert_eq!(decoded.toc_bytes, bundle.toc_bytes);
+        assert_eq!(decoded.header.footer_offset, 9000);
+        assert_eq!(decoded.range_bytes(), 47);
+
+        let mut corrupted = encoded.clone();
+        corrupted[PREFIX_LEN + 10] ^= 0xFF;
+        assert!(DeltaBundle::decode(&corrupted).is_err());
+        assert!(DeltaBundle::decode(&encoded[..encoded.len() - 1]).is_err());
+    }
+}
@@ -23,6 +23,8 @@
 //! }
 //! ```
 
+use serde::{Deserialize, Serialize};
+
 use crate::error::Result;
 
 /// A document candidate for reranking.
@@ -72,8 +74,17 @@ pub struct RerankerResult {
 }
 
 /// Configuration for reranking.
-#[derive(Debug, Clone)]
+///
+/// Set on `SearchRequest::rerank` to rerank hits with the reranker installed via
+/// `Memvid::set_reranker`, or with stored token embeddings when `kind` is
+/// [`RerankerKind::LateInteraction`].
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RerankerConfig {
+    /// Scorer to use. `LateInteraction` scores hits by MaxSim against the token embeddings
+    /// stored by `Memvid::index_token_embeddings`; any other kind uses the installed reranker.
+    pub kind: RerankerKind,
     /// Maximum number of candidates to consider.
     pub max_candidates: usize,
     /// Maximum number of results to return.
@@ -87,6 +98,7 @@ pub struct RerankerConfig {
 impl Default for RerankerConfig {
     fn default() -> Self {
         Self {
+            kind: RerankerKind::None,
             max_candidates: 50,
             top_k: 10,
             min_score: 0.0,
@@ -100,6 +112,7 @@ impl RerankerConfig {
     #[must_use]
     pub fn high_recall() -> Self {
         Self {
+            kind: RerankerKind::None,
             max_candidates: 100,
             top_k: 20,
             min_score: 0.0,
@@ -111,12 +124,22 @@ impl RerankerConfig {
     #[must_use]
     pub fn high_precision() -> Self {
         Self {
+            kind: RerankerKind::None,
             max_candidates: 20,
             top_k: 5,
             min_score: 0.3,
             use_metadata: true,
         }
     }
+
+    /// Create config for late-interaction reranking over stored token embeddings.
+    #[must_use]
+    pub fn late_interaction() -> Self {
+        Self {
+            kind: RerankerKind::LateInteraction,
+            ..Self::default()
+        }
+    }
 }
 
 /// Trait for reranking search results.
@@ -181,7 +204,9 @@ pub trait Reranker: Send + Sync {
 }
 
 /// Enum wrapper for reranker kinds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum RerankerKind {
     /// No reranking.
     None,
@@ -192,7 +217,10 @@ pub enum RerankerKind {
     /// LLM-based reranking.
     Llm,
     /// OpenAI-based reranking.
+    #[serde(rename = "openai")]
     OpenAI,
+    /// ColBERT-style MaxSim over stored token embeddings.
+    LateInteraction,
 }
 
 impl Default for RerankerKind {
@@ -209,6 +237,7 @@ impl std::fmt::Display for RerankerKind {
             Self::CrossEncoder => write!(f, "cross-encoder"),
             Self::Llm => write!(f, "llm"),
             Self::OpenAI => write!(f, "openai"),
+            Self::LateInteraction => write!(f, "late-interaction"),
         }
     }
 }
@@ -223,6 +252,7 @@ impl std::str::FromStr for RerankerKind {
             "cross-encoder" | "crossencoder" | "cross_encoder" => Ok(Self::CrossEncoder),
             "llm" | "local" => Ok(Self::Llm),
             "openai" => Ok(Self::OpenAI),
+            "late-interaction" | "late_interaction" | "colbert" => Ok(Self::LateInteraction),
             _ => Err(format!("Unknown reranker kind: {s}")),
         }
     }
@@ -297,6 +327,14 @@ mod tests {
             RerankerKind::OpenAI
         );
         assert_eq!("llm".parse::<RerankerKind>().unwrap(), RerankerKind::Llm);
+        assert_eq!(
+            "colbert".parse::<RerankerKind>().unwrap(),
+            RerankerKind::LateInteraction
+        );
+        assert_eq!(
+            RerankerKind::LateInteraction.to_string(),
+            "late-interaction"
+        );
     }
 
     #[test]
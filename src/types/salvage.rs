@@ -0,0 +1,49 @@
+//! Results of salvaging frames from a damaged memory file (see `Memvid::salvage`).
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// Where a salvaged frame was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SalvageSource {
+    /// Listed in a table of contents that still decodes; metadata is preserved.
+    Toc,
+    /// Decoded from a WAL record; metadata is preserved.
+    Wal,
+    /// Found by scanning the data region for compressed payloads; content only.
+    Scan,
+}
+
+/// A frame copied into the salvaged file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SalvagedFrame {
+    pub source: SalvageSource,
+    /// Offset in the damaged file where the payload (or WAL record) was read.
+    pub offset: u64,
+    /// Frame id in the damaged file, when a table of contents named it.
+    pub original_id: Option<FrameId>,
+    pub uri: Option<String>,
+}
+
+/// Bytes of the damaged file that yielded no frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnrecoverableRange {
+    pub offset: u64,
+    pub length: u64,
+    pub reason: String,
+}
+
+/// Outcome of `Memvid::salvage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SalvageReport {
+    pub source_path: PathBuf,
+    pub dest_path: PathBuf,
+    /// Whether a table of contents (current or recovered from an older footer) was readable.
+    pub toc_found: bool,
+    pub recovered: Vec<SalvagedFrame>,
+    pub unrecoverable: Vec<UnrecoverableRange>,
+}
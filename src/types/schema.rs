@@ -7,8 +7,9 @@
 //! - Inverse relationship tracking
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use super::common::FrameId;
 use super::logic_mesh::EntityKind;
 
 /// Unique identifier for a predicate in the schema.
@@ -252,6 +253,64 @@ impl std::fmt::Display for SchemaError {
 
 impl std::error::Error for SchemaError {}
 
+/// Offenders kept per predicate in a [`SchemaViolationLog`].
+pub const MAX_VIOLATION_SAMPLES: usize = 5;
+
+/// A card that failed schema validation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub entity: String,
+    pub slot: String,
+    pub value: String,
+    /// The validation error, as displayed by [`SchemaError`].
+    pub reason: String,
+    pub source_frame_id: FrameId,
+    /// Engine that produced the card (e.g., "rules", "llm:gpt-4o-mini").
+    pub engine: String,
+    /// Unix timestamp when the violation was recorded.
+    pub detected_at: i64,
+}
+
+/// Violations of one predicate: a running count and the first few offenders.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PredicateViolations {
+    pub count: u64,
+    pub samples: Vec<SchemaViolation>,
+}
+
+/// Schema violations grouped by predicate, persisted with the memories track so extraction
+/// rules can be fixed after the fact.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaViolationLog {
+    pub predicates: BTreeMap<PredicateId, PredicateViolations>,
+}
+
+impl SchemaViolationLog {
+    /// Count `violation` under its predicate, keeping it as a sample while there is room.
+    pub fn record(&mut self, violation: SchemaViolation) {
+        let entry = self.predicates.entry(violation.slot.clone()).or_default();
+        entry.count += 1;
+        if entry.samples.len() < MAX_VIOLATION_SAMPLES {
+            entry.samples.push(violation);
+        }
+    }
+
+    /// Total violations across all predicates.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.predicates.values().map(|entry| entry.count).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.predicates.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.predicates.clear();
+    }
+}
+
 /// Registry of predicate schemas with built-in defaults.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SchemaRegistry {
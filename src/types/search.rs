@@ -6,6 +6,10 @@ use super::acl::{AclContext, AclEnforcementMode};
 use super::common::FrameId;
 #[cfg(feature = "temporal_track")]
 use super::frame::AnchorSource;
+use super::geo::GeoFilter;
+use super::links::RelatedFrame;
+use super::meta::MetaFilter;
+use super::reranker::RerankerConfig;
 #[cfg(feature = "temporal_track")]
 use super::temporal::{TemporalFilter, TemporalMentionFlags, TemporalMentionKind};
 
@@ -19,6 +23,40 @@ pub struct SearchParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// Cursor token for pagination.
     pub cursor: Option<String>,
+    #[serde(default)]
+    /// Exact rescoring of quantized vector candidates.
+    pub vec_rescore: VecRescore,
+}
+
+/// How vector search ranks hits from a product-quantized index.
+///
+/// Indexes built with `VectorCompression::Pq96` keep their full-precision vectors on disk;
+/// rescoring reads those for the best PQ candidates and reorders them by exact distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VecRescore {
+    /// Rescore `top_k * VecRescore::DEFAULT_OVERSAMPLE` candidates when full-precision
+    /// vectors are stored.
+    #[default]
+    Auto,
+    /// Rank by quantized distance only.
+    Off,
+    /// Rescore this many PQ candidates (at least `top_k`).
+    Candidates(usize),
+}
+
+impl VecRescore {
+    pub const DEFAULT_OVERSAMPLE: usize = 4;
+
+    /// PQ candidates to fetch for `top_k` hits, or `None` to skip rescoring.
+    #[must_use]
+    pub fn candidates(self, top_k: usize) -> Option<usize> {
+        match self {
+            Self::Auto => Some(top_k.saturating_mul(Self::DEFAULT_OVERSAMPLE)),
+            Self::Off => None,
+            Self::Candidates(count) => Some(count.max(top_k)),
+        }
+    }
 }
 
 /// Engine selected to satisfy a search.
@@ -28,6 +66,7 @@ pub enum SearchEngineKind {
     Tantivy,
     LexFallback,
     Hybrid,
+    Sparse,
 }
 
 impl Default for SearchEngineKind {
@@ -36,14 +75,42 @@ impl Default for SearchEngineKind {
     }
 }
 
+impl SearchEngineKind {
+    /// Stable `snake_case` name, as used in serialized responses.
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Tantivy => "tantivy",
+            Self::LexFallback => "lex_fallback",
+            Self::Hybrid => "hybrid",
+            Self::Sparse => "sparse",
+        }
+    }
+}
+
+/// Retrieval path used to find a search's hits.
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    /// BM25 over the lexical index.
+    #[default]
+    Lexical,
+    /// Learned sparse term weights from the memory's sparse track, with the query encoded by
+    /// the encoder installed via `Memvid::set_sparse_encoder`.
+    Sparse,
+}
+
 /// Search request accepted by the core; supports lexical, hybrid, and temporal filters.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchRequest {
     /// Query string (lexical or semantic depending on engine).
     pub query: String,
     /// Maximum hits to return.
     pub top_k: usize,
-    /// Number of characters to capture around matches.
+    /// Number of characters to capture around matches; 0 uses the file's
+    /// `FileConfig::snippet_chars` when it sets one.
     pub snippet_chars: usize,
     #[serde(default)]
     /// Restrict search to a specific URI.
@@ -56,6 +123,7 @@ pub struct SearchRequest {
     pub cursor: Option<String>,
     #[cfg(feature = "temporal_track")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub temporal: Option<TemporalFilter>,
     #[serde(default)]
     /// Replay: Filter to frames with id <= `as_of_frame` (time-travel view).
@@ -68,10 +136,58 @@ pub struct SearchRequest {
     pub no_sketch: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// Optional caller identity context used for ACL filtering.
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub acl_context: Option<AclContext>,
     #[serde(default)]
     /// ACL evaluation mode (`audit` or `enforce`).
+    #[cfg_attr(feature = "mcp", schemars(skip))]
     pub acl_enforcement_mode: AclEnforcementMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Second-stage reranking of the first `max_candidates` hits with the memory's reranker.
+    pub rerank: Option<RerankerConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Only frames located inside this region (EXIF GPS or `geo_lat`/`geo_lon` metadata).
+    pub geo: Option<GeoFilter>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Metadata comparisons every hit must satisfy, as with `meta.<key> <op> <value>` in the query.
+    pub filters: Vec<MetaFilter>,
+    #[serde(default)]
+    /// Lift frames that earlier searches returned and asks cited (see `Memvid::hot_frames`).
+    pub access_boost: bool,
+    #[serde(default)]
+    /// Matched windows to return per hit in `SearchHit::highlights`; 0 returns none.
+    pub highlight_windows: usize,
+    #[serde(default)]
+    /// ISO 639-1 code (or English name) of the query's language; query text is then also
+    /// analyzed like documents detected in that language, e.g. with the German stemmer.
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Maximal-marginal-relevance trade-off in `[0, 1]`: 1 keeps the relevance order, lower
+    /// values prefer hits unlike those ranked above them (by embedding, else by document).
+    pub diversify: Option<f32>,
+    #[serde(default)]
+    /// Collapse hits from the same document (a frame and its chunks) into the best one.
+    pub group_by_parent: bool,
+    #[serde(default)]
+    /// Return the parent document in place of its chunk hits, scored by the sum of the chunks'
+    /// scores with the best chunk's snippet and the whole document in `chunk_text`.
+    pub return_parents: bool,
+    #[serde(default)]
+    /// Attach a [`QueryExplain`] describing the filters, engine, score components, and stage
+    /// timings behind the response.
+    pub explain: bool,
+    #[serde(default)]
+    /// Retrieval path; [`SearchMode::Sparse`] needs a sparse track built by
+    /// `Memvid::index_sparse`.
+    pub mode: SearchMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Milliseconds the search may take. Once they are spent, optional stages are skipped and
+    /// the best hits found so far are returned with `SearchResponse::partial` set.
+    ///
+    /// The budget is best-effort and only checked between stages and between candidates: the
+    /// engine query and the sketch pre-filter always run to completion, so a search can
+    /// overrun the budget by however long those take.
+    pub time_budget_ms: Option<u64>,
 }
 
 /// A single ranked hit with snippet metadata.
@@ -93,6 +209,19 @@ pub struct SearchHit {
     pub score: Option<f32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<SearchHitMetadata>,
+    /// Every matched window of the hit's chunk, up to `SearchRequest::highlight_windows`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<HighlightSpan>,
+}
+
+/// One matched window of a hit's chunk. Offsets are byte offsets into the frame's text, in the
+/// same coordinates as `SearchHit::range`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightSpan {
+    pub range: (usize, usize),
+    pub text: String,
+    /// Offsets of the matched terms inside the window.
+    pub terms: Vec<(usize, usize)>,
 }
 
 /// Entity reference in search hit metadata.
@@ -125,6 +254,9 @@ pub struct SearchHitMetadata {
     /// Entities mentioned in this search hit (from Logic-Mesh).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub entities: Vec<SearchHitEntity>,
+    /// Frames linked to this hit's frame (or its parent, for chunks) with `Memvid::link_frames`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedFrame>,
     /// Custom user-defined metadata stored with the frame via `PutOptions.extra_metadata`.
     #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
     pub extra_metadata: std::collections::BTreeMap<String, String>,
@@ -167,6 +299,9 @@ pub struct SearchHitTemporalMention {
     pub text: Option<String>,
     pub byte_start: u32,
     pub byte_len: u32,
+    /// `RRULE` of a recurring mention, e.g. `FREQ=WEEKLY;INTERVAL=1;BYDAY=MO`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
 }
 
 /// Full search response with hits, params, engine, and an optional cursor.
@@ -193,6 +328,140 @@ pub struct SearchResponse {
     /// Number of search hits skipped due to stale frame_ids in the index.
     #[serde(default, skip_serializing_if = "is_zero")]
     pub stale_index_skips: u32,
+    /// "Did you mean" rewrites of the query, best first; filled when few hits were found and
+    /// the `spelling` feature is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+    /// How the response was produced; filled when `SearchRequest::explain` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explain: Option<QueryExplain>,
+    /// Set when `SearchRequest::time_budget_ms` ran out and stages were cut short.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    /// Stages cut short by the time budget, in pipeline order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_stages: Vec<SkippedStage>,
+}
+
+impl SearchResponse {
+    /// Record a stage cut short by the time budget.
+    pub(crate) fn skip_stage(&mut self, stage: SkippedStage) {
+        self.partial = true;
+        self.skipped_stages.push(stage);
+    }
+}
+
+/// A search stage cut short to stay within `SearchRequest::time_budget_ms`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum SkippedStage {
+    /// Only the first `evaluated` of the engine's `total` candidates were checked against the
+    /// query and turned into snippets.
+    Candidates { evaluated: usize, total: usize },
+    /// The reranker did not run; hits keep the engine's order and scores.
+    Rerank,
+    /// Maximal-marginal-relevance reordering did not run.
+    Diversify,
+    /// Logic-Mesh entities were not attached to the hits.
+    Entities,
+    /// Spelling suggestions were not computed.
+    Spelling,
+}
+
+/// Query plan of one search: which filters narrowed the candidates, which engine answered,
+/// how each hit's score was composed, and where the time went.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueryExplain {
+    pub engine: SearchEngineKind,
+    /// Candidate filters in the order applied (`date`, `temporal`, `replay`, `geo`, `meta`,
+    /// `sketch`) with the frames left after each.
+    pub filters: Vec<ExplainFilter>,
+    /// Frames proposed by the sketch pre-filter; `None` when it did not run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sketch_candidates: Option<usize>,
+    /// Hits the ACL check denied; `None` without an ACL context. In audit mode denied hits
+    /// are counted but kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acl_denied: Option<usize>,
+    /// Hits dropped because their frames are quarantined.
+    #[serde(default)]
+    pub quarantined: usize,
+    /// Per-hit score components, in the order of `SearchResponse::hits`.
+    pub hits: Vec<HitExplain>,
+    /// Stages in the order they ran (`filters`, `sketch`, `retrieve`, `post`, then `rerank`
+    /// or `regroup` when requested).
+    pub stages: Vec<StageTiming>,
+}
+
+/// One candidate filter and the frames it left.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplainFilter {
+    pub name: String,
+    pub candidates: usize,
+}
+
+/// Score components of one hit: `score` is `engine_score` multiplied by both boost factors,
+/// unless a reranker or parent merge replaced it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HitExplain {
+    pub rank: usize,
+    pub frame_id: FrameId,
+    /// Snippet range, as in `SearchHit::range`.
+    pub range: (usize, usize),
+    /// Score assigned by the retrieval engine.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_score: Option<f32>,
+    /// Factor applied for memory importance (1 when none).
+    pub importance_boost: f32,
+    /// Factor applied for access frequency (1 when none).
+    pub access_boost: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+/// Wall time of one search stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub stage: String,
+    pub micros: u64,
+}
+
+impl QueryExplain {
+    pub(crate) fn record_stage(&mut self, stage: &str, elapsed: std::time::Duration) {
+        self.stages.push(StageTiming {
+            stage: stage.to_string(),
+            micros: u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+        });
+    }
+
+    /// Line the score components up with `hits` after a stage reordered, dropped, or merged
+    /// them; hits the retrieval stage did not return get neutral components.
+    pub(crate) fn align_hits(&mut self, hits: &[SearchHit]) {
+        let mut components = std::mem::take(&mut self.hits);
+        self.hits = hits
+            .iter()
+            .map(|hit| {
+                let found = components.iter().position(|component| {
+                    component.frame_id == hit.frame_id && component.range == hit.range
+                });
+                let mut component = match found {
+                    Some(index) => components.remove(index),
+                    None => HitExplain {
+                        rank: hit.rank,
+                        frame_id: hit.frame_id,
+                        range: hit.range,
+                        engine_score: None,
+                        importance_boost: 1.0,
+                        access_boost: 1.0,
+                        score: None,
+                    },
+                };
+                component.rank = hit.rank;
+                component.score = hit.score;
+                component
+            })
+            .collect();
+    }
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
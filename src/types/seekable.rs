@@ -0,0 +1,209 @@
+//! Seekable zstd payloads.
+//!
+//! UTF-8 payloads longer than [`SEEKABLE_BLOCK_SIZE`] that are compressed with zstd are written
+//! as independent zstd frames of that many canonical bytes each, followed by a seek table in
+//! the zstd seekable format: a skippable frame listing every frame's compressed and
+//! decompressed size. Decoders skip the table, so the payload still decodes in one pass, while
+//! snippet and range reads decode only the frames overlapping the bytes they need.
+//!
+//! ## Seek table
+//!
+//! ```text
+//! magic 0x184D2A5E (u32), frame size (u32)
+//! per frame: compressed size (u32), decompressed size (u32)[, checksum (u32)]
+//! frame count (u32), descriptor (u8, bit 7: checksums present), magic 0x8F92EAB1 (u32)
+//! ```
+
+use std::io::Cursor;
+
+/// Canonical bytes per zstd frame of a seekable payload; shorter payloads stay one frame.
+pub const SEEKABLE_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Length of the footer closing a seek table.
+pub const SEEK_TABLE_FOOTER_SIZE: usize = 9;
+
+const SKIPPABLE_MAGIC: u32 = 0x184D_2A5E;
+const SEEKABLE_MAGIC: u32 = 0x8F92_EAB1;
+const SKIPPABLE_HEADER_SIZE: usize = 8;
+const CHECKSUM_FLAG: u8 = 0x80;
+
+/// Compress `bytes` as seekable zstd frames of [`SEEKABLE_BLOCK_SIZE`] canonical bytes.
+pub(crate) fn compress_seekable(bytes: &[u8], level: i32) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut entries = Vec::new();
+    for block in bytes.chunks(SEEKABLE_BLOCK_SIZE) {
+        let compressed = zstd::encode_all(Cursor::new(block), level)?;
+        out.extend_from_slice(&compressed);
+        entries.push((table_u32(compressed.len())?, table_u32(block.len())?));
+    }
+    let frame_size = entries.len() * 8 + SEEK_TABLE_FOOTER_SIZE;
+    out.extend_from_slice(&SKIPPABLE_MAGIC.to_le_bytes());
+    out.extend_from_slice(&table_u32(frame_size)?.to_le_bytes());
+    for (compressed, decompressed) in &entries {
+        out.extend_from_slice(&compressed.to_le_bytes());
+        out.extend_from_slice(&decompressed.to_le_bytes());
+    }
+    out.extend_from_slice(&table_u32(entries.len())?.to_le_bytes());
+    out.push(0);
+    out.extend_from_slice(&SEEKABLE_MAGIC.to_le_bytes());
+    Ok(out)
+}
+
+fn table_u32(value: usize) -> std::io::Result<u32> {
+    u32::try_from(value).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "payload too large for a seek table",
+        )
+    })
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    let bytes = bytes.get(at..at + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Frames of a seekable payload, as listed in its seek table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SeekTable {
+    /// `(compressed, decompressed)` size of every frame, in payload order.
+    frames: Vec<(u64, u64)>,
+}
+
+/// Compressed bytes to decode for a canonical range, and where their output starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SeekSpan {
+    /// Offset of the first covering frame from the start of the payload.
+    pub compressed_offset: u64,
+    pub compressed_length: u64,
+    /// Canonical offset of the first decoded byte.
+    pub canonical_offset: u64,
+}
+
+impl SeekTable {
+    /// Length of the whole seek table, given the last [`SEEK_TABLE_FOOTER_SIZE`] bytes of a
+    /// payload; `None` when the payload does not end with one.
+    pub(crate) fn stored_len(footer: &[u8]) -> Option<u64> {
+        if footer.len() != SEEK_TABLE_FOOTER_SIZE || read_u32(footer, 5)? != SEEKABLE_MAGIC {
+            return None;
+        }
+        let descriptor = footer[4];
+        if descriptor & !CHECKSUM_FLAG != 0 {
+            return None;
+        }
+        let entry = if descriptor & CHECKSUM_FLAG == 0 {
+            8
+        } else {
+            12
+        };
+        let frames = u64::from(read_u32(footer, 0)?);
+        Some(SKIPPABLE_HEADER_SIZE as u64 + frames * entry + SEEK_TABLE_FOOTER_SIZE as u64)
+    }
+
+    /// Parse a seek table stored as `bytes`, the trailing [`Self::stored_len`] bytes of a
+    /// payload.
+    pub(crate) fn parse(bytes: &[u8]) -> Option<Self> {
+        let footer = bytes.get(bytes.len().checked_sub(SEEK_TABLE_FOOTER_SIZE)?..)?;
+        if Self::stored_len(footer)? != bytes.len() as u64
+            || read_u32(bytes, 0)? != SKIPPABLE_MAGIC
+            || read_u32(bytes, 4)? as usize != bytes.len() - SKIPPABLE_HEADER_SIZE
+        {
+            return None;
+        }
+        let entry = if footer[4] & CHECKSUM_FLAG == 0 {
+            8
+        } else {
+            12
+        };
+        let count = read_u32(footer, 0)? as usize;
+        let frames = (0..count)
+            .map(|index| {
+                let at = SKIPPABLE_HEADER_SIZE + index * entry;
+                Some((
+                    u64::from(read_u32(bytes, at)?),
+                    u64::from(read_u32(bytes, at + 4)?),
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { frames })
+    }
+
+    /// Total canonical length of the payload.
+    pub(crate) fn canonical_len(&self) -> u64 {
+        self.frames
+            .iter()
+            .map(|(_, decompressed)| decompressed)
+            .sum()
+    }
+
+    /// The run of frames covering canonical bytes `start..end`, or `None` when the range is
+    /// empty or past the end.
+    pub(crate) fn span(&self, start: u64, end: u64) -> Option<SeekSpan> {
+        if start >= end {
+            return None;
+        }
+        let mut compressed_offset = 0u64;
+        let mut canonical_offset = 0u64;
+        let mut first = None;
+        for (compressed, decompressed) in &self.frames {
+            let frame_end = canonical_offset + decompressed;
+            if first.is_none() && frame_end > start {
+                first = Some((compressed_offset, canonical_offset));
+            }
+            compressed_offset += compressed;
+            canonical_offset = frame_end;
+            if frame_end >= end {
+                let (offset, canonical) = first?;
+                return Some(SeekSpan {
+                    compressed_offset: offset,
+                    compressed_length: compressed_offset - offset,
+                    canonical_offset: canonical,
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seekable_payload_decodes_whole_and_by_frame() {
+        let text = (0..20_000).fold(String::new(), |mut text, index| {
+            text.push_str(&format!("line {index}\n"));
+            text
+        });
+        let bytes = text.as_bytes();
+        let payload = compress_seekable(bytes, 3).expect("compress");
+        // The seek table is a skippable frame, so plain decoding ignores it.
+        assert_eq!(
+            zstd::decode_all(Cursor::new(&payload)).expect("decode"),
+            bytes
+        );
+
+        let footer = &payload[payload.len() - SEEK_TABLE_FOOTER_SIZE..];
+        let table_len = SeekTable::stored_len(footer).expect("seekable") as usize;
+        let table = SeekTable::parse(&payload[payload.len() - table_len..]).expect("table");
+        assert_eq!(table.canonical_len(), bytes.len() as u64);
+        assert!(table.frames.len() > 2);
+
+        let start = SEEKABLE_BLOCK_SIZE as u64 + 100;
+        let span = table.span(start, start + 160).expect("span");
+        assert_eq!(span.canonical_offset, SEEKABLE_BLOCK_SIZE as u64);
+        let offset = span.compressed_offset as usize;
+        let frame = &payload[offset..offset + span.compressed_length as usize];
+        let decoded = zstd::decode_all(Cursor::new(frame)).expect("decode frame");
+        assert_eq!(decoded.len(), SEEKABLE_BLOCK_SIZE);
+        let local = (start - span.canonical_offset) as usize;
+        assert_eq!(
+            &decoded[local..local + 160],
+            &bytes[start as usize..start as usize + 160]
+        );
+        assert!(table.span(start, bytes.len() as u64 + 1).is_none());
+
+        let plain = zstd::encode_all(Cursor::new(bytes), 3).expect("compress");
+        assert!(SeekTable::stored_len(&plain[plain.len() - SEEK_TABLE_FOOTER_SIZE..]).is_none());
+    }
+}
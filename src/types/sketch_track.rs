@@ -954,6 +954,31 @@ impl SketchTrack {
         candidates
     }
 
+    /// Find candidates for several queries in one pass over the track.
+    ///
+    /// Each query comes with its own `max_candidates`; returns one list per query, in query
+    /// order, equal to what `find_candidates` returns for it alone.
+    #[must_use]
+    pub fn find_candidates_many(
+        &self,
+        queries: &[(QuerySketch, usize)],
+        hamming_threshold: u32,
+    ) -> Vec<Vec<(FrameId, f32)>> {
+        let mut candidates: Vec<Vec<(FrameId, f32)>> = vec![Vec::new(); queries.len()];
+        for entry in self.iter() {
+            for ((query, _), found) in queries.iter().zip(candidates.iter_mut()) {
+                if let Some(score) = query.score_entry(entry, hamming_threshold) {
+                    found.push((entry.frame_id, score));
+                }
+            }
+        }
+        for ((_, max_candidates), found) in queries.iter().zip(candidates.iter_mut()) {
+            found.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            found.truncate(*max_candidates);
+        }
+        candidates
+    }
+
     /// Get statistics about the track.
     #[must_use]
     pub fn stats(&self) -> SketchTrackStats {
@@ -1321,4 +1346,34 @@ mod tests {
             "Track should have entries"
         );
     }
+
+    #[test]
+    fn test_find_candidates_many_matches_single_queries() {
+        let mut track = SketchTrack::new(SketchVariant::Small);
+        let texts = [
+            "cats are wonderful pets that love to sleep and play",
+            "dogs are loyal companions for families and children",
+            "cats and dogs can live together as pets in harmony",
+            "programming in rust language provides memory safety",
+        ];
+        for (frame_id, text) in (0u64..).zip(texts) {
+            track.insert(generate_sketch(frame_id, text, SketchVariant::Small, None));
+        }
+
+        let queries = vec![
+            (
+                QuerySketch::from_query("cats wonderful pets love", SketchVariant::Small),
+                10,
+            ),
+            (
+                QuerySketch::from_query("rust memory safety", SketchVariant::Small),
+                1,
+            ),
+        ];
+        let batched = track.find_candidates_many(&queries, 32);
+        assert_eq!(batched.len(), 2);
+        for ((query, max_candidates), found) in queries.iter().zip(&batched) {
+            assert_eq!(found, &track.find_candidates(query, 32, *max_candidates));
+        }
+    }
 }
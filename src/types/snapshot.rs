@@ -0,0 +1,107 @@
+//! Named point-in-time snapshots.
+//!
+//! Frame payloads are written once and never move on commit, but index blobs, tracks, and the
+//! TOC itself are rewritten in place. A snapshot therefore archives a copy of every non-payload
+//! blob plus a TOC relocated to point at those copies. The archive lives in the payload region,
+//! so later commits append after it instead of overwriting it. The table of snapshots is stored
+//! in the TOC under [`SNAPSHOT_EXTENSION`].
+
+use serde::{Deserialize, Serialize};
+
+use super::manifest::{Header, Toc};
+
+/// TOC extension key holding the [`SnapshotTable`].
+pub const SNAPSHOT_EXTENSION: &str = "memvid.snapshots";
+
+/// A labelled view of the memory at one committed generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub label: String,
+    /// Footer generation of the commit the snapshot captures.
+    pub generation: u64,
+    /// Unix seconds when the snapshot was taken.
+    pub created_at: i64,
+    pub frame_count: u64,
+    /// Start of the data region (WAL end) when the archive was written. WAL growth shifts all
+    /// data by the same amount, so offsets below are corrected by the difference on open.
+    pub data_start: u64,
+    pub archive_offset: u64,
+    pub archive_length: u64,
+    /// Location of the relocated TOC inside the archive.
+    pub toc_offset: u64,
+    pub toc_length: u64,
+    pub toc_hash: [u8; 32],
+}
+
+impl Snapshot {
+    /// Amount the data region has moved since the snapshot was written.
+    #[must_use]
+    pub fn shift(&self, header: &Header) -> u64 {
+        header.data_start.saturating_sub(self.data_start)
+    }
+
+    /// Current `(offset, length)` of the archive.
+    #[must_use]
+    pub fn archive_range(&self, header: &Header) -> (u64, u64) {
+        (
+            self.archive_offset.saturating_add(self.shift(header)),
+            self.archive_length,
+        )
+    }
+}
+
+/// All snapshots recorded in a memory, ordered by creation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotTable {
+    snapshots: Vec<Snapshot>,
+}
+
+impl SnapshotTable {
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<&Snapshot> {
+        self.snapshots
+            .iter()
+            .find(|snapshot| snapshot.label == label)
+    }
+
+    #[must_use]
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+
+    pub fn insert(&mut self, snapshot: Snapshot) {
+        self.snapshots
+            .retain(|existing| existing.label != snapshot.label);
+        self.snapshots.push(snapshot);
+    }
+
+    pub fn remove(&mut self, label: &str) -> Option<Snapshot> {
+        let index = self
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.label == label)?;
+        Some(self.snapshots.remove(index))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+/// Current `(offset, length)` of every snapshot archive referenced by `toc`.
+///
+/// Decoding errors are treated as "no snapshots" so a damaged table never blocks opening.
+pub(crate) fn snapshot_archive_ranges(toc: &Toc, header: &Header) -> Vec<(u64, u64)> {
+    toc.extension::<SnapshotTable>(SNAPSHOT_EXTENSION)
+        .ok()
+        .flatten()
+        .map(|table| {
+            table
+                .snapshots
+                .iter()
+                .map(|snapshot| snapshot.archive_range(header))
+                .collect()
+        })
+        .unwrap_or_default()
+}
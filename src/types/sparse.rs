@@ -0,0 +1,570 @@
+//! Learned sparse retrieval track.
+//!
+//! A [`SparseEncoder`] (SPLADE-style) maps text to weights over its vocabulary, expanding a
+//! passage with related terms it never spells out. Indexed frames are kept in an inverted
+//! impact index: one posting list per vocabulary term holding `(frame, impact)` pairs, with
+//! impacts quantized to a byte against the track's largest weight. A query scores frames by
+//! the sum of its term weights times their impacts, so only the posting lists of the query's
+//! terms are read and no dense vectors are needed.
+//!
+//! The track is written after the footer like the sketch track and located by a
+//! [`SparseTrackManifest`] stored in the TOC under [`SPARSE_TRACK_EXTENSION`].
+//!
+//! ## Format
+//!
+//! ```text
+//! header (32 bytes): magic "MVSP", version u16, flags u16, frame_count u64,
+//!                    term_count u64, scale f32, reserved u32
+//! per term, ascending: term u32, postings u32, then per posting (ascending frame):
+//!                      frame delta as LEB128 varint, impact u8
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use blake3::Hasher;
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::manifest::Toc;
+use crate::error::{MemvidError, Result};
+
+/// TOC extension key holding the [`SparseTrackManifest`].
+pub const SPARSE_TRACK_EXTENSION: &str = "memvid.sparse_track";
+
+/// Magic bytes identifying the sparse track: "MVSP"
+pub const SPARSE_TRACK_MAGIC: [u8; 4] = *b"MVSP";
+
+/// Current version of the sparse track format.
+pub const SPARSE_TRACK_VERSION: u16 = 1;
+
+const HEADER_SIZE: usize = 32;
+
+/// Term weights for one passage, keyed by the encoder's vocabulary ids.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SparseVector {
+    /// `(term, weight)` pairs sorted by term, weights positive.
+    terms: Vec<(u32, f32)>,
+}
+
+impl SparseVector {
+    /// Build from `(term, weight)` pairs. Non-positive and non-finite weights are dropped and a
+    /// repeated term keeps its largest weight.
+    pub fn new(terms: impl IntoIterator<Item = (u32, f32)>) -> Self {
+        let mut merged: BTreeMap<u32, f32> = BTreeMap::new();
+        for (term, weight) in terms {
+            if weight.is_finite() && weight > 0.0 {
+                let slot = merged.entry(term).or_insert(0.0);
+                *slot = slot.max(weight);
+            }
+        }
+        Self {
+            terms: merged.into_iter().collect(),
+        }
+    }
+
+    /// `(term, weight)` pairs in term order.
+    #[must_use]
+    pub fn terms(&self) -> &[(u32, f32)] {
+        &self.terms
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Weight of `term`, if present.
+    #[must_use]
+    pub fn weight(&self, term: u32) -> Option<f32> {
+        self.terms
+            .binary_search_by_key(&term, |(id, _)| *id)
+            .ok()
+            .map(|index| self.terms[index].1)
+    }
+
+    /// Dot product with `other`.
+    #[must_use]
+    pub fn dot(&self, other: &SparseVector) -> f32 {
+        let (mut left, mut right) = (self.terms.iter().peekable(), other.terms.iter().peekable());
+        let mut sum = 0.0;
+        while let (Some(&&(a, wa)), Some(&&(b, wb))) = (left.peek(), right.peek()) {
+            match a.cmp(&b) {
+                std::cmp::Ordering::Less => {
+                    left.next();
+                }
+                std::cmp::Ordering::Greater => {
+                    right.next();
+                }
+                std::cmp::Ordering::Equal => {
+                    sum += wa * wb;
+                    left.next();
+                    right.next();
+                }
+            }
+        }
+        sum
+    }
+
+    /// Keep only the `max_terms` heaviest terms.
+    pub fn prune(&mut self, max_terms: usize) {
+        if self.terms.len() <= max_terms {
+            return;
+        }
+        self.terms
+            .sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        self.terms.truncate(max_terms);
+        self.terms.sort_by_key(|(term, _)| *term);
+    }
+}
+
+/// Encodes text into [`SparseVector`]s for `Memvid::index_sparse` and `SearchMode::Sparse`.
+///
+/// `SpladeEncoder` (`splade` feature) runs a SPLADE model exported to ONNX; any other learned
+/// sparse model can be plugged in by implementing this trait.
+pub trait SparseEncoder: Send + Sync {
+    /// Identifier recorded on the track, e.g. a model name. Queries must be encoded by the
+    /// encoder that built the track.
+    fn kind(&self) -> &str;
+
+    /// Encode a passage.
+    fn encode(&self, text: &str) -> Result<SparseVector>;
+
+    /// Encode several passages, in input order.
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<SparseVector>> {
+        texts.iter().map(|text| self.encode(text)).collect()
+    }
+
+    /// Encode a query. Defaults to [`SparseEncoder::encode`].
+    fn encode_query(&self, text: &str) -> Result<SparseVector> {
+        self.encode(text)
+    }
+}
+
+/// Location and shape of the persisted sparse track.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseTrackManifest {
+    pub bytes_offset: u64,
+    pub bytes_length: u64,
+    pub frame_count: u64,
+    pub term_count: u64,
+    pub posting_count: u64,
+    /// [`SparseEncoder::kind`] of the encoder that built the track.
+    pub encoder: String,
+    pub checksum: [u8; 32],
+}
+
+/// Size of the sparse track.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseTrackStats {
+    /// Frames with term weights.
+    pub frames: u64,
+    /// Distinct vocabulary terms with at least one posting.
+    pub terms: u64,
+    /// `(frame, impact)` pairs across all posting lists.
+    pub postings: u64,
+    /// Encoder that built the track, if any frame is indexed.
+    pub encoder: Option<String>,
+}
+
+/// Running totals for `Memvid::index_sparse`, reported after every committed batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseIndexReport {
+    /// Active frames missing from the track when indexing started.
+    pub candidates: usize,
+    /// Frames encoded and committed so far.
+    pub encoded: usize,
+    /// Frames skipped because they have no text to encode.
+    pub skipped_empty: usize,
+    /// Frames dropped from the track because they are no longer active.
+    pub removed: usize,
+    /// Batches committed so far.
+    pub batches: usize,
+}
+
+impl SparseIndexReport {
+    /// Frames not yet encoded or skipped.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.candidates
+            .saturating_sub(self.encoded + self.skipped_empty)
+    }
+}
+
+/// In-memory inverted impact index over frames' sparse vectors.
+#[derive(Debug, Clone, Default)]
+pub struct SparseTrack {
+    encoder: Option<String>,
+    /// Posting lists keyed by term, each sorted by frame id.
+    postings: BTreeMap<u32, Vec<(FrameId, f32)>>,
+    /// Terms of each indexed frame, for removal.
+    frames: BTreeMap<FrameId, Vec<u32>>,
+}
+
+impl SparseTrack {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encoder that built the track; `None` until the first insert.
+    #[must_use]
+    pub fn encoder(&self) -> Option<&str> {
+        self.encoder.as_deref()
+    }
+
+    /// Record `encoder` as the track's encoder. Existing entries are kept.
+    pub fn set_encoder(&mut self, encoder: impl Into<String>) {
+        self.encoder = Some(encoder.into());
+    }
+
+    /// Number of indexed frames.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, frame_id: FrameId) -> bool {
+        self.frames.contains_key(&frame_id)
+    }
+
+    /// Indexed frame ids in ascending order.
+    pub fn frame_ids(&self) -> impl Iterator<Item = FrameId> + '_ {
+        self.frames.keys().copied()
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> SparseTrackStats {
+        SparseTrackStats {
+            frames: self.frames.len() as u64,
+            terms: self.postings.len() as u64,
+            postings: self.postings.values().map(|list| list.len() as u64).sum(),
+            encoder: self.encoder.clone(),
+        }
+    }
+
+    /// Index `vector` for `frame_id`, replacing any previous entry.
+    pub fn insert(&mut self, frame_id: FrameId, vector: &SparseVector) {
+        self.remove(frame_id);
+        if vector.is_empty() {
+            return;
+        }
+        for &(term, weight) in vector.terms() {
+            let list = self.postings.entry(term).or_default();
+            match list.last() {
+                Some(&(last, _)) if last > frame_id => {
+                    let at = list.partition_point(|&(id, _)| id < frame_id);
+                    list.insert(at, (frame_id, weight));
+                }
+                _ => list.push((frame_id, weight)),
+            }
+        }
+        self.frames.insert(
+            frame_id,
+            vector.terms().iter().map(|(term, _)| *term).collect(),
+        );
+    }
+
+    /// Drop `frame_id` from the index, returning whether it was present.
+    pub fn remove(&mut self, frame_id: FrameId) -> bool {
+        let Some(terms) = self.frames.remove(&frame_id) else {
+            return false;
+        };
+        for term in terms {
+            if let Some(list) = self.postings.get_mut(&term) {
+                if let Ok(at) = list.binary_search_by_key(&frame_id, |&(id, _)| id) {
+                    list.remove(at);
+                }
+                if list.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+        true
+    }
+
+    /// Stored weights of `frame_id`, after quantization if the track was loaded from disk.
+    #[must_use]
+    pub fn vector(&self, frame_id: FrameId) -> Option<SparseVector> {
+        let terms = self.frames.get(&frame_id)?;
+        Some(SparseVector::new(terms.iter().filter_map(|term| {
+            let list = self.postings.get(term)?;
+            let at = list.binary_search_by_key(&frame_id, |&(id, _)| id).ok()?;
+            Some((*term, list[at].1))
+        })))
+    }
+
+    /// Every frame sharing a term with `query`, scored by the dot product, best first (ties by
+    /// frame id).
+    #[must_use]
+    pub fn search(&self, query: &SparseVector) -> Vec<(FrameId, f32)> {
+        let mut scores: HashMap<FrameId, f32> = HashMap::new();
+        for &(term, query_weight) in query.terms() {
+            if let Some(list) = self.postings.get(&term) {
+                for &(frame_id, weight) in list {
+                    *scores.entry(frame_id).or_insert(0.0) += query_weight * weight;
+                }
+            }
+        }
+        let mut ranked: Vec<(FrameId, f32)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+    }
+
+    fn max_weight(&self) -> f32 {
+        self.postings
+            .values()
+            .flatten()
+            .map(|(_, weight)| *weight)
+            .fold(0.0, f32::max)
+    }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        #[allow(clippy::cast_possible_truncation)]
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    buf.push(value as u8);
+}
+
+fn invalid(reason: impl Into<std::borrow::Cow<'static, str>>) -> MemvidError {
+    MemvidError::InvalidSparseTrack {
+        reason: reason.into(),
+    }
+}
+
+/// Cursor over the serialized track that reports truncation as an invalid track.
+struct Bytes<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Bytes<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or_else(|| invalid("sparse track is truncated"))?;
+        let slice = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("sparse track varint overflows u64"))
+    }
+}
+
+/// Write `track` at the writer's position, returning `(offset, length, checksum)`.
+pub fn write_sparse_track<W: Write + Seek>(
+    writer: &mut W,
+    track: &SparseTrack,
+) -> Result<(u64, u64, [u8; 32])> {
+    let offset = writer.stream_position()?;
+    let scale = track.max_weight();
+    let mut buf = Vec::with_capacity(HEADER_SIZE);
+    buf.extend_from_slice(&SPARSE_TRACK_MAGIC);
+    buf.extend_from_slice(&SPARSE_TRACK_VERSION.to_le_bytes());
+    buf.extend_from_slice(&0u16.to_le_bytes());
+    buf.extend_from_slice(&(track.frames.len() as u64).to_le_bytes());
+    buf.extend_from_slice(&(track.postings.len() as u64).to_le_bytes());
+    buf.extend_from_slice(&scale.to_le_bytes());
+    buf.extend_from_slice(&0u32.to_le_bytes());
+
+    for (&term, list) in &track.postings {
+        let count = u32::try_from(list.len()).map_err(|_| invalid("posting list too long"))?;
+        buf.extend_from_slice(&term.to_le_bytes());
+        buf.extend_from_slice(&count.to_le_bytes());
+        let mut previous = 0;
+        for &(frame_id, weight) in list {
+            write_varint(&mut buf, frame_id - previous);
+            previous = frame_id;
+            // Every stored weight is positive, so keep at least impact 1.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let impact = ((weight / scale) * 255.0).round().clamp(1.0, 255.0) as u8;
+            buf.push(impact);
+        }
+    }
+
+    writer.write_all(&buf)?;
+    let checksum = *Hasher::new().update(&buf).finalize().as_bytes();
+    Ok((offset, buf.len() as u64, checksum))
+}
+
+/// Read the track described by `manifest`, verifying its checksum.
+pub fn read_sparse_track<R: Read + Seek>(
+    reader: &mut R,
+    manifest: &SparseTrackManifest,
+) -> Result<SparseTrack> {
+    if manifest.bytes_length > crate::MAX_INDEX_BYTES {
+        return Err(invalid(format!(
+            "sparse track length {} exceeds the index limit",
+            manifest.bytes_length
+        )));
+    }
+    let len = usize::try_from(manifest.bytes_length)
+        .map_err(|_| invalid("sparse track exceeds addressable memory"))?;
+    let mut buf = vec![0u8; len];
+    reader.seek(SeekFrom::Start(manifest.bytes_offset))?;
+    reader.read_exact(&mut buf)?;
+    if *Hasher::new().update(&buf).finalize().as_bytes() != manifest.checksum {
+        return Err(invalid("sparse track checksum mismatch"));
+    }
+
+    let mut bytes = Bytes { buf: &buf, pos: 0 };
+    if bytes.take(4)? != SPARSE_TRACK_MAGIC {
+        return Err(invalid("bad sparse track magic"));
+    }
+    let version = bytes.take(2)?;
+    let version = u16::from_le_bytes([version[0], version[1]]);
+    if version != SPARSE_TRACK_VERSION {
+        return Err(invalid(format!(
+            "unsupported sparse track version {version}"
+        )));
+    }
+    bytes.take(2 + 8)?;
+    let term_count = bytes.take(8)?;
+    let term_count = u64::from_le_bytes(term_count.try_into().unwrap_or([0; 8]));
+    let scale = f32::from_bits(bytes.u32()?);
+    bytes.take(4)?;
+
+    let mut track = SparseTrack {
+        encoder: Some(manifest.encoder.clone()),
+        ..SparseTrack::default()
+    };
+    for _ in 0..term_count {
+        let term = bytes.u32()?;
+        let count = bytes.u32()?;
+        let mut list = Vec::with_capacity(count.min(1 << 16) as usize);
+        let mut frame_id: FrameId = 0;
+        for _ in 0..count {
+            frame_id = frame_id
+                .checked_add(bytes.varint()?)
+                .ok_or_else(|| invalid("sparse track frame id overflows"))?;
+            let weight = f32::from(bytes.u8()?) / 255.0 * scale;
+            list.push((frame_id, weight));
+            track.frames.entry(frame_id).or_default().push(term);
+        }
+        track.postings.insert(term, list);
+    }
+    Ok(track)
+}
+
+/// Sparse track manifest stored in `toc`; decoding errors are treated as "no track".
+pub(crate) fn sparse_track_manifest(toc: &Toc) -> Option<SparseTrackManifest> {
+    toc.extension::<SparseTrackManifest>(SPARSE_TRACK_EXTENSION)
+        .ok()
+        .flatten()
+}
+
+/// `(offset, length)` of the persisted sparse track, if any.
+pub(crate) fn sparse_track_range(toc: &Toc) -> Option<(u64, u64)> {
+    sparse_track_manifest(toc).map(|manifest| (manifest.bytes_offset, manifest.bytes_length))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn sparse_vector_merges_and_drops_weights() {
+        let vector = SparseVector::new([(7, 0.5), (3, 1.0), (7, 2.0), (9, 0.0), (4, f32::NAN)]);
+        assert_eq!(vector.terms(), &[(3, 1.0), (7, 2.0)]);
+        let other = SparseVector::new([(7, 1.5), (8, 1.0)]);
+        assert!((vector.dot(&other) - 3.0).abs() < f32::EPSILON);
+
+        let mut pruned = SparseVector::new([(1, 0.2), (2, 0.9), (3, 0.5)]);
+        pruned.prune(2);
+        assert_eq!(pruned.terms(), &[(2, 0.9), (3, 0.5)]);
+    }
+
+    #[test]
+    fn sparse_track_roundtrip_keeps_ranking() {
+        let mut track = SparseTrack::new();
+        track.set_encoder("test-splade");
+        track.insert(4, &SparseVector::new([(10, 2.0), (11, 0.5)]));
+        track.insert(1, &SparseVector::new([(10, 0.5), (12, 1.0)]));
+        track.insert(300, &SparseVector::new([(11, 1.0), (12, 0.25)]));
+
+        let mut cursor = Cursor::new(Vec::new());
+        let (offset, length, checksum) = write_sparse_track(&mut cursor, &track).unwrap();
+        let stats = track.stats();
+        let manifest = SparseTrackManifest {
+            bytes_offset: offset,
+            bytes_length: length,
+            frame_count: stats.frames,
+            term_count: stats.terms,
+            posting_count: stats.postings,
+            encoder: "test-splade".to_string(),
+            checksum,
+        };
+        let loaded = read_sparse_track(&mut cursor, &manifest).unwrap();
+        assert_eq!(loaded.stats(), stats);
+
+        let query = SparseVector::new([(10, 1.0), (12, 1.0)]);
+        let ranked: Vec<FrameId> = loaded
+            .search(&query)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ranked, vec![4, 1, 300]);
+        assert_eq!(
+            track
+                .search(&query)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            ranked
+        );
+
+        let mut corrupted = cursor.into_inner();
+        corrupted[HEADER_SIZE] ^= 0xff;
+        assert!(read_sparse_track(&mut Cursor::new(corrupted), &manifest).is_err());
+    }
+
+    #[test]
+    fn sparse_track_remove_prunes_postings() {
+        let mut track = SparseTrack::new();
+        track.insert(1, &SparseVector::new([(5, 1.0), (6, 1.0)]));
+        track.insert(2, &SparseVector::new([(6, 1.0)]));
+        assert!(track.remove(1));
+        assert!(!track.remove(1));
+        assert_eq!(track.stats().terms, 1);
+        assert_eq!(track.vector(2), Some(SparseVector::new([(6, 1.0)])));
+        assert!(track.search(&SparseVector::new([(5, 1.0)])).is_empty());
+    }
+}
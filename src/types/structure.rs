@@ -598,6 +598,42 @@ impl StructuredDocument {
     }
 }
 
+/// Extra-metadata key listing the definitions in a code chunk as `kind name@start-end`, with
+/// 1-based line ranges, separated by `, `.
+pub const CODE_SYMBOLS_KEY: &str = "code_symbols";
+
+/// A definition found in source code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeSymbol {
+    pub name: String,
+    /// `function`, `method`, `class`, `struct`, `enum`, `trait`, `interface`, `impl`,
+    /// `module`, `type`, or `macro`.
+    pub kind: String,
+    /// First line, 1-based.
+    pub start_line: usize,
+    /// Last line, inclusive.
+    pub end_line: usize,
+}
+
+impl CodeSymbol {
+    /// The `kind name@start-end` form stored under [`CODE_SYMBOLS_KEY`].
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!(
+            "{} {}@{}-{}",
+            self.kind, self.name, self.start_line, self.end_line
+        )
+    }
+
+    /// The `(kind, name)` of each label in a [`CODE_SYMBOLS_KEY`] value.
+    pub fn parse_labels(value: &str) -> impl Iterator<Item = (&str, &str)> {
+        value.split(", ").filter_map(|label| {
+            let (symbol, _lines) = label.rsplit_once('@')?;
+            symbol.split_once(' ')
+        })
+    }
+}
+
 /// A chunk produced by structural chunking.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructuredChunk {
@@ -619,6 +655,9 @@ pub struct StructuredChunk {
     pub char_start: usize,
     /// Character end offset
     pub char_end: usize,
+    /// Definitions in the chunk, when code was split by syntax.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<CodeSymbol>,
 }
 
 impl StructuredChunk {
@@ -634,6 +673,7 @@ impl StructuredChunk {
             context: None,
             char_start,
             char_end,
+            symbols: Vec::new(),
         }
     }
 
@@ -655,6 +695,7 @@ impl StructuredChunk {
             context: None,
             char_start,
             char_end,
+            symbols: Vec::new(),
         }
     }
 
@@ -679,6 +720,7 @@ impl StructuredChunk {
             context: Some(header_context.into()),
             char_start,
             char_end,
+            symbols: Vec::new(),
         }
     }
 
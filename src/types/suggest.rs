@@ -0,0 +1,185 @@
+//! Prefix suggestions for search-as-you-type.
+//!
+//! Stored in the TOC under [`SUGGEST_INDEX_EXTENSION`] and refreshed on every commit: titles,
+//! tags, and entity names are re-read from the frames and the Logic-Mesh, while word
+//! frequencies are updated from the frames each commit inserts or tombstones.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// TOC extension key holding the file's [`SuggestIndex`].
+pub const SUGGEST_INDEX_EXTENSION: &str = "memvid.suggest";
+
+/// Frames a word must appear in before it is suggested.
+pub const MIN_TERM_FRAMES: u32 = 2;
+
+/// Words tracked before the rarest are dropped.
+const MAX_TERMS: usize = 50_000;
+const MIN_TERM_CHARS: usize = 3;
+const MAX_TERM_CHARS: usize = 40;
+
+/// Where a suggestion came from; earlier kinds win when the same text has several sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SuggestionKind {
+    Title,
+    Entity,
+    Tag,
+    Term,
+}
+
+/// One completion returned by [`Memvid::suggest`](crate::Memvid::suggest).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// Completion text in its original casing (terms are lowercase).
+    pub text: String,
+    pub kind: SuggestionKind,
+    /// Number of active frames carrying the title, tag, entity, or word.
+    pub weight: u32,
+}
+
+/// Suggestion sources keyed by lowercase text, so a prefix lookup is a range scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuggestIndex {
+    /// Titles, tags, and entity names as `(original text, frame count)` per kind.
+    pub phrases: BTreeMap<(SuggestionKind, String), (String, u32)>,
+    /// Number of active frames each word appears in.
+    pub terms: BTreeMap<String, u32>,
+}
+
+impl SuggestIndex {
+    /// Count `frames` more frames carrying the title, tag, or entity `text`.
+    pub fn add_phrase(&mut self, kind: SuggestionKind, text: &str, frames: u32) {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            return;
+        }
+        self.phrases
+            .entry((kind, text.to_lowercase()))
+            .or_insert_with(|| (text, 0))
+            .1 += frames;
+    }
+
+    /// Count one more frame containing each of `words`.
+    pub fn add_terms(&mut self, words: &BTreeSet<String>) {
+        for word in words {
+            *self.terms.entry(word.clone()).or_default() += 1;
+        }
+        if self.terms.len() > MAX_TERMS {
+            // Words seen once are the noise (typos, identifiers) a cap should shed first
+            self.terms.retain(|_, frames| *frames >= MIN_TERM_FRAMES);
+        }
+    }
+
+    /// Forget one frame containing each of `words`.
+    pub fn remove_terms(&mut self, words: &BTreeSet<String>) {
+        for word in words {
+            if let Some(frames) = self.terms.get_mut(word) {
+                *frames = frames.saturating_sub(1);
+                if *frames == 0 {
+                    self.terms.remove(word);
+                }
+            }
+        }
+    }
+
+    /// Up to `limit` completions of `prefix` (case-insensitive), most frequent first. A word
+    /// already suggested as a title, entity, or tag is not repeated as a term.
+    #[must_use]
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Vec<Suggestion> {
+        let prefix = prefix.trim_start().to_lowercase();
+        if prefix.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let mut seen = BTreeSet::new();
+        let mut found = Vec::new();
+        for kind in [
+            SuggestionKind::Title,
+            SuggestionKind::Entity,
+            SuggestionKind::Tag,
+        ] {
+            let matches = self.phrases.range((kind, prefix.clone())..).take_while(
+                |((entry_kind, key), _)| *entry_kind == kind && key.starts_with(&prefix),
+            );
+            for ((_, key), (text, frames)) in matches {
+                if seen.insert(key.as_str()) {
+                    found.push(Suggestion {
+                        text: text.clone(),
+                        kind,
+                        weight: *frames,
+                    });
+                }
+            }
+        }
+        let terms = self
+            .terms
+            .range(prefix.clone()..)
+            .take_while(|(word, _)| word.starts_with(&prefix))
+            .filter(|(_, frames)| **frames >= MIN_TERM_FRAMES);
+        for (word, frames) in terms {
+            if seen.insert(word.as_str()) {
+                found.push(Suggestion {
+                    text: word.clone(),
+                    kind: SuggestionKind::Term,
+                    weight: *frames,
+                });
+            }
+        }
+        found.sort_by(|a, b| {
+            b.weight
+                .cmp(&a.weight)
+                .then(a.kind.cmp(&b.kind))
+                .then_with(|| a.text.cmp(&b.text))
+        });
+        found.truncate(limit);
+        found
+    }
+}
+
+/// Distinct lowercase words of `text` worth completing: alphabetic-led, 3 to 40 characters,
+/// and not rejected by `is_stopword`.
+pub(crate) fn suggestion_terms(text: &str, is_stopword: impl Fn(&str) -> bool) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| {
+            let chars = word.chars().count();
+            (MIN_TERM_CHARS..=MAX_TERM_CHARS).contains(&chars)
+                && word.chars().next().is_some_and(char::is_alphabetic)
+        })
+        .map(str::to_lowercase)
+        .filter(|word| !is_stopword(word))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggestions_rank_by_frequency_and_prefer_phrases() {
+        let mut index = SuggestIndex::default();
+        index.add_phrase(SuggestionKind::Title, "Kubernetes  Operators", 1);
+        index.add_phrase(SuggestionKind::Tag, "kubernetes", 1);
+        index.add_phrase(SuggestionKind::Tag, "kubernetes", 1);
+        let words = suggestion_terms("Kubernetes kubelet, the KUBELET and k8 42kube", |word| {
+            matches!(word, "the" | "and")
+        });
+        assert_eq!(words.len(), 2);
+        for _ in 0..3 {
+            index.add_terms(&words);
+        }
+        index.add_terms(&suggestion_terms("kubectl", |_| false));
+
+        let found = index.suggest("KUBE", 10);
+        let texts: Vec<&str> = found.iter().map(|s| s.text.as_str()).collect();
+        // "kubernetes" is a tag, so the word is not repeated; "kubectl" is seen once
+        assert_eq!(texts, vec!["kubelet", "kubernetes", "Kubernetes Operators"]);
+        assert_eq!(found[1].kind, SuggestionKind::Tag);
+        assert_eq!(index.suggest("kube", 1).len(), 1);
+        assert!(index.suggest("  ", 5).is_empty());
+
+        index.remove_terms(&words);
+        index.remove_terms(&words);
+        index.remove_terms(&words);
+        assert!(!index.terms.contains_key("kubelet"));
+    }
+}
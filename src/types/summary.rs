@@ -0,0 +1,115 @@
+//! Persisted document and scope summaries.
+//!
+//! Summaries live beside the memories track: each [`SummaryCard`] condenses one document frame
+//! or every document under a URI scope, and the whole [`SummaryTrack`] is stored in the TOC
+//! under [`SUMMARY_TRACK_EXTENSION`]. Timeline previews and `ask` context use a document's
+//! summary when one exists, so long documents are no longer represented by their first chunk.
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::manifest::Toc;
+use crate::Result;
+
+/// TOC extension key holding the [`SummaryTrack`].
+pub const SUMMARY_TRACK_EXTENSION: &str = "memvid.summaries";
+
+/// What a summary condenses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryTarget {
+    /// A single document frame, including its chunks.
+    Frame(FrameId),
+    /// Every active document whose URI starts with the scope prefix.
+    Scope(String),
+}
+
+/// A stored summary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SummaryCard {
+    pub target: SummaryTarget,
+    pub text: String,
+    /// [`Summarizer::kind`] of the summarizer that wrote the card.
+    pub summarizer: String,
+    /// Document frames the summary was built from.
+    pub sources: Vec<FrameId>,
+    /// Hash of the source payload checksums; a mismatch means the sources changed.
+    pub source_checksum: [u8; 32],
+    /// Unix seconds when the summary was written.
+    pub created_at: i64,
+}
+
+/// All summaries in a memory, at most one per target.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SummaryTrack {
+    pub cards: Vec<SummaryCard>,
+}
+
+impl SummaryTrack {
+    #[must_use]
+    pub fn get(&self, target: &SummaryTarget) -> Option<&SummaryCard> {
+        self.cards.iter().find(|card| &card.target == target)
+    }
+
+    #[must_use]
+    pub fn frame(&self, frame_id: FrameId) -> Option<&SummaryCard> {
+        self.get(&SummaryTarget::Frame(frame_id))
+    }
+
+    #[must_use]
+    pub fn scope(&self, scope: &str) -> Option<&SummaryCard> {
+        self.get(&SummaryTarget::Scope(scope.to_string()))
+    }
+
+    /// Insert `card`, replacing any summary for the same target.
+    pub fn upsert(&mut self, card: SummaryCard) {
+        match self
+            .cards
+            .iter_mut()
+            .find(|slot| slot.target == card.target)
+        {
+            Some(slot) => *slot = card,
+            None => self.cards.push(card),
+        }
+    }
+
+    /// Remove the summary for `target`, returning it.
+    pub fn remove(&mut self, target: &SummaryTarget) -> Option<SummaryCard> {
+        let index = self.cards.iter().position(|card| &card.target == target)?;
+        Some(self.cards.remove(index))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
+
+/// Produces summaries for `Memvid::summarize_frame` and `Memvid::summarize_scope`.
+pub trait Summarizer: Send + Sync {
+    /// Identifier recorded on every card, e.g. a model name.
+    fn kind(&self) -> &str;
+
+    /// Summarize one document's text.
+    fn summarize(&self, text: &str) -> Result<String>;
+
+    /// Merge per-document summaries into a scope summary. Defaults to summarizing their
+    /// concatenation.
+    fn combine(&self, summaries: &[&str]) -> Result<String> {
+        self.summarize(&summaries.join("\n\n"))
+    }
+}
+
+/// Summary track stored in `toc`; decoding errors are treated as "no summaries" so a damaged
+/// extension never breaks reads.
+pub(crate) fn summary_track(toc: &Toc) -> SummaryTrack {
+    toc.extension::<SummaryTrack>(SUMMARY_TRACK_EXTENSION)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
@@ -0,0 +1,125 @@
+//! Global tag edits and their audit trail.
+//!
+//! `Memvid::rename_tag`, `merge_tags`, and `delete_tag` rewrite the tags of every active frame
+//! in place and append a [`TagEditRecord`] to a bounded log persisted in the TOC (extension key
+//! [`TAG_LOG_EXTENSION`]). Read it back with `Memvid::tag_edits`.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+
+/// TOC extension key holding the persisted [`TagLog`].
+pub const TAG_LOG_EXTENSION: &str = "memvid.tag_log";
+
+/// Number of tag edits retained by default.
+pub const DEFAULT_TAG_LOG_CAPACITY: usize = 1024;
+
+/// A change applied to a tag across the whole memory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagEdit {
+    Rename { from: String, to: String },
+    Merge { sources: Vec<String>, into: String },
+    Delete { tag: String },
+}
+
+impl TagEdit {
+    /// Apply the edit to one frame's tags, keeping their order and dropping duplicates it
+    /// creates. Returns whether anything changed.
+    pub fn apply(&self, tags: &mut Vec<String>) -> bool {
+        let before = tags.clone();
+        let mut edited: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags.drain(..) {
+            let replacement = match self {
+                Self::Rename { from, to } if &tag == from => Some(to.clone()),
+                Self::Merge { sources, into } if sources.contains(&tag) => Some(into.clone()),
+                Self::Delete { tag: deleted } if &tag == deleted => None,
+                _ => Some(tag),
+            };
+            if let Some(tag) = replacement {
+                if !edited.contains(&tag) {
+                    edited.push(tag);
+                }
+            }
+        }
+        *tags = edited;
+        *tags != before
+    }
+}
+
+/// One applied tag edit and the frames it changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagEditRecord {
+    pub edit: TagEdit,
+    /// Frames whose tags changed, in id order.
+    pub frames: Vec<FrameId>,
+    /// Unix seconds.
+    pub applied_at: i64,
+    /// Author and agent set with `Memvid::set_commit_author` when the edit was made.
+    pub author: Option<String>,
+    pub agent_id: Option<String>,
+}
+
+/// Bounded log of tag edits, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagLog {
+    capacity: usize,
+    entries: VecDeque<TagEditRecord>,
+}
+
+impl Default for TagLog {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_TAG_LOG_CAPACITY,
+            entries: VecDeque::new(),
+        }
+    }
+}
+
+impl TagLog {
+    pub fn push(&mut self, record: TagEditRecord) {
+        self.entries.push_back(record);
+        while self.entries.len() > self.capacity.max(1) {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TagEditRecord> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn edits_keep_order_and_drop_duplicates() {
+        let mut frame_tags = tags(&["projext-x", "draft", "project-x"]);
+        let rename = TagEdit::Rename {
+            from: "projext-x".into(),
+            to: "project-x".into(),
+        };
+        assert!(rename.apply(&mut frame_tags));
+        assert_eq!(frame_tags, tags(&["project-x", "draft"]));
+        assert!(!rename.apply(&mut frame_tags));
+
+        let merge = TagEdit::Merge {
+            sources: tags(&["draft", "wip"]),
+            into: "in-progress".into(),
+        };
+        assert!(merge.apply(&mut frame_tags));
+        assert_eq!(frame_tags, tags(&["project-x", "in-progress"]));
+
+        let delete = TagEdit::Delete {
+            tag: "project-x".into(),
+        };
+        assert!(delete.apply(&mut frame_tags));
+        assert_eq!(frame_tags, tags(&["in-progress"]));
+    }
+}
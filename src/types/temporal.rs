@@ -1,6 +1,7 @@
 //! Temporal mention primitives (feature-gated by `temporal_track`).
 
 use serde::{Deserialize, Serialize};
+use time::{Date, Month, OffsetDateTime};
 
 use super::{common::FrameId, frame::AnchorSource};
 
@@ -90,6 +91,9 @@ pub struct TemporalMention {
     pub confidence: u16,
     pub tz_hint_minutes: i16,
     pub flags: TemporalMentionFlags,
+    /// Repetition of a `Recurring` mention, whose `ts_utc` is then the first occurrence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<TemporalRecurrence>,
 }
 
 impl TemporalMention {
@@ -114,8 +118,215 @@ impl TemporalMention {
             confidence,
             tz_hint_minutes,
             flags,
+            recurrence: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_recurrence(mut self, recurrence: TemporalRecurrence) -> Self {
+        self.recurrence = Some(recurrence);
+        self
+    }
+
+    /// Whether the mention falls within `[from, until]`; recurring mentions match when any
+    /// occurrence does.
+    #[must_use]
+    pub fn occurs_between(&self, from: i64, until: i64) -> bool {
+        match self.recurrence {
+            Some(rule) => rule.occurs_between(self.ts_utc, from, until),
+            None => (from..=until).contains(&self.ts_utc),
+        }
+    }
+}
+
+/// Period a recurring mention repeats on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl RecurrenceFrequency {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "DAILY",
+            Self::Weekly => "WEEKLY",
+            Self::Monthly => "MONTHLY",
+            Self::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// RRULE-style repetition of a recurring mention. The mention's `ts_utc` is the first
+/// occurrence (`DTSTART`); rules never end. "Quarterly" is a monthly rule with interval 3.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TemporalRecurrence {
+    pub frequency: RecurrenceFrequency,
+    /// Repeat every `interval` periods (`INTERVAL`), between 1 and [`Self::MAX_INTERVAL`].
+    pub interval: u8,
+    /// Weekly rules only: the days the event falls on (`BYDAY`), bit 0 for Monday through
+    /// bit 6 for Sunday. Zero means the weekday of the first occurrence.
+    pub weekdays: u8,
+}
+
+impl TemporalRecurrence {
+    pub const MAX_INTERVAL: u8 = 31;
+
+    const PRESENT: u16 = 1 << 15;
+
+    /// A rule repeating every `interval` periods, clamped to `1..=MAX_INTERVAL`.
+    #[must_use]
+    pub fn new(frequency: RecurrenceFrequency, interval: u8) -> Self {
+        Self {
+            frequency,
+            interval: interval.clamp(1, Self::MAX_INTERVAL),
+            weekdays: 0,
+        }
+    }
+
+    /// Restrict a weekly rule to the days in `weekdays` (bit 0 = Monday).
+    #[must_use]
+    pub fn on_weekdays(mut self, weekdays: u8) -> Self {
+        self.weekdays = weekdays & 0b0111_1111;
+        self
+    }
+
+    /// The rule as an iCalendar `RRULE` value, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH`.
+    #[must_use]
+    pub fn to_rrule(&self) -> String {
+        const DAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+        let mut rule = format!(
+            "FREQ={};INTERVAL={}",
+            self.frequency.as_str(),
+            self.interval
+        );
+        if self.weekdays != 0 {
+            let days: Vec<&str> = DAYS
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| self.weekdays & (1 << index) != 0)
+                .map(|(_, day)| *day)
+                .collect();
+            rule.push_str(";BYDAY=");
+            rule.push_str(&days.join(","));
+        }
+        rule
+    }
+
+    /// Packs the rule into the 16 spare bits of a temporal track mention record; zero means
+    /// "no rule", so tracks written before recurrences existed read back unchanged.
+    #[must_use]
+    pub fn to_bits(self) -> u16 {
+        let frequency = match self.frequency {
+            RecurrenceFrequency::Daily => 0,
+            RecurrenceFrequency::Weekly => 1,
+            RecurrenceFrequency::Monthly => 2,
+            RecurrenceFrequency::Yearly => 3,
+        };
+        Self::PRESENT
+            | (frequency << 12)
+            | (u16::from(self.interval & 0x1f) << 7)
+            | u16::from(self.weekdays & 0x7f)
+    }
+
+    #[must_use]
+    pub fn from_bits(bits: u16) -> Option<Self> {
+        if bits & Self::PRESENT == 0 {
+            return None;
+        }
+        let frequency = match (bits >> 12) & 0b11 {
+            0 => RecurrenceFrequency::Daily,
+            1 => RecurrenceFrequency::Weekly,
+            2 => RecurrenceFrequency::Monthly,
+            _ => RecurrenceFrequency::Yearly,
+        };
+        // Safe: both values are masked to fit in a u8
+        #[allow(clippy::cast_possible_truncation)]
+        let (interval, weekdays) = (((bits >> 7) & 0x1f) as u8, (bits & 0x7f) as u8);
+        Some(Self::new(frequency, interval).on_weekdays(weekdays))
+    }
+
+    /// First occurrence at or after `from` of a rule whose first occurrence is `start`.
+    #[must_use]
+    pub fn next_occurrence(&self, start: i64, from: i64) -> Option<i64> {
+        if from <= start {
+            return Some(start);
+        }
+        let interval = i64::from(self.interval.max(1));
+        let start_day = start.div_euclid(SECONDS_PER_DAY);
+        let time_of_day = start.rem_euclid(SECONDS_PER_DAY);
+        match self.frequency {
+            RecurrenceFrequency::Daily => {
+                let period = interval * SECONDS_PER_DAY;
+                let steps = (from - start + period - 1).div_euclid(period);
+                start.checked_add(steps.checked_mul(period)?)
+            }
+            RecurrenceFrequency::Weekly => {
+                let weekdays = if self.weekdays == 0 {
+                    1 << weekday_index(start_day)
+                } else {
+                    self.weekdays
+                };
+                let start_week = start_day - weekday_index(start_day);
+                let first_day = from.div_euclid(SECONDS_PER_DAY);
+                // Every matching week is reached within `interval` weeks of `from`
+                (first_day..=first_day + 7 * (interval + 1))
+                    .filter(|day| weekdays & (1 << weekday_index(*day)) != 0)
+                    .filter(|day| ((day - weekday_index(*day)) - start_week) / 7 % interval == 0)
+                    .map(|day| day * SECONDS_PER_DAY + time_of_day)
+                    .find(|timestamp| *timestamp >= from)
+            }
+            RecurrenceFrequency::Monthly | RecurrenceFrequency::Yearly => {
+                let step = if self.frequency == RecurrenceFrequency::Yearly {
+                    12 * interval
+                } else {
+                    interval
+                };
+                let first = OffsetDateTime::from_unix_timestamp(start).ok()?;
+                let target = OffsetDateTime::from_unix_timestamp(from).ok()?;
+                let month_index = |at: OffsetDateTime| {
+                    i64::from(at.year()) * 12 + i64::from(u8::from(at.month())) - 1
+                };
+                let elapsed = month_index(target) - month_index(first);
+                let mut months = elapsed.div_euclid(step) * step;
+                // The candidate month may fall just before `from`; the next one cannot
+                for _ in 0..2 {
+                    let index = month_index(first) + months;
+                    let year = i32::try_from(index.div_euclid(12)).ok()?;
+                    let month =
+                        Month::try_from(u8::try_from(index.rem_euclid(12) + 1).ok()?).ok()?;
+                    // Clamp to the month's last day ("the 31st" becomes the 30th in April)
+                    let date = (28..=first.day())
+                        .rev()
+                        .find_map(|day| Date::from_calendar_date(year, month, day).ok())?;
+                    let timestamp = date.midnight().assume_utc().unix_timestamp() + time_of_day;
+                    if timestamp >= from {
+                        return Some(timestamp);
+                    }
+                    months += step;
+                }
+                None
+            }
         }
     }
+
+    /// Whether an occurrence of a rule starting at `start` falls within `[from, until]`.
+    #[must_use]
+    pub fn occurs_between(&self, start: i64, from: i64, until: i64) -> bool {
+        self.next_occurrence(start, from)
+            .is_some_and(|occurrence| occurrence <= until)
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Days since Monday of the day `day` days after the Unix epoch (a Thursday).
+fn weekday_index(day: i64) -> i64 {
+    (day + 3).rem_euclid(7)
 }
 
 /// Persisted anchor metadata per frame.
@@ -181,6 +392,10 @@ pub struct TemporalFilter {
     pub phrase: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tz: Option<String>,
+    /// Only match recurring mentions, such as "every Monday", with an occurrence inside the
+    /// window. Without a window every frame with a recurring mention matches.
+    #[serde(default)]
+    pub recurring_only: bool,
 }
 
 impl TemporalFilter {
@@ -191,7 +406,7 @@ impl TemporalFilter {
             .as_ref()
             .map(|phrase| phrase.trim().is_empty())
             .unwrap_or(true);
-        self.start_utc.is_none() && self.end_utc.is_none() && phrase_empty
+        self.start_utc.is_none() && self.end_utc.is_none() && phrase_empty && !self.recurring_only
     }
 }
 
@@ -201,3 +416,56 @@ pub struct TemporalCapabilities {
     pub has_anchors: bool,
     pub has_mentions: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Monday 2024-01-01 09:00 UTC
+    const MONDAY_NINE: i64 = 1_704_099_600;
+    const DAY: i64 = 86_400;
+
+    #[test]
+    fn recurrence_bits_roundtrip_and_render_rrule() {
+        let rule = TemporalRecurrence::new(RecurrenceFrequency::Weekly, 2).on_weekdays(0b0000_1001);
+        assert_eq!(TemporalRecurrence::from_bits(rule.to_bits()), Some(rule));
+        assert_eq!(TemporalRecurrence::from_bits(0), None);
+        assert_eq!(rule.to_rrule(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH");
+    }
+
+    #[test]
+    fn recurrence_finds_next_occurrence() {
+        let daily = TemporalRecurrence::new(RecurrenceFrequency::Daily, 3);
+        assert_eq!(
+            daily.next_occurrence(MONDAY_NINE, MONDAY_NINE + 1),
+            Some(MONDAY_NINE + 3 * DAY)
+        );
+
+        let mondays_thursdays =
+            TemporalRecurrence::new(RecurrenceFrequency::Weekly, 1).on_weekdays(0b0000_1001);
+        assert_eq!(
+            mondays_thursdays.next_occurrence(MONDAY_NINE, MONDAY_NINE + DAY),
+            Some(MONDAY_NINE + 3 * DAY)
+        );
+
+        let fortnightly = TemporalRecurrence::new(RecurrenceFrequency::Weekly, 2);
+        assert_eq!(
+            fortnightly.next_occurrence(MONDAY_NINE, MONDAY_NINE + DAY),
+            Some(MONDAY_NINE + 14 * DAY)
+        );
+        assert!(!fortnightly.occurs_between(
+            MONDAY_NINE,
+            MONDAY_NINE + 7 * DAY - 1,
+            MONDAY_NINE + 13 * DAY
+        ));
+
+        // Quarterly from January 31st lands on the last day of shorter months
+        let quarterly = TemporalRecurrence::new(RecurrenceFrequency::Monthly, 3);
+        let january_31 = MONDAY_NINE + 30 * DAY;
+        let april_30 = january_31 + (29 + 31 + 30) * DAY;
+        assert_eq!(
+            quarterly.next_occurrence(january_31, january_31 + 1),
+            Some(april_30)
+        );
+    }
+}
@@ -0,0 +1,559 @@
+//! Token embedding track for late-interaction (ColBERT-style) reranking.
+//!
+//! A [`TokenEncoder`] maps text to one embedding per token instead of one per passage. Frames
+//! under the scopes passed to `Memvid::index_token_embeddings` keep those embeddings here,
+//! each token quantized to int8 against its largest component. A reranker configured with
+//! `RerankerKind::LateInteraction` scores BM25 or sketch candidates by MaxSim: every query
+//! token is matched with its most similar passage token and the similarities are averaged.
+//!
+//! The track is written after the footer like the sketch track and located by a
+//! [`TokenTrackManifest`] stored in the TOC under [`TOKEN_TRACK_EXTENSION`].
+//!
+//! ## Format
+//!
+//! ```text
+//! header (32 bytes): magic "MVTK", version u16, flags u16, frame_count u64,
+//!                    dimension u32, reserved u32 x 3
+//! per frame, ascending: frame_id u64, tokens u32, then per token:
+//!                       scale f32, dimension x i8 codes
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use blake3::Hasher;
+use serde::{Deserialize, Serialize};
+
+use super::common::FrameId;
+use super::manifest::Toc;
+use crate::error::{MemvidError, Result};
+
+/// TOC extension key holding the [`TokenTrackManifest`].
+pub const TOKEN_TRACK_EXTENSION: &str = "memvid.token_track";
+
+/// Magic bytes identifying the token track: "MVTK"
+pub const TOKEN_TRACK_MAGIC: [u8; 4] = *b"MVTK";
+
+/// Current version of the token track format.
+pub const TOKEN_TRACK_VERSION: u16 = 1;
+
+const HEADER_SIZE: usize = 32;
+
+/// One embedding per token of a passage or query, stored row-major.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenEmbeddings {
+    dimension: usize,
+    data: Vec<f32>,
+}
+
+impl TokenEmbeddings {
+    /// Build from per-token rows, which must all have `dimension` components.
+    pub fn new(dimension: usize, rows: impl IntoIterator<Item = Vec<f32>>) -> Result<Self> {
+        let mut data = Vec::new();
+        for row in rows {
+            if row.len() != dimension {
+                return Err(MemvidError::VecDimensionMismatch {
+                    expected: u32::try_from(dimension).unwrap_or(u32::MAX),
+                    actual: row.len(),
+                });
+            }
+            data.extend(row);
+        }
+        Ok(Self { dimension, data })
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Number of tokens.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len().checked_div(self.dimension).unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Token rows in order.
+    pub fn rows(&self) -> impl Iterator<Item = &[f32]> + '_ {
+        self.data.chunks_exact(self.dimension.max(1))
+    }
+
+    /// Mean over `self`'s tokens of their best dot product with a token of `document`.
+    ///
+    /// With unit-length embeddings the score is a cosine similarity in `[-1, 1]`; it is 0 when
+    /// either side has no tokens.
+    #[must_use]
+    pub fn max_sim(&self, document: &TokenEmbeddings) -> f32 {
+        if self.is_empty() || document.is_empty() || self.dimension != document.dimension {
+            return 0.0;
+        }
+        let total: f32 = self
+            .rows()
+            .map(|query| {
+                document
+                    .rows()
+                    .map(|token| dot(query, token))
+                    .fold(f32::NEG_INFINITY, f32::max)
+            })
+            .sum();
+        #[allow(clippy::cast_precision_loss)]
+        let tokens = self.len() as f32;
+        total / tokens
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Encodes text into [`TokenEmbeddings`] for `Memvid::index_token_embeddings` and
+/// late-interaction reranking.
+///
+/// `ColbertEncoder` (`colbert` feature) runs a ColBERT model exported to ONNX; any other
+/// multi-vector model can be plugged in by implementing this trait.
+pub trait TokenEncoder: Send + Sync {
+    /// Identifier recorded on the track, e.g. a model name. Queries must be encoded by the
+    /// encoder that built the track.
+    fn kind(&self) -> &str;
+
+    /// Components per token embedding.
+    fn dimension(&self) -> usize;
+
+    /// Encode a passage.
+    fn encode(&self, text: &str) -> Result<TokenEmbeddings>;
+
+    /// Encode several passages, in input order.
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<TokenEmbeddings>> {
+        texts.iter().map(|text| self.encode(text)).collect()
+    }
+
+    /// Encode a query. Defaults to [`TokenEncoder::encode`].
+    fn encode_query(&self, text: &str) -> Result<TokenEmbeddings> {
+        self.encode(text)
+    }
+}
+
+/// Location and shape of the persisted token track.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTrackManifest {
+    pub bytes_offset: u64,
+    pub bytes_length: u64,
+    pub frame_count: u64,
+    pub token_count: u64,
+    pub dimension: u32,
+    /// [`TokenEncoder::kind`] of the encoder that built the track.
+    pub encoder: String,
+    /// URI prefixes whose frames are indexed; empty means every frame.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub checksum: [u8; 32],
+}
+
+/// Size and coverage of the token track.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTrackStats {
+    /// Frames with stored token embeddings.
+    pub frames: u64,
+    /// Token embeddings across all frames.
+    pub tokens: u64,
+    /// Components per token embedding, 0 while the track is empty.
+    pub dimension: u32,
+    /// Encoder that built the track, if any frame is indexed.
+    pub encoder: Option<String>,
+    /// URI prefixes whose frames are indexed; empty means every frame.
+    pub scopes: Vec<String>,
+}
+
+/// Running totals for `Memvid::index_token_embeddings`, reported after every committed batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenIndexReport {
+    /// Active in-scope frames missing from the track when indexing started.
+    pub candidates: usize,
+    /// Frames encoded and committed so far.
+    pub encoded: usize,
+    /// Frames skipped because they have no text to encode.
+    pub skipped_empty: usize,
+    /// Frames dropped from the track because they are no longer active.
+    pub removed: usize,
+    /// Batches committed so far.
+    pub batches: usize,
+}
+
+impl TokenIndexReport {
+    /// Frames not yet encoded or skipped.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.candidates
+            .saturating_sub(self.encoded + self.skipped_empty)
+    }
+}
+
+/// Token embeddings of one frame, int8-quantized per token.
+#[derive(Debug, Clone, PartialEq)]
+struct QuantizedTokens {
+    scales: Vec<f32>,
+    codes: Vec<i8>,
+}
+
+impl QuantizedTokens {
+    fn encode(embeddings: &TokenEmbeddings) -> Self {
+        let mut scales = Vec::with_capacity(embeddings.len());
+        let mut codes = Vec::with_capacity(embeddings.data.len());
+        for row in embeddings.rows() {
+            let max_abs = row.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+            if max_abs == 0.0 || !max_abs.is_finite() {
+                scales.push(0.0);
+                codes.extend(std::iter::repeat_n(0, row.len()));
+                continue;
+            }
+            let scale = max_abs / 127.0;
+            scales.push(scale);
+            codes.extend(row.iter().map(|value| {
+                #[allow(clippy::cast_possible_truncation)]
+                let code = (value / scale).round().clamp(-127.0, 127.0) as i8;
+                code
+            }));
+        }
+        Self { scales, codes }
+    }
+
+    fn decode(&self, dimension: usize) -> TokenEmbeddings {
+        let data = self
+            .codes
+            .chunks_exact(dimension.max(1))
+            .zip(&self.scales)
+            .flat_map(|(row, scale)| row.iter().map(move |code| f32::from(*code) * scale))
+            .collect();
+        TokenEmbeddings { dimension, data }
+    }
+}
+
+/// In-memory token embeddings of the indexed frames.
+#[derive(Debug, Clone, Default)]
+pub struct TokenTrack {
+    encoder: Option<String>,
+    dimension: usize,
+    scopes: Vec<String>,
+    frames: BTreeMap<FrameId, QuantizedTokens>,
+}
+
+impl TokenTrack {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encoder that built the track; `None` until the first insert.
+    #[must_use]
+    pub fn encoder(&self) -> Option<&str> {
+        self.encoder.as_deref()
+    }
+
+    /// Record `encoder` and its embedding `dimension`. Existing entries are kept.
+    pub fn set_encoder(&mut self, encoder: impl Into<String>, dimension: usize) {
+        self.encoder = Some(encoder.into());
+        self.dimension = dimension;
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// URI prefixes whose frames are indexed; empty means every frame.
+    #[must_use]
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// Add `scopes` to the indexed prefixes; an empty list widens the track to every frame.
+    pub fn add_scopes(&mut self, scopes: &[String]) {
+        if scopes.is_empty() {
+            self.scopes.clear();
+            return;
+        }
+        if self.scopes.is_empty() && !self.frames.is_empty() {
+            // Already covers every frame.
+            return;
+        }
+        for scope in scopes {
+            if !self.scopes.contains(scope) {
+                self.scopes.push(scope.clone());
+            }
+        }
+        self.scopes.sort();
+    }
+
+    /// Whether a frame at `uri` falls under the track's scopes.
+    #[must_use]
+    pub fn in_scope(&self, uri: &str) -> bool {
+        self.scopes.is_empty()
+            || self
+                .scopes
+                .iter()
+                .any(|scope| uri.starts_with(scope.as_str()))
+    }
+
+    /// Number of indexed frames.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, frame_id: FrameId) -> bool {
+        self.frames.contains_key(&frame_id)
+    }
+
+    /// Indexed frame ids in ascending order.
+    pub fn frame_ids(&self) -> impl Iterator<Item = FrameId> + '_ {
+        self.frames.keys().copied()
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> TokenTrackStats {
+        TokenTrackStats {
+            frames: self.frames.len() as u64,
+            tokens: self.token_count(),
+            dimension: u32::try_from(self.dimension).unwrap_or(u32::MAX),
+            encoder: self.encoder.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+
+    /// Store `embeddings` for `frame_id`, replacing any previous entry. Fails if the
+    /// dimension differs from the track's.
+    pub fn insert(&mut self, frame_id: FrameId, embeddings: &TokenEmbeddings) -> Result<()> {
+        if embeddings.dimension() != self.dimension {
+            return Err(MemvidError::VecDimensionMismatch {
+                expected: u32::try_from(self.dimension).unwrap_or(u32::MAX),
+                actual: embeddings.dimension(),
+            });
+        }
+        if embeddings.is_empty() {
+            self.frames.remove(&frame_id);
+        } else {
+            self.frames
+                .insert(frame_id, QuantizedTokens::encode(embeddings));
+        }
+        Ok(())
+    }
+
+    /// Drop `frame_id`, returning whether it was present.
+    pub fn remove(&mut self, frame_id: FrameId) -> bool {
+        self.frames.remove(&frame_id).is_some()
+    }
+
+    /// Stored embeddings of `frame_id`, dequantized.
+    #[must_use]
+    pub fn embeddings(&self, frame_id: FrameId) -> Option<TokenEmbeddings> {
+        self.frames
+            .get(&frame_id)
+            .map(|tokens| tokens.decode(self.dimension))
+    }
+
+    fn token_count(&self) -> u64 {
+        self.frames
+            .values()
+            .map(|tokens| tokens.scales.len() as u64)
+            .sum()
+    }
+}
+
+fn invalid(reason: impl Into<std::borrow::Cow<'static, str>>) -> MemvidError {
+    MemvidError::InvalidTokenTrack {
+        reason: reason.into(),
+    }
+}
+
+/// Write `track` at the writer's position, returning `(offset, length, checksum)`.
+pub fn write_token_track<W: Write + Seek>(
+    writer: &mut W,
+    track: &TokenTrack,
+) -> Result<(u64, u64, [u8; 32])> {
+    let offset = writer.stream_position()?;
+    let dimension =
+        u32::try_from(track.dimension).map_err(|_| invalid("token dimension too large"))?;
+    let mut buf = Vec::with_capacity(HEADER_SIZE + track.frames.len() * 12);
+    buf.extend_from_slice(&TOKEN_TRACK_MAGIC);
+    buf.extend_from_slice(&TOKEN_TRACK_VERSION.to_le_bytes());
+    buf.extend_from_slice(&0u16.to_le_bytes());
+    buf.extend_from_slice(&(track.frames.len() as u64).to_le_bytes());
+    buf.extend_from_slice(&dimension.to_le_bytes());
+    buf.extend_from_slice(&[0u8; 12]);
+
+    for (&frame_id, tokens) in &track.frames {
+        let count =
+            u32::try_from(tokens.scales.len()).map_err(|_| invalid("too many tokens in frame"))?;
+        buf.extend_from_slice(&frame_id.to_le_bytes());
+        buf.extend_from_slice(&count.to_le_bytes());
+        for (scale, row) in tokens
+            .scales
+            .iter()
+            .zip(tokens.codes.chunks_exact(track.dimension.max(1)))
+        {
+            buf.extend_from_slice(&scale.to_le_bytes());
+            buf.extend(row.iter().map(|code| code.to_le_bytes()[0]));
+        }
+    }
+
+    writer.write_all(&buf)?;
+    let checksum = *Hasher::new().update(&buf).finalize().as_bytes();
+    Ok((offset, buf.len() as u64, checksum))
+}
+
+/// Read the track described by `manifest`, verifying its checksum.
+pub fn read_token_track<R: Read + Seek>(
+    reader: &mut R,
+    manifest: &TokenTrackManifest,
+) -> Result<TokenTrack> {
+    if manifest.bytes_length > crate::MAX_INDEX_BYTES {
+        return Err(invalid(format!(
+            "token track length {} exceeds the index limit",
+            manifest.bytes_length
+        )));
+    }
+    let len = usize::try_from(manifest.bytes_length)
+        .map_err(|_| invalid("token track exceeds addressable memory"))?;
+    let mut buf = vec![0u8; len];
+    reader.seek(SeekFrom::Start(manifest.bytes_offset))?;
+    reader.read_exact(&mut buf)?;
+    if *Hasher::new().update(&buf).finalize().as_bytes() != manifest.checksum {
+        return Err(invalid("token track checksum mismatch"));
+    }
+    if buf.len() < HEADER_SIZE || buf[..4] != TOKEN_TRACK_MAGIC {
+        return Err(invalid("bad token track magic"));
+    }
+    let version = u16::from_le_bytes([buf[4], buf[5]]);
+    if version != TOKEN_TRACK_VERSION {
+        return Err(invalid(format!(
+            "unsupported token track version {version}"
+        )));
+    }
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&buf[8..16]);
+    let frame_count = u64::from_le_bytes(word);
+    let dimension = u32::from_le_bytes([buf[16], buf[17], buf[18], buf[19]]) as usize;
+
+    let mut track = TokenTrack {
+        encoder: Some(manifest.encoder.clone()),
+        dimension,
+        scopes: manifest.scopes.clone(),
+        frames: BTreeMap::new(),
+    };
+    let mut pos = HEADER_SIZE;
+    let mut take = |len: usize| -> Result<&[u8]> {
+        let end = pos
+            .checked_add(len)
+            .filter(|end| *end <= buf.len())
+            .ok_or_else(|| invalid("token track is truncated"))?;
+        let slice = &buf[pos..end];
+        pos = end;
+        Ok(slice)
+    };
+    for _ in 0..frame_count {
+        word.copy_from_slice(take(8)?);
+        let frame_id = u64::from_le_bytes(word);
+        let count = take(4)?;
+        let count = u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize;
+        let mut tokens = QuantizedTokens {
+            scales: Vec::with_capacity(count.min(1 << 12)),
+            codes: Vec::with_capacity(count.min(1 << 12) * dimension),
+        };
+        for _ in 0..count {
+            let scale = take(4)?;
+            tokens
+                .scales
+                .push(f32::from_le_bytes([scale[0], scale[1], scale[2], scale[3]]));
+            tokens.codes.extend(
+                take(dimension)?
+                    .iter()
+                    .map(|byte| i8::from_le_bytes([*byte])),
+            );
+        }
+        track.frames.insert(frame_id, tokens);
+    }
+    Ok(track)
+}
+
+/// Token track manifest stored in `toc`; decoding errors are treated as "no track".
+pub(crate) fn token_track_manifest(toc: &Toc) -> Option<TokenTrackManifest> {
+    toc.extension::<TokenTrackManifest>(TOKEN_TRACK_EXTENSION)
+        .ok()
+        .flatten()
+}
+
+/// `(offset, length)` of the persisted token track, if any.
+pub(crate) fn token_track_range(toc: &Toc) -> Option<(u64, u64)> {
+    token_track_manifest(toc).map(|manifest| (manifest.bytes_offset, manifest.bytes_length))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn embeddings(rows: &[[f32; 2]]) -> TokenEmbeddings {
+        TokenEmbeddings::new(2, rows.iter().map(|row| row.to_vec())).unwrap()
+    }
+
+    #[test]
+    fn max_sim_matches_each_query_token_to_its_best_token() {
+        let query = embeddings(&[[1.0, 0.0], [0.0, 1.0]]);
+        let both = embeddings(&[[0.0, 1.0], [1.0, 0.0]]);
+        let one = embeddings(&[[1.0, 0.0], [1.0, 0.0]]);
+        assert!((query.max_sim(&both) - 1.0).abs() < f32::EPSILON);
+        assert!((query.max_sim(&one) - 0.5).abs() < f32::EPSILON);
+        assert!(query.max_sim(&TokenEmbeddings::default()).abs() < f32::EPSILON);
+        assert!(TokenEmbeddings::new(2, [vec![1.0]]).is_err());
+    }
+
+    #[test]
+    fn token_track_roundtrip_quantizes_within_tolerance() {
+        let mut track = TokenTrack::new();
+        track.set_encoder("test-colbert", 2);
+        track.add_scopes(&["mv2://docs/".to_string()]);
+        track
+            .insert(7, &embeddings(&[[0.6, 0.8], [-1.0, 0.0]]))
+            .unwrap();
+        track.insert(2, &embeddings(&[[0.0, 1.0]])).unwrap();
+        assert!(track.insert(3, &TokenEmbeddings::default()).is_err());
+
+        let mut cursor = Cursor::new(Vec::new());
+        let (offset, length, checksum) = write_token_track(&mut cursor, &track).unwrap();
+        let stats = track.stats();
+        let manifest = TokenTrackManifest {
+            bytes_offset: offset,
+            bytes_length: length,
+            frame_count: stats.frames,
+            token_count: stats.tokens,
+            dimension: stats.dimension,
+            encoder: "test-colbert".to_string(),
+            scopes: stats.scopes.clone(),
+            checksum,
+        };
+        let loaded = read_token_track(&mut cursor, &manifest).unwrap();
+        assert_eq!(loaded.stats(), stats);
+        assert!(loaded.in_scope("mv2://docs/a"));
+        assert!(!loaded.in_scope("mv2://notes/a"));
+        let restored = loaded.embeddings(7).unwrap();
+        for (got, want) in restored.rows().flatten().zip([0.6, 0.8, -1.0, 0.0]) {
+            assert!((got - want).abs() < 0.01);
+        }
+
+        let mut corrupted = cursor.into_inner();
+        corrupted[HEADER_SIZE] ^= 0xff;
+        assert!(read_token_track(&mut Cursor::new(corrupted), &manifest).is_err());
+    }
+}
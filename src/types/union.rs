@@ -0,0 +1,61 @@
+//! Results served by `MemvidUnion`, each tagged with the member file it came from.
+//!
+//! Frame ids are only unique within one file, so every hit and timeline entry carries the
+//! member's position in the union (`source`) and its path.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::ask::AskCitation;
+use super::frame::TimelineEntry;
+use super::search::SearchHit;
+
+/// A search hit from one member of a union.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnionSearchHit {
+    /// Position of the member in `MemvidUnion::paths`.
+    pub source: usize,
+    pub path: PathBuf,
+    /// The hit as the member returned it; `rank` is renumbered across the union.
+    #[serde(flatten)]
+    pub hit: SearchHit,
+}
+
+/// Search results merged across a union.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnionSearchResponse {
+    pub query: String,
+    pub elapsed_ms: u128,
+    /// Sum of the members' `total_hits`.
+    pub total_hits: usize,
+    pub hits: Vec<UnionSearchHit>,
+    /// Concatenated snippets of the merged hits.
+    pub context: String,
+}
+
+/// A timeline entry from one member of a union.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnionTimelineEntry {
+    pub source: usize,
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub entry: TimelineEntry,
+}
+
+/// `ask` over a union: retrieval is merged, the answer comes from one member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnionAskResponse {
+    pub question: String,
+    /// Answer of the member holding the best merged hit; `None` for context-only requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// Member that produced `answer` and `citations`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer_source: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub citations: Vec<AskCitation>,
+    pub hits: Vec<UnionSearchHit>,
+    pub context: String,
+    pub elapsed_ms: u128,
+}
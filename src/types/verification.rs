@@ -13,8 +13,15 @@ pub struct DoctorOptions {
     pub rebuild_lex_index: bool,
     #[serde(default)]
     pub rebuild_vec_index: bool,
+    /// Supersede exact duplicate frames (see `Memvid::find_duplicates`) with their oldest copy.
+    #[serde(default)]
+    pub supersede_duplicates: bool,
     #[serde(default)]
     pub vacuum: bool,
+    /// Compact the file when the probe finds orphaned bytes (index segments and blobs no
+    /// longer referenced by the TOC). Orphans are reported either way.
+    #[serde(default)]
+    pub reclaim_orphans: bool,
     #[serde(default)]
     pub dry_run: bool,
     /// Suppress debug output when true.
@@ -25,6 +32,10 @@ pub struct DoctorOptions {
 /// Version identifier embedded in `DoctorPlan` for compatibility checks.
 pub const DOCTOR_PLAN_VERSION: u32 = 1;
 
+/// Version of the JSON layout produced by [`DoctorReport::to_json`]. Fields may be added
+/// within a version; renaming or removing one bumps it.
+pub const DOCTOR_REPORT_VERSION: u32 = 1;
+
 /// Coarse phases executed by the doctor orchestrator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -33,6 +44,7 @@ pub enum DoctorPhaseKind {
     HeaderHealing,
     WalReplay,
     IndexRebuild,
+    Deduplicate,
     Vacuum,
     Finalize,
     Verify,
@@ -49,15 +61,17 @@ pub enum DoctorActionKind {
     RebuildTimeIndex,
     RebuildLexIndex,
     RebuildVecIndex,
+    SupersedeDuplicates,
     VacuumCompaction,
+    ReclaimOrphans,
     RecomputeToc,
     UpdateHeader,
     DeepVerify,
     NoOp,
 }
 
-/// Severity assigned to individual findings.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Severity assigned to individual findings, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DoctorSeverity {
     Info,
@@ -90,8 +104,11 @@ pub enum DoctorFindingCode {
     MerkleMismatch,
     SegmentCatalogInconsistent,
     VacuumIncomplete,
+    OrphanedBytes,
     LockContention,
     UnsupportedFeature,
+    /// The file is under a legal hold, so the doctor only plans.
+    LegalHold,
     InternalError,
 }
 
@@ -159,6 +176,12 @@ pub struct DoctorPlan {
     pub findings: Vec<DoctorFinding>,
     #[serde(default)]
     pub phases: Vec<DoctorPhasePlan>,
+    /// Bytes between the WAL and the footer that the TOC no longer references.
+    #[serde(default)]
+    pub reclaimable_bytes: u64,
+    /// What executing the plan would change.
+    #[serde(default)]
+    pub diff: DoctorPlanDiff,
 }
 
 impl DoctorPlan {
@@ -179,6 +202,58 @@ impl DoctorPlan {
     }
 }
 
+/// Indexes the doctor can rebuild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorIndexKind {
+    Time,
+    Lex,
+    Vec,
+}
+
+/// A byte range the plan would overwrite, and the action responsible.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorByteRange {
+    pub offset: u64,
+    pub length: u64,
+    pub action: DoctorActionKind,
+}
+
+/// An index whose manifest the plan would replace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorIndexBump {
+    pub index: DoctorIndexKind,
+    /// Footer generation holding the current manifest; the rebuilt one lands in a later one.
+    pub from_generation: u64,
+}
+
+/// Structured "what would change" summary of a plan, derived from the probe.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorPlanDiff {
+    /// Frames whose payloads or index entries would be rewritten.
+    #[serde(default)]
+    pub frames_affected: u64,
+    /// Byte ranges of the current file that would be overwritten or relocated.
+    #[serde(default)]
+    pub rewritten_ranges: Vec<DoctorByteRange>,
+    /// Indexes that would be rebuilt.
+    #[serde(default)]
+    pub index_bumps: Vec<DoctorIndexBump>,
+}
+
+impl DoctorPlanDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames_affected == 0 && self.rewritten_ranges.is_empty() && self.index_bumps.is_empty()
+    }
+
+    /// Total length of `rewritten_ranges`.
+    #[must_use]
+    pub fn rewritten_bytes(&self) -> u64 {
+        self.rewritten_ranges.iter().map(|range| range.length).sum()
+    }
+}
+
 /// Phase-level plan that groups related actions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DoctorPhasePlan {
@@ -230,6 +305,10 @@ pub enum DoctorActionDetail {
     VacuumStats {
         active_frames: u64,
     },
+    OrphanedBytes {
+        orphaned_ranges: usize,
+        reclaimable_bytes: u64,
+    },
 }
 
 /// Aggregated metrics reported after doctor execution.
@@ -243,6 +322,12 @@ pub struct DoctorMetrics {
     pub actions_completed: usize,
     #[serde(default)]
     pub actions_skipped: usize,
+    /// Orphaned bytes found by the probe (see `DoctorPlan::reclaimable_bytes`).
+    #[serde(default)]
+    pub reclaimable_bytes: u64,
+    /// Bytes the file shrank by during the run.
+    #[serde(default)]
+    pub reclaimed_bytes: u64,
 }
 
 /// Duration recorded for a single phase.
@@ -308,6 +393,9 @@ pub struct DoctorActionReport {
 /// Composite report returned by doctor after executing a plan.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DoctorReport {
+    /// Layout version of the serialized report (see [`DOCTOR_REPORT_VERSION`]).
+    #[serde(default = "DoctorReport::default_version")]
+    pub version: u32,
     /// Plan that was executed (possibly with adjustments).
     pub plan: DoctorPlan,
     pub status: DoctorStatus,
@@ -321,6 +409,33 @@ pub struct DoctorReport {
     pub verification: Option<VerificationReport>,
 }
 
+impl DoctorReport {
+    fn default_version() -> u32 {
+        DOCTOR_REPORT_VERSION
+    }
+
+    /// Findings at `threshold` severity or above.
+    pub fn findings_at_or_above(
+        &self,
+        threshold: DoctorSeverity,
+    ) -> impl Iterator<Item = &DoctorFinding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.severity >= threshold)
+    }
+
+    /// Whether a check-mode run should fail: any finding at `threshold` or above.
+    #[must_use]
+    pub fn exceeds(&self, threshold: DoctorSeverity) -> bool {
+        self.findings_at_or_above(threshold).next().is_some()
+    }
+
+    /// Serialize the report in its stable, versioned JSON layout.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
 /// Metadata returned by `verify` (or attached to a doctor report when requested).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationReport {
@@ -0,0 +1,68 @@
+//! Video recordings stored as a media frame, CLIP-indexed keyframes, and aligned transcripts.
+//!
+//! `Memvid::put_video` (features `clip` and `whisper`) and `Memvid::put_decoded_video` write one
+//! parent frame holding the raw video, one child frame per sampled keyframe, and one child
+//! frame per transcript segment. Keyframe frames carry their offset under
+//! [`VIDEO_OFFSET_MS_KEY`] and the transcript spoken while they were on screen, and their CLIP
+//! embeddings land in the `ClipIndex`, so a `search_clip` hit resolves to a seekable offset via
+//! `Memvid::media_offset_ms`. Transcript segments record the keyframe they were spoken over
+//! under [`VIDEO_KEYFRAME_MS_KEY`].
+//!
+//! Decoding is pluggable through [`VideoDecoder`]; `crate::video::FfmpegVideoDecoder` shells
+//! out to an `ffmpeg` binary.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// `kind` of the frame holding the raw video.
+pub const VIDEO_FRAME_KIND: &str = "video";
+/// `kind` of a sampled keyframe frame.
+pub const VIDEO_KEYFRAME_FRAME_KIND: &str = "video_keyframe";
+/// Extra-metadata key holding a keyframe's offset in milliseconds.
+pub const VIDEO_OFFSET_MS_KEY: &str = "video_offset_ms";
+/// Extra-metadata key linking a transcript segment to the keyframe on screen when it started.
+pub const VIDEO_KEYFRAME_MS_KEY: &str = "video_keyframe_ms";
+
+/// One sampled still from a video.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoKeyframe {
+    /// Offset from the start of the video in milliseconds.
+    pub offset_ms: u64,
+    /// Encoded image (JPEG or PNG).
+    pub image: Vec<u8>,
+    /// MIME type of `image`.
+    pub mime: String,
+}
+
+/// Audio track decoded to 16kHz mono samples, ready for Whisper.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VideoAudio {
+    pub pcm: Vec<f32>,
+    pub duration_secs: f32,
+}
+
+/// Extracts keyframes and the audio track from a video container.
+pub trait VideoDecoder: Send + Sync {
+    /// Decoder identifier, e.g. `ffmpeg`.
+    fn kind(&self) -> &str;
+
+    /// Stills sampled every `interval_ms`, in offset order.
+    fn keyframes(&self, bytes: &[u8], interval_ms: u64) -> Result<Vec<VideoKeyframe>>;
+
+    /// The audio track, or `None` when the video has none.
+    fn audio(&self, bytes: &[u8]) -> Result<Option<VideoAudio>>;
+}
+
+/// WAL sequences written by one video ingestion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoReceipt {
+    /// Sequence of the video frame.
+    pub parent_sequence: u64,
+    /// Sequences of the keyframe frames, in offset order.
+    pub keyframe_sequences: Vec<u64>,
+    /// Sequences of the transcript segment frames, in transcript order.
+    pub segment_sequences: Vec<u64>,
+    /// Length of the video in milliseconds.
+    pub duration_ms: u64,
+}
@@ -131,6 +131,7 @@ pub enum VecIndex {
         documents: Vec<VecDocument>,
     },
     Compressed(crate::vec_pq::QuantizedVecIndex),
+    Scalar(crate::vec_scalar::ScalarQuantizedVecIndex),
     #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
     Hnsw(HnswVecIndex),
 }
@@ -152,6 +153,13 @@ impl VecIndex {
         bytes: &[u8],
         _compression: crate::VectorCompression,
     ) -> Result<Self> {
+        if crate::vec_pq::full_precision_pq_section_len(bytes).is_some() {
+            return crate::vec_pq::QuantizedVecIndex::decode(bytes).map(Self::Compressed);
+        }
+        if crate::vec_scalar::ScalarQuantizedVecIndex::is_scalar_encoded(bytes) {
+            return crate::vec_scalar::ScalarQuantizedVecIndex::decode(bytes).map(Self::Scalar);
+        }
+
         // Try uncompressed format first, regardless of compression flag.
         // This is necessary because MIN_VECTORS_FOR_PQ threshold (100 vectors)
         // causes most segments to be stored as uncompressed even when Pq96 is requested.
@@ -254,6 +262,7 @@ impl VecIndex {
                 hits
             }
             VecIndex::Compressed(quantized) => quantized.search(query, limit),
+            VecIndex::Scalar(scalar) => scalar.search(query, limit),
             #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
             VecIndex::Hnsw(index) => index.search(query, limit),
         }
@@ -267,7 +276,7 @@ impl VecIndex {
                     .iter()
                     .map(|doc| (doc.frame_id, doc.embedding.as_slice())),
             ),
-            VecIndex::Compressed(_) => {
+            VecIndex::Compressed(_) | VecIndex::Scalar(_) => {
                 // Compressed vectors don't have direct f32 access
                 Box::new(std::iter::empty())
             }
@@ -286,7 +295,7 @@ impl VecIndex {
                 .iter()
                 .find(|doc| doc.frame_id == frame_id)
                 .map(|doc| doc.embedding.as_slice()),
-            VecIndex::Compressed(_) => {
+            VecIndex::Compressed(_) | VecIndex::Scalar(_) => {
                 // Compressed vectors don't have direct f32 access
                 None
             }
@@ -299,6 +308,42 @@ impl VecIndex {
         }
     }
 
+    /// Owned copies of the stored vectors. Scalar-quantized indexes return their dequantized
+    /// approximations; PQ and HNSW indexes return nothing, as with [`Self::entries`].
+    #[must_use]
+    pub fn owned_entries(&self) -> Vec<(FrameId, Vec<f32>)> {
+        if let VecIndex::Scalar(scalar) = self {
+            return scalar.dequantized();
+        }
+        self.entries()
+            .map(|(frame_id, embedding)| (frame_id, embedding.to_vec()))
+            .collect()
+    }
+
+    /// Whether the index holds a vector for `frame_id`, in any encoding.
+    #[must_use]
+    pub fn contains(&self, frame_id: FrameId) -> bool {
+        match self {
+            VecIndex::Uncompressed { documents } => {
+                documents.iter().any(|doc| doc.frame_id == frame_id)
+            }
+            VecIndex::Compressed(quantized) => quantized.contains(frame_id),
+            VecIndex::Scalar(scalar) => scalar.contains(frame_id),
+            #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
+            VecIndex::Hnsw(index) => index.ids.contains(&frame_id),
+        }
+    }
+
+    /// Full-precision vectors stored next to a quantized index, used for exact rescoring.
+    #[must_use]
+    pub fn full_precision(&self) -> Option<&crate::vec_pq::FullPrecisionStore> {
+        if let VecIndex::Compressed(quantized) = self {
+            quantized.full_precision()
+        } else {
+            None
+        }
+    }
+
     pub fn remove(&mut self, frame_id: FrameId) {
         match self {
             VecIndex::Uncompressed { documents } => {
@@ -307,6 +352,7 @@ impl VecIndex {
             VecIndex::Compressed(_quantized) => {
                 // Compressed indices are immutable
             }
+            VecIndex::Scalar(scalar) => scalar.remove(frame_id),
             #[cfg(any(feature = "vec", feature = "hnsw_bench"))]
             VecIndex::Hnsw(_) => {
                 // HNSW indices are immutable in this implementation
@@ -9,6 +9,8 @@
 //! 3. Each vector is encoded as 96 bytes (one u8 index per subspace)
 //! 4. Search uses ADC (Asymmetric Distance Computation) with lookup tables
 
+use std::collections::HashMap;
+
 use blake3::hash;
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +32,20 @@ const SUBSPACE_DIM: usize = 4; // Dimensions per subspace
 const NUM_CENTROIDS: usize = 256; // 2^8 centroids (encoded as u8)
 const TOTAL_DIM: usize = NUM_SUBSPACES * SUBSPACE_DIM; // 384
 
+/// Prefix of a PQ index stored together with its full-precision vectors:
+/// `magic (8) | PQ section length (u64 LE) | PQ section | f32 LE vectors in document order`.
+pub const FULL_PRECISION_PQ_MAGIC: [u8; 8] = *b"MVPQFP01";
+/// Bytes before the PQ section of a full-precision PQ index.
+pub const FULL_PRECISION_PQ_PREAMBLE: usize = 16;
+
+/// Length of the PQ section if `preamble` starts a full-precision PQ index.
+#[must_use]
+pub fn full_precision_pq_section_len(preamble: &[u8]) -> Option<u64> {
+    let magic = preamble.get(..8)?;
+    let len = preamble.get(8..FULL_PRECISION_PQ_PREAMBLE)?;
+    (magic == FULL_PRECISION_PQ_MAGIC).then(|| u64::from_le_bytes(len.try_into().unwrap_or([0; 8])))
+}
+
 /// Codebook for one subspace: 256 centroids, each with `SUBSPACE_DIM` dimensions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubspaceCodebook {
@@ -233,6 +249,8 @@ pub struct QuantizedVecDocument {
 pub struct QuantizedVecIndexBuilder {
     documents: Vec<QuantizedVecDocument>,
     quantizer: Option<ProductQuantizer>,
+    /// Full-precision vectors kept for exact rescoring, flattened in document order.
+    full_precision: Option<Vec<f32>>,
 }
 
 impl QuantizedVecIndexBuilder {
@@ -249,6 +267,14 @@ impl QuantizedVecIndexBuilder {
         Ok(())
     }
 
+    /// Store full-precision vectors after the PQ codes so searches can rescore candidates
+    /// exactly. Must be called before adding documents.
+    #[must_use]
+    pub fn with_full_precision(mut self) -> Self {
+        self.full_precision = Some(Vec::new());
+        self
+    }
+
     /// Add document with pre-trained quantizer
     pub fn add_document(&mut self, frame_id: FrameId, embedding: Vec<f32>) -> Result<()> {
         let quantizer = self
@@ -259,6 +285,9 @@ impl QuantizedVecIndexBuilder {
             })?;
 
         let codes = quantizer.encode(&embedding)?;
+        if let Some(full_precision) = self.full_precision.as_mut() {
+            full_precision.extend_from_slice(&embedding);
+        }
 
         self.documents
             .push(QuantizedVecDocument { frame_id, codes });
@@ -272,8 +301,21 @@ impl QuantizedVecIndexBuilder {
         })?;
 
         let vector_count = self.documents.len() as u64;
-        let bytes =
+        let mut bytes =
             bincode::serde::encode_to_vec(&(quantizer.clone(), self.documents), vec_config())?;
+        if let Some(full_precision) = self.full_precision {
+            let pq_len = bytes.len() as u64;
+            let mut framed = Vec::with_capacity(
+                FULL_PRECISION_PQ_PREAMBLE + bytes.len() + full_precision.len() * 4,
+            );
+            framed.extend_from_slice(&FULL_PRECISION_PQ_MAGIC);
+            framed.extend_from_slice(&pq_len.to_le_bytes());
+            framed.append(&mut bytes);
+            for value in full_precision {
+                framed.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes = framed;
+        }
         let checksum = *hash(&bytes).as_bytes();
 
         Ok(QuantizedVecIndexArtifact {
@@ -295,14 +337,112 @@ pub struct QuantizedVecIndexArtifact {
     pub compression_ratio: f64,
 }
 
+/// Where the full-precision vectors of a PQ index live, relative to the start of its blob.
+///
+/// Only offsets are held in memory; vectors are read from disk when a search rescores.
+#[derive(Debug, Clone)]
+pub struct FullPrecisionStore {
+    offset: u64,
+    dimension: u32,
+    order: Vec<FrameId>,
+    positions: HashMap<FrameId, u64>,
+}
+
+impl FullPrecisionStore {
+    fn new(offset: u64, dimension: u32, documents: &[QuantizedVecDocument]) -> Self {
+        let order: Vec<FrameId> = documents.iter().map(|doc| doc.frame_id).collect();
+        let positions = order
+            .iter()
+            .enumerate()
+            .map(|(position, &frame_id)| (frame_id, position as u64))
+            .collect();
+        Self {
+            offset,
+            dimension,
+            order,
+            positions,
+        }
+    }
+
+    fn record_len(&self) -> u64 {
+        u64::from(self.dimension) * 4
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    /// Byte range of the vector for `frame_id`, relative to the start of the index blob.
+    #[must_use]
+    pub fn vector_range(&self, frame_id: FrameId) -> Option<(u64, u64)> {
+        let position = *self.positions.get(&frame_id)?;
+        Some((
+            self.offset + position * self.record_len(),
+            self.record_len(),
+        ))
+    }
+
+    /// Byte range of all vectors, relative to the start of the index blob.
+    #[must_use]
+    pub fn section_range(&self) -> (u64, u64) {
+        (self.offset, self.order.len() as u64 * self.record_len())
+    }
+
+    /// Decode one vector read from [`Self::vector_range`].
+    #[must_use]
+    pub fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect()
+    }
+
+    /// Decode the whole section read from [`Self::section_range`] into `(frame_id, vector)`,
+    /// skipping vectors removed from the index.
+    #[must_use]
+    pub fn decode_section(&self, bytes: &[u8]) -> Vec<(FrameId, Vec<f32>)> {
+        let record_len = usize::try_from(self.record_len()).unwrap_or(usize::MAX);
+        self.order
+            .iter()
+            .zip(bytes.chunks_exact(record_len.max(1)))
+            .filter(|(frame_id, _)| self.positions.contains_key(frame_id))
+            .map(|(&frame_id, record)| (frame_id, Self::decode_vector(record)))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QuantizedVecIndex {
     quantizer: ProductQuantizer,
     documents: Vec<QuantizedVecDocument>,
+    full_precision: Option<FullPrecisionStore>,
 }
 
 impl QuantizedVecIndex {
+    /// Decode a PQ index. For full-precision indexes only the preamble and PQ section are
+    /// needed; the vectors after them are located but not read.
     pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if let Some(pq_len) = full_precision_pq_section_len(bytes) {
+            let end = usize::try_from(pq_len)
+                .ok()
+                .and_then(|len| len.checked_add(FULL_PRECISION_PQ_PREAMBLE))
+                .filter(|&end| end <= bytes.len())
+                .ok_or_else(|| MemvidError::InvalidToc {
+                    reason: "truncated full-precision PQ index".into(),
+                })?;
+            let mut index = Self::decode_codes(&bytes[FULL_PRECISION_PQ_PREAMBLE..end])?;
+            index.full_precision = Some(FullPrecisionStore::new(
+                end as u64,
+                index.quantizer.dimension,
+                &index.documents,
+            ));
+            return Ok(index);
+        }
+        Self::decode_codes(bytes)
+    }
+
+    fn decode_codes(bytes: &[u8]) -> Result<Self> {
         // Try decoding with current format (with dimension field)
         let config = bincode::config::standard()
             .with_fixed_int_encoding()
@@ -318,6 +458,7 @@ impl QuantizedVecIndex {
                 return Ok(Self {
                     quantizer,
                     documents,
+                    full_precision: None,
                 });
             }
         }
@@ -349,9 +490,24 @@ impl QuantizedVecIndex {
         Ok(Self {
             quantizer,
             documents,
+            full_precision: None,
         })
     }
 
+    /// Full-precision vectors stored with this index, if it was built with them.
+    #[must_use]
+    pub fn full_precision(&self) -> Option<&FullPrecisionStore> {
+        self.full_precision.as_ref()
+    }
+
+    #[must_use]
+    pub fn contains(&self, frame_id: FrameId) -> bool {
+        match &self.full_precision {
+            Some(store) => store.positions.contains_key(&frame_id),
+            None => self.documents.iter().any(|doc| doc.frame_id == frame_id),
+        }
+    }
+
     /// Search using asymmetric distance computation
     #[must_use]
     pub fn search(&self, query: &[f32], limit: usize) -> Vec<VecSearchHit> {
@@ -383,6 +539,9 @@ impl QuantizedVecIndex {
 
     pub fn remove(&mut self, frame_id: FrameId) {
         self.documents.retain(|doc| doc.frame_id != frame_id);
+        if let Some(store) = self.full_precision.as_mut() {
+            store.positions.remove(&frame_id);
+        }
     }
 
     /// Get compression statistics
@@ -415,7 +574,11 @@ pub struct CompressionStats {
 }
 
 /// K-means clustering for a single subspace
-fn kmeans(vectors: &[Vec<f32>], k: usize, max_iterations: usize) -> Result<Vec<Vec<f32>>> {
+pub(crate) fn kmeans(
+    vectors: &[Vec<f32>],
+    k: usize,
+    max_iterations: usize,
+) -> Result<Vec<Vec<f32>>> {
     if vectors.is_empty() {
         return Err(MemvidError::InvalidQuery {
             reason: "Cannot run k-means on empty vector set".to_string(),
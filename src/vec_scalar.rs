@@ -0,0 +1,377 @@
+//! Scalar-quantized vector storage (int8 and binary).
+//!
+//! Two encodings trade score precision for size without any training step:
+//! 1. `Int8`: each vector is scaled by its largest absolute component and rounded to
+//!    `[-127, 127]` (4x smaller than f32). Distances are approximate L2, computed from an
+//!    int8 dot product against the equally quantized query.
+//! 2. `Binary`: one sign bit per dimension, packed 8 per byte (32x smaller). Distances are
+//!    hamming distances between sign patterns, useful for ranking but not comparable to L2.
+//!
+//! Both encodings work for any dimension and are searched by a linear scan.
+
+use blake3::hash;
+use serde::{Deserialize, Serialize};
+
+use crate::simd::{dot_i8_simd, hamming_distance_simd};
+use crate::types::{FrameId, VectorCompression};
+use crate::vec::{VecIndexArtifact, VecSearchHit};
+use crate::{MemvidError, Result};
+
+/// Prefix identifying a scalar-quantized vec index blob.
+pub const SCALAR_VEC_MAGIC: [u8; 8] = *b"MVSQ0001";
+
+#[allow(clippy::cast_possible_truncation)]
+const SCALAR_DECODE_LIMIT: usize = crate::MAX_INDEX_BYTES as usize;
+
+fn scalar_config() -> impl bincode::config::Config {
+    bincode::config::standard()
+        .with_fixed_int_encoding()
+        .with_little_endian()
+}
+
+/// Encoding used by a [`ScalarQuantizedVecIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScalarEncoding {
+    Int8,
+    Binary,
+}
+
+impl ScalarEncoding {
+    /// Scalar encoding selected by `compression`, if any.
+    #[must_use]
+    pub fn from_compression(compression: &VectorCompression) -> Option<Self> {
+        match compression {
+            VectorCompression::Int8 => Some(Self::Int8),
+            VectorCompression::Binary => Some(Self::Binary),
+            VectorCompression::None | VectorCompression::Pq96 => None,
+        }
+    }
+
+    /// Bytes needed to store one vector of `dimension` components.
+    #[must_use]
+    pub fn code_len(self, dimension: usize) -> usize {
+        match self {
+            Self::Int8 => dimension,
+            Self::Binary => dimension.div_ceil(8),
+        }
+    }
+
+    fn encode(self, embedding: &[f32]) -> ScalarCode {
+        match self {
+            Self::Int8 => {
+                let max_abs = embedding.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+                if max_abs == 0.0 || !max_abs.is_finite() {
+                    return ScalarCode {
+                        scale: 0.0,
+                        norm_sq: 0.0,
+                        codes: vec![0; embedding.len()],
+                        bits: Vec::new(),
+                    };
+                }
+                let scale = max_abs / 127.0;
+                let mut norm = 0i64;
+                let codes = embedding
+                    .iter()
+                    .map(|value| {
+                        #[allow(clippy::cast_possible_truncation)]
+                        let code = (value / scale).round().clamp(-127.0, 127.0) as i8;
+                        norm += i64::from(code) * i64::from(code);
+                        code
+                    })
+                    .collect();
+                #[allow(clippy::cast_precision_loss)]
+                let norm_sq = norm as f32 * scale * scale;
+                ScalarCode {
+                    scale,
+                    norm_sq,
+                    codes,
+                    bits: Vec::new(),
+                }
+            }
+            Self::Binary => {
+                let mut bits = vec![0u8; self.code_len(embedding.len())];
+                for (i, value) in embedding.iter().enumerate() {
+                    if *value > 0.0 {
+                        bits[i / 8] |= 1 << (i % 8);
+                    }
+                }
+                ScalarCode {
+                    scale: 1.0,
+                    norm_sq: 0.0,
+                    codes: Vec::new(),
+                    bits,
+                }
+            }
+        }
+    }
+}
+
+struct ScalarCode {
+    scale: f32,
+    norm_sq: f32,
+    codes: Vec<i8>,
+    bits: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScalarVecDocument {
+    pub frame_id: FrameId,
+    /// Int8: value of one code step. Binary: unused.
+    pub scale: f32,
+    /// Int8: squared norm of the dequantized vector. Binary: unused.
+    pub norm_sq: f32,
+    /// Int8 codes, one per dimension. Empty for binary vectors.
+    pub codes: Vec<i8>,
+    /// Packed sign bits. Empty for int8 vectors.
+    pub bits: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScalarVecPayload {
+    encoding: ScalarEncoding,
+    dimension: u32,
+    documents: Vec<ScalarVecDocument>,
+}
+
+pub struct ScalarQuantizedVecIndexBuilder {
+    encoding: ScalarEncoding,
+    dimension: Option<usize>,
+    documents: Vec<ScalarVecDocument>,
+}
+
+impl ScalarQuantizedVecIndexBuilder {
+    #[must_use]
+    pub fn new(encoding: ScalarEncoding) -> Self {
+        Self {
+            encoding,
+            dimension: None,
+            documents: Vec::new(),
+        }
+    }
+
+    /// Quantize and add one vector. All vectors must share the first one's dimension.
+    pub fn add_document(&mut self, frame_id: FrameId, embedding: &[f32]) -> Result<()> {
+        let dimension = *self.dimension.get_or_insert(embedding.len());
+        if embedding.len() != dimension {
+            return Err(MemvidError::VecDimensionMismatch {
+                expected: u32::try_from(dimension).unwrap_or(u32::MAX),
+                actual: embedding.len(),
+            });
+        }
+        let code = self.encoding.encode(embedding);
+        self.documents.push(ScalarVecDocument {
+            frame_id,
+            scale: code.scale,
+            norm_sq: code.norm_sq,
+            codes: code.codes,
+            bits: code.bits,
+        });
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<VecIndexArtifact> {
+        let dimension = u32::try_from(self.dimension.unwrap_or(0)).unwrap_or(u32::MAX);
+        let vector_count = self.documents.len() as u64;
+        #[cfg(feature = "parallel_segments")]
+        let bytes_uncompressed = vector_count * u64::from(dimension) * 4;
+        let payload = ScalarVecPayload {
+            encoding: self.encoding,
+            dimension,
+            documents: self.documents,
+        };
+        let mut bytes = SCALAR_VEC_MAGIC.to_vec();
+        bytes.extend(bincode::serde::encode_to_vec(&payload, scalar_config())?);
+        let checksum = *hash(&bytes).as_bytes();
+        Ok(VecIndexArtifact {
+            bytes,
+            vector_count,
+            dimension,
+            checksum,
+            #[cfg(feature = "parallel_segments")]
+            bytes_uncompressed,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScalarQuantizedVecIndex {
+    encoding: ScalarEncoding,
+    dimension: u32,
+    documents: Vec<ScalarVecDocument>,
+}
+
+impl ScalarQuantizedVecIndex {
+    /// Whether `bytes` start with the scalar index magic.
+    #[must_use]
+    pub fn is_scalar_encoded(bytes: &[u8]) -> bool {
+        bytes.starts_with(&SCALAR_VEC_MAGIC)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let body =
+            bytes
+                .strip_prefix(&SCALAR_VEC_MAGIC)
+                .ok_or_else(|| MemvidError::InvalidToc {
+                    reason: "missing scalar vector index magic".into(),
+                })?;
+        let (payload, read): (ScalarVecPayload, usize) = bincode::serde::decode_from_slice(
+            body,
+            bincode::config::standard()
+                .with_fixed_int_encoding()
+                .with_little_endian()
+                .with_limit::<SCALAR_DECODE_LIMIT>(),
+        )?;
+        if read != body.len() {
+            return Err(MemvidError::InvalidToc {
+                reason: "trailing bytes after scalar vector index".into(),
+            });
+        }
+        Ok(Self {
+            encoding: payload.encoding,
+            dimension: payload.dimension,
+            documents: payload.documents,
+        })
+    }
+
+    #[must_use]
+    pub fn encoding(&self) -> ScalarEncoding {
+        self.encoding
+    }
+
+    #[must_use]
+    pub fn dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, frame_id: FrameId) -> bool {
+        self.documents.iter().any(|doc| doc.frame_id == frame_id)
+    }
+
+    pub fn remove(&mut self, frame_id: FrameId) {
+        self.documents.retain(|doc| doc.frame_id != frame_id);
+    }
+
+    /// Linear scan returning the `limit` closest vectors. Int8 distances approximate L2;
+    /// binary distances count differing sign bits.
+    #[must_use]
+    pub fn search(&self, query: &[f32], limit: usize) -> Vec<VecSearchHit> {
+        if query.len() != self.dimension as usize {
+            return Vec::new();
+        }
+        let encoded = self.encoding.encode(query);
+        let mut hits: Vec<VecSearchHit> = match self.encoding {
+            ScalarEncoding::Int8 => {
+                let query_norm_sq: f32 = query.iter().map(|v| v * v).sum();
+                self.documents
+                    .iter()
+                    .map(|doc| {
+                        #[allow(clippy::cast_precision_loss)]
+                        let dot = dot_i8_simd(&encoded.codes, &doc.codes) as f32
+                            * encoded.scale
+                            * doc.scale;
+                        VecSearchHit {
+                            frame_id: doc.frame_id,
+                            distance: (query_norm_sq - 2.0 * dot + doc.norm_sq).max(0.0).sqrt(),
+                        }
+                    })
+                    .collect()
+            }
+            ScalarEncoding::Binary => self
+                .documents
+                .iter()
+                .map(|doc| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let distance = hamming_distance_simd(&encoded.bits, &doc.bits) as f32;
+                    VecSearchHit {
+                        frame_id: doc.frame_id,
+                        distance,
+                    }
+                })
+                .collect(),
+        };
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Approximate f32 vectors reconstructed from the codes. Binary vectors come back as
+    /// `±1.0` per dimension. Re-quantizing the result yields the same codes, so indexes can
+    /// be rebuilt from it without further loss.
+    #[must_use]
+    pub fn dequantized(&self) -> Vec<(FrameId, Vec<f32>)> {
+        let dimension = self.dimension as usize;
+        self.documents
+            .iter()
+            .map(|doc| {
+                let vector = match self.encoding {
+                    ScalarEncoding::Int8 => doc
+                        .codes
+                        .iter()
+                        .map(|code| f32::from(*code) * doc.scale)
+                        .collect(),
+                    ScalarEncoding::Binary => (0..dimension)
+                        .map(|i| {
+                            if doc.bits[i / 8] & (1 << (i % 8)) == 0 {
+                                -1.0
+                            } else {
+                                1.0
+                            }
+                        })
+                        .collect(),
+                };
+                (doc.frame_id, vector)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vectors() -> Vec<Vec<f32>> {
+        (0..20)
+            .map(|i| {
+                (0..48)
+                    .map(|d| ((i * 31 + d * 7) % 17) as f32 / 8.0 - 1.0)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn int8_and_binary_round_trip_and_rank() {
+        for encoding in [ScalarEncoding::Int8, ScalarEncoding::Binary] {
+            let vectors = vectors();
+            let mut builder = ScalarQuantizedVecIndexBuilder::new(encoding);
+            for (i, vector) in vectors.iter().enumerate() {
+                builder.add_document(i as FrameId, vector).expect("add");
+            }
+            let artifact = builder.finish().expect("finish");
+            assert_eq!(artifact.vector_count, 20);
+            assert!(artifact.bytes.len() < 20 * 48 * 4 / 2);
+
+            let index = ScalarQuantizedVecIndex::decode(&artifact.bytes).expect("decode");
+            let hits = index.search(&vectors[5], 3);
+            assert_eq!(hits[0].frame_id, 5, "{encoding:?}");
+
+            // Rebuilding from dequantized vectors keeps the same codes.
+            let mut rebuilt = ScalarQuantizedVecIndexBuilder::new(encoding);
+            for (frame_id, vector) in index.dequantized() {
+                rebuilt.add_document(frame_id, &vector).expect("add");
+            }
+            assert_eq!(rebuilt.finish().expect("finish").bytes, artifact.bytes);
+        }
+    }
+}
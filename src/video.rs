@@ -0,0 +1,200 @@
+//! Video decoding for `Memvid::put_video`.
+//!
+//! [`FfmpegVideoDecoder`] shells out to an `ffmpeg` binary: keyframes are sampled with the
+//! `fps` filter and streamed back as MJPEG, and the audio track is resampled to 16kHz mono
+//! `f32le` for Whisper. Any other decoder can be plugged in by implementing [`VideoDecoder`].
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::error::{MemvidError, Result};
+use crate::types::video::{VideoAudio, VideoDecoder, VideoKeyframe};
+
+/// Default spacing between sampled keyframes.
+pub const DEFAULT_KEYFRAME_INTERVAL_MS: u64 = 5_000;
+
+const AUDIO_SAMPLE_RATE: u32 = 16_000;
+
+/// Settings for [`FfmpegVideoDecoder`].
+#[derive(Debug, Clone)]
+pub struct FfmpegConfig {
+    /// `ffmpeg` executable; resolved on `PATH` when not absolute.
+    pub binary: PathBuf,
+    /// Upper bound on keyframes sampled from one video.
+    pub max_keyframes: usize,
+    /// Longest side of sampled keyframes, in pixels.
+    pub max_dimension: u32,
+}
+
+impl Default for FfmpegConfig {
+    fn default() -> Self {
+        Self {
+            binary: std::env::var("MEMVID_FFMPEG")
+                .map_or_else(|_| PathBuf::from("ffmpeg"), PathBuf::from),
+            max_keyframes: 240,
+            max_dimension: 512,
+        }
+    }
+}
+
+/// [`VideoDecoder`] backed by the `ffmpeg` command-line tool.
+#[derive(Debug, Clone, Default)]
+pub struct FfmpegVideoDecoder {
+    config: FfmpegConfig,
+}
+
+impl FfmpegVideoDecoder {
+    #[must_use]
+    pub fn new(config: FfmpegConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run ffmpeg over `bytes` with `args` between the input and `pipe:1`, returning stdout.
+    ///
+    /// Input goes through a temporary file because MP4 and MOV containers often keep their
+    /// index at the end, which ffmpeg cannot seek to on a pipe.
+    fn run(&self, bytes: &[u8], args: &[&str]) -> Result<Vec<u8>> {
+        let mut input = tempfile::NamedTempFile::new()?;
+        input.write_all(bytes)?;
+        input.flush()?;
+
+        let output = Command::new(&self.config.binary)
+            .args(["-hide_banner", "-loglevel", "error", "-nostdin", "-i"])
+            .arg(input.path())
+            .args(args)
+            .arg("pipe:1")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|err| MemvidError::VideoDecodeFailed {
+                reason: format!("failed to run {}: {err}", self.config.binary.display()).into(),
+            })?;
+        if !output.status.success() {
+            return Err(MemvidError::VideoDecodeFailed {
+                reason: format!(
+                    "ffmpeg exited with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )
+                .into(),
+            });
+        }
+        Ok(output.stdout)
+    }
+}
+
+impl VideoDecoder for FfmpegVideoDecoder {
+    fn kind(&self) -> &'static str {
+        "ffmpeg"
+    }
+
+    fn keyframes(&self, bytes: &[u8], interval_ms: u64) -> Result<Vec<VideoKeyframe>> {
+        let interval_ms = interval_ms.max(1);
+        let side = self.config.max_dimension.max(1);
+        let filter = format!(
+            "fps=1000/{interval_ms},scale='min({side},iw)':'min({side},ih)':force_original_aspect_ratio=decrease"
+        );
+        let frames = self.config.max_keyframes.to_string();
+        let stream = self.run(
+            bytes,
+            &[
+                "-an",
+                "-vf",
+                &filter,
+                "-frames:v",
+                &frames,
+                "-f",
+                "image2pipe",
+                "-c:v",
+                "mjpeg",
+            ],
+        )?;
+        Ok(split_jpeg_stream(&stream)
+            .into_iter()
+            .zip(0u64..)
+            .map(|(image, index)| VideoKeyframe {
+                offset_ms: index * interval_ms,
+                image: image.to_vec(),
+                mime: "image/jpeg".to_string(),
+            })
+            .collect())
+    }
+
+    fn audio(&self, bytes: &[u8]) -> Result<Option<VideoAudio>> {
+        let rate = AUDIO_SAMPLE_RATE.to_string();
+        let raw = match self.run(
+            bytes,
+            &[
+                "-vn",
+                "-ac",
+                "1",
+                "-ar",
+                &rate,
+                "-f",
+                "f32le",
+                "-c:a",
+                "pcm_f32le",
+            ],
+        ) {
+            Ok(raw) => raw,
+            // Silent videos have no stream to map to the output.
+            Err(MemvidError::VideoDecodeFailed { reason })
+                if reason.contains("does not contain any stream") =>
+            {
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+        if raw.len() < 4 {
+            return Ok(None);
+        }
+        let pcm: Vec<f32> = raw
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        #[allow(clippy::cast_precision_loss)]
+        let duration_secs = pcm.len() as f32 / AUDIO_SAMPLE_RATE as f32;
+        Ok(Some(VideoAudio { pcm, duration_secs }))
+    }
+}
+
+/// Split concatenated JPEGs on their start-of-image / end-of-image markers.
+fn split_jpeg_stream(stream: &[u8]) -> Vec<&[u8]> {
+    let mut images = Vec::new();
+    let mut start = None;
+    let mut index = 0;
+    while index + 1 < stream.len() {
+        match (stream[index], stream[index + 1]) {
+            (0xFF, 0xD8) if start.is_none() => {
+                start = Some(index);
+                index += 2;
+            }
+            (0xFF, 0xD9) => {
+                if let Some(begin) = start.take() {
+                    images.push(&stream[begin..index + 2]);
+                }
+                index += 2;
+            }
+            _ => index += 1,
+        }
+    }
+    images
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jpeg_stream_splits_on_markers() {
+        let stream = [
+            0xFF, 0xD8, 1, 2, 0xFF, 0xD9, 0x00, 0xFF, 0xD8, 3, 0xFF, 0xD9, 0xFF, 0xD8, 4,
+        ];
+        let images = split_jpeg_stream(&stream);
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0], &[0xFF, 0xD8, 1, 2, 0xFF, 0xD9]);
+        assert_eq!(images[1], &[0xFF, 0xD8, 3, 0xFF, 0xD9]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Continuous folder sync into a memory.
+//!
+//! Requires the `notify` feature. A [`FolderWatcher`] subscribes to file system events under
+//! a folder and collects the paths they name. Once no event has arrived for the debounce
+//! interval, the collected paths are synced with [`Memvid::sync_dir`] semantics (changed files
+//! upserted by source path and hash, deleted files tombstoned) and committed. The watcher
+//! holds no handle to the memory: the caller drives it with [`FolderWatcher::poll`] or
+//! [`FolderWatcher::run`] on the thread that owns the `Memvid`.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, channel};
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::error::Result;
+use crate::memvid::lifecycle::Memvid;
+use crate::types::{DirSyncReport, IngestDirOptions};
+
+/// Default quiet period before collected changes are synced.
+pub const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// How a [`FolderWatcher`] selects files and when it syncs.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// File selection, as for `Memvid::sync_dir`.
+    pub ingest: IngestDirOptions,
+    /// Sync once no event has arrived for this long.
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            ingest: IngestDirOptions::default(),
+            debounce: DEFAULT_WATCH_DEBOUNCE,
+        }
+    }
+}
+
+/// Watches a folder and syncs its changes into a memory on a debounce.
+pub struct FolderWatcher {
+    root: PathBuf,
+    options: WatchOptions,
+    // Dropping the watcher ends the subscription.
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    pending: BTreeSet<PathBuf>,
+    last_event: Option<Instant>,
+}
+
+impl std::fmt::Debug for FolderWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FolderWatcher")
+            .field("root", &self.root)
+            .field("options", &self.options)
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FolderWatcher {
+    /// Start watching `root`. Events are collected from now on; call
+    /// [`FolderWatcher::sync_all`] to pick up changes made before.
+    ///
+    /// # Errors
+    /// Fails if `root` cannot be resolved or watched.
+    pub fn new(root: impl AsRef<Path>, options: WatchOptions) -> Result<Self> {
+        let root = std::fs::canonicalize(root.as_ref())?;
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            // The receiver is gone once the watcher is dropped.
+            let _ = sender.send(event);
+        })?;
+        let mode = if options.ingest.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&root, mode)?;
+        Ok(Self {
+            root,
+            options,
+            _watcher: watcher,
+            events,
+            pending: BTreeSet::new(),
+            last_event: None,
+        })
+    }
+
+    /// The watched folder, canonicalized.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Paths changed since the last sync.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Sync the whole folder into `memvid` and commit, discarding collected events.
+    ///
+    /// # Errors
+    /// Fails if the folder cannot be walked or a write or commit fails.
+    pub fn sync_all(&mut self, memvid: &mut Memvid) -> Result<DirSyncReport> {
+        self.drain();
+        self.pending.clear();
+        self.last_event = None;
+        memvid.sync_dir(&self.root, &self.options.ingest)
+    }
+
+    /// Wait up to `timeout` for events, then sync and commit if changes are pending and the
+    /// debounce interval has passed since the last event. Returns `None` when nothing was
+    /// synced.
+    ///
+    /// # Errors
+    /// Fails if a write or commit fails; the changed paths stay pending.
+    pub fn poll(
+        &mut self,
+        memvid: &mut Memvid,
+        timeout: Duration,
+    ) -> Result<Option<DirSyncReport>> {
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => {
+                self.collect(event);
+                self.drain();
+            }
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => {}
+        }
+        let quiet = self
+            .last_event
+            .is_some_and(|last| last.elapsed() >= self.options.debounce);
+        if self.pending.is_empty() || !quiet {
+            return Ok(None);
+        }
+
+        let paths: Vec<PathBuf> = self.pending.iter().cloned().collect();
+        let report = memvid.sync_paths(&self.root, &paths, &self.options.ingest)?;
+        if report.has_changes() {
+            memvid.commit()?;
+        }
+        self.pending.clear();
+        self.last_event = None;
+        Ok(Some(report))
+    }
+
+    /// Sync the whole folder, then keep syncing changes until `stop` is set, calling
+    /// `on_sync` after each sync.
+    ///
+    /// # Errors
+    /// Stops at the first failed sync.
+    pub fn run<F>(&mut self, memvid: &mut Memvid, stop: &AtomicBool, mut on_sync: F) -> Result<()>
+    where
+        F: FnMut(&DirSyncReport),
+    {
+        on_sync(&self.sync_all(memvid)?);
+        let tick = self
+            .options
+            .debounce
+            .clamp(Duration::from_millis(10), Duration::from_millis(250));
+        while !stop.load(Ordering::Relaxed) {
+            if let Some(report) = self.poll(memvid, tick)? {
+                on_sync(&report);
+            }
+        }
+        Ok(())
+    }
+
+    fn drain(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            self.collect(event);
+        }
+    }
+
+    fn collect(&mut self, event: notify::Result<notify::Event>) {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::warn!(error = %err, root = %self.root.display(), "folder watch error");
+                return;
+            }
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        let mut relevant = false;
+        for path in event.paths {
+            if path.starts_with(&self.root) {
+                self.pending.insert(path);
+                relevant = true;
+            }
+        }
+        if relevant {
+            self.last_event = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn watcher_syncs_changes_after_debounce() {
+        let dir = tempdir().expect("tempdir");
+        let folder = dir.path().join("folder");
+        std::fs::create_dir(&folder).expect("mkdir");
+        std::fs::write(folder.join("keep.md"), "kept notes").expect("write");
+        let mut mem = Memvid::create(dir.path().join("watch.mv2")).expect("create");
+        let options = WatchOptions {
+            debounce: Duration::from_millis(50),
+            ..WatchOptions::default()
+        };
+        let mut watcher = FolderWatcher::new(&folder, options).expect("watch");
+        let initial = watcher.sync_all(&mut mem).expect("initial sync");
+        assert_eq!(initial.added.len(), 1);
+
+        std::fs::write(folder.join("new.md"), "fresh notes").expect("write");
+        std::fs::remove_file(folder.join("keep.md")).expect("remove");
+        let mut synced = DirSyncReport::default();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while (synced.added.is_empty() || synced.deleted.is_empty()) && Instant::now() < deadline {
+            if let Some(report) = watcher
+                .poll(&mut mem, Duration::from_millis(20))
+                .expect("poll")
+            {
+                synced.merge(report);
+            }
+        }
+        assert_eq!(synced.added.len(), 1);
+        assert_eq!(synced.deleted, initial.added);
+        assert_eq!(watcher.pending(), 0);
+    }
+}
@@ -148,6 +148,8 @@ pub struct WhisperConfig {
     pub models_dir: PathBuf,
     /// Whether to run in offline mode (no downloads)
     pub offline: bool,
+    /// Label segments with speakers after transcription (default: off)
+    pub diarization: Option<DiarizationConfig>,
 }
 
 impl Default for WhisperConfig {
@@ -167,6 +169,7 @@ impl Default for WhisperConfig {
             model_name,
             models_dir,
             offline,
+            diarization: None,
         }
     }
 }
@@ -210,6 +213,13 @@ impl WhisperConfig {
             ..Default::default()
         }
     }
+
+    /// Enable speaker diarization
+    #[must_use]
+    pub fn with_diarization(mut self, diarization: DiarizationConfig) -> Self {
+        self.diarization = Some(diarization);
+        self
+    }
 }
 
 // ============================================================================
@@ -275,6 +285,209 @@ pub struct TranscriptionSegment {
     pub end: f32,
     /// Transcribed text for this segment
     pub text: String,
+    /// Speaker label assigned by diarization (e.g., "Speaker 1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+impl TranscriptionResult {
+    /// Distinct speaker labels, in order of first appearance
+    #[must_use]
+    pub fn speakers(&self) -> Vec<String> {
+        let mut speakers: Vec<String> = Vec::new();
+        for speaker in self.segments.iter().filter_map(|s| s.speaker.as_ref()) {
+            if !speakers.contains(speaker) {
+                speakers.push(speaker.clone());
+            }
+        }
+        speakers
+    }
+}
+
+// ============================================================================
+// Speaker Diarization
+// ============================================================================
+
+/// Extra-metadata key holding the speaker label(s) of a frame, comma-separated.
+///
+/// Searched by the `speaker:` query field, together with chat message authors.
+pub const SPEAKER_KEY: &str = "speaker";
+
+/// Configuration for energy-based speaker diarization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiarizationConfig {
+    /// Upper bound on distinct speakers (default: 4)
+    pub max_speakers: usize,
+    /// Segments whose voice features are farther apart than this (in standard deviations)
+    /// are attributed to different speakers (default: 1.5)
+    pub distance_threshold: f32,
+    /// Analysis window in milliseconds (default: 25)
+    pub window_ms: u32,
+}
+
+impl Default for DiarizationConfig {
+    fn default() -> Self {
+        Self {
+            max_speakers: 4,
+            distance_threshold: 1.5,
+            window_ms: 25,
+        }
+    }
+}
+
+/// Label `segments` with speakers by clustering per-segment voice features.
+///
+/// Each segment is summarised by the mean and spread of its windows' log energy,
+/// zero-crossing rate, and high-frequency energy ratio over voiced windows, features are
+/// standardised across segments, and segments are merged by average-linkage clustering
+/// until the closest clusters are further apart than `distance_threshold` and no more than
+/// `max_speakers` remain. Labels are numbered in order of first appearance; segments without
+/// voiced audio are left unlabelled. Segment times index into `pcm` at `sample_rate`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn diarize_segments(
+    pcm: &[f32],
+    sample_rate: u32,
+    segments: &mut [TranscriptionSegment],
+    config: &DiarizationConfig,
+) {
+    let window = (sample_rate as usize * config.window_ms.max(1) as usize / 1000).max(1);
+    let features: Vec<Option<Vec<f32>>> = segments
+        .iter()
+        .map(|segment| {
+            let start = ((segment.start.max(0.0) * sample_rate as f32) as usize).min(pcm.len());
+            let end = ((segment.end.max(0.0) * sample_rate as f32) as usize).min(pcm.len());
+            voice_features(&pcm[start..end.max(start)], window)
+        })
+        .collect();
+
+    let voiced: Vec<usize> = (0..segments.len())
+        .filter(|&index| features[index].is_some())
+        .collect();
+    if voiced.is_empty() {
+        return;
+    }
+    let mut points: Vec<Vec<f32>> = voiced
+        .iter()
+        .filter_map(|&index| features[index].clone())
+        .collect();
+    standardize(&mut points);
+
+    // Average-linkage agglomerative clustering over standardised features
+    let mut clusters: Vec<Vec<usize>> = (0..points.len()).map(|point| vec![point]).collect();
+    let max_speakers = config.max_speakers.max(1);
+    while clusters.len() > 1 {
+        let mut closest = (0, 1, f32::INFINITY);
+        for a in 0..clusters.len() {
+            for b in (a + 1)..clusters.len() {
+                let distance = linkage(&points, &clusters[a], &clusters[b]);
+                if distance < closest.2 {
+                    closest = (a, b, distance);
+                }
+            }
+        }
+        let (a, b, distance) = closest;
+        if distance > config.distance_threshold && clusters.len() <= max_speakers {
+            break;
+        }
+        let merged = clusters.remove(b);
+        clusters[a].extend(merged);
+    }
+
+    let mut cluster_of = vec![0usize; points.len()];
+    for (cluster, members) in clusters.iter().enumerate() {
+        for &point in members {
+            cluster_of[point] = cluster;
+        }
+    }
+    let mut labels: Vec<Option<usize>> = vec![None; clusters.len()];
+    let mut next_label = 0;
+    for (point, &index) in voiced.iter().enumerate() {
+        let label = *labels[cluster_of[point]].get_or_insert_with(|| {
+            next_label += 1;
+            next_label
+        });
+        segments[index].speaker = Some(format!("Speaker {label}"));
+    }
+}
+
+/// Mean and standard deviation of (log energy, zero-crossing rate, high-frequency ratio)
+/// over voiced windows, or `None` when nothing in `samples` is voiced.
+fn voice_features(samples: &[f32], window: usize) -> Option<Vec<f32>> {
+    const VOICED_RMS: f32 = 0.01;
+    let mut rows: Vec<[f32; 3]> = Vec::new();
+    for chunk in samples.chunks(window) {
+        if chunk.len() < 2 {
+            continue;
+        }
+        let energy = chunk.iter().map(|x| x * x).sum::<f32>() / chunk.len() as f32;
+        if energy.sqrt() < VOICED_RMS {
+            continue;
+        }
+        let crossings = chunk
+            .windows(2)
+            .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+            .count() as f32
+            / (chunk.len() - 1) as f32;
+        // Energy of the first difference approximates the high-frequency share of the signal
+        let diff_energy = chunk
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).powi(2))
+            .sum::<f32>()
+            / chunk.len() as f32;
+        rows.push([energy.ln(), crossings, diff_energy / (energy + diff_energy)]);
+    }
+    if rows.is_empty() {
+        return None;
+    }
+    let count = rows.len() as f32;
+    let mut features = Vec::with_capacity(6);
+    for column in 0..3 {
+        let mean = rows.iter().map(|row| row[column]).sum::<f32>() / count;
+        let variance = rows
+            .iter()
+            .map(|row| (row[column] - mean).powi(2))
+            .sum::<f32>()
+            / count;
+        features.push(mean);
+        features.push(variance.sqrt());
+    }
+    Some(features)
+}
+
+/// Scale each feature to zero mean and unit variance across `points`.
+fn standardize(points: &mut [Vec<f32>]) {
+    let Some(dims) = points.first().map(Vec::len) else {
+        return;
+    };
+    let count = points.len() as f32;
+    for dim in 0..dims {
+        let mean = points.iter().map(|p| p[dim]).sum::<f32>() / count;
+        let std = (points.iter().map(|p| (p[dim] - mean).powi(2)).sum::<f32>() / count).sqrt();
+        for point in points.iter_mut() {
+            point[dim] = if std > f32::EPSILON {
+                (point[dim] - mean) / std
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+/// Mean pairwise Euclidean distance between two clusters.
+fn linkage(points: &[Vec<f32>], a: &[usize], b: &[usize]) -> f32 {
+    let mut total = 0.0;
+    for &i in a {
+        for &j in b {
+            total += points[i]
+                .iter()
+                .zip(&points[j])
+                .map(|(x, y)| (x - y).powi(2))
+                .sum::<f32>()
+                .sqrt();
+        }
+    }
+    total / (a.len() * b.len()) as f32
 }
 
 // ============================================================================
@@ -310,15 +523,40 @@ mod audio {
             hint.with_extension(ext);
         }
 
+        decode_media(mss, &hint, &|cause| WhisperError::AudioDecodeError {
+            path: path.to_path_buf(),
+            cause,
+        })
+    }
+
+    /// Decode in-memory audio to f32 samples, resampling to 16kHz mono.
+    ///
+    /// `extension` (e.g. `"mp3"`) speeds up format probing but is optional.
+    pub fn decode_audio_bytes(bytes: &[u8], extension: Option<&str>) -> Result<(Vec<f32>, f32)> {
+        let cursor = std::io::Cursor::new(bytes.to_vec());
+        let mss = MediaSourceStream::new(Box::new(cursor), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(ext) = extension {
+            hint.with_extension(ext);
+        }
+
+        decode_media(mss, &hint, &|cause| WhisperError::AudioBytesDecodeError {
+            cause,
+        })
+    }
+
+    fn decode_media(
+        mss: MediaSourceStream,
+        hint: &Hint,
+        fail: &dyn Fn(String) -> WhisperError,
+    ) -> Result<(Vec<f32>, f32)> {
         // Probe the media source
         let format_opts = FormatOptions::default();
         let metadata_opts = MetadataOptions::default();
         let probed = symphonia::default::get_probe()
-            .format(&hint, mss, &format_opts, &metadata_opts)
-            .map_err(|e| WhisperError::AudioDecodeError {
-                path: path.to_path_buf(),
-                cause: format!("Failed to probe audio format: {}", e),
-            })?;
+            .format(hint, mss, &format_opts, &metadata_opts)
+            .map_err(|e| fail(format!("Failed to probe audio format: {}", e)))?;
 
         let mut format = probed.format;
 
@@ -327,10 +565,7 @@ mod audio {
             .tracks()
             .iter()
             .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
-            .ok_or_else(|| WhisperError::AudioDecodeError {
-                path: path.to_path_buf(),
-                cause: "No audio track found".to_string(),
-            })?;
+            .ok_or_else(|| fail("No audio track found".to_string()))?;
 
         let track_id = track.id;
         let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
@@ -340,10 +575,7 @@ mod audio {
         let decoder_opts = DecoderOptions::default();
         let mut decoder = symphonia::default::get_codecs()
             .make(&track.codec_params, &decoder_opts)
-            .map_err(|e| WhisperError::AudioDecodeError {
-                path: path.to_path_buf(),
-                cause: format!("Failed to create decoder: {}", e),
-            })?;
+            .map_err(|e| fail(format!("Failed to create decoder: {}", e)))?;
 
         let mut samples: Vec<f32> = Vec::new();
 
@@ -485,6 +717,7 @@ mod inference {
         config: Config,
         mel_filters: Vec<f32>,
         device: Device,
+        diarization: Option<DiarizationConfig>,
     }
 
     #[allow(dead_code)]
@@ -668,6 +901,7 @@ mod inference {
                 config: model_config,
                 mel_filters,
                 device,
+                diarization: config.diarization.clone(),
             })
         }
 
@@ -751,7 +985,8 @@ mod inference {
                 "Trimmed silence"
             );
 
-            // Use trimmed audio
+            // Use trimmed audio; segment times stay relative to the full input
+            let full_pcm = pcm_data;
             let pcm_data = &pcm_data[start_sample..end_sample];
             let _trimmed_duration = pcm_data.len() as f32 / SAMPLE_RATE;
 
@@ -1116,10 +1351,20 @@ mod inference {
                         start: start_time,
                         end: end_time,
                         text: trimmed_text.to_string(),
+                        speaker: None,
                     });
                 }
             }
 
+            if let Some(diarization) = &self.diarization {
+                super::diarize_segments(
+                    full_pcm,
+                    super::WHISPER_SAMPLE_RATE,
+                    &mut segments,
+                    diarization,
+                );
+            }
+
             Ok(TranscriptionResult {
                 text: all_text.trim().to_string(),
                 language: "en".to_string(),
@@ -1194,5 +1439,61 @@ mod tests {
     fn whisper_config_defaults() {
         let config = WhisperConfig::default();
         assert_eq!(config.model_name, "whisper-small-en");
+        assert!(config.diarization.is_none());
+    }
+
+    #[test]
+    fn diarization_separates_distinct_voices() {
+        const RATE: u32 = 16_000;
+        // Alternating turns: a loud low-pitched voice and a quiet high-pitched one
+        let voice = |turn: usize, i: usize| {
+            let t = i as f32 / RATE as f32;
+            if turn % 2 == 0 {
+                0.4 * (2.0 * std::f32::consts::PI * 140.0 * t).sin()
+            } else {
+                0.05 * (2.0 * std::f32::consts::PI * 2_400.0 * t).sin()
+            }
+        };
+        let turn_samples = RATE as usize * 2;
+        let mut pcm = Vec::new();
+        let mut segments = Vec::new();
+        for turn in 0..4 {
+            pcm.extend((0..turn_samples).map(|i| voice(turn, i)));
+            segments.push(TranscriptionSegment {
+                start: (turn * 2) as f32,
+                end: (turn * 2 + 2) as f32,
+                text: format!("turn {turn}"),
+                speaker: None,
+            });
+        }
+        // Trailing silence is not attributed to anyone
+        pcm.extend(std::iter::repeat_n(0.0, turn_samples));
+        segments.push(TranscriptionSegment {
+            start: 8.0,
+            end: 10.0,
+            text: String::new(),
+            speaker: None,
+        });
+
+        diarize_segments(&pcm, RATE, &mut segments, &DiarizationConfig::default());
+        let labels: Vec<Option<&str>> = segments.iter().map(|s| s.speaker.as_deref()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some("Speaker 1"),
+                Some("Speaker 2"),
+                Some("Speaker 1"),
+                Some("Speaker 2"),
+                None
+            ]
+        );
+
+        let result = TranscriptionResult {
+            text: String::new(),
+            language: "en".into(),
+            duration_secs: 10.0,
+            segments,
+        };
+        assert_eq!(result.speakers(), vec!["Speaker 1", "Speaker 2"]);
     }
 }
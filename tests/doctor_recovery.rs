@@ -5,8 +5,9 @@ use std::fs::{read, write};
 use tempfile::{NamedTempFile, TempDir};
 
 use memvid_core::{
-    DoctorOptions, DoctorPhaseKind, DoctorStatus, HEADER_SIZE, Memvid, PutOptions, SearchRequest,
-    io::header::HeaderCodec,
+    DOCTOR_REPORT_VERSION, DoctorActionKind, DoctorFindingCode, DoctorIndexKind, DoctorOptions,
+    DoctorPhaseKind, DoctorReport, DoctorSeverity, DoctorStatus, DuplicateKind, FrameStatus,
+    HEADER_SIZE, Memvid, PutOptions, SearchRequest, SketchVariant, io::header::HeaderCodec,
 };
 
 /// Windows needs extra time for Tantivy to release file handles.
@@ -72,6 +73,18 @@ fn doctor_rebuilds_tantivy_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: memvid_core::types::SearchMode::Lexical,
+                time_budget_ms: None,
             })
             .unwrap();
 
@@ -92,7 +105,9 @@ fn doctor_rebuilds_tantivy_index() {
                 rebuild_lex_index: true,
                 rebuild_time_index: true, // Must rebuild time index with lex index
                 rebuild_vec_index: false,
+                supersede_duplicates: false,
                 vacuum: false,
+                reclaim_orphans: false,
                 dry_run: false,
                 quiet: true,
             },
@@ -125,6 +140,18 @@ fn doctor_rebuilds_tantivy_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: memvid_core::types::SearchMode::Lexical,
+                time_budget_ms: None,
             })
             .unwrap();
 
@@ -166,7 +193,9 @@ fn doctor_handles_empty_file() {
                 rebuild_lex_index: true,
                 rebuild_time_index: true, // Must rebuild time index with lex index
                 rebuild_vec_index: false,
+                supersede_duplicates: false,
                 vacuum: false,
+                reclaim_orphans: false,
                 dry_run: false,
                 quiet: true,
             },
@@ -222,7 +251,9 @@ fn doctor_handles_lex_disabled() {
                 rebuild_lex_index: false,
                 rebuild_time_index: true,
                 rebuild_vec_index: false,
+                supersede_duplicates: false,
                 vacuum: false,
+                reclaim_orphans: false,
                 dry_run: false,
                 quiet: true,
             },
@@ -278,6 +309,18 @@ fn open_file_with_tantivy_segments_enables_lex() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         });
 
         assert!(
@@ -337,7 +380,9 @@ fn doctor_rebuild_produces_searchable_index() {
                 rebuild_lex_index: true,
                 rebuild_time_index: true, // Must rebuild time index with lex index
                 rebuild_vec_index: false,
+                supersede_duplicates: false,
                 vacuum: false,
+                reclaim_orphans: false,
                 dry_run: false,
                 quiet: true,
             },
@@ -371,6 +416,18 @@ fn doctor_rebuild_produces_searchable_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: memvid_core::types::SearchMode::Lexical,
+                time_budget_ms: None,
             })
             .unwrap();
 
@@ -400,6 +457,18 @@ fn doctor_rebuild_produces_searchable_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: memvid_core::types::SearchMode::Lexical,
+                time_budget_ms: None,
             })
             .unwrap();
 
@@ -449,7 +518,9 @@ fn doctor_recovers_corrupted_wal() {
         rebuild_time_index: true,
         rebuild_lex_index: true,
         rebuild_vec_index: true,
+        supersede_duplicates: false,
         vacuum: true,
+        reclaim_orphans: false,
         dry_run: false, // should be false for repair
         quiet: true,
     };
@@ -615,6 +686,48 @@ fn doctor_dry_run_returns_plan() {
     let _ = Memvid::open(&mv2_path).expect("open");
 }
 
+/// A dry run describes what it would change and round-trips through its JSON layout.
+#[test]
+fn doctor_dry_run_reports_diff_as_json() {
+    let dir = TempDir::new().expect("temp");
+    let mv2_path = dir.path().join("diff.mv2");
+    {
+        let mut mem = Memvid::create(&mv2_path).expect("create mem");
+        for i in 0..3 {
+            mem.put_bytes(format!("diffed document {i}").as_bytes())
+                .expect("put bytes");
+        }
+        mem.commit().expect("commit");
+    }
+
+    let report = Memvid::doctor(
+        &mv2_path,
+        DoctorOptions {
+            rebuild_time_index: true,
+            dry_run: true,
+            quiet: true,
+            ..Default::default()
+        },
+    )
+    .expect("report");
+    let diff = &report.plan.diff;
+    assert_eq!(diff.frames_affected, 3);
+    assert_eq!(diff.index_bumps.len(), 1);
+    assert_eq!(diff.index_bumps[0].index, DoctorIndexKind::Time);
+    assert!(
+        diff.rewritten_ranges
+            .iter()
+            .any(|range| range.action == DoctorActionKind::UpdateHeader && range.offset == 0)
+    );
+    assert!(!report.exceeds(DoctorSeverity::Warning));
+
+    let json = report.to_json().expect("json");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("parse");
+    assert_eq!(value["version"], DOCTOR_REPORT_VERSION);
+    let decoded: DoctorReport = serde_json::from_str(&json).expect("decode");
+    assert_eq!(&decoded.plan.diff, diff);
+}
+
 /*
     Test: doctor detects index out of bounds
     1. create .mv2, truncate file to make index offsets invalid
@@ -746,3 +859,202 @@ fn doctor_preserves_footer_offset() {
 
     let _ = Memvid::open(&mv2_path).expect("file should open after doctor");
 }
+
+/// Test that duplicates are reported and that doctor supersedes only the exact ones.
+#[test]
+fn doctor_supersedes_exact_duplicates() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("dupes.mv2");
+    let base = "the quarterly report covers revenue growth in the northern region, new hires \
+                across engineering and support, the office relocation timeline, vendor contract \
+                renewals, customer retention figures, and the roadmap for the mobile application \
+                with milestones for design, testing, and launch in the coming months ahead";
+    let texts = [
+        base.to_string(),
+        base.to_string(),
+        base.replace("northern", "southern"),
+        "a recipe for sourdough bread with flour water salt and a long cold fermentation".into(),
+        base.to_string(),
+    ];
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        for (i, text) in texts.iter().enumerate() {
+            let options = PutOptions {
+                uri: Some(format!("mv2://crawl/{i}")),
+                search_text: Some(text.clone()),
+                ..Default::default()
+            };
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .unwrap();
+        }
+        mem.commit().unwrap();
+        mem.build_all_sketches(SketchVariant::Small);
+        mem.commit().unwrap();
+
+        let exact = mem.find_duplicates(0);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].kind, DuplicateKind::Exact);
+        assert_eq!(exact[0].frame_ids, vec![0, 1, 4]);
+        assert_eq!(exact[0].canonical, 0);
+        assert_eq!(exact[0].redundant(), 2);
+
+        let near = mem.find_duplicates(16);
+        assert_eq!(near.len(), 1);
+        assert_eq!(near[0].kind, DuplicateKind::Near);
+        assert_eq!(near[0].frame_ids, vec![0, 1, 2, 4]);
+        assert!(near[0].max_distance > 0);
+    }
+    windows_file_handle_delay();
+
+    let report = Memvid::doctor(
+        &path,
+        DoctorOptions {
+            supersede_duplicates: true,
+            quiet: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(
+        report
+            .plan
+            .phases
+            .iter()
+            .any(|phase| phase.phase == DoctorPhaseKind::Deduplicate)
+    );
+    assert_ne!(report.status, DoctorStatus::Failed);
+    windows_file_handle_delay();
+
+    let mem = Memvid::open_read_only(&path).unwrap();
+    let statuses: Vec<FrameStatus> = (0..5)
+        .map(|id| mem.frame_by_id(id).unwrap().status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            FrameStatus::Active,
+            FrameStatus::Superseded,
+            FrameStatus::Active,
+            FrameStatus::Active,
+            FrameStatus::Superseded,
+        ]
+    );
+    assert_eq!(mem.frame_by_id(4).unwrap().superseded_by, Some(0));
+    assert!(mem.find_duplicates(0).is_empty());
+}
+
+/// Orphaned index bytes are reported on a dry run and reclaimed when asked.
+#[test]
+fn doctor_reports_and_reclaims_orphaned_bytes() {
+    let dir = TempDir::new().expect("temp");
+    let path = dir.path().join("orphans.mv2");
+    {
+        let mut mem = Memvid::create(&path).expect("create");
+        for batch in 0..4 {
+            for i in 0..20 {
+                let text = format!("batch {batch} document {i} about orphaned index segments");
+                mem.put_bytes(text.as_bytes()).expect("put");
+            }
+            mem.commit().expect("commit");
+        }
+    }
+    windows_file_handle_delay();
+
+    // Rebuilding writes a fresh index and tail tracks, leaving the old copies unreferenced.
+    Memvid::doctor(
+        &path,
+        DoctorOptions {
+            rebuild_time_index: true,
+            quiet: true,
+            ..Default::default()
+        },
+    )
+    .expect("doctor rebuild");
+
+    let check = Memvid::doctor(
+        &path,
+        DoctorOptions {
+            dry_run: true,
+            quiet: true,
+            ..Default::default()
+        },
+    )
+    .expect("doctor dry run");
+    assert!(check.metrics.reclaimable_bytes > 0);
+    assert!(
+        check
+            .plan
+            .findings
+            .iter()
+            .any(|finding| finding.code == DoctorFindingCode::OrphanedBytes)
+    );
+
+    let report = Memvid::doctor(
+        &path,
+        DoctorOptions {
+            reclaim_orphans: true,
+            quiet: true,
+            ..Default::default()
+        },
+    )
+    .expect("doctor reclaim");
+    assert_ne!(report.status, DoctorStatus::Failed);
+    assert!(report.metrics.reclaimed_bytes > 0);
+
+    let after = Memvid::doctor(
+        &path,
+        DoctorOptions {
+            dry_run: true,
+            quiet: true,
+            ..Default::default()
+        },
+    )
+    .expect("doctor dry run");
+    assert_eq!(after.metrics.reclaimable_bytes, 0);
+    windows_file_handle_delay();
+
+    let mem = Memvid::open_read_only(&path).expect("open");
+    assert_eq!(mem.frame_count(), 80);
+}
+
+/// With the header, TOC, and footer all destroyed, salvage still copies every frame out.
+#[test]
+fn salvage_recovers_frames_without_toc_or_header() {
+    let dir = TempDir::new().expect("temp");
+    let path = dir.path().join("damaged.mv2");
+    let texts: Vec<String> = (0..4)
+        .map(|i| format!("salvageable document number {i} with enough text to compress"))
+        .collect();
+    {
+        let mut mem = Memvid::create(&path).expect("create");
+        for (i, text) in texts.iter().enumerate() {
+            let options = PutOptions::builder().uri(format!("mv2://doc/{i}")).build();
+            mem.put_bytes_with_options(text.as_bytes(), options)
+                .expect("put");
+        }
+        mem.commit().expect("commit");
+    }
+    windows_file_handle_delay();
+
+    let mut file = std::fs::File::open(&path).expect("open");
+    let footer_offset = HeaderCodec::read(&mut file).expect("header").footer_offset;
+    drop(file);
+    let mut bytes = read(&path).expect("read");
+    let footer_offset = usize::try_from(footer_offset).expect("offset");
+    bytes[..HEADER_SIZE].fill(0);
+    bytes[footer_offset..].fill(0);
+    write(&path, &bytes).expect("write");
+    assert!(Memvid::open_read_only(&path).is_err());
+
+    let dest = dir.path().join("salvaged.mv2");
+    let report = Memvid::salvage(&path, &dest).expect("salvage");
+    assert!(!report.toc_found);
+    assert_eq!(report.recovered.len(), texts.len());
+    assert!(!report.unrecoverable.is_empty());
+
+    let mut mem = Memvid::open_read_only(&dest).expect("open salvaged");
+    assert_eq!(mem.frame_count(), texts.len());
+    let frame = mem.frame_by_uri("mv2://doc/2").expect("uri preserved");
+    let payload = mem.frame_canonical_payload(frame.id).expect("payload");
+    assert_eq!(payload, texts[2].as_bytes());
+}
@@ -2,10 +2,16 @@
 //! Tests: put, put_bytes_with_options, update, delete
 
 use memvid_core::{
-    EmbeddingIdentitySummary, MEMVID_EMBEDDING_MODEL_KEY, MEMVID_EMBEDDING_PROVIDER_KEY, Memvid,
-    MemvidError, PutOptions, TimelineQuery,
+    BlobExtentOptions, BlobExtentStats, CanonicalEncoding, Collection, CommitMode, CommitOptions,
+    CompressionCodec, CompressionDefaults, DocMetadata, DurabilityProfile,
+    EmbeddingIdentitySummary, IngestProfile, MEMVID_EMBEDDING_MODEL_KEY,
+    MEMVID_EMBEDDING_PROVIDER_KEY, MediaManifest, Memvid, MemvidError, MemvidHooks, PutEvent,
+    PutOptions, SearchRequest, TagEdit, TimelineQuery,
 };
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom};
 use std::num::NonZeroU64;
+use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
 
 /// Test basic put operation with bytes.
@@ -434,3 +440,751 @@ fn timeline_iteration() {
 
     assert_eq!(entries.len(), 3, "Should have 3 timeline entries");
 }
+
+/// Test that relaxed durability profiles still persist committed frames.
+#[test]
+fn durability_profiles_persist_commits() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.lock_settings_mut().durability = DurabilityProfile::Fast;
+        mem.put_bytes(b"written without fsync").unwrap();
+        mem.commit().unwrap();
+
+        mem.put_bytes(b"written with one fsync per commit").unwrap();
+        mem.commit_with_options(
+            CommitOptions::new(CommitMode::Full).durability(DurabilityProfile::Balanced),
+        )
+        .unwrap();
+        assert_eq!(mem.lock_settings().durability, DurabilityProfile::Fast);
+    }
+
+    let mem = Memvid::open_read_only(&path).unwrap();
+    assert_eq!(mem.stats().unwrap().frame_count, 2);
+}
+
+/// Test that coalesced commits defer until their window closes or the handle drops.
+#[test]
+fn coalesced_commits_share_one_flush() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let window = CommitOptions::new(CommitMode::Coalesced {
+        max_delay: std::time::Duration::from_secs(3600),
+    });
+    let expired = CommitOptions::new(CommitMode::Coalesced {
+        max_delay: std::time::Duration::ZERO,
+    });
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.put_bytes(b"first small put").unwrap();
+        mem.commit_with_options(window.clone()).unwrap();
+        mem.put_bytes(b"second small put").unwrap();
+        mem.commit_with_options(window.clone()).unwrap();
+        assert!(mem.commit_deferred());
+        assert_eq!(mem.stats().unwrap().frame_count, 0);
+
+        mem.commit_with_options(expired).unwrap();
+        assert!(!mem.commit_deferred());
+        assert_eq!(mem.stats().unwrap().frame_count, 2);
+
+        mem.put_bytes(b"deferred until flush").unwrap();
+        mem.commit_with_options(window.clone()).unwrap();
+        mem.flush().unwrap();
+        assert!(!mem.commit_deferred());
+        assert_eq!(mem.stats().unwrap().frame_count, 3);
+        mem.flush().unwrap();
+
+        mem.put_bytes(b"deferred until drop").unwrap();
+        mem.commit_with_options(window).unwrap();
+        assert!(mem.commit_deferred());
+    }
+
+    let mem = Memvid::open_read_only(&path).unwrap();
+    assert_eq!(mem.stats().unwrap().frame_count, 4);
+}
+
+/// Test that put_many produces the same frames as serial puts.
+#[test]
+fn put_many_matches_serial_puts() {
+    let dir = TempDir::new().unwrap();
+    let documents: Vec<(Vec<u8>, PutOptions)> = (0..12)
+        .map(|i| {
+            let text = if i == 5 {
+                "Long report on river deltas and sediment transport. ".repeat(80)
+            } else {
+                format!("Field note {i} about alpine meadows and migrating birds.")
+            };
+            let opts = PutOptions {
+                uri: Some(format!("mv2://note/{i}")),
+                timestamp: Some(1_700_000_000 + i),
+                ..Default::default()
+            };
+            (text.into_bytes(), opts)
+        })
+        .collect();
+
+    let serial_path = dir.path().join("serial.mv2");
+    let mut serial = Memvid::create(&serial_path).unwrap();
+    for (payload, opts) in documents.clone() {
+        serial.put_bytes_with_options(&payload, opts).unwrap();
+    }
+    serial.commit().unwrap();
+
+    let parallel_path = dir.path().join("parallel.mv2");
+    let mut parallel = Memvid::create(&parallel_path).unwrap();
+    let sequences = parallel.put_many(documents).unwrap();
+    parallel.commit().unwrap();
+
+    assert_eq!(sequences.len(), 12);
+    assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        parallel.stats().unwrap().frame_count,
+        serial.stats().unwrap().frame_count
+    );
+    for i in 0..12 {
+        let uri = format!("mv2://note/{i}");
+        let expected = serial.frame_by_uri(&uri).unwrap();
+        let actual = parallel.frame_by_uri(&uri).unwrap();
+        assert_eq!(actual.id, expected.id);
+        assert_eq!(actual.search_text, expected.search_text);
+        assert_eq!(actual.tags, expected.tags);
+        assert_eq!(actual.chunk_count, expected.chunk_count);
+    }
+}
+
+/// Test that frame_bytes_ref borrows plain payloads from the mapped file.
+#[test]
+fn frame_bytes_ref_borrows_plain_payloads() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let binary: Vec<u8> = (0..=255u8).rev().cycle().take(2048).collect();
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        for (uri, payload) in [
+            ("mv2://binary", binary.clone()),
+            ("mv2://text", b"Plain words".to_vec()),
+        ] {
+            let opts = PutOptions {
+                uri: Some(uri.to_string()),
+                ..Default::default()
+            };
+            mem.put_bytes_with_options(&payload, opts).unwrap();
+        }
+        mem.commit().unwrap();
+    }
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let binary_id = mem.frame_by_uri("mv2://binary").unwrap().id;
+    let text_id = mem.frame_by_uri("mv2://text").unwrap().id;
+
+    let bytes = mem.frame_bytes_ref(binary_id).unwrap();
+    assert!(matches!(bytes, Cow::Borrowed(_)));
+    assert_eq!(&*bytes, binary.as_slice());
+
+    // Compressed payloads are decoded into an owned buffer.
+    let text = mem.frame_bytes_ref(text_id).unwrap().into_owned();
+    assert_eq!(text, mem.frame_canonical_payload(text_id).unwrap());
+}
+
+/// Test ranged blob reads against plain and compressed payloads.
+#[test]
+fn blob_reader_range_reads_requested_bytes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let video: Vec<u8> = (0..=255u8).rev().cycle().take(4096).collect();
+    let text = "0123456789".repeat(50);
+
+    let mut mem = Memvid::create(&path).unwrap();
+    let metadata = DocMetadata {
+        media: Some(MediaManifest {
+            kind: "video".to_string(),
+            mime: "video/mp4".to_string(),
+            bytes: video.len() as u64,
+            filename: None,
+            duration_ms: None,
+            width: None,
+            height: None,
+            codec: None,
+        }),
+        ..Default::default()
+    };
+    let opts = PutOptions {
+        uri: Some("mv2://video".to_string()),
+        metadata: Some(metadata),
+        ..Default::default()
+    };
+    mem.put_bytes_with_options(&video, opts).unwrap();
+    let opts = PutOptions {
+        uri: Some("mv2://text".to_string()),
+        ..Default::default()
+    };
+    mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    mem.commit().unwrap();
+
+    let mut reader = mem.blob_reader_range("mv2://video", 1000, 500).unwrap();
+    assert_eq!(reader.len(), 500);
+    let mut range = Vec::new();
+    reader.read_to_end(&mut range).unwrap();
+    assert_eq!(range, &video[1000..1500]);
+    reader.seek(SeekFrom::Start(100)).unwrap();
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte).unwrap();
+    assert_eq!(byte[0], video[1100]);
+
+    let mut range = Vec::new();
+    mem.blob_reader_range("mv2://text", 25, 10)
+        .unwrap()
+        .read_to_end(&mut range)
+        .unwrap();
+    assert_eq!(range, b"5678901234");
+
+    let result = mem.blob_reader_range("mv2://video", 4000, 200);
+    assert!(matches!(
+        result,
+        Err(MemvidError::BlobRangeOutOfBounds {
+            end: 4200,
+            size: 4096,
+            ..
+        })
+    ));
+}
+
+/// Long zstd texts are stored seekable: ranged reads decode only the frames they cover.
+#[test]
+fn seekable_payload_ranges_skip_other_frames() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let text: String = (0..40_000).map(|i| format!("line {i:05}\n")).collect();
+
+    let mut mem = Memvid::create(&path).unwrap();
+    let opts = PutOptions {
+        uri: Some("mv2://long".to_string()),
+        chunk_chars: Some(1_000_000),
+        ..Default::default()
+    };
+    mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+    mem.commit().unwrap();
+    let frame = mem.frame_by_uri("mv2://long").unwrap();
+    assert!(frame.payload_length > 0);
+    assert_eq!(
+        mem.frame_canonical_payload(frame.id).unwrap(),
+        text.as_bytes()
+    );
+    drop(mem);
+
+    // Damage the last quarter of the payload; the start stays readable by range.
+    let mut bytes = std::fs::read(&path).unwrap();
+    let damaged = (frame.payload_offset + frame.payload_length * 3 / 4) as usize;
+    for byte in &mut bytes[damaged..damaged + 64] {
+        *byte ^= 0x5A;
+    }
+    std::fs::write(&path, bytes).unwrap();
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let mut range = Vec::new();
+    mem.blob_reader_range("mv2://long", 70_000, 160)
+        .unwrap()
+        .read_to_end(&mut range)
+        .unwrap();
+    assert_eq!(range, &text.as_bytes()[70_000..70_160]);
+    assert_ne!(
+        mem.frame_canonical_payload(frame.id).ok(),
+        Some(text.into_bytes())
+    );
+}
+
+/// Large payloads are split into shared extents that survive deletes and vacuum.
+#[test]
+fn blob_extents_share_and_compact() {
+    fn noise(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let first = noise(1, 3000);
+    let mut second = first[..2048].to_vec();
+    second.extend(noise(2, 1500));
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.set_blob_extents(Some(BlobExtentOptions {
+        extent_size: 1024,
+        min_blob_bytes: 2048,
+    }))
+    .unwrap();
+    for (uri, payload) in [
+        ("mv2://first", first.as_slice()),
+        ("mv2://second", second.as_slice()),
+        ("mv2://small", b"small text stays contiguous".as_slice()),
+    ] {
+        let opts = PutOptions {
+            uri: Some(uri.to_string()),
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(payload, opts).unwrap();
+    }
+    mem.commit().unwrap();
+
+    assert_eq!(
+        mem.blob_extent_stats().unwrap(),
+        BlobExtentStats {
+            frames: 2,
+            extents: 5,
+            stored_bytes: 4500,
+            referenced_bytes: 6548,
+        }
+    );
+    let first_id = mem.frame_by_uri("mv2://first").unwrap().id;
+    let second_id = mem.frame_by_uri("mv2://second").unwrap().id;
+    let read_blob = |mem: &mut Memvid, frame_id| {
+        let mut bytes = Vec::new();
+        mem.blob_reader(frame_id)
+            .unwrap()
+            .read_to_end(&mut bytes)
+            .unwrap();
+        bytes
+    };
+    assert_eq!(read_blob(&mut mem, first_id), first);
+    assert_eq!(read_blob(&mut mem, second_id), second);
+    let mut range = Vec::new();
+    mem.blob_reader_range("mv2://second", 1000, 1100)
+        .unwrap()
+        .read_to_end(&mut range)
+        .unwrap();
+    assert_eq!(range, &second[1000..2100]);
+
+    mem.delete_frame(first_id).unwrap();
+    mem.commit().unwrap();
+    mem.vacuum().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let stats = mem.blob_extent_stats().unwrap();
+    assert_eq!(
+        (stats.frames, stats.extents, stats.stored_bytes),
+        (1, 4, 3548)
+    );
+    assert_eq!(read_blob(&mut mem, second_id), second);
+    let small = mem.frame_by_uri("mv2://small").unwrap();
+    assert!(small.payload_length > 0);
+    assert_eq!(
+        mem.blob_extent_options()
+            .unwrap()
+            .map(|opts| opts.extent_size),
+        Some(1024)
+    );
+}
+
+#[test]
+fn compression_codecs_per_put_and_per_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("codecs.mv2");
+    let text = "hot data compresses well ".repeat(40);
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.set_compression_defaults(CompressionDefaults {
+        payloads: CompressionCodec::Lz4,
+        segments: CompressionCodec::Zstd { level: 9 },
+    })
+    .unwrap();
+    for (uri, codec) in [
+        ("mv2://default", None),
+        ("mv2://none", Some(CompressionCodec::None)),
+        ("mv2://zstd", Some(CompressionCodec::Zstd { level: 19 })),
+    ] {
+        let mut opts = PutOptions::builder().uri(uri);
+        if let Some(codec) = codec {
+            opts = opts.compression(codec);
+        }
+        mem.put_bytes_with_options(text.as_bytes(), opts.build())
+            .unwrap();
+    }
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    assert_eq!(
+        mem.compression_defaults().unwrap().payloads,
+        CompressionCodec::Lz4
+    );
+    for (uri, encoding) in [
+        ("mv2://default", CanonicalEncoding::Lz4),
+        ("mv2://none", CanonicalEncoding::Plain),
+        ("mv2://zstd", CanonicalEncoding::Zstd),
+    ] {
+        let frame = mem.frame_by_uri(uri).unwrap();
+        assert_eq!(frame.canonical_encoding, encoding, "{uri}");
+        assert_eq!(
+            mem.frame_canonical_payload(frame.id).unwrap(),
+            text.as_bytes(),
+            "{uri}"
+        );
+    }
+}
+
+fn search_request(query: &str) -> SearchRequest {
+    SearchRequest {
+        query: query.to_string(),
+        top_k: 10,
+        snippet_chars: 100,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
+    }
+}
+
+fn tag_search(mem: &mut Memvid, tag: &str) -> Vec<u64> {
+    let response = mem
+        .search(search_request(&format!("quarterly tag:{tag}")))
+        .unwrap();
+    let mut ids: Vec<u64> = response.hits.iter().map(|hit| hit.frame_id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn tags_rename_merge_and_delete_globally() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("tags.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    for (uri, tags) in [
+        ("mv2://a", vec!["projext", "draft"]),
+        ("mv2://b", vec!["project", "wip"]),
+        ("mv2://c", vec!["misc"]),
+    ] {
+        let mut opts = PutOptions::builder().uri(uri).auto_tag(false);
+        for tag in tags {
+            opts = opts.push_tag(tag);
+        }
+        mem.put_bytes_with_options(format!("quarterly planning {uri}").as_bytes(), opts.build())
+            .unwrap();
+    }
+    mem.commit().unwrap();
+    let a = mem.frame_by_uri("mv2://a").unwrap().id;
+    let b = mem.frame_by_uri("mv2://b").unwrap().id;
+    let c = mem.frame_by_uri("mv2://c").unwrap().id;
+
+    mem.set_commit_author(Some("curator".into()), None);
+    assert_eq!(mem.rename_tag("projext", "project").unwrap(), vec![a]);
+    assert_eq!(
+        mem.merge_tags(&["draft", "wip"], "in-progress").unwrap(),
+        vec![a, b]
+    );
+    assert_eq!(mem.delete_tag("misc").unwrap(), vec![c]);
+    assert!(mem.delete_tag("missing").unwrap().is_empty());
+    assert!(matches!(
+        mem.rename_tag("project", " "),
+        Err(MemvidError::InvalidTag { .. })
+    ));
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    assert_eq!(
+        mem.frame_by_id(a).unwrap().tags,
+        vec!["project".to_string(), "in-progress".to_string()]
+    );
+    assert!(mem.frame_by_id(c).unwrap().tags.is_empty());
+    assert_eq!(tag_search(&mut mem, "project"), vec![a, b]);
+    assert!(tag_search(&mut mem, "projext").is_empty());
+    assert!(tag_search(&mut mem, "wip").is_empty());
+
+    let edits = mem.tag_edits().unwrap();
+    assert_eq!(edits.len(), 3);
+    assert_eq!(
+        edits[0].edit,
+        TagEdit::Rename {
+            from: "projext".into(),
+            to: "project".into(),
+        }
+    );
+    assert_eq!(edits[1].frames, vec![a, b]);
+    assert_eq!(edits[2].author.as_deref(), Some("curator"));
+}
+
+#[test]
+fn collections_scope_puts_stats_and_search() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("collections.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.create_collection(
+        Collection::new("planner", "mv2://agents/planner/")
+            .description("Plans written by the planner agent")
+            .defaults(PutOptions::builder().track("plans").auto_tag(false).build()),
+    )
+    .unwrap();
+    assert!(matches!(
+        mem.create_collection(Collection::new("planner", "mv2://elsewhere/")),
+        Err(MemvidError::CollectionExists { .. })
+    ));
+    mem.put_into(
+        "planner",
+        b"roadmap review on monday",
+        Some(PutOptions::builder().uri("roadmap").build()),
+    )
+    .unwrap();
+    mem.put_into("planner", b"roadmap budget on friday", None)
+        .unwrap();
+    mem.put_bytes_with_options(
+        b"roadmap outside any collection",
+        PutOptions::builder().uri("mv2://misc/roadmap").build(),
+    )
+    .unwrap();
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let collection = mem.collection("planner").unwrap();
+    assert_eq!(
+        collection.description.as_deref(),
+        Some("Plans written by the planner agent")
+    );
+    let frame = mem.frame_by_uri("mv2://agents/planner/roadmap").unwrap();
+    assert_eq!(frame.track.as_deref(), Some("plans"));
+
+    let stats = mem.collection_stats("planner").unwrap();
+    assert_eq!(stats.frame_count, 2);
+    assert!(stats.logical_bytes >= 48);
+    assert!(stats.oldest_timestamp <= stats.newest_timestamp);
+
+    let mut request = search_request("roadmap");
+    request.scope = Some("mv2://misc/".into());
+    let hits = mem.search_collection("planner", request).unwrap().hits;
+    assert_eq!(hits.len(), 2);
+    assert!(
+        hits.iter()
+            .all(|hit| hit.uri.starts_with("mv2://agents/planner/"))
+    );
+    assert!(matches!(
+        mem.collection_stats("missing"),
+        Err(MemvidError::CollectionNotFound { .. })
+    ));
+}
+
+struct PutCounter(Arc<Mutex<Vec<u64>>>);
+
+impl MemvidHooks for PutCounter {
+    fn on_put(&mut self, event: &PutEvent) {
+        self.0.lock().unwrap().push(event.payload_bytes);
+    }
+}
+
+#[test]
+fn hooks_observe_puts_and_commits() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("hooks.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+
+    let puts = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&puts);
+    mem.on_put(move |event| seen.lock().unwrap().push(event.clone()));
+    let commits = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&commits);
+    mem.on_commit(move |event| {
+        seen.lock()
+            .unwrap()
+            .push(event.touches_scope("mv2://projects/x/"));
+    });
+    let sizes = Arc::new(Mutex::new(Vec::new()));
+    mem.register_hooks(Box::new(PutCounter(Arc::clone(&sizes))));
+
+    let sequence = mem
+        .put_bytes_with_options(
+            b"design review notes",
+            PutOptions::builder()
+                .uri("mv2://projects/x/review")
+                .push_tag("design")
+                .build(),
+        )
+        .unwrap();
+    mem.commit().unwrap();
+    mem.put_bytes_with_options(
+        b"grocery list",
+        PutOptions::builder().uri("mv2://home/groceries").build(),
+    )
+    .unwrap();
+    mem.commit().unwrap();
+
+    let puts = puts.lock().unwrap();
+    assert_eq!(puts.len(), 2);
+    assert_eq!(puts[0].sequence, sequence);
+    assert_eq!(puts[0].uri.as_deref(), Some("mv2://projects/x/review"));
+    assert_eq!(puts[0].tags, vec!["design".to_string()]);
+    assert_eq!(*sizes.lock().unwrap(), vec![19, 12]);
+    assert_eq!(*commits.lock().unwrap(), vec![true, false]);
+
+    mem.clear_hooks();
+    mem.put_bytes(b"unobserved").unwrap();
+    mem.commit().unwrap();
+    assert_eq!(commits.lock().unwrap().len(), 2);
+}
+
+/// Outgrowing the embedded WAL relocates it past the data instead of shifting every payload,
+/// and records pending at the move are still committed after a reopen.
+#[test]
+fn wal_growth_relocates_without_moving_payloads() {
+    use memvid_core::io::header::HeaderCodec;
+    use memvid_core::{SPEC_VERSION, WAL_EXTENT_VERSION};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let read_header = || {
+        HeaderCodec::read(
+            std::fs::File::options()
+                .read(true)
+                .write(true)
+                .open(&path)
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.put_bytes(b"first harbor note").unwrap();
+    mem.commit().unwrap();
+    let (first, second) = (0, 1);
+    let offset = mem.frame_by_id(first).unwrap().payload_offset;
+    let original = read_header();
+    assert_eq!(original.version, SPEC_VERSION);
+
+    let mut state = 0x9E37_79B9_u32;
+    let noise: Vec<u8> = (0..256 * 1024)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            state.to_le_bytes()[3]
+        })
+        .collect();
+    mem.put_bytes(&noise).unwrap();
+    let relocated = read_header();
+    assert_eq!(relocated.version, WAL_EXTENT_VERSION);
+    assert_eq!(relocated.data_start, original.data_start);
+    assert!(relocated.wal_relocated());
+    assert!(relocated.wal_size > original.wal_size);
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    mem.put_bytes(b"later harbor note").unwrap();
+    mem.commit().unwrap();
+    let frames = mem.frame_count();
+    assert!(frames >= 3);
+    assert_eq!(mem.frame_by_id(first).unwrap().payload_offset, offset);
+    let payload = mem.frame_canonical_payload(second).unwrap();
+    assert!(!payload.is_empty());
+    let text = mem.frame_text_by_id(first).unwrap();
+
+    mem.vacuum().unwrap();
+    drop(mem);
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(mem.frame_count(), frames);
+    assert_eq!(mem.frame_canonical_payload(second).unwrap(), payload);
+    assert_eq!(mem.frame_text_by_id(first).unwrap(), text);
+    assert!(read_header().wal_relocated());
+}
+
+#[test]
+fn ingest_profiles_apply_stored_settings() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("profiles.mv2");
+    let text = "profiled payload compresses well ".repeat(40);
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.set_ingest_profile("media", IngestProfile::media())
+        .unwrap();
+    mem.set_ingest_profile(
+        "code",
+        IngestProfile {
+            compression: Some(CompressionCodec::Zstd { level: 19 }),
+            ..IngestProfile::code()
+        },
+    )
+    .unwrap();
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(
+        mem.ingest_profiles().unwrap().keys().collect::<Vec<_>>(),
+        ["code", "media"]
+    );
+    mem.put_bytes_with_options(
+        text.as_bytes(),
+        PutOptions::builder()
+            .uri("mv2://media")
+            .profile("media")
+            .build(),
+    )
+    .unwrap();
+    // Settings the put chooses itself win over the profile's.
+    mem.put_bytes_with_options(
+        text.as_bytes(),
+        PutOptions::builder()
+            .uri("mv2://code")
+            .profile("code")
+            .compression(CompressionCodec::Lz4)
+            .build(),
+    )
+    .unwrap();
+    mem.put_many([(
+        text.as_bytes().to_vec(),
+        PutOptions {
+            uri: Some("mv2://batch".into()),
+            ..PutOptions::profile("code")
+        },
+    )])
+    .unwrap();
+    assert!(matches!(
+        mem.put_bytes_with_options(text.as_bytes(), PutOptions::profile("missing")),
+        Err(MemvidError::IngestProfileNotFound { .. })
+    ));
+    mem.commit().unwrap();
+
+    for (uri, encoding) in [
+        ("mv2://media", CanonicalEncoding::Plain),
+        ("mv2://code", CanonicalEncoding::Lz4),
+        ("mv2://batch", CanonicalEncoding::Zstd),
+    ] {
+        let frame = mem.frame_by_uri(uri).unwrap();
+        assert_eq!(frame.canonical_encoding, encoding, "{uri}");
+    }
+    assert_eq!(
+        mem.remove_ingest_profile("media").unwrap(),
+        IngestProfile::media()
+    );
+    assert!(matches!(
+        mem.ingest_profile("media"),
+        Err(MemvidError::IngestProfileNotFound { .. })
+    ));
+}
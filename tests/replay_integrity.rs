@@ -46,6 +46,18 @@ fn replay_save_does_not_corrupt_lex_index() {
                 no_sketch: false,
                 acl_context: None,
                 acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: memvid_core::types::SearchMode::Lexical,
+                time_budget_ms: None,
             })
             .unwrap();
         mem.end_session().unwrap();
@@ -73,6 +85,18 @@ fn replay_save_does_not_corrupt_lex_index() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -1,7 +1,10 @@
 //! Integration tests for Memvid search operations.
-//! Tests: search (lex), timeline queries
+//! Tests: search (lex), timeline queries, quantized vector rescoring, int8/binary storage
 
-use memvid_core::{Memvid, PutOptions, SearchRequest, TimelineQuery};
+use memvid_core::{
+    LinkDirection, LinkKind, Memvid, PutOptions, SearchParams, SearchRequest, SketchVariant,
+    SkippedStage, TimelineQuery, VecRescore, VectorCompression,
+};
 use std::num::NonZeroU64;
 use tempfile::TempDir;
 
@@ -78,6 +81,18 @@ fn search_basic_query() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -115,6 +130,18 @@ fn search_multiple_results() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -169,12 +196,93 @@ fn search_respects_top_k() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
     assert_eq!(results.hits.len(), 5, "Should return exactly top_k results");
 }
 
+/// Test that a spent time budget stops candidate evaluation once a page is filled.
+#[test]
+#[cfg(feature = "lex")]
+fn search_time_budget_truncates_candidates() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for i in 0..20 {
+        let opts = PutOptions {
+            uri: Some(format!("mv2://doc{}", i)),
+            search_text: Some(format!("budget document number {}", i)),
+            ..Default::default()
+        };
+        mem.put_bytes_with_options(format!("Content {}", i).as_bytes(), opts)
+            .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let request = SearchRequest {
+        query: "budget".to_string(),
+        top_k: 5,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: Some(60_000),
+    };
+    let complete = mem.search(request.clone()).unwrap();
+    assert!(!complete.partial);
+    assert!(complete.skipped_stages.is_empty());
+    assert_eq!(complete.total_hits, 20);
+
+    let partial = mem
+        .search(SearchRequest {
+            time_budget_ms: Some(0),
+            ..request
+        })
+        .unwrap();
+    assert!(partial.partial);
+    assert_eq!(
+        partial.skipped_stages[0],
+        SkippedStage::Candidates {
+            evaluated: 5,
+            total: 20
+        }
+    );
+    assert_eq!(partial.hits.len(), 5);
+}
+
 /// Test search with scope filter.
 #[test]
 #[cfg(feature = "lex")]
@@ -202,6 +310,18 @@ fn search_with_scope() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -214,6 +334,244 @@ fn search_with_scope() {
     }
 }
 
+/// Test `speaker:` filters on diarized speakers and chat authors.
+#[test]
+#[cfg(feature = "lex")]
+fn search_with_speaker_filter() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.enable_lex().unwrap();
+        for (uri, key, speaker, text) in [
+            (
+                "mv2://meeting#0",
+                "speaker",
+                "Speaker 1",
+                "the budget is approved",
+            ),
+            (
+                "mv2://meeting#1",
+                "speaker",
+                "Speaker 2, Speaker 3",
+                "the budget needs review",
+            ),
+            (
+                "mv2://chat#0",
+                "chat_author",
+                "dana",
+                "budget numbers attached",
+            ),
+        ] {
+            let mut opts = PutOptions {
+                uri: Some(uri.to_string()),
+                ..Default::default()
+            };
+            opts.extra_metadata
+                .insert(key.to_string(), speaker.to_string());
+            mem.put_bytes_with_options(text.as_bytes(), opts).unwrap();
+        }
+        mem.commit().unwrap();
+    }
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let mut uris_for = |query: &str| -> Vec<String> {
+        mem.search(SearchRequest {
+            query: query.to_string(),
+            top_k: 10,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap()
+        .hits
+        .into_iter()
+        .map(|hit| hit.uri)
+        .collect()
+    };
+
+    assert_eq!(
+        uris_for("budget speaker:\"speaker 3\""),
+        vec!["mv2://meeting#1"]
+    );
+    assert_eq!(
+        uris_for("budget speaker:\"Speaker 1\""),
+        vec!["mv2://meeting#0"]
+    );
+    assert_eq!(uris_for("budget speaker:Dana"), vec!["mv2://chat#0"]);
+    assert_eq!(uris_for("speaker:dana"), vec!["mv2://chat#0"]);
+}
+
+/// Test search restricted to a geo radius.
+#[test]
+#[cfg(feature = "lex")]
+fn search_with_geo_filter() {
+    use memvid_core::types::{GEO_LAT_KEY, GEO_LON_KEY, GeoFilter};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.enable_lex().unwrap();
+        for (uri, lat, lon) in [
+            ("mv2://photos/office", "37.7749", "-122.4194"),
+            ("mv2://photos/london", "51.5072", "-0.1276"),
+        ] {
+            let mut opts = PutOptions {
+                uri: Some(uri.to_string()),
+                ..Default::default()
+            };
+            opts.extra_metadata
+                .insert(GEO_LAT_KEY.to_string(), lat.to_string());
+            opts.extra_metadata
+                .insert(GEO_LON_KEY.to_string(), lon.to_string());
+            mem.put_bytes_with_options(b"team photo", opts).unwrap();
+        }
+        mem.put_bytes(b"team photo without location").unwrap();
+        mem.commit().unwrap();
+    }
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let response = mem
+        .search(SearchRequest {
+            query: "team photo".to_string(),
+            top_k: 10,
+            snippet_chars: 200,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: false,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: Some(GeoFilter::within_km(37.7749, -122.4194, 5.0)),
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap();
+    let uris: Vec<String> = response.hits.into_iter().map(|hit| hit.uri).collect();
+    assert_eq!(uris, vec!["mv2://photos/office"]);
+}
+
+/// Test typed metadata filters in the query and in `SearchRequest::filters`.
+#[test]
+#[cfg(feature = "lex")]
+fn search_with_meta_filters() {
+    use memvid_core::types::{MetaFilter, MetaOp};
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+
+    {
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.enable_lex().unwrap();
+        for (uri, priority, status) in [
+            ("mv2://tickets/1", 5, "open"),
+            ("mv2://tickets/2", 10, "done"),
+            ("mv2://tickets/3", 2, "open"),
+            ("mv2://tickets/4", 3, "open"),
+        ] {
+            let opts = PutOptions::builder()
+                .uri(uri)
+                .meta("priority", priority)
+                .meta("status", status)
+                .build();
+            mem.put_bytes_with_options(b"ticket about billing", opts)
+                .unwrap();
+        }
+        mem.commit().unwrap();
+    }
+
+    let mut mem = Memvid::open_read_only(&path).unwrap();
+    let request = |query: &str, filters: Vec<MetaFilter>| SearchRequest {
+        query: query.to_string(),
+        top_k: 10,
+        snippet_chars: 200,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: false,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters,
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
+    };
+    let uris = |response: memvid_core::SearchResponse| {
+        let mut uris: Vec<String> = response.hits.into_iter().map(|hit| hit.uri).collect();
+        uris.sort();
+        uris
+    };
+
+    let response = mem
+        .search(request(
+            r#"billing meta.priority >= 3 AND meta.status != "done""#,
+            Vec::new(),
+        ))
+        .unwrap();
+    assert_eq!(uris(response), ["mv2://tickets/1", "mv2://tickets/4"]);
+
+    let response = mem
+        .search(request(
+            "billing",
+            vec![MetaFilter::new("priority", MetaOp::Lt, 5)],
+        ))
+        .unwrap();
+    assert_eq!(uris(response), ["mv2://tickets/3", "mv2://tickets/4"]);
+
+    let response = mem
+        .search(request("NOT meta.status = open", Vec::new()))
+        .unwrap();
+    assert_eq!(uris(response), ["mv2://tickets/2"]);
+}
+
 /// Test search returns snippets.
 #[test]
 #[cfg(feature = "lex")]
@@ -239,6 +597,18 @@ fn search_returns_snippets() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -274,6 +644,18 @@ fn search_no_results() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -309,6 +691,18 @@ fn search_empty_memory() {
             no_sketch: false,
             acl_context: None,
             acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
         })
         .unwrap();
 
@@ -482,3 +876,1251 @@ fn timeline_respects_limit() {
         "Timeline should return exactly limit entries"
     );
 }
+
+/// Deterministic pseudo-random 384-dim vectors.
+fn pseudo_random_vectors(count: usize) -> Vec<Vec<f32>> {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    (0..count)
+        .map(|_| {
+            (0..384)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1_442_695_040_888_963_407);
+                    ((state >> 40) as f32 / (1u64 << 24) as f32) - 0.5
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn quantized_vector_search_rescores_candidates() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("pq.mv2");
+    let vectors = pseudo_random_vectors(120);
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_vec().unwrap();
+    mem.set_vector_compression(VectorCompression::Pq96);
+    for (i, vector) in vectors.iter().enumerate() {
+        mem.put_with_embedding(format!("doc {i}").as_bytes(), vector.clone())
+            .unwrap();
+    }
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(mem.vector_compression(), &VectorCompression::Pq96);
+    let mut params = SearchParams {
+        top_k: 5,
+        snippet_chars: 0,
+        cursor: None,
+        vec_rescore: VecRescore::Auto,
+    };
+    let hits = mem.search_vec_with_params(&vectors[7], &params).unwrap();
+    assert_eq!(hits.len(), 5);
+    assert_eq!(hits[0].frame_id, 7);
+    assert!(hits[0].distance < 1e-4, "exact distance expected");
+    assert!(hits.windows(2).all(|w| w[0].distance <= w[1].distance));
+
+    params.vec_rescore = VecRescore::Off;
+    let approximate = mem.search_vec_with_params(&vectors[7], &params).unwrap();
+    assert_eq!(approximate.len(), 5);
+
+    assert_eq!(
+        mem.frame_embedding(7).unwrap().as_deref(),
+        Some(&vectors[7][..])
+    );
+
+    // A later rebuild keeps the full-precision vectors of existing frames.
+    let extra = pseudo_random_vectors(121).pop().unwrap();
+    mem.put_with_embedding(b"doc extra", extra).unwrap();
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(mem.vector_count(), 121);
+    params.vec_rescore = VecRescore::Candidates(40);
+    let hits = mem.search_vec_with_params(&vectors[42], &params).unwrap();
+    assert_eq!(hits[0].frame_id, 42);
+    assert!(hits[0].distance < 1e-4);
+}
+
+#[test]
+fn scalar_quantized_vectors_survive_rebuilds() {
+    let vectors: Vec<Vec<f32>> = pseudo_random_vectors(31)
+        .into_iter()
+        .map(|v| v[..64].to_vec())
+        .collect();
+    for compression in [VectorCompression::Int8, VectorCompression::Binary] {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("scalar.mv2");
+
+        let mut mem = Memvid::create(&path).unwrap();
+        mem.enable_vec_with_compression(compression.clone())
+            .unwrap();
+        for (i, vector) in vectors[..30].iter().enumerate() {
+            mem.put_with_embedding(format!("doc {i}").as_bytes(), vector.clone())
+                .unwrap();
+        }
+        mem.commit().unwrap();
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).unwrap();
+        assert_eq!(mem.vector_compression(), &compression);
+        let hits = mem.search_vec(&vectors[11], 3).unwrap();
+        assert_eq!(hits[0].frame_id, 11, "{compression:?}");
+
+        // Adding a vector rebuilds the index from the quantized codes.
+        mem.put_with_embedding(b"doc 30", vectors[30].clone())
+            .unwrap();
+        mem.commit().unwrap();
+        drop(mem);
+
+        let mut mem = Memvid::open(&path).unwrap();
+        assert_eq!(mem.vector_count(), 31);
+        assert_eq!(mem.search_vec(&vectors[11], 1).unwrap()[0].frame_id, 11);
+        assert_eq!(mem.search_vec(&vectors[30], 1).unwrap()[0].frame_id, 30);
+    }
+}
+
+/// Highlights list every matched window of a long chunk, with term offsets.
+#[test]
+#[cfg(feature = "lex")]
+fn search_returns_multiple_highlight_windows() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let filler = "Nothing of note happened during this part of the meeting, which ran long. ";
+    let content = format!(
+        "The budget opened the meeting. {filler}{filler}{filler}Then the budget was revised. \
+         {filler}{filler}{filler}Finally the budget was approved. {filler}{filler}{filler}Later \
+         the budget was published."
+    );
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    mem.put_bytes_with_options(
+        content.as_bytes(),
+        PutOptions::builder().uri("mv2://minutes").build(),
+    )
+    .unwrap();
+    mem.commit().unwrap();
+
+    let request = |highlight_windows| SearchRequest {
+        query: "budget".to_string(),
+        top_k: 10,
+        snippet_chars: 80,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
+    };
+
+    let hits = mem.search(request(0)).unwrap().hits;
+    assert!(hits.iter().all(|hit| hit.highlights.is_empty()));
+
+    let hits = mem.search(request(3)).unwrap().hits;
+    let highlights = &hits[0].highlights;
+    assert_eq!(highlights.len(), 3);
+    for span in highlights {
+        assert_eq!(&content[span.range.0..span.range.1], span.text);
+        for &(start, end) in &span.terms {
+            assert!(start >= span.range.0 && end <= span.range.1);
+            assert!(content[start..end].eq_ignore_ascii_case("budget"));
+        }
+    }
+    assert!(highlights.windows(2).all(|w| w[0].range.1 <= w[1].range.0));
+}
+
+/// Detected languages pick the analyzer: German text is stemmed as German, Japanese is split
+/// into bigrams, and a query hint analyzes the query the same way.
+#[test]
+#[cfg(feature = "lex")]
+fn search_uses_language_specific_analyzers() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for (uri, text) in [
+        (
+            "mv2://de",
+            "Die Mieten in den Häusern der Altstadt sind nicht mehr bezahlbar, und das ist ein Problem.",
+        ),
+        (
+            "mv2://ja",
+            "昨日は東京タワーへ行きました。とても楽しかったです。",
+        ),
+        (
+            "mv2://en",
+            "The houses in the old town are no longer affordable and that is a problem.",
+        ),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder().uri(uri).auto_tag(false).build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let languages: Vec<Option<String>> = (0..3)
+        .map(|id| {
+            mem.frame_by_id(id)
+                .unwrap()
+                .extra_metadata
+                .get(memvid_core::LANGUAGE_KEY)
+                .cloned()
+        })
+        .collect();
+    assert_eq!(
+        languages,
+        vec![Some("de".into()), Some("ja".into()), Some("en".into())]
+    );
+
+    let search = |mem: &mut Memvid, query: &str, language: Option<&str>| {
+        mem.search(SearchRequest {
+            query: query.to_string(),
+            top_k: 10,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: language.map(str::to_string),
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .map(|response| {
+            response
+                .hits
+                .into_iter()
+                .map(|hit| hit.uri)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // "Häusern" was indexed as its German stem, which the English analyzer never produces
+    assert!(search(&mut mem, "Häuser", None).unwrap().is_empty());
+    assert_eq!(
+        search(&mut mem, "Häuser", Some("de")).unwrap(),
+        vec!["mv2://de"]
+    );
+    assert_eq!(search(&mut mem, "東京", None).unwrap(), vec!["mv2://ja"]);
+    assert_eq!(
+        search(&mut mem, "houses", Some("de")).unwrap(),
+        vec!["mv2://en"]
+    );
+    assert!(search(&mut mem, "houses", Some("klingon")).is_err());
+}
+
+/// `similar` ranks frames by the source frame's embedding, or its sketch without vectors.
+#[test]
+fn similar_uses_embeddings_or_sketches() {
+    let dir = TempDir::new().unwrap();
+    let docs = [
+        (
+            "mv2://rust/borrow",
+            "the rust borrow checker rejects aliasing mutable references in safe code",
+            [1.0, 0.0, 0.1],
+        ),
+        (
+            "mv2://rust/lifetimes",
+            "the rust borrow checker rejects aliasing mutable references in unsafe code",
+            [0.9, 0.1, 0.1],
+        ),
+        (
+            "mv2://garden/tomatoes",
+            "tomato seedlings need warm soil and plenty of sunlight in spring",
+            [0.0, 1.0, 0.0],
+        ),
+    ];
+
+    let path = dir.path().join("vectors.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    for (uri, text, embedding) in &docs {
+        mem.put_with_embedding_and_options(
+            text.as_bytes(),
+            embedding.to_vec(),
+            PutOptions::builder().uri(*uri).build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+    let source = mem.frame_by_uri("mv2://rust/borrow").unwrap().id;
+    let hits = mem.similar(source, 5).unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].uri, "mv2://rust/lifetimes");
+    assert!(hits.iter().all(|hit| hit.frame_id != source));
+    assert!(hits[0].score > hits[1].score);
+
+    let path = dir.path().join("sketches.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    for (uri, text, _) in &docs {
+        mem.put_bytes_with_options(text.as_bytes(), PutOptions::builder().uri(*uri).build())
+            .unwrap();
+    }
+    mem.commit().unwrap();
+    mem.build_all_sketches(SketchVariant::Small);
+    let source = mem.frame_by_uri("mv2://rust/borrow").unwrap().id;
+    let hits = mem.similar(source, 1).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].uri, "mv2://rust/lifetimes");
+}
+
+#[test]
+fn analyzer_config_applies_synonyms_stopwords_and_protected_terms() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for (uri, text) in [
+        (
+            "mv2://k8s",
+            "Upgrading our k8s cluster to the next minor version",
+        ),
+        (
+            "mv2://kubernetes",
+            "Kubernetes restarted the failing pods overnight",
+        ),
+        (
+            "mv2://bears",
+            "The panda bears at the zoo eat bamboo all day",
+        ),
+        (
+            "mv2://dataframes",
+            "A pandas dataframe tutorial for analysts",
+        ),
+        ("mv2://report", "Quarterly report for the Globex board"),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder().uri(uri).auto_tag(false).build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let search = |mem: &mut Memvid, query: &str| {
+        let mut uris: Vec<String> = mem
+            .search(SearchRequest {
+                query: query.to_string(),
+                top_k: 10,
+                snippet_chars: 80,
+                uri: None,
+                scope: None,
+                cursor: None,
+                #[cfg(feature = "temporal_track")]
+                temporal: None,
+                as_of_frame: None,
+                as_of_ts: None,
+                no_sketch: true,
+                acl_context: None,
+                acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+                rerank: None,
+                geo: None,
+                filters: Vec::new(),
+                access_boost: false,
+                highlight_windows: 0,
+                language: None,
+                diversify: None,
+                group_by_parent: false,
+                return_parents: false,
+                explain: false,
+                mode: memvid_core::types::SearchMode::Lexical,
+                time_budget_ms: None,
+            })
+            .unwrap()
+            .hits
+            .into_iter()
+            .map(|hit| hit.uri)
+            .collect();
+        uris.sort();
+        uris.dedup();
+        uris
+    };
+
+    assert_eq!(search(&mut mem, "kubernetes"), vec!["mv2://kubernetes"]);
+    assert_eq!(
+        search(&mut mem, "panda"),
+        vec!["mv2://bears", "mv2://dataframes"]
+    );
+    assert!(search(&mut mem, "acme quarterly report").is_empty());
+
+    let config = memvid_core::AnalyzerConfig::new()
+        .with_synonyms(["k8s", "Kubernetes"])
+        .with_stopwords(["acme"])
+        .with_protected_terms(["pandas"]);
+    mem.set_analyzer_config(config.clone()).unwrap();
+
+    assert_eq!(
+        search(&mut mem, "kubernetes"),
+        vec!["mv2://k8s", "mv2://kubernetes"]
+    );
+    assert_eq!(search(&mut mem, "panda"), vec!["mv2://bears"]);
+    assert_eq!(search(&mut mem, "pandas"), vec!["mv2://dataframes"]);
+    assert_eq!(
+        search(&mut mem, "acme quarterly report"),
+        vec!["mv2://report"]
+    );
+
+    mem.commit().unwrap();
+    drop(mem);
+    let mut reopened = Memvid::open(&path).unwrap();
+    assert_eq!(reopened.analyzer_config().unwrap(), config.normalized());
+    assert_eq!(
+        search(&mut reopened, "k8s"),
+        vec!["mv2://k8s", "mv2://kubernetes"]
+    );
+    assert_eq!(search(&mut reopened, "panda"), vec!["mv2://bears"]);
+}
+
+#[test]
+fn suggest_completes_titles_tags_and_frequent_terms() {
+    use memvid_core::SuggestionKind;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    for (uri, title, tag, text) in [
+        (
+            "mv2://deploy",
+            "Deployment Runbook",
+            "devops",
+            "Deploying the service with helm and kubectl",
+        ),
+        (
+            "mv2://rollback",
+            "Rollback Checklist",
+            "devops",
+            "Rolling back a release with kubectl rollout undo",
+        ),
+        (
+            "mv2://dentist",
+            "Dentist Appointment",
+            "personal",
+            "Reminder about the dentist on Tuesday",
+        ),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder()
+                .uri(uri)
+                .title(title)
+                .push_tag(tag)
+                .auto_tag(false)
+                .build(),
+        )
+        .unwrap();
+    }
+    assert!(mem.suggest("de", 10).unwrap().is_empty());
+    mem.commit().unwrap();
+
+    let texts = |mem: &Memvid, prefix: &str| -> Vec<String> {
+        mem.suggest(prefix, 10)
+            .unwrap()
+            .into_iter()
+            .map(|suggestion| suggestion.text)
+            .collect()
+    };
+    assert_eq!(
+        texts(&mem, "De"),
+        vec!["devops", "Dentist Appointment", "Deployment Runbook"]
+    );
+    let kubectl = mem.suggest("kub", 10).unwrap();
+    assert_eq!(kubectl.len(), 1);
+    assert_eq!(kubectl[0].kind, SuggestionKind::Term);
+    assert_eq!(kubectl[0].weight, 2);
+    // Seen in one frame only
+    assert!(texts(&mem, "hel").is_empty());
+
+    let rollback = mem.frame_by_uri("mv2://rollback").unwrap().id;
+    mem.delete_frame(rollback).unwrap();
+    mem.commit().unwrap();
+    assert!(texts(&mem, "kub").is_empty());
+    assert!(texts(&mem, "roll").is_empty());
+
+    drop(mem);
+    let reopened = Memvid::open_read_only(&path).unwrap();
+    assert_eq!(
+        texts(&reopened, "de"),
+        vec!["Dentist Appointment", "Deployment Runbook", "devops"]
+    );
+}
+
+#[cfg(feature = "spelling")]
+#[test]
+fn search_suggests_spelling_corrections_when_few_hits() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for (uri, text) in [
+        (
+            "mv2://upgrade",
+            "Upgrading the kubernetes cluster with helm",
+        ),
+        ("mv2://pods", "Kubernetes restarted the failing pods"),
+        ("mv2://kubelet", "The kubelet logs show image pull errors"),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder().uri(uri).auto_tag(false).build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let search = |mem: &mut Memvid, query: &str| {
+        mem.search(SearchRequest {
+            query: query.to_string(),
+            top_k: 10,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap()
+    };
+
+    let response = search(&mut mem, "kubernets hlem");
+    assert!(response.hits.is_empty());
+    assert_eq!(response.suggestions[0], "kubernetes helm");
+    assert_eq!(
+        search(&mut mem, "title:kubernets").suggestions[0],
+        "title:kubernetes"
+    );
+    // Known words are left alone even when they match little
+    assert!(search(&mut mem, "kubelet").suggestions.is_empty());
+}
+
+#[test]
+fn search_diversifies_and_groups_chunk_hits() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let report: String = (0..40)
+        .map(|section| {
+            format!(
+                "Section {section} of the annual report reviews the budget, the budget variance, \
+                 and budget forecasts for the coming quarter.\n\n"
+            )
+        })
+        .collect();
+    mem.put_bytes_with_options(
+        report.as_bytes(),
+        PutOptions::builder()
+            .uri("mv2://report")
+            .auto_tag(false)
+            .build(),
+    )
+    .unwrap();
+    for (uri, text) in [
+        (
+            "mv2://memo",
+            "Memo: the travel budget is frozen until March",
+        ),
+        (
+            "mv2://minutes",
+            "Minutes: the board approved the hiring budget",
+        ),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder().uri(uri).auto_tag(false).build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let search = |mem: &mut Memvid, diversify: Option<f32>, group_by_parent: bool| {
+        mem.search(SearchRequest {
+            query: "budget".to_string(),
+            top_k: 3,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify,
+            group_by_parent,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap()
+        .hits
+    };
+    let documents = |mem: &Memvid, hits: &[memvid_core::SearchHit]| -> Vec<u64> {
+        hits.iter()
+            .map(|hit| {
+                let frame = mem.frame_by_id(hit.frame_id).unwrap();
+                frame.parent_id.unwrap_or(frame.id)
+            })
+            .collect()
+    };
+
+    let plain = search(&mut mem, None, false);
+    let plain_documents = documents(&mem, &plain);
+    assert_eq!(plain.len(), 3);
+    assert!(plain_documents.iter().all(|id| *id == plain_documents[0]));
+
+    for hits in [
+        search(&mut mem, None, true),
+        search(&mut mem, Some(0.3), false),
+    ] {
+        let mut ids = documents(&mem, &hits);
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].frame_id, plain[0].frame_id);
+        assert_eq!(
+            hits.iter().map(|hit| hit.rank).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+    }
+    // A lambda of 1 is plain relevance order
+    let relevance = search(&mut mem, Some(1.0), false);
+    assert_eq!(documents(&mem, &relevance), plain_documents);
+}
+
+#[test]
+fn search_returns_parent_documents_for_chunk_hits() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    let report: String = (0..40)
+        .map(|section| {
+            format!(
+                "Section {section} of the annual report reviews the budget and the hiring \
+                 plan for the coming quarter.\n\n"
+            )
+        })
+        .collect();
+    mem.put_bytes_with_options(
+        report.as_bytes(),
+        PutOptions::builder()
+            .uri("mv2://report")
+            .title("Annual Report")
+            .auto_tag(false)
+            .build(),
+    )
+    .unwrap();
+    mem.put_bytes_with_options(
+        b"Memo: the travel budget is frozen until March",
+        PutOptions::builder()
+            .uri("mv2://memo")
+            .auto_tag(false)
+            .build(),
+    )
+    .unwrap();
+    mem.commit().unwrap();
+
+    let search = |mem: &mut Memvid, return_parents: bool| {
+        mem.search(SearchRequest {
+            query: "budget".to_string(),
+            top_k: 2,
+            snippet_chars: 80,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 1,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap()
+        .hits
+    };
+
+    // The report is indexed both whole and as page chunks
+    let chunk = search(&mut mem, false)
+        .into_iter()
+        .find(|hit| hit.uri.starts_with("mv2://report#"))
+        .unwrap();
+    let parent_id = mem.frame_by_id(chunk.frame_id).unwrap().parent_id.unwrap();
+
+    let hits = search(&mut mem, true);
+    let uris: Vec<&str> = hits.iter().map(|hit| hit.uri.as_str()).collect();
+    assert_eq!(uris, vec!["mv2://report", "mv2://memo"]);
+    let parent = &hits[0];
+    assert_eq!(parent.frame_id, parent_id);
+    assert_eq!(parent.title.as_deref(), Some("Annual Report"));
+    assert!(parent.score.unwrap() > chunk.score.unwrap());
+    let document = parent.chunk_text.as_deref().unwrap();
+    assert_eq!(document, mem.frame_text_by_id(parent_id).unwrap());
+    assert_eq!(&document[parent.range.0..parent.range.1], parent.text);
+    let highlight = &parent.highlights[0];
+    assert_eq!(
+        &document[highlight.range.0..highlight.range.1],
+        highlight.text
+    );
+}
+
+#[test]
+fn build_context_packs_passages_within_budget() {
+    use memvid_core::ApproxTokenCounter;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for (uri, title, text) in [
+        (
+            "mv2://pricing",
+            "Pricing",
+            "The enterprise plan costs 40 dollars per seat and includes audit logs.",
+        ),
+        (
+            "mv2://plans",
+            "Plans",
+            "Every plan includes email support; the enterprise plan adds a named contact.",
+        ),
+        (
+            "mv2://history",
+            "History",
+            "The enterprise plan was introduced in 2019 after customers asked for single \
+             sign-on, audit logs, custom retention, dedicated support, and invoicing in \
+             several currencies, which took the team most of a year to build and roll out.",
+        ),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder()
+                .uri(uri)
+                .title(title)
+                .auto_tag(false)
+                .build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+
+    let full = mem
+        .build_context("enterprise plan", 10_000, &ApproxTokenCounter)
+        .unwrap();
+    assert_eq!(full.fragments.len(), 3);
+    assert_eq!(full.omitted, 0);
+    for (index, fragment) in full.fragments.iter().enumerate() {
+        let marker = format!("[{}] {}\n", index + 1, fragment.title.as_deref().unwrap());
+        assert_eq!(fragment.rank, index + 1);
+        assert!(full.text.contains(&marker));
+        assert!(full.text.contains(&fragment.text));
+    }
+
+    // Room for two short passages: the long one is skipped, not truncated
+    let words = |text: &str| text.split_whitespace().count();
+    let tight = mem.build_context("enterprise plan", 40, &words).unwrap();
+    assert_eq!(tight.fragments.len(), 2);
+    assert_eq!(tight.omitted, 1);
+    assert!(tight.tokens <= 40);
+    assert!(
+        tight
+            .fragments
+            .iter()
+            .all(|fragment| fragment.uri != "mv2://history")
+    );
+    assert_eq!(tight.text.matches("\n[").count(), 1);
+}
+
+#[test]
+fn search_many_answers_each_request_in_order() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    create_searchable_memory(&path);
+    let mut mem = Memvid::open(&path).unwrap();
+
+    let request = |query: &str| SearchRequest {
+        query: query.to_string(),
+        top_k: 5,
+        snippet_chars: 80,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch: false,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
+    };
+    let queries = ["quantum", "living cells", "quantum", "nonexistentterm"];
+    let responses = mem
+        .search_many(queries.iter().map(|query| request(query)).collect())
+        .unwrap();
+    assert_eq!(responses.len(), queries.len());
+    // The repeated "quantum" request is answered once and counted once.
+    let stats = mem.access_stats().unwrap();
+    for hit in &responses[0].hits {
+        let distinct_returns = [0, 1, 3]
+            .iter()
+            .filter(|&&index| {
+                responses[index]
+                    .hits
+                    .iter()
+                    .any(|other| other.frame_id == hit.frame_id)
+            })
+            .count() as u64;
+        assert_eq!(stats.frames[&hit.frame_id].returned, distinct_returns);
+    }
+    for (query, response) in queries.iter().zip(&responses) {
+        let single = mem.search(request(query)).unwrap();
+        assert_eq!(response.query, *query);
+        assert_eq!(
+            response
+                .hits
+                .iter()
+                .map(|hit| hit.frame_id)
+                .collect::<Vec<_>>(),
+            single
+                .hits
+                .iter()
+                .map(|hit| hit.frame_id)
+                .collect::<Vec<_>>()
+        );
+    }
+    assert!(!responses[0].hits.is_empty());
+    assert!(!responses[1].hits.is_empty());
+    assert!(responses[3].hits.is_empty());
+
+    let empty = request("");
+    assert!(mem.search_many(vec![request("quantum"), empty]).is_err());
+}
+
+#[test]
+fn search_many_with_workers_matches_the_serial_batch() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    create_searchable_memory(&path);
+    let mut mem = Memvid::open(&path).unwrap();
+    // The batch shares one sketch scan across its requests.
+    mem.build_all_sketches(SketchVariant::Small);
+
+    let request = |query: &str, no_sketch: bool| SearchRequest {
+        query: query.to_string(),
+        top_k: 5,
+        snippet_chars: 80,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts: None,
+        no_sketch,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
+    };
+    let batch = || {
+        vec![
+            request("quantum", false),
+            request("mechanics", false),
+            request("living cells", true),
+            request("chemical bonds", false),
+            request("nonexistentterm", false),
+        ]
+    };
+    let frame_ids = |responses: &[memvid_core::SearchResponse]| {
+        responses
+            .iter()
+            .map(|response| {
+                response
+                    .hits
+                    .iter()
+                    .map(|hit| hit.frame_id)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let single = batch()
+        .into_iter()
+        .map(|request| mem.search(request).unwrap())
+        .collect::<Vec<_>>();
+    let serial = mem.search_many(batch()).unwrap();
+    let parallel = mem.search_many_with_workers(batch(), 4).unwrap();
+    assert_eq!(frame_ids(&serial), frame_ids(&single));
+    assert_eq!(frame_ids(&parallel), frame_ids(&serial));
+    assert!(!parallel[1].hits.is_empty());
+    assert!(parallel[4].hits.is_empty());
+}
+
+#[test]
+fn search_explain_reports_filters_scores_and_stages() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.mv2");
+    create_searchable_memory(&path);
+    let mut mem = Memvid::open(&path).unwrap();
+
+    let request = |explain: bool, as_of_ts: Option<i64>| SearchRequest {
+        query: "quantum".to_string(),
+        top_k: 5,
+        snippet_chars: 80,
+        uri: None,
+        scope: None,
+        cursor: None,
+        #[cfg(feature = "temporal_track")]
+        temporal: None,
+        as_of_frame: None,
+        as_of_ts,
+        no_sketch: true,
+        acl_context: None,
+        acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
+    };
+
+    assert!(mem.search(request(false, None)).unwrap().explain.is_none());
+
+    let response = mem.search(request(true, None)).unwrap();
+    let explain = response.explain.as_ref().expect("explain requested");
+    assert_eq!(explain.engine, response.engine);
+    assert!(explain.filters.is_empty());
+    assert_eq!(explain.sketch_candidates, None);
+    assert_eq!(explain.acl_denied, None);
+    let stages: Vec<&str> = explain.stages.iter().map(|s| s.stage.as_str()).collect();
+    assert_eq!(stages, vec!["filters", "retrieve", "post"]);
+    assert!(!response.hits.is_empty());
+    assert_eq!(explain.hits.len(), response.hits.len());
+    for (hit, component) in response.hits.iter().zip(&explain.hits) {
+        assert_eq!(component.frame_id, hit.frame_id);
+        assert_eq!(component.rank, hit.rank);
+        assert_eq!(component.score, hit.score);
+        let composed =
+            component.engine_score.unwrap() * component.importance_boost * component.access_boost;
+        assert!((composed - hit.score.unwrap()).abs() < 1e-4);
+    }
+
+    // A time-travel view before any frame existed filters every candidate out
+    let response = mem.search(request(true, Some(0))).unwrap();
+    assert!(response.hits.is_empty());
+    let explain = response.explain.expect("explain requested");
+    assert_eq!(explain.filters.len(), 1);
+    assert_eq!(explain.filters[0].name, "replay");
+    assert_eq!(explain.filters[0].candidates, 0);
+}
+
+/// Indexes moved into the `.mv2x` sidecar are attached on open, and come back on internalize.
+#[test]
+#[cfg(feature = "lex")]
+fn externalized_indexes_attach_from_sidecar() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("cold.mv2");
+    let sidecar = memvid_core::sidecar_path(&path);
+    let vectors = pseudo_random_vectors(20);
+    let lex_hits = |mem: &mut Memvid, query: &str| -> Vec<u64> {
+        mem.search(SearchRequest {
+            query: query.to_string(),
+            top_k: 5,
+            snippet_chars: 50,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap()
+        .hits
+        .iter()
+        .map(|hit| hit.frame_id)
+        .collect()
+    };
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    mem.enable_vec().unwrap();
+    for (i, vector) in vectors.iter().enumerate() {
+        mem.put_with_embedding(format!("entry{i} field notes").as_bytes(), vector.clone())
+            .unwrap();
+    }
+    mem.commit().unwrap();
+    let embedded_len = std::fs::metadata(&path).unwrap().len();
+
+    assert_eq!(mem.externalize_indexes().unwrap(), sidecar);
+    assert!(mem.indexes_externalized());
+    assert!(sidecar.exists());
+    let primary_len = std::fs::metadata(&path).unwrap().len();
+    assert!(
+        primary_len < embedded_len,
+        "primary should shrink: {primary_len} >= {embedded_len}"
+    );
+    drop(mem);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), primary_len);
+    assert_eq!(
+        Memvid::verify(&path, true).unwrap().overall_status,
+        memvid_core::VerificationStatus::Passed
+    );
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(lex_hits(&mut mem, "entry12"), vec![12]);
+    assert_eq!(mem.search_vec(&vectors[7], 1).unwrap()[0].frame_id, 7);
+    drop(mem);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), primary_len);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    mem.internalize_indexes().unwrap();
+    assert!(!mem.indexes_externalized());
+    assert!(!sidecar.exists());
+    drop(mem);
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(lex_hits(&mut mem, "entry12"), vec![12]);
+    assert_eq!(mem.search_vec(&vectors[7], 1).unwrap()[0].frame_id, 7);
+
+    // A sidecar that no longer matches its checksums is ignored: lexical search rebuilds
+    // from the frames and vector search is empty.
+    mem.externalize_indexes().unwrap();
+    drop(mem);
+    let mut bytes = std::fs::read(&sidecar).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+    std::fs::write(&sidecar, bytes).unwrap();
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(lex_hits(&mut mem, "entry12"), vec![12]);
+    assert!(matches!(
+        mem.search_vec(&vectors[7], 1),
+        Err(memvid_core::MemvidError::VecNotEnabled)
+    ));
+
+    // Writing to the memory rebuilds the indexes inside it again.
+    let extra = pseudo_random_vectors(21).pop().unwrap();
+    mem.put_with_embedding(b"entry20 field notes", extra.clone())
+        .unwrap();
+    mem.commit().unwrap();
+    assert!(!mem.indexes_externalized());
+    drop(mem);
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(lex_hits(&mut mem, "entry20"), vec![20]);
+    assert_eq!(mem.search_vec(&extra, 1).unwrap()[0].frame_id, 20);
+}
+
+#[test]
+#[cfg(feature = "lex")]
+fn frame_links_traverse_and_decorate_hits() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("links.mv2");
+
+    let mut mem = Memvid::create(&path).unwrap();
+    mem.enable_lex().unwrap();
+    for (uri, text) in [
+        ("mv2://mail/1", "Quarterly offsite venue proposal"),
+        ("mv2://mail/2", "Re: the lakeside venue works for everyone"),
+        ("mv2://mail/2-de", "Antwort: der Ort am See passt allen"),
+    ] {
+        mem.put_bytes_with_options(
+            text.as_bytes(),
+            PutOptions::builder().uri(uri).title(uri).build(),
+        )
+        .unwrap();
+    }
+    mem.commit().unwrap();
+    let id = |mem: &Memvid, uri: &str| mem.frame_by_uri(uri).unwrap().id;
+    let (original, reply, translation) = (
+        id(&mem, "mv2://mail/1"),
+        id(&mem, "mv2://mail/2"),
+        id(&mem, "mv2://mail/2-de"),
+    );
+    mem.link_frames(reply, original, LinkKind::ReplyTo).unwrap();
+    mem.link_frames(reply, original, LinkKind::ReplyTo).unwrap();
+    mem.link_frames(translation, reply, LinkKind::Translates)
+        .unwrap();
+    assert!(mem.link_frames(reply, reply, LinkKind::References).is_err());
+    assert!(mem.link_frames(reply, 99, LinkKind::References).is_err());
+    mem.commit().unwrap();
+    drop(mem);
+
+    let mut mem = Memvid::open(&path).unwrap();
+    assert_eq!(mem.frame_links().unwrap().len(), 2);
+    let related = mem.related(reply).unwrap();
+    assert_eq!(
+        related
+            .iter()
+            .map(|related| (related.frame_id, related.kind, related.direction))
+            .collect::<Vec<_>>(),
+        [
+            (original, LinkKind::ReplyTo, LinkDirection::Outgoing),
+            (translation, LinkKind::Translates, LinkDirection::Incoming),
+        ]
+    );
+    assert_eq!(related[0].uri.as_deref(), Some("mv2://mail/1"));
+
+    let response = mem
+        .search(SearchRequest {
+            query: "lakeside".to_string(),
+            top_k: 5,
+            snippet_chars: 50,
+            uri: None,
+            scope: None,
+            cursor: None,
+            #[cfg(feature = "temporal_track")]
+            temporal: None,
+            as_of_frame: None,
+            as_of_ts: None,
+            no_sketch: true,
+            acl_context: None,
+            acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+            rerank: None,
+            geo: None,
+            filters: Vec::new(),
+            access_boost: false,
+            highlight_windows: 0,
+            language: None,
+            diversify: None,
+            group_by_parent: false,
+            return_parents: false,
+            explain: false,
+            mode: memvid_core::types::SearchMode::Lexical,
+            time_budget_ms: None,
+        })
+        .unwrap();
+    let hit = &response.hits[0];
+    assert_eq!(hit.frame_id, reply);
+    assert_eq!(hit.metadata.as_ref().unwrap().related, related);
+
+    mem.delete_frame(translation).unwrap();
+    mem.commit().unwrap();
+    assert_eq!(mem.related(reply).unwrap().len(), 1);
+    assert!(
+        mem.unlink_frames(reply, original, LinkKind::ReplyTo)
+            .unwrap()
+    );
+    assert!(
+        !mem.unlink_frames(reply, original, LinkKind::ReplyTo)
+            .unwrap()
+    );
+    assert!(mem.related(original).unwrap().is_empty());
+}
@@ -279,7 +279,9 @@ fn doctor_maintains_single_file() {
                 rebuild_lex_index: false,
                 rebuild_time_index: true,
                 rebuild_vec_index: false,
+                supersede_duplicates: false,
                 vacuum: false,
+                reclaim_orphans: false,
                 dry_run: false,
                 quiet: true,
             },
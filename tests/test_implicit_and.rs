@@ -52,6 +52,18 @@ fn test_implicit_and_precision() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
     })?;
 
     assert_eq!(
@@ -107,6 +119,18 @@ fn test_explicit_operators_still_work() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
     })?;
 
     assert_eq!(results.hits.len(), 1, "Explicit AND should work");
@@ -126,6 +150,18 @@ fn test_explicit_operators_still_work() -> memvid_core::Result<()> {
         no_sketch: false,
         acl_context: None,
         acl_enforcement_mode: memvid_core::types::AclEnforcementMode::Audit,
+        rerank: None,
+        geo: None,
+        filters: Vec::new(),
+        access_boost: false,
+        highlight_windows: 0,
+        language: None,
+        diversify: None,
+        group_by_parent: false,
+        return_parents: false,
+        explain: false,
+        mode: memvid_core::types::SearchMode::Lexical,
+        time_budget_ms: None,
     })?;
 
     assert!(results.hits.len() >= 2, "Explicit OR should work");